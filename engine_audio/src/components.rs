@@ -0,0 +1,68 @@
+//! Audio components attached to engine_core entities
+
+use engine_core::components::AudioClipHandle;
+
+/// Audio source component - plays a clip from the entity position
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioSource {
+    pub clip: AudioClipHandle,
+    pub volume: f32,
+    pub pitch: f32,
+    pub looping: bool,
+    /// Attenuate by distance to the AudioListener
+    pub spatial: bool,
+    /// Full volume up to this distance
+    pub min_distance: f32,
+    /// Silent beyond this distance
+    pub max_distance: f32,
+    /// Mixer bus the source is routed through
+    pub bus: String,
+    /// Start playing the first time the AudioSystem sees the entity
+    pub play_on_start: bool,
+}
+
+impl Default for AudioSource {
+    fn default() -> Self {
+        Self {
+            clip: AudioClipHandle::invalid(),
+            volume: 1.0,
+            pitch: 1.0,
+            looping: false,
+            spatial: false,
+            min_distance: 1.0,
+            max_distance: 50.0,
+            bus: "sfx".to_string(),
+            play_on_start: false,
+        }
+    }
+}
+
+impl AudioSource {
+    pub fn new(clip: AudioClipHandle) -> Self {
+        Self {
+            clip,
+            ..Default::default()
+        }
+    }
+
+    /// Linear falloff between min_distance and max_distance
+    pub fn attenuation(&self, distance: f32) -> f32 {
+        if !self.spatial || distance <= self.min_distance {
+            return 1.0;
+        }
+        let range = (self.max_distance - self.min_distance).max(f32::EPSILON);
+        (1.0 - (distance - self.min_distance) / range).clamp(0.0, 1.0)
+    }
+}
+
+/// Audio listener component - the "ears" for spatial sources, usually on the camera
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct AudioListener {
+    pub active: bool,
+}
+
+impl AudioListener {
+    pub fn new() -> Self {
+        Self { active: true }
+    }
+}
@@ -0,0 +1,13 @@
+//! Engine Audio - clip playback backed by rodio
+//!
+//! Clips are loaded through the engine_render `AssetManager`; `AudioSource`
+//! and `AudioListener` live in the engine_core world and `AudioSystem` plays
+//! them through the `AudioMixer` buses.
+
+pub mod components;
+pub mod mixer;
+pub mod system;
+
+pub use components::*;
+pub use mixer::*;
+pub use system::*;
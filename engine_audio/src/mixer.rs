@@ -0,0 +1,56 @@
+//! Audio mixer resource - master and per-bus volumes
+
+use std::collections::HashMap;
+
+/// Mixer resource - every source volume is scaled by its bus and the master volume
+#[derive(Debug, Clone)]
+pub struct AudioMixer {
+    pub master_volume: f32,
+    pub muted: bool,
+    buses: HashMap<String, f32>,
+}
+
+impl Default for AudioMixer {
+    fn default() -> Self {
+        let mut buses = HashMap::new();
+        buses.insert("music".to_string(), 1.0);
+        buses.insert("sfx".to_string(), 1.0);
+        buses.insert("ui".to_string(), 1.0);
+        Self {
+            master_volume: 1.0,
+            muted: false,
+            buses,
+        }
+    }
+}
+
+impl AudioMixer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bus volume; unknown buses play at full volume
+    pub fn bus_volume(&self, bus: &str) -> f32 {
+        self.buses.get(bus).copied().unwrap_or(1.0)
+    }
+
+    /// Set (or create) a bus volume
+    pub fn set_bus_volume(&mut self, bus: &str, volume: f32) {
+        self.buses.insert(bus.to_string(), volume.max(0.0));
+    }
+
+    /// Bus names, sorted
+    pub fn buses(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.buses.keys().map(String::as_str).collect();
+        names.sort();
+        names
+    }
+
+    /// Final gain for a source volume routed through `bus`
+    pub fn effective_volume(&self, bus: &str, volume: f32) -> f32 {
+        if self.muted {
+            return 0.0;
+        }
+        (volume * self.bus_volume(bus) * self.master_volume).max(0.0)
+    }
+}
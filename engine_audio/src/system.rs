@@ -0,0 +1,202 @@
+//! Audio system - owns the output device and one voice per playing entity
+
+use std::collections::{HashMap, HashSet};
+use std::io::Cursor;
+use std::time::Duration;
+
+use engine_core::components::Transform;
+use engine_core::ecs::EngineWorld;
+use engine_render::{AssetManager, AudioClipData};
+use glam::Vec3;
+use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink};
+
+use crate::components::{AudioListener, AudioSource};
+use crate::mixer::AudioMixer;
+
+struct Voice {
+    sink: Sink,
+    looping: bool,
+}
+
+/// Audio system - plays AudioSource components through the default output device
+pub struct AudioSystem {
+    _stream: OutputStream,
+    handle: OutputStreamHandle,
+    voices: HashMap<hecs::Entity, Voice>,
+    gains: HashMap<hecs::Entity, f32>,
+    pending_play: HashSet<hecs::Entity>,
+    started: HashSet<hecs::Entity>,
+    preview: Option<Sink>,
+    errors: Vec<String>,
+}
+
+impl AudioSystem {
+    /// Open the default output device
+    pub fn new() -> Result<Self, String> {
+        let (stream, handle) =
+            OutputStream::try_default().map_err(|e| format!("no audio output: {e}"))?;
+        Ok(Self {
+            _stream: stream,
+            handle,
+            voices: HashMap::new(),
+            gains: HashMap::new(),
+            pending_play: HashSet::new(),
+            started: HashSet::new(),
+            preview: None,
+            errors: Vec::new(),
+        })
+    }
+
+    /// Errors raised during the last update (missing clips, decode failures)
+    pub fn errors(&self) -> &[String] {
+        &self.errors
+    }
+
+    /// Start (or restart) the entity's AudioSource on the next update
+    pub fn play(&mut self, entity: hecs::Entity) {
+        self.pending_play.insert(entity);
+    }
+
+    /// Stop the entity's voice immediately
+    pub fn stop(&mut self, entity: hecs::Entity) {
+        self.pending_play.remove(&entity);
+        if let Some(voice) = self.voices.remove(&entity) {
+            voice.sink.stop();
+        }
+    }
+
+    /// Runtime gain on top of AudioSource::volume (1.0 = unchanged)
+    pub fn set_volume(&mut self, entity: hecs::Entity, volume: f32) {
+        self.gains.insert(entity, volume.max(0.0));
+    }
+
+    pub fn is_playing(&self, entity: hecs::Entity) -> bool {
+        self.voices.get(&entity).is_some_and(|v| !v.sink.empty())
+    }
+
+    /// Stop every voice and forget play_on_start state
+    pub fn stop_all(&mut self) {
+        for (_, voice) in self.voices.drain() {
+            voice.sink.stop();
+        }
+        self.gains.clear();
+        self.pending_play.clear();
+        self.started.clear();
+        self.stop_preview();
+    }
+
+    /// Play a clip outside the world (editor preview, UI sounds); replaces the previous preview
+    pub fn play_clip(&mut self, clip: &AudioClipData, volume: f32) -> Result<(), String> {
+        self.stop_preview();
+        let sink = self.create_sink(clip, false)?;
+        sink.set_volume(volume.max(0.0));
+        self.preview = Some(sink);
+        Ok(())
+    }
+
+    pub fn stop_preview(&mut self) {
+        if let Some(sink) = self.preview.take() {
+            sink.stop();
+        }
+    }
+
+    pub fn is_preview_playing(&self) -> bool {
+        self.preview.as_ref().is_some_and(|s| !s.empty())
+    }
+
+    /// How far the preview clip has played
+    pub fn preview_position(&self) -> Option<Duration> {
+        self.preview
+            .as_ref()
+            .filter(|s| !s.empty())
+            .map(|s| s.get_pos())
+    }
+
+    /// Start requested sources, drop finished voices and refresh volumes
+    pub fn update(&mut self, world: &EngineWorld, assets: &AssetManager, mixer: &AudioMixer) {
+        self.errors.clear();
+
+        let listener = world
+            .world()
+            .query::<(&AudioListener, &Transform)>()
+            .iter()
+            .find(|(listener, _)| listener.active)
+            .map(|(_, transform)| transform.position);
+
+        let sources: Vec<(hecs::Entity, AudioSource, Vec3)> = world
+            .world()
+            .query::<(hecs::Entity, &AudioSource, Option<&Transform>)>()
+            .iter()
+            .map(|(entity, source, transform)| {
+                let position = transform.map(|t| t.position).unwrap_or(Vec3::ZERO);
+                (entity, source.clone(), position)
+            })
+            .collect();
+
+        let live: HashSet<hecs::Entity> = sources.iter().map(|(e, _, _)| *e).collect();
+        let stale: Vec<hecs::Entity> = self
+            .voices
+            .keys()
+            .filter(|e| !live.contains(*e))
+            .copied()
+            .collect();
+        for entity in stale {
+            self.stop(entity);
+        }
+        self.gains.retain(|e, _| live.contains(e));
+        self.started.retain(|e| live.contains(e));
+        self.pending_play.retain(|e| live.contains(e));
+
+        for (entity, source, position) in &sources {
+            let first_frame = self.started.insert(*entity);
+            let requested = self.pending_play.remove(entity);
+            if requested || (first_frame && source.play_on_start) {
+                if let Some(voice) = self.voices.remove(entity) {
+                    voice.sink.stop();
+                }
+                match self.start_voice(source, assets) {
+                    Ok(voice) => {
+                        self.voices.insert(*entity, voice);
+                    }
+                    Err(e) => self.errors.push(e),
+                }
+            }
+
+            let Some(voice) = self.voices.get(entity) else {
+                continue;
+            };
+            let distance = listener.map(|l| l.distance(*position)).unwrap_or(0.0);
+            let gain = self.gains.get(entity).copied().unwrap_or(1.0);
+            voice.sink.set_volume(
+                mixer.effective_volume(&source.bus, source.volume * gain)
+                    * source.attenuation(distance),
+            );
+            voice.sink.set_speed(source.pitch.max(0.01));
+        }
+
+        self.voices.retain(|_, v| v.looping || !v.sink.empty());
+    }
+
+    fn start_voice(&self, source: &AudioSource, assets: &AssetManager) -> Result<Voice, String> {
+        let clip = assets
+            .get_audio_clip(source.clip)
+            .ok_or_else(|| format!("invalid audio clip handle {}", source.clip.id))?;
+        Ok(Voice {
+            sink: self.create_sink(clip, source.looping)?,
+            looping: source.looping,
+        })
+    }
+
+    fn create_sink(&self, clip: &AudioClipData, looping: bool) -> Result<Sink, String> {
+        let sink = Sink::try_new(&self.handle).map_err(|e| format!("{}: {e}", clip.name))?;
+        let data = Cursor::new(clip.bytes.clone());
+        if looping {
+            let decoder = Decoder::new_looped(data).map_err(|e| format!("{}: {e}", clip.name))?;
+            sink.append(decoder);
+        } else {
+            let decoder = Decoder::new(data).map_err(|e| format!("{}: {e}", clip.name))?;
+            sink.append(decoder);
+        }
+        Ok(sink)
+    }
+}
@@ -0,0 +1,93 @@
+//! Deferred entity operations
+//!
+//! Systems that iterate a query cannot spawn, despawn or change the archetype
+//! of entities in the same world without invalidating the iterator. `Commands`
+//! records those operations and applies them later at a sync point.
+
+use hecs::{Bundle, CommandBuffer, Component, DynamicBundle, EntityBuilder};
+
+use crate::ecs::SceneHandle;
+
+/// Queue of spawn/despawn/insert/remove operations applied by `EngineWorld::apply_commands`
+#[derive(Default)]
+pub struct Commands {
+    buffer: CommandBuffer,
+    spawned: u64,
+    queued: usize,
+}
+
+impl Commands {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spawn an entity with components (tuple)
+    pub fn spawn(&mut self, components: impl DynamicBundle) {
+        self.buffer.spawn(components);
+        self.spawned += 1;
+        self.queued += 1;
+    }
+
+    /// Spawn an entity owned by a scene. The scene is checked when the queue
+    /// is applied: if it was unloaded by then the entity spawns unowned
+    pub fn spawn_in_scene(&mut self, scene: SceneHandle, components: impl DynamicBundle) {
+        let mut builder = EntityBuilder::new();
+        builder.add_bundle(components);
+        if scene.is_valid() {
+            builder.add(scene);
+        }
+        self.spawn(builder.build());
+    }
+
+    pub fn despawn(&mut self, entity: hecs::Entity) {
+        self.buffer.despawn(entity);
+        self.queued += 1;
+    }
+
+    /// Add or replace components on an existing entity
+    pub fn insert(&mut self, entity: hecs::Entity, components: impl DynamicBundle) {
+        self.buffer.insert(entity, components);
+        self.queued += 1;
+    }
+
+    pub fn insert_one(&mut self, entity: hecs::Entity, component: impl Component) {
+        self.buffer.insert_one(entity, component);
+        self.queued += 1;
+    }
+
+    /// Remove a bundle of components; ignored if the entity lacks any of them
+    pub fn remove<T: Bundle + 'static>(&mut self, entity: hecs::Entity) {
+        self.buffer.remove::<T>(entity);
+        self.queued += 1;
+    }
+
+    pub fn remove_one<T: Component>(&mut self, entity: hecs::Entity) {
+        self.buffer.remove_one::<T>(entity);
+        self.queued += 1;
+    }
+
+    /// Number of queued operations
+    pub fn len(&self) -> usize {
+        self.queued
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queued == 0
+    }
+
+    /// Drop every queued operation without applying it
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.spawned = 0;
+        self.queued = 0;
+    }
+
+    /// Apply the queue to a hecs world, returning how many entities were spawned
+    pub(crate) fn run_on(&mut self, world: &mut hecs::World) -> u64 {
+        self.buffer.run_on(world);
+        let spawned = self.spawned;
+        self.spawned = 0;
+        self.queued = 0;
+        spawned
+    }
+}
@@ -1,6 +1,8 @@
 //! Core components for the ECS-based engine
 
-use glam::{Mat4, Quat, Vec3};
+use glam::{Mat4, Quat, Vec2, Vec3};
+
+use crate::refs::EntityRef;
 
 /// Transform component - position, rotation, and scale of an entity
 #[derive(Debug, Clone, Copy)]
@@ -76,6 +78,71 @@ impl Transform {
     }
 }
 
+/// Stable entity identifier - survives snapshots, save/load and scene reloads,
+/// unlike `hecs::Entity` which is only valid inside one world
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Guid {
+    pub value: u128,
+}
+
+impl Guid {
+    /// Random (v4) identifier
+    pub fn generate() -> Self {
+        Self {
+            value: uuid::Uuid::new_v4().as_u128(),
+        }
+    }
+
+    pub fn nil() -> Self {
+        Self { value: 0 }
+    }
+
+    pub fn is_nil(&self) -> bool {
+        self.value == 0
+    }
+
+    /// Parse the hyphenated form written by `Display`
+    pub fn parse(text: &str) -> Option<Self> {
+        uuid::Uuid::parse_str(text.trim())
+            .ok()
+            .map(|u| Self { value: u.as_u128() })
+    }
+}
+
+impl std::fmt::Display for Guid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", uuid::Uuid::from_u128(self.value).hyphenated())
+    }
+}
+
+/// Display name of an entity (hierarchy label, scene files)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Name(pub String);
+
+impl Name {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Mesh file an entity is drawn with, relative to the project's Assets folder (the
+/// same key the file has in the asset bundles). Whoever loads the scene (editor
+/// viewport, player) turns it into a `MeshRenderer`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MeshAsset {
+    pub path: String,
+}
+
+impl MeshAsset {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
 /// Handle to a mesh asset - used instead of direct mesh storage
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct MeshHandle {
@@ -120,6 +187,116 @@ impl Default for MaterialHandle {
     }
 }
 
+/// Handle to a texture asset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TextureHandle {
+    pub id: u64,
+}
+
+impl TextureHandle {
+    pub fn invalid() -> Self {
+        Self { id: 0 }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.id != 0
+    }
+}
+
+impl Default for TextureHandle {
+    fn default() -> Self {
+        Self::invalid()
+    }
+}
+
+/// Handle to an environment map (skybox) asset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EnvironmentHandle {
+    pub id: u64,
+}
+
+impl EnvironmentHandle {
+    pub fn invalid() -> Self {
+        Self { id: 0 }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.id != 0
+    }
+}
+
+impl Default for EnvironmentHandle {
+    fn default() -> Self {
+        Self::invalid()
+    }
+}
+
+/// Handle to an audio clip asset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AudioClipHandle {
+    pub id: u64,
+}
+
+impl AudioClipHandle {
+    pub fn invalid() -> Self {
+        Self { id: 0 }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.id != 0
+    }
+}
+
+impl Default for AudioClipHandle {
+    fn default() -> Self {
+        Self::invalid()
+    }
+}
+
+/// Handle to a font asset (SDF glyph atlas)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FontHandle {
+    pub id: u64,
+}
+
+impl FontHandle {
+    pub fn invalid() -> Self {
+        Self { id: 0 }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.id != 0
+    }
+}
+
+impl Default for FontHandle {
+    fn default() -> Self {
+        Self::invalid()
+    }
+}
+
+/// Handle to a user material shader (WGSL under `Assets/Shaders`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MaterialShaderHandle {
+    pub id: u64,
+}
+
+impl MaterialShaderHandle {
+    pub fn invalid() -> Self {
+        Self { id: 0 }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.id != 0
+    }
+}
+
+impl Default for MaterialShaderHandle {
+    fn default() -> Self {
+        Self::invalid()
+    }
+}
+
 /// Mesh renderer component - references mesh and material assets
 #[derive(Debug, Clone, Copy)]
 pub struct MeshRenderer {
@@ -153,9 +330,628 @@ impl MeshRenderer {
     }
 }
 
-/// Tag component for camera entities
+/// Maximum number of levels in a `LodGroup`
+pub const MAX_LOD_LEVELS: usize = 4;
+
+/// One level of a `LodGroup`, drawn while the camera is closer than `distance`
 #[derive(Debug, Clone, Copy, Default)]
-pub struct Camera;
+pub struct LodLevel {
+    pub mesh: MeshHandle,
+    pub distance: f32,
+}
+
+/// Level of detail: replaces the `MeshRenderer` mesh by camera distance.
+/// Levels are kept sorted by distance; past the last one the entity is culled
+/// when `cull` is set, otherwise the last level stays visible.
+#[derive(Debug, Clone, Copy)]
+pub struct LodGroup {
+    levels: [LodLevel; MAX_LOD_LEVELS],
+    len: usize,
+    /// Multiplies every switch distance (lower = coarser levels sooner)
+    pub bias: f32,
+    pub cull: bool,
+}
+
+impl Default for LodGroup {
+    fn default() -> Self {
+        Self {
+            levels: [LodLevel::default(); MAX_LOD_LEVELS],
+            len: 0,
+            bias: 1.0,
+            cull: false,
+        }
+    }
+}
+
+impl LodGroup {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a level, keeping the levels sorted. Returns false when the group is full.
+    pub fn push(&mut self, mesh: MeshHandle, distance: f32) -> bool {
+        if self.len == MAX_LOD_LEVELS {
+            return false;
+        }
+        let at = self.levels[..self.len]
+            .iter()
+            .position(|level| level.distance > distance)
+            .unwrap_or(self.len);
+        self.levels.copy_within(at..self.len, at + 1);
+        self.levels[at] = LodLevel { mesh, distance };
+        self.len += 1;
+        true
+    }
+
+    pub fn levels(&self) -> &[LodLevel] {
+        &self.levels[..self.len]
+    }
+
+    /// Level to draw at `distance` from the camera, None when culled or empty
+    pub fn select(&self, distance: f32) -> Option<usize> {
+        select_lod_level(
+            self.levels().iter().map(|level| level.distance),
+            self.bias,
+            self.cull,
+            distance,
+        )
+    }
+}
+
+/// Index of the first switch distance (scaled by `bias`) not exceeded by `distance`.
+/// Past the last one: None when `cull` is set, otherwise the last index.
+pub fn select_lod_level(
+    distances: impl IntoIterator<Item = f32>,
+    bias: f32,
+    cull: bool,
+    distance: f32,
+) -> Option<usize> {
+    let bias = bias.max(0.01);
+    let mut last = None;
+    for (i, switch) in distances.into_iter().enumerate() {
+        if distance <= switch * bias {
+            return Some(i);
+        }
+        last = Some(i);
+    }
+    if cull { None } else { last }
+}
+
+/// Camera component - the entity's transform is the eye, looking down -Z.
+/// Cameras render in ascending `order`, so among the cameras that draw to the
+/// screen the highest order ends on top (the Game view shows it).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Camera {
+    /// Vertical field of view in degrees (perspective only)
+    pub fov: f32,
+    /// Orthographic projection instead of perspective (2D, level blocking)
+    pub orthographic: bool,
+    /// World units shown vertically by an orthographic camera
+    pub orthographic_size: f32,
+    pub near: f32,
+    pub far: f32,
+    /// Normalized screen rect (x, y, width, height) from the top-left corner,
+    /// (0, 0, 1, 1) = full screen
+    pub viewport: [f32; 4],
+    pub order: i32,
+    /// Render into this texture instead of the screen
+    pub target_texture: Option<TextureHandle>,
+    /// Replaces the environment fog while this camera renders
+    pub fog: Option<FogSettings>,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self {
+            fov: 60.0,
+            orthographic: false,
+            orthographic_size: 10.0,
+            near: 0.1,
+            far: 1000.0,
+            viewport: [0.0, 0.0, 1.0, 1.0],
+            order: 0,
+            target_texture: None,
+            fog: None,
+        }
+    }
+}
+
+impl Camera {
+    pub fn new(fov: f32, near: f32, far: f32) -> Self {
+        Self {
+            fov,
+            near,
+            far,
+            ..Default::default()
+        }
+    }
+
+    /// Draws to the screen (no target texture)
+    pub fn is_screen_camera(&self) -> bool {
+        self.target_texture.is_none()
+    }
+
+    /// Viewport rect clamped to the screen, (x, y, width, height) normalized
+    pub fn viewport_rect(&self) -> [f32; 4] {
+        let x = self.viewport[0].clamp(0.0, 1.0);
+        let y = self.viewport[1].clamp(0.0, 1.0);
+        [
+            x,
+            y,
+            self.viewport[2].clamp(0.0, 1.0 - x),
+            self.viewport[3].clamp(0.0, 1.0 - y),
+        ]
+    }
+
+    /// View matrix of a camera placed at `transform` (scale is ignored)
+    pub fn view_matrix(transform: &Transform) -> Mat4 {
+        Mat4::look_to_rh(transform.position, transform.forward(), transform.up())
+    }
+
+    /// Orthographic camera of `size` world units vertically
+    pub fn orthographic(size: f32, near: f32, far: f32) -> Self {
+        Self {
+            orthographic: true,
+            orthographic_size: size,
+            near,
+            far,
+            ..Default::default()
+        }
+    }
+
+    pub fn projection_matrix(&self, aspect_ratio: f32) -> Mat4 {
+        let near = self.near.max(1e-4);
+        let far = self.far.max(near + 1e-3);
+        let aspect_ratio = aspect_ratio.max(1e-3);
+        if self.orthographic {
+            let half_height = self.orthographic_size.max(1e-3) * 0.5;
+            let half_width = half_height * aspect_ratio;
+            return Mat4::orthographic_rh_gl(
+                -half_width,
+                half_width,
+                -half_height,
+                half_height,
+                near,
+                far,
+            );
+        }
+        Mat4::perspective_rh_gl(
+            self.fov.clamp(1.0, 179.0).to_radians(),
+            aspect_ratio,
+            near,
+            far,
+        )
+    }
+}
+
+/// 2D sprite component - a textured quad in the entity's local XY plane.
+/// Sprites are drawn back to front by `sorting_layer`, then `order_in_layer`,
+/// then distance to the camera.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sprite {
+    pub texture: TextureHandle,
+    /// Quad size in world units, before the transform scale
+    pub size: Vec2,
+    /// Normalized pivot, (0.5, 0.5) = center
+    pub pivot: Vec2,
+    /// Texture region (u0, v0, u1, v1), for atlases and sprite sheets
+    pub uv_rect: [f32; 4],
+    pub color: [f32; 4],
+    pub flip_x: bool,
+    pub flip_y: bool,
+    /// Index into `SortingLayers`
+    pub sorting_layer: u16,
+    pub order_in_layer: i32,
+}
+
+impl Default for Sprite {
+    fn default() -> Self {
+        Self {
+            texture: TextureHandle::invalid(),
+            size: Vec2::ONE,
+            pivot: Vec2::splat(0.5),
+            uv_rect: [0.0, 0.0, 1.0, 1.0],
+            color: [1.0; 4],
+            flip_x: false,
+            flip_y: false,
+            sorting_layer: 0,
+            order_in_layer: 0,
+        }
+    }
+}
+
+impl Sprite {
+    pub fn new(texture: TextureHandle, size: Vec2) -> Self {
+        Self {
+            texture,
+            size,
+            ..Default::default()
+        }
+    }
+
+    /// Sprite sized from its texture: `pixels_per_unit` texels span one world unit
+    pub fn from_pixels(
+        texture: TextureHandle,
+        width: u32,
+        height: u32,
+        pixels_per_unit: f32,
+    ) -> Self {
+        let ppu = pixels_per_unit.max(1e-3);
+        Self::new(texture, Vec2::new(width as f32 / ppu, height as f32 / ppu))
+    }
+}
+
+/// Texture file an entity's `Sprite` is drawn with, relative to the project's
+/// Assets folder like `MeshAsset`. Whoever loads the scene (player) sets the
+/// sprite's `TextureHandle` from it.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SpriteTexture {
+    pub path: String,
+}
+
+impl SpriteTexture {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+/// Named sorting layers for sprites, back to front; `Sprite::sorting_layer`
+/// is an index into this list
+#[derive(Debug, Clone, PartialEq)]
+pub struct SortingLayers {
+    names: Vec<String>,
+}
+
+impl Default for SortingLayers {
+    fn default() -> Self {
+        Self {
+            names: vec!["Default".to_string()],
+        }
+    }
+}
+
+impl SortingLayers {
+    /// Append a layer (drawn in front of the existing ones) and return its index;
+    /// an existing name returns its current index
+    pub fn add(&mut self, name: &str) -> u16 {
+        if let Some(index) = self.index_of(name) {
+            return index;
+        }
+        self.names.push(name.to_string());
+        (self.names.len() - 1) as u16
+    }
+
+    pub fn index_of(&self, name: &str) -> Option<u16> {
+        self.names
+            .iter()
+            .position(|n| n == name)
+            .map(|index| index as u16)
+    }
+
+    pub fn name(&self, index: u16) -> Option<&str> {
+        self.names.get(index as usize).map(String::as_str)
+    }
+
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+}
+
+/// Horizontal alignment of each line of a `Text3D`, relative to the entity origin
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextAlign {
+    Left,
+    #[default]
+    Center,
+    Right,
+}
+
+/// In-world text component (names, damage numbers), rendered as SDF glyph quads.
+/// Billboard text always faces the camera; otherwise it lies in the entity's
+/// local XY plane like a `Sprite`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Text3D {
+    pub text: String,
+    pub font: FontHandle,
+    /// Line height in world units, before the transform scale
+    pub size: f32,
+    pub color: [f32; 4],
+    pub align: TextAlign,
+    pub billboard: bool,
+    /// Offset from the entity origin, in world units (e.g. above a character's head)
+    pub offset: Vec3,
+}
+
+impl Default for Text3D {
+    fn default() -> Self {
+        Self {
+            text: String::new(),
+            font: FontHandle::invalid(),
+            size: 0.5,
+            color: [1.0; 4],
+            align: TextAlign::Center,
+            billboard: false,
+            offset: Vec3::ZERO,
+        }
+    }
+}
+
+impl Text3D {
+    pub fn new(text: impl Into<String>, font: FontHandle, size: f32) -> Self {
+        Self {
+            text: text.into(),
+            font,
+            size,
+            ..Default::default()
+        }
+    }
+
+    /// Camera-facing text (`TextBillboard`)
+    pub fn billboard(text: impl Into<String>, font: FontHandle, size: f32) -> Self {
+        Self {
+            billboard: true,
+            ..Self::new(text, font, size)
+        }
+    }
+}
+
+/// Font file an entity's `Text3D` or `UiText` is drawn with, relative to the
+/// project's Assets folder like `SpriteTexture`. Whoever loads the scene
+/// (player) sets the text's `FontHandle` from it.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TextFont {
+    pub path: String,
+}
+
+impl TextFont {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+/// Screen-space text of the runtime UI (scores, menus, dialogue), drawn over
+/// the scene with the same SDF font atlas as `Text3D`. It needs no `Transform`:
+/// `anchor` picks a point of the screen from (0, 0) top-left to (1, 1)
+/// bottom-right and `offset` moves the text from there in pixels (y down).
+#[derive(Debug, Clone, PartialEq)]
+pub struct UiText {
+    pub text: String,
+    pub font: FontHandle,
+    /// Line height in pixels
+    pub size: f32,
+    pub color: [f32; 4],
+    pub align: TextAlign,
+    pub anchor: Vec2,
+    pub offset: Vec2,
+    pub visible: bool,
+}
+
+impl Default for UiText {
+    fn default() -> Self {
+        Self {
+            text: String::new(),
+            font: FontHandle::invalid(),
+            size: 24.0,
+            color: [1.0; 4],
+            align: TextAlign::Left,
+            anchor: Vec2::ZERO,
+            offset: Vec2::ZERO,
+            visible: true,
+        }
+    }
+}
+
+impl UiText {
+    pub fn new(text: impl Into<String>, font: FontHandle, size: f32) -> Self {
+        Self {
+            text: text.into(),
+            font,
+            size,
+            ..Default::default()
+        }
+    }
+
+    /// Same text placed at another point of the screen
+    pub fn anchored(self, anchor: Vec2, offset: Vec2) -> Self {
+        Self {
+            anchor,
+            offset,
+            ..self
+        }
+    }
+}
+
+/// Makes a camera entity follow another entity
+#[derive(Debug, Clone, Copy)]
+pub struct CameraFollow {
+    pub target: EntityRef,
+    /// Camera position relative to the target
+    pub offset: Vec3,
+    /// 0 = snap to the target, closer to 1 = slower follow
+    pub smoothing: f32,
+}
+
+impl Default for CameraFollow {
+    fn default() -> Self {
+        Self {
+            target: EntityRef::default(),
+            offset: Vec3::new(0.0, 3.0, 6.0),
+            smoothing: 0.0,
+        }
+    }
+}
+
+impl CameraFollow {
+    pub fn new(target: EntityRef) -> Self {
+        Self {
+            target,
+            ..Default::default()
+        }
+    }
+}
+
+/// Tonemapping operator applied by the post-process chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Tonemapping {
+    /// Clamp only
+    None,
+    Reinhard,
+    #[default]
+    Aces,
+}
+
+/// Post-process chain of a camera, applied to the HDR scene in this order:
+/// auto exposure, bloom, exposure + tonemapping, color grading LUT, vignette
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PostProcessSettings {
+    pub enabled: bool,
+    /// Linear multiplier applied before tonemapping (compensation when auto exposure is on)
+    pub exposure: f32,
+    /// Adapt exposure to the average scene luminance
+    pub auto_exposure: bool,
+    /// Range of the exposure chosen by auto exposure
+    pub auto_exposure_min: f32,
+    pub auto_exposure_max: f32,
+    /// Adaptation speed (higher = faster eye adaptation)
+    pub auto_exposure_speed: f32,
+    pub tonemapping: Tonemapping,
+    pub bloom_enabled: bool,
+    /// Luminance above which pixels contribute to bloom
+    pub bloom_threshold: f32,
+    pub bloom_intensity: f32,
+    /// 0 = no vignette, 1 = black corners
+    pub vignette_intensity: f32,
+    /// Width of the vignette falloff
+    pub vignette_smoothness: f32,
+    /// Color grading LUT (horizontal strip of `size` slices of `size`×`size`)
+    pub color_lut: Option<TextureHandle>,
+    /// Blend between the graded and ungraded image
+    pub lut_contribution: f32,
+}
+
+impl Default for PostProcessSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            exposure: 1.0,
+            auto_exposure: false,
+            auto_exposure_min: 0.1,
+            auto_exposure_max: 8.0,
+            auto_exposure_speed: 1.5,
+            tonemapping: Tonemapping::default(),
+            bloom_enabled: true,
+            bloom_threshold: 1.0,
+            bloom_intensity: 0.5,
+            vignette_intensity: 0.0,
+            vignette_smoothness: 0.5,
+            color_lut: None,
+            lut_contribution: 1.0,
+        }
+    }
+}
+
+/// Distance falloff of `FogSettings`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FogMode {
+    /// No distance fog (height fog still applies)
+    #[default]
+    Off,
+    /// Ramps from `start` to `end`
+    Linear,
+    /// `1 - exp(-density * d)`
+    Exponential,
+    /// `1 - exp(-(density * d)^2)`, clearer near the camera
+    ExponentialSquared,
+}
+
+impl FogMode {
+    pub const ALL: [FogMode; 4] = [
+        FogMode::Off,
+        FogMode::Linear,
+        FogMode::Exponential,
+        FogMode::ExponentialSquared,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FogMode::Off => "Off",
+            FogMode::Linear => "Linear",
+            FogMode::Exponential => "Exp",
+            FogMode::ExponentialSquared => "Exp2",
+        }
+    }
+}
+
+/// Distance and height fog, blended over lit surfaces in the forward pass.
+/// Set per scene in the environment; a `Camera` can override it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FogSettings {
+    pub mode: FogMode,
+    pub color: Vec3,
+    /// Linear mode: distance where fog starts and where it is full
+    pub start: f32,
+    pub end: f32,
+    /// Exponential modes: fog per world unit
+    pub density: f32,
+    /// Height fog density at `height`; 0 = no height fog
+    pub height_density: f32,
+    /// World height where the height fog has `height_density`
+    pub height: f32,
+    /// How fast the height fog thins out above `height` (per world unit)
+    pub height_falloff: f32,
+    /// Upper bound of the fog amount, so far silhouettes stay visible
+    pub max_opacity: f32,
+}
+
+impl Default for FogSettings {
+    fn default() -> Self {
+        Self {
+            mode: FogMode::Off,
+            color: Vec3::new(0.62, 0.70, 0.78),
+            start: 20.0,
+            end: 150.0,
+            density: 0.02,
+            height_density: 0.0,
+            height: 0.0,
+            height_falloff: 0.2,
+            max_opacity: 1.0,
+        }
+    }
+}
+
+impl FogSettings {
+    pub fn is_enabled(&self) -> bool {
+        (self.mode != FogMode::Off || self.height_density > 0.0) && self.max_opacity > 0.0
+    }
+
+    /// Fog amount (0..1) over a point `distance` away, seen from `camera_height`
+    /// at `point_height`; mirrors `fog_amount` in the lit shader
+    pub fn amount(&self, distance: f32, camera_height: f32, point_height: f32) -> f32 {
+        let distance = distance.max(0.0);
+        let mut amount = match self.mode {
+            FogMode::Off => 0.0,
+            FogMode::Linear => {
+                ((distance - self.start) / (self.end - self.start).max(1e-4)).clamp(0.0, 1.0)
+            }
+            FogMode::Exponential => 1.0 - (-self.density * distance).exp(),
+            FogMode::ExponentialSquared => 1.0 - (-(self.density * distance).powi(2)).exp(),
+        };
+        if self.height_density > 0.0 {
+            // Density integrated along the ray through an exponential height profile
+            let falloff = self.height_falloff.max(1e-4);
+            let rise = falloff * (point_height - camera_height);
+            let base =
+                self.height_density * (-falloff * (camera_height - self.height)).min(80.0).exp();
+            let along = if rise.abs() > 1e-4 {
+                (1.0 - (-rise).exp()) / rise
+            } else {
+                1.0
+            };
+            amount = 1.0 - (1.0 - amount) * (-base * distance * along).exp();
+        }
+        amount.min(self.max_opacity.clamp(0.0, 1.0))
+    }
+}
 
 /// Tag component for light entities
 #[derive(Debug, Clone, Copy)]
@@ -186,6 +982,91 @@ impl Light {
     }
 }
 
+/// Directional light (sun); shines along the entity's `Transform::forward`
+#[derive(Debug, Clone, Copy)]
+pub struct DirectionalLight {
+    pub color: Vec3,
+    pub intensity: f32,
+}
+
+impl Default for DirectionalLight {
+    fn default() -> Self {
+        Self {
+            color: Vec3::ONE,
+            intensity: 1.0,
+        }
+    }
+}
+
+impl DirectionalLight {
+    pub fn new(color: Vec3, intensity: f32) -> Self {
+        Self { color, intensity }
+    }
+}
+
+/// Omnidirectional light placed at the entity's position, fading out at `range`
+#[derive(Debug, Clone, Copy)]
+pub struct PointLight {
+    pub color: Vec3,
+    pub intensity: f32,
+    pub range: f32,
+}
+
+impl Default for PointLight {
+    fn default() -> Self {
+        Self {
+            color: Vec3::ONE,
+            intensity: 1.0,
+            range: 10.0,
+        }
+    }
+}
+
+impl PointLight {
+    pub fn new(color: Vec3, intensity: f32, range: f32) -> Self {
+        Self {
+            color,
+            intensity,
+            range,
+        }
+    }
+}
+
+/// Cone light along the entity's `Transform::forward`.
+/// Angles are half-angles in radians; the falloff runs from `inner_angle` to `outer_angle`.
+#[derive(Debug, Clone, Copy)]
+pub struct SpotLight {
+    pub color: Vec3,
+    pub intensity: f32,
+    pub range: f32,
+    pub inner_angle: f32,
+    pub outer_angle: f32,
+}
+
+impl Default for SpotLight {
+    fn default() -> Self {
+        Self {
+            color: Vec3::ONE,
+            intensity: 1.0,
+            range: 10.0,
+            inner_angle: 20.0_f32.to_radians(),
+            outer_angle: 30.0_f32.to_radians(),
+        }
+    }
+}
+
+impl SpotLight {
+    pub fn new(color: Vec3, intensity: f32, range: f32, outer_angle: f32) -> Self {
+        Self {
+            color,
+            intensity,
+            range,
+            inner_angle: outer_angle * 0.8,
+            outer_angle,
+        }
+    }
+}
+
 /// Tag component for player-controlled entities
 #[derive(Debug, Clone, Copy, Default)]
 pub struct Player;
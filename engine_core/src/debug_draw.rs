@@ -0,0 +1,124 @@
+//! Immediate-mode debug drawing - lines, wire boxes, spheres, arrows and text
+//!
+//! Systems and scripts push shapes into a `DebugDraw` queue while they run;
+//! the viewport draws whatever is queued as an overlay on top of the scene.
+//! A shape lives for a single frame unless it is given a duration, so callers
+//! simply re-submit every frame what they want to keep on screen.
+
+use glam::{Quat, Vec3};
+
+/// Default color for shapes submitted without one (opaque green)
+pub const DEBUG_DEFAULT_COLOR: [f32; 4] = [0.2, 1.0, 0.4, 1.0];
+
+/// Shape kinds understood by the debug overlay (world space)
+#[derive(Debug, Clone, PartialEq)]
+pub enum DebugShape {
+    Line {
+        start: Vec3,
+        end: Vec3,
+    },
+    WireBox {
+        center: Vec3,
+        half_extents: Vec3,
+        rotation: Quat,
+    },
+    Sphere {
+        center: Vec3,
+        radius: f32,
+    },
+    Arrow {
+        start: Vec3,
+        end: Vec3,
+    },
+    /// Screen-facing label anchored at a world position
+    Text {
+        position: Vec3,
+        text: String,
+    },
+}
+
+/// A queued shape with its color (linear RGBA) and remaining lifetime
+#[derive(Debug, Clone, PartialEq)]
+pub struct DebugCommand {
+    pub shape: DebugShape,
+    pub color: [f32; 4],
+    /// Seconds left on screen; zero means "this frame only"
+    pub remaining: f32,
+}
+
+/// Debug draw queue - fill it during the frame, render `commands()`, then `tick`
+#[derive(Debug, Clone, Default)]
+pub struct DebugDraw {
+    commands: Vec<DebugCommand>,
+}
+
+impl DebugDraw {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a shape that stays visible for `duration` seconds
+    pub fn push(&mut self, shape: DebugShape, color: [f32; 4], duration: f32) {
+        self.commands.push(DebugCommand {
+            shape,
+            color,
+            remaining: duration.max(0.0),
+        });
+    }
+
+    pub fn line(&mut self, start: Vec3, end: Vec3, color: [f32; 4]) {
+        self.push(DebugShape::Line { start, end }, color, 0.0);
+    }
+
+    pub fn wire_box(&mut self, center: Vec3, half_extents: Vec3, rotation: Quat, color: [f32; 4]) {
+        self.push(
+            DebugShape::WireBox {
+                center,
+                half_extents,
+                rotation,
+            },
+            color,
+            0.0,
+        );
+    }
+
+    pub fn sphere(&mut self, center: Vec3, radius: f32, color: [f32; 4]) {
+        self.push(DebugShape::Sphere { center, radius }, color, 0.0);
+    }
+
+    pub fn arrow(&mut self, start: Vec3, end: Vec3, color: [f32; 4]) {
+        self.push(DebugShape::Arrow { start, end }, color, 0.0);
+    }
+
+    pub fn text(&mut self, position: Vec3, text: impl Into<String>, color: [f32; 4]) {
+        self.push(
+            DebugShape::Text {
+                position,
+                text: text.into(),
+            },
+            color,
+            0.0,
+        );
+    }
+
+    /// Shapes currently queued, in submission order
+    pub fn commands(&self) -> &[DebugCommand] {
+        &self.commands
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Age every shape by `dt`; one-frame shapes and expired ones are dropped
+    pub fn tick(&mut self, dt: f32) {
+        self.commands.retain_mut(|command| {
+            command.remaining -= dt.max(0.0);
+            command.remaining > 0.0
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.commands.clear();
+    }
+}
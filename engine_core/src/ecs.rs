@@ -1,8 +1,18 @@
 //! ECS World wrapper for the game engine
 
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::fmt;
+
 use hecs::World as HecsWorld;
 
+use crate::commands::Commands;
 use crate::components::*;
+use crate::refs::{EntityRef, MapEntityRefs, Parent};
+use crate::scripting::ScriptComponent;
+use crate::spatial::Bounds;
+use crate::systems::Cullable;
+use crate::timer::Timer;
 
 /// Spawned entity handle
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -20,11 +30,210 @@ impl EntityHandle {
     }
 }
 
+/// Handle to a scene loaded into the world.
+/// Also attached to entities as a component to mark their owning scene.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SceneHandle {
+    pub id: u64,
+}
+
+impl SceneHandle {
+    pub fn invalid() -> Self {
+        Self { id: 0 }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.id != 0
+    }
+}
+
+impl Default for SceneHandle {
+    fn default() -> Self {
+        Self::invalid()
+    }
+}
+
+/// Scene currently loaded into the world
+#[derive(Debug, Clone)]
+pub struct LoadedScene {
+    pub handle: SceneHandle,
+    pub name: String,
+}
+
+/// Clones one component type from a live entity into a snapshot builder
+type CloneFn = fn(&hecs::EntityRef<'_>, &mut hecs::EntityBuilderClone);
+/// Points the `EntityRef`s of one component type at remapped Guids
+type RemapFn = fn(&HecsWorld, hecs::Entity, &HashMap<Guid, Guid>);
+
+fn clone_component<T: hecs::Component + Clone>(
+    entity: &hecs::EntityRef<'_>,
+    builder: &mut hecs::EntityBuilderClone,
+) {
+    if let Some(component) = entity.get::<&T>() {
+        builder.add((*component).clone());
+    }
+}
+
+fn remap_component<T: hecs::Component + MapEntityRefs>(
+    world: &HecsWorld,
+    entity: hecs::Entity,
+    guids: &HashMap<Guid, Guid>,
+) {
+    if let Ok(mut component) = world.get::<&mut T>(entity) {
+        component.map_entity_refs(&mut |target| {
+            if let Some(guid) = guids.get(&target.guid) {
+                *target = EntityRef::new(*guid);
+            }
+        });
+    }
+}
+
+#[derive(Clone, Copy)]
+struct RegisteredComponent {
+    id: TypeId,
+    clone: CloneFn,
+    remap: Option<RemapFn>,
+}
+
+/// Component types captured by world snapshots.
+/// `Guid` and `SceneHandle` are tracked by the snapshot itself and never registered.
+#[derive(Clone)]
+pub struct ComponentRegistry {
+    types: Vec<RegisteredComponent>,
+}
+
+impl Default for ComponentRegistry {
+    fn default() -> Self {
+        let mut registry = Self { types: Vec::new() };
+        registry.register::<Name>();
+        registry.register::<MeshAsset>();
+        registry.register_with_refs::<Parent>();
+        registry.register::<Transform>();
+        registry.register::<MeshRenderer>();
+        registry.register::<LodGroup>();
+        registry.register::<Camera>();
+        registry.register_with_refs::<CameraFollow>();
+        registry.register::<PostProcessSettings>();
+        registry.register::<FogSettings>();
+        registry.register::<Light>();
+        registry.register::<DirectionalLight>();
+        registry.register::<PointLight>();
+        registry.register::<SpotLight>();
+        registry.register::<Player>();
+        registry.register::<Sprite>();
+        registry.register::<SpriteTexture>();
+        registry.register::<Text3D>();
+        registry.register::<UiText>();
+        registry.register::<TextFont>();
+        registry.register_with_refs::<EntityRef>();
+        registry.register::<Timer>();
+        registry.register::<ScriptComponent>();
+        registry.register::<Bounds>();
+        registry.register::<Cullable>();
+        registry
+    }
+}
+
+impl ComponentRegistry {
+    /// Register a component type; registering the same type twice is a no-op
+    pub fn register<T: hecs::Component + Clone>(&mut self) {
+        self.insert(RegisteredComponent {
+            id: TypeId::of::<T>(),
+            clone: clone_component::<T>,
+            remap: None,
+        });
+    }
+
+    /// Register a component type whose `EntityRef`s follow the Guid remap of
+    /// `EngineWorld::load_scene_additive`
+    pub fn register_with_refs<T: hecs::Component + Clone + MapEntityRefs>(&mut self) {
+        self.insert(RegisteredComponent {
+            id: TypeId::of::<T>(),
+            clone: clone_component::<T>,
+            remap: Some(remap_component::<T>),
+        });
+    }
+
+    fn insert(&mut self, component: RegisteredComponent) {
+        if !self.contains(component.id) {
+            self.types.push(component);
+        }
+    }
+
+    pub fn contains(&self, id: TypeId) -> bool {
+        self.types.iter().any(|c| c.id == id)
+    }
+
+    fn remap_refs(&self, world: &HecsWorld, entity: hecs::Entity, guids: &HashMap<Guid, Guid>) {
+        for remap in self.types.iter().filter_map(|c| c.remap) {
+            remap(world, entity, guids);
+        }
+    }
+
+    fn capture(&self, entity: hecs::EntityRef<'_>) -> EntitySnapshot {
+        let mut builder = hecs::EntityBuilderClone::new();
+        for component in &self.types {
+            (component.clone)(&entity, &mut builder);
+        }
+        EntitySnapshot {
+            entity: entity.entity(),
+            guid: entity.get::<&Guid>().map(|c| *c),
+            scene: entity.get::<&SceneHandle>().map(|c| *c),
+            components: builder.build(),
+        }
+    }
+}
+
+/// Captured components of a single entity
+#[derive(Clone)]
+struct EntitySnapshot {
+    /// hecs id at capture time, reused by `EngineWorld::restore`
+    entity: hecs::Entity,
+    guid: Option<Guid>,
+    scene: Option<SceneHandle>,
+    components: hecs::BuiltEntityClone,
+}
+
+impl fmt::Debug for EntitySnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EntitySnapshot")
+            .field("entity", &self.entity)
+            .field("guid", &self.guid)
+            .field("scene", &self.scene)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Copy of the world state, taken when entering play mode and restored on stop.
+/// Every type of the world's `ComponentRegistry` is captured; unregistered
+/// components are dropped.
+#[derive(Debug, Clone, Default)]
+pub struct WorldSnapshot {
+    entities: Vec<EntitySnapshot>,
+    scenes: Vec<LoadedScene>,
+    next_entity_id: u64,
+    next_scene_id: u64,
+}
+
+impl WorldSnapshot {
+    /// Number of entities in the snapshot
+    pub fn entity_count(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+}
+
 /// ECS World wrapper with convenience methods
 #[derive(Default)]
 pub struct EngineWorld {
     pub world: HecsWorld,
     next_entity_id: u64,
+    scenes: Vec<LoadedScene>,
+    next_scene_id: u64,
+    components: ComponentRegistry,
 }
 
 impl EngineWorld {
@@ -34,7 +243,25 @@ impl EngineWorld {
 
     /// Spawn an entity with components (tuple)
     pub fn spawn(&mut self, components: impl hecs::DynamicBundle) -> EntityHandle {
-        let _entity = self.world.spawn(components);
+        let entity = self.world.spawn(components);
+        self.ensure_guid(entity);
+        self.next_entity_id += 1;
+        EntityHandle {
+            id: self.next_entity_id,
+        }
+    }
+
+    /// Spawn an entity owned by a loaded scene
+    pub fn spawn_in_scene(
+        &mut self,
+        scene: SceneHandle,
+        components: impl hecs::DynamicBundle,
+    ) -> EntityHandle {
+        let entity = self.world.spawn(components);
+        self.ensure_guid(entity);
+        if self.is_scene_loaded(scene) {
+            let _ = self.world.insert_one(entity, scene);
+        }
         self.next_entity_id += 1;
         EntityHandle {
             id: self.next_entity_id,
@@ -86,6 +313,201 @@ impl EngineWorld {
         false
     }
 
+    /// Make snapshots capture a component type defined outside engine_core
+    pub fn register_component<T: hecs::Component + Clone>(&mut self) {
+        self.components.register::<T>();
+    }
+
+    /// Component types captured by `snapshot`
+    pub fn components(&self) -> &ComponentRegistry {
+        &self.components
+    }
+
+    /// Copy the current state of every entity
+    pub fn snapshot(&self) -> WorldSnapshot {
+        WorldSnapshot {
+            entities: self
+                .world
+                .iter()
+                .map(|e| self.components.capture(e))
+                .collect(),
+            scenes: self.scenes.clone(),
+            next_entity_id: self.next_entity_id,
+            next_scene_id: self.next_scene_id,
+        }
+    }
+
+    /// Replace the world contents with a previously taken snapshot.
+    /// Entities come back under the same hecs ids, so maps keyed by
+    /// `hecs::Entity` stay valid across a snapshot/restore round-trip.
+    pub fn restore(&mut self, snapshot: &WorldSnapshot) {
+        self.world.clear();
+        for entity in &snapshot.entities {
+            self.world.spawn_at(entity.entity, &entity.components);
+            self.insert_identity(entity.entity, entity.guid, entity.scene);
+        }
+        self.scenes = snapshot.scenes.clone();
+        self.next_entity_id = snapshot.next_entity_id;
+        self.next_scene_id = snapshot.next_scene_id;
+    }
+
+    /// Register an empty scene (e.g. a persistent "manager" scene)
+    pub fn create_scene(&mut self, name: &str) -> SceneHandle {
+        self.next_scene_id += 1;
+        let handle = SceneHandle {
+            id: self.next_scene_id,
+        };
+        self.scenes.push(LoadedScene {
+            handle,
+            name: name.to_string(),
+        });
+        handle
+    }
+
+    /// Load a scene on top of the current world contents.
+    /// Every entity gets a fresh Guid so the same scene can be loaded more than
+    /// once; `EntityRef`s between entities of the scene follow the new Guids,
+    /// references to entities outside it are kept.
+    pub fn load_scene_additive(&mut self, name: &str, scene: &WorldSnapshot) -> SceneHandle {
+        let handle = self.create_scene(name);
+        let mut guids = HashMap::new();
+        let mut spawned = Vec::with_capacity(scene.entities.len());
+        for entity in &scene.entities {
+            let guid = Guid::generate();
+            if let Some(old) = entity.guid {
+                guids.insert(old, guid);
+            }
+            let e = self.world.spawn(&entity.components);
+            self.insert_identity(e, Some(guid), Some(handle));
+            self.next_entity_id += 1;
+            spawned.push(e);
+        }
+        for entity in spawned {
+            self.components.remap_refs(&self.world, entity, &guids);
+        }
+        handle
+    }
+
+    /// Despawn every entity owned by the scene and forget it
+    pub fn unload_scene(&mut self, scene: SceneHandle) -> bool {
+        let Some(idx) = self.scenes.iter().position(|s| s.handle == scene) else {
+            return false;
+        };
+        self.scenes.remove(idx);
+        for entity in self.scene_entities(scene) {
+            let _ = self.world.despawn(entity);
+        }
+        true
+    }
+
+    /// Copy only the entities owned by a scene (without the ownership marker)
+    pub fn snapshot_scene(&self, scene: SceneHandle) -> WorldSnapshot {
+        let entities = self
+            .world
+            .iter()
+            .filter(|e| e.get::<&SceneHandle>().is_some_and(|s| *s == scene))
+            .map(|e| EntitySnapshot {
+                scene: None,
+                ..self.components.capture(e)
+            })
+            .collect();
+        WorldSnapshot {
+            entities,
+            ..Default::default()
+        }
+    }
+
+    /// Entities owned by a scene
+    pub fn scene_entities(&self, scene: SceneHandle) -> Vec<hecs::Entity> {
+        self.world
+            .query::<(hecs::Entity, &SceneHandle)>()
+            .iter()
+            .filter(|(_, owner)| **owner == scene)
+            .map(|(entity, _)| entity)
+            .collect()
+    }
+
+    /// Owning scene of an entity, if any
+    pub fn scene_of(&self, entity: hecs::Entity) -> Option<SceneHandle> {
+        self.world.get::<&SceneHandle>(entity).ok().map(|s| *s)
+    }
+
+    pub fn is_scene_loaded(&self, scene: SceneHandle) -> bool {
+        self.scenes.iter().any(|s| s.handle == scene)
+    }
+
+    /// Scenes currently loaded, in load order
+    pub fn scenes(&self) -> &[LoadedScene] {
+        &self.scenes
+    }
+
+    /// Sync point - apply every queued command in order, leaving the queue empty.
+    /// Operations on entities despawned in the meantime are skipped, and
+    /// deferred spawns tagged with a scene unloaded since then are left
+    /// unowned, like `spawn_in_scene` does for scenes that are not loaded.
+    pub fn apply_commands(&mut self, commands: &mut Commands) {
+        self.next_entity_id += commands.run_on(&mut self.world);
+        let orphaned: Vec<hecs::Entity> = self
+            .world
+            .query::<(hecs::Entity, &SceneHandle)>()
+            .iter()
+            .filter(|(_, scene)| !self.is_scene_loaded(**scene))
+            .map(|(entity, _)| entity)
+            .collect();
+        for entity in orphaned {
+            let _ = self.world.remove_one::<SceneHandle>(entity);
+        }
+        let missing: Vec<hecs::Entity> = self
+            .world
+            .query::<hecs::Entity>()
+            .without::<&Guid>()
+            .iter()
+            .collect();
+        for entity in missing {
+            self.ensure_guid(entity);
+        }
+    }
+
+    /// Put back the identity components a snapshot tracks outside the registry
+    fn insert_identity(
+        &mut self,
+        entity: hecs::Entity,
+        guid: Option<Guid>,
+        scene: Option<SceneHandle>,
+    ) {
+        if let Some(guid) = guid {
+            let _ = self.world.insert_one(entity, guid);
+        }
+        if let Some(scene) = scene {
+            let _ = self.world.insert_one(entity, scene);
+        }
+    }
+
+    /// Give the entity a fresh Guid unless it already has one
+    fn ensure_guid(&mut self, entity: hecs::Entity) {
+        let has_guid = self.world.entity(entity).is_ok_and(|e| e.has::<Guid>());
+        if !has_guid {
+            let _ = self.world.insert_one(entity, Guid::generate());
+        }
+    }
+
+    /// Stable identifier of an entity
+    pub fn guid_of(&self, entity: hecs::Entity) -> Option<Guid> {
+        self.world.get::<&Guid>(entity).ok().map(|g| *g)
+    }
+
+    /// Resolve a Guid to the entity currently carrying it
+    pub fn find_by_guid(&self, guid: Guid) -> Option<hecs::Entity> {
+        if guid.is_nil() {
+            return None;
+        }
+        self.world
+            .query::<(hecs::Entity, &Guid)>()
+            .iter()
+            .find(|(_, g)| **g == guid)
+            .map(|(entity, _)| entity)
+    }
+
     /// Get underlying hecs world reference
     pub fn world(&self) -> &HecsWorld {
         &self.world
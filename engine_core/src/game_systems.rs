@@ -0,0 +1,58 @@
+//! Per-frame game systems shared by the editor's Play mode and the player
+//!
+//! Both call `GameSystems::update` once per frame with the scaled delta,
+//! right after the scripts, so the game state, `Timer` components and
+//! `Tasks` advance the same way in the editor and in an exported game.
+
+use crate::ecs::EngineWorld;
+use crate::state::{GameState, StateScheduler, States};
+use crate::timer::{Tasks, TimerSystem};
+
+/// Game state machine, timers and scheduled work of a running game
+pub struct GameSystems {
+    /// Starts in `GameState::Playing`; scripts change it with `set_game_state`
+    pub states: States<GameState>,
+    pub scheduler: StateScheduler<GameState>,
+    pub tasks: Tasks,
+}
+
+impl Default for GameSystems {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameSystems {
+    /// `Timer` components and `Tasks` only advance while `Playing`, so any
+    /// other state (`Paused`, `Menu`...) freezes them
+    pub fn new() -> Self {
+        let mut scheduler = StateScheduler::new();
+        scheduler.on_update(GameState::Playing, |world, _, dt| {
+            TimerSystem::update(world, dt);
+        });
+        Self {
+            states: States::new(GameState::Playing),
+            scheduler,
+            tasks: Tasks::new(),
+        }
+    }
+
+    pub fn state(&self) -> GameState {
+        self.states.current()
+    }
+
+    /// Whether gameplay (timers, tasks, physics) advances this frame
+    pub fn is_playing(&self) -> bool {
+        self.states.is(GameState::Playing)
+    }
+
+    /// Apply the queued state transition and run the systems of the current
+    /// state
+    pub fn update(&mut self, world: &mut EngineWorld, dt: f32) {
+        crate::profile_function!();
+        self.scheduler.run(world, &mut self.states, dt);
+        if self.is_playing() {
+            self.tasks.update(world, dt);
+        }
+    }
+}
@@ -0,0 +1,261 @@
+//! Input resource - keyboard, mouse and gamepad state for one frame
+//!
+//! The platform layer (editor or runtime) feeds raw events into `Input` once
+//! per frame; game systems only read from it. Keys are identified by static
+//! names ("W", "Space", "Up", ...), so the core stays independent of the
+//! windowing crate and lookups don't allocate.
+
+use std::borrow::Borrow;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use glam::Vec2;
+
+/// Pressed / just pressed / just released state for a set of buttons
+#[derive(Debug, Clone)]
+pub struct ButtonInput<T: Eq + Hash + Clone> {
+    down: HashSet<T>,
+    just_pressed: HashSet<T>,
+    just_released: HashSet<T>,
+}
+
+impl<T: Eq + Hash + Clone> Default for ButtonInput<T> {
+    fn default() -> Self {
+        Self {
+            down: HashSet::new(),
+            just_pressed: HashSet::new(),
+            just_released: HashSet::new(),
+        }
+    }
+}
+
+impl<T: Eq + Hash + Clone> ButtonInput<T> {
+    pub fn press(&mut self, button: T) {
+        if self.down.insert(button.clone()) {
+            self.just_pressed.insert(button);
+        }
+    }
+
+    pub fn release(&mut self, button: T) {
+        if self.down.remove(&button) {
+            self.just_released.insert(button);
+        }
+    }
+
+    /// Replace the held set, deriving just_pressed/just_released from the difference
+    pub fn set_down(&mut self, buttons: impl IntoIterator<Item = T>) {
+        let next: HashSet<T> = buttons.into_iter().collect();
+        for button in self.down.difference(&next) {
+            self.just_released.insert(button.clone());
+        }
+        for button in next.difference(&self.down) {
+            self.just_pressed.insert(button.clone());
+        }
+        self.down = next;
+    }
+
+    pub fn pressed<Q: Eq + Hash + ?Sized>(&self, button: &Q) -> bool
+    where
+        T: Borrow<Q>,
+    {
+        self.down.contains(button)
+    }
+
+    pub fn just_pressed<Q: Eq + Hash + ?Sized>(&self, button: &Q) -> bool
+    where
+        T: Borrow<Q>,
+    {
+        self.just_pressed.contains(button)
+    }
+
+    pub fn just_released<Q: Eq + Hash + ?Sized>(&self, button: &Q) -> bool
+    where
+        T: Borrow<Q>,
+    {
+        self.just_released.contains(button)
+    }
+
+    pub fn get_pressed(&self) -> impl Iterator<Item = &T> {
+        self.down.iter()
+    }
+
+    /// Forget per-frame transitions, keep held buttons
+    pub fn clear_frame(&mut self) {
+        self.just_pressed.clear();
+        self.just_released.clear();
+    }
+
+    /// Release everything (e.g. when the window loses focus)
+    pub fn reset(&mut self) {
+        self.down.clear();
+        self.clear_frame();
+    }
+}
+
+/// Mouse button
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+    Back,
+    Forward,
+}
+
+/// Gamepad button, named by position (Xbox A = South)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadButton {
+    South,
+    East,
+    West,
+    North,
+    LeftBumper,
+    RightBumper,
+    Select,
+    Start,
+    LeftStick,
+    RightStick,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+/// Gamepad analog axis, in [-1, 1] (triggers in [0, 1])
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+    LeftTrigger,
+    RightTrigger,
+}
+
+/// Virtual axis - combines keys and a gamepad axis into one value in [-1, 1]
+#[derive(Debug, Clone, Default)]
+pub struct AxisBinding {
+    pub positive: Vec<&'static str>,
+    pub negative: Vec<&'static str>,
+    pub gamepad: Option<GamepadAxis>,
+}
+
+impl AxisBinding {
+    pub fn keys(positive: &[&'static str], negative: &[&'static str]) -> Self {
+        Self {
+            positive: positive.to_vec(),
+            negative: negative.to_vec(),
+            gamepad: None,
+        }
+    }
+
+    pub fn with_gamepad(mut self, axis: GamepadAxis) -> Self {
+        self.gamepad = Some(axis);
+        self
+    }
+}
+
+/// Input resource - the single source of input for game systems
+#[derive(Debug, Clone)]
+pub struct Input {
+    pub keys: ButtonInput<&'static str>,
+    pub mouse_buttons: ButtonInput<MouseButton>,
+    pub gamepad_buttons: ButtonInput<GamepadButton>,
+    pub mouse_position: Vec2,
+    pub mouse_delta: Vec2,
+    pub scroll_delta: Vec2,
+    /// Stick values below this magnitude read as zero
+    pub deadzone: f32,
+    gamepad_axes: HashMap<GamepadAxis, f32>,
+    axes: HashMap<String, AxisBinding>,
+}
+
+impl Default for Input {
+    fn default() -> Self {
+        let mut axes = HashMap::new();
+        axes.insert(
+            "Horizontal".to_string(),
+            AxisBinding::keys(&["D", "Right"], &["A", "Left"])
+                .with_gamepad(GamepadAxis::LeftStickX),
+        );
+        axes.insert(
+            "Vertical".to_string(),
+            AxisBinding::keys(&["W", "Up"], &["S", "Down"]).with_gamepad(GamepadAxis::LeftStickY),
+        );
+        axes.insert(
+            "LookX".to_string(),
+            AxisBinding::default().with_gamepad(GamepadAxis::RightStickX),
+        );
+        axes.insert(
+            "LookY".to_string(),
+            AxisBinding::default().with_gamepad(GamepadAxis::RightStickY),
+        );
+        Self {
+            keys: ButtonInput::default(),
+            mouse_buttons: ButtonInput::default(),
+            gamepad_buttons: ButtonInput::default(),
+            mouse_position: Vec2::ZERO,
+            mouse_delta: Vec2::ZERO,
+            scroll_delta: Vec2::ZERO,
+            deadzone: 0.15,
+            gamepad_axes: HashMap::new(),
+            axes,
+        }
+    }
+}
+
+impl Input {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a new frame: clear transitions and deltas before feeding new events
+    pub fn begin_frame(&mut self) {
+        self.keys.clear_frame();
+        self.mouse_buttons.clear_frame();
+        self.gamepad_buttons.clear_frame();
+        self.mouse_delta = Vec2::ZERO;
+        self.scroll_delta = Vec2::ZERO;
+    }
+
+    pub fn key_pressed(&self, key: &str) -> bool {
+        self.keys.pressed(key)
+    }
+
+    pub fn key_just_pressed(&self, key: &str) -> bool {
+        self.keys.just_pressed(key)
+    }
+
+    pub fn key_just_released(&self, key: &str) -> bool {
+        self.keys.just_released(key)
+    }
+
+    pub fn set_gamepad_axis(&mut self, axis: GamepadAxis, value: f32) {
+        self.gamepad_axes.insert(axis, value.clamp(-1.0, 1.0));
+    }
+
+    /// Gamepad axis value with the deadzone applied
+    pub fn gamepad_axis(&self, axis: GamepadAxis) -> f32 {
+        let v = self.gamepad_axes.get(&axis).copied().unwrap_or(0.0);
+        if v.abs() < self.deadzone { 0.0 } else { v }
+    }
+
+    pub fn set_axis_binding(&mut self, name: &str, binding: AxisBinding) {
+        self.axes.insert(name.to_string(), binding);
+    }
+
+    pub fn axis_binding(&self, name: &str) -> Option<&AxisBinding> {
+        self.axes.get(name)
+    }
+
+    /// Virtual axis value in [-1, 1]; unknown axes read as zero
+    pub fn axis(&self, name: &str) -> f32 {
+        let Some(binding) = self.axes.get(name) else {
+            return 0.0;
+        };
+        let held = |keys: &[&'static str]| keys.iter().any(|k| self.keys.pressed(*k));
+        let keys = held(&binding.positive) as i32 - held(&binding.negative) as i32;
+        let pad = binding.gamepad.map(|a| self.gamepad_axis(a)).unwrap_or(0.0);
+        (keys as f32 + pad).clamp(-1.0, 1.0)
+    }
+}
@@ -2,10 +2,35 @@
 //!
 //! This module provides the core ECS functionality without any GUI dependencies.
 
+pub mod commands;
 pub mod components;
+pub mod debug_draw;
 pub mod ecs;
+pub mod game_systems;
+pub mod input;
+pub mod profiler;
+pub mod refs;
+pub mod scene_file;
+pub mod scripting;
+pub mod spatial;
+pub mod state;
 pub mod systems;
+pub mod time;
+pub mod timer;
 
+pub use commands::*;
 pub use components::*;
+pub use debug_draw::*;
 pub use ecs::*;
+pub use game_systems::*;
+pub use input::*;
+// Free functions stay under `profiler::` (`profiler::finish_frame`, ...)
+pub use profiler::{ProfileCategory, ProfileFrame, ProfileScope, ProfileSpan};
+pub use refs::*;
+pub use scene_file::*;
+pub use scripting::*;
+pub use spatial::*;
+pub use state::*;
 pub use systems::*;
+pub use time::*;
+pub use timer::*;
@@ -0,0 +1,393 @@
+//! Frame profiler - named scopes timed per thread and grouped into frames
+//!
+//! Code is instrumented with `profile_scope!("name")` (or `profile_function!()`),
+//! which times the rest of the enclosing block. Finished scopes are buffered
+//! until the engine loop calls `finish_frame`, which closes the current frame
+//! and keeps the last `MAX_FRAMES` for the profiler panel. While recording is
+//! off (the default) a scope costs one relaxed atomic load.
+
+use std::cell::Cell;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Frames kept in the history
+pub const MAX_FRAMES: usize = 300;
+
+/// Scopes kept per frame; the rest of a runaway frame is dropped
+const MAX_SPANS_PER_FRAME: usize = 20_000;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static NEXT_THREAD: AtomicU32 = AtomicU32::new(0);
+static COLLECTOR: Mutex<Collector> = Mutex::new(Collector::new());
+
+thread_local! {
+    static THREAD: Cell<Option<u32>> = const { Cell::new(None) };
+    static DEPTH: Cell<u16> = const { Cell::new(0) };
+}
+
+/// Where a scope belongs, used to group and color the flame graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProfileCategory {
+    /// Editor UI (panels, windows, input)
+    Editor,
+    /// ECS systems, scripting and physics
+    Systems,
+    /// Render passes, GPU uploads and asset loading
+    Render,
+    Other,
+}
+
+impl ProfileCategory {
+    pub const ALL: [ProfileCategory; 4] = [
+        ProfileCategory::Editor,
+        ProfileCategory::Systems,
+        ProfileCategory::Render,
+        ProfileCategory::Other,
+    ];
+
+    /// Default category from the crate a scope is written in
+    pub fn from_module(module_path: &str) -> Self {
+        let krate = module_path.split("::").next().unwrap_or_default();
+        match krate {
+            "dengine" | "engine_editor" => ProfileCategory::Editor,
+            "engine_core" | "engine_physics" | "engine_net" | "engine_audio" => {
+                ProfileCategory::Systems
+            }
+            "engine_render" => ProfileCategory::Render,
+            _ => ProfileCategory::Other,
+        }
+    }
+
+    pub fn index(self) -> usize {
+        match self {
+            ProfileCategory::Editor => 0,
+            ProfileCategory::Systems => 1,
+            ProfileCategory::Render => 2,
+            ProfileCategory::Other => 3,
+        }
+    }
+}
+
+/// One timed scope, relative to the start of its frame
+#[derive(Debug, Clone, Copy)]
+pub struct ProfileSpan {
+    pub name: &'static str,
+    pub category: ProfileCategory,
+    /// Profiler thread index, see `ProfileFrame::threads`
+    pub thread: u32,
+    /// Nesting level inside the thread (0 = outermost scope)
+    pub depth: u16,
+    pub start: Duration,
+    pub duration: Duration,
+}
+
+impl ProfileSpan {
+    pub fn end(&self) -> Duration {
+        self.start + self.duration
+    }
+}
+
+/// A closed frame with every scope that finished during it
+#[derive(Debug, Clone)]
+pub struct ProfileFrame {
+    /// Frames closed since the process started
+    pub index: u64,
+    pub duration: Duration,
+    /// Sorted by thread, then start time
+    pub spans: Vec<ProfileSpan>,
+    /// Names of the threads that recorded scopes, by thread index
+    pub threads: Vec<(u32, String)>,
+}
+
+impl ProfileFrame {
+    /// Total time spent in outermost scopes of `category`
+    pub fn category_time(&self, category: ProfileCategory) -> Duration {
+        self.spans
+            .iter()
+            .filter(|span| span.depth == 0 && span.category == category)
+            .map(|span| span.duration)
+            .sum()
+    }
+}
+
+struct RawSpan {
+    name: &'static str,
+    category: ProfileCategory,
+    thread: u32,
+    depth: u16,
+    start: Instant,
+    end: Instant,
+}
+
+struct Collector {
+    frames: VecDeque<Arc<ProfileFrame>>,
+    current: Vec<RawSpan>,
+    frame_start: Option<Instant>,
+    next_index: u64,
+    thread_names: Option<HashMap<u32, String>>,
+}
+
+impl Collector {
+    const fn new() -> Self {
+        Self {
+            frames: VecDeque::new(),
+            current: Vec::new(),
+            frame_start: None,
+            next_index: 0,
+            thread_names: None,
+        }
+    }
+}
+
+fn collector() -> std::sync::MutexGuard<'static, Collector> {
+    // A panic while holding the lock only loses profiling data
+    COLLECTOR.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn thread_index() -> u32 {
+    THREAD.with(|thread| {
+        if let Some(index) = thread.get() {
+            return index;
+        }
+        let index = NEXT_THREAD.fetch_add(1, Ordering::Relaxed);
+        thread.set(Some(index));
+        let current = std::thread::current();
+        let name = current
+            .name()
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("thread {index}"));
+        collector()
+            .thread_names
+            .get_or_insert_with(HashMap::new)
+            .insert(index, name);
+        index
+    })
+}
+
+/// Turn recording on or off; turning it off drops the frame in progress
+pub fn set_enabled(enabled: bool) {
+    let was = ENABLED.swap(enabled, Ordering::Relaxed);
+    if was && !enabled {
+        let mut collector = collector();
+        collector.current.clear();
+        collector.frame_start = None;
+    }
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Close the current frame and start the next one; called once per frame by
+/// the engine loop, before anything of the new frame is profiled
+pub fn finish_frame() {
+    if !is_enabled() {
+        return;
+    }
+    let now = Instant::now();
+    let mut collector = collector();
+    let Some(start) = collector.frame_start.replace(now) else {
+        // First frame after enabling: its start is unknown
+        collector.current.clear();
+        return;
+    };
+    let raw = std::mem::take(&mut collector.current);
+    let mut spans: Vec<ProfileSpan> = raw
+        .into_iter()
+        .map(|span| {
+            // Scopes opened before the frame started are clipped to it
+            let span_start = span.start.max(start);
+            ProfileSpan {
+                name: span.name,
+                category: span.category,
+                thread: span.thread,
+                depth: span.depth,
+                start: span_start - start,
+                duration: span.end.saturating_duration_since(span_start),
+            }
+        })
+        .collect();
+    spans.sort_by_key(|span| (span.thread, span.start, span.depth));
+    let mut threads: Vec<(u32, String)> = Vec::new();
+    for span in &spans {
+        if threads
+            .last()
+            .is_none_or(|(thread, _)| *thread != span.thread)
+        {
+            let name = collector
+                .thread_names
+                .as_ref()
+                .and_then(|names| names.get(&span.thread).cloned())
+                .unwrap_or_else(|| format!("thread {}", span.thread));
+            threads.push((span.thread, name));
+        }
+    }
+    let frame = ProfileFrame {
+        index: collector.next_index,
+        duration: now - start,
+        spans,
+        threads,
+    };
+    collector.next_index += 1;
+    if collector.frames.len() == MAX_FRAMES {
+        collector.frames.pop_front();
+    }
+    collector.frames.push_back(Arc::new(frame));
+}
+
+/// Recorded frames, oldest first
+pub fn frames() -> Vec<Arc<ProfileFrame>> {
+    collector().frames.iter().cloned().collect()
+}
+
+/// Forget every recorded frame
+pub fn clear_frames() {
+    collector().frames.clear();
+}
+
+/// Times the scope it lives in; created by `profile_scope!`
+pub struct ProfileScope {
+    name: &'static str,
+    category: ProfileCategory,
+    start: Option<Instant>,
+    depth: u16,
+}
+
+impl ProfileScope {
+    pub fn new(name: &'static str, category: ProfileCategory) -> Self {
+        if !is_enabled() {
+            return Self {
+                name,
+                category,
+                start: None,
+                depth: 0,
+            };
+        }
+        let depth = DEPTH.with(|depth| {
+            let current = depth.get();
+            depth.set(current.saturating_add(1));
+            current
+        });
+        Self {
+            name,
+            category,
+            start: Some(Instant::now()),
+            depth,
+        }
+    }
+}
+
+impl Drop for ProfileScope {
+    fn drop(&mut self) {
+        let Some(start) = self.start else {
+            return;
+        };
+        let end = Instant::now();
+        DEPTH.with(|depth| depth.set(self.depth));
+        let thread = thread_index();
+        let mut collector = collector();
+        if collector.frame_start.is_none() || collector.current.len() >= MAX_SPANS_PER_FRAME {
+            return;
+        }
+        collector.current.push(RawSpan {
+            name: self.name,
+            category: self.category,
+            thread,
+            depth: self.depth,
+            start,
+            end,
+        });
+    }
+}
+
+/// Short name of the enclosing function from the type name of a nested item,
+/// e.g. `engine_core::systems::LightSystem::update::f` -> `LightSystem::update`
+/// and `dengine::log_panel::<impl dengine::EditorApp>::draw_log_panel::f` ->
+/// `EditorApp::draw_log_panel`
+pub fn function_name(item_type_name: &str) -> String {
+    let mut path = item_type_name.strip_suffix("::f").unwrap_or(item_type_name);
+    while let Some(outer) = path.strip_suffix("::{{closure}}") {
+        path = outer;
+    }
+    let segments = split_path(path);
+    let first = segments.len().saturating_sub(2);
+    segments[first..]
+        .iter()
+        .map(|segment| {
+            // `<impl Type>` and `<Type as Trait>` name the type
+            match segment.strip_prefix('<').and_then(|s| s.strip_suffix('>')) {
+                Some(inner) => {
+                    let inner = inner.strip_prefix("impl ").unwrap_or(inner);
+                    let ty = inner.split(" as ").next().unwrap_or(inner);
+                    split_path(ty).last().copied().unwrap_or(ty)
+                }
+                None => segment,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+/// Splits a type path at the `::` that are not inside generic arguments
+fn split_path(path: &str) -> Vec<&str> {
+    let bytes = path.as_bytes();
+    let mut segments = Vec::new();
+    let mut generics = 0;
+    let mut start = 0;
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'<' => generics += 1,
+            b'>' => generics -= 1,
+            b':' if generics == 0 && bytes.get(index + 1) == Some(&b':') => {
+                segments.push(&path[start..index]);
+                index += 1;
+                start = index + 1;
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+    segments.push(&path[start..]);
+    segments
+}
+
+/// Time the rest of the enclosing block under `name`; the category defaults
+/// to the crate the macro is used in
+#[macro_export]
+macro_rules! profile_scope {
+    ($name:expr) => {
+        $crate::profile_scope!(
+            $name,
+            $crate::profiler::ProfileCategory::from_module(module_path!())
+        )
+    };
+    ($name:expr, $category:expr) => {
+        let _profile_scope = $crate::profiler::ProfileScope::new($name, $category);
+    };
+}
+
+/// Time the rest of the enclosing function, named after it
+#[macro_export]
+macro_rules! profile_function {
+    () => {
+        $crate::profile_function!($crate::profiler::ProfileCategory::from_module(
+            module_path!()
+        ))
+    };
+    ($category:expr) => {
+        $crate::profile_scope!(
+            {
+                fn f() {}
+                static NAME: ::std::sync::OnceLock<&'static str> = ::std::sync::OnceLock::new();
+                *NAME.get_or_init(|| {
+                    let name = $crate::profiler::function_name(::std::any::type_name_of_val(&f));
+                    ::std::boxed::Box::leak(name.into_boxed_str())
+                })
+            },
+            $category
+        )
+    };
+}
@@ -0,0 +1,163 @@
+//! Serializable references between entities and to assets
+//!
+//! Both reference types store only a `Guid` when saved; the live `hecs::Entity`
+//! or asset handle is looked up again after loading and cached until it goes stale.
+
+use crate::components::{CameraFollow, Guid};
+use crate::ecs::EngineWorld;
+
+/// Reference to another entity, stored as its Guid
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EntityRef {
+    pub guid: Guid,
+    cached: Option<hecs::Entity>,
+}
+
+impl PartialEq for EntityRef {
+    fn eq(&self, other: &Self) -> bool {
+        self.guid == other.guid
+    }
+}
+
+impl Eq for EntityRef {}
+
+impl EntityRef {
+    pub fn new(guid: Guid) -> Self {
+        Self { guid, cached: None }
+    }
+
+    /// Reference an entity of the world; empty if the entity has no Guid
+    pub fn from_entity(world: &EngineWorld, entity: hecs::Entity) -> Self {
+        match world.guid_of(entity) {
+            Some(guid) => Self {
+                guid,
+                cached: Some(entity),
+            },
+            None => Self::default(),
+        }
+    }
+
+    pub fn is_none(&self) -> bool {
+        self.guid.is_nil()
+    }
+
+    /// Look the entity up without touching the cache
+    pub fn get(&self, world: &EngineWorld) -> Option<hecs::Entity> {
+        if let Some(entity) = self.cached.filter(|e| world.guid_of(*e) == Some(self.guid)) {
+            return Some(entity);
+        }
+        world.find_by_guid(self.guid)
+    }
+
+    /// Look the entity up and cache it for the next call
+    pub fn resolve(&mut self, world: &EngineWorld) -> Option<hecs::Entity> {
+        self.cached = self.get(world);
+        self.cached
+    }
+
+    /// Saved form - the hyphenated Guid, empty for no reference
+    pub fn serialize(&self) -> String {
+        if self.is_none() {
+            String::new()
+        } else {
+            self.guid.to_string()
+        }
+    }
+
+    pub fn deserialize(text: &str) -> Self {
+        Guid::parse(text).map(Self::new).unwrap_or_default()
+    }
+}
+
+/// Reference to an asset, stored as the asset Guid and resolved to a handle `T`
+#[derive(Debug, Clone, Copy)]
+pub struct AssetRef<T: Copy> {
+    pub guid: Guid,
+    handle: Option<T>,
+}
+
+impl<T: Copy> Default for AssetRef<T> {
+    fn default() -> Self {
+        Self {
+            guid: Guid::nil(),
+            handle: None,
+        }
+    }
+}
+
+impl<T: Copy> AssetRef<T> {
+    pub fn new(guid: Guid) -> Self {
+        Self { guid, handle: None }
+    }
+
+    /// Reference an already loaded asset
+    pub fn with_handle(guid: Guid, handle: T) -> Self {
+        Self {
+            guid,
+            handle: Some(handle),
+        }
+    }
+
+    pub fn is_none(&self) -> bool {
+        self.guid.is_nil()
+    }
+
+    /// Cached handle from the last successful resolve
+    pub fn handle(&self) -> Option<T> {
+        self.handle
+    }
+
+    /// Resolve the Guid through the asset database (`lookup`), caching the handle
+    pub fn resolve(&mut self, lookup: impl FnOnce(Guid) -> Option<T>) -> Option<T> {
+        if self.handle.is_none() && !self.is_none() {
+            self.handle = lookup(self.guid);
+        }
+        self.handle
+    }
+
+    /// Forget the cached handle (e.g. after the asset was reloaded)
+    pub fn invalidate(&mut self) {
+        self.handle = None;
+    }
+
+    /// Saved form - the hyphenated Guid, empty for no reference
+    pub fn serialize(&self) -> String {
+        if self.is_none() {
+            String::new()
+        } else {
+            self.guid.to_string()
+        }
+    }
+
+    pub fn deserialize(text: &str) -> Self {
+        Guid::parse(text).map(Self::new).unwrap_or_default()
+    }
+}
+
+/// Parent in the scene hierarchy; the entity's `Transform` is relative to it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Parent(pub EntityRef);
+
+/// Components holding `EntityRef`s, so loading a scene twice can point each copy
+/// at its own entities
+pub trait MapEntityRefs {
+    fn map_entity_refs(&mut self, f: &mut dyn FnMut(&mut EntityRef));
+}
+
+impl MapEntityRefs for EntityRef {
+    fn map_entity_refs(&mut self, f: &mut dyn FnMut(&mut EntityRef)) {
+        f(self);
+    }
+}
+
+impl MapEntityRefs for Parent {
+    fn map_entity_refs(&mut self, f: &mut dyn FnMut(&mut EntityRef)) {
+        f(&mut self.0);
+    }
+}
+
+impl MapEntityRefs for CameraFollow {
+    fn map_entity_refs(&mut self, f: &mut dyn FnMut(&mut EntityRef)) {
+        f(&mut self.target);
+    }
+}
@@ -0,0 +1,483 @@
+//! Scene files - the entities of one scene as `key=value` blocks
+//!
+//! ```text
+//! version=1
+//! [entity]
+//! guid=6f1c...
+//! name=Cube
+//! parent=0b7e...
+//! mesh=Assets/Meshes/cube.obj
+//! position=0,1,0
+//! rotation=0,0,0,1
+//! scale=1,1,1
+//! script=Assets/Scripts/spin.lua
+//! ```
+//!
+//! Written by the editor and read by both the editor (additive loading) and the
+//! player. Rotations are stored as quaternions (x, y, z, w); lights and cameras
+//! as their color/intensity/range and fov/near/far values; sprites as their
+//! texture path (`sprite_texture`) plus size, pivot, UV rect, color, flips and
+//! sorting (`sprite`); in-world text as its font path (`text_font`), size,
+//! color, alignment, billboard flag and offset (`text3d`) and its string with
+//! `\n` and `\\` escaped (`text3d_text`); screen text as its size, color,
+//! alignment, anchor, pixel offset and visibility (`ui_text`) and its string
+//! (`ui_text_text`), sharing `text_font`. Components of other crates
+//! (physics) are written and read through a `SceneComponents`.
+
+use glam::{Quat, Vec2, Vec3};
+
+use crate::components::*;
+use crate::ecs::{EngineWorld, WorldSnapshot};
+use crate::refs::{EntityRef, Parent};
+use crate::scripting::ScriptComponent;
+
+/// Scene file format version written by `write_scene`
+pub const SCENE_VERSION: u32 = 1;
+
+fn floats(text: &str) -> Option<Vec<f32>> {
+    text.split(',')
+        .map(|p| p.trim().parse::<f32>().ok())
+        .collect()
+}
+
+/// One-line form of a text component's string
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                out.push('\n');
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                out.push('\\');
+                chars.next();
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn text_align_index(align: TextAlign) -> f32 {
+    match align {
+        TextAlign::Left => 0.0,
+        TextAlign::Center => 1.0,
+        TextAlign::Right => 2.0,
+    }
+}
+
+fn text_align(index: f32) -> TextAlign {
+    match index as i32 {
+        0 => TextAlign::Left,
+        2 => TextAlign::Right,
+        _ => TextAlign::Center,
+    }
+}
+
+fn vec3(text: &str) -> Option<Vec3> {
+    match floats(text)?.as_slice() {
+        [x, y, z] => Some(Vec3::new(*x, *y, *z)),
+        _ => None,
+    }
+}
+
+fn join(values: &[f32]) -> String {
+    values
+        .iter()
+        .map(f32::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Components defined outside engine_core that live in scene files. The
+/// reader hands every key it doesn't know to `read`, in file order
+pub trait SceneComponents {
+    /// Make snapshots of the read world keep these components
+    fn register(&self, world: &mut EngineWorld);
+
+    /// Append the `key=value` lines of the entity's components
+    fn write(&self, entity: hecs::EntityRef<'_>, out: &mut String);
+
+    /// Add the component of one line; `Ok(false)` when the key isn't one of these
+    fn read(
+        &self,
+        key: &str,
+        value: &str,
+        builder: &mut hecs::EntityBuilder,
+    ) -> Result<bool, String>;
+}
+
+/// Serialize the given entities; entities of the world that are not listed are
+/// left out, so `Parent`s pointing at them become roots when loaded
+pub fn write_scene(world: &EngineWorld, entities: &[hecs::Entity]) -> String {
+    write_scene_with(world, entities, &[])
+}
+
+/// `write_scene` plus the components of `extra`
+pub fn write_scene_with(
+    world: &EngineWorld,
+    entities: &[hecs::Entity],
+    extra: &[&dyn SceneComponents],
+) -> String {
+    let mut out = format!("version={SCENE_VERSION}\n");
+    for &entity in entities {
+        let Ok(e) = world.world().entity(entity) else {
+            continue;
+        };
+        out.push_str("[entity]\n");
+        if let Some(guid) = e.get::<&Guid>() {
+            out.push_str(&format!("guid={}\n", *guid));
+        }
+        if let Some(name) = e.get::<&Name>() {
+            out.push_str(&format!("name={}\n", name.as_str()));
+        }
+        if let Some(parent) = e.get::<&Parent>().filter(|p| !p.0.is_none()) {
+            out.push_str(&format!("parent={}\n", parent.0.serialize()));
+        }
+        if let Some(mesh) = e.get::<&MeshAsset>() {
+            out.push_str(&format!("mesh={}\n", mesh.path));
+        }
+        if let Some(t) = e.get::<&Transform>() {
+            out.push_str(&format!("position={}\n", join(&t.position.to_array())));
+            out.push_str(&format!("rotation={}\n", join(&t.rotation.to_array())));
+            out.push_str(&format!("scale={}\n", join(&t.scale.to_array())));
+        }
+        if let Some(script) = e.get::<&ScriptComponent>() {
+            out.push_str(&format!("script={}\n", script.path.display()));
+        }
+        if let Some(c) = e.get::<&Camera>() {
+            out.push_str(&format!("camera={}\n", join(&[c.fov, c.near, c.far])));
+        }
+        if let Some(l) = e.get::<&DirectionalLight>() {
+            let [r, g, b] = l.color.to_array();
+            out.push_str(&format!(
+                "directional_light={}\n",
+                join(&[r, g, b, l.intensity])
+            ));
+        }
+        if let Some(l) = e.get::<&PointLight>() {
+            let [r, g, b] = l.color.to_array();
+            out.push_str(&format!(
+                "point_light={}\n",
+                join(&[r, g, b, l.intensity, l.range])
+            ));
+        }
+        if let Some(l) = e.get::<&SpotLight>() {
+            let [r, g, b] = l.color.to_array();
+            out.push_str(&format!(
+                "spot_light={}\n",
+                join(&[r, g, b, l.intensity, l.range, l.inner_angle, l.outer_angle])
+            ));
+        }
+        if let Some(texture) = e.get::<&SpriteTexture>() {
+            out.push_str(&format!("sprite_texture={}\n", texture.path));
+        }
+        if let Some(s) = e.get::<&Sprite>() {
+            let [u0, v0, u1, v1] = s.uv_rect;
+            let [r, g, b, a] = s.color;
+            out.push_str(&format!(
+                "sprite={}\n",
+                join(&[
+                    s.size.x,
+                    s.size.y,
+                    s.pivot.x,
+                    s.pivot.y,
+                    u0,
+                    v0,
+                    u1,
+                    v1,
+                    r,
+                    g,
+                    b,
+                    a,
+                    f32::from(u8::from(s.flip_x)),
+                    f32::from(u8::from(s.flip_y)),
+                    f32::from(s.sorting_layer),
+                    s.order_in_layer as f32,
+                ])
+            ));
+        }
+        if let Some(font) = e.get::<&TextFont>() {
+            out.push_str(&format!("text_font={}\n", font.path));
+        }
+        if let Some(t) = e.get::<&Text3D>() {
+            let [r, g, b, a] = t.color;
+            let [ox, oy, oz] = t.offset.to_array();
+            out.push_str(&format!(
+                "text3d={}\n",
+                join(&[
+                    t.size,
+                    r,
+                    g,
+                    b,
+                    a,
+                    text_align_index(t.align),
+                    f32::from(u8::from(t.billboard)),
+                    ox,
+                    oy,
+                    oz,
+                ])
+            ));
+            out.push_str(&format!("text3d_text={}\n", escape_text(&t.text)));
+        }
+        if let Some(t) = e.get::<&UiText>() {
+            let [r, g, b, a] = t.color;
+            out.push_str(&format!(
+                "ui_text={}\n",
+                join(&[
+                    t.size,
+                    r,
+                    g,
+                    b,
+                    a,
+                    text_align_index(t.align),
+                    t.anchor.x,
+                    t.anchor.y,
+                    t.offset.x,
+                    t.offset.y,
+                    f32::from(u8::from(t.visible)),
+                ])
+            ));
+            out.push_str(&format!("ui_text_text={}\n", escape_text(&t.text)));
+        }
+        for components in extra {
+            components.write(e, &mut out);
+        }
+    }
+    out
+}
+
+/// One `[entity]` block while it is being read
+#[derive(Default)]
+struct EntityBlock {
+    builder: hecs::EntityBuilder,
+    transform: Option<Transform>,
+    // Built from its `text3d` and `text3d_text` lines
+    text3d: Option<Text3D>,
+    // Built from its `ui_text` and `ui_text_text` lines
+    ui_text: Option<UiText>,
+}
+
+impl EntityBlock {
+    fn spawn(mut self, world: &mut EngineWorld) {
+        if let Some(transform) = self.transform {
+            self.builder.add(transform);
+        }
+        if let Some(text) = self.text3d {
+            self.builder.add(text);
+        }
+        if let Some(text) = self.ui_text {
+            self.builder.add(text);
+        }
+        world.spawn(self.builder.build());
+    }
+}
+
+/// Parse a scene file into a snapshot ready for `EngineWorld::restore` or
+/// `EngineWorld::load_scene_additive`. Entities keep the Guids of the file.
+pub fn read_scene(text: &str) -> Result<WorldSnapshot, String> {
+    read_scene_with(text, &[])
+}
+
+/// `read_scene` plus the components of `extra`
+pub fn read_scene_with(
+    text: &str,
+    extra: &[&dyn SceneComponents],
+) -> Result<WorldSnapshot, String> {
+    let mut world = EngineWorld::new();
+    for components in extra {
+        components.register(&mut world);
+    }
+    let mut block: Option<EntityBlock> = None;
+    for (idx, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line == "[entity]" {
+            if let Some(done) = block.replace(EntityBlock::default()) {
+                done.spawn(&mut world);
+            }
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(format!("line {}: expected key=value, got {line}", idx + 1));
+        };
+        let value = value.trim();
+        let key = key.trim();
+        if key == "version" {
+            let version: u32 = value
+                .parse()
+                .map_err(|_| format!("invalid scene version: {value}"))?;
+            if version > SCENE_VERSION {
+                return Err(format!(
+                    "scene version {version} is newer than the supported {SCENE_VERSION}"
+                ));
+            }
+            continue;
+        }
+        let Some(current) = block.as_mut() else {
+            return Err(format!(
+                "line {}: {line} outside of an [entity] block",
+                idx + 1
+            ));
+        };
+        let bad = || format!("line {}: invalid value in {line}", idx + 1);
+        let transform = &mut current.transform;
+        let text3d = &mut current.text3d;
+        let ui_text = &mut current.ui_text;
+        let numbers = || floats(value).ok_or_else(bad);
+        match key {
+            "guid" => {
+                current.builder.add(Guid::parse(value).ok_or_else(bad)?);
+            }
+            "name" => {
+                current.builder.add(Name::new(value));
+            }
+            "parent" => {
+                current
+                    .builder
+                    .add(Parent(EntityRef::new(Guid::parse(value).ok_or_else(bad)?)));
+            }
+            "mesh" => {
+                current.builder.add(MeshAsset::new(value));
+            }
+            "position" => {
+                transform.get_or_insert_default().position = vec3(value).ok_or_else(bad)?;
+            }
+            "scale" => {
+                transform.get_or_insert_default().scale = vec3(value).ok_or_else(bad)?;
+            }
+            "rotation" => match numbers()?.as_slice() {
+                [x, y, z, w] => {
+                    transform.get_or_insert_default().rotation =
+                        Quat::from_xyzw(*x, *y, *z, *w).normalize();
+                }
+                _ => return Err(bad()),
+            },
+            "script" => {
+                current.builder.add(ScriptComponent::new(value));
+            }
+            "camera" => match numbers()?.as_slice() {
+                [fov, near, far] => {
+                    current.builder.add(Camera::new(*fov, *near, *far));
+                }
+                _ => return Err(bad()),
+            },
+            "directional_light" => match numbers()?.as_slice() {
+                [r, g, b, intensity] => {
+                    current
+                        .builder
+                        .add(DirectionalLight::new(Vec3::new(*r, *g, *b), *intensity));
+                }
+                _ => return Err(bad()),
+            },
+            "point_light" => match numbers()?.as_slice() {
+                [r, g, b, intensity, range] => {
+                    current
+                        .builder
+                        .add(PointLight::new(Vec3::new(*r, *g, *b), *intensity, *range));
+                }
+                _ => return Err(bad()),
+            },
+            "spot_light" => match numbers()?.as_slice() {
+                [r, g, b, intensity, range, inner, outer] => {
+                    let mut light =
+                        SpotLight::new(Vec3::new(*r, *g, *b), *intensity, *range, *outer);
+                    light.inner_angle = *inner;
+                    current.builder.add(light);
+                }
+                _ => return Err(bad()),
+            },
+            "sprite_texture" => {
+                current.builder.add(SpriteTexture::new(value));
+            }
+            "sprite" => match numbers()?.as_slice() {
+                [
+                    w,
+                    h,
+                    px,
+                    py,
+                    u0,
+                    v0,
+                    u1,
+                    v1,
+                    r,
+                    g,
+                    b,
+                    a,
+                    flip_x,
+                    flip_y,
+                    layer,
+                    order,
+                ] => {
+                    current.builder.add(Sprite {
+                        texture: TextureHandle::invalid(),
+                        size: Vec2::new(*w, *h),
+                        pivot: Vec2::new(*px, *py),
+                        uv_rect: [*u0, *v0, *u1, *v1],
+                        color: [*r, *g, *b, *a],
+                        flip_x: *flip_x != 0.0,
+                        flip_y: *flip_y != 0.0,
+                        sorting_layer: *layer as u16,
+                        order_in_layer: *order as i32,
+                    });
+                }
+                _ => return Err(bad()),
+            },
+            "text_font" => {
+                current.builder.add(TextFont::new(value));
+            }
+            "text3d" => match numbers()?.as_slice() {
+                [size, r, g, b, a, align, billboard, ox, oy, oz] => {
+                    let text = text3d.get_or_insert_default();
+                    text.size = *size;
+                    text.color = [*r, *g, *b, *a];
+                    text.align = text_align(*align);
+                    text.billboard = *billboard != 0.0;
+                    text.offset = Vec3::new(*ox, *oy, *oz);
+                }
+                _ => return Err(bad()),
+            },
+            "text3d_text" => {
+                text3d.get_or_insert_default().text = unescape_text(value);
+            }
+            "ui_text" => match numbers()?.as_slice() {
+                [size, r, g, b, a, align, ax, ay, ox, oy, visible] => {
+                    let text = ui_text.get_or_insert_default();
+                    text.size = *size;
+                    text.color = [*r, *g, *b, *a];
+                    text.align = text_align(*align);
+                    text.anchor = Vec2::new(*ax, *ay);
+                    text.offset = Vec2::new(*ox, *oy);
+                    text.visible = *visible != 0.0;
+                }
+                _ => return Err(bad()),
+            },
+            "ui_text_text" => {
+                ui_text.get_or_insert_default().text = unescape_text(value);
+            }
+            // Keys from newer versions are skipped instead of failing the load
+            _ => {
+                for components in extra {
+                    let added = components
+                        .read(key, value, &mut current.builder)
+                        .map_err(|e| format!("line {}: {e}", idx + 1))?;
+                    if added {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    if let Some(done) = block {
+        done.spawn(&mut world);
+    }
+    Ok(world.snapshot())
+}
@@ -0,0 +1,614 @@
+//! Lua gameplay scripting - per-entity scripts with on_start/on_update callbacks
+//!
+//! A script is a plain Lua file that may define global `on_start()` and
+//! `on_update(dt)` functions. Every entity gets its own environment, so script
+//! globals are per-entity state. Inside the callbacks the script sees:
+//! - `entity.id` and the `transform` table (`position`, `rotation` in degrees, `scale`)
+//! - `translate(x, y, z)`, `rotate(x, y, z)` helpers
+//! - the `input` table (`axis`, `look`, `action`, `key_down(name)`)
+//! - `game_state`, the name of the current `GameState`, and
+//!   `set_game_state(name)`, applied after the scripts of the frame
+//! - `after(seconds, fn)`, `every(seconds, fn)` and `cancel(handle)` timers
+//! - `start_coroutine(fn)` with `wait(seconds)` inside the coroutine
+//! - `debug_line(a, b)`, `debug_box(center, half_extents)`, `debug_sphere(center, r)`,
+//!   `debug_arrow(a, b)` and `debug_text(position, text)`; points are `{x, y, z}`
+//!   tables and every call takes optional trailing `color` (`{r, g, b, a}`) and
+//!   `duration` (seconds, default one frame)
+//! - `screenshot(path, width, height)` saves the viewport to a `.png` or `.exr`
+//!   file; the size defaults to the viewport size
+//! - `print(...)` goes to the `log` facade under the `script` target, prefixed
+//!   with the script path and line
+
+use std::cell::{Ref, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::SystemTime;
+
+use glam::{EulerRot, Quat, Vec3};
+use mlua::{Function, Lua, RegistryKey, Table};
+
+use crate::components::Transform;
+use crate::debug_draw::{DEBUG_DEFAULT_COLOR, DebugDraw, DebugShape};
+use crate::ecs::EngineWorld;
+use crate::state::GameState;
+
+/// Helpers loaded into every script environment
+const SCRIPT_PRELUDE: &str = r#"
+function translate(x, y, z)
+    local p = transform.position
+    p.x = p.x + (x or 0)
+    p.y = p.y + (y or 0)
+    p.z = p.z + (z or 0)
+end
+
+function rotate(x, y, z)
+    local r = transform.rotation
+    r.x = r.x + (x or 0)
+    r.y = r.y + (y or 0)
+    r.z = r.z + (z or 0)
+end
+
+local timers = {}
+local coroutines = {}
+
+function after(seconds, fn)
+    local t = { left = seconds or 0, fn = fn }
+    table.insert(timers, t)
+    return t
+end
+
+function every(seconds, fn)
+    local t = { left = seconds or 0, interval = math.max(seconds or 0, 0), fn = fn }
+    table.insert(timers, t)
+    return t
+end
+
+function cancel(handle)
+    if handle then handle.cancelled = true end
+end
+
+function start_coroutine(fn)
+    local c = { co = coroutine.create(fn), left = 0 }
+    table.insert(coroutines, c)
+    return c
+end
+
+function wait(seconds)
+    coroutine.yield(seconds or 0)
+end
+
+function __tick_scheduled(dt)
+    local due = timers
+    timers = {}
+    for _, t in ipairs(due) do
+        if not t.cancelled then
+            t.left = t.left - dt
+            if t.left <= 0 then
+                t.fn()
+                if t.interval and not t.cancelled then
+                    t.left = math.max(t.left + t.interval, 0)
+                    table.insert(timers, t)
+                end
+            else
+                table.insert(timers, t)
+            end
+        end
+    end
+    local running = coroutines
+    coroutines = {}
+    for _, c in ipairs(running) do
+        if not c.cancelled then
+            c.left = c.left - dt
+            if c.left <= 0 then
+                local ok, w = coroutine.resume(c.co)
+                if not ok then error(w, 0) end
+                if coroutine.status(c.co) ~= "dead" then
+                    c.left = tonumber(w) or 0
+                    table.insert(coroutines, c)
+                end
+            else
+                table.insert(coroutines, c)
+            end
+        end
+    end
+end
+"#;
+
+/// Script component - binds a Lua file to an entity
+#[derive(Debug, Clone)]
+pub struct ScriptComponent {
+    pub path: PathBuf,
+    pub enabled: bool,
+}
+
+impl ScriptComponent {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            enabled: true,
+        }
+    }
+}
+
+/// Input state handed to scripts each frame
+#[derive(Debug, Clone, Default)]
+pub struct ScriptInput {
+    pub axis: [f32; 2],
+    pub look: [f32; 2],
+    pub action: f32,
+    pub keys_down: HashSet<String>,
+    pub game_state: GameState,
+}
+
+/// Screenshot asked by a script; the editor renders it on the next frame
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScreenshotRequest {
+    pub path: PathBuf,
+    /// `None` = viewport size
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+}
+
+struct ScriptInstance {
+    path: PathBuf,
+    env: RegistryKey,
+    started: bool,
+}
+
+/// Script that failed to load, kept until the file changes so it is neither
+/// re-read nor reported again every frame
+struct FailedLoad {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Script system - owns the Lua state and runs every enabled ScriptComponent
+pub struct ScriptSystem {
+    lua: Lua,
+    instances: HashMap<hecs::Entity, ScriptInstance>,
+    failed: HashMap<hecs::Entity, FailedLoad>,
+    errors: Vec<String>,
+    debug: Rc<RefCell<DebugDraw>>,
+    screenshots: Rc<RefCell<Vec<ScreenshotRequest>>>,
+    state_request: Rc<RefCell<Option<GameState>>>,
+}
+
+impl Default for ScriptSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScriptSystem {
+    pub fn new() -> Self {
+        Self {
+            lua: Lua::new(),
+            instances: HashMap::new(),
+            failed: HashMap::new(),
+            errors: Vec::new(),
+            debug: Rc::new(RefCell::new(DebugDraw::new())),
+            screenshots: Rc::new(RefCell::new(Vec::new())),
+            state_request: Rc::new(RefCell::new(None)),
+        }
+    }
+
+    /// Errors raised during the last update (load, compile or runtime)
+    pub fn errors(&self) -> &[String] {
+        &self.errors
+    }
+
+    /// Debug shapes queued by scripts (kept until their duration runs out)
+    pub fn debug_draw(&self) -> Ref<'_, DebugDraw> {
+        self.debug.borrow()
+    }
+
+    /// Screenshots requested since the last call
+    pub fn take_screenshot_requests(&mut self) -> Vec<ScreenshotRequest> {
+        std::mem::take(&mut *self.screenshots.borrow_mut())
+    }
+
+    /// State asked by `set_game_state` since the last call (the last call wins)
+    pub fn take_state_request(&mut self) -> Option<GameState> {
+        self.state_request.borrow_mut().take()
+    }
+
+    /// Drop every script instance; the next update calls on_start again
+    pub fn reset(&mut self) {
+        self.instances.clear();
+        self.failed.clear();
+        self.errors.clear();
+        self.debug.borrow_mut().clear();
+        self.screenshots.borrow_mut().clear();
+        self.state_request.borrow_mut().take();
+        self.lua.expire_registry_values();
+    }
+
+    /// Drop the instances running the script at `path` (changed on disk); the
+    /// next update loads it again and calls on_start. Returns how many were dropped
+    pub fn reload_path(&mut self, path: &Path) -> usize {
+        let canonical = path.canonicalize().ok();
+        let other = |script: &Path| {
+            script != path && (canonical.is_none() || script.canonicalize().ok() != canonical)
+        };
+        self.failed.retain(|_, failed| other(&failed.path));
+        let before = self.instances.len();
+        self.instances.retain(|_, instance| other(&instance.path));
+        before - self.instances.len()
+    }
+
+    /// Run on_start (first frame) and on_update(dt) for every scripted entity
+    pub fn update(&mut self, world: &mut EngineWorld, input: &ScriptInput, dt: f32) {
+        crate::profile_function!();
+        self.errors.clear();
+        self.debug.borrow_mut().tick(dt);
+
+        let scripted: Vec<(hecs::Entity, ScriptComponent, Option<Transform>)> = world
+            .world()
+            .query::<(hecs::Entity, &ScriptComponent, Option<&Transform>)>()
+            .iter()
+            .filter(|(_, script, _)| script.enabled)
+            .map(|(entity, script, transform)| (entity, script.clone(), transform.copied()))
+            .collect();
+
+        let live: HashSet<hecs::Entity> = scripted.iter().map(|(e, _, _)| *e).collect();
+        self.instances.retain(|entity, _| live.contains(entity));
+        self.failed.retain(|entity, _| live.contains(entity));
+
+        for (entity, script, transform) in scripted {
+            match self.run_entity(entity, &script, transform.unwrap_or_default(), input, dt) {
+                Ok(new_transform) => {
+                    if let Ok(mut t) = world.world_mut().get::<&mut Transform>(entity) {
+                        *t = new_transform;
+                    }
+                }
+                Err(e) => self.errors.push(format!("{}: {e}", script.path.display())),
+            }
+        }
+        self.lua.expire_registry_values();
+    }
+
+    fn run_entity(
+        &mut self,
+        entity: hecs::Entity,
+        script: &ScriptComponent,
+        transform: Transform,
+        input: &ScriptInput,
+        dt: f32,
+    ) -> Result<Transform, String> {
+        crate::profile_function!();
+        let reload = self
+            .instances
+            .get(&entity)
+            .is_none_or(|inst| inst.path != script.path);
+        if reload {
+            let modified = modified_time(&script.path);
+            if self
+                .failed
+                .get(&entity)
+                .is_some_and(|failed| failed.path == script.path && failed.modified == modified)
+            {
+                // Already reported; waits for the file to change
+                return Ok(transform);
+            }
+            let env = match self.load_instance(entity, script) {
+                Ok(env) => env,
+                Err(e) => {
+                    self.failed.insert(
+                        entity,
+                        FailedLoad {
+                            path: script.path.clone(),
+                            modified,
+                        },
+                    );
+                    return Err(e);
+                }
+            };
+            self.failed.remove(&entity);
+            self.instances.insert(
+                entity,
+                ScriptInstance {
+                    path: script.path.clone(),
+                    env,
+                    started: false,
+                },
+            );
+        }
+        let Some(instance) = self.instances.get_mut(&entity) else {
+            return Ok(transform);
+        };
+        let env: Table = self
+            .lua
+            .registry_value(&instance.env)
+            .map_err(|e| e.to_string())?;
+
+        write_transform(&self.lua, &env, &transform).map_err(|e| e.to_string())?;
+        write_input(&self.lua, &env, input).map_err(|e| e.to_string())?;
+
+        if !instance.started {
+            instance.started = true;
+            if let Ok(Some(on_start)) = env.get::<Option<Function>>("on_start") {
+                on_start.call::<()>(()).map_err(|e| e.to_string())?;
+            }
+        }
+        if let Ok(Some(on_update)) = env.get::<Option<Function>>("on_update") {
+            on_update.call::<()>(dt).map_err(|e| e.to_string())?;
+        }
+        if let Ok(Some(tick)) = env.get::<Option<Function>>("__tick_scheduled") {
+            tick.call::<()>(dt).map_err(|e| e.to_string())?;
+        }
+
+        read_transform(&env, &transform).map_err(|e| e.to_string())
+    }
+
+    fn load_instance(
+        &self,
+        entity: hecs::Entity,
+        script: &ScriptComponent,
+    ) -> Result<RegistryKey, String> {
+        let source = std::fs::read_to_string(&script.path)
+            .map_err(|e| format!("failed to read script: {e}"))?;
+        let env: Table = self
+            .lua
+            .load("return setmetatable({}, { __index = _G })")
+            .eval()
+            .map_err(|e| e.to_string())?;
+        let entity_table = self.lua.create_table().map_err(|e| e.to_string())?;
+        entity_table
+            .set("id", entity.to_bits().get())
+            .map_err(|e| e.to_string())?;
+        env.set("entity", entity_table).map_err(|e| e.to_string())?;
+        write_debug_draw(&self.lua, &env, &self.debug).map_err(|e| e.to_string())?;
+        write_screenshot(&self.lua, &env, &self.screenshots).map_err(|e| e.to_string())?;
+        write_set_game_state(&self.lua, &env, &self.state_request).map_err(|e| e.to_string())?;
+        write_print(&self.lua, &env, &script.path).map_err(|e| e.to_string())?;
+        self.lua
+            .load(SCRIPT_PRELUDE)
+            .set_name("prelude")
+            .set_environment(env.clone())
+            .exec()
+            .map_err(|e| e.to_string())?;
+        self.lua
+            .load(&source)
+            .set_name(script.path.to_string_lossy())
+            .set_environment(env.clone())
+            .exec()
+            .map_err(|e| format!("Lua compile error: {e}"))?;
+        self.lua
+            .create_registry_value(env)
+            .map_err(|e| format!("Lua registry error: {e}"))
+    }
+}
+
+fn vec3_table(lua: &Lua, v: Vec3) -> mlua::Result<Table> {
+    let t = lua.create_table()?;
+    t.set("x", v.x)?;
+    t.set("y", v.y)?;
+    t.set("z", v.z)?;
+    Ok(t)
+}
+
+fn table_vec3(t: &Table) -> mlua::Result<Vec3> {
+    Ok(Vec3::new(t.get("x")?, t.get("y")?, t.get("z")?))
+}
+
+/// Rotation as the script sees it: XYZ Euler angles in degrees
+fn euler_degrees(rotation: Quat) -> Vec3 {
+    let (rx, ry, rz) = rotation.to_euler(EulerRot::XYZ);
+    Vec3::new(rx.to_degrees(), ry.to_degrees(), rz.to_degrees())
+}
+
+fn write_transform(lua: &Lua, env: &Table, transform: &Transform) -> mlua::Result<()> {
+    let t = lua.create_table()?;
+    t.set("position", vec3_table(lua, transform.position)?)?;
+    t.set(
+        "rotation",
+        vec3_table(lua, euler_degrees(transform.rotation))?,
+    )?;
+    t.set("scale", vec3_table(lua, transform.scale)?)?;
+    env.set("transform", t)
+}
+
+/// Transform after the callbacks. The rotation only goes back through Euler
+/// angles when the script changed them, so an untouched quaternion neither
+/// drifts nor flips between equivalent angle sets
+fn read_transform(env: &Table, before: &Transform) -> mlua::Result<Transform> {
+    let t: Table = env.get("transform")?;
+    let rotation = table_vec3(&t.get("rotation")?)?;
+    let rotation = if rotation == euler_degrees(before.rotation) {
+        before.rotation
+    } else {
+        Quat::from_euler(
+            EulerRot::XYZ,
+            rotation.x.to_radians(),
+            rotation.y.to_radians(),
+            rotation.z.to_radians(),
+        )
+    };
+    Ok(Transform {
+        position: table_vec3(&t.get("position")?)?,
+        rotation,
+        scale: table_vec3(&t.get("scale")?)?,
+    })
+}
+
+fn write_input(lua: &Lua, env: &Table, input: &ScriptInput) -> mlua::Result<()> {
+    let t = lua.create_table()?;
+    let axis = lua.create_table()?;
+    axis.set("x", input.axis[0])?;
+    axis.set("y", input.axis[1])?;
+    t.set("axis", axis)?;
+    let look = lua.create_table()?;
+    look.set("x", input.look[0])?;
+    look.set("y", input.look[1])?;
+    t.set("look", look)?;
+    t.set("action", input.action)?;
+    let keys = input.keys_down.clone();
+    t.set(
+        "key_down",
+        lua.create_function(move |_, name: String| Ok(keys.contains(&name)))?,
+    )?;
+    env.set("input", t)?;
+    env.set("game_state", input.game_state.name())
+}
+
+fn table_color(color: Option<Table>) -> mlua::Result<[f32; 4]> {
+    let Some(t) = color else {
+        return Ok(DEBUG_DEFAULT_COLOR);
+    };
+    Ok([
+        t.get::<Option<f32>>("r")?.unwrap_or(1.0),
+        t.get::<Option<f32>>("g")?.unwrap_or(1.0),
+        t.get::<Option<f32>>("b")?.unwrap_or(1.0),
+        t.get::<Option<f32>>("a")?.unwrap_or(1.0),
+    ])
+}
+
+fn push_debug_shape(
+    debug: &Rc<RefCell<DebugDraw>>,
+    shape: DebugShape,
+    color: Option<Table>,
+    duration: Option<f32>,
+) -> mlua::Result<()> {
+    let color = table_color(color)?;
+    debug
+        .borrow_mut()
+        .push(shape, color, duration.unwrap_or(0.0));
+    Ok(())
+}
+
+fn write_debug_draw(lua: &Lua, env: &Table, debug: &Rc<RefCell<DebugDraw>>) -> mlua::Result<()> {
+    let d = debug.clone();
+    env.set(
+        "debug_line",
+        lua.create_function(
+            move |_, (a, b, color, duration): (Table, Table, Option<Table>, Option<f32>)| {
+                let shape = DebugShape::Line {
+                    start: table_vec3(&a)?,
+                    end: table_vec3(&b)?,
+                };
+                push_debug_shape(&d, shape, color, duration)
+            },
+        )?,
+    )?;
+    let d = debug.clone();
+    env.set(
+        "debug_arrow",
+        lua.create_function(
+            move |_, (a, b, color, duration): (Table, Table, Option<Table>, Option<f32>)| {
+                let shape = DebugShape::Arrow {
+                    start: table_vec3(&a)?,
+                    end: table_vec3(&b)?,
+                };
+                push_debug_shape(&d, shape, color, duration)
+            },
+        )?,
+    )?;
+    let d = debug.clone();
+    env.set(
+        "debug_box",
+        lua.create_function(
+            move |_, (center, half, color, duration): (Table, Table, Option<Table>, Option<f32>)| {
+                let shape = DebugShape::WireBox {
+                    center: table_vec3(&center)?,
+                    half_extents: table_vec3(&half)?,
+                    rotation: Quat::IDENTITY,
+                };
+                push_debug_shape(&d, shape, color, duration)
+            },
+        )?,
+    )?;
+    let d = debug.clone();
+    env.set(
+        "debug_sphere",
+        lua.create_function(
+            move |_, (center, radius, color, duration): (Table, f32, Option<Table>, Option<f32>)| {
+                let shape = DebugShape::Sphere {
+                    center: table_vec3(&center)?,
+                    radius,
+                };
+                push_debug_shape(&d, shape, color, duration)
+            },
+        )?,
+    )?;
+    let d = debug.clone();
+    env.set(
+        "debug_text",
+        lua.create_function(
+            move |_,
+                  (position, text, color, duration): (
+                Table,
+                String,
+                Option<Table>,
+                Option<f32>,
+            )| {
+                let shape = DebugShape::Text {
+                    position: table_vec3(&position)?,
+                    text,
+                };
+                push_debug_shape(&d, shape, color, duration)
+            },
+        )?,
+    )
+}
+
+fn write_screenshot(
+    lua: &Lua,
+    env: &Table,
+    screenshots: &Rc<RefCell<Vec<ScreenshotRequest>>>,
+) -> mlua::Result<()> {
+    let requests = screenshots.clone();
+    env.set(
+        "screenshot",
+        lua.create_function(
+            move |_, (path, width, height): (String, Option<u32>, Option<u32>)| {
+                requests.borrow_mut().push(ScreenshotRequest {
+                    path: PathBuf::from(path),
+                    width,
+                    height,
+                });
+                Ok(())
+            },
+        )?,
+    )
+}
+
+fn write_set_game_state(
+    lua: &Lua,
+    env: &Table,
+    request: &Rc<RefCell<Option<GameState>>>,
+) -> mlua::Result<()> {
+    let request = request.clone();
+    env.set(
+        "set_game_state",
+        lua.create_function(move |_, name: String| {
+            let state = GameState::from_name(&name)
+                .ok_or_else(|| mlua::Error::RuntimeError(format!("unknown game state {name}")))?;
+            *request.borrow_mut() = Some(state);
+            Ok(())
+        })?,
+    )
+}
+
+fn write_print(lua: &Lua, env: &Table, path: &Path) -> mlua::Result<()> {
+    let path = path.display().to_string();
+    env.set(
+        "print",
+        lua.create_function(move |lua, args: mlua::Variadic<mlua::Value>| {
+            let text = args
+                .iter()
+                .map(|value| value.to_string().unwrap_or_else(|_| format!("{value:?}")))
+                .collect::<Vec<_>>()
+                .join("\t");
+            match lua.inspect_stack(1).map(|frame| frame.curr_line()) {
+                Some(line) if line > 0 => log::info!(target: "script", "{path}:{line}: {text}"),
+                _ => log::info!(target: "script", "{path}: {text}"),
+            }
+            Ok(())
+        })?,
+    )
+}
@@ -0,0 +1,510 @@
+//! Spatial partitioning - AABBs, a bounding volume hierarchy and ray queries
+//!
+//! `Bvh<T>` is a generic tree over (item, bounds) pairs. `SpatialIndex` keeps
+//! one over keyed bounds that move: moved items refit the tree, only added or
+//! removed keys rebuild it. It answers `raycast`, `query_aabb` and `query`
+//! (any box test, e.g. a frustum) for gameplay code over entities with
+//! `Transform` + `Bounds`, and for the editor's viewport over its objects.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use glam::{Mat4, Vec3};
+
+use crate::components::Transform;
+use crate::ecs::EngineWorld;
+
+/// Axis-aligned bounding box
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Default for Aabb {
+    fn default() -> Self {
+        Self::from_center_half_extents(Vec3::ZERO, Vec3::splat(0.5))
+    }
+}
+
+impl Aabb {
+    pub fn new(min: Vec3, max: Vec3) -> Self {
+        Self {
+            min: min.min(max),
+            max: min.max(max),
+        }
+    }
+
+    pub fn from_center_half_extents(center: Vec3, half_extents: Vec3) -> Self {
+        Self::new(center - half_extents, center + half_extents)
+    }
+
+    /// Smallest box containing every point; None for an empty iterator
+    pub fn from_points(points: impl IntoIterator<Item = Vec3>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let (min, max) = points.fold((first, first), |(min, max), p| (min.min(p), max.max(p)));
+        Some(Self { min, max })
+    }
+
+    pub fn center(&self) -> Vec3 {
+        (self.min + self.max) * 0.5
+    }
+
+    pub fn half_extents(&self) -> Vec3 {
+        (self.max - self.min) * 0.5
+    }
+
+    pub fn union(&self, other: &Aabb) -> Aabb {
+        Aabb {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+
+    pub fn intersects(&self, other: &Aabb) -> bool {
+        self.min.cmple(other.max).all() && self.max.cmpge(other.min).all()
+    }
+
+    pub fn contains_point(&self, p: Vec3) -> bool {
+        self.min.cmple(p).all() && self.max.cmpge(p).all()
+    }
+
+    /// Bounds of this box after an affine transform
+    pub fn transformed(&self, matrix: &Mat4) -> Aabb {
+        let c = self.center();
+        let e = self.half_extents();
+        let center = matrix.transform_point3(c);
+        let abs = Mat4::from_cols(
+            matrix.x_axis.abs(),
+            matrix.y_axis.abs(),
+            matrix.z_axis.abs(),
+            glam::Vec4::W,
+        );
+        let extents = abs.transform_vector3(e);
+        Aabb::from_center_half_extents(center, extents)
+    }
+
+    /// Distance along the ray to the box entry (0 if the origin is inside)
+    pub fn ray_distance(&self, ray: &Ray) -> Option<f32> {
+        let inv = ray.direction.recip();
+        let t1 = (self.min - ray.origin) * inv;
+        let t2 = (self.max - ray.origin) * inv;
+        let t_near = t1.min(t2).max_element();
+        let t_far = t1.max(t2).min_element();
+        if t_far < 0.0 || t_near > t_far || t_near.is_nan() {
+            return None;
+        }
+        Some(t_near.max(0.0))
+    }
+}
+
+/// Ray with a normalized direction
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ray {
+    pub origin: Vec3,
+    pub direction: Vec3,
+}
+
+impl Ray {
+    pub fn new(origin: Vec3, direction: Vec3) -> Self {
+        Self {
+            origin,
+            direction: direction.normalize_or(Vec3::NEG_Z),
+        }
+    }
+
+    pub fn at(&self, distance: f32) -> Vec3 {
+        self.origin + self.direction * distance
+    }
+
+    /// Möller–Trumbore; distance to the triangle, both faces count
+    pub fn triangle_distance(&self, a: Vec3, b: Vec3, c: Vec3) -> Option<f32> {
+        let e1 = b - a;
+        let e2 = c - a;
+        let p = self.direction.cross(e2);
+        let det = e1.dot(p);
+        if det.abs() < 1e-8 {
+            return None;
+        }
+        let inv_det = 1.0 / det;
+        let s = self.origin - a;
+        let u = s.dot(p) * inv_det;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+        let q = s.cross(e1);
+        let v = self.direction.dot(q) * inv_det;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+        let t = e2.dot(q) * inv_det;
+        (t >= 0.0).then_some(t)
+    }
+}
+
+/// Result of a ray query
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit<T> {
+    pub item: T,
+    pub distance: f32,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum BvhNode {
+    Leaf {
+        bounds: Aabb,
+        first: usize,
+        count: usize,
+    },
+    Branch {
+        bounds: Aabb,
+        left: usize,
+        right: usize,
+    },
+}
+
+impl BvhNode {
+    fn bounds(&self) -> &Aabb {
+        match self {
+            BvhNode::Leaf { bounds, .. } | BvhNode::Branch { bounds, .. } => bounds,
+        }
+    }
+}
+
+const BVH_LEAF_SIZE: usize = 4;
+
+/// Bounding volume hierarchy over (item, bounds) pairs, rebuilt on demand
+#[derive(Debug, Clone)]
+pub struct Bvh<T: Copy> {
+    items: Vec<(T, Aabb)>,
+    nodes: Vec<BvhNode>,
+}
+
+impl<T: Copy> Default for Bvh<T> {
+    fn default() -> Self {
+        Self {
+            items: Vec::new(),
+            nodes: Vec::new(),
+        }
+    }
+}
+
+impl<T: Copy> Bvh<T> {
+    /// Build the tree (median split on the longest axis)
+    pub fn build(items: Vec<(T, Aabb)>) -> Self {
+        let mut bvh = Self {
+            items,
+            nodes: Vec::new(),
+        };
+        if !bvh.items.is_empty() {
+            let count = bvh.items.len();
+            bvh.build_node(0, count);
+        }
+        bvh
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Bounds of everything in the tree
+    pub fn bounds(&self) -> Option<Aabb> {
+        self.nodes.first().map(|n| *n.bounds())
+    }
+
+    fn build_node(&mut self, first: usize, count: usize) -> usize {
+        let slice = &mut self.items[first..first + count];
+        let bounds = slice
+            .iter()
+            .skip(1)
+            .fold(slice[0].1, |acc, (_, b)| acc.union(b));
+        let index = self.nodes.len();
+        if count <= BVH_LEAF_SIZE {
+            self.nodes.push(BvhNode::Leaf {
+                bounds,
+                first,
+                count,
+            });
+            return index;
+        }
+        let size = bounds.max - bounds.min;
+        let axis = if size.x >= size.y && size.x >= size.z {
+            0
+        } else if size.y >= size.z {
+            1
+        } else {
+            2
+        };
+        slice.sort_by(|a, b| a.1.center()[axis].total_cmp(&b.1.center()[axis]));
+        // Placeholder, patched once both children exist
+        self.nodes.push(BvhNode::Leaf {
+            bounds,
+            first,
+            count,
+        });
+        let half = count / 2;
+        let left = self.build_node(first, half);
+        let right = self.build_node(first + half, count - half);
+        self.nodes[index] = BvhNode::Branch {
+            bounds,
+            left,
+            right,
+        };
+        index
+    }
+
+    /// Recompute every node's bounds from the items, keeping the tree shape;
+    /// cheaper than `build` when items moved but none were added or removed
+    fn refit(&mut self) {
+        // Children are always pushed after their parent
+        for index in (0..self.nodes.len()).rev() {
+            let bounds = match self.nodes[index] {
+                BvhNode::Leaf { first, count, .. } => self.items[first + 1..first + count]
+                    .iter()
+                    .fold(self.items[first].1, |acc, (_, b)| acc.union(b)),
+                BvhNode::Branch { left, right, .. } => {
+                    self.nodes[left].bounds().union(self.nodes[right].bounds())
+                }
+            };
+            match &mut self.nodes[index] {
+                BvhNode::Leaf { bounds: b, .. } | BvhNode::Branch { bounds: b, .. } => *b = bounds,
+            }
+        }
+    }
+
+    /// Closest item whose bounds the ray hits within `max_distance`
+    pub fn raycast(&self, ray: &Ray, max_distance: f32) -> Option<RayHit<T>> {
+        self.raycast_all(ray, max_distance).into_iter().next()
+    }
+
+    /// Every item whose bounds the ray hits, nearest first
+    pub fn raycast_all(&self, ray: &Ray, max_distance: f32) -> Vec<RayHit<T>> {
+        let mut hits = Vec::new();
+        if self.nodes.is_empty() {
+            return hits;
+        }
+        let mut stack = vec![0];
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if node
+                .bounds()
+                .ray_distance(ray)
+                .is_none_or(|d| d > max_distance)
+            {
+                continue;
+            }
+            match *node {
+                BvhNode::Leaf { first, count, .. } => {
+                    for (item, bounds) in &self.items[first..first + count] {
+                        if let Some(distance) =
+                            bounds.ray_distance(ray).filter(|d| *d <= max_distance)
+                        {
+                            hits.push(RayHit {
+                                item: *item,
+                                distance,
+                            });
+                        }
+                    }
+                }
+                BvhNode::Branch { left, right, .. } => {
+                    stack.push(left);
+                    stack.push(right);
+                }
+            }
+        }
+        hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        hits
+    }
+
+    /// Every item whose bounds overlap `area`
+    pub fn query_aabb(&self, area: &Aabb) -> Vec<T> {
+        self.query(|bounds| bounds.intersects(area))
+    }
+
+    /// Every item whose bounds pass `test`. Subtrees whose bounds fail it are
+    /// skipped, so the test must be conservative (a box that fails contains no
+    /// box that passes), as overlap and frustum tests are
+    pub fn query(&self, test: impl Fn(&Aabb) -> bool) -> Vec<T> {
+        let mut out = Vec::new();
+        if self.nodes.is_empty() {
+            return out;
+        }
+        let mut stack = vec![0];
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !test(node.bounds()) {
+                continue;
+            }
+            match *node {
+                BvhNode::Leaf { first, count, .. } => {
+                    out.extend(
+                        self.items[first..first + count]
+                            .iter()
+                            .filter(|(_, b)| test(b))
+                            .map(|(item, _)| *item),
+                    );
+                }
+                BvhNode::Branch { left, right, .. } => {
+                    stack.push(left);
+                    stack.push(right);
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Bounds component - local-space box, moved by the entity Transform
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Bounds {
+    pub local: Aabb,
+}
+
+impl Bounds {
+    pub fn new(local: Aabb) -> Self {
+        Self { local }
+    }
+}
+
+/// BVH over keyed world bounds. `set` and `remove` queue changes and
+/// `refresh` applies them; queries see the bounds of the last `refresh`
+#[derive(Debug, Clone)]
+pub struct SpatialIndex<K: Copy + Eq + Hash = hecs::Entity> {
+    bvh: Bvh<K>,
+    bounds: HashMap<K, Aabb>,
+    // Position of each key in the tree's items
+    slots: HashMap<K, usize>,
+    // Keys were added or removed since the last refresh
+    rebuild: bool,
+    // Bounds of keys already in the tree moved since the last refresh
+    refit: bool,
+}
+
+impl<K: Copy + Eq + Hash> Default for SpatialIndex<K> {
+    fn default() -> Self {
+        Self {
+            bvh: Bvh::default(),
+            bounds: HashMap::new(),
+            slots: HashMap::new(),
+            rebuild: false,
+            refit: false,
+        }
+    }
+}
+
+impl SpatialIndex {
+    /// Track every entity with Transform + Bounds; entities that lost either
+    /// leave the index
+    pub fn update(&mut self, world: &EngineWorld) {
+        crate::profile_function!();
+        let mut live = std::collections::HashSet::new();
+        for (entity, transform, bounds) in world
+            .world()
+            .query::<(hecs::Entity, &Transform, &Bounds)>()
+            .iter()
+        {
+            live.insert(entity);
+            self.set(entity, bounds.local.transformed(&transform.to_mat4()));
+        }
+        self.retain(|entity| live.contains(entity));
+        self.refresh();
+    }
+}
+
+impl<K: Copy + Eq + Hash> SpatialIndex<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `key` or move it to new world bounds
+    pub fn set(&mut self, key: K, bounds: Aabb) {
+        if self.bounds.insert(key, bounds) == Some(bounds) {
+            return;
+        }
+        match self.slots.get(&key) {
+            Some(&slot) => {
+                self.bvh.items[slot].1 = bounds;
+                self.refit = true;
+            }
+            None => self.rebuild = true,
+        }
+    }
+
+    pub fn remove(&mut self, key: K) {
+        if self.bounds.remove(&key).is_some() {
+            self.rebuild = true;
+        }
+    }
+
+    /// Keep only the keys `keep` accepts
+    pub fn retain(&mut self, mut keep: impl FnMut(&K) -> bool) {
+        let before = self.bounds.len();
+        self.bounds.retain(|key, _| keep(key));
+        if self.bounds.len() != before {
+            self.rebuild = true;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Apply the changes since the last refresh: rebuild when keys were
+    /// added or removed, else refit the moved bounds
+    pub fn refresh(&mut self) {
+        crate::profile_function!();
+        if self.rebuild {
+            self.bvh = Bvh::build(self.bounds.iter().map(|(k, b)| (*k, *b)).collect());
+            self.slots = self
+                .bvh
+                .items
+                .iter()
+                .enumerate()
+                .map(|(slot, (key, _))| (*key, slot))
+                .collect();
+        } else if self.refit {
+            self.bvh.refit();
+        }
+        self.rebuild = false;
+        self.refit = false;
+    }
+
+    /// World bounds of `key` as last `set`
+    pub fn bounds(&self, key: K) -> Option<Aabb> {
+        self.bounds.get(&key).copied()
+    }
+
+    /// Closest key whose world bounds the ray hits
+    pub fn raycast(&self, origin: Vec3, direction: Vec3) -> Option<RayHit<K>> {
+        self.bvh
+            .raycast(&Ray::new(origin, direction), f32::INFINITY)
+    }
+
+    /// Every key whose world bounds the ray hits, nearest first
+    pub fn raycast_all(&self, origin: Vec3, direction: Vec3) -> Vec<RayHit<K>> {
+        self.bvh
+            .raycast_all(&Ray::new(origin, direction), f32::INFINITY)
+    }
+
+    pub fn query_aabb(&self, bounds: &Aabb) -> Vec<K> {
+        self.bvh.query_aabb(bounds)
+    }
+
+    /// Every key whose world bounds pass `test` (see `Bvh::query`)
+    pub fn query(&self, test: impl Fn(&Aabb) -> bool) -> Vec<K> {
+        self.bvh.query(test)
+    }
+
+    pub fn len(&self) -> usize {
+        self.bvh.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bvh.is_empty()
+    }
+}
@@ -0,0 +1,227 @@
+//! Game state machine - `States<T>` resource and a scheduler that runs
+//! on-enter / on-exit / on-update system sets per state
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use crate::ecs::EngineWorld;
+
+/// Any fieldless enum can be used as a state
+pub trait StateValue: Copy + Eq + Hash + Debug + 'static {}
+
+impl<T: Copy + Eq + Hash + Debug + 'static> StateValue for T {}
+
+/// Standard flow for games that do not need their own state enum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum GameState {
+    #[default]
+    Menu,
+    Loading,
+    Playing,
+    Paused,
+    GameOver,
+}
+
+impl GameState {
+    pub const ALL: [GameState; 5] = [
+        GameState::Menu,
+        GameState::Loading,
+        GameState::Playing,
+        GameState::Paused,
+        GameState::GameOver,
+    ];
+
+    /// Name scripts use for the state (`"Playing"`, `"Paused"`...)
+    pub fn name(self) -> &'static str {
+        match self {
+            GameState::Menu => "Menu",
+            GameState::Loading => "Loading",
+            GameState::Playing => "Playing",
+            GameState::Paused => "Paused",
+            GameState::GameOver => "GameOver",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|state| state.name() == name)
+    }
+}
+
+/// Current state plus a queued transition, applied by the scheduler at the start of a frame
+#[derive(Debug, Clone)]
+pub struct States<T: StateValue> {
+    current: T,
+    previous: Option<T>,
+    next: Option<T>,
+}
+
+impl<T: StateValue + Default> Default for States<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: StateValue> States<T> {
+    pub fn new(initial: T) -> Self {
+        Self {
+            current: initial,
+            previous: None,
+            next: None,
+        }
+    }
+
+    pub fn current(&self) -> T {
+        self.current
+    }
+
+    /// State active before the last transition
+    pub fn previous(&self) -> Option<T> {
+        self.previous
+    }
+
+    pub fn is(&self, state: T) -> bool {
+        self.current == state
+    }
+
+    /// Queue a transition; the last call in a frame wins
+    pub fn set(&mut self, next: T) {
+        self.next = Some(next);
+    }
+
+    pub fn pending(&self) -> Option<T> {
+        self.next
+    }
+
+    /// Apply the queued transition, returning (from, to). Setting the current state is a no-op.
+    pub fn apply_transition(&mut self) -> Option<(T, T)> {
+        let next = self.next.take()?;
+        if next == self.current {
+            return None;
+        }
+        let from = self.current;
+        self.previous = Some(from);
+        self.current = next;
+        Some((from, next))
+    }
+}
+
+type StateSystem<T> = Box<dyn FnMut(&mut EngineWorld, &mut States<T>, f32)>;
+
+/// Maximum chained transitions applied in one frame (enter systems may set another state)
+const MAX_TRANSITIONS_PER_FRAME: usize = 8;
+
+/// Runs system sets according to `States<T>`
+pub struct StateScheduler<T: StateValue> {
+    on_enter: HashMap<T, Vec<StateSystem<T>>>,
+    on_exit: HashMap<T, Vec<StateSystem<T>>>,
+    on_update: HashMap<T, Vec<StateSystem<T>>>,
+    always: Vec<StateSystem<T>>,
+    entered_initial: bool,
+}
+
+impl<T: StateValue> Default for StateScheduler<T> {
+    fn default() -> Self {
+        Self {
+            on_enter: HashMap::new(),
+            on_exit: HashMap::new(),
+            on_update: HashMap::new(),
+            always: Vec::new(),
+            entered_initial: false,
+        }
+    }
+}
+
+impl<T: StateValue> StateScheduler<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run once when the state becomes active
+    pub fn on_enter(
+        &mut self,
+        state: T,
+        system: impl FnMut(&mut EngineWorld, &mut States<T>, f32) + 'static,
+    ) -> &mut Self {
+        self.on_enter
+            .entry(state)
+            .or_default()
+            .push(Box::new(system));
+        self
+    }
+
+    /// Run once when the state is left
+    pub fn on_exit(
+        &mut self,
+        state: T,
+        system: impl FnMut(&mut EngineWorld, &mut States<T>, f32) + 'static,
+    ) -> &mut Self {
+        self.on_exit
+            .entry(state)
+            .or_default()
+            .push(Box::new(system));
+        self
+    }
+
+    /// Run every frame while the state is active
+    pub fn on_update(
+        &mut self,
+        state: T,
+        system: impl FnMut(&mut EngineWorld, &mut States<T>, f32) + 'static,
+    ) -> &mut Self {
+        self.on_update
+            .entry(state)
+            .or_default()
+            .push(Box::new(system));
+        self
+    }
+
+    /// Run every frame regardless of state
+    pub fn always(
+        &mut self,
+        system: impl FnMut(&mut EngineWorld, &mut States<T>, f32) + 'static,
+    ) -> &mut Self {
+        self.always.push(Box::new(system));
+        self
+    }
+
+    /// Apply pending transitions (exit then enter), then run the update sets
+    pub fn run(&mut self, world: &mut EngineWorld, states: &mut States<T>, dt: f32) {
+        crate::profile_function!();
+        if !self.entered_initial {
+            self.entered_initial = true;
+            let initial = states.current();
+            Self::run_set(self.on_enter.get_mut(&initial), world, states, dt);
+        }
+
+        for _ in 0..MAX_TRANSITIONS_PER_FRAME {
+            let Some((from, to)) = states.apply_transition() else {
+                break;
+            };
+            Self::run_set(self.on_exit.get_mut(&from), world, states, dt);
+            Self::run_set(self.on_enter.get_mut(&to), world, states, dt);
+        }
+
+        let current = states.current();
+        Self::run_set(self.on_update.get_mut(&current), world, states, dt);
+        for system in &mut self.always {
+            system(world, states, dt);
+        }
+    }
+
+    /// Forget that the initial state was entered (e.g. when restarting the game)
+    pub fn reset(&mut self) {
+        self.entered_initial = false;
+    }
+
+    fn run_set(
+        set: Option<&mut Vec<StateSystem<T>>>,
+        world: &mut EngineWorld,
+        states: &mut States<T>,
+        dt: f32,
+    ) {
+        for system in set.into_iter().flatten() {
+            system(world, states, dt);
+        }
+    }
+}
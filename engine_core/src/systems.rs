@@ -22,6 +22,7 @@ impl MovementSystem {
     }
 
     pub fn update(&mut self, world: &mut EngineWorld, dt: f32) {
+        crate::profile_function!();
         // Iterate over entities with Transform and Player components
         for (transform, _player) in &mut world.world_mut().query::<(&mut Transform, &Player)>() {
             // Player movement logic - can be extended with input
@@ -52,6 +53,7 @@ pub struct RenderSystem;
 
 impl RenderSystem {
     pub fn update(&mut self, world: &EngineWorld) -> Vec<Renderable> {
+        crate::profile_function!();
         let mut renderables = Vec::new();
 
         for (transform, mesh_renderer) in &mut world.world().query::<(&Transform, &MeshRenderer)>()
@@ -68,6 +70,37 @@ impl RenderSystem {
 
         renderables
     }
+
+    /// Like `update`, but entities with a `LodGroup` use the level matching their
+    /// distance to `camera_position` (and are skipped when the group culls them)
+    pub fn update_lod(&mut self, world: &EngineWorld, camera_position: Vec3) -> Vec<Renderable> {
+        crate::profile_function!();
+        let mut renderables = Vec::new();
+
+        for (transform, mesh_renderer, lod) in
+            &mut world
+                .world()
+                .query::<(&Transform, &MeshRenderer, Option<&LodGroup>)>()
+        {
+            let mut mesh = mesh_renderer.mesh;
+            if let Some(lod) = lod.filter(|lod| !lod.levels().is_empty()) {
+                let distance = transform.position.distance(camera_position);
+                let Some(level) = lod.select(distance) else {
+                    continue;
+                };
+                mesh = lod.levels()[level].mesh;
+            }
+            if mesh.is_valid() {
+                renderables.push(Renderable {
+                    transform: *transform,
+                    mesh,
+                    material: mesh_renderer.material,
+                });
+            }
+        }
+
+        renderables
+    }
 }
 
 /// Renderable data for the renderer
@@ -78,6 +111,89 @@ pub struct Renderable {
     pub material: MaterialHandle,
 }
 
+/// Light system - collects the dynamic lights of the world
+pub struct LightSystem;
+
+impl LightSystem {
+    pub fn update(&mut self, world: &EngineWorld) -> Vec<LightInstance> {
+        crate::profile_function!();
+        let mut lights = Vec::new();
+        let world = world.world();
+
+        for (transform, light) in &mut world.query::<(&Transform, &DirectionalLight)>() {
+            lights.push(LightInstance {
+                kind: LightKind::Directional,
+                position: transform.position,
+                direction: transform.forward(),
+                color: light.color,
+                intensity: light.intensity,
+                range: f32::INFINITY,
+                inner_angle: 0.0,
+                outer_angle: 0.0,
+            });
+        }
+        for (transform, light) in &mut world.query::<(&Transform, &PointLight)>() {
+            lights.push(LightInstance {
+                kind: LightKind::Point,
+                position: transform.position,
+                direction: transform.forward(),
+                color: light.color,
+                intensity: light.intensity,
+                range: light.range,
+                inner_angle: 0.0,
+                outer_angle: 0.0,
+            });
+        }
+        for (transform, light) in &mut world.query::<(&Transform, &SpotLight)>() {
+            lights.push(LightInstance {
+                kind: LightKind::Spot,
+                position: transform.position,
+                direction: transform.forward(),
+                color: light.color,
+                intensity: light.intensity,
+                range: light.range,
+                inner_angle: light.inner_angle,
+                outer_angle: light.outer_angle,
+            });
+        }
+
+        lights
+    }
+}
+
+/// Kind of a collected light
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LightKind {
+    Directional,
+    Point,
+    Spot,
+}
+
+/// World-space light data for the renderer
+#[derive(Debug, Clone, Copy)]
+pub struct LightInstance {
+    pub kind: LightKind,
+    pub position: Vec3,
+    pub direction: Vec3,
+    pub color: Vec3,
+    pub intensity: f32,
+    pub range: f32,
+    pub inner_angle: f32,
+    pub outer_angle: f32,
+}
+
+/// Projection used by `CameraSystem`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum CameraProjection {
+    #[default]
+    Perspective,
+    /// Shows `size` world units vertically
+    Orthographic { size: f32 },
+    /// Orthographic for 2D pixel art: one texel (`1 / pixels_per_unit` world units)
+    /// covers exactly `zoom` screen pixels and the camera snaps to that grid
+    PixelPerfect { pixels_per_unit: f32, zoom: u32 },
+}
+
 /// Camera system - manages camera view and projection
 pub struct CameraSystem {
     pub position: Vec3,
@@ -87,6 +203,9 @@ pub struct CameraSystem {
     pub aspect_ratio: f32,
     pub near: f32,
     pub far: f32,
+    pub projection: CameraProjection,
+    /// Viewport height in pixels, used by `CameraProjection::PixelPerfect`
+    pub viewport_height: u32,
 }
 
 impl Default for CameraSystem {
@@ -99,6 +218,8 @@ impl Default for CameraSystem {
             aspect_ratio: 16.0 / 9.0,
             near: 0.1,
             far: 100.0,
+            projection: CameraProjection::Perspective,
+            viewport_height: 1080,
         }
     }
 }
@@ -111,16 +232,51 @@ impl CameraSystem {
         camera
     }
 
-    /// Get view matrix
+    /// Get view matrix (snapped to the texel grid in pixel-perfect mode)
     pub fn view_matrix(&self) -> Mat4 {
-        Mat4::look_at_rh(self.position, self.target, self.up)
+        let CameraProjection::PixelPerfect {
+            pixels_per_unit,
+            zoom,
+        } = self.projection
+        else {
+            return Mat4::look_at_rh(self.position, self.target, self.up);
+        };
+        let texel = 1.0 / (pixels_per_unit.max(1e-3) * zoom.max(1) as f32);
+        let snapped = Vec3::new(
+            (self.position.x / texel).round() * texel,
+            (self.position.y / texel).round() * texel,
+            self.position.z,
+        );
+        let offset = snapped - self.position;
+        Mat4::look_at_rh(snapped, self.target + offset, self.up)
     }
 
-    /// Get projection matrix (perspective)
+    /// Get projection matrix
     pub fn projection_matrix(&self) -> Mat4 {
-        Mat4::perspective_rh_gl(
-            self.fov.to_radians(),
-            self.aspect_ratio,
+        let half_height = match self.projection {
+            CameraProjection::Perspective => {
+                return Mat4::perspective_rh_gl(
+                    self.fov.to_radians(),
+                    self.aspect_ratio,
+                    self.near,
+                    self.far,
+                );
+            }
+            CameraProjection::Orthographic { size } => size.max(1e-3) * 0.5,
+            CameraProjection::PixelPerfect {
+                pixels_per_unit,
+                zoom,
+            } => {
+                self.viewport_height.max(1) as f32
+                    / (2.0 * pixels_per_unit.max(1e-3) * zoom.max(1) as f32)
+            }
+        };
+        let half_width = half_height * self.aspect_ratio;
+        Mat4::orthographic_rh_gl(
+            -half_width,
+            half_width,
+            -half_height,
+            half_height,
             self.near,
             self.far,
         )
@@ -149,10 +305,85 @@ impl CameraSystem {
         self.position = self.target + Vec3::new(x, y, z);
     }
 
+    /// Look through a camera entity (keeps `aspect_ratio` and `viewport_height`)
+    pub fn set_from_camera(&mut self, camera: &Camera, transform: &Transform) {
+        self.position = transform.position;
+        self.target = transform.position + transform.forward();
+        self.up = transform.up();
+        self.fov = camera.fov;
+        self.near = camera.near;
+        self.far = camera.far;
+        self.projection = if camera.orthographic {
+            CameraProjection::Orthographic {
+                size: camera.orthographic_size,
+            }
+        } else {
+            CameraProjection::Perspective
+        };
+    }
+
+    /// Camera entities in render order (ascending `Camera::order`)
+    pub fn cameras_by_order(world: &EngineWorld) -> Vec<(hecs::Entity, Camera, Transform)> {
+        let mut cameras: Vec<(hecs::Entity, Camera, Transform)> = world
+            .world()
+            .query::<(hecs::Entity, &Camera, &Transform)>()
+            .iter()
+            .map(|(entity, camera, transform)| (entity, *camera, *transform))
+            .collect();
+        // Stable: cameras with the same order keep their spawn order
+        cameras.sort_by_key(|(_, camera, _)| camera.order);
+        cameras
+    }
+
+    /// Screen camera drawn last (highest order), shown by the Game view
+    pub fn game_camera(world: &EngineWorld) -> Option<(hecs::Entity, Camera, Transform)> {
+        Self::cameras_by_order(world)
+            .into_iter()
+            .rev()
+            .find(|(_, camera, _)| camera.is_screen_camera())
+    }
+
+    /// Follow the target of the first camera entity with a CameraFollow component
+    pub fn follow(&mut self, world: &EngineWorld, dt: f32) {
+        crate::profile_function!();
+        let follow = world
+            .world()
+            .query::<(&Camera, &CameraFollow)>()
+            .iter()
+            .map(|(_, follow)| *follow)
+            .next();
+        let Some(follow) = follow else {
+            return;
+        };
+        let Some(target) = follow
+            .target
+            .get(world)
+            .and_then(|e| world.world().get::<&Transform>(e).ok().map(|t| t.position))
+        else {
+            return;
+        };
+        let desired = target + follow.offset;
+        let t = if follow.smoothing <= 0.0 {
+            1.0
+        } else {
+            1.0 - follow.smoothing.clamp(0.0, 0.999).powf(dt * 60.0)
+        };
+        self.position = self.position.lerp(desired, t);
+        self.target = self.target.lerp(target, t);
+    }
+
     /// Update aspect ratio (e.g., on window resize)
     pub fn set_aspect_ratio(&mut self, aspect_ratio: f32) {
         self.aspect_ratio = aspect_ratio;
     }
+
+    /// Update aspect ratio and pixel height from the viewport size in pixels
+    pub fn set_viewport_size(&mut self, width: u32, height: u32) {
+        if height > 0 {
+            self.aspect_ratio = width as f32 / height as f32;
+            self.viewport_height = height;
+        }
+    }
 }
 
 /// Tag for entities that should be culled
@@ -0,0 +1,111 @@
+//! Time resource - frame delta, elapsed time, pause and time scale
+//!
+//! The engine loop calls `Time::advance` once per frame with the real frame
+//! time; game systems read `delta` (scaled, zero while paused) instead of the
+//! raw frame time, so pause and slow motion apply everywhere at once. While
+//! paused, `step` lets exactly one fixed update through.
+
+/// Time resource managed by the engine loop
+#[derive(Debug, Clone, Copy)]
+pub struct Time {
+    /// Scaled frame time in seconds, 0 while paused
+    pub delta: f32,
+    /// Real frame time in seconds, ignores pause and scale
+    pub unscaled_delta: f32,
+    /// Scaled time since the last reset
+    pub elapsed: f64,
+    /// Step used by fixed-rate systems (physics)
+    pub fixed_delta: f32,
+    /// Multiplier applied to delta (1 = normal speed)
+    pub scale: f32,
+    pub paused: bool,
+    /// Frames advanced since the last reset
+    pub frame_count: u64,
+    /// Real frame times above this are clamped (e.g. after a hitch or breakpoint)
+    pub max_delta: f32,
+    /// True on the frame advanced by `step` (delta = fixed_delta while paused)
+    pub stepping: bool,
+    step_requested: bool,
+}
+
+impl Default for Time {
+    fn default() -> Self {
+        Self {
+            delta: 0.0,
+            unscaled_delta: 0.0,
+            elapsed: 0.0,
+            fixed_delta: 1.0 / 60.0,
+            scale: 1.0,
+            paused: false,
+            frame_count: 0,
+            max_delta: 0.25,
+            stepping: false,
+            step_requested: false,
+        }
+    }
+}
+
+impl Time {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Advance one frame with the real elapsed time
+    pub fn advance(&mut self, real_dt: f32) {
+        self.unscaled_delta = real_dt.clamp(0.0, self.max_delta);
+        self.stepping = self.paused && std::mem::take(&mut self.step_requested);
+        self.delta = if self.stepping {
+            self.fixed_delta
+        } else if self.paused {
+            0.0
+        } else {
+            self.unscaled_delta * self.scale
+        };
+        self.elapsed += self.delta as f64;
+        self.frame_count += 1;
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+        self.delta = 0.0;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Pause (if running) and let the next `advance` run exactly one fixed
+    /// update, for debugging physics and animation frame by frame
+    pub fn step(&mut self) {
+        self.pause();
+        self.step_requested = true;
+    }
+
+    pub fn toggle_pause(&mut self) {
+        if self.paused {
+            self.resume();
+        } else {
+            self.pause();
+        }
+    }
+
+    /// Set the time scale (clamped to [0, 100])
+    pub fn set_scale(&mut self, scale: f32) {
+        self.scale = scale.clamp(0.0, 100.0);
+    }
+
+    /// True when game systems should run this frame
+    pub fn is_running(&self) -> bool {
+        (!self.paused || self.stepping) && self.delta > 0.0
+    }
+
+    /// Back to a fresh clock, keeping scale and fixed_delta
+    pub fn reset(&mut self) {
+        *self = Self {
+            scale: self.scale,
+            fixed_delta: self.fixed_delta,
+            max_delta: self.max_delta,
+            ..Self::default()
+        };
+    }
+}
@@ -0,0 +1,245 @@
+//! Timers and scheduled tasks
+//!
+//! `Timer` is a component ticked by `TimerSystem`; `Tasks` is a resource that
+//! runs closures after a delay, at an interval, or once per frame until they
+//! report completion (a lightweight coroutine). `GameSystems` runs both every
+//! frame in the editor's Play mode and in the player.
+
+use crate::ecs::EngineWorld;
+
+/// Timer component - counts scaled time up to `duration`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Timer {
+    pub duration: f32,
+    pub elapsed: f32,
+    pub repeating: bool,
+    pub paused: bool,
+    finished: bool,
+    times_finished_this_tick: u32,
+}
+
+impl Default for Timer {
+    fn default() -> Self {
+        Self::once(1.0)
+    }
+}
+
+impl Timer {
+    pub fn new(duration: f32, repeating: bool) -> Self {
+        Self {
+            duration: duration.max(0.0),
+            elapsed: 0.0,
+            repeating,
+            paused: false,
+            finished: false,
+            times_finished_this_tick: 0,
+        }
+    }
+
+    /// Fires once, then stays finished
+    pub fn once(duration: f32) -> Self {
+        Self::new(duration, false)
+    }
+
+    /// Fires every `duration` seconds
+    pub fn repeating(duration: f32) -> Self {
+        Self::new(duration, true)
+    }
+
+    /// Advance the timer; returns true if it fired during this tick
+    pub fn tick(&mut self, dt: f32) -> bool {
+        self.times_finished_this_tick = 0;
+        if self.paused || (self.finished && !self.repeating) {
+            return false;
+        }
+        self.elapsed += dt.max(0.0);
+        if self.elapsed < self.duration {
+            return false;
+        }
+        self.finished = true;
+        if self.repeating && self.duration > 0.0 {
+            self.times_finished_this_tick = (self.elapsed / self.duration) as u32;
+            self.elapsed %= self.duration;
+        } else {
+            self.times_finished_this_tick = 1;
+            self.elapsed = self.duration;
+        }
+        true
+    }
+
+    /// True on the tick the timer fired
+    pub fn just_finished(&self) -> bool {
+        self.times_finished_this_tick > 0
+    }
+
+    /// How many times a repeating timer fired during the last tick (large dt)
+    pub fn times_finished_this_tick(&self) -> u32 {
+        self.times_finished_this_tick
+    }
+
+    /// A one-shot timer that already fired (repeating timers: fired at least once)
+    pub fn finished(&self) -> bool {
+        self.finished
+    }
+
+    pub fn remaining(&self) -> f32 {
+        (self.duration - self.elapsed).max(0.0)
+    }
+
+    /// Progress in [0, 1]
+    pub fn fraction(&self) -> f32 {
+        if self.duration <= 0.0 {
+            1.0
+        } else {
+            (self.elapsed / self.duration).clamp(0.0, 1.0)
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+        self.finished = false;
+        self.times_finished_this_tick = 0;
+    }
+}
+
+/// Ticks every Timer component
+pub struct TimerSystem;
+
+impl TimerSystem {
+    pub fn update(world: &mut EngineWorld, dt: f32) {
+        crate::profile_function!();
+        for timer in world.world_mut().query_mut::<&mut Timer>() {
+            timer.tick(dt);
+        }
+    }
+}
+
+/// What a per-frame task wants next
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TaskStatus {
+    /// Run again next frame
+    Continue,
+    /// Sleep for the given seconds, then run again
+    Wait(f32),
+    /// Remove the task
+    Done,
+}
+
+/// Identifier returned by `Tasks`, used to cancel a task
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TaskId(u64);
+
+type TaskFn = Box<dyn FnMut(&mut EngineWorld, f32) -> TaskStatus>;
+
+struct Task {
+    id: TaskId,
+    wait: f32,
+    run: TaskFn,
+}
+
+/// Scheduled work resource - call `update` once per frame with the scaled delta
+#[derive(Default)]
+pub struct Tasks {
+    tasks: Vec<Task>,
+    next_id: u64,
+}
+
+impl Tasks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `f` once after `seconds`
+    pub fn after(&mut self, seconds: f32, f: impl FnOnce(&mut EngineWorld) + 'static) -> TaskId {
+        let mut f = Some(f);
+        self.push(seconds, move |world, _| {
+            if let Some(f) = f.take() {
+                f(world);
+            }
+            TaskStatus::Done
+        })
+    }
+
+    /// Run `f` every `seconds` until cancelled
+    pub fn every(&mut self, seconds: f32, mut f: impl FnMut(&mut EngineWorld) + 'static) -> TaskId {
+        let interval = seconds.max(0.0);
+        self.push(interval, move |world, _| {
+            f(world);
+            TaskStatus::Wait(interval)
+        })
+    }
+
+    /// Run `f(world, dt)` every frame; the returned status decides when it runs next
+    pub fn coroutine(
+        &mut self,
+        f: impl FnMut(&mut EngineWorld, f32) -> TaskStatus + 'static,
+    ) -> TaskId {
+        self.push(0.0, f)
+    }
+
+    pub fn cancel(&mut self, id: TaskId) -> bool {
+        let before = self.tasks.len();
+        self.tasks.retain(|t| t.id != id);
+        self.tasks.len() != before
+    }
+
+    pub fn is_scheduled(&self, id: TaskId) -> bool {
+        self.tasks.iter().any(|t| t.id == id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.tasks.clear();
+    }
+
+    /// Advance waits and run every due task. Tasks scheduled from inside a
+    /// task start on the next update.
+    pub fn update(&mut self, world: &mut EngineWorld, dt: f32) {
+        crate::profile_function!();
+        let mut pending = std::mem::take(&mut self.tasks);
+        pending.retain_mut(|task| {
+            task.wait -= dt;
+            if task.wait > 0.0 {
+                return true;
+            }
+            match (task.run)(world, dt) {
+                TaskStatus::Continue => {
+                    task.wait = 0.0;
+                    true
+                }
+                TaskStatus::Wait(seconds) => {
+                    task.wait += seconds.max(0.0);
+                    if task.wait <= 0.0 {
+                        task.wait = seconds.max(0.0);
+                    }
+                    true
+                }
+                TaskStatus::Done => false,
+            }
+        });
+        pending.append(&mut self.tasks);
+        self.tasks = pending;
+    }
+
+    fn push(
+        &mut self,
+        wait: f32,
+        run: impl FnMut(&mut EngineWorld, f32) -> TaskStatus + 'static,
+    ) -> TaskId {
+        self.next_id += 1;
+        let id = TaskId(self.next_id);
+        self.tasks.push(Task {
+            id,
+            wait: wait.max(0.0),
+            run: Box::new(run),
+        });
+        id
+    }
+}
@@ -0,0 +1,15 @@
+//! Engine Net - basic client/server replication over UDP
+//!
+//! Entities tagged with `Replicated` are sent by the server as `Snapshot`s at a
+//! fixed rate; clients buffer them and interpolate transforms a little behind
+//! real time. `NetSession` owns the transport and drives both roles.
+
+pub mod protocol;
+pub mod replication;
+pub mod session;
+pub mod transport;
+
+pub use protocol::*;
+pub use replication::*;
+pub use session::*;
+pub use transport::*;
@@ -0,0 +1,232 @@
+//! Wire protocol - little-endian binary messages, one per UDP datagram
+
+use engine_core::Guid;
+use glam::{Quat, Vec3};
+
+use crate::replication::{EntityState, NetOwner, Snapshot};
+
+/// Every datagram starts with this tag; anything else is ignored
+pub const PROTOCOL_MAGIC: [u8; 4] = *b"DNG1";
+
+/// Payload budget per datagram, kept under the common MTU
+pub const MAX_DATAGRAM_SIZE: usize = 1200;
+
+/// Encoded size of one `EntityState`
+pub const ENTITY_STATE_SIZE: usize = 16 + 4 + 12 + 16 + 12;
+
+/// Snapshot header: magic, kind, tick, time, state count
+const SNAPSHOT_HEADER_SIZE: usize = 4 + 1 + 4 + 8 + 4;
+
+/// Entity states that fit in one snapshot datagram
+pub const MAX_STATES_PER_DATAGRAM: usize =
+    (MAX_DATAGRAM_SIZE - SNAPSHOT_HEADER_SIZE) / ENTITY_STATE_SIZE;
+
+/// Messages exchanged between client and server
+#[derive(Debug, Clone, PartialEq)]
+pub enum Message {
+    /// Client asks to join
+    Hello {
+        name: String,
+    },
+    /// Server accepts the client and assigns its id
+    Welcome {
+        client_id: u32,
+        send_rate: f32,
+    },
+    /// Either side leaves
+    Goodbye,
+    Ping {
+        seq: u32,
+    },
+    Pong {
+        seq: u32,
+    },
+    /// Server -> client world state (possibly one part of a tick)
+    Snapshot(Snapshot),
+    /// Client -> server state of the entities it owns
+    ClientState(Vec<EntityState>),
+}
+
+impl Message {
+    fn kind(&self) -> u8 {
+        match self {
+            Message::Hello { .. } => 1,
+            Message::Welcome { .. } => 2,
+            Message::Goodbye => 3,
+            Message::Ping { .. } => 4,
+            Message::Pong { .. } => 5,
+            Message::Snapshot(_) => 6,
+            Message::ClientState(_) => 7,
+        }
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut w = Writer::default();
+        w.bytes(&PROTOCOL_MAGIC);
+        w.u8(self.kind());
+        match self {
+            Message::Hello { name } => w.string(name),
+            Message::Welcome {
+                client_id,
+                send_rate,
+            } => {
+                w.u32(*client_id);
+                w.f32(*send_rate);
+            }
+            Message::Goodbye => {}
+            Message::Ping { seq } | Message::Pong { seq } => w.u32(*seq),
+            Message::Snapshot(snapshot) => {
+                w.u32(snapshot.tick);
+                w.f64(snapshot.time);
+                w.states(&snapshot.entities);
+            }
+            Message::ClientState(states) => w.states(states),
+        }
+        w.buf
+    }
+
+    /// Decode a datagram; None for foreign or malformed packets
+    pub fn decode(bytes: &[u8]) -> Option<Message> {
+        let mut r = Reader { buf: bytes, pos: 0 };
+        if r.take(PROTOCOL_MAGIC.len())? != PROTOCOL_MAGIC {
+            return None;
+        }
+        let message = match r.u8()? {
+            1 => Message::Hello { name: r.string()? },
+            2 => Message::Welcome {
+                client_id: r.u32()?,
+                send_rate: r.f32()?,
+            },
+            3 => Message::Goodbye,
+            4 => Message::Ping { seq: r.u32()? },
+            5 => Message::Pong { seq: r.u32()? },
+            6 => Message::Snapshot(Snapshot {
+                tick: r.u32()?,
+                time: r.f64()?,
+                entities: r.states()?,
+            }),
+            7 => Message::ClientState(r.states()?),
+            _ => return None,
+        };
+        (r.pos == bytes.len()).then_some(message)
+    }
+}
+
+#[derive(Default)]
+struct Writer {
+    buf: Vec<u8>,
+}
+
+impl Writer {
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.buf.extend_from_slice(bytes);
+    }
+
+    fn u8(&mut self, v: u8) {
+        self.buf.push(v);
+    }
+
+    fn u32(&mut self, v: u32) {
+        self.bytes(&v.to_le_bytes());
+    }
+
+    fn f32(&mut self, v: f32) {
+        self.bytes(&v.to_le_bytes());
+    }
+
+    fn f64(&mut self, v: f64) {
+        self.bytes(&v.to_le_bytes());
+    }
+
+    fn string(&mut self, s: &str) {
+        let end = (0..=s.len().min(255))
+            .rev()
+            .find(|i| s.is_char_boundary(*i))
+            .unwrap_or(0);
+        let bytes = &s.as_bytes()[..end];
+        self.u8(bytes.len() as u8);
+        self.bytes(bytes);
+    }
+
+    fn vec3(&mut self, v: Vec3) {
+        for c in v.to_array() {
+            self.f32(c);
+        }
+    }
+
+    fn states(&mut self, states: &[EntityState]) {
+        self.u32(states.len() as u32);
+        for s in states {
+            self.bytes(&s.guid.value.to_le_bytes());
+            self.u32(s.owner.to_u32());
+            self.vec3(s.position);
+            for c in s.rotation.to_array() {
+                self.f32(c);
+            }
+            self.vec3(s.scale);
+        }
+    }
+}
+
+struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Option<&'a [u8]> {
+        let slice = self.buf.get(self.pos..self.pos.checked_add(n)?)?;
+        self.pos += n;
+        Some(slice)
+    }
+
+    fn array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        self.take(N)?.try_into().ok()
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        Some(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        Some(u32::from_le_bytes(self.array()?))
+    }
+
+    fn f32(&mut self) -> Option<f32> {
+        Some(f32::from_le_bytes(self.array()?)).filter(|v| v.is_finite())
+    }
+
+    fn f64(&mut self) -> Option<f64> {
+        Some(f64::from_le_bytes(self.array()?)).filter(|v| v.is_finite())
+    }
+
+    fn string(&mut self) -> Option<String> {
+        let len = self.u8()? as usize;
+        String::from_utf8(self.take(len)?.to_vec()).ok()
+    }
+
+    fn vec3(&mut self) -> Option<Vec3> {
+        Some(Vec3::new(self.f32()?, self.f32()?, self.f32()?))
+    }
+
+    fn states(&mut self) -> Option<Vec<EntityState>> {
+        let count = self.u32()? as usize;
+        if count > (self.buf.len() - self.pos) / ENTITY_STATE_SIZE {
+            return None;
+        }
+        (0..count)
+            .map(|_| {
+                Some(EntityState {
+                    guid: Guid {
+                        value: u128::from_le_bytes(self.array()?),
+                    },
+                    owner: NetOwner::from_u32(self.u32()?),
+                    position: self.vec3()?,
+                    rotation: Quat::from_xyzw(self.f32()?, self.f32()?, self.f32()?, self.f32()?)
+                        .normalize(),
+                    scale: self.vec3()?,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,319 @@
+//! Replication tags, snapshots and client-side interpolation
+
+use std::collections::{HashMap, VecDeque};
+
+use engine_core::{EngineWorld, Guid, Transform};
+use glam::{Quat, Vec3};
+
+/// Who is allowed to write a replicated entity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum NetOwner {
+    #[default]
+    Server,
+    Client(u32),
+}
+
+impl NetOwner {
+    /// Wire form - 0 is the server, client ids start at 1
+    pub fn to_u32(self) -> u32 {
+        match self {
+            NetOwner::Server => 0,
+            NetOwner::Client(id) => id,
+        }
+    }
+
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            0 => NetOwner::Server,
+            id => NetOwner::Client(id),
+        }
+    }
+}
+
+/// Replication tag - entities with this component and a Guid are synced over the network
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Replicated {
+    pub owner: NetOwner,
+    /// Smooth remote updates between snapshots instead of snapping
+    pub interpolate: bool,
+}
+
+impl Default for Replicated {
+    fn default() -> Self {
+        Self {
+            owner: NetOwner::Server,
+            interpolate: true,
+        }
+    }
+}
+
+impl Replicated {
+    pub fn new(owner: NetOwner) -> Self {
+        Self {
+            owner,
+            ..Default::default()
+        }
+    }
+
+    /// Owned by a client, which sends its state to the server
+    pub fn client_owned(client_id: u32) -> Self {
+        Self::new(NetOwner::Client(client_id))
+    }
+}
+
+/// Replicated state of one entity
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EntityState {
+    pub guid: Guid,
+    pub owner: NetOwner,
+    pub position: Vec3,
+    pub rotation: Quat,
+    pub scale: Vec3,
+}
+
+impl EntityState {
+    pub fn from_transform(guid: Guid, owner: NetOwner, transform: &Transform) -> Self {
+        Self {
+            guid,
+            owner,
+            position: transform.position,
+            rotation: transform.rotation,
+            scale: transform.scale,
+        }
+    }
+
+    pub fn transform(&self) -> Transform {
+        Transform::new(self.position, self.rotation, self.scale)
+    }
+
+    /// Interpolate toward `other` (t in [0, 1])
+    pub fn lerp(&self, other: &EntityState, t: f32) -> EntityState {
+        EntityState {
+            guid: self.guid,
+            owner: other.owner,
+            position: self.position.lerp(other.position, t),
+            rotation: self.rotation.slerp(other.rotation, t),
+            scale: self.scale.lerp(other.scale, t),
+        }
+    }
+}
+
+/// World state at one server tick
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Snapshot {
+    pub tick: u32,
+    /// Server clock in seconds when the snapshot was taken
+    pub time: f64,
+    pub entities: Vec<EntityState>,
+}
+
+impl Snapshot {
+    /// Capture every replicated entity (Guid + Transform + Replicated) of the world
+    pub fn capture(world: &EngineWorld, tick: u32, time: f64) -> Self {
+        Self::capture_filtered(world, tick, time, |_| true)
+    }
+
+    /// Capture the replicated entities whose tag passes `filter`
+    pub fn capture_filtered(
+        world: &EngineWorld,
+        tick: u32,
+        time: f64,
+        filter: impl Fn(&Replicated) -> bool,
+    ) -> Self {
+        let mut entities: Vec<EntityState> = world
+            .world()
+            .query::<(&Guid, &Transform, &Replicated)>()
+            .iter()
+            .filter(|(guid, _, rep)| !guid.is_nil() && filter(rep))
+            .map(|(guid, transform, rep)| EntityState::from_transform(*guid, rep.owner, transform))
+            .collect();
+        entities.sort_by_key(|e| e.guid.value);
+        Self {
+            tick,
+            time,
+            entities,
+        }
+    }
+
+    /// Split into snapshots of at most `max_entities` each (one datagram per part)
+    pub fn split(&self, max_entities: usize) -> Vec<Snapshot> {
+        if self.entities.is_empty() {
+            return vec![self.clone()];
+        }
+        self.entities
+            .chunks(max_entities.max(1))
+            .map(|chunk| Snapshot {
+                tick: self.tick,
+                time: self.time,
+                entities: chunk.to_vec(),
+            })
+            .collect()
+    }
+}
+
+/// Write states into the world: existing entities (by Guid) get the new Transform,
+/// unknown Guids are spawned as replicated entities
+pub fn apply_states<'a>(
+    world: &mut EngineWorld,
+    states: impl IntoIterator<Item = &'a EntityState>,
+) {
+    for state in states {
+        let transform = state.transform();
+        match world.find_by_guid(state.guid) {
+            Some(entity) => {
+                let world = world.world_mut();
+                let _ = world.insert_one(entity, transform);
+                if let Ok(mut rep) = world.get::<&mut Replicated>(entity) {
+                    rep.owner = state.owner;
+                }
+            }
+            None => {
+                world.spawn((transform, state.guid, Replicated::new(state.owner)));
+            }
+        }
+    }
+}
+
+/// Client-side buffer of received snapshots, sampled `delay` seconds behind the server
+#[derive(Debug, Clone)]
+pub struct SnapshotBuffer {
+    snapshots: VecDeque<Snapshot>,
+    capacity: usize,
+    pub delay: f64,
+    /// Local clock when the newest snapshot arrived
+    received_at: f64,
+}
+
+impl Default for SnapshotBuffer {
+    fn default() -> Self {
+        Self::new(0.1)
+    }
+}
+
+impl SnapshotBuffer {
+    pub fn new(delay: f64) -> Self {
+        Self {
+            snapshots: VecDeque::new(),
+            capacity: 32,
+            delay: delay.max(0.0),
+            received_at: 0.0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    pub fn latest_tick(&self) -> Option<u32> {
+        self.snapshots.back().map(|s| s.tick)
+    }
+
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+    }
+
+    /// Insert a snapshot (or a part of one) received at local time `now`.
+    /// Parts of the same tick are merged; snapshots older than the buffer are dropped.
+    pub fn push(&mut self, snapshot: Snapshot, now: f64) {
+        if let Some(existing) = self.snapshots.iter_mut().find(|s| s.tick == snapshot.tick) {
+            for state in snapshot.entities {
+                match existing.entities.iter_mut().find(|e| e.guid == state.guid) {
+                    Some(slot) => *slot = state,
+                    None => existing.entities.push(state),
+                }
+            }
+            return;
+        }
+        if self
+            .snapshots
+            .front()
+            .is_some_and(|s| snapshot.tick < s.tick && self.snapshots.len() >= self.capacity)
+        {
+            return;
+        }
+        if self.latest_tick().is_none_or(|t| snapshot.tick > t) {
+            self.received_at = now;
+        }
+        let index = self.snapshots.partition_point(|s| s.tick < snapshot.tick);
+        self.snapshots.insert(index, snapshot);
+        while self.snapshots.len() > self.capacity {
+            self.snapshots.pop_front();
+        }
+    }
+
+    /// Server time being displayed at local time `now`
+    pub fn render_time(&self, now: f64) -> Option<f64> {
+        let latest = self.snapshots.back()?;
+        Some(latest.time + (now - self.received_at) - self.delay)
+    }
+
+    /// Interpolated states at server time `time`; holds the nearest snapshot outside the buffer
+    pub fn sample(&self, time: f64) -> Vec<EntityState> {
+        let Some(newest) = self.snapshots.back() else {
+            return Vec::new();
+        };
+        let next_index = self.snapshots.partition_point(|s| s.time <= time);
+        if next_index == 0 {
+            return self.snapshots[0].entities.clone();
+        }
+        if next_index >= self.snapshots.len() {
+            return newest.entities.clone();
+        }
+        let from = &self.snapshots[next_index - 1];
+        let to = &self.snapshots[next_index];
+        let span = to.time - from.time;
+        let t = if span > 0.0 {
+            ((time - from.time) / span).clamp(0.0, 1.0) as f32
+        } else {
+            1.0
+        };
+        let previous: HashMap<Guid, &EntityState> =
+            from.entities.iter().map(|e| (e.guid, e)).collect();
+        to.entities
+            .iter()
+            .map(|target| match previous.get(&target.guid) {
+                Some(start) => start.lerp(target, t),
+                None => *target,
+            })
+            .collect()
+    }
+
+    /// Apply the interpolated state at local time `now` to the world, skipping entities
+    /// owned by `local_owner` (the client writes those itself)
+    pub fn apply(&self, world: &mut EngineWorld, now: f64, local_owner: Option<NetOwner>) {
+        let Some(time) = self.render_time(now) else {
+            return;
+        };
+        let newest: HashMap<Guid, &EntityState> = self
+            .snapshots
+            .back()
+            .map(|s| s.entities.iter().map(|e| (e.guid, e)).collect())
+            .unwrap_or_default();
+        let states: Vec<EntityState> = self
+            .sample(time)
+            .into_iter()
+            .filter(|s| Some(s.owner) != local_owner)
+            .map(|s| {
+                let snap = world
+                    .find_by_guid(s.guid)
+                    .and_then(|e| {
+                        world
+                            .world()
+                            .get::<&Replicated>(e)
+                            .ok()
+                            .map(|r| !r.interpolate)
+                    })
+                    .unwrap_or(false);
+                match newest.get(&s.guid) {
+                    Some(latest) if snap => **latest,
+                    _ => s,
+                }
+            })
+            .collect();
+        apply_states(world, &states);
+    }
+}
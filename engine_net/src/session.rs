@@ -0,0 +1,519 @@
+//! Network session - hosting, connecting and the per-frame replication loop
+
+use std::collections::HashMap;
+use std::net::{SocketAddr, ToSocketAddrs};
+
+use engine_core::EngineWorld;
+
+use crate::protocol::{MAX_STATES_PER_DATAGRAM, Message};
+use crate::replication::{NetOwner, Replicated, Snapshot, SnapshotBuffer, apply_states};
+use crate::transport::{NetStats, UdpTransport};
+
+pub const DEFAULT_PORT: u16 = 7777;
+
+/// Tuning shared by both roles
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NetConfig {
+    /// Snapshots (server) / state updates (client) per second
+    pub send_rate: f32,
+    /// How far behind the newest snapshot clients render, in seconds
+    pub interpolation_delay: f32,
+    /// Peers silent for this long are dropped
+    pub timeout: f32,
+    pub max_clients: usize,
+}
+
+impl NetConfig {
+    pub fn send_interval(&self) -> f32 {
+        1.0 / self.send_rate.max(1.0)
+    }
+}
+
+impl Default for NetConfig {
+    fn default() -> Self {
+        Self {
+            send_rate: 20.0,
+            interpolation_delay: 0.1,
+            timeout: 5.0,
+            max_clients: 8,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConnectionState {
+    #[default]
+    Disconnected,
+    /// Client waiting for the server Welcome
+    Connecting,
+    /// Client accepted by the server
+    Connected,
+    /// Server listening for clients
+    Hosting,
+}
+
+/// Client known to the server
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeerInfo {
+    pub id: u32,
+    pub addr: SocketAddr,
+    pub name: String,
+    /// Seconds since the last packet
+    pub idle: f32,
+}
+
+/// Connection changes reported by `NetSession::drain_events`
+#[derive(Debug, Clone, PartialEq)]
+pub enum NetEvent {
+    ClientConnected { id: u32, name: String },
+    ClientDisconnected { id: u32 },
+    Connected { client_id: u32 },
+    Disconnected { reason: String },
+}
+
+const HELLO_RESEND_INTERVAL: f32 = 0.5;
+const PING_INTERVAL: f32 = 1.0;
+
+/// Server or client end of a connection; call `update` once per frame
+pub struct NetSession {
+    pub config: NetConfig,
+    state: ConnectionState,
+    transport: Option<UdpTransport>,
+    server_addr: Option<SocketAddr>,
+    player_name: String,
+    client_id: Option<u32>,
+    peers: Vec<PeerInfo>,
+    next_client_id: u32,
+    buffer: SnapshotBuffer,
+    clock: f64,
+    tick: u32,
+    send_accumulator: f32,
+    ping_accumulator: f32,
+    hello_accumulator: f32,
+    /// Seconds since the server was last heard from (client)
+    server_idle: f32,
+    ping_seq: u32,
+    pings_in_flight: HashMap<u32, f64>,
+    rtt: Option<f32>,
+    events: Vec<NetEvent>,
+    last_error: Option<String>,
+}
+
+impl Default for NetSession {
+    fn default() -> Self {
+        Self::new(NetConfig::default())
+    }
+}
+
+impl NetSession {
+    pub fn new(config: NetConfig) -> Self {
+        Self {
+            config,
+            state: ConnectionState::Disconnected,
+            transport: None,
+            server_addr: None,
+            player_name: String::new(),
+            client_id: None,
+            peers: Vec::new(),
+            next_client_id: 1,
+            buffer: SnapshotBuffer::new(config.interpolation_delay as f64),
+            clock: 0.0,
+            tick: 0,
+            send_accumulator: 0.0,
+            ping_accumulator: 0.0,
+            hello_accumulator: 0.0,
+            server_idle: 0.0,
+            ping_seq: 0,
+            pings_in_flight: HashMap::new(),
+            rtt: None,
+            events: Vec::new(),
+            last_error: None,
+        }
+    }
+
+    pub fn state(&self) -> ConnectionState {
+        self.state
+    }
+
+    pub fn is_server(&self) -> bool {
+        self.state == ConnectionState::Hosting
+    }
+
+    pub fn is_client(&self) -> bool {
+        matches!(
+            self.state,
+            ConnectionState::Connecting | ConnectionState::Connected
+        )
+    }
+
+    /// Id assigned by the server (client only)
+    pub fn client_id(&self) -> Option<u32> {
+        self.client_id
+    }
+
+    /// Owner tag for entities written by this end
+    pub fn local_owner(&self) -> Option<NetOwner> {
+        match self.state {
+            ConnectionState::Hosting => Some(NetOwner::Server),
+            ConnectionState::Connected => self.client_id.map(NetOwner::Client),
+            _ => None,
+        }
+    }
+
+    pub fn peers(&self) -> &[PeerInfo] {
+        &self.peers
+    }
+
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.transport.as_ref().and_then(|t| t.local_addr())
+    }
+
+    pub fn server_addr(&self) -> Option<SocketAddr> {
+        self.server_addr
+    }
+
+    /// Round-trip time to the server in seconds (client only)
+    pub fn rtt(&self) -> Option<f32> {
+        self.rtt
+    }
+
+    pub fn stats(&self) -> NetStats {
+        self.transport
+            .as_ref()
+            .map(|t| t.stats())
+            .unwrap_or_default()
+    }
+
+    pub fn tick(&self) -> u32 {
+        self.tick
+    }
+
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+
+    /// Take the last error so it is reported only once
+    pub fn take_error(&mut self) -> Option<String> {
+        self.last_error.take()
+    }
+
+    pub fn drain_events(&mut self) -> Vec<NetEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Start a server on `port` (all interfaces)
+    pub fn host(&mut self, port: u16) -> Result<(), String> {
+        self.disconnect();
+        let transport = UdpTransport::bind(("0.0.0.0", port)).inspect_err(|e| {
+            self.last_error = Some(e.clone());
+        })?;
+        self.reset_clock();
+        self.transport = Some(transport);
+        self.state = ConnectionState::Hosting;
+        Ok(())
+    }
+
+    /// Connect to a server ("host:port")
+    pub fn connect(&mut self, address: &str, player_name: &str) -> Result<(), String> {
+        self.disconnect();
+        let result = address
+            .to_socket_addrs()
+            .map_err(|e| format!("Invalid address '{}': {}", address, e))
+            .and_then(|mut addrs| {
+                addrs
+                    .next()
+                    .ok_or_else(|| format!("Invalid address '{}'", address))
+            })
+            .and_then(|server| {
+                let local = if server.is_ipv4() {
+                    "0.0.0.0:0"
+                } else {
+                    "[::]:0"
+                };
+                UdpTransport::bind(local).map(|t| (server, t))
+            });
+        let (server, transport) = result.inspect_err(|e| {
+            self.last_error = Some(e.clone());
+        })?;
+        self.reset_clock();
+        self.transport = Some(transport);
+        self.server_addr = Some(server);
+        self.player_name = player_name.to_string();
+        self.state = ConnectionState::Connecting;
+        self.hello_accumulator = HELLO_RESEND_INTERVAL;
+        Ok(())
+    }
+
+    /// Leave the session, notifying the other side
+    pub fn disconnect(&mut self) {
+        if let Some(transport) = self.transport.as_mut() {
+            let targets: Vec<SocketAddr> = match self.state {
+                ConnectionState::Hosting => self.peers.iter().map(|p| p.addr).collect(),
+                _ => self.server_addr.into_iter().collect(),
+            };
+            for addr in targets {
+                let _ = transport.send(&Message::Goodbye, addr);
+            }
+        }
+        if self.state != ConnectionState::Disconnected {
+            self.events.push(NetEvent::Disconnected {
+                reason: "Disconnected".to_string(),
+            });
+        }
+        self.transport = None;
+        self.server_addr = None;
+        self.client_id = None;
+        self.peers.clear();
+        self.buffer.clear();
+        self.state = ConnectionState::Disconnected;
+    }
+
+    fn reset_clock(&mut self) {
+        self.clock = 0.0;
+        self.tick = 0;
+        self.send_accumulator = 0.0;
+        self.ping_accumulator = 0.0;
+        self.server_idle = 0.0;
+        self.pings_in_flight.clear();
+        self.rtt = None;
+        self.last_error = None;
+        self.next_client_id = 1;
+        self.buffer = SnapshotBuffer::new(self.config.interpolation_delay as f64);
+    }
+
+    /// Receive, replicate and send; `dt` is the real (unscaled) frame time
+    pub fn update(&mut self, world: &mut EngineWorld, dt: f32) {
+        if self.transport.is_none() {
+            return;
+        }
+        self.clock += dt as f64;
+        match self.state {
+            ConnectionState::Hosting => self.update_server(world, dt),
+            ConnectionState::Connecting | ConnectionState::Connected => {
+                self.update_client(world, dt)
+            }
+            ConnectionState::Disconnected => {}
+        }
+    }
+
+    fn update_server(&mut self, world: &mut EngineWorld, dt: f32) {
+        let Some(transport) = self.transport.as_mut() else {
+            return;
+        };
+        for peer in &mut self.peers {
+            peer.idle += dt;
+        }
+        for (from, message) in transport.receive() {
+            let known = self.peers.iter().position(|p| p.addr == from);
+            if let Some(i) = known {
+                self.peers[i].idle = 0.0;
+            }
+            match message {
+                Message::Hello { name } => {
+                    let id = match known {
+                        Some(i) => self.peers[i].id,
+                        None if self.peers.len() >= self.config.max_clients => {
+                            let _ = transport.send(&Message::Goodbye, from);
+                            continue;
+                        }
+                        None => {
+                            let id = self.next_client_id;
+                            self.next_client_id += 1;
+                            self.peers.push(PeerInfo {
+                                id,
+                                addr: from,
+                                name: name.clone(),
+                                idle: 0.0,
+                            });
+                            self.events.push(NetEvent::ClientConnected { id, name });
+                            id
+                        }
+                    };
+                    let _ = transport.send(
+                        &Message::Welcome {
+                            client_id: id,
+                            send_rate: self.config.send_rate,
+                        },
+                        from,
+                    );
+                }
+                Message::Goodbye => {
+                    if let Some(i) = known {
+                        let peer = self.peers.remove(i);
+                        Self::release_client_entities(world, peer.id);
+                        self.events
+                            .push(NetEvent::ClientDisconnected { id: peer.id });
+                    }
+                }
+                Message::Ping { seq } if known.is_some() => {
+                    let _ = transport.send(&Message::Pong { seq }, from);
+                }
+                Message::ClientState(states) => {
+                    let Some(id) = known.map(|i| self.peers[i].id) else {
+                        continue;
+                    };
+                    // A client may only write entities that are free or already its own
+                    let owner = NetOwner::Client(id);
+                    let accepted: Vec<_> = states
+                        .into_iter()
+                        .filter(|s| {
+                            world
+                                .find_by_guid(s.guid)
+                                .and_then(|e| {
+                                    world.world().get::<&Replicated>(e).ok().map(|r| r.owner)
+                                })
+                                .is_none_or(|o| o == owner || o == NetOwner::Server)
+                        })
+                        .map(|mut s| {
+                            s.owner = owner;
+                            s
+                        })
+                        .collect();
+                    apply_states(world, &accepted);
+                }
+                _ => {}
+            }
+        }
+
+        let timeout = self.config.timeout;
+        let (alive, expired): (Vec<_>, Vec<_>) =
+            self.peers.drain(..).partition(|p| p.idle < timeout);
+        self.peers = alive;
+        for peer in expired {
+            Self::release_client_entities(world, peer.id);
+            self.events
+                .push(NetEvent::ClientDisconnected { id: peer.id });
+        }
+
+        self.send_accumulator += dt;
+        let interval = self.config.send_interval();
+        if self.send_accumulator < interval {
+            return;
+        }
+        self.send_accumulator %= interval;
+        self.tick = self.tick.wrapping_add(1);
+        if self.peers.is_empty() {
+            return;
+        }
+        let snapshot = Snapshot::capture(world, self.tick, self.clock);
+        for part in snapshot.split(MAX_STATES_PER_DATAGRAM) {
+            let message = Message::Snapshot(part);
+            for peer in &self.peers {
+                if let Err(e) = transport.send(&message, peer.addr) {
+                    self.last_error = Some(e);
+                }
+            }
+        }
+    }
+
+    /// Hand the entities of a departed client back to the server
+    fn release_client_entities(world: &mut EngineWorld, client_id: u32) {
+        for rep in world.world_mut().query_mut::<&mut Replicated>() {
+            if rep.owner == NetOwner::Client(client_id) {
+                rep.owner = NetOwner::Server;
+            }
+        }
+    }
+
+    fn update_client(&mut self, world: &mut EngineWorld, dt: f32) {
+        let (Some(transport), Some(server)) = (self.transport.as_mut(), self.server_addr) else {
+            return;
+        };
+        self.server_idle += dt;
+        for (from, message) in transport.receive() {
+            if from != server {
+                continue;
+            }
+            self.server_idle = 0.0;
+            match message {
+                Message::Welcome {
+                    client_id,
+                    send_rate,
+                } => {
+                    if self.state == ConnectionState::Connecting {
+                        self.state = ConnectionState::Connected;
+                        self.client_id = Some(client_id);
+                        self.config.send_rate = send_rate;
+                        self.events.push(NetEvent::Connected { client_id });
+                    }
+                }
+                Message::Snapshot(snapshot) => {
+                    self.buffer.push(snapshot, self.clock);
+                }
+                Message::Pong { seq } => {
+                    if let Some(sent) = self.pings_in_flight.remove(&seq) {
+                        self.rtt = Some((self.clock - sent) as f32);
+                    }
+                }
+                Message::Goodbye => {
+                    self.transport = None;
+                    self.server_addr = None;
+                    self.client_id = None;
+                    self.state = ConnectionState::Disconnected;
+                    self.events.push(NetEvent::Disconnected {
+                        reason: "Server closed the connection".to_string(),
+                    });
+                    return;
+                }
+                _ => {}
+            }
+        }
+
+        if self.server_idle >= self.config.timeout {
+            let reason = if self.state == ConnectionState::Connecting {
+                "Server did not answer"
+            } else {
+                "Connection timed out"
+            };
+            self.disconnect();
+            self.last_error = Some(reason.to_string());
+            return;
+        }
+
+        let Some(transport) = self.transport.as_mut() else {
+            return;
+        };
+        if self.state == ConnectionState::Connecting {
+            self.hello_accumulator += dt;
+            if self.hello_accumulator >= HELLO_RESEND_INTERVAL {
+                self.hello_accumulator = 0.0;
+                let hello = Message::Hello {
+                    name: self.player_name.clone(),
+                };
+                if let Err(e) = transport.send(&hello, server) {
+                    self.last_error = Some(e);
+                }
+            }
+            return;
+        }
+
+        let local_owner = self.client_id.map(NetOwner::Client);
+        self.buffer.apply(world, self.clock, local_owner);
+
+        self.ping_accumulator += dt;
+        if self.ping_accumulator >= PING_INTERVAL {
+            self.ping_accumulator = 0.0;
+            self.ping_seq = self.ping_seq.wrapping_add(1);
+            self.pings_in_flight
+                .retain(|_, sent| self.clock - *sent < 10.0);
+            self.pings_in_flight.insert(self.ping_seq, self.clock);
+            let _ = transport.send(&Message::Ping { seq: self.ping_seq }, server);
+        }
+
+        self.send_accumulator += dt;
+        let interval = self.config.send_interval();
+        if self.send_accumulator < interval {
+            return;
+        }
+        self.send_accumulator %= interval;
+        let Some(owner) = local_owner else {
+            return;
+        };
+        let own = Snapshot::capture_filtered(world, 0, self.clock, |r| r.owner == owner);
+        for part in own.entities.chunks(MAX_STATES_PER_DATAGRAM) {
+            if let Err(e) = transport.send(&Message::ClientState(part.to_vec()), server) {
+                self.last_error = Some(e);
+            }
+        }
+    }
+}
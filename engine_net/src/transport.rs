@@ -0,0 +1,78 @@
+//! Non-blocking UDP transport
+
+use std::io::ErrorKind;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+
+use crate::protocol::{MAX_DATAGRAM_SIZE, Message};
+
+/// Traffic counters
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NetStats {
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    pub packets_sent: u64,
+    pub packets_received: u64,
+    /// Datagrams that failed to decode
+    pub packets_dropped: u64,
+}
+
+/// UDP socket sending and receiving `Message`s
+pub struct UdpTransport {
+    socket: UdpSocket,
+    stats: NetStats,
+}
+
+impl UdpTransport {
+    /// Bind to a local address ("0.0.0.0:7777" to host, "0.0.0.0:0" for a client)
+    pub fn bind(addr: impl ToSocketAddrs) -> Result<Self, String> {
+        let socket = UdpSocket::bind(addr).map_err(|e| format!("Failed to bind socket: {}", e))?;
+        socket
+            .set_nonblocking(true)
+            .map_err(|e| format!("Failed to configure socket: {}", e))?;
+        Ok(Self {
+            socket,
+            stats: NetStats::default(),
+        })
+    }
+
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.socket.local_addr().ok()
+    }
+
+    pub fn stats(&self) -> NetStats {
+        self.stats
+    }
+
+    pub fn send(&mut self, message: &Message, to: SocketAddr) -> Result<(), String> {
+        let bytes = message.encode();
+        let sent = self
+            .socket
+            .send_to(&bytes, to)
+            .map_err(|e| format!("Failed to send to {}: {}", to, e))?;
+        self.stats.bytes_sent += sent as u64;
+        self.stats.packets_sent += 1;
+        Ok(())
+    }
+
+    /// Drain every pending datagram
+    pub fn receive(&mut self) -> Vec<(SocketAddr, Message)> {
+        let mut out = Vec::new();
+        let mut buf = [0u8; MAX_DATAGRAM_SIZE * 2];
+        loop {
+            match self.socket.recv_from(&mut buf) {
+                Ok((len, from)) => {
+                    self.stats.bytes_received += len as u64;
+                    self.stats.packets_received += 1;
+                    match Message::decode(&buf[..len]) {
+                        Some(message) => out.push((from, message)),
+                        None => self.stats.packets_dropped += 1,
+                    }
+                }
+                // Windows reports ICMP port unreachable as ConnectionReset on UDP
+                Err(e) if e.kind() == ErrorKind::ConnectionReset => continue,
+                Err(_) => break,
+            }
+        }
+        out
+    }
+}
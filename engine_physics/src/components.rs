@@ -0,0 +1,133 @@
+//! Physics components attached to engine_core entities
+
+use glam::Vec3;
+
+/// How a rigid body is driven
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BodyType {
+    /// Moved by forces, gravity and contacts
+    #[default]
+    Dynamic,
+    /// Never moves
+    Fixed,
+    /// Follows the entity Transform, pushes dynamic bodies
+    Kinematic,
+}
+
+impl BodyType {
+    pub const ALL: [BodyType; 3] = [BodyType::Dynamic, BodyType::Fixed, BodyType::Kinematic];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BodyType::Dynamic => "Dynamic",
+            BodyType::Fixed => "Fixed",
+            BodyType::Kinematic => "Kinematic",
+        }
+    }
+}
+
+/// Rigid body component - simulated by PhysicsWorld
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RigidBody {
+    pub body_type: BodyType,
+    pub mass: f32,
+    pub gravity_scale: f32,
+    pub linear_damping: f32,
+    pub angular_damping: f32,
+    pub lock_rotation: bool,
+}
+
+impl Default for RigidBody {
+    fn default() -> Self {
+        Self {
+            body_type: BodyType::Dynamic,
+            mass: 1.0,
+            gravity_scale: 1.0,
+            linear_damping: 0.0,
+            angular_damping: 0.05,
+            lock_rotation: false,
+        }
+    }
+}
+
+impl RigidBody {
+    pub fn dynamic(mass: f32) -> Self {
+        Self {
+            mass,
+            ..Default::default()
+        }
+    }
+
+    pub fn fixed() -> Self {
+        Self {
+            body_type: BodyType::Fixed,
+            ..Default::default()
+        }
+    }
+}
+
+/// Collision shape, already in world units (entity scale is not applied)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColliderShape {
+    Cuboid { half_extents: Vec3 },
+    Sphere { radius: f32 },
+    Capsule { half_height: f32, radius: f32 },
+}
+
+impl Default for ColliderShape {
+    fn default() -> Self {
+        ColliderShape::Cuboid {
+            half_extents: Vec3::splat(0.5),
+        }
+    }
+}
+
+/// Collider component - static on its own, attached to the RigidBody of the same entity otherwise
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Collider {
+    pub shape: ColliderShape,
+    /// Offset from the entity origin
+    pub offset: Vec3,
+    pub friction: f32,
+    pub restitution: f32,
+    /// Sensors report overlaps but do not generate contacts
+    pub is_sensor: bool,
+}
+
+impl Default for Collider {
+    fn default() -> Self {
+        Self {
+            shape: ColliderShape::default(),
+            offset: Vec3::ZERO,
+            friction: 0.5,
+            restitution: 0.0,
+            is_sensor: false,
+        }
+    }
+}
+
+impl Collider {
+    pub fn cuboid(half_extents: Vec3) -> Self {
+        Self {
+            shape: ColliderShape::Cuboid { half_extents },
+            ..Default::default()
+        }
+    }
+
+    pub fn sphere(radius: f32) -> Self {
+        Self {
+            shape: ColliderShape::Sphere { radius },
+            ..Default::default()
+        }
+    }
+
+    pub fn capsule(half_height: f32, radius: f32) -> Self {
+        Self {
+            shape: ColliderShape::Capsule {
+                half_height,
+                radius,
+            },
+            ..Default::default()
+        }
+    }
+}
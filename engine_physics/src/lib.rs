@@ -0,0 +1,13 @@
+//! Engine Physics - rigid body simulation backed by rapier3d
+//!
+//! Components live in the engine_core world; `PhysicsWorld` mirrors them into
+//! rapier and steps the simulation at a fixed rate. `PhysicsSceneComponents`
+//! stores them in scene files.
+
+pub mod components;
+pub mod scene_file;
+pub mod world;
+
+pub use components::*;
+pub use scene_file::*;
+pub use world::*;
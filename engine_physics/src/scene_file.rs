@@ -0,0 +1,172 @@
+//! Physics components in scene files
+//!
+//! ```text
+//! rigidbody=dynamic,1,1,0,0.05,0
+//! collider=cuboid,0.5,0.5,0.5,0,0,0,0.5,0,0
+//! ```
+//!
+//! `rigidbody` is the body type followed by mass, gravity scale, linear and
+//! angular damping and lock rotation (0/1). `collider` is the shape with its
+//! sizes in world units (cuboid half extents, sphere radius, capsule half
+//! height and radius), then offset, friction, restitution and sensor (0/1).
+
+use engine_core::{EngineWorld, SceneComponents};
+use glam::Vec3;
+
+use crate::components::*;
+
+/// `SceneComponents` for `RigidBody` and `Collider`
+pub struct PhysicsSceneComponents;
+
+fn floats(values: &[&str]) -> Option<Vec<f32>> {
+    values
+        .iter()
+        .map(|v| v.trim().parse::<f32>().ok())
+        .collect()
+}
+
+fn join(values: &[f32]) -> String {
+    values
+        .iter()
+        .map(f32::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn flag(value: bool) -> f32 {
+    if value { 1.0 } else { 0.0 }
+}
+
+fn body_type_key(body_type: BodyType) -> &'static str {
+    match body_type {
+        BodyType::Dynamic => "dynamic",
+        BodyType::Fixed => "fixed",
+        BodyType::Kinematic => "kinematic",
+    }
+}
+
+fn read_rigid_body(value: &str) -> Option<RigidBody> {
+    let parts: Vec<&str> = value.split(',').collect();
+    let (kind, rest) = parts.split_first()?;
+    let body_type = match kind.trim() {
+        "dynamic" => BodyType::Dynamic,
+        "fixed" => BodyType::Fixed,
+        "kinematic" => BodyType::Kinematic,
+        _ => return None,
+    };
+    match floats(rest)?.as_slice() {
+        [
+            mass,
+            gravity_scale,
+            linear_damping,
+            angular_damping,
+            lock_rotation,
+        ] => Some(RigidBody {
+            body_type,
+            mass: *mass,
+            gravity_scale: *gravity_scale,
+            linear_damping: *linear_damping,
+            angular_damping: *angular_damping,
+            lock_rotation: *lock_rotation != 0.0,
+        }),
+        _ => None,
+    }
+}
+
+fn read_collider(value: &str) -> Option<Collider> {
+    let parts: Vec<&str> = value.split(',').collect();
+    let (kind, rest) = parts.split_first()?;
+    let sizes = match kind.trim() {
+        "cuboid" => 3,
+        "sphere" => 1,
+        "capsule" => 2,
+        _ => return None,
+    };
+    let values = floats(rest)?;
+    if values.len() != sizes + 6 {
+        return None;
+    }
+    let (size, material) = values.split_at(sizes);
+    let shape = match size {
+        [x, y, z] => ColliderShape::Cuboid {
+            half_extents: Vec3::new(*x, *y, *z),
+        },
+        [radius] => ColliderShape::Sphere { radius: *radius },
+        [half_height, radius] => ColliderShape::Capsule {
+            half_height: *half_height,
+            radius: *radius,
+        },
+        _ => return None,
+    };
+    let [ox, oy, oz, friction, restitution, sensor] = material else {
+        return None;
+    };
+    Some(Collider {
+        shape,
+        offset: Vec3::new(*ox, *oy, *oz),
+        friction: *friction,
+        restitution: *restitution,
+        is_sensor: *sensor != 0.0,
+    })
+}
+
+impl SceneComponents for PhysicsSceneComponents {
+    fn register(&self, world: &mut EngineWorld) {
+        world.register_component::<RigidBody>();
+        world.register_component::<Collider>();
+    }
+
+    fn write(&self, entity: hecs::EntityRef<'_>, out: &mut String) {
+        if let Some(rb) = entity.get::<&RigidBody>() {
+            out.push_str(&format!(
+                "rigidbody={},{}\n",
+                body_type_key(rb.body_type),
+                join(&[
+                    rb.mass,
+                    rb.gravity_scale,
+                    rb.linear_damping,
+                    rb.angular_damping,
+                    flag(rb.lock_rotation),
+                ])
+            ));
+        }
+        if let Some(c) = entity.get::<&Collider>() {
+            let (kind, size) = match c.shape {
+                ColliderShape::Cuboid { half_extents } => {
+                    ("cuboid", half_extents.to_array().to_vec())
+                }
+                ColliderShape::Sphere { radius } => ("sphere", vec![radius]),
+                ColliderShape::Capsule {
+                    half_height,
+                    radius,
+                } => ("capsule", vec![half_height, radius]),
+            };
+            let mut values = size;
+            values.extend_from_slice(&c.offset.to_array());
+            values.extend_from_slice(&[c.friction, c.restitution, flag(c.is_sensor)]);
+            out.push_str(&format!("collider={kind},{}\n", join(&values)));
+        }
+    }
+
+    fn read(
+        &self,
+        key: &str,
+        value: &str,
+        builder: &mut hecs::EntityBuilder,
+    ) -> Result<bool, String> {
+        match key {
+            "rigidbody" => {
+                let rb =
+                    read_rigid_body(value).ok_or_else(|| format!("invalid rigidbody {value}"))?;
+                builder.add(rb);
+            }
+            "collider" => {
+                let collider =
+                    read_collider(value).ok_or_else(|| format!("invalid collider {value}"))?;
+                builder.add(collider);
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+}
@@ -0,0 +1,423 @@
+//! PhysicsWorld resource - mirrors ECS physics components into rapier
+
+use std::collections::{HashMap, HashSet};
+
+use engine_core::{EngineWorld, Transform};
+use glam::{Quat, Vec3};
+use rapier3d::na::{Isometry3, Quaternion, Translation3, UnitQuaternion, Vector3};
+use rapier3d::prelude as rp;
+
+use crate::components::*;
+
+/// Distance/angle under which the ECS transform is considered unchanged
+const SYNC_EPSILON: f32 = 1e-4;
+
+type PhysicsEntity = (hecs::Entity, Transform, Option<RigidBody>, Option<Collider>);
+
+struct BodyEntry {
+    body: rp::RigidBodyHandle,
+    collider: rp::ColliderHandle,
+    last_body: RigidBody,
+    last_collider: Option<Collider>,
+}
+
+struct StaticEntry {
+    collider: rp::ColliderHandle,
+    last_collider: Collider,
+}
+
+/// Physics simulation resource stepped at a fixed rate
+pub struct PhysicsWorld {
+    pub gravity: Vec3,
+    /// Fixed simulation step in seconds
+    pub fixed_dt: f32,
+    /// Upper bound of fixed steps per update, avoids the spiral of death on slow frames
+    pub max_substeps: u32,
+    accumulator: f32,
+    bodies: rp::RigidBodySet,
+    colliders: rp::ColliderSet,
+    pipeline: rp::PhysicsPipeline,
+    islands: rp::IslandManager,
+    broad_phase: rp::DefaultBroadPhase,
+    narrow_phase: rp::NarrowPhase,
+    impulse_joints: rp::ImpulseJointSet,
+    multibody_joints: rp::MultibodyJointSet,
+    ccd: rp::CCDSolver,
+    query_pipeline: rp::QueryPipeline,
+    entity_bodies: HashMap<hecs::Entity, BodyEntry>,
+    entity_statics: HashMap<hecs::Entity, StaticEntry>,
+    ground: Vec<rp::ColliderHandle>,
+}
+
+impl Default for PhysicsWorld {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PhysicsWorld {
+    pub fn new() -> Self {
+        Self {
+            gravity: Vec3::new(0.0, -9.81, 0.0),
+            fixed_dt: 1.0 / 60.0,
+            max_substeps: 8,
+            accumulator: 0.0,
+            bodies: rp::RigidBodySet::new(),
+            colliders: rp::ColliderSet::new(),
+            pipeline: rp::PhysicsPipeline::new(),
+            islands: rp::IslandManager::new(),
+            broad_phase: rp::DefaultBroadPhase::new(),
+            narrow_phase: rp::NarrowPhase::new(),
+            impulse_joints: rp::ImpulseJointSet::new(),
+            multibody_joints: rp::MultibodyJointSet::new(),
+            ccd: rp::CCDSolver::new(),
+            query_pipeline: rp::QueryPipeline::new(),
+            entity_bodies: HashMap::new(),
+            entity_statics: HashMap::new(),
+            ground: Vec::new(),
+        }
+    }
+
+    /// Remove every body and collider
+    pub fn clear(&mut self) {
+        *self = Self {
+            gravity: self.gravity,
+            fixed_dt: self.fixed_dt,
+            max_substeps: self.max_substeps,
+            ..Self::new()
+        };
+    }
+
+    /// Add an infinite static ground plane at height `y`
+    pub fn add_ground_plane(&mut self, y: f32) {
+        let collider = rp::ColliderBuilder::halfspace(Vector3::y_axis())
+            .translation(Vector3::new(0.0, y, 0.0))
+            .build();
+        self.ground.push(self.colliders.insert(collider));
+    }
+
+    pub fn body_count(&self) -> usize {
+        self.bodies.len()
+    }
+
+    /// Sync components, run as many fixed steps as `dt` allows and write transforms back.
+    /// Returns the number of fixed steps taken.
+    pub fn step(&mut self, world: &mut EngineWorld, dt: f32) -> u32 {
+        engine_core::profile_function!();
+        self.sync_from_world(world);
+
+        self.accumulator += dt.clamp(0.0, 0.25);
+        let mut steps = 0;
+        while self.accumulator >= self.fixed_dt && steps < self.max_substeps {
+            self.step_fixed();
+            self.accumulator -= self.fixed_dt;
+            steps += 1;
+        }
+        self.accumulator = self.accumulator.min(self.fixed_dt);
+
+        self.write_to_world(world);
+        steps
+    }
+
+    pub fn linear_velocity(&self, entity: hecs::Entity) -> Option<Vec3> {
+        let entry = self.entity_bodies.get(&entity)?;
+        self.bodies.get(entry.body).map(|b| from_na(b.linvel()))
+    }
+
+    pub fn set_linear_velocity(&mut self, entity: hecs::Entity, velocity: Vec3) -> bool {
+        let Some(body) = self
+            .entity_bodies
+            .get(&entity)
+            .and_then(|e| self.bodies.get_mut(e.body))
+        else {
+            return false;
+        };
+        body.set_linvel(to_na(velocity), true);
+        true
+    }
+
+    pub fn apply_impulse(&mut self, entity: hecs::Entity, impulse: Vec3) -> bool {
+        let Some(body) = self
+            .entity_bodies
+            .get(&entity)
+            .and_then(|e| self.bodies.get_mut(e.body))
+        else {
+            return false;
+        };
+        body.apply_impulse(to_na(impulse), true);
+        true
+    }
+
+    /// Whether a body touches any other collider (ground included)
+    pub fn is_touching(&self, entity: hecs::Entity) -> bool {
+        let Some(entry) = self.entity_bodies.get(&entity) else {
+            return false;
+        };
+        self.narrow_phase
+            .contact_pairs_with(entry.collider)
+            .any(|pair| pair.has_any_active_contact)
+    }
+
+    fn step_fixed(&mut self) {
+        let integration = rp::IntegrationParameters {
+            dt: self.fixed_dt,
+            ..Default::default()
+        };
+        self.pipeline.step(
+            &to_na(self.gravity),
+            &integration,
+            &mut self.islands,
+            &mut self.broad_phase,
+            &mut self.narrow_phase,
+            &mut self.bodies,
+            &mut self.colliders,
+            &mut self.impulse_joints,
+            &mut self.multibody_joints,
+            &mut self.ccd,
+            Some(&mut self.query_pipeline),
+            &(),
+            &(),
+        );
+    }
+
+    fn sync_from_world(&mut self, world: &EngineWorld) {
+        let entities: Vec<PhysicsEntity> = world
+            .world()
+            .query::<(
+                hecs::Entity,
+                &Transform,
+                Option<&RigidBody>,
+                Option<&Collider>,
+            )>()
+            .iter()
+            .filter(|(_, _, rb, col)| rb.is_some() || col.is_some())
+            .map(|(e, t, rb, col)| (e, *t, rb.copied(), col.copied()))
+            .collect();
+
+        let with_body: HashSet<hecs::Entity> = entities
+            .iter()
+            .filter(|(_, _, rb, _)| rb.is_some())
+            .map(|(e, _, _, _)| *e)
+            .collect();
+        let static_only: HashSet<hecs::Entity> = entities
+            .iter()
+            .filter(|(_, _, rb, _)| rb.is_none())
+            .map(|(e, _, _, _)| *e)
+            .collect();
+
+        let removed_bodies: Vec<hecs::Entity> = self
+            .entity_bodies
+            .keys()
+            .filter(|e| !with_body.contains(e))
+            .copied()
+            .collect();
+        for entity in removed_bodies {
+            self.remove_body(entity);
+        }
+        let removed_statics: Vec<hecs::Entity> = self
+            .entity_statics
+            .keys()
+            .filter(|e| !static_only.contains(e))
+            .copied()
+            .collect();
+        for entity in removed_statics {
+            self.remove_static(entity);
+        }
+
+        for (entity, transform, rb, collider) in entities {
+            match rb {
+                Some(rb) => self.sync_body(entity, &transform, rb, collider),
+                None => {
+                    if let Some(collider) = collider {
+                        self.sync_static(entity, &transform, collider);
+                    }
+                }
+            }
+        }
+    }
+
+    fn sync_body(
+        &mut self,
+        entity: hecs::Entity,
+        transform: &Transform,
+        rb: RigidBody,
+        collider: Option<Collider>,
+    ) {
+        let iso = to_iso(transform.position, transform.rotation);
+        let rebuild = self
+            .entity_bodies
+            .get(&entity)
+            .is_none_or(|e| e.last_collider != collider || e.last_body.mass != rb.mass);
+        if rebuild {
+            self.remove_body(entity);
+            let body = rp::RigidBodyBuilder::new(body_type_to_rapier(rb.body_type))
+                .position(iso)
+                .build();
+            let body = self.bodies.insert(body);
+            let col = build_collider(&collider.unwrap_or_default())
+                .mass(rb.mass.max(0.001))
+                .build();
+            let col = self
+                .colliders
+                .insert_with_parent(col, body, &mut self.bodies);
+            self.entity_bodies.insert(
+                entity,
+                BodyEntry {
+                    body,
+                    collider: col,
+                    last_body: rb,
+                    last_collider: collider,
+                },
+            );
+        }
+
+        let Some(entry) = self.entity_bodies.get_mut(&entity) else {
+            return;
+        };
+        let Some(body) = self.bodies.get_mut(entry.body) else {
+            return;
+        };
+        body.set_body_type(body_type_to_rapier(rb.body_type), true);
+        body.set_gravity_scale(rb.gravity_scale, true);
+        body.set_linear_damping(rb.linear_damping);
+        body.set_angular_damping(rb.angular_damping);
+        body.lock_rotations(rb.lock_rotation, true);
+        entry.last_body = rb;
+
+        match rb.body_type {
+            BodyType::Kinematic => body.set_next_kinematic_position(iso),
+            _ => {
+                // The ECS transform was moved from outside (editor, scripts): teleport
+                if iso_differs(body.position(), transform) {
+                    body.set_position(iso, true);
+                }
+            }
+        }
+    }
+
+    fn sync_static(&mut self, entity: hecs::Entity, transform: &Transform, collider: Collider) {
+        let iso = to_iso(transform.position, transform.rotation);
+        let rebuild = self
+            .entity_statics
+            .get(&entity)
+            .is_none_or(|e| e.last_collider != collider);
+        if rebuild {
+            self.remove_static(entity);
+            let col = build_collider(&collider)
+                .position(iso * offset_iso(collider.offset))
+                .build();
+            let handle = self.colliders.insert(col);
+            self.entity_statics.insert(
+                entity,
+                StaticEntry {
+                    collider: handle,
+                    last_collider: collider,
+                },
+            );
+            return;
+        }
+        if let Some(col) = self
+            .entity_statics
+            .get(&entity)
+            .and_then(|e| self.colliders.get_mut(e.collider))
+        {
+            col.set_position(iso * offset_iso(collider.offset));
+        }
+    }
+
+    fn remove_body(&mut self, entity: hecs::Entity) {
+        if let Some(entry) = self.entity_bodies.remove(&entity) {
+            self.bodies.remove(
+                entry.body,
+                &mut self.islands,
+                &mut self.colliders,
+                &mut self.impulse_joints,
+                &mut self.multibody_joints,
+                true,
+            );
+        }
+    }
+
+    fn remove_static(&mut self, entity: hecs::Entity) {
+        if let Some(entry) = self.entity_statics.remove(&entity) {
+            self.colliders
+                .remove(entry.collider, &mut self.islands, &mut self.bodies, true);
+        }
+    }
+
+    fn write_to_world(&self, world: &mut EngineWorld) {
+        for (entity, entry) in &self.entity_bodies {
+            let Some(body) = self.bodies.get(entry.body) else {
+                continue;
+            };
+            if !body.is_dynamic() {
+                continue;
+            }
+            if let Ok(mut t) = world.world_mut().get::<&mut Transform>(*entity) {
+                t.position = from_na(body.translation());
+                t.rotation = from_na_rotation(body.rotation());
+            }
+        }
+    }
+}
+
+fn body_type_to_rapier(body_type: BodyType) -> rp::RigidBodyType {
+    match body_type {
+        BodyType::Dynamic => rp::RigidBodyType::Dynamic,
+        BodyType::Fixed => rp::RigidBodyType::Fixed,
+        BodyType::Kinematic => rp::RigidBodyType::KinematicPositionBased,
+    }
+}
+
+fn build_collider(collider: &Collider) -> rp::ColliderBuilder {
+    let builder = match collider.shape {
+        ColliderShape::Cuboid { half_extents } => rp::ColliderBuilder::cuboid(
+            half_extents.x.max(0.001),
+            half_extents.y.max(0.001),
+            half_extents.z.max(0.001),
+        ),
+        ColliderShape::Sphere { radius } => rp::ColliderBuilder::ball(radius.max(0.001)),
+        ColliderShape::Capsule {
+            half_height,
+            radius,
+        } => rp::ColliderBuilder::capsule_y(half_height.max(0.0), radius.max(0.001)),
+    };
+    builder
+        .translation(to_na(collider.offset))
+        .friction(collider.friction)
+        .restitution(collider.restitution)
+        .sensor(collider.is_sensor)
+}
+
+fn offset_iso(offset: Vec3) -> Isometry3<f32> {
+    Isometry3::translation(offset.x, offset.y, offset.z)
+}
+
+fn to_na(v: Vec3) -> Vector3<f32> {
+    Vector3::new(v.x, v.y, v.z)
+}
+
+fn from_na(v: &Vector3<f32>) -> Vec3 {
+    Vec3::new(v.x, v.y, v.z)
+}
+
+fn from_na_rotation(r: &UnitQuaternion<f32>) -> Quat {
+    let c = r.coords;
+    Quat::from_xyzw(c.x, c.y, c.z, c.w)
+}
+
+fn to_iso(position: Vec3, rotation: Quat) -> Isometry3<f32> {
+    Isometry3::from_parts(
+        Translation3::new(position.x, position.y, position.z),
+        UnitQuaternion::from_quaternion(Quaternion::new(
+            rotation.w, rotation.x, rotation.y, rotation.z,
+        )),
+    )
+}
+
+fn iso_differs(iso: &Isometry3<f32>, transform: &Transform) -> bool {
+    let pos = from_na(&iso.translation.vector);
+    let rot = from_na_rotation(&iso.rotation);
+    pos.distance(transform.position) > SYNC_EPSILON
+        || rot.angle_between(transform.rotation) > SYNC_EPSILON
+}
@@ -0,0 +1,482 @@
+//! Dengine player - runs a game exported by the editor's Build window
+//!
+//! A build is a folder laid out as:
+//!
+//! ```text
+//! <game>[.exe]        this binary, renamed after the game
+//! game.cfg            title, startup_scene, width, height (`key=value` lines)
+//! Data/*.dpak         asset bundles cooked from the project's Assets
+//! Data/<scene>.dscene startup scene
+//! ```
+//!
+//! The editor ships this binary prebuilt and only copies it into the build.
+//! The player mounts the bundles under `Assets`, loads the startup scene and
+//! runs it: scripts and physics step every frame and the meshes, sprites and
+//! 3D text are drawn through the scene's game camera, with the UI text on
+//! top. Assets that fail to load are listed in the window instead of a crash,
+//! so a broken build can still be diagnosed on the player's machine.
+
+mod scene_gpu;
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use eframe::egui;
+use eframe::egui_wgpu;
+use engine_core::{
+    EngineWorld, GameSystems, Input, LightSystem, MeshAsset, MeshRenderer, Parent, SceneComponents,
+    ScriptComponent, ScriptInput, ScriptSystem, Sprite, SpriteTexture, Text3D, TextFont, Time,
+    Transform, UiText, read_scene_with,
+};
+use engine_physics::{PhysicsSceneComponents, PhysicsWorld};
+use engine_render::vfs::{vfs, vfs_mut};
+use engine_render::{BundleReader, ClusteredLights, Renderer, TextBatch};
+use glam::{Mat4, Vec3};
+
+use scene_gpu::{MeshUpload, QuadBatch, SceneCallback, SceneDraw, SceneFrame, SceneResources};
+
+/// Folder next to the executable with the bundles and the startup scene
+const DATA_DIR: &str = "Data";
+const CONFIG_FILE: &str = "game.cfg";
+/// Where the bundles are mounted; scene asset paths are relative to it
+const ASSETS_MOUNT: &str = "Assets";
+const MAX_HIERARCHY_DEPTH: usize = 64;
+
+/// `game.cfg`, written by the editor next to the executable
+struct GameConfig {
+    title: String,
+    startup_scene: String,
+    width: u32,
+    height: u32,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            title: "Dengine".to_string(),
+            startup_scene: String::new(),
+            width: 1280,
+            height: 720,
+        }
+    }
+}
+
+impl GameConfig {
+    fn load(root: &Path) -> Result<Self, String> {
+        let path = root.join(CONFIG_FILE);
+        let raw = fs::read_to_string(&path)
+            .map_err(|e| format!("Falha ao ler {}: {e}", path.display()))?;
+        let mut config = Self::default();
+        for (key, value) in raw.lines().filter_map(|line| line.split_once('=')) {
+            let value = value.trim();
+            match key.trim() {
+                "title" => config.title = value.to_string(),
+                "startup_scene" => config.startup_scene = value.to_string(),
+                "width" => {
+                    if let Ok(width) = value.parse::<u32>() {
+                        config.width = width.max(320);
+                    }
+                }
+                "height" => {
+                    if let Ok(height) = value.parse::<u32>() {
+                        config.height = height.max(240);
+                    }
+                }
+                _ => {}
+            }
+        }
+        if config.startup_scene.is_empty() {
+            return Err(format!("{} sem startup_scene", path.display()));
+        }
+        Ok(config)
+    }
+}
+
+/// The startup scene loaded from the bundles, with the systems that run it
+struct Game {
+    world: EngineWorld,
+    renderer: Renderer,
+    physics: PhysicsWorld,
+    scripts: ScriptSystem,
+    game: GameSystems,
+    input: Input,
+    time: Time,
+    // Meshes already sent to the GPU
+    uploaded: HashSet<u64>,
+    // Sprite textures already sent to the GPU
+    uploaded_textures: HashSet<u64>,
+    // Font atlases already sent to the GPU
+    uploaded_fonts: HashSet<u64>,
+    scene: String,
+    /// Assets or scripts that failed to load; the rest of the scene still runs
+    warnings: Vec<String>,
+}
+
+impl Game {
+    fn load(root: &Path, config: &GameConfig) -> Result<Self, String> {
+        let data = root.join(DATA_DIR);
+        let bundles = BundleReader::mount_dir(&data)?;
+        vfs_mut().mount(ASSETS_MOUNT, bundles);
+        let scene_path = data.join(&config.startup_scene);
+        let raw = fs::read_to_string(&scene_path)
+            .map_err(|e| format!("Falha ao ler a cena {}: {e}", scene_path.display()))?;
+        let snapshot = read_scene_with(&raw, &[&PhysicsSceneComponents])
+            .map_err(|e| format!("Cena {} inválida: {e}", config.startup_scene))?;
+
+        let mut world = EngineWorld::new();
+        PhysicsSceneComponents.register(&mut world);
+        world.restore(&snapshot);
+        flatten_hierarchy(&mut world);
+
+        let mut renderer = Renderer::default();
+        let mut warnings = Vec::new();
+        let meshes: Vec<(hecs::Entity, String)> = world
+            .world()
+            .query::<(hecs::Entity, &MeshAsset)>()
+            .iter()
+            .map(|(entity, asset)| (entity, asset.path.clone()))
+            .collect();
+        for (entity, key) in meshes {
+            let loaded = vfs()
+                .local_path(Path::new(ASSETS_MOUNT).join(&key))
+                .and_then(|path| renderer.load_mesh(&path));
+            match loaded {
+                Ok(handle) => {
+                    let _ = world
+                        .world_mut()
+                        .insert_one(entity, MeshRenderer::new(handle));
+                }
+                Err(e) => warnings.push(format!("Malha {key}: {e}")),
+            }
+        }
+
+        let sprites: Vec<(hecs::Entity, String)> = world
+            .world()
+            .query::<(hecs::Entity, &SpriteTexture)>()
+            .iter()
+            .map(|(entity, texture)| (entity, texture.path.clone()))
+            .collect();
+        // Atlases first: sprites whose texture was packed are drawn from the page
+        for (path, e) in renderer.load_sprite_atlases(ASSETS_MOUNT) {
+            warnings.push(format!("Atlas {}: {e}", path.display()));
+        }
+        for (entity, key) in sprites {
+            // Read through the VFS under the virtual path, so the texture is
+            // matched against the atlases by its Assets key
+            match renderer.load_texture(&Path::new(ASSETS_MOUNT).join(&key)) {
+                Ok(handle) => {
+                    if let Ok(mut sprite) = world.world_mut().get::<&mut Sprite>(entity) {
+                        sprite.texture = handle;
+                    }
+                }
+                Err(e) => warnings.push(format!("Sprite {key}: {e}")),
+            }
+        }
+
+        let texts: Vec<(hecs::Entity, String)> = world
+            .world()
+            .query::<(hecs::Entity, &TextFont)>()
+            .iter()
+            .map(|(entity, font)| (entity, font.path.clone()))
+            .collect();
+        for (entity, key) in texts {
+            match renderer.load_font(&Path::new(ASSETS_MOUNT).join(&key)) {
+                Ok(handle) => {
+                    if let Ok(mut text) = world.world_mut().get::<&mut Text3D>(entity) {
+                        text.font = handle;
+                    }
+                    if let Ok(mut text) = world.world_mut().get::<&mut UiText>(entity) {
+                        text.font = handle;
+                    }
+                }
+                Err(e) => warnings.push(format!("Fonte {key}: {e}")),
+            }
+        }
+
+        // Scripts are read from disk: point them at the files extracted from
+        // the bundles
+        for script in world.world_mut().query_mut::<&mut ScriptComponent>() {
+            let key = script.path.clone();
+            match vfs().local_path(Path::new(ASSETS_MOUNT).join(&key)) {
+                Ok(path) => script.path = path,
+                Err(e) => {
+                    warnings.push(format!("Script {}: {e}", key.display()));
+                    script.enabled = false;
+                }
+            }
+        }
+
+        let mut physics = PhysicsWorld::new();
+        physics.add_ground_plane(0.0);
+        Ok(Self {
+            world,
+            renderer,
+            physics,
+            scripts: ScriptSystem::new(),
+            game: GameSystems::new(),
+            input: Input::new(),
+            time: Time::new(),
+            uploaded: HashSet::new(),
+            uploaded_textures: HashSet::new(),
+            uploaded_fonts: HashSet::new(),
+            scene: config.startup_scene.clone(),
+            warnings,
+        })
+    }
+
+    /// Input, scripts, game state, timers and physics for one frame
+    fn update(&mut self, ctx: &egui::Context) {
+        self.time.advance(ctx.input(|i| i.stable_dt));
+        let dt = self.time.delta;
+        let input = &mut self.input;
+        input.begin_frame();
+        ctx.input(|i| {
+            if i.focused {
+                input.keys.set_down(i.keys_down.iter().map(|k| k.name()));
+            } else {
+                input.keys.reset();
+            }
+        });
+        let script_input = ScriptInput {
+            axis: [input.axis("Horizontal"), input.axis("Vertical")],
+            look: [input.axis("LookX"), input.axis("LookY")],
+            action: if input.key_pressed("Space") { 1.0 } else { 0.0 },
+            keys_down: input.keys.get_pressed().map(|k| k.to_string()).collect(),
+            game_state: self.game.state(),
+        };
+        self.scripts.update(&mut self.world, &script_input, dt);
+        if let Some(state) = self.scripts.take_state_request() {
+            self.game.states.set(state);
+        }
+        self.game.update(&mut self.world, dt);
+        if self.game.is_playing() {
+            self.physics.step(&mut self.world, dt);
+        }
+    }
+
+    /// What the GPU draws this frame, seen through the scene's game camera
+    fn frame(&mut self, size: [u32; 2]) -> SceneFrame {
+        self.renderer.set_viewport_size(size[0], size[1]);
+        self.renderer.use_game_camera(&self.world);
+        let output = self.renderer.render(&self.world);
+        let camera = self.renderer.camera();
+        let view = camera.view_matrix();
+        let projection = camera.projection_matrix();
+        let camera_forward = (camera.target - camera.position).normalize_or(Vec3::NEG_Z);
+        let lights = ClusteredLights::build(
+            &LightSystem.update(&self.world),
+            view,
+            projection,
+            camera.near,
+            camera.far,
+        );
+
+        let mut uploads = Vec::new();
+        let draws = output
+            .renderables
+            .iter()
+            .map(|renderable| {
+                let id = renderable.mesh.id;
+                if self.uploaded.insert(id) {
+                    if let Some(mesh) = self.renderer.get_mesh(renderable.mesh) {
+                        uploads.push((id, MeshUpload::new(mesh)));
+                    }
+                }
+                SceneDraw {
+                    mesh: id,
+                    model: renderable.transform.to_mat4(),
+                }
+            })
+            .collect();
+
+        let mut texture_uploads = Vec::new();
+        let sprites = output
+            .sprites
+            .iter()
+            .map(|batch| {
+                let id = batch.texture.id;
+                if self.uploaded_textures.insert(id) {
+                    if let Some(texture) = self.renderer.asset_manager().get_texture(batch.texture)
+                    {
+                        texture_uploads.push((id, texture.clone()));
+                    }
+                }
+                QuadBatch {
+                    texture: id,
+                    vertices: batch.vertex_bytes(),
+                    indices: batch.indices.clone(),
+                }
+            })
+            .collect();
+
+        // 3D and UI text share the fonts' atlases
+        let mut font_uploads = Vec::new();
+        for batch in output.texts.iter().chain(&output.ui_texts) {
+            let id = batch.font.id;
+            if self.uploaded_fonts.insert(id) {
+                if let Some(font) = self.renderer.asset_manager().get_font(batch.font) {
+                    font_uploads.push((id, font.to_texture_data()));
+                }
+            }
+        }
+        let text_quads = |batch: &TextBatch| QuadBatch {
+            texture: batch.font.id,
+            vertices: batch.vertex_bytes(),
+            indices: batch.indices.clone(),
+        };
+        let texts = output.texts.iter().map(text_quads).collect();
+        let ui_texts = output.ui_texts.iter().map(text_quads).collect();
+        SceneFrame {
+            draws,
+            view_projection: output.view_projection,
+            camera_position: output.camera_position,
+            camera_forward,
+            lights,
+            uploads,
+            sprites,
+            texture_uploads,
+            texts,
+            ui_texts,
+            font_uploads,
+        }
+    }
+
+    fn show(&mut self, ui: &mut egui::Ui) {
+        let rect = ui.max_rect();
+        let pixels_per_point = ui.ctx().pixels_per_point();
+        let size = [
+            (rect.width() * pixels_per_point).max(1.0) as u32,
+            (rect.height() * pixels_per_point).max(1.0) as u32,
+        ];
+        let frame = self.frame(size);
+        ui.painter().add(egui_wgpu::Callback::new_paint_callback(
+            rect,
+            SceneCallback { frame },
+        ));
+
+        let warning = egui::Color32::from_rgb(230, 180, 80);
+        for message in &self.warnings {
+            ui.colored_label(warning, message);
+        }
+        for error in self.scripts.errors() {
+            ui.colored_label(egui::Color32::from_rgb(230, 90, 90), error);
+        }
+        if !self.warnings.is_empty() {
+            ui.label(
+                egui::RichText::new(&self.scene)
+                    .small()
+                    .color(egui::Color32::from_gray(120)),
+            );
+        }
+    }
+}
+
+/// Transform of `entity` in world space, composed up its `Parent` chain
+fn world_matrix(world: &EngineWorld, entity: hecs::Entity) -> Mat4 {
+    let mut matrix = Mat4::IDENTITY;
+    let mut current = Some(entity);
+    // Bounded so a cycle in a hand-edited scene can't hang the player
+    for _ in 0..MAX_HIERARCHY_DEPTH {
+        let Some(e) = current else {
+            break;
+        };
+        if let Ok(transform) = world.world().get::<&Transform>(e) {
+            matrix = transform.to_mat4() * matrix;
+        }
+        current = world
+            .world()
+            .get::<&Parent>(e)
+            .ok()
+            .and_then(|parent| parent.0.get(world));
+    }
+    matrix
+}
+
+/// Bake parented transforms to world space. The renderer, physics and
+/// scripts all read `Transform` as world space, like the editor's Play mode
+fn flatten_hierarchy(world: &mut EngineWorld) {
+    let baked: Vec<(hecs::Entity, Transform)> = world
+        .world()
+        .query::<(hecs::Entity, &Parent)>()
+        .iter()
+        .map(|(entity, _)| {
+            let (scale, rotation, position) =
+                world_matrix(world, entity).to_scale_rotation_translation();
+            (entity, Transform::new(position, rotation, scale))
+        })
+        .collect();
+    for (entity, transform) in baked {
+        let _ = world.world_mut().insert_one(entity, transform);
+        let _ = world.world_mut().remove_one::<Parent>(entity);
+    }
+}
+
+struct PlayerApp {
+    game: Result<Game, String>,
+}
+
+impl eframe::App for PlayerApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+        }
+        if let Ok(game) = &mut self.game {
+            game.update(ctx);
+        }
+
+        egui::CentralPanel::default()
+            .frame(egui::Frame::new().fill(egui::Color32::BLACK))
+            .show(ctx, |ui| match &mut self.game {
+                Ok(game) => game.show(ui),
+                Err(err) => {
+                    ui.colored_label(egui::Color32::from_rgb(230, 90, 90), err.as_str());
+                }
+            });
+        ctx.request_repaint();
+    }
+}
+
+/// Folder of the running executable; builds keep everything next to it
+fn game_root() -> PathBuf {
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf))
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+fn main() -> eframe::Result<()> {
+    let root = game_root();
+    let (config, game) = match GameConfig::load(&root) {
+        Ok(config) => {
+            let game = Game::load(&root, &config);
+            (config, game)
+        }
+        Err(err) => (GameConfig::default(), Err(err)),
+    };
+    let options = eframe::NativeOptions {
+        viewport: egui::ViewportBuilder::default()
+            .with_title(config.title.clone())
+            .with_inner_size([config.width as f32, config.height as f32]),
+        renderer: eframe::Renderer::Wgpu,
+        // Scene meshes are depth tested inside egui's render pass
+        depth_buffer: 24,
+        ..Default::default()
+    };
+    eframe::run_native(
+        &config.title,
+        options,
+        Box::new(move |cc| {
+            let render_state = cc
+                .wgpu_render_state
+                .as_ref()
+                .ok_or("wgpu indisponível para o player")?;
+            render_state
+                .renderer
+                .write()
+                .callback_resources
+                .insert(SceneResources::new(render_state));
+            Ok(Box::new(PlayerApp { game }))
+        }),
+    )
+}
@@ -0,0 +1,873 @@
+//! Draws the world inside egui's render pass
+//!
+//! Every frame the player turns the `RenderOutput` of engine_render into a
+//! `SceneFrame` and hands it to a paint callback. The meshes are drawn with
+//! engine_render's `LIT_SHADER` (clustered lights, no IBL) into the window's
+//! color target and the depth buffer requested through
+//! `NativeOptions::depth_buffer`. Meshes are uploaded once, the first frame
+//! they are drawn. Sprite batches follow the meshes with `SPRITE_SHADER`,
+//! depth tested against them; their textures are uploaded the first frame a
+//! batch uses them. 3D text follows with `TEXT_SHADER`, in the same vertex
+//! layout, sampling each font's SDF atlas. UI text is already in clip space:
+//! it is drawn last with the same shader and an identity view-projection,
+//! ignoring the depth buffer.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use eframe::egui;
+use eframe::egui_wgpu;
+use eframe::wgpu;
+use eframe::wgpu::util::DeviceExt;
+use glam::{Mat4, Vec3};
+
+use engine_render::asset_manager::TextureData;
+use engine_render::lighting::ClusteredLights;
+use engine_render::mesh::MeshData;
+use engine_render::shader::{
+    LIT_SHADER, LIT_UNIFORM_SIZE, LIT_VERTEX_STRIDE, SPRITE_SHADER, SPRITE_UNIFORM_SIZE,
+    SPRITE_VERTEX_STRIDE, TEXT_SHADER,
+};
+use engine_render::vfs::open_image;
+
+/// Geometry of one mesh, packed for the lit shader's vertex layout
+pub struct MeshUpload {
+    vertices: Vec<u8>,
+    indices: Vec<u32>,
+    texture: Option<PathBuf>,
+}
+
+impl MeshUpload {
+    pub fn new(mesh: &MeshData) -> Self {
+        Self {
+            vertices: mesh
+                .vertices
+                .iter()
+                .flat_map(|v| v.to_packed())
+                .flat_map(f32::to_le_bytes)
+                .collect(),
+            indices: mesh.indices.clone(),
+            texture: mesh.albedo_texture_path.clone(),
+        }
+    }
+}
+
+/// Quads sharing one texture (or font), in the sprite shader's vertex layout
+pub struct QuadBatch {
+    pub texture: u64,
+    pub vertices: Vec<u8>,
+    pub indices: Vec<u32>,
+}
+
+/// One mesh instance to draw
+pub struct SceneDraw {
+    pub mesh: u64,
+    pub model: Mat4,
+}
+
+/// Everything the GPU needs for one frame
+pub struct SceneFrame {
+    pub draws: Vec<SceneDraw>,
+    pub view_projection: Mat4,
+    pub camera_position: Vec3,
+    pub camera_forward: Vec3,
+    pub lights: ClusteredLights,
+    /// Meshes drawn for the first time this frame
+    pub uploads: Vec<(u64, MeshUpload)>,
+    /// Sprite batches, back to front, drawn after the meshes
+    pub sprites: Vec<QuadBatch>,
+    /// Sprite textures (or atlas pages) used for the first time this frame
+    pub texture_uploads: Vec<(u64, TextureData)>,
+    /// 3D text batches by font id, back to front, drawn after the sprites
+    pub texts: Vec<QuadBatch>,
+    /// Runtime UI text batches in clip space, drawn over everything
+    pub ui_texts: Vec<QuadBatch>,
+    /// Font SDF atlases used for the first time this frame
+    pub font_uploads: Vec<(u64, TextureData)>,
+}
+
+struct GpuMesh {
+    vertices: wgpu::Buffer,
+    indices: wgpu::Buffer,
+    index_count: u32,
+    texture: Option<wgpu::TextureView>,
+}
+
+/// Vertex and index buffers of one quad batch, remade every frame
+struct QuadBuffers {
+    texture: u64,
+    vertices: wgpu::Buffer,
+    indices: wgpu::Buffer,
+    index_count: u32,
+}
+
+/// Uniform buffer of one draw and the bind group made for it
+struct DrawSlot {
+    uniform: wgpu::Buffer,
+    // Mesh and light buffer generation the bind group was made for
+    bound: Option<(u64, u64, wgpu::BindGroup)>,
+}
+
+/// GPU state kept in egui's callback resources
+pub struct SceneResources {
+    pipeline: wgpu::RenderPipeline,
+    layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    env_sampler: wgpu::Sampler,
+    white: wgpu::TextureView,
+    // Stands in for the IBL maps, which are off (`ibl_params.x` = 0)
+    black: wgpu::TextureView,
+    lights: wgpu::Buffer,
+    clusters: wgpu::Buffer,
+    light_indices: wgpu::Buffer,
+    // Bumped when a light buffer is recreated, so bind groups are remade
+    light_generation: u64,
+    meshes: HashMap<u64, GpuMesh>,
+    slots: Vec<DrawSlot>,
+    sprite_pipeline: wgpu::RenderPipeline,
+    // Uniforms, texture and sampler of the quad shaders
+    quad_layout: wgpu::BindGroupLayout,
+    // View-projection of the frame, shared by every sprite and text batch
+    quad_uniform: wgpu::Buffer,
+    // Nearest filtering keeps pixel art crisp
+    sprite_sampler: wgpu::Sampler,
+    // Sprite textures by handle id, with the bind group that samples them
+    sprite_textures: HashMap<u64, (wgpu::TextureView, wgpu::BindGroup)>,
+    // Sprites without a texture (or whose file failed to load) are white quads
+    untextured_sprite: wgpu::BindGroup,
+    sprite_batches: Vec<QuadBuffers>,
+    text_pipeline: wgpu::RenderPipeline,
+    // Linear filtering keeps the SDF edges smooth when scaled
+    text_sampler: wgpu::Sampler,
+    // Font atlases by font id, with the bind groups that sample them
+    font_textures: HashMap<u64, FontTexture>,
+    text_batches: Vec<QuadBuffers>,
+    ui_pipeline: wgpu::RenderPipeline,
+    // Identity view-projection of the clip-space UI text
+    ui_uniform: wgpu::Buffer,
+    ui_batches: Vec<QuadBuffers>,
+}
+
+/// SDF atlas of one font, bound with the frame's view-projection (3D text)
+/// and with the identity (UI text)
+struct FontTexture {
+    _view: wgpu::TextureView,
+    world: wgpu::BindGroup,
+    ui: wgpu::BindGroup,
+}
+
+fn pixel_texture(device: &wgpu::Device, queue: &wgpu::Queue, rgba: [u8; 4]) -> wgpu::TextureView {
+    device
+        .create_texture_with_data(
+            queue,
+            &wgpu::TextureDescriptor {
+                label: Some("player_pixel"),
+                size: wgpu::Extent3d {
+                    width: 1,
+                    height: 1,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+            wgpu::util::TextureDataOrder::LayerMajor,
+            &rgba,
+        )
+        .create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+fn rgba_texture(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    label: &str,
+    [width, height]: [u32; 2],
+    rgba: &[u8],
+) -> wgpu::TextureView {
+    device
+        .create_texture_with_data(
+            queue,
+            &wgpu::TextureDescriptor {
+                label: Some(label),
+                size: wgpu::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+            wgpu::util::TextureDataOrder::LayerMajor,
+            rgba,
+        )
+        .create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+fn load_texture(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    path: &Path,
+) -> Result<wgpu::TextureView, String> {
+    let image = open_image(path)?.to_rgba8();
+    Ok(rgba_texture(
+        device,
+        queue,
+        "player_albedo",
+        [image.width(), image.height()],
+        image.as_raw(),
+    ))
+}
+
+/// Layout shared by the quad shaders: view-projection, texture, sampler
+fn quad_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+    device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("player_quad_layout"),
+        entries: &[
+            wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 1,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 2,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                count: None,
+            },
+        ],
+    })
+}
+
+fn quad_bind_group(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    uniform: &wgpu::Buffer,
+    texture: &wgpu::TextureView,
+    sampler: &wgpu::Sampler,
+) -> wgpu::BindGroup {
+    device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("player_quad_bind_group"),
+        layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform.as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::TextureView(texture),
+            },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: wgpu::BindingResource::Sampler(sampler),
+            },
+        ],
+    })
+}
+
+/// Alpha-blended quads in the sprite vertex layout (pos, uv, color). They
+/// never write depth, so sorted quads blend over each other; `depth_compare`
+/// tests them against the meshes (or not, for the UI)
+fn quad_pipeline(
+    device: &wgpu::Device,
+    format: wgpu::TextureFormat,
+    layout: &wgpu::BindGroupLayout,
+    source: &str,
+    entry_points: [&str; 2],
+    depth_compare: wgpu::CompareFunction,
+) -> wgpu::RenderPipeline {
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("player_quad_shader"),
+        source: wgpu::ShaderSource::Wgsl(source.into()),
+    });
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("player_quad_pipeline_layout"),
+        bind_group_layouts: &[layout],
+        push_constant_ranges: &[],
+    });
+    let vertex_layout = wgpu::VertexBufferLayout {
+        array_stride: SPRITE_VERTEX_STRIDE as u64,
+        step_mode: wgpu::VertexStepMode::Vertex,
+        attributes: &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x2, 2 => Float32x4],
+    };
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("player_quad_pipeline"),
+        layout: Some(&pipeline_layout),
+        vertex: wgpu::VertexState {
+            module: &shader,
+            entry_point: Some(entry_points[0]),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            buffers: &[vertex_layout],
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: &shader,
+            entry_point: Some(entry_points[1]),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+        }),
+        // Flipped sprites turn their back to the camera
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            cull_mode: None,
+            ..Default::default()
+        },
+        depth_stencil: Some(wgpu::DepthStencilState {
+            format: wgpu::TextureFormat::Depth24Plus,
+            depth_write_enabled: false,
+            depth_compare,
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        }),
+        multisample: wgpu::MultisampleState::default(),
+        multiview: None,
+        cache: None,
+    })
+}
+
+fn quad_buffers(device: &wgpu::Device, batch: &QuadBatch) -> QuadBuffers {
+    QuadBuffers {
+        texture: batch.texture,
+        vertices: device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("player_quad_vertices"),
+            contents: &batch.vertices,
+            usage: wgpu::BufferUsages::VERTEX,
+        }),
+        indices: device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("player_quad_indices"),
+            contents: &batch
+                .indices
+                .iter()
+                .flat_map(|i| i.to_le_bytes())
+                .collect::<Vec<u8>>(),
+            usage: wgpu::BufferUsages::INDEX,
+        }),
+        index_count: batch.indices.len() as u32,
+    }
+}
+
+fn storage_buffer(device: &wgpu::Device, label: &str, bytes: &[u8]) -> wgpu::Buffer {
+    device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some(label),
+        contents: bytes,
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+    })
+}
+
+/// Write `bytes` into the buffer, recreating it when it is too small; true
+/// when it was recreated
+fn write_storage(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    buffer: &mut wgpu::Buffer,
+    label: &str,
+    bytes: &[u8],
+) -> bool {
+    if (bytes.len() as u64) > buffer.size() {
+        *buffer = storage_buffer(device, label, bytes);
+        return true;
+    }
+    queue.write_buffer(buffer, 0, bytes);
+    false
+}
+
+fn upload_mesh(device: &wgpu::Device, queue: &wgpu::Queue, upload: &MeshUpload) -> GpuMesh {
+    GpuMesh {
+        vertices: device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("player_mesh_vertices"),
+            contents: &upload.vertices,
+            usage: wgpu::BufferUsages::VERTEX,
+        }),
+        indices: device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("player_mesh_indices"),
+            contents: &upload
+                .indices
+                .iter()
+                .flat_map(|i| i.to_le_bytes())
+                .collect::<Vec<u8>>(),
+            usage: wgpu::BufferUsages::INDEX,
+        }),
+        index_count: upload.indices.len() as u32,
+        // A texture that fails to load leaves the mesh untextured
+        texture: upload
+            .texture
+            .as_ref()
+            .and_then(|path| load_texture(device, queue, path).ok()),
+    }
+}
+
+/// Lit shader uniforms of one draw (see `LIT_SHADER`); the single legacy
+/// light is off, every light comes from the clustered list
+fn uniform_bytes(frame: &SceneFrame, model: Mat4, has_texture: bool) -> Vec<u8> {
+    let lights = &frame.lights;
+    let mut values: Vec<f32> = Vec::with_capacity(LIT_UNIFORM_SIZE / 4);
+    values.extend_from_slice(&(frame.view_projection * model).to_cols_array());
+    values.extend_from_slice(&model.to_cols_array());
+    values.extend_from_slice(&frame.camera_position.to_array());
+    // light_intensity, light_dir, light_enabled, light_color
+    values.extend_from_slice(&[0.0; 8]);
+    values.push(if has_texture { 1.0 } else { 0.0 });
+    // tint
+    values.extend_from_slice(&[1.0; 4]);
+    values.extend_from_slice(&[
+        lights.near,
+        lights.far,
+        lights.directional_count as f32,
+        lights.local_count() as f32,
+    ]);
+    values.extend_from_slice(&frame.camera_forward.extend(0.0).to_array());
+    // ibl_params, then fog color/params/height (fog off)
+    values.extend_from_slice(&[0.0; 16]);
+    values.iter().flat_map(|f| f.to_le_bytes()).collect()
+}
+
+impl SceneResources {
+    pub fn new(render_state: &egui_wgpu::RenderState) -> Self {
+        let device = &render_state.device;
+        let queue = &render_state.queue;
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("player_lit_shader"),
+            source: wgpu::ShaderSource::Wgsl(LIT_SHADER.into()),
+        });
+        // Vertex layout: pos(vec3) + normal(vec3) + uv(vec2) = 32 bytes
+        let vertex_layout = wgpu::VertexBufferLayout {
+            array_stride: LIT_VERTEX_STRIDE as u64,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &[
+                wgpu::VertexAttribute {
+                    format: wgpu::VertexFormat::Float32x3,
+                    offset: 0,
+                    shader_location: 0,
+                },
+                wgpu::VertexAttribute {
+                    format: wgpu::VertexFormat::Float32x3,
+                    offset: 12,
+                    shader_location: 1,
+                },
+                wgpu::VertexAttribute {
+                    format: wgpu::VertexFormat::Float32x2,
+                    offset: 24,
+                    shader_location: 2,
+                },
+            ],
+        };
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("player_lit_pipeline"),
+            layout: None,
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+                buffers: &[vertex_layout],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: render_state.target_format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                cull_mode: Some(wgpu::Face::Back),
+                front_face: wgpu::FrontFace::Ccw,
+                ..Default::default()
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: wgpu::TextureFormat::Depth24Plus,
+                depth_write_enabled: true,
+                depth_compare: wgpu::CompareFunction::LessEqual,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+        let layout = pipeline.get_bind_group_layout(0);
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("player_albedo_sampler"),
+            address_mode_u: wgpu::AddressMode::Repeat,
+            address_mode_v: wgpu::AddressMode::Repeat,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        let env_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("player_env_sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        let quad_layout = quad_bind_group_layout(device);
+        let sprite_pipeline = quad_pipeline(
+            device,
+            render_state.target_format,
+            &quad_layout,
+            SPRITE_SHADER,
+            ["vs_sprite", "fs_sprite"],
+            wgpu::CompareFunction::LessEqual,
+        );
+        let sprite_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("player_sprite_sampler"),
+            mag_filter: wgpu::FilterMode::Nearest,
+            min_filter: wgpu::FilterMode::Nearest,
+            ..Default::default()
+        });
+        let text_pipeline = quad_pipeline(
+            device,
+            render_state.target_format,
+            &quad_layout,
+            TEXT_SHADER,
+            ["vs_text", "fs_text"],
+            wgpu::CompareFunction::LessEqual,
+        );
+        let ui_pipeline = quad_pipeline(
+            device,
+            render_state.target_format,
+            &quad_layout,
+            TEXT_SHADER,
+            ["vs_text", "fs_text"],
+            wgpu::CompareFunction::Always,
+        );
+        let text_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("player_text_sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        // Both quad shaders take a bare view-projection
+        let quad_uniform = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("player_quad_uniforms"),
+            size: SPRITE_UNIFORM_SIZE as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let ui_uniform = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("player_ui_uniforms"),
+            contents: &Mat4::IDENTITY
+                .to_cols_array()
+                .iter()
+                .flat_map(|f| f.to_le_bytes())
+                .collect::<Vec<u8>>(),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        let white = pixel_texture(device, queue, [255; 4]);
+        let untextured_sprite =
+            quad_bind_group(device, &quad_layout, &quad_uniform, &white, &sprite_sampler);
+        let empty = ClusteredLights::default();
+        Self {
+            pipeline,
+            layout,
+            sampler,
+            env_sampler,
+            white,
+            black: pixel_texture(device, queue, [0, 0, 0, 255]),
+            lights: storage_buffer(device, "player_lights", &empty.light_bytes()),
+            clusters: storage_buffer(device, "player_clusters", &empty.cluster_bytes()),
+            light_indices: storage_buffer(device, "player_light_indices", &empty.index_bytes()),
+            light_generation: 0,
+            meshes: HashMap::new(),
+            slots: Vec::new(),
+            sprite_pipeline,
+            quad_layout,
+            quad_uniform,
+            sprite_sampler,
+            sprite_textures: HashMap::new(),
+            untextured_sprite,
+            sprite_batches: Vec::new(),
+            text_pipeline,
+            text_sampler,
+            font_textures: HashMap::new(),
+            text_batches: Vec::new(),
+            ui_pipeline,
+            ui_uniform,
+            ui_batches: Vec::new(),
+        }
+    }
+}
+
+/// Paint callback drawing one `SceneFrame`
+pub struct SceneCallback {
+    pub frame: SceneFrame,
+}
+
+impl egui_wgpu::CallbackTrait for SceneCallback {
+    fn prepare(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        _screen_descriptor: &egui_wgpu::ScreenDescriptor,
+        _egui_encoder: &mut wgpu::CommandEncoder,
+        callback_resources: &mut egui_wgpu::CallbackResources,
+    ) -> Vec<wgpu::CommandBuffer> {
+        let Some(resources) = callback_resources.get_mut::<SceneResources>() else {
+            return Vec::new();
+        };
+        let frame = &self.frame;
+        for (id, upload) in &frame.uploads {
+            resources
+                .meshes
+                .insert(*id, upload_mesh(device, queue, upload));
+        }
+
+        let lights = &frame.lights;
+        let resized = [
+            write_storage(
+                device,
+                queue,
+                &mut resources.lights,
+                "player_lights",
+                &lights.light_bytes(),
+            ),
+            write_storage(
+                device,
+                queue,
+                &mut resources.clusters,
+                "player_clusters",
+                &lights.cluster_bytes(),
+            ),
+            write_storage(
+                device,
+                queue,
+                &mut resources.light_indices,
+                "player_light_indices",
+                &lights.index_bytes(),
+            ),
+        ];
+        if resized.contains(&true) {
+            resources.light_generation += 1;
+        }
+
+        while resources.slots.len() < frame.draws.len() {
+            resources.slots.push(DrawSlot {
+                uniform: device.create_buffer(&wgpu::BufferDescriptor {
+                    label: Some("player_draw_uniforms"),
+                    size: LIT_UNIFORM_SIZE as u64,
+                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                }),
+                bound: None,
+            });
+        }
+        let generation = resources.light_generation;
+        for (draw, slot) in frame.draws.iter().zip(resources.slots.iter_mut()) {
+            let Some(mesh) = resources.meshes.get(&draw.mesh) else {
+                continue;
+            };
+            queue.write_buffer(
+                &slot.uniform,
+                0,
+                &uniform_bytes(frame, draw.model, mesh.texture.is_some()),
+            );
+            if slot
+                .bound
+                .as_ref()
+                .is_some_and(|(id, g, _)| *id == draw.mesh && *g == generation)
+            {
+                continue;
+            }
+            let albedo = mesh.texture.as_ref().unwrap_or(&resources.white);
+            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("player_draw_bind_group"),
+                layout: &resources.layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: slot.uniform.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(&resources.sampler),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::TextureView(albedo),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 3,
+                        resource: resources.lights.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 4,
+                        resource: resources.clusters.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 5,
+                        resource: resources.light_indices.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 6,
+                        resource: wgpu::BindingResource::TextureView(&resources.black),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 7,
+                        resource: wgpu::BindingResource::TextureView(&resources.black),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 8,
+                        resource: wgpu::BindingResource::Sampler(&resources.env_sampler),
+                    },
+                ],
+            });
+            slot.bound = Some((draw.mesh, generation, bind_group));
+        }
+
+        for (id, texture) in &frame.texture_uploads {
+            let view = rgba_texture(
+                device,
+                queue,
+                "player_sprite_texture",
+                [texture.width, texture.height],
+                &texture.rgba,
+            );
+            let bind_group = quad_bind_group(
+                device,
+                &resources.quad_layout,
+                &resources.quad_uniform,
+                &view,
+                &resources.sprite_sampler,
+            );
+            resources.sprite_textures.insert(*id, (view, bind_group));
+        }
+        // The distance is stored linearly in alpha, so never as sRGB
+        for (id, atlas) in &frame.font_uploads {
+            let view = rgba_texture(
+                device,
+                queue,
+                "player_font_atlas",
+                [atlas.width, atlas.height],
+                &atlas.rgba,
+            );
+            let [world, ui] = [&resources.quad_uniform, &resources.ui_uniform].map(|uniform| {
+                quad_bind_group(
+                    device,
+                    &resources.quad_layout,
+                    uniform,
+                    &view,
+                    &resources.text_sampler,
+                )
+            });
+            resources.font_textures.insert(
+                *id,
+                FontTexture {
+                    _view: view,
+                    world,
+                    ui,
+                },
+            );
+        }
+        let view_projection: Vec<u8> = frame
+            .view_projection
+            .to_cols_array()
+            .iter()
+            .flat_map(|f| f.to_le_bytes())
+            .collect();
+        queue.write_buffer(&resources.quad_uniform, 0, &view_projection);
+        resources.sprite_batches = frame
+            .sprites
+            .iter()
+            .filter(|batch| !batch.indices.is_empty())
+            .map(|batch| quad_buffers(device, batch))
+            .collect();
+        resources.text_batches = frame
+            .texts
+            .iter()
+            .filter(|batch| !batch.indices.is_empty())
+            .map(|batch| quad_buffers(device, batch))
+            .collect();
+        resources.ui_batches = frame
+            .ui_texts
+            .iter()
+            .filter(|batch| !batch.indices.is_empty())
+            .map(|batch| quad_buffers(device, batch))
+            .collect();
+        Vec::new()
+    }
+
+    fn paint(
+        &self,
+        _info: egui::PaintCallbackInfo,
+        render_pass: &mut wgpu::RenderPass<'static>,
+        callback_resources: &egui_wgpu::CallbackResources,
+    ) {
+        let Some(resources) = callback_resources.get::<SceneResources>() else {
+            return;
+        };
+        render_pass.set_pipeline(&resources.pipeline);
+        for (draw, slot) in self.frame.draws.iter().zip(&resources.slots) {
+            let (Some(mesh), Some((_, _, bind_group))) =
+                (resources.meshes.get(&draw.mesh), &slot.bound)
+            else {
+                continue;
+            };
+            render_pass.set_bind_group(0, bind_group, &[]);
+            render_pass.set_vertex_buffer(0, mesh.vertices.slice(..));
+            render_pass.set_index_buffer(mesh.indices.slice(..), wgpu::IndexFormat::Uint32);
+            render_pass.draw_indexed(0..mesh.index_count, 0, 0..1);
+        }
+
+        render_pass.set_pipeline(&resources.sprite_pipeline);
+        for batch in &resources.sprite_batches {
+            let bind_group = resources
+                .sprite_textures
+                .get(&batch.texture)
+                .map_or(&resources.untextured_sprite, |(_, bind_group)| bind_group);
+            render_pass.set_bind_group(0, bind_group, &[]);
+            render_pass.set_vertex_buffer(0, batch.vertices.slice(..));
+            render_pass.set_index_buffer(batch.indices.slice(..), wgpu::IndexFormat::Uint32);
+            render_pass.draw_indexed(0..batch.index_count, 0, 0..1);
+        }
+
+        render_pass.set_pipeline(&resources.text_pipeline);
+        for batch in &resources.text_batches {
+            // Text whose font failed to load is not drawn
+            let Some(font) = resources.font_textures.get(&batch.texture) else {
+                continue;
+            };
+            render_pass.set_bind_group(0, &font.world, &[]);
+            render_pass.set_vertex_buffer(0, batch.vertices.slice(..));
+            render_pass.set_index_buffer(batch.indices.slice(..), wgpu::IndexFormat::Uint32);
+            render_pass.draw_indexed(0..batch.index_count, 0, 0..1);
+        }
+
+        render_pass.set_pipeline(&resources.ui_pipeline);
+        for batch in &resources.ui_batches {
+            let Some(font) = resources.font_textures.get(&batch.texture) else {
+                continue;
+            };
+            render_pass.set_bind_group(0, &font.ui, &[]);
+            render_pass.set_vertex_buffer(0, batch.vertices.slice(..));
+            render_pass.set_index_buffer(batch.indices.slice(..), wgpu::IndexFormat::Uint32);
+            render_pass.draw_indexed(0..batch.index_count, 0, 0..1);
+        }
+    }
+}
@@ -0,0 +1,274 @@
+//! Read-only archives mounted into the VFS - zip files and `.dpak` bundles
+//!
+//! Content packs ship as a single file that `mount_archive` mounts at a
+//! virtual path (`Assets/DLC/Winter`): every file inside it is then read like
+//! any other asset, so `AssetManager::load("Assets/DLC/Winter/snow.png")`
+//! works without knowing where the bytes come from. The format is picked from
+//! the first bytes, not the extension, so a `.pak` can be either.
+//!
+//! `ZipArchive` reads the central directory once and inflates entries on
+//! demand. Only what content packs need is supported: stored and deflated
+//! entries, no encryption, no ZIP64 (archives under 4 GB).
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use flate2::Crc;
+use flate2::read::DeflateDecoder;
+
+use crate::asset_bundle::{AssetBundle, BundleReader};
+use crate::vfs::{VfsSource, vfs_mut};
+
+const LOCAL_HEADER: u32 = 0x0403_4b50;
+const CENTRAL_HEADER: u32 = 0x0201_4b50;
+const END_OF_CENTRAL_DIR: u32 = 0x0605_4b50;
+/// End record without its comment
+const END_RECORD_LEN: usize = 22;
+const METHOD_STORED: u16 = 0;
+const METHOD_DEFLATE: u16 = 8;
+const FLAG_ENCRYPTED: u16 = 1;
+/// Largest file accepted inside an archive, decompressed; the sizes in the
+/// headers are untrusted, so a corrupt pack can't ask for more memory than this
+const MAX_ENTRY_SIZE: u64 = 1 << 30;
+
+/// One file inside a zip archive
+#[derive(Debug, Clone)]
+struct ZipEntry {
+    /// Offset of the local header
+    header_offset: u64,
+    stored: u64,
+    size: u64,
+    crc: u32,
+    method: u16,
+    encrypted: bool,
+}
+
+/// Index of a zip file; the data stays on disk until `read`
+#[derive(Debug, Clone)]
+pub struct ZipArchive {
+    path: PathBuf,
+    entries: HashMap<String, ZipEntry>,
+}
+
+fn u16_at(bytes: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes([bytes[at], bytes[at + 1]])
+}
+
+fn u32_at(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]])
+}
+
+impl ZipArchive {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let error = |e: std::io::Error| format!("Falha ao ler {}: {e}", path.display());
+        let invalid = |what: &str| format!("Zip inválido ({what}): {}", path.display());
+        let mut file = File::open(path).map_err(error)?;
+        let len = file.metadata().map_err(error)?.len();
+
+        // The end record sits in the last 22 bytes plus a comment of up to 64 KB
+        let tail_len = len.min((END_RECORD_LEN + u16::MAX as usize) as u64);
+        file.seek(SeekFrom::Start(len - tail_len)).map_err(error)?;
+        let mut tail = vec![0u8; tail_len as usize];
+        file.read_exact(&mut tail).map_err(error)?;
+        let end = (0..tail.len().saturating_sub(END_RECORD_LEN - 1))
+            .rev()
+            .find(|&at| u32_at(&tail, at) == END_OF_CENTRAL_DIR)
+            .ok_or_else(|| invalid("fim do diretório central não encontrado"))?;
+        let count = u16_at(&tail, end + 10);
+        let directory_len = u32_at(&tail, end + 12);
+        let directory_offset = u32_at(&tail, end + 16);
+        if count == u16::MAX || directory_len == u32::MAX || directory_offset == u32::MAX {
+            return Err(format!("Zip64 não suportado: {}", path.display()));
+        }
+
+        file.seek(SeekFrom::Start(directory_offset as u64))
+            .map_err(error)?;
+        let mut directory = vec![0u8; directory_len as usize];
+        file.read_exact(&mut directory).map_err(error)?;
+        let mut entries = HashMap::with_capacity(count as usize);
+        let mut at = 0;
+        for _ in 0..count {
+            if at + 46 > directory.len() || u32_at(&directory, at) != CENTRAL_HEADER {
+                return Err(invalid("diretório central corrompido"));
+            }
+            let flags = u16_at(&directory, at + 8);
+            let method = u16_at(&directory, at + 10);
+            let crc = u32_at(&directory, at + 16);
+            let stored = u32_at(&directory, at + 20);
+            let size = u32_at(&directory, at + 24);
+            let name_len = u16_at(&directory, at + 28) as usize;
+            let extra_len = u16_at(&directory, at + 30) as usize;
+            let comment_len = u16_at(&directory, at + 32) as usize;
+            let header_offset = u32_at(&directory, at + 42);
+            let name = directory
+                .get(at + 46..at + 46 + name_len)
+                .ok_or_else(|| invalid("nome cortado"))?;
+            let name = String::from_utf8_lossy(name).replace('\\', "/");
+            at += 46 + name_len + extra_len + comment_len;
+            // Folders are implied by the files inside them
+            if name.ends_with('/') {
+                continue;
+            }
+            if header_offset as u64 + stored as u64 > len || size as u64 > MAX_ENTRY_SIZE {
+                return Err(invalid(&format!("tamanho inválido em {name}")));
+            }
+            entries.insert(
+                name.trim_start_matches("./").to_string(),
+                ZipEntry {
+                    header_offset: header_offset as u64,
+                    stored: stored as u64,
+                    size: size as u64,
+                    crc,
+                    method,
+                    encrypted: flags & FLAG_ENCRYPTED != 0,
+                },
+            );
+        }
+        Ok(Self {
+            path: path.to_path_buf(),
+            entries,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn contains(&self, path: &str) -> bool {
+        self.entries.contains_key(path)
+    }
+
+    /// Bytes of one file, decompressed and checked against its CRC
+    pub fn read(&self, path: &str) -> Result<Vec<u8>, String> {
+        let entry = self
+            .entries
+            .get(path)
+            .ok_or_else(|| format!("Arquivo não está no zip: {path}"))?;
+        if entry.encrypted {
+            return Err(format!("Arquivo criptografado no zip: {path}"));
+        }
+        let error = |e: std::io::Error| format!("Falha ao ler {}: {e}", self.path.display());
+        let mut file = File::open(&self.path).map_err(error)?;
+        file.seek(SeekFrom::Start(entry.header_offset))
+            .map_err(error)?;
+        let mut header = [0u8; 30];
+        file.read_exact(&mut header).map_err(error)?;
+        if u32_at(&header, 0) != LOCAL_HEADER {
+            return Err(format!("Zip corrompido: {path} em {}", self.path.display()));
+        }
+        // The local name and extra field may differ from the central ones
+        let skip = u16_at(&header, 26) as i64 + u16_at(&header, 28) as i64;
+        file.seek(SeekFrom::Current(skip)).map_err(error)?;
+        let stored = file.take(entry.stored);
+        // No preallocation from the header, and one byte past the declared size
+        // is enough to tell a lying entry apart without inflating all of it
+        let limit = entry.size + 1;
+        let mut data = Vec::new();
+        match entry.method {
+            METHOD_STORED => stored.take(limit).read_to_end(&mut data).map_err(error)?,
+            METHOD_DEFLATE => DeflateDecoder::new(stored)
+                .take(limit)
+                .read_to_end(&mut data)
+                .map_err(error)?,
+            method => {
+                return Err(format!(
+                    "Compressão {method} não suportada: {path} em {}",
+                    self.path.display()
+                ));
+            }
+        };
+        let mut crc = Crc::new();
+        crc.update(&data);
+        if data.len() as u64 != entry.size || crc.sum() != entry.crc {
+            return Err(format!("Zip corrompido: {path} em {}", self.path.display()));
+        }
+        Ok(data)
+    }
+
+    /// Every file, sorted
+    pub fn files(&self) -> Vec<&str> {
+        let mut files: Vec<&str> = self.entries.keys().map(String::as_str).collect();
+        files.sort_unstable();
+        files
+    }
+}
+
+impl VfsSource for ZipArchive {
+    fn read(&self, path: &str) -> Option<Result<Vec<u8>, String>> {
+        self.contains(path).then(|| ZipArchive::read(self, path))
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        // A folder exists when some file is inside it
+        self.contains(path)
+            || path.is_empty()
+            || self
+                .entries
+                .keys()
+                .any(|file| file.starts_with(&format!("{path}/")))
+    }
+
+    fn files(&self) -> Vec<String> {
+        ZipArchive::files(self)
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+/// Kind of archive a file holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveKind {
+    Zip,
+    /// `.dpak` asset bundle (see `asset_bundle`)
+    Bundle,
+}
+
+impl ArchiveKind {
+    /// Detect the format from the first bytes of the file
+    pub fn detect(path: &Path) -> Result<Self, String> {
+        let mut magic = [0u8; 4];
+        File::open(path)
+            .and_then(|mut file| file.read_exact(&mut magic))
+            .map_err(|e| format!("Falha ao ler {}: {e}", path.display()))?;
+        match &magic {
+            b"DPAK" => Ok(ArchiveKind::Bundle),
+            // Local header, or the end record of an empty zip
+            [b'P', b'K', 3, 4] | [b'P', b'K', 5, 6] => Ok(ArchiveKind::Zip),
+            _ => Err(format!(
+                "Formato de pacote desconhecido: {}",
+                path.display()
+            )),
+        }
+    }
+}
+
+/// Mount a zip or bundle file read-only at `point` of the process-wide VFS;
+/// returns how many files it holds
+pub fn mount_archive(point: &str, path: &Path) -> Result<usize, String> {
+    match ArchiveKind::detect(path)? {
+        ArchiveKind::Zip => {
+            let archive = ZipArchive::open(path)?;
+            let files = archive.len();
+            vfs_mut().mount(point, archive);
+            Ok(files)
+        }
+        ArchiveKind::Bundle => {
+            let mut reader = BundleReader::default();
+            reader.mount(AssetBundle::open(path)?);
+            let files = reader.len();
+            vfs_mut().mount(point, reader);
+            Ok(files)
+        }
+    }
+}
@@ -0,0 +1,406 @@
+//! Asset bundles - cooked project assets for shipped builds
+//!
+//! `pack_bundles` walks a project's Assets directory and writes every asset
+//! (sidecars and hidden editor caches excluded) into `assets_NNN.dpak` files of
+//! at most `max_bundle_bytes` each. A bundle starts with its own index:
+//!
+//! ```text
+//! "DPAK" | version u32 | entry count u32
+//! per entry: path len u16 | path (utf-8, '/' separated, relative to Assets)
+//!            guid u128 (0 = no .meta) | offset u64 | stored u64 | size u64
+//!            flags u8 (1 = deflate)
+//! data of every entry
+//! ```
+//!
+//! All numbers are little endian. Files that don't shrink (PNG, JPEG, audio)
+//! are stored as they are. `BundleReader` mounts the bundles of a folder and
+//! reads assets back by path or GUID, which is all a shipped game needs.
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use flate2::Compression;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+
+use crate::asset_meta::{AssetGuid, is_sidecar, read_guid};
+
+/// Extension of a bundle file
+pub const BUNDLE_EXTENSION: &str = "dpak";
+
+const BUNDLE_MAGIC: &[u8; 4] = b"DPAK";
+const BUNDLE_VERSION: u32 = 1;
+const FLAG_DEFLATE: u8 = 1;
+
+/// One asset inside a bundle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleEntry {
+    /// Path relative to the Assets directory, `/` separated
+    pub path: String,
+    pub guid: Option<AssetGuid>,
+    offset: u64,
+    stored: u64,
+    /// Size of the asset once read back
+    pub size: u64,
+    compressed: bool,
+}
+
+/// What `pack_bundles` wrote
+#[derive(Debug, Clone, Default)]
+pub struct BundleReport {
+    pub bundles: Vec<PathBuf>,
+    pub files: usize,
+    /// Bytes of the source files
+    pub source_bytes: u64,
+    /// Bytes of the bundle files, index included
+    pub bundle_bytes: u64,
+}
+
+/// Key under which an asset is stored: `\` becomes `/` and a leading `./` or
+/// `Assets/` is dropped, so editor paths and bundle paths match
+pub fn bundle_key(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let path = path.trim_start_matches("./");
+    path.strip_prefix("Assets/").unwrap_or(path).to_string()
+}
+
+/// Every asset file under `root`, sorted so bundles are reproducible
+fn collect_assets(root: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let hidden = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with('.'));
+            if hidden {
+                continue;
+            }
+            if path.is_dir() {
+                pending.push(path);
+            } else if !is_sidecar(&path) {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+/// Deflated bytes, or `None` when compressing doesn't pay off
+fn deflate(data: &[u8]) -> Option<Vec<u8>> {
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data).ok()?;
+    let packed = encoder.finish().ok()?;
+    // Under ~3% smaller isn't worth decompressing at load time
+    (packed.len() < data.len() - data.len() / 32).then_some(packed)
+}
+
+/// Cook every asset under `root` into bundles inside `out_dir`. Old bundles in
+/// `out_dir` are removed first so a shipped build never mixes two packs.
+pub fn pack_bundles(
+    root: &Path,
+    out_dir: &Path,
+    max_bundle_bytes: u64,
+) -> Result<BundleReport, String> {
+    if !root.is_dir() {
+        return Err(format!(
+            "Pasta de assets não encontrada: {}",
+            root.display()
+        ));
+    }
+    fs::create_dir_all(out_dir)
+        .map_err(|e| format!("Falha ao criar {}: {e}", out_dir.display()))?;
+    if let Ok(entries) = fs::read_dir(out_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) == Some(BUNDLE_EXTENSION) {
+                let _ = fs::remove_file(path);
+            }
+        }
+    }
+
+    let mut report = BundleReport::default();
+    let mut pending: Vec<(BundleEntry, Vec<u8>)> = Vec::new();
+    let mut pending_bytes = 0u64;
+    for file in collect_assets(root) {
+        let data = fs::read(&file).map_err(|e| format!("Falha ao ler {}: {e}", file.display()))?;
+        let relative = file.strip_prefix(root).unwrap_or(&file);
+        let (stored, compressed) = match deflate(&data) {
+            Some(packed) => (packed, true),
+            None => (data.clone(), false),
+        };
+        if !pending.is_empty() && pending_bytes + stored.len() as u64 > max_bundle_bytes {
+            report
+                .bundles
+                .push(write_bundle(out_dir, report.bundles.len(), &pending)?);
+            pending.clear();
+            pending_bytes = 0;
+        }
+        report.files += 1;
+        report.source_bytes += data.len() as u64;
+        pending_bytes += stored.len() as u64;
+        pending.push((
+            BundleEntry {
+                path: bundle_key(&relative.to_string_lossy()),
+                guid: read_guid(&file),
+                offset: 0,
+                stored: stored.len() as u64,
+                size: data.len() as u64,
+                compressed,
+            },
+            stored,
+        ));
+    }
+    if !pending.is_empty() {
+        report
+            .bundles
+            .push(write_bundle(out_dir, report.bundles.len(), &pending)?);
+    }
+    report.bundle_bytes = report
+        .bundles
+        .iter()
+        .filter_map(|path| fs::metadata(path).ok())
+        .map(|meta| meta.len())
+        .sum();
+    Ok(report)
+}
+
+fn index_len(entries: &[(BundleEntry, Vec<u8>)]) -> u64 {
+    let header = (BUNDLE_MAGIC.len() + 4 + 4) as u64;
+    // path len + guid + offset + stored + size + flags
+    let fixed = (2 + 16 + 8 + 8 + 8 + 1) as u64;
+    header
+        + entries
+            .iter()
+            .map(|(entry, _)| fixed + entry.path.len() as u64)
+            .sum::<u64>()
+}
+
+fn write_bundle(
+    out_dir: &Path,
+    number: usize,
+    entries: &[(BundleEntry, Vec<u8>)],
+) -> Result<PathBuf, String> {
+    let path = out_dir.join(format!("assets_{number:03}.{BUNDLE_EXTENSION}"));
+    let error = |e: std::io::Error| format!("Falha ao salvar {}: {e}", path.display());
+    let mut out = BufWriter::new(File::create(&path).map_err(error)?);
+
+    out.write_all(BUNDLE_MAGIC).map_err(error)?;
+    out.write_all(&BUNDLE_VERSION.to_le_bytes())
+        .map_err(error)?;
+    out.write_all(&(entries.len() as u32).to_le_bytes())
+        .map_err(error)?;
+    let mut offset = index_len(entries);
+    for (entry, data) in entries {
+        let name = entry.path.as_bytes();
+        let name_len = u16::try_from(name.len())
+            .map_err(|_| format!("Caminho longo demais: {}", entry.path))?;
+        out.write_all(&name_len.to_le_bytes()).map_err(error)?;
+        out.write_all(name).map_err(error)?;
+        out.write_all(&entry.guid.map_or(0, |g| g.0).to_le_bytes())
+            .map_err(error)?;
+        out.write_all(&offset.to_le_bytes()).map_err(error)?;
+        out.write_all(&(data.len() as u64).to_le_bytes())
+            .map_err(error)?;
+        out.write_all(&entry.size.to_le_bytes()).map_err(error)?;
+        let flags = if entry.compressed { FLAG_DEFLATE } else { 0 };
+        out.write_all(&[flags]).map_err(error)?;
+        offset += data.len() as u64;
+    }
+    for (_, data) in entries {
+        out.write_all(data).map_err(error)?;
+    }
+    out.flush().map_err(error)?;
+    Ok(path)
+}
+
+fn read_array<const N: usize>(reader: &mut impl Read) -> std::io::Result<[u8; N]> {
+    let mut bytes = [0u8; N];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Index of one bundle file
+#[derive(Debug, Clone)]
+pub struct AssetBundle {
+    path: PathBuf,
+    entries: Vec<BundleEntry>,
+}
+
+impl AssetBundle {
+    /// Read the index of a bundle; the data stays on disk until `read`
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let error = |e: std::io::Error| format!("Falha ao ler {}: {e}", path.display());
+        let mut file = BufReader::new(File::open(path).map_err(error)?);
+        if &read_array::<4>(&mut file).map_err(error)? != BUNDLE_MAGIC {
+            return Err(format!("Não é um bundle de assets: {}", path.display()));
+        }
+        let version = u32::from_le_bytes(read_array(&mut file).map_err(error)?);
+        if version != BUNDLE_VERSION {
+            return Err(format!(
+                "Versão de bundle não suportada ({version}): {}",
+                path.display()
+            ));
+        }
+        let count = u32::from_le_bytes(read_array(&mut file).map_err(error)?);
+        let mut entries = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let name_len = u16::from_le_bytes(read_array(&mut file).map_err(error)?);
+            let mut name = vec![0u8; name_len as usize];
+            file.read_exact(&mut name).map_err(error)?;
+            let guid = u128::from_le_bytes(read_array(&mut file).map_err(error)?);
+            let offset = u64::from_le_bytes(read_array(&mut file).map_err(error)?);
+            let stored = u64::from_le_bytes(read_array(&mut file).map_err(error)?);
+            let size = u64::from_le_bytes(read_array(&mut file).map_err(error)?);
+            let [flags] = read_array::<1>(&mut file).map_err(error)?;
+            entries.push(BundleEntry {
+                path: String::from_utf8_lossy(&name).to_string(),
+                guid: (guid != 0).then_some(AssetGuid(guid)),
+                offset,
+                stored,
+                size,
+                compressed: flags & FLAG_DEFLATE != 0,
+            });
+        }
+        Ok(Self {
+            path: path.to_path_buf(),
+            entries,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn entries(&self) -> &[BundleEntry] {
+        &self.entries
+    }
+
+    /// Bytes of one of this bundle's entries, decompressed
+    pub fn read_entry(&self, entry: &BundleEntry) -> Result<Vec<u8>, String> {
+        let error = |e: std::io::Error| format!("Falha ao ler {}: {e}", self.path.display());
+        let mut file = File::open(&self.path).map_err(error)?;
+        file.seek(SeekFrom::Start(entry.offset)).map_err(error)?;
+        let mut stored = file.take(entry.stored);
+        let mut data = Vec::with_capacity(entry.size as usize);
+        if entry.compressed {
+            DeflateDecoder::new(stored)
+                .read_to_end(&mut data)
+                .map_err(error)?;
+        } else {
+            stored.read_to_end(&mut data).map_err(error)?;
+        }
+        if data.len() as u64 != entry.size {
+            return Err(format!(
+                "Bundle corrompido: {} em {}",
+                entry.path,
+                self.path.display()
+            ));
+        }
+        Ok(data)
+    }
+}
+
+/// Runtime side of the bundles: every bundle of a folder behind one lookup
+#[derive(Debug, Default)]
+pub struct BundleReader {
+    bundles: Vec<AssetBundle>,
+    // Asset key -> (bundle, entry)
+    paths: HashMap<String, (usize, usize)>,
+    guids: HashMap<AssetGuid, (usize, usize)>,
+}
+
+impl BundleReader {
+    /// Mount every `.dpak` file in `dir`; later bundles override earlier ones
+    pub fn mount_dir(dir: &Path) -> Result<Self, String> {
+        let mut files: Vec<PathBuf> = fs::read_dir(dir)
+            .map_err(|e| format!("Falha ao ler {}: {e}", dir.display()))?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().and_then(|e| e.to_str()) == Some(BUNDLE_EXTENSION))
+            .collect();
+        files.sort();
+        let mut reader = Self::default();
+        for file in files {
+            reader.mount(AssetBundle::open(&file)?);
+        }
+        Ok(reader)
+    }
+
+    pub fn mount(&mut self, bundle: AssetBundle) {
+        let index = self.bundles.len();
+        for (entry_index, entry) in bundle.entries.iter().enumerate() {
+            self.paths.insert(entry.path.clone(), (index, entry_index));
+            if let Some(guid) = entry.guid {
+                self.guids.insert(guid, (index, entry_index));
+            }
+        }
+        self.bundles.push(bundle);
+    }
+
+    pub fn bundles(&self) -> &[AssetBundle] {
+        &self.bundles
+    }
+
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    /// Whether an asset is in a mounted bundle (`Assets/` prefix optional)
+    pub fn contains(&self, path: &str) -> bool {
+        self.paths.contains_key(&bundle_key(path))
+    }
+
+    fn entry(&self, (bundle, entry): (usize, usize)) -> (&AssetBundle, &BundleEntry) {
+        let bundle = &self.bundles[bundle];
+        (bundle, &bundle.entries[entry])
+    }
+
+    /// Bytes of an asset by its path in the project
+    pub fn read(&self, path: &str) -> Result<Vec<u8>, String> {
+        let key = bundle_key(path);
+        let location = self
+            .paths
+            .get(&key)
+            .copied()
+            .ok_or_else(|| format!("Asset não está nos bundles: {key}"))?;
+        let (bundle, entry) = self.entry(location);
+        bundle.read_entry(entry)
+    }
+
+    /// Bytes of an asset by the GUID from its `.meta` file
+    pub fn read_guid(&self, guid: AssetGuid) -> Result<Vec<u8>, String> {
+        let location = self
+            .guids
+            .get(&guid)
+            .copied()
+            .ok_or_else(|| format!("GUID não está nos bundles: {guid}"))?;
+        let (bundle, entry) = self.entry(location);
+        bundle.read_entry(entry)
+    }
+
+    /// Project path of a GUID, to resolve references written by the editor
+    pub fn path_of(&self, guid: AssetGuid) -> Option<&str> {
+        let location = self.guids.get(&guid).copied()?;
+        Some(self.entry(location).1.path.as_str())
+    }
+
+    /// Every asset path, sorted
+    pub fn files(&self) -> Vec<&str> {
+        let mut files: Vec<&str> = self.paths.keys().map(String::as_str).collect();
+        files.sort_unstable();
+        files
+    }
+}
@@ -0,0 +1,569 @@
+//! Asset importers - how each file format becomes an engine asset
+//!
+//! An `AssetImporter` names the extensions it reads, the `AssetKind` it makes
+//! and its own settings struct. `AssetManager` keeps an `ImporterRegistry`
+//! and asks it which importer reads a path, so a new format is one
+//! `register_importer` call instead of another arm in a match. Importers run on
+//! the loader threads and must not touch the manager.
+//!
+//! The built-in importers cover textures (PNG/JPG/WebP, HDR/EXR on request), meshes (OBJ, glTF,
+//! FBX), audio (WAV/OGG/MP3), fonts and environment maps.
+//!
+//! The settings given at registration are the defaults; one asset overrides
+//! them with `import.<name>=<value>` lines in its `.meta` file
+//! (`ImportSettings::with_meta`), which the importer reads before every import.
+
+use std::path::Path;
+use std::sync::{Arc, LazyLock};
+
+use crate::asset_manager::{AssetKind, AudioClipData, TextureData};
+use crate::asset_meta::{read_meta_value, write_meta_value};
+use crate::environment::EnvironmentMap;
+use crate::font::{FONT_PX_RANGE, FontAsset, SDF_FONT_PX};
+use crate::hdr::{is_float_image, tonemap_rgba8};
+use crate::mesh::MeshData;
+use crate::vfs::vfs;
+
+/// Asset decoded by an importer
+pub enum ImportedAsset {
+    Mesh(MeshData),
+    Texture(TextureData),
+    AudioClip(AudioClipData),
+    Font(FontAsset),
+    Environment(EnvironmentMap),
+}
+
+impl ImportedAsset {
+    pub fn kind(&self) -> AssetKind {
+        match self {
+            ImportedAsset::Mesh(_) => AssetKind::Mesh,
+            ImportedAsset::Texture(_) => AssetKind::Texture,
+            ImportedAsset::AudioClip(_) => AssetKind::AudioClip,
+            ImportedAsset::Font(_) => AssetKind::Font,
+            ImportedAsset::Environment(_) => AssetKind::Environment,
+        }
+    }
+}
+
+/// Reads one family of file formats into one kind of asset
+pub trait AssetImporter: Send + Sync + 'static {
+    /// Options of this importer, given at registration
+    type Settings: ImportSettings;
+
+    /// Shown in logs and errors
+    fn name(&self) -> &'static str;
+
+    /// Lowercase extensions without the dot
+    fn extensions(&self) -> &'static [&'static str];
+
+    /// Formats read only when this kind is asked for (`AssetManager::load_as`),
+    /// since another importer claims the extension
+    fn also_reads(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn kind(&self) -> AssetKind;
+
+    /// Read and decode `path`, on a loader thread
+    fn import(&self, path: &Path, settings: &Self::Settings) -> Result<ImportedAsset, String>;
+}
+
+/// Importer options that one asset can override in its `.meta` file
+pub trait ImportSettings: Default + Clone + Send + Sync + 'static {
+    /// `self` with the values the asset's `.meta` file sets
+    fn with_meta(&self, asset_path: &Path) -> Self;
+
+    /// Write every value to the asset's `.meta` file
+    fn save_meta(&self, asset_path: &Path) -> Result<(), String>;
+}
+
+impl ImportSettings for () {
+    fn with_meta(&self, _asset_path: &Path) -> Self {}
+
+    fn save_meta(&self, _asset_path: &Path) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// `import.<name>` value of the asset's `.meta` file, parsed
+fn meta_setting<T: std::str::FromStr>(asset_path: &Path, name: &str) -> Option<T> {
+    read_meta_value(asset_path, &format!("import.{name}"))?
+        .parse()
+        .ok()
+}
+
+fn save_meta_setting(asset_path: &Path, name: &str, value: impl ToString) -> Result<(), String> {
+    write_meta_value(
+        asset_path,
+        &format!("import.{name}"),
+        Some(&value.to_string()),
+    )
+}
+
+/// An importer together with its settings, behind one object-safe type
+pub trait RegisteredImporter: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn extensions(&self) -> &'static [&'static str];
+    fn also_reads(&self) -> &'static [&'static str];
+    fn kind(&self) -> AssetKind;
+    fn import(&self, path: &Path) -> Result<ImportedAsset, String>;
+}
+
+struct WithSettings<I: AssetImporter> {
+    importer: I,
+    settings: I::Settings,
+}
+
+impl<I: AssetImporter> RegisteredImporter for WithSettings<I> {
+    fn name(&self) -> &'static str {
+        self.importer.name()
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        self.importer.extensions()
+    }
+
+    fn also_reads(&self) -> &'static [&'static str] {
+        self.importer.also_reads()
+    }
+
+    fn kind(&self) -> AssetKind {
+        self.importer.kind()
+    }
+
+    fn import(&self, path: &Path) -> Result<ImportedAsset, String> {
+        let settings = self.settings.with_meta(path);
+        let asset = self.importer.import(path, &settings)?;
+        if asset.kind() != self.importer.kind() {
+            return Err(format!(
+                "Importador {} devolveu {:?} em vez de {:?}",
+                self.importer.name(),
+                asset.kind(),
+                self.importer.kind()
+            ));
+        }
+        Ok(asset)
+    }
+}
+
+/// Importers by extension; the latest registered wins for a shared extension
+#[derive(Clone, Default)]
+pub struct ImporterRegistry {
+    importers: Vec<Arc<dyn RegisteredImporter>>,
+}
+
+impl ImporterRegistry {
+    /// Registry without importers
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    /// Registry with every built-in importer, default settings
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::empty();
+        registry.register(TextureImporter, TextureImportSettings::default());
+        registry.register(ObjImporter, MeshImportSettings::default());
+        registry.register(GltfImporter, MeshImportSettings::default());
+        registry.register(FbxImporter, MeshImportSettings::default());
+        registry.register(AudioImporter, ());
+        registry.register(FontImporter, FontImportSettings::default());
+        registry.register(EnvironmentImporter, ());
+        registry
+    }
+
+    pub fn register<I: AssetImporter>(&mut self, importer: I, settings: I::Settings) {
+        self.importers
+            .push(Arc::new(WithSettings { importer, settings }));
+    }
+
+    fn matches(extensions: &[&str], path: &Path) -> bool {
+        let Some(ext) = path.extension().and_then(|e| e.to_str()) else {
+            return false;
+        };
+        extensions
+            .iter()
+            .any(|known| known.eq_ignore_ascii_case(ext))
+    }
+
+    /// Importer that claims the path's extension
+    pub fn find(&self, path: &Path) -> Option<Arc<dyn RegisteredImporter>> {
+        self.importers
+            .iter()
+            .rev()
+            .find(|importer| Self::matches(importer.extensions(), path))
+            .cloned()
+    }
+
+    /// Importer that reads `path` into `kind`
+    pub fn find_kind(&self, path: &Path, kind: AssetKind) -> Option<Arc<dyn RegisteredImporter>> {
+        self.importers
+            .iter()
+            .rev()
+            .find(|importer| {
+                importer.kind() == kind
+                    && (Self::matches(importer.extensions(), path)
+                        || Self::matches(importer.also_reads(), path))
+            })
+            .cloned()
+    }
+
+    /// Every registered importer, in registration order
+    pub fn importers(&self) -> impl Iterator<Item = &dyn RegisteredImporter> {
+        self.importers.iter().map(|importer| importer.as_ref())
+    }
+
+    /// Every extension some importer reads
+    pub fn extensions(&self) -> Vec<&'static str> {
+        let mut extensions: Vec<&'static str> = self
+            .importers
+            .iter()
+            .flat_map(|importer| importer.extensions().iter().copied())
+            .collect();
+        extensions.sort_unstable();
+        extensions.dedup();
+        extensions
+    }
+}
+
+/// Built-in importers, for code that only needs to know what a path is
+pub(crate) static BUILTIN_IMPORTERS: LazyLock<ImporterRegistry> =
+    LazyLock::new(ImporterRegistry::with_builtins);
+
+/// Options of `TextureImporter`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextureImportSettings {
+    /// Larger images are scaled down to fit; 0 keeps the original size
+    pub max_size: u32,
+    /// Texels are sRGB colors; off for masks and other linear data
+    pub srgb: bool,
+    /// Tangent-space normal map, always read as linear data
+    pub normal_map: bool,
+}
+
+impl Default for TextureImportSettings {
+    fn default() -> Self {
+        Self {
+            max_size: 0,
+            srgb: true,
+            normal_map: false,
+        }
+    }
+}
+
+impl TextureImportSettings {
+    /// Whether the GPU samples the texture through an sRGB view
+    pub fn is_srgb(&self) -> bool {
+        self.srgb && !self.normal_map
+    }
+}
+
+impl ImportSettings for TextureImportSettings {
+    fn with_meta(&self, asset_path: &Path) -> Self {
+        Self {
+            max_size: meta_setting(asset_path, "max_size").unwrap_or(self.max_size),
+            srgb: meta_setting(asset_path, "srgb").unwrap_or(self.srgb),
+            normal_map: meta_setting(asset_path, "normal_map").unwrap_or(self.normal_map),
+        }
+    }
+
+    fn save_meta(&self, asset_path: &Path) -> Result<(), String> {
+        save_meta_setting(asset_path, "max_size", self.max_size)?;
+        save_meta_setting(asset_path, "srgb", self.srgb)?;
+        save_meta_setting(asset_path, "normal_map", self.normal_map)
+    }
+}
+
+/// Decode an image file and apply the texture import settings; what
+/// `TextureImporter` does, for code that uploads textures itself
+pub fn import_texture(
+    path: &Path,
+    settings: &TextureImportSettings,
+) -> Result<TextureData, String> {
+    engine_core::profile_function!();
+    let mut texture = TextureData::load(path)?;
+    let max = settings.max_size;
+    if max > 0 && (texture.width > max || texture.height > max) {
+        let invalid = || format!("Imagem inválida: {}", path.display());
+        let image = match texture.hdr_rgba.take() {
+            Some(pixels) => image::DynamicImage::ImageRgba32F(
+                image::Rgba32FImage::from_raw(texture.width, texture.height, pixels)
+                    .ok_or_else(invalid)?,
+            ),
+            None => image::DynamicImage::ImageRgba8(
+                image::RgbaImage::from_raw(
+                    texture.width,
+                    texture.height,
+                    std::mem::take(&mut texture.rgba),
+                )
+                .ok_or_else(invalid)?,
+            ),
+        };
+        let resized = image.resize(max, max, image::imageops::FilterType::Triangle);
+        texture.width = resized.width();
+        texture.height = resized.height();
+        if is_float_image(&resized) {
+            let pixels = resized.into_rgba32f().into_raw();
+            texture.rgba = tonemap_rgba8(&pixels);
+            texture.hdr_rgba = Some(pixels);
+        } else {
+            texture.rgba = resized.into_rgba8().into_raw();
+        }
+    }
+    Ok(texture)
+}
+
+/// PNG, JPEG and WebP images; HDR/EXR (float texels) when asked for with
+/// `AssetManager::load_as`, since `EnvironmentImporter` claims them
+pub struct TextureImporter;
+
+impl AssetImporter for TextureImporter {
+    type Settings = TextureImportSettings;
+
+    fn name(&self) -> &'static str {
+        "Texture"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["png", "jpg", "jpeg", "webp"]
+    }
+
+    fn also_reads(&self) -> &'static [&'static str] {
+        &["hdr", "exr"]
+    }
+
+    fn kind(&self) -> AssetKind {
+        AssetKind::Texture
+    }
+
+    fn import(&self, path: &Path, settings: &Self::Settings) -> Result<ImportedAsset, String> {
+        import_texture(path, settings).map(ImportedAsset::Texture)
+    }
+}
+
+/// Options shared by the mesh importers
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeshImportSettings {
+    /// Uniform scale applied to every vertex (e.g. 0.01 for centimeter files)
+    pub scale: f32,
+    /// Give objects made from the mesh a box collider fitted to it (editor)
+    pub generate_colliders: bool,
+}
+
+impl Default for MeshImportSettings {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            generate_colliders: false,
+        }
+    }
+}
+
+impl ImportSettings for MeshImportSettings {
+    fn with_meta(&self, asset_path: &Path) -> Self {
+        Self {
+            scale: meta_setting(asset_path, "scale")
+                .filter(|scale: &f32| scale.is_finite() && *scale > 0.0)
+                .unwrap_or(self.scale),
+            generate_colliders: meta_setting(asset_path, "generate_colliders")
+                .unwrap_or(self.generate_colliders),
+        }
+    }
+
+    fn save_meta(&self, asset_path: &Path) -> Result<(), String> {
+        save_meta_setting(asset_path, "scale", self.scale)?;
+        save_meta_setting(asset_path, "generate_colliders", self.generate_colliders)
+    }
+}
+
+fn import_mesh(path: &Path, settings: &MeshImportSettings) -> Result<ImportedAsset, String> {
+    // Model loaders open files (and their .mtl/.bin) by path
+    let local = vfs().local_path(path)?;
+    let mut mesh = MeshData::load_from_file(&local)?;
+    if settings.scale != 1.0 {
+        for vertex in &mut mesh.vertices {
+            vertex.position *= settings.scale;
+        }
+    }
+    Ok(ImportedAsset::Mesh(mesh))
+}
+
+/// Wavefront OBJ (with its MTL), merged into one mesh
+pub struct ObjImporter;
+
+impl AssetImporter for ObjImporter {
+    type Settings = MeshImportSettings;
+
+    fn name(&self) -> &'static str {
+        "OBJ"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["obj"]
+    }
+
+    fn kind(&self) -> AssetKind {
+        AssetKind::Mesh
+    }
+
+    fn import(&self, path: &Path, settings: &Self::Settings) -> Result<ImportedAsset, String> {
+        import_mesh(path, settings)
+    }
+}
+
+/// glTF 2.0, text or binary, nodes flattened into one mesh
+pub struct GltfImporter;
+
+impl AssetImporter for GltfImporter {
+    type Settings = MeshImportSettings;
+
+    fn name(&self) -> &'static str {
+        "glTF"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["gltf", "glb"]
+    }
+
+    fn kind(&self) -> AssetKind {
+        AssetKind::Mesh
+    }
+
+    fn import(&self, path: &Path, settings: &Self::Settings) -> Result<ImportedAsset, String> {
+        import_mesh(path, settings)
+    }
+}
+
+/// FBX, merged into one mesh
+pub struct FbxImporter;
+
+impl AssetImporter for FbxImporter {
+    type Settings = MeshImportSettings;
+
+    fn name(&self) -> &'static str {
+        "FBX"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["fbx"]
+    }
+
+    fn kind(&self) -> AssetKind {
+        AssetKind::Mesh
+    }
+
+    fn import(&self, path: &Path, settings: &Self::Settings) -> Result<ImportedAsset, String> {
+        import_mesh(path, settings)
+    }
+}
+
+/// WAV, OGG and MP3 clips, kept encoded for the audio backend
+pub struct AudioImporter;
+
+impl AssetImporter for AudioImporter {
+    type Settings = ();
+
+    fn name(&self) -> &'static str {
+        "Audio"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["wav", "ogg", "mp3"]
+    }
+
+    fn kind(&self) -> AssetKind {
+        AssetKind::AudioClip
+    }
+
+    fn import(&self, path: &Path, _settings: &()) -> Result<ImportedAsset, String> {
+        AudioClipData::load(path).map(ImportedAsset::AudioClip)
+    }
+}
+
+/// Options of `FontImporter`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FontImportSettings {
+    /// Pixel height glyphs are rasterized at: larger keeps big text crisper,
+    /// at the cost of a taller atlas
+    pub pixel_size: f32,
+    /// Characters packed besides ASCII and Latin-1 (`€ŒœĀā...`)
+    pub extra_chars: String,
+}
+
+impl Default for FontImportSettings {
+    fn default() -> Self {
+        Self {
+            pixel_size: SDF_FONT_PX,
+            extra_chars: String::new(),
+        }
+    }
+}
+
+impl ImportSettings for FontImportSettings {
+    fn with_meta(&self, asset_path: &Path) -> Self {
+        Self {
+            pixel_size: meta_setting(asset_path, "pixel_size")
+                .filter(|size| FONT_PX_RANGE.contains(size))
+                .unwrap_or(self.pixel_size),
+            extra_chars: meta_setting(asset_path, "extra_chars")
+                .unwrap_or_else(|| self.extra_chars.clone()),
+        }
+    }
+
+    fn save_meta(&self, asset_path: &Path) -> Result<(), String> {
+        save_meta_setting(asset_path, "pixel_size", self.pixel_size)?;
+        save_meta_setting(asset_path, "extra_chars", &self.extra_chars)
+    }
+}
+
+/// TrueType and OpenType fonts
+pub struct FontImporter;
+
+impl AssetImporter for FontImporter {
+    type Settings = FontImportSettings;
+
+    fn name(&self) -> &'static str {
+        "Font"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["ttf", "otf"]
+    }
+
+    fn kind(&self) -> AssetKind {
+        AssetKind::Font
+    }
+
+    fn import(&self, path: &Path, settings: &Self::Settings) -> Result<ImportedAsset, String> {
+        FontAsset::load_with(path, settings.pixel_size, &settings.extra_chars)
+            .map(ImportedAsset::Font)
+    }
+}
+
+/// Sky panoramas and cubemaps: equirectangular HDR/EXR, or an LDR image
+/// asked for with `AssetManager::load_as`; strip and cross images are read as
+/// cubemaps (see `EnvironmentMap::load`)
+pub struct EnvironmentImporter;
+
+impl AssetImporter for EnvironmentImporter {
+    type Settings = ();
+
+    fn name(&self) -> &'static str {
+        "Environment"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["hdr", "exr"]
+    }
+
+    fn also_reads(&self) -> &'static [&'static str] {
+        &["png", "jpg", "jpeg", "webp"]
+    }
+
+    fn kind(&self) -> AssetKind {
+        AssetKind::Environment
+    }
+
+    fn import(&self, path: &Path, _settings: &()) -> Result<ImportedAsset, String> {
+        EnvironmentMap::load(path).map(ImportedAsset::Environment)
+    }
+}
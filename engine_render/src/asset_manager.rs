@@ -1,13 +1,45 @@
 //! Asset Manager with Handle-based resource loading
 //!
 //! All assets are accessed through handles, never directly stored in components.
+//!
+//! `load` reads and decodes files on a background thread: the handle is valid
+//! right away and the asset shows up once `poll_loads` collects the result.
+//! Which code reads a file is up to the registered `AssetImporter`s (see
+//! `asset_importer`), looked up by extension.
+//!
+//! Handles stay plain `Copy` ids; lifetime is tracked here instead. `retain` /
+//! `release` count explicit owners and `unload_unused` drops every asset that
+//! has no owner and is not referenced by the world (see `world_asset_handles`).
+//! Under memory pressure `evict_to_budget` drops the least recently `touch`ed
+//! assets that came from `load` and have no owner, since they can be loaded
+//! again.
+//!
+//! Textures packed into a loaded sprite atlas (`load_sprite_atlas`) keep their
+//! own handles; `sprite_atlases` tells the sprite batcher to draw them from the
+//! atlas page.
 
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
-use engine_core::components::{MaterialHandle, MeshHandle};
+use engine_core::EngineWorld;
+use engine_core::components::{
+    AudioClipHandle, Camera, EnvironmentHandle, FontHandle, LodGroup, MaterialHandle,
+    MaterialShaderHandle, MeshHandle, MeshRenderer, PostProcessSettings, Sprite, Text3D,
+    TextureHandle, UiText,
+};
 
+use crate::asset_importer::{AssetImporter, BUILTIN_IMPORTERS, ImportedAsset, ImporterRegistry};
+use crate::asset_watcher::same_file;
+use crate::audio_waveform::{AudioInfo, analyze_audio};
+use crate::environment::EnvironmentMap;
+use crate::font::FontAsset;
+use crate::hdr::{is_float_image, tonemap_rgba8};
+use crate::material_shader::MaterialShader;
+use crate::memory_budget::{pick_lru, use_stamp};
 use crate::mesh::MeshData;
+use crate::sprite_atlas::{ATLAS_EXTENSION, SpriteAtlas, SpriteAtlases};
+use crate::vfs::{open_image, vfs, vfs_read};
 
 /// Asset Manager - handles loading and storing of engine assets
 ///
@@ -16,8 +48,95 @@ use crate::mesh::MeshData;
 pub struct AssetManager {
     pub meshes: HashMap<u64, MeshData>,
     pub materials: HashMap<u64, MaterialData>,
+    pub audio_clips: HashMap<u64, AudioClipData>,
+    pub textures: HashMap<u64, TextureData>,
+    pub environments: HashMap<u64, EnvironmentMap>,
+    pub fonts: HashMap<u64, FontAsset>,
+    pub material_shaders: HashMap<u64, MaterialShader>,
     pub next_mesh_id: u64,
     pub next_material_id: u64,
+    pub next_audio_clip_id: u64,
+    pub next_texture_id: u64,
+    pub next_environment_id: u64,
+    pub next_font_id: u64,
+    pub next_material_shader_id: u64,
+    /// State of every asset requested through `load`
+    pub(crate) load_states: HashMap<AssetHandle, LoadState>,
+    /// Handle given to each (path, kind) passed to `load`
+    load_paths: HashMap<(PathBuf, AssetKind), AssetHandle>,
+    /// Results written by the loader threads, drained by `poll_loads`
+    finished_loads: Arc<Mutex<Vec<(AssetHandle, Result<ImportedAsset, String>)>>>,
+    /// Which importer reads each file format
+    importers: ImporterRegistry,
+    /// Explicit owners of each asset (`retain` / `release`)
+    ref_counts: HashMap<AssetHandle, usize>,
+    /// Last `use_stamp` of each asset (`touch`), for `evict_to_budget`
+    last_used: HashMap<AssetHandle, u64>,
+    /// Packed sprites of the loaded atlases
+    sprite_atlases: SpriteAtlases,
+}
+
+/// Kind of asset `load` can read in the background
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AssetKind {
+    Mesh,
+    Texture,
+    AudioClip,
+    Font,
+    Environment,
+}
+
+impl AssetKind {
+    /// Kind the built-in importer of this extension makes. Environment maps
+    /// share their formats with textures and have to be requested with `load_as`
+    pub fn from_path(path: &Path) -> Option<Self> {
+        BUILTIN_IMPORTERS.find(path).map(|importer| importer.kind())
+    }
+}
+
+/// Handle of any asset kind, as returned by `load`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AssetHandle {
+    Mesh(MeshHandle),
+    Texture(TextureHandle),
+    AudioClip(AudioClipHandle),
+    Font(FontHandle),
+    Environment(EnvironmentHandle),
+    Material(MaterialHandle),
+    MaterialShader(MaterialShaderHandle),
+}
+
+impl AssetHandle {
+    /// Kind name shown in the memory report
+    pub fn kind_name(self) -> &'static str {
+        match self {
+            AssetHandle::Mesh(_) => "Mesh",
+            AssetHandle::Texture(_) => "Texture",
+            AssetHandle::AudioClip(_) => "AudioClip",
+            AssetHandle::Font(_) => "Font",
+            AssetHandle::Environment(_) => "Environment",
+            AssetHandle::Material(_) => "Material",
+            AssetHandle::MaterialShader(_) => "MaterialShader",
+        }
+    }
+}
+
+/// One stored asset in `AssetManager::memory_report`
+#[derive(Debug, Clone)]
+pub struct AssetMemory {
+    pub handle: AssetHandle,
+    pub name: String,
+    /// Approximate CPU memory of the asset data
+    pub bytes: usize,
+    pub ref_count: usize,
+}
+
+/// Progress of an asset requested through `load`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadState {
+    Loading,
+    Loaded,
+    Failed(String),
 }
 
 /// Material data
@@ -28,6 +147,12 @@ pub struct MaterialData {
     pub metallic: f32,
     pub roughness: f32,
     pub albedo_texture: Option<String>,
+    /// Decoded albedo texture (embedded textures have no path)
+    pub albedo_texture_handle: Option<TextureHandle>,
+    /// Custom material shader; None = built-in lit shader
+    pub shader: Option<MaterialShaderHandle>,
+    /// Values of the shader's reflected parameters, by name
+    pub shader_params: HashMap<String, [f32; 4]>,
 }
 
 impl Default for MaterialData {
@@ -38,6 +163,9 @@ impl Default for MaterialData {
             metallic: 0.0,
             roughness: 0.5,
             albedo_texture: None,
+            albedo_texture_handle: None,
+            shader: None,
+            shader_params: HashMap::new(),
         }
     }
 }
@@ -54,6 +182,121 @@ impl MaterialData {
         self.albedo = [r, g, b, 1.0];
         self
     }
+
+    /// Blinn-Phong -> rugosidade: sqrt(2 / (brilho + 2)), usado por OBJ/MTL e FBX
+    pub fn roughness_from_shininess(shininess: f32) -> f32 {
+        (2.0 / (shininess.max(0.0) + 2.0)).sqrt()
+    }
+}
+
+/// Texture data - decoded RGBA8 pixels, plus the float texels of HDR/EXR files
+#[derive(Debug, Clone)]
+pub struct TextureData {
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    /// sRGB pixels; for float sources a tonemapped copy used by previews
+    pub rgba: Vec<u8>,
+    /// Linear RGBA32F texels of float sources (.hdr, .exr), uploaded as `Rgba16Float`
+    pub hdr_rgba: Option<Vec<f32>>,
+    /// Source file, None for textures embedded in another asset
+    pub source_path: Option<PathBuf>,
+}
+
+impl TextureData {
+    /// Decode an image file into RGBA8, keeping float images as RGBA32F too
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let image = open_image(path)?;
+        let (width, height) = (image.width(), image.height());
+        let (rgba, hdr_rgba) = if is_float_image(&image) {
+            let pixels = image.into_rgba32f().into_raw();
+            (tonemap_rgba8(&pixels), Some(pixels))
+        } else {
+            (image.into_rgba8().into_raw(), None)
+        };
+        Ok(Self {
+            name: path
+                .file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string(),
+            width,
+            height,
+            rgba,
+            hdr_rgba,
+            source_path: Some(path.to_path_buf()),
+        })
+    }
+
+    /// Whether the texture has float texels for the GPU
+    pub fn is_hdr(&self) -> bool {
+        self.hdr_rgba.is_some()
+    }
+
+    /// Bytes kept in memory
+    pub fn byte_size(&self) -> usize {
+        self.rgba.len()
+            + self
+                .hdr_rgba
+                .as_ref()
+                .map_or(0, |p| std::mem::size_of_val(&p[..]))
+    }
+}
+
+/// Encoded audio container supported by the audio backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioFormat {
+    Wav,
+    Ogg,
+    Mp3,
+}
+
+impl AudioFormat {
+    /// Detect the container from the file header
+    pub fn detect(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WAVE" {
+            Some(AudioFormat::Wav)
+        } else if bytes.starts_with(b"OggS") {
+            Some(AudioFormat::Ogg)
+        } else if bytes.starts_with(b"ID3")
+            || (bytes.len() >= 2 && bytes[0] == 0xFF && bytes[1] & 0xE0 == 0xE0)
+        {
+            // ID3 tag or a bare MPEG frame sync
+            Some(AudioFormat::Mp3)
+        } else {
+            None
+        }
+    }
+}
+
+/// Audio clip data - the encoded file, decoded by the audio backend on playback
+#[derive(Debug, Clone)]
+pub struct AudioClipData {
+    pub name: String,
+    pub path: PathBuf,
+    pub format: AudioFormat,
+    pub bytes: Arc<[u8]>,
+    /// Duration and waveform, decoded at import
+    pub info: AudioInfo,
+}
+
+impl AudioClipData {
+    /// Read a wav/ogg/mp3 file, keeping it encoded; decoded once for `info`
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let name = path.to_string_lossy().to_string();
+        let bytes = vfs_read(path).map_err(|e| format!("Falha ao ler áudio: {e}"))?;
+        let format = AudioFormat::detect(&bytes)
+            .ok_or_else(|| format!("Formato de áudio não suportado: {name}"))?;
+        let bytes: Arc<[u8]> = bytes.into();
+        let info = analyze_audio(bytes.clone(), format).map_err(|e| format!("{name}: {e}"))?;
+        Ok(Self {
+            name,
+            path: path.to_path_buf(),
+            format,
+            bytes,
+            info,
+        })
+    }
 }
 
 impl Default for AssetManager {
@@ -67,11 +310,446 @@ impl AssetManager {
         Self {
             meshes: HashMap::new(),
             materials: HashMap::new(),
+            audio_clips: HashMap::new(),
+            textures: HashMap::new(),
+            environments: HashMap::new(),
+            fonts: HashMap::new(),
+            material_shaders: HashMap::new(),
             next_mesh_id: 1,
             next_material_id: 1,
+            next_audio_clip_id: 1,
+            next_texture_id: 1,
+            next_environment_id: 1,
+            next_font_id: 1,
+            next_material_shader_id: 1,
+            load_states: HashMap::new(),
+            load_paths: HashMap::new(),
+            finished_loads: Arc::new(Mutex::new(Vec::new())),
+            ref_counts: HashMap::new(),
+            importers: ImporterRegistry::with_builtins(),
+            last_used: HashMap::new(),
+            sprite_atlases: SpriteAtlases::default(),
+        }
+    }
+
+    /// Add an importer; it takes over the extensions it shares with earlier ones
+    pub fn register_importer<I: AssetImporter>(&mut self, importer: I, settings: I::Settings) {
+        self.importers.register(importer, settings);
+    }
+
+    pub fn importers(&self) -> &ImporterRegistry {
+        &self.importers
+    }
+
+    /// Start loading a file in the background, the kind given by its extension.
+    /// Returns at once; the asset is available after `poll_loads` reports it
+    pub fn load(&mut self, path: &Path) -> Result<(AssetHandle, LoadState), String> {
+        let importer = self
+            .importers
+            .find(path)
+            .ok_or_else(|| format!("Tipo de asset não suportado: {}", path.display()))?;
+        Ok(self.load_as(path, importer.kind()))
+    }
+
+    /// Start loading a file as the given kind on a background thread. A path
+    /// still loading or already loaded returns the same handle
+    pub fn load_as(&mut self, path: &Path, kind: AssetKind) -> (AssetHandle, LoadState) {
+        let key = (path.to_path_buf(), kind);
+        if let Some(&handle) = self.load_paths.get(&key) {
+            let state = self.load_state(handle);
+            if state == LoadState::Loading || self.contains(handle) {
+                self.touch(handle);
+                return (handle, state);
+            }
+        }
+
+        let handle = match kind {
+            AssetKind::Mesh => {
+                let id = self.next_mesh_id;
+                self.next_mesh_id += 1;
+                AssetHandle::Mesh(MeshHandle { id })
+            }
+            AssetKind::Texture => {
+                let id = self.next_texture_id;
+                self.next_texture_id += 1;
+                AssetHandle::Texture(TextureHandle { id })
+            }
+            AssetKind::AudioClip => {
+                let id = self.next_audio_clip_id;
+                self.next_audio_clip_id += 1;
+                AssetHandle::AudioClip(AudioClipHandle { id })
+            }
+            AssetKind::Font => {
+                let id = self.next_font_id;
+                self.next_font_id += 1;
+                AssetHandle::Font(FontHandle { id })
+            }
+            AssetKind::Environment => {
+                let id = self.next_environment_id;
+                self.next_environment_id += 1;
+                AssetHandle::Environment(EnvironmentHandle { id })
+            }
+        };
+        if let AssetHandle::Texture(texture) = handle {
+            self.sprite_atlases.bind_texture(path, texture);
+        }
+        self.load_paths.insert(key, handle);
+        self.load_states.insert(handle, LoadState::Loading);
+        self.spawn_load(path, kind, handle);
+        (handle, LoadState::Loading)
+    }
+
+    /// Read a changed file again into the handles already loaded from it, on
+    /// a background thread. The old asset stays in place until `poll_loads`
+    /// swaps in the new one. Returns the handles being reloaded
+    pub fn reload(&mut self, path: &Path) -> Vec<AssetHandle> {
+        let targets: Vec<(AssetHandle, AssetKind)> = self
+            .load_paths
+            .iter()
+            .filter(|((loaded, _), handle)| {
+                same_file(loaded, path)
+                    && self.contains(**handle)
+                    && self.load_states.get(*handle) != Some(&LoadState::Loading)
+            })
+            .map(|((_, kind), handle)| (*handle, *kind))
+            .collect();
+        for &(handle, kind) in &targets {
+            self.load_states.insert(handle, LoadState::Loading);
+            self.spawn_load(path, kind, handle);
+        }
+        targets.into_iter().map(|(handle, _)| handle).collect()
+    }
+
+    fn spawn_load(&self, path: &Path, kind: AssetKind, handle: AssetHandle) {
+        let path = path.to_path_buf();
+        let finished = self.finished_loads.clone();
+        let importer = self.importers.find_kind(&path, kind);
+        std::thread::spawn(move || {
+            let result = match importer {
+                Some(importer) => importer.import(&path),
+                None => Err(format!(
+                    "Nenhum importador de {kind:?} para {}",
+                    path.display()
+                )),
+            };
+            finished
+                .lock()
+                .expect("asset load lock")
+                .push((handle, result));
+        });
+    }
+
+    /// Store the assets finished by the loader threads. Returns the handles
+    /// whose state changed this call (now `Loaded` or `Failed`). A failed
+    /// `reload` keeps the previous asset: it is reported as `Failed` while the
+    /// handle stays `Loaded`
+    pub fn poll_loads(&mut self) -> Vec<(AssetHandle, LoadState)> {
+        let finished = std::mem::take(&mut *self.finished_loads.lock().expect("asset load lock"));
+        let mut changed = Vec::with_capacity(finished.len());
+        for (handle, result) in finished {
+            // Unloaded while still loading
+            if self.load_states.get(&handle) != Some(&LoadState::Loading) {
+                continue;
+            }
+            let state = match result {
+                Ok(asset) => {
+                    self.insert_loaded(handle, asset);
+                    LoadState::Loaded
+                }
+                Err(e) if self.contains(handle) => {
+                    self.load_states.insert(handle, LoadState::Loaded);
+                    changed.push((handle, LoadState::Failed(e)));
+                    continue;
+                }
+                Err(e) => LoadState::Failed(e),
+            };
+            self.load_states.insert(handle, state.clone());
+            changed.push((handle, state));
+        }
+        changed
+    }
+
+    fn insert_loaded(&mut self, handle: AssetHandle, asset: ImportedAsset) {
+        self.touch(handle);
+        match (handle, asset) {
+            (AssetHandle::Mesh(h), ImportedAsset::Mesh(mesh)) => {
+                self.meshes.insert(h.id, mesh);
+            }
+            (AssetHandle::Texture(h), ImportedAsset::Texture(texture)) => {
+                self.textures.insert(h.id, texture);
+            }
+            (AssetHandle::AudioClip(h), ImportedAsset::AudioClip(clip)) => {
+                self.audio_clips.insert(h.id, clip);
+            }
+            (AssetHandle::Font(h), ImportedAsset::Font(font)) => {
+                self.fonts.insert(h.id, font);
+            }
+            (AssetHandle::Environment(h), ImportedAsset::Environment(map)) => {
+                self.environments.insert(h.id, map);
+            }
+            _ => {}
+        }
+    }
+
+    /// State of a handle: assets added synchronously are `Loaded` while stored
+    pub fn load_state(&self, handle: AssetHandle) -> LoadState {
+        match self.load_states.get(&handle) {
+            Some(LoadState::Loaded) | None if !self.contains(handle) => {
+                LoadState::Failed("Asset não carregado".to_string())
+            }
+            Some(state) => state.clone(),
+            None => LoadState::Loaded,
+        }
+    }
+
+    /// Number of `load` requests still running
+    pub fn pending_loads(&self) -> usize {
+        self.load_states
+            .values()
+            .filter(|state| **state == LoadState::Loading)
+            .count()
+    }
+
+    /// Whether the asset behind the handle is stored
+    pub fn contains(&self, handle: AssetHandle) -> bool {
+        match handle {
+            AssetHandle::Mesh(h) => self.meshes.contains_key(&h.id),
+            AssetHandle::Texture(h) => self.textures.contains_key(&h.id),
+            AssetHandle::AudioClip(h) => self.audio_clips.contains_key(&h.id),
+            AssetHandle::Font(h) => self.fonts.contains_key(&h.id),
+            AssetHandle::Environment(h) => self.environments.contains_key(&h.id),
+            AssetHandle::Material(h) => self.materials.contains_key(&h.id),
+            AssetHandle::MaterialShader(h) => self.material_shaders.contains_key(&h.id),
         }
     }
 
+    /// Add an owner; the asset is kept by `unload_unused` while owned.
+    /// Returns the new count
+    pub fn retain(&mut self, handle: AssetHandle) -> usize {
+        let count = self.ref_counts.entry(handle).or_insert(0);
+        *count += 1;
+        *count
+    }
+
+    /// Drop an owner; the asset is unloaded when the last one goes.
+    /// Returns whether it was unloaded
+    pub fn release(&mut self, handle: AssetHandle) -> bool {
+        let Some(count) = self.ref_counts.get_mut(&handle) else {
+            return false;
+        };
+        *count = count.saturating_sub(1);
+        if *count > 0 {
+            return false;
+        }
+        self.ref_counts.remove(&handle);
+        self.unload(handle)
+    }
+
+    /// Number of owners registered with `retain`
+    pub fn ref_count(&self, handle: AssetHandle) -> usize {
+        self.ref_counts.get(&handle).copied().unwrap_or(0)
+    }
+
+    /// Unload any asset now, whatever its owners
+    pub fn unload(&mut self, handle: AssetHandle) -> bool {
+        self.ref_counts.remove(&handle);
+        self.last_used.remove(&handle);
+        match handle {
+            AssetHandle::Mesh(h) => self.unload_mesh(h),
+            AssetHandle::Texture(h) => self.unload_texture(h),
+            AssetHandle::AudioClip(h) => self.unload_audio_clip(h),
+            AssetHandle::Font(h) => self.unload_font(h),
+            AssetHandle::Environment(h) => self.unload_environment(h),
+            AssetHandle::Material(h) => self.unload_material(h),
+            AssetHandle::MaterialShader(h) => self.unload_material_shader(h),
+        }
+    }
+
+    /// Unload every stored asset without owners that is not in `used`. Textures
+    /// and shaders of used or owned materials count as used. Returns the
+    /// unloaded handles
+    pub fn unload_unused(
+        &mut self,
+        used: impl IntoIterator<Item = AssetHandle>,
+    ) -> Vec<AssetHandle> {
+        let mut used: HashSet<AssetHandle> = used.into_iter().collect();
+        used.extend(self.ref_counts.keys().copied());
+        let dependencies: Vec<AssetHandle> = used
+            .iter()
+            .flat_map(|handle| self.dependencies(*handle))
+            .collect();
+        used.extend(dependencies);
+        // Still loading: the result would land after the unload
+        let unused: Vec<AssetHandle> = self
+            .stored_handles()
+            .into_iter()
+            .filter(|handle| !used.contains(handle))
+            .filter(|handle| self.load_states.get(handle) != Some(&LoadState::Loading))
+            .collect();
+        for &handle in &unused {
+            self.unload(handle);
+        }
+        unused
+    }
+
+    /// Assets `handle` references directly: a material's texture and shader
+    pub fn dependencies(&self, handle: AssetHandle) -> Vec<AssetHandle> {
+        let AssetHandle::Material(material) = handle else {
+            return Vec::new();
+        };
+        let Some(data) = self.materials.get(&material.id) else {
+            return Vec::new();
+        };
+        let texture = data.albedo_texture_handle.map(AssetHandle::Texture);
+        let shader = data.shader.map(AssetHandle::MaterialShader);
+        texture.into_iter().chain(shader).collect()
+    }
+
+    /// Stored assets that reference `handle` directly
+    pub fn references(&self, handle: AssetHandle) -> Vec<AssetHandle> {
+        self.stored_handles()
+            .into_iter()
+            .filter(|owner| self.dependencies(*owner).contains(&handle))
+            .collect()
+    }
+
+    /// Handles of every stored asset
+    pub fn stored_handles(&self) -> Vec<AssetHandle> {
+        let mut handles = Vec::new();
+        handles.extend(
+            self.meshes
+                .keys()
+                .map(|&id| AssetHandle::Mesh(MeshHandle { id })),
+        );
+        handles.extend(
+            self.textures
+                .keys()
+                .map(|&id| AssetHandle::Texture(TextureHandle { id })),
+        );
+        handles.extend(
+            self.audio_clips
+                .keys()
+                .map(|&id| AssetHandle::AudioClip(AudioClipHandle { id })),
+        );
+        handles.extend(
+            self.fonts
+                .keys()
+                .map(|&id| AssetHandle::Font(FontHandle { id })),
+        );
+        handles.extend(
+            self.environments
+                .keys()
+                .map(|&id| AssetHandle::Environment(EnvironmentHandle { id })),
+        );
+        handles.extend(
+            self.materials
+                .keys()
+                .map(|&id| AssetHandle::Material(MaterialHandle { id })),
+        );
+        handles.extend(
+            self.material_shaders
+                .keys()
+                .map(|&id| AssetHandle::MaterialShader(MaterialShaderHandle { id })),
+        );
+        handles
+    }
+
+    /// Stored assets with their approximate size, largest first
+    pub fn memory_report(&self) -> Vec<AssetMemory> {
+        let mut report: Vec<AssetMemory> = self
+            .stored_handles()
+            .into_iter()
+            .filter_map(|handle| {
+                let (name, bytes) = self.asset_size(handle)?;
+                Some(AssetMemory {
+                    handle,
+                    name,
+                    bytes,
+                    ref_count: self.ref_count(handle),
+                })
+            })
+            .collect();
+        report.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.name.cmp(&b.name)));
+        report
+    }
+
+    /// Name and approximate CPU bytes of a stored asset
+    fn asset_size(&self, handle: AssetHandle) -> Option<(String, usize)> {
+        Some(match handle {
+            AssetHandle::Mesh(h) => {
+                let mesh = self.meshes.get(&h.id)?;
+                (mesh.name.clone(), mesh_bytes(mesh))
+            }
+            AssetHandle::Texture(h) => {
+                let texture = self.textures.get(&h.id)?;
+                (texture.name.clone(), texture.byte_size())
+            }
+            AssetHandle::AudioClip(h) => {
+                let clip = self.audio_clips.get(&h.id)?;
+                (clip.name.clone(), clip.bytes.len())
+            }
+            AssetHandle::Font(h) => {
+                let font = self.fonts.get(&h.id)?;
+                (font.name.clone(), font.atlas.len())
+            }
+            AssetHandle::Environment(h) => {
+                let map = self.environments.get(&h.id)?;
+                (map.name.clone(), std::mem::size_of_val(&map.pixels[..]))
+            }
+            AssetHandle::Material(h) => {
+                let material = self.materials.get(&h.id)?;
+                (material.name.clone(), std::mem::size_of::<MaterialData>())
+            }
+            AssetHandle::MaterialShader(h) => {
+                let shader = self.material_shaders.get(&h.id)?;
+                (shader.name.clone(), shader.source.len())
+            }
+        })
+    }
+
+    /// Mark an asset as used now; `evict_to_budget` drops the least recently
+    /// used ones first. `load` and finished loads count as uses
+    pub fn touch(&mut self, handle: AssetHandle) {
+        self.last_used.insert(handle, use_stamp());
+    }
+
+    /// Assets that can be dropped and loaded again: stored by `load`, without
+    /// owners and not referenced by an owned asset. `(handle, last use, bytes)`
+    pub fn eviction_candidates(&self) -> Vec<(AssetHandle, u64, usize)> {
+        let owned_dependencies: HashSet<AssetHandle> = self
+            .ref_counts
+            .keys()
+            .flat_map(|handle| self.dependencies(*handle))
+            .collect();
+        let streamed: HashSet<AssetHandle> = self.load_paths.values().copied().collect();
+        streamed
+            .into_iter()
+            .filter(|handle| self.contains(*handle))
+            .filter(|handle| self.ref_count(*handle) == 0 && !owned_dependencies.contains(handle))
+            .filter(|handle| self.load_states.get(handle) != Some(&LoadState::Loading))
+            .filter_map(|handle| {
+                let (_, bytes) = self.asset_size(handle)?;
+                let last_used = self.last_used.get(&handle).copied().unwrap_or(0);
+                Some((handle, last_used, bytes))
+            })
+            .collect()
+    }
+
+    /// Unload least recently used `eviction_candidates` until the stored assets
+    /// take at most `budget` bytes (or no candidate is left). Returns the
+    /// unloaded handles
+    pub fn evict_to_budget(&mut self, budget: usize) -> Vec<AssetHandle> {
+        let total: usize = self.memory_report().iter().map(|asset| asset.bytes).sum();
+        if total <= budget {
+            return Vec::new();
+        }
+        let victims = pick_lru(self.eviction_candidates(), total - budget);
+        for &handle in &victims {
+            self.unload(handle);
+        }
+        victims
+    }
+
     /// Load a mesh from file path
     pub fn load_mesh(&mut self, path: &Path) -> Result<MeshHandle, String> {
         // Check if already loaded
@@ -93,6 +771,7 @@ impl AssetManager {
                     metallic: 0.5,
                     roughness: 0.5,
                     albedo_texture: Some(texture_path.to_string_lossy().to_string()),
+                    ..Default::default()
                 };
                 let id = self.next_material_id;
                 self.next_material_id += 1;
@@ -148,6 +827,7 @@ impl AssetManager {
 
     /// Unload mesh by handle
     pub fn unload_mesh(&mut self, handle: MeshHandle) -> bool {
+        self.load_states.remove(&AssetHandle::Mesh(handle));
         self.meshes.remove(&handle.id).is_some()
     }
 
@@ -180,6 +860,155 @@ impl AssetManager {
         self.materials.remove(&handle.id).is_some()
     }
 
+    /// Load an audio clip (wav/ogg) from file path
+    pub fn load_audio_clip(&mut self, path: &Path) -> Result<AudioClipHandle, String> {
+        let name = path.to_string_lossy().to_string();
+        if let Some((id, _)) = self.audio_clips.iter().find(|(_, clip)| clip.name == name) {
+            return Ok(AudioClipHandle { id: *id });
+        }
+
+        let clip = AudioClipData::load(path)?;
+        let id = self.next_audio_clip_id;
+        self.next_audio_clip_id += 1;
+        self.audio_clips.insert(id, clip);
+        Ok(AudioClipHandle { id })
+    }
+
+    /// Get audio clip data by handle
+    pub fn get_audio_clip(&self, handle: AudioClipHandle) -> Option<&AudioClipData> {
+        self.audio_clips.get(&handle.id)
+    }
+
+    /// Unload audio clip by handle
+    pub fn unload_audio_clip(&mut self, handle: AudioClipHandle) -> bool {
+        self.load_states.remove(&AssetHandle::AudioClip(handle));
+        self.audio_clips.remove(&handle.id).is_some()
+    }
+
+    /// Load a font (TTF/OTF) and build its SDF atlas
+    pub fn load_font(&mut self, path: &Path) -> Result<FontHandle, String> {
+        let name = path.to_string_lossy().to_string();
+        if let Some((id, _)) = self.fonts.iter().find(|(_, font)| font.name == name) {
+            return Ok(FontHandle { id: *id });
+        }
+        let font = FontAsset::load(path)?;
+        Ok(self.add_font(font))
+    }
+
+    /// Store an already built font
+    pub fn add_font(&mut self, font: FontAsset) -> FontHandle {
+        let id = self.next_font_id;
+        self.next_font_id += 1;
+        self.fonts.insert(id, font);
+        FontHandle { id }
+    }
+
+    /// Get font data by handle
+    pub fn get_font(&self, handle: FontHandle) -> Option<&FontAsset> {
+        self.fonts.get(&handle.id)
+    }
+
+    /// Unload font by handle
+    pub fn unload_font(&mut self, handle: FontHandle) -> bool {
+        self.load_states.remove(&AssetHandle::Font(handle));
+        self.fonts.remove(&handle.id).is_some()
+    }
+
+    /// Store a decoded texture
+    pub fn add_texture(&mut self, texture: TextureData) -> TextureHandle {
+        let id = self.next_texture_id;
+        self.next_texture_id += 1;
+        self.textures.insert(id, texture);
+        TextureHandle { id }
+    }
+
+    /// Get texture data by handle
+    pub fn get_texture(&self, handle: TextureHandle) -> Option<&TextureData> {
+        self.textures.get(&handle.id)
+    }
+
+    /// Decode a texture file now instead of on a background thread (the player,
+    /// while loading the scene). A path already loaded returns the same handle
+    pub fn load_texture(&mut self, path: &Path) -> Result<TextureHandle, String> {
+        let key = (path.to_path_buf(), AssetKind::Texture);
+        let loaded = self
+            .load_paths
+            .get(&key)
+            .copied()
+            .filter(|&handle| self.contains(handle));
+        if let Some(AssetHandle::Texture(texture)) = loaded {
+            return Ok(texture);
+        }
+        let texture = self.add_texture(TextureData::load(path)?);
+        self.load_paths.insert(key, AssetHandle::Texture(texture));
+        self.load_states
+            .insert(AssetHandle::Texture(texture), LoadState::Loaded);
+        self.sprite_atlases.bind_texture(path, texture);
+        Ok(texture)
+    }
+
+    /// Load an `.atlas` file and its page: from now on sprites using any of
+    /// its textures are drawn from the page. Returns the page's handle
+    pub fn load_sprite_atlas(&mut self, path: &Path) -> Result<TextureHandle, String> {
+        let atlas = SpriteAtlas::load(path)?;
+        let page = self.add_texture(TextureData::load(&atlas.image_path(path))?);
+        self.sprite_atlases.add(&atlas, page);
+        for ((loaded, kind), handle) in &self.load_paths {
+            if let (AssetKind::Texture, AssetHandle::Texture(texture)) = (kind, handle) {
+                self.sprite_atlases.bind_texture(loaded, *texture);
+            }
+        }
+        Ok(page)
+    }
+
+    /// `load_sprite_atlas` for every `.atlas` file under a VFS mount point
+    /// (`Assets` at startup); returns the files that failed
+    pub fn load_sprite_atlases(&mut self, point: &str) -> Vec<(PathBuf, String)> {
+        let atlases: Vec<PathBuf> = vfs()
+            .files(point)
+            .into_iter()
+            .map(PathBuf::from)
+            .filter(|path| {
+                path.extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| e.eq_ignore_ascii_case(ATLAS_EXTENSION))
+            })
+            .collect();
+        atlases
+            .into_iter()
+            .filter_map(|path| {
+                let error = self.load_sprite_atlas(&path).err()?;
+                Some((path, error))
+            })
+            .collect()
+    }
+
+    pub fn sprite_atlases(&self) -> &SpriteAtlases {
+        &self.sprite_atlases
+    }
+
+    /// Unload texture by handle
+    pub fn unload_texture(&mut self, handle: TextureHandle) -> bool {
+        self.load_states.remove(&AssetHandle::Texture(handle));
+        self.textures.remove(&handle.id).is_some()
+    }
+
+    /// Add a mesh, returning its handle
+    pub fn add_mesh(&mut self, mesh: MeshData) -> MeshHandle {
+        let id = self.next_mesh_id;
+        self.next_mesh_id += 1;
+        self.meshes.insert(id, mesh);
+        MeshHandle { id }
+    }
+
+    /// Add a material, returning its handle
+    pub fn add_material(&mut self, material: MaterialData) -> MaterialHandle {
+        let id = self.next_material_id;
+        self.next_material_id += 1;
+        self.materials.insert(id, material);
+        MaterialHandle { id }
+    }
+
     /// Get all mesh handles (for iteration)
     pub fn mesh_handles(&self) -> Vec<MeshHandle> {
         self.meshes
@@ -198,13 +1027,114 @@ impl AssetManager {
         self.materials.len()
     }
 
+    /// Get audio clip count
+    pub fn audio_clip_count(&self) -> usize {
+        self.audio_clips.len()
+    }
+
+    /// Get texture count
+    pub fn texture_count(&self) -> usize {
+        self.textures.len()
+    }
+
+    /// Get environment map count
+    pub fn environment_count(&self) -> usize {
+        self.environments.len()
+    }
+
+    /// Get font count
+    pub fn font_count(&self) -> usize {
+        self.fonts.len()
+    }
+
+    /// Get material shader count
+    pub fn material_shader_count(&self) -> usize {
+        self.material_shaders.len()
+    }
+
     /// Clear all assets
     pub fn clear(&mut self) {
         self.meshes.clear();
         self.materials.clear();
+        self.audio_clips.clear();
+        self.textures.clear();
+        self.environments.clear();
+        self.fonts.clear();
+        self.material_shaders.clear();
         self.next_mesh_id = 1;
         self.next_material_id = 1;
+        self.next_audio_clip_id = 1;
+        self.next_texture_id = 1;
+        self.next_environment_id = 1;
+        self.next_font_id = 1;
+        self.next_material_shader_id = 1;
+        self.load_states.clear();
+        self.load_paths.clear();
+        self.ref_counts.clear();
+        self.sprite_atlases.clear();
+        // Loads still running report into the old queue and are dropped
+        self.finished_loads = Arc::new(Mutex::new(Vec::new()));
+    }
+}
+
+/// Approximate size of a mesh's vertex, index, skin and attribute data
+fn mesh_bytes(mesh: &MeshData) -> usize {
+    let mut bytes =
+        std::mem::size_of_val(&mesh.vertices[..]) + std::mem::size_of_val(&mesh.indices[..]);
+    if let Some(skin) = &mesh.skin {
+        bytes += std::mem::size_of_val(&skin.inverse_bind_matrices[..])
+            + std::mem::size_of_val(&skin.joints[..])
+            + std::mem::size_of_val(&skin.weights[..]);
+    }
+    if let Some(attributes) = &mesh.attributes {
+        bytes += std::mem::size_of_val(&attributes.colors[..])
+            + std::mem::size_of_val(&attributes.tangents[..])
+            + attributes
+                .uv_sets
+                .iter()
+                .map(|set| std::mem::size_of_val(&set[..]))
+                .sum::<usize>()
+            + attributes
+                .custom
+                .iter()
+                .map(|channel| std::mem::size_of_val(&channel.data[..]))
+                .sum::<usize>();
+    }
+    bytes
+}
+
+/// Assets referenced by the components of a world (mesh renderers, LOD groups,
+/// camera targets and LUTs, sprites and texts), for `unload_unused`
+pub fn world_asset_handles(world: &EngineWorld) -> HashSet<AssetHandle> {
+    let world = world.world();
+    let mut handles = HashSet::new();
+    for renderer in world.query::<&MeshRenderer>().iter() {
+        handles.insert(AssetHandle::Mesh(renderer.mesh));
+        handles.insert(AssetHandle::Material(renderer.material));
+    }
+    for lod in world.query::<&LodGroup>().iter() {
+        handles.extend(
+            lod.levels()
+                .iter()
+                .map(|level| AssetHandle::Mesh(level.mesh)),
+        );
+    }
+    for camera in world.query::<&Camera>().iter() {
+        handles.extend(camera.target_texture.map(AssetHandle::Texture));
+    }
+    for post in world.query::<&PostProcessSettings>().iter() {
+        handles.extend(post.color_lut.map(AssetHandle::Texture));
+    }
+    for sprite in world.query::<&Sprite>().iter() {
+        handles.insert(AssetHandle::Texture(sprite.texture));
+    }
+    for text in world.query::<&Text3D>().iter() {
+        handles.insert(AssetHandle::Font(text.font));
+    }
+    for text in world.query::<&UiText>().iter() {
+        handles.insert(AssetHandle::Font(text.font));
     }
+    handles
 }
 
 /// Extension trait for MeshHandle to allow loading
@@ -0,0 +1,436 @@
+//! Asset GUIDs - stable ids kept in `.meta` sidecar files
+//!
+//! Every file under a project's Assets directory gets a `<file>.meta` next to
+//! it (`guid=<32 hex digits>`), created the first time it is seen. An asset that
+//! references another one (a material's textures) stores the GUID next to the
+//! path: `albedo_texture=...` plus `albedo_texture_guid=...`. The path stays the
+//! fast lookup; `AssetDatabase::fix_references` rewrites it whenever the GUID
+//! now lives somewhere else, so moving or renaming a file together with its
+//! `.meta` keeps everything that points at it working.
+//!
+//! The same scan records which asset references which (material -> textures
+//! and shader, prefab -> meshes), for "find references" and safe deletes.
+//! Other per-asset data (search labels, import settings) lives in the same
+//! file as more `key=value` lines.
+
+use std::collections::HashMap;
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::fs;
+use std::hash::{BuildHasher, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::asset_watcher::same_file;
+
+/// Extension of the sidecar file
+pub const META_EXTENSION: &str = "meta";
+
+/// Keys of a `.mat` file that hold a path to another asset
+pub const MATERIAL_REFERENCE_KEYS: [&str; 5] = [
+    "albedo_texture",
+    "diffuse_texture",
+    "texture",
+    "texture_path",
+    "normal_map",
+];
+
+/// Text assets whose `key=value` lines may point at other assets
+pub const REFERENCING_EXTENSIONS: [&str; 6] =
+    ["mat", "mold", "anim", "controller", "animctrl", "animodule"];
+
+/// Globally unique id of an asset file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AssetGuid(pub u128);
+
+impl AssetGuid {
+    /// Fresh random GUID
+    pub fn generate() -> Self {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+        // Each RandomState carries its own random keys
+        let half = || {
+            let mut hasher = RandomState::new().build_hasher();
+            hasher.write_u128(nanos);
+            hasher.write_u64(count);
+            hasher.write_u32(std::process::id());
+            hasher.finish()
+        };
+        AssetGuid(((half() as u128) << 64) | half() as u128)
+    }
+
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        if text.len() != 32 {
+            return None;
+        }
+        u128::from_str_radix(text, 16).ok().map(AssetGuid)
+    }
+}
+
+impl fmt::Display for AssetGuid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:032x}", self.0)
+    }
+}
+
+/// Sidecar file of `asset_path`
+pub fn meta_path(asset_path: &Path) -> PathBuf {
+    let mut path = asset_path.as_os_str().to_owned();
+    path.push(".");
+    path.push(META_EXTENSION);
+    PathBuf::from(path)
+}
+
+/// Whether `path` is a sidecar (`.meta`, `.sampling`) rather than an asset
+pub fn is_sidecar(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case(META_EXTENSION) || e == "sampling")
+}
+
+/// GUID stored in the asset's `.meta` file, if any
+pub fn read_guid(asset_path: &Path) -> Option<AssetGuid> {
+    let content = fs::read_to_string(meta_path(asset_path)).ok()?;
+    content
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .find(|(key, _)| key.trim() == "guid")
+        .and_then(|(_, value)| AssetGuid::parse(value))
+}
+
+fn write_guid(asset_path: &Path, guid: AssetGuid) -> Result<(), String> {
+    write_meta_value(asset_path, "guid", Some(&guid.to_string()))
+}
+
+/// Value of a `key=value` line of the asset's `.meta` file
+pub fn read_meta_value(asset_path: &Path, key: &str) -> Option<String> {
+    let content = fs::read_to_string(meta_path(asset_path)).ok()?;
+    content
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .find(|(k, _)| k.trim() == key)
+        .map(|(_, value)| value.trim().to_string())
+}
+
+/// Set (or with `None` remove) one `key=value` line of the asset's `.meta`
+/// file, keeping the other lines
+pub fn write_meta_value(asset_path: &Path, key: &str, value: Option<&str>) -> Result<(), String> {
+    let meta = meta_path(asset_path);
+    let content = fs::read_to_string(&meta).unwrap_or_default();
+    let mut out = String::with_capacity(content.len() + 64);
+    let mut written = false;
+    for line in content.lines() {
+        let is_key = line.split_once('=').is_some_and(|(k, _)| k.trim() == key);
+        if !is_key {
+            out.push_str(line);
+            out.push('\n');
+        } else if let (Some(value), false) = (value, written) {
+            out.push_str(&format!("{key}={value}\n"));
+            written = true;
+        }
+    }
+    if let (Some(value), false) = (value, written) {
+        out.push_str(&format!("{key}={value}\n"));
+    }
+    fs::write(&meta, out).map_err(|e| format!("Falha ao salvar {}: {e}", meta.display()))
+}
+
+/// Search labels of the asset (`labels=a,b` in its `.meta`), lowercase
+pub fn read_labels(asset_path: &Path) -> Vec<String> {
+    read_meta_value(asset_path, "labels")
+        .map(|value| parse_labels(&value))
+        .unwrap_or_default()
+}
+
+/// Replace the asset's labels; an empty list removes the line
+pub fn write_labels(asset_path: &Path, labels: &[String]) -> Result<(), String> {
+    let value = labels.join(",");
+    write_meta_value(
+        asset_path,
+        "labels",
+        (!value.is_empty()).then_some(value.as_str()),
+    )
+}
+
+/// `"Props, wood ,props"` -> `["props", "wood"]`
+pub fn parse_labels(text: &str) -> Vec<String> {
+    let mut labels: Vec<String> = Vec::new();
+    for label in text.split(',') {
+        let label = label.trim().to_lowercase();
+        if !label.is_empty() && !labels.contains(&label) {
+            labels.push(label);
+        }
+    }
+    labels
+}
+
+/// GUID of the asset, creating its `.meta` file when missing
+pub fn ensure_guid(asset_path: &Path) -> Result<AssetGuid, String> {
+    if let Some(guid) = read_guid(asset_path) {
+        return Ok(guid);
+    }
+    if !asset_path.is_file() {
+        return Err(format!("Asset não encontrado: {}", asset_path.display()));
+    }
+    let guid = AssetGuid::generate();
+    write_guid(asset_path, guid)?;
+    Ok(guid)
+}
+
+/// Path written into references: relative paths keep `/` separators
+fn reference_string(path: &Path) -> String {
+    let text = path.to_string_lossy().to_string();
+    if path.is_absolute() {
+        text
+    } else {
+        text.replace('\\', "/")
+    }
+}
+
+/// GUID <-> path of every asset under a project's Assets directory
+#[derive(Debug, Default)]
+pub struct AssetDatabase {
+    root: PathBuf,
+    paths: HashMap<AssetGuid, PathBuf>,
+    // Canonical path -> GUID, so absolute and relative references both match
+    guids: HashMap<PathBuf, AssetGuid>,
+    // Assets each asset references directly
+    dependencies: HashMap<AssetGuid, Vec<AssetGuid>>,
+}
+
+impl AssetDatabase {
+    /// Index every asset under `root`, creating missing `.meta` files. A file
+    /// copied together with its `.meta` gets a new GUID (the first path in
+    /// sorted order keeps the original)
+    pub fn scan(root: &Path) -> Self {
+        let mut files = Vec::new();
+        collect_assets(root, &mut files);
+        files.sort();
+
+        let mut db = Self {
+            root: root.to_path_buf(),
+            ..Self::default()
+        };
+        for path in files {
+            let mut guid = match ensure_guid(&path) {
+                Ok(guid) => guid,
+                Err(_) => continue,
+            };
+            if db.paths.contains_key(&guid) {
+                guid = AssetGuid::generate();
+                if write_guid(&path, guid).is_err() {
+                    continue;
+                }
+            }
+            let canonical = fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+            db.guids.insert(canonical, guid);
+            db.paths.insert(guid, path);
+        }
+
+        let referencing: Vec<(AssetGuid, PathBuf)> = db
+            .paths
+            .iter()
+            .filter(|(_, path)| {
+                path.extension().and_then(|e| e.to_str()).is_some_and(|e| {
+                    REFERENCING_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str())
+                })
+            })
+            .map(|(guid, path)| (*guid, path.clone()))
+            .collect();
+        for (guid, path) in referencing {
+            let dependencies = db.read_dependencies(&path);
+            if !dependencies.is_empty() {
+                db.dependencies.insert(guid, dependencies);
+            }
+        }
+        db
+    }
+
+    /// Assets referenced by the `key=value` lines of a text asset: `*_guid`
+    /// values, paths (relative to the project or to the file) and a
+    /// material's `shader=Name`
+    fn read_dependencies(&self, file: &Path) -> Vec<AssetGuid> {
+        let Ok(content) = fs::read_to_string(file) else {
+            return Vec::new();
+        };
+        let dir = file.parent().unwrap_or(Path::new(""));
+        let mut found = Vec::new();
+        for (key, value) in content.lines().filter_map(|line| line.split_once('=')) {
+            let key = key.trim();
+            let value = value.trim().trim_matches('"');
+            if value.is_empty() {
+                continue;
+            }
+            let guid = if key.ends_with("_guid") {
+                AssetGuid::parse(value).filter(|guid| self.paths.contains_key(guid))
+            } else if key == "shader" {
+                self.find_shader(value)
+            } else {
+                self.guid_of(Path::new(value))
+                    .or_else(|| self.guid_of(&dir.join(value)))
+            };
+            if let Some(guid) = guid.filter(|guid| !found.contains(guid)) {
+                found.push(guid);
+            }
+        }
+        found
+    }
+
+    /// Material shader file (`.wgsl`) named `name`
+    fn find_shader(&self, name: &str) -> Option<AssetGuid> {
+        self.paths
+            .iter()
+            .find(|(_, path)| {
+                path.extension()
+                    .is_some_and(|e| e.eq_ignore_ascii_case("wgsl"))
+                    && path
+                        .file_stem()
+                        .and_then(|s| s.to_str())
+                        .is_some_and(|s| s.eq_ignore_ascii_case(name))
+            })
+            .map(|(guid, _)| *guid)
+    }
+
+    /// Assets `guid` references directly
+    pub fn dependencies(&self, guid: AssetGuid) -> &[AssetGuid] {
+        self.dependencies.get(&guid).map_or(&[], Vec::as_slice)
+    }
+
+    /// Assets that reference `guid` directly, sorted by path
+    pub fn references(&self, guid: AssetGuid) -> Vec<AssetGuid> {
+        let mut owners: Vec<AssetGuid> = self
+            .dependencies
+            .iter()
+            .filter(|(_, dependencies)| dependencies.contains(&guid))
+            .map(|(owner, _)| *owner)
+            .collect();
+        owners.sort_by(|a, b| self.path_of(*a).cmp(&self.path_of(*b)));
+        owners
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    pub fn path_of(&self, guid: AssetGuid) -> Option<&Path> {
+        self.paths.get(&guid).map(PathBuf::as_path)
+    }
+
+    pub fn guid_of(&self, path: &Path) -> Option<AssetGuid> {
+        let canonical = fs::canonicalize(path).ok()?;
+        self.guids.get(&canonical).copied()
+    }
+
+    /// Every asset with its GUID, in no particular order
+    pub fn assets(&self) -> impl Iterator<Item = (AssetGuid, &Path)> {
+        self.paths
+            .iter()
+            .map(|(guid, path)| (*guid, path.as_path()))
+    }
+
+    /// Every `.mat` file in the database
+    pub fn materials(&self) -> Vec<PathBuf> {
+        let mut materials: Vec<PathBuf> = self
+            .paths
+            .values()
+            .filter(|path| {
+                path.extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| e.eq_ignore_ascii_case("mat"))
+            })
+            .cloned()
+            .collect();
+        materials.sort();
+        materials
+    }
+
+    /// Bring the references of a `key=value` file (`keys` hold paths) in line
+    /// with the database: a path whose GUID moved is rewritten, and a path
+    /// without a GUID line gets one. Returns whether the file changed
+    pub fn fix_references(&self, file: &Path, keys: &[&str]) -> Result<bool, String> {
+        let content = fs::read_to_string(file)
+            .map_err(|e| format!("Falha ao abrir {}: {e}", file.display()))?;
+        let lines: Vec<&str> = content.lines().collect();
+        let value_of = |key: &str| {
+            lines.iter().find_map(|line| {
+                let (k, v) = line.split_once('=')?;
+                (k.trim() == key).then(|| v.trim().trim_matches('"').to_string())
+            })
+        };
+
+        let mut out = String::with_capacity(content.len());
+        let mut changed = false;
+        for line in &lines {
+            let reference = line
+                .split_once('=')
+                .map(|(k, v)| (k.trim(), v.trim().trim_matches('"')))
+                .filter(|(k, v)| keys.contains(k) && !v.is_empty());
+            let Some((key, value)) = reference else {
+                out.push_str(line);
+                out.push('\n');
+                continue;
+            };
+            let guid_key = format!("{key}_guid");
+            let stored = value_of(&guid_key).and_then(|g| AssetGuid::parse(&g));
+            match stored {
+                Some(guid) => match self.path_of(guid) {
+                    Some(current) if !same_file(current, Path::new(value)) => {
+                        out.push_str(&format!("{key}={}\n", reference_string(current)));
+                        changed = true;
+                    }
+                    _ => {
+                        out.push_str(line);
+                        out.push('\n');
+                    }
+                },
+                None => {
+                    out.push_str(line);
+                    out.push('\n');
+                    if let Some(guid) = self.guid_of(Path::new(value)) {
+                        out.push_str(&format!("{guid_key}={guid}\n"));
+                        changed = true;
+                    }
+                }
+            }
+        }
+        if changed {
+            fs::write(file, out).map_err(|e| format!("Falha ao salvar {}: {e}", file.display()))?;
+        }
+        Ok(changed)
+    }
+}
+
+fn collect_assets(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let hidden = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with('.'));
+        if hidden {
+            continue;
+        }
+        if path.is_dir() {
+            collect_assets(&path, out);
+        } else if !is_sidecar(&path) {
+            out.push(path);
+        }
+    }
+}
@@ -0,0 +1,316 @@
+//! Project-wide asset search - names, types and labels
+//!
+//! `AssetSearchIndex` is rebuilt from the `AssetDatabase` after every scan.
+//! A query is a list of words: `t:mesh` keeps one asset type, `l:wood` keeps
+//! assets with that label, and every other word must fuzzy-match the file
+//! name (letters in order, gaps allowed) or one of the labels. Hits are ranked
+//! by how tight the matches are.
+
+use std::path::{Path, PathBuf};
+
+use crate::asset_manager::AssetKind;
+use crate::asset_meta::{AssetDatabase, AssetGuid, read_labels};
+
+/// What an asset file is, for search filters and pickers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum AssetType {
+    Mesh,
+    Texture,
+    Material,
+    Audio,
+    Prefab,
+    Scene,
+    Script,
+    Shader,
+    Animation,
+    Font,
+    Environment,
+    Other,
+}
+
+impl AssetType {
+    pub const ALL: [AssetType; 12] = [
+        AssetType::Mesh,
+        AssetType::Texture,
+        AssetType::Material,
+        AssetType::Audio,
+        AssetType::Prefab,
+        AssetType::Scene,
+        AssetType::Script,
+        AssetType::Shader,
+        AssetType::Animation,
+        AssetType::Font,
+        AssetType::Environment,
+        AssetType::Other,
+    ];
+
+    pub fn from_path(path: &Path) -> Self {
+        if let Some(kind) = AssetKind::from_path(path) {
+            return match kind {
+                AssetKind::Mesh => AssetType::Mesh,
+                AssetKind::Texture => AssetType::Texture,
+                AssetKind::AudioClip => AssetType::Audio,
+                AssetKind::Font => AssetType::Font,
+                AssetKind::Environment => AssetType::Environment,
+            };
+        }
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match ext.as_str() {
+            "mat" | "material" => AssetType::Material,
+            "mold" => AssetType::Prefab,
+            "deng" => AssetType::Scene,
+            "cs" | "rs" | "lua" => AssetType::Script,
+            "wgsl" => AssetType::Shader,
+            "anim" | "controller" | "animctrl" | "animodule" => AssetType::Animation,
+            _ => AssetType::Other,
+        }
+    }
+
+    /// Name used by `t:` filters
+    pub fn name(self) -> &'static str {
+        match self {
+            AssetType::Mesh => "mesh",
+            AssetType::Texture => "texture",
+            AssetType::Material => "material",
+            AssetType::Audio => "audio",
+            AssetType::Prefab => "prefab",
+            AssetType::Scene => "scene",
+            AssetType::Script => "script",
+            AssetType::Shader => "shader",
+            AssetType::Animation => "animation",
+            AssetType::Font => "font",
+            AssetType::Environment => "environment",
+            AssetType::Other => "other",
+        }
+    }
+
+    /// Type named by a `t:` filter; a prefix is enough (`t:tex`)
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.to_ascii_lowercase();
+        if text.is_empty() {
+            return None;
+        }
+        Self::ALL.into_iter().find(|t| t.name().starts_with(&text))
+    }
+}
+
+/// One indexed asset
+#[derive(Debug, Clone)]
+pub struct SearchEntry {
+    pub guid: AssetGuid,
+    pub path: PathBuf,
+    /// File name, as shown in the Project window
+    pub name: String,
+    pub asset_type: AssetType,
+    /// Lowercase labels from the `.meta` file
+    pub labels: Vec<String>,
+}
+
+/// A search result, best first
+#[derive(Debug, Clone)]
+pub struct SearchHit<'a> {
+    pub entry: &'a SearchEntry,
+    pub score: i32,
+}
+
+/// Names, types and labels of every asset of a project
+#[derive(Debug, Clone, Default)]
+pub struct AssetSearchIndex {
+    entries: Vec<SearchEntry>,
+}
+
+impl AssetSearchIndex {
+    /// Index every asset of the database, reading labels from the `.meta` files
+    pub fn build(database: &AssetDatabase) -> Self {
+        let mut entries: Vec<SearchEntry> = database
+            .assets()
+            .map(|(guid, path)| SearchEntry {
+                guid,
+                path: path.to_path_buf(),
+                name: path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                asset_type: AssetType::from_path(path),
+                labels: read_labels(path),
+            })
+            .collect();
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        Self { entries }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn entries(&self) -> &[SearchEntry] {
+        &self.entries
+    }
+
+    pub fn entry(&self, guid: AssetGuid) -> Option<&SearchEntry> {
+        self.entries.iter().find(|entry| entry.guid == guid)
+    }
+
+    /// Entry of a file, given by the same path or one relative to the project
+    /// (`Assets/Meshes/a.obj` finds `/project/Assets/Meshes/a.obj`)
+    pub fn entry_at(&self, path: &Path) -> Option<&SearchEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.path == path || entry.path.ends_with(path))
+    }
+
+    /// Update the labels of one asset after they were written to its `.meta`
+    pub fn set_labels(&mut self, guid: AssetGuid, labels: Vec<String>) {
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.guid == guid) {
+            entry.labels = labels;
+        }
+    }
+
+    /// Every label in use, sorted
+    pub fn labels(&self) -> Vec<&str> {
+        let mut labels: Vec<&str> = self
+            .entries
+            .iter()
+            .flat_map(|entry| entry.labels.iter().map(String::as_str))
+            .collect();
+        labels.sort_unstable();
+        labels.dedup();
+        labels
+    }
+
+    /// Assets matching `query` (see the module docs), at most `limit`
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchHit<'_>> {
+        self.search_type(query, None, limit)
+    }
+
+    /// `search` restricted to one type, for asset pickers
+    pub fn search_type(
+        &self,
+        query: &str,
+        asset_type: Option<AssetType>,
+        limit: usize,
+    ) -> Vec<SearchHit<'_>> {
+        let query = SearchQuery::parse(query);
+        let mut hits: Vec<SearchHit> = self
+            .entries
+            .iter()
+            .filter(|entry| asset_type.is_none_or(|t| entry.asset_type == t))
+            .filter_map(|entry| query.score(entry).map(|score| SearchHit { entry, score }))
+            .collect();
+        hits.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then_with(|| a.entry.name.len().cmp(&b.entry.name.len()))
+                .then_with(|| a.entry.path.cmp(&b.entry.path))
+        });
+        hits.truncate(limit);
+        hits
+    }
+}
+
+/// A parsed query: filters and the words to match
+#[derive(Debug, Clone, Default)]
+pub struct SearchQuery {
+    /// `None` for a `t:` naming no type, which matches nothing
+    types: Vec<Option<AssetType>>,
+    labels: Vec<String>,
+    words: Vec<String>,
+}
+
+impl SearchQuery {
+    pub fn parse(query: &str) -> Self {
+        let mut parsed = Self::default();
+        for word in query.split_whitespace() {
+            let lower = word.to_lowercase();
+            if let Some(name) = lower.strip_prefix("t:") {
+                parsed.types.push(AssetType::parse(name));
+            } else if let Some(label) = lower.strip_prefix("l:") {
+                if !label.is_empty() {
+                    parsed.labels.push(label.to_string());
+                }
+            } else {
+                parsed.words.push(lower);
+            }
+        }
+        parsed
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty() && self.labels.is_empty() && self.words.is_empty()
+    }
+
+    pub fn score(&self, entry: &SearchEntry) -> Option<i32> {
+        self.score_parts(&entry.name, entry.asset_type, &entry.labels)
+    }
+
+    /// Score of an asset that may not be indexed yet
+    pub fn score_parts(&self, name: &str, asset_type: AssetType, labels: &[String]) -> Option<i32> {
+        if !self.types.is_empty() && !self.types.contains(&Some(asset_type)) {
+            return None;
+        }
+        if !self
+            .labels
+            .iter()
+            .all(|label| labels.iter().any(|l| l.starts_with(label.as_str())))
+        {
+            return None;
+        }
+        let mut total = 0;
+        for word in &self.words {
+            let name = fuzzy_score(word, name);
+            let label = labels
+                .iter()
+                .filter_map(|l| fuzzy_score(word, l))
+                .max()
+                .map(|s| s - 5);
+            total += name.max(label)?;
+        }
+        Some(total)
+    }
+}
+
+/// How well `pattern` (lowercase) matches `text` as an in-order subsequence;
+/// `None` when some letter is missing. Consecutive letters, word starts and a
+/// match at the very start score higher, gaps lower
+pub fn fuzzy_score(pattern: &str, text: &str) -> Option<i32> {
+    if pattern.is_empty() {
+        return Some(0);
+    }
+    let text: Vec<char> = text.chars().collect();
+    let lower: Vec<char> = text.iter().flat_map(|c| c.to_lowercase()).collect();
+    if lower.len() != text.len() {
+        // Letters that change length when lowercased: plain substring test
+        let haystack: String = lower.iter().collect();
+        return haystack.contains(pattern).then_some(1);
+    }
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous: Option<usize> = None;
+    for p in pattern.chars() {
+        let found = (next..lower.len()).find(|&i| lower[i] == p)?;
+        score += 1;
+        if found == 0 {
+            score += 8;
+        } else if !text[found - 1].is_alphanumeric()
+            || (text[found].is_uppercase() && text[found - 1].is_lowercase())
+        {
+            score += 6;
+        }
+        match previous {
+            Some(prev) if prev + 1 == found => score += 5,
+            Some(prev) => score -= ((found - prev - 1) as i32).min(5),
+            None => {}
+        }
+        previous = Some(found);
+        next = found + 1;
+    }
+    Some(score)
+}
@@ -0,0 +1,146 @@
+//! Asset watcher - hot reload of a project's Assets directory
+//!
+//! Works like the shader library: no OS file events, just the size and
+//! modification time of every file under the directory, compared at most every
+//! `POLL_INTERVAL`. Each edit is reported once as an `AssetChange`; the caller
+//! reloads the asset in place so the handles and paths pointing at it stay valid.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::asset_manager::AssetKind;
+use crate::shader_library::file_stamp;
+
+/// How often the directory is scanned
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// What a changed file is, from its extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WatchedAsset {
+    /// Anything `AssetManager::load` reads
+    Asset(AssetKind),
+    /// Material shader (`.wgsl`)
+    Shader,
+    /// Lua script
+    Script,
+    /// Material description (`.mat`)
+    Material,
+}
+
+impl WatchedAsset {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        if let Some(kind) = AssetKind::from_path(path) {
+            return Some(WatchedAsset::Asset(kind));
+        }
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "wgsl" => Some(WatchedAsset::Shader),
+            "lua" => Some(WatchedAsset::Script),
+            "mat" => Some(WatchedAsset::Material),
+            _ => None,
+        }
+    }
+}
+
+/// A file created or modified since the previous poll
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetChange {
+    pub path: PathBuf,
+    pub kind: WatchedAsset,
+}
+
+/// Polls every known asset file under a directory
+#[derive(Debug)]
+pub struct AssetWatcher {
+    dir: PathBuf,
+    // (size, mtime) of each file as last seen on disk
+    stamps: HashMap<PathBuf, (u64, u64)>,
+    last_poll: Option<Instant>,
+}
+
+impl AssetWatcher {
+    /// Start watching `dir`; files already there are not reported
+    pub fn watch(dir: &Path) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+            stamps: scan(dir),
+            last_poll: None,
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Files created or modified since the last poll (checked at most every
+    /// `POLL_INTERVAL`). Removed files are forgotten silently.
+    pub fn poll(&mut self) -> Vec<AssetChange> {
+        engine_core::profile_function!();
+        let now = Instant::now();
+        if self
+            .last_poll
+            .is_some_and(|last| now.duration_since(last) < POLL_INTERVAL)
+        {
+            return Vec::new();
+        }
+        self.last_poll = Some(now);
+
+        let stamps = scan(&self.dir);
+        let mut changes: Vec<AssetChange> = stamps
+            .iter()
+            .filter(|(path, stamp)| self.stamps.get(*path) != Some(*stamp))
+            .filter_map(|(path, _)| {
+                Some(AssetChange {
+                    path: path.clone(),
+                    kind: WatchedAsset::from_path(path)?,
+                })
+            })
+            .collect();
+        changes.sort_by(|a, b| a.path.cmp(&b.path));
+        self.stamps = stamps;
+        changes
+    }
+}
+
+/// Whether two paths, relative or absolute, name the same file
+pub fn same_file(a: &Path, b: &Path) -> bool {
+    if a == b {
+        return true;
+    }
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Stamp of every watched file under `dir`. Hidden directories (editor caches
+/// such as `.cache`) are skipped
+fn scan(dir: &Path) -> HashMap<PathBuf, (u64, u64)> {
+    let mut stamps = HashMap::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let hidden = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with('.'));
+            if hidden {
+                continue;
+            }
+            if path.is_dir() {
+                pending.push(path);
+            } else if WatchedAsset::from_path(&path).is_some() {
+                if let Some(stamp) = file_stamp(&path) {
+                    stamps.insert(path, stamp);
+                }
+            }
+        }
+    }
+    stamps
+}
@@ -0,0 +1,162 @@
+//! Audio decoding for metadata - duration, channels and a waveform overview
+//!
+//! Clips stay encoded in `AudioClipData` for the audio backend; this module
+//! decodes them once at import (WAV, OGG Vorbis and MP3 through symphonia) to
+//! learn their length and to reduce the samples to `WAVEFORM_BUCKETS` peaks
+//! the editor can draw.
+
+use std::io::{Cursor, ErrorKind};
+use std::sync::Arc;
+
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{CODEC_TYPE_NULL, DecoderOptions};
+use symphonia::core::errors::Error;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+use crate::asset_manager::AudioFormat;
+
+/// Number of peaks kept for the waveform overview
+pub const WAVEFORM_BUCKETS: usize = 256;
+
+/// What a decoded clip looks like
+#[derive(Debug, Clone, Default)]
+pub struct AudioInfo {
+    pub sample_rate: u32,
+    pub channels: u16,
+    /// Sample frames per channel
+    pub frames: u64,
+    pub duration_secs: f32,
+    /// Highest absolute sample (0..1) of each slice of the clip, all channels mixed
+    pub peaks: Vec<f32>,
+}
+
+impl AudioInfo {
+    /// Duration as `m:ss.t`
+    pub fn duration_label(&self) -> String {
+        let tenths = (self.duration_secs * 10.0).round() as u64;
+        format!("{}:{:02}.{}", tenths / 600, tenths / 10 % 60, tenths % 10)
+    }
+}
+
+/// Decode the whole clip and summarize it
+pub fn analyze_audio(bytes: Arc<[u8]>, format: AudioFormat) -> Result<AudioInfo, String> {
+    let source = MediaSourceStream::new(Box::new(Cursor::new(bytes)), Default::default());
+    let mut hint = Hint::new();
+    hint.with_extension(match format {
+        AudioFormat::Wav => "wav",
+        AudioFormat::Ogg => "ogg",
+        AudioFormat::Mp3 => "mp3",
+    });
+    let probed = symphonia::default::get_probe()
+        .format(
+            &hint,
+            source,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .map_err(|e| format!("Formato de áudio não reconhecido: {e}"))?;
+    let mut reader = probed.format;
+    let track = reader
+        .tracks()
+        .iter()
+        .find(|track| track.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| "Arquivo de áudio sem trilha".to_string())?;
+    let track_id = track.id;
+    let mut sample_rate = track.codec_params.sample_rate.unwrap_or(0);
+    let mut channels = track
+        .codec_params
+        .channels
+        .map(|c| c.count() as u16)
+        .unwrap_or(0);
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(|e| format!("Codec de áudio não suportado: {e}"))?;
+
+    // Peaks of fixed 10 ms blocks, reduced to the buckets at the end
+    let mut block_peaks: Vec<f32> = Vec::new();
+    let mut block_len = 0usize;
+    let mut block_frames = 0usize;
+    let mut block_peak = 0.0f32;
+    let mut frames = 0u64;
+    let mut samples: Option<SampleBuffer<f32>> = None;
+
+    loop {
+        let packet = match reader.next_packet() {
+            Ok(packet) => packet,
+            Err(Error::IoError(e)) if e.kind() == ErrorKind::UnexpectedEof => break,
+            Err(Error::ResetRequired) => break,
+            Err(e) => return Err(format!("Falha ao ler áudio: {e}")),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            // A damaged packet only loses its own samples
+            Err(Error::DecodeError(_)) => continue,
+            Err(e) => return Err(format!("Falha ao decodificar áudio: {e}")),
+        };
+        let spec = *decoded.spec();
+        sample_rate = spec.rate;
+        channels = spec.channels.count() as u16;
+        if block_len == 0 {
+            block_len = (sample_rate as usize / 100).max(1);
+        }
+        if samples
+            .as_ref()
+            .is_none_or(|buffer| buffer.capacity() < decoded.capacity())
+        {
+            samples = Some(SampleBuffer::new(decoded.capacity() as u64, spec));
+        }
+        let Some(buffer) = samples.as_mut() else {
+            continue;
+        };
+        buffer.copy_interleaved_ref(decoded);
+        let step = channels.max(1) as usize;
+        for frame in buffer.samples().chunks(step) {
+            for sample in frame {
+                block_peak = block_peak.max(sample.abs());
+            }
+            block_frames += 1;
+            if block_frames == block_len {
+                block_peaks.push(block_peak.min(1.0));
+                block_frames = 0;
+                block_peak = 0.0;
+            }
+        }
+        frames += (buffer.len() / step) as u64;
+    }
+    if block_frames > 0 {
+        block_peaks.push(block_peak.min(1.0));
+    }
+
+    let duration_secs = if sample_rate > 0 {
+        frames as f32 / sample_rate as f32
+    } else {
+        0.0
+    };
+    Ok(AudioInfo {
+        sample_rate,
+        channels,
+        frames,
+        duration_secs,
+        peaks: reduce_peaks(&block_peaks, WAVEFORM_BUCKETS),
+    })
+}
+
+/// Max of each of `buckets` equal slices; short inputs are kept as they are
+fn reduce_peaks(peaks: &[f32], buckets: usize) -> Vec<f32> {
+    if peaks.len() <= buckets {
+        return peaks.to_vec();
+    }
+    (0..buckets)
+        .map(|bucket| {
+            let start = bucket * peaks.len() / buckets;
+            let end = ((bucket + 1) * peaks.len() / buckets).max(start + 1);
+            peaks[start..end].iter().copied().fold(0.0, f32::max)
+        })
+        .collect()
+}
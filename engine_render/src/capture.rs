@@ -0,0 +1,150 @@
+//! Viewport capture - screenshot requests and PNG/EXR encoding
+//!
+//! The viewport renders a requested capture offscreen at the asked resolution
+//! and reads it back; this module validates the request and writes the file.
+//! PNG gets the final tonemapped image, EXR the linear HDR scene color (before
+//! exposure, bloom and tonemapping) for compositing.
+
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Largest capture side accepted (the GPU limit may be lower)
+pub const MAX_CAPTURE_SIZE: u32 = 16384;
+
+/// Output file format, picked from the path extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureFormat {
+    /// 8-bit sRGB, post-processed
+    Png,
+    /// 32-bit float linear HDR
+    Exr,
+}
+
+impl CaptureFormat {
+    pub const ALL: [CaptureFormat; 2] = [CaptureFormat::Png, CaptureFormat::Exr];
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            CaptureFormat::Png => "png",
+            CaptureFormat::Exr => "exr",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CaptureFormat::Png => "PNG",
+            CaptureFormat::Exr => "EXR (HDR)",
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_lowercase();
+        match ext.as_str() {
+            "png" => Some(CaptureFormat::Png),
+            "exr" => Some(CaptureFormat::Exr),
+            _ => None,
+        }
+    }
+}
+
+/// One screenshot to take on the next viewport frame
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaptureRequest {
+    pub path: PathBuf,
+    pub width: u32,
+    pub height: u32,
+    pub format: CaptureFormat,
+}
+
+impl CaptureRequest {
+    /// Validate the size and the extension of `path` (`.png` or `.exr`)
+    pub fn new(path: impl Into<PathBuf>, width: u32, height: u32) -> Result<Self, String> {
+        let path = path.into();
+        let format = CaptureFormat::from_path(&path).ok_or_else(|| {
+            format!(
+                "Formato de captura não suportado: {} (use .png ou .exr)",
+                path.display()
+            )
+        })?;
+        if width == 0 || height == 0 || width > MAX_CAPTURE_SIZE || height > MAX_CAPTURE_SIZE {
+            return Err(format!(
+                "Resolução de captura inválida: {width}x{height} (1..={MAX_CAPTURE_SIZE})"
+            ));
+        }
+        Ok(Self {
+            path,
+            width,
+            height,
+            format,
+        })
+    }
+
+    pub fn size(&self) -> [u32; 2] {
+        [self.width, self.height]
+    }
+
+    pub fn aspect(&self) -> f32 {
+        self.width as f32 / self.height as f32
+    }
+}
+
+/// Pixels read back from the GPU, tightly packed RGBA rows (top row first)
+pub enum CapturePixels {
+    Rgba8(Vec<u8>),
+    RgbaF32(Vec<f32>),
+}
+
+/// Write the captured pixels to `request.path`, creating the folder if needed
+pub fn save_capture(request: &CaptureRequest, pixels: CapturePixels) -> Result<(), String> {
+    if let Some(dir) = request
+        .path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+    {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("Falha ao criar {}: {e}", dir.display()))?;
+    }
+    let (width, height) = (request.width, request.height);
+    let result = match (request.format, pixels) {
+        (CaptureFormat::Png, CapturePixels::Rgba8(data)) => {
+            image::RgbaImage::from_raw(width, height, data)
+                .ok_or_else(|| "Tamanho dos pixels não confere com a captura".to_string())?
+                .save_with_format(&request.path, image::ImageFormat::Png)
+        }
+        (CaptureFormat::Exr, CapturePixels::RgbaF32(data)) => {
+            image::Rgba32FImage::from_raw(width, height, data)
+                .ok_or_else(|| "Tamanho dos pixels não confere com a captura".to_string())?
+                .save_with_format(&request.path, image::ImageFormat::OpenExr)
+        }
+        _ => return Err("Pixels da captura no formato errado".to_string()),
+    };
+    result.map_err(|e| format!("Falha ao salvar {}: {e}", request.path.display()))
+}
+
+/// `<dir>/<stem>_<unix seconds>.<extension>`, suffixed when the name is taken
+/// (screenshots and video recordings)
+pub fn timestamped_capture_path(dir: &Path, stem: &str, extension: &str) -> PathBuf {
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let mut path = dir.join(format!("{stem}_{stamp}.{extension}"));
+    let mut n = 1;
+    while path.exists() {
+        path = dir.join(format!("{stem}_{stamp}_{n}.{extension}"));
+        n += 1;
+    }
+    path
+}
+
+/// IEEE half to f32 (for `Rgba16Float` readbacks)
+pub fn f16_to_f32(bits: u16) -> f32 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = ((bits >> 10) & 0x1f) as i32;
+    let mantissa = (bits & 0x3ff) as f32;
+    match exponent {
+        0 => sign * mantissa * 2f32.powi(-24),
+        0x1f if mantissa == 0.0 => sign * f32::INFINITY,
+        0x1f => f32::NAN,
+        _ => sign * (1.0 + mantissa / 1024.0) * 2f32.powi(exponent - 15),
+    }
+}
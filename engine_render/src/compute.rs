@@ -0,0 +1,358 @@
+//! Compute API - GPU buffers, pipelines and dispatches for user simulations
+//!
+//! Wraps a `wgpu` device/queue so game code can run compute shaders (boids,
+//! particles, cellular automata...) without touching the renderer. Every call
+//! that could trip wgpu validation runs in an error scope and returns
+//! `Err(message)` instead of panicking:
+//!
+//! ```ignore
+//! let compute = viewport_gpu.compute();
+//! let boids = compute.create_buffer_f32("boids", &positions, ComputeBufferUsage::Storage)?;
+//! let pipeline = compute.create_pipeline("boids", BOIDS_WGSL, "cs_main")?;
+//! let group = compute.create_bind_group(&pipeline, 0, &[(0, &boids)])?;
+//! compute.dispatch(&pipeline, &[&group], [count.div_ceil(64), 1, 1])?;
+//! let positions = compute.read_f32(&boids)?;
+//! ```
+
+use std::sync::mpsc;
+
+/// How a buffer is bound in the shader
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComputeBufferUsage {
+    /// `var<storage, read>` / `var<storage, read_write>`; can be read back
+    Storage,
+    /// `var<uniform>`
+    Uniform,
+}
+
+impl ComputeBufferUsage {
+    fn wgpu_usage(self) -> wgpu::BufferUsages {
+        match self {
+            ComputeBufferUsage::Storage => {
+                wgpu::BufferUsages::STORAGE
+                    | wgpu::BufferUsages::COPY_SRC
+                    | wgpu::BufferUsages::COPY_DST
+            }
+            ComputeBufferUsage::Uniform => {
+                wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST
+            }
+        }
+    }
+}
+
+/// GPU buffer created by a `ComputeContext`
+#[derive(Debug, Clone)]
+pub struct ComputeBuffer {
+    buffer: wgpu::Buffer,
+    usage: ComputeBufferUsage,
+}
+
+impl ComputeBuffer {
+    /// Size in bytes
+    pub fn size(&self) -> u64 {
+        self.buffer.size()
+    }
+
+    pub fn usage(&self) -> ComputeBufferUsage {
+        self.usage
+    }
+
+    /// Raw buffer, to share the data with a render pass
+    pub fn raw(&self) -> &wgpu::Buffer {
+        &self.buffer
+    }
+}
+
+/// Compiled compute shader entry point (bind group layouts are derived from
+/// the shader)
+#[derive(Debug, Clone)]
+pub struct ComputePipeline {
+    pipeline: wgpu::ComputePipeline,
+    label: String,
+}
+
+/// Buffers bound to one `@group` of a pipeline
+#[derive(Debug, Clone)]
+pub struct ComputeBindGroup {
+    bind_group: wgpu::BindGroup,
+    group: u32,
+}
+
+/// Entry point of the compute API, sharing the renderer's device
+#[derive(Debug, Clone)]
+pub struct ComputeContext {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+}
+
+impl ComputeContext {
+    pub fn new(device: wgpu::Device, queue: wgpu::Queue) -> Self {
+        Self { device, queue }
+    }
+
+    /// Zero-initialized buffer of `size` bytes (rounded up to 4)
+    pub fn create_buffer(
+        &self,
+        label: &str,
+        size: u64,
+        usage: ComputeBufferUsage,
+    ) -> Result<ComputeBuffer, String> {
+        if size == 0 {
+            return Err(format!("Buffer \"{label}\" vazio"));
+        }
+        let buffer = scoped(&self.device, || {
+            self.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size: size.next_multiple_of(wgpu::COPY_BUFFER_ALIGNMENT),
+                usage: usage.wgpu_usage(),
+                mapped_at_creation: false,
+            })
+        })?;
+        Ok(ComputeBuffer { buffer, usage })
+    }
+
+    pub fn create_buffer_bytes(
+        &self,
+        label: &str,
+        data: &[u8],
+        usage: ComputeBufferUsage,
+    ) -> Result<ComputeBuffer, String> {
+        let buffer = self.create_buffer(label, data.len() as u64, usage)?;
+        self.write_bytes(&buffer, 0, data)?;
+        Ok(buffer)
+    }
+
+    pub fn create_buffer_f32(
+        &self,
+        label: &str,
+        data: &[f32],
+        usage: ComputeBufferUsage,
+    ) -> Result<ComputeBuffer, String> {
+        self.create_buffer_bytes(label, &f32_bytes(data), usage)
+    }
+
+    pub fn create_buffer_u32(
+        &self,
+        label: &str,
+        data: &[u32],
+        usage: ComputeBufferUsage,
+    ) -> Result<ComputeBuffer, String> {
+        self.create_buffer_bytes(label, &u32_bytes(data), usage)
+    }
+
+    /// Queue a write at `offset` bytes; it lands before the next dispatch
+    pub fn write_bytes(
+        &self,
+        buffer: &ComputeBuffer,
+        offset: u64,
+        data: &[u8],
+    ) -> Result<(), String> {
+        if offset % wgpu::COPY_BUFFER_ALIGNMENT != 0 {
+            return Err(format!("Offset {offset} não é múltiplo de 4"));
+        }
+        if offset + data.len() as u64 > buffer.size() {
+            return Err(format!(
+                "Escrita de {} bytes em {offset} excede o buffer ({} bytes)",
+                data.len(),
+                buffer.size()
+            ));
+        }
+        // write_buffer exige tamanho múltiplo de 4
+        let mut padded = data.to_vec();
+        padded.resize(
+            data.len()
+                .next_multiple_of(wgpu::COPY_BUFFER_ALIGNMENT as usize),
+            0,
+        );
+        self.queue.write_buffer(&buffer.buffer, offset, &padded);
+        Ok(())
+    }
+
+    pub fn write_f32(
+        &self,
+        buffer: &ComputeBuffer,
+        offset: u64,
+        data: &[f32],
+    ) -> Result<(), String> {
+        self.write_bytes(buffer, offset, &f32_bytes(data))
+    }
+
+    pub fn write_u32(
+        &self,
+        buffer: &ComputeBuffer,
+        offset: u64,
+        data: &[u32],
+    ) -> Result<(), String> {
+        self.write_bytes(buffer, offset, &u32_bytes(data))
+    }
+
+    /// Compile `source` and build the pipeline for `entry_point`
+    pub fn create_pipeline(
+        &self,
+        label: &str,
+        source: &str,
+        entry_point: &str,
+    ) -> Result<ComputePipeline, String> {
+        let module = scoped(&self.device, || {
+            self.device
+                .create_shader_module(wgpu::ShaderModuleDescriptor {
+                    label: Some(label),
+                    source: wgpu::ShaderSource::Wgsl(source.into()),
+                })
+        })?;
+        let pipeline = scoped(&self.device, || {
+            self.device
+                .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                    label: Some(label),
+                    layout: None,
+                    module: &module,
+                    entry_point: Some(entry_point),
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                    cache: None,
+                })
+        })?;
+        Ok(ComputePipeline {
+            pipeline,
+            label: label.to_string(),
+        })
+    }
+
+    /// Bind `(binding, buffer)` pairs to `@group(group)` of `pipeline`
+    pub fn create_bind_group(
+        &self,
+        pipeline: &ComputePipeline,
+        group: u32,
+        buffers: &[(u32, &ComputeBuffer)],
+    ) -> Result<ComputeBindGroup, String> {
+        let bind_group = scoped(&self.device, || {
+            let layout = pipeline.pipeline.get_bind_group_layout(group);
+            let entries: Vec<wgpu::BindGroupEntry> = buffers
+                .iter()
+                .map(|(binding, buffer)| wgpu::BindGroupEntry {
+                    binding: *binding,
+                    resource: buffer.buffer.as_entire_binding(),
+                })
+                .collect();
+            self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some(&format!("{}_group{group}", pipeline.label)),
+                layout: &layout,
+                entries: &entries,
+            })
+        })?;
+        Ok(ComputeBindGroup { bind_group, group })
+    }
+
+    /// Run `pipeline` over `workgroups` (x, y, z) and submit it
+    pub fn dispatch(
+        &self,
+        pipeline: &ComputePipeline,
+        bind_groups: &[&ComputeBindGroup],
+        workgroups: [u32; 3],
+    ) -> Result<(), String> {
+        engine_core::profile_function!();
+        let max = self.device.limits().max_compute_workgroups_per_dimension;
+        if workgroups.iter().any(|&count| count > max) {
+            return Err(format!(
+                "Dispatch {workgroups:?} excede o limite de {max} workgroups por eixo"
+            ));
+        }
+        if workgroups.contains(&0) {
+            return Ok(());
+        }
+        let commands = scoped(&self.device, || {
+            let mut encoder = self
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some(&pipeline.label),
+                });
+            {
+                let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                    label: Some(&pipeline.label),
+                    timestamp_writes: None,
+                });
+                pass.set_pipeline(&pipeline.pipeline);
+                for group in bind_groups {
+                    pass.set_bind_group(group.group, &group.bind_group, &[]);
+                }
+                pass.dispatch_workgroups(workgroups[0], workgroups[1], workgroups[2]);
+            }
+            encoder.finish()
+        })?;
+        self.queue.submit(Some(commands));
+        Ok(())
+    }
+
+    /// Copy a storage buffer back to the CPU (waits for the GPU)
+    pub fn read_bytes(&self, buffer: &ComputeBuffer) -> Result<Vec<u8>, String> {
+        engine_core::profile_function!();
+        if buffer.usage != ComputeBufferUsage::Storage {
+            return Err("Só buffers Storage podem ser lidos".to_string());
+        }
+        let staging = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("compute_readback"),
+            size: buffer.size(),
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("compute_readback"),
+            });
+        encoder.copy_buffer_to_buffer(&buffer.buffer, 0, &staging, 0, buffer.size());
+        self.queue.submit(Some(encoder.finish()));
+
+        let (sender, receiver) = mpsc::channel();
+        staging
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, move |result| {
+                let _ = sender.send(result);
+            });
+        self.device
+            .poll(wgpu::PollType::wait_indefinitely())
+            .map_err(|e| format!("Falha ao esperar a GPU: {e}"))?;
+        receiver
+            .recv()
+            .map_err(|e| format!("Leitura cancelada: {e}"))?
+            .map_err(|e| format!("Falha ao mapear o buffer: {e}"))?;
+        let data = staging.slice(..).get_mapped_range().to_vec();
+        staging.unmap();
+        Ok(data)
+    }
+
+    pub fn read_f32(&self, buffer: &ComputeBuffer) -> Result<Vec<f32>, String> {
+        let bytes = self.read_bytes(buffer)?;
+        Ok(bytes
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect())
+    }
+
+    pub fn read_u32(&self, buffer: &ComputeBuffer) -> Result<Vec<u32>, String> {
+        let bytes = self.read_bytes(buffer)?;
+        Ok(bytes
+            .chunks_exact(4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect())
+    }
+}
+
+/// Run `f` in a validation error scope, turning wgpu errors into `Err`
+fn scoped<T>(device: &wgpu::Device, f: impl FnOnce() -> T) -> Result<T, String> {
+    device.push_error_scope(wgpu::ErrorFilter::Validation);
+    let value = f();
+    // On native wgpu the scope is already resolved when popped
+    let error = std::pin::pin!(device.pop_error_scope());
+    match error.poll(&mut std::task::Context::from_waker(std::task::Waker::noop())) {
+        std::task::Poll::Ready(Some(error)) => Err(error.to_string()),
+        _ => Ok(value),
+    }
+}
+
+fn f32_bytes(data: &[f32]) -> Vec<u8> {
+    data.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+fn u32_bytes(data: &[u32]) -> Vec<u8> {
+    data.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
@@ -0,0 +1,377 @@
+//! Cubemaps - six square faces around a point, for skies and reflections
+//!
+//! A cubemap is imported from a folder with six face images (px/nx/py/ny/pz/nz,
+//! posx/negx/..., or right/left/top/bottom/front/back), from one image holding
+//! the faces as a strip (6:1 or 1:6) or a cross (4:3 or 3:4), or resampled from
+//! an equirectangular panorama. `CubemapData` keeps the faces as linear
+//! RGBA32F in the layer order of GPU cube textures (+X, -X, +Y, -Y, +Z, -Z) and
+//! `create_cube_texture` uploads them as one `Rgba16Float` cube texture.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use glam::Vec3;
+
+use crate::environment::{EnvironmentMap, equirect_to_direction, load_linear_rgba};
+use crate::hdr::pack_rgba16f;
+
+/// Faces of a cubemap
+pub const CUBE_FACES: usize = 6;
+
+/// File stems accepted for the six cubemap faces, ordered +X, -X, +Y, -Y, +Z, -Z
+pub const CUBEMAP_FACE_NAMES: [[&str; CUBE_FACES]; 3] = [
+    ["px", "nx", "py", "ny", "pz", "nz"],
+    ["posx", "negx", "posy", "negy", "posz", "negz"],
+    ["right", "left", "top", "bottom", "front", "back"],
+];
+
+/// Face size used when a panorama is turned into a cubemap without one given
+pub const DEFAULT_CUBEMAP_FACE_SIZE: u32 = 512;
+
+/// How the six faces are arranged in a single image
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CubemapLayout {
+    /// 6:1, faces left to right in layer order
+    HorizontalStrip,
+    /// 1:6, faces top to bottom in layer order
+    VerticalStrip,
+    /// 4:3, -X +Z +X -Z across the middle row, +Y above and -Y below +Z
+    HorizontalCross,
+    /// 3:4, -X +Z +X across, +Y above, -Y and then -Z (upside down) below +Z
+    VerticalCross,
+}
+
+impl CubemapLayout {
+    /// Layout of an image of this size, if it holds six square faces
+    pub fn detect(width: u32, height: u32) -> Option<Self> {
+        if width == 0 || height == 0 {
+            return None;
+        }
+        if width == height * 6 {
+            Some(CubemapLayout::HorizontalStrip)
+        } else if height == width * 6 {
+            Some(CubemapLayout::VerticalStrip)
+        } else if width * 3 == height * 4 && width % 4 == 0 {
+            Some(CubemapLayout::HorizontalCross)
+        } else if width * 4 == height * 3 && width % 3 == 0 {
+            Some(CubemapLayout::VerticalCross)
+        } else {
+            None
+        }
+    }
+
+    /// Edge of one face
+    pub fn face_size(self, width: u32, height: u32) -> u32 {
+        match self {
+            CubemapLayout::HorizontalStrip => height,
+            CubemapLayout::VerticalStrip => width,
+            CubemapLayout::HorizontalCross => width / 4,
+            CubemapLayout::VerticalCross => width / 3,
+        }
+    }
+
+    /// Cell (column, row) of a face and whether it is stored rotated 180°
+    fn cell(self, face: usize) -> (u32, u32, bool) {
+        const CROSS: [(u32, u32); CUBE_FACES] = [(2, 1), (0, 1), (1, 0), (1, 2), (1, 1), (3, 1)];
+        match self {
+            CubemapLayout::HorizontalStrip => (face as u32, 0, false),
+            CubemapLayout::VerticalStrip => (0, face as u32, false),
+            CubemapLayout::HorizontalCross => (CROSS[face].0, CROSS[face].1, false),
+            CubemapLayout::VerticalCross if face == 5 => (1, 3, true),
+            CubemapLayout::VerticalCross => (CROSS[face].0, CROSS[face].1, false),
+        }
+    }
+}
+
+/// Decoded cubemap - six linear RGBA32F faces
+#[derive(Debug, Clone)]
+pub struct CubemapData {
+    pub name: String,
+    pub source_path: PathBuf,
+    /// Edge of each face in texels
+    pub size: u32,
+    /// Faces one after another in layer order, `size * size * 4` floats each
+    pub pixels: Arc<[f32]>,
+}
+
+impl CubemapData {
+    /// Read a cubemap folder, a strip/cross image or an equirectangular panorama
+    /// (resampled to `DEFAULT_CUBEMAP_FACE_SIZE`)
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if path.is_dir() {
+            return Self::load_folder(path);
+        }
+        let (width, height, pixels) = load_linear_rgba(path)?;
+        match CubemapLayout::detect(width, height) {
+            Some(layout) => Self::from_layout(path, layout, width, height, &pixels),
+            None => {
+                let map = EnvironmentMap::from_pixels(path, width, height, pixels)?;
+                Ok(map.to_cubemap(DEFAULT_CUBEMAP_FACE_SIZE))
+            }
+        }
+    }
+
+    /// Read the six face images of a folder
+    pub fn load_folder(dir: &Path) -> Result<Self, String> {
+        let face_paths = cubemap_folder_faces(dir)
+            .ok_or_else(|| format!("Pasta de cubemap sem as 6 faces: {}", dir.display()))?;
+        let mut pixels = Vec::new();
+        let mut size = 0;
+        for path in &face_paths {
+            let (width, height, face) = load_linear_rgba(path)?;
+            if width != height || (size != 0 && width != size) {
+                return Err(format!(
+                    "Faces do cubemap devem ser quadradas e do mesmo tamanho: {}",
+                    path.display()
+                ));
+            }
+            size = width;
+            pixels.extend_from_slice(&face);
+        }
+        Ok(Self {
+            name: file_stem(dir),
+            source_path: dir.to_path_buf(),
+            size,
+            pixels: pixels.into(),
+        })
+    }
+
+    /// Cut the faces out of a strip or cross image
+    pub fn from_layout(
+        path: &Path,
+        layout: CubemapLayout,
+        width: u32,
+        height: u32,
+        pixels: &[f32],
+    ) -> Result<Self, String> {
+        if pixels.len() != (width * height * 4) as usize {
+            return Err(format!("Imagem inválida: {}", path.display()));
+        }
+        let size = layout.face_size(width, height);
+        let mut faces = Vec::with_capacity((size * size * 4) as usize * CUBE_FACES);
+        for face in 0..CUBE_FACES {
+            let (column, row, rotated) = layout.cell(face);
+            for y in 0..size {
+                for x in 0..size {
+                    let (fx, fy) = if rotated {
+                        (size - 1 - x, size - 1 - y)
+                    } else {
+                        (x, y)
+                    };
+                    let i = (((row * size + fy) * width + column * size + fx) * 4) as usize;
+                    faces.extend_from_slice(&pixels[i..i + 4]);
+                }
+            }
+        }
+        Ok(Self {
+            name: file_stem(path),
+            source_path: path.to_path_buf(),
+            size,
+            pixels: faces.into(),
+        })
+    }
+
+    /// Texels of one face, row by row
+    pub fn face(&self, face: usize) -> &[f32] {
+        let len = (self.size * self.size * 4) as usize;
+        &self.pixels[face * len..(face + 1) * len]
+    }
+
+    /// Radiance along a world-space direction (nearest texel)
+    pub fn sample(&self, direction: Vec3) -> Vec3 {
+        let (face, u, v) = cubemap_face_uv(direction.normalize_or(Vec3::Y));
+        let size = self.size.max(1);
+        let x = ((u * size as f32) as u32).min(size - 1);
+        let y = ((v * size as f32) as u32).min(size - 1);
+        let i = ((y * size + x) * 4) as usize;
+        let texels = self.face(face);
+        Vec3::new(texels[i], texels[i + 1], texels[i + 2])
+    }
+
+    /// Resample into an equirectangular panorama four faces wide (at most
+    /// `max_width`); returns (width, height, RGBA32F pixels)
+    pub fn to_equirect(&self, max_width: u32) -> (u32, u32, Vec<f32>) {
+        let width = (self.size * 4).clamp(8, max_width.max(8));
+        let height = width / 2;
+        let mut pixels = Vec::with_capacity((width * height * 4) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                let direction = equirect_to_direction(
+                    (x as f32 + 0.5) / width as f32,
+                    (y as f32 + 0.5) / height as f32,
+                );
+                let (face, u, v) = cubemap_face_uv(direction);
+                let fx = ((u * self.size as f32) as u32).min(self.size - 1);
+                let fy = ((v * self.size as f32) as u32).min(self.size - 1);
+                let i = ((fy * self.size + fx) * 4) as usize;
+                pixels.extend_from_slice(&self.face(face)[i..i + 4]);
+            }
+        }
+        (width, height, pixels)
+    }
+
+    /// Every face packed as RGBA16F, in layer order, for GPU upload
+    pub fn rgba16f_bytes(&self) -> Vec<u8> {
+        pack_rgba16f(&self.pixels)
+    }
+}
+
+impl EnvironmentMap {
+    /// Resample the panorama into six faces of `size` texels (bilinear)
+    pub fn to_cubemap(&self, size: u32) -> CubemapData {
+        let size = size.max(1);
+        let mut pixels = Vec::with_capacity((size * size * 4) as usize * CUBE_FACES);
+        for face in 0..CUBE_FACES {
+            for y in 0..size {
+                for x in 0..size {
+                    let direction = cubemap_face_direction(
+                        face,
+                        (x as f32 + 0.5) / size as f32,
+                        (y as f32 + 0.5) / size as f32,
+                    );
+                    pixels.extend_from_slice(&self.sample_bilinear(direction));
+                }
+            }
+        }
+        CubemapData {
+            name: self.name.clone(),
+            source_path: self.source_path.clone(),
+            size,
+            pixels: pixels.into(),
+        }
+    }
+}
+
+/// The six face images of a cubemap folder, in layer order
+pub fn cubemap_folder_faces(dir: &Path) -> Option<Vec<PathBuf>> {
+    let entries: Vec<PathBuf> = std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.is_file())
+        .collect();
+    let find_face = |stem: &str| {
+        entries
+            .iter()
+            .find(|p| {
+                p.file_stem()
+                    .and_then(|s| s.to_str())
+                    .is_some_and(|s| s.eq_ignore_ascii_case(stem))
+            })
+            .cloned()
+    };
+    CUBEMAP_FACE_NAMES.iter().find_map(|names| {
+        names
+            .iter()
+            .map(|n| find_face(n))
+            .collect::<Option<Vec<_>>>()
+    })
+}
+
+/// Whether `path` can be read as a cubemap: a folder with six faces, an HDR/EXR
+/// panorama or an image laid out as a strip or cross
+pub fn is_cubemap_source(path: &Path) -> bool {
+    if path.is_dir() {
+        return cubemap_folder_faces(path).is_some();
+    }
+    let Some(ext) = path.extension().and_then(|e| e.to_str()) else {
+        return false;
+    };
+    match ext.to_ascii_lowercase().as_str() {
+        "hdr" | "exr" => true,
+        "png" | "jpg" | "jpeg" | "webp" => image::image_dimensions(path)
+            .is_ok_and(|(width, height)| CubemapLayout::detect(width, height).is_some()),
+        _ => false,
+    }
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Cubemap face (+X, -X, +Y, -Y, +Z, -Z) and face UV, V pointing down
+pub fn cubemap_face_uv(d: Vec3) -> (usize, f32, f32) {
+    let a = d.abs();
+    let (face, sc, tc, ma) = if a.x >= a.y && a.x >= a.z {
+        if d.x > 0.0 {
+            (0, -d.z, -d.y, a.x)
+        } else {
+            (1, d.z, -d.y, a.x)
+        }
+    } else if a.y >= a.z {
+        if d.y > 0.0 {
+            (2, d.x, d.z, a.y)
+        } else {
+            (3, d.x, -d.z, a.y)
+        }
+    } else if d.z > 0.0 {
+        (4, d.x, -d.y, a.z)
+    } else {
+        (5, -d.x, -d.y, a.z)
+    };
+    let ma = ma.max(1e-6);
+    (face, (sc / ma + 1.0) * 0.5, (tc / ma + 1.0) * 0.5)
+}
+
+/// Inverse of `cubemap_face_uv`: unit direction through a point of a face
+pub fn cubemap_face_direction(face: usize, u: f32, v: f32) -> Vec3 {
+    let sc = u * 2.0 - 1.0;
+    let tc = v * 2.0 - 1.0;
+    let d = match face {
+        0 => Vec3::new(1.0, -tc, -sc),
+        1 => Vec3::new(-1.0, -tc, sc),
+        2 => Vec3::new(sc, 1.0, tc),
+        3 => Vec3::new(sc, -1.0, -tc),
+        4 => Vec3::new(sc, -tc, 1.0),
+        _ => Vec3::new(-sc, -tc, -1.0),
+    };
+    d.normalize()
+}
+
+/// Upload a cubemap as an `Rgba16Float` cube texture; the view has the `Cube`
+/// dimension, ready for a `texture_cube<f32>` binding
+pub fn create_cube_texture(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    cubemap: &CubemapData,
+    label: &str,
+) -> (wgpu::Texture, wgpu::TextureView) {
+    let size = wgpu::Extent3d {
+        width: cubemap.size,
+        height: cubemap.size,
+        depth_or_array_layers: CUBE_FACES as u32,
+    };
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some(label),
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba16Float,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    });
+    queue.write_texture(
+        wgpu::TexelCopyTextureInfo {
+            texture: &texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        &cubemap.rgba16f_bytes(),
+        wgpu::TexelCopyBufferLayout {
+            offset: 0,
+            bytes_per_row: Some(cubemap.size * 8),
+            rows_per_image: Some(cubemap.size),
+        },
+        size,
+    );
+    let view = texture.create_view(&wgpu::TextureViewDescriptor {
+        label: Some(label),
+        dimension: Some(wgpu::TextureViewDimension::Cube),
+        ..Default::default()
+    });
+    (texture, view)
+}
@@ -0,0 +1,330 @@
+//! Keyframed curves with Bezier tangents
+//!
+//! Shared by animation clips, particle lifetime curves and Fios curve nodes.
+//! Tangents are slopes (value per unit of time); the Bezier control points of a
+//! segment sit a third of the way along it, following each key's tangent.
+
+/// How a key's tangents are set
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TangentMode {
+    /// Smooth through the neighbours, flat at extremes and at the ends
+    #[default]
+    Auto,
+    /// Straight lines to the neighbouring keys
+    Linear,
+    /// Holds the value until the next key
+    Constant,
+    /// Set by hand, in and out tangents aligned
+    Free,
+    /// Set by hand, in and out tangents independent
+    Broken,
+}
+
+impl TangentMode {
+    pub const ALL: [TangentMode; 5] = [
+        TangentMode::Auto,
+        TangentMode::Linear,
+        TangentMode::Constant,
+        TangentMode::Free,
+        TangentMode::Broken,
+    ];
+
+    pub fn id(self) -> &'static str {
+        match self {
+            TangentMode::Auto => "auto",
+            TangentMode::Linear => "linear",
+            TangentMode::Constant => "constant",
+            TangentMode::Free => "free",
+            TangentMode::Broken => "broken",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|mode| mode.id() == id)
+    }
+
+    /// Whether the tangents are kept by hand rather than recomputed
+    pub fn is_manual(self) -> bool {
+        matches!(self, TangentMode::Free | TangentMode::Broken)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CurveKey {
+    pub time: f32,
+    pub value: f32,
+    pub in_tangent: f32,
+    pub out_tangent: f32,
+    pub mode: TangentMode,
+}
+
+impl CurveKey {
+    pub fn new(time: f32, value: f32, mode: TangentMode) -> Self {
+        Self {
+            time,
+            value,
+            in_tangent: 0.0,
+            out_tangent: 0.0,
+            mode,
+        }
+    }
+
+    /// `time,value,in,out,mode`
+    pub fn to_text(&self) -> String {
+        format!(
+            "{:.4},{:.4},{:.4},{:.4},{}",
+            self.time,
+            self.value,
+            self.in_tangent,
+            self.out_tangent,
+            self.mode.id()
+        )
+    }
+
+    /// Reads `to_text`; a bare `time,value` is a linear key
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = text.split(',').map(str::trim).collect();
+        let number = |index: usize| -> Result<f32, String> {
+            fields
+                .get(index)
+                .and_then(|field| field.parse().ok())
+                .ok_or_else(|| format!("Invalid curve key: {text}"))
+        };
+        let mut key = CurveKey::new(number(0)?, number(1)?, TangentMode::Linear);
+        if fields.len() >= 4 {
+            key.in_tangent = number(2)?;
+            key.out_tangent = number(3)?;
+            key.mode = fields
+                .get(4)
+                .and_then(|id| TangentMode::from_id(id))
+                .unwrap_or(TangentMode::Free);
+        }
+        Ok(key)
+    }
+}
+
+/// Keys sorted by time; before the first and after the last key the curve
+/// holds their values
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Curve {
+    keys: Vec<CurveKey>,
+}
+
+impl Curve {
+    pub fn constant(value: f32) -> Self {
+        Self::from_points([(0.0, value)], TangentMode::Linear)
+    }
+
+    /// Straight line from `(0, start)` to `(1, end)`
+    pub fn linear(start: f32, end: f32) -> Self {
+        Self::from_points([(0.0, start), (1.0, end)], TangentMode::Linear)
+    }
+
+    pub fn from_points(points: impl IntoIterator<Item = (f32, f32)>, mode: TangentMode) -> Self {
+        let mut curve = Self {
+            keys: points
+                .into_iter()
+                .map(|(time, value)| CurveKey::new(time, value, mode))
+                .collect(),
+        };
+        curve.keys.sort_by(|a, b| a.time.total_cmp(&b.time));
+        curve.update_tangents();
+        curve
+    }
+
+    pub fn keys(&self) -> &[CurveKey] {
+        &self.keys
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Value at `time`; `None` without keys
+    pub fn evaluate(&self, time: f32) -> Option<f32> {
+        let first = self.keys.first()?;
+        let last = self.keys.last()?;
+        if time <= first.time {
+            return Some(first.value);
+        }
+        if time >= last.time {
+            return Some(last.value);
+        }
+        let next = self.keys.partition_point(|key| key.time <= time);
+        let (a, b) = (&self.keys[next - 1], &self.keys[next]);
+        if a.mode == TangentMode::Constant {
+            return Some(a.value);
+        }
+        let span = (b.time - a.time).max(1e-6);
+        let s = (time - a.time) / span;
+        // Control points at thirds make the Bezier uniform in time, so it is the
+        // cubic Hermite of the two keys
+        let s2 = s * s;
+        let s3 = s2 * s;
+        let h00 = 2.0 * s3 - 3.0 * s2 + 1.0;
+        let h10 = s3 - 2.0 * s2 + s;
+        let h01 = -2.0 * s3 + 3.0 * s2;
+        let h11 = s3 - s2;
+        Some(h00 * a.value + h10 * span * a.out_tangent + h01 * b.value + h11 * span * b.in_tangent)
+    }
+
+    /// Smallest and largest value the curve reaches between its keys
+    pub fn value_range(&self) -> Option<(f32, f32)> {
+        let first = self.keys.first()?;
+        let last = self.keys.last()?;
+        let mut range = (first.value, first.value);
+        const STEPS: usize = 64;
+        for i in 0..=STEPS {
+            let time = first.time + (last.time - first.time) * i as f32 / STEPS as f32;
+            let value = self.evaluate(time).unwrap_or(first.value);
+            range = (range.0.min(value), range.1.max(value));
+        }
+        Some(range)
+    }
+
+    /// Adds a key, or replaces the value of the one within `tolerance` of `time`;
+    /// returns its index
+    pub fn set_key(&mut self, time: f32, value: f32, tolerance: f32) -> usize {
+        let index = match self
+            .keys
+            .iter()
+            .position(|key| (key.time - time).abs() <= tolerance)
+        {
+            Some(index) => {
+                self.keys[index].value = value;
+                index
+            }
+            None => self.insert_key(CurveKey::new(time, value, TangentMode::Auto)),
+        };
+        self.update_tangents();
+        index
+    }
+
+    /// Inserts `key` in time order and returns its index
+    pub fn insert_key(&mut self, key: CurveKey) -> usize {
+        let index = self.keys.partition_point(|k| k.time <= key.time);
+        self.keys.insert(index, key);
+        self.update_tangents();
+        index
+    }
+
+    pub fn remove_key(&mut self, index: usize) -> Option<CurveKey> {
+        if index >= self.keys.len() {
+            return None;
+        }
+        let key = self.keys.remove(index);
+        self.update_tangents();
+        Some(key)
+    }
+
+    /// Moves a key without letting it pass its neighbours, so indices stay valid
+    pub fn move_key(&mut self, index: usize, time: f32, value: f32) {
+        const GAP: f32 = 1e-4;
+        let Some(key) = self.keys.get(index) else {
+            return;
+        };
+        let mut time = time;
+        if index > 0 {
+            time = time.max(self.keys[index - 1].time + GAP);
+        }
+        if let Some(next) = self.keys.get(index + 1) {
+            time = time.min(next.time - GAP);
+        }
+        if key.time == time && key.value == value {
+            return;
+        }
+        self.keys[index].time = time;
+        self.keys[index].value = value;
+        self.update_tangents();
+    }
+
+    /// Sets tangents by hand; `Free` keys keep in and out aligned
+    pub fn set_tangents(&mut self, index: usize, in_tangent: f32, out_tangent: f32) {
+        let Some(key) = self.keys.get_mut(index) else {
+            return;
+        };
+        key.in_tangent = in_tangent;
+        key.out_tangent = out_tangent;
+        if !key.mode.is_manual() {
+            key.mode = TangentMode::Free;
+        }
+    }
+
+    pub fn set_mode(&mut self, index: usize, mode: TangentMode) {
+        let Some(key) = self.keys.get_mut(index) else {
+            return;
+        };
+        key.mode = mode;
+        if mode == TangentMode::Free {
+            let slope = (key.in_tangent + key.out_tangent) * 0.5;
+            key.in_tangent = slope;
+            key.out_tangent = slope;
+        }
+        self.update_tangents();
+    }
+
+    /// Recomputes the tangents of keys not set by hand
+    pub fn update_tangents(&mut self) {
+        for index in 0..self.keys.len() {
+            let prev = index.checked_sub(1).map(|i| self.keys[i]);
+            let next = self.keys.get(index + 1).copied();
+            let key = &mut self.keys[index];
+            let (time, value) = (key.time, key.value);
+            let slope_to = |other: &CurveKey| {
+                let dt = other.time - time;
+                if dt.abs() < 1e-6 {
+                    0.0
+                } else {
+                    (other.value - value) / dt
+                }
+            };
+            match key.mode {
+                TangentMode::Auto => {
+                    let slope = match (prev, next) {
+                        (Some(p), Some(n)) => {
+                            let extreme = (value - p.value) * (n.value - value) <= 0.0;
+                            if extreme {
+                                0.0
+                            } else {
+                                (n.value - p.value) / (n.time - p.time).max(1e-6)
+                            }
+                        }
+                        _ => 0.0,
+                    };
+                    key.in_tangent = slope;
+                    key.out_tangent = slope;
+                }
+                TangentMode::Linear => {
+                    let in_slope = prev.map(|p| slope_to(&p));
+                    let out_slope = next.map(|n| slope_to(&n));
+                    key.in_tangent = in_slope.or(out_slope).unwrap_or(0.0);
+                    key.out_tangent = out_slope.or(in_slope).unwrap_or(0.0);
+                }
+                TangentMode::Constant => {
+                    key.in_tangent = 0.0;
+                    key.out_tangent = 0.0;
+                }
+                TangentMode::Free | TangentMode::Broken => {}
+            }
+        }
+    }
+
+    /// Keys joined by `;`, each as `CurveKey::to_text`
+    pub fn to_text(&self) -> String {
+        self.keys
+            .iter()
+            .map(CurveKey::to_text)
+            .collect::<Vec<_>>()
+            .join(";")
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut curve = Self::default();
+        for key in text.split(';').map(str::trim).filter(|k| !k.is_empty()) {
+            curve.keys.push(CurveKey::parse(key)?);
+        }
+        curve.keys.sort_by(|a, b| a.time.total_cmp(&b.time));
+        curve.update_tangents();
+        Ok(curve)
+    }
+}
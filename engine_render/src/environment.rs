@@ -0,0 +1,288 @@
+//! Environment maps (skybox) and scene fog
+//!
+//! Environments are stored as linear RGBA32F equirectangular panoramas. Cubemaps
+//! (a folder with six face images, or one strip/cross image, see `cubemap`) are
+//! resampled into that layout on load, so the sky pass only has to deal with one
+//! texture format; their faces are kept for code that wants a cube texture.
+
+use std::f32::consts::{PI, TAU};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use engine_core::components::{EnvironmentHandle, FogMode, FogSettings};
+use glam::Vec3;
+
+use crate::asset_manager::{AssetHandle, AssetManager};
+use crate::cubemap::{CubemapData, CubemapLayout};
+use crate::hdr::{is_float_image, pack_rgba16f};
+use crate::vfs::open_image;
+
+/// Largest panorama width produced when assembling a cubemap
+const MAX_CUBEMAP_PANORAMA_WIDTH: u32 = 4096;
+
+/// Decoded environment map - linear RGBA32F equirectangular panorama
+#[derive(Debug, Clone)]
+pub struct EnvironmentMap {
+    pub name: String,
+    pub source_path: PathBuf,
+    pub width: u32,
+    pub height: u32,
+    /// Row-major RGBA, `width * height * 4` floats
+    pub pixels: Arc<[f32]>,
+    /// Faces as imported, when the source was a cubemap
+    pub faces: Option<Arc<CubemapData>>,
+}
+
+impl EnvironmentMap {
+    /// Radiance along a world-space direction (nearest texel)
+    pub fn sample(&self, direction: Vec3) -> Vec3 {
+        let (u, v) = direction_to_equirect(direction.normalize_or(Vec3::Y));
+        let x = ((u * self.width as f32) as u32).min(self.width - 1);
+        let y = ((v * self.height as f32) as u32).min(self.height - 1);
+        let i = ((y * self.width + x) * 4) as usize;
+        Vec3::new(self.pixels[i], self.pixels[i + 1], self.pixels[i + 2])
+    }
+
+    /// Radiance along a world-space direction, filtered between the four
+    /// nearest texels (wrapping around the seam)
+    pub fn sample_bilinear(&self, direction: Vec3) -> [f32; 4] {
+        let (u, v) = direction_to_equirect(direction.normalize_or(Vec3::Y));
+        let x = u * self.width as f32 - 0.5;
+        let y = (v * self.height as f32 - 0.5).clamp(0.0, (self.height - 1) as f32);
+        let (x0, y0) = (x.floor(), y.floor());
+        let (tx, ty) = (x - x0, y - y0);
+        let column = |x: f32| (x as i64).rem_euclid(self.width as i64) as u32;
+        let row = |y: f32| (y as u32).min(self.height - 1);
+        let texel = |x: u32, y: u32| {
+            let i = ((y * self.width + x) * 4) as usize;
+            glam::Vec4::from_slice(&self.pixels[i..i + 4])
+        };
+        let (c0, c1) = (column(x0), column(x0 + 1.0));
+        let (r0, r1) = (row(y0), row(y0 + 1.0));
+        let top = texel(c0, r0).lerp(texel(c1, r0), tx);
+        let bottom = texel(c0, r1).lerp(texel(c1, r1), tx);
+        top.lerp(bottom, ty).to_array()
+    }
+
+    /// Pixels packed as RGBA16F for GPU upload
+    pub fn rgba16f_bytes(&self) -> Vec<u8> {
+        pack_rgba16f(&self.pixels)
+    }
+
+    /// The six faces: as imported for cubemap sources, else resampled from the
+    /// panorama at `face_size`
+    pub fn cubemap(&self, face_size: u32) -> Arc<CubemapData> {
+        engine_core::profile_function!();
+        match &self.faces {
+            Some(faces) => faces.clone(),
+            None => Arc::new(self.to_cubemap(face_size)),
+        }
+    }
+}
+
+/// Per-scene sky settings
+#[derive(Debug, Clone, Copy)]
+pub struct EnvironmentSettings {
+    /// Panorama shown as skybox; None draws the gradient sky
+    pub environment: Option<EnvironmentHandle>,
+    pub exposure: f32,
+    /// Rotation around the world Y axis, in radians
+    pub rotation: f32,
+    pub zenith_color: [f32; 3],
+    pub horizon_color: [f32; 3],
+    pub ground_color: [f32; 3],
+    /// Image-based lighting from the panorama (irradiance + prefiltered specular)
+    pub ibl_enabled: bool,
+    pub ibl_intensity: f32,
+    /// Distance/height fog of the scene (cameras may override it)
+    pub fog: FogSettings,
+}
+
+impl Default for EnvironmentSettings {
+    fn default() -> Self {
+        Self {
+            environment: None,
+            exposure: 1.0,
+            rotation: 0.0,
+            zenith_color: [0.18, 0.32, 0.58],
+            horizon_color: [0.62, 0.70, 0.78],
+            ground_color: [0.16, 0.15, 0.14],
+            ibl_enabled: true,
+            ibl_intensity: 1.0,
+            fog: FogSettings::default(),
+        }
+    }
+}
+
+/// Fog packed as the three `fog_*` vec4s of the lit shader uniforms:
+/// color + mode, (start, end, density, max opacity), (height density, height,
+/// height falloff, 0). A disabled fog packs as mode 0 with no height fog.
+pub fn fog_uniform(fog: &FogSettings) -> [f32; 12] {
+    if !fog.is_enabled() {
+        return [0.0; 12];
+    }
+    let mode = match fog.mode {
+        FogMode::Off => 0.0,
+        FogMode::Linear => 1.0,
+        FogMode::Exponential => 2.0,
+        FogMode::ExponentialSquared => 3.0,
+    };
+    [
+        fog.color.x,
+        fog.color.y,
+        fog.color.z,
+        mode,
+        fog.start,
+        fog.end,
+        fog.density.max(0.0),
+        fog.max_opacity.clamp(0.0, 1.0),
+        fog.height_density.max(0.0),
+        fog.height,
+        fog.height_falloff.max(1e-4),
+        0.0,
+    ]
+}
+
+impl EnvironmentMap {
+    /// Read an equirectangular image, a cubemap folder or a strip/cross image
+    /// (see `AssetManager::load_environment`) without storing it
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if path.is_dir() {
+            return Ok(Self::from_cubemap(CubemapData::load_folder(path)?));
+        }
+        let (width, height, pixels) = load_linear_rgba(path)?;
+        match CubemapLayout::detect(width, height) {
+            Some(layout) => Ok(Self::from_cubemap(CubemapData::from_layout(
+                path, layout, width, height, &pixels,
+            )?)),
+            None => Self::from_pixels(path, width, height, pixels),
+        }
+    }
+
+    /// Panorama from decoded linear RGBA32F pixels, which must be 2:1
+    pub fn from_pixels(
+        path: &Path,
+        width: u32,
+        height: u32,
+        pixels: Vec<f32>,
+    ) -> Result<Self, String> {
+        if width != height * 2 {
+            return Err(format!(
+                "Panorama equirretangular deve ter proporção 2:1, ou ser cubemap em tira (6:1) ou cruz (4:3) ({}x{})",
+                width, height
+            ));
+        }
+        Ok(EnvironmentMap {
+            name: path
+                .file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string(),
+            source_path: path.to_path_buf(),
+            width,
+            height,
+            pixels: pixels.into(),
+            faces: None,
+        })
+    }
+
+    /// Panorama resampled from cubemap faces, keeping the faces
+    pub fn from_cubemap(cubemap: CubemapData) -> Self {
+        let (width, height, pixels) = cubemap.to_equirect(MAX_CUBEMAP_PANORAMA_WIDTH);
+        EnvironmentMap {
+            name: cubemap.name.clone(),
+            source_path: cubemap.source_path.clone(),
+            width,
+            height,
+            pixels: pixels.into(),
+            faces: Some(Arc::new(cubemap)),
+        }
+    }
+}
+
+impl AssetManager {
+    /// Load an environment map: an equirectangular image (.hdr, .png, .jpg, ...),
+    /// a cubemap strip (6:1) or cross (4:3) image, or a folder containing six
+    /// cubemap faces (px/nx/py/ny/pz/nz, posx/negx/..., or
+    /// right/left/top/bottom/front/back).
+    pub fn load_environment(&mut self, path: &Path) -> Result<EnvironmentHandle, String> {
+        if let Some((id, _)) = self
+            .environments
+            .iter()
+            .find(|(_, env)| env.source_path == path)
+        {
+            return Ok(EnvironmentHandle { id: *id });
+        }
+
+        let map = EnvironmentMap::load(path)?;
+        let id = self.next_environment_id;
+        self.next_environment_id += 1;
+        self.environments.insert(id, map);
+        Ok(EnvironmentHandle { id })
+    }
+
+    /// Get environment map by handle
+    pub fn get_environment(&self, handle: EnvironmentHandle) -> Option<&EnvironmentMap> {
+        self.environments.get(&handle.id)
+    }
+
+    /// Faces of a loaded environment, for a cube texture (`create_cube_texture`);
+    /// panoramas are resampled at `face_size`
+    pub fn environment_cubemap(
+        &self,
+        handle: EnvironmentHandle,
+        face_size: u32,
+    ) -> Option<Arc<CubemapData>> {
+        self.get_environment(handle)
+            .map(|map| map.cubemap(face_size))
+    }
+
+    /// Unload environment map by handle
+    pub fn unload_environment(&mut self, handle: EnvironmentHandle) -> bool {
+        self.load_states.remove(&AssetHandle::Environment(handle));
+        self.environments.remove(&handle.id).is_some()
+    }
+}
+
+/// Equirectangular UV (u around +Y starting at -X, v from the zenith down)
+pub fn direction_to_equirect(direction: Vec3) -> (f32, f32) {
+    let u = direction.z.atan2(direction.x) / TAU + 0.5;
+    let v = direction.y.clamp(-1.0, 1.0).acos() / PI;
+    (u, v)
+}
+
+/// Inverse of `direction_to_equirect`
+pub fn equirect_to_direction(u: f32, v: f32) -> Vec3 {
+    let phi = (u - 0.5) * TAU;
+    let theta = v * PI;
+    Vec3::new(
+        theta.sin() * phi.cos(),
+        theta.cos(),
+        theta.sin() * phi.sin(),
+    )
+}
+
+/// Decode an image into linear RGBA32F; LDR images are converted from sRGB
+pub(crate) fn load_linear_rgba(path: &Path) -> Result<(u32, u32, Vec<f32>), String> {
+    let image = open_image(path)?;
+    let is_hdr = is_float_image(&image);
+    let rgba = image.to_rgba32f();
+    let (width, height) = rgba.dimensions();
+    let mut pixels = rgba.into_raw();
+    if !is_hdr {
+        for px in pixels.chunks_exact_mut(4) {
+            for c in &mut px[..3] {
+                *c = srgb_to_linear(*c);
+            }
+        }
+    }
+    Ok((width, height, pixels))
+}
+
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
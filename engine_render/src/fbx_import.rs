@@ -0,0 +1,716 @@
+//! Importação FBX (binário 7.x)
+//!
+//! Lê o documento com `fbxcel-dom` e percorre os nós de Objects/Connections:
+//! geometrias (posições, normais, UVs e material por polígono), skins (clusters),
+//! materiais com textura difusa e a hierarquia de modelos com as transformações
+//! locais. O resultado é o mesmo `ImportedScene` da importação glTF.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use engine_core::components::{MaterialHandle, MeshHandle, TextureHandle, Transform};
+use fbxcel_dom::any::AnyDocument;
+use fbxcel_dom::fbxcel::low::v7400::AttributeValue;
+use fbxcel_dom::fbxcel::tree::v7400::NodeHandle;
+use glam::{EulerRot, Mat4, Quat, Vec2, Vec3};
+
+use crate::asset_manager::{AssetManager, MaterialData, TextureData};
+use crate::gltf_import::{ImportedNode, ImportedPrimitive, ImportedScene};
+use crate::mesh::{MeshData, MeshSkin, Vertex, VertexAttributes};
+
+/// Influências máximas por vértice (mesmo limite de `MeshSkin`)
+const MAX_INFLUENCES: usize = 4;
+
+/// Objeto da seção Objects
+struct FbxObject<'a> {
+    node: NodeHandle<'a>,
+    name: String,
+    /// Nome do nó: "Geometry", "Model", "Material", "Texture", "Video", "Deformer"...
+    class: &'a str,
+    /// "Mesh", "LimbNode", "Null", "Skin", "Cluster"...
+    subclass: &'a str,
+}
+
+/// Conexão `src` -> `dst`; `property` só nas conexões objeto-propriedade (OP)
+struct FbxConnection<'a> {
+    src: i64,
+    dst: i64,
+    property: Option<&'a str>,
+}
+
+struct FbxDocument<'a> {
+    objects: HashMap<i64, FbxObject<'a>>,
+    /// Ids na ordem do arquivo
+    order: Vec<i64>,
+    connections: Vec<FbxConnection<'a>>,
+}
+
+impl<'a> FbxDocument<'a> {
+    fn parse(root: NodeHandle<'a>) -> Self {
+        let mut objects = HashMap::new();
+        let mut order = Vec::new();
+        if let Some(section) = root.first_child_by_name("Objects") {
+            for node in section.children() {
+                let attrs = node.attributes();
+                let Some(id) = attrs.first().and_then(attribute_i64) else {
+                    continue;
+                };
+                let name = attrs
+                    .get(1)
+                    .and_then(attribute_str)
+                    .map(object_name)
+                    .unwrap_or_default();
+                let subclass = attrs.get(2).and_then(attribute_str).unwrap_or_default();
+                objects.insert(
+                    id,
+                    FbxObject {
+                        node,
+                        name,
+                        class: node.name(),
+                        subclass,
+                    },
+                );
+                order.push(id);
+            }
+        }
+
+        let mut connections = Vec::new();
+        if let Some(section) = root.first_child_by_name("Connections") {
+            for node in section.children_by_name("C") {
+                let attrs = node.attributes();
+                let (Some(src), Some(dst)) = (
+                    attrs.get(1).and_then(attribute_i64),
+                    attrs.get(2).and_then(attribute_i64),
+                ) else {
+                    continue;
+                };
+                connections.push(FbxConnection {
+                    src,
+                    dst,
+                    property: attrs.get(3).and_then(attribute_str),
+                });
+            }
+        }
+
+        Self {
+            objects,
+            order,
+            connections,
+        }
+    }
+
+    fn get(&self, id: i64) -> Option<&FbxObject<'a>> {
+        self.objects.get(&id)
+    }
+
+    /// Objetos da classe `class` na ordem do arquivo
+    fn of_class(&self, class: &str) -> Vec<i64> {
+        self.order
+            .iter()
+            .copied()
+            .filter(|id| self.objects[id].class == class)
+            .collect()
+    }
+
+    /// Objetos da classe `class` conectados a `dst`, na ordem das conexões
+    fn sources(&self, dst: i64, class: &str) -> Vec<i64> {
+        self.connections
+            .iter()
+            .filter(|c| c.dst == dst)
+            .filter(|c| self.get(c.src).is_some_and(|o| o.class == class))
+            .map(|c| c.src)
+            .collect()
+    }
+
+    /// Objetos da classe `class` aos quais `src` está conectado
+    fn destinations(&self, src: i64, class: &str) -> Vec<i64> {
+        self.connections
+            .iter()
+            .filter(|c| c.src == src)
+            .filter(|c| self.get(c.dst).is_some_and(|o| o.class == class))
+            .map(|c| c.dst)
+            .collect()
+    }
+}
+
+/// Influências de ossos por ponto de controle de uma geometria
+#[derive(Default)]
+struct ControlPointSkin {
+    bones: Vec<String>,
+    inverse_bind_matrices: Vec<Mat4>,
+    influences: Vec<Vec<(u16, f32)>>,
+}
+
+impl ControlPointSkin {
+    /// Até 4 influências mais fortes, normalizadas
+    fn vertex(&self, control_point: usize) -> ([u16; 4], [f32; 4]) {
+        let mut joints = [0; 4];
+        let mut weights = [0.0; 4];
+        let Some(influences) = self.influences.get(control_point) else {
+            return (joints, weights);
+        };
+        let mut sorted = influences.clone();
+        sorted.sort_by(|a, b| b.1.total_cmp(&a.1));
+        sorted.truncate(MAX_INFLUENCES);
+        let total: f32 = sorted.iter().map(|(_, w)| *w).sum();
+        if total <= 0.0 {
+            return (joints, weights);
+        }
+        for (slot, (joint, weight)) in sorted.into_iter().enumerate() {
+            joints[slot] = joint;
+            weights[slot] = weight / total;
+        }
+        (joints, weights)
+    }
+}
+
+/// Camada de dados por vértice (LayerElementNormal, LayerElementUV...)
+struct LayerElement<'a> {
+    mapping: &'a str,
+    reference: &'a str,
+    values: Vec<f64>,
+    index: Vec<i32>,
+}
+
+impl<'a> LayerElement<'a> {
+    fn read(geometry: NodeHandle<'a>, element: &str, values: &str, index: &str) -> Option<Self> {
+        let node = geometry.first_child_by_name(element)?;
+        Some(Self::from_node(node, values, index))
+    }
+
+    /// Todas as camadas com o nome (ex.: um LayerElementUV por conjunto de UV)
+    fn read_all(geometry: NodeHandle<'a>, element: &str, values: &str, index: &str) -> Vec<Self> {
+        geometry
+            .children_by_name(element)
+            .map(|node| Self::from_node(node, values, index))
+            .collect()
+    }
+
+    fn from_node(node: NodeHandle<'a>, values: &str, index: &str) -> Self {
+        Self {
+            mapping: child_str(node, "MappingInformationType").unwrap_or("ByPolygonVertex"),
+            reference: child_str(node, "ReferenceInformationType").unwrap_or("Direct"),
+            values: f64_array(node, values),
+            index: i32_array(node, index),
+        }
+    }
+
+    /// Índice do elemento em `values` para um vértice de polígono
+    fn lookup(&self, control_point: usize, polygon_vertex: usize, polygon: usize) -> Option<usize> {
+        let i = match self.mapping {
+            "ByPolygonVertex" => polygon_vertex,
+            "ByPolygon" => polygon,
+            "AllSame" => 0,
+            // ByControlPoint / ByVertice
+            _ => control_point,
+        };
+        match self.reference {
+            "Direct" => Some(i),
+            _ => self.index.get(i).and_then(|v| usize::try_from(*v).ok()),
+        }
+    }
+
+    fn vec3(&self, i: usize) -> Option<Vec3> {
+        let v = self.values.get(i * 3..i * 3 + 3)?;
+        Some(Vec3::new(v[0] as f32, v[1] as f32, v[2] as f32))
+    }
+
+    fn vec2(&self, i: usize) -> Option<Vec2> {
+        let v = self.values.get(i * 2..i * 2 + 2)?;
+        Some(Vec2::new(v[0] as f32, v[1] as f32))
+    }
+
+    fn vec4(&self, i: usize) -> Option<[f32; 4]> {
+        let v = self.values.get(i * 4..i * 4 + 4)?;
+        Some([v[0] as f32, v[1] as f32, v[2] as f32, v[3] as f32])
+    }
+}
+
+impl AssetManager {
+    /// Importa um .fbx: malhas por material, skins, materiais, texturas e hierarquia de modelos
+    pub fn import_fbx(&mut self, path: &Path) -> Result<ImportedScene, String> {
+        engine_core::profile_function!();
+        let file = std::fs::File::open(path).map_err(|e| format!("Falha ao ler FBX: {}", e))?;
+        let document = match AnyDocument::from_seekable_reader(BufReader::new(file))
+            .map_err(|e| format!("Falha ao carregar FBX: {}", e))?
+        {
+            AnyDocument::V7400(_, document) => document,
+            _ => return Err("Versão FBX não suportada".to_string()),
+        };
+        let fbx = FbxDocument::parse(document.tree().root());
+        let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+        let file_stem = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("fbx")
+            .to_string();
+
+        let mut scene = ImportedScene {
+            name: file_stem.clone(),
+            source: path.to_path_buf(),
+            ..Default::default()
+        };
+
+        // Materiais (com a textura difusa, externa ou embutida)
+        let mut texture_handles: HashMap<i64, Option<TextureHandle>> = HashMap::new();
+        let mut material_handles: HashMap<i64, MaterialHandle> = HashMap::new();
+        for id in fbx.of_class("Material") {
+            let material = &fbx.objects[&id];
+            let textures = fbx.sources(id, "Texture");
+            let diffuse = fbx
+                .connections
+                .iter()
+                .find(|c| {
+                    c.dst == id && textures.contains(&c.src) && c.property == Some("DiffuseColor")
+                })
+                .map(|c| c.src)
+                .or_else(|| textures.first().copied());
+
+            let mut albedo_texture = None;
+            let mut albedo_texture_handle = None;
+            if let Some(texture_id) = diffuse {
+                albedo_texture = texture_file(&fbx, texture_id, base_dir)
+                    .map(|p| p.to_string_lossy().to_string());
+                albedo_texture_handle = *texture_handles.entry(texture_id).or_insert_with(|| {
+                    let texture = load_texture(&fbx, texture_id, base_dir)?;
+                    let handle = self.add_texture(texture);
+                    scene.textures.push(handle);
+                    Some(handle)
+                });
+            }
+
+            let color = property_vec3(material.node, "DiffuseColor")
+                .or_else(|| property_vec3(material.node, "Diffuse"))
+                .unwrap_or(Vec3::ONE);
+            let opacity = property_f32(material.node, "Opacity").unwrap_or(1.0);
+            let roughness = property_f32(material.node, "Shininess")
+                .map(MaterialData::roughness_from_shininess)
+                .unwrap_or(0.5);
+            let handle = self.add_material(MaterialData {
+                name: if material.name.is_empty() {
+                    format!("{}_material_{}", file_stem, material_handles.len())
+                } else {
+                    material.name.clone()
+                },
+                albedo: [color.x, color.y, color.z, opacity.clamp(0.0, 1.0)],
+                metallic: 0.0,
+                roughness,
+                albedo_texture,
+                albedo_texture_handle,
+                ..Default::default()
+            });
+            scene.materials.push(handle);
+            material_handles.insert(id, handle);
+        }
+
+        // Geometrias: uma MeshData por slot de material
+        let mut geometry_meshes: HashMap<i64, Vec<(usize, MeshHandle)>> = HashMap::new();
+        for id in fbx.of_class("Geometry") {
+            let geometry = &fbx.objects[&id];
+            if geometry.subclass != "Mesh" {
+                continue;
+            }
+            let skin = read_skin(&fbx, id);
+            let geometry_name = if geometry.name.is_empty() {
+                format!("{}_mesh_{}", file_stem, geometry_meshes.len())
+            } else {
+                geometry.name.clone()
+            };
+            let mut slots = Vec::new();
+            for (slot, mut data) in read_geometry(geometry.node, skin.as_ref()) {
+                data.name = format!("{}#{}", geometry_name, slot);
+                let handle = self.add_mesh(data);
+                scene.meshes.push(handle);
+                slots.push((slot, handle));
+            }
+            geometry_meshes.insert(id, slots);
+        }
+
+        // Hierarquia de modelos
+        let models = fbx.of_class("Model");
+        let node_index: HashMap<i64, usize> =
+            models.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+        for id in &models {
+            let model = &fbx.objects[id];
+            let materials = fbx.sources(*id, "Material");
+            let primitives = fbx
+                .sources(*id, "Geometry")
+                .first()
+                .and_then(|g| geometry_meshes.get(g))
+                .map(|slots| {
+                    slots
+                        .iter()
+                        .map(|(slot, mesh)| ImportedPrimitive {
+                            mesh: *mesh,
+                            material: materials
+                                .get(*slot)
+                                .and_then(|m| material_handles.get(m))
+                                .copied(),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            scene.nodes.push(ImportedNode {
+                name: if model.name.is_empty() {
+                    format!("Node {}", scene.nodes.len())
+                } else {
+                    model.name.clone()
+                },
+                parent: fbx
+                    .destinations(*id, "Model")
+                    .first()
+                    .and_then(|p| node_index.get(p))
+                    .copied(),
+                children: Vec::new(),
+                transform: model_transform(model.node),
+                primitives,
+            });
+        }
+        for index in 0..scene.nodes.len() {
+            match scene.nodes[index].parent {
+                Some(parent) => scene.nodes[parent].children.push(index),
+                None => scene.roots.push(index),
+            }
+        }
+
+        if scene.meshes.is_empty() {
+            return Err("FBX sem malha suportada".to_string());
+        }
+        Ok(scene)
+    }
+}
+
+/// Influências dos clusters (Geometry <- Skin <- Cluster <- osso)
+fn read_skin(fbx: &FbxDocument<'_>, geometry: i64) -> Option<ControlPointSkin> {
+    let skin_id = fbx
+        .sources(geometry, "Deformer")
+        .into_iter()
+        .find(|id| fbx.objects[id].subclass == "Skin")?;
+    let mut skin = ControlPointSkin::default();
+    for cluster_id in fbx.sources(skin_id, "Deformer") {
+        let cluster = &fbx.objects[&cluster_id];
+        if cluster.subclass != "Cluster" {
+            continue;
+        }
+        let Some(bone) = fbx
+            .sources(cluster_id, "Model")
+            .first()
+            .map(|b| &fbx.objects[b])
+        else {
+            continue;
+        };
+        let indices = i32_array(cluster.node, "Indexes");
+        let weights = f64_array(cluster.node, "Weights");
+        let bind = f64_array(cluster.node, "TransformLink");
+        let joint = skin.bones.len() as u16;
+        skin.bones.push(bone.name.clone());
+        skin.inverse_bind_matrices.push(match bind.len() {
+            16 => Mat4::from_cols_array(&std::array::from_fn(|i| bind[i] as f32)).inverse(),
+            _ => Mat4::IDENTITY,
+        });
+        for (index, weight) in indices.iter().zip(&weights) {
+            let Ok(control_point) = usize::try_from(*index) else {
+                continue;
+            };
+            if skin.influences.len() <= control_point {
+                skin.influences.resize(control_point + 1, Vec::new());
+            }
+            skin.influences[control_point].push((joint, *weight as f32));
+        }
+    }
+    (!skin.bones.is_empty()).then_some(skin)
+}
+
+/// Converte uma geometria em malhas por slot de material (polígonos em leque)
+fn read_geometry(node: NodeHandle<'_>, skin: Option<&ControlPointSkin>) -> Vec<(usize, MeshData)> {
+    let positions: Vec<Vec3> = f64_array(node, "Vertices")
+        .chunks_exact(3)
+        .map(|p| Vec3::new(p[0] as f32, p[1] as f32, p[2] as f32))
+        .collect();
+    let polygon_vertices = i32_array(node, "PolygonVertexIndex");
+    let normals = LayerElement::read(node, "LayerElementNormal", "Normals", "NormalsIndex");
+    // O primeiro conjunto de UV vai em `Vertex::texcoord`, os demais (lightmap)
+    // em `VertexAttributes::uv_sets`
+    let mut uv_layers = LayerElement::read_all(node, "LayerElementUV", "UV", "UVIndex").into_iter();
+    let uvs = uv_layers.next();
+    let extra_uvs: Vec<LayerElement> = uv_layers.collect();
+    let colors = LayerElement::read(node, "LayerElementColor", "Colors", "ColorIndex");
+    let materials = node
+        .first_child_by_name("LayerElementMaterial")
+        .map(|element| {
+            (
+                child_str(element, "MappingInformationType").unwrap_or("AllSame"),
+                i32_array(element, "Materials"),
+            )
+        });
+
+    // Vértices soldados por (ponto de controle, normal, uv, cor, uvs extras) em cada slot
+    type WeldKey = (usize, usize, usize, usize, Vec<Option<usize>>);
+    let mut slots: BTreeMap<usize, (MeshData, HashMap<WeldKey, u32>)> = BTreeMap::new();
+    let mut polygon = Vec::new();
+    let mut polygon_index = 0;
+    for (polygon_vertex, raw) in polygon_vertices.iter().enumerate() {
+        // O último vértice de cada polígono vem com o índice negado (-i - 1)
+        let end = *raw < 0;
+        let control_point = if end {
+            (-raw - 1) as usize
+        } else {
+            *raw as usize
+        };
+        if control_point < positions.len() {
+            polygon.push((control_point, polygon_vertex));
+        }
+        if !end {
+            continue;
+        }
+
+        let slot = materials
+            .as_ref()
+            .and_then(|(mapping, values)| match *mapping {
+                "AllSame" => values.first(),
+                _ => values.get(polygon_index),
+            })
+            .and_then(|v| usize::try_from(*v).ok())
+            .unwrap_or(0);
+        let (mesh, welded) = slots.entry(slot).or_default();
+        let mut corners = Vec::with_capacity(polygon.len());
+        for &(control_point, polygon_vertex) in &polygon {
+            let normal_index = normals
+                .as_ref()
+                .and_then(|n| n.lookup(control_point, polygon_vertex, polygon_index));
+            let uv_index = uvs
+                .as_ref()
+                .and_then(|u| u.lookup(control_point, polygon_vertex, polygon_index));
+            let color_index = colors
+                .as_ref()
+                .and_then(|c| c.lookup(control_point, polygon_vertex, polygon_index));
+            let extra_uv_indices: Vec<Option<usize>> = extra_uvs
+                .iter()
+                .map(|u| u.lookup(control_point, polygon_vertex, polygon_index))
+                .collect();
+            let key = (
+                control_point,
+                normal_index.unwrap_or(usize::MAX),
+                uv_index.unwrap_or(usize::MAX),
+                color_index.unwrap_or(usize::MAX),
+                extra_uv_indices.clone(),
+            );
+            let index = *welded.entry(key).or_insert_with(|| {
+                let normal = normals
+                    .as_ref()
+                    .zip(normal_index)
+                    .and_then(|(n, i)| n.vec3(i))
+                    .unwrap_or(Vec3::ZERO);
+                // FBX usa V de baixo para cima; o motor segue a convenção do glTF
+                let texcoord = uvs
+                    .as_ref()
+                    .zip(uv_index)
+                    .and_then(|(u, i)| u.vec2(i))
+                    .map(|uv| Vec2::new(uv.x, 1.0 - uv.y))
+                    .unwrap_or(Vec2::ZERO);
+                mesh.vertices
+                    .push(Vertex::new(positions[control_point], normal, texcoord));
+                if colors.is_some() || !extra_uvs.is_empty() {
+                    let attributes = mesh.attributes.get_or_insert_with(|| VertexAttributes {
+                        uv_sets: vec![Vec::new(); extra_uvs.len()],
+                        ..Default::default()
+                    });
+                    if let Some(colors) = &colors {
+                        attributes
+                            .colors
+                            .push(color_index.and_then(|i| colors.vec4(i)).unwrap_or([1.0; 4]));
+                    }
+                    for ((layer, uvs), index) in extra_uvs
+                        .iter()
+                        .zip(&mut attributes.uv_sets)
+                        .zip(&extra_uv_indices)
+                    {
+                        uvs.push(
+                            index
+                                .and_then(|i| layer.vec2(i))
+                                .map(|uv| Vec2::new(uv.x, 1.0 - uv.y))
+                                .unwrap_or(Vec2::ZERO),
+                        );
+                    }
+                }
+                if let Some(skin) = skin {
+                    let (joints, weights) = skin.vertex(control_point);
+                    let mesh_skin = mesh.skin.get_or_insert_with(|| MeshSkin {
+                        bones: skin.bones.clone(),
+                        inverse_bind_matrices: skin.inverse_bind_matrices.clone(),
+                        ..Default::default()
+                    });
+                    mesh_skin.joints.push(joints);
+                    mesh_skin.weights.push(weights);
+                }
+                (mesh.vertices.len() - 1) as u32
+            });
+            corners.push(index);
+        }
+        for i in 1..corners.len().saturating_sub(1) {
+            mesh.indices
+                .extend_from_slice(&[corners[0], corners[i], corners[i + 1]]);
+        }
+        polygon.clear();
+        polygon_index += 1;
+    }
+
+    slots
+        .into_iter()
+        .filter(|(_, (mesh, _))| !mesh.indices.is_empty())
+        .map(|(slot, (mut mesh, _))| {
+            mesh.ensure_normals();
+            // Tangentes do FBX vêm na convenção de V do exportador; usa sempre MikkTSpace
+            mesh.ensure_tangents();
+            (slot, mesh)
+        })
+        .collect()
+}
+
+/// Transformação local do modelo: T * PreRotation * Rotation * S (pivôs ignorados)
+fn model_transform(node: NodeHandle<'_>) -> Transform {
+    let translation = property_vec3(node, "Lcl Translation").unwrap_or(Vec3::ZERO);
+    let rotation = property_vec3(node, "Lcl Rotation").unwrap_or(Vec3::ZERO);
+    let pre_rotation = property_vec3(node, "PreRotation").unwrap_or(Vec3::ZERO);
+    let scale = property_vec3(node, "Lcl Scaling").unwrap_or(Vec3::ONE);
+    Transform::new(
+        translation,
+        euler_xyz(pre_rotation) * euler_xyz(rotation),
+        scale,
+    )
+}
+
+/// Rotação FBX padrão (eEulerXYZ, graus): X aplicado primeiro
+fn euler_xyz(degrees: Vec3) -> Quat {
+    Quat::from_euler(
+        EulerRot::ZYX,
+        degrees.z.to_radians(),
+        degrees.y.to_radians(),
+        degrees.x.to_radians(),
+    )
+}
+
+/// Arquivo da textura: RelativeFilename (relativo ao .fbx) ou FileName, se existir
+fn texture_file(fbx: &FbxDocument<'_>, texture: i64, base_dir: &Path) -> Option<PathBuf> {
+    let node = fbx.get(texture)?.node;
+    let relative = child_str(node, "RelativeFilename").map(|f| base_dir.join(f.replace('\\', "/")));
+    let absolute = child_str(node, "FileName").map(|f| PathBuf::from(f.replace('\\', "/")));
+    relative.into_iter().chain(absolute).find(|p| p.is_file())
+}
+
+/// Decodifica a textura do arquivo externo ou do conteúdo embutido no Video
+fn load_texture(fbx: &FbxDocument<'_>, texture: i64, base_dir: &Path) -> Option<TextureData> {
+    let source_path = texture_file(fbx, texture, base_dir);
+    let image = match &source_path {
+        Some(path) => image::open(path).ok()?,
+        None => {
+            let video = fbx.sources(texture, "Video").first().copied()?;
+            let content = match fbx
+                .get(video)?
+                .node
+                .first_child_by_name("Content")?
+                .attributes()
+                .first()?
+            {
+                AttributeValue::Binary(bytes) => bytes,
+                _ => return None,
+            };
+            image::load_from_memory(content).ok()?
+        }
+    }
+    .to_rgba8();
+    Some(TextureData {
+        name: fbx.get(texture)?.name.clone(),
+        width: image.width(),
+        height: image.height(),
+        rgba: image.into_raw(),
+        hdr_rgba: None,
+        source_path,
+    })
+}
+
+/// "Nome\0\x01Classe" (binário) ou "Classe::Nome" (ASCII)
+fn object_name(raw: &str) -> String {
+    match raw.split_once("\u{0}\u{1}") {
+        Some((name, _)) => name.to_string(),
+        None => raw.rsplit("::").next().unwrap_or(raw).to_string(),
+    }
+}
+
+fn attribute_i64(value: &AttributeValue) -> Option<i64> {
+    match value {
+        AttributeValue::I64(v) => Some(*v),
+        AttributeValue::I32(v) => Some(*v as i64),
+        _ => None,
+    }
+}
+
+fn attribute_f32(value: &AttributeValue) -> Option<f32> {
+    match value {
+        AttributeValue::F64(v) => Some(*v as f32),
+        AttributeValue::F32(v) => Some(*v),
+        AttributeValue::I64(v) => Some(*v as f32),
+        AttributeValue::I32(v) => Some(*v as f32),
+        AttributeValue::I16(v) => Some(*v as f32),
+        _ => None,
+    }
+}
+
+fn attribute_str(value: &AttributeValue) -> Option<&str> {
+    match value {
+        AttributeValue::String(s) => Some(s.as_str()),
+        _ => None,
+    }
+}
+
+fn child_str<'a>(node: NodeHandle<'a>, child: &str) -> Option<&'a str> {
+    node.first_child_by_name(child)?
+        .attributes()
+        .first()
+        .and_then(attribute_str)
+}
+
+fn f64_array(node: NodeHandle<'_>, child: &str) -> Vec<f64> {
+    match node
+        .first_child_by_name(child)
+        .and_then(|c| c.attributes().first())
+    {
+        Some(AttributeValue::ArrF64(values)) => values.clone(),
+        Some(AttributeValue::ArrF32(values)) => values.iter().map(|v| *v as f64).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn i32_array(node: NodeHandle<'_>, child: &str) -> Vec<i32> {
+    match node
+        .first_child_by_name(child)
+        .and_then(|c| c.attributes().first())
+    {
+        Some(AttributeValue::ArrI32(values)) => values.clone(),
+        Some(AttributeValue::ArrI64(values)) => values.iter().map(|v| *v as i32).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Valores de uma propriedade de Properties70 (depois de nome, tipo, rótulo e flags)
+fn property<'a>(node: NodeHandle<'a>, name: &str) -> Option<&'a [AttributeValue]> {
+    node.first_child_by_name("Properties70")?
+        .children_by_name("P")
+        .map(|p| p.attributes())
+        .find(|attrs| attrs.first().and_then(attribute_str) == Some(name))
+        .and_then(|attrs| attrs.get(4..))
+}
+
+fn property_f32(node: NodeHandle<'_>, name: &str) -> Option<f32> {
+    property(node, name)?.first().and_then(attribute_f32)
+}
+
+fn property_vec3(node: NodeHandle<'_>, name: &str) -> Option<Vec3> {
+    let values = property(node, name)?;
+    Some(Vec3::new(
+        attribute_f32(values.first()?)?,
+        attribute_f32(values.get(1)?)?,
+        attribute_f32(values.get(2)?)?,
+    ))
+}
@@ -0,0 +1,351 @@
+//! Font assets - signed distance field glyph atlases for in-world text
+//!
+//! A TTF/OTF file is rasterized once (at `SDF_FONT_PX` unless its import
+//! settings ask for another size) and every glyph is turned into a distance
+//! field, so `Text3D` in the world and `UiText` on screen stay sharp at any
+//! size. The atlas stores the distance in a single channel: 128 is the glyph
+//! edge, higher values are inside. `TEXT_SHADER` thresholds it with `smoothstep`.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use ab_glyph::{Font, FontVec, PxScale, ScaleFont};
+use engine_core::components::TextAlign;
+use glam::Vec2;
+
+use crate::asset_manager::TextureData;
+use crate::vfs::vfs_read;
+
+/// Pixel height glyphs are rasterized at before the distance transform
+pub const SDF_FONT_PX: f32 = 32.0;
+
+/// Rasterization sizes `FontImportSettings` may ask for
+pub const FONT_PX_RANGE: std::ops::RangeInclusive<f32> = 8.0..=128.0;
+
+/// Distance (in atlas pixels) covered by the field on each side of the edge
+pub const SDF_SPREAD: u32 = 4;
+
+/// Atlas width; rows are added as needed
+const ATLAS_WIDTH: u32 = 512;
+
+/// Placement of one glyph in the atlas, in pixels at the rasterization size
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GlyphInfo {
+    /// Atlas region (u0, v0, u1, v1)
+    pub uv_rect: [f32; 4],
+    /// Top-left of the padded bitmap relative to the pen on the baseline (y down)
+    pub offset: Vec2,
+    pub size: Vec2,
+    pub advance: f32,
+}
+
+/// A glyph quad of laid-out text, in text space (y up, origin at the anchor)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GlyphQuad {
+    pub min: Vec2,
+    pub max: Vec2,
+    pub uv_rect: [f32; 4],
+}
+
+/// Laid-out text: quads plus the size of the whole block
+#[derive(Debug, Clone, Default)]
+pub struct TextLayout {
+    pub quads: Vec<GlyphQuad>,
+    pub size: Vec2,
+}
+
+/// Font asset - SDF atlas and metrics for ASCII, Latin-1 and any extra
+/// characters asked for at import
+#[derive(Debug, Clone)]
+pub struct FontAsset {
+    pub name: String,
+    pub source_path: Option<PathBuf>,
+    /// Pixel height the glyphs were rasterized at
+    pub pixel_size: f32,
+    pub atlas_width: u32,
+    pub atlas_height: u32,
+    /// One distance byte per texel
+    pub atlas: Vec<u8>,
+    pub glyphs: HashMap<char, GlyphInfo>,
+    pub kerning: HashMap<(char, char), f32>,
+    pub ascent: f32,
+    pub line_height: f32,
+}
+
+impl FontAsset {
+    /// Load a TTF/OTF file and build its SDF atlas
+    pub fn load(path: &Path) -> Result<Self, String> {
+        Self::load_with(path, SDF_FONT_PX, "")
+    }
+
+    /// Load a TTF/OTF file rasterized at `pixel_size`, with `extra_chars`
+    /// packed besides ASCII and Latin-1
+    pub fn load_with(path: &Path, pixel_size: f32, extra_chars: &str) -> Result<Self, String> {
+        let bytes = vfs_read(path).map_err(|e| format!("Falha ao ler fonte: {e}"))?;
+        let name = path.to_string_lossy().to_string();
+        let mut font = Self::build(&name, bytes, pixel_size, &font_charset(extra_chars))?;
+        font.source_path = Some(path.to_path_buf());
+        Ok(font)
+    }
+
+    /// Build the SDF atlas from font file bytes
+    pub fn from_bytes(name: &str, bytes: Vec<u8>) -> Result<Self, String> {
+        Self::build(name, bytes, SDF_FONT_PX, &font_charset(""))
+    }
+
+    fn build(
+        name: &str,
+        bytes: Vec<u8>,
+        pixel_size: f32,
+        charset: &[char],
+    ) -> Result<Self, String> {
+        let pixel_size = pixel_size.clamp(*FONT_PX_RANGE.start(), *FONT_PX_RANGE.end());
+        let font = FontVec::try_from_vec(bytes).map_err(|e| format!("Fonte inválida: {e}"))?;
+        let scaled = font.as_scaled(PxScale::from(pixel_size));
+
+        let pad = SDF_SPREAD as i32;
+        let mut packer = ShelfPacker::new(ATLAS_WIDTH);
+        let mut bitmaps = Vec::new();
+        let mut glyphs = HashMap::new();
+        for &c in charset {
+            let id = font.glyph_id(c);
+            if id.0 == 0 {
+                continue;
+            }
+            let advance = scaled.h_advance(id);
+            let Some(outline) = font.outline_glyph(id.with_scale(pixel_size)) else {
+                // Whitespace: advance only
+                glyphs.insert(
+                    c,
+                    GlyphInfo {
+                        uv_rect: [0.0; 4],
+                        offset: Vec2::ZERO,
+                        size: Vec2::ZERO,
+                        advance,
+                    },
+                );
+                continue;
+            };
+            let bounds = outline.px_bounds();
+            let width = bounds.width() as i32 + 2 * pad;
+            let height = bounds.height() as i32 + 2 * pad;
+            let mut coverage = vec![0.0_f32; (width * height) as usize];
+            outline.draw(|x, y, c| {
+                let index = (y as i32 + pad) * width + x as i32 + pad;
+                coverage[index as usize] = c;
+            });
+            let (x, y) = packer.place(width as u32, height as u32);
+            bitmaps.push((c, x, y, width as u32, height as u32, coverage));
+            glyphs.insert(
+                c,
+                GlyphInfo {
+                    uv_rect: [0.0; 4],
+                    offset: Vec2::new(bounds.min.x - pad as f32, bounds.min.y - pad as f32),
+                    size: Vec2::new(width as f32, height as f32),
+                    advance,
+                },
+            );
+        }
+
+        let atlas_width = ATLAS_WIDTH;
+        let atlas_height = packer.height().max(1).next_multiple_of(4);
+        let mut atlas = vec![0_u8; (atlas_width * atlas_height) as usize];
+        for (c, x, y, width, height, coverage) in bitmaps {
+            let field = distance_field(&coverage, width as usize, height as usize, SDF_SPREAD);
+            for row in 0..height {
+                let start = ((y + row) * atlas_width + x) as usize;
+                let src = (row * width) as usize;
+                atlas[start..start + width as usize]
+                    .copy_from_slice(&field[src..src + width as usize]);
+            }
+            if let Some(glyph) = glyphs.get_mut(&c) {
+                glyph.uv_rect = [
+                    x as f32 / atlas_width as f32,
+                    y as f32 / atlas_height as f32,
+                    (x + width) as f32 / atlas_width as f32,
+                    (y + height) as f32 / atlas_height as f32,
+                ];
+            }
+        }
+
+        let mut kerning = HashMap::new();
+        for &a in charset {
+            for &b in charset {
+                let kern = scaled.kern(font.glyph_id(a), font.glyph_id(b));
+                if kern != 0.0 {
+                    kerning.insert((a, b), kern);
+                }
+            }
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            source_path: None,
+            pixel_size,
+            atlas_width,
+            atlas_height,
+            atlas,
+            glyphs,
+            kerning,
+            ascent: scaled.ascent(),
+            line_height: scaled.height() + scaled.line_gap(),
+        })
+    }
+
+    /// Atlas as an RGBA texture (white, distance in alpha) for the texture upload path
+    pub fn to_texture_data(&self) -> TextureData {
+        TextureData {
+            name: format!("{} (SDF)", self.name),
+            width: self.atlas_width,
+            height: self.atlas_height,
+            rgba: self
+                .atlas
+                .iter()
+                .flat_map(|&d| [255, 255, 255, d])
+                .collect(),
+            hdr_rgba: None,
+            source_path: None,
+        }
+    }
+
+    fn glyph(&self, c: char) -> Option<&GlyphInfo> {
+        self.glyphs.get(&c).or_else(|| self.glyphs.get(&'?'))
+    }
+
+    fn line_width(&self, line: &str) -> f32 {
+        let mut width = 0.0;
+        let mut prev = None;
+        for c in line.chars() {
+            if let Some(glyph) = self.glyph(c) {
+                width += glyph.advance + prev.map_or(0.0, |p| self.kern(p, c));
+            }
+            prev = Some(c);
+        }
+        width
+    }
+
+    fn kern(&self, a: char, b: char) -> f32 {
+        self.kerning.get(&(a, b)).copied().unwrap_or(0.0)
+    }
+
+    /// Lay out `text` with lines `size` units tall, centered vertically on the
+    /// anchor and aligned horizontally per line
+    pub fn layout_text(&self, text: &str, size: f32, align: TextAlign) -> TextLayout {
+        let scale = size / self.line_height.max(1e-3);
+        let lines: Vec<&str> = text.lines().collect();
+        let block_height = lines.len() as f32 * self.line_height;
+        let mut layout = TextLayout {
+            quads: Vec::new(),
+            size: Vec2::new(0.0, block_height * scale),
+        };
+        for (i, line) in lines.iter().enumerate() {
+            let width = self.line_width(line);
+            layout.size.x = layout.size.x.max(width * scale);
+            let mut pen = match align {
+                TextAlign::Left => 0.0,
+                TextAlign::Center => -width * 0.5,
+                TextAlign::Right => -width,
+            };
+            let baseline = self.ascent + i as f32 * self.line_height;
+            let mut prev = None;
+            for c in line.chars() {
+                let Some(glyph) = self.glyph(c) else {
+                    continue;
+                };
+                pen += prev.map_or(0.0, |p| self.kern(p, c));
+                prev = Some(c);
+                if glyph.size.x > 0.0 {
+                    // Pixel rows grow downwards; text space grows upwards
+                    let x0 = pen + glyph.offset.x;
+                    let y0 = baseline + glyph.offset.y;
+                    layout.quads.push(GlyphQuad {
+                        min: Vec2::new(x0, block_height * 0.5 - (y0 + glyph.size.y)) * scale,
+                        max: Vec2::new(x0 + glyph.size.x, block_height * 0.5 - y0) * scale,
+                        uv_rect: glyph.uv_rect,
+                    });
+                }
+                pen += glyph.advance;
+            }
+        }
+        layout
+    }
+}
+
+/// ASCII and Latin-1 followed by the characters of `extra` not already in it
+pub fn font_charset(extra: &str) -> Vec<char> {
+    let mut charset: Vec<char> = (' '..='~').chain('\u{a1}'..='\u{ff}').collect();
+    for c in extra.chars() {
+        if !c.is_control() && !charset.contains(&c) {
+            charset.push(c);
+        }
+    }
+    charset
+}
+
+/// Row-by-row rectangle packer with a 1px gutter between glyphs
+struct ShelfPacker {
+    width: u32,
+    x: u32,
+    y: u32,
+    row_height: u32,
+}
+
+impl ShelfPacker {
+    fn new(width: u32) -> Self {
+        Self {
+            width,
+            x: 0,
+            y: 0,
+            row_height: 0,
+        }
+    }
+
+    fn place(&mut self, width: u32, height: u32) -> (u32, u32) {
+        if self.x + width > self.width {
+            self.x = 0;
+            self.y += self.row_height + 1;
+            self.row_height = 0;
+        }
+        let position = (self.x, self.y);
+        self.x += width + 1;
+        self.row_height = self.row_height.max(height);
+        position
+    }
+
+    fn height(&self) -> u32 {
+        self.y + self.row_height
+    }
+}
+
+/// Signed distance to the nearest texel on the other side of the edge,
+/// searched within `spread` texels and mapped to 0..255 (128 = edge)
+fn distance_field(coverage: &[f32], width: usize, height: usize, spread: u32) -> Vec<u8> {
+    let spread = spread as i32;
+    let inside = |x: i32, y: i32| {
+        x >= 0
+            && y >= 0
+            && (x as usize) < width
+            && (y as usize) < height
+            && coverage[y as usize * width + x as usize] >= 0.5
+    };
+    let mut field = Vec::with_capacity(width * height);
+    for y in 0..height as i32 {
+        for x in 0..width as i32 {
+            let here = inside(x, y);
+            let mut nearest = spread as f32;
+            for dy in -spread..=spread {
+                for dx in -spread..=spread {
+                    if inside(x + dx, y + dy) != here {
+                        nearest = nearest.min(((dx * dx + dy * dy) as f32).sqrt());
+                    }
+                }
+            }
+            // The edge lies halfway between the two texel centers
+            let distance = nearest - 0.5;
+            let signed = if here { distance } else { -distance };
+            let value = 0.5 + signed / (2.0 * spread as f32);
+            field.push((value.clamp(0.0, 1.0) * 255.0).round() as u8);
+        }
+    }
+    field
+}
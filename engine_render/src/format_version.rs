@@ -0,0 +1,265 @@
+//! Versions of the serialized editor formats and the migrations between them
+//!
+//! Project files start with a `DENG<n>` line; scenes, materials and graphs
+//! carry a `version=<n>` line. Files written before a format was versioned
+//! have neither and count as version 0. Loaders hand the raw text to
+//! `migrate_format`: every registered step from the file's version up to the
+//! current one runs in order (each step turns version `n` into `n + 1`), so
+//! parsers only ever see the current layout. Files from a newer editor are
+//! refused instead of being half-read.
+//!
+//! One `MigrationRegistry` is shared by the process through `migrations()` /
+//! `migrations_mut()`; it starts with the engine's own steps, and tools or
+//! plugins register theirs for the versions they introduce.
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// A serialized format with its own version number
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FormatKind {
+    /// `.deng` project file
+    Project,
+    Scene,
+    /// `.mat` material
+    Material,
+    /// Fios node graph
+    Graph,
+    /// `.anim` keyframe clip from the timeline
+    Animation,
+    /// `.rig` skeleton from the Rig panel
+    Rig,
+}
+
+impl FormatKind {
+    pub const ALL: [FormatKind; 6] = [
+        FormatKind::Project,
+        FormatKind::Scene,
+        FormatKind::Material,
+        FormatKind::Graph,
+        FormatKind::Animation,
+        FormatKind::Rig,
+    ];
+
+    /// Version written by this editor
+    pub fn current_version(self) -> u32 {
+        match self {
+            FormatKind::Project => 1,
+            FormatKind::Scene => 1,
+            FormatKind::Material => 1,
+            FormatKind::Graph => 1,
+            FormatKind::Animation => 1,
+            FormatKind::Rig => 1,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            FormatKind::Project => "projeto",
+            FormatKind::Scene => "cena",
+            FormatKind::Material => "material",
+            FormatKind::Graph => "grafo",
+            FormatKind::Animation => "animação",
+            FormatKind::Rig => "rig",
+        }
+    }
+
+    /// Version line for a new file of the current version
+    pub fn header(self) -> String {
+        self.version_line(self.current_version())
+    }
+
+    fn version_line(self, version: u32) -> String {
+        match self {
+            FormatKind::Project => format!("DENG{version}\n"),
+            _ => format!("version={version}\n"),
+        }
+    }
+
+    /// Version a file says it has; 0 when it predates versioning
+    pub fn read_version(self, text: &str) -> Result<u32, String> {
+        match self {
+            FormatKind::Project => match text.lines().next().map(str::trim) {
+                Some(line) if line.starts_with("DENG") => line["DENG".len()..]
+                    .parse()
+                    .map_err(|_| format!("Cabeçalho de projeto inválido: {line}")),
+                _ => Ok(0),
+            },
+            _ => {
+                let Some(value) = text
+                    .lines()
+                    .find_map(|line| line.trim().strip_prefix("version="))
+                else {
+                    return Ok(0);
+                };
+                value
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("Versão de {} inválida: {value}", self.name()))
+            }
+        }
+    }
+
+    /// `text` with its version line replaced (or added) for `version`
+    fn stamp(self, text: &str, version: u32) -> String {
+        let line = self.version_line(version);
+        let mut out = String::with_capacity(text.len() + line.len());
+        match self {
+            FormatKind::Project => {
+                out.push_str(&line);
+                let mut lines = text.lines().peekable();
+                if lines
+                    .peek()
+                    .is_some_and(|first| first.trim().starts_with("DENG"))
+                {
+                    lines.next();
+                }
+                for rest in lines {
+                    out.push_str(rest);
+                    out.push('\n');
+                }
+            }
+            _ => {
+                // After the leading comments, where a reader looks for it
+                let mut written = false;
+                for rest in text.lines() {
+                    if rest.trim().starts_with("version=") {
+                        continue;
+                    }
+                    if !written && !rest.trim_start().starts_with('#') {
+                        out.push_str(&line);
+                        written = true;
+                    }
+                    out.push_str(rest);
+                    out.push('\n');
+                }
+                if !written {
+                    out.push_str(&line);
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Turns the text of version `n` into the text of version `n + 1`; the version
+/// line is updated by the registry
+pub type MigrationFn = fn(&str) -> Result<String, String>;
+
+/// Text ready for the current parser
+#[derive(Debug, Clone)]
+pub struct Migrated {
+    pub text: String,
+    /// Version the file had on disk
+    pub from_version: u32,
+    pub to_version: u32,
+}
+
+impl Migrated {
+    /// Whether any migration ran, i.e. saving would rewrite an older file
+    pub fn upgraded(&self) -> bool {
+        self.from_version != self.to_version
+    }
+}
+
+/// Migration steps of every format
+pub struct MigrationRegistry {
+    steps: HashMap<(FormatKind, u32), MigrationFn>,
+}
+
+impl Default for MigrationRegistry {
+    fn default() -> Self {
+        let mut registry = Self {
+            steps: HashMap::new(),
+        };
+        // Projects, scenes and graphs only gained the version line
+        registry.register(FormatKind::Project, 0, unchanged);
+        registry.register(FormatKind::Scene, 0, unchanged);
+        registry.register(FormatKind::Graph, 0, unchanged);
+        registry.register(FormatKind::Material, 0, material_v0_to_v1);
+        // `.anim` files from before the timeline have no version line
+        registry.register(FormatKind::Animation, 0, unchanged);
+        registry
+    }
+}
+
+impl MigrationRegistry {
+    /// Register the step that upgrades `kind` from `from_version`; replaces an
+    /// earlier step for the same version
+    pub fn register(&mut self, kind: FormatKind, from_version: u32, migration: MigrationFn) {
+        self.steps.insert((kind, from_version), migration);
+    }
+
+    pub fn has_step(&self, kind: FormatKind, from_version: u32) -> bool {
+        self.steps.contains_key(&(kind, from_version))
+    }
+
+    /// Bring `text` up to the current version of `kind`
+    pub fn migrate(&self, kind: FormatKind, text: &str) -> Result<Migrated, String> {
+        let from_version = kind.read_version(text)?;
+        let to_version = kind.current_version();
+        if from_version > to_version {
+            return Err(format!(
+                "Arquivo de {} na versão {from_version}, mais nova que a suportada ({to_version}); atualize a engine",
+                kind.name()
+            ));
+        }
+        let mut text = text.to_string();
+        for version in from_version..to_version {
+            let step = self.steps.get(&(kind, version)).ok_or_else(|| {
+                format!(
+                    "Sem migração de {} da versão {version} para {}",
+                    kind.name(),
+                    version + 1
+                )
+            })?;
+            text = kind.stamp(&step(&text)?, version + 1);
+        }
+        Ok(Migrated {
+            text,
+            from_version,
+            to_version,
+        })
+    }
+}
+
+fn unchanged(text: &str) -> Result<String, String> {
+    Ok(text.to_string())
+}
+
+/// v1 materials drop the legacy aliases: `smoothness` becomes `roughness` and
+/// `texture` becomes `albedo_texture`
+fn material_v0_to_v1(text: &str) -> Result<String, String> {
+    let mut out = String::with_capacity(text.len());
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if let Some(value) = trimmed.strip_prefix("smoothness=") {
+            let smoothness: f32 = value.trim().parse().unwrap_or(0.5);
+            out.push_str(&format!("roughness={}\n", 1.0 - smoothness));
+        } else if let Some(value) = trimmed.strip_prefix("texture=") {
+            out.push_str(&format!("albedo_texture={}\n", value.trim()));
+        } else {
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    Ok(out)
+}
+
+static MIGRATIONS: LazyLock<RwLock<MigrationRegistry>> =
+    LazyLock::new(|| RwLock::new(MigrationRegistry::default()));
+
+/// The process-wide migration steps
+pub fn migrations() -> RwLockReadGuard<'static, MigrationRegistry> {
+    MIGRATIONS.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The process-wide migration steps, to register new ones
+pub fn migrations_mut() -> RwLockWriteGuard<'static, MigrationRegistry> {
+    MIGRATIONS.write().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// `MigrationRegistry::migrate` on the shared registry
+pub fn migrate_format(kind: FormatKind, text: &str) -> Result<Migrated, String> {
+    migrations().migrate(kind, text)
+}
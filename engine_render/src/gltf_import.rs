@@ -0,0 +1,311 @@
+//! Importação glTF 2.0 (.gltf/.glb)
+//!
+//! Converte o documento em assets do `AssetManager` (malhas por primitiva,
+//! materiais PBR e texturas RGBA8) e preserva a hierarquia de nós com as
+//! transformações locais, para que o arquivo possa ser instanciado como árvore.
+
+use std::path::{Path, PathBuf};
+
+use engine_core::components::{MaterialHandle, MeshHandle, MeshRenderer, TextureHandle, Transform};
+use engine_core::ecs::{EngineWorld, EntityHandle};
+use glam::{Mat4, Quat, Vec3};
+
+use crate::asset_manager::{AssetManager, MaterialData, TextureData};
+use crate::mesh::MeshData;
+
+/// Primitiva de um nó: malha + material (None usa o material padrão)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImportedPrimitive {
+    pub mesh: MeshHandle,
+    pub material: Option<MaterialHandle>,
+}
+
+/// Nó da hierarquia importada
+#[derive(Debug, Clone)]
+pub struct ImportedNode {
+    pub name: String,
+    pub parent: Option<usize>,
+    pub children: Vec<usize>,
+    /// Transformação local (relativa ao pai)
+    pub transform: Transform,
+    pub primitives: Vec<ImportedPrimitive>,
+}
+
+/// Resultado da importação de um arquivo glTF ou FBX
+#[derive(Debug, Clone, Default)]
+pub struct ImportedScene {
+    pub name: String,
+    pub source: PathBuf,
+    pub nodes: Vec<ImportedNode>,
+    /// Nós raiz da cena padrão
+    pub roots: Vec<usize>,
+    pub meshes: Vec<MeshHandle>,
+    pub materials: Vec<MaterialHandle>,
+    pub textures: Vec<TextureHandle>,
+}
+
+impl ImportedScene {
+    /// Matriz de mundo do nó (composição com todos os pais)
+    pub fn world_matrix(&self, index: usize) -> Mat4 {
+        let mut matrix = Mat4::IDENTITY;
+        let mut cursor = Some(index);
+        while let Some(i) = cursor {
+            let Some(node) = self.nodes.get(i) else {
+                break;
+            };
+            matrix = node.transform.to_mat4() * matrix;
+            cursor = node.parent;
+        }
+        matrix
+    }
+
+    /// Nós alcançáveis a partir das raízes, pais antes dos filhos
+    pub fn traverse(&self) -> Vec<usize> {
+        let mut order = Vec::with_capacity(self.nodes.len());
+        let mut stack: Vec<usize> = self.roots.iter().rev().copied().collect();
+        while let Some(i) = stack.pop() {
+            if order.contains(&i) {
+                continue;
+            }
+            order.push(i);
+            if let Some(node) = self.nodes.get(i) {
+                stack.extend(node.children.iter().rev().copied());
+            }
+        }
+        order
+    }
+
+    /// Nós com malha, na ordem de `traverse`
+    pub fn mesh_nodes(&self) -> Vec<usize> {
+        self.traverse()
+            .into_iter()
+            .filter(|i| !self.nodes[*i].primitives.is_empty())
+            .collect()
+    }
+
+    /// Une todas as primitivas numa só malha, no espaço da cena
+    pub fn merged_mesh(&self, assets: &AssetManager) -> MeshData {
+        let mut merged = MeshData {
+            name: self.name.clone(),
+            ..Default::default()
+        };
+        for index in self.mesh_nodes() {
+            let world = self.world_matrix(index);
+            for primitive in &self.nodes[index].primitives {
+                if let Some(mesh) = assets.get_mesh(primitive.mesh) {
+                    merged.append_transformed(mesh, world);
+                    if merged.albedo_texture_path.is_none() {
+                        merged.albedo_texture_path =
+                            mesh.albedo_texture_path.clone().or_else(|| {
+                                primitive
+                                    .material
+                                    .and_then(|m| assets.get_material(m))
+                                    .and_then(|m| m.albedo_texture.as_ref())
+                                    .map(PathBuf::from)
+                            });
+                    }
+                }
+            }
+        }
+        merged
+    }
+
+    /// Cria uma entidade por primitiva com a transformação de mundo do nó
+    pub fn spawn_into(&self, world: &mut EngineWorld, root: Transform) -> Vec<EntityHandle> {
+        let root = root.to_mat4();
+        let mut spawned = Vec::new();
+        for index in self.mesh_nodes() {
+            let (scale, rotation, position) =
+                (root * self.world_matrix(index)).to_scale_rotation_translation();
+            let transform = Transform::new(position, rotation, scale);
+            for primitive in &self.nodes[index].primitives {
+                let renderer = MeshRenderer {
+                    mesh: primitive.mesh,
+                    material: primitive.material.unwrap_or_default(),
+                };
+                spawned.push(world.spawn((transform, renderer)));
+            }
+        }
+        spawned
+    }
+}
+
+impl AssetManager {
+    /// Importa um .gltf/.glb: malhas, materiais, texturas e hierarquia de nós
+    pub fn import_gltf(&mut self, path: &Path) -> Result<ImportedScene, String> {
+        engine_core::profile_function!();
+        let (document, buffers, images) =
+            gltf::import(path).map_err(|e| format!("Falha ao carregar GLTF: {}", e))?;
+        let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+        let file_stem = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("gltf")
+            .to_string();
+
+        let mut scene = ImportedScene {
+            name: file_stem.clone(),
+            source: path.to_path_buf(),
+            ..Default::default()
+        };
+
+        // Texturas: uma por imagem do documento
+        let mut image_textures = Vec::with_capacity(images.len());
+        for (image, data) in document.images().zip(&images) {
+            let Some(rgba) = image_to_rgba8(data) else {
+                image_textures.push(None);
+                continue;
+            };
+            let source_path = match image.source() {
+                gltf::image::Source::Uri { uri, .. } if !uri.starts_with("data:") => {
+                    Some(base_dir.join(uri))
+                }
+                _ => None,
+            };
+            let handle = self.add_texture(TextureData {
+                name: image
+                    .name()
+                    .map(str::to_string)
+                    .unwrap_or_else(|| format!("{}_texture_{}", file_stem, image.index())),
+                width: data.width,
+                height: data.height,
+                rgba,
+                hdr_rgba: None,
+                source_path,
+            });
+            scene.textures.push(handle);
+            image_textures.push(Some(handle));
+        }
+
+        // Materiais PBR metallic-roughness
+        let mut material_handles = Vec::new();
+        for material in document.materials() {
+            let pbr = material.pbr_metallic_roughness();
+            let texture = pbr
+                .base_color_texture()
+                .and_then(|info| image_textures.get(info.texture().source().index()))
+                .copied()
+                .flatten();
+            let albedo_texture = texture
+                .and_then(|h| self.get_texture(h))
+                .and_then(|t| t.source_path.as_ref())
+                .map(|p| p.to_string_lossy().to_string());
+            let handle = self.add_material(MaterialData {
+                name: material.name().map(str::to_string).unwrap_or_else(|| {
+                    format!("{}_material_{}", file_stem, material.index().unwrap_or(0))
+                }),
+                albedo: pbr.base_color_factor(),
+                metallic: pbr.metallic_factor(),
+                roughness: pbr.roughness_factor(),
+                albedo_texture,
+                albedo_texture_handle: texture,
+                ..Default::default()
+            });
+            scene.materials.push(handle);
+            material_handles.push(handle);
+        }
+
+        // Malhas: uma MeshData por primitiva de triângulos
+        let mut mesh_primitives: Vec<Vec<ImportedPrimitive>> = Vec::new();
+        for mesh in document.meshes() {
+            let mesh_name = mesh
+                .name()
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("{}_mesh_{}", file_stem, mesh.index()));
+            let mut primitives = Vec::new();
+            for primitive in mesh.primitives() {
+                let name = format!("{}#{}", mesh_name, primitive.index());
+                let Some(mut data) = MeshData::from_gltf_primitive(&name, &primitive, &buffers)
+                else {
+                    continue;
+                };
+                let material = primitive
+                    .material()
+                    .index()
+                    .and_then(|i| material_handles.get(i).copied());
+                data.albedo_texture_path = material
+                    .and_then(|m| self.get_material(m))
+                    .and_then(|m| m.albedo_texture.as_ref())
+                    .map(PathBuf::from);
+                let handle = self.add_mesh(data);
+                scene.meshes.push(handle);
+                primitives.push(ImportedPrimitive {
+                    mesh: handle,
+                    material,
+                });
+            }
+            mesh_primitives.push(primitives);
+        }
+
+        // Hierarquia de nós
+        for node in document.nodes() {
+            let (translation, rotation, scale) = node.transform().decomposed();
+            scene.nodes.push(ImportedNode {
+                name: node
+                    .name()
+                    .map(str::to_string)
+                    .unwrap_or_else(|| format!("Node {}", node.index())),
+                parent: None,
+                children: node.children().map(|c| c.index()).collect(),
+                transform: Transform::new(
+                    Vec3::from_array(translation),
+                    Quat::from_array(rotation),
+                    Vec3::from_array(scale),
+                ),
+                primitives: node
+                    .mesh()
+                    .and_then(|m| mesh_primitives.get(m.index()).cloned())
+                    .unwrap_or_default(),
+            });
+        }
+        for parent in 0..scene.nodes.len() {
+            for child in scene.nodes[parent].children.clone() {
+                if let Some(node) = scene.nodes.get_mut(child) {
+                    node.parent = Some(parent);
+                }
+            }
+        }
+        scene.roots = match document
+            .default_scene()
+            .or_else(|| document.scenes().next())
+        {
+            Some(s) => s.nodes().map(|n| n.index()).collect(),
+            None => (0..scene.nodes.len())
+                .filter(|i| scene.nodes[*i].parent.is_none())
+                .collect(),
+        };
+
+        if scene.meshes.is_empty() {
+            return Err("GLTF sem triângulos suportados".to_string());
+        }
+        Ok(scene)
+    }
+}
+
+/// Converte os formatos de imagem do glTF para RGBA8
+fn image_to_rgba8(data: &gltf::image::Data) -> Option<Vec<u8>> {
+    use gltf::image::Format;
+    let pixels = &data.pixels;
+    let rgba = match data.format {
+        Format::R8G8B8A8 => pixels.clone(),
+        Format::R8G8B8 => pixels
+            .chunks_exact(3)
+            .flat_map(|p| [p[0], p[1], p[2], 255])
+            .collect(),
+        Format::R8G8 => pixels
+            .chunks_exact(2)
+            .flat_map(|p| [p[0], p[1], 0, 255])
+            .collect(),
+        Format::R8 => pixels.iter().flat_map(|v| [*v, *v, *v, 255]).collect(),
+        Format::R16G16B16A16 => pixels
+            .chunks_exact(8)
+            .flat_map(|p| [p[1], p[3], p[5], p[7]])
+            .collect(),
+        Format::R16G16B16 => pixels
+            .chunks_exact(6)
+            .flat_map(|p| [p[1], p[3], p[5], 255])
+            .collect(),
+        _ => return None,
+    };
+    (rgba.len() == (data.width * data.height * 4) as usize).then_some(rgba)
+}
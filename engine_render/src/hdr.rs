@@ -0,0 +1,109 @@
+//! Float images - Radiance HDR and OpenEXR
+//!
+//! Float sources keep their linear RGBA32F texels next to an 8-bit tonemapped
+//! copy: the GPU gets the floats as `Rgba16Float` (with a mip chain built here,
+//! since the compute mipmapper only writes RGBA8), while previews, thumbnails
+//! and egui get the 8-bit pixels.
+
+use image::{ColorType, DynamicImage};
+
+/// Whether the decoded image holds float channels (.hdr, .exr)
+pub fn is_float_image(image: &DynamicImage) -> bool {
+    matches!(image.color(), ColorType::Rgb32F | ColorType::Rgba32F)
+}
+
+/// Linear RGBA32F -> sRGB RGBA8 with a Reinhard curve, for display only
+pub fn tonemap_rgba8(pixels: &[f32]) -> Vec<u8> {
+    pixels
+        .chunks_exact(4)
+        .flat_map(|px| {
+            let tone = |c: f32| {
+                let c = c.max(0.0);
+                (linear_to_srgb(c / (1.0 + c)) * 255.0 + 0.5) as u8
+            };
+            [
+                tone(px[0]),
+                tone(px[1]),
+                tone(px[2]),
+                (px[3].clamp(0.0, 1.0) * 255.0 + 0.5) as u8,
+            ]
+        })
+        .collect()
+}
+
+fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Pixels packed as RGBA16F (little endian) for GPU upload
+pub fn pack_rgba16f(pixels: &[f32]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(pixels.len() * 2);
+    for &f in pixels {
+        bytes.extend_from_slice(&f32_to_f16_bits(f).to_le_bytes());
+    }
+    bytes
+}
+
+/// Half-size level of an RGBA32F image (2x2 box filter, odd edges clamped)
+pub fn downsample_rgba32f(width: u32, height: u32, pixels: &[f32]) -> (u32, u32, Vec<f32>) {
+    let (w, h) = ((width / 2).max(1), (height / 2).max(1));
+    let mut out = Vec::with_capacity((w * h * 4) as usize);
+    for y in 0..h {
+        for x in 0..w {
+            let mut sum = [0.0f32; 4];
+            for (dx, dy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+                let sx = (x * 2 + dx).min(width - 1);
+                let sy = (y * 2 + dy).min(height - 1);
+                let i = ((sy * width + sx) * 4) as usize;
+                for c in 0..4 {
+                    sum[c] += pixels[i + c];
+                }
+            }
+            out.extend(sum.iter().map(|s| s * 0.25));
+        }
+    }
+    (w, h, out)
+}
+
+/// `levels` mips of an RGBA32F image as RGBA16F bytes, largest first
+pub fn rgba16f_mip_chain(
+    width: u32,
+    height: u32,
+    pixels: &[f32],
+    levels: u32,
+) -> Vec<(u32, u32, Vec<u8>)> {
+    let mut chain = vec![(width, height, pack_rgba16f(pixels))];
+    let mut level = (width, height, pixels.to_vec());
+    for _ in 1..levels.max(1) {
+        level = downsample_rgba32f(level.0, level.1, &level.2);
+        chain.push((level.0, level.1, pack_rgba16f(&level.2)));
+    }
+    chain
+}
+
+/// IEEE 754 half-precision bits (round toward zero, overflow clamps to the largest finite value)
+fn f32_to_f16_bits(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    if value.is_nan() {
+        return sign | 0x7e00;
+    }
+    let exponent = ((bits >> 23) & 0xff) as i32 - 127 + 15;
+    let mantissa = bits & 0x7f_ffff;
+    if exponent >= 0x1f {
+        sign | 0x7bff
+    } else if exponent <= 0 {
+        if exponent < -10 {
+            return sign;
+        }
+        // Subnormal half
+        let m = (mantissa | 0x80_0000) >> (14 - exponent);
+        sign | m as u16
+    } else {
+        sign | ((exponent as u16) << 10) | (mantissa >> 13) as u16
+    }
+}
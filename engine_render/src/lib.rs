@@ -2,12 +2,84 @@
 //!
 //! Este módulo gerencia assets, materiais, shaders e dados de mesh.
 
+pub mod archive;
+pub mod asset_bundle;
+pub mod asset_importer;
 pub mod asset_manager;
+pub mod asset_meta;
+pub mod asset_search;
+pub mod asset_watcher;
+pub mod audio_waveform;
+pub mod capture;
+pub mod compute;
+pub mod cubemap;
+pub mod curve;
+pub mod environment;
+pub mod fbx_import;
+pub mod font;
+pub mod format_version;
+pub mod gltf_import;
+pub mod hdr;
+pub mod lighting;
+pub mod material_shader;
+pub mod memory_budget;
 pub mod mesh;
+pub mod mesh_simplify;
+pub mod obj_import;
+pub mod particles;
+pub mod post_process;
+pub mod render_stats;
 pub mod renderer;
+pub mod rig;
 pub mod shader;
+pub mod shader_library;
+pub mod sprite;
+pub mod sprite_atlas;
+pub mod terrain;
+pub mod text;
+pub mod texture_sampling;
+pub mod thumbnail;
+pub mod vfs;
+pub mod video;
+pub mod water;
 
+pub use archive::*;
+pub use asset_bundle::*;
+pub use asset_importer::*;
 pub use asset_manager::*;
+pub use asset_meta::*;
+pub use asset_search::*;
+pub use asset_watcher::*;
+pub use audio_waveform::*;
+pub use capture::*;
+pub use compute::*;
+pub use cubemap::*;
+pub use curve::*;
+pub use environment::*;
+pub use fbx_import::*;
+pub use font::*;
+pub use format_version::*;
+pub use gltf_import::*;
+pub use hdr::*;
+pub use lighting::*;
+pub use material_shader::*;
+pub use memory_budget::*;
 pub use mesh::*;
+pub use mesh_simplify::*;
+pub use obj_import::*;
+pub use particles::*;
+pub use post_process::*;
+pub use render_stats::*;
 pub use renderer::*;
+pub use rig::*;
 pub use shader::*;
+pub use shader_library::*;
+pub use sprite::*;
+pub use sprite_atlas::*;
+pub use terrain::*;
+pub use text::*;
+pub use texture_sampling::*;
+pub use thumbnail::*;
+pub use vfs::*;
+pub use video::*;
+pub use water::*;
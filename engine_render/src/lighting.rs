@@ -0,0 +1,256 @@
+//! Clustered forward lighting (forward+)
+//!
+//! The view frustum is split into a grid of clusters (screen tiles × exponential
+//! depth slices). Every frame the local lights are assigned on the CPU to the
+//! clusters their bounding sphere touches, so the fragment shader only loops over
+//! the few lights that can actually reach each pixel. Directional lights affect
+//! every fragment and are stored at the start of the light list.
+
+use engine_core::systems::{LightInstance, LightKind};
+use glam::{Mat4, Vec3, Vec4};
+
+/// Number of screen tiles along X
+pub const CLUSTER_GRID_X: u32 = 16;
+/// Number of screen tiles along Y
+pub const CLUSTER_GRID_Y: u32 = 9;
+/// Number of depth slices
+pub const CLUSTER_GRID_Z: u32 = 24;
+/// Total number of clusters
+pub const CLUSTER_COUNT: usize = (CLUSTER_GRID_X * CLUSTER_GRID_Y * CLUSTER_GRID_Z) as usize;
+/// Maximum number of lights uploaded per frame
+pub const MAX_LIGHTS: usize = 256;
+/// Maximum number of lights referenced by a single cluster
+pub const MAX_LIGHTS_PER_CLUSTER: usize = 64;
+
+/// Size of a packed `GpuLight` in bytes
+pub const GPU_LIGHT_SIZE: usize = 64;
+
+/// Light as laid out in the shader's storage buffer (4 × vec4<f32>)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GpuLight {
+    /// xyz = world position, w = range
+    pub position_range: [f32; 4],
+    /// rgb = color, a = intensity
+    pub color_intensity: [f32; 4],
+    /// xyz = direction the light shines towards, w = kind (0 directional, 1 point, 2 spot)
+    pub direction_kind: [f32; 4],
+    /// x = cos(inner angle), y = cos(outer angle)
+    pub cone: [f32; 4],
+}
+
+impl GpuLight {
+    pub fn from_instance(light: &LightInstance) -> Self {
+        let kind = match light.kind {
+            LightKind::Directional => 0.0,
+            LightKind::Point => 1.0,
+            LightKind::Spot => 2.0,
+        };
+        let direction = light.direction.normalize_or(-Vec3::Y);
+        let range = if light.range.is_finite() {
+            light.range.max(0.01)
+        } else {
+            0.0
+        };
+        Self {
+            position_range: [light.position.x, light.position.y, light.position.z, range],
+            color_intensity: [light.color.x, light.color.y, light.color.z, light.intensity],
+            direction_kind: [direction.x, direction.y, direction.z, kind],
+            cone: [
+                light.inner_angle.min(light.outer_angle).cos(),
+                light.outer_angle.cos(),
+                0.0,
+                0.0,
+            ],
+        }
+    }
+}
+
+/// Per-frame light list and cluster → light assignment
+#[derive(Debug, Clone, Default)]
+pub struct ClusteredLights {
+    /// Directional lights first, then the local (point/spot) lights
+    pub lights: Vec<GpuLight>,
+    /// (offset, count) into `indices` for every cluster
+    pub clusters: Vec<[u32; 2]>,
+    /// Indices into `lights`
+    pub indices: Vec<u32>,
+    pub directional_count: u32,
+    pub near: f32,
+    pub far: f32,
+}
+
+impl ClusteredLights {
+    /// Assign the lights to the clusters of the camera described by `view` and `proj`.
+    /// `near`/`far` must match the projection's clip planes.
+    pub fn build(lights: &[LightInstance], view: Mat4, proj: Mat4, near: f32, far: f32) -> Self {
+        engine_core::profile_function!();
+        let near = near.max(1e-3);
+        let far = far.max(near + 1e-3);
+
+        let mut out = Self {
+            near,
+            far,
+            ..Default::default()
+        };
+        for light in lights
+            .iter()
+            .filter(|l| l.kind == LightKind::Directional)
+            .take(MAX_LIGHTS)
+        {
+            out.lights.push(GpuLight::from_instance(light));
+        }
+        out.directional_count = out.lights.len() as u32;
+
+        let mut buckets: Vec<Vec<u32>> = vec![Vec::new(); CLUSTER_COUNT];
+        for light in lights
+            .iter()
+            .filter(|l| l.kind != LightKind::Directional && l.intensity > 0.0 && l.range > 0.0)
+        {
+            if out.lights.len() >= MAX_LIGHTS {
+                break;
+            }
+            let Some((min, max)) = cluster_bounds(light, view, proj, near, far) else {
+                continue;
+            };
+            let index = out.lights.len() as u32;
+            out.lights.push(GpuLight::from_instance(light));
+            for z in min[2]..=max[2] {
+                for y in min[1]..=max[1] {
+                    for x in min[0]..=max[0] {
+                        let bucket = &mut buckets[cluster_index(x, y, z)];
+                        if bucket.len() < MAX_LIGHTS_PER_CLUSTER {
+                            bucket.push(index);
+                        }
+                    }
+                }
+            }
+        }
+
+        out.clusters.reserve(CLUSTER_COUNT);
+        for bucket in &buckets {
+            out.clusters
+                .push([out.indices.len() as u32, bucket.len() as u32]);
+            out.indices.extend_from_slice(bucket);
+        }
+        out
+    }
+
+    /// Number of point/spot lights in the list
+    pub fn local_count(&self) -> u32 {
+        self.lights.len() as u32 - self.directional_count
+    }
+
+    /// Light list bytes for the storage buffer (never empty)
+    pub fn light_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.lights.len().max(1) * GPU_LIGHT_SIZE);
+        for light in &self.lights {
+            for v in [
+                light.position_range,
+                light.color_intensity,
+                light.direction_kind,
+                light.cone,
+            ] {
+                for f in v {
+                    bytes.extend_from_slice(&f.to_le_bytes());
+                }
+            }
+        }
+        if bytes.is_empty() {
+            bytes.resize(GPU_LIGHT_SIZE, 0);
+        }
+        bytes
+    }
+
+    /// Cluster grid bytes for the storage buffer (always `CLUSTER_COUNT` entries)
+    pub fn cluster_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(CLUSTER_COUNT * 8);
+        for c in 0..CLUSTER_COUNT {
+            let [offset, count] = self.clusters.get(c).copied().unwrap_or([0, 0]);
+            bytes.extend_from_slice(&offset.to_le_bytes());
+            bytes.extend_from_slice(&count.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Light index bytes for the storage buffer (never empty)
+    pub fn index_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.indices.len().max(1) * 4);
+        for i in &self.indices {
+            bytes.extend_from_slice(&i.to_le_bytes());
+        }
+        if bytes.is_empty() {
+            bytes.resize(4, 0);
+        }
+        bytes
+    }
+}
+
+fn cluster_index(x: u32, y: u32, z: u32) -> usize {
+    (x + y * CLUSTER_GRID_X + z * CLUSTER_GRID_X * CLUSTER_GRID_Y) as usize
+}
+
+/// Depth slice of a view-space depth (exponential distribution, same formula as the shader)
+fn depth_slice(depth: f32, near: f32, far: f32) -> u32 {
+    let t = (depth.max(near) / near).ln() / (far / near).ln();
+    ((t * CLUSTER_GRID_Z as f32) as u32).min(CLUSTER_GRID_Z - 1)
+}
+
+/// Inclusive cluster range covered by the bounding sphere of a local light
+fn cluster_bounds(
+    light: &LightInstance,
+    view: Mat4,
+    proj: Mat4,
+    near: f32,
+    far: f32,
+) -> Option<([u32; 3], [u32; 3])> {
+    let center = view.transform_point3(light.position);
+    let radius = light.range;
+    // View space looks down -Z
+    let depth = -center.z;
+    let depth_min = depth - radius;
+    let depth_max = depth + radius;
+    if depth_max < near || depth_min > far {
+        return None;
+    }
+
+    // Project the corners of the view-space box around the sphere; corners behind
+    // the near plane are pulled onto it, which only widens the screen rectangle.
+    let mut ndc_min = Vec3::splat(f32::MAX);
+    let mut ndc_max = Vec3::splat(f32::MIN);
+    for corner in 0..8 {
+        let offset = Vec3::new(
+            if corner & 1 == 0 { -radius } else { radius },
+            if corner & 2 == 0 { -radius } else { radius },
+            if corner & 4 == 0 { -radius } else { radius },
+        );
+        let mut p = center + offset;
+        p.z = p.z.min(-near);
+        let clip = proj * Vec4::new(p.x, p.y, p.z, 1.0);
+        if clip.w.abs() < 1e-6 {
+            continue;
+        }
+        let ndc = clip.truncate() / clip.w;
+        ndc_min = ndc_min.min(ndc);
+        ndc_max = ndc_max.max(ndc);
+    }
+    if ndc_max.x < -1.0 || ndc_min.x > 1.0 || ndc_max.y < -1.0 || ndc_min.y > 1.0 {
+        return None;
+    }
+
+    let tile = |ndc: f32, count: u32| -> u32 {
+        let t = (ndc.clamp(-1.0, 1.0) * 0.5 + 0.5) * count as f32;
+        (t as u32).min(count - 1)
+    };
+    Some((
+        [
+            tile(ndc_min.x, CLUSTER_GRID_X),
+            tile(ndc_min.y, CLUSTER_GRID_Y),
+            depth_slice(depth_min, near, far),
+        ],
+        [
+            tile(ndc_max.x, CLUSTER_GRID_X),
+            tile(ndc_max.y, CLUSTER_GRID_Y),
+            depth_slice(depth_max.min(far), near, far),
+        ],
+    ))
+}
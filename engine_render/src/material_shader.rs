@@ -0,0 +1,341 @@
+//! User material shaders - custom WGSL files under `Assets/Shaders`
+//!
+//! A material shader uses the vertex layout and group 0 bindings of
+//! `LIT_SHADER` and must define `vs_main` and `fs_main`. Its parameters are a
+//! uniform struct at `@group(1) @binding(0)`, reflected from the source so the
+//! editor can show one field per member:
+//!
+//! ```wgsl
+//! struct MaterialParams {
+//!     tint: vec4<f32>,      // @color
+//!     bands: f32,           // @range(1, 8) @default(3)
+//!     rim_power: f32,
+//! }
+//! @group(1) @binding(0) var<uniform> material: MaterialParams;
+//! ```
+//!
+//! Supported member types are `f32`, `vec2<f32>`, `vec3<f32>` and `vec4<f32>`
+//! (and the `vec2f`/`vec3f`/`vec4f` aliases). Values are packed with the WGSL
+//! uniform layout by `MaterialShader::uniform_bytes`.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use engine_core::components::MaterialShaderHandle;
+
+use crate::asset_manager::AssetManager;
+
+/// Bind group of the material uniform block
+pub const MATERIAL_PARAMS_GROUP: u32 = 1;
+
+/// Type of a reflected material parameter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShaderParamKind {
+    Float,
+    Vec2,
+    Vec3,
+    Vec4,
+}
+
+impl ShaderParamKind {
+    fn parse(ty: &str) -> Option<Self> {
+        match ty.replace(' ', "").as_str() {
+            "f32" => Some(ShaderParamKind::Float),
+            "vec2<f32>" | "vec2f" => Some(ShaderParamKind::Vec2),
+            "vec3<f32>" | "vec3f" => Some(ShaderParamKind::Vec3),
+            "vec4<f32>" | "vec4f" => Some(ShaderParamKind::Vec4),
+            _ => None,
+        }
+    }
+
+    /// Number of floats
+    pub fn components(self) -> usize {
+        match self {
+            ShaderParamKind::Float => 1,
+            ShaderParamKind::Vec2 => 2,
+            ShaderParamKind::Vec3 => 3,
+            ShaderParamKind::Vec4 => 4,
+        }
+    }
+
+    /// Alignment in a uniform buffer (bytes)
+    fn align(self) -> usize {
+        match self {
+            ShaderParamKind::Float => 4,
+            ShaderParamKind::Vec2 => 8,
+            ShaderParamKind::Vec3 | ShaderParamKind::Vec4 => 16,
+        }
+    }
+}
+
+/// One member of the material uniform struct
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShaderParam {
+    pub name: String,
+    pub kind: ShaderParamKind,
+    /// Byte offset in the uniform block
+    pub offset: usize,
+    /// Edited with a color picker (`// @color`, vec3/vec4 only)
+    pub color: bool,
+    /// Slider range (`// @range(min, max)`)
+    pub range: Option<(f32, f32)>,
+    /// Initial value (`// @default(...)`); colors default to white, the rest to zero
+    pub default: [f32; 4],
+}
+
+/// A registered material shader and its reflected parameters
+#[derive(Debug, Clone)]
+pub struct MaterialShader {
+    pub name: String,
+    pub source_path: PathBuf,
+    pub source: String,
+    pub params: Vec<ShaderParam>,
+    /// Size of the uniform block in bytes (multiple of 16, 0 without parameters)
+    pub uniform_size: usize,
+}
+
+impl MaterialShader {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let source = fs::read_to_string(path).map_err(|e| format!("Falha ao ler shader: {e}"))?;
+        let name = path
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        let mut shader = Self::from_source(&name, source)?;
+        shader.source_path = path.to_path_buf();
+        Ok(shader)
+    }
+
+    pub fn from_source(name: &str, source: String) -> Result<Self, String> {
+        for entry in ["vs_main", "fs_main"] {
+            if !source.contains(&format!("fn {entry}")) {
+                return Err(format!("Shader \"{name}\" não define `{entry}`"));
+            }
+        }
+        let params = reflect_material_params(&source)?;
+        let uniform_size = params.last().map_or(0, |p| {
+            (p.offset + p.kind.components() * 4).next_multiple_of(16)
+        });
+        Ok(Self {
+            name: name.to_string(),
+            source_path: PathBuf::new(),
+            source,
+            params,
+            uniform_size,
+        })
+    }
+
+    pub fn param(&self, name: &str) -> Option<&ShaderParam> {
+        self.params.iter().find(|p| p.name == name)
+    }
+
+    /// Uniform block with `values` (missing parameters use their default)
+    pub fn uniform_bytes(&self, values: &HashMap<String, [f32; 4]>) -> Vec<u8> {
+        let mut bytes = vec![0_u8; self.uniform_size];
+        for param in &self.params {
+            let value = values.get(&param.name).unwrap_or(&param.default);
+            for (i, component) in value[..param.kind.components()].iter().enumerate() {
+                let at = param.offset + i * 4;
+                bytes[at..at + 4].copy_from_slice(&component.to_le_bytes());
+            }
+        }
+        bytes
+    }
+}
+
+/// Members of the struct bound at `@group(1) @binding(0)`; empty when the
+/// shader has no material uniform
+pub fn reflect_material_params(source: &str) -> Result<Vec<ShaderParam>, String> {
+    let code = strip_block_comments(source);
+    let Some(struct_name) = material_uniform_type(&code) else {
+        return Ok(Vec::new());
+    };
+    let body = struct_body(&code, &struct_name)
+        .ok_or_else(|| format!("Struct `{struct_name}` não encontrada"))?;
+
+    let mut params = Vec::new();
+    let mut offset = 0;
+    for line in body.lines() {
+        let (decl, comment) = line.split_once("//").unwrap_or((line, ""));
+        let decl = decl.trim().trim_end_matches(',');
+        if decl.is_empty() {
+            continue;
+        }
+        // Attributes like @size / @align change the layout; not supported
+        if decl.starts_with('@') {
+            return Err(format!("Atributo não suportado em `{struct_name}`: {decl}"));
+        }
+        let Some((name, ty)) = decl.split_once(':') else {
+            continue;
+        };
+        let name = name.trim().to_string();
+        let kind = ShaderParamKind::parse(ty.trim()).ok_or_else(|| {
+            format!(
+                "Tipo não suportado em `{struct_name}.{name}`: {}",
+                ty.trim()
+            )
+        })?;
+        offset = offset.next_multiple_of(kind.align());
+        let color = comment.contains("@color") && kind.components() >= 3;
+        let default = annotation(comment, "@default")
+            .map(|values| {
+                let mut default = [0.0; 4];
+                for (slot, value) in default.iter_mut().zip(values) {
+                    *slot = value;
+                }
+                default
+            })
+            .unwrap_or(if color { [1.0; 4] } else { [0.0; 4] });
+        let range = annotation(comment, "@range").and_then(|values| match values[..] {
+            [min, max, ..] if min < max => Some((min, max)),
+            _ => None,
+        });
+        params.push(ShaderParam {
+            name,
+            kind,
+            offset,
+            color,
+            range,
+            default,
+        });
+        offset += kind.components() * 4;
+    }
+    Ok(params)
+}
+
+/// Type of the `var<uniform>` declared at the material group / binding 0
+fn material_uniform_type(code: &str) -> Option<String> {
+    let group = format!("@group({MATERIAL_PARAMS_GROUP})");
+    code.split(';').find_map(|statement| {
+        let statement = statement.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !statement.contains(&group)
+            || !statement.contains("@binding(0)")
+            || !statement.contains("var<uniform>")
+        {
+            return None;
+        }
+        let ty = statement.rsplit_once(':')?.1.trim();
+        Some(ty.to_string())
+    })
+}
+
+/// Text between the braces of `struct name { ... }`
+fn struct_body<'a>(code: &'a str, name: &str) -> Option<&'a str> {
+    let mut search = code;
+    loop {
+        let at = search.find("struct ")?;
+        let rest = &search[at + "struct ".len()..];
+        let open = rest.find('{')?;
+        if rest[..open].trim() == name {
+            let close = rest[open..].find('}')?;
+            return Some(&rest[open + 1..open + close]);
+        }
+        search = rest;
+    }
+}
+
+/// Numbers of `@name(a, b, ...)` in a field comment
+fn annotation(comment: &str, name: &str) -> Option<Vec<f32>> {
+    let start = comment.find(&format!("{name}("))? + name.len() + 1;
+    let end = start + comment[start..].find(')')?;
+    let values: Vec<f32> = comment[start..end]
+        .split(',')
+        .filter_map(|v| v.trim().parse().ok())
+        .collect();
+    (!values.is_empty()).then_some(values)
+}
+
+fn strip_block_comments(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut rest = source;
+    while let Some(start) = rest.find("/*") {
+        out.push_str(&rest[..start]);
+        match rest[start..].find("*/") {
+            Some(end) => rest = &rest[start + end + 2..],
+            None => return out,
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+impl AssetManager {
+    /// Load (or reload, when the file changed) a material shader
+    pub fn load_material_shader(&mut self, path: &Path) -> Result<MaterialShaderHandle, String> {
+        let shader = MaterialShader::load(path)?;
+        if let Some((id, existing)) = self
+            .material_shaders
+            .iter_mut()
+            .find(|(_, s)| s.source_path == path)
+        {
+            *existing = shader;
+            return Ok(MaterialShaderHandle { id: *id });
+        }
+        let id = self.next_material_shader_id;
+        self.next_material_shader_id += 1;
+        self.material_shaders.insert(id, shader);
+        Ok(MaterialShaderHandle { id })
+    }
+
+    /// Register every `.wgsl` under `dir` (usually `Assets/Shaders`); shaders
+    /// whose file is gone are unloaded. Returns the files that failed.
+    pub fn register_material_shaders(&mut self, dir: &Path) -> Vec<(PathBuf, String)> {
+        let mut files = Vec::new();
+        collect_wgsl_files(dir, &mut files);
+        self.material_shaders
+            .retain(|_, shader| files.contains(&shader.source_path));
+        let mut errors = Vec::new();
+        for path in files {
+            if let Err(e) = self.load_material_shader(&path) {
+                errors.push((path, e));
+            }
+        }
+        errors
+    }
+
+    pub fn get_material_shader(&self, handle: MaterialShaderHandle) -> Option<&MaterialShader> {
+        self.material_shaders.get(&handle.id)
+    }
+
+    /// Material shader by name (file stem), as referenced by `.mat` files
+    pub fn find_material_shader(&self, name: &str) -> Option<MaterialShaderHandle> {
+        self.material_shaders
+            .iter()
+            .find(|(_, shader)| shader.name.eq_ignore_ascii_case(name))
+            .map(|(id, _)| MaterialShaderHandle { id: *id })
+    }
+
+    /// Registered shaders sorted by name
+    pub fn material_shader_handles(&self) -> Vec<MaterialShaderHandle> {
+        let mut shaders: Vec<(&u64, &MaterialShader)> = self.material_shaders.iter().collect();
+        shaders.sort_by(|a, b| a.1.name.cmp(&b.1.name));
+        shaders
+            .into_iter()
+            .map(|(id, _)| MaterialShaderHandle { id: *id })
+            .collect()
+    }
+
+    pub fn unload_material_shader(&mut self, handle: MaterialShaderHandle) -> bool {
+        self.material_shaders.remove(&handle.id).is_some()
+    }
+}
+
+fn collect_wgsl_files(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut paths: Vec<PathBuf> = entries.flatten().map(|e| e.path()).collect();
+    paths.sort();
+    for path in paths {
+        if path.is_dir() {
+            collect_wgsl_files(&path, out);
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("wgsl"))
+        {
+            out.push(path);
+        }
+    }
+}
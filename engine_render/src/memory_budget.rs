@@ -0,0 +1,169 @@
+//! Memory budgets - per-asset byte accounting with least-recently-used eviction
+//!
+//! Uses are stamped from one process-wide counter (`use_stamp`), so assets
+//! tracked by different budgets and asset managers can be ordered against each
+//! other. `MemoryBudget` keeps the size and last use of the GPU copies of
+//! assets; when the total goes over the limit, `evict` picks the least recently
+//! used evictable entries (those that can be streamed in again) until it fits.
+//! Entries used since `begin_frame` are never picked, so a frame that needs more
+//! than the budget goes over it instead of thrashing.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static USE_CLOCK: AtomicU64 = AtomicU64::new(1);
+
+/// A new use stamp, later than every earlier one
+pub fn use_stamp() -> u64 {
+    USE_CLOCK.fetch_add(1, Ordering::Relaxed)
+}
+
+/// One tracked asset
+#[derive(Debug, Clone)]
+pub struct BudgetEntry {
+    pub name: String,
+    /// Group shown in the breakdown ("Texture", "Mesh", ...)
+    pub category: &'static str,
+    pub bytes: usize,
+    pub last_used: u64,
+    /// Whether it can be dropped and streamed in again later
+    pub evictable: bool,
+}
+
+/// Sizes and last uses of a set of assets, with an optional byte limit
+#[derive(Debug, Clone, Default)]
+pub struct MemoryBudget {
+    /// 0 = no limit
+    limit: usize,
+    entries: HashMap<String, BudgetEntry>,
+    frame_start: u64,
+}
+
+impl MemoryBudget {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            ..Default::default()
+        }
+    }
+
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    pub fn set_limit(&mut self, limit: usize) {
+        self.limit = limit;
+    }
+
+    /// Start a frame: what is used from now on is protected from `evict`
+    pub fn begin_frame(&mut self) {
+        self.frame_start = use_stamp();
+    }
+
+    /// Add or resize an entry and mark it used
+    pub fn track(&mut self, name: &str, category: &'static str, bytes: usize, evictable: bool) {
+        self.entries.insert(
+            name.to_string(),
+            BudgetEntry {
+                name: name.to_string(),
+                category,
+                bytes,
+                last_used: use_stamp(),
+                evictable,
+            },
+        );
+    }
+
+    /// Mark an entry used
+    pub fn touch(&mut self, name: &str) {
+        if let Some(entry) = self.entries.get_mut(name) {
+            entry.last_used = use_stamp();
+        }
+    }
+
+    pub fn forget(&mut self, name: &str) -> Option<BudgetEntry> {
+        self.entries.remove(name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.entries.contains_key(name)
+    }
+
+    pub fn total(&self) -> usize {
+        self.entries.values().map(|entry| entry.bytes).sum()
+    }
+
+    pub fn over_budget(&self) -> bool {
+        self.limit > 0 && self.total() > self.limit
+    }
+
+    /// Every entry, largest first
+    pub fn entries(&self) -> Vec<BudgetEntry> {
+        let mut entries: Vec<BudgetEntry> = self.entries.values().cloned().collect();
+        entries.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.name.cmp(&b.name)));
+        entries
+    }
+
+    /// Bytes per category, largest first
+    pub fn totals_by_category(&self) -> Vec<(&'static str, usize)> {
+        let mut totals: Vec<(&'static str, usize)> = Vec::new();
+        for entry in self.entries.values() {
+            match totals.iter_mut().find(|(c, _)| *c == entry.category) {
+                Some((_, bytes)) => *bytes += entry.bytes,
+                None => totals.push((entry.category, entry.bytes)),
+            }
+        }
+        totals.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        totals
+    }
+
+    /// Stop tracking the least recently used evictable entries until the total
+    /// fits the limit; returns their names for the owner to free
+    pub fn evict(&mut self) -> Vec<String> {
+        if !self.over_budget() {
+            return Vec::new();
+        }
+        let excess = self.total() - self.limit;
+        let candidates = self
+            .entries
+            .values()
+            .filter(|entry| entry.evictable && entry.last_used < self.frame_start)
+            .map(|entry| (entry.name.clone(), entry.last_used, entry.bytes));
+        let victims = pick_lru(candidates, excess);
+        for name in &victims {
+            self.entries.remove(name);
+        }
+        victims
+    }
+}
+
+/// Least recently used `(item, last_used, bytes)` candidates whose sizes add up
+/// to at least `to_free` (all of them when they don't)
+pub fn pick_lru<T>(
+    candidates: impl IntoIterator<Item = (T, u64, usize)>,
+    to_free: usize,
+) -> Vec<T> {
+    let mut candidates: Vec<(T, u64, usize)> = candidates.into_iter().collect();
+    candidates.sort_by_key(|(_, last_used, _)| *last_used);
+    let mut freed = 0;
+    let mut victims = Vec::new();
+    for (item, _, bytes) in candidates {
+        if freed >= to_free {
+            break;
+        }
+        freed += bytes;
+        victims.push(item);
+    }
+    victims
+}
+
+/// GPU bytes of a 2D texture with `mip_levels` levels
+pub fn texture_gpu_bytes(width: u32, height: u32, bytes_per_texel: u32, mip_levels: u32) -> usize {
+    (0..mip_levels.max(1))
+        .map(|level| {
+            let w = (width >> level).max(1) as usize;
+            let h = (height >> level).max(1) as usize;
+            w * h * bytes_per_texel as usize
+        })
+        .sum()
+}
@@ -2,10 +2,22 @@
 //!
 //! MeshData contém geometria pronta para upload à GPU:
 //! posições, normais, UVs e índices.
+//!
+//! Atributos extras por vértice (cores, UVs adicionais como o UV de lightmap e
+//! canais livres como AO baked) ficam em `VertexAttributes`. `MeshData::interleave`
+//! empacota o layout base (`Vertex::to_packed`) seguido dos canais pedidos, na
+//! ordem dada, para shaders que declaram `@location(3)` em diante.
+//!
+//! Na importação, malhas sem normais recebem normais suaves e malhas sem
+//! tangentes recebem tangentes MikkTSpace (as mesmas dos bakers de normal map).
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
-use glam::{Vec2, Vec3};
+use glam::{Mat3, Mat4, Vec2, Vec3};
+
+use crate::asset_manager::AssetManager;
+use crate::gltf_import::ImportedScene;
 
 /// Dados de vértice para renderização
 #[derive(Debug, Clone, Default)]
@@ -47,6 +59,203 @@ pub struct MeshData {
     pub vertices: Vec<Vertex>,
     pub indices: Vec<u32>,
     pub albedo_texture_path: Option<PathBuf>,
+    /// Pesos de skinning por vértice (malhas com ossos)
+    pub skin: Option<MeshSkin>,
+    /// Cores, UVs extras e dados livres por vértice
+    pub attributes: Option<VertexAttributes>,
+}
+
+/// Canal de atributo extra, usado para consultar e empacotar `VertexAttributes`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VertexChannel {
+    /// Cor RGBA linear
+    Color,
+    /// Tangente xyz + sinal da bitangente em w
+    Tangent,
+    /// Conjunto de UV `n` (0 = `Vertex::texcoord`, 1 = primeiro extra, ...)
+    Uv(usize),
+    /// Canal livre pelo nome
+    Custom(String),
+}
+
+/// Canal livre por vértice (ex.: "ao" com 1 componente)
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomAttribute {
+    pub name: String,
+    /// Floats por vértice (1 a 4)
+    pub components: usize,
+    /// `components` valores por vértice, em sequência
+    pub data: Vec<f32>,
+}
+
+/// Tangente usada onde a malha não tem uma (eixo X, bitangente positiva)
+const DEFAULT_TANGENT: [f32; 4] = [1.0, 0.0, 0.0, 1.0];
+
+/// Atributos além de posição/normal/UV0; cada lista tem um valor por vértice
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VertexAttributes {
+    /// Cor RGBA linear (vazio = malha sem cores)
+    pub colors: Vec<[f32; 4]>,
+    /// Tangente MikkTSpace: xyz + sinal da bitangente (vazio = sem tangentes)
+    pub tangents: Vec<[f32; 4]>,
+    /// UVs a partir do conjunto 1 (o 0 é `Vertex::texcoord`); o 1 costuma ser o
+    /// UV de lightmap
+    pub uv_sets: Vec<Vec<Vec2>>,
+    pub custom: Vec<CustomAttribute>,
+}
+
+impl VertexAttributes {
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+            && self.tangents.is_empty()
+            && self.uv_sets.is_empty()
+            && self.custom.is_empty()
+    }
+
+    /// Completa cada canal até `vertex_count` (cor branca, UV e dados zerados)
+    pub fn pad(&mut self, vertex_count: usize) {
+        if !self.colors.is_empty() {
+            self.colors.resize(vertex_count, [1.0; 4]);
+        }
+        if !self.tangents.is_empty() {
+            self.tangents.resize(vertex_count, DEFAULT_TANGENT);
+        }
+        for uvs in &mut self.uv_sets {
+            uvs.resize(vertex_count, Vec2::ZERO);
+        }
+        for channel in &mut self.custom {
+            channel.data.resize(vertex_count * channel.components, 0.0);
+        }
+    }
+
+    /// Anexa os atributos de outra malha; `base` e `count` são os vértices antes
+    /// e depois do anexo. Canais que só existem de um lado são completados
+    pub fn append(&mut self, other: &VertexAttributes, base: usize, count: usize) {
+        if !other.colors.is_empty() || !self.colors.is_empty() {
+            self.colors.resize(base, [1.0; 4]);
+            self.colors.extend_from_slice(&other.colors);
+        }
+        if !other.tangents.is_empty() || !self.tangents.is_empty() {
+            self.tangents.resize(base, DEFAULT_TANGENT);
+            self.tangents.extend_from_slice(&other.tangents);
+        }
+        if self.uv_sets.len() < other.uv_sets.len() {
+            self.uv_sets
+                .resize(other.uv_sets.len(), vec![Vec2::ZERO; base]);
+        }
+        for (set, uvs) in self.uv_sets.iter_mut().enumerate() {
+            uvs.resize(base, Vec2::ZERO);
+            if let Some(other_uvs) = other.uv_sets.get(set) {
+                uvs.extend_from_slice(other_uvs);
+            }
+        }
+        for other_channel in &other.custom {
+            match self
+                .custom
+                .iter_mut()
+                .find(|c| c.name == other_channel.name)
+            {
+                Some(channel) if channel.components == other_channel.components => {
+                    channel.data.resize(base * channel.components, 0.0);
+                    channel.data.extend_from_slice(&other_channel.data);
+                }
+                // Mesmo nome com outro tamanho: mantém o canal existente
+                Some(_) => {}
+                None => {
+                    let mut data = vec![0.0; base * other_channel.components];
+                    data.extend_from_slice(&other_channel.data);
+                    self.custom.push(CustomAttribute {
+                        name: other_channel.name.clone(),
+                        components: other_channel.components,
+                        data,
+                    });
+                }
+            }
+        }
+        self.pad(count);
+    }
+
+    /// Floats por vértice do canal (None se a malha não tem o canal)
+    pub fn components(&self, channel: &VertexChannel) -> Option<usize> {
+        match channel {
+            VertexChannel::Color => (!self.colors.is_empty()).then_some(4),
+            VertexChannel::Tangent => (!self.tangents.is_empty()).then_some(4),
+            VertexChannel::Uv(0) => Some(2),
+            VertexChannel::Uv(set) => (*set <= self.uv_sets.len()).then_some(2),
+            VertexChannel::Custom(name) => self
+                .custom
+                .iter()
+                .find(|c| &c.name == name)
+                .map(|c| c.components),
+        }
+    }
+
+    pub fn custom(&self, name: &str) -> Option<&CustomAttribute> {
+        self.custom.iter().find(|c| c.name == name)
+    }
+
+    /// Adiciona (ou substitui) um canal livre; `data` deve ter `components`
+    /// valores por vértice
+    pub fn set_custom(&mut self, name: &str, components: usize, data: Vec<f32>) {
+        let components = components.clamp(1, 4);
+        self.custom.retain(|c| c.name != name);
+        self.custom.push(CustomAttribute {
+            name: name.to_string(),
+            components,
+            data,
+        });
+    }
+}
+
+/// Skinning: ossos da malha e até 4 influências por vértice
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MeshSkin {
+    /// Nome de cada osso, na ordem dos índices de `joints`
+    pub bones: Vec<String>,
+    /// Inversa da matriz de mundo de cada osso na pose de bind
+    pub inverse_bind_matrices: Vec<Mat4>,
+    pub joints: Vec<[u16; 4]>,
+    pub weights: Vec<[f32; 4]>,
+}
+
+impl MeshSkin {
+    /// Completa com vértices sem influência até `vertex_count`
+    pub fn pad(&mut self, vertex_count: usize) {
+        self.joints.resize(vertex_count, [0; 4]);
+        self.weights.resize(vertex_count, [0.0; 4]);
+    }
+
+    /// Anexa as influências de outra skin, unificando os ossos pelo nome
+    pub fn append(&mut self, other: &MeshSkin) {
+        let remap: Vec<u16> = other
+            .bones
+            .iter()
+            .enumerate()
+            .map(
+                |(i, bone)| match self.bones.iter().position(|b| b == bone) {
+                    Some(index) => index as u16,
+                    None => {
+                        self.bones.push(bone.clone());
+                        self.inverse_bind_matrices.push(
+                            other
+                                .inverse_bind_matrices
+                                .get(i)
+                                .copied()
+                                .unwrap_or(Mat4::IDENTITY),
+                        );
+                        (self.bones.len() - 1) as u16
+                    }
+                },
+            )
+            .collect();
+        self.joints.extend(
+            other
+                .joints
+                .iter()
+                .map(|joints| joints.map(|j| remap.get(j as usize).copied().unwrap_or(0))),
+        );
+        self.weights.extend_from_slice(&other.weights);
+    }
 }
 
 impl Default for MeshData {
@@ -56,12 +265,14 @@ impl Default for MeshData {
             vertices: Vec::new(),
             indices: Vec::new(),
             albedo_texture_path: None,
+            skin: None,
+            attributes: None,
         }
     }
 }
 
 impl MeshData {
-    /// Carrega mesh de arquivo (suporta .obj, .gltf, .glb)
+    /// Carrega mesh de arquivo (suporta .obj, .gltf, .glb, .fbx)
     pub fn load_from_file(path: &Path) -> Result<Self, String> {
         let ext = path
             .extension()
@@ -70,56 +281,34 @@ impl MeshData {
             .ok_or("Sem extensão de arquivo")?;
 
         match ext.as_str() {
-            "obj" => Self::load_obj(path),
+            "obj" => Self::load_merged(path, AssetManager::import_obj),
             "gltf" | "glb" => Self::load_gltf(path),
+            "fbx" => Self::load_merged(path, AssetManager::import_fbx),
             _ => Err(format!("Formato não suportado: {}", ext)),
         }
     }
 
-    /// Carrega arquivo OBJ
-    fn load_obj(path: &Path) -> Result<Self, String> {
-        let load_options = tobj::LoadOptions {
-            triangulate: true,
-            single_index: false,
-            ignore_points: true,
-            ignore_lines: true,
-        };
-        let (models, _) = tobj::load_obj(path, &load_options)
-            .map_err(|e| format!("Falha ao carregar OBJ: {}", e))?;
-
-        let mut vertices = Vec::new();
-        let mut indices = Vec::new();
-
-        for model in models {
-            let mesh = &model.mesh;
-            let positions = &mesh.positions;
-            let normals = &mesh.normals;
-            let texcoords = &mesh.texcoords;
-            let indices_raw = &mesh.indices;
-
-            for i in 0..positions.len() / 3 {
-                let position =
-                    Vec3::new(positions[i * 3], positions[i * 3 + 1], positions[i * 3 + 2]);
-
-                let normal = if normals.len() >= (i + 1) * 3 {
-                    Vec3::new(normals[i * 3], normals[i * 3 + 1], normals[i * 3 + 2])
-                } else {
-                    Vec3::ZERO // Será calculada depois
-                };
-
-                let texcoord = if texcoords.len() >= (i + 1) * 2 {
-                    Vec2::new(texcoords[i * 2], texcoords[i * 2 + 1])
-                } else {
-                    Vec2::ZERO
-                };
-
-                vertices.push(Vertex::new(position, normal, texcoord));
-            }
+    /// Importa a cena completa (OBJ/MTL, FBX) e une todas as malhas numa só
+    fn load_merged(
+        path: &Path,
+        import: fn(&mut AssetManager, &Path) -> Result<ImportedScene, String>,
+    ) -> Result<Self, String> {
+        let mut assets = AssetManager::new();
+        let scene = import(&mut assets, path)?;
+        let mut mesh = scene.merged_mesh(&assets);
+        mesh.name = path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        Ok(mesh)
+    }
 
-            for &idx in indices_raw {
-                indices.push(idx);
-            }
-        }
+    /// Carrega arquivo GLTF/GLB como uma única malha (nós achatados no espaço do modelo).
+    /// Para importar a hierarquia completa use `AssetManager::import_gltf`.
+    fn load_gltf(path: &Path) -> Result<Self, String> {
+        let (document, buffers, _) =
+            gltf::import(path).map_err(|e| format!("Falha ao carregar GLTF: {}", e))?;
 
         let mut mesh = Self {
             name: path
@@ -127,94 +316,259 @@ impl MeshData {
                 .unwrap_or_default()
                 .to_string_lossy()
                 .to_string(),
-            vertices,
-            indices,
-            albedo_texture_path: None,
+            ..Default::default()
         };
 
-        // Se não tinha normais, calcula a partir dos triângulos
+        let roots: Vec<gltf::Node> = match document
+            .default_scene()
+            .or_else(|| document.scenes().next())
+        {
+            Some(scene) => scene.nodes().collect(),
+            None => document.nodes().collect(),
+        };
+        let mut stack: Vec<(gltf::Node, Mat4)> =
+            roots.into_iter().map(|n| (n, Mat4::IDENTITY)).collect();
+        while let Some((node, parent)) = stack.pop() {
+            let world = parent * Mat4::from_cols_array_2d(&node.transform().matrix());
+            if let Some(gltf_mesh) = node.mesh() {
+                for primitive in gltf_mesh.primitives() {
+                    if let Some(part) = Self::from_gltf_primitive("", &primitive, &buffers) {
+                        mesh.append_transformed(&part, world);
+                    }
+                }
+            }
+            stack.extend(node.children().map(|c| (c, world)));
+        }
+
+        if mesh.indices.is_empty() {
+            return Err("GLTF sem triângulos suportados".to_string());
+        }
         mesh.ensure_normals();
         Ok(mesh)
     }
 
-    /// Carrega arquivo GLTF/GLB
-    fn load_gltf(path: &Path) -> Result<Self, String> {
-        let file = std::fs::read(path).map_err(|e| format!("Falha ao ler arquivo: {}", e))?;
-        let document =
-            gltf::Gltf::from_slice(&file).map_err(|e| format!("Falha ao parsear GLTF: {}", e))?;
-
-        let mut vertices = Vec::new();
-        let mut indices = Vec::new();
-        let albedo_texture_path: Option<PathBuf> = None;
-
-        // Carrega dados dos buffers
-        let mut buffer_data = Vec::new();
-        for buffer in document.buffers() {
-            let start = buffer.index() * buffer.length();
-            let end = start + buffer.length();
-            if end <= file.len() {
-                buffer_data.push(file[start..end].to_vec());
-            } else {
-                buffer_data.push(Vec::new());
+    /// Lê uma primitiva de triângulos do glTF; None para outros modos ou sem posições
+    pub(crate) fn from_gltf_primitive(
+        name: &str,
+        primitive: &gltf::Primitive,
+        buffers: &[gltf::buffer::Data],
+    ) -> Option<Self> {
+        if primitive.mode() != gltf::mesh::Mode::Triangles {
+            return None;
+        }
+        let reader = primitive.reader(|buffer| buffers.get(buffer.index()).map(|d| d.0.as_slice()));
+
+        let mut vertices: Vec<Vertex> = reader
+            .read_positions()?
+            .map(|p| Vertex::new(Vec3::from_array(p), Vec3::ZERO, Vec2::ZERO))
+            .collect();
+        if let Some(normals) = reader.read_normals() {
+            for (vertex, n) in vertices.iter_mut().zip(normals) {
+                vertex.normal = Vec3::from_array(n);
             }
         }
+        if let Some(texcoords) = reader.read_tex_coords(0) {
+            for (vertex, uv) in vertices.iter_mut().zip(texcoords.into_f32()) {
+                vertex.texcoord = Vec2::from_array(uv);
+            }
+        }
+        let attributes = Self::gltf_vertex_attributes(primitive, buffers, vertices.len());
 
-        for mesh in document.meshes() {
-            for primitive in mesh.primitives() {
-                let reader =
-                    primitive.reader(|buffer| Some(buffer_data[buffer.index()].as_slice()));
+        let count = vertices.len() as u32;
+        let indices: Vec<u32> = match reader.read_indices() {
+            Some(indices) => indices.into_u32().collect(),
+            None => (0..count).collect(),
+        };
+        let indices: Vec<u32> = indices
+            .chunks_exact(3)
+            .filter(|tri| tri.iter().all(|i| *i < count))
+            .flatten()
+            .copied()
+            .collect();
+        if indices.is_empty() {
+            return None;
+        }
 
-                let base_vertex = vertices.len();
+        let mut mesh = Self {
+            name: name.to_string(),
+            vertices,
+            indices,
+            albedo_texture_path: None,
+            skin: None,
+            attributes,
+        };
+        mesh.ensure_normals();
+        mesh.ensure_tangents();
+        Some(mesh)
+    }
 
-                if let Some(positions) = reader.read_positions() {
-                    for position in positions {
-                        vertices.push(Vertex {
-                            position: Vec3::new(position[0], position[1], position[2]),
-                            normal: Vec3::ZERO,
-                            texcoord: Vec2::ZERO,
-                        });
-                    }
+    /// COLOR_0, TEXCOORD_1.. e atributos `_NOME` (float, 1 a 4 componentes)
+    fn gltf_vertex_attributes(
+        primitive: &gltf::Primitive,
+        buffers: &[gltf::buffer::Data],
+        vertex_count: usize,
+    ) -> Option<VertexAttributes> {
+        let get_buffer = gltf_buffer_data(buffers);
+        let reader = primitive.reader(get_buffer);
+        let mut attributes = VertexAttributes::default();
+        if let Some(colors) = reader.read_colors(0) {
+            attributes.colors = colors.into_rgba_f32().collect();
+        }
+        if let Some(tangents) = reader.read_tangents() {
+            attributes.tangents = tangents.collect();
+        }
+        let mut set = 1;
+        while let Some(texcoords) = reader.read_tex_coords(set) {
+            attributes
+                .uv_sets
+                .push(texcoords.into_f32().map(Vec2::from_array).collect());
+            set += 1;
+        }
+        for (semantic, accessor) in primitive.attributes() {
+            let gltf::Semantic::Extras(name) = semantic else {
+                continue;
+            };
+            if accessor.data_type() != gltf::accessor::DataType::F32 {
+                continue;
+            }
+            let data: Option<Vec<f32>> = match accessor.dimensions() {
+                gltf::accessor::Dimensions::Scalar => {
+                    gltf::accessor::Iter::<f32>::new(accessor, get_buffer).map(|it| it.collect())
                 }
-
-                if let Some(normals) = reader.read_normals() {
-                    for (i, normal) in normals.enumerate() {
-                        let idx = base_vertex + i;
-                        if idx < vertices.len() {
-                            vertices[idx].normal = Vec3::new(normal[0], normal[1], normal[2]);
-                        }
-                    }
+                gltf::accessor::Dimensions::Vec2 => {
+                    gltf::accessor::Iter::<[f32; 2]>::new(accessor, get_buffer)
+                        .map(|it| it.flatten().collect())
                 }
-
-                if let Some(texcoords) = reader.read_tex_coords(0) {
-                    for (i, texcoord) in texcoords.into_f32().enumerate() {
-                        let idx = base_vertex + i;
-                        if idx < vertices.len() {
-                            vertices[idx].texcoord = Vec2::new(texcoord[0], texcoord[1]);
-                        }
-                    }
+                gltf::accessor::Dimensions::Vec3 => {
+                    gltf::accessor::Iter::<[f32; 3]>::new(accessor, get_buffer)
+                        .map(|it| it.flatten().collect())
                 }
+                gltf::accessor::Dimensions::Vec4 => {
+                    gltf::accessor::Iter::<[f32; 4]>::new(accessor, get_buffer)
+                        .map(|it| it.flatten().collect())
+                }
+                _ => None,
+            };
+            if let Some(data) = data {
+                let components = data.len() / vertex_count.max(1);
+                // O glTF exige o prefixo "_" nos atributos da aplicação
+                attributes.set_custom(name.trim_start_matches('_'), components, data);
+            }
+        }
+        attributes.pad(vertex_count);
+        (!attributes.is_empty()).then_some(attributes)
+    }
 
-                if let Some(indices_data) = reader.read_indices() {
-                    for idx in indices_data.into_u32() {
-                        indices.push(idx);
-                    }
+    /// Anexa outra malha transformada por `matrix`
+    pub fn append_transformed(&mut self, other: &MeshData, matrix: Mat4) {
+        let base = self.vertices.len() as u32;
+        let normal_matrix = Mat3::from_mat4(matrix).inverse().transpose();
+        self.vertices.extend(other.vertices.iter().map(|v| Vertex {
+            position: matrix.transform_point3(v.position),
+            normal: (normal_matrix * v.normal).normalize_or_zero(),
+            texcoord: v.texcoord,
+        }));
+        self.indices.extend(other.indices.iter().map(|i| base + i));
+
+        if let Some(other_attributes) = &other.attributes {
+            // Tangentes seguem a parte linear da matriz; o sinal inverte com espelhamento
+            let transformed;
+            let mut other_attributes = other_attributes;
+            if !other_attributes.tangents.is_empty() {
+                let linear = Mat3::from_mat4(matrix);
+                let flip = if linear.determinant() < 0.0 {
+                    -1.0
+                } else {
+                    1.0
+                };
+                let mut attributes = other_attributes.clone();
+                for t in &mut attributes.tangents {
+                    let xyz = (linear * Vec3::new(t[0], t[1], t[2])).normalize_or(Vec3::X);
+                    *t = [xyz.x, xyz.y, xyz.z, t[3] * flip];
                 }
+                transformed = attributes;
+                other_attributes = &transformed;
             }
+            self.attributes
+                .get_or_insert_with(VertexAttributes::default)
+                .append(other_attributes, base as usize, self.vertices.len());
+        } else if let Some(attributes) = &mut self.attributes {
+            attributes.pad(self.vertices.len());
         }
 
-        let mut mesh = Self {
-            name: path
-                .file_name()
-                .unwrap_or_default()
-                .to_string_lossy()
-                .to_string(),
-            vertices,
-            indices,
-            albedo_texture_path,
-        };
+        if let Some(other_skin) = &other.skin {
+            let skin = self.skin.get_or_insert_with(MeshSkin::default);
+            skin.pad(base as usize);
+            skin.append(other_skin);
+        } else if let Some(skin) = &mut self.skin {
+            skin.pad(self.vertices.len());
+        }
+    }
 
-        mesh.ensure_normals();
-        Ok(mesh)
+    /// Valores de um canal extra no vértice `index` (cor e UVs com padrão
+    /// quando a malha não tem o canal)
+    pub fn channel_value(&self, channel: &VertexChannel, index: usize) -> Option<Vec<f32>> {
+        let attributes = self.attributes.as_ref();
+        match channel {
+            VertexChannel::Color => Some(
+                attributes
+                    .and_then(|a| a.colors.get(index))
+                    .copied()
+                    .unwrap_or([1.0; 4])
+                    .to_vec(),
+            ),
+            VertexChannel::Tangent => Some(
+                attributes
+                    .and_then(|a| a.tangents.get(index))
+                    .copied()
+                    .unwrap_or(DEFAULT_TANGENT)
+                    .to_vec(),
+            ),
+            VertexChannel::Uv(0) => self
+                .vertices
+                .get(index)
+                .map(|v| v.texcoord.to_array().to_vec()),
+            VertexChannel::Uv(set) => Some(
+                attributes
+                    .and_then(|a| a.uv_sets.get(set - 1))
+                    .and_then(|uvs| uvs.get(index))
+                    .copied()
+                    .unwrap_or(Vec2::ZERO)
+                    .to_array()
+                    .to_vec(),
+            ),
+            VertexChannel::Custom(name) => {
+                let custom = attributes?.custom(name)?;
+                let start = index * custom.components;
+                Some(
+                    custom
+                        .data
+                        .get(start..start + custom.components)
+                        .map_or_else(|| vec![0.0; custom.components], <[f32]>::to_vec),
+                )
+            }
+        }
+    }
+
+    /// Vértices intercalados para a GPU: `Vertex::to_packed` seguido dos canais
+    /// pedidos, na ordem dada. Retorna os floats e o stride em floats; canais
+    /// livres ausentes na malha são ignorados
+    pub fn interleave(&self, channels: &[VertexChannel]) -> (Vec<f32>, usize) {
+        let channels: Vec<&VertexChannel> = channels
+            .iter()
+            .filter(|c| {
+                !matches!(c, VertexChannel::Custom(_)) || self.channel_value(c, 0).is_some()
+            })
+            .collect();
+        let mut data = Vec::new();
+        for (index, vertex) in self.vertices.iter().enumerate() {
+            data.extend_from_slice(&vertex.to_packed());
+            for channel in &channels {
+                data.extend(self.channel_value(channel, index).unwrap_or_default());
+            }
+        }
+        let stride = data.len().checked_div(self.vertices.len()).unwrap_or(8);
+        (data, stride)
     }
 
     /// Garante que todos os vértices tenham normais válidas.
@@ -229,12 +583,9 @@ impl MeshData {
             return;
         }
 
-        // Zera todas as normais e recalcula
-        for v in &mut self.vertices {
-            v.normal = Vec3::ZERO;
-        }
-
-        // Acumula normais por face em cada vértice (smooth shading)
+        // Acumula normais por face em cada posição (smooth shading), para que
+        // vértices separados só por UV não criem costuras de iluminação
+        let mut by_position: HashMap<[u32; 3], Vec3> = HashMap::new();
         for tri in self.indices.chunks(3) {
             if tri.len() < 3 {
                 continue;
@@ -252,9 +603,15 @@ impl MeshData {
             let edge2 = p2 - p0;
             let face_normal = edge1.cross(edge2);
             // Não normaliza aqui — a magnitude pondera pela área do triângulo
-            self.vertices[i0].normal += face_normal;
-            self.vertices[i1].normal += face_normal;
-            self.vertices[i2].normal += face_normal;
+            for p in [p0, p1, p2] {
+                *by_position.entry(position_key(p)).or_insert(Vec3::ZERO) += face_normal;
+            }
+        }
+        for v in &mut self.vertices {
+            v.normal = by_position
+                .get(&position_key(v.position))
+                .copied()
+                .unwrap_or(Vec3::ZERO);
         }
 
         // Normaliza todas
@@ -268,6 +625,40 @@ impl MeshData {
         }
     }
 
+    pub fn has_tangents(&self) -> bool {
+        self.attributes
+            .as_ref()
+            .is_some_and(|a| a.tangents.len() == self.vertices.len() && !a.tangents.is_empty())
+    }
+
+    /// Gera tangentes MikkTSpace se a malha não as tiver (precisa de normais e UV0)
+    pub fn ensure_tangents(&mut self) {
+        if !self.has_tangents() {
+            self.generate_tangents();
+        }
+    }
+
+    /// Recalcula as tangentes MikkTSpace; retorna false se a geometria for inválida
+    pub fn generate_tangents(&mut self) -> bool {
+        let count = self.vertices.len();
+        if self.indices.is_empty() || self.indices.iter().any(|i| *i as usize >= count) {
+            return false;
+        }
+        let mut geometry = TangentGeometry {
+            vertices: &self.vertices,
+            indices: &self.indices,
+            tangents: vec![DEFAULT_TANGENT; count],
+        };
+        if !mikktspace::generate_tangents(&mut geometry) {
+            return false;
+        }
+        let tangents = geometry.tangents;
+        self.attributes
+            .get_or_insert_with(VertexAttributes::default)
+            .tangents = tangents;
+        true
+    }
+
     /// Cria mesh de cubo
     pub fn cube() -> Self {
         let positions = [
@@ -376,6 +767,8 @@ impl MeshData {
             vertices,
             indices: indices.to_vec(),
             albedo_texture_path: None,
+            skin: None,
+            attributes: None,
         }
     }
 
@@ -435,6 +828,8 @@ impl MeshData {
             vertices,
             indices,
             albedo_texture_path: None,
+            skin: None,
+            attributes: None,
         }
     }
 
@@ -472,6 +867,8 @@ impl MeshData {
             vertices,
             indices: indices.to_vec(),
             albedo_texture_path: None,
+            skin: None,
+            attributes: None,
         }
     }
 
@@ -522,3 +919,58 @@ pub fn compute_flat_normals(positions: &[Vec3], triangles: &[[u32; 3]]) -> Vec<V
 
     normals
 }
+
+/// Acesso aos buffers carregados pelo `gltf::import` (leitores e acessores)
+fn gltf_buffer_data<'s>(
+    buffers: &'s [gltf::buffer::Data],
+) -> impl Fn(gltf::Buffer<'_>) -> Option<&'s [u8]> + Copy + 's {
+    move |buffer| buffers.get(buffer.index()).map(|d| d.0.as_slice())
+}
+
+/// Chave exata de posição para agrupar vértices coincidentes
+pub(crate) fn position_key(p: Vec3) -> [u32; 3] {
+    // -0.0 e 0.0 são a mesma posição
+    let bits = |v: f32| if v == 0.0 { 0 } else { v.to_bits() };
+    [bits(p.x), bits(p.y), bits(p.z)]
+}
+
+/// Malha indexada vista pelo MikkTSpace; vértices compartilhados recebem a
+/// tangente do último canto escrito
+struct TangentGeometry<'a> {
+    vertices: &'a [Vertex],
+    indices: &'a [u32],
+    tangents: Vec<[f32; 4]>,
+}
+
+impl TangentGeometry<'_> {
+    fn vertex(&self, face: usize, vert: usize) -> &Vertex {
+        &self.vertices[self.indices[face * 3 + vert] as usize]
+    }
+}
+
+impl mikktspace::Geometry for TangentGeometry<'_> {
+    fn num_faces(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    fn num_vertices_of_face(&self, _face: usize) -> usize {
+        3
+    }
+
+    fn position(&self, face: usize, vert: usize) -> [f32; 3] {
+        self.vertex(face, vert).position.to_array()
+    }
+
+    fn normal(&self, face: usize, vert: usize) -> [f32; 3] {
+        self.vertex(face, vert).normal.to_array()
+    }
+
+    fn tex_coord(&self, face: usize, vert: usize) -> [f32; 2] {
+        self.vertex(face, vert).texcoord.to_array()
+    }
+
+    fn set_tangent_encoded(&mut self, tangent: [f32; 4], face: usize, vert: usize) {
+        let index = self.indices[face * 3 + vert] as usize;
+        self.tangents[index] = tangent;
+    }
+}
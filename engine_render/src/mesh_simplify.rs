@@ -0,0 +1,409 @@
+//! Simplificação de malhas para gerar níveis de LOD
+//!
+//! Colapso de arestas guiado por quádricas de erro (Garland-Heckbert): cada
+//! colapso move um vértice para o outro extremo da aresta, escolhendo o de menor
+//! erro. Vértices na mesma posição (costuras de UV/normal) colapsam juntos, e as
+//! bordas abertas recebem planos extras para não encolherem.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+use glam::{DVec3, Vec3};
+
+use crate::mesh::{CustomAttribute, MeshData, MeshSkin, Vertex, VertexAttributes, position_key};
+
+/// Peso dos planos de borda em relação aos planos das faces
+const BOUNDARY_WEIGHT: f64 = 100.0;
+
+/// Quádrica de erro simétrica 4x4 (10 coeficientes)
+#[derive(Clone, Copy, Default)]
+struct Quadric([f64; 10]);
+
+impl Quadric {
+    /// Distância ao quadrado ao plano `n·p + d = 0`, multiplicada por `weight`
+    fn plane(n: DVec3, d: f64, weight: f64) -> Self {
+        let (a, b, c) = (n.x, n.y, n.z);
+        Self([
+            a * a,
+            a * b,
+            a * c,
+            a * d,
+            b * b,
+            b * c,
+            b * d,
+            c * c,
+            c * d,
+            d * d,
+        ])
+        .scaled(weight)
+    }
+
+    fn scaled(mut self, weight: f64) -> Self {
+        for q in &mut self.0 {
+            *q *= weight;
+        }
+        self
+    }
+
+    fn add(&mut self, other: &Quadric) {
+        for (q, o) in self.0.iter_mut().zip(other.0) {
+            *q += o;
+        }
+    }
+
+    fn error(&self, p: DVec3) -> f64 {
+        let q = &self.0;
+        let (x, y, z) = (p.x, p.y, p.z);
+        q[0] * x * x
+            + 2.0 * q[1] * x * y
+            + 2.0 * q[2] * x * z
+            + 2.0 * q[3] * x
+            + q[4] * y * y
+            + 2.0 * q[5] * y * z
+            + 2.0 * q[6] * y
+            + q[7] * z * z
+            + 2.0 * q[8] * z
+            + q[9]
+    }
+}
+
+/// Colapso candidato de `from` em `to`; válido enquanto os carimbos dos dois nós
+/// não mudarem
+struct Collapse {
+    cost: f64,
+    from: usize,
+    to: usize,
+    stamps: (u32, u32),
+}
+
+impl PartialEq for Collapse {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Collapse {}
+
+impl PartialOrd for Collapse {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Collapse {
+    // Invertido: o BinaryHeap devolve primeiro o menor custo
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.total_cmp(&self.cost)
+    }
+}
+
+/// Estado do colapso: nós são posições únicas, triângulos guardam os vértices
+/// originais para preservar UVs e normais
+struct Simplifier<'a> {
+    triangles: Vec<[usize; 3]>,
+    node_of: &'a [usize],
+    positions: Vec<DVec3>,
+    parent: Vec<usize>,
+    quadrics: Vec<Quadric>,
+    stamps: Vec<u32>,
+    alive: Vec<bool>,
+    node_triangles: Vec<Vec<usize>>,
+}
+
+impl Simplifier<'_> {
+    /// Nó atual de um nó original (segue os colapsos)
+    fn find(&mut self, mut node: usize) -> usize {
+        while self.parent[node] != node {
+            self.parent[node] = self.parent[self.parent[node]];
+            node = self.parent[node];
+        }
+        node
+    }
+
+    fn triangle_nodes(&mut self, triangle: usize) -> [usize; 3] {
+        let [a, b, c] = self.triangles[triangle];
+        [
+            self.find(self.node_of[a]),
+            self.find(self.node_of[b]),
+            self.find(self.node_of[c]),
+        ]
+    }
+
+    /// Colapso da aresta `a`-`b` no extremo de menor erro
+    fn candidate(&self, a: usize, b: usize) -> Collapse {
+        let mut q = self.quadrics[a];
+        q.add(&self.quadrics[b]);
+        let (cost_a, cost_b) = (q.error(self.positions[a]), q.error(self.positions[b]));
+        let (from, to, cost) = if cost_a <= cost_b {
+            (b, a, cost_a)
+        } else {
+            (a, b, cost_b)
+        };
+        Collapse {
+            cost,
+            from,
+            to,
+            stamps: (self.stamps[from], self.stamps[to]),
+        }
+    }
+
+    /// O colapso inverteria (ou degeneraria) algum triângulo que sobrevive?
+    fn flips(&mut self, from: usize, to: usize) -> bool {
+        let triangles = self.node_triangles[from].clone();
+        for t in triangles {
+            if !self.alive[t] {
+                continue;
+            }
+            let nodes = self.triangle_nodes(t);
+            if nodes.contains(&to) {
+                continue;
+            }
+            let old = nodes.map(|n| self.positions[n]);
+            let new = nodes.map(|n| self.positions[if n == from { to } else { n }]);
+            let old_normal = (old[1] - old[0]).cross(old[2] - old[0]);
+            let new_normal = (new[1] - new[0]).cross(new[2] - new[0]);
+            if new_normal.length_squared() <= f64::EPSILON * old_normal.length_squared()
+                || old_normal.dot(new_normal) <= 0.0
+            {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// Reduz a malha para cerca de `ratio` (0..1) dos triângulos. Vértices mantêm
+/// normais, UVs e atributos; só as posições colapsam
+pub fn simplify_mesh(mesh: &MeshData, ratio: f32) -> MeshData {
+    engine_core::profile_function!();
+    let triangle_count = mesh.indices.len() / 3;
+    let target = ((triangle_count as f32 * ratio.clamp(0.0, 1.0)).ceil() as usize).max(1);
+    let vertex_count = mesh.vertices.len();
+    if target >= triangle_count || mesh.indices.iter().any(|i| *i as usize >= vertex_count) {
+        return mesh.clone();
+    }
+
+    // Vértices coincidentes formam um único nó, para as costuras não abrirem buracos
+    let mut positions = Vec::new();
+    let mut by_position = HashMap::new();
+    let node_of: Vec<usize> = mesh
+        .vertices
+        .iter()
+        .map(|v| {
+            *by_position
+                .entry(position_key(v.position))
+                .or_insert_with(|| {
+                    positions.push(v.position.as_dvec3());
+                    positions.len() - 1
+                })
+        })
+        .collect();
+    let node_count = positions.len();
+
+    let mut simplifier = Simplifier {
+        triangles: mesh
+            .indices
+            .chunks_exact(3)
+            .map(|t| [t[0] as usize, t[1] as usize, t[2] as usize])
+            .collect(),
+        node_of: &node_of,
+        positions,
+        parent: (0..node_count).collect(),
+        quadrics: vec![Quadric::default(); node_count],
+        stamps: vec![0; node_count],
+        alive: Vec::new(),
+        node_triangles: vec![Vec::new(); node_count],
+    };
+
+    // Planos das faces (ponderados pela área) e contagem de uso das arestas
+    let mut edge_faces: HashMap<(usize, usize), (u32, DVec3)> = HashMap::new();
+    for t in 0..simplifier.triangles.len() {
+        let nodes = simplifier.triangles[t].map(|v| node_of[v]);
+        let alive = nodes[0] != nodes[1] && nodes[1] != nodes[2] && nodes[0] != nodes[2];
+        simplifier.alive.push(alive);
+        if !alive {
+            continue;
+        }
+        let p = nodes.map(|n| simplifier.positions[n]);
+        let cross = (p[1] - p[0]).cross(p[2] - p[0]);
+        let length = cross.length();
+        let normal = if length > 0.0 {
+            cross / length
+        } else {
+            DVec3::ZERO
+        };
+        let quadric = Quadric::plane(normal, -normal.dot(p[0]), length * 0.5);
+        for n in nodes {
+            simplifier.node_triangles[n].push(t);
+            simplifier.quadrics[n].add(&quadric);
+        }
+        for (a, b) in [(0, 1), (1, 2), (2, 0)] {
+            let entry = edge_faces
+                .entry((nodes[a].min(nodes[b]), nodes[a].max(nodes[b])))
+                .or_insert((0, normal));
+            entry.0 += 1;
+        }
+    }
+
+    // Bordas abertas: plano perpendicular à face ao longo da aresta
+    for (&(a, b), &(uses, normal)) in &edge_faces {
+        if uses != 1 {
+            continue;
+        }
+        let edge = simplifier.positions[b] - simplifier.positions[a];
+        let side = edge.cross(normal).normalize_or_zero();
+        let quadric = Quadric::plane(
+            side,
+            -side.dot(simplifier.positions[a]),
+            edge.length_squared() * BOUNDARY_WEIGHT,
+        );
+        simplifier.quadrics[a].add(&quadric);
+        simplifier.quadrics[b].add(&quadric);
+    }
+
+    let mut heap: BinaryHeap<Collapse> = edge_faces
+        .keys()
+        .map(|&(a, b)| simplifier.candidate(a, b))
+        .collect();
+    let mut live = simplifier.alive.iter().filter(|a| **a).count();
+    while live > target {
+        let Some(collapse) = heap.pop() else {
+            break;
+        };
+        let (from, to) = (collapse.from, collapse.to);
+        let stale = simplifier.parent[from] != from
+            || simplifier.parent[to] != to
+            || collapse.stamps != (simplifier.stamps[from], simplifier.stamps[to]);
+        if stale || simplifier.flips(from, to) {
+            continue;
+        }
+
+        simplifier.parent[from] = to;
+        let quadric = simplifier.quadrics[from];
+        simplifier.quadrics[to].add(&quadric);
+        simplifier.stamps[from] += 1;
+        simplifier.stamps[to] += 1;
+        for t in std::mem::take(&mut simplifier.node_triangles[from]) {
+            if !simplifier.alive[t] {
+                continue;
+            }
+            let nodes = simplifier.triangle_nodes(t);
+            if nodes[0] == nodes[1] || nodes[1] == nodes[2] || nodes[0] == nodes[2] {
+                simplifier.alive[t] = false;
+                live -= 1;
+            } else {
+                simplifier.node_triangles[to].push(t);
+            }
+        }
+
+        // Novos candidatos em volta do nó resultante
+        let mut triangles = std::mem::take(&mut simplifier.node_triangles[to]);
+        triangles.retain(|t| simplifier.alive[*t]);
+        let mut neighbors = Vec::new();
+        for &t in &triangles {
+            for n in simplifier.triangle_nodes(t) {
+                if n != to && !neighbors.contains(&n) {
+                    neighbors.push(n);
+                }
+            }
+        }
+        simplifier.node_triangles[to] = triangles;
+        for n in neighbors {
+            heap.push(simplifier.candidate(to, n));
+        }
+    }
+
+    let positions: Vec<Vec3> = (0..vertex_count)
+        .map(|v| {
+            let node = simplifier.find(node_of[v]);
+            simplifier.positions[node].as_vec3()
+        })
+        .collect();
+    let indices: Vec<u32> = (0..simplifier.triangles.len())
+        .filter(|t| simplifier.alive[*t])
+        .flat_map(|t| simplifier.triangles[t].map(|v| v as u32))
+        .collect();
+    compact(mesh, &positions, &indices)
+}
+
+/// Níveis de LOD: o nível `k` (1..=levels) fica com `ratio^k` dos triângulos
+/// da malha original
+pub fn generate_lods(mesh: &MeshData, ratio: f32, levels: usize) -> Vec<MeshData> {
+    (1..=levels)
+        .map(|level| {
+            let mut lod = simplify_mesh(mesh, ratio.powi(level as i32));
+            lod.name = format!("{}_LOD{}", mesh.name, level);
+            lod
+        })
+        .collect()
+}
+
+/// Nova malha só com os vértices usados por `indices`, nas posições dadas
+fn compact(mesh: &MeshData, positions: &[Vec3], indices: &[u32]) -> MeshData {
+    let mut remap = vec![u32::MAX; mesh.vertices.len()];
+    let mut keep = Vec::new();
+    let indices = indices
+        .iter()
+        .map(|&i| {
+            let slot = &mut remap[i as usize];
+            if *slot == u32::MAX {
+                *slot = keep.len() as u32;
+                keep.push(i as usize);
+            }
+            *slot
+        })
+        .collect();
+
+    let vertices = keep
+        .iter()
+        .map(|&i| Vertex {
+            position: positions[i],
+            ..mesh.vertices[i].clone()
+        })
+        .collect();
+    let attributes = mesh.attributes.as_ref().map(|a| VertexAttributes {
+        colors: pick(&a.colors, &keep),
+        tangents: pick(&a.tangents, &keep),
+        uv_sets: a.uv_sets.iter().map(|uvs| pick(uvs, &keep)).collect(),
+        custom: a
+            .custom
+            .iter()
+            .map(|channel| CustomAttribute {
+                name: channel.name.clone(),
+                components: channel.components,
+                data: keep
+                    .iter()
+                    .flat_map(|&i| {
+                        let start = i * channel.components;
+                        channel
+                            .data
+                            .get(start..start + channel.components)
+                            .unwrap_or(&[])
+                            .iter()
+                            .copied()
+                    })
+                    .collect(),
+            })
+            .collect(),
+    });
+    let skin = mesh.skin.as_ref().map(|skin| MeshSkin {
+        bones: skin.bones.clone(),
+        inverse_bind_matrices: skin.inverse_bind_matrices.clone(),
+        joints: pick(&skin.joints, &keep),
+        weights: pick(&skin.weights, &keep),
+    });
+    MeshData {
+        name: mesh.name.clone(),
+        vertices,
+        indices,
+        albedo_texture_path: mesh.albedo_texture_path.clone(),
+        skin,
+        attributes,
+    }
+}
+
+fn pick<T: Copy>(values: &[T], keep: &[usize]) -> Vec<T> {
+    keep.iter()
+        .filter_map(|&i| values.get(i).copied())
+        .collect()
+}
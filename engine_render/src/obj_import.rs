@@ -0,0 +1,191 @@
+//! Importação Wavefront OBJ + MTL (e exportação de malhas para OBJ)
+//!
+//! Cada objeto/grupo do OBJ vira um nó com uma malha. Os materiais do .mtl
+//! (cor difusa, opacidade, brilho e textura map_Kd) são criados no `AssetManager`
+//! e atribuídos às primitivas conforme o `usemtl` de cada grupo.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use engine_core::components::{TextureHandle, Transform};
+use glam::{Vec2, Vec3};
+
+use crate::asset_manager::{AssetManager, MaterialData, TextureData};
+use crate::gltf_import::{ImportedNode, ImportedPrimitive, ImportedScene};
+use crate::mesh::{MeshData, Vertex, VertexAttributes};
+
+impl AssetManager {
+    /// Importa um .obj com os materiais do .mtl referenciado por `mtllib`
+    pub fn import_obj(&mut self, path: &Path) -> Result<ImportedScene, String> {
+        engine_core::profile_function!();
+        let load_options = tobj::LoadOptions {
+            triangulate: true,
+            single_index: true,
+            ignore_points: true,
+            ignore_lines: true,
+        };
+        let (models, materials) = tobj::load_obj(path, &load_options)
+            .map_err(|e| format!("Falha ao carregar OBJ: {}", e))?;
+        // .mtl ausente ou inválido não impede a importação da geometria
+        let materials = materials.unwrap_or_default();
+        let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+        let file_stem = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("obj")
+            .to_string();
+
+        let mut scene = ImportedScene {
+            name: file_stem.clone(),
+            source: path.to_path_buf(),
+            ..Default::default()
+        };
+
+        // Materiais; texturas compartilhadas entre materiais são carregadas uma vez
+        let mut textures: HashMap<PathBuf, Option<TextureHandle>> = HashMap::new();
+        let mut material_handles = Vec::with_capacity(materials.len());
+        for (index, material) in materials.iter().enumerate() {
+            let texture_path = material
+                .diffuse_texture
+                .as_ref()
+                .map(|t| base_dir.join(t.trim().replace('\\', "/")))
+                .filter(|p| p.is_file());
+            let texture = texture_path.as_ref().and_then(|p| {
+                *textures.entry(p.clone()).or_insert_with(|| {
+                    let image = image::open(p).ok()?.to_rgba8();
+                    let handle = self.add_texture(TextureData {
+                        name: p
+                            .file_stem()
+                            .unwrap_or_default()
+                            .to_string_lossy()
+                            .to_string(),
+                        width: image.width(),
+                        height: image.height(),
+                        rgba: image.into_raw(),
+                        hdr_rgba: None,
+                        source_path: Some(p.clone()),
+                    });
+                    scene.textures.push(handle);
+                    Some(handle)
+                })
+            });
+            let [r, g, b] = material.diffuse.unwrap_or([1.0; 3]);
+            let handle = self.add_material(MaterialData {
+                name: if material.name.is_empty() {
+                    format!("{}_material_{}", file_stem, index)
+                } else {
+                    material.name.clone()
+                },
+                albedo: [r, g, b, material.dissolve.unwrap_or(1.0).clamp(0.0, 1.0)],
+                metallic: 0.0,
+                roughness: material
+                    .shininess
+                    .map(MaterialData::roughness_from_shininess)
+                    .unwrap_or(0.5),
+                albedo_texture: texture_path.map(|p| p.to_string_lossy().to_string()),
+                albedo_texture_handle: texture,
+                ..Default::default()
+            });
+            scene.materials.push(handle);
+            material_handles.push(handle);
+        }
+
+        // Um nó por objeto/grupo, todos na raiz
+        for (index, model) in models.iter().enumerate() {
+            let mesh = &model.mesh;
+            if mesh.indices.len() < 3 {
+                continue;
+            }
+            let vertices = (0..mesh.positions.len() / 3)
+                .map(|i| {
+                    let position = Vec3::from_slice(&mesh.positions[i * 3..i * 3 + 3]);
+                    let normal = mesh
+                        .normals
+                        .get(i * 3..i * 3 + 3)
+                        .map(Vec3::from_slice)
+                        .unwrap_or(Vec3::ZERO);
+                    // OBJ usa V de baixo para cima; o motor segue a convenção do glTF
+                    let texcoord = mesh
+                        .texcoords
+                        .get(i * 2..i * 2 + 2)
+                        .map(|uv| Vec2::new(uv[0], 1.0 - uv[1]))
+                        .unwrap_or(Vec2::ZERO);
+                    Vertex::new(position, normal, texcoord)
+                })
+                .collect();
+            let name = if model.name.is_empty() {
+                format!("{}_{}", file_stem, index)
+            } else {
+                model.name.clone()
+            };
+            let material = mesh
+                .material_id
+                .and_then(|i| material_handles.get(i))
+                .copied();
+            // Cores por vértice da extensão "v x y z r g b"
+            let attributes = (!mesh.vertex_color.is_empty()).then(|| {
+                let mut attributes = VertexAttributes {
+                    colors: mesh
+                        .vertex_color
+                        .chunks_exact(3)
+                        .map(|c| [c[0], c[1], c[2], 1.0])
+                        .collect(),
+                    ..Default::default()
+                };
+                attributes.pad(vertices.len());
+                attributes
+            });
+            let mut data = MeshData {
+                name: name.clone(),
+                vertices,
+                indices: mesh.indices.clone(),
+                albedo_texture_path: material
+                    .and_then(|m| self.get_material(m))
+                    .and_then(|m| m.albedo_texture.as_ref())
+                    .map(PathBuf::from),
+                skin: None,
+                attributes,
+            };
+            data.ensure_normals();
+            data.ensure_tangents();
+            let handle = self.add_mesh(data);
+            scene.meshes.push(handle);
+            scene.roots.push(scene.nodes.len());
+            scene.nodes.push(ImportedNode {
+                name,
+                parent: None,
+                children: Vec::new(),
+                transform: Transform::default(),
+                primitives: vec![ImportedPrimitive {
+                    mesh: handle,
+                    material,
+                }],
+            });
+        }
+
+        if scene.meshes.is_empty() {
+            return Err("OBJ sem vértices/triângulos".to_string());
+        }
+        Ok(scene)
+    }
+}
+
+/// Grava a malha como .obj (posições, UVs e normais; sem materiais). Usado pelos
+/// níveis de LOD gerados na importação
+pub fn write_obj(mesh: &MeshData, path: &Path) -> Result<(), String> {
+    let mut out = String::new();
+    let _ = writeln!(out, "# Dengine mesh\no {}", mesh.name);
+    for v in &mesh.vertices {
+        let (p, n) = (v.position, v.normal);
+        let _ = writeln!(out, "v {} {} {}", p.x, p.y, p.z);
+        // Volta para V de baixo para cima
+        let _ = writeln!(out, "vt {} {}", v.texcoord.x, 1.0 - v.texcoord.y);
+        let _ = writeln!(out, "vn {} {} {}", n.x, n.y, n.z);
+    }
+    for tri in mesh.indices.chunks_exact(3) {
+        let [a, b, c] = [tri[0] + 1, tri[1] + 1, tri[2] + 1];
+        let _ = writeln!(out, "f {a}/{a}/{a} {b}/{b}/{b} {c}/{c}/{c}");
+    }
+    std::fs::write(path, out).map_err(|e| format!("Falha ao salvar {}: {}", path.display(), e))
+}
@@ -0,0 +1,252 @@
+//! Particle system - emitter component, lifetime curves and GPU simulation data
+//!
+//! Simulation runs on the GPU (see `PARTICLE_SHADER`). The CPU side only keeps
+//! a `ParticleSpawner` per emitter, which decides how many ring-buffer slots are
+//! respawned each frame, and packs the emitter settings into the uniform block.
+
+use glam::{Mat4, Vec3};
+
+use crate::curve::{Curve, CurveKey, TangentMode};
+use crate::shader::PARTICLE_UNIFORM_SIZE;
+
+/// Upper bound on the particle buffer of a single emitter
+pub const MAX_EMITTER_PARTICLES: u32 = 16_384;
+
+/// Samples per lifetime curve in the uniform block
+pub const PARTICLE_CURVE_SAMPLES: usize = 16;
+
+/// Curve over the normalized particle age (0 = birth, 1 = death)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParticleCurve {
+    curve: Curve,
+}
+
+impl Default for ParticleCurve {
+    fn default() -> Self {
+        Self::constant(1.0)
+    }
+}
+
+impl ParticleCurve {
+    pub fn constant(value: f32) -> Self {
+        Self {
+            curve: Curve::constant(value),
+        }
+    }
+
+    /// Straight line from `start` at birth to `end` at death
+    pub fn linear(start: f32, end: f32) -> Self {
+        Self {
+            curve: Curve::linear(start, end),
+        }
+    }
+
+    /// Linear segments through the keys; ages are clamped to [0, 1]
+    pub fn from_keys(keys: impl IntoIterator<Item = (f32, f32)>) -> Self {
+        Self::from_curve(Curve::from_points(keys, TangentMode::Linear))
+    }
+
+    /// Keys outside [0, 1] are clamped to it; an empty curve is 1
+    pub fn from_curve(curve: Curve) -> Self {
+        if curve.is_empty() {
+            return Self::default();
+        }
+        let mut clamped = Curve::default();
+        for key in curve.keys() {
+            clamped.insert_key(CurveKey {
+                time: key.time.clamp(0.0, 1.0),
+                ..*key
+            });
+        }
+        Self { curve: clamped }
+    }
+
+    pub fn curve(&self) -> &Curve {
+        &self.curve
+    }
+
+    pub fn evaluate(&self, t: f32) -> f32 {
+        self.curve.evaluate(t).unwrap_or(1.0)
+    }
+}
+
+/// Flipbook layout of the particle texture
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextureSheet {
+    pub columns: u32,
+    pub rows: u32,
+    /// Times the animation plays over a particle's life (0 = first frame only)
+    pub cycles: f32,
+}
+
+impl Default for TextureSheet {
+    fn default() -> Self {
+        Self {
+            columns: 1,
+            rows: 1,
+            cycles: 1.0,
+        }
+    }
+}
+
+/// Particle emitter component
+///
+/// `[min, max]` pairs are start values picked at random per particle; the
+/// curves scale size, alpha and speed over the particle's life.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParticleEmitter {
+    pub enabled: bool,
+    pub max_particles: u32,
+    /// Particles spawned per second
+    pub rate: f32,
+    pub lifetime: [f32; 2],
+    pub start_speed: [f32; 2],
+    pub start_size: [f32; 2],
+    pub start_color: [f32; 4],
+    pub end_color: [f32; 4],
+    /// Half-angle of the emission cone around the local +Y, in degrees
+    pub cone_angle: f32,
+    pub gravity: f32,
+    pub size_over_lifetime: ParticleCurve,
+    pub alpha_over_lifetime: ParticleCurve,
+    pub speed_over_lifetime: ParticleCurve,
+    pub texture: Option<String>,
+    pub sheet: TextureSheet,
+    pub additive: bool,
+}
+
+impl Default for ParticleEmitter {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_particles: 1_000,
+            rate: 50.0,
+            lifetime: [1.5, 2.5],
+            start_speed: [1.5, 2.5],
+            start_size: [0.15, 0.3],
+            start_color: [1.0, 1.0, 1.0, 1.0],
+            end_color: [1.0, 1.0, 1.0, 1.0],
+            cone_angle: 20.0,
+            gravity: 0.0,
+            size_over_lifetime: ParticleCurve::constant(1.0),
+            alpha_over_lifetime: ParticleCurve::linear(1.0, 0.0),
+            speed_over_lifetime: ParticleCurve::constant(1.0),
+            texture: None,
+            sheet: TextureSheet::default(),
+            additive: false,
+        }
+    }
+}
+
+impl ParticleEmitter {
+    /// Size of the particle buffer (ring) on the GPU
+    pub fn capacity(&self) -> u32 {
+        self.max_particles.clamp(1, MAX_EMITTER_PARTICLES)
+    }
+}
+
+/// Slots respawned by the GPU this frame
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ParticleStep {
+    pub first: u32,
+    pub count: u32,
+    pub dt: f32,
+    pub seed: u32,
+}
+
+/// Per-emitter emission state (fractional particles, ring cursor, frame seed)
+#[derive(Debug, Clone, Default)]
+pub struct ParticleSpawner {
+    accumulator: f32,
+    cursor: u32,
+    frame: u32,
+}
+
+impl ParticleSpawner {
+    /// Advance emission by `dt`; frame hitches are clamped so a long stall
+    /// does not flush the whole buffer at once
+    pub fn advance(&mut self, emitter: &ParticleEmitter, dt: f32) -> ParticleStep {
+        engine_core::profile_function!();
+        let capacity = emitter.capacity();
+        let dt = dt.clamp(0.0, 0.1);
+        if emitter.enabled {
+            self.accumulator += emitter.rate.max(0.0) * dt;
+        }
+        let count = (self.accumulator.floor() as u32).min(capacity);
+        self.accumulator = (self.accumulator - count as f32).min(1.0);
+        let first = self.cursor % capacity;
+        self.cursor = (first + count) % capacity;
+        // The seed goes through an f32 uniform, so it stays below 2^24
+        self.frame = (self.frame + 1) & 0x00FF_FFFF;
+        ParticleStep {
+            first,
+            count,
+            dt,
+            seed: self.frame,
+        }
+    }
+}
+
+/// Camera data the billboards face
+#[derive(Debug, Clone, Copy)]
+pub struct ParticleView {
+    pub view_proj: Mat4,
+    pub camera_right: Vec3,
+    pub camera_up: Vec3,
+}
+
+/// Uniform block of `PARTICLE_SHADER` for one emitter
+pub fn particle_uniform_bytes(
+    emitter: &ParticleEmitter,
+    transform: Mat4,
+    view: &ParticleView,
+    step: &ParticleStep,
+    has_texture: bool,
+) -> [u8; PARTICLE_UNIFORM_SIZE] {
+    let mut floats: Vec<f32> = Vec::with_capacity(PARTICLE_UNIFORM_SIZE / 4);
+    floats.extend_from_slice(&view.view_proj.to_cols_array());
+    floats.extend_from_slice(&transform.to_cols_array());
+    floats.extend_from_slice(&view.camera_right.extend(step.dt).to_array());
+    floats.extend_from_slice(&view.camera_up.extend(0.0).to_array());
+    floats.extend_from_slice(&[
+        step.first as f32,
+        step.count as f32,
+        emitter.capacity() as f32,
+        step.seed as f32,
+    ]);
+    floats.extend_from_slice(&[
+        emitter.lifetime[0].max(0.0),
+        emitter.lifetime[1].max(emitter.lifetime[0]),
+        emitter.start_speed[0],
+        emitter.start_speed[1],
+    ]);
+    floats.extend_from_slice(&[
+        emitter.start_size[0].max(0.0),
+        emitter.start_size[1].max(0.0),
+        emitter.cone_angle.clamp(0.0, 180.0).to_radians(),
+        emitter.gravity,
+    ]);
+    floats.extend_from_slice(&emitter.start_color);
+    floats.extend_from_slice(&emitter.end_color);
+    floats.extend_from_slice(&[
+        emitter.sheet.columns.max(1) as f32,
+        emitter.sheet.rows.max(1) as f32,
+        emitter.sheet.cycles.max(0.0),
+        if has_texture { 1.0 } else { 0.0 },
+    ]);
+    for i in 0..PARTICLE_CURVE_SAMPLES {
+        let t = i as f32 / (PARTICLE_CURVE_SAMPLES - 1) as f32;
+        floats.extend_from_slice(&[
+            emitter.size_over_lifetime.evaluate(t).max(0.0),
+            emitter.alpha_over_lifetime.evaluate(t).clamp(0.0, 1.0),
+            emitter.speed_over_lifetime.evaluate(t),
+            0.0,
+        ]);
+    }
+
+    let mut bytes = [0_u8; PARTICLE_UNIFORM_SIZE];
+    for (chunk, value) in bytes.chunks_exact_mut(4).zip(floats) {
+        chunk.copy_from_slice(&value.to_le_bytes());
+    }
+    bytes
+}
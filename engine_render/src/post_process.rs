@@ -0,0 +1,226 @@
+//! Post-process chain
+//!
+//! The main pass renders into an offscreen HDR color target; the passes below run
+//! on that image before it is presented (see `POST_SHADER`). The chain order is
+//! fixed: auto exposure, bloom, exposure + tonemapping, color grading LUT,
+//! vignette. Passes whose settings make them a no-op are left out.
+
+use std::path::Path;
+
+use engine_core::components::{PostProcessSettings, TextureHandle, Tonemapping};
+
+use crate::asset_manager::{AssetManager, TextureData};
+use crate::shader::POST_UNIFORM_SIZE;
+use crate::vfs::open_image;
+
+/// Bloom is computed at 1/`BLOOM_DOWNSCALE` of the scene resolution
+pub const BLOOM_DOWNSCALE: u32 = 2;
+
+/// Smallest and largest supported color grading LUT sizes
+pub const MIN_LUT_SIZE: u32 = 2;
+pub const MAX_LUT_SIZE: u32 = 64;
+
+/// One step of the post-process chain
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PostProcessPass {
+    /// Average scene luminance drives the exposure (see `EXPOSURE_SHADER`)
+    AutoExposure {
+        min: f32,
+        max: f32,
+        /// Adaptation speed, in 1/seconds
+        speed: f32,
+    },
+    /// Bright pass + separable blur, added back before tonemapping
+    Bloom {
+        threshold: f32,
+        intensity: f32,
+    },
+    /// Exposure and tonemapping operator
+    Tonemap {
+        exposure: f32,
+        tonemapping: Tonemapping,
+    },
+    /// 3D LUT stored as a horizontal strip
+    ColorGrading {
+        lut: TextureHandle,
+        contribution: f32,
+    },
+    Vignette {
+        intensity: f32,
+        smoothness: f32,
+    },
+}
+
+/// Ordered passes for the given settings (empty when post-processing is disabled)
+pub fn post_process_chain(settings: &PostProcessSettings) -> Vec<PostProcessPass> {
+    let mut chain = Vec::new();
+    if !settings.enabled {
+        return chain;
+    }
+    if settings.auto_exposure {
+        let min = settings.auto_exposure_min.max(1e-3);
+        chain.push(PostProcessPass::AutoExposure {
+            min,
+            max: settings.auto_exposure_max.max(min),
+            speed: settings.auto_exposure_speed.max(0.0),
+        });
+    }
+    if settings.bloom_enabled && settings.bloom_intensity > 0.0 {
+        chain.push(PostProcessPass::Bloom {
+            threshold: settings.bloom_threshold.max(0.0),
+            intensity: settings.bloom_intensity,
+        });
+    }
+    chain.push(PostProcessPass::Tonemap {
+        exposure: settings.exposure.max(0.0),
+        tonemapping: settings.tonemapping,
+    });
+    if let Some(lut) = settings
+        .color_lut
+        .filter(|lut| lut.is_valid() && settings.lut_contribution > 0.0)
+    {
+        chain.push(PostProcessPass::ColorGrading {
+            lut,
+            contribution: settings.lut_contribution.min(1.0),
+        });
+    }
+    if settings.vignette_intensity > 0.0 {
+        chain.push(PostProcessPass::Vignette {
+            intensity: settings.vignette_intensity.min(1.0),
+            smoothness: settings.vignette_smoothness.clamp(0.01, 1.0),
+        });
+    }
+    chain
+}
+
+/// Uniform bytes for `POST_SHADER` built from a chain.
+/// `lut_size` is the size of the bound LUT (0 when none is bound),
+/// `blur_step` the blur offset in UV units (only read by `fs_blur`) and
+/// `delta_time` the frame time in seconds (auto exposure adaptation).
+pub fn post_uniform_bytes(
+    chain: &[PostProcessPass],
+    lut_size: u32,
+    blur_step: [f32; 2],
+    delta_time: f32,
+) -> [u8; POST_UNIFORM_SIZE] {
+    // Identity values: passes missing from the chain leave the image untouched
+    let mut exposure_tonemap = [1.0, 0.0, 0.0, 0.0];
+    let mut vignette_lut = [0.0, 0.5, 0.0, 0.0];
+    let mut auto_exposure = [0.0, 1.0, 1.0, 1.0];
+    for pass in chain {
+        match *pass {
+            PostProcessPass::AutoExposure { min, max, speed } => {
+                // Frame-rate independent exponential adaptation
+                let blend = 1.0 - (-speed * delta_time.max(0.0)).exp();
+                auto_exposure = [1.0, min, max, blend];
+            }
+            PostProcessPass::Bloom {
+                threshold,
+                intensity,
+            } => {
+                exposure_tonemap[2] = intensity;
+                exposure_tonemap[3] = threshold;
+            }
+            PostProcessPass::Tonemap {
+                exposure,
+                tonemapping,
+            } => {
+                exposure_tonemap[0] = exposure;
+                exposure_tonemap[1] = match tonemapping {
+                    Tonemapping::None => 0.0,
+                    Tonemapping::Reinhard => 1.0,
+                    Tonemapping::Aces => 2.0,
+                };
+            }
+            PostProcessPass::ColorGrading { contribution, .. } => {
+                if lut_size >= MIN_LUT_SIZE {
+                    vignette_lut[2] = contribution;
+                    vignette_lut[3] = lut_size as f32;
+                }
+            }
+            PostProcessPass::Vignette {
+                intensity,
+                smoothness,
+            } => {
+                vignette_lut[0] = intensity;
+                vignette_lut[1] = smoothness;
+            }
+        }
+    }
+
+    let mut bytes = [0_u8; POST_UNIFORM_SIZE];
+    let values = exposure_tonemap
+        .into_iter()
+        .chain(vignette_lut)
+        .chain([blur_step[0], blur_step[1], 0.0, 0.0])
+        .chain(auto_exposure);
+    for (i, f) in values.enumerate() {
+        bytes[i * 4..i * 4 + 4].copy_from_slice(&f.to_le_bytes());
+    }
+    bytes
+}
+
+/// Size of a LUT strip (`size * size` wide, `size` tall), None if the layout is wrong
+pub fn lut_strip_size(width: u32, height: u32) -> Option<u32> {
+    ((MIN_LUT_SIZE..=MAX_LUT_SIZE).contains(&height) && width == height * height).then_some(height)
+}
+
+/// Identity LUT strip, useful as a starting point for grading in an image editor
+pub fn identity_lut(size: u32) -> TextureData {
+    let size = size.clamp(MIN_LUT_SIZE, MAX_LUT_SIZE);
+    let max = (size - 1) as f32;
+    let mut rgba = Vec::with_capacity((size * size * size * 4) as usize);
+    for g in 0..size {
+        for b in 0..size {
+            for r in 0..size {
+                for c in [r, g, b] {
+                    rgba.push((c as f32 / max * 255.0).round() as u8);
+                }
+                rgba.push(255);
+            }
+        }
+    }
+    TextureData {
+        name: format!("identity_lut_{}", size),
+        width: size * size,
+        height: size,
+        rgba,
+        hdr_rgba: None,
+        source_path: None,
+    }
+}
+
+impl AssetManager {
+    /// Load a color grading LUT strip (e.g. 256x16 or 1024x32 PNG)
+    pub fn load_color_lut(&mut self, path: &Path) -> Result<TextureHandle, String> {
+        if let Some((id, _)) = self
+            .textures
+            .iter()
+            .find(|(_, tex)| tex.source_path.as_deref() == Some(path))
+        {
+            return Ok(TextureHandle { id: *id });
+        }
+
+        let image = open_image(path)?;
+        let rgba = image.to_rgba8();
+        let (width, height) = rgba.dimensions();
+        if lut_strip_size(width, height).is_none() {
+            return Err(format!(
+                "LUT deve ser uma faixa de N fatias NxN (largura = altura², {}..{}): {}x{}",
+                MIN_LUT_SIZE, MAX_LUT_SIZE, width, height
+            ));
+        }
+        Ok(self.add_texture(TextureData {
+            name: path
+                .file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string(),
+            width,
+            height,
+            rgba: rgba.into_raw(),
+            hdr_rgba: None,
+            source_path: Some(path.to_path_buf()),
+        }))
+    }
+}
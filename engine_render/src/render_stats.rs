@@ -0,0 +1,36 @@
+//! Render statistics - draw calls and triangles encoded per frame
+//!
+//! Render passes call `RenderCounters::record_draw` for every draw they encode.
+//! The counters are atomics so passes recorded from shared paint callbacks can
+//! add to them; the editor `take`s the totals once per frame.
+
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+
+/// Totals of one frame
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RenderStats {
+    pub draw_calls: u32,
+    pub triangles: u64,
+}
+
+/// Counters shared by the render passes of a frame
+#[derive(Debug, Default)]
+pub struct RenderCounters {
+    draw_calls: AtomicU32,
+    triangles: AtomicU64,
+}
+
+impl RenderCounters {
+    pub fn record_draw(&self, triangles: u64) {
+        self.draw_calls.fetch_add(1, Ordering::Relaxed);
+        self.triangles.fetch_add(triangles, Ordering::Relaxed);
+    }
+
+    /// Totals since the last call, resetting the counters
+    pub fn take(&self) -> RenderStats {
+        RenderStats {
+            draw_calls: self.draw_calls.swap(0, Ordering::Relaxed),
+            triangles: self.triangles.swap(0, Ordering::Relaxed),
+        }
+    }
+}
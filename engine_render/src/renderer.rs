@@ -2,17 +2,21 @@
 //!
 //! The renderer takes the ECS world and produces a texture that can be displayed by the editor.
 
+use engine_core::components::{Camera, PostProcessSettings};
 use engine_core::ecs::EngineWorld;
 use engine_core::systems::{CameraSystem, RenderSystem, Renderable};
 
 use crate::asset_manager::AssetManager;
 use crate::mesh::MeshData;
+use crate::sprite::{SpriteBatch, build_sprite_batches};
+use crate::text::{TextBatch, build_text_batches, build_ui_text_batches};
 
 /// Render pass configuration
 pub struct RenderConfig {
     pub clear_color: [f32; 4],
     pub width: u32,
     pub height: u32,
+    pub anti_aliasing: AntiAliasing,
 }
 
 impl Default for RenderConfig {
@@ -21,6 +25,90 @@ impl Default for RenderConfig {
             clear_color: [0.1, 0.1, 0.1, 1.0],
             width: 1920,
             height: 1080,
+            anti_aliasing: AntiAliasing::default(),
+        }
+    }
+}
+
+/// Anti-aliasing of the scene pass
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AntiAliasing {
+    Off,
+    /// Multisampling with the given sample count (see `MSAA_SAMPLE_COUNTS`)
+    Msaa(u32),
+    /// Fast approximate AA, a post-process pass over the final image
+    Fxaa,
+}
+
+impl Default for AntiAliasing {
+    fn default() -> Self {
+        AntiAliasing::Msaa(4)
+    }
+}
+
+impl AntiAliasing {
+    /// MSAA sample counts offered to the user
+    pub const MSAA_SAMPLE_COUNTS: [u32; 3] = [2, 4, 8];
+
+    /// Every selectable mode, in menu order
+    pub fn all() -> [AntiAliasing; 5] {
+        [
+            AntiAliasing::Off,
+            AntiAliasing::Fxaa,
+            AntiAliasing::Msaa(2),
+            AntiAliasing::Msaa(4),
+            AntiAliasing::Msaa(8),
+        ]
+    }
+
+    /// Sample count of the scene color/depth targets
+    pub fn sample_count(self) -> u32 {
+        match self {
+            AntiAliasing::Msaa(samples) => samples.max(1),
+            _ => 1,
+        }
+    }
+
+    /// Mode actually used on a device supporting `supported_samples`:
+    /// an unsupported MSAA sample count falls back to FXAA
+    pub fn resolve(self, supported_samples: &[u32]) -> Self {
+        match self {
+            AntiAliasing::Msaa(samples) if !supported_samples.contains(&samples) => {
+                AntiAliasing::Fxaa
+            }
+            mode => mode,
+        }
+    }
+
+    /// Value stored in config files
+    pub fn as_config_str(self) -> &'static str {
+        match self {
+            AntiAliasing::Off => "off",
+            AntiAliasing::Fxaa => "fxaa",
+            AntiAliasing::Msaa(2) => "msaa2",
+            AntiAliasing::Msaa(8) => "msaa8",
+            AntiAliasing::Msaa(_) => "msaa4",
+        }
+    }
+
+    /// Parse a value written by `as_config_str`
+    pub fn from_config_str(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "off" | "none" => Some(AntiAliasing::Off),
+            "fxaa" => Some(AntiAliasing::Fxaa),
+            "msaa2" => Some(AntiAliasing::Msaa(2)),
+            "msaa4" | "msaa" => Some(AntiAliasing::Msaa(4)),
+            "msaa8" => Some(AntiAliasing::Msaa(8)),
+            _ => None,
+        }
+    }
+
+    /// Short label for menus
+    pub fn label(self) -> String {
+        match self {
+            AntiAliasing::Off => "Off".to_string(),
+            AntiAliasing::Fxaa => "FXAA".to_string(),
+            AntiAliasing::Msaa(samples) => format!("MSAA {}x", samples),
         }
     }
 }
@@ -72,11 +160,19 @@ impl Renderer {
         self.camera.look_at(glam::Vec3::new(x, y, z));
     }
 
+    /// Render through the game camera entity (see `CameraSystem::game_camera`);
+    /// false when the world has none and the current view is kept
+    pub fn use_game_camera(&mut self, world: &EngineWorld) -> bool {
+        let Some((_, camera, transform)) = CameraSystem::game_camera(world) else {
+            return false;
+        };
+        self.camera.set_from_camera(&camera, &transform);
+        true
+    }
+
     /// Update camera aspect ratio (call on resize)
     pub fn set_viewport_size(&mut self, width: u32, height: u32) {
-        if height > 0 {
-            self.camera.set_aspect_ratio(width as f32 / height as f32);
-        }
+        self.camera.set_viewport_size(width, height);
     }
 
     /// Load a mesh and return handle
@@ -92,6 +188,28 @@ impl Renderer {
         self.asset_manager.create_cube()
     }
 
+    /// Load a texture and return handle
+    pub fn load_texture(
+        &mut self,
+        path: &std::path::Path,
+    ) -> Result<engine_core::components::TextureHandle, String> {
+        self.asset_manager.load_texture(path)
+    }
+
+    /// Load a font and return handle
+    pub fn load_font(
+        &mut self,
+        path: &std::path::Path,
+    ) -> Result<engine_core::components::FontHandle, String> {
+        self.asset_manager.load_font(path)
+    }
+
+    /// Load every sprite atlas under a VFS mount point; sprites whose textures
+    /// were packed are then drawn from the atlas pages
+    pub fn load_sprite_atlases(&mut self, point: &str) -> Vec<(std::path::PathBuf, String)> {
+        self.asset_manager.load_sprite_atlases(point)
+    }
+
     /// Get mesh data
     pub fn get_mesh(&self, handle: engine_core::components::MeshHandle) -> Option<&MeshData> {
         self.asset_manager.get_mesh(handle)
@@ -99,13 +217,52 @@ impl Renderer {
 
     /// Render the world and return renderables for external rendering
     pub fn render(&mut self, world: &EngineWorld) -> RenderOutput {
+        engine_core::profile_function!();
+        // LOD groups pick their level from the distance to the render camera
         let mut render_system = RenderSystem;
-        let renderables = render_system.update(world);
+        let renderables = render_system.update_lod(world, self.camera.position);
+
+        // Post-process chain of the game camera, else the first camera that has one enabled
+        let game_camera = CameraSystem::game_camera(world).map(|(entity, _, _)| entity);
+        let post_process = game_camera
+            .and_then(|entity| world.world().get::<&PostProcessSettings>(entity).ok())
+            .map(|settings| *settings)
+            .filter(|settings| settings.enabled)
+            .or_else(|| {
+                world
+                    .world()
+                    .query::<(&Camera, &PostProcessSettings)>()
+                    .iter()
+                    .map(|(_, settings)| *settings)
+                    .find(|settings| settings.enabled)
+            });
+
+        let sprites = build_sprite_batches(
+            world,
+            self.asset_manager.sprite_atlases(),
+            self.camera.position,
+            self.camera.target,
+        );
+        let texts = build_text_batches(
+            world,
+            &self.asset_manager,
+            self.camera.position,
+            self.camera.target,
+            self.camera.up,
+        );
+        let viewport_height = self.camera.viewport_height;
+        let viewport_width = (viewport_height as f32 * self.camera.aspect_ratio).round() as u32;
+        let ui_texts =
+            build_ui_text_batches(world, &self.asset_manager, viewport_width, viewport_height);
 
         RenderOutput {
             renderables,
+            sprites,
+            texts,
+            ui_texts,
             view_projection: self.camera.view_projection(),
             camera_position: self.camera.position,
+            post_process,
         }
     }
 }
@@ -114,8 +271,16 @@ impl Renderer {
 #[derive(Debug)]
 pub struct RenderOutput {
     pub renderables: Vec<Renderable>,
+    /// Drawn after the meshes, already sorted back to front
+    pub sprites: Vec<SpriteBatch>,
+    /// In-world text, drawn after the sprites (back to front)
+    pub texts: Vec<TextBatch>,
+    /// Runtime UI text, already in clip space: drawn last, over everything
+    pub ui_texts: Vec<TextBatch>,
     pub view_projection: glam::Mat4,
     pub camera_position: glam::Vec3,
+    /// Applied after the main pass, None renders the scene as-is
+    pub post_process: Option<PostProcessSettings>,
 }
 
 /// Extension for AssetManager to add plane creation
@@ -0,0 +1,376 @@
+//! Skeleton rigs: the bone hierarchy of a skinned model with its bind pose
+//!
+//! Built from an imported scene, edited in the editor's Rig panel (names,
+//! parents and constraints) and saved as `.rig` files for the animation
+//! system. Bones keep the name they had in the source file, so animation
+//! channels, which use those names, still find their bone after a rename.
+//! Bind matrices stay in the space of the source file.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use glam::{EulerRot, Mat4, Quat, Vec3};
+
+use crate::asset_manager::AssetManager;
+use crate::format_version::{FormatKind, migrate_format};
+use crate::gltf_import::ImportedScene;
+
+/// Restriction applied to a bone's local rotation after sampling a pose
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BoneConstraint {
+    /// Euler XYZ angles clamped per axis, in degrees
+    RotationLimit { min: Vec3, max: Vec3 },
+    /// Blends towards the local rotation of bone `source` by `weight`
+    CopyRotation { source: usize, weight: f32 },
+}
+
+impl BoneConstraint {
+    pub fn id(&self) -> &'static str {
+        match self {
+            BoneConstraint::RotationLimit { .. } => "rotation_limit",
+            BoneConstraint::CopyRotation { .. } => "copy_rotation",
+        }
+    }
+
+    /// `rotation` after the constraint; `rotations` are the local rotations of
+    /// every bone of the rig
+    pub fn apply(&self, rotation: Quat, rotations: &[Quat]) -> Quat {
+        match *self {
+            BoneConstraint::RotationLimit { min, max } => {
+                let (x, y, z) = rotation.to_euler(EulerRot::XYZ);
+                let clamped =
+                    Vec3::new(x, y, z).clamp(min.min(max) * DEG_TO_RAD, max.max(min) * DEG_TO_RAD);
+                Quat::from_euler(EulerRot::XYZ, clamped.x, clamped.y, clamped.z)
+            }
+            BoneConstraint::CopyRotation { source, weight } => match rotations.get(source) {
+                Some(target) => rotation.slerp(*target, weight.clamp(0.0, 1.0)),
+                None => rotation,
+            },
+        }
+    }
+}
+
+const DEG_TO_RAD: f32 = std::f32::consts::PI / 180.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bone {
+    pub name: String,
+    /// Name in the imported file, matched against animation channels
+    pub source_name: String,
+    pub parent: Option<usize>,
+    /// World matrix in the bind pose
+    pub bind: Mat4,
+    pub constraints: Vec<BoneConstraint>,
+}
+
+/// Bones of one skinned model; indices never change once built
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Rig {
+    /// Model the rig was built from
+    pub source: Option<PathBuf>,
+    /// World matrix of the skinned mesh in the bind pose; the mesh object in a
+    /// scene maps it to the object's transform
+    pub mesh_bind: Mat4,
+    bones: Vec<Bone>,
+}
+
+impl Rig {
+    /// Bones of every skin in the scene, unified by name, parented through
+    /// the scene's node hierarchy
+    pub fn from_scene(scene: &ImportedScene, assets: &AssetManager) -> Result<Self, String> {
+        let node_by_name: HashMap<&str, usize> = scene
+            .nodes
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(index, node)| (node.name.as_str(), index))
+            .collect();
+        let mut rig = Rig {
+            source: Some(scene.source.clone()),
+            ..Default::default()
+        };
+        let mut mesh_bind = None;
+        for node in scene.mesh_nodes() {
+            for primitive in &scene.nodes[node].primitives {
+                let Some(skin) = assets
+                    .get_mesh(primitive.mesh)
+                    .and_then(|mesh| mesh.skin.as_ref())
+                else {
+                    continue;
+                };
+                mesh_bind.get_or_insert_with(|| scene.world_matrix(node));
+                for (index, name) in skin.bones.iter().enumerate() {
+                    if rig.find_source(name).is_some() {
+                        continue;
+                    }
+                    let bind = match skin.inverse_bind_matrices.get(index) {
+                        Some(inverse) if *inverse != Mat4::IDENTITY => inverse.inverse(),
+                        _ => node_by_name
+                            .get(name.as_str())
+                            .map(|&i| scene.world_matrix(i))
+                            .unwrap_or(Mat4::IDENTITY),
+                    };
+                    rig.bones.push(Bone {
+                        name: name.clone(),
+                        source_name: name.clone(),
+                        parent: None,
+                        bind,
+                        constraints: Vec::new(),
+                    });
+                }
+            }
+        }
+        if rig.bones.is_empty() {
+            return Err("Modelo sem esqueleto (nenhuma malha com skin)".to_string());
+        }
+        rig.mesh_bind = mesh_bind.unwrap_or(Mat4::IDENTITY);
+
+        // Parent: the closest ancestor node that is also a bone
+        for index in 0..rig.bones.len() {
+            let mut cursor = node_by_name
+                .get(rig.bones[index].source_name.as_str())
+                .and_then(|&node| scene.nodes[node].parent);
+            while let Some(node) = cursor {
+                if let Some(parent) = rig.find_source(&scene.nodes[node].name) {
+                    rig.bones[index].parent = Some(parent);
+                    break;
+                }
+                cursor = scene.nodes[node].parent;
+            }
+        }
+        Ok(rig)
+    }
+
+    pub fn bones(&self) -> &[Bone] {
+        &self.bones
+    }
+
+    pub fn bone(&self, index: usize) -> Option<&Bone> {
+        self.bones.get(index)
+    }
+
+    pub fn find(&self, name: &str) -> Option<usize> {
+        self.bones.iter().position(|bone| bone.name == name)
+    }
+
+    /// Bone whose name in the source file is `source_name`
+    pub fn find_source(&self, source_name: &str) -> Option<usize> {
+        self.bones
+            .iter()
+            .position(|bone| bone.source_name == source_name)
+    }
+
+    pub fn roots(&self) -> Vec<usize> {
+        (0..self.bones.len())
+            .filter(|&index| self.bones[index].parent.is_none())
+            .collect()
+    }
+
+    pub fn children(&self, index: usize) -> Vec<usize> {
+        (0..self.bones.len())
+            .filter(|&child| self.bones[child].parent == Some(index))
+            .collect()
+    }
+
+    /// Whether `ancestor` is `index` or one of its parents
+    pub fn is_descendant(&self, index: usize, ancestor: usize) -> bool {
+        let mut cursor = Some(index);
+        // Bounded in case a hand-edited file has a cycle
+        for _ in 0..=self.bones.len() {
+            match cursor {
+                Some(bone) if bone == ancestor => return true,
+                Some(bone) => cursor = self.bones.get(bone).and_then(|b| b.parent),
+                None => return false,
+            }
+        }
+        false
+    }
+
+    pub fn rename(&mut self, index: usize, name: &str) -> Result<(), String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("O nome do osso não pode ficar vazio".to_string());
+        }
+        if name.contains('|') {
+            return Err("O nome do osso não pode ter '|'".to_string());
+        }
+        if self.find(name).is_some_and(|other| other != index) {
+            return Err(format!("Já existe um osso chamado {name}"));
+        }
+        let bone = self.bones.get_mut(index).ok_or("Osso inexistente")?;
+        bone.name = name.to_string();
+        Ok(())
+    }
+
+    /// Re-parents a bone; refuses to make it a child of itself or of one of
+    /// its descendants
+    pub fn set_parent(&mut self, index: usize, parent: Option<usize>) -> Result<(), String> {
+        if index >= self.bones.len() || parent.is_some_and(|p| p >= self.bones.len()) {
+            return Err("Osso inexistente".to_string());
+        }
+        if let Some(parent) = parent {
+            if self.is_descendant(parent, index) {
+                return Err(
+                    "Um osso não pode ser filho dele mesmo ou de um descendente".to_string()
+                );
+            }
+        }
+        self.bones[index].parent = parent;
+        Ok(())
+    }
+
+    pub fn constraints_mut(&mut self, index: usize) -> Option<&mut Vec<BoneConstraint>> {
+        self.bones.get_mut(index).map(|bone| &mut bone.constraints)
+    }
+
+    /// Bind matrix relative to the parent bone
+    pub fn local_bind(&self, index: usize) -> Mat4 {
+        let bone = &self.bones[index];
+        match bone.parent.and_then(|p| self.bones.get(p)) {
+            Some(parent) => parent.bind.inverse() * bone.bind,
+            None => bone.bind,
+        }
+    }
+
+    /// Runs every bone's constraints over a pose given as local rotations, in
+    /// bone order
+    pub fn apply_constraints(&self, rotations: &mut [Quat]) {
+        for (index, bone) in self.bones.iter().enumerate() {
+            if index >= rotations.len() {
+                break;
+            }
+            for constraint in &bone.constraints {
+                rotations[index] = constraint.apply(rotations[index], rotations);
+            }
+        }
+    }
+
+    pub fn to_text(&self) -> String {
+        let matrix = |m: &Mat4| {
+            m.to_cols_array()
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        let vec3 = |v: Vec3| format!("{},{},{}", v.x, v.y, v.z);
+        let mut out = FormatKind::Rig.header();
+        if let Some(source) = &self.source {
+            out.push_str(&format!("source={}\n", source.to_string_lossy()));
+        }
+        out.push_str(&format!("mesh_bind={}\n", matrix(&self.mesh_bind)));
+        // bone=<name>|<name in the source file>|<parent or -1>|<bind matrix>
+        for bone in &self.bones {
+            out.push_str(&format!(
+                "bone={}|{}|{}|{}\n",
+                bone.name,
+                bone.source_name,
+                bone.parent.map_or(-1, |p| p as i64),
+                matrix(&bone.bind)
+            ));
+        }
+        for (index, bone) in self.bones.iter().enumerate() {
+            for constraint in &bone.constraints {
+                let args = match *constraint {
+                    BoneConstraint::RotationLimit { min, max } => {
+                        format!("{}|{}", vec3(min), vec3(max))
+                    }
+                    BoneConstraint::CopyRotation { source, weight } => {
+                        format!("{source}|{weight}")
+                    }
+                };
+                out.push_str(&format!("constraint={index}|{}|{args}\n", constraint.id()));
+            }
+        }
+        out
+    }
+
+    /// Reads `to_text` of the current version
+    pub fn parse(text: &str) -> Result<Self, String> {
+        fn floats<const N: usize>(text: &str) -> Result<[f32; N], String> {
+            let values: Vec<f32> = text
+                .split(',')
+                .map(|v| v.trim().parse::<f32>())
+                .collect::<Result<_, _>>()
+                .map_err(|_| format!("Números inválidos: {text}"))?;
+            values
+                .try_into()
+                .map_err(|_| format!("Esperados {N} números: {text}"))
+        }
+        let mut rig = Rig::default();
+        let mut constraints = Vec::new();
+        for line in text.lines().map(str::trim) {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            match key {
+                "source" => rig.source = Some(PathBuf::from(value)),
+                "mesh_bind" => rig.mesh_bind = Mat4::from_cols_array(&floats(value)?),
+                "bone" => {
+                    let fields: Vec<&str> = value.split('|').collect();
+                    let [name, source_name, parent, bind] = fields[..] else {
+                        return Err(format!("Osso inválido: {value}"));
+                    };
+                    let parent: i64 = parent
+                        .parse()
+                        .map_err(|_| format!("Pai inválido: {value}"))?;
+                    rig.bones.push(Bone {
+                        name: name.to_string(),
+                        source_name: source_name.to_string(),
+                        parent: usize::try_from(parent).ok(),
+                        bind: Mat4::from_cols_array(&floats(bind)?),
+                        constraints: Vec::new(),
+                    });
+                }
+                "constraint" => constraints.push(value),
+                _ => {}
+            }
+        }
+        // After every bone, so a constraint may come before the bone it names
+        for value in constraints {
+            let fields: Vec<&str> = value.split('|').collect();
+            let invalid = || format!("Restrição inválida: {value}");
+            let bone: usize = fields
+                .first()
+                .and_then(|v| v.parse().ok())
+                .ok_or_else(invalid)?;
+            let constraint = match fields[1..] {
+                ["rotation_limit", min, max] => BoneConstraint::RotationLimit {
+                    min: Vec3::from_array(floats(min)?),
+                    max: Vec3::from_array(floats(max)?),
+                },
+                ["copy_rotation", source, weight] => BoneConstraint::CopyRotation {
+                    source: source.parse().map_err(|_| invalid())?,
+                    weight: weight.parse().map_err(|_| invalid())?,
+                },
+                _ => return Err(invalid()),
+            };
+            rig.constraints_mut(bone)
+                .ok_or_else(invalid)?
+                .push(constraint);
+        }
+        // Parents out of range or forming a cycle (hand-edited files) become roots
+        let bone_count = rig.bones.len();
+        for index in 0..bone_count {
+            let parent = rig.bones[index].parent;
+            if parent.is_some_and(|p| p >= bone_count || rig.is_descendant(p, index)) {
+                rig.bones[index].parent = None;
+            }
+        }
+        Ok(rig)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let raw = std::fs::read_to_string(path)
+            .map_err(|e| format!("Falha ao ler {}: {e}", path.display()))?;
+        Self::parse(&migrate_format(FormatKind::Rig, &raw)?.text)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        std::fs::write(path, self.to_text())
+            .map_err(|e| format!("Falha ao salvar {}: {e}", path.display()))
+    }
+}
@@ -1,7 +1,10 @@
 //! WGSL shaders for the Dengine renderer
 //!
 //! Professional Blinn-Phong lighting with vertex normals, texture support,
-//! and grid rendering.
+//! clustered point/spot lights, skybox, post-processing and grid rendering.
+//!
+//! The sources live in `src/shaders/*.wgsl` and are embedded at build time;
+//! `ShaderLibrary` watches the same files to reload them while the editor runs.
 
 /// Main lit shader — Blinn-Phong with vertex normals
 ///
@@ -17,172 +20,309 @@
 ///   - light_color: vec3<f32>     (12 bytes)
 ///   - has_texture: f32           (4 bytes)
 ///   - tint: vec4<f32>            (16 bytes)
-///   Total = 192 bytes
-pub const LIT_SHADER: &str = r#"
-struct Uniforms {
-    mvp: mat4x4<f32>,
-    model: mat4x4<f32>,
-    camera_pos: vec3<f32>,
-    light_intensity: f32,
-    light_dir: vec3<f32>,
-    light_enabled: f32,
-    light_color: vec3<f32>,
-    has_texture: f32,
-    tint: vec4<f32>,
-};
-
-@group(0) @binding(0)
-var<uniform> ubo: Uniforms;
-
-@group(0) @binding(1)
-var tex_sampler: sampler;
-
-@group(0) @binding(2)
-var albedo_texture: texture_2d<f32>;
-
-struct VsIn {
-    @location(0) pos: vec3<f32>,
-    @location(1) normal: vec3<f32>,
-    @location(2) uv: vec2<f32>,
-};
-
-struct VsOut {
-    @builtin(position) clip_pos: vec4<f32>,
-    @location(0) world_pos: vec3<f32>,
-    @location(1) world_normal: vec3<f32>,
-    @location(2) uv: vec2<f32>,
-};
-
-@vertex
-fn vs_main(v: VsIn) -> VsOut {
-    var out: VsOut;
-    out.clip_pos = ubo.mvp * vec4<f32>(v.pos, 1.0);
-    out.world_pos = (ubo.model * vec4<f32>(v.pos, 1.0)).xyz;
-    // Transform normal by model matrix (assumes uniform scale or use inverse-transpose)
-    out.world_normal = normalize((ubo.model * vec4<f32>(v.normal, 0.0)).xyz);
-    out.uv = v.uv;
-    return out;
+///   - cluster_params: vec4<f32>  (16 bytes) near, far, directional count, local count
+///   - camera_forward: vec4<f32>  (16 bytes)
+///   - ibl_params: vec4<f32>      (16 bytes) intensity (0 = off), sky rotation, roughness, metallic
+///   - fog_color: vec4<f32>       (16 bytes) rgb + mode (0 off, 1 linear, 2 exp, 3 exp2)
+///   - fog_params: vec4<f32>      (16 bytes) start, end, density, max opacity
+///   - fog_height: vec4<f32>      (16 bytes) height density (0 = off), height, falloff
+///   Total = 288 bytes (see `fog_uniform`)
+///
+/// Clustered lights (see `lighting.rs`):
+///   - binding 3: array<GpuLight>      (directional lights first, then point/spot)
+///   - binding 4: array<vec2<u32>>     (offset, count) per cluster
+///   - binding 5: array<u32>           light indices
+///
+/// Image-based lighting (see `IBL_SHADER`):
+///   - binding 6: irradiance map (equirect)
+///   - binding 7: prefiltered specular map (equirect, `IBL_SPECULAR_MIPS` mips)
+///   - binding 8: sampler (repeat on U)
+pub const LIT_SHADER: &str = include_str!("shaders/lit.wgsl");
+
+/// Viewport shading mode, each one a fragment entry point of `LIT_SHADER`
+///
+/// - `Lit`: full lighting (`fs_main`)
+/// - `Unlit`: albedo only (`fs_unlit`)
+/// - `Wireframe`: triangle edges, drawn as a line list (`fs_wireframe`)
+/// - `Normals`: world-space normals as RGB (`fs_normals`)
+/// - `Overdraw`: additive, depth test off, so hot spots show shading cost (`fs_overdraw`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ShadingMode {
+    #[default]
+    Lit,
+    Unlit,
+    Wireframe,
+    Normals,
+    Overdraw,
 }
 
-@fragment
-fn fs_main(v: VsOut) -> @location(0) vec4<f32> {
-    // Normalize interpolated normal
-    var n = normalize(v.world_normal);
-
-    // If normal is degenerate, compute from derivatives as fallback
-    let n_len = dot(n, n);
-    if (n_len < 0.01) {
-        let dpdx_pos = dpdx(v.world_pos);
-        let dpdy_pos = dpdy(v.world_pos);
-        let c = cross(dpdx_pos, dpdy_pos);
-        let len2 = max(dot(c, c), 1e-8);
-        n = c * inverseSqrt(len2);
+impl ShadingMode {
+    /// Every mode, in menu order
+    pub fn all() -> [ShadingMode; 5] {
+        [
+            ShadingMode::Lit,
+            ShadingMode::Unlit,
+            ShadingMode::Wireframe,
+            ShadingMode::Normals,
+            ShadingMode::Overdraw,
+        ]
     }
 
-    // View direction
-    let view_dir = normalize(ubo.camera_pos - v.world_pos);
+    /// Fragment entry point in `LIT_SHADER`
+    pub fn fragment_entry(self) -> &'static str {
+        match self {
+            ShadingMode::Lit => "fs_main",
+            ShadingMode::Unlit => "fs_unlit",
+            ShadingMode::Wireframe => "fs_wireframe",
+            ShadingMode::Normals => "fs_normals",
+            ShadingMode::Overdraw => "fs_overdraw",
+        }
+    }
 
-    // Default lighting factors
-    let ambient = 0.15;
-    var diffuse = 0.0;
-    var specular = 0.0;
-    var l_color = vec3<f32>(1.0, 1.0, 1.0);
+    /// Short label for menus
+    pub fn label(self) -> &'static str {
+        match self {
+            ShadingMode::Lit => "Lit",
+            ShadingMode::Unlit => "Unlit",
+            ShadingMode::Wireframe => "Wireframe",
+            ShadingMode::Normals => "Normals",
+            ShadingMode::Overdraw => "Overdraw",
+        }
+    }
+}
 
-    if (ubo.light_enabled > 0.5) {
-        // Light direction (normalized directional light)
-        let l = normalize(ubo.light_dir);
-        let ndotl = max(dot(n, l), 0.0);
-        diffuse = ndotl * 0.65;
+/// Uniform buffer size in bytes (must match the Uniforms struct above)
+pub const LIT_UNIFORM_SIZE: usize = 288;
 
-        // Specular (Blinn half-vector)
-        let half_dir = normalize(l + view_dir);
-        let ndoth = max(dot(n, half_dir), 0.0);
-        specular = pow(ndoth, 32.0) * 0.25;
+/// Stride of a single vertex in bytes: pos(12) + normal(12) + uv(8) = 32
+pub const LIT_VERTEX_STRIDE: usize = 32;
 
-        l_color = ubo.light_color * ubo.light_intensity;
-    }
+/// GPU particles — compute simulation plus camera-facing billboards
+///
+/// Particles live in a storage buffer used as a ring: every frame the CPU picks
+/// the slots to respawn (`spawn`), `cs_simulate` re-seeds those and ages the
+/// others, then `vs_particle` expands each instance into a quad (6 vertices).
+///
+/// Particle (48 bytes): position + age, velocity + lifetime (0 = dead),
+/// start size + random value
+///
+/// Uniforms (binding 0, see `particle_uniform_bytes`):
+///   - view_proj, emitter: mat4x4<f32>    (128 bytes)
+///   - camera_right: vec4<f32>            xyz, w = delta time
+///   - camera_up: vec4<f32>               xyz, w unused
+///   - spawn: vec4<f32>                   first slot, count, capacity, seed
+///   - lifetime_speed: vec4<f32>          lifetime min/max, start speed min/max
+///   - size_shape: vec4<f32>              start size min/max, cone angle (rad), gravity
+///   - start_color, end_color: vec4<f32>
+///   - sheet: vec4<f32>                   columns, rows, cycles, has texture
+///   - curves: array<vec4<f32>, 16>       size, alpha, speed over normalized age
+///   Total = 512 bytes
+///
+/// Bindings: 1 = particles (read_write, compute), 2 = particles (read, render),
+/// 3 = sheet texture, 4 = sampler
+pub const PARTICLE_SHADER: &str = include_str!("shaders/particle.wgsl");
 
-    // Rim lighting for depth perception
-    let rim = pow(1.0 - max(dot(n, view_dir), 0.0), 2.5) * 0.08;
+/// Particle uniform buffer size in bytes (must match ParticleUniforms above)
+pub const PARTICLE_UNIFORM_SIZE: usize = 512;
 
-    // Fill light from below-behind for shadow areas
-    let fill_dir = normalize(vec3<f32>(-0.3, -0.5, -0.4));
-    let fill = max(dot(n, fill_dir), 0.0) * 0.10;
+/// Stride of one particle in the storage buffer: 3 × vec4 = 48 bytes
+pub const PARTICLE_STRIDE: usize = 48;
 
-    let total_light = (ambient + (diffuse + specular) + rim + fill);
-    let shade = clamp(total_light, 0.0, 1.5); // Allow slightly over 1.0 for highlights
+/// Workgroup size of `cs_simulate`
+pub const PARTICLE_WORKGROUP_SIZE: u32 = 64;
 
-    // Base color from texture or tint
-    var base_color = ubo.tint;
-    if (ubo.has_texture > 0.5) {
-        let tex_color = textureSample(albedo_texture, tex_sampler, v.uv);
-        base_color = tex_color * ubo.tint;
-    }
+/// Sprite shader — batched, unlit textured quads (see `sprite.rs`)
+///
+/// Vertex layout: position (vec3), uv (vec2), color (vec4) = 36 bytes/vertex
+///
+/// Uniforms (binding 0): view_proj: mat4x4<f32> (64 bytes)
+/// Binding 1: sprite texture, binding 2: sampler (nearest for pixel art)
+pub const SPRITE_SHADER: &str = include_str!("shaders/sprite.wgsl");
+
+/// Sprite uniform buffer size in bytes
+pub const SPRITE_UNIFORM_SIZE: usize = 64;
+
+/// Stride of a sprite vertex: pos(12) + uv(8) + color(16) = 36
+pub const SPRITE_VERTEX_STRIDE: usize = 36;
+
+/// In-world text shader (`Text3D`)
+///
+/// Same vertex layout as `SPRITE_SHADER`; the texture is a font SDF atlas with
+/// the distance in alpha (0.5 = glyph edge). The edge is antialiased over one
+/// screen pixel using the screen-space derivative of the distance.
+///
+/// Uniforms: view_proj (64 bytes)
+pub const TEXT_SHADER: &str = include_str!("shaders/text.wgsl");
 
-    let color = base_color.rgb * l_color * shade;
-    return vec4<f32>(color, base_color.a);
+/// Text uniform buffer size in bytes
+pub const TEXT_UNIFORM_SIZE: usize = 64;
+
+/// Terrain shader with texture splatting
+///
+/// Same vertex layout as `LIT_SHADER` (local position, normal, terrain uv in
+/// 0..1). The splat map holds one layer weight per channel; each layer samples
+/// its texture at `uv * tiling` and is tinted by its color. Lit by the main
+/// directional light with a constant ambient term.
+///
+/// Uniforms:
+///   view_proj (64), model (64), light_dir + intensity (16), light_color (16),
+///   tiling per layer (16), color + has_texture per layer (4 × 16) = 240 bytes
+pub const TERRAIN_SHADER: &str = include_str!("shaders/terrain.wgsl");
+
+/// Terrain uniform buffer size in bytes
+pub const TERRAIN_UNIFORM_SIZE: usize = 240;
+
+/// Planar water surface, drawn over the resolved offscreen HDR scene
+///
+/// The quad is generated from `vertex_index` (two triangles spanning
+/// `waves.xy` in the local XZ plane). There is no depth attachment: the
+/// fragment compares itself with the scene depth and discards when hidden.
+/// Normals come from a scrolling normal map (or a sum of sine waves without
+/// one). The color mixes the refracted scene (a copy of the scene color,
+/// distorted by the normal and fogged by the water thickness) with the
+/// reflection (screen-space march over the scene depth, sky as fallback) by
+/// a Schlick fresnel, plus the sun highlight.
+///
+/// Uniforms (binding 0):
+///   view_proj (64), inv_view_proj (64), model (64), camera_pos + time (16),
+///   light_dir + intensity (16), light_color + has_normal_map (16),
+///   shallow_color + fog_density (16), deep_color + reflectivity (16),
+///   waves: size.xy, wave_scale, wave_speed (16),
+///   params: wave_strength, refraction_strength (16) = 304 bytes
+///
+/// Bindings 1..7: sky uniforms (`SKY_SHADER` layout), repeat sampler, clamp
+/// sampler, sky panorama, scene color copy, scene depth, normal map.
+/// `water_shader_source` swaps the depth type for multisampled targets.
+pub const WATER_SHADER: &str = include_str!("shaders/water.wgsl");
+
+/// Water uniform buffer size in bytes
+pub const WATER_UNIFORM_SIZE: usize = 304;
+
+/// Water `source` (`WATER_SHADER` or a reloaded copy) for a scene depth target
+/// with `sample_count` samples
+pub fn water_shader_source(source: &str, sample_count: u32) -> String {
+    if sample_count > 1 {
+        source.replace("texture_depth_2d", "texture_depth_multisampled_2d")
+    } else {
+        source.to_string()
+    }
 }
-"#;
 
-/// Uniform buffer size in bytes (must match the Uniforms struct above)
-pub const LIT_UNIFORM_SIZE: usize = 192;
+/// ID-buffer shader for GPU picking
+///
+/// Draws the same vertex buffer as `LIT_SHADER` (only the position is read) into
+/// an R32Uint target. Each object is a separate draw whose first instance is its
+/// pick ID, so `instance_index` identifies it without an extra vertex attribute.
+/// ID 0 is reserved for the background.
+///
+/// Uniforms: binding 0 of the `LIT_SHADER` bind group (only `mvp` is read)
+pub const PICK_SHADER: &str = include_str!("shaders/pick.wgsl");
 
-/// Stride of a single vertex in bytes: pos(12) + normal(12) + uv(8) = 32
-pub const LIT_VERTEX_STRIDE: usize = 32;
+/// Selection outline, drawn over the final viewport image
+///
+/// The selected objects are first drawn with `PICK_SHADER` into an R32Uint mask
+/// (non-zero = selected). `fs_outline` is a fullscreen pass that, outside the
+/// mask, finds the nearest masked texel within `params.x` pixels and blends the
+/// outline color with a one pixel soft edge.
+///
+/// Uniforms (bind group 0, binding 0):
+///   - color: vec4<f32>   (16 bytes) outline color, alpha
+///   - params: vec4<f32>  (16 bytes) width in pixels, _pad
+///   Total = 32 bytes
+///
+/// Binding 1: mask texture_2d<u32>
+pub const OUTLINE_SHADER: &str = include_str!("shaders/outline.wgsl");
+
+/// Outline uniform buffer size in bytes (must match OutlineUniforms above)
+pub const OUTLINE_UNIFORM_SIZE: usize = 32;
 
 /// Grid shader — infinite ground grid rendered via fullscreen quad
-pub const GRID_SHADER: &str = r#"
-struct GridUniforms {
-    view_proj_inv: mat4x4<f32>,
-    camera_pos: vec3<f32>,
-    _pad: f32,
-};
-
-@group(0) @binding(0)
-var<uniform> grid_ubo: GridUniforms;
-
-struct VsOut {
-    @builtin(position) clip_pos: vec4<f32>,
-    @location(0) near_point: vec3<f32>,
-    @location(1) far_point: vec3<f32>,
-};
-
-fn unproject(p: vec3<f32>) -> vec3<f32> {
-    let r = grid_ubo.view_proj_inv * vec4<f32>(p, 1.0);
-    return r.xyz / r.w;
-}
+pub const GRID_SHADER: &str = include_str!("shaders/grid.wgsl");
 
-@vertex
-fn vs_grid(@builtin(vertex_index) idx: u32) -> VsOut {
-    // Fullscreen triangle
-    let positions = array<vec2<f32>, 6>(
-        vec2<f32>(-1.0, -1.0), vec2<f32>(1.0, -1.0), vec2<f32>(1.0, 1.0),
-        vec2<f32>(-1.0, -1.0), vec2<f32>(1.0, 1.0), vec2<f32>(-1.0, 1.0),
-    );
-    let p = positions[idx];
-    var out: VsOut;
-    out.clip_pos = vec4<f32>(p, 0.0, 1.0);
-    out.near_point = unproject(vec3<f32>(p, 0.0));
-    out.far_point = unproject(vec3<f32>(p, 1.0));
-    return out;
-}
+/// Sky shader — fullscreen pass drawn behind the scene
+///
+/// Samples an equirectangular environment map (see `environment.rs`) along the
+/// view ray, or draws a zenith/horizon/ground gradient when no map is set.
+///
+/// Uniforms (bind group 0, binding 0):
+///   - view_proj_inv: mat4x4<f32>  (64 bytes)
+///   - camera_pos: vec3<f32>       (12 bytes)
+///   - exposure: f32               (4 bytes)
+///   - params: vec4<f32>           (16 bytes) rotation, has_map
+///   - zenith_color: vec4<f32>     (16 bytes)
+///   - horizon_color: vec4<f32>    (16 bytes)
+///   - ground_color: vec4<f32>     (16 bytes)
+///   Total = 144 bytes
+pub const SKY_SHADER: &str = include_str!("shaders/sky.wgsl");
+
+/// Sky uniform buffer size in bytes (must match SkyUniforms above)
+pub const SKY_UNIFORM_SIZE: usize = 144;
+
+/// Post-process chain (see `post_process.rs`), fullscreen passes over the
+/// offscreen HDR (RGBA16F) scene color:
+///   - `fs_bright`: soft-threshold bright pass into the half-resolution bloom target
+///   - `fs_blur`: 9-tap separable gaussian, direction given by `blur.xy`
+///   - `fs_composite`: scene + bloom, exposure, tonemapping, color grading LUT, vignette
+///   - `fs_fxaa`: FXAA over the composited image (anti-aliasing fallback to MSAA)
+///
+/// Uniforms (bind group 0, binding 0):
+///   - exposure_tonemap: vec4<f32>  (16 bytes) exposure, tonemapping (0 none, 1 Reinhard, 2 ACES), bloom intensity, bloom threshold
+///   - vignette_lut: vec4<f32>      (16 bytes) vignette intensity, vignette smoothness, LUT contribution, LUT size (0 = none)
+///   - blur: vec4<f32>              (16 bytes) blur step in UV units
+///   - auto_exposure: vec4<f32>     (16 bytes) enabled, min exposure, max exposure, adaptation blend for this frame
+///   Total = 64 bytes
+///
+/// Bindings 1..5: sampler, source texture, bloom texture, LUT strip,
+/// exposure state written by `EXPOSURE_SHADER`
+pub const POST_SHADER: &str = include_str!("shaders/post.wgsl");
 
-@fragment
-fn fs_grid(v: VsOut) -> @location(0) vec4<f32> {
-    let t = -v.near_point.y / (v.far_point.y - v.near_point.y);
-    if (t < 0.0) { discard; }
-    let world_pos = v.near_point + t * (v.far_point - v.near_point);
+/// Post-process uniform buffer size in bytes (must match PostUniforms above)
+pub const POST_UNIFORM_SIZE: usize = 64;
 
-    let coord = world_pos.xz;
-    let grid = abs(fract(coord - 0.5) - 0.5) / fwidth(coord);
-    let line = min(grid.x, grid.y);
-    let alpha = 1.0 - min(line, 1.0);
+/// Auto exposure (compute shader), run on the HDR scene before the post chain
+///
+/// `cs_exposure` runs as a single 16x16 workgroup: each invocation averages the
+/// log luminance of a grid of texels, the workgroup reduces them and the first
+/// invocation eases the exposure towards `EXPOSURE_KEY / average`.
+///
+/// Bindings (bind group 0):
+///   - binding 0: uniform PostUniforms (same buffer as `POST_SHADER`)
+///   - binding 1: HDR scene texture_2d<f32>
+///   - binding 2: storage array<f32, 4> { exposure, average luminance, _pad }
+pub const EXPOSURE_SHADER: &str = include_str!("shaders/exposure.wgsl");
+
+/// Image-based lighting precomputation (compute shaders)
+///
+/// Works on equirectangular RGBA16F textures, same mapping as `SKY_SHADER`:
+///   - `cs_downsample`: box-filters the full panorama into a small base map
+///   - `cs_irradiance`: cosine-weighted convolution of the base map (diffuse)
+///   - `cs_prefilter`: specular lobe convolution, one dispatch per mip level
+///
+/// Bindings (bind group 0):
+///   - binding 0: source texture_2d<f32>
+///   - binding 1: destination texture_storage_2d<rgba16float, write>
+///   - binding 2: uniform IblParams { exponent, _pad }   (16 bytes)
+pub const IBL_SHADER: &str = include_str!("shaders/ibl.wgsl");
+
+/// Mip chain generation for imported textures (compute shader)
+///
+/// `cs_downsample` box-filters one mip level into the next. The source is read
+/// through an sRGB view (linear values) and the destination is written as
+/// `rgba8unorm` with the sRGB encoding applied in the shader, since sRGB
+/// formats can't be storage textures.
+///
+/// Bindings (bind group 0):
+///   - binding 0: source mip texture_2d<f32> (Rgba8UnormSrgb view)
+///   - binding 1: destination mip texture_storage_2d<rgba8unorm, write>
+pub const MIPMAP_SHADER: &str = include_str!("shaders/mipmap.wgsl");
 
-    // Fade with distance
-    let dist = length(world_pos.xz - grid_ubo.camera_pos.xz);
-    let fade = 1.0 - smoothstep(8.0, 40.0, dist);
+/// Mip levels of the prefiltered specular map (roughness 0 → 1)
+pub const IBL_SPECULAR_MIPS: u32 = 5;
 
-    let color = vec3<f32>(0.35, 0.35, 0.38);
-    return vec4<f32>(color, alpha * fade * 0.4);
+/// Specular exponent of the lobe used for a prefiltered mip level
+pub fn ibl_prefilter_exponent(level: u32) -> f32 {
+    let roughness = level as f32 / (IBL_SPECULAR_MIPS - 1) as f32;
+    // Blinn-Phong equivalent of a GGX lobe (Walter et al.), capped for mip 0
+    let alpha = (roughness * roughness).max(0.02);
+    (2.0 / (alpha * alpha) - 2.0).min(4096.0)
 }
-"#;
@@ -0,0 +1,209 @@
+//! Shader library - current WGSL sources with hot reload from disk
+//!
+//! Every built-in shader is a file in `src/shaders` (see `shader.rs`). The
+//! library starts from the embedded copies and, while that directory exists
+//! (running from a source checkout), polls the files' size and modification
+//! time. Changed files are handed back as `ShaderChange`s so the renderer can
+//! validate them before `apply` swaps the source and bumps `generation`.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::shader::{
+    EXPOSURE_SHADER, GRID_SHADER, IBL_SHADER, LIT_SHADER, MIPMAP_SHADER, OUTLINE_SHADER,
+    PARTICLE_SHADER, PICK_SHADER, POST_SHADER, SKY_SHADER, SPRITE_SHADER, TERRAIN_SHADER,
+    TEXT_SHADER, WATER_SHADER,
+};
+
+/// Directory the built-in shaders are embedded from
+pub const SHADER_SOURCE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/shaders");
+
+/// How often the shader files are checked for changes
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// One of the built-in shader files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShaderFile {
+    Lit,
+    Particle,
+    Sprite,
+    Text,
+    Terrain,
+    Water,
+    Pick,
+    Outline,
+    Grid,
+    Sky,
+    Post,
+    Exposure,
+    Ibl,
+    Mipmap,
+}
+
+impl ShaderFile {
+    pub const ALL: [ShaderFile; 14] = [
+        ShaderFile::Lit,
+        ShaderFile::Particle,
+        ShaderFile::Sprite,
+        ShaderFile::Text,
+        ShaderFile::Terrain,
+        ShaderFile::Water,
+        ShaderFile::Pick,
+        ShaderFile::Outline,
+        ShaderFile::Grid,
+        ShaderFile::Sky,
+        ShaderFile::Post,
+        ShaderFile::Exposure,
+        ShaderFile::Ibl,
+        ShaderFile::Mipmap,
+    ];
+
+    pub fn file_name(self) -> &'static str {
+        match self {
+            ShaderFile::Lit => "lit.wgsl",
+            ShaderFile::Particle => "particle.wgsl",
+            ShaderFile::Sprite => "sprite.wgsl",
+            ShaderFile::Text => "text.wgsl",
+            ShaderFile::Terrain => "terrain.wgsl",
+            ShaderFile::Water => "water.wgsl",
+            ShaderFile::Pick => "pick.wgsl",
+            ShaderFile::Outline => "outline.wgsl",
+            ShaderFile::Grid => "grid.wgsl",
+            ShaderFile::Sky => "sky.wgsl",
+            ShaderFile::Post => "post.wgsl",
+            ShaderFile::Exposure => "exposure.wgsl",
+            ShaderFile::Ibl => "ibl.wgsl",
+            ShaderFile::Mipmap => "mipmap.wgsl",
+        }
+    }
+
+    /// Source embedded in the binary
+    pub fn builtin_source(self) -> &'static str {
+        match self {
+            ShaderFile::Lit => LIT_SHADER,
+            ShaderFile::Particle => PARTICLE_SHADER,
+            ShaderFile::Sprite => SPRITE_SHADER,
+            ShaderFile::Text => TEXT_SHADER,
+            ShaderFile::Terrain => TERRAIN_SHADER,
+            ShaderFile::Water => WATER_SHADER,
+            ShaderFile::Pick => PICK_SHADER,
+            ShaderFile::Outline => OUTLINE_SHADER,
+            ShaderFile::Grid => GRID_SHADER,
+            ShaderFile::Sky => SKY_SHADER,
+            ShaderFile::Post => POST_SHADER,
+            ShaderFile::Exposure => EXPOSURE_SHADER,
+            ShaderFile::Ibl => IBL_SHADER,
+            ShaderFile::Mipmap => MIPMAP_SHADER,
+        }
+    }
+}
+
+/// New contents of a shader file, not applied yet
+#[derive(Debug, Clone)]
+pub struct ShaderChange {
+    pub file: ShaderFile,
+    pub path: PathBuf,
+    pub source: String,
+}
+
+/// Current source of every built-in shader
+#[derive(Debug)]
+pub struct ShaderLibrary {
+    dir: PathBuf,
+    sources: HashMap<ShaderFile, String>,
+    // (size, mtime) of each file as last seen on disk
+    stamps: HashMap<ShaderFile, (u64, u64)>,
+    last_poll: Option<Instant>,
+    generation: u64,
+}
+
+impl Default for ShaderLibrary {
+    fn default() -> Self {
+        Self::watch(Path::new(SHADER_SOURCE_DIR))
+    }
+}
+
+impl ShaderLibrary {
+    /// Embedded sources, watching the files in `dir`
+    pub fn watch(dir: &Path) -> Self {
+        let stamps = ShaderFile::ALL
+            .into_iter()
+            .filter_map(|file| Some((file, file_stamp(&dir.join(file.file_name()))?)))
+            .collect();
+        Self {
+            dir: dir.to_path_buf(),
+            sources: HashMap::new(),
+            stamps,
+            last_poll: None,
+            generation: 0,
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn source(&self, file: ShaderFile) -> &str {
+        self.sources
+            .get(&file)
+            .map_or(file.builtin_source(), String::as_str)
+    }
+
+    /// Bumped by every applied change; pipelines built from an older
+    /// generation must be recreated
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Files changed on disk since the last poll (checked at most every
+    /// `POLL_INTERVAL`). A file whose contents match the current source is
+    /// skipped, so touching a file does not rebuild anything.
+    pub fn poll(&mut self) -> Vec<ShaderChange> {
+        let now = Instant::now();
+        if self
+            .last_poll
+            .is_some_and(|last| now.duration_since(last) < POLL_INTERVAL)
+        {
+            return Vec::new();
+        }
+        self.last_poll = Some(now);
+
+        let mut changes = Vec::new();
+        for file in ShaderFile::ALL {
+            let path = self.dir.join(file.file_name());
+            let Some(stamp) = file_stamp(&path) else {
+                continue;
+            };
+            if self.stamps.get(&file) == Some(&stamp) {
+                continue;
+            }
+            self.stamps.insert(file, stamp);
+            let Ok(source) = fs::read_to_string(&path) else {
+                continue;
+            };
+            if source != self.source(file) {
+                changes.push(ShaderChange { file, path, source });
+            }
+        }
+        changes
+    }
+
+    /// Use the changed source from now on
+    pub fn apply(&mut self, change: ShaderChange) {
+        self.sources.insert(change.file, change.source);
+        self.generation += 1;
+    }
+}
+
+pub(crate) fn file_stamp(path: &Path) -> Option<(u64, u64)> {
+    let meta = fs::metadata(path).ok()?;
+    let mtime = meta
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+    Some((meta.len(), mtime))
+}
@@ -0,0 +1,59 @@
+struct PostUniforms {
+    exposure_tonemap: vec4<f32>,
+    vignette_lut: vec4<f32>,
+    blur: vec4<f32>,
+    auto_exposure: vec4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> post: PostUniforms;
+
+@group(0) @binding(1)
+var scene_texture: texture_2d<f32>;
+
+@group(0) @binding(2)
+var<storage, read_write> exposure_state: array<f32, 4>;
+
+// Middle gray
+const EXPOSURE_KEY: f32 = 0.18;
+// Texels sampled per invocation along each axis
+const TAPS: u32 = 4u;
+
+var<workgroup> log_sums: array<f32, 256>;
+
+@compute @workgroup_size(16, 16, 1)
+fn cs_exposure(@builtin(local_invocation_index) index: u32, @builtin(local_invocation_id) id: vec3<u32>) {
+    let size = textureDimensions(scene_texture);
+    let grid = vec2<u32>(16u * TAPS, 16u * TAPS);
+    var sum = 0.0;
+    for (var y = 0u; y < TAPS; y++) {
+        for (var x = 0u; x < TAPS; x++) {
+            let cell = id.xy * TAPS + vec2<u32>(x, y);
+            let p = min((cell * size + size / (2u * grid)) / grid, size - 1u);
+            let c = textureLoad(scene_texture, p, 0).rgb;
+            sum += log2(max(dot(c, vec3<f32>(0.2126, 0.7152, 0.0722)), 1e-4));
+        }
+    }
+    log_sums[index] = sum / f32(TAPS * TAPS);
+    workgroupBarrier();
+
+    for (var stride = 128u; stride > 0u; stride >>= 1u) {
+        if (index < stride) {
+            log_sums[index] += log_sums[index + stride];
+        }
+        workgroupBarrier();
+    }
+
+    if (index == 0u) {
+        let average = exp2(log_sums[0] / 256.0);
+        let target_exposure = clamp(EXPOSURE_KEY / average, post.auto_exposure.y, post.auto_exposure.z);
+        let current = exposure_state[0];
+        // First frame (or after a reset) jumps straight to the target
+        if (current <= 0.0) {
+            exposure_state[0] = target_exposure;
+        } else {
+            exposure_state[0] = mix(current, target_exposure, post.auto_exposure.w);
+        }
+        exposure_state[1] = average;
+    }
+}
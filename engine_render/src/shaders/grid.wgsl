@@ -0,0 +1,53 @@
+struct GridUniforms {
+    view_proj_inv: mat4x4<f32>,
+    camera_pos: vec3<f32>,
+    _pad: f32,
+};
+
+@group(0) @binding(0)
+var<uniform> grid_ubo: GridUniforms;
+
+struct VsOut {
+    @builtin(position) clip_pos: vec4<f32>,
+    @location(0) near_point: vec3<f32>,
+    @location(1) far_point: vec3<f32>,
+};
+
+fn unproject(p: vec3<f32>) -> vec3<f32> {
+    let r = grid_ubo.view_proj_inv * vec4<f32>(p, 1.0);
+    return r.xyz / r.w;
+}
+
+@vertex
+fn vs_grid(@builtin(vertex_index) idx: u32) -> VsOut {
+    // Fullscreen triangle
+    let positions = array<vec2<f32>, 6>(
+        vec2<f32>(-1.0, -1.0), vec2<f32>(1.0, -1.0), vec2<f32>(1.0, 1.0),
+        vec2<f32>(-1.0, -1.0), vec2<f32>(1.0, 1.0), vec2<f32>(-1.0, 1.0),
+    );
+    let p = positions[idx];
+    var out: VsOut;
+    out.clip_pos = vec4<f32>(p, 0.0, 1.0);
+    out.near_point = unproject(vec3<f32>(p, 0.0));
+    out.far_point = unproject(vec3<f32>(p, 1.0));
+    return out;
+}
+
+@fragment
+fn fs_grid(v: VsOut) -> @location(0) vec4<f32> {
+    let t = -v.near_point.y / (v.far_point.y - v.near_point.y);
+    if (t < 0.0) { discard; }
+    let world_pos = v.near_point + t * (v.far_point - v.near_point);
+
+    let coord = world_pos.xz;
+    let grid = abs(fract(coord - 0.5) - 0.5) / fwidth(coord);
+    let line = min(grid.x, grid.y);
+    let alpha = 1.0 - min(line, 1.0);
+
+    // Fade with distance
+    let dist = length(world_pos.xz - grid_ubo.camera_pos.xz);
+    let fade = 1.0 - smoothstep(8.0, 40.0, dist);
+
+    let color = vec3<f32>(0.35, 0.35, 0.38);
+    return vec4<f32>(color, alpha * fade * 0.4);
+}
@@ -0,0 +1,98 @@
+struct IblParams {
+    exponent: f32,
+    _pad: vec3<f32>,
+};
+
+@group(0) @binding(0)
+var src_tex: texture_2d<f32>;
+
+@group(0) @binding(1)
+var dst_tex: texture_storage_2d<rgba16float, write>;
+
+@group(0) @binding(2)
+var<uniform> params: IblParams;
+
+const PI: f32 = 3.14159265;
+
+fn texel_direction(coord: vec2<u32>, size: vec2<u32>) -> vec3<f32> {
+    let uv = (vec2<f32>(coord) + 0.5) / vec2<f32>(size);
+    let phi = (uv.x - 0.5) * 2.0 * PI;
+    let theta = uv.y * PI;
+    return vec3<f32>(sin(theta) * cos(phi), cos(theta), sin(theta) * sin(phi));
+}
+
+@compute @workgroup_size(8, 8, 1)
+fn cs_downsample(@builtin(global_invocation_id) id: vec3<u32>) {
+    let dst_size = textureDimensions(dst_tex);
+    if (id.x >= dst_size.x || id.y >= dst_size.y) {
+        return;
+    }
+    let src_size = textureDimensions(src_tex);
+    let block = max(src_size / dst_size, vec2<u32>(1u, 1u));
+    // At most 16x16 taps per output texel
+    let step = max(block / 16u, vec2<u32>(1u, 1u));
+    let origin = id.xy * src_size / dst_size;
+    var sum = vec3<f32>(0.0);
+    var count = 0.0;
+    for (var y = 0u; y < block.y; y += step.y) {
+        for (var x = 0u; x < block.x; x += step.x) {
+            let p = min(origin + vec2<u32>(x, y), src_size - 1u);
+            sum += textureLoad(src_tex, p, 0).rgb;
+            count += 1.0;
+        }
+    }
+    textureStore(dst_tex, id.xy, vec4<f32>(sum / max(count, 1.0), 1.0));
+}
+
+@compute @workgroup_size(8, 8, 1)
+fn cs_irradiance(@builtin(global_invocation_id) id: vec3<u32>) {
+    let dst_size = textureDimensions(dst_tex);
+    if (id.x >= dst_size.x || id.y >= dst_size.y) {
+        return;
+    }
+    let n = texel_direction(id.xy, dst_size);
+    let src_size = textureDimensions(src_tex);
+    let texel_area = (2.0 * PI / f32(src_size.x)) * (PI / f32(src_size.y));
+    var sum = vec3<f32>(0.0);
+    for (var y = 0u; y < src_size.y; y++) {
+        for (var x = 0u; x < src_size.x; x++) {
+            let l = texel_direction(vec2<u32>(x, y), src_size);
+            let cos_theta = dot(n, l);
+            if (cos_theta <= 0.0) {
+                continue;
+            }
+            // Solid angle of an equirect texel shrinks towards the poles
+            let solid_angle = texel_area * sqrt(max(1.0 - l.y * l.y, 0.0));
+            sum += textureLoad(src_tex, vec2<u32>(x, y), 0).rgb * cos_theta * solid_angle;
+        }
+    }
+    textureStore(dst_tex, id.xy, vec4<f32>(sum / PI, 1.0));
+}
+
+@compute @workgroup_size(8, 8, 1)
+fn cs_prefilter(@builtin(global_invocation_id) id: vec3<u32>) {
+    let dst_size = textureDimensions(dst_tex);
+    if (id.x >= dst_size.x || id.y >= dst_size.y) {
+        return;
+    }
+    // N = V = R: the lobe is centered on the texel direction
+    let r = texel_direction(id.xy, dst_size);
+    let src_size = textureDimensions(src_tex);
+    let texel_area = (2.0 * PI / f32(src_size.x)) * (PI / f32(src_size.y));
+    var sum = vec3<f32>(0.0);
+    var weight = 0.0;
+    for (var y = 0u; y < src_size.y; y++) {
+        for (var x = 0u; x < src_size.x; x++) {
+            let l = texel_direction(vec2<u32>(x, y), src_size);
+            let cos_theta = dot(r, l);
+            if (cos_theta <= 0.0) {
+                continue;
+            }
+            let solid_angle = texel_area * sqrt(max(1.0 - l.y * l.y, 0.0));
+            let w = pow(cos_theta, params.exponent) * solid_angle;
+            sum += textureLoad(src_tex, vec2<u32>(x, y), 0).rgb * w;
+            weight += w;
+        }
+    }
+    textureStore(dst_tex, id.xy, vec4<f32>(sum / max(weight, 1e-6), 1.0));
+}
@@ -0,0 +1,300 @@
+struct Uniforms {
+    mvp: mat4x4<f32>,
+    model: mat4x4<f32>,
+    camera_pos: vec3<f32>,
+    light_intensity: f32,
+    light_dir: vec3<f32>,
+    light_enabled: f32,
+    light_color: vec3<f32>,
+    has_texture: f32,
+    tint: vec4<f32>,
+    cluster_params: vec4<f32>,
+    camera_forward: vec4<f32>,
+    ibl_params: vec4<f32>,
+    fog_color: vec4<f32>,
+    fog_params: vec4<f32>,
+    fog_height: vec4<f32>,
+};
+
+struct GpuLight {
+    position_range: vec4<f32>,
+    color_intensity: vec4<f32>,
+    direction_kind: vec4<f32>,
+    cone: vec4<f32>,
+};
+
+const CLUSTER_GRID_X: u32 = 16u;
+const CLUSTER_GRID_Y: u32 = 9u;
+const CLUSTER_GRID_Z: u32 = 24u;
+
+@group(0) @binding(0)
+var<uniform> ubo: Uniforms;
+
+@group(0) @binding(1)
+var tex_sampler: sampler;
+
+@group(0) @binding(2)
+var albedo_texture: texture_2d<f32>;
+
+@group(0) @binding(3)
+var<storage, read> lights: array<GpuLight>;
+
+@group(0) @binding(4)
+var<storage, read> clusters: array<vec2<u32>>;
+
+@group(0) @binding(5)
+var<storage, read> light_indices: array<u32>;
+
+@group(0) @binding(6)
+var irradiance_map: texture_2d<f32>;
+
+@group(0) @binding(7)
+var specular_map: texture_2d<f32>;
+
+@group(0) @binding(8)
+var env_sampler: sampler;
+
+const IBL_MAX_LOD: f32 = 4.0;
+const PI: f32 = 3.14159265;
+
+struct VsIn {
+    @location(0) pos: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+};
+
+struct VsOut {
+    @builtin(position) clip_pos: vec4<f32>,
+    @location(0) world_pos: vec3<f32>,
+    @location(1) world_normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+    @location(3) clip: vec4<f32>,
+};
+
+@vertex
+fn vs_main(v: VsIn) -> VsOut {
+    var out: VsOut;
+    out.clip_pos = ubo.mvp * vec4<f32>(v.pos, 1.0);
+    out.world_pos = (ubo.model * vec4<f32>(v.pos, 1.0)).xyz;
+    // Transform normal by model matrix (assumes uniform scale or use inverse-transpose)
+    out.world_normal = normalize((ubo.model * vec4<f32>(v.normal, 0.0)).xyz);
+    out.uv = v.uv;
+    out.clip = out.clip_pos;
+    return out;
+}
+
+// Cluster containing the fragment (same slicing as ClusteredLights::build)
+fn cluster_of(v: VsOut) -> u32 {
+    let ndc = v.clip.xy / v.clip.w;
+    let tile = clamp(
+        vec2<u32>((ndc * 0.5 + 0.5) * vec2<f32>(f32(CLUSTER_GRID_X), f32(CLUSTER_GRID_Y))),
+        vec2<u32>(0u, 0u),
+        vec2<u32>(CLUSTER_GRID_X - 1u, CLUSTER_GRID_Y - 1u),
+    );
+    let near = ubo.cluster_params.x;
+    let far = ubo.cluster_params.y;
+    let depth = max(dot(v.world_pos - ubo.camera_pos, ubo.camera_forward.xyz), near);
+    let slice = min(
+        u32(log(depth / near) / log(far / near) * f32(CLUSTER_GRID_Z)),
+        CLUSTER_GRID_Z - 1u,
+    );
+    return tile.x + tile.y * CLUSTER_GRID_X + slice * CLUSTER_GRID_X * CLUSTER_GRID_Y;
+}
+
+// Equirect UV of a world direction, with the sky rotation applied
+fn env_uv(dir: vec3<f32>) -> vec2<f32> {
+    let c = cos(ubo.ibl_params.y);
+    let s = sin(ubo.ibl_params.y);
+    let d = vec3<f32>(c * dir.x - s * dir.z, dir.y, s * dir.x + c * dir.z);
+    return vec2<f32>(atan2(d.z, d.x) / (2.0 * PI) + 0.5, acos(clamp(d.y, -1.0, 1.0)) / PI);
+}
+
+// Analytic split-sum environment BRDF (Karis, mobile approximation)
+fn env_brdf(f0: vec3<f32>, roughness: f32, n_dot_v: f32) -> vec3<f32> {
+    let c0 = vec4<f32>(-1.0, -0.0275, -0.572, 0.022);
+    let c1 = vec4<f32>(1.0, 0.0425, 1.04, -0.04);
+    let r = roughness * c0 + c1;
+    let a004 = min(r.x * r.x, exp2(-9.28 * n_dot_v)) * r.x + r.y;
+    let ab = vec2<f32>(-1.04, 1.04) * a004 + r.zw;
+    return f0 * ab.x + ab.y;
+}
+
+// Distance + height fog over a surface point (same math as FogSettings::amount)
+fn fog_amount(world_pos: vec3<f32>) -> f32 {
+    let mode = ubo.fog_color.w;
+    let dist = distance(world_pos, ubo.camera_pos);
+    var amount = 0.0;
+    if (mode > 2.5) {
+        let d = ubo.fog_params.z * dist;
+        amount = 1.0 - exp(-d * d);
+    } else if (mode > 1.5) {
+        amount = 1.0 - exp(-ubo.fog_params.z * dist);
+    } else if (mode > 0.5) {
+        amount = clamp((dist - ubo.fog_params.x) / max(ubo.fog_params.y - ubo.fog_params.x, 1e-4), 0.0, 1.0);
+    }
+    if (ubo.fog_height.x > 0.0) {
+        // Exponential height profile integrated along the view ray
+        let falloff = ubo.fog_height.z;
+        let rise = falloff * (world_pos.y - ubo.camera_pos.y);
+        let base = ubo.fog_height.x * exp(min(-falloff * (ubo.camera_pos.y - ubo.fog_height.y), 80.0));
+        var along = 1.0;
+        if (abs(rise) > 1e-4) {
+            along = (1.0 - exp(-rise)) / rise;
+        }
+        amount = 1.0 - (1.0 - amount) * exp(-base * dist * along);
+    }
+    return min(amount, ubo.fog_params.w);
+}
+
+// Blinn-Phong contribution of one light (diffuse + specular, already colored)
+fn shade_light(light: GpuLight, n: vec3<f32>, view_dir: vec3<f32>, world_pos: vec3<f32>) -> vec3<f32> {
+    let kind = light.direction_kind.w;
+    var l = -normalize(light.direction_kind.xyz);
+    var attenuation = 1.0;
+    if (kind > 0.5) {
+        let to_light = light.position_range.xyz - world_pos;
+        let dist = length(to_light);
+        let range = light.position_range.w;
+        if (dist >= range) {
+            return vec3<f32>(0.0);
+        }
+        l = to_light / max(dist, 1e-4);
+        // Smooth window so the light reaches exactly zero at its range
+        let falloff = clamp(1.0 - pow(dist / range, 4.0), 0.0, 1.0);
+        attenuation = falloff * falloff / (1.0 + dist * dist);
+        if (kind > 1.5) {
+            let cos_angle = dot(-l, normalize(light.direction_kind.xyz));
+            attenuation *= smoothstep(light.cone.y, light.cone.x, cos_angle);
+        }
+    }
+    let ndotl = max(dot(n, l), 0.0);
+    let half_dir = normalize(l + view_dir);
+    let specular = pow(max(dot(n, half_dir), 0.0), 32.0) * 0.25;
+    return light.color_intensity.rgb * light.color_intensity.a * attenuation * (ndotl * 0.65 + specular * ndotl);
+}
+
+@fragment
+fn fs_main(v: VsOut) -> @location(0) vec4<f32> {
+    // Normalize interpolated normal
+    var n = normalize(v.world_normal);
+
+    // If normal is degenerate, compute from derivatives as fallback
+    let n_len = dot(n, n);
+    if (n_len < 0.01) {
+        let dpdx_pos = dpdx(v.world_pos);
+        let dpdy_pos = dpdy(v.world_pos);
+        let c = cross(dpdx_pos, dpdy_pos);
+        let len2 = max(dot(c, c), 1e-8);
+        n = c * inverseSqrt(len2);
+    }
+
+    // View direction
+    let view_dir = normalize(ubo.camera_pos - v.world_pos);
+
+    // Default lighting factors
+    let ibl_on = ubo.ibl_params.x > 0.0;
+    var ambient = 0.15;
+    var diffuse = 0.0;
+    var specular = 0.0;
+    var l_color = vec3<f32>(1.0, 1.0, 1.0);
+
+    if (ubo.light_enabled > 0.5) {
+        // Light direction (normalized directional light)
+        let l = normalize(ubo.light_dir);
+        let ndotl = max(dot(n, l), 0.0);
+        diffuse = ndotl * 0.65;
+
+        // Specular (Blinn half-vector)
+        let half_dir = normalize(l + view_dir);
+        let ndoth = max(dot(n, half_dir), 0.0);
+        specular = pow(ndoth, 32.0) * 0.25;
+
+        l_color = ubo.light_color * ubo.light_intensity;
+    }
+
+    // Rim lighting for depth perception
+    let rim = pow(1.0 - max(dot(n, view_dir), 0.0), 2.5) * 0.08;
+
+    // Fill light from below-behind for shadow areas
+    let fill_dir = normalize(vec3<f32>(-0.3, -0.5, -0.4));
+    var fill = max(dot(n, fill_dir), 0.0) * 0.10;
+
+    // With IBL the environment replaces the constant ambient and fill light
+    if (ibl_on) {
+        ambient = 0.0;
+        fill = 0.0;
+    }
+
+    let total_light = (ambient + (diffuse + specular) + rim + fill);
+    let shade = clamp(total_light, 0.0, 1.5); // Allow slightly over 1.0 for highlights
+
+    // Base color from texture or tint
+    var base_color = ubo.tint;
+    if (ubo.has_texture > 0.5) {
+        let tex_color = textureSample(albedo_texture, tex_sampler, v.uv);
+        base_color = tex_color * ubo.tint;
+    }
+
+    // Extra directional lights, then the point/spot lights of this cluster
+    var dynamic_light = vec3<f32>(0.0);
+    let directional_count = u32(ubo.cluster_params.z);
+    for (var i = 0u; i < directional_count; i++) {
+        dynamic_light += shade_light(lights[i], n, view_dir, v.world_pos);
+    }
+    if (ubo.cluster_params.w > 0.5) {
+        let cluster = clusters[cluster_of(v)];
+        for (var i = 0u; i < cluster.y; i++) {
+            let light = lights[light_indices[cluster.x + i]];
+            dynamic_light += shade_light(light, n, view_dir, v.world_pos);
+        }
+    }
+
+    var color = base_color.rgb * (l_color * shade + dynamic_light);
+
+    if (ibl_on) {
+        let roughness = ubo.ibl_params.z;
+        let metallic = ubo.ibl_params.w;
+        let irradiance = textureSampleLevel(irradiance_map, env_sampler, env_uv(n), 0.0).rgb;
+        let r = reflect(-view_dir, n);
+        let prefiltered =
+            textureSampleLevel(specular_map, env_sampler, env_uv(r), roughness * IBL_MAX_LOD).rgb;
+        let f0 = mix(vec3<f32>(0.04), base_color.rgb, metallic);
+        let specular_ibl = prefiltered * env_brdf(f0, roughness, max(dot(n, view_dir), 0.0));
+        let diffuse_ibl = irradiance * base_color.rgb * (1.0 - metallic);
+        color += (diffuse_ibl + specular_ibl) * ubo.ibl_params.x;
+    }
+
+    color = mix(color, ubo.fog_color.rgb, fog_amount(v.world_pos));
+    return vec4<f32>(color, base_color.a);
+}
+
+// Debug shading variants (see ShadingMode); same vertex stage and bindings
+
+@fragment
+fn fs_unlit(v: VsOut) -> @location(0) vec4<f32> {
+    var base_color = ubo.tint;
+    if (ubo.has_texture > 0.5) {
+        base_color = textureSample(albedo_texture, tex_sampler, v.uv) * ubo.tint;
+    }
+    return base_color;
+}
+
+@fragment
+fn fs_wireframe(v: VsOut) -> @location(0) vec4<f32> {
+    return vec4<f32>(0.85, 0.9, 0.95, 1.0);
+}
+
+@fragment
+fn fs_normals(v: VsOut) -> @location(0) vec4<f32> {
+    var n = v.world_normal;
+    if (dot(n, n) < 0.01) {
+        n = cross(dpdx(v.world_pos), dpdy(v.world_pos));
+    }
+    return vec4<f32>(normalize(n) * 0.5 + 0.5, 1.0);
+}
+
+// Drawn with additive blending and no depth test: brightness = layers shaded
+@fragment
+fn fs_overdraw(v: VsOut) -> @location(0) vec4<f32> {
+    return vec4<f32>(0.12, 0.05, 0.02, 1.0);
+}
@@ -0,0 +1,30 @@
+@group(0) @binding(0)
+var src_tex: texture_2d<f32>;
+
+@group(0) @binding(1)
+var dst_tex: texture_storage_2d<rgba8unorm, write>;
+
+// A view sRGB da origem já devolve cor linear; o destino é gravado em sRGB
+fn linear_to_srgb(c: vec3<f32>) -> vec3<f32> {
+    let low = c * 12.92;
+    let high = 1.055 * pow(c, vec3<f32>(1.0 / 2.4)) - 0.055;
+    return select(high, low, c <= vec3<f32>(0.0031308));
+}
+
+@compute @workgroup_size(8, 8, 1)
+fn cs_downsample(@builtin(global_invocation_id) id: vec3<u32>) {
+    let dst_size = textureDimensions(dst_tex);
+    if (id.x >= dst_size.x || id.y >= dst_size.y) {
+        return;
+    }
+    let src_max = textureDimensions(src_tex) - vec2<u32>(1u);
+    let base = id.xy * 2u;
+    var sum = vec4<f32>(0.0);
+    for (var y = 0u; y < 2u; y = y + 1u) {
+        for (var x = 0u; x < 2u; x = x + 1u) {
+            sum = sum + textureLoad(src_tex, min(base + vec2<u32>(x, y), src_max), 0);
+        }
+    }
+    let color = sum * 0.25;
+    textureStore(dst_tex, vec2<i32>(id.xy), vec4<f32>(linear_to_srgb(color.rgb), color.a));
+}
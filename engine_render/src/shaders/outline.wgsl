@@ -0,0 +1,57 @@
+struct OutlineUniforms {
+    color: vec4<f32>,
+    params: vec4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> outline: OutlineUniforms;
+
+@group(0) @binding(1)
+var mask_texture: texture_2d<u32>;
+
+struct VsOut {
+    @builtin(position) clip_pos: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+@vertex
+fn vs_outline(@builtin(vertex_index) idx: u32) -> VsOut {
+    var out: VsOut;
+    let uv = vec2<f32>(f32((idx << 1u) & 2u), f32(idx & 2u));
+    out.clip_pos = vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+    out.uv = vec2<f32>(uv.x, 1.0 - uv.y);
+    return out;
+}
+
+fn masked(p: vec2<i32>, size: vec2<i32>) -> bool {
+    if any(p < vec2<i32>(0)) || any(p >= size) {
+        return false;
+    }
+    return textureLoad(mask_texture, p, 0).r != 0u;
+}
+
+@fragment
+fn fs_outline(v: VsOut) -> @location(0) vec4<f32> {
+    let size = vec2<i32>(textureDimensions(mask_texture));
+    let p = clamp(vec2<i32>(v.uv * vec2<f32>(size)), vec2<i32>(0), size - vec2<i32>(1));
+    // Só fora da silhueta: o objeto continua visível por dentro
+    if masked(p, size) {
+        discard;
+    }
+    let width = outline.params.x;
+    let radius = i32(ceil(width));
+    var nearest = 1e9;
+    for (var y = -radius; y <= radius; y++) {
+        for (var x = -radius; x <= radius; x++) {
+            if masked(p + vec2<i32>(x, y), size) {
+                nearest = min(nearest, length(vec2<f32>(f32(x), f32(y))));
+            }
+        }
+    }
+    // Borda suavizada no último pixel
+    let coverage = clamp(width + 0.5 - nearest, 0.0, 1.0);
+    if coverage <= 0.0 {
+        discard;
+    }
+    return vec4<f32>(outline.color.rgb, outline.color.a * coverage);
+}
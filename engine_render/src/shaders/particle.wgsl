@@ -0,0 +1,155 @@
+const CURVE_SAMPLES: u32 = 16u;
+
+struct ParticleUniforms {
+    view_proj: mat4x4<f32>,
+    emitter: mat4x4<f32>,
+    camera_right: vec4<f32>,
+    camera_up: vec4<f32>,
+    spawn: vec4<f32>,
+    lifetime_speed: vec4<f32>,
+    size_shape: vec4<f32>,
+    start_color: vec4<f32>,
+    end_color: vec4<f32>,
+    sheet: vec4<f32>,
+    curves: array<vec4<f32>, 16>,
+};
+
+struct Particle {
+    position_age: vec4<f32>,
+    velocity_life: vec4<f32>,
+    size_seed: vec4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> u: ParticleUniforms;
+
+@group(0) @binding(1)
+var<storage, read_write> sim_particles: array<Particle>;
+
+@group(0) @binding(2)
+var<storage, read> particles: array<Particle>;
+
+@group(0) @binding(3)
+var sheet_texture: texture_2d<f32>;
+
+@group(0) @binding(4)
+var sheet_sampler: sampler;
+
+fn hash(x: u32) -> u32 {
+    var h = x;
+    h ^= h >> 16u;
+    h *= 0x7feb352du;
+    h ^= h >> 15u;
+    h *= 0x846ca68bu;
+    h ^= h >> 16u;
+    return h;
+}
+
+fn rand(seed: ptr<function, u32>) -> f32 {
+    *seed = hash(*seed);
+    return f32(*seed) / 4294967295.0;
+}
+
+// Lifetime curves sampled at a normalized age (linear between samples)
+fn curve(t: f32) -> vec4<f32> {
+    let x = clamp(t, 0.0, 1.0) * f32(CURVE_SAMPLES - 1u);
+    let i = min(u32(x), CURVE_SAMPLES - 2u);
+    return mix(u.curves[i], u.curves[i + 1u], x - f32(i));
+}
+
+@compute @workgroup_size(64)
+fn cs_simulate(@builtin(global_invocation_id) id: vec3<u32>) {
+    let capacity = u32(u.spawn.z);
+    let i = id.x;
+    if (i >= capacity) {
+        return;
+    }
+    let dt = u.camera_right.w;
+    let first = u32(u.spawn.x);
+    let count = u32(u.spawn.y);
+    var p = sim_particles[i];
+    if ((i + capacity - first) % capacity < count) {
+        // Respawn at the emitter, inside a cone around its local +Y
+        var seed = hash(i ^ hash(u32(u.spawn.w)));
+        let angle = rand(&seed) * 6.2831853;
+        let cos_t = mix(1.0, cos(u.size_shape.z), rand(&seed));
+        let sin_t = sqrt(max(1.0 - cos_t * cos_t, 0.0));
+        let local_dir = vec3<f32>(cos(angle) * sin_t, cos_t, sin(angle) * sin_t);
+        let dir = normalize((u.emitter * vec4<f32>(local_dir, 0.0)).xyz);
+        let speed = mix(u.lifetime_speed.z, u.lifetime_speed.w, rand(&seed));
+        let lifetime = max(mix(u.lifetime_speed.x, u.lifetime_speed.y, rand(&seed)), 1e-3);
+        p.position_age = vec4<f32>((u.emitter * vec4<f32>(0.0, 0.0, 0.0, 1.0)).xyz, 0.0);
+        p.velocity_life = vec4<f32>(dir * speed, lifetime);
+        p.size_seed = vec4<f32>(mix(u.size_shape.x, u.size_shape.y, rand(&seed)), rand(&seed), 0.0, 0.0);
+    } else if (p.velocity_life.w > 0.0) {
+        let age = p.position_age.w + dt;
+        if (age >= p.velocity_life.w) {
+            p.velocity_life.w = 0.0;
+        } else {
+            var v = p.velocity_life.xyz;
+            v.y -= u.size_shape.w * dt;
+            let speed = curve(age / p.velocity_life.w).z;
+            p.velocity_life = vec4<f32>(v, p.velocity_life.w);
+            p.position_age = vec4<f32>(p.position_age.xyz + v * speed * dt, age);
+        }
+    }
+    sim_particles[i] = p;
+}
+
+struct VsOut {
+    @builtin(position) clip_pos: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) local: vec2<f32>,
+    @location(2) color: vec4<f32>,
+};
+
+@vertex
+fn vs_particle(@builtin(vertex_index) vertex: u32, @builtin(instance_index) instance: u32) -> VsOut {
+    var out: VsOut;
+    let p = particles[instance];
+    if (p.velocity_life.w <= 0.0) {
+        // Dead particle: every corner outside the clip volume
+        out.clip_pos = vec4<f32>(2.0, 2.0, 2.0, 1.0);
+        return out;
+    }
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(-0.5, -0.5),
+        vec2<f32>(0.5, -0.5),
+        vec2<f32>(0.5, 0.5),
+        vec2<f32>(-0.5, -0.5),
+        vec2<f32>(0.5, 0.5),
+        vec2<f32>(-0.5, 0.5),
+    );
+    let corner = corners[vertex % 6u];
+    let t = clamp(p.position_age.w / p.velocity_life.w, 0.0, 1.0);
+    let c = curve(t);
+    let size = p.size_seed.x * c.x;
+    let world = p.position_age.xyz + (u.camera_right.xyz * corner.x + u.camera_up.xyz * corner.y) * size;
+    out.clip_pos = u.view_proj * vec4<f32>(world, 1.0);
+
+    // Texture sheet: frames left to right, top to bottom, `cycles` times per life
+    let columns = max(u.sheet.x, 1.0);
+    let rows = max(u.sheet.y, 1.0);
+    let frames = columns * rows;
+    let frame = min(floor(fract(t * max(u.sheet.z, 0.0)) * frames), frames - 1.0);
+    let cell = vec2<f32>(frame % columns, floor(frame / columns));
+    out.uv = (cell + vec2<f32>(corner.x + 0.5, 0.5 - corner.y)) / vec2<f32>(columns, rows);
+    out.local = corner;
+
+    var color = mix(u.start_color, u.end_color, t);
+    color.a *= c.y;
+    out.color = color;
+    return out;
+}
+
+@fragment
+fn fs_particle(v: VsOut) -> @location(0) vec4<f32> {
+    var color = v.color;
+    if (u.sheet.w > 0.5) {
+        color *= textureSample(sheet_texture, sheet_sampler, v.uv);
+    } else {
+        // Soft round sprite when there is no texture
+        color.a *= smoothstep(0.5, 0.2, length(v.local));
+    }
+    return color;
+}
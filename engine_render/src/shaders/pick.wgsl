@@ -0,0 +1,24 @@
+struct PickUniforms {
+    mvp: mat4x4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> ubo: PickUniforms;
+
+struct VsOut {
+    @builtin(position) clip_pos: vec4<f32>,
+    @location(0) @interpolate(flat) id: u32,
+};
+
+@vertex
+fn vs_pick(@location(0) pos: vec3<f32>, @builtin(instance_index) instance: u32) -> VsOut {
+    var out: VsOut;
+    out.clip_pos = ubo.mvp * vec4<f32>(pos, 1.0);
+    out.id = instance;
+    return out;
+}
+
+@fragment
+fn fs_pick(v: VsOut) -> @location(0) u32 {
+    return v.id;
+}
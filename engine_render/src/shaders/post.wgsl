@@ -0,0 +1,154 @@
+struct PostUniforms {
+    exposure_tonemap: vec4<f32>,
+    vignette_lut: vec4<f32>,
+    blur: vec4<f32>,
+    auto_exposure: vec4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> post: PostUniforms;
+
+@group(0) @binding(1)
+var post_sampler: sampler;
+
+@group(0) @binding(2)
+var source_texture: texture_2d<f32>;
+
+@group(0) @binding(3)
+var bloom_texture: texture_2d<f32>;
+
+@group(0) @binding(4)
+var lut_texture: texture_2d<f32>;
+
+@group(0) @binding(5)
+var<storage, read> exposure_state: array<f32, 4>;
+
+struct VsOut {
+    @builtin(position) clip_pos: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+@vertex
+fn vs_post(@builtin(vertex_index) idx: u32) -> VsOut {
+    // Fullscreen triangle
+    let uv = vec2<f32>(f32((idx << 1u) & 2u), f32(idx & 2u));
+    var out: VsOut;
+    out.clip_pos = vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+    out.uv = vec2<f32>(uv.x, 1.0 - uv.y);
+    return out;
+}
+
+fn luminance(c: vec3<f32>) -> f32 {
+    return dot(c, vec3<f32>(0.2126, 0.7152, 0.0722));
+}
+
+// Manual exposure, or compensation on top of the adapted exposure
+fn scene_exposure() -> f32 {
+    if (post.auto_exposure.x > 0.5) {
+        return post.exposure_tonemap.x * exposure_state[0];
+    }
+    return post.exposure_tonemap.x;
+}
+
+@fragment
+fn fs_bright(v: VsOut) -> @location(0) vec4<f32> {
+    let color = textureSampleLevel(source_texture, post_sampler, v.uv, 0.0).rgb
+        * scene_exposure();
+    let threshold = post.exposure_tonemap.w;
+    // Soft knee around the threshold avoids a hard cut-off
+    let knee = max(threshold * 0.5, 1e-4);
+    let l = luminance(color);
+    let soft = clamp(l - threshold + knee, 0.0, 2.0 * knee);
+    let contribution = max(soft * soft / (4.0 * knee), l - threshold) / max(l, 1e-4);
+    return vec4<f32>(color * max(contribution, 0.0), 1.0);
+}
+
+@fragment
+fn fs_blur(v: VsOut) -> @location(0) vec4<f32> {
+    var weights = array<f32, 5>(0.227027, 0.1945946, 0.1216216, 0.054054, 0.016216);
+    var sum = textureSampleLevel(source_texture, post_sampler, v.uv, 0.0).rgb * weights[0];
+    for (var i = 1; i < 5; i++) {
+        let offset = post.blur.xy * f32(i);
+        sum += textureSampleLevel(source_texture, post_sampler, v.uv + offset, 0.0).rgb * weights[i];
+        sum += textureSampleLevel(source_texture, post_sampler, v.uv - offset, 0.0).rgb * weights[i];
+    }
+    return vec4<f32>(sum, 1.0);
+}
+
+fn tonemap(c: vec3<f32>, mode: f32) -> vec3<f32> {
+    if (mode > 1.5) {
+        // ACES filmic fit (Narkowicz)
+        return clamp((c * (2.51 * c + 0.03)) / (c * (2.43 * c + 0.59) + 0.14), vec3<f32>(0.0), vec3<f32>(1.0));
+    }
+    if (mode > 0.5) {
+        return c / (1.0 + c);
+    }
+    return clamp(c, vec3<f32>(0.0), vec3<f32>(1.0));
+}
+
+fn apply_lut(c: vec3<f32>, size: f32) -> vec3<f32> {
+    // Strip of `size` slices along X, blue selects the slice
+    let cell = clamp(c, vec3<f32>(0.0), vec3<f32>(1.0)) * (size - 1.0);
+    let slice0 = floor(cell.b);
+    let slice1 = min(slice0 + 1.0, size - 1.0);
+    let v = (cell.g + 0.5) / size;
+    let u0 = (slice0 * size + cell.r + 0.5) / (size * size);
+    let u1 = (slice1 * size + cell.r + 0.5) / (size * size);
+    let a = textureSampleLevel(lut_texture, post_sampler, vec2<f32>(u0, v), 0.0).rgb;
+    let b = textureSampleLevel(lut_texture, post_sampler, vec2<f32>(u1, v), 0.0).rgb;
+    return mix(a, b, cell.b - slice0);
+}
+
+@fragment
+fn fs_composite(v: VsOut) -> @location(0) vec4<f32> {
+    var color = textureSampleLevel(source_texture, post_sampler, v.uv, 0.0).rgb
+        * scene_exposure();
+    color += textureSampleLevel(bloom_texture, post_sampler, v.uv, 0.0).rgb
+        * post.exposure_tonemap.z;
+    color = tonemap(color, post.exposure_tonemap.y);
+
+    let lut_size = post.vignette_lut.w;
+    if (lut_size > 1.5) {
+        color = mix(color, apply_lut(color, lut_size), clamp(post.vignette_lut.z, 0.0, 1.0));
+    }
+
+    let d = distance(v.uv, vec2<f32>(0.5)) * 1.41421356;
+    let smoothness = max(post.vignette_lut.y, 0.01);
+    color *= 1.0 - post.vignette_lut.x * smoothstep(1.0 - smoothness, 1.0, d);
+    return vec4<f32>(color, 1.0);
+}
+
+const FXAA_REDUCE_MIN: f32 = 1.0 / 128.0;
+const FXAA_REDUCE_MUL: f32 = 1.0 / 8.0;
+const FXAA_SPAN_MAX: f32 = 8.0;
+
+fn fxaa_sample(uv: vec2<f32>) -> vec3<f32> {
+    return textureSampleLevel(source_texture, post_sampler, uv, 0.0).rgb;
+}
+
+@fragment
+fn fs_fxaa(v: VsOut) -> @location(0) vec4<f32> {
+    let texel = 1.0 / vec2<f32>(textureDimensions(source_texture));
+    let rgb_m = fxaa_sample(v.uv);
+    let luma_nw = luminance(fxaa_sample(v.uv + vec2<f32>(-1.0, -1.0) * texel));
+    let luma_ne = luminance(fxaa_sample(v.uv + vec2<f32>(1.0, -1.0) * texel));
+    let luma_sw = luminance(fxaa_sample(v.uv + vec2<f32>(-1.0, 1.0) * texel));
+    let luma_se = luminance(fxaa_sample(v.uv + vec2<f32>(1.0, 1.0) * texel));
+    let luma_m = luminance(rgb_m);
+    let luma_min = min(luma_m, min(min(luma_nw, luma_ne), min(luma_sw, luma_se)));
+    let luma_max = max(luma_m, max(max(luma_nw, luma_ne), max(luma_sw, luma_se)));
+
+    // Blur along the edge, perpendicular to the luma gradient
+    var dir = vec2<f32>(-((luma_nw + luma_ne) - (luma_sw + luma_se)), (luma_nw + luma_sw) - (luma_ne + luma_se));
+    let dir_reduce = max((luma_nw + luma_ne + luma_sw + luma_se) * 0.25 * FXAA_REDUCE_MUL, FXAA_REDUCE_MIN);
+    let rcp_dir_min = 1.0 / (min(abs(dir.x), abs(dir.y)) + dir_reduce);
+    dir = clamp(dir * rcp_dir_min, vec2<f32>(-FXAA_SPAN_MAX), vec2<f32>(FXAA_SPAN_MAX)) * texel;
+
+    let rgb_a = 0.5 * (fxaa_sample(v.uv + dir * (1.0 / 3.0 - 0.5)) + fxaa_sample(v.uv + dir * (2.0 / 3.0 - 0.5)));
+    let rgb_b = rgb_a * 0.5 + 0.25 * (fxaa_sample(v.uv - dir * 0.5) + fxaa_sample(v.uv + dir * 0.5));
+    let luma_b = luminance(rgb_b);
+    if (luma_b < luma_min || luma_b > luma_max) {
+        return vec4<f32>(rgb_a, 1.0);
+    }
+    return vec4<f32>(rgb_b, 1.0);
+}
@@ -0,0 +1,59 @@
+struct SkyUniforms {
+    view_proj_inv: mat4x4<f32>,
+    camera_pos: vec3<f32>,
+    exposure: f32,
+    params: vec4<f32>,
+    zenith_color: vec4<f32>,
+    horizon_color: vec4<f32>,
+    ground_color: vec4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> sky_ubo: SkyUniforms;
+
+@group(0) @binding(1)
+var sky_sampler: sampler;
+
+@group(0) @binding(2)
+var sky_texture: texture_2d<f32>;
+
+struct VsOut {
+    @builtin(position) clip_pos: vec4<f32>,
+    @location(0) ndc: vec2<f32>,
+};
+
+@vertex
+fn vs_sky(@builtin(vertex_index) idx: u32) -> VsOut {
+    // Fullscreen triangle
+    let uv = vec2<f32>(f32((idx << 1u) & 2u), f32(idx & 2u));
+    let p = uv * 2.0 - 1.0;
+    var out: VsOut;
+    out.clip_pos = vec4<f32>(p, 1.0, 1.0);
+    out.ndc = p;
+    return out;
+}
+
+const PI: f32 = 3.14159265;
+
+@fragment
+fn fs_sky(v: VsOut) -> @location(0) vec4<f32> {
+    let far = sky_ubo.view_proj_inv * vec4<f32>(v.ndc, 1.0, 1.0);
+    var dir = normalize(far.xyz / far.w - sky_ubo.camera_pos);
+
+    // Rotate the sky around Y
+    let c = cos(sky_ubo.params.x);
+    let s = sin(sky_ubo.params.x);
+    dir = vec3<f32>(c * dir.x - s * dir.z, dir.y, s * dir.x + c * dir.z);
+
+    var color: vec3<f32>;
+    if (sky_ubo.params.y > 0.5) {
+        let uv = vec2<f32>(atan2(dir.z, dir.x) / (2.0 * PI) + 0.5, acos(clamp(dir.y, -1.0, 1.0)) / PI);
+        // Explicit LOD: the u seam at +-PI would otherwise pick a tiny mip
+        color = textureSampleLevel(sky_texture, sky_sampler, uv, 0.0).rgb;
+    } else if (dir.y >= 0.0) {
+        color = mix(sky_ubo.horizon_color.rgb, sky_ubo.zenith_color.rgb, pow(dir.y, 0.5));
+    } else {
+        color = mix(sky_ubo.horizon_color.rgb, sky_ubo.ground_color.rgb, pow(-dir.y, 0.35));
+    }
+    return vec4<f32>(color * sky_ubo.exposure, 1.0);
+}
@@ -0,0 +1,42 @@
+struct SpriteUniforms {
+    view_proj: mat4x4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> ubo: SpriteUniforms;
+
+@group(0) @binding(1)
+var sprite_texture: texture_2d<f32>;
+
+@group(0) @binding(2)
+var sprite_sampler: sampler;
+
+struct VsIn {
+    @location(0) pos: vec3<f32>,
+    @location(1) uv: vec2<f32>,
+    @location(2) color: vec4<f32>,
+};
+
+struct VsOut {
+    @builtin(position) clip_pos: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+};
+
+@vertex
+fn vs_sprite(v: VsIn) -> VsOut {
+    var out: VsOut;
+    out.clip_pos = ubo.view_proj * vec4<f32>(v.pos, 1.0);
+    out.uv = v.uv;
+    out.color = v.color;
+    return out;
+}
+
+@fragment
+fn fs_sprite(v: VsOut) -> @location(0) vec4<f32> {
+    let color = textureSample(sprite_texture, sprite_sampler, v.uv) * v.color;
+    if (color.a <= 0.001) {
+        discard;
+    }
+    return color;
+}
@@ -0,0 +1,75 @@
+struct TerrainUniforms {
+    view_proj: mat4x4<f32>,
+    model: mat4x4<f32>,
+    light_dir: vec4<f32>,
+    light_color: vec4<f32>,
+    tiling: vec4<f32>,
+    layer_colors: array<vec4<f32>, 4>,
+};
+
+@group(0) @binding(0)
+var<uniform> ubo: TerrainUniforms;
+
+@group(0) @binding(1)
+var layer_sampler: sampler;
+
+@group(0) @binding(2)
+var splat_sampler: sampler;
+
+@group(0) @binding(3)
+var splat_map: texture_2d<f32>;
+
+@group(0) @binding(4)
+var layer0: texture_2d<f32>;
+
+@group(0) @binding(5)
+var layer1: texture_2d<f32>;
+
+@group(0) @binding(6)
+var layer2: texture_2d<f32>;
+
+@group(0) @binding(7)
+var layer3: texture_2d<f32>;
+
+struct VsIn {
+    @location(0) pos: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+};
+
+struct VsOut {
+    @builtin(position) clip_pos: vec4<f32>,
+    @location(0) normal: vec3<f32>,
+    @location(1) uv: vec2<f32>,
+};
+
+@vertex
+fn vs_terrain(v: VsIn) -> VsOut {
+    var out: VsOut;
+    let world = ubo.model * vec4<f32>(v.pos, 1.0);
+    out.clip_pos = ubo.view_proj * world;
+    out.normal = normalize((ubo.model * vec4<f32>(v.normal, 0.0)).xyz);
+    out.uv = v.uv;
+    return out;
+}
+
+fn layer_color(index: u32, sampled: vec4<f32>) -> vec3<f32> {
+    let layer = ubo.layer_colors[index];
+    return layer.rgb * mix(vec3<f32>(1.0), sampled.rgb, layer.w);
+}
+
+@fragment
+fn fs_terrain(v: VsOut) -> @location(0) vec4<f32> {
+    let weights = textureSample(splat_map, splat_sampler, v.uv);
+    let total = max(weights.r + weights.g + weights.b + weights.a, 0.0001);
+    var albedo = layer_color(0u, textureSample(layer0, layer_sampler, v.uv * ubo.tiling.x)) * weights.r;
+    albedo += layer_color(1u, textureSample(layer1, layer_sampler, v.uv * ubo.tiling.y)) * weights.g;
+    albedo += layer_color(2u, textureSample(layer2, layer_sampler, v.uv * ubo.tiling.z)) * weights.b;
+    albedo += layer_color(3u, textureSample(layer3, layer_sampler, v.uv * ubo.tiling.w)) * weights.a;
+    albedo /= total;
+
+    let n = normalize(v.normal);
+    let diffuse = max(dot(n, normalize(ubo.light_dir.xyz)), 0.0) * ubo.light_dir.w;
+    let lit = albedo * (0.25 + ubo.light_color.rgb * diffuse);
+    return vec4<f32>(lit, 1.0);
+}
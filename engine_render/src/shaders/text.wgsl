@@ -0,0 +1,45 @@
+struct TextUniforms {
+    view_proj: mat4x4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> ubo: TextUniforms;
+
+@group(0) @binding(1)
+var font_atlas: texture_2d<f32>;
+
+@group(0) @binding(2)
+var font_sampler: sampler;
+
+struct VsIn {
+    @location(0) pos: vec3<f32>,
+    @location(1) uv: vec2<f32>,
+    @location(2) color: vec4<f32>,
+};
+
+struct VsOut {
+    @builtin(position) clip_pos: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+};
+
+@vertex
+fn vs_text(v: VsIn) -> VsOut {
+    var out: VsOut;
+    out.clip_pos = ubo.view_proj * vec4<f32>(v.pos, 1.0);
+    out.uv = v.uv;
+    out.color = v.color;
+    return out;
+}
+
+@fragment
+fn fs_text(v: VsOut) -> @location(0) vec4<f32> {
+    let distance = textureSample(font_atlas, font_sampler, v.uv).a;
+    let width = max(fwidth(distance) * 0.5, 0.001);
+    let coverage = smoothstep(0.5 - width, 0.5 + width, distance);
+    let alpha = v.color.a * coverage;
+    if (alpha <= 0.001) {
+        discard;
+    }
+    return vec4<f32>(v.color.rgb, alpha);
+}
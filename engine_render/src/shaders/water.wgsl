@@ -0,0 +1,194 @@
+struct WaterUniforms {
+    view_proj: mat4x4<f32>,
+    inv_view_proj: mat4x4<f32>,
+    model: mat4x4<f32>,
+    camera_pos: vec4<f32>,
+    light_dir: vec4<f32>,
+    light_color: vec4<f32>,
+    shallow_color: vec4<f32>,
+    deep_color: vec4<f32>,
+    waves: vec4<f32>,
+    params: vec4<f32>,
+};
+
+struct SkyUniforms {
+    view_proj_inv: mat4x4<f32>,
+    camera_pos: vec3<f32>,
+    exposure: f32,
+    params: vec4<f32>,
+    zenith_color: vec4<f32>,
+    horizon_color: vec4<f32>,
+    ground_color: vec4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> ubo: WaterUniforms;
+
+@group(0) @binding(1)
+var<uniform> sky: SkyUniforms;
+
+@group(0) @binding(2)
+var repeat_sampler: sampler;
+
+@group(0) @binding(3)
+var clamp_sampler: sampler;
+
+@group(0) @binding(4)
+var sky_texture: texture_2d<f32>;
+
+@group(0) @binding(5)
+var scene_color: texture_2d<f32>;
+
+@group(0) @binding(6)
+var scene_depth: texture_depth_2d;
+
+@group(0) @binding(7)
+var normal_map: texture_2d<f32>;
+
+struct VsOut {
+    @builtin(position) clip_pos: vec4<f32>,
+    @location(0) world: vec3<f32>,
+};
+
+@vertex
+fn vs_water(@builtin(vertex_index) idx: u32) -> VsOut {
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(-0.5, -0.5),
+        vec2<f32>(-0.5, 0.5),
+        vec2<f32>(0.5, 0.5),
+        vec2<f32>(-0.5, -0.5),
+        vec2<f32>(0.5, 0.5),
+        vec2<f32>(0.5, -0.5),
+    );
+    let corner = corners[idx] * ubo.waves.xy;
+    let world = ubo.model * vec4<f32>(corner.x, 0.0, corner.y, 1.0);
+    var out: VsOut;
+    out.clip_pos = ubo.view_proj * world;
+    out.world = world.xyz;
+    return out;
+}
+
+const PI: f32 = 3.14159265;
+
+fn depth_at(uv: vec2<f32>) -> f32 {
+    let size = vec2<i32>(textureDimensions(scene_depth));
+    let pixel = clamp(vec2<i32>(uv * vec2<f32>(size)), vec2<i32>(0), size - 1);
+    return textureLoad(scene_depth, pixel, 0);
+}
+
+fn world_at(uv: vec2<f32>, depth: f32) -> vec3<f32> {
+    let ndc = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, depth, 1.0);
+    let p = ubo.inv_view_proj * ndc;
+    return p.xyz / p.w;
+}
+
+// Same sky as `SKY_SHADER`, so reflections match the background
+fn sky_color(direction: vec3<f32>) -> vec3<f32> {
+    let c = cos(sky.params.x);
+    let s = sin(sky.params.x);
+    let dir = vec3<f32>(c * direction.x - s * direction.z, direction.y, s * direction.x + c * direction.z);
+    var color: vec3<f32>;
+    if (sky.params.y > 0.5) {
+        let uv = vec2<f32>(atan2(dir.z, dir.x) / (2.0 * PI) + 0.5, acos(clamp(dir.y, -1.0, 1.0)) / PI);
+        color = textureSampleLevel(sky_texture, repeat_sampler, uv, 0.0).rgb;
+    } else if (dir.y >= 0.0) {
+        color = mix(sky.horizon_color.rgb, sky.zenith_color.rgb, pow(dir.y, 0.5));
+    } else {
+        color = mix(sky.horizon_color.rgb, sky.ground_color.rgb, pow(-dir.y, 0.35));
+    }
+    return color * sky.exposure;
+}
+
+// Surface slope (d height / dx, d height / dz) at a world position
+fn wave_slope(p: vec2<f32>) -> vec2<f32> {
+    let scale = max(ubo.waves.z, 0.01);
+    let t = ubo.camera_pos.w * ubo.waves.w;
+    if (ubo.light_color.w > 0.5) {
+        let a = textureSampleLevel(normal_map, repeat_sampler, p / scale + vec2<f32>(t, t * 0.4) / scale, 0.0).xy;
+        let b = textureSampleLevel(normal_map, repeat_sampler, p / (scale * 0.63) - vec2<f32>(t * 0.3, t) / scale, 0.0).xy;
+        return (a + b - 1.0) * ubo.params.x;
+    }
+    var slope = vec2<f32>(0.0);
+    var dirs = array<vec2<f32>, 4>(
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(0.6, 0.8),
+        vec2<f32>(-0.7, 0.7),
+        vec2<f32>(0.2, -0.98),
+    );
+    var lengths = array<f32, 4>(1.0, 0.61, 0.37, 0.23);
+    for (var i = 0; i < 4; i++) {
+        let k = 2.0 * PI / (scale * lengths[i]);
+        let phase = dot(dirs[i], p) * k + t * k;
+        slope += dirs[i] * cos(phase) * lengths[i];
+    }
+    return slope * ubo.params.x * 0.25;
+}
+
+// Screen-space reflection: march the reflected ray against the scene depth
+fn trace_reflection(origin: vec3<f32>, direction: vec3<f32>) -> vec4<f32> {
+    var travel = 0.15;
+    for (var i = 0; i < 32; i++) {
+        let p = origin + direction * travel;
+        let clip = ubo.view_proj * vec4<f32>(p, 1.0);
+        if (clip.w <= 0.0) {
+            break;
+        }
+        let ndc = clip.xyz / clip.w;
+        let uv = vec2<f32>(ndc.x * 0.5 + 0.5, 0.5 - ndc.y * 0.5);
+        if (any(uv < vec2<f32>(0.0)) || any(uv > vec2<f32>(1.0))) {
+            break;
+        }
+        let depth = depth_at(uv);
+        if (depth < 1.0 && ndc.z > depth) {
+            // Behind the depth buffer: a hit if the surface is close enough
+            let hit = world_at(uv, depth);
+            if (distance(hit, p) < travel * 0.08 + 0.1) {
+                let edge = min(min(uv.x, 1.0 - uv.x), min(uv.y, 1.0 - uv.y));
+                let fade = clamp(edge * 10.0, 0.0, 1.0);
+                return vec4<f32>(textureSampleLevel(scene_color, clamp_sampler, uv, 0.0).rgb, fade);
+            }
+            break;
+        }
+        travel *= 1.15;
+    }
+    return vec4<f32>(0.0);
+}
+
+@fragment
+fn fs_water(v: VsOut) -> @location(0) vec4<f32> {
+    let size = vec2<f32>(textureDimensions(scene_color));
+    let uv = v.clip_pos.xy / size;
+    if (depth_at(uv) < v.clip_pos.z) {
+        discard;
+    }
+
+    let slope = wave_slope(v.world.xz);
+    let n = normalize(vec3<f32>(-slope.x, 1.0, -slope.y));
+    let to_eye = ubo.camera_pos.xyz - v.world;
+    let view_dir = normalize(to_eye);
+
+    // Refraction: distorted scene, unless that pulls in something in front of the water
+    var refract_uv = uv + n.xz * ubo.params.y;
+    if (depth_at(refract_uv) < v.clip_pos.z) {
+        refract_uv = uv;
+    }
+    let floor_depth = depth_at(refract_uv);
+    var thickness = 1000.0;
+    if (floor_depth < 1.0) {
+        thickness = distance(world_at(refract_uv, floor_depth), v.world);
+    }
+    let fog = 1.0 - exp(-ubo.shallow_color.w * thickness);
+    let below = textureSampleLevel(scene_color, clamp_sampler, refract_uv, 0.0).rgb * ubo.shallow_color.rgb;
+    let refracted = mix(below, ubo.deep_color.rgb, fog);
+
+    let r = reflect(-view_dir, n);
+    let traced = trace_reflection(v.world, r);
+    let reflected = mix(sky_color(r), traced.rgb, traced.a);
+
+    let cos_theta = clamp(dot(n, view_dir), 0.0, 1.0);
+    let fresnel = (0.02 + 0.98 * pow(1.0 - cos_theta, 5.0)) * ubo.deep_color.w;
+    let h = normalize(normalize(ubo.light_dir.xyz) + view_dir);
+    let specular = pow(max(dot(n, h), 0.0), 256.0) * ubo.light_dir.w * ubo.light_color.rgb;
+
+    return vec4<f32>(mix(refracted, reflected, fresnel) + specular, 1.0);
+}
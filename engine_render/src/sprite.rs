@@ -0,0 +1,139 @@
+//! Sprite batching - turns `Sprite` components into sorted quads grouped by texture
+//!
+//! Sprites are sorted back to front (sorting layer, order in layer, then camera
+//! distance) and consecutive sprites sharing a texture and layer are merged into
+//! one `SpriteBatch`, drawn with `SPRITE_SHADER` in a single indexed draw.
+//! Sprites whose texture was packed into a loaded atlas are drawn from the
+//! atlas page (see `sprite_atlas`), so they batch with each other.
+
+use engine_core::components::{Sprite, TextureHandle, Transform};
+use engine_core::ecs::EngineWorld;
+use glam::{Vec2, Vec3};
+
+use crate::shader::SPRITE_VERTEX_STRIDE;
+use crate::sprite_atlas::SpriteAtlases;
+
+/// One sprite vertex: position (vec3), uv (vec2), color (vec4) = 36 bytes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpriteVertex {
+    pub position: [f32; 3],
+    pub uv: [f32; 2],
+    pub color: [f32; 4],
+}
+
+/// Quads sharing a texture, in draw order
+#[derive(Debug, Clone, Default)]
+pub struct SpriteBatch {
+    pub texture: TextureHandle,
+    pub sorting_layer: u16,
+    pub vertices: Vec<SpriteVertex>,
+    pub indices: Vec<u32>,
+}
+
+impl SpriteBatch {
+    pub fn quad_count(&self) -> usize {
+        self.vertices.len() / 4
+    }
+
+    /// Vertex buffer contents (layout of `SPRITE_SHADER`)
+    pub fn vertex_bytes(&self) -> Vec<u8> {
+        vertex_bytes(&self.vertices)
+    }
+
+    fn push_quad(&mut self, quad: [SpriteVertex; 4]) {
+        push_quad(&mut self.vertices, &mut self.indices, quad);
+    }
+}
+
+/// Packed `SpriteVertex` data (also used by the text batches and the editor viewport)
+pub fn vertex_bytes(vertices: &[SpriteVertex]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(vertices.len() * SPRITE_VERTEX_STRIDE);
+    for v in vertices {
+        for value in v.position.iter().chain(&v.uv).chain(&v.color) {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+    bytes
+}
+
+/// Append a quad (counter-clockwise corners) as two triangles
+pub fn push_quad(
+    vertices: &mut Vec<SpriteVertex>,
+    indices: &mut Vec<u32>,
+    quad: [SpriteVertex; 4],
+) {
+    let base = vertices.len() as u32;
+    vertices.extend_from_slice(&quad);
+    indices.extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
+}
+
+/// World-space corners of a sprite (counter-clockwise from bottom-left)
+pub fn sprite_quad(transform: &Transform, sprite: &Sprite) -> [SpriteVertex; 4] {
+    let matrix = transform.to_mat4();
+    let [u0, v0, u1, v1] = sprite.uv_rect;
+    let (u0, u1) = if sprite.flip_x { (u1, u0) } else { (u0, u1) };
+    let (v0, v1) = if sprite.flip_y { (v1, v0) } else { (v0, v1) };
+    // Texture V grows downwards, so the bottom edge samples v1
+    let corners = [
+        (Vec2::new(0.0, 0.0), [u0, v1]),
+        (Vec2::new(1.0, 0.0), [u1, v1]),
+        (Vec2::new(1.0, 1.0), [u1, v0]),
+        (Vec2::new(0.0, 1.0), [u0, v0]),
+    ];
+    corners.map(|(corner, uv)| {
+        let local = (corner - sprite.pivot) * sprite.size;
+        SpriteVertex {
+            position: matrix.transform_point3(local.extend(0.0)).to_array(),
+            uv,
+            color: sprite.color,
+        }
+    })
+}
+
+/// Sorted, texture-grouped batches for every sprite in the world
+pub fn build_sprite_batches(
+    world: &EngineWorld,
+    atlases: &SpriteAtlases,
+    camera_position: Vec3,
+    camera_target: Vec3,
+) -> Vec<SpriteBatch> {
+    let forward = (camera_target - camera_position).normalize_or(Vec3::NEG_Z);
+    let mut sprites: Vec<(Sprite, Transform, f32)> = world
+        .world()
+        .query::<(&Transform, &Sprite)>()
+        .iter()
+        .map(|(transform, sprite)| {
+            let depth = (transform.position - camera_position).dot(forward);
+            (atlases.remap(sprite), *transform, depth)
+        })
+        .collect();
+    sprites.sort_by(|a, b| {
+        a.0.sorting_layer
+            .cmp(&b.0.sorting_layer)
+            .then(a.0.order_in_layer.cmp(&b.0.order_in_layer))
+            .then(b.2.total_cmp(&a.2))
+    });
+
+    let mut batches: Vec<SpriteBatch> = Vec::new();
+    for (sprite, transform, _) in &sprites {
+        let quad = sprite_quad(transform, sprite);
+        match batches.last_mut() {
+            Some(batch)
+                if batch.texture == sprite.texture
+                    && batch.sorting_layer == sprite.sorting_layer =>
+            {
+                batch.push_quad(quad);
+            }
+            _ => {
+                let mut batch = SpriteBatch {
+                    texture: sprite.texture,
+                    sorting_layer: sprite.sorting_layer,
+                    ..Default::default()
+                };
+                batch.push_quad(quad);
+                batches.push(batch);
+            }
+        }
+    }
+    batches
+}
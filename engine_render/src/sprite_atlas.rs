@@ -0,0 +1,322 @@
+//! Sprite atlases - many sprite images packed into one texture
+//!
+//! `pack_sprite_atlas` packs images into rows (tallest first) on the smallest
+//! power-of-two page that fits, then writes the page as `<name>.png` next to a
+//! `<name>.atlas` text file listing where each sprite landed:
+//!
+//! ```text
+//! # Dengine sprite atlas
+//! image=ui.png
+//! size=512 256
+//! sprite=Sprites/button.png 0 0 128 48
+//! ```
+//!
+//! Sprite paths are relative to the Assets directory (see `bundle_key`). Once
+//! an atlas is loaded (`AssetManager::load_sprite_atlas`), every sprite whose
+//! texture was packed is drawn from the atlas instead: `build_sprite_batches`
+//! swaps the texture and remaps the UV rect, so sprites of one atlas share a
+//! batch and a draw call.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use engine_core::components::{Sprite, TextureHandle};
+
+use crate::asset_bundle::bundle_key;
+use crate::vfs::{open_image, vfs};
+
+/// Extension of the atlas description file
+pub const ATLAS_EXTENSION: &str = "atlas";
+
+const ATLAS_HEADER: &str = "# Dengine sprite atlas";
+
+/// Where one sprite sits in the atlas page, in pixels
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AtlasRegion {
+    /// Path of the source image relative to Assets, `/` separated
+    pub sprite: String,
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Layout of a packed atlas page
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpriteAtlas {
+    /// Page image, relative to the `.atlas` file
+    pub image: String,
+    pub width: u32,
+    pub height: u32,
+    pub regions: Vec<AtlasRegion>,
+}
+
+impl SpriteAtlas {
+    pub fn region(&self, sprite: &str) -> Option<&AtlasRegion> {
+        let key = bundle_key(sprite);
+        self.regions.iter().find(|region| region.sprite == key)
+    }
+
+    /// Normalized (u0, v0, u1, v1) of a region, as `Sprite::uv_rect` expects
+    pub fn uv_rect(&self, region: &AtlasRegion) -> [f32; 4] {
+        let (w, h) = (self.width.max(1) as f32, self.height.max(1) as f32);
+        [
+            region.x as f32 / w,
+            region.y as f32 / h,
+            (region.x + region.width) as f32 / w,
+            (region.y + region.height) as f32 / h,
+        ]
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut out = format!(
+            "{ATLAS_HEADER}\nimage={}\nsize={} {}\n",
+            self.image, self.width, self.height
+        );
+        for region in &self.regions {
+            out.push_str(&format!(
+                "sprite={} {} {} {} {}\n",
+                region.sprite, region.x, region.y, region.width, region.height
+            ));
+        }
+        out
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut atlas = Self::default();
+        for line in text.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            match key.trim() {
+                "image" => atlas.image = value.trim().to_string(),
+                "size" => {
+                    let mut numbers = value.split_whitespace().map(str::parse::<u32>);
+                    match (numbers.next(), numbers.next()) {
+                        (Some(Ok(w)), Some(Ok(h))) => (atlas.width, atlas.height) = (w, h),
+                        _ => return Err(format!("Tamanho de atlas inválido: {value}")),
+                    }
+                }
+                "sprite" => {
+                    // The path may contain spaces: the four numbers are the last words
+                    let mut words: Vec<&str> = value.trim().rsplitn(5, ' ').collect();
+                    words.reverse();
+                    let numbers: Vec<u32> = words
+                        .iter()
+                        .skip(1)
+                        .filter_map(|w| w.parse().ok())
+                        .collect();
+                    if words.len() != 5 || numbers.len() != 4 {
+                        return Err(format!("Sprite de atlas inválido: {value}"));
+                    }
+                    atlas.regions.push(AtlasRegion {
+                        sprite: words[0].to_string(),
+                        x: numbers[0],
+                        y: numbers[1],
+                        width: numbers[2],
+                        height: numbers[3],
+                    });
+                }
+                _ => {}
+            }
+        }
+        if atlas.image.is_empty() || atlas.width == 0 || atlas.height == 0 {
+            return Err("Atlas sem imagem ou tamanho".to_string());
+        }
+        Ok(atlas)
+    }
+
+    /// Read an `.atlas` file through the VFS
+    pub fn load(path: &Path) -> Result<Self, String> {
+        Self::parse(&vfs().read_to_string(path)?).map_err(|e| format!("{e} em {}", path.display()))
+    }
+
+    /// Page image of the `.atlas` file at `path`
+    pub fn image_path(&self, path: &Path) -> PathBuf {
+        path.parent().unwrap_or(Path::new("")).join(&self.image)
+    }
+}
+
+/// Positions of `sizes` packed into rows on the smallest square-ish
+/// power-of-two page up to `max_size`, `padding` pixels apart; `None` when
+/// they don't fit
+pub fn pack_rects(
+    sizes: &[(u32, u32)],
+    max_size: u32,
+    padding: u32,
+) -> Option<(u32, u32, Vec<(u32, u32)>)> {
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by_key(|&i| std::cmp::Reverse((sizes[i].1, sizes[i].0)));
+    let area: u64 = sizes
+        .iter()
+        .map(|&(w, h)| (w + padding) as u64 * (h + padding) as u64)
+        .sum();
+    let widest = sizes.iter().map(|s| s.0).max().unwrap_or(1);
+
+    let mut width = widest.next_power_of_two().max(1);
+    let mut height = width;
+    while (width as u64 * height as u64) < area {
+        if width <= height {
+            width *= 2;
+        } else {
+            height *= 2;
+        }
+    }
+    while width <= max_size && height <= max_size {
+        if let Some(positions) = pack_rows(sizes, &order, width, height, padding) {
+            return Some((width, height, positions));
+        }
+        if width <= height {
+            width *= 2;
+        } else {
+            height *= 2;
+        }
+    }
+    None
+}
+
+fn pack_rows(
+    sizes: &[(u32, u32)],
+    order: &[usize],
+    width: u32,
+    height: u32,
+    padding: u32,
+) -> Option<Vec<(u32, u32)>> {
+    let mut positions = vec![(0, 0); sizes.len()];
+    let (mut x, mut y, mut row_height) = (0u32, 0u32, 0u32);
+    for &i in order {
+        let (w, h) = sizes[i];
+        if w > width {
+            return None;
+        }
+        if x + w > width {
+            x = 0;
+            y += row_height + padding;
+            row_height = 0;
+        }
+        if y + h > height {
+            return None;
+        }
+        positions[i] = (x, y);
+        x += w + padding;
+        row_height = row_height.max(h);
+    }
+    Some(positions)
+}
+
+/// Pack `sprites` (image files under `assets_root`) into `atlas_path` (a
+/// `.atlas` file) and its `.png` page
+pub fn pack_sprite_atlas(
+    sprites: &[PathBuf],
+    assets_root: &Path,
+    atlas_path: &Path,
+    max_size: u32,
+    padding: u32,
+) -> Result<SpriteAtlas, String> {
+    if sprites.is_empty() {
+        return Err("Nenhum sprite para empacotar".to_string());
+    }
+    let mut images = Vec::with_capacity(sprites.len());
+    for path in sprites {
+        let relative = path
+            .strip_prefix(assets_root)
+            .map_err(|_| format!("Sprite fora da pasta Assets do projeto: {}", path.display()))?;
+        let image = open_image(path)?.into_rgba8();
+        images.push((bundle_key(&relative.to_string_lossy()), image));
+    }
+    let sizes: Vec<(u32, u32)> = images.iter().map(|(_, i)| i.dimensions()).collect();
+    let (width, height, positions) = pack_rects(&sizes, max_size, padding)
+        .ok_or_else(|| format!("Os sprites não cabem num atlas de {max_size}x{max_size}"))?;
+
+    let mut page = image::RgbaImage::new(width, height);
+    let mut atlas = SpriteAtlas {
+        image: atlas_path
+            .with_extension("png")
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        width,
+        height,
+        regions: Vec::with_capacity(images.len()),
+    };
+    for ((sprite, image), (x, y)) in images.into_iter().zip(positions) {
+        image::imageops::replace(&mut page, &image, x as i64, y as i64);
+        atlas.regions.push(AtlasRegion {
+            sprite,
+            x,
+            y,
+            width: image.width(),
+            height: image.height(),
+        });
+    }
+    let page_path = atlas.image_path(atlas_path);
+    page.save(&page_path)
+        .map_err(|e| format!("Falha ao salvar {}: {e}", page_path.display()))?;
+    fs::write(atlas_path, atlas.to_text())
+        .map_err(|e| format!("Falha ao salvar {}: {e}", atlas_path.display()))?;
+    Ok(atlas)
+}
+
+/// Where the sprites of loaded atlases are drawn from
+#[derive(Debug, Clone, Default)]
+pub struct SpriteAtlases {
+    /// Asset key of a packed sprite -> (atlas page, UV rect in the page)
+    by_path: HashMap<String, (TextureHandle, [f32; 4])>,
+    /// Same, for the texture handles loaded from those paths
+    by_texture: HashMap<TextureHandle, (TextureHandle, [f32; 4])>,
+}
+
+impl SpriteAtlases {
+    pub fn is_empty(&self) -> bool {
+        self.by_path.is_empty()
+    }
+
+    /// Number of packed sprites
+    pub fn len(&self) -> usize {
+        self.by_path.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.by_path.clear();
+        self.by_texture.clear();
+    }
+
+    /// Draw every sprite of `atlas` from `page`
+    pub fn add(&mut self, atlas: &SpriteAtlas, page: TextureHandle) {
+        for region in &atlas.regions {
+            self.by_path
+                .insert(region.sprite.clone(), (page, atlas.uv_rect(region)));
+        }
+    }
+
+    /// Record that `texture` was loaded from `path`, so sprites using it are
+    /// drawn from the atlas when it was packed; returns whether it was
+    pub fn bind_texture(&mut self, path: &Path, texture: TextureHandle) -> bool {
+        match self.by_path.get(&bundle_key(&path.to_string_lossy())) {
+            Some(&entry) => {
+                self.by_texture.insert(texture, entry);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// `sprite` drawn from its atlas page when its texture was packed
+    pub fn remap(&self, sprite: &Sprite) -> Sprite {
+        let Some(&(page, [a0, b0, a1, b1])) = self.by_texture.get(&sprite.texture) else {
+            return *sprite;
+        };
+        let [u0, v0, u1, v1] = sprite.uv_rect;
+        Sprite {
+            texture: page,
+            uv_rect: [
+                a0 + u0 * (a1 - a0),
+                b0 + v0 * (b1 - b0),
+                a0 + u1 * (a1 - a0),
+                b0 + v1 * (b1 - b0),
+            ],
+            ..*sprite
+        }
+    }
+}
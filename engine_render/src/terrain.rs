@@ -0,0 +1,611 @@
+//! Heightmap terrain - height/splat data, chunked LOD meshes and sculpt/paint brushes
+//!
+//! The terrain is a square grid centered on its origin in the local XZ plane.
+//! Heights are stored normalized (0..1) and scaled by `height_scale`; the splat
+//! map holds four layer weights per sample, blended by `TERRAIN_SHADER`.
+//!
+//! The mesh is split into chunks of `TERRAIN_CHUNK_QUADS` quads per side. Each
+//! chunk picks a LOD (every `2^lod`-th sample) from its distance to the camera
+//! and gets a skirt along its border that hides the cracks between neighbouring
+//! chunks at different LODs.
+
+use std::path::Path;
+
+use glam::{Mat4, Vec2, Vec3};
+
+use crate::shader::TERRAIN_UNIFORM_SIZE;
+
+/// Texture layers blended by the splat map
+pub const TERRAIN_LAYERS: usize = 4;
+
+/// Quads per chunk side at LOD 0; the grid is always a multiple of it
+pub const TERRAIN_CHUNK_QUADS: u32 = 32;
+
+/// Coarsest LOD (a chunk side is then 32 / 2^5 = 1 quad)
+pub const TERRAIN_MAX_LOD: u32 = 5;
+
+/// One texture layer of the splat map
+#[derive(Debug, Clone, PartialEq)]
+pub struct TerrainLayer {
+    pub texture: Option<String>,
+    /// Texture repeats across the whole terrain
+    pub tiling: f32,
+    /// Multiplies the texture (the color itself when there is none)
+    pub color: [f32; 3],
+}
+
+impl Default for TerrainLayer {
+    fn default() -> Self {
+        Self {
+            texture: None,
+            tiling: 32.0,
+            color: [1.0; 3],
+        }
+    }
+}
+
+/// What a brush stroke does to the terrain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BrushMode {
+    #[default]
+    Raise,
+    Lower,
+    Smooth,
+    /// Pulls heights towards `TerrainBrush::target_height`
+    Flatten,
+    /// Adds weight to `TerrainBrush::layer` in the splat map
+    Paint,
+}
+
+impl BrushMode {
+    pub fn all() -> [BrushMode; 5] {
+        [
+            BrushMode::Raise,
+            BrushMode::Lower,
+            BrushMode::Smooth,
+            BrushMode::Flatten,
+            BrushMode::Paint,
+        ]
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            BrushMode::Raise => "Elevar",
+            BrushMode::Lower => "Baixar",
+            BrushMode::Smooth => "Suavizar",
+            BrushMode::Flatten => "Nivelar",
+            BrushMode::Paint => "Pintar",
+        }
+    }
+}
+
+/// Sculpt/paint brush settings
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TerrainBrush {
+    pub mode: BrushMode,
+    /// World units
+    pub radius: f32,
+    /// Height change (world units) or paint weight per second at the center
+    pub strength: f32,
+    pub layer: usize,
+    /// Local height used by `BrushMode::Flatten`
+    pub target_height: f32,
+}
+
+impl Default for TerrainBrush {
+    fn default() -> Self {
+        Self {
+            mode: BrushMode::Raise,
+            radius: 3.0,
+            strength: 2.0,
+            layer: 0,
+            target_height: 0.0,
+        }
+    }
+}
+
+/// Vertex data of the terrain mesh (local space, uv spans the whole terrain)
+#[derive(Debug, Clone, Default)]
+pub struct TerrainMesh {
+    pub positions: Vec<Vec3>,
+    pub normals: Vec<Vec3>,
+    pub uvs: Vec<[f32; 2]>,
+    pub indices: Vec<u32>,
+}
+
+impl TerrainMesh {
+    pub fn triangles(&self) -> Vec<[u32; 3]> {
+        self.indices
+            .chunks_exact(3)
+            .map(|t| [t[0], t[1], t[2]])
+            .collect()
+    }
+
+    /// Vertex buffer contents: pos(vec3) + normal(vec3) + uv(vec2), as `LIT_VERTEX_STRIDE`
+    pub fn vertex_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.positions.len() * 32);
+        for ((p, n), uv) in self.positions.iter().zip(&self.normals).zip(&self.uvs) {
+            for value in p.to_array().iter().chain(&n.to_array()).chain(uv) {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        bytes
+    }
+
+    pub fn index_bytes(&self) -> Vec<u8> {
+        self.indices.iter().flat_map(|i| i.to_le_bytes()).collect()
+    }
+
+    fn append(&mut self, other: TerrainMesh) {
+        let base = self.positions.len() as u32;
+        self.positions.extend(other.positions);
+        self.normals.extend(other.normals);
+        self.uvs.extend(other.uvs);
+        self.indices
+            .extend(other.indices.into_iter().map(|i| base + i));
+    }
+}
+
+/// Heightmap terrain
+#[derive(Debug, Clone, PartialEq)]
+pub struct Terrain {
+    /// Samples per side
+    resolution: u32,
+    /// Normalized heights, row-major (z rows, x columns)
+    heights: Vec<f32>,
+    /// Layer weights per sample, summing to 1
+    splat: Vec<[f32; TERRAIN_LAYERS]>,
+    /// World size of one side
+    size: f32,
+    /// Height of a sample at 1.0
+    height_scale: f32,
+    /// Chunks closer than this use LOD 0; each further doubling drops a level
+    pub lod_distance: f32,
+    pub layers: [TerrainLayer; TERRAIN_LAYERS],
+    // Bumped by every height edit (mesh) and splat edit (texture)
+    height_version: u64,
+    splat_version: u64,
+}
+
+impl Terrain {
+    /// Flat terrain; `resolution` is rounded up to a whole number of chunks
+    pub fn new(resolution: u32, size: f32, height_scale: f32) -> Self {
+        let resolution = Self::valid_resolution(resolution);
+        let count = (resolution * resolution) as usize;
+        Self {
+            resolution,
+            heights: vec![0.0; count],
+            splat: vec![[1.0, 0.0, 0.0, 0.0]; count],
+            size: size.max(1e-3),
+            height_scale,
+            lod_distance: 20.0,
+            layers: Default::default(),
+            height_version: 1,
+            splat_version: 1,
+        }
+    }
+
+    /// Nearest grid size that fits whole chunks: 32·n + 1 samples
+    pub fn valid_resolution(resolution: u32) -> u32 {
+        let quads = resolution.saturating_sub(1).max(TERRAIN_CHUNK_QUADS);
+        quads.div_ceil(TERRAIN_CHUNK_QUADS) * TERRAIN_CHUNK_QUADS + 1
+    }
+
+    /// Terrain from a grayscale image (16-bit PNGs keep the full precision),
+    /// resampled to `resolution`
+    pub fn from_heightmap(
+        path: &Path,
+        resolution: u32,
+        size: f32,
+        height_scale: f32,
+    ) -> Result<Self, String> {
+        let image = image::open(path)
+            .map_err(|e| format!("Falha ao abrir heightmap {}: {e}", path.display()))?
+            .to_luma16();
+        let mut terrain = Self::new(resolution, size, height_scale);
+        let (width, height) = image.dimensions();
+        let n = terrain.resolution;
+        for z in 0..n {
+            for x in 0..n {
+                let u = x as f32 / (n - 1) as f32 * (width - 1) as f32;
+                let v = z as f32 / (n - 1) as f32 * (height - 1) as f32;
+                let sample = |px: u32, pz: u32| {
+                    image.get_pixel(px.min(width - 1), pz.min(height - 1))[0] as f32
+                        / u16::MAX as f32
+                };
+                let (x0, z0) = (u.floor() as u32, v.floor() as u32);
+                let (fx, fz) = (u.fract(), v.fract());
+                let top = sample(x0, z0) * (1.0 - fx) + sample(x0 + 1, z0) * fx;
+                let bottom = sample(x0, z0 + 1) * (1.0 - fx) + sample(x0 + 1, z0 + 1) * fx;
+                terrain.heights[(z * n + x) as usize] = top * (1.0 - fz) + bottom * fz;
+            }
+        }
+        Ok(terrain)
+    }
+
+    /// Write the heights as a 16-bit grayscale PNG
+    pub fn save_heightmap(&self, path: &Path) -> Result<(), String> {
+        let n = self.resolution;
+        let pixels: Vec<u16> = self
+            .heights
+            .iter()
+            .map(|h| (h.clamp(0.0, 1.0) * u16::MAX as f32).round() as u16)
+            .collect();
+        image::ImageBuffer::<image::Luma<u16>, _>::from_raw(n, n, pixels)
+            .ok_or_else(|| "Heightmap com tamanho inválido".to_string())?
+            .save(path)
+            .map_err(|e| format!("Falha ao salvar heightmap: {e}"))
+    }
+
+    /// Read layer weights from an RGBA image (one channel per layer)
+    pub fn load_splatmap(&mut self, path: &Path) -> Result<(), String> {
+        let image = image::open(path)
+            .map_err(|e| format!("Falha ao abrir splat map {}: {e}", path.display()))?
+            .resize_exact(
+                self.resolution,
+                self.resolution,
+                image::imageops::FilterType::Triangle,
+            )
+            .to_rgba8();
+        for (weights, pixel) in self.splat.iter_mut().zip(image.pixels()) {
+            *weights = pixel.0.map(|c| c as f32 / 255.0);
+            normalize_weights(weights);
+        }
+        self.splat_version += 1;
+        Ok(())
+    }
+
+    pub fn save_splatmap(&self, path: &Path) -> Result<(), String> {
+        image::RgbaImage::from_raw(self.resolution, self.resolution, self.splat_rgba())
+            .ok_or_else(|| "Splat map com tamanho inválido".to_string())?
+            .save(path)
+            .map_err(|e| format!("Falha ao salvar splat map: {e}"))
+    }
+
+    /// Splat map texture contents (RGBA8, `resolution`²)
+    pub fn splat_rgba(&self) -> Vec<u8> {
+        self.splat
+            .iter()
+            .flat_map(|w| w.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8))
+            .collect()
+    }
+
+    /// Resize horizontally/vertically; the mesh is rebuilt on the next sync
+    pub fn set_dimensions(&mut self, size: f32, height_scale: f32) {
+        let size = size.max(1e-3);
+        if self.size != size || self.height_scale != height_scale {
+            self.size = size;
+            self.height_scale = height_scale;
+            self.height_version += 1;
+        }
+    }
+
+    pub fn size(&self) -> f32 {
+        self.size
+    }
+
+    pub fn height_scale(&self) -> f32 {
+        self.height_scale
+    }
+
+    pub fn resolution(&self) -> u32 {
+        self.resolution
+    }
+
+    pub fn heights(&self) -> &[f32] {
+        &self.heights
+    }
+
+    /// Changes whenever a height is edited
+    pub fn height_version(&self) -> u64 {
+        self.height_version
+    }
+
+    /// Changes whenever the splat map is edited
+    pub fn splat_version(&self) -> u64 {
+        self.splat_version
+    }
+
+    fn cell_size(&self) -> f32 {
+        self.size / (self.resolution - 1) as f32
+    }
+
+    fn chunks_per_side(&self) -> u32 {
+        (self.resolution - 1) / TERRAIN_CHUNK_QUADS
+    }
+
+    /// Local height of grid sample (x, z), clamped to the grid
+    fn sample(&self, x: i64, z: i64) -> f32 {
+        let last = self.resolution as i64 - 1;
+        let index = z.clamp(0, last) * self.resolution as i64 + x.clamp(0, last);
+        self.heights[index as usize] * self.height_scale
+    }
+
+    /// Grid coordinates of a local XZ position
+    fn grid_position(&self, local: Vec2) -> Vec2 {
+        (local / self.size + 0.5) * (self.resolution - 1) as f32
+    }
+
+    fn local_position(&self, x: u32, z: u32) -> Vec3 {
+        let cell = self.cell_size();
+        Vec3::new(
+            x as f32 * cell - self.size * 0.5,
+            self.sample(x as i64, z as i64),
+            z as f32 * cell - self.size * 0.5,
+        )
+    }
+
+    /// Whether a local XZ position lies on the terrain
+    pub fn contains(&self, local: Vec2) -> bool {
+        local.abs().max_element() <= self.size * 0.5
+    }
+
+    /// Bilinear local height under a local XZ position
+    pub fn height_at(&self, local: Vec2) -> f32 {
+        let grid = self.grid_position(local);
+        let (x0, z0) = (grid.x.floor() as i64, grid.y.floor() as i64);
+        let (fx, fz) = (grid.x - x0 as f32, grid.y - z0 as f32);
+        let top = self.sample(x0, z0) * (1.0 - fx) + self.sample(x0 + 1, z0) * fx;
+        let bottom = self.sample(x0, z0 + 1) * (1.0 - fx) + self.sample(x0 + 1, z0 + 1) * fx;
+        top * (1.0 - fz) + bottom * fz
+    }
+
+    /// Normal from central differences of `step` samples
+    fn grid_normal(&self, x: u32, z: u32, step: u32) -> Vec3 {
+        let (x, z, s) = (x as i64, z as i64, step as i64);
+        let dx = self.sample(x + s, z) - self.sample(x - s, z);
+        let dz = self.sample(x, z + s) - self.sample(x, z - s);
+        Vec3::new(-dx, 2.0 * self.cell_size() * step as f32, -dz).normalize_or(Vec3::Y)
+    }
+
+    /// First hit of a local-space ray with the height field
+    pub fn raycast(&self, origin: Vec3, direction: Vec3) -> Option<Vec3> {
+        let direction = direction.normalize_or_zero();
+        if direction == Vec3::ZERO {
+            return None;
+        }
+        let step = self.cell_size() * 0.5;
+        let max_distance = self.size * 2.0 + self.height_scale.abs() * 2.0 + origin.length();
+        let above = |t: f32| {
+            let p = origin + direction * t;
+            p.y - self.height_at(Vec2::new(p.x, p.z))
+        };
+        let mut previous = 0.0;
+        let mut t = 0.0;
+        while t <= max_distance {
+            let p = origin + direction * t;
+            if self.contains(Vec2::new(p.x, p.z)) && above(t) <= 0.0 {
+                // Refine between the last sample above and the first one below
+                let (mut lo, mut hi) = (previous, t);
+                for _ in 0..12 {
+                    let mid = (lo + hi) * 0.5;
+                    if above(mid) > 0.0 {
+                        lo = mid;
+                    } else {
+                        hi = mid;
+                    }
+                }
+                return Some(origin + direction * hi);
+            }
+            previous = t;
+            t += step;
+        }
+        None
+    }
+
+    /// Apply one brush step of `dt` seconds centered at a local position;
+    /// returns whether anything changed
+    pub fn apply_brush(&mut self, center: Vec3, brush: &TerrainBrush, dt: f32) -> bool {
+        let radius = brush.radius.max(1e-3);
+        let grid = self.grid_position(Vec2::new(center.x, center.z));
+        let reach = (radius / self.cell_size()).ceil() as i64 + 1;
+        let last = self.resolution as i64 - 1;
+        let (x_min, x_max) = (
+            (grid.x as i64 - reach).max(0),
+            (grid.x as i64 + reach).min(last),
+        );
+        let (z_min, z_max) = (
+            (grid.y as i64 - reach).max(0),
+            (grid.y as i64 + reach).min(last),
+        );
+        if x_min > x_max || z_min > z_max {
+            return false;
+        }
+
+        let scale = self.height_scale.abs().max(1e-3);
+        let source = (brush.mode == BrushMode::Smooth).then(|| self.heights.clone());
+        let mut changed = false;
+        for z in z_min..=z_max {
+            for x in x_min..=x_max {
+                let distance = Vec2::new(x as f32, z as f32).distance(grid) * self.cell_size();
+                if distance >= radius {
+                    continue;
+                }
+                // Smooth falloff: 1 at the center, 0 at the edge
+                let falloff = (1.0 - (distance / radius).powi(2)).powi(2);
+                let amount = (brush.strength * dt * falloff).max(0.0);
+                let index = (z * self.resolution as i64 + x) as usize;
+                match brush.mode {
+                    BrushMode::Raise => self.heights[index] += amount / scale,
+                    BrushMode::Lower => self.heights[index] -= amount / scale,
+                    BrushMode::Smooth => {
+                        let heights = source.as_ref().expect("smooth source");
+                        let mut sum = 0.0;
+                        let mut count = 0.0;
+                        for (dx, dz) in [(-1, 0), (1, 0), (0, -1), (0, 1), (0, 0)] {
+                            let (nx, nz) = ((x + dx).clamp(0, last), (z + dz).clamp(0, last));
+                            sum += heights[(nz * self.resolution as i64 + nx) as usize];
+                            count += 1.0;
+                        }
+                        let t = amount.min(1.0);
+                        self.heights[index] += (sum / count - self.heights[index]) * t;
+                    }
+                    BrushMode::Flatten => {
+                        let target = brush.target_height / scale;
+                        let t = (amount / scale).min(1.0);
+                        self.heights[index] += (target - self.heights[index]) * t;
+                    }
+                    BrushMode::Paint => {
+                        let layer = brush.layer.min(TERRAIN_LAYERS - 1);
+                        let weights = &mut self.splat[index];
+                        weights[layer] += amount.min(1.0);
+                        normalize_weights(weights);
+                    }
+                }
+                if brush.mode != BrushMode::Paint {
+                    self.heights[index] = self.heights[index].clamp(0.0, 1.0);
+                }
+                changed = true;
+            }
+        }
+        if changed {
+            if brush.mode == BrushMode::Paint {
+                self.splat_version += 1;
+            } else {
+                self.height_version += 1;
+            }
+        }
+        changed
+    }
+
+    /// LOD of every chunk (row-major) seen from a local camera position
+    pub fn select_lods(&self, eye: Vec3) -> Vec<u32> {
+        let chunks = self.chunks_per_side();
+        let chunk_size = self.size / chunks as f32;
+        let lod_distance = self.lod_distance.max(1e-3);
+        let mut lods = Vec::with_capacity((chunks * chunks) as usize);
+        for cz in 0..chunks {
+            for cx in 0..chunks {
+                let center = Vec2::new(
+                    (cx as f32 + 0.5) * chunk_size - self.size * 0.5,
+                    (cz as f32 + 0.5) * chunk_size - self.size * 0.5,
+                );
+                let center = Vec3::new(center.x, self.height_at(center), center.y);
+                // Distance to the chunk's edge rather than its center
+                let distance = (eye.distance(center) - chunk_size * 0.7).max(0.0);
+                let lod = if distance <= lod_distance {
+                    0
+                } else {
+                    ((distance / lod_distance).log2().floor() as u32 + 1).min(TERRAIN_MAX_LOD)
+                };
+                lods.push(lod);
+            }
+        }
+        lods
+    }
+
+    /// Whole terrain at the given chunk LODs (as returned by `select_lods`)
+    pub fn build_mesh(&self, lods: &[u32]) -> TerrainMesh {
+        let chunks = self.chunks_per_side();
+        let mut mesh = TerrainMesh::default();
+        for cz in 0..chunks {
+            for cx in 0..chunks {
+                let lod = lods.get((cz * chunks + cx) as usize).copied().unwrap_or(0);
+                mesh.append(self.build_chunk(cx, cz, lod));
+            }
+        }
+        mesh
+    }
+
+    /// Whole terrain with every chunk at one LOD (collision, CPU preview)
+    pub fn build_uniform_mesh(&self, lod: u32) -> TerrainMesh {
+        let chunks = self.chunks_per_side();
+        self.build_mesh(&vec![lod; (chunks * chunks) as usize])
+    }
+
+    /// Grid of one chunk plus its skirt
+    pub fn build_chunk(&self, cx: u32, cz: u32, lod: u32) -> TerrainMesh {
+        let step = 1 << lod.min(TERRAIN_MAX_LOD);
+        let quads = TERRAIN_CHUNK_QUADS / step;
+        let side = quads + 1;
+        let (x0, z0) = (cx * TERRAIN_CHUNK_QUADS, cz * TERRAIN_CHUNK_QUADS);
+        let uv_scale = 1.0 / (self.resolution - 1) as f32;
+
+        let mut mesh = TerrainMesh::default();
+        for j in 0..side {
+            for i in 0..side {
+                let (x, z) = (x0 + i * step, z0 + j * step);
+                mesh.positions.push(self.local_position(x, z));
+                mesh.normals.push(self.grid_normal(x, z, step));
+                mesh.uvs.push([x as f32 * uv_scale, z as f32 * uv_scale]);
+            }
+        }
+        for j in 0..quads {
+            for i in 0..quads {
+                let a = j * side + i;
+                let b = a + 1;
+                let c = a + side;
+                let d = c + 1;
+                // CCW seen from above (+Y)
+                mesh.indices.extend_from_slice(&[a, c, b, b, c, d]);
+            }
+        }
+
+        // Skirt: the chunk border repeated further down, closing cracks between LODs
+        let depth = self.cell_size() * step as f32 + self.height_scale.abs() * 0.02;
+        let border: Vec<u32> = (0..quads)
+            .chain((0..quads).map(|j| j * side + quads))
+            .chain((0..quads).map(|i| quads * side + quads - i))
+            .chain((0..quads).map(|j| (quads - j) * side))
+            .collect();
+        let first_skirt = mesh.positions.len() as u32;
+        for &index in &border {
+            let i = index as usize;
+            mesh.positions.push(mesh.positions[i] - Vec3::Y * depth);
+            mesh.normals.push(mesh.normals[i]);
+            mesh.uvs.push(mesh.uvs[i]);
+        }
+        let count = border.len() as u32;
+        for k in 0..count {
+            let next = (k + 1) % count;
+            let (top_a, top_b) = (border[k as usize], border[next as usize]);
+            let (low_a, low_b) = (first_skirt + k, first_skirt + next);
+            // The border runs clockwise seen from above, so these face outwards
+            mesh.indices
+                .extend_from_slice(&[top_a, top_b, low_a, top_b, low_b, low_a]);
+        }
+        mesh
+    }
+}
+
+/// Scale layer weights to sum 1 (all-zero falls back to the first layer)
+fn normalize_weights(weights: &mut [f32; TERRAIN_LAYERS]) {
+    let sum: f32 = weights.iter().map(|w| w.max(0.0)).sum();
+    if sum <= 1e-6 {
+        *weights = [1.0, 0.0, 0.0, 0.0];
+    } else {
+        *weights = weights.map(|w| w.max(0.0) / sum);
+    }
+}
+
+/// Uniform block of `TERRAIN_SHADER`
+pub fn terrain_uniform_bytes(
+    view_proj: Mat4,
+    model: Mat4,
+    light_dir: Vec3,
+    light_color: Vec3,
+    light_intensity: f32,
+    layers: &[TerrainLayer; TERRAIN_LAYERS],
+) -> [u8; TERRAIN_UNIFORM_SIZE] {
+    let mut floats: Vec<f32> = Vec::with_capacity(TERRAIN_UNIFORM_SIZE / 4);
+    floats.extend_from_slice(&view_proj.to_cols_array());
+    floats.extend_from_slice(&model.to_cols_array());
+    floats.extend_from_slice(
+        &light_dir
+            .normalize_or(Vec3::Y)
+            .extend(light_intensity)
+            .to_array(),
+    );
+    floats.extend_from_slice(&light_color.extend(0.0).to_array());
+    floats.extend(layers.iter().map(|layer| layer.tiling.max(1e-3)));
+    for layer in layers {
+        floats.extend_from_slice(&layer.color);
+        floats.push(if layer.texture.is_some() { 1.0 } else { 0.0 });
+    }
+
+    let mut bytes = [0_u8; TERRAIN_UNIFORM_SIZE];
+    for (chunk, value) in bytes.chunks_exact_mut(4).zip(floats) {
+        chunk.copy_from_slice(&value.to_le_bytes());
+    }
+    bytes
+}
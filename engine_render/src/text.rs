@@ -0,0 +1,199 @@
+//! Text batching - turns `Text3D` and `UiText` components into SDF glyph quads
+//!
+//! Text is laid out with its `FontAsset`, placed in world space (facing the
+//! camera for billboards), sorted back to front and merged into one
+//! `TextBatch` per run of entities sharing a font. Batches use the sprite
+//! vertex layout and are drawn with `TEXT_SHADER` over the font atlas.
+//!
+//! `UiText` goes through the same layout but is placed in clip space, so its
+//! batches are drawn last with an identity view-projection.
+
+use engine_core::components::{FontHandle, Text3D, Transform, UiText};
+use engine_core::ecs::EngineWorld;
+use glam::{Vec2, Vec3};
+
+use crate::asset_manager::AssetManager;
+use crate::font::{FontAsset, TextLayout};
+use crate::sprite::{SpriteVertex, push_quad, vertex_bytes};
+
+/// Glyph quads sharing a font atlas, in draw order
+#[derive(Debug, Clone, Default)]
+pub struct TextBatch {
+    pub font: FontHandle,
+    pub vertices: Vec<SpriteVertex>,
+    pub indices: Vec<u32>,
+}
+
+impl TextBatch {
+    pub fn quad_count(&self) -> usize {
+        self.vertices.len() / 4
+    }
+
+    /// Vertex buffer contents (layout of `TEXT_SHADER`)
+    pub fn vertex_bytes(&self) -> Vec<u8> {
+        vertex_bytes(&self.vertices)
+    }
+}
+
+/// Sorted, font-grouped batches for every `Text3D` whose font is loaded
+pub fn build_text_batches(
+    world: &EngineWorld,
+    assets: &AssetManager,
+    camera_position: Vec3,
+    camera_target: Vec3,
+    camera_up: Vec3,
+) -> Vec<TextBatch> {
+    engine_core::profile_function!();
+    let forward = (camera_target - camera_position).normalize_or(Vec3::NEG_Z);
+    let right = forward.cross(camera_up).normalize_or(Vec3::X);
+    let up = right.cross(forward);
+
+    let mut texts: Vec<(Text3D, Transform, f32)> = world
+        .world()
+        .query::<(&Transform, &Text3D)>()
+        .iter()
+        .filter(|(_, text)| !text.text.is_empty() && text.font.is_valid())
+        .map(|(transform, text)| {
+            let depth = (transform.position + text.offset - camera_position).dot(forward);
+            (text.clone(), *transform, depth)
+        })
+        .collect();
+    texts.sort_by(|a, b| b.2.total_cmp(&a.2));
+
+    let mut batches: Vec<TextBatch> = Vec::new();
+    for (text, transform, _) in &texts {
+        let Some(font) = assets.get_font(text.font) else {
+            continue;
+        };
+        if batches.last().is_none_or(|batch| batch.font != text.font) {
+            batches.push(TextBatch {
+                font: text.font,
+                ..Default::default()
+            });
+        }
+        let batch = batches.last_mut().expect("batch just pushed");
+        push_text3d_quads(
+            font,
+            text,
+            transform,
+            right,
+            up,
+            &mut batch.vertices,
+            &mut batch.indices,
+        );
+    }
+    batches.retain(|batch| !batch.vertices.is_empty());
+    batches
+}
+
+/// Append the world-space glyph quads of one `Text3D`; billboards are laid
+/// along the camera's `right` and `up`. Also used by the editor viewport,
+/// which has no `EngineWorld` to batch
+pub fn push_text3d_quads(
+    font: &FontAsset,
+    text: &Text3D,
+    transform: &Transform,
+    right: Vec3,
+    up: Vec3,
+    vertices: &mut Vec<SpriteVertex>,
+    indices: &mut Vec<u32>,
+) {
+    let layout = font.layout_text(&text.text, text.size, text.align);
+    let matrix = transform.to_mat4();
+    let anchor = transform.position + text.offset;
+    let place = |p: Vec2| {
+        if text.billboard {
+            let p = p * transform.scale.truncate();
+            anchor + right * p.x + up * p.y
+        } else {
+            matrix.transform_point3(p.extend(0.0)) + text.offset
+        }
+    };
+    push_layout_quads(&layout, text.color, place, vertices, indices);
+}
+
+/// Append the glyph quads of `layout`, each corner moved from text space by `place`
+fn push_layout_quads(
+    layout: &TextLayout,
+    color: [f32; 4],
+    place: impl Fn(Vec2) -> Vec3,
+    vertices: &mut Vec<SpriteVertex>,
+    indices: &mut Vec<u32>,
+) {
+    for glyph in &layout.quads {
+        let [u0, v0, u1, v1] = glyph.uv_rect;
+        let corners = [
+            (Vec2::new(glyph.min.x, glyph.min.y), [u0, v1]),
+            (Vec2::new(glyph.max.x, glyph.min.y), [u1, v1]),
+            (Vec2::new(glyph.max.x, glyph.max.y), [u1, v0]),
+            (Vec2::new(glyph.min.x, glyph.max.y), [u0, v0]),
+        ];
+        let quad = corners.map(|(corner, uv)| SpriteVertex {
+            position: place(corner).to_array(),
+            uv,
+            color,
+        });
+        push_quad(vertices, indices, quad);
+    }
+}
+
+/// Batches for every visible `UiText` whose font is loaded, in clip space of a
+/// `width` x `height` pixel viewport, in entity order
+pub fn build_ui_text_batches(
+    world: &EngineWorld,
+    assets: &AssetManager,
+    width: u32,
+    height: u32,
+) -> Vec<TextBatch> {
+    engine_core::profile_function!();
+    let viewport = Vec2::new(width.max(1) as f32, height.max(1) as f32);
+    let mut batches: Vec<TextBatch> = Vec::new();
+    for text in world.world().query::<&UiText>().iter() {
+        if !text.visible || text.text.is_empty() || !text.font.is_valid() {
+            continue;
+        }
+        let Some(font) = assets.get_font(text.font) else {
+            continue;
+        };
+        if batches.last().is_none_or(|batch| batch.font != text.font) {
+            batches.push(TextBatch {
+                font: text.font,
+                ..Default::default()
+            });
+        }
+        let batch = batches.last_mut().expect("batch just pushed");
+        push_ui_text_quads(
+            font,
+            text,
+            viewport,
+            &mut batch.vertices,
+            &mut batch.indices,
+        );
+    }
+    batches.retain(|batch| !batch.vertices.is_empty());
+    batches
+}
+
+/// Append the clip-space glyph quads of one `UiText` on a `viewport` of that
+/// many pixels. Also used by the editor viewport in play mode
+pub fn push_ui_text_quads(
+    font: &FontAsset,
+    text: &UiText,
+    viewport: Vec2,
+    vertices: &mut Vec<SpriteVertex>,
+    indices: &mut Vec<u32>,
+) {
+    let layout = font.layout_text(&text.text, text.size, text.align);
+    let anchor = text.anchor * viewport + text.offset;
+    // The block hangs below a top anchor and sits above a bottom one
+    let shift = layout.size.y * (0.5 - text.anchor.y);
+    let place = |p: Vec2| {
+        let pixel = Vec2::new(anchor.x + p.x, anchor.y - p.y + shift);
+        Vec3::new(
+            pixel.x / viewport.x * 2.0 - 1.0,
+            1.0 - pixel.y / viewport.y * 2.0,
+            0.0,
+        )
+    };
+    push_layout_quads(&layout, text.color, place, vertices, indices);
+}
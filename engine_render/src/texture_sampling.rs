@@ -0,0 +1,148 @@
+//! Texture sampling - per-texture filtering, anisotropy and mipmap options
+//!
+//! Options live next to the image in a `<file>.sampling` text file
+//! (`filter=trilinear`, `anisotropy=8`, `mipmaps=true`); textures without one use
+//! `TextureSampling::default()`. Mip chains are built on the GPU at import time
+//! by `MIPMAP_SHADER`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Anisotropy levels offered by the editor (1 = off)
+pub const ANISOTROPY_LEVELS: [u16; 5] = [1, 2, 4, 8, 16];
+
+/// Texture filtering mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextureFilter {
+    /// Nearest texel, no mip blending (pixel art)
+    Point,
+    /// Linear inside a mip level, nearest between levels
+    Bilinear,
+    /// Linear inside and between mip levels
+    Trilinear,
+}
+
+impl TextureFilter {
+    pub const ALL: [TextureFilter; 3] = [
+        TextureFilter::Point,
+        TextureFilter::Bilinear,
+        TextureFilter::Trilinear,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TextureFilter::Point => "point",
+            TextureFilter::Bilinear => "bilinear",
+            TextureFilter::Trilinear => "trilinear",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TextureFilter::Point => "Point",
+            TextureFilter::Bilinear => "Bilinear",
+            TextureFilter::Trilinear => "Trilinear",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "point" | "nearest" => Some(TextureFilter::Point),
+            "bilinear" => Some(TextureFilter::Bilinear),
+            "trilinear" => Some(TextureFilter::Trilinear),
+            _ => None,
+        }
+    }
+}
+
+/// Sampling options of one texture
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextureSampling {
+    pub filter: TextureFilter,
+    /// Max anisotropy (1..=16); only applies to trilinear filtering
+    pub anisotropy: u16,
+    /// Generate the mip chain on import
+    pub mipmaps: bool,
+}
+
+impl Default for TextureSampling {
+    fn default() -> Self {
+        Self {
+            filter: TextureFilter::Trilinear,
+            anisotropy: 4,
+            mipmaps: true,
+        }
+    }
+}
+
+impl TextureSampling {
+    /// Options file of `texture_path`
+    pub fn settings_path(texture_path: &Path) -> PathBuf {
+        let mut path = texture_path.as_os_str().to_owned();
+        path.push(".sampling");
+        PathBuf::from(path)
+    }
+
+    /// Options of `texture_path` (defaults when there is no options file)
+    pub fn load(texture_path: &Path) -> Self {
+        let mut sampling = Self::default();
+        let Ok(content) = fs::read_to_string(Self::settings_path(texture_path)) else {
+            return sampling;
+        };
+        for line in content.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            match key.trim() {
+                "filter" => {
+                    if let Some(filter) = TextureFilter::parse(value) {
+                        sampling.filter = filter;
+                    }
+                }
+                "anisotropy" => {
+                    if let Ok(anisotropy) = value.trim().parse::<u16>() {
+                        sampling.anisotropy = anisotropy.clamp(1, 16);
+                    }
+                }
+                "mipmaps" => sampling.mipmaps = value.trim() != "false",
+                _ => {}
+            }
+        }
+        sampling
+    }
+
+    pub fn save(&self, texture_path: &Path) -> Result<(), String> {
+        let content = format!(
+            "filter={}\nanisotropy={}\nmipmaps={}\n",
+            self.filter.as_str(),
+            self.anisotropy,
+            self.mipmaps
+        );
+        fs::write(Self::settings_path(texture_path), content)
+            .map_err(|e| format!("Falha ao salvar amostragem: {e}"))
+    }
+
+    /// Mip levels to allocate for a `width` x `height` texture
+    pub fn mip_levels(&self, width: u32, height: u32) -> u32 {
+        if self.mipmaps {
+            mip_level_count(width, height)
+        } else {
+            1
+        }
+    }
+
+    /// Anisotropy actually usable: the GPU requires linear filtering on every
+    /// axis (and mips to be worth it)
+    pub fn effective_anisotropy(&self) -> u16 {
+        if self.filter == TextureFilter::Trilinear && self.mipmaps {
+            self.anisotropy.clamp(1, 16)
+        } else {
+            1
+        }
+    }
+}
+
+/// Full mip chain length down to 1x1
+pub fn mip_level_count(width: u32, height: u32) -> u32 {
+    32 - width.max(height).max(1).leading_zeros()
+}
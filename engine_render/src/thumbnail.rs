@@ -0,0 +1,542 @@
+//! Asset thumbnails - small previews for the Project window
+//!
+//! Textures are scaled down, meshes are rendered in software from
+//! `TURNTABLE_FRAMES` angles around the model (a turntable strip, frames side
+//! by side) and materials are drawn as a lit sphere; environments can be
+//! previewed on a mirror sphere (`environment_sphere_thumbnail`). Every thumbnail is cached
+//! as a PNG under the cache directory, named after the source path and keyed by
+//! its size and modification time, so a file is only rendered again after it
+//! changes. `ThumbnailGenerator` runs all of it on worker threads.
+
+use std::collections::HashSet;
+use std::collections::hash_map::DefaultHasher;
+use std::f32::consts::{PI, TAU};
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+
+use glam::{Vec2, Vec3};
+
+use crate::environment::EnvironmentMap;
+use crate::format_version::{FormatKind, migrate_format};
+use crate::hdr::{is_float_image, tonemap_rgba8};
+use crate::mesh::MeshData;
+use crate::shader_library::file_stamp;
+use crate::vfs::open_image;
+
+/// Edge of a thumbnail (of each turntable frame for meshes)
+pub const THUMBNAIL_SIZE: u32 = 128;
+/// Angles rendered around a mesh
+pub const TURNTABLE_FRAMES: u32 = 8;
+
+/// Meshes are rendered at this multiple of the size and filtered down
+const SUPERSAMPLE: u32 = 2;
+const MESH_COLOR: Vec3 = Vec3::new(0.62, 0.74, 0.86);
+const LIGHT_DIR: Vec3 = Vec3::new(-0.45, 0.7, 0.55);
+
+/// What a thumbnail is made from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ThumbnailKind {
+    Texture,
+    Mesh,
+    Material,
+}
+
+impl ThumbnailKind {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "png" | "jpg" | "jpeg" | "webp" | "hdr" | "exr" => Some(ThumbnailKind::Texture),
+            "obj" | "gltf" | "glb" | "fbx" => Some(ThumbnailKind::Mesh),
+            "mat" => Some(ThumbnailKind::Material),
+            _ => None,
+        }
+    }
+}
+
+/// RGBA8 preview; meshes hold `frames` images side by side
+#[derive(Debug, Clone)]
+pub struct Thumbnail {
+    /// Width of one frame
+    pub width: u32,
+    pub height: u32,
+    pub frames: u32,
+    pub rgba: Vec<u8>,
+}
+
+impl Thumbnail {
+    /// UV rect (min, max) of a frame inside the strip
+    pub fn frame_uv(&self, frame: u32) -> ([f32; 2], [f32; 2]) {
+        let frames = self.frames.max(1) as f32;
+        let frame = (frame % self.frames.max(1)) as f32;
+        ([frame / frames, 0.0], [(frame + 1.0) / frames, 1.0])
+    }
+}
+
+/// Reads a mesh file into positions and triangles
+pub type MeshGeometryLoader = fn(&Path) -> Result<(Vec<Vec3>, Vec<[u32; 3]>), String>;
+
+/// Positions and triangles of any mesh `MeshData` can load
+pub fn load_mesh_geometry(path: &Path) -> Result<(Vec<Vec3>, Vec<[u32; 3]>), String> {
+    let mesh = MeshData::load_from_file(path)?;
+    let positions = mesh.vertices.iter().map(|v| v.position).collect();
+    let triangles = mesh
+        .indices
+        .chunks_exact(3)
+        .map(|t| [t[0], t[1], t[2]])
+        .collect();
+    Ok((positions, triangles))
+}
+
+/// Texture scaled to fit `size`, keeping its aspect ratio; HDR/EXR are tonemapped
+pub fn texture_thumbnail(path: &Path, size: u32) -> Result<Thumbnail, String> {
+    let image = open_image(path)?.thumbnail(size, size);
+    let (width, height) = (image.width(), image.height());
+    let rgba = if is_float_image(&image) {
+        tonemap_rgba8(image.into_rgba32f().as_raw())
+    } else {
+        image.into_rgba8().into_raw()
+    };
+    Ok(Thumbnail {
+        width,
+        height,
+        frames: 1,
+        rgba,
+    })
+}
+
+/// Turntable of a mesh: `frames` flat-shaded renders, one turn around Y
+pub fn mesh_thumbnail(
+    positions: &[Vec3],
+    triangles: &[[u32; 3]],
+    size: u32,
+    frames: u32,
+) -> Result<Thumbnail, String> {
+    if positions.is_empty() || triangles.is_empty() {
+        return Err("Malha vazia".to_string());
+    }
+    let (min, max) = positions
+        .iter()
+        .fold((Vec3::INFINITY, Vec3::NEG_INFINITY), |(min, max), p| {
+            (min.min(*p), max.max(*p))
+        });
+    let center = (min + max) * 0.5;
+    // Fits every frame: the bounding sphere doesn't change while turning
+    let radius = positions
+        .iter()
+        .map(|p| p.distance(center))
+        .fold(0.0f32, f32::max)
+        .max(1e-5);
+
+    let frames = frames.max(1);
+    let strip_width = size * frames;
+    let mut rgba = vec![0u8; (strip_width * size * 4) as usize];
+    for frame in 0..frames {
+        let yaw = 0.65 + TAU * frame as f32 / frames as f32;
+        let pixels = render_mesh_frame(positions, triangles, center, radius, yaw, size);
+        for y in 0..size {
+            let src = (y * size * 4) as usize;
+            let dst = ((y * strip_width + frame * size) * 4) as usize;
+            rgba[dst..dst + (size * 4) as usize]
+                .copy_from_slice(&pixels[src..src + (size * 4) as usize]);
+        }
+    }
+    Ok(Thumbnail {
+        width: size,
+        height: size,
+        frames,
+        rgba,
+    })
+}
+
+/// One orthographic, depth-tested render, supersampled then box filtered
+fn render_mesh_frame(
+    positions: &[Vec3],
+    triangles: &[[u32; 3]],
+    center: Vec3,
+    radius: f32,
+    yaw: f32,
+    size: u32,
+) -> Vec<u8> {
+    let big = (size * SUPERSAMPLE) as usize;
+    let (sy, cy) = yaw.sin_cos();
+    let (sp, cp) = 0.45f32.sin_cos();
+    // Model turned by `yaw`, camera tilted down by the pitch; +Z looks at the viewer
+    let view = |p: Vec3| {
+        let p = (p - center) / radius;
+        let x = p.x * cy - p.z * sy;
+        let z = p.x * sy + p.z * cy;
+        Vec3::new(x, p.y * cp - z * sp, p.y * sp + z * cp)
+    };
+    let projected: Vec<Vec3> = positions.iter().map(|p| view(*p)).collect();
+    let scale = big as f32 * 0.46;
+    let half = big as f32 * 0.5;
+    let to_screen = |p: Vec3| Vec2::new(half + p.x * scale, half - p.y * scale);
+    let light = LIGHT_DIR.normalize();
+
+    let mut depth = vec![f32::NEG_INFINITY; big * big];
+    let mut color = vec![[0.0f32; 4]; big * big];
+    for tri in triangles {
+        let (Some(&a), Some(&b), Some(&c)) = (
+            projected.get(tri[0] as usize),
+            projected.get(tri[1] as usize),
+            projected.get(tri[2] as usize),
+        ) else {
+            continue;
+        };
+        let normal = (b - a).cross(c - a);
+        if normal.length_squared() <= f32::EPSILON {
+            continue;
+        }
+        // Both sides lit: imported meshes don't agree on winding
+        let mut normal = normal.normalize();
+        if normal.z < 0.0 {
+            normal = -normal;
+        }
+        let shade = 0.28 + 0.72 * normal.dot(light).max(0.0);
+        let rgb = MESH_COLOR * shade;
+
+        let (sa, sb, sc) = (to_screen(a), to_screen(b), to_screen(c));
+        let area = edge(sa, sb, sc);
+        if area.abs() <= f32::EPSILON {
+            continue;
+        }
+        let lo = sa.min(sb).min(sc).floor().max(Vec2::ZERO);
+        let hi = sa.max(sb).max(sc).ceil().min(Vec2::splat(big as f32 - 1.0));
+        for y in lo.y as usize..=hi.y as usize {
+            for x in lo.x as usize..=hi.x as usize {
+                let p = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+                let w0 = edge(sb, sc, p) / area;
+                let w1 = edge(sc, sa, p) / area;
+                let w2 = edge(sa, sb, p) / area;
+                if w0 < 0.0 || w1 < 0.0 || w2 < 0.0 {
+                    continue;
+                }
+                let z = a.z * w0 + b.z * w1 + c.z * w2;
+                let index = y * big + x;
+                if z > depth[index] {
+                    depth[index] = z;
+                    color[index] = [rgb.x, rgb.y, rgb.z, 1.0];
+                }
+            }
+        }
+    }
+
+    let mut rgba = vec![0u8; (size * size * 4) as usize];
+    let samples = (SUPERSAMPLE * SUPERSAMPLE) as f32;
+    for y in 0..size as usize {
+        for x in 0..size as usize {
+            let mut sum = [0.0f32; 4];
+            for sy in 0..SUPERSAMPLE as usize {
+                for sx in 0..SUPERSAMPLE as usize {
+                    let sample = color
+                        [(y * SUPERSAMPLE as usize + sy) * big + x * SUPERSAMPLE as usize + sx];
+                    for (total, value) in sum.iter_mut().zip(sample) {
+                        *total += value;
+                    }
+                }
+            }
+            let alpha = sum[3] / samples;
+            let out = &mut rgba[(y * size as usize + x) * 4..][..4];
+            for channel in 0..3 {
+                // Unpremultiplied, so edges don't darken
+                let value = if alpha > 0.0 {
+                    sum[channel] / sum[3]
+                } else {
+                    0.0
+                };
+                out[channel] = (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+            }
+            out[3] = (alpha * 255.0).round() as u8;
+        }
+    }
+    rgba
+}
+
+fn edge(a: Vec2, b: Vec2, p: Vec2) -> f32 {
+    (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x)
+}
+
+/// Values of a `.mat` file the preview sphere uses
+struct MaterialPreview {
+    albedo: [f32; 4],
+    metallic: f32,
+    roughness: f32,
+    texture: Option<image::RgbaImage>,
+}
+
+fn read_material_preview(path: &Path) -> Result<MaterialPreview, String> {
+    let raw =
+        fs::read_to_string(path).map_err(|e| format!("Falha ao ler {}: {e}", path.display()))?;
+    let content = migrate_format(FormatKind::Material, &raw)?.text;
+    let mut material = MaterialPreview {
+        albedo: [1.0; 4],
+        metallic: 0.0,
+        roughness: 0.5,
+        texture: None,
+    };
+    for (key, value) in content
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+    {
+        let value = value.trim().trim_matches('"');
+        match key.trim() {
+            "albedo" | "color" => {
+                for (slot, part) in material.albedo.iter_mut().zip(value.split(',')) {
+                    if let Ok(v) = part.trim().parse::<f32>() {
+                        *slot = v;
+                    }
+                }
+            }
+            "metallic" => material.metallic = value.parse().unwrap_or(material.metallic),
+            "roughness" => material.roughness = value.parse().unwrap_or(material.roughness),
+            "albedo_texture" | "diffuse_texture" if material.texture.is_none() => {
+                let texture = Path::new(value);
+                let texture = if texture.exists() {
+                    texture.to_path_buf()
+                } else {
+                    path.parent().unwrap_or(Path::new("")).join(texture)
+                };
+                material.texture = open_image(&texture)
+                    .ok()
+                    .map(|image| image.thumbnail(256, 256).to_rgba8());
+            }
+            _ => {}
+        }
+    }
+    Ok(material)
+}
+
+/// Material on a lit sphere
+pub fn material_thumbnail(path: &Path, size: u32) -> Result<Thumbnail, String> {
+    let material = read_material_preview(path)?;
+    let albedo = Vec3::new(material.albedo[0], material.albedo[1], material.albedo[2]);
+    let metallic = material.metallic.clamp(0.0, 1.0);
+    let roughness = material.roughness.clamp(0.04, 1.0);
+    let shininess = 2.0 / (roughness * roughness) - 2.0;
+    let light = LIGHT_DIR.normalize();
+    let half_vector = (light + Vec3::Z).normalize();
+    // Metals tint their highlight, dielectrics reflect ~4% white
+    let specular_color = Vec3::splat(0.04).lerp(albedo, metallic);
+
+    let mut rgba = vec![0u8; (size * size * 4) as usize];
+    let radius = size as f32 * 0.46;
+    let center = size as f32 * 0.5;
+    for y in 0..size {
+        for x in 0..size {
+            let px = (x as f32 + 0.5 - center) / radius;
+            let py = (center - (y as f32 + 0.5)) / radius;
+            let d2 = px * px + py * py;
+            // One pixel of antialiasing at the silhouette
+            let coverage = ((1.0 - d2.sqrt()) * radius + 0.5).clamp(0.0, 1.0);
+            if coverage <= 0.0 {
+                continue;
+            }
+            let normal = Vec3::new(px, py, (1.0 - d2.min(1.0)).sqrt()).normalize();
+            let mut base = albedo;
+            if let Some(texture) = &material.texture {
+                let u = 0.5 + normal.x.atan2(normal.z) / TAU;
+                let v = normal.y.clamp(-1.0, 1.0).acos() / PI;
+                let tx = ((u * texture.width() as f32) as u32).min(texture.width() - 1);
+                let ty = ((v * texture.height() as f32) as u32).min(texture.height() - 1);
+                let texel = texture.get_pixel(tx, ty).0;
+                base *= Vec3::new(texel[0] as f32, texel[1] as f32, texel[2] as f32) / 255.0;
+            }
+            let diffuse = base * (1.0 - metallic) * (0.2 + 0.8 * normal.dot(light).max(0.0));
+            let specular = specular_color
+                * normal.dot(half_vector).max(0.0).powf(shininess)
+                * (shininess + 8.0)
+                / 32.0;
+            let ambient = base * metallic * 0.4;
+            let rgb = diffuse + specular + ambient;
+            let out = &mut rgba[((y * size + x) * 4) as usize..][..4];
+            out[0] = (rgb.x.clamp(0.0, 1.0) * 255.0).round() as u8;
+            out[1] = (rgb.y.clamp(0.0, 1.0) * 255.0).round() as u8;
+            out[2] = (rgb.z.clamp(0.0, 1.0) * 255.0).round() as u8;
+            out[3] = (coverage * material.albedo[3].clamp(0.0, 1.0) * 255.0).round() as u8;
+        }
+    }
+    Ok(Thumbnail {
+        width: size,
+        height: size,
+        frames: 1,
+        rgba,
+    })
+}
+
+/// Sky panorama or cubemap reflected in a chrome sphere, seen from +Z
+pub fn environment_sphere_thumbnail(path: &Path, size: u32) -> Result<Thumbnail, String> {
+    let map = EnvironmentMap::load(path)?;
+    let mut pixels = vec![0.0f32; (size * size * 4) as usize];
+    let radius = size as f32 * 0.46;
+    let center = size as f32 * 0.5;
+    for y in 0..size {
+        for x in 0..size {
+            let px = (x as f32 + 0.5 - center) / radius;
+            let py = (center - (y as f32 + 0.5)) / radius;
+            let d2 = px * px + py * py;
+            let coverage = ((1.0 - d2.sqrt()) * radius + 0.5).clamp(0.0, 1.0);
+            if coverage <= 0.0 {
+                continue;
+            }
+            let normal = Vec3::new(px, py, (1.0 - d2.min(1.0)).sqrt()).normalize();
+            // The camera looks down -Z: reflect its ray about the normal
+            let view = Vec3::NEG_Z;
+            let reflected = view - 2.0 * view.dot(normal) * normal;
+            let texel = map.sample_bilinear(reflected);
+            let out = &mut pixels[((y * size + x) * 4) as usize..][..4];
+            out[..3].copy_from_slice(&texel[..3]);
+            out[3] = coverage;
+        }
+    }
+    Ok(Thumbnail {
+        width: size,
+        height: size,
+        frames: 1,
+        rgba: tonemap_rgba8(&pixels),
+    })
+}
+
+/// Thumbnail of any supported asset, without the cache
+pub fn generate_thumbnail(path: &Path, load_mesh: MeshGeometryLoader) -> Result<Thumbnail, String> {
+    engine_core::profile_function!();
+    match ThumbnailKind::from_path(path) {
+        Some(ThumbnailKind::Texture) => texture_thumbnail(path, THUMBNAIL_SIZE),
+        Some(ThumbnailKind::Mesh) => {
+            let (positions, triangles) = load_mesh(path)?;
+            mesh_thumbnail(&positions, &triangles, THUMBNAIL_SIZE, TURNTABLE_FRAMES)
+        }
+        Some(ThumbnailKind::Material) => material_thumbnail(path, THUMBNAIL_SIZE),
+        None => Err(format!("Sem miniatura para {}", path.display())),
+    }
+}
+
+/// PNG in the cache for this version of `path`
+fn cache_file(cache_dir: &Path, path: &Path) -> Option<PathBuf> {
+    let (len, mtime) = file_stamp(path)?;
+    let mut hasher = DefaultHasher::new();
+    fs::canonicalize(path)
+        .unwrap_or_else(|_| path.to_path_buf())
+        .hash(&mut hasher);
+    Some(cache_dir.join(format!("{:016x}_{len:x}_{mtime:x}.png", hasher.finish())))
+}
+
+fn read_cached(file: &Path, frames: u32) -> Option<Thumbnail> {
+    let rgba = image::open(file).ok()?.to_rgba8();
+    let frames = frames.max(1);
+    (rgba.width() % frames == 0).then(|| Thumbnail {
+        width: rgba.width() / frames,
+        height: rgba.height(),
+        frames,
+        rgba: rgba.into_raw(),
+    })
+}
+
+fn write_cached(file: &Path, thumbnail: &Thumbnail) -> Result<(), String> {
+    if let Some(dir) = file.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Falha ao criar {}: {e}", dir.display()))?;
+    }
+    image::save_buffer(
+        file,
+        &thumbnail.rgba,
+        thumbnail.width * thumbnail.frames,
+        thumbnail.height,
+        image::ExtendedColorType::Rgba8,
+    )
+    .map_err(|e| format!("Falha ao salvar {}: {e}", file.display()))
+}
+
+/// Cached thumbnail of `path`, rendering and caching it when missing or stale
+pub fn cached_thumbnail(
+    cache_dir: &Path,
+    path: &Path,
+    load_mesh: MeshGeometryLoader,
+) -> Result<Thumbnail, String> {
+    let frames = match ThumbnailKind::from_path(path) {
+        Some(ThumbnailKind::Mesh) => TURNTABLE_FRAMES,
+        _ => 1,
+    };
+    let file = cache_file(cache_dir, path);
+    if let Some(cached) = file.as_deref().and_then(|f| read_cached(f, frames)) {
+        return Ok(cached);
+    }
+    let thumbnail = generate_thumbnail(path, load_mesh)?;
+    if let Some(file) = file {
+        // Without the cache it is only rendered again next time
+        let _ = write_cached(&file, &thumbnail);
+    }
+    Ok(thumbnail)
+}
+
+/// A finished request
+pub struct ThumbnailResult {
+    pub path: PathBuf,
+    pub thumbnail: Result<Thumbnail, String>,
+}
+
+/// Worker threads that fill and read the thumbnail cache
+pub struct ThumbnailGenerator {
+    requests: Sender<PathBuf>,
+    results: Receiver<ThumbnailResult>,
+    pending: HashSet<PathBuf>,
+}
+
+impl ThumbnailGenerator {
+    /// `workers` threads caching into `cache_dir`; meshes are read with `load_mesh`
+    pub fn new(cache_dir: PathBuf, workers: usize, load_mesh: MeshGeometryLoader) -> Self {
+        let (request_tx, request_rx) = mpsc::channel::<PathBuf>();
+        let (result_tx, result_rx) = mpsc::channel();
+        let request_rx = Arc::new(Mutex::new(request_rx));
+        for _ in 0..workers.max(1) {
+            let requests = Arc::clone(&request_rx);
+            let results = result_tx.clone();
+            let cache_dir = cache_dir.clone();
+            std::thread::spawn(move || {
+                loop {
+                    // The queue closes when the generator is dropped
+                    let next = requests.lock().ok().and_then(|rx| rx.recv().ok());
+                    let Some(path) = next else {
+                        break;
+                    };
+                    let thumbnail = cached_thumbnail(&cache_dir, &path, load_mesh);
+                    if results.send(ThumbnailResult { path, thumbnail }).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        Self {
+            requests: request_tx,
+            results: result_rx,
+            pending: HashSet::new(),
+        }
+    }
+
+    /// Queue `path`; false when it is already queued or has no thumbnail kind
+    pub fn request(&mut self, path: &Path) -> bool {
+        if ThumbnailKind::from_path(path).is_none() || self.pending.contains(path) {
+            return false;
+        }
+        if self.requests.send(path.to_path_buf()).is_err() {
+            return false;
+        }
+        self.pending.insert(path.to_path_buf());
+        true
+    }
+
+    pub fn is_pending(&self, path: &Path) -> bool {
+        self.pending.contains(path)
+    }
+
+    /// Thumbnails finished since the last poll
+    pub fn poll(&mut self) -> Vec<ThumbnailResult> {
+        let results: Vec<ThumbnailResult> = self.results.try_iter().collect();
+        for result in &results {
+            self.pending.remove(&result.path);
+        }
+        results
+    }
+}
@@ -0,0 +1,319 @@
+//! Virtual filesystem - where asset bytes come from
+//!
+//! Paths are `/` separated and start with a mount point: `Assets/Meshes/a.obj`
+//! reads `Meshes/a.obj` from whatever is mounted at `Assets` (the project
+//! directory in the editor, the cooked bundles in a shipped game) and
+//! `Editor/icons/add.png` reads the editor's built-in files. The deepest mount
+//! point wins, and among equal ones the latest mount, so a content pack mounted
+//! at `Assets` overrides the files below it. Paths outside every mount point
+//! (absolute paths, files picked in a dialog) go straight to the disk.
+//!
+//! One `Vfs` is shared by the whole process through `vfs()` / `vfs_mut()`;
+//! it starts with the `Assets` directory of the current working directory.
+
+use std::cmp::Reverse;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::asset_bundle::{BundleReader, bundle_key};
+
+/// Something that can be mounted: paths are relative to the mount point
+pub trait VfsSource: Send + Sync {
+    /// Bytes of `path`, or `None` when this source doesn't have it
+    fn read(&self, path: &str) -> Option<Result<Vec<u8>, String>>;
+
+    fn exists(&self, path: &str) -> bool;
+
+    /// File on disk behind `path`, for code that can only open real files
+    fn disk_path(&self, _path: &str) -> Option<PathBuf> {
+        None
+    }
+
+    /// Every file, relative to the mount point
+    fn files(&self) -> Vec<String>;
+}
+
+/// A directory on disk
+#[derive(Debug, Clone)]
+pub struct DirSource {
+    root: PathBuf,
+}
+
+impl DirSource {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
+
+impl VfsSource for DirSource {
+    fn read(&self, path: &str) -> Option<Result<Vec<u8>, String>> {
+        let file = self.root.join(path);
+        file.is_file()
+            .then(|| fs::read(&file).map_err(|e| format!("Falha ao ler {}: {e}", file.display())))
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        self.root.join(path).exists()
+    }
+
+    fn disk_path(&self, path: &str) -> Option<PathBuf> {
+        Some(if path.is_empty() {
+            self.root.clone()
+        } else {
+            self.root.join(path)
+        })
+    }
+
+    fn files(&self) -> Vec<String> {
+        let mut files = Vec::new();
+        let mut pending = vec![self.root.clone()];
+        while let Some(dir) = pending.pop() {
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    pending.push(path);
+                } else if let Ok(relative) = path.strip_prefix(&self.root) {
+                    files.push(relative.to_string_lossy().replace('\\', "/"));
+                }
+            }
+        }
+        files.sort();
+        files
+    }
+}
+
+impl VfsSource for BundleReader {
+    fn read(&self, path: &str) -> Option<Result<Vec<u8>, String>> {
+        self.contains(path).then(|| BundleReader::read(self, path))
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        let key = bundle_key(path);
+        // A folder exists when some file is inside it
+        self.contains(&key)
+            || self
+                .files()
+                .iter()
+                .any(|file| key.is_empty() || file.starts_with(&format!("{key}/")))
+    }
+
+    fn files(&self) -> Vec<String> {
+        BundleReader::files(self)
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+struct Mount {
+    point: String,
+    source: Arc<dyn VfsSource>,
+}
+
+/// Mount points and their sources
+#[derive(Default)]
+pub struct Vfs {
+    mounts: Vec<Mount>,
+}
+
+/// `\` -> `/`, no leading `./` or trailing `/`
+fn normalize(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    path.trim_start_matches("./")
+        .trim_end_matches('/')
+        .to_string()
+}
+
+impl Vfs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mount `source` at `point` (`Assets`, `Editor`, `Assets/DLC`...)
+    pub fn mount(&mut self, point: &str, source: impl VfsSource + 'static) {
+        self.mounts.push(Mount {
+            point: normalize(point),
+            source: Arc::new(source),
+        });
+    }
+
+    /// Remove every source mounted at `point`; returns how many there were
+    pub fn unmount(&mut self, point: &str) -> usize {
+        let point = normalize(point);
+        let before = self.mounts.len();
+        self.mounts.retain(|mount| mount.point != point);
+        before - self.mounts.len()
+    }
+
+    /// Mount points, in mount order
+    pub fn mount_points(&self) -> Vec<&str> {
+        self.mounts.iter().map(|m| m.point.as_str()).collect()
+    }
+
+    /// Sources that may hold `path` with the path relative to each, best first
+    fn candidates(&self, path: &str) -> Vec<(&dyn VfsSource, String)> {
+        let path = normalize(path);
+        let mut found: Vec<(usize, &dyn VfsSource, String)> = self
+            .mounts
+            .iter()
+            .rev()
+            .filter_map(|mount| {
+                let rest = if path == mount.point {
+                    ""
+                } else {
+                    path.strip_prefix(&mount.point)?.strip_prefix('/')?
+                };
+                Some((mount.point.len(), mount.source.as_ref(), rest.to_string()))
+            })
+            .collect();
+        // Stable: among equal points the latest mount stays first
+        found.sort_by_key(|(len, _, _)| Reverse(*len));
+        found
+            .into_iter()
+            .map(|(_, source, rest)| (source, rest))
+            .collect()
+    }
+
+    /// Bytes of a virtual path, or of a real file outside every mount point
+    pub fn read(&self, path: impl AsRef<Path>) -> Result<Vec<u8>, String> {
+        let path = path.as_ref();
+        let text = path.to_string_lossy();
+        let candidates = self.candidates(&text);
+        if candidates.is_empty() {
+            return fs::read(path).map_err(|e| format!("Falha ao ler {}: {e}", path.display()));
+        }
+        candidates
+            .into_iter()
+            .find_map(|(source, rest)| source.read(&rest))
+            .unwrap_or_else(|| Err(format!("Arquivo não encontrado: {text}")))
+    }
+
+    pub fn read_to_string(&self, path: impl AsRef<Path>) -> Result<String, String> {
+        let path = path.as_ref();
+        String::from_utf8(self.read(path)?)
+            .map_err(|_| format!("Arquivo não é texto UTF-8: {}", path.display()))
+    }
+
+    pub fn exists(&self, path: impl AsRef<Path>) -> bool {
+        let path = path.as_ref();
+        let candidates = self.candidates(&path.to_string_lossy());
+        if candidates.is_empty() {
+            return path.exists();
+        }
+        candidates
+            .into_iter()
+            .any(|(source, rest)| source.exists(&rest))
+    }
+
+    /// Real path on disk of a virtual path: the first directory mount holding
+    /// it, else the best directory mount (for files about to be created).
+    /// `None` when only archives and bundles are mounted there.
+    pub fn resolve(&self, path: impl AsRef<Path>) -> Option<PathBuf> {
+        let path = path.as_ref();
+        let candidates = self.candidates(&path.to_string_lossy());
+        if candidates.is_empty() {
+            return Some(path.to_path_buf());
+        }
+        candidates
+            .iter()
+            .find(|(source, rest)| source.exists(rest) && source.disk_path(rest).is_some())
+            .or_else(|| candidates.iter().find(|(s, r)| s.disk_path(r).is_some()))
+            .and_then(|(source, rest)| source.disk_path(rest))
+    }
+
+    /// Real file for a loader that can only open paths (mesh importers): the
+    /// file itself when a directory mount holds it, else a copy extracted from
+    /// the archive into the temp directory, together with the other files of
+    /// its folder (`.mtl`, `.bin` and textures next to a model)
+    pub fn local_path(&self, path: impl AsRef<Path>) -> Result<PathBuf, String> {
+        let path = path.as_ref();
+        let virtual_path = normalize(&path.to_string_lossy());
+        let candidates = self.candidates(&virtual_path);
+        if candidates.is_empty() {
+            return Ok(path.to_path_buf());
+        }
+        let (source, rest) = candidates
+            .into_iter()
+            .find(|(source, rest)| source.exists(rest))
+            .ok_or_else(|| format!("Arquivo não encontrado: {virtual_path}"))?;
+        if let Some(disk) = source.disk_path(&rest) {
+            return Ok(disk);
+        }
+        if rest.is_empty() {
+            return Err(format!("Não é um arquivo: {virtual_path}"));
+        }
+
+        let cache = std::env::temp_dir().join("dengine_vfs");
+        let folder = rest.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("");
+        // Mount point with its trailing `/`
+        let point = &virtual_path[..virtual_path.len() - rest.len()];
+        for file in source.files() {
+            let parent = file.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("");
+            if parent != folder {
+                continue;
+            }
+            // Always written again: another pack may have used the same path
+            let target = cache.join(format!("{point}{file}"));
+            let Some(bytes) = source.read(&file) else {
+                continue;
+            };
+            if let Some(dir) = target.parent() {
+                fs::create_dir_all(dir)
+                    .map_err(|e| format!("Falha ao criar {}: {e}", dir.display()))?;
+            }
+            fs::write(&target, bytes?)
+                .map_err(|e| format!("Falha ao extrair {}: {e}", target.display()))?;
+        }
+        Ok(cache.join(&virtual_path))
+    }
+
+    /// Every file under a mount point, as virtual paths
+    pub fn files(&self, point: &str) -> Vec<String> {
+        let point = normalize(point);
+        let mut files: Vec<String> = self
+            .mounts
+            .iter()
+            .filter(|mount| mount.point == point)
+            .flat_map(|mount| mount.source.files())
+            .map(|file| format!("{point}/{file}"))
+            .collect();
+        files.sort();
+        files.dedup();
+        files
+    }
+}
+
+static VFS: LazyLock<RwLock<Vfs>> = LazyLock::new(|| {
+    let mut vfs = Vfs::new();
+    vfs.mount("Assets", DirSource::new("Assets"));
+    RwLock::new(vfs)
+});
+
+/// The process-wide VFS
+pub fn vfs() -> RwLockReadGuard<'static, Vfs> {
+    VFS.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The process-wide VFS, to mount or unmount sources
+pub fn vfs_mut() -> RwLockWriteGuard<'static, Vfs> {
+    VFS.write().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Shorthand for `vfs().read(path)`
+pub fn vfs_read(path: impl AsRef<Path>) -> Result<Vec<u8>, String> {
+    vfs().read(path)
+}
+
+/// Decode an image through the VFS (format guessed from the bytes)
+pub fn open_image(path: &Path) -> Result<image::DynamicImage, String> {
+    let bytes = vfs_read(path)?;
+    image::load_from_memory(&bytes).map_err(|e| format!("Falha ao abrir {}: {}", path.display(), e))
+}
@@ -0,0 +1,225 @@
+//! Video capture - streams viewport frames to an `ffmpeg` encoder process
+//!
+//! Frames arrive from the GPU readback path with the time they were rendered;
+//! an encoder thread writes them as raw RGBA to `ffmpeg`'s stdin, repeating or
+//! skipping frames so the clip plays at real-time speed at a constant frame
+//! rate. `ffmpeg` must be on the `PATH`.
+
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// Frame rate of recorded clips
+pub const VIDEO_FPS: u32 = 30;
+/// Frames queued for the encoder before new ones are dropped
+const VIDEO_QUEUE: usize = 8;
+
+/// Container / codec of a recording
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VideoFormat {
+    /// H.264 in MP4
+    Mp4,
+    /// VP9 in WebM
+    WebM,
+}
+
+impl VideoFormat {
+    pub const ALL: [VideoFormat; 2] = [VideoFormat::Mp4, VideoFormat::WebM];
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            VideoFormat::Mp4 => "mp4",
+            VideoFormat::WebM => "webm",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            VideoFormat::Mp4 => "MP4 (H.264)",
+            VideoFormat::WebM => "WebM (VP9)",
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_lowercase();
+        match ext.as_str() {
+            "mp4" => Some(VideoFormat::Mp4),
+            "webm" => Some(VideoFormat::WebM),
+            _ => None,
+        }
+    }
+
+    fn codec_args(self) -> &'static [&'static str] {
+        match self {
+            VideoFormat::Mp4 => &[
+                "-c:v",
+                "libx264",
+                "-preset",
+                "veryfast",
+                "-crf",
+                "20",
+                "-movflags",
+                "+faststart",
+            ],
+            VideoFormat::WebM => &[
+                "-c:v",
+                "libvpx-vp9",
+                "-deadline",
+                "realtime",
+                "-b:v",
+                "0",
+                "-crf",
+                "32",
+            ],
+        }
+    }
+}
+
+struct VideoFrame {
+    rgba: Vec<u8>,
+    /// Time since the recording started
+    time: Duration,
+}
+
+/// Recording in progress; frames must be `size()` RGBA, top row first
+pub struct VideoRecorder {
+    path: PathBuf,
+    size: [u32; 2],
+    sender: SyncSender<VideoFrame>,
+    encoder: JoinHandle<Result<(), String>>,
+    dropped: u64,
+}
+
+impl VideoRecorder {
+    /// Start `ffmpeg` for a `width` x `height` clip (rounded down to even sizes,
+    /// as required by yuv420p)
+    pub fn start(path: impl Into<PathBuf>, width: u32, height: u32) -> Result<Self, String> {
+        let path = path.into();
+        let format = VideoFormat::from_path(&path).ok_or_else(|| {
+            format!(
+                "Formato de vídeo não suportado: {} (use .mp4 ou .webm)",
+                path.display()
+            )
+        })?;
+        let size = [(width & !1).max(2), (height & !1).max(2)];
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| format!("Falha ao criar {}: {e}", dir.display()))?;
+        }
+        let child = Command::new("ffmpeg")
+            .args([
+                "-y",
+                "-loglevel",
+                "error",
+                "-f",
+                "rawvideo",
+                "-pix_fmt",
+                "rgba",
+            ])
+            .args(["-s", &format!("{}x{}", size[0], size[1])])
+            .args(["-r", &VIDEO_FPS.to_string(), "-i", "-"])
+            .args(format.codec_args())
+            .args(["-pix_fmt", "yuv420p"])
+            .arg(&path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Falha ao iniciar o ffmpeg (está no PATH?): {e}"))?;
+        let (sender, receiver) = mpsc::sync_channel(VIDEO_QUEUE);
+        let encoder = std::thread::spawn(move || encode_frames(child, receiver));
+        Ok(Self {
+            path,
+            size,
+            sender,
+            encoder,
+            dropped: 0,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Frame size in pixels
+    pub fn size(&self) -> [u32; 2] {
+        self.size
+    }
+
+    /// Frames dropped because the encoder fell behind
+    pub fn dropped_frames(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Queue a frame rendered `time` after the start; dropped (returns false)
+    /// when the encoder queue is full
+    pub fn push_frame(&mut self, rgba: Vec<u8>, time: Duration) -> bool {
+        let expected = self.size[0] as usize * self.size[1] as usize * 4;
+        if rgba.len() != expected {
+            self.dropped += 1;
+            return false;
+        }
+        match self.sender.try_send(VideoFrame { rgba, time }) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_) | TrySendError::Disconnected(_)) => {
+                self.dropped += 1;
+                false
+            }
+        }
+    }
+
+    /// Flush the queued frames and wait for `ffmpeg` to close the file
+    pub fn finish(self) -> Result<PathBuf, String> {
+        drop(self.sender);
+        self.encoder
+            .join()
+            .map_err(|_| "Thread do encoder de vídeo falhou".to_string())??;
+        Ok(self.path)
+    }
+}
+
+/// Encoder thread: writes each frame as many times as needed to keep the clip
+/// at `VIDEO_FPS`
+fn encode_frames(mut child: Child, receiver: Receiver<VideoFrame>) -> Result<(), String> {
+    let mut stdin = child.stdin.take().ok_or("ffmpeg sem stdin")?;
+    let mut written: u64 = 0;
+    let mut write_error = None;
+    for frame in receiver {
+        let due = (frame.time.as_secs_f64() * VIDEO_FPS as f64).floor() as u64 + 1;
+        while written < due {
+            if let Err(e) = stdin.write_all(&frame.rgba) {
+                write_error = Some(e);
+                break;
+            }
+            written += 1;
+        }
+        if write_error.is_some() {
+            break;
+        }
+    }
+    drop(stdin);
+
+    let mut log = String::new();
+    if let Some(stderr) = child.stderr.as_mut() {
+        let _ = stderr.read_to_string(&mut log);
+    }
+    let status = child
+        .wait()
+        .map_err(|e| format!("Falha ao esperar o ffmpeg: {e}"))?;
+    if !status.success() {
+        return Err(format!(
+            "ffmpeg terminou com erro ({status}): {}",
+            log.trim()
+        ));
+    }
+    if let Some(e) = write_error {
+        return Err(format!("Falha ao enviar frames ao ffmpeg: {e}"));
+    }
+    if written == 0 {
+        return Err("Gravação sem frames".to_string());
+    }
+    Ok(())
+}
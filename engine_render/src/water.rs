@@ -0,0 +1,109 @@
+//! Water surfaces - planar water material settings and uniform packing
+//!
+//! A water surface is a flat rectangle in the local XZ plane of its object.
+//! It is drawn by `WATER_SHADER` after the opaque scene, which it samples for
+//! refraction, depth fog and screen-space reflections, so it needs the
+//! offscreen scene targets (color copy + depth).
+
+use glam::{Mat4, Vec3};
+
+use crate::shader::WATER_UNIFORM_SIZE;
+
+/// Planar water material
+#[derive(Debug, Clone, PartialEq)]
+pub struct WaterSurface {
+    /// Extent along local X and Z
+    pub size: [f32; 2],
+    /// Tint of what is seen through shallow water
+    pub shallow_color: [f32; 3],
+    /// Color the water fades to as it gets deeper
+    pub deep_color: [f32; 3],
+    /// Fog per world unit of water between the surface and the bottom
+    pub fog_density: f32,
+    /// Length of the largest wave (or normal map tile) in world units
+    pub wave_scale: f32,
+    /// Wave scroll speed in world units per second
+    pub wave_speed: f32,
+    /// How much the waves tilt the surface normal
+    pub wave_strength: f32,
+    /// Scales the fresnel reflection (0 = refraction only)
+    pub reflectivity: f32,
+    /// Screen-space distortion of the refracted scene
+    pub refraction_strength: f32,
+    /// Tangent-space normal map; sine waves when `None`
+    pub normal_map: Option<String>,
+}
+
+impl Default for WaterSurface {
+    fn default() -> Self {
+        Self {
+            size: [20.0, 20.0],
+            shallow_color: [0.75, 0.92, 0.95],
+            deep_color: [0.02, 0.12, 0.18],
+            fog_density: 0.35,
+            wave_scale: 4.0,
+            wave_speed: 0.6,
+            wave_strength: 0.3,
+            reflectivity: 1.0,
+            refraction_strength: 0.03,
+            normal_map: None,
+        }
+    }
+}
+
+/// Camera, light and clock the surface is drawn with
+#[derive(Debug, Clone, Copy)]
+pub struct WaterView {
+    pub view_proj: Mat4,
+    pub camera_pos: Vec3,
+    pub light_dir: Vec3,
+    pub light_color: Vec3,
+    pub light_intensity: f32,
+    /// Seconds, drives the wave animation
+    pub time: f32,
+}
+
+/// Uniform block of `WATER_SHADER` for one surface
+pub fn water_uniform_bytes(
+    water: &WaterSurface,
+    model: Mat4,
+    view: &WaterView,
+) -> [u8; WATER_UNIFORM_SIZE] {
+    let mut floats: Vec<f32> = Vec::with_capacity(WATER_UNIFORM_SIZE / 4);
+    floats.extend_from_slice(&view.view_proj.to_cols_array());
+    floats.extend_from_slice(&view.view_proj.inverse().to_cols_array());
+    floats.extend_from_slice(&model.to_cols_array());
+    // The clock wraps so it keeps its precision in an f32
+    floats.extend_from_slice(&view.camera_pos.extend(view.time % 3600.0).to_array());
+    floats.extend_from_slice(
+        &view
+            .light_dir
+            .normalize_or(Vec3::Y)
+            .extend(view.light_intensity)
+            .to_array(),
+    );
+    floats.extend_from_slice(&view.light_color.to_array());
+    floats.push(if water.normal_map.is_some() { 1.0 } else { 0.0 });
+    floats.extend_from_slice(&water.shallow_color);
+    floats.push(water.fog_density.max(0.0));
+    floats.extend_from_slice(&water.deep_color);
+    floats.push(water.reflectivity.clamp(0.0, 1.0));
+    floats.extend_from_slice(&[
+        water.size[0].max(0.0),
+        water.size[1].max(0.0),
+        water.wave_scale.max(0.01),
+        water.wave_speed,
+    ]);
+    floats.extend_from_slice(&[
+        water.wave_strength.max(0.0),
+        water.refraction_strength.max(0.0),
+        0.0,
+        0.0,
+    ]);
+
+    let mut bytes = [0_u8; WATER_UNIFORM_SIZE];
+    for (chunk, value) in bytes.chunks_exact_mut(4).zip(floats) {
+        chunk.copy_from_slice(&value.to_le_bytes());
+    }
+    bytes
+}
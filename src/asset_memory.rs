@@ -0,0 +1,262 @@
+use super::*;
+use crate::log_panel::LogLevel;
+use engine_render::{AssetMemory, MemoryBudget, pick_lru};
+
+/// Janela de Memória de assets: o que cada painel do editor mantém carregado,
+/// o que a viewport ocupa na GPU e os orçamentos de cada uma
+#[derive(Default)]
+pub(crate) struct AssetMemoryWindow {
+    pub(crate) open: bool,
+}
+
+/// Tamanho legível (B, KB, MB, GB)
+pub(crate) fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// Uso contra o limite (0 = sem limite) numa barra
+fn budget_bar(ui: &mut egui::Ui, used: usize, limit: usize) {
+    let (fraction, text) = if limit == 0 {
+        (0.0, format!("{} (sem limite)", format_bytes(used)))
+    } else {
+        (
+            (used as f32 / limit as f32).min(1.0),
+            format!("{} / {}", format_bytes(used), format_bytes(limit)),
+        )
+    };
+    let bar = egui::ProgressBar::new(fraction)
+        .text(text)
+        .desired_width(180.0);
+    let bar = if limit > 0 && used > limit {
+        bar.fill(egui::Color32::from_rgb(190, 70, 60))
+    } else {
+        bar
+    };
+    ui.add(bar);
+}
+
+impl EditorApp {
+    /// Com os painéis acima do orçamento de CPU, descarrega os assets carregados sob
+    /// demanda (`AssetManager::load`) menos usados recentemente, de qualquer painel
+    pub(crate) fn enforce_memory_budget(&mut self) {
+        let budget = self.graphics.memory_budgets().0;
+        if budget == 0 {
+            return;
+        }
+        let total: usize = [
+            self.environment.assets(),
+            self.post_process.assets(),
+            self.audio_preview.assets(),
+            self.inspector.material_shader_assets(),
+        ]
+        .iter()
+        .flat_map(|assets| assets.memory_report())
+        .map(|asset| asset.bytes)
+        .sum();
+        if total <= budget {
+            return;
+        }
+        // Só estes painéis usam `load`; os outros nunca têm candidatos
+        let mut streaming = [
+            self.environment.assets_mut(),
+            self.audio_preview.assets_mut(),
+        ];
+        let candidates: Vec<_> = streaming
+            .iter()
+            .enumerate()
+            .flat_map(|(index, assets)| {
+                assets
+                    .eviction_candidates()
+                    .into_iter()
+                    .map(move |(handle, last_used, bytes)| ((index, handle), last_used, bytes))
+            })
+            .collect();
+        for (index, handle) in pick_lru(candidates, total - budget) {
+            if streaming[index].unload(handle) {
+                self.log.push(
+                    LogLevel::Info,
+                    "Memória",
+                    format!("{} descarregado (orçamento de CPU)", handle.kind_name()),
+                );
+            }
+        }
+    }
+
+    pub(crate) fn draw_asset_memory_window(&mut self, ctx: &egui::Context) {
+        engine_core::profile_function!();
+        if !self.asset_memory.open {
+            return;
+        }
+        let gpu = self
+            .viewport_gpu
+            .as_ref()
+            .map(ViewportGpuRenderer::memory_usage)
+            .unwrap_or_default();
+        let sections: [(&str, Vec<AssetMemory>); 4] = [
+            ("Ambiente", self.environment.assets().memory_report()),
+            (
+                "Pós-processamento",
+                self.post_process.assets().memory_report(),
+            ),
+            (
+                "Pré-escuta de áudio",
+                self.audio_preview.assets().memory_report(),
+            ),
+            (
+                "Shaders de material",
+                self.inspector.material_shader_assets().memory_report(),
+            ),
+        ];
+        let total: usize = sections
+            .iter()
+            .flat_map(|(_, report)| report.iter())
+            .map(|asset| asset.bytes)
+            .sum();
+
+        let (cpu_limit, gpu_limit) = self.graphics.memory_budgets();
+        let (mut cpu_mb, mut gpu_mb) = self.graphics.memory_budgets_mb();
+        let mut budgets_changed = false;
+
+        let mut open = self.asset_memory.open;
+        egui::Window::new("📊 Memória de assets")
+            .open(&mut open)
+            .default_size([420.0, 380.0])
+            .resizable(true)
+            .show(ctx, |ui| {
+                egui::Grid::new("asset_memory_budgets")
+                    .num_columns(3)
+                    .spacing([8.0, 4.0])
+                    .show(ui, |ui| {
+                        ui.label(egui::RichText::new("CPU").strong());
+                        budget_bar(ui, total, cpu_limit);
+                        budgets_changed |= ui
+                            .add(egui::DragValue::new(&mut cpu_mb).suffix(" MB").speed(8.0))
+                            .on_hover_text("Orçamento dos painéis do editor (0 = sem limite)")
+                            .changed();
+                        ui.end_row();
+                        ui.label(egui::RichText::new("GPU").strong());
+                        budget_bar(ui, gpu.total(), gpu_limit);
+                        budgets_changed |= ui
+                            .add(egui::DragValue::new(&mut gpu_mb).suffix(" MB").speed(8.0))
+                            .on_hover_text(
+                                "Orçamento da viewport (0 = sem limite); texturas acima dele saem e são lidas de novo quando voltam a ser usadas",
+                            )
+                            .changed();
+                        ui.end_row();
+                    });
+                ui.add_space(4.0);
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    gpu_memory_section(ui, &gpu);
+                    for (title, report) in &sections {
+                        let bytes: usize = report.iter().map(|asset| asset.bytes).sum();
+                        egui::CollapsingHeader::new(format!(
+                            "{} — {} ({})",
+                            title,
+                            report.len(),
+                            format_bytes(bytes)
+                        ))
+                        .id_salt(title)
+                        .default_open(!report.is_empty())
+                        .show(ui, |ui| {
+                            if report.is_empty() {
+                                ui.label(egui::RichText::new("Nenhum asset carregado").weak());
+                                return;
+                            }
+                            egui::Grid::new(("asset_memory_grid", *title))
+                                .num_columns(4)
+                                .striped(true)
+                                .spacing([12.0, 4.0])
+                                .show(ui, |ui| {
+                                    ui.label(egui::RichText::new("Nome").strong());
+                                    ui.label(egui::RichText::new("Tipo").strong());
+                                    ui.label(egui::RichText::new("Tamanho").strong());
+                                    ui.label(egui::RichText::new("Refs").strong());
+                                    ui.end_row();
+                                    for asset in report {
+                                        let name = Path::new(&asset.name)
+                                            .file_name()
+                                            .map(|name| name.to_string_lossy().to_string())
+                                            .unwrap_or_else(|| asset.name.clone());
+                                        ui.label(name).on_hover_text(&asset.name);
+                                        ui.label(asset.handle.kind_name());
+                                        ui.label(format_bytes(asset.bytes));
+                                        ui.label(asset.ref_count.to_string());
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+                    }
+                });
+            });
+        self.asset_memory.open = open;
+
+        if budgets_changed {
+            if let Err(e) = self.graphics.set_memory_budgets_mb(cpu_mb, gpu_mb) {
+                self.log.push(LogLevel::Error, "Memória", e);
+                self.log_enabled = true;
+            }
+        }
+    }
+}
+
+/// Memória da viewport na GPU: total por tipo e cada asset
+fn gpu_memory_section(ui: &mut egui::Ui, gpu: &MemoryBudget) {
+    let entries = gpu.entries();
+    egui::CollapsingHeader::new(format!(
+        "GPU (viewport) — {} ({})",
+        entries.len(),
+        format_bytes(gpu.total())
+    ))
+    .id_salt("asset_memory_gpu")
+    .default_open(!entries.is_empty())
+    .show(ui, |ui| {
+        if entries.is_empty() {
+            ui.label(egui::RichText::new("Nada enviado para a GPU").weak());
+            return;
+        }
+        let totals = gpu
+            .totals_by_category()
+            .into_iter()
+            .map(|(category, bytes)| format!("{category}: {}", format_bytes(bytes)))
+            .collect::<Vec<_>>()
+            .join("   ");
+        ui.label(egui::RichText::new(totals).small());
+        egui::Grid::new("asset_memory_gpu_grid")
+            .num_columns(4)
+            .striped(true)
+            .spacing([12.0, 4.0])
+            .show(ui, |ui| {
+                ui.label(egui::RichText::new("Nome").strong());
+                ui.label(egui::RichText::new("Tipo").strong());
+                ui.label(egui::RichText::new("Tamanho").strong());
+                ui.label(egui::RichText::new("Residência").strong());
+                ui.end_row();
+                for entry in &entries {
+                    let name = Path::new(&entry.name)
+                        .file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                        .unwrap_or_else(|| entry.name.clone());
+                    ui.label(name).on_hover_text(&entry.name);
+                    ui.label(entry.category);
+                    ui.label(format_bytes(entry.bytes));
+                    ui.label(if entry.evictable {
+                        "Streaming"
+                    } else {
+                        "Residente"
+                    });
+                    ui.end_row();
+                }
+            });
+    });
+}
@@ -0,0 +1,202 @@
+use super::*;
+use crate::log_panel::LogLevel;
+use engine_audio::AudioSystem;
+use engine_render::{AssetHandle, AssetManager, AudioInfo, LoadState};
+use inspector::AudioPreviewRequest;
+
+/// Estado da pre-escuta do clipe selecionado, mostrado no Projeto e no Inspetor
+#[derive(Clone)]
+pub(crate) struct AudioClipView {
+    pub(crate) path: PathBuf,
+    pub(crate) playing: bool,
+    /// Duração e forma de onda; `None` enquanto o clipe decodifica
+    pub(crate) info: Option<Arc<AudioInfo>>,
+    /// Fração já tocada (0..1)
+    pub(crate) progress: f32,
+}
+
+/// Desenha os picos da forma de onda em `rect`, com a parte já tocada destacada
+pub(crate) fn paint_waveform(painter: &egui::Painter, rect: egui::Rect, view: &AudioClipView) {
+    painter.rect_filled(rect, 3.0, egui::Color32::from_rgb(28, 30, 34));
+    let Some(info) = view.info.as_ref() else {
+        painter.text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            "…",
+            egui::FontId::proportional(11.0),
+            theme::palette().text_weak,
+        );
+        return;
+    };
+    if info.peaks.is_empty() {
+        return;
+    }
+    let played_x = rect.left() + rect.width() * view.progress.clamp(0.0, 1.0);
+    let columns = (rect.width() as usize).max(1);
+    let mid = rect.center().y;
+    let half = rect.height() * 0.45;
+    for column in 0..columns {
+        let start = column * info.peaks.len() / columns;
+        let end = ((column + 1) * info.peaks.len() / columns)
+            .max(start + 1)
+            .min(info.peaks.len());
+        let peak = info.peaks[start.min(info.peaks.len() - 1)..end]
+            .iter()
+            .copied()
+            .fold(0.0, f32::max);
+        let x = rect.left() + column as f32 + 0.5;
+        let color = if view.playing && x <= played_x {
+            theme::palette().accent
+        } else {
+            egui::Color32::from_rgb(150, 120, 200)
+        };
+        let h = (peak * half).max(0.5);
+        painter.line_segment(
+            [egui::pos2(x, mid - h), egui::pos2(x, mid + h)],
+            egui::Stroke::new(1.0, color),
+        );
+    }
+    if view.playing {
+        painter.line_segment(
+            [
+                egui::pos2(played_x, rect.top()),
+                egui::pos2(played_x, rect.bottom()),
+            ],
+            egui::Stroke::new(1.0, egui::Color32::WHITE),
+        );
+    }
+}
+
+/// Pre-escuta de clipes de áudio do Projeto; o dispositivo de saída só é aberto no primeiro uso
+#[derive(Default)]
+pub(crate) struct AudioPreview {
+    assets: AssetManager,
+    system: Option<AudioSystem>,
+    /// Clipe selecionado, decodificado em segundo plano para a forma de onda
+    selected: Option<(PathBuf, AssetHandle)>,
+    info: Option<Arc<AudioInfo>>,
+}
+
+impl AudioPreview {
+    pub(crate) fn assets(&self) -> &AssetManager {
+        &self.assets
+    }
+
+    pub(crate) fn assets_mut(&mut self) -> &mut AssetManager {
+        &mut self.assets
+    }
+
+    fn is_playing(&self) -> bool {
+        self.system
+            .as_ref()
+            .is_some_and(AudioSystem::is_preview_playing)
+    }
+
+    fn progress(&self) -> f32 {
+        let Some(position) = self.system.as_ref().and_then(AudioSystem::preview_position) else {
+            return 0.0;
+        };
+        match self.info.as_ref() {
+            Some(info) if info.duration_secs > 0.0 => position.as_secs_f32() / info.duration_secs,
+            _ => 0.0,
+        }
+    }
+
+    /// Começa a decodificar o clipe selecionado e recolhe a forma de onda quando pronta
+    fn select(&mut self, path: Option<&Path>) -> Result<(), String> {
+        if self.selected.as_ref().map(|(p, _)| p.as_path()) != path {
+            self.info = None;
+            // O clipe anterior só servia para a forma de onda
+            if let Some((_, AssetHandle::AudioClip(old))) = self.selected.take() {
+                self.assets.unload_audio_clip(old);
+            }
+        }
+        let Some(path) = path else {
+            return Ok(());
+        };
+        match self.selected {
+            None => {
+                let (handle, _) = self.assets.load(path)?;
+                self.selected = Some((path.to_path_buf(), handle));
+            }
+            // Em uso enquanto selecionado: é o último a sair pelo orçamento de memória
+            Some((_, handle)) => self.assets.touch(handle),
+        }
+        for (handle, state) in self.assets.poll_loads() {
+            if let LoadState::Failed(e) = state {
+                // Mantém a seleção para não tentar de novo a cada quadro
+                if self.selected.as_ref().is_some_and(|(_, h)| *h == handle) {
+                    return Err(e);
+                }
+            }
+        }
+        if self.info.is_none() {
+            if let Some((_, AssetHandle::AudioClip(handle))) = self.selected {
+                self.info = self
+                    .assets
+                    .get_audio_clip(handle)
+                    .map(|clip| Arc::new(clip.info.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    fn play(&mut self, path: &Path) -> Result<(), String> {
+        let handle = self.assets.load_audio_clip(path)?;
+        let clip = self
+            .assets
+            .get_audio_clip(handle)
+            .ok_or_else(|| "clip de áudio inválido".to_string())?;
+        if self.system.is_none() {
+            self.system = Some(AudioSystem::new()?);
+        }
+        match self.system.as_mut() {
+            Some(system) => system.play_clip(clip, 1.0),
+            None => Ok(()),
+        }
+    }
+
+    fn stop(&mut self) {
+        if let Some(system) = self.system.as_mut() {
+            system.stop_preview();
+        }
+    }
+}
+
+impl EditorApp {
+    /// Sincroniza o asset de áudio selecionado com o Projeto e o Inspetor e executa os pedidos de
+    /// pre-escuta vindos de qualquer um dos dois
+    pub(crate) fn update_audio_preview(&mut self) {
+        let requests = [
+            self.inspector.take_audio_preview_request(),
+            self.project.take_audio_preview_request(),
+        ];
+        for request in requests.into_iter().flatten() {
+            match request {
+                AudioPreviewRequest::Play(path) => {
+                    if let Err(e) = self.audio_preview.play(&path) {
+                        self.log.push(LogLevel::Error, "Audio", e);
+                        self.log_enabled = true;
+                    }
+                }
+                AudioPreviewRequest::Stop => self.audio_preview.stop(),
+            }
+        }
+        let selected = self.project.selected_audio_asset_path();
+        if selected.is_none() {
+            self.audio_preview.stop();
+        }
+        if let Err(e) = self.audio_preview.select(selected.as_deref()) {
+            self.log.push(LogLevel::Error, "Audio", e);
+            self.log_enabled = true;
+        }
+        let view = selected.map(|path| AudioClipView {
+            path,
+            playing: self.audio_preview.is_playing(),
+            info: self.audio_preview.info.clone(),
+            progress: self.audio_preview.progress(),
+        });
+        self.project.set_audio_clip_view(view.clone());
+        self.inspector.set_audio_asset(view);
+    }
+}
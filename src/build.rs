@@ -0,0 +1,730 @@
+use super::*;
+use crate::bundles::MAX_BUNDLE_BYTES;
+use crate::scenes::SCENE_EXTENSION;
+use engine_render::pack_bundles;
+use std::path::Component;
+use std::sync::mpsc::Sender;
+
+// Player pré-compilado que vai com o editor: o da plataforma do editor fica
+// ao lado dele e os das outras em `players/<triple>/`
+const PLAYER_BINARY: &str = "dengine_player";
+const PLAYERS_DIR: &str = "players";
+// Sem player pronto, compila este pacote do workspace do editor com o cargo
+const PLAYER_PACKAGE: &str = "engine_player";
+// Pasta ao lado do executável com os bundles e a cena inicial (igual ao player)
+const DATA_DIR: &str = "Data";
+// Arquivos gravados pelo último build, relativos à saída: o próximo build
+// apaga só estes, nunca o resto da pasta
+const BUILD_MANIFEST: &str = "build.manifest";
+
+/// Plataforma do executável gerado
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum BuildTarget {
+    Windows,
+    Linux,
+    // Apple Silicon
+    MacOs,
+    // Macs Intel
+    MacOsIntel,
+}
+
+impl BuildTarget {
+    const ALL: [BuildTarget; 4] = [
+        BuildTarget::Windows,
+        BuildTarget::Linux,
+        BuildTarget::MacOs,
+        BuildTarget::MacOsIntel,
+    ];
+
+    /// A plataforma em que o editor está rodando (não pede toolchain extra)
+    fn host() -> Self {
+        if cfg!(target_os = "windows") {
+            BuildTarget::Windows
+        } else if cfg!(all(target_os = "macos", target_arch = "x86_64")) {
+            BuildTarget::MacOsIntel
+        } else if cfg!(target_os = "macos") {
+            BuildTarget::MacOs
+        } else {
+            BuildTarget::Linux
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            BuildTarget::Windows => "Windows",
+            BuildTarget::Linux => "Linux",
+            BuildTarget::MacOs => "macOS (Apple Silicon)",
+            BuildTarget::MacOsIntel => "macOS (Intel)",
+        }
+    }
+
+    fn triple(self) -> &'static str {
+        match self {
+            BuildTarget::Windows => "x86_64-pc-windows-msvc",
+            BuildTarget::Linux => "x86_64-unknown-linux-gnu",
+            BuildTarget::MacOs => "aarch64-apple-darwin",
+            BuildTarget::MacOsIntel => "x86_64-apple-darwin",
+        }
+    }
+
+    fn exe_name(self, stem: &str) -> String {
+        match self {
+            BuildTarget::Windows => format!("{stem}.exe"),
+            _ => stem.to_string(),
+        }
+    }
+
+    fn as_config_str(self) -> &'static str {
+        match self {
+            BuildTarget::Windows => "windows",
+            BuildTarget::Linux => "linux",
+            BuildTarget::MacOs => "macos",
+            BuildTarget::MacOsIntel => "macos-x86_64",
+        }
+    }
+
+    fn from_config_str(value: &str) -> Option<Self> {
+        match value.trim() {
+            "windows" => Some(BuildTarget::Windows),
+            "linux" => Some(BuildTarget::Linux),
+            "macos" => Some(BuildTarget::MacOs),
+            "macos-x86_64" => Some(BuildTarget::MacOsIntel),
+            _ => None,
+        }
+    }
+}
+
+/// De onde sai o executável do jogo
+enum PlayerSource {
+    /// Player pré-compilado que vem com o editor
+    Prebuilt(PathBuf),
+    /// Sem player pronto: `cargo build -p engine_player` neste workspace
+    Cargo(PathBuf),
+}
+
+/// Tudo que a thread do build precisa, copiado do editor
+struct BuildJob {
+    player: PlayerSource,
+    assets: PathBuf,
+    // Cena inicial já em .dscene: nome do arquivo em Data e conteúdo
+    scene_name: String,
+    scene_text: String,
+    output: PathBuf,
+    game_name: String,
+    target: BuildTarget,
+    width: u32,
+    height: u32,
+}
+
+enum BuildEvent {
+    Step(String),
+    Done(Result<PathBuf, String>),
+}
+
+/// Janela de Build, com as opções salvas em `ProjectSettings/build.cfg`
+pub(crate) struct BuildSettings {
+    pub(crate) open: bool,
+    game_name: String,
+    // Cena inicial: o .deng do projeto (a cena aberta) ou um .dscene da raiz
+    startup_scene: String,
+    target: BuildTarget,
+    width: u32,
+    height: u32,
+    output_dir: String,
+    // Projeto cujas opções estão carregadas
+    loaded_for: Option<PathBuf>,
+    job: Option<Receiver<BuildEvent>>,
+    steps: Vec<String>,
+    last_output: Option<PathBuf>,
+    status: Option<String>,
+}
+
+impl Default for BuildSettings {
+    fn default() -> Self {
+        Self {
+            open: false,
+            game_name: String::new(),
+            startup_scene: String::new(),
+            target: BuildTarget::host(),
+            width: 1280,
+            height: 720,
+            output_dir: String::new(),
+            loaded_for: None,
+            job: None,
+            steps: Vec::new(),
+            last_output: None,
+            status: None,
+        }
+    }
+}
+
+impl BuildSettings {
+    fn config_path(project: &Path) -> Option<PathBuf> {
+        project
+            .parent()
+            .map(|root| root.join("ProjectSettings").join("build.cfg"))
+    }
+
+    fn load(&mut self, project: Option<&Path>) {
+        let job = self.job.take();
+        *self = Self {
+            open: self.open,
+            job,
+            ..Self::default()
+        };
+        self.loaded_for = project.map(Path::to_path_buf);
+        let Some(project) = project else {
+            return;
+        };
+        let root = project.parent().unwrap_or(Path::new("."));
+        self.game_name = project
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        self.startup_scene = project
+            .file_name()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        self.output_dir = root.join("Builds").display().to_string();
+        let Some(raw) = Self::config_path(project).and_then(|path| fs::read_to_string(path).ok())
+        else {
+            return;
+        };
+        for (key, value) in raw.lines().filter_map(|line| line.split_once('=')) {
+            let value = value.trim();
+            match key.trim() {
+                "game_name" if !value.is_empty() => self.game_name = value.to_string(),
+                "startup_scene" if !value.is_empty() => self.startup_scene = value.to_string(),
+                "target" => {
+                    if let Some(target) = BuildTarget::from_config_str(value) {
+                        self.target = target;
+                    }
+                }
+                "width" => {
+                    if let Ok(width) = value.parse::<u32>() {
+                        self.width = width.max(320);
+                    }
+                }
+                "height" => {
+                    if let Ok(height) = value.parse::<u32>() {
+                        self.height = height.max(240);
+                    }
+                }
+                "output_dir" if !value.is_empty() => self.output_dir = value.to_string(),
+                _ => {}
+            }
+        }
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let Some(path) = self.loaded_for.as_deref().and_then(Self::config_path) else {
+            return Err("Abra um projeto para salvar as opções de build".to_string());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let out = format!(
+            "game_name={}\nstartup_scene={}\ntarget={}\nwidth={}\nheight={}\noutput_dir={}\n",
+            self.game_name,
+            self.startup_scene,
+            self.target.as_config_str(),
+            self.width,
+            self.height,
+            self.output_dir
+        );
+        fs::write(&path, out).map_err(|e| e.to_string())
+    }
+}
+
+/// Player para a plataforma: o pré-compilado ao lado do editor ou, sem ele,
+/// o pacote do player compilado pelo cargo no workspace de onde o editor veio
+fn find_player(target: BuildTarget) -> Result<PlayerSource, String> {
+    let editor_dir = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf))
+        .ok_or("Pasta do editor não encontrada")?;
+    let exe = target.exe_name(PLAYER_BINARY);
+    let mut candidates = vec![
+        editor_dir
+            .join(PLAYERS_DIR)
+            .join(target.triple())
+            .join(&exe),
+    ];
+    if target == BuildTarget::host() {
+        candidates.push(editor_dir.join(&exe));
+    }
+    if let Some(path) = candidates.iter().find(|path| path.is_file()) {
+        return Ok(PlayerSource::Prebuilt(path.clone()));
+    }
+    let workspace = Path::new(env!("CARGO_MANIFEST_DIR"));
+    if workspace.join(PLAYER_PACKAGE).join("Cargo.toml").is_file() {
+        return Ok(PlayerSource::Cargo(workspace.to_path_buf()));
+    }
+    Err(format!(
+        "Player para {} não encontrado em {} nem o código do player para compilar",
+        target.label(),
+        candidates[0].display()
+    ))
+}
+
+/// Compila o player para a plataforma e devolve o executável gerado
+fn cargo_build_player(workspace: &Path, target: BuildTarget) -> Result<PathBuf, String> {
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let output = Command::new(cargo)
+        .current_dir(workspace)
+        .args(["build", "--release", "-p", PLAYER_PACKAGE, "--target"])
+        .arg(target.triple())
+        .output()
+        .map_err(|e| format!("Falha ao rodar o cargo: {e}"))?;
+    if !output.status.success() {
+        // O fim do stderr tem o erro; falta de target pede `rustup target add`
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let lines: Vec<&str> = stderr.lines().collect();
+        let tail = lines[lines.len().saturating_sub(8)..].join("\n");
+        return Err(format!(
+            "Falha ao compilar o player para {} (rustup target add {}?):\n{tail}",
+            target.label(),
+            target.triple()
+        ));
+    }
+    let target_dir = std::env::var_os("CARGO_TARGET_DIR")
+        .map(|dir| workspace.join(dir))
+        .unwrap_or_else(|| workspace.join("target"));
+    let exe = target_dir
+        .join(target.triple())
+        .join("release")
+        .join(target.exe_name(PLAYER_BINARY));
+    if exe.is_file() {
+        Ok(exe)
+    } else {
+        Err(format!(
+            "Player compilado não encontrado em {}",
+            exe.display()
+        ))
+    }
+}
+
+/// A saída não pode conter o projeto (apagaria arquivos dele) nem ficar em Assets
+fn check_output_dir(output: &Path, root: &Path, assets: &Path) -> Result<(), String> {
+    let canonical = |path: &Path| fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let output = canonical(output);
+    if canonical(root).starts_with(&output) {
+        return Err(format!(
+            "A saída {} contém o projeto: escolha uma subpasta, como Builds",
+            output.display()
+        ));
+    }
+    if output.starts_with(canonical(assets)) {
+        return Err(format!(
+            "A saída {} fica dentro de Assets",
+            output.display()
+        ));
+    }
+    Ok(())
+}
+
+/// Apaga só os arquivos que o build anterior gravou nesta saída
+fn clean_previous_build(output: &Path) -> Result<(), String> {
+    let Ok(raw) = fs::read_to_string(output.join(BUILD_MANIFEST)) else {
+        return Ok(());
+    };
+    for line in raw.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        // Só caminhos relativos simples: um manifesto editado não sai da pasta
+        let relative = Path::new(line);
+        if !relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+        {
+            continue;
+        }
+        let path = output.join(relative);
+        if path.is_file() {
+            fs::remove_file(&path)
+                .map_err(|e| format!("Falha ao apagar {}: {e}", path.display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Cenas que podem abrir o jogo: o .deng do projeto (exportado da cena
+/// aberta) e os .dscene salvos na raiz
+fn project_scenes(root: &Path, project: &Path) -> Vec<String> {
+    let mut scenes: Vec<String> = fs::read_dir(root)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| e.eq_ignore_ascii_case(SCENE_EXTENSION))
+        })
+        .filter_map(|path| path.file_name().map(|n| n.to_string_lossy().to_string()))
+        .collect();
+    scenes.sort_by_key(|s| s.to_ascii_lowercase());
+    if let Some(name) = project.file_name() {
+        scenes.insert(0, name.to_string_lossy().to_string());
+    }
+    scenes
+}
+
+/// Nome do executável: sem separadores nem caracteres que algum sistema recusa
+fn sanitize_file_stem(name: &str) -> String {
+    let stem: String = name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '-' | '_' | ' ') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if stem.trim().is_empty() {
+        "Game".to_string()
+    } else {
+        stem.trim().to_string()
+    }
+}
+
+/// Mostra a pasta do build no gerenciador de arquivos do sistema
+fn open_folder(path: &Path) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    let result = Command::new("explorer").arg(path).spawn();
+    #[cfg(target_os = "macos")]
+    let result = Command::new("open").arg(path).spawn();
+    #[cfg(all(unix, not(target_os = "macos")))]
+    let result = Command::new("xdg-open").arg(path).spawn();
+    result
+        .map(|_| ())
+        .map_err(|err| format!("Falha ao abrir {}: {err}", path.display()))
+}
+
+fn run_build(job: BuildJob, tx: &Sender<BuildEvent>) -> Result<PathBuf, String> {
+    let step = |msg: String| {
+        let _ = tx.send(BuildEvent::Step(msg));
+    };
+
+    // 1. Player: o pré-compilado ou um cargo build para a plataforma
+    let player = match &job.player {
+        PlayerSource::Prebuilt(path) => path.clone(),
+        PlayerSource::Cargo(workspace) => {
+            step(format!(
+                "Compilando o player para {} ({})...",
+                job.target.label(),
+                job.target.triple()
+            ));
+            cargo_build_player(workspace, job.target)?
+        }
+    };
+
+    // 2. Assets em bundles, depois de tirar o que o build anterior gravou
+    clean_previous_build(&job.output)?;
+    let data = job.output.join(DATA_DIR);
+    fs::create_dir_all(&data).map_err(|e| e.to_string())?;
+    step("Empacotando assets...".to_string());
+    let report = pack_bundles(&job.assets, &data, MAX_BUNDLE_BYTES)?;
+    step(format!(
+        "{} assets em {} bundle(s) ({:.1} MB)",
+        report.files,
+        report.bundles.len(),
+        report.bundle_bytes as f64 / (1024.0 * 1024.0)
+    ));
+
+    // 3. Cena inicial, player e game.cfg
+    let scene = data.join(&job.scene_name);
+    fs::write(&scene, &job.scene_text)
+        .map_err(|e| format!("Falha ao gravar a cena {}: {e}", scene.display()))?;
+    step(format!("Copiando o player ({})...", job.target.label()));
+    let exe = job
+        .output
+        .join(job.target.exe_name(&sanitize_file_stem(&job.game_name)));
+    fs::copy(&player, &exe).map_err(|e| format!("Falha ao copiar o player: {e}"))?;
+    let config = format!(
+        "title={}\nstartup_scene={}\nwidth={}\nheight={}\n",
+        job.game_name.trim(),
+        job.scene_name,
+        job.width,
+        job.height
+    );
+    let game_cfg = job.output.join("game.cfg");
+    fs::write(&game_cfg, config).map_err(|e| e.to_string())?;
+
+    let mut written = report.bundles;
+    written.extend([scene, exe, game_cfg]);
+    let manifest: String = written
+        .iter()
+        .filter_map(|path| path.strip_prefix(&job.output).ok())
+        .map(|path| format!("{}\n", path.to_string_lossy().replace('\\', "/")))
+        .collect();
+    fs::write(job.output.join(BUILD_MANIFEST), manifest).map_err(|e| e.to_string())?;
+    Ok(job.output)
+}
+
+impl EditorApp {
+    /// Inicia o build em segundo plano com as opções da janela
+    fn start_build(&mut self) {
+        let Some(project) = self.current_project.clone() else {
+            return;
+        };
+        let root = project.parent().unwrap_or(Path::new(".")).to_path_buf();
+        let player = match find_player(self.build.target) {
+            Ok(player) => player,
+            Err(e) => {
+                self.build.status = Some(e);
+                return;
+            }
+        };
+        let assets = self.hot_reload_dir();
+        // O .deng do projeto sai da cena aberta; um .dscene vai como está
+        let startup = root.join(&self.build.startup_scene);
+        let is_project = project.file_name() == startup.file_name();
+        let scene = if is_project {
+            self.scene_text(None).map(|(text, _)| text)
+        } else {
+            fs::read_to_string(&startup)
+                .map_err(|e| format!("Cena inicial não encontrada: {} ({e})", startup.display()))
+        };
+        let scene_text = match scene {
+            Ok(text) => text,
+            Err(e) => {
+                self.build.status = Some(e);
+                return;
+            }
+        };
+        let scene_name = format!(
+            "{}.{SCENE_EXTENSION}",
+            startup
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_else(|| "Scene".to_string())
+        );
+        let build = &mut self.build;
+        if build.output_dir.trim().is_empty() {
+            build.status = Some("Escolha a pasta de saída".to_string());
+            return;
+        }
+        let output = root.join(build.output_dir.trim());
+        if let Err(e) = fs::create_dir_all(&output) {
+            build.status = Some(format!("Falha ao criar {}: {e}", output.display()));
+            return;
+        }
+        if let Err(e) = check_output_dir(&output, &root, &assets) {
+            build.status = Some(e);
+            return;
+        }
+        let job = BuildJob {
+            player,
+            assets,
+            scene_name,
+            scene_text,
+            output,
+            game_name: build.game_name.clone(),
+            target: build.target,
+            width: build.width,
+            height: build.height,
+        };
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let result = run_build(job, &tx);
+            let _ = tx.send(BuildEvent::Done(result));
+        });
+        build.job = Some(rx);
+        build.steps.clear();
+        build.last_output = None;
+        build.status = None;
+        self.log.push(
+            LogLevel::Info,
+            "Build",
+            format!("Build para {} iniciado", build.target.label()),
+        );
+    }
+
+    fn poll_build(&mut self, ctx: &egui::Context) {
+        let Some(rx) = &self.build.job else {
+            return;
+        };
+        loop {
+            match rx.try_recv() {
+                Ok(BuildEvent::Step(msg)) => {
+                    self.log.push(LogLevel::Info, "Build", msg.clone());
+                    self.build.steps.push(msg);
+                }
+                Ok(BuildEvent::Done(result)) => {
+                    self.build.job = None;
+                    match result {
+                        Ok(out) => {
+                            self.log.push(
+                                LogLevel::Info,
+                                "Build",
+                                format!("Build pronto em {}", out.display()),
+                            );
+                            self.build.status = Some("Build concluído".to_string());
+                            self.build.last_output = Some(out);
+                        }
+                        Err(err) => {
+                            self.log.push(LogLevel::Error, "Build", err.clone());
+                            self.log_enabled = true;
+                            self.build.status = Some(err);
+                        }
+                    }
+                    return;
+                }
+                Err(TryRecvError::Empty) => {
+                    ctx.request_repaint();
+                    return;
+                }
+                Err(TryRecvError::Disconnected) => {
+                    self.build.job = None;
+                    return;
+                }
+            }
+        }
+    }
+
+    pub(crate) fn draw_build_window(&mut self, ctx: &egui::Context) {
+        engine_core::profile_function!();
+        self.poll_build(ctx);
+        if !self.build.open {
+            return;
+        }
+        if self.build.loaded_for != self.current_project {
+            let project = self.current_project.clone();
+            self.build.load(project.as_deref());
+        }
+        let root = self
+            .current_project
+            .as_deref()
+            .and_then(Path::parent)
+            .map(Path::to_path_buf);
+        let scenes = match (&root, &self.current_project) {
+            (Some(root), Some(project)) => project_scenes(root, project),
+            _ => Vec::new(),
+        };
+
+        let mut open = self.build.open;
+        let mut changed = false;
+        let mut start = false;
+        egui::Window::new("📦 Build")
+            .open(&mut open)
+            .default_size([360.0, 260.0])
+            .show(ctx, |ui| {
+                let Some(root) = &root else {
+                    ui.label(egui::RichText::new("Abra um projeto para gerar o build").weak());
+                    return;
+                };
+                let build = &mut self.build;
+                let busy = build.job.is_some();
+                ui.add_enabled_ui(!busy, |ui| {
+                    egui::Grid::new("build_grid")
+                        .num_columns(2)
+                        .spacing([8.0, 6.0])
+                        .show(ui, |ui| {
+                            ui.label("Nome do jogo");
+                            changed |= ui.text_edit_singleline(&mut build.game_name).lost_focus();
+                            ui.end_row();
+
+                            ui.label("Cena inicial");
+                            egui::ComboBox::from_id_salt("build_startup_scene")
+                                .selected_text(build.startup_scene.as_str())
+                                .show_ui(ui, |ui| {
+                                    for scene in &scenes {
+                                        changed |= ui
+                                            .selectable_value(
+                                                &mut build.startup_scene,
+                                                scene.clone(),
+                                                scene,
+                                            )
+                                            .changed();
+                                    }
+                                });
+                            ui.end_row();
+
+                            ui.label("Plataforma");
+                            egui::ComboBox::from_id_salt("build_target")
+                                .selected_text(build.target.label())
+                                .show_ui(ui, |ui| {
+                                    for target in BuildTarget::ALL {
+                                        changed |= ui
+                                            .selectable_value(
+                                                &mut build.target,
+                                                target,
+                                                target.label(),
+                                            )
+                                            .changed();
+                                    }
+                                })
+                                .response
+                                .on_hover_text(format!(
+                                    "O player de outras plataformas vem pronto em \
+                                     {PLAYERS_DIR}/<target>/ ao lado do editor; sem ele, \
+                                     o build compila o {PLAYER_PACKAGE} com o cargo"
+                                ));
+                            ui.end_row();
+
+                            ui.label("Janela");
+                            ui.horizontal(|ui| {
+                                changed |= ui
+                                    .add(egui::DragValue::new(&mut build.width).range(320..=7680))
+                                    .changed();
+                                ui.label("x");
+                                changed |= ui
+                                    .add(egui::DragValue::new(&mut build.height).range(240..=4320))
+                                    .changed();
+                            });
+                            ui.end_row();
+
+                            ui.label("Saída");
+                            ui.horizontal(|ui| {
+                                changed |= ui
+                                    .add(
+                                        egui::TextEdit::singleline(&mut build.output_dir)
+                                            .desired_width(200.0),
+                                    )
+                                    .lost_focus();
+                                if ui.button("...").clicked() {
+                                    if let Some(dir) =
+                                        rfd::FileDialog::new().set_directory(root).pick_folder()
+                                    {
+                                        build.output_dir = dir.display().to_string();
+                                        changed = true;
+                                    }
+                                }
+                            });
+                            ui.end_row();
+                        });
+                });
+                ui.separator();
+                ui.horizontal(|ui| {
+                    start = ui.add_enabled(!busy, egui::Button::new("Build")).clicked();
+                    if busy {
+                        ui.add(egui::Spinner::new().size(14.0));
+                    }
+                    if let Some(out) = build.last_output.clone() {
+                        if ui.button("Abrir pasta").clicked() {
+                            if let Err(err) = open_folder(&out) {
+                                build.status = Some(err);
+                            }
+                        }
+                    }
+                });
+                for step in &build.steps {
+                    ui.label(egui::RichText::new(step).small().weak());
+                }
+                if let Some(status) = &build.status {
+                    ui.label(egui::RichText::new(status).small());
+                }
+            });
+        self.build.open = open;
+
+        if changed {
+            if let Err(err) = self.build.save() {
+                self.build.status = Some(err);
+            }
+        }
+        if start {
+            self.start_build();
+        }
+    }
+}
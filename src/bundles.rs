@@ -0,0 +1,61 @@
+use super::*;
+use crate::log_panel::LogLevel;
+use engine_render::{mount_archive, pack_bundles};
+
+/// Tamanho máximo de cada bundle; arquivos maiores ficam sozinhos num bundle
+pub(crate) const MAX_BUNDLE_BYTES: u64 = 64 * 1024 * 1024;
+
+impl EditorApp {
+    /// Empacota a pasta Assets do projeto em bundles comprimidos para o jogo exportado
+    pub(crate) fn pack_asset_bundles_dialog(&mut self) {
+        let Some(out_dir) = rfd::FileDialog::new().pick_folder() else {
+            return;
+        };
+        let assets = self.hot_reload_dir();
+        match pack_bundles(&assets, &out_dir, MAX_BUNDLE_BYTES) {
+            Ok(report) => self.log.push(
+                LogLevel::Info,
+                "Bundles",
+                format!(
+                    "{} assets em {} bundle(s): {:.1} MB -> {:.1} MB ({})",
+                    report.files,
+                    report.bundles.len(),
+                    report.source_bytes as f64 / (1024.0 * 1024.0),
+                    report.bundle_bytes as f64 / (1024.0 * 1024.0),
+                    out_dir.display()
+                ),
+            ),
+            Err(e) => {
+                self.log.push(LogLevel::Error, "Bundles", e);
+                self.log_enabled = true;
+            }
+        }
+    }
+
+    /// Monta um pacote de conteúdo (.zip, .pak, .dpak) somente leitura em
+    /// `Assets/<nome do pacote>`, onde os assets dele passam a ser carregados
+    pub(crate) fn mount_archive_dialog(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Pacotes", &["zip", "pak", "dpak"])
+            .pick_file()
+        else {
+            return;
+        };
+        let name = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let point = format!("Assets/{name}");
+        match mount_archive(&point, &path) {
+            Ok(files) => self.log.push(
+                LogLevel::Info,
+                "Bundles",
+                format!("{} montado em {point} ({files} arquivos)", path.display()),
+            ),
+            Err(e) => {
+                self.log.push(LogLevel::Error, "Bundles", e);
+                self.log_enabled = true;
+            }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use super::*;
+use crate::viewport::{CAMERA_BOOKMARK_SLOTS, CameraPose};
+use glam::Vec3;
+
+type Bookmarks = [Option<CameraPose>; CAMERA_BOOKMARK_SLOTS];
+
+/// Marcadores de câmera da cena aberta, salvos em
+/// `ProjectSettings/<cena>.bookmarks` (uma linha `n=yaw pitch dist x y z proj` por marcador)
+#[derive(Default)]
+pub(crate) struct CameraBookmarks {
+    // Cena (.deng) cujos marcadores estão na viewport
+    loaded_for: Option<PathBuf>,
+}
+
+impl CameraBookmarks {
+    fn config_path(scene: &Path) -> Option<PathBuf> {
+        let stem = scene.file_stem()?.to_str()?;
+        scene.parent().map(|root| {
+            root.join("ProjectSettings")
+                .join(format!("{stem}.bookmarks"))
+        })
+    }
+
+    fn load(scene: &Path) -> Bookmarks {
+        let mut bookmarks = [None; CAMERA_BOOKMARK_SLOTS];
+        let Some(raw) = Self::config_path(scene).and_then(|path| fs::read_to_string(path).ok())
+        else {
+            return bookmarks;
+        };
+        for (key, value) in raw.lines().filter_map(|line| line.split_once('=')) {
+            let Some(slot) = key
+                .trim()
+                .parse::<usize>()
+                .ok()
+                .and_then(|n| n.checked_sub(1))
+                .filter(|slot| *slot < CAMERA_BOOKMARK_SLOTS)
+            else {
+                continue;
+            };
+            let fields: Vec<&str> = value.split_whitespace().collect();
+            let numbers: Vec<f32> = fields
+                .iter()
+                .take(6)
+                .filter_map(|field| field.parse().ok())
+                .collect();
+            if numbers.len() != 6 {
+                continue;
+            }
+            bookmarks[slot] = Some(CameraPose {
+                yaw: numbers[0],
+                pitch: numbers[1],
+                distance: numbers[2],
+                target: Vec3::new(numbers[3], numbers[4], numbers[5]),
+                ortho: fields.get(6) == Some(&"ortho"),
+            });
+        }
+        bookmarks
+    }
+
+    fn save(scene: &Path, bookmarks: &Bookmarks) -> Result<(), String> {
+        let Some(path) = Self::config_path(scene) else {
+            return Err(format!("Cena inválida: {}", scene.display()));
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let mut out = String::new();
+        for (slot, pose) in bookmarks.iter().enumerate() {
+            let Some(pose) = pose else {
+                continue;
+            };
+            out.push_str(&format!(
+                "{}={} {} {} {} {} {} {}\n",
+                slot + 1,
+                pose.yaw,
+                pose.pitch,
+                pose.distance,
+                pose.target.x,
+                pose.target.y,
+                pose.target.z,
+                if pose.ortho { "ortho" } else { "persp" }
+            ));
+        }
+        fs::write(&path, out).map_err(|e| e.to_string())
+    }
+}
+
+impl EditorApp {
+    /// Troca os marcadores da viewport ao abrir outra cena e grava os que
+    /// foram salvos com Ctrl+Shift+1..9
+    pub(crate) fn sync_camera_bookmarks(&mut self) {
+        if self.camera_bookmarks.loaded_for != self.current_project {
+            self.camera_bookmarks.loaded_for = self.current_project.clone();
+            let bookmarks = self
+                .current_project
+                .as_deref()
+                .map(CameraBookmarks::load)
+                .unwrap_or([None; CAMERA_BOOKMARK_SLOTS]);
+            self.viewport.set_camera_bookmarks(bookmarks);
+        }
+        let Some(bookmarks) = self.viewport.take_changed_camera_bookmarks() else {
+            return;
+        };
+        // Sem cena aberta os marcadores valem só até fechar o editor
+        let Some(scene) = self.camera_bookmarks.loaded_for.clone() else {
+            return;
+        };
+        if let Err(err) = CameraBookmarks::save(&scene, &bookmarks) {
+            self.log.push(
+                LogLevel::Error,
+                "Câmera",
+                format!("Falha ao salvar marcadores: {err}"),
+            );
+            self.log_enabled = true;
+        }
+    }
+}
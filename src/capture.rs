@@ -0,0 +1,190 @@
+use super::*;
+use crate::log_panel::LogLevel;
+use engine_render::{
+    CaptureFormat, CaptureRequest, MAX_CAPTURE_SIZE, VideoFormat, timestamped_capture_path,
+};
+
+fn tr(language: EngineLanguage, key: &'static str) -> &'static str {
+    i18n::text(language, "capture", key)
+}
+
+/// Opções do menu de captura da viewport
+pub(crate) struct CaptureSettings {
+    format: CaptureFormat,
+    // Resolução livre do item "Personalizada"
+    custom_size: [u32; 2],
+    // Formato das gravações do botão ao lado do Play
+    video_format: VideoFormat,
+}
+
+impl Default for CaptureSettings {
+    fn default() -> Self {
+        Self {
+            format: CaptureFormat::Png,
+            custom_size: [3840, 2160],
+            video_format: VideoFormat::Mp4,
+        }
+    }
+}
+
+impl EditorApp {
+    /// Raiz do projeto aberto (ou o diretório atual); capturas vão para
+    /// `Screenshots` e gravações para `Recordings`
+    fn capture_root(&self) -> PathBuf {
+        self.current_project
+            .as_deref()
+            .and_then(Path::parent)
+            .unwrap_or_else(|| Path::new("."))
+            .to_path_buf()
+    }
+
+    /// Pede a captura da cena em `size` pixels; erros vão para o Log
+    fn request_viewport_capture(&mut self, path: PathBuf, size: [u32; 2]) {
+        let Some(gpu) = &self.viewport_gpu else {
+            self.log.push(
+                LogLevel::Error,
+                "Captura",
+                "Captura indisponível sem a viewport na GPU".to_string(),
+            );
+            return;
+        };
+        let result = CaptureRequest::new(path, size[0], size[1])
+            .and_then(|request| gpu.request_capture(request));
+        if let Err(e) = result {
+            self.log.push(LogLevel::Error, "Captura", e);
+            self.log_enabled = true;
+        }
+    }
+
+    /// Itens do menu Capturar: escala da viewport ou resolução personalizada
+    pub(crate) fn capture_menu(&mut self, ui: &mut egui::Ui) {
+        let busy = self
+            .viewport_gpu
+            .as_ref()
+            .is_some_and(|gpu| gpu.capture_busy());
+        let viewport = self.viewport.pixel_size();
+        ui.horizontal(|ui| {
+            for format in CaptureFormat::ALL {
+                ui.radio_value(&mut self.capture.format, format, format.label());
+            }
+        });
+        ui.separator();
+
+        let mut size = None;
+        ui.add_enabled_ui(!busy && viewport[0] > 0 && viewport[1] > 0, |ui| {
+            for scale in [1, 2, 4] {
+                let label = format!("{scale}x ({}x{})", viewport[0] * scale, viewport[1] * scale);
+                if ui.button(label).clicked() {
+                    size = Some([viewport[0] * scale, viewport[1] * scale]);
+                }
+            }
+        });
+        ui.separator();
+        ui.horizontal(|ui| {
+            let custom = &mut self.capture.custom_size;
+            ui.add(egui::DragValue::new(&mut custom[0]).range(1..=MAX_CAPTURE_SIZE));
+            ui.label("x");
+            ui.add(egui::DragValue::new(&mut custom[1]).range(1..=MAX_CAPTURE_SIZE));
+            let label = tr(self.language, "capture");
+            if ui.add_enabled(!busy, egui::Button::new(label)).clicked() {
+                size = Some(*custom);
+            }
+        });
+        if busy {
+            ui.label(tr(self.language, "capturing"));
+        }
+
+        if let Some(size) = size {
+            let path = timestamped_capture_path(
+                &self.capture_root().join("Screenshots"),
+                "screenshot",
+                self.capture.format.extension(),
+            );
+            self.request_viewport_capture(path, size);
+            ui.close();
+        }
+    }
+
+    /// Botão de gravação ao lado do Play: inicia/para o vídeo da viewport no
+    /// tamanho atual dela
+    pub(crate) fn toggle_recording(&mut self) {
+        let Some(gpu) = &self.viewport_gpu else {
+            self.log.push(
+                LogLevel::Error,
+                "Captura",
+                "Gravação indisponível sem a viewport na GPU".to_string(),
+            );
+            return;
+        };
+        if gpu.is_recording() {
+            gpu.stop_recording();
+            return;
+        }
+        let format = self.capture.video_format;
+        let path = timestamped_capture_path(
+            &self.capture_root().join("Recordings"),
+            "recording",
+            format.extension(),
+        );
+        let size = self.viewport.pixel_size();
+        match gpu.start_recording(path.clone(), size) {
+            Ok(()) => self.log.push(
+                LogLevel::Info,
+                "Captura",
+                format!("Gravando {}", path.display()),
+            ),
+            Err(e) => {
+                self.log.push(LogLevel::Error, "Captura", e);
+                self.log_enabled = true;
+            }
+        }
+    }
+
+    /// Menu de contexto do botão de gravação
+    pub(crate) fn recording_menu(&mut self, ui: &mut egui::Ui) {
+        ui.label(tr(self.language, "video_format"));
+        for format in VideoFormat::ALL {
+            if ui
+                .radio_value(&mut self.capture.video_format, format, format.label())
+                .clicked()
+            {
+                ui.close();
+            }
+        }
+    }
+
+    /// Encaminha as capturas pedidas pelos scripts e registra as concluídas no Log
+    pub(crate) fn sync_captures(&mut self) {
+        let viewport = self.viewport.pixel_size();
+        for request in self.scripts.take_screenshot_requests() {
+            // Caminhos relativos partem da raiz do projeto
+            let path = if request.path.is_absolute() {
+                request.path
+            } else {
+                self.capture_root().join(&request.path)
+            };
+            let size = [
+                request.width.unwrap_or(viewport[0]),
+                request.height.unwrap_or(viewport[1]),
+            ];
+            self.request_viewport_capture(path, size);
+        }
+
+        let Some(gpu) = &self.viewport_gpu else {
+            return;
+        };
+        for result in gpu.take_capture_results() {
+            match result {
+                Ok(path) => self.log.push(
+                    LogLevel::Info,
+                    "Captura",
+                    format!("Captura salva: {}", path.display()),
+                ),
+                Err(e) => {
+                    self.log.push(LogLevel::Error, "Captura", e);
+                    self.log_enabled = true;
+                }
+            }
+        }
+    }
+}
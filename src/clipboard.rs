@@ -0,0 +1,191 @@
+use super::*;
+use crate::hierarchy::ClipboardRequest;
+use crate::inspector::ObjectComponents;
+use crate::selection::Selection;
+use crate::viewport::SceneObjectCopy;
+
+/// Deslocamento de cada colagem, para a cópia não cobrir o original
+const PASTE_OFFSET: [f32; 3] = [0.5, 0.0, 0.5];
+
+/// Um objeto copiado com o que cada painel guarda dele; o original pode ser
+/// deletado antes da colagem
+#[derive(Clone)]
+struct CopiedObject {
+    // Nome sem o prefixo do pai
+    label: String,
+    scene: Option<SceneObjectCopy>,
+    components: ObjectComponents,
+    color: Option<egui::Color32>,
+    visible: bool,
+}
+
+/// Objeto de topo copiado com seus filhos
+#[derive(Clone)]
+struct CopiedTree {
+    root: CopiedObject,
+    children: Vec<CopiedObject>,
+}
+
+/// Objetos copiados com Ctrl+C, colados com Ctrl+V
+#[derive(Default)]
+pub(crate) struct EntityClipboard {
+    trees: Vec<CopiedTree>,
+    // Colagens desde a cópia; cada uma desloca mais um passo
+    pastes: u32,
+}
+
+impl EntityClipboard {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.trees.is_empty()
+    }
+}
+
+impl EditorApp {
+    fn copy_object(&self, object_name: &str, label: &str) -> CopiedObject {
+        let (color, visible) = self.hierarchy.object_style(object_name);
+        CopiedObject {
+            label: label.to_string(),
+            scene: self.viewport.copy_scene_object(object_name),
+            components: self.inspector.object_components(object_name),
+            color,
+            visible,
+        }
+    }
+
+    /// Selecionados com seus filhos; um filho cujo pai também está
+    /// selecionado vai junto com o pai
+    fn copy_selection(&self) -> Vec<CopiedTree> {
+        let selection = self.hierarchy.selection();
+        selection
+            .names()
+            .iter()
+            .filter(|name| !self.hierarchy.object_is_deleted(name))
+            .filter(|name| {
+                self.hierarchy
+                    .object_parent(name)
+                    .is_none_or(|parent| !selection.contains(parent))
+            })
+            .map(|name| {
+                let label = self
+                    .hierarchy
+                    .object_parent(name)
+                    .and_then(|parent| name.strip_prefix(&format!("{parent}/")))
+                    .unwrap_or(name);
+                let prefix = format!("{name}/");
+                let children = self
+                    .hierarchy
+                    .object_children(name)
+                    .iter()
+                    .map(|child| {
+                        let label = child.strip_prefix(&prefix).unwrap_or(child);
+                        self.copy_object(child, label)
+                    })
+                    .collect();
+                CopiedTree {
+                    root: self.copy_object(name, label),
+                    children,
+                }
+            })
+            .collect()
+    }
+
+    /// Cria os objetos copiados, deslocados `steps` passos, e os seleciona
+    fn paste_trees(&mut self, trees: &[CopiedTree], steps: u32) {
+        let mut scene_objects = Vec::new();
+        let mut pasted = Selection::default();
+        for tree in trees {
+            let child_labels: Vec<String> = tree.children.iter().map(|c| c.label.clone()).collect();
+            let (root_name, child_names) = self
+                .hierarchy
+                .add_pasted_object(&tree.root.label, &child_labels);
+            let objects = std::iter::once((&root_name, &tree.root))
+                .chain(child_names.iter().zip(&tree.children));
+            for (name, object) in objects {
+                self.inspector
+                    .set_object_components(name, object.components.clone());
+                self.hierarchy
+                    .set_object_style(name, object.color, object.visible);
+                if let Some(scene) = &object.scene {
+                    scene_objects.push((name.clone(), scene.clone()));
+                }
+            }
+            pasted.add(&root_name);
+        }
+        let offset = PASTE_OFFSET.map(|v| v * steps as f32);
+        self.viewport.paste_scene_objects(scene_objects, offset);
+        self.hierarchy.set_selection(&pasted);
+    }
+
+    /// Copia os selecionados; os nomes também vão para a área de
+    /// transferência do sistema
+    pub(crate) fn copy_selected_objects(&mut self, ctx: &egui::Context) {
+        let trees = self.copy_selection();
+        if trees.is_empty() {
+            return;
+        }
+        let names: Vec<&str> = trees.iter().map(|t| t.root.label.as_str()).collect();
+        ctx.copy_text(names.join("\n"));
+        self.entity_clipboard = EntityClipboard { trees, pastes: 0 };
+    }
+
+    pub(crate) fn paste_objects(&mut self) {
+        if self.entity_clipboard.is_empty() {
+            return;
+        }
+        self.entity_clipboard.pastes += 1;
+        let trees = self.entity_clipboard.trees.clone();
+        self.paste_trees(&trees, self.entity_clipboard.pastes);
+    }
+
+    /// Cópia dos selecionados ao lado deles, sem mexer na área de transferência
+    pub(crate) fn duplicate_selected_objects(&mut self) {
+        let trees = self.copy_selection();
+        self.paste_trees(&trees, 1);
+    }
+
+    pub(crate) fn handle_clipboard_request(
+        &mut self,
+        ctx: &egui::Context,
+        request: ClipboardRequest,
+    ) {
+        match request {
+            ClipboardRequest::Copy => self.copy_selected_objects(ctx),
+            ClipboardRequest::Paste => self.paste_objects(),
+            ClipboardRequest::Duplicate => self.duplicate_selected_objects(),
+        }
+    }
+
+    /// Ctrl+C / Ctrl+V / Ctrl+D fora de campos de texto. O egui entrega
+    /// Ctrl+C e Ctrl+V como eventos Copy/Paste em vez de teclas
+    pub(crate) fn handle_clipboard_shortcuts(&mut self, ctx: &egui::Context) {
+        if ctx.wants_keyboard_input() || self.viewport.in_play_mode() {
+            return;
+        }
+        let (copy, paste) = ctx.input(|i| {
+            i.events
+                .iter()
+                .fold((false, false), |(copy, paste), event| match event {
+                    egui::Event::Copy => (true, paste),
+                    egui::Event::Paste(_) => (copy, true),
+                    egui::Event::Key {
+                        key,
+                        pressed: true,
+                        modifiers,
+                        ..
+                    } if modifiers.command => (copy || *key == Key::C, paste || *key == Key::V),
+                    _ => (copy, paste),
+                })
+        });
+        let duplicate = ctx.input_mut(|i| i.consume_key(Modifiers::COMMAND, Key::D));
+        let request = if duplicate {
+            ClipboardRequest::Duplicate
+        } else if paste {
+            ClipboardRequest::Paste
+        } else if copy {
+            ClipboardRequest::Copy
+        } else {
+            return;
+        };
+        self.handle_clipboard_request(ctx, request);
+    }
+}
@@ -0,0 +1,610 @@
+use crate::EngineLanguage;
+use crate::i18n;
+use crate::theme;
+use eframe::egui::{self, Color32, Id, Key, Modifiers, Pos2, Rect, Sense, Stroke, Vec2};
+use engine_render::{Curve, CurveKey, TangentMode};
+use std::ops::RangeInclusive;
+
+const KEY_RADIUS: f32 = 4.5;
+const HANDLE_LENGTH: f32 = 34.0;
+const HANDLE_RADIUS: f32 = 3.5;
+const PICK_DISTANCE: f32 = 7.0;
+
+fn tr(language: EngineLanguage, key: &'static str) -> &'static str {
+    i18n::text(language, "curve_editor", key)
+}
+
+/// O que o arrasto em andamento move
+#[derive(Clone, Copy)]
+enum CurveDrag {
+    // Chaves selecionadas, ancoradas na que foi agarrada
+    Keys { anchor: usize },
+    Tangent { index: usize, out: bool },
+    Marquee { origin: Pos2, current: Pos2 },
+}
+
+/// Estado de cada editor entre frames, na memória do egui
+#[derive(Clone, Default)]
+struct CurveEditorState {
+    selected: Vec<usize>,
+    drag: Option<CurveDrag>,
+    // Faixa de valores enquadrada; não muda durante um arrasto
+    value_range: Option<(f32, f32)>,
+    snap: bool,
+    // Onde o menu de contexto foi aberto, em (tempo, valor)
+    menu_at: Option<(f32, f32)>,
+}
+
+/// Chaves copiadas, com tempos relativos à primeira; compartilhada por todos
+/// os editores para colar de uma curva em outra
+#[derive(Clone, Default)]
+struct CurveClipboard(Vec<CurveKey>);
+
+fn clipboard_id() -> Id {
+    Id::new("curve_editor_clipboard")
+}
+
+/// Passo de grade "redondo" (1, 2 ou 5 × 10ⁿ) com pelo menos `min_px` pixels
+fn grid_step(span: f32, pixels: f32, min_px: f32) -> f32 {
+    let raw = span * min_px / pixels.max(1.0);
+    let magnitude = 10_f32.powf(raw.max(1e-6).log10().floor());
+    [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|m| m * magnitude)
+        .find(|step| *step >= raw)
+        .unwrap_or(10.0 * magnitude)
+}
+
+/// Editor de curva Bezier reutilizável: a linha do tempo, as curvas de vida
+/// das partículas e o nó Curva do Fios usam o mesmo widget.
+///
+/// Clique duplo adiciona uma chave, arrastar move as selecionadas (ou abre uma
+/// seleção retangular no fundo), as alças das chaves selecionadas ajustam as
+/// tangentes e o botão direito muda o tipo de tangente, apaga, copia e cola.
+/// Com o editor em foco, Delete apaga e Ctrl+C / Ctrl+V copiam e colam
+pub(crate) struct CurveEditor<'a> {
+    id_salt: Id,
+    curve: &'a mut Curve,
+    language: EngineLanguage,
+    height: f32,
+    time_range: RangeInclusive<f32>,
+    value_range: Option<RangeInclusive<f32>>,
+    time_step: Option<f32>,
+    playhead: Option<f32>,
+}
+
+impl<'a> CurveEditor<'a> {
+    pub(crate) fn new(
+        id_salt: impl std::hash::Hash,
+        curve: &'a mut Curve,
+        language: EngineLanguage,
+    ) -> Self {
+        Self {
+            id_salt: Id::new(id_salt),
+            curve,
+            language,
+            height: 90.0,
+            time_range: 0.0..=1.0,
+            value_range: None,
+            time_step: None,
+            playhead: None,
+        }
+    }
+
+    pub(crate) fn height(mut self, height: f32) -> Self {
+        self.height = height;
+        self
+    }
+
+    pub(crate) fn time_range(mut self, range: RangeInclusive<f32>) -> Self {
+        self.time_range = range;
+        self
+    }
+
+    /// Faixa vertical fixa; sem ela o editor enquadra a curva
+    pub(crate) fn value_range(mut self, range: RangeInclusive<f32>) -> Self {
+        self.value_range = Some(range);
+        self
+    }
+
+    /// Passo do encaixe horizontal (quadros da animação, por exemplo)
+    pub(crate) fn time_step(mut self, step: f32) -> Self {
+        self.time_step = Some(step);
+        self
+    }
+
+    pub(crate) fn playhead(mut self, time: f32) -> Self {
+        self.playhead = Some(time);
+        self
+    }
+
+    /// Desenha e edita a curva; a resposta fica `changed` quando ela mudou
+    pub(crate) fn show(self, ui: &mut egui::Ui) -> egui::Response {
+        let id = ui.make_persistent_id(self.id_salt);
+        let size = egui::vec2(ui.available_width(), self.height);
+        let (rect, mut response) = ui.allocate_exact_size(size, Sense::click_and_drag());
+        let mut state: CurveEditorState = ui.data(|d| d.get_temp(id)).unwrap_or_default();
+        let language = self.language;
+        let curve = self.curve;
+        state.selected.retain(|&index| index < curve.keys().len());
+
+        let plot = rect.shrink2(egui::vec2(6.0, 8.0));
+        let (t0, t1) = (*self.time_range.start(), *self.time_range.end());
+        let t1 = if t1 - t0 < 1e-4 { t0 + 1.0 } else { t1 };
+        let (v0, v1) = match &self.value_range {
+            Some(range) => (*range.start(), *range.end()),
+            None => match (state.drag, state.value_range) {
+                (Some(_), Some(range)) => range,
+                _ => {
+                    let (lo, hi) = curve.value_range().unwrap_or((0.0, 1.0));
+                    let pad = if hi - lo < 1e-3 { 0.5 } else { (hi - lo) * 0.1 };
+                    (lo - pad, hi + pad)
+                }
+            },
+        };
+        let v1 = if v1 - v0 < 1e-4 { v0 + 1.0 } else { v1 };
+        state.value_range = Some((v0, v1));
+        let px_per_t = plot.width() / (t1 - t0);
+        let px_per_v = plot.height() / (v1 - v0);
+        let to_screen = |time: f32, value: f32| {
+            egui::pos2(
+                plot.left() + (time - t0) * px_per_t,
+                plot.bottom() - (value - v0) * px_per_v,
+            )
+        };
+        let from_screen = |pos: Pos2| {
+            (
+                t0 + (pos.x - plot.left()) / px_per_t,
+                v0 + (plot.bottom() - pos.y) / px_per_v,
+            )
+        };
+        let time_grid = grid_step(t1 - t0, plot.width(), 40.0);
+        let value_grid = grid_step(v1 - v0, plot.height(), 24.0);
+        let (ctrl, shift, alt) =
+            ui.input(|i| (i.modifiers.command, i.modifiers.shift, i.modifiers.alt));
+        let snapping = state.snap != ctrl;
+        let time_snap = self.time_step.unwrap_or(time_grid);
+        let snap = |(time, value): (f32, f32)| {
+            if snapping {
+                (
+                    (time / time_snap).round() * time_snap,
+                    (value / value_grid).round() * value_grid,
+                )
+            } else {
+                (time, value)
+            }
+        };
+        // Direção na tela de uma tangente (inclinação em valor por tempo)
+        let tangent_dir = |slope: f32| egui::vec2(px_per_t, -slope * px_per_v).normalized();
+        let handle_pos = |key: &CurveKey, out: bool| {
+            let center = to_screen(key.time, key.value);
+            if out {
+                center + tangent_dir(key.out_tangent) * HANDLE_LENGTH
+            } else {
+                center - tangent_dir(key.in_tangent) * HANDLE_LENGTH
+            }
+        };
+        let pick_key = |curve: &Curve, pos: Pos2| {
+            curve
+                .keys()
+                .iter()
+                .position(|key| to_screen(key.time, key.value).distance(pos) <= PICK_DISTANCE)
+        };
+        let pick_handle = |curve: &Curve, selected: &[usize], pos: Pos2| {
+            selected.iter().find_map(|&index| {
+                let key = &curve.keys()[index];
+                if key.mode == TangentMode::Constant {
+                    return None;
+                }
+                [false, true]
+                    .into_iter()
+                    .find(|&out| handle_pos(key, out).distance(pos) <= PICK_DISTANCE)
+                    .map(|out| (index, out))
+            })
+        };
+
+        let mut changed = false;
+        if response.clicked() || response.drag_started() || response.secondary_clicked() {
+            response.request_focus();
+        }
+
+        // Arrastos: alça, chave ou seleção retangular
+        if response.drag_started() {
+            let origin = ui
+                .input(|i| i.pointer.press_origin())
+                .unwrap_or(rect.center());
+            if let Some((index, out)) = pick_handle(curve, &state.selected, origin) {
+                state.drag = Some(CurveDrag::Tangent { index, out });
+            } else if let Some(index) = pick_key(curve, origin) {
+                if !state.selected.contains(&index) {
+                    if !shift {
+                        state.selected.clear();
+                    }
+                    state.selected.push(index);
+                }
+                state.drag = Some(CurveDrag::Keys { anchor: index });
+            } else {
+                if !shift {
+                    state.selected.clear();
+                }
+                state.drag = Some(CurveDrag::Marquee {
+                    origin,
+                    current: origin,
+                });
+            }
+        }
+        let pointer = response.interact_pointer_pos();
+        if response.dragged() {
+            match (state.drag, pointer) {
+                (Some(CurveDrag::Keys { anchor }), Some(pos)) => {
+                    let (time, value) = snap(from_screen(pos));
+                    let key = curve.keys()[anchor];
+                    let (dt, dv) = (time - key.time, value - key.value);
+                    if dt != 0.0 || dv != 0.0 {
+                        let mut order = state.selected.clone();
+                        // Quem vai na frente do movimento anda primeiro, sem
+                        // trombar nas outras selecionadas
+                        order.sort_unstable();
+                        if dt > 0.0 {
+                            order.reverse();
+                        }
+                        for index in order {
+                            let key = curve.keys()[index];
+                            curve.move_key(index, key.time + dt, key.value + dv);
+                        }
+                        changed = true;
+                    }
+                }
+                (Some(CurveDrag::Tangent { index, out }), Some(pos)) => {
+                    let key = curve.keys()[index];
+                    let center = to_screen(key.time, key.value);
+                    let delta = pos - center;
+                    let dx = if out { delta.x } else { -delta.x }.max(0.5);
+                    let dy = if out { -delta.y } else { delta.y };
+                    let slope = (dy / px_per_v) / (dx / px_per_t);
+                    let broken = alt || key.mode == TangentMode::Broken;
+                    if broken {
+                        curve.set_mode(index, TangentMode::Broken);
+                    }
+                    let (in_tangent, out_tangent) = match (broken, out) {
+                        (false, _) => (slope, slope),
+                        (true, true) => (key.in_tangent, slope),
+                        (true, false) => (slope, key.out_tangent),
+                    };
+                    curve.set_tangents(index, in_tangent, out_tangent);
+                    changed = true;
+                }
+                (Some(CurveDrag::Marquee { origin, .. }), Some(pos)) => {
+                    state.drag = Some(CurveDrag::Marquee {
+                        origin,
+                        current: pos,
+                    });
+                }
+                _ => {}
+            }
+        }
+        if response.drag_stopped() {
+            if let Some(CurveDrag::Marquee { origin, current }) = state.drag {
+                let marquee = Rect::from_two_pos(origin, current);
+                for (index, key) in curve.keys().iter().enumerate() {
+                    if marquee.contains(to_screen(key.time, key.value))
+                        && !state.selected.contains(&index)
+                    {
+                        state.selected.push(index);
+                    }
+                }
+            }
+            state.drag = None;
+        }
+
+        if response.double_clicked() {
+            if let Some(pos) = pointer {
+                if pick_key(curve, pos).is_none() {
+                    let (time, value) = snap(from_screen(pos));
+                    let index = curve.insert_key(CurveKey::new(time, value, TangentMode::Auto));
+                    state.selected = vec![index];
+                    changed = true;
+                }
+            }
+        } else if response.clicked() {
+            if let Some(pos) = pointer {
+                match pick_key(curve, pos) {
+                    Some(index) if shift => match state.selected.iter().position(|&i| i == index) {
+                        Some(at) => {
+                            state.selected.remove(at);
+                        }
+                        None => state.selected.push(index),
+                    },
+                    Some(index) => state.selected = vec![index],
+                    None if pick_handle(curve, &state.selected, pos).is_none() => {
+                        state.selected.clear()
+                    }
+                    None => {}
+                }
+            }
+        }
+        if response.secondary_clicked() {
+            if let Some(pos) = pointer {
+                if let Some(index) = pick_key(curve, pos) {
+                    if !state.selected.contains(&index) {
+                        state.selected = vec![index];
+                    }
+                }
+                state.menu_at = Some(snap(from_screen(pos)));
+            }
+        }
+
+        // Teclado com o editor em foco
+        let mut copy = false;
+        let mut paste_at = None;
+        let mut delete = false;
+        if response.has_focus() {
+            let hover_time = response
+                .hover_pos()
+                .map(|pos| snap(from_screen(pos)).0)
+                .or(self.playhead)
+                .unwrap_or(t0);
+            ui.input_mut(|i| {
+                for event in &i.events {
+                    match event {
+                        egui::Event::Copy => copy = true,
+                        egui::Event::Paste(_) => paste_at = Some(hover_time),
+                        _ => {}
+                    }
+                }
+                delete = i.consume_key(Modifiers::NONE, Key::Delete)
+                    || i.consume_key(Modifiers::NONE, Key::Backspace);
+                if i.consume_key(Modifiers::COMMAND, Key::A) {
+                    state.selected = (0..curve.keys().len()).collect();
+                }
+            });
+        }
+
+        let has_clipboard = ui.data(|d| {
+            d.get_temp::<CurveClipboard>(clipboard_id())
+                .is_some_and(|clip| !clip.0.is_empty())
+        });
+        let mut set_mode = None;
+        let mut add_at = None;
+        response.context_menu(|ui| {
+            if !state.selected.is_empty() {
+                let current = state.selected.first().map(|&i| curve.keys()[i].mode);
+                for mode in TangentMode::ALL {
+                    if ui
+                        .radio(current == Some(mode), tr(language, mode.id()))
+                        .clicked()
+                    {
+                        set_mode = Some(mode);
+                        ui.close();
+                    }
+                }
+                ui.separator();
+                if ui.button(tr(language, "copy")).clicked() {
+                    copy = true;
+                    ui.close();
+                }
+                if ui.button(tr(language, "delete")).clicked() {
+                    delete = true;
+                    ui.close();
+                }
+            } else if ui.button(tr(language, "add_key")).clicked() {
+                add_at = state.menu_at;
+                ui.close();
+            }
+            if ui
+                .add_enabled(has_clipboard, egui::Button::new(tr(language, "paste")))
+                .clicked()
+            {
+                paste_at = state.menu_at.map(|(time, _)| time);
+                ui.close();
+            }
+            ui.separator();
+            ui.checkbox(&mut state.snap, tr(language, "snap"));
+        });
+
+        if let Some(mode) = set_mode {
+            for &index in &state.selected {
+                curve.set_mode(index, mode);
+            }
+            changed = true;
+        }
+        if let Some((time, value)) = add_at {
+            let index = curve.insert_key(CurveKey::new(time, value, TangentMode::Auto));
+            state.selected = vec![index];
+            changed = true;
+        }
+        if copy && !state.selected.is_empty() {
+            let mut keys: Vec<CurveKey> = state.selected.iter().map(|&i| curve.keys()[i]).collect();
+            keys.sort_by(|a, b| a.time.total_cmp(&b.time));
+            let start = keys[0].time;
+            for key in &mut keys {
+                key.time -= start;
+            }
+            ui.data_mut(|d| d.insert_temp(clipboard_id(), CurveClipboard(keys)));
+        }
+        if let Some(at) = paste_at {
+            let keys = ui
+                .data(|d| d.get_temp::<CurveClipboard>(clipboard_id()))
+                .unwrap_or_default()
+                .0;
+            if !keys.is_empty() {
+                let tolerance = 0.5 * time_snap.min(time_grid);
+                let mut times = Vec::with_capacity(keys.len());
+                for key in keys {
+                    let time = at + key.time;
+                    // Uma chave no mesmo tempo é substituída pela colada
+                    if let Some(existing) = curve
+                        .keys()
+                        .iter()
+                        .position(|k| (k.time - time).abs() <= tolerance)
+                    {
+                        curve.remove_key(existing);
+                    }
+                    curve.insert_key(CurveKey { time, ..key });
+                    times.push(time);
+                }
+                state.selected = curve
+                    .keys()
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, key)| times.contains(&key.time))
+                    .map(|(index, _)| index)
+                    .collect();
+                changed = true;
+            }
+        }
+        if delete && !state.selected.is_empty() {
+            let mut indices = std::mem::take(&mut state.selected);
+            indices.sort_unstable();
+            for index in indices.into_iter().rev() {
+                curve.remove_key(index);
+            }
+            state.drag = None;
+            changed = true;
+        }
+
+        // Desenho
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 3.0, Color32::from_rgb(22, 22, 24));
+        let border = if response.has_focus() {
+            Color32::from_rgb(70, 110, 90)
+        } else {
+            theme::palette().border
+        };
+        painter.rect_stroke(
+            rect,
+            3.0,
+            Stroke::new(1.0, border),
+            egui::StrokeKind::Inside,
+        );
+        let grid_stroke = Stroke::new(1.0, Color32::from_gray(40));
+        let first_t = (t0 / time_grid).ceil() as i32;
+        let last_t = (t1 / time_grid).floor() as i32;
+        for step in first_t..=last_t {
+            let x = to_screen(step as f32 * time_grid, v0).x;
+            painter.vline(x, plot.y_range(), grid_stroke);
+        }
+        let first_v = (v0 / value_grid).ceil() as i32;
+        let last_v = (v1 / value_grid).floor() as i32;
+        let label_values = plot.height() >= 50.0;
+        for step in first_v..=last_v {
+            let value = step as f32 * value_grid;
+            let y = to_screen(t0, value).y;
+            let stroke = if step == 0 {
+                Stroke::new(1.0, Color32::from_gray(70))
+            } else {
+                grid_stroke
+            };
+            painter.hline(plot.x_range(), y, stroke);
+            if label_values {
+                painter.text(
+                    egui::pos2(rect.left() + 3.0, y - 1.0),
+                    egui::Align2::LEFT_BOTTOM,
+                    format!("{value:.2}"),
+                    egui::FontId::proportional(9.0),
+                    Color32::from_gray(110),
+                );
+            }
+        }
+
+        if !curve.is_empty() {
+            let samples = (plot.width() / 2.0).max(2.0) as usize;
+            let points: Vec<Pos2> = (0..=samples)
+                .filter_map(|i| {
+                    let time = t0 + (t1 - t0) * i as f32 / samples as f32;
+                    curve.evaluate(time).map(|value| to_screen(time, value))
+                })
+                .collect();
+            painter.add(egui::Shape::line(
+                points,
+                Stroke::new(1.5, theme::palette().accent),
+            ));
+        }
+        if let Some(time) = self.playhead {
+            painter.vline(
+                to_screen(time, v0).x,
+                rect.y_range(),
+                Stroke::new(1.0, Color32::from_rgb(230, 190, 80)),
+            );
+        }
+
+        for &index in &state.selected {
+            let key = &curve.keys()[index];
+            if key.mode == TangentMode::Constant {
+                continue;
+            }
+            let center = to_screen(key.time, key.value);
+            for out in [false, true] {
+                let handle = handle_pos(key, out);
+                painter.line_segment(
+                    [center, handle],
+                    Stroke::new(1.0, theme::palette().text_weak),
+                );
+                painter.circle_filled(handle, HANDLE_RADIUS, Color32::from_gray(200));
+            }
+        }
+        let hover = response.hover_pos();
+        let hovered_key = hover.and_then(|pos| pick_key(curve, pos));
+        for (index, key) in curve.keys().iter().enumerate() {
+            let center = to_screen(key.time, key.value);
+            let selected = state.selected.contains(&index);
+            let fill = if selected {
+                theme::palette().accent
+            } else if hovered_key == Some(index) {
+                Color32::WHITE
+            } else {
+                theme::palette().text
+            };
+            painter.add(egui::Shape::convex_polygon(
+                vec![
+                    center + Vec2::new(0.0, -KEY_RADIUS),
+                    center + Vec2::new(KEY_RADIUS, 0.0),
+                    center + Vec2::new(0.0, KEY_RADIUS),
+                    center + Vec2::new(-KEY_RADIUS, 0.0),
+                ],
+                fill,
+                Stroke::new(1.0, Color32::from_gray(20)),
+            ));
+        }
+        if let Some(index) = hovered_key.or(match state.drag {
+            Some(CurveDrag::Keys { anchor }) => Some(anchor),
+            _ => None,
+        }) {
+            let key = &curve.keys()[index];
+            let center = to_screen(key.time, key.value);
+            let align = if center.x > rect.center().x {
+                egui::Align2::RIGHT_BOTTOM
+            } else {
+                egui::Align2::LEFT_BOTTOM
+            };
+            let offset = if center.x > rect.center().x {
+                -8.0
+            } else {
+                8.0
+            };
+            painter.text(
+                center + egui::vec2(offset, -6.0),
+                align,
+                format!("{:.2}, {:.3}", key.time, key.value),
+                egui::FontId::monospace(10.0),
+                theme::palette().text_strong,
+            );
+        }
+        if let Some(CurveDrag::Marquee { origin, current }) = state.drag {
+            let marquee = Rect::from_two_pos(origin, current);
+            painter.rect_filled(marquee, 0.0, theme::palette().accent.gamma_multiply(0.12));
+            painter.rect_stroke(
+                marquee,
+                0.0,
+                Stroke::new(1.0, theme::palette().accent),
+                egui::StrokeKind::Inside,
+            );
+        }
+
+        ui.data_mut(|d| d.insert_temp(id, state));
+        if changed {
+            response.mark_changed();
+        }
+        response
+    }
+}
@@ -0,0 +1,843 @@
+use super::*;
+use eframe::egui::{Color32, FontId, Id, Pos2, Rect, Stroke};
+
+const TAB_BAR_HEIGHT: f32 = 24.0;
+/// Faixa entre painéis (e entre eles e a viewport) que redimensiona
+const SPLITTER_WIDTH: f32 = 5.0;
+const SIDE_MIN_WIDTH: f32 = 180.0;
+const SIDE_MAX_WIDTH: f32 = 640.0;
+const BOTTOM_MIN_HEIGHT: f32 = 140.0;
+const BOTTOM_GROUP_MIN_WIDTH: f32 = 200.0;
+/// Soltar uma aba a esta distância da borda cria uma coluna/faixa nova
+const EDGE_DROP_ZONE: f32 = 36.0;
+/// Espaço que sobra para a viewport entre as colunas laterais
+const VIEWPORT_MIN_WIDTH: f32 = 240.0;
+const VIEWPORT_MIN_HEIGHT: f32 = 160.0;
+
+/// Painéis que o layout posiciona
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum DockPanel {
+    Hierarchy,
+    Inspector,
+    Project,
+    Log,
+    Git,
+    Animator,
+    Material,
+    Timeline,
+    Rig,
+    Profiler,
+}
+
+impl DockPanel {
+    const ALL: [DockPanel; 10] = [
+        DockPanel::Hierarchy,
+        DockPanel::Inspector,
+        DockPanel::Project,
+        DockPanel::Log,
+        DockPanel::Git,
+        DockPanel::Animator,
+        DockPanel::Material,
+        DockPanel::Timeline,
+        DockPanel::Rig,
+        DockPanel::Profiler,
+    ];
+
+    fn key(self) -> &'static str {
+        match self {
+            DockPanel::Hierarchy => "hierarchy",
+            DockPanel::Inspector => "inspector",
+            DockPanel::Project => "project",
+            DockPanel::Log => "log",
+            DockPanel::Git => "git",
+            DockPanel::Animator => "animator",
+            DockPanel::Material => "material",
+            DockPanel::Timeline => "timeline",
+            DockPanel::Rig => "rig",
+            DockPanel::Profiler => "profiler",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|panel| panel.key() == key)
+    }
+
+    fn title(self, language: EngineLanguage) -> &'static str {
+        i18n::text(language, "dock", self.key())
+    }
+
+    /// Onde o painel fica num layout sem ele (arquivo antigo ou editado à mão)
+    fn default_side(self) -> DockSide {
+        match self {
+            DockPanel::Hierarchy | DockPanel::Material => DockSide::Right,
+            DockPanel::Inspector => DockSide::Left,
+            DockPanel::Project
+            | DockPanel::Log
+            | DockPanel::Git
+            | DockPanel::Animator
+            | DockPanel::Timeline
+            | DockPanel::Rig
+            | DockPanel::Profiler => DockSide::Bottom,
+        }
+    }
+}
+
+/// Borda da janela onde ficam os grupos de painéis
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum DockSide {
+    Left,
+    Right,
+    Bottom,
+}
+
+impl DockSide {
+    const ALL: [DockSide; 3] = [DockSide::Left, DockSide::Right, DockSide::Bottom];
+
+    fn key(self) -> &'static str {
+        match self {
+            DockSide::Left => "left",
+            DockSide::Right => "right",
+            DockSide::Bottom => "bottom",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|side| side.key() == key)
+    }
+}
+
+/// Painéis em abas: uma coluna nas laterais ou um trecho da faixa de baixo
+#[derive(Clone, Debug, PartialEq)]
+struct DockGroup {
+    tabs: Vec<DockPanel>,
+    active: DockPanel,
+    // Largura; o último grupo de baixo ocupa o que sobra
+    size: f32,
+}
+
+impl DockGroup {
+    fn new(tabs: Vec<DockPanel>, size: f32) -> Self {
+        Self {
+            active: tabs[0],
+            tabs,
+            size,
+        }
+    }
+
+    /// Aba visível: a ativa se estiver aberta, senão a primeira aberta
+    fn shown(&self, open: &[DockPanel]) -> Option<DockPanel> {
+        if open.contains(&self.active) {
+            return Some(self.active);
+        }
+        self.tabs.iter().copied().find(|tab| open.contains(tab))
+    }
+}
+
+/// Onde uma aba arrastada vai parar
+#[derive(Clone, Copy, PartialEq)]
+enum DockDrop {
+    /// Vira mais uma aba do grupo
+    Group(DockSide, usize),
+    /// Grupo novo encostado na borda
+    Edge(DockSide),
+}
+
+#[derive(Clone, Copy)]
+enum DockResize {
+    /// Largura de um grupo
+    Group(DockSide, usize),
+    /// Altura da faixa de baixo
+    Bottom,
+}
+
+/// Grupo visível neste frame
+struct VisibleGroup {
+    side: DockSide,
+    index: usize,
+    rect: Rect,
+    tabs: Vec<DockPanel>,
+    shown: DockPanel,
+}
+
+/// Layout dos painéis do editor: grupos de abas nas bordas esquerda, direita
+/// e de baixo, redimensionáveis e arrastáveis entre si. Salvo por projeto em
+/// `ProjectSettings/layout.cfg`; o último usado também fica nas preferências
+/// do editor, para projetos sem layout próprio
+pub(crate) struct DockLayout {
+    // Colunas de fora para dentro
+    left: Vec<DockGroup>,
+    right: Vec<DockGroup>,
+    // Da esquerda para a direita
+    bottom: Vec<DockGroup>,
+    bottom_height: f32,
+    // Espaço de cada painel visível e das bordas ocupadas, do último `show`
+    rects: Vec<(DockPanel, Rect)>,
+    margins: (f32, f32, f32),
+    dragging: Option<DockPanel>,
+    resizing: Option<DockResize>,
+    // Painéis abertos no último frame; os que abrirem ganham o foco da aba
+    open_before: Option<Vec<DockPanel>>,
+    // Projeto cujo layout está carregado
+    loaded_for: Option<PathBuf>,
+    changed: bool,
+}
+
+impl Default for DockLayout {
+    fn default() -> Self {
+        Self {
+            left: vec![DockGroup::new(vec![DockPanel::Inspector], 300.0)],
+            right: vec![DockGroup::new(
+                vec![DockPanel::Hierarchy, DockPanel::Material],
+                240.0,
+            )],
+            bottom: vec![DockGroup::new(
+                vec![
+                    DockPanel::Project,
+                    DockPanel::Log,
+                    DockPanel::Git,
+                    DockPanel::Animator,
+                    DockPanel::Timeline,
+                    DockPanel::Rig,
+                    DockPanel::Profiler,
+                ],
+                0.0,
+            )],
+            bottom_height: 260.0,
+            rects: Vec::new(),
+            margins: (0.0, 0.0, 0.0),
+            dragging: None,
+            resizing: None,
+            open_before: None,
+            loaded_for: None,
+            changed: false,
+        }
+    }
+}
+
+impl DockLayout {
+    fn config_path(project: &Path) -> Option<PathBuf> {
+        project
+            .parent()
+            .map(|root| root.join("ProjectSettings").join("layout.cfg"))
+    }
+
+    fn groups(&self, side: DockSide) -> &Vec<DockGroup> {
+        match side {
+            DockSide::Left => &self.left,
+            DockSide::Right => &self.right,
+            DockSide::Bottom => &self.bottom,
+        }
+    }
+
+    fn groups_mut(&mut self, side: DockSide) -> &mut Vec<DockGroup> {
+        match side {
+            DockSide::Left => &mut self.left,
+            DockSide::Right => &mut self.right,
+            DockSide::Bottom => &mut self.bottom,
+        }
+    }
+
+    /// Espaço do painel neste frame; `None` se está fechado ou é uma aba
+    /// escondida
+    pub(crate) fn panel_rect(&self, panel: DockPanel) -> Option<Rect> {
+        self.rects
+            .iter()
+            .find(|(p, _)| *p == panel)
+            .map(|(_, rect)| *rect)
+    }
+
+    /// Largura ocupada à esquerda e à direita e altura ocupada embaixo (com a
+    /// barra inferior), o que sobra é da viewport
+    pub(crate) fn margins(&self) -> (f32, f32, f32) {
+        self.margins
+    }
+
+    /// Mostra a aba do painel no grupo dele
+    pub(crate) fn activate(&mut self, panel: DockPanel) {
+        for side in DockSide::ALL {
+            for group in self.groups_mut(side) {
+                if group.tabs.contains(&panel) {
+                    group.active = panel;
+                }
+            }
+        }
+    }
+
+    fn move_panel(&mut self, panel: DockPanel, target: DockDrop) {
+        if let DockDrop::Group(side, index) = target {
+            if self.groups(side)[index].tabs.contains(&panel) {
+                self.groups_mut(side)[index].active = panel;
+                return;
+            }
+        }
+        // Grupos vazios só saem depois, para o índice do alvo continuar valendo
+        for side in DockSide::ALL {
+            for group in self.groups_mut(side) {
+                group.tabs.retain(|tab| *tab != panel);
+                if group.active == panel {
+                    if let Some(&first) = group.tabs.first() {
+                        group.active = first;
+                    }
+                }
+            }
+        }
+        match target {
+            DockDrop::Group(side, index) => {
+                let group = &mut self.groups_mut(side)[index];
+                group.tabs.push(panel);
+                group.active = panel;
+            }
+            DockDrop::Edge(DockSide::Bottom) => {
+                // O grupo que ocupava o resto passa a ter largura própria
+                if let Some(last) = self.bottom.last_mut() {
+                    last.size = last.size.max(BOTTOM_GROUP_MIN_WIDTH * 3.0);
+                }
+                self.bottom.push(DockGroup::new(vec![panel], 0.0));
+            }
+            DockDrop::Edge(side) => {
+                self.groups_mut(side)
+                    .insert(0, DockGroup::new(vec![panel], 260.0));
+            }
+        }
+        for side in DockSide::ALL {
+            self.groups_mut(side).retain(|g| !g.tabs.is_empty());
+        }
+        self.changed = true;
+    }
+
+    /// Grupos com algum painel aberto e seus retângulos dentro de `area`
+    /// (janela sem a barra inferior); atualiza `rects` e `margins`
+    fn layout(&mut self, area: Rect, bar_height: f32, open: &[DockPanel]) -> Vec<VisibleGroup> {
+        let mut visible = Vec::new();
+        let shown = |groups: &Vec<DockGroup>| -> Vec<(usize, DockPanel)> {
+            groups
+                .iter()
+                .enumerate()
+                .filter_map(|(i, g)| g.shown(open).map(|shown| (i, shown)))
+                .collect()
+        };
+        let bottom_groups = shown(&self.bottom);
+        let max_bottom = (area.height() - VIEWPORT_MIN_HEIGHT).max(BOTTOM_MIN_HEIGHT);
+        self.bottom_height = self.bottom_height.clamp(BOTTOM_MIN_HEIGHT, max_bottom);
+        let bottom_height = if bottom_groups.is_empty() {
+            0.0
+        } else {
+            self.bottom_height
+        };
+        let sides_bottom = area.bottom() - bottom_height;
+
+        let mut x = area.left();
+        for &(index, panel) in &bottom_groups {
+            let last = Some(&(index, panel)) == bottom_groups.last();
+            let right = if last {
+                area.right()
+            } else {
+                (x + self.bottom[index].size.max(BOTTOM_GROUP_MIN_WIDTH))
+                    .min(area.right() - BOTTOM_GROUP_MIN_WIDTH)
+            };
+            visible.push(VisibleGroup {
+                side: DockSide::Bottom,
+                index,
+                rect: Rect::from_min_max(
+                    egui::pos2(x, sides_bottom + SPLITTER_WIDTH),
+                    egui::pos2(
+                        right - if last { 0.0 } else { SPLITTER_WIDTH },
+                        area.bottom(),
+                    ),
+                ),
+                tabs: self.bottom[index].tabs.clone(),
+                shown: panel,
+            });
+            x = right;
+        }
+
+        // Colunas não passam do espaço mínimo da viewport
+        let mut free = (area.width() - VIEWPORT_MIN_WIDTH).max(0.0);
+        let mut widths = (0.0, 0.0);
+        for side in [DockSide::Left, DockSide::Right] {
+            let mut edge = match side {
+                DockSide::Left => area.left(),
+                _ => area.right(),
+            };
+            for (index, panel) in shown(self.groups(side)) {
+                let width = self.groups(side)[index].size.min(free);
+                if width < SIDE_MIN_WIDTH {
+                    break;
+                }
+                free -= width;
+                let (min_x, max_x) = match side {
+                    DockSide::Left => (edge, edge + width - SPLITTER_WIDTH),
+                    _ => (edge - width + SPLITTER_WIDTH, edge),
+                };
+                visible.push(VisibleGroup {
+                    side,
+                    index,
+                    rect: Rect::from_min_max(
+                        egui::pos2(min_x, area.top()),
+                        egui::pos2(max_x, sides_bottom),
+                    ),
+                    tabs: self.groups(side)[index].tabs.clone(),
+                    shown: panel,
+                });
+                match side {
+                    DockSide::Left => {
+                        edge += width;
+                        widths.0 += width;
+                    }
+                    _ => {
+                        edge -= width;
+                        widths.1 += width;
+                    }
+                }
+            }
+        }
+
+        self.margins = (widths.0, widths.1, bottom_height + bar_height);
+        self.rects = visible
+            .iter()
+            .map(|g| {
+                let content = Rect::from_min_max(
+                    egui::pos2(g.rect.left(), g.rect.top() + TAB_BAR_HEIGHT),
+                    g.rect.max,
+                );
+                (g.shown, content)
+            })
+            .collect();
+        visible
+    }
+
+    fn drop_target(
+        &self,
+        pos: Pos2,
+        area: Rect,
+        visible: &[VisibleGroup],
+    ) -> Option<(DockDrop, Rect)> {
+        if !area.contains(pos) {
+            return None;
+        }
+        let strip = |side: DockSide| match side {
+            DockSide::Left => Rect::from_min_max(
+                area.min,
+                egui::pos2(area.left() + EDGE_DROP_ZONE, area.bottom()),
+            ),
+            DockSide::Right => Rect::from_min_max(
+                egui::pos2(area.right() - EDGE_DROP_ZONE, area.top()),
+                area.max,
+            ),
+            DockSide::Bottom => Rect::from_min_max(
+                egui::pos2(area.left(), area.bottom() - EDGE_DROP_ZONE),
+                area.max,
+            ),
+        };
+        if let Some(side) = DockSide::ALL.into_iter().find(|&s| strip(s).contains(pos)) {
+            return Some((DockDrop::Edge(side), strip(side)));
+        }
+        visible
+            .iter()
+            .find(|g| g.rect.contains(pos))
+            .map(|g| (DockDrop::Group(g.side, g.index), g.rect))
+    }
+
+    /// Posiciona os painéis `open` acima da barra inferior de `bar_height`,
+    /// desenha as abas e trata arrastar abas e redimensionar grupos
+    pub(crate) fn show(
+        &mut self,
+        ctx: &egui::Context,
+        open: &[DockPanel],
+        bar_height: f32,
+        language: EngineLanguage,
+    ) {
+        let newly_open: Vec<DockPanel> = match &self.open_before {
+            Some(before) => open
+                .iter()
+                .copied()
+                .filter(|p| !before.contains(p))
+                .collect(),
+            None => Vec::new(),
+        };
+        for panel in newly_open {
+            self.activate(panel);
+        }
+        self.open_before = Some(open.to_vec());
+
+        let full = ctx.available_rect();
+        let area = Rect::from_min_max(
+            full.min,
+            egui::pos2(full.right(), (full.bottom() - bar_height).max(full.top())),
+        );
+        let visible = self.layout(area, bar_height, open);
+
+        let mut clicked_tab = None;
+        let mut drag_started = None;
+        let mut resize_started = None;
+        let accent = theme::palette().accent;
+        for group in &visible {
+            let bar = Rect::from_min_size(
+                group.rect.min,
+                egui::vec2(group.rect.width(), TAB_BAR_HEIGHT),
+            );
+            egui::Area::new(Id::new(("dock_tabs", group.side, group.index)))
+                .order(egui::Order::Foreground)
+                .fixed_pos(bar.min)
+                .show(ctx, |ui| {
+                    let (bar, _) = ui.allocate_exact_size(bar.size(), egui::Sense::hover());
+                    ui.painter().rect_filled(bar, 0.0, theme::palette().tab_bar);
+                    let mut x = bar.left() + 4.0;
+                    for &tab in group.tabs.iter().filter(|tab| open.contains(tab)) {
+                        let galley = ui.painter().layout_no_wrap(
+                            tab.title(language).to_string(),
+                            FontId::proportional(12.0),
+                            Color32::WHITE,
+                        );
+                        let tab_rect = Rect::from_min_size(
+                            egui::pos2(x, bar.top() + 3.0),
+                            egui::vec2(galley.size().x + 20.0, bar.height() - 3.0),
+                        );
+                        x = tab_rect.right() + 2.0;
+                        let resp = ui.interact(
+                            tab_rect,
+                            ui.id().with(tab.key()),
+                            egui::Sense::click_and_drag(),
+                        );
+                        let selected = tab == group.shown;
+                        let fill = if selected {
+                            Color32::from_rgb(40, 40, 40)
+                        } else if resp.hovered() {
+                            Color32::from_rgb(34, 34, 34)
+                        } else {
+                            Color32::from_rgb(28, 28, 28)
+                        };
+                        ui.painter().rect_filled(tab_rect, 4.0, fill);
+                        if selected {
+                            ui.painter().line_segment(
+                                [tab_rect.left_top(), tab_rect.right_top()],
+                                Stroke::new(2.0, accent),
+                            );
+                        }
+                        ui.painter().text(
+                            tab_rect.center(),
+                            egui::Align2::CENTER_CENTER,
+                            tab.title(language),
+                            FontId::proportional(12.0),
+                            if selected {
+                                Color32::WHITE
+                            } else {
+                                Color32::from_gray(160)
+                            },
+                        );
+                        if resp.clicked() {
+                            clicked_tab = Some(tab);
+                        }
+                        if resp.drag_started() {
+                            drag_started = Some(tab);
+                        }
+                    }
+                });
+
+            // Divisória na borda voltada para a viewport (ou para o próximo grupo)
+            let handle = match group.side {
+                DockSide::Left => Some((
+                    Rect::from_min_max(
+                        egui::pos2(group.rect.right(), group.rect.top()),
+                        egui::pos2(group.rect.right() + SPLITTER_WIDTH, group.rect.bottom()),
+                    ),
+                    DockResize::Group(group.side, group.index),
+                )),
+                DockSide::Right => Some((
+                    Rect::from_min_max(
+                        egui::pos2(group.rect.left() - SPLITTER_WIDTH, group.rect.top()),
+                        egui::pos2(group.rect.left(), group.rect.bottom()),
+                    ),
+                    DockResize::Group(group.side, group.index),
+                )),
+                DockSide::Bottom => (group.rect.right() < area.right()).then(|| {
+                    (
+                        Rect::from_min_max(
+                            egui::pos2(group.rect.right(), group.rect.top()),
+                            egui::pos2(group.rect.right() + SPLITTER_WIDTH, group.rect.bottom()),
+                        ),
+                        DockResize::Group(group.side, group.index),
+                    )
+                }),
+            };
+            let bottom_handle =
+                (group.side == DockSide::Bottom && group.rect.left() <= area.left()).then(|| {
+                    (
+                        Rect::from_min_max(
+                            egui::pos2(area.left(), group.rect.top() - SPLITTER_WIDTH),
+                            egui::pos2(area.right(), group.rect.top()),
+                        ),
+                        DockResize::Bottom,
+                    )
+                });
+            for (rect, resize) in handle.into_iter().chain(bottom_handle) {
+                let (id, horizontal) = match resize {
+                    DockResize::Group(side, index) => {
+                        (Id::new(("dock_splitter", side, index)), true)
+                    }
+                    DockResize::Bottom => (Id::new("dock_splitter_bottom"), false),
+                };
+                egui::Area::new(id)
+                    .order(egui::Order::Foreground)
+                    .fixed_pos(rect.min)
+                    .show(ctx, |ui| {
+                        let (rect, resp) = ui.allocate_exact_size(rect.size(), egui::Sense::drag());
+                        if resp.hovered() || resp.dragged() {
+                            ui.ctx().set_cursor_icon(if horizontal {
+                                egui::CursorIcon::ResizeHorizontal
+                            } else {
+                                egui::CursorIcon::ResizeVertical
+                            });
+                            ui.painter()
+                                .rect_filled(rect, 0.0, accent.gamma_multiply(0.6));
+                        }
+                        if resp.drag_started() {
+                            resize_started = Some(resize);
+                        }
+                    });
+            }
+        }
+
+        if let Some(tab) = clicked_tab {
+            self.activate(tab);
+            self.changed = true;
+        }
+        if let Some(tab) = drag_started {
+            self.dragging = Some(tab);
+        }
+        if let Some(resize) = resize_started {
+            self.resizing = Some(resize);
+        }
+
+        let (pointer_down, delta, pointer) = ctx.input(|i| {
+            (
+                i.pointer.primary_down(),
+                i.pointer.delta(),
+                i.pointer.hover_pos(),
+            )
+        });
+        if let Some(resize) = self.resizing {
+            if pointer_down {
+                match resize {
+                    DockResize::Bottom => self.bottom_height -= delta.y,
+                    DockResize::Group(side, index) => {
+                        let dx = if side == DockSide::Right {
+                            -delta.x
+                        } else {
+                            delta.x
+                        };
+                        let (min, max) = if side == DockSide::Bottom {
+                            (
+                                BOTTOM_GROUP_MIN_WIDTH,
+                                area.width() - BOTTOM_GROUP_MIN_WIDTH,
+                            )
+                        } else {
+                            (SIDE_MIN_WIDTH, SIDE_MAX_WIDTH)
+                        };
+                        if let Some(group) = self.groups_mut(side).get_mut(index) {
+                            group.size = (group.size + dx).clamp(min, max.max(min));
+                        }
+                    }
+                }
+            } else {
+                self.resizing = None;
+                self.changed = true;
+            }
+        }
+
+        if let Some(panel) = self.dragging {
+            let target = pointer.and_then(|pos| self.drop_target(pos, area, &visible));
+            let painter = ctx.layer_painter(egui::LayerId::new(
+                egui::Order::Tooltip,
+                Id::new("dock_drop_hint"),
+            ));
+            if let Some((_, rect)) = target {
+                painter.rect_filled(rect, 6.0, accent.gamma_multiply(0.25));
+                painter.rect_stroke(
+                    rect,
+                    6.0,
+                    Stroke::new(2.0, accent),
+                    egui::StrokeKind::Inside,
+                );
+            }
+            if let Some(pos) = pointer {
+                painter.text(
+                    pos + egui::vec2(14.0, 10.0),
+                    egui::Align2::LEFT_TOP,
+                    panel.title(language),
+                    FontId::proportional(12.0),
+                    Color32::WHITE,
+                );
+            }
+            if !pointer_down {
+                self.dragging = None;
+                if let Some((target, _)) = target {
+                    self.move_panel(panel, target);
+                }
+            }
+        }
+    }
+
+    /// Layout do projeto ou, se ele não tem um, `fallback` (o último usado)
+    pub(crate) fn load(&mut self, project: Option<&Path>, fallback: &str) {
+        *self = Self {
+            loaded_for: project.map(Path::to_path_buf),
+            ..Self::default()
+        };
+        let raw = project
+            .and_then(Self::config_path)
+            .and_then(|path| fs::read_to_string(path).ok())
+            .unwrap_or_else(|| fallback.to_string());
+        let mut groups: Vec<(DockSide, DockGroup)> = Vec::new();
+        let mut bottom_height = None;
+        for (key, value) in raw.lines().filter_map(|line| line.split_once('=')) {
+            match key.trim() {
+                "bottom_height" => bottom_height = value.trim().parse().ok(),
+                // group=<lado> <largura> <aba ativa> <abas separadas por vírgula>
+                "group" => {
+                    let words: Vec<&str> = value.split_whitespace().collect();
+                    let [side, size, active, tabs] = words[..] else {
+                        continue;
+                    };
+                    let (Some(side), Ok(size)) = (DockSide::from_key(side), size.parse()) else {
+                        continue;
+                    };
+                    let mut tabs: Vec<DockPanel> =
+                        tabs.split(',').filter_map(DockPanel::from_key).collect();
+                    // Um painel só pode estar num grupo
+                    tabs.retain(|tab| !groups.iter().any(|(_, g)| g.tabs.contains(tab)));
+                    tabs.dedup();
+                    if tabs.is_empty() {
+                        continue;
+                    }
+                    let mut group = DockGroup::new(tabs, size);
+                    if let Some(active) = DockPanel::from_key(active) {
+                        if group.tabs.contains(&active) {
+                            group.active = active;
+                        }
+                    }
+                    groups.push((side, group));
+                }
+                _ => {}
+            }
+        }
+        if groups.is_empty() {
+            return;
+        }
+        self.left.clear();
+        self.right.clear();
+        self.bottom.clear();
+        for (side, group) in groups {
+            self.groups_mut(side).push(group);
+        }
+        for panel in DockPanel::ALL {
+            if self.groups_of_all().any(|g| g.tabs.contains(&panel)) {
+                continue;
+            }
+            let side = panel.default_side();
+            match self.groups_mut(side).first_mut() {
+                Some(group) => group.tabs.push(panel),
+                None => self
+                    .groups_mut(side)
+                    .push(DockGroup::new(vec![panel], 260.0)),
+            }
+        }
+        if let Some(height) = bottom_height {
+            self.bottom_height = height;
+        }
+    }
+
+    fn groups_of_all(&self) -> impl Iterator<Item = &DockGroup> {
+        self.left.iter().chain(&self.right).chain(&self.bottom)
+    }
+
+    /// Conteúdo de `layout.cfg`
+    pub(crate) fn to_config(&self) -> String {
+        let mut out = format!("bottom_height={}\n", self.bottom_height.round());
+        for side in DockSide::ALL {
+            for group in self.groups(side) {
+                let tabs: Vec<&str> = group.tabs.iter().map(|tab| tab.key()).collect();
+                out.push_str(&format!(
+                    "group={} {} {} {}\n",
+                    side.key(),
+                    group.size.round(),
+                    group.active.key(),
+                    tabs.join(",")
+                ));
+            }
+        }
+        out
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let Some(path) = self.loaded_for.as_deref().and_then(Self::config_path) else {
+            // Sem projeto aberto o layout fica só nas preferências
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        fs::write(&path, self.to_config()).map_err(|e| e.to_string())
+    }
+}
+
+impl EditorApp {
+    /// Painéis abertos, na ordem em que o layout os considera
+    fn open_dock_panels(&self) -> Vec<DockPanel> {
+        let mut open = Vec::new();
+        if self.hierarchy.open {
+            open.push(DockPanel::Hierarchy);
+        }
+        if self.inspector.open {
+            open.push(DockPanel::Inspector);
+        }
+        if self.project.open && !self.project_collapsed {
+            open.push(DockPanel::Project);
+        }
+        if self.log_enabled {
+            open.push(DockPanel::Log);
+        }
+        if self.git_enabled {
+            open.push(DockPanel::Git);
+        }
+        if self.fios_enabled {
+            open.push(DockPanel::Animator);
+        }
+        if self.material_editor.open {
+            open.push(DockPanel::Material);
+        }
+        if self.animator_enabled {
+            open.push(DockPanel::Timeline);
+        }
+        if self.rig_enabled {
+            open.push(DockPanel::Rig);
+        }
+        if self.profiler.open {
+            open.push(DockPanel::Profiler);
+        }
+        open
+    }
+
+    /// Carrega o layout ao trocar de projeto e posiciona os painéis do frame
+    pub(crate) fn update_dock_layout(&mut self, ctx: &egui::Context, bar_height: f32) {
+        engine_core::profile_function!();
+        if self.dock.loaded_for != self.current_project {
+            let project = self.current_project.clone();
+            self.dock.load(project.as_deref(), &self.preferences.layout);
+        }
+        let open = self.open_dock_panels();
+        self.dock.show(ctx, &open, bar_height, self.language);
+        if self.dock.changed && self.dock.resizing.is_none() {
+            self.dock.changed = false;
+            if let Err(e) = self.dock.save() {
+                self.log.push(
+                    LogLevel::Error,
+                    "Layout",
+                    format!("Falha ao salvar o layout: {e}"),
+                );
+            }
+            self.preferences.layout = self.dock.to_config();
+            self.save_editor_preferences();
+        }
+    }
+}
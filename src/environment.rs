@@ -0,0 +1,356 @@
+use super::*;
+use crate::reflect::{self, Field, FieldKind, Reflect, ReflectContext, ReflectEnum};
+use engine_core::{FogMode, FogSettings};
+use engine_render::{
+    AssetHandle, AssetKind, AssetManager, EnvironmentMap, EnvironmentSettings, LoadState,
+};
+use glam::Vec3;
+
+/// Céu de uma cena: configurações + arquivo/pasta do panorama
+#[derive(Clone, Default)]
+struct SceneEnvironment {
+    settings: EnvironmentSettings,
+    source: Option<PathBuf>,
+}
+
+/// Janela de Ambiente: céu (panorama HDR/cubemap ou gradiente) por cena
+pub(crate) struct EnvironmentPanel {
+    pub(crate) open: bool,
+    assets: AssetManager,
+    // Chave "" = cena principal
+    scenes: HashMap<String, SceneEnvironment>,
+    status: Option<String>,
+    // Algum panorama terminou de carregar: a viewport reenvia o céu
+    map_loaded: bool,
+}
+
+impl Default for EnvironmentPanel {
+    fn default() -> Self {
+        Self {
+            open: false,
+            assets: AssetManager::new(),
+            scenes: HashMap::new(),
+            status: None,
+            map_loaded: false,
+        }
+    }
+}
+
+impl EnvironmentPanel {
+    fn scene_mut(&mut self, scene: Option<&str>) -> &mut SceneEnvironment {
+        self.scenes
+            .entry(scene.unwrap_or_default().to_string())
+            .or_default()
+    }
+
+    /// Panoramas grandes são lidos em segundo plano; até lá a cena usa o gradiente
+    fn load_sky(&mut self, scene: Option<&str>, path: PathBuf) {
+        let (handle, state) = self.assets.load_as(&path, AssetKind::Environment);
+        let AssetHandle::Environment(handle) = handle else {
+            return;
+        };
+        // O mesmo arquivo já carregado é aplicado na hora
+        self.status = Some(if state == LoadState::Loaded {
+            format!("Céu carregado: {}", path.display())
+        } else {
+            format!("Carregando céu: {}", path.display())
+        });
+        // Cada cena é dona do seu panorama; o anterior sai quando ninguém mais o usa
+        self.assets.retain(AssetHandle::Environment(handle));
+        let env = self.scene_mut(scene);
+        let previous = env.settings.environment.replace(handle);
+        env.source = Some(path);
+        if let Some(previous) = previous {
+            self.assets.release(AssetHandle::Environment(previous));
+        }
+    }
+
+    /// Volta a cena ao gradiente, soltando o panorama
+    fn clear_sky(&mut self, scene: Option<&str>) {
+        let env = self.scene_mut(scene);
+        env.source = None;
+        if let Some(handle) = env.settings.environment.take() {
+            self.assets.release(AssetHandle::Environment(handle));
+        }
+    }
+
+    pub(crate) fn assets(&self) -> &AssetManager {
+        &self.assets
+    }
+
+    pub(crate) fn assets_mut(&mut self) -> &mut AssetManager {
+        &mut self.assets
+    }
+
+    /// Arquivo alterado em disco: relê os panoramas vindos dele (ou da pasta de
+    /// cubemap que o contém) sem trocar os handles das cenas
+    pub(crate) fn reload_file(&mut self, path: &Path) -> bool {
+        let mut reloaded = self.assets.reload(path);
+        if let Some(dir) = path.parent() {
+            reloaded.extend(self.assets.reload(dir));
+        }
+        !reloaded.is_empty()
+    }
+
+    /// Recolhe os panoramas lidos em segundo plano; true enquanto algum carrega
+    fn poll_loads(&mut self) -> bool {
+        for (handle, state) in self.assets.poll_loads() {
+            let AssetHandle::Environment(handle) = handle else {
+                continue;
+            };
+            match state {
+                // Recarga que falhou: o panorama anterior continua
+                LoadState::Failed(e) if self.assets.contains(AssetHandle::Environment(handle)) => {
+                    self.status = Some(e);
+                }
+                LoadState::Failed(e) => {
+                    // Cenas que esperavam este panorama voltam ao gradiente
+                    for env in self
+                        .scenes
+                        .values_mut()
+                        .filter(|env| env.settings.environment == Some(handle))
+                    {
+                        env.settings.environment = None;
+                        env.source = None;
+                        self.assets.release(AssetHandle::Environment(handle));
+                    }
+                    self.status = Some(e);
+                }
+                _ => {
+                    self.map_loaded = true;
+                    if let Some(map) = self.assets.get_environment(handle) {
+                        self.status = Some(format!("Céu carregado: {}", map.source_path.display()));
+                    }
+                }
+            }
+        }
+        self.assets.pending_loads() > 0
+    }
+
+    /// Configurações e panorama da cena
+    fn resolve(&self, scene: Option<&str>) -> (EnvironmentSettings, Option<&EnvironmentMap>) {
+        let settings = self
+            .scenes
+            .get(scene.unwrap_or_default())
+            .map(|env| env.settings)
+            .unwrap_or_default();
+        let map = settings
+            .environment
+            .and_then(|handle| self.assets.get_environment(handle));
+        (settings, map)
+    }
+}
+
+impl EditorApp {
+    /// Envia o céu da cena ativa para a viewport
+    pub(crate) fn sync_environment(&mut self, ctx: &egui::Context) {
+        if self.environment.poll_loads() {
+            ctx.request_repaint_after(std::time::Duration::from_millis(100));
+        }
+        if std::mem::take(&mut self.environment.map_loaded) {
+            if let Some(gpu) = &self.viewport_gpu {
+                gpu.reload_environment();
+            }
+        }
+        let (settings, map) = self.environment.resolve(self.hierarchy.active_scene_name());
+        self.viewport.set_environment(settings, map.cloned());
+    }
+
+    pub(crate) fn draw_environment_window(&mut self, ctx: &egui::Context) {
+        engine_core::profile_function!();
+        if !self.environment.open {
+            return;
+        }
+        let scene = self.hierarchy.active_scene_name().map(str::to_string);
+        let scene = scene.as_deref();
+        let language = self.language;
+        let start_dir = self
+            .current_project
+            .as_ref()
+            .and_then(|p| p.parent())
+            .map(|p| p.join("Assets"))
+            .filter(|p| p.is_dir());
+
+        let mut open = self.environment.open;
+        let mut pick_file = false;
+        let mut pick_folder = false;
+        let mut clear_sky = false;
+        egui::Window::new("🌅 Ambiente")
+            .open(&mut open)
+            .default_size([300.0, 320.0])
+            .resizable(true)
+            .show(ctx, |ui| {
+                let panel = &mut self.environment;
+                ui.label(
+                    egui::RichText::new(format!("Cena: {}", scene.unwrap_or("Principal"))).strong(),
+                );
+                ui.add_space(4.0);
+
+                let env = panel.scene_mut(scene);
+                ui.label("Céu");
+                ui.horizontal(|ui| {
+                    match &env.source {
+                        Some(path) => ui.label(
+                            path.file_name()
+                                .unwrap_or_default()
+                                .to_string_lossy()
+                                .to_string(),
+                        ),
+                        None => ui.label(egui::RichText::new("Gradiente").weak()),
+                    };
+                    if env.source.is_some() && ui.small_button("✖").clicked() {
+                        clear_sky = true;
+                    }
+                });
+                ui.horizontal(|ui| {
+                    pick_file = ui.button("Panorama (.hdr/.png)...").clicked();
+                    pick_folder = ui.button("Pasta de cubemap...").clicked();
+                });
+                ui.add_space(6.0);
+
+                egui::Grid::new("environment_grid")
+                    .num_columns(2)
+                    .spacing([8.0, 6.0])
+                    .show(ui, |ui| {
+                        ui.label("Exposição");
+                        ui.add(
+                            egui::Slider::new(&mut env.settings.exposure, 0.0..=8.0)
+                                .logarithmic(true),
+                        );
+                        ui.end_row();
+                        ui.label("Rotação");
+                        ui.add(
+                            egui::Slider::new(
+                                &mut env.settings.rotation,
+                                0.0..=std::f32::consts::TAU,
+                            )
+                            .custom_formatter(|v, _| format!("{:.0}°", v.to_degrees())),
+                        );
+                        ui.end_row();
+                        ui.label("Iluminação (IBL)");
+                        ui.add_enabled(
+                            env.settings.environment.is_some(),
+                            egui::Checkbox::without_text(&mut env.settings.ibl_enabled),
+                        );
+                        ui.end_row();
+                        ui.label("Intensidade IBL");
+                        ui.add_enabled(
+                            env.settings.environment.is_some() && env.settings.ibl_enabled,
+                            egui::Slider::new(&mut env.settings.ibl_intensity, 0.0..=4.0),
+                        );
+                        ui.end_row();
+                        if env.settings.environment.is_none() {
+                            ui.label("Zênite");
+                            ui.color_edit_button_rgb(&mut env.settings.zenith_color);
+                            ui.end_row();
+                            ui.label("Horizonte");
+                            ui.color_edit_button_rgb(&mut env.settings.horizon_color);
+                            ui.end_row();
+                            ui.label("Chão");
+                            ui.color_edit_button_rgb(&mut env.settings.ground_color);
+                            ui.end_row();
+                        }
+                    });
+
+                ui.add_space(6.0);
+                ui.label("Névoa");
+                fog_grid(ui, "environment_fog_grid", &mut env.settings.fog, language);
+
+                if let Some(status) = &panel.status {
+                    ui.add_space(6.0);
+                    ui.label(egui::RichText::new(status).small().weak());
+                }
+            });
+        self.environment.open = open;
+        if clear_sky {
+            self.environment.clear_sky(scene);
+        }
+
+        let picked = if pick_file {
+            let mut dialog = rfd::FileDialog::new()
+                .add_filter("Panorama", &["hdr", "exr", "png", "jpg", "jpeg", "webp"]);
+            if let Some(dir) = &start_dir {
+                dialog = dialog.set_directory(dir);
+            }
+            dialog.pick_file()
+        } else if pick_folder {
+            let mut dialog = rfd::FileDialog::new();
+            if let Some(dir) = &start_dir {
+                dialog = dialog.set_directory(dir);
+            }
+            dialog.pick_folder()
+        } else {
+            None
+        };
+        if let Some(path) = picked {
+            self.environment.load_sky(scene, path);
+        }
+    }
+}
+
+impl ReflectEnum for FogMode {
+    fn variants(&self) -> Vec<&'static str> {
+        FogMode::ALL.iter().map(|m| m.label()).collect()
+    }
+
+    fn index(&self) -> usize {
+        FogMode::ALL.iter().position(|m| m == self).unwrap_or(0)
+    }
+
+    fn set_index(&mut self, index: usize) {
+        if let Some(mode) = FogMode::ALL.get(index) {
+            *self = *mode;
+        }
+    }
+}
+
+/// Campos da névoa (ambiente da cena e substituição por câmera)
+impl Reflect for FogSettings {
+    fn fields(&mut self) -> Vec<Field<'_>> {
+        let start = self.start;
+        let mode = self.mode;
+        let height_fog = self.height_density > 0.0;
+        let mut fields = vec![
+            Field::new("Modo", FieldKind::Enum(&mut self.mode)),
+            Field::new("Cor", FieldKind::Color(self.color.as_mut())),
+        ];
+        match mode {
+            FogMode::Off => {}
+            FogMode::Linear => {
+                fields.push(Field::float("Início", &mut self.start, 0.5, 0.0..=f32::MAX));
+                fields.push(Field::float("Fim", &mut self.end, 0.5, start..=f32::MAX));
+            }
+            FogMode::Exponential | FogMode::ExponentialSquared => {
+                fields.push(Field::log_slider("Densidade", &mut self.density, 0.0..=0.5));
+            }
+        }
+        fields.push(Field::log_slider(
+            "Névoa de altura",
+            &mut self.height_density,
+            0.0..=0.5,
+        ));
+        if height_fog {
+            fields.push(Field::float(
+                "Altura",
+                &mut self.height,
+                0.1,
+                f32::NEG_INFINITY..=f32::INFINITY,
+            ));
+            fields.push(Field::log_slider(
+                "Queda",
+                &mut self.height_falloff,
+                0.01..=2.0,
+            ));
+        }
+        fields.push(Field::slider(
+            "Opacidade máx.",
+            &mut self.max_opacity,
+            0.0..=1.0,
+        ));
+        fields
+    }
+}
+
+fn fog_grid(ui: &mut egui::Ui, id: &str, fog: &mut FogSettings, language: EngineLanguage) {
+    reflect::draw_fields(ui, id, fog, &mut ReflectContext::new(language));
+}
@@ -0,0 +1,231 @@
+use super::*;
+use engine_render::fuzzy_score;
+
+/// Resultados mostrados de uma vez
+const FINDER_RESULTS: usize = 20;
+
+enum FinderTarget {
+    Entity(String),
+    Asset(PathBuf),
+}
+
+struct FinderHit {
+    target: FinderTarget,
+    name: String,
+    detail: String,
+    score: i32,
+}
+
+/// Busca rápida (Ctrl+P) de objetos da cena aberta e assets do projeto; o
+/// escolhido é selecionado na Hierarquia ou na janela Projeto
+#[derive(Default)]
+pub(crate) struct QuickFinder {
+    open: bool,
+    query: String,
+    // Linha destacada, trocada com as setas e aberta com Enter
+    highlighted: usize,
+    // Foco na caixa de texto no frame em que a busca abre
+    focus_query: bool,
+}
+
+impl QuickFinder {
+    pub(crate) fn show(&mut self) {
+        *self = Self {
+            open: true,
+            focus_query: true,
+            ..Self::default()
+        };
+    }
+}
+
+fn tr(language: EngineLanguage, key: &'static str) -> &'static str {
+    i18n::text(language, "finder", key)
+}
+
+impl EditorApp {
+    /// Objetos cujo nome tem todas as palavras (letras em ordem) e assets pela
+    /// busca do projeto, misturados pela pontuação. `t:` e `l:` só valem para
+    /// assets, então com eles os objetos ficam de fora
+    fn finder_hits(&self, query: &str) -> Vec<FinderHit> {
+        let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        if words.is_empty() {
+            return Vec::new();
+        }
+        let mut hits = Vec::new();
+        let filtered = words
+            .iter()
+            .any(|word| word.starts_with("t:") || word.starts_with("l:"));
+        if !filtered {
+            let entity = tr(self.language, "entity");
+            for name in self.hierarchy.scene_objects() {
+                let score: Option<i32> = words.iter().map(|word| fuzzy_score(word, &name)).sum();
+                if let Some(score) = score {
+                    hits.push(FinderHit {
+                        name: name.clone(),
+                        target: FinderTarget::Entity(name),
+                        detail: entity.to_string(),
+                        score,
+                    });
+                }
+            }
+        }
+        let index = self.project.search_index();
+        for hit in index.search(query, FINDER_RESULTS) {
+            let entry = hit.entry;
+            let folder = entry
+                .path
+                .parent()
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_default();
+            hits.push(FinderHit {
+                target: FinderTarget::Asset(entry.path.clone()),
+                name: entry.name.clone(),
+                detail: format!("{} · {folder}", entry.asset_type.name()),
+                score: hit.score,
+            });
+        }
+        hits.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then_with(|| a.name.len().cmp(&b.name.len()))
+        });
+        hits.truncate(FINDER_RESULTS);
+        hits
+    }
+
+    fn reveal_in_hierarchy(&mut self, object_name: &str) {
+        self.hierarchy.open = true;
+        self.dock.activate(DockPanel::Hierarchy);
+        self.hierarchy.reveal_object(object_name);
+    }
+
+    fn reveal_in_project(&mut self, path: &Path) {
+        self.project.open = true;
+        self.project_collapsed = false;
+        self.dock.activate(DockPanel::Project);
+        self.project.reveal_asset(path, self.language);
+    }
+
+    /// Abre/fecha com Ctrl+P; setas escolhem, Enter abre e Esc fecha
+    pub(crate) fn draw_quick_finder(&mut self, ctx: &egui::Context) {
+        if ctx.input_mut(|i| i.consume_key(Modifiers::COMMAND, Key::P)) {
+            if self.finder.open {
+                self.finder.open = false;
+            } else {
+                self.finder.show();
+            }
+        }
+        if !self.finder.open {
+            return;
+        }
+        let lang = self.language;
+        let hits = self.finder_hits(&self.finder.query);
+        // Consumidas antes da caixa de texto, que usaria as setas e o Enter
+        let (up, down, enter, escape) = ctx.input_mut(|i| {
+            (
+                i.consume_key(Modifiers::NONE, Key::ArrowUp),
+                i.consume_key(Modifiers::NONE, Key::ArrowDown),
+                i.consume_key(Modifiers::NONE, Key::Enter),
+                i.consume_key(Modifiers::NONE, Key::Escape),
+            )
+        });
+        if escape {
+            self.finder.open = false;
+            return;
+        }
+        let last = hits.len().saturating_sub(1);
+        if down {
+            self.finder.highlighted += 1;
+        }
+        if up {
+            self.finder.highlighted = self.finder.highlighted.saturating_sub(1);
+        }
+        self.finder.highlighted = self.finder.highlighted.min(last);
+        let highlighted = self.finder.highlighted;
+        let mut picked = (enter && !hits.is_empty()).then_some(highlighted);
+        let mut query_changed = false;
+        let window = egui::Window::new(tr(lang, "title"))
+            .id(egui::Id::new("quick_finder"))
+            .title_bar(false)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 80.0])
+            .show(ctx, |ui| {
+                ui.set_width(460.0);
+                let edit = ui.add(
+                    egui::TextEdit::singleline(&mut self.finder.query)
+                        .hint_text(tr(lang, "hint"))
+                        .desired_width(f32::INFINITY),
+                );
+                if self.finder.focus_query {
+                    self.finder.focus_query = false;
+                    edit.request_focus();
+                }
+                query_changed = edit.changed();
+                if self.finder.query.trim().is_empty() {
+                    return;
+                }
+                ui.separator();
+                if hits.is_empty() {
+                    ui.label(
+                        egui::RichText::new(tr(lang, "none")).color(theme::palette().text_weak),
+                    );
+                    return;
+                }
+                egui::ScrollArea::vertical()
+                    .max_height(360.0)
+                    .show(ui, |ui| {
+                        for (idx, hit) in hits.iter().enumerate() {
+                            let mut text = LayoutJob::default();
+                            text.append(
+                                &hit.name,
+                                0.0,
+                                egui::TextFormat::simple(
+                                    egui::FontId::proportional(12.0),
+                                    theme::palette().text_strong,
+                                ),
+                            );
+                            text.append(
+                                &format!("  {}", hit.detail),
+                                0.0,
+                                egui::TextFormat::simple(
+                                    egui::FontId::proportional(10.0),
+                                    theme::palette().text_weak,
+                                ),
+                            );
+                            let resp = ui.selectable_label(idx == highlighted, text);
+                            if idx == highlighted && (up || down) {
+                                resp.scroll_to_me(None);
+                            }
+                            if resp.clicked() {
+                                picked = Some(idx);
+                            }
+                        }
+                    });
+            });
+        // Clique fora fecha, como nos outros popups
+        let pressed_outside = window.is_some_and(|window| {
+            ctx.input(|i| {
+                i.pointer.any_pressed()
+                    && i.pointer
+                        .interact_pos()
+                        .is_some_and(|p| !window.response.rect.contains(p))
+            })
+        });
+        if pressed_outside {
+            self.finder.open = false;
+        }
+        if query_changed {
+            self.finder.highlighted = 0;
+            ctx.request_repaint();
+        }
+        let Some(hit) = picked.and_then(|idx| hits.into_iter().nth(idx)) else {
+            return;
+        };
+        self.finder.open = false;
+        match hit.target {
+            FinderTarget::Entity(name) => self.reveal_in_hierarchy(&name),
+            FinderTarget::Asset(path) => self.reveal_in_project(&path),
+        }
+    }
+}
@@ -1,5 +1,10 @@
 use crate::EngineLanguage;
+use crate::curve_editor::CurveEditor;
+use crate::i18n;
+use crate::theme;
 use eframe::egui::{self, UiKind};
+use engine_core::Input;
+use engine_render::{Curve, FormatKind, TangentMode, migrate_format};
 use mlua::{Function, Lua, MultiValue, RegistryKey, Table, Value};
 use std::collections::{HashMap, HashSet};
 use std::fs;
@@ -11,6 +16,10 @@ use modules::{
     group_modules_by_category, parse_available_module,
 };
 
+fn tr(language: EngineLanguage, key: &'static str) -> &'static str {
+    i18n::text(language, "fios", key)
+}
+
 const ACTION_COUNT: usize = 8;
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -77,64 +86,27 @@ impl FiosAction {
         }
     }
 
-    fn label(self, lang: EngineLanguage) -> &'static str {
-        match (lang, self) {
-            (EngineLanguage::Pt, Self::Forward) => "Mover Frente",
-            (EngineLanguage::Pt, Self::Backward) => "Mover Tras",
-            (EngineLanguage::Pt, Self::Left) => "Mover Esquerda",
-            (EngineLanguage::Pt, Self::Right) => "Mover Direita",
-            (EngineLanguage::Pt, Self::Jump) => "Pular",
-            (EngineLanguage::Pt, Self::Interact) => "Interagir",
-            (EngineLanguage::Pt, Self::Action1) => "Acao 1",
-            (EngineLanguage::Pt, Self::Action2) => "Acao 2",
-            (EngineLanguage::En, Self::Forward) => "Move Forward",
-            (EngineLanguage::En, Self::Backward) => "Move Backward",
-            (EngineLanguage::En, Self::Left) => "Move Left",
-            (EngineLanguage::En, Self::Right) => "Move Right",
-            (EngineLanguage::En, Self::Jump) => "Jump",
-            (EngineLanguage::En, Self::Interact) => "Interact",
-            (EngineLanguage::En, Self::Action1) => "Action 1",
-            (EngineLanguage::En, Self::Action2) => "Action 2",
-            (EngineLanguage::Es, Self::Forward) => "Mover Adelante",
-            (EngineLanguage::Es, Self::Backward) => "Mover Atras",
-            (EngineLanguage::Es, Self::Left) => "Mover Izquierda",
-            (EngineLanguage::Es, Self::Right) => "Mover Derecha",
-            (EngineLanguage::Es, Self::Jump) => "Saltar",
-            (EngineLanguage::Es, Self::Interact) => "Interactuar",
-            (EngineLanguage::Es, Self::Action1) => "Accion 1",
-            (EngineLanguage::Es, Self::Action2) => "Accion 2",
+    fn key(self) -> &'static str {
+        match self {
+            Self::Forward => "forward",
+            Self::Backward => "backward",
+            Self::Left => "left",
+            Self::Right => "right",
+            Self::Jump => "jump",
+            Self::Interact => "interact",
+            Self::Action1 => "action1",
+            Self::Action2 => "action2",
         }
     }
 
+    fn label(self, lang: EngineLanguage) -> &'static str {
+        tr(lang, self.key())
+    }
+
     fn label_for_mode(self, lang: EngineLanguage, mode: FiosControlMode) -> &'static str {
         match mode {
             FiosControlMode::Movement => self.label(lang),
-            FiosControlMode::Animation => match (lang, self) {
-                (EngineLanguage::Pt, Self::Forward) => "Animacao Anterior",
-                (EngineLanguage::Pt, Self::Backward) => "Proxima Animacao",
-                (EngineLanguage::Pt, Self::Left) => "Frame -",
-                (EngineLanguage::Pt, Self::Right) => "Frame +",
-                (EngineLanguage::Pt, Self::Jump) => "Play/Pause",
-                (EngineLanguage::Pt, Self::Interact) => "Stop",
-                (EngineLanguage::Pt, Self::Action1) => "Blend +",
-                (EngineLanguage::Pt, Self::Action2) => "Blend -",
-                (EngineLanguage::En, Self::Forward) => "Previous Anim",
-                (EngineLanguage::En, Self::Backward) => "Next Anim",
-                (EngineLanguage::En, Self::Left) => "Frame -",
-                (EngineLanguage::En, Self::Right) => "Frame +",
-                (EngineLanguage::En, Self::Jump) => "Play/Pause",
-                (EngineLanguage::En, Self::Interact) => "Stop",
-                (EngineLanguage::En, Self::Action1) => "Blend +",
-                (EngineLanguage::En, Self::Action2) => "Blend -",
-                (EngineLanguage::Es, Self::Forward) => "Animacion Anterior",
-                (EngineLanguage::Es, Self::Backward) => "Siguiente Animacion",
-                (EngineLanguage::Es, Self::Left) => "Frame -",
-                (EngineLanguage::Es, Self::Right) => "Frame +",
-                (EngineLanguage::Es, Self::Jump) => "Play/Pause",
-                (EngineLanguage::Es, Self::Interact) => "Stop",
-                (EngineLanguage::Es, Self::Action1) => "Blend +",
-                (EngineLanguage::Es, Self::Action2) => "Blend -",
-            },
+            FiosControlMode::Animation => i18n::text(lang, "fios_animation", self.key()),
         }
     }
 }
@@ -157,6 +129,7 @@ enum FiosNodeKind {
     Deadzone,
     Invert,
     Smooth,
+    Curve,
     OutputMove,
     OutputLook,
     OutputAction,
@@ -189,6 +162,7 @@ impl FiosNodeKind {
             Self::Deadzone => "deadzone",
             Self::Invert => "invert",
             Self::Smooth => "smooth",
+            Self::Curve => "curve",
             Self::OutputMove => "output_move",
             Self::OutputLook => "output_look",
             Self::OutputAction => "output_action",
@@ -214,6 +188,7 @@ impl FiosNodeKind {
             "deadzone" => Self::Deadzone,
             "invert" => Self::Invert,
             "smooth" => Self::Smooth,
+            "curve" => Self::Curve,
             "output_move" => Self::OutputMove,
             "output_look" => Self::OutputLook,
             "output_action" => Self::OutputAction,
@@ -240,6 +215,7 @@ impl FiosNodeKind {
             Self::Deadzone => 1,
             Self::Invert => 1,
             Self::Smooth => 1,
+            Self::Curve => 1,
             Self::OutputMove => 2,
             Self::OutputLook => 2,
             Self::OutputAction => 1,
@@ -265,6 +241,7 @@ impl FiosNodeKind {
             Self::Deadzone => 1,
             Self::Invert => 1,
             Self::Smooth => 1,
+            Self::Curve => 1,
             Self::OutputMove => 0,
             Self::OutputLook => 0,
             Self::OutputAction => 0,
@@ -289,6 +266,7 @@ impl FiosNodeKind {
             (Self::Gate, 0) => "V",
             (Self::Gate, 1) => "Gate",
             (Self::Clamp, 0) | (Self::Deadzone, 0) | (Self::Invert, 0) | (Self::Smooth, 0) => "In",
+            (Self::Curve, 0) => "In",
             (Self::Abs, 0) | (Self::Sign, 0) => "In",
             (Self::OutputMove, 0) => "X",
             (Self::OutputMove, 1) => "Y",
@@ -318,7 +296,8 @@ impl FiosNodeKind {
             | (Self::Clamp, 0)
             | (Self::Deadzone, 0)
             | (Self::Invert, 0)
-            | (Self::Smooth, 0) => "Out",
+            | (Self::Smooth, 0)
+            | (Self::Curve, 0) => "Out",
             _ => "",
         }
     }
@@ -333,6 +312,8 @@ struct FiosNode {
     value: f32,
     param_a: f32,
     param_b: f32,
+    /// Curva do nó Curva: entrada no eixo horizontal, saída no vertical
+    curve: Curve,
 }
 
 #[derive(Clone, Copy)]
@@ -382,6 +363,8 @@ pub struct FiosState {
     just_pressed: [bool; ACTION_COUNT],
     capture_index: Option<usize>,
     status: Option<String>,
+    /// Grafo em disco de uma versão mais nova: não é sobrescrito
+    graph_load_error: Option<String>,
     add_icon_texture: Option<egui::TextureHandle>,
     module_add_texture: Option<egui::TextureHandle>,
     available_modules: Vec<ModuleCategory>,
@@ -440,13 +423,9 @@ pub struct FiosState {
 
 impl FiosState {
     fn control_mode_label(mode: FiosControlMode, lang: EngineLanguage) -> &'static str {
-        match (lang, mode) {
-            (EngineLanguage::Pt, FiosControlMode::Movement) => "Movimento",
-            (EngineLanguage::Pt, FiosControlMode::Animation) => "Animacao",
-            (EngineLanguage::En, FiosControlMode::Movement) => "Movement",
-            (EngineLanguage::En, FiosControlMode::Animation) => "Animation",
-            (EngineLanguage::Es, FiosControlMode::Movement) => "Movimiento",
-            (EngineLanguage::Es, FiosControlMode::Animation) => "Animacion",
+        match mode {
+            FiosControlMode::Movement => tr(lang, "mode_movement"),
+            FiosControlMode::Animation => tr(lang, "mode_animation"),
         }
     }
 
@@ -599,16 +578,13 @@ impl FiosState {
             let module = &self.module_chain[module_idx];
             module.controls.len()
         };
-        let details_label = match lang {
-            EngineLanguage::Pt => format!("▸ Controles ({control_count})"),
-            EngineLanguage::En => format!("▸ Controls ({control_count})"),
-            EngineLanguage::Es => format!("▸ Controles ({control_count})"),
-        };
+        let details_label =
+            tr(lang, "controls_count").replace("{count}", &control_count.to_string());
         let header_id = ui.id().with(("mod_details_collapse", module_idx));
         egui::CollapsingHeader::new(
             egui::RichText::new(details_label)
                 .size(11.0)
-                .color(egui::Color32::from_gray(185)),
+                .color(theme::palette().text),
         )
         .id_salt(header_id)
         .default_open(false)
@@ -630,12 +606,12 @@ impl FiosState {
                 ui.label(
                     egui::RichText::new(format!("{key}:"))
                         .size(10.0)
-                        .color(egui::Color32::from_gray(130)),
+                        .color(theme::palette().text_weak),
                 );
                 ui.label(
                     egui::RichText::new(value)
                         .size(10.0)
-                        .color(egui::Color32::from_gray(165)),
+                        .color(theme::palette().text_weak),
                 );
             });
         }
@@ -649,42 +625,22 @@ impl FiosState {
     ) {
         let module = &mut self.module_chain[module_idx];
         if module.controls.is_empty() {
-            let empty_txt = match lang {
-                EngineLanguage::Pt => "Nenhum controle disponível para este módulo",
-                EngineLanguage::En => "No editable controls for this module",
-                EngineLanguage::Es => "Ningún control editable para este módulo",
-            };
+            let empty_txt = tr(lang, "no_controls");
             ui.label(
                 egui::RichText::new(empty_txt)
                     .small()
-                    .color(egui::Color32::from_gray(150)),
+                    .color(theme::palette().text_weak),
             );
             return;
         }
-        let label_txt = match lang {
-            EngineLanguage::Pt => "Controle",
-            EngineLanguage::En => "Control",
-            EngineLanguage::Es => "Control",
-        };
-        let value_txt = match lang {
-            EngineLanguage::Pt => "Valor",
-            EngineLanguage::En => "Value",
-            EngineLanguage::Es => "Valor",
-        };
-        let param_a_txt = match lang {
-            EngineLanguage::Pt => "Parâmetro A",
-            EngineLanguage::En => "Param A",
-            EngineLanguage::Es => "Parámetro A",
-        };
-        let param_b_txt = match lang {
-            EngineLanguage::Pt => "Parâmetro B",
-            EngineLanguage::En => "Param B",
-            EngineLanguage::Es => "Parámetro B",
-        };
+        let label_txt = tr(lang, "control");
+        let value_txt = tr(lang, "value");
+        let param_a_txt = tr(lang, "param_a");
+        let param_b_txt = tr(lang, "param_b");
         let grid_id = format!("module_controls_grid_{}", module.id);
         egui::Frame::new()
-            .fill(egui::Color32::from_rgb(22, 24, 28))
-            .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(70, 76, 90)))
+            .fill(theme::palette().panel_header)
+            .stroke(egui::Stroke::new(1.0, theme::palette().border))
             .corner_radius(8.0)
             .inner_margin(egui::Margin::symmetric(10, 10))
             .show(ui, |ui| {
@@ -701,29 +657,29 @@ impl FiosState {
                                 ui.label(
                                     egui::RichText::new(label_txt)
                                         .strong()
-                                        .color(egui::Color32::from_gray(220)),
+                                        .color(theme::palette().text_strong),
                                 );
                                 ui.label(
                                     egui::RichText::new(value_txt)
                                         .strong()
-                                        .color(egui::Color32::from_gray(220)),
+                                        .color(theme::palette().text_strong),
                                 );
                                 ui.label(
                                     egui::RichText::new(param_a_txt)
                                         .strong()
-                                        .color(egui::Color32::from_gray(220)),
+                                        .color(theme::palette().text_strong),
                                 );
                                 ui.label(
                                     egui::RichText::new(param_b_txt)
                                         .strong()
-                                        .color(egui::Color32::from_gray(220)),
+                                        .color(theme::palette().text_strong),
                                 );
                                 ui.end_row();
                                 for control in &mut module.controls {
                                     ui.label(
                                         egui::RichText::new(&control.name)
                                             .small()
-                                            .color(egui::Color32::from_gray(210)),
+                                            .color(theme::palette().text),
                                     );
                                     ui.add(
                                         egui::DragValue::new(&mut control.value)
@@ -762,45 +718,27 @@ impl FiosState {
         lang: EngineLanguage,
         bindings: &[egui::Key; ACTION_COUNT],
     ) {
-        let key_map_title = match lang {
-            EngineLanguage::Pt => "Mapa de teclas",
-            EngineLanguage::En => "Key map",
-            EngineLanguage::Es => "Mapa de teclas",
-        };
-        let action_col = match lang {
-            EngineLanguage::Pt => "Ação",
-            EngineLanguage::En => "Action",
-            EngineLanguage::Es => "Acción",
-        };
-        let key_col = match lang {
-            EngineLanguage::Pt => "Tecla",
-            EngineLanguage::En => "Key",
-            EngineLanguage::Es => "Tecla",
-        };
+        let key_map_title = tr(lang, "key_map");
+        let action_col = tr(lang, "action");
+        let key_col = tr(lang, "key");
         egui::Frame::new()
-            .fill(egui::Color32::from_rgb(22, 24, 28))
-            .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(70, 76, 90)))
+            .fill(theme::palette().panel_header)
+            .stroke(egui::Stroke::new(1.0, theme::palette().border))
             .corner_radius(8.0)
             .inner_margin(egui::Margin::same(10))
             .show(ui, |ui| {
                 ui.label(
                     egui::RichText::new(key_map_title)
                         .strong()
-                        .color(egui::Color32::from_gray(210)),
+                        .color(theme::palette().text),
                 );
                 ui.add_space(4.0);
 
-                let instructions = match lang {
-                    EngineLanguage::Pt => "Clique no botão da tecla para editar diretamente:",
-                    EngineLanguage::En => "Click the key button to edit directly:",
-                    EngineLanguage::Es => {
-                        "Haga clic en el botón de la tecla para editar directamente:"
-                    }
-                };
+                let instructions = tr(lang, "key_edit_hint");
                 ui.label(
                     egui::RichText::new(instructions)
                         .small()
-                        .color(egui::Color32::from_rgb(200, 200, 200)),
+                        .color(theme::palette().text),
                 );
                 ui.add_space(6.0);
 
@@ -813,14 +751,14 @@ impl FiosState {
                             egui::RichText::new(action_col)
                                 .small()
                                 .strong()
-                                .color(egui::Color32::from_gray(210)),
+                                .color(theme::palette().text),
                         );
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                             ui.label(
                                 egui::RichText::new(key_col)
                                     .small()
                                     .strong()
-                                    .color(egui::Color32::from_gray(210)),
+                                    .color(theme::palette().text),
                             )
                         });
                         ui.end_row();
@@ -829,7 +767,7 @@ impl FiosState {
                             ui.label(
                                 egui::RichText::new(action.label(lang))
                                     .small()
-                                    .color(egui::Color32::from_gray(200)),
+                                    .color(theme::palette().text),
                             );
 
                             ui.with_layout(
@@ -841,28 +779,13 @@ impl FiosState {
                                     let button_response = ui.add_sized(
                                         [120.0, 20.0],
                                         egui::Button::new(button_text)
-                                            .stroke(egui::Stroke::new(
-                                                1.0,
-                                                egui::Color32::from_rgb(100, 150, 200),
-                                            ))
-                                            .fill(egui::Color32::from_rgba_unmultiplied(
-                                                50, 75, 110, 180,
-                                            )),
+                                            .stroke(egui::Stroke::new(1.0, theme::palette().border))
+                                            .fill(theme::palette().widget_bg),
                                     );
 
                                     if button_response.clicked() {
                                         self.capture_index = Some(i);
-                                        self.status = match lang {
-                                            EngineLanguage::Pt => {
-                                                Some("Aguardando tecla...".to_string())
-                                            }
-                                            EngineLanguage::En => {
-                                                Some("Waiting for key...".to_string())
-                                            }
-                                            EngineLanguage::Es => {
-                                                Some("Esperando tecla...".to_string())
-                                            }
-                                        };
+                                        self.status = Some(tr(lang, "waiting_for_key").to_string());
                                     }
                                 },
                             );
@@ -883,11 +806,7 @@ impl FiosState {
 
     fn module_menu_content(&mut self, ui: &mut egui::Ui, lang: EngineLanguage) -> bool {
         if self.available_modules.is_empty() {
-            let no_modules_txt = match lang {
-                EngineLanguage::Pt => "Nenhum módulo disponível",
-                EngineLanguage::En => "No modules available",
-                EngineLanguage::Es => "No hay módulos disponibles",
-            };
+            let no_modules_txt = tr(lang, "no_modules_available");
             ui.label(no_modules_txt);
             return false;
         }
@@ -900,7 +819,7 @@ impl FiosState {
                 egui::RichText::new(&category.name)
                     .size(11.0)
                     .strong()
-                    .color(egui::Color32::from_gray(200)),
+                    .color(theme::palette().text),
                 |ui| {
                     ui.set_min_width(208.0);
                     for module in &category.modules {
@@ -911,7 +830,7 @@ impl FiosState {
                                     .size(12.0),
                             )
                             .frame(false)
-                            .fill(egui::Color32::from_rgba_unmultiplied(80, 80, 90, 200)),
+                            .fill(theme::palette().widget_bg),
                         );
                         if btn.clicked() {
                             selected_asset = Some(module.asset.clone());
@@ -922,7 +841,7 @@ impl FiosState {
                             ui.label(
                                 egui::RichText::new(desc)
                                     .small()
-                                    .color(egui::Color32::from_gray(150)),
+                                    .color(theme::palette().text_weak),
                             );
                         }
                         ui.add_space(4.0);
@@ -945,9 +864,9 @@ impl FiosState {
     fn module_add_button(&mut self, ui: &mut egui::Ui, label: &str) -> egui::Response {
         if self.module_add_texture.is_none() {
             self.module_add_texture =
-                Self::load_png_texture(ui.ctx(), "src/assets/icons/addmodulo.png");
+                Self::load_png_texture(ui.ctx(), "Editor/icons/addmodulo.png");
         }
-        let accent = egui::Color32::from_rgb(15, 232, 121);
+        let accent = theme::palette().accent;
         let button = if let Some(texture) = &self.module_add_texture {
             let icon = egui::Image::new(texture).fit_to_exact_size(egui::vec2(14.0, 14.0));
             egui::Button::image_and_text(
@@ -957,18 +876,15 @@ impl FiosState {
         } else {
             egui::Button::new(egui::RichText::new(label).size(11.5).strong().color(accent))
         }
-        .fill(egui::Color32::from_rgba_unmultiplied(15, 232, 121, 18))
-        .stroke(egui::Stroke::new(
-            1.0,
-            egui::Color32::from_rgba_unmultiplied(15, 232, 121, 60),
-        ))
+        .fill(accent.gamma_multiply(0.07))
+        .stroke(egui::Stroke::new(1.0, accent.gamma_multiply(0.24)))
         .corner_radius(16.0)
         .min_size(egui::vec2(130.0, 30.0));
         ui.add(button)
     }
 
     fn load_png_texture(ctx: &egui::Context, path: &str) -> Option<egui::TextureHandle> {
-        let bytes = fs::read(path).ok()?;
+        let bytes = engine_render::vfs_read(path).ok()?;
         let rgba = image::load_from_memory(&bytes).ok()?.to_rgba8();
         let size = [rgba.width() as usize, rgba.height() as usize];
         let color_image = egui::ColorImage::from_rgba_unmultiplied(size, rgba.as_raw());
@@ -993,6 +909,7 @@ impl FiosState {
             FiosNodeKind::Deadzone => "Deadzone",
             FiosNodeKind::Invert => "Invert",
             FiosNodeKind::Smooth => "Smooth",
+            FiosNodeKind::Curve => "Curve",
             FiosNodeKind::OutputMove => "Output Move",
             FiosNodeKind::OutputLook => "Output Look",
             FiosNodeKind::OutputAction => "Output Action",
@@ -1089,7 +1006,7 @@ impl FiosState {
             let path = path
                 .map(PathBuf::from)
                 .unwrap_or_else(|| PathBuf::from("Assets").join("Meshes").join(asset_name));
-            let clips = fs::read(&path)
+            let clips = engine_render::vfs_read(&path)
                 .ok()
                 .map(|bytes| Self::parse_fbx_animation_names(&String::from_utf8_lossy(&bytes)))
                 .unwrap_or_default();
@@ -1321,6 +1238,7 @@ impl FiosState {
             just_pressed: [false; ACTION_COUNT],
             capture_index: None,
             status: None,
+            graph_load_error: None,
             add_icon_texture: None,
             module_add_texture: None,
             available_modules: Vec::new(),
@@ -1378,9 +1296,17 @@ impl FiosState {
         };
         out.load_from_disk();
         out.load_lua_script_from_disk();
-        if !out.load_graph_from_disk() {
-            out.init_default_graph();
-            let _ = out.save_graph_to_disk();
+        match out.load_graph_from_disk() {
+            Ok(true) => {}
+            Ok(false) => {
+                out.init_default_graph();
+                let _ = out.save_graph_to_disk();
+            }
+            Err(e) => {
+                out.init_default_graph();
+                out.status = Some(e.clone());
+                out.graph_load_error = Some(e);
+            }
         }
         out
     }
@@ -1399,6 +1325,7 @@ impl FiosState {
             value: 0.0,
             param_a: 0.0,
             param_b: 0.0,
+            curve: Curve::default(),
         });
         self.nodes.push(FiosNode {
             id: output_id,
@@ -1408,6 +1335,7 @@ impl FiosState {
             value: 0.0,
             param_a: 0.0,
             param_b: 0.0,
+            curve: Curve::default(),
         });
         self.links.push(FiosLink {
             from_node: input_id,
@@ -1633,12 +1561,15 @@ impl FiosState {
     }
 
     fn save_graph_to_disk(&self) -> Result<(), String> {
+        if let Some(e) = &self.graph_load_error {
+            return Err(e.clone());
+        }
         let mut out = String::new();
-        out.push_str("version=1\n");
+        out.push_str(&FormatKind::Graph.header());
         out.push_str(&format!("next_node_id={}\n", self.next_node_id));
         for n in &self.nodes {
             out.push_str(&format!(
-                "node={}|{}|{}|{}|{}|{}|{}|{}\n",
+                "node={}|{}|{}|{}|{}|{}|{}|{}|{}\n",
                 n.id,
                 n.kind.id(),
                 n.pos.x,
@@ -1646,7 +1577,8 @@ impl FiosState {
                 n.value,
                 n.param_a,
                 n.param_b,
-                Self::encode_field(&n.display_name)
+                Self::encode_field(&n.display_name),
+                n.curve.to_text()
             ));
         }
         for l in &self.links {
@@ -1676,10 +1608,11 @@ impl FiosState {
         fs::write(Self::graph_path(), out).map_err(|e| e.to_string())
     }
 
-    fn load_graph_from_disk(&mut self) -> bool {
+    fn load_graph_from_disk(&mut self) -> Result<bool, String> {
         let Ok(raw) = fs::read_to_string(Self::graph_path()) else {
-            return false;
+            return Ok(false);
         };
+        let raw = migrate_format(FormatKind::Graph, &raw)?.text;
         let mut parsed_nodes = Vec::<FiosNode>::new();
         let mut parsed_links = Vec::<FiosLink>::new();
         let mut parsed_groups = Vec::<FiosGroup>::new();
@@ -1729,6 +1662,10 @@ impl FiosState {
                     } else {
                         Self::default_node_name(kind).to_string()
                     };
+                    let curve = match seg.get(8) {
+                        Some(text) => Curve::parse(text).unwrap_or_default(),
+                        None => Self::default_node_curve(kind),
+                    };
                     parsed_nodes.push(FiosNode {
                         id,
                         kind,
@@ -1737,6 +1674,7 @@ impl FiosState {
                         value,
                         param_a,
                         param_b,
+                        curve,
                     });
                 }
                 "link" => {
@@ -1798,7 +1736,7 @@ impl FiosState {
             }
         }
         if parsed_nodes.is_empty() {
-            return false;
+            return Ok(false);
         }
         self.nodes = parsed_nodes;
         self.links = parsed_links;
@@ -1826,10 +1764,10 @@ impl FiosState {
         self.rename_node = None;
         self.rename_buffer.clear();
         self.smooth_state.clear();
-        true
+        Ok(true)
     }
 
-    pub fn update_input(&mut self, ctx: &egui::Context) {
+    pub fn update_input(&mut self, ctx: &egui::Context, input: &Input) {
         self.controls_enabled = true;
         if !self.controls_enabled {
             self.pressed = [false; ACTION_COUNT];
@@ -1843,7 +1781,7 @@ impl FiosState {
             return;
         }
         for i in 0..ACTION_COUNT {
-            let down = ctx.input(|inp| inp.key_down(self.bindings[i]));
+            let down = input.key_pressed(self.bindings[i].name());
             self.just_pressed[i] = down && !self.pressed[i];
             self.pressed[i] = down;
         }
@@ -2536,6 +2474,22 @@ impl FiosState {
                     smooth_state.insert(key, v);
                     v
                 }
+                FiosNodeKind::Curve => {
+                    let v = Self::eval_input_of_node(
+                        nodes,
+                        links,
+                        smooth_state,
+                        pressed,
+                        just_pressed,
+                        node_id,
+                        0,
+                        0.0,
+                        base_axis,
+                        cache,
+                        stack,
+                    );
+                    node.curve.evaluate(v).unwrap_or(v)
+                }
                 FiosNodeKind::OutputMove
                 | FiosNodeKind::OutputLook
                 | FiosNodeKind::OutputAction
@@ -2580,6 +2534,7 @@ impl FiosState {
             | FiosNodeKind::Deadzone
             | FiosNodeKind::Invert
             | FiosNodeKind::Smooth => egui::vec2(180.0, 94.0),
+            FiosNodeKind::Curve => egui::vec2(220.0, 150.0),
             FiosNodeKind::OutputMove | FiosNodeKind::OutputLook => egui::vec2(190.0, 88.0),
             FiosNodeKind::OutputAction | FiosNodeKind::OutputAnimCommand => egui::vec2(170.0, 74.0),
         }
@@ -2597,6 +2552,16 @@ impl FiosState {
         egui::pos2(rect.right() - 4.0, y)
     }
 
+    /// Nó Curva novo: identidade de -1 a 1, a faixa dos eixos de entrada
+    fn default_node_curve(kind: FiosNodeKind) -> Curve {
+        match kind {
+            FiosNodeKind::Curve => {
+                Curve::from_points([(-1.0, -1.0), (1.0, 1.0)], TangentMode::Linear)
+            }
+            _ => Curve::default(),
+        }
+    }
+
     fn add_node(&mut self, kind: FiosNodeKind) {
         let id = self.alloc_node_id();
         let slot = (id % 6) as f32;
@@ -2616,6 +2581,7 @@ impl FiosState {
             value,
             param_a,
             param_b,
+            curve: Self::default_node_curve(kind),
         });
         self.selected_node = Some(id);
         self.selected_nodes.clear();
@@ -2640,6 +2606,7 @@ impl FiosState {
             value,
             param_a,
             param_b,
+            curve: Self::default_node_curve(kind),
         });
         id
     }
@@ -3192,6 +3159,7 @@ impl FiosState {
             deadzone_txt,
             invert_txt,
             smooth_txt,
+            curve_txt,
             output_move_txt,
             output_look_txt,
             output_action_txt,
@@ -3210,130 +3178,57 @@ impl FiosState {
             module_jump_txt,
             actions_txt,
             del_txt,
-        ) = match lang {
-            EngineLanguage::Pt => (
-                "Entrada Eixo",
-                "Entrada Ação",
-                "Constante",
-                "Somar",
-                "Subtrair",
-                "Multiplicar",
-                "Dividir",
-                "Máximo",
-                "Mínimo",
-                "Portão",
-                "Absoluto",
-                "Sinal",
-                "Limitar",
-                "Zona Morta",
-                "Inverter",
-                "Suavizar",
-                "Saída Mover",
-                "Saída Olhar",
-                "Saída Ação",
-                "Saída Cmd Anim",
-                "Selecionado(s)",
-                "Nenhum",
-                "Renomear",
-                "Aplicar Nome",
-                "Add Bloco",
-                "Módulos",
-                "Locomoção Básica",
-                "Locomoção Avançada",
-                "Look Básico",
-                "Look Avançado",
-                "Ação 1 Básica",
-                "Pulo Básico",
-                "Ações",
-                "Excluir Selecionado",
-            ),
-            EngineLanguage::En => (
-                "Input Axis",
-                "Input Action",
-                "Constant",
-                "Add",
-                "Subtract",
-                "Multiply",
-                "Divide",
-                "Max",
-                "Min",
-                "Gate",
-                "Abs",
-                "Sign",
-                "Clamp",
-                "Deadzone",
-                "Invert",
-                "Smooth",
-                "Output Move",
-                "Output Look",
-                "Output Action",
-                "Output Anim Cmd",
-                "Selected",
-                "None",
-                "Rename",
-                "Apply Name",
-                "Add Block",
-                "Modules",
-                "Basic Locomotion",
-                "Advanced Locomotion",
-                "Basic Look",
-                "Advanced Look",
-                "Basic Action 1",
-                "Basic Jump",
-                "Actions",
-                "Delete Selected",
-            ),
-            EngineLanguage::Es => (
-                "Entrada Eje",
-                "Entrada Accion",
-                "Constante",
-                "Sumar",
-                "Restar",
-                "Multiplicar",
-                "Dividir",
-                "Maximo",
-                "Minimo",
-                "Compuerta",
-                "Absoluto",
-                "Signo",
-                "Limitar",
-                "Zona Muerta",
-                "Invertir",
-                "Suavizar",
-                "Salida Mover",
-                "Salida Mirar",
-                "Salida Accion",
-                "Salida Cmd Anim",
-                "Seleccionado(s)",
-                "Ninguno",
-                "Renombrar",
-                "Aplicar Nombre",
-                "Agregar Bloque",
-                "Modulos",
-                "Locomocion Basica",
-                "Locomocion Avanzada",
-                "Look Basico",
-                "Look Avanzado",
-                "Accion 1 Basica",
-                "Salto Basico",
-                "Acciones",
-                "Eliminar Seleccionado",
-            ),
-        };
+        ) = (
+            tr(lang, "input_axis"),
+            tr(lang, "input_action"),
+            tr(lang, "constant"),
+            tr(lang, "add"),
+            tr(lang, "subtract"),
+            tr(lang, "multiply"),
+            tr(lang, "divide"),
+            tr(lang, "max"),
+            tr(lang, "min"),
+            tr(lang, "gate"),
+            tr(lang, "abs"),
+            tr(lang, "sign"),
+            tr(lang, "clamp"),
+            tr(lang, "deadzone"),
+            tr(lang, "invert"),
+            tr(lang, "smooth"),
+            tr(lang, "curve"),
+            tr(lang, "output_move"),
+            tr(lang, "output_look"),
+            tr(lang, "output_action"),
+            tr(lang, "output_anim_cmd"),
+            tr(lang, "selected"),
+            tr(lang, "none"),
+            tr(lang, "rename"),
+            tr(lang, "apply_name"),
+            tr(lang, "add_block"),
+            tr(lang, "modules"),
+            tr(lang, "module_move"),
+            tr(lang, "module_move_adv"),
+            tr(lang, "module_look"),
+            tr(lang, "module_look_adv"),
+            tr(lang, "module_action1"),
+            tr(lang, "module_jump"),
+            tr(lang, "actions"),
+            tr(lang, "delete_selected"),
+        );
 
         ui.group(|ui| {
             ui.horizontal_wrapped(|ui| {
                 ui.label(
                     egui::RichText::new(add_block_txt)
                         .strong()
-                        .color(egui::Color32::from_gray(220)),
+                        .color(theme::palette().text_strong),
                 );
                 ui.separator();
                 ui.menu_button(
                     egui::RichText::new(add_block_txt)
                         .strong()
-                        .color(egui::Color32::from_rgb(20, 24, 20))
-                        .background_color(egui::Color32::from_rgb(15, 232, 121)),
+                        .color(theme::palette().panel_bg)
+                        .background_color(theme::palette().accent),
                     |ui| {
                         if ui.button(input_axis_txt).clicked() {
                             self.add_node(FiosNodeKind::InputAxis);
@@ -3399,6 +3294,10 @@ impl FiosState {
                             self.add_node(FiosNodeKind::Smooth);
                             ui.close();
                         }
+                        if ui.button(curve_txt).clicked() {
+                            self.add_node(FiosNodeKind::Curve);
+                            ui.close();
+                        }
                         if ui.button(output_move_txt).clicked() {
                             self.add_node(FiosNodeKind::OutputMove);
                             ui.close();
@@ -3443,12 +3342,12 @@ impl FiosState {
                         "{actions_txt}  |  {selected_txt}: {selected_text}"
                     ))
                     .strong()
-                    .color(egui::Color32::from_gray(220)),
+                    .color(theme::palette().text_strong),
                 );
                 if ui
                     .add_sized(
                         egui::vec2(140.0, 26.0),
-                        egui::Button::new(del_txt).fill(egui::Color32::from_rgb(128, 72, 78)),
+                        egui::Button::new(del_txt).fill(theme::palette().error.gamma_multiply(0.6)),
                     )
                     .clicked()
                     && self.remove_selected_nodes()
@@ -3497,11 +3396,11 @@ impl FiosState {
         let (canvas_rect, canvas_resp) =
             ui.allocate_exact_size(canvas_size, egui::Sense::click_and_drag());
         let painter = ui.painter_at(canvas_rect);
-        painter.rect_filled(canvas_rect, 6.0, egui::Color32::from_rgb(21, 22, 24));
+        painter.rect_filled(canvas_rect, 6.0, theme::palette().graph_bg);
         painter.rect_stroke(
             canvas_rect,
             6.0,
-            egui::Stroke::new(1.0, egui::Color32::from_rgb(64, 66, 72)),
+            egui::Stroke::new(1.0, theme::palette().graph_border),
             egui::StrokeKind::Outside,
         );
 
@@ -3559,7 +3458,7 @@ impl FiosState {
                     egui::pos2(x, canvas_rect.top()),
                     egui::pos2(x, canvas_rect.bottom()),
                 ],
-                egui::Stroke::new(0.5, egui::Color32::from_rgb(30, 31, 36)),
+                egui::Stroke::new(0.5, theme::palette().graph_grid),
             );
             x += grid;
         }
@@ -3570,7 +3469,7 @@ impl FiosState {
                     egui::pos2(canvas_rect.left(), y),
                     egui::pos2(canvas_rect.right(), y),
                 ],
-                egui::Stroke::new(0.5, egui::Color32::from_rgb(30, 31, 36)),
+                egui::Stroke::new(0.5, theme::palette().graph_grid),
             );
             y += grid;
         }
@@ -3678,36 +3577,12 @@ impl FiosState {
         let mut do_group = false;
         let mut quick_color: Option<egui::Color32> = None;
         canvas_resp.context_menu(|ui| {
-            let add_block_menu_txt = match lang {
-                EngineLanguage::Pt => "Add Bloco",
-                EngineLanguage::En => "Add Block",
-                EngineLanguage::Es => "Agregar Bloque",
-            };
-            let input_txt = match lang {
-                EngineLanguage::Pt => "Entradas",
-                EngineLanguage::En => "Inputs",
-                EngineLanguage::Es => "Entradas",
-            };
-            let math_txt = match lang {
-                EngineLanguage::Pt => "Matematica",
-                EngineLanguage::En => "Math",
-                EngineLanguage::Es => "Matematica",
-            };
-            let out_txt = match lang {
-                EngineLanguage::Pt => "Saida",
-                EngineLanguage::En => "Output",
-                EngineLanguage::Es => "Salida",
-            };
-            let group_txt = match lang {
-                EngineLanguage::Pt => "Agrupar Selecionados",
-                EngineLanguage::En => "Group Selected",
-                EngineLanguage::Es => "Agrupar Seleccionados",
-            };
-            let color_txt = match lang {
-                EngineLanguage::Pt => "Cor Rapida do Grupo",
-                EngineLanguage::En => "Quick Group Color",
-                EngineLanguage::Es => "Color Rapido del Grupo",
-            };
+            let add_block_menu_txt = tr(lang, "add_block");
+            let input_txt = tr(lang, "inputs");
+            let math_txt = tr(lang, "math");
+            let out_txt = tr(lang, "output");
+            let group_txt = tr(lang, "group_selected");
+            let color_txt = tr(lang, "quick_group_color");
             ui.menu_button(add_block_menu_txt, |ui| {
                 ui.menu_button(input_txt, |ui| {
                     if ui.button(input_axis_txt).clicked() {
@@ -3776,6 +3651,10 @@ impl FiosState {
                         self.add_node(FiosNodeKind::Smooth);
                         ui.close();
                     }
+                    if ui.button(curve_txt).clicked() {
+                        self.add_node(FiosNodeKind::Curve);
+                        ui.close();
+                    }
                 });
                 ui.menu_button(out_txt, |ui| {
                     if ui.button(output_move_txt).clicked() {
@@ -3832,7 +3711,7 @@ impl FiosState {
                         .add(
                             egui::Button::new(label)
                                 .fill(c)
-                                .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(30))),
+                                .stroke(egui::Stroke::new(1.0, theme::palette().border)),
                         )
                         .clicked()
                     {
@@ -3937,7 +3816,7 @@ impl FiosState {
             painter.circle_stroke(
                 color_btn_rect.center(),
                 5.0,
-                egui::Stroke::new(1.0, egui::Color32::from_gray(25)),
+                egui::Stroke::new(1.0, theme::palette().border),
             );
             egui::Popup::menu(&color_resp)
                 .id(ui.id().with(("fios_group_color_popup", group_id)))
@@ -3995,7 +3874,7 @@ impl FiosState {
             }
             painter.add(egui::Shape::line(
                 pts.clone(),
-                egui::Stroke::new(2.0, egui::Color32::from_rgb(15, 232, 121)),
+                egui::Stroke::new(2.0, theme::palette().accent),
             ));
             link_curves.push((link_idx, pts));
         }
@@ -4084,7 +3963,7 @@ impl FiosState {
         if !self.cut_points.is_empty() {
             painter.add(egui::Shape::line(
                 self.cut_points.clone(),
-                egui::Stroke::new(2.0, egui::Color32::from_rgb(245, 100, 100)),
+                egui::Stroke::new(2.0, theme::palette().error),
             ));
         }
 
@@ -4148,16 +4027,16 @@ impl FiosState {
                 graph_dirty = true;
             }
             let is_selected = self.selected_nodes.contains(&node.id);
-            painter.rect_filled(rect, 6.0, egui::Color32::from_rgb(37, 37, 40));
+            painter.rect_filled(rect, 6.0, theme::palette().panel_header);
             painter.rect_stroke(
                 rect,
                 6.0,
                 egui::Stroke::new(
                     if is_selected { 2.0 } else { 1.0 },
                     if is_selected {
-                        egui::Color32::from_rgb(15, 232, 121)
+                        theme::palette().accent
                     } else {
-                        egui::Color32::from_rgb(78, 78, 86)
+                        theme::palette().graph_border
                     },
                 ),
                 egui::StrokeKind::Outside,
@@ -4167,7 +4046,7 @@ impl FiosState {
                 egui::Align2::LEFT_TOP,
                 &node.display_name,
                 egui::FontId::proportional(12.0),
-                egui::Color32::from_gray(220),
+                theme::palette().text_strong,
             );
 
             if node.kind == FiosNodeKind::Constant {
@@ -4291,6 +4170,22 @@ impl FiosState {
                     });
                 });
             }
+            if node.kind == FiosNodeKind::Curve {
+                let area = egui::Rect::from_min_max(
+                    rect.left_top() + egui::vec2(10.0, 28.0),
+                    rect.right_bottom() - egui::vec2(10.0, 8.0),
+                );
+                ui.scope_builder(egui::UiBuilder::new().max_rect(area), |ui| {
+                    if CurveEditor::new(("fios_curve", node.id), &mut node.curve, lang)
+                        .height(area.height())
+                        .time_range(-1.0..=1.0)
+                        .show(ui)
+                        .changed()
+                    {
+                        graph_dirty = true;
+                    }
+                });
+            }
             if node.kind == FiosNodeKind::Smooth {
                 let r1 = egui::Rect::from_min_size(
                     rect.left_top() + egui::vec2(8.0, 34.0),
@@ -4319,7 +4214,7 @@ impl FiosState {
                     egui::Align2::LEFT_TOP,
                     format!("X: {:.2}  Y: {:.2}", self.last_axis[0], self.last_axis[1]),
                     egui::FontId::monospace(11.0),
-                    egui::Color32::from_gray(190),
+                    theme::palette().text,
                 );
             }
             if node.kind == FiosNodeKind::OutputLook {
@@ -4331,7 +4226,7 @@ impl FiosState {
                         self.last_look[0], self.last_look[1]
                     ),
                     egui::FontId::monospace(11.0),
-                    egui::Color32::from_gray(190),
+                    theme::palette().text,
                 );
             }
             if node.kind == FiosNodeKind::OutputAction {
@@ -4340,7 +4235,7 @@ impl FiosState {
                     egui::Align2::LEFT_TOP,
                     format!("A: {:.2}", self.last_action),
                     egui::FontId::monospace(11.0),
-                    egui::Color32::from_gray(190),
+                    theme::palette().text,
                 );
             }
             if node.kind == FiosNodeKind::OutputAnimCommand {
@@ -4349,7 +4244,7 @@ impl FiosState {
                     egui::Align2::LEFT_TOP,
                     format!("Cmd: {:.2}", self.last_anim_cmd_signal),
                     egui::FontId::monospace(11.0),
-                    egui::Color32::from_gray(190),
+                    theme::palette().text,
                 );
             }
 
@@ -4361,7 +4256,7 @@ impl FiosState {
                     egui::Align2::LEFT_TOP,
                     node.kind.input_name(i),
                     egui::FontId::proportional(10.0),
-                    egui::Color32::from_gray(170),
+                    theme::palette().text_weak,
                 );
                 let r = egui::Rect::from_center_size(p, egui::vec2(24.0, 24.0));
                 let resp = ui.interact(
@@ -4387,7 +4282,7 @@ impl FiosState {
                     egui::Align2::RIGHT_TOP,
                     node.kind.output_name(i),
                     egui::FontId::proportional(10.0),
-                    egui::Color32::from_gray(170),
+                    theme::palette().text_weak,
                 );
                 let r = egui::Rect::from_center_size(p, egui::vec2(24.0, 24.0));
                 let resp = ui.interact(
@@ -4511,26 +4406,23 @@ impl FiosState {
                     if self.wire_drag_path.len() > 1 {
                         painter.add(egui::Shape::line(
                             self.wire_drag_path.clone(),
-                            egui::Stroke::new(2.0, egui::Color32::from_rgb(15, 232, 121)),
+                            egui::Stroke::new(2.0, theme::palette().accent),
                         ));
                     } else {
                         painter.line_segment(
                             [from, mouse],
-                            egui::Stroke::new(2.0, egui::Color32::from_rgb(15, 232, 121)),
+                            egui::Stroke::new(2.0, theme::palette().accent),
                         );
                     }
                     if let Some((_, _, _, predicted_pos)) = predicted_input {
                         painter.circle_stroke(
                             predicted_pos,
                             7.0,
-                            egui::Stroke::new(1.5, egui::Color32::from_rgb(15, 232, 121)),
+                            egui::Stroke::new(1.5, theme::palette().accent),
                         );
                         painter.line_segment(
                             [mouse, predicted_pos],
-                            egui::Stroke::new(
-                                1.0,
-                                egui::Color32::from_rgba_unmultiplied(15, 232, 121, 130),
-                            ),
+                            egui::Stroke::new(1.0, theme::palette().accent.gamma_multiply(0.5)),
                         );
                     }
                 }
@@ -4600,15 +4492,11 @@ impl FiosState {
 
         if let (Some(a), Some(b)) = (self.marquee_start, self.marquee_end) {
             let r = egui::Rect::from_two_pos(a, b);
-            painter.rect_filled(
-                r,
-                0.0,
-                egui::Color32::from_rgba_unmultiplied(86, 148, 255, 24),
-            );
+            painter.rect_filled(r, 0.0, theme::palette().accent.gamma_multiply(0.1));
             painter.rect_stroke(
                 r,
                 0.0,
-                egui::Stroke::new(1.0, egui::Color32::from_rgb(86, 148, 255)),
+                egui::Stroke::new(1.0, theme::palette().accent),
                 egui::StrokeKind::Outside,
             );
         }
@@ -4634,72 +4522,32 @@ impl FiosState {
     }
 
     fn draw_controls_tab(&mut self, ui: &mut egui::Ui, lang: EngineLanguage) {
-        let accent = egui::Color32::from_rgb(15, 232, 121);
-        let surface_0 = egui::Color32::from_rgb(22, 24, 28);
-        let surface_1 = egui::Color32::from_rgb(30, 33, 37);
-        let surface_2 = egui::Color32::from_rgb(38, 42, 48);
-        let border = egui::Color32::from_rgb(52, 58, 66);
-        let text_primary = egui::Color32::from_gray(235);
-        let text_secondary = egui::Color32::from_gray(170);
-        let text_muted = egui::Color32::from_gray(120);
-
-        let enabled_txt = match lang {
-            EngineLanguage::Pt => "Ativo",
-            EngineLanguage::En => "Enabled",
-            EngineLanguage::Es => "Activo",
-        };
-        let add_module_txt = match lang {
-            EngineLanguage::Pt => "+ Adicionar módulo",
-            EngineLanguage::En => "+ Add Module",
-            EngineLanguage::Es => "+ Agregar módulo",
-        };
-        let modules_section_txt = match lang {
-            EngineLanguage::Pt => "Módulos Ativos",
-            EngineLanguage::En => "Active Modules",
-            EngineLanguage::Es => "Módulos Activos",
-        };
-        let modes_section_txt = match lang {
-            EngineLanguage::Pt => "Modos de Controle",
-            EngineLanguage::En => "Control Modes",
-            EngineLanguage::Es => "Modos de Control",
-        };
-        let keys_section_txt = match lang {
-            EngineLanguage::Pt => "Mapa de Teclas",
-            EngineLanguage::En => "Key Map",
-            EngineLanguage::Es => "Mapa de Teclas",
-        };
-        let action_header = match lang {
-            EngineLanguage::Pt => "Ação",
-            EngineLanguage::En => "Action",
-            EngineLanguage::Es => "Acción",
-        };
-        let key_header = match lang {
-            EngineLanguage::Pt => "Tecla",
-            EngineLanguage::En => "Key",
-            EngineLanguage::Es => "Tecla",
-        };
-        let state_header = match lang {
-            EngineLanguage::Pt => "Estado",
-            EngineLanguage::En => "State",
-            EngineLanguage::Es => "Estado",
-        };
-        let save_txt = match lang {
-            EngineLanguage::Pt => "Salvar",
-            EngineLanguage::En => "Save",
-            EngineLanguage::Es => "Guardar",
-        };
-        let restore_txt = match lang {
-            EngineLanguage::Pt => "Restaurar Padrão",
-            EngineLanguage::En => "Restore Defaults",
-            EngineLanguage::Es => "Restaurar Pred.",
-        };
+        let accent = theme::palette().accent;
+        let surface_0 = theme::palette().panel_bg;
+        let surface_1 = theme::palette().panel_header;
+        let surface_2 = theme::palette().widget_bg;
+        let border = theme::palette().border;
+        let text_primary = theme::palette().text_strong;
+        let text_secondary = theme::palette().text;
+        let text_muted = theme::palette().text_weak;
+
+        let enabled_txt = tr(lang, "enabled");
+        let add_module_txt = tr(lang, "add_module");
+        let modules_section_txt = tr(lang, "active_modules");
+        let modes_section_txt = tr(lang, "control_modes");
+        let keys_section_txt = tr(lang, "key_map_section");
+        let action_header = tr(lang, "action");
+        let key_header = tr(lang, "key");
+        let state_header = tr(lang, "state");
+        let save_txt = tr(lang, "save");
+        let restore_txt = tr(lang, "restore_defaults");
 
         let bindings = self.bindings;
 
         // ─── Status banner ───
         if let Some(status) = &self.status {
             egui::Frame::new()
-                .fill(egui::Color32::from_rgba_unmultiplied(15, 232, 121, 12))
+                .fill(accent.gamma_multiply(0.05))
                 .corner_radius(6.0)
                 .inner_margin(egui::Margin::symmetric(10, 5))
                 .show(ui, |ui| {
@@ -4754,26 +4602,14 @@ impl FiosState {
                             ui.vertical_centered(|ui| {
                                 ui.label(egui::RichText::new("🔌").size(28.0));
                                 ui.add_space(8.0);
-                                let empty_txt = match lang {
-                                    EngineLanguage::Pt => "Nenhum módulo adicionado",
-                                    EngineLanguage::En => "No modules added",
-                                    EngineLanguage::Es => "Ningún módulo agregado",
-                                };
+                                let empty_txt = tr(lang, "no_modules_added");
                                 ui.label(
                                     egui::RichText::new(empty_txt)
                                         .size(12.0)
                                         .color(text_secondary),
                                 );
                                 ui.add_space(6.0);
-                                let hint_txt = match lang {
-                                    EngineLanguage::Pt => {
-                                        "Clique em \"+ Adicionar módulo\" para começar"
-                                    }
-                                    EngineLanguage::En => "Click \"+ Add Module\" to get started",
-                                    EngineLanguage::Es => {
-                                        "Haga clic en \"+ Agregar módulo\" para comenzar"
-                                    }
-                                };
+                                let hint_txt = tr(lang, "empty_modules_hint");
                                 ui.label(
                                     egui::RichText::new(hint_txt).size(10.5).color(text_muted),
                                 );
@@ -4790,14 +4626,10 @@ impl FiosState {
                     let mut modules_to_remove = Vec::new();
                     for module_idx in 0..self.module_chain.len() {
                         let is_enabled = self.module_chain[module_idx].enabled;
-                        let dot_color = if is_enabled {
-                            accent
-                        } else {
-                            egui::Color32::from_gray(70)
-                        };
+                        let dot_color = if is_enabled { accent } else { border };
                         let card_bg = if is_enabled { surface_1 } else { surface_0 };
                         let card_border = if is_enabled {
-                            egui::Color32::from_rgba_unmultiplied(15, 232, 121, 35)
+                            accent.gamma_multiply(0.14)
                         } else {
                             border
                         };
@@ -4838,7 +4670,7 @@ impl FiosState {
                                                     egui::Button::new(
                                                         egui::RichText::new("✕")
                                                             .size(10.0)
-                                                            .color(egui::Color32::from_gray(100)),
+                                                            .color(text_muted),
                                                     )
                                                     .frame(false)
                                                     .min_size(egui::vec2(20.0, 20.0))
@@ -4853,19 +4685,13 @@ impl FiosState {
                                                     ui.painter().circle_filled(
                                                         close_resp.rect.center(),
                                                         10.0,
-                                                        egui::Color32::from_rgba_unmultiplied(
-                                                            200, 60, 60, 40,
-                                                        ),
+                                                        theme::palette().error.gamma_multiply(0.16),
                                                     );
                                                 }
 
                                                 // Enable checkbox
                                                 let checkbox = ui.checkbox(&mut module.enabled, "");
-                                                checkbox.on_hover_text(match lang {
-                                                    EngineLanguage::Pt => "Ativar módulo",
-                                                    EngineLanguage::En => "Enable module",
-                                                    EngineLanguage::Es => "Activar módulo",
-                                                });
+                                                checkbox.on_hover_text(tr(lang, "enable_module"));
                                             },
                                         );
                                     });
@@ -4928,7 +4754,7 @@ impl FiosState {
                     for mode in self.control_modes.clone() {
                         let selected = self.active_control_mode == mode;
                         let pill_fill = if selected {
-                            egui::Color32::from_rgba_unmultiplied(15, 232, 121, 30)
+                            accent.gamma_multiply(0.12)
                         } else {
                             surface_2
                         };
@@ -5085,26 +4911,18 @@ impl FiosState {
 
                                     let capture = self.capture_index == Some(i);
                                     let key_text = if capture {
-                                        match lang {
-                                            EngineLanguage::Pt => "Pressione...",
-                                            EngineLanguage::En => "Press key...",
-                                            EngineLanguage::Es => "Presione...",
-                                        }
+                                        tr(lang, "press_key")
                                     } else {
                                         Self::key_to_string(self.bindings[i])
                                     };
 
                                     let key_btn = egui::Button::new(
-                                        egui::RichText::new(key_text).size(10.5).color(
-                                            if capture {
-                                                accent
-                                            } else {
-                                                egui::Color32::from_gray(200)
-                                            },
-                                        ),
+                                        egui::RichText::new(key_text)
+                                            .size(10.5)
+                                            .color(if capture { accent } else { text_primary }),
                                     )
                                     .fill(if capture {
-                                        egui::Color32::from_rgba_unmultiplied(15, 232, 121, 18)
+                                        accent.gamma_multiply(0.07)
                                     } else {
                                         surface_2
                                     })
@@ -5116,25 +4934,18 @@ impl FiosState {
 
                                     if ui.add_sized([110.0, 22.0], key_btn).clicked() {
                                         self.capture_index = Some(i);
-                                        self.status = Some(
-                                            match lang {
-                                                EngineLanguage::Pt => "Aguardando tecla...",
-                                                EngineLanguage::En => "Waiting for key...",
-                                                EngineLanguage::Es => "Esperando tecla...",
-                                            }
-                                            .to_string(),
-                                        );
+                                        self.status = Some(tr(lang, "waiting_for_key").to_string());
                                     }
 
                                     let is_on = self.pressed[i];
                                     let state_txt = if is_on { "●" } else { "○" };
-                                    ui.label(egui::RichText::new(state_txt).size(12.0).color(
-                                        if is_on {
+                                    ui.label(
+                                        egui::RichText::new(state_txt).size(12.0).color(if is_on {
                                             accent
                                         } else {
-                                            egui::Color32::from_gray(70)
-                                        },
-                                    ));
+                                            border
+                                        }),
+                                    );
                                     ui.end_row();
                                 }
                             });
@@ -5152,14 +4963,7 @@ impl FiosState {
                             if ui.add(restore_btn).clicked() {
                                 self.bindings = Self::default_bindings();
                                 self.status = match self.save_to_disk() {
-                                    Ok(()) => Some(
-                                        match lang {
-                                            EngineLanguage::Pt => "Padrão restaurado",
-                                            EngineLanguage::En => "Defaults restored",
-                                            EngineLanguage::Es => "Pred. restaurado",
-                                        }
-                                        .to_string(),
-                                    ),
+                                    Ok(()) => Some(tr(lang, "defaults_restored").to_string()),
                                     Err(err) => Some(format!("Falha ao salvar: {err}")),
                                 };
                             }
@@ -5167,22 +4971,12 @@ impl FiosState {
                             let save_btn = egui::Button::new(
                                 egui::RichText::new(save_txt).size(10.5).color(accent),
                             )
-                            .fill(egui::Color32::from_rgba_unmultiplied(15, 232, 121, 18))
-                            .stroke(egui::Stroke::new(
-                                1.0,
-                                egui::Color32::from_rgba_unmultiplied(15, 232, 121, 60),
-                            ))
+                            .fill(accent.gamma_multiply(0.07))
+                            .stroke(egui::Stroke::new(1.0, accent.gamma_multiply(0.24)))
                             .corner_radius(6.0);
                             if ui.add(save_btn).clicked() {
                                 self.status = match self.save_to_disk() {
-                                    Ok(()) => Some(
-                                        match lang {
-                                            EngineLanguage::Pt => "Controles salvos",
-                                            EngineLanguage::En => "Controls saved",
-                                            EngineLanguage::Es => "Controles guardados",
-                                        }
-                                        .to_string(),
-                                    ),
+                                    Ok(()) => Some(tr(lang, "controls_saved").to_string()),
                                     Err(err) => Some(format!("Falha ao salvar: {err}")),
                                 };
                             }
@@ -5202,28 +4996,40 @@ impl FiosState {
         }
 
         egui::Frame::default()
-            .fill(egui::Color32::from_rgb(32, 32, 36))
+            .fill(theme::palette().panel_header)
             .show(ui, |ui| {
                 ui.add_space(8.0);
 
                 ui.horizontal(|ui| {
                     let icon_size = egui::vec2(32.0, 32.0);
 
-                    let play_icon = egui::RichText::new("▶").size(16.0).color(egui::Color32::WHITE);
+                    let play_icon = egui::RichText::new("▶")
+                        .size(16.0)
+                        .color(theme::palette().text_strong);
                     let play_btn = egui::Button::new(play_icon)
-                        .fill(if self.anim_is_playing { egui::Color32::from_rgb(60, 100, 60) } else { egui::Color32::from_rgb(40, 80, 50) })
+                        .fill(if self.anim_is_playing {
+                            theme::palette().selection
+                        } else {
+                            theme::palette().widget_bg
+                        })
                         .frame(true)
                         .min_size(icon_size);
                     if ui.add(play_btn).clicked() {
                         self.anim_is_playing = !self.anim_is_playing;
-                        self.anim_tab_status = Some(if self.anim_is_playing { "Playing".to_string() } else { "Paused".to_string() });
+                        self.anim_tab_status = Some(if self.anim_is_playing {
+                            "Playing".to_string()
+                        } else {
+                            "Paused".to_string()
+                        });
                     }
 
                     ui.add_space(4.0);
 
-                    let stop_icon = egui::RichText::new("◼").size(14.0).color(egui::Color32::WHITE);
+                    let stop_icon = egui::RichText::new("◼")
+                        .size(14.0)
+                        .color(theme::palette().text_strong);
                     let stop_btn = egui::Button::new(stop_icon)
-                        .fill(egui::Color32::from_rgb(90, 50, 50))
+                        .fill(theme::palette().widget_bg)
                         .frame(true)
                         .min_size(icon_size);
                     if ui.add(stop_btn).clicked() {
@@ -5234,21 +5040,39 @@ impl FiosState {
 
                     ui.add_space(12.0);
 
-                    let record_icon = egui::RichText::new("●").size(12.0).color(if self.anim_is_recording { egui::Color32::RED } else { egui::Color32::from_gray(150) });
+                    let record_icon =
+                        egui::RichText::new("●")
+                            .size(12.0)
+                            .color(if self.anim_is_recording {
+                                theme::palette().error
+                            } else {
+                                theme::palette().text_weak
+                            });
                     let record_btn = egui::Button::new(record_icon)
-                        .fill(if self.anim_is_recording { egui::Color32::from_rgb(100, 30, 30) } else { egui::Color32::from_rgb(60, 40, 40) })
+                        .fill(if self.anim_is_recording {
+                            theme::palette().error.gamma_multiply(0.4)
+                        } else {
+                            theme::palette().widget_bg
+                        })
                         .frame(true)
                         .min_size(icon_size);
                     if ui.add(record_btn).clicked() {
                         self.anim_is_recording = !self.anim_is_recording;
-                        self.anim_tab_status = Some(if self.anim_is_recording { "Recording" } else { "Recording stopped" }.to_string());
+                        self.anim_tab_status = Some(
+                            if self.anim_is_recording {
+                                "Recording"
+                            } else {
+                                "Recording stopped"
+                            }
+                            .to_string(),
+                        );
                     }
 
                     ui.add_space(16.0);
 
                     let skip_start_icon = egui::RichText::new("⏮").size(14.0);
                     let skip_start_btn = egui::Button::new(skip_start_icon)
-                        .fill(egui::Color32::from_rgb(45, 45, 55))
+                        .fill(theme::palette().widget_bg)
                         .frame(true)
                         .min_size(icon_size);
                     if ui.add(skip_start_btn).clicked() {
@@ -5259,7 +5083,7 @@ impl FiosState {
 
                     let prev_key_icon = egui::RichText::new("⏪").size(14.0);
                     let prev_key_btn = egui::Button::new(prev_key_icon)
-                        .fill(egui::Color32::from_rgb(45, 45, 55))
+                        .fill(theme::palette().widget_bg)
                         .frame(true)
                         .min_size(icon_size);
                     if ui.add(prev_key_btn).clicked() {
@@ -5270,18 +5094,19 @@ impl FiosState {
 
                     let next_key_icon = egui::RichText::new("⏩").size(14.0);
                     let next_key_btn = egui::Button::new(next_key_icon)
-                        .fill(egui::Color32::from_rgb(45, 45, 55))
+                        .fill(theme::palette().widget_bg)
                         .frame(true)
                         .min_size(icon_size);
                     if ui.add(next_key_btn).clicked() {
-                        self.anim_current_time = (self.anim_current_time + 0.1).min(self.anim_total_duration);
+                        self.anim_current_time =
+                            (self.anim_current_time + 0.1).min(self.anim_total_duration);
                     }
 
                     ui.add_space(4.0);
 
                     let skip_end_icon = egui::RichText::new("⏭").size(14.0);
                     let skip_end_btn = egui::Button::new(skip_end_icon)
-                        .fill(egui::Color32::from_rgb(45, 45, 55))
+                        .fill(theme::palette().widget_bg)
                         .frame(true)
                         .min_size(icon_size);
                     if ui.add(skip_end_btn).clicked() {
@@ -5290,20 +5115,25 @@ impl FiosState {
 
                     ui.add_space(16.0);
 
-                    let keyframe_icon = egui::RichText::new("◆").size(12.0).color(egui::Color32::from_rgb(100, 180, 255));
+                    let keyframe_icon = egui::RichText::new("◆")
+                        .size(12.0)
+                        .color(egui::Color32::from_rgb(100, 180, 255));
                     let keyframe_btn = egui::Button::new(keyframe_icon)
-                        .fill(egui::Color32::from_rgb(50, 70, 100))
+                        .fill(theme::palette().widget_bg)
                         .frame(true)
                         .min_size(icon_size);
                     if ui.add(keyframe_btn).clicked() {
-                        self.anim_tab_status = Some(format!("Keyframe added at {:.2}s", self.anim_current_time));
+                        self.anim_tab_status =
+                            Some(format!("Keyframe added at {:.2}s", self.anim_current_time));
                     }
 
                     ui.add_space(4.0);
 
-                    let delete_key_icon = egui::RichText::new("◇").size(12.0).color(egui::Color32::from_gray(150));
+                    let delete_key_icon = egui::RichText::new("◇")
+                        .size(12.0)
+                        .color(theme::palette().text_weak);
                     let delete_key_btn = egui::Button::new(delete_key_icon)
-                        .fill(egui::Color32::from_rgb(80, 50, 50))
+                        .fill(theme::palette().widget_bg)
                         .frame(true)
                         .min_size(icon_size);
                     if ui.add(delete_key_btn).clicked() {
@@ -5314,7 +5144,7 @@ impl FiosState {
 
                     let loop_icon = egui::RichText::new("🔁").size(14.0);
                     let loop_btn = egui::Button::new(loop_icon)
-                        .fill(egui::Color32::from_rgb(45, 45, 55))
+                        .fill(theme::palette().widget_bg)
                         .frame(true)
                         .min_size(icon_size);
                     if ui.add(loop_btn).clicked() {
@@ -5329,26 +5159,47 @@ impl FiosState {
                 ui.add_space(8.0);
 
                 ui.horizontal(|ui| {
-                    ui.label(egui::RichText::new("Time:").size(12.0).color(egui::Color32::from_gray(180)));
+                    ui.label(
+                        egui::RichText::new("Time:")
+                            .size(12.0)
+                            .color(theme::palette().text_weak),
+                    );
                     ui.add_space(4.0);
 
                     let current_str = format!("{:.2}", self.anim_current_time);
                     let duration_str = format!("{:.2}", self.anim_total_duration);
-                    ui.label(egui::RichText::new(format!("{}/{}", current_str, duration_str)).size(13.0).strong().color(egui::Color32::from_rgb(255, 200, 100)));
+                    ui.label(
+                        egui::RichText::new(format!("{}/{}", current_str, duration_str))
+                            .size(13.0)
+                            .strong()
+                            .color(theme::palette().warning),
+                    );
 
                     ui.add_space(20.0);
 
-                    ui.label(egui::RichText::new("Duration:").size(12.0).color(egui::Color32::from_gray(180)));
+                    ui.label(
+                        egui::RichText::new("Duration:")
+                            .size(12.0)
+                            .color(theme::palette().text_weak),
+                    );
                     ui.add_space(4.0);
 
                     let mut duration = self.anim_total_duration;
-                    ui.add(egui::DragValue::new(&mut duration).range(0.1..=60.0).speed(0.1));
+                    ui.add(
+                        egui::DragValue::new(&mut duration)
+                            .range(0.1..=60.0)
+                            .speed(0.1),
+                    );
                     self.anim_total_duration = duration;
 
                     ui.add_space(20.0);
 
                     let fps = 30;
-                    ui.label(egui::RichText::new(format!("FPS: {}", fps)).size(12.0).color(egui::Color32::from_gray(150)));
+                    ui.label(
+                        egui::RichText::new(format!("FPS: {}", fps))
+                            .size(12.0)
+                            .color(theme::palette().text_weak),
+                    );
                 });
 
                 ui.add_space(12.0);
@@ -5361,11 +5212,17 @@ impl FiosState {
                 );
 
                 let painter = ui.painter();
-                painter.rect_filled(track_area, 4.0, egui::Color32::from_rgb(28, 28, 32));
-                painter.rect_stroke(track_area, 4.0, egui::Stroke::new(1.0, egui::Color32::from_rgb(60, 60, 70)), egui::StrokeKind::Outside);
+                painter.rect_filled(track_area, 4.0, theme::palette().graph_bg);
+                painter.rect_stroke(
+                    track_area,
+                    4.0,
+                    egui::Stroke::new(1.0, theme::palette().graph_border),
+                    egui::StrokeKind::Outside,
+                );
 
                 let time_to_x = |t: f64| -> f32 {
-                    track_area.left() + (t / self.anim_total_duration * track_area.width() as f64) as f32
+                    track_area.left()
+                        + (t / self.anim_total_duration * track_area.width() as f64) as f32
                 };
 
                 let tick_count = (self.anim_total_duration / 0.5).ceil() as usize;
@@ -5380,7 +5237,7 @@ impl FiosState {
                             egui::pos2(x, track_area.bottom() - tick_height),
                             egui::pos2(x, track_area.bottom()),
                         ],
-                        egui::Stroke::new(1.0, egui::Color32::from_rgba_unmultiplied(100, 100, 120, 150)),
+                        egui::Stroke::new(1.0, theme::palette().graph_border),
                     );
 
                     if is_major {
@@ -5389,7 +5246,7 @@ impl FiosState {
                             egui::Align2::CENTER_TOP,
                             format!("{:.1}s", t),
                             egui::FontId::proportional(10.0),
-                            egui::Color32::from_gray(140),
+                            theme::palette().text_weak,
                         );
                     }
                 }
@@ -5401,7 +5258,7 @@ impl FiosState {
                             egui::pos2(track_area.left(), track_y),
                             egui::pos2(track_area.right(), track_y),
                         ],
-                        egui::Stroke::new(1.0, egui::Color32::from_rgba_unmultiplied(70, 70, 90, 80)),
+                        egui::Stroke::new(1.0, theme::palette().graph_grid),
                     );
                 }
 
@@ -5413,7 +5270,7 @@ impl FiosState {
                         egui::Align2::LEFT_TOP,
                         *label,
                         egui::FontId::proportional(10.0),
-                        egui::Color32::from_gray(160),
+                        theme::palette().text_weak,
                     );
                 }
 
@@ -5423,16 +5280,24 @@ impl FiosState {
                         egui::pos2(playhead_x, track_area.top()),
                         egui::pos2(playhead_x, track_area.bottom()),
                     ],
-                    egui::Stroke::new(2.0, egui::Color32::from_rgb(255, 100, 80)),
+                    egui::Stroke::new(2.0, theme::palette().error),
+                );
+                painter.circle_filled(
+                    egui::pos2(playhead_x, track_area.top() + 6.0),
+                    5.0,
+                    theme::palette().error,
                 );
-                painter.circle_filled(egui::pos2(playhead_x, track_area.top() + 6.0), 5.0, egui::Color32::from_rgb(255, 100, 80));
 
                 let keyframe_times = [0.5, 1.2, 2.0, 3.5, 4.0];
                 for &kf_time in &keyframe_times {
                     let kf_x = time_to_x(kf_time);
                     for track in 0..3 {
                         let track_y = track_area.top() + 12.0 + (track as f32) * 22.0;
-                        painter.circle_filled(egui::pos2(kf_x, track_y), 4.0, egui::Color32::from_rgb(100, 200, 255));
+                        painter.circle_filled(
+                            egui::pos2(kf_x, track_y),
+                            4.0,
+                            egui::Color32::from_rgb(100, 200, 255),
+                        );
                     }
                 }
 
@@ -5446,7 +5311,12 @@ impl FiosState {
                     .num_columns(2)
                     .spacing([8.0, 6.0])
                     .show(ui, |ui| {
-                        ui.label(egui::RichText::new("Position").size(12.0).strong().color(egui::Color32::from_rgb(180, 180, 220)));
+                        ui.label(
+                            egui::RichText::new("Position")
+                                .size(12.0)
+                                .strong()
+                                .color(egui::Color32::from_rgb(180, 180, 220)),
+                        );
                         ui.horizontal(|ui| {
                             let mut px = 0.0f32;
                             let mut py = 0.0f32;
@@ -5457,7 +5327,12 @@ impl FiosState {
                         });
                         ui.end_row();
 
-                        ui.label(egui::RichText::new("Rotation").size(12.0).strong().color(egui::Color32::from_rgb(220, 180, 180)));
+                        ui.label(
+                            egui::RichText::new("Rotation")
+                                .size(12.0)
+                                .strong()
+                                .color(egui::Color32::from_rgb(220, 180, 180)),
+                        );
                         ui.horizontal(|ui| {
                             let mut rx = 0.0f32;
                             let mut ry = 0.0f32;
@@ -5468,7 +5343,12 @@ impl FiosState {
                         });
                         ui.end_row();
 
-                        ui.label(egui::RichText::new("Scale").size(12.0).strong().color(egui::Color32::from_rgb(180, 220, 180)));
+                        ui.label(
+                            egui::RichText::new("Scale")
+                                .size(12.0)
+                                .strong()
+                                .color(egui::Color32::from_rgb(180, 220, 180)),
+                        );
                         ui.horizontal(|ui| {
                             let mut sx = 1.0f32;
                             let mut sy = 1.0f32;
@@ -5487,24 +5367,24 @@ impl FiosState {
                 ui.add_space(8.0);
 
                 ui.horizontal(|ui| {
-                    let help_txt = match lang {
-                        EngineLanguage::Pt => "Barra de Espaço: Play/Pause | K: Add Keyframe | L: Loop | Home/End: Ir para início/fim",
-                        EngineLanguage::En => "Space: Play/Pause | K: Add Keyframe | L: Loop | Home/End: Go to start/end",
-                        EngineLanguage::Es => "Espacio: Play/Pause | K: Add Keyframe | L: Loop | Inicio/Fin: Ir al inicio/final",
-                    };
-                    ui.label(egui::RichText::new(help_txt).size(10.0).color(egui::Color32::from_gray(120)));
+                    let help_txt = tr(lang, "timeline_shortcuts");
+                    ui.label(
+                        egui::RichText::new(help_txt)
+                            .size(10.0)
+                            .color(theme::palette().text_weak),
+                    );
                 });
             });
 
         if let Some(status) = &self.anim_tab_status {
             ui.add_space(8.0);
             egui::Frame::default()
-                .fill(egui::Color32::from_rgba_unmultiplied(40, 40, 50, 200))
+                .fill(theme::palette().panel_header)
                 .show(ui, |ui| {
                     ui.label(
                         egui::RichText::new(status)
                             .size(11.0)
-                            .color(egui::Color32::from_gray(200)),
+                            .color(theme::palette().text),
                     );
                 });
         }
@@ -5512,28 +5392,14 @@ impl FiosState {
 
     fn draw_controller_tab(&mut self, ui: &mut egui::Ui, lang: EngineLanguage) {
         self.refresh_anim_clip_cache(ui.ctx(), false);
-        let clips_txt = match lang {
-            EngineLanguage::Pt => "Clipes",
-            EngineLanguage::En => "Clips",
-            EngineLanguage::Es => "Clips",
-        };
-        let help_txt = match lang {
-            EngineLanguage::Pt => {
-                "Arraste clipes para o canvas. Clique saída e depois entrada para ligar estados."
-            }
-            EngineLanguage::En => {
-                "Drag clips to canvas. Click output then input to connect states."
-            }
-            EngineLanguage::Es => {
-                "Arrastra clips al canvas. Haz clic en salida y luego entrada para conectar estados."
-            }
-        };
+        let clips_txt = tr(lang, "clips");
+        let help_txt = tr(lang, "controller_hint");
 
         ui.horizontal(|ui| {
             ui.label(
                 egui::RichText::new(help_txt)
                     .size(11.0)
-                    .color(egui::Color32::from_gray(185)),
+                    .color(theme::palette().text),
             );
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 if ui.button("Atualizar").clicked() {
@@ -5569,28 +5435,28 @@ impl FiosState {
         let canvas_painter = ui.painter().with_clip_rect(canvas_rect);
 
         ui.painter()
-            .rect_filled(left_rect, 6.0, egui::Color32::from_rgb(24, 26, 30));
+            .rect_filled(left_rect, 6.0, theme::palette().panel_header);
         ui.painter().rect_stroke(
             left_rect,
             6.0,
-            egui::Stroke::new(1.0, egui::Color32::from_rgb(58, 64, 72)),
+            egui::Stroke::new(1.0, theme::palette().border),
             egui::StrokeKind::Outside,
         );
 
         ui.painter()
-            .rect_filled(right_rect, 6.0, egui::Color32::from_rgb(24, 26, 30));
+            .rect_filled(right_rect, 6.0, theme::palette().panel_header);
         ui.painter().rect_stroke(
             right_rect,
             6.0,
-            egui::Stroke::new(1.0, egui::Color32::from_rgb(58, 64, 72)),
+            egui::Stroke::new(1.0, theme::palette().border),
             egui::StrokeKind::Outside,
         );
 
-        canvas_painter.rect_filled(canvas_rect, 6.0, egui::Color32::from_rgb(19, 21, 25));
+        canvas_painter.rect_filled(canvas_rect, 6.0, theme::palette().graph_bg);
         canvas_painter.rect_stroke(
             canvas_rect,
             6.0,
-            egui::Stroke::new(1.0, egui::Color32::from_rgb(56, 70, 94)),
+            egui::Stroke::new(1.0, theme::palette().graph_border),
             egui::StrokeKind::Outside,
         );
 
@@ -5605,7 +5471,7 @@ impl FiosState {
                     ui.label(
                         egui::RichText::new("Sem clipes detectados")
                             .size(11.0)
-                            .color(egui::Color32::from_gray(170)),
+                            .color(theme::palette().text_weak),
                     );
                     ui.add_space(4.0);
                     if ui.button("Atualizar").clicked() {
@@ -5629,13 +5495,8 @@ impl FiosState {
                                             )
                                             .size(10.5),
                                         )
-                                        .fill(egui::Color32::from_rgb(36, 40, 46))
-                                        .stroke(
-                                            egui::Stroke::new(
-                                                1.0,
-                                                egui::Color32::from_rgb(52, 58, 66),
-                                            ),
-                                        ),
+                                        .fill(theme::palette().widget_bg)
+                                        .stroke(egui::Stroke::new(1.0, theme::palette().border)),
                                     )
                                     .on_hover_text(clip.as_str());
                                 if resp.drag_started() {
@@ -5665,7 +5526,7 @@ impl FiosState {
                     egui::pos2(gx, canvas_rect.top()),
                     egui::pos2(gx, canvas_rect.bottom()),
                 ],
-                egui::Stroke::new(1.0, egui::Color32::from_rgba_unmultiplied(76, 98, 132, 18)),
+                egui::Stroke::new(1.0, theme::palette().graph_grid),
             );
             gx += grid_step;
         }
@@ -5676,7 +5537,7 @@ impl FiosState {
                     egui::pos2(canvas_rect.left(), gy),
                     egui::pos2(canvas_rect.right(), gy),
                 ],
-                egui::Stroke::new(1.0, egui::Color32::from_rgba_unmultiplied(76, 98, 132, 18)),
+                egui::Stroke::new(1.0, theme::palette().graph_grid),
             );
             gy += grid_step;
         }
@@ -5690,7 +5551,7 @@ impl FiosState {
 
                 let is_selected = self.anim_selected_link == Some(link_idx);
                 let link_color = if is_selected {
-                    egui::Color32::from_rgb(15, 232, 121)
+                    theme::palette().accent
                 } else {
                     egui::Color32::from_rgb(110, 182, 232)
                 };
@@ -5723,11 +5584,11 @@ impl FiosState {
                     canvas_rect.min + local.to_vec2(),
                     egui::vec2(170.0, 48.0),
                 );
-                canvas_painter.rect_filled(rect, 5.0, egui::Color32::from_rgb(35, 45, 58));
+                canvas_painter.rect_filled(rect, 5.0, theme::palette().widget_bg);
                 canvas_painter.rect_stroke(
                     rect,
                     5.0,
-                    egui::Stroke::new(1.0, egui::Color32::from_rgb(80, 124, 174)),
+                    egui::Stroke::new(1.0, theme::palette().graph_border),
                     egui::StrokeKind::Outside,
                 );
                 canvas_painter.text(
@@ -5735,14 +5596,14 @@ impl FiosState {
                     egui::Align2::LEFT_TOP,
                     &self.anim_nodes[i].name,
                     egui::FontId::proportional(11.0),
-                    egui::Color32::from_gray(232),
+                    theme::palette().text_strong,
                 );
                 canvas_painter.text(
                     rect.left_bottom() + egui::vec2(8.0, -7.0),
                     egui::Align2::LEFT_BOTTOM,
                     &self.anim_nodes[i].clip_ref,
                     egui::FontId::proportional(9.0),
-                    egui::Color32::from_gray(186),
+                    theme::palette().text_weak,
                 );
 
                 let body = ui.interact(
@@ -5808,17 +5669,13 @@ impl FiosState {
             if let Some(p) = pointer_pos {
                 let drag_rect =
                     egui::Rect::from_center_size(p + egui::vec2(8.0, 8.0), egui::vec2(180.0, 22.0));
-                canvas_painter.rect_filled(
-                    drag_rect,
-                    4.0,
-                    egui::Color32::from_rgba_unmultiplied(48, 66, 90, 220),
-                );
+                canvas_painter.rect_filled(drag_rect, 4.0, theme::palette().selection);
                 canvas_painter.text(
                     drag_rect.center(),
                     egui::Align2::CENTER_CENTER,
                     &clip,
                     egui::FontId::proportional(10.0),
-                    egui::Color32::from_gray(235),
+                    theme::palette().text_strong,
                 );
             }
             if !mouse_down {
@@ -5857,7 +5714,7 @@ impl FiosState {
                 egui::Align2::LEFT_BOTTOM,
                 msg,
                 egui::FontId::proportional(10.0),
-                egui::Color32::from_gray(172),
+                theme::palette().text_weak,
             );
         }
 
@@ -5866,11 +5723,7 @@ impl FiosState {
                 .max_rect(right_rect.shrink(10.0))
                 .layout(egui::Layout::top_down(egui::Align::Min)),
             |ui| {
-                let props_txt = match lang {
-                    EngineLanguage::Pt => "Propriedades",
-                    EngineLanguage::En => "Properties",
-                    EngineLanguage::Es => "Propiedades",
-                };
+                let props_txt = tr(lang, "properties");
                 ui.label(egui::RichText::new(props_txt).strong().size(12.0));
                 ui.add_space(8.0);
 
@@ -5881,7 +5734,7 @@ impl FiosState {
                     ui.label(
                         egui::RichText::new("Selecione um nó ou conexão")
                             .size(11.0)
-                            .color(egui::Color32::from_gray(120)),
+                            .color(theme::palette().text_weak),
                     );
                 } else if selected_count == 1 {
                     if let Some(&node_id) = self.anim_selected_nodes.iter().next() {
@@ -5889,50 +5742,34 @@ impl FiosState {
                         {
                             let node = &mut self.anim_nodes[node_idx];
 
-                            let name_txt = match lang {
-                                EngineLanguage::Pt => "Nome",
-                                EngineLanguage::En => "Name",
-                                EngineLanguage::Es => "Nombre",
-                            };
+                            let name_txt = tr(lang, "name");
                             ui.label(
                                 egui::RichText::new(name_txt)
                                     .size(10.0)
-                                    .color(egui::Color32::from_gray(170)),
+                                    .color(theme::palette().text_weak),
                             );
                             ui.text_edit_singleline(&mut node.name);
 
                             ui.add_space(6.0);
-                            let clip_txt = match lang {
-                                EngineLanguage::Pt => "Clipe",
-                                EngineLanguage::En => "Clip",
-                                EngineLanguage::Es => "Clip",
-                            };
+                            let clip_txt = tr(lang, "clip");
                             ui.label(
                                 egui::RichText::new(clip_txt)
                                     .size(10.0)
-                                    .color(egui::Color32::from_gray(170)),
+                                    .color(theme::palette().text_weak),
                             );
                             ui.text_edit_singleline(&mut node.clip_ref);
 
                             ui.add_space(6.0);
-                            let speed_txt = match lang {
-                                EngineLanguage::Pt => "Velocidade",
-                                EngineLanguage::En => "Speed",
-                                EngineLanguage::Es => "Velocidad",
-                            };
+                            let speed_txt = tr(lang, "speed");
                             ui.label(
                                 egui::RichText::new(speed_txt)
                                     .size(10.0)
-                                    .color(egui::Color32::from_gray(170)),
+                                    .color(theme::palette().text_weak),
                             );
                             ui.add(egui::Slider::new(&mut node.speed, 0.1..=3.0).text("Speed"));
 
                             ui.add_space(10.0);
-                            let delete_txt = match lang {
-                                EngineLanguage::Pt => "Deletar",
-                                EngineLanguage::En => "Delete",
-                                EngineLanguage::Es => "Eliminar",
-                            };
+                            let delete_txt = tr(lang, "delete");
                             if ui.button(delete_txt).clicked() {
                                 self.anim_links
                                     .retain(|l| l.from != node_id && l.to != node_id);
@@ -5946,19 +5783,15 @@ impl FiosState {
                     ui.label(
                         egui::RichText::new(format!("{} nós selecionados", selected_count))
                             .size(11.0)
-                            .color(egui::Color32::from_gray(170)),
+                            .color(theme::palette().text_weak),
                     );
                 } else if let Some(link_idx) = selected_link {
                     if let Some(_link) = self.anim_links.get(link_idx) {
-                        let trans_txt = match lang {
-                            EngineLanguage::Pt => "Tipo de Transição",
-                            EngineLanguage::En => "Transition Type",
-                            EngineLanguage::Es => "Tipo de Transición",
-                        };
+                        let trans_txt = tr(lang, "transition_type");
                         ui.label(
                             egui::RichText::new(trans_txt)
                                 .size(10.0)
-                                .color(egui::Color32::from_gray(170)),
+                                .color(theme::palette().text_weak),
                         );
 
                         let link_mut = &mut self.anim_links[link_idx];
@@ -5984,15 +5817,11 @@ impl FiosState {
                             });
 
                         ui.add_space(6.0);
-                        let blend_txt = match lang {
-                            EngineLanguage::Pt => "Tempo de Blend",
-                            EngineLanguage::En => "Blend Time",
-                            EngineLanguage::Es => "Tiempo de Blend",
-                        };
+                        let blend_txt = tr(lang, "blend_time");
                         ui.label(
                             egui::RichText::new(blend_txt)
                                 .size(10.0)
-                                .color(egui::Color32::from_gray(170)),
+                                .color(theme::palette().text_weak),
                         );
                         ui.add(egui::Slider::new(&mut link_mut.blend_time, 0.0..=2.0).text("s"));
 
@@ -6010,39 +5839,23 @@ impl FiosState {
 
     fn draw_tabs_content(&mut self, ui: &mut egui::Ui, ctx: &egui::Context, lang: EngineLanguage) {
         if self.add_icon_texture.is_none() {
-            self.add_icon_texture = Self::load_png_texture(ctx, "src/assets/icons/add.png");
+            self.add_icon_texture = Self::load_png_texture(ctx, "Editor/icons/add.png");
         }
         ui.add_space(4.0);
         ui.horizontal(|ui| {
-            let controls_txt = match lang {
-                EngineLanguage::Pt => "Módulos",
-                EngineLanguage::En => "Módulos",
-                EngineLanguage::Es => "Módulos",
-            };
-            let graph_txt = match lang {
-                EngineLanguage::Pt => "Fios",
-                EngineLanguage::En => "Fios",
-                EngineLanguage::Es => "Fios",
-            };
-            let creator_txt = match lang {
-                EngineLanguage::Pt => "Controlador de animação",
-                EngineLanguage::En => "Animation Controller",
-                EngineLanguage::Es => "Controlador de animación",
-            };
-            let animator_txt = match lang {
-                EngineLanguage::Pt => "Animador",
-                EngineLanguage::En => "Animator",
-                EngineLanguage::Es => "Animador",
-            };
+            let controls_txt = tr(lang, "tab_modules");
+            let graph_txt = tr(lang, "tab_fios");
+            let creator_txt = tr(lang, "tab_controller");
+            let animator_txt = tr(lang, "tab_animator");
             let c = self.tab == FiosTab::Controls;
             let g = self.tab == FiosTab::Graph;
             let k = self.tab == FiosTab::Controller;
             let a = self.tab == FiosTab::Animator;
             if ui
                 .add(egui::Button::new(controls_txt).fill(if c {
-                    egui::Color32::from_rgb(58, 84, 64)
+                    theme::palette().selection
                 } else {
-                    egui::Color32::from_rgb(52, 52, 52)
+                    theme::palette().widget_bg
                 }))
                 .clicked()
             {
@@ -6052,7 +5865,7 @@ impl FiosState {
                 .add(egui::Button::new(graph_txt).fill(if g {
                     egui::Color32::from_rgb(108, 76, 156)
                 } else {
-                    egui::Color32::from_rgb(52, 52, 52)
+                    theme::palette().widget_bg
                 }))
                 .clicked()
             {
@@ -6062,7 +5875,7 @@ impl FiosState {
                 .add(egui::Button::new(creator_txt).fill(if k {
                     egui::Color32::from_rgb(76, 96, 156)
                 } else {
-                    egui::Color32::from_rgb(52, 52, 52)
+                    theme::palette().widget_bg
                 }))
                 .clicked()
             {
@@ -6072,7 +5885,7 @@ impl FiosState {
                 .add(egui::Button::new(animator_txt).fill(if a {
                     egui::Color32::from_rgb(156, 96, 76)
                 } else {
-                    egui::Color32::from_rgb(52, 52, 52)
+                    theme::palette().widget_bg
                 }))
                 .clicked()
             {
@@ -6090,41 +5903,31 @@ impl FiosState {
         }
     }
 
+    /// Editor Fios/Animator no espaço que o layout de painéis deu a ele
     pub fn draw_embedded(
         &mut self,
         ctx: &egui::Context,
-        left_reserved: f32,
-        right_reserved: f32,
-        bottom_reserved: f32,
+        panel_rect: egui::Rect,
         lang: EngineLanguage,
     ) {
-        ctx.output_mut(|o| o.cursor_icon = egui::CursorIcon::Default);
-        egui::CentralPanel::default()
-            .frame(
-                egui::Frame::new()
-                    .fill(egui::Color32::from_rgb(28, 28, 30))
-                    .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(48, 48, 52))),
-            )
+        engine_core::profile_function!();
+        egui::Area::new(egui::Id::new("fios_embedded_panel"))
+            .order(egui::Order::Foreground)
+            .fixed_pos(panel_rect.min)
             .show(ctx, |ui| {
-                let content = ui.max_rect();
-                let panel_rect = egui::Rect::from_min_max(
-                    egui::pos2(content.left() + left_reserved, content.top()),
-                    egui::pos2(
-                        content.right() - right_reserved,
-                        content.bottom() - bottom_reserved,
-                    ),
-                );
+                let (panel_rect, _) =
+                    ui.allocate_exact_size(panel_rect.size(), egui::Sense::hover());
                 if panel_rect.width() < 80.0 || panel_rect.height() < 80.0 {
                     self.embedded_panel_rect = None;
                     return;
                 }
                 self.embedded_panel_rect = Some(panel_rect);
                 ui.painter()
-                    .rect_filled(panel_rect, 0.0, egui::Color32::from_rgb(22, 22, 24));
+                    .rect_filled(panel_rect, 0.0, theme::palette().panel_bg);
                 ui.painter().rect_stroke(
                     panel_rect,
                     0.0,
-                    egui::Stroke::new(1.0, egui::Color32::from_rgb(58, 58, 62)),
+                    egui::Stroke::new(1.0, theme::palette().border),
                     egui::StrokeKind::Outside,
                 );
                 ui.scope_builder(
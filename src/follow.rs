@@ -0,0 +1,33 @@
+use super::*;
+use glam::Vec3;
+
+impl EditorApp {
+    /// Camera Follow: durante o Play, mantém o objeto no offset do alvo referenciado por GUID
+    pub(crate) fn run_camera_follow(&mut self) {
+        if !self.is_playing || !self.time.is_running() {
+            return;
+        }
+        let dt = self.time.delta;
+        for (name, cfg) in self.inspector.camera_follow_targets() {
+            let Some(target) = self.hierarchy.object_by_guid(cfg.target.guid) else {
+                continue;
+            };
+            let Some((target_pos, _, _)) = self.viewport.object_transform_components(target) else {
+                continue;
+            };
+            let Some((pos, rot, scale)) = self.viewport.object_transform_components(&name) else {
+                continue;
+            };
+            let desired = Vec3::from_array(target_pos) + Vec3::from_array(cfg.offset);
+            let t = if cfg.smoothing <= 0.0 {
+                1.0
+            } else {
+                1.0 - cfg.smoothing.clamp(0.0, 0.99).powf(dt * 60.0)
+            };
+            let next = Vec3::from_array(pos).lerp(desired, t);
+            let _ =
+                self.viewport
+                    .set_object_transform_components(&name, next.to_array(), rot, scale);
+        }
+    }
+}
@@ -0,0 +1,762 @@
+use super::*;
+use git2::{
+    BranchType, Cred, CredentialType, DiffFormat, DiffOptions, FetchOptions, PushOptions,
+    RemoteCallbacks, Repository, Status, StatusOptions, build::CheckoutBuilder,
+};
+
+/// Com o painel aberto o status é relido neste intervalo (segundos)
+const REFRESH_INTERVAL: f64 = 3.0;
+
+/// Arquivo alterado no repositório
+struct GitFile {
+    // Relativo à raiz do repositório, como o git2 espera
+    path: String,
+    // Relativo à pasta do projeto, para exibir
+    label: String,
+    // Letra do estado no índice (preparado para o commit) e na pasta de trabalho
+    staged: Option<char>,
+    changed: Option<char>,
+}
+
+#[derive(Clone, Copy)]
+enum RemoteOp {
+    Pull,
+    Push,
+}
+
+/// Controle de versão do projeto aberto: status, diff, preparar/desfazer,
+/// commit, troca de branch e push/pull
+#[derive(Default)]
+pub(crate) struct GitPanel {
+    // Pasta do projeto cujo status está carregado
+    loaded_for: Option<PathBuf>,
+    // Raiz do repositório que contém o projeto; `None` se não há repositório
+    workdir: Option<PathBuf>,
+    branch: Option<String>,
+    branches: Vec<String>,
+    files: Vec<GitFile>,
+    // Arquivo do diff e se é a versão preparada
+    selected: Option<(String, bool)>,
+    diff: Vec<(char, String)>,
+    message: String,
+    new_branch: String,
+    // Resultado da última operação, exibido na barra do painel
+    status: Option<Result<String, String>>,
+    // Push/pull rodam fora da thread da interface
+    job: Option<(RemoteOp, Receiver<Result<String, String>>)>,
+    refreshed_at: f64,
+}
+
+fn git_error(err: git2::Error) -> String {
+    err.message().to_string()
+}
+
+fn status_letter(status: Status, staged: bool) -> Option<char> {
+    let flags = if staged {
+        [
+            (Status::INDEX_NEW, 'A'),
+            (Status::INDEX_MODIFIED, 'M'),
+            (Status::INDEX_DELETED, 'D'),
+            (Status::INDEX_RENAMED, 'R'),
+            (Status::INDEX_TYPECHANGE, 'T'),
+        ]
+    } else {
+        [
+            (Status::WT_NEW, '?'),
+            (Status::WT_MODIFIED, 'M'),
+            (Status::WT_DELETED, 'D'),
+            (Status::WT_RENAMED, 'R'),
+            (Status::WT_TYPECHANGE, 'T'),
+        ]
+    };
+    if !staged && status.contains(Status::CONFLICTED) {
+        return Some('!');
+    }
+    flags
+        .into_iter()
+        .find(|(flag, _)| status.contains(*flag))
+        .map(|(_, letter)| letter)
+}
+
+fn letter_color(letter: char) -> egui::Color32 {
+    match letter {
+        'A' | '?' => egui::Color32::from_rgb(120, 200, 120),
+        'D' | '!' => egui::Color32::from_rgb(235, 110, 100),
+        _ => egui::Color32::from_rgb(230, 190, 90),
+    }
+}
+
+/// Credenciais do agente SSH ou do credential helper do git; desiste depois
+/// de algumas tentativas para o libgit2 não ficar pedindo para sempre
+fn remote_callbacks(config: &git2::Config) -> RemoteCallbacks<'_> {
+    let mut attempts = 0;
+    let mut callbacks = RemoteCallbacks::new();
+    callbacks.credentials(move |url, username, allowed| {
+        attempts += 1;
+        if attempts > 3 {
+            return Err(git2::Error::from_str("Falha na autenticação com o remoto"));
+        }
+        if allowed.contains(CredentialType::SSH_KEY) {
+            Cred::ssh_key_from_agent(username.unwrap_or("git"))
+        } else if allowed.contains(CredentialType::USER_PASS_PLAINTEXT) {
+            Cred::credential_helper(config, url, username)
+        } else {
+            Cred::default()
+        }
+    });
+    callbacks
+}
+
+/// Push ou pull (só fast-forward) da branch atual com o `origin`
+fn run_remote(workdir: &Path, op: RemoteOp) -> Result<String, String> {
+    let repo = Repository::open(workdir).map_err(git_error)?;
+    let head = repo.head().map_err(git_error)?;
+    let branch = head
+        .shorthand()
+        .filter(|_| head.is_branch())
+        .ok_or("HEAD não está numa branch")?
+        .to_string();
+    let config = repo.config().map_err(git_error)?;
+    let mut remote = repo.find_remote("origin").map_err(git_error)?;
+    match op {
+        RemoteOp::Push => {
+            let mut rejected = None;
+            {
+                let mut callbacks = remote_callbacks(&config);
+                callbacks.push_update_reference(|_, status| {
+                    rejected = status.map(str::to_string);
+                    Ok(())
+                });
+                let mut options = PushOptions::new();
+                options.remote_callbacks(callbacks);
+                let refspec = format!("refs/heads/{branch}:refs/heads/{branch}");
+                remote
+                    .push(&[refspec.as_str()], Some(&mut options))
+                    .map_err(git_error)?;
+            }
+            match rejected {
+                Some(reason) => Err(format!("Push recusado: {reason}")),
+                None => Ok(format!("Push de {branch} concluído")),
+            }
+        }
+        RemoteOp::Pull => {
+            let mut options = FetchOptions::new();
+            options.remote_callbacks(remote_callbacks(&config));
+            remote
+                .fetch(&[branch.as_str()], Some(&mut options), None)
+                .map_err(git_error)?;
+            let fetch_head = repo.find_reference("FETCH_HEAD").map_err(git_error)?;
+            let fetched = repo
+                .reference_to_annotated_commit(&fetch_head)
+                .map_err(git_error)?;
+            let (analysis, _) = repo.merge_analysis(&[&fetched]).map_err(git_error)?;
+            if analysis.is_up_to_date() {
+                return Ok(format!("{branch} já está atualizada"));
+            }
+            if !analysis.is_fast_forward() {
+                return Err(format!(
+                    "{branch} divergiu do remoto; faça o merge pelo terminal"
+                ));
+            }
+            let refname = format!("refs/heads/{branch}");
+            let mut reference = repo.find_reference(&refname).map_err(git_error)?;
+            reference
+                .set_target(fetched.id(), "pull: fast-forward")
+                .map_err(git_error)?;
+            repo.set_head(&refname).map_err(git_error)?;
+            repo.checkout_head(Some(CheckoutBuilder::new().safe()))
+                .map_err(git_error)?;
+            Ok(format!("Pull de {branch} concluído"))
+        }
+    }
+}
+
+impl GitPanel {
+    fn repo(&self) -> Result<Repository, String> {
+        let workdir = self.workdir.as_deref().ok_or("Sem repositório")?;
+        Repository::open(workdir).map_err(git_error)
+    }
+
+    /// Relê branch, branches e arquivos alterados dentro da pasta do projeto
+    fn refresh(&mut self, project_root: &Path) -> Result<(), String> {
+        self.loaded_for = Some(project_root.to_path_buf());
+        self.workdir = None;
+        self.branch = None;
+        self.branches.clear();
+        self.files.clear();
+        let Ok(repo) = Repository::discover(project_root) else {
+            self.selected = None;
+            self.diff.clear();
+            return Ok(());
+        };
+        let Some(workdir) = repo.workdir().map(Path::to_path_buf) else {
+            return Err("Repositório sem pasta de trabalho".to_string());
+        };
+        let root = fs::canonicalize(&workdir).unwrap_or_else(|_| workdir.clone());
+        let project = fs::canonicalize(project_root).unwrap_or_else(|_| project_root.to_path_buf());
+        // Caminho do projeto dentro do repositório, com `/` como o git usa
+        let prefix = project
+            .strip_prefix(&root)
+            .map(|rel| rel.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_default();
+        self.workdir = Some(workdir);
+
+        self.branch = match repo.head() {
+            Ok(head) => head.shorthand().map(str::to_string),
+            // Repositório sem commits: HEAD aponta para uma branch que ainda não existe
+            Err(_) => repo
+                .find_reference("HEAD")
+                .ok()
+                .and_then(|head| head.symbolic_target().map(str::to_string))
+                .map(|target| target.trim_start_matches("refs/heads/").to_string()),
+        };
+        for (branch, _) in repo
+            .branches(Some(BranchType::Local))
+            .map_err(git_error)?
+            .flatten()
+        {
+            if let Ok(Some(name)) = branch.name() {
+                self.branches.push(name.to_string());
+            }
+        }
+
+        let mut options = StatusOptions::new();
+        options.include_untracked(true).recurse_untracked_dirs(true);
+        if !prefix.is_empty() {
+            options.pathspec(prefix.as_str());
+        }
+        let statuses = repo.statuses(Some(&mut options)).map_err(git_error)?;
+        for entry in statuses.iter() {
+            let Some(path) = entry.path() else {
+                continue;
+            };
+            let status = entry.status();
+            let (staged, changed) = (status_letter(status, true), status_letter(status, false));
+            if staged.is_none() && changed.is_none() {
+                continue;
+            }
+            let label = path
+                .strip_prefix(&prefix)
+                .map(|rest| rest.trim_start_matches('/'))
+                .unwrap_or(path)
+                .to_string();
+            self.files.push(GitFile {
+                path: path.to_string(),
+                label,
+                staged,
+                changed,
+            });
+        }
+        self.files.sort_by(|a, b| a.label.cmp(&b.label));
+
+        let still_listed = self.selected.as_ref().is_some_and(|(path, staged)| {
+            self.files.iter().any(|file| {
+                file.path == *path
+                    && if *staged {
+                        file.staged.is_some()
+                    } else {
+                        file.changed.is_some()
+                    }
+            })
+        });
+        if !still_listed {
+            self.selected = None;
+        }
+        self.load_diff()
+    }
+
+    /// Diff do arquivo selecionado: preparado contra o HEAD, ou a pasta de
+    /// trabalho contra o índice
+    fn load_diff(&mut self) -> Result<(), String> {
+        self.diff.clear();
+        let Some((path, staged)) = self.selected.clone() else {
+            return Ok(());
+        };
+        let repo = self.repo()?;
+        let mut options = DiffOptions::new();
+        options
+            .pathspec(path.as_str())
+            .include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .show_untracked_content(true);
+        let diff = if staged {
+            let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+            repo.diff_tree_to_index(head_tree.as_ref(), None, Some(&mut options))
+        } else {
+            repo.diff_index_to_workdir(None, Some(&mut options))
+        }
+        .map_err(git_error)?;
+        let lines = &mut self.diff;
+        diff.print(DiffFormat::Patch, |_, _, line| {
+            let text = String::from_utf8_lossy(line.content());
+            lines.push((
+                line.origin(),
+                text.trim_end_matches(['\n', '\r']).to_string(),
+            ));
+            true
+        })
+        .map_err(git_error)
+    }
+
+    fn stage(&self, paths: &[&str]) -> Result<(), String> {
+        let repo = self.repo()?;
+        let workdir = self.workdir.as_deref().ok_or("Sem repositório")?;
+        let mut index = repo.index().map_err(git_error)?;
+        for path in paths {
+            if workdir.join(path).exists() {
+                index.add_path(Path::new(path)).map_err(git_error)?;
+            } else {
+                index.remove_path(Path::new(path)).map_err(git_error)?;
+            }
+        }
+        index.write().map_err(git_error)
+    }
+
+    fn unstage(&self, paths: &[&str]) -> Result<(), String> {
+        let repo = self.repo()?;
+        match repo.head().and_then(|head| head.peel_to_commit()) {
+            Ok(commit) => repo
+                .reset_default(Some(commit.as_object()), paths.iter().copied())
+                .map_err(git_error),
+            // Sem commits não há para onde voltar: o arquivo só sai do índice
+            Err(_) => {
+                let mut index = repo.index().map_err(git_error)?;
+                for path in paths {
+                    index.remove_path(Path::new(path)).map_err(git_error)?;
+                }
+                index.write().map_err(git_error)
+            }
+        }
+    }
+
+    fn commit(&self) -> Result<String, String> {
+        let repo = self.repo()?;
+        let signature = repo
+            .signature()
+            .map_err(|_| "Configure user.name e user.email do git antes do commit".to_string())?;
+        let mut index = repo.index().map_err(git_error)?;
+        let tree = repo
+            .find_tree(index.write_tree().map_err(git_error)?)
+            .map_err(git_error)?;
+        let parent = repo.head().and_then(|head| head.peel_to_commit()).ok();
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        let id = repo
+            .commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                self.message.trim(),
+                &tree,
+                &parents,
+            )
+            .map_err(git_error)?;
+        Ok(format!("Commit {}", &id.to_string()[..7]))
+    }
+
+    fn checkout(&self, branch: &str) -> Result<String, String> {
+        let repo = self.repo()?;
+        let refname = format!("refs/heads/{branch}");
+        let target = repo.revparse_single(&refname).map_err(git_error)?;
+        repo.checkout_tree(&target, Some(CheckoutBuilder::new().safe()))
+            .map_err(git_error)?;
+        repo.set_head(&refname).map_err(git_error)?;
+        Ok(format!("Branch {branch}"))
+    }
+
+    fn create_branch(&self, name: &str) -> Result<String, String> {
+        let repo = self.repo()?;
+        let head = repo
+            .head()
+            .and_then(|head| head.peel_to_commit())
+            .map_err(|_| "Faça o primeiro commit antes de criar branches".to_string())?;
+        repo.branch(name, &head, false).map_err(git_error)?;
+        self.checkout(name)
+    }
+
+    fn start_remote(&mut self, op: RemoteOp) {
+        let Some(workdir) = self.workdir.clone() else {
+            return;
+        };
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(run_remote(&workdir, op));
+        });
+        self.job = Some((op, rx));
+        self.status = None;
+    }
+}
+
+impl EditorApp {
+    /// Painel Git no espaço que o layout de painéis deu a ele
+    pub(crate) fn draw_git_panel(&mut self, ctx: &egui::Context, panel: egui::Rect) {
+        engine_core::profile_function!();
+        let project_root = self
+            .current_project
+            .as_deref()
+            .and_then(Path::parent)
+            .map(Path::to_path_buf);
+        let now = ctx.input(|i| i.time);
+        if let Some(root) = &project_root {
+            let stale = self.git.loaded_for.as_deref() != Some(root.as_path())
+                || now - self.git.refreshed_at > REFRESH_INTERVAL;
+            if stale && self.git.job.is_none() {
+                self.refresh_git(root, now);
+            }
+        }
+        if let Some((_, rx)) = &self.git.job {
+            match rx.try_recv() {
+                Ok(result) => {
+                    self.git.job = None;
+                    self.finish_git_action(result);
+                }
+                Err(TryRecvError::Empty) => ctx.request_repaint(),
+                Err(TryRecvError::Disconnected) => self.git.job = None,
+            }
+        }
+        ctx.request_repaint_after(std::time::Duration::from_secs_f64(REFRESH_INTERVAL));
+
+        egui::Area::new(egui::Id::new("git_panel"))
+            .order(egui::Order::Foreground)
+            .fixed_pos(panel.min)
+            .show(ctx, |ui| {
+                let (rect, _) = ui.allocate_exact_size(panel.size(), egui::Sense::hover());
+                ui.painter()
+                    .rect_filled(rect, 0.0, theme::palette().panel_bg);
+                ui.painter().rect_stroke(
+                    rect,
+                    0.0,
+                    egui::Stroke::new(1.0, theme::palette().border),
+                    egui::StrokeKind::Outside,
+                );
+                let inner = rect.shrink2(egui::vec2(8.0, 6.0));
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(inner)
+                        .layout(egui::Layout::top_down(egui::Align::Min)),
+                    |ui| match &project_root {
+                        Some(root) => self.draw_git_contents(ui, root),
+                        None => {
+                            ui.label(egui::RichText::new(tr(self.language, "no_project")).weak());
+                        }
+                    },
+                );
+            });
+    }
+
+    fn refresh_git(&mut self, root: &Path, now: f64) {
+        self.git.refreshed_at = now;
+        if let Err(err) = self.git.refresh(root) {
+            self.git.status = Some(Err(err));
+        }
+    }
+
+    /// Mostra o resultado de uma operação, manda erros ao Log e relê o status
+    fn finish_git_action(&mut self, result: Result<String, String>) {
+        if let Err(err) = &result {
+            self.log.push(LogLevel::Error, "Git", err.clone());
+        }
+        self.git.status = Some(result);
+        // O próximo frame relê o status
+        self.git.refreshed_at = f64::NEG_INFINITY;
+    }
+
+    fn draw_git_contents(&mut self, ui: &mut egui::Ui, root: &Path) {
+        let language = self.language;
+        if self.git.workdir.is_none() {
+            ui.label(egui::RichText::new(tr(language, "no_repo")).weak());
+            if ui.button(tr(language, "init")).clicked() {
+                let result = Repository::init(root)
+                    .map(|_| tr(language, "initialized").to_string())
+                    .map_err(git_error);
+                self.finish_git_action(result);
+            }
+            return;
+        }
+
+        let busy = self.git.job.is_some();
+        let mut action: Option<Result<String, String>> = None;
+        ui.horizontal(|ui| {
+            let current = self
+                .git
+                .branch
+                .clone()
+                .unwrap_or_else(|| tr(language, "detached").to_string());
+            ui.add_enabled_ui(!busy, |ui| {
+                egui::ComboBox::from_id_salt("git_branch")
+                    .selected_text(format!("⎇ {current}"))
+                    .width(160.0)
+                    .show_ui(ui, |ui| {
+                        for branch in &self.git.branches {
+                            if ui
+                                .selectable_label(self.git.branch.as_ref() == Some(branch), branch)
+                                .clicked()
+                                && self.git.branch.as_ref() != Some(branch)
+                            {
+                                action = Some(self.git.checkout(branch));
+                            }
+                        }
+                        ui.separator();
+                        ui.horizontal(|ui| {
+                            ui.add(
+                                egui::TextEdit::singleline(&mut self.git.new_branch)
+                                    .hint_text(tr(language, "new_branch"))
+                                    .desired_width(110.0),
+                            );
+                            let name = self.git.new_branch.trim().to_string();
+                            if ui
+                                .add_enabled(
+                                    !name.is_empty(),
+                                    egui::Button::new(tr(language, "create")),
+                                )
+                                .clicked()
+                            {
+                                action = Some(self.git.create_branch(&name));
+                                self.git.new_branch.clear();
+                                ui.close();
+                            }
+                        });
+                    });
+                if ui.button(format!("⬇ {}", tr(language, "pull"))).clicked() {
+                    self.git.start_remote(RemoteOp::Pull);
+                }
+                if ui.button(format!("⬆ {}", tr(language, "push"))).clicked() {
+                    self.git.start_remote(RemoteOp::Push);
+                }
+                if ui
+                    .button("⟳")
+                    .on_hover_text(tr(language, "refresh"))
+                    .clicked()
+                {
+                    self.git.refreshed_at = f64::NEG_INFINITY;
+                }
+            });
+            if let Some((op, _)) = &self.git.job {
+                ui.spinner();
+                ui.label(
+                    egui::RichText::new(match op {
+                        RemoteOp::Pull => tr(language, "pulling"),
+                        RemoteOp::Push => tr(language, "pushing"),
+                    })
+                    .small()
+                    .weak(),
+                );
+            } else if let Some(status) = &self.git.status {
+                let (text, color) = match status {
+                    Ok(text) => (text, egui::Color32::from_rgb(120, 200, 120)),
+                    Err(text) => (text, egui::Color32::from_rgb(235, 110, 100)),
+                };
+                ui.add(egui::Label::new(egui::RichText::new(text).small().color(color)).truncate());
+            }
+        });
+        ui.separator();
+
+        let body = ui.available_rect_before_wrap();
+        let list_width = (body.width() * 0.42).clamp(220.0, 420.0);
+        let list_rect = egui::Rect::from_min_size(body.min, egui::vec2(list_width, body.height()));
+        let diff_rect =
+            egui::Rect::from_min_max(egui::pos2(list_rect.right() + 8.0, body.top()), body.max);
+        ui.scope_builder(
+            egui::UiBuilder::new()
+                .max_rect(list_rect)
+                .layout(egui::Layout::top_down(egui::Align::Min)),
+            |ui| {
+                if let Some(result) = self.draw_git_changes(ui, busy) {
+                    action = Some(result);
+                }
+            },
+        );
+        ui.painter().vline(
+            list_rect.right() + 4.0,
+            body.y_range(),
+            egui::Stroke::new(1.0, theme::palette().border),
+        );
+        ui.scope_builder(
+            egui::UiBuilder::new()
+                .max_rect(diff_rect)
+                .layout(egui::Layout::top_down(egui::Align::Min)),
+            |ui| self.draw_git_diff(ui),
+        );
+        ui.allocate_rect(body, egui::Sense::hover());
+
+        if let Some(result) = action {
+            self.finish_git_action(result);
+        }
+    }
+
+    /// Listas de preparados e alterados e a caixa de commit; retorna o
+    /// resultado da operação feita neste frame
+    fn draw_git_changes(
+        &mut self,
+        ui: &mut egui::Ui,
+        busy: bool,
+    ) -> Option<Result<String, String>> {
+        let language = self.language;
+        let mut action = None;
+        let mut select = None;
+        let commit_height = 78.0;
+        egui::ScrollArea::vertical()
+            .id_salt("git_changes")
+            .auto_shrink([false, false])
+            .max_height((ui.available_height() - commit_height).max(40.0))
+            .show(ui, |ui| {
+                for staged in [true, false] {
+                    let files: Vec<(&GitFile, char)> = self
+                        .git
+                        .files
+                        .iter()
+                        .filter_map(|file| {
+                            (if staged { file.staged } else { file.changed }).map(|l| (file, l))
+                        })
+                        .collect();
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "{} ({})",
+                                tr(language, if staged { "staged" } else { "changes" }),
+                                files.len()
+                            ))
+                            .strong(),
+                        );
+                        let all: Vec<&str> = files.iter().map(|(f, _)| f.path.as_str()).collect();
+                        let (label, hint) = if staged {
+                            ("−", "unstage_all")
+                        } else {
+                            ("+", "stage_all")
+                        };
+                        if !all.is_empty()
+                            && ui
+                                .small_button(label)
+                                .on_hover_text(tr(language, hint))
+                                .clicked()
+                        {
+                            let result = if staged {
+                                self.git.unstage(&all)
+                            } else {
+                                self.git.stage(&all)
+                            };
+                            action = Some(result.map(|_| tr(language, hint).to_string()));
+                        }
+                    });
+                    for (file, letter) in &files {
+                        ui.horizontal(|ui| {
+                            ui.label(
+                                egui::RichText::new(letter.to_string())
+                                    .monospace()
+                                    .color(letter_color(*letter)),
+                            );
+                            let selected =
+                                self.git.selected.as_ref() == Some(&(file.path.clone(), staged));
+                            let button = if staged { "−" } else { "+" };
+                            ui.with_layout(
+                                egui::Layout::right_to_left(egui::Align::Center),
+                                |ui| {
+                                    if ui
+                                        .small_button(button)
+                                        .on_hover_text(tr(
+                                            language,
+                                            if staged { "unstage" } else { "stage" },
+                                        ))
+                                        .clicked()
+                                    {
+                                        let paths = [file.path.as_str()];
+                                        action = Some(
+                                            if staged {
+                                                self.git.unstage(&paths)
+                                            } else {
+                                                self.git.stage(&paths)
+                                            }
+                                            .map(|_| file.label.clone()),
+                                        );
+                                    }
+                                    ui.with_layout(
+                                        egui::Layout::left_to_right(egui::Align::Center),
+                                        |ui| {
+                                            if ui
+                                                .selectable_label(selected, &file.label)
+                                                .on_hover_text(&file.path)
+                                                .clicked()
+                                            {
+                                                select = Some((file.path.clone(), staged));
+                                            }
+                                        },
+                                    );
+                                },
+                            );
+                        });
+                    }
+                    ui.add_space(6.0);
+                }
+            });
+
+        ui.separator();
+        ui.add(
+            egui::TextEdit::multiline(&mut self.git.message)
+                .hint_text(tr(language, "message"))
+                .desired_rows(2)
+                .desired_width(f32::INFINITY),
+        );
+        let can_commit = !busy
+            && !self.git.message.trim().is_empty()
+            && self.git.files.iter().any(|file| file.staged.is_some());
+        if ui
+            .add_enabled(can_commit, egui::Button::new(tr(language, "commit")))
+            .clicked()
+        {
+            let result = self.git.commit();
+            if result.is_ok() {
+                self.git.message.clear();
+            }
+            action = Some(result);
+        }
+
+        if let Some(selected) = select {
+            self.git.selected = Some(selected);
+            if let Err(err) = self.git.load_diff() {
+                self.git.status = Some(Err(err));
+            }
+        }
+        action
+    }
+
+    fn draw_git_diff(&mut self, ui: &mut egui::Ui) {
+        let Some((path, staged)) = &self.git.selected else {
+            ui.label(egui::RichText::new(tr(self.language, "select_file")).weak());
+            return;
+        };
+        ui.label(
+            egui::RichText::new(format!(
+                "{path}{}",
+                if *staged {
+                    format!(" ({})", tr(self.language, "staged").to_lowercase())
+                } else {
+                    String::new()
+                }
+            ))
+            .small()
+            .weak(),
+        );
+        egui::ScrollArea::both()
+            .id_salt("git_diff")
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                for (origin, text) in &self.git.diff {
+                    let (prefix, color) = match origin {
+                        '+' => ("+", egui::Color32::from_rgb(120, 200, 120)),
+                        '-' => ("-", egui::Color32::from_rgb(235, 110, 100)),
+                        'H' => ("", egui::Color32::from_rgb(110, 170, 230)),
+                        ' ' => (" ", theme::palette().text),
+                        _ => ("", egui::Color32::from_gray(130)),
+                    };
+                    ui.label(
+                        egui::RichText::new(format!("{prefix}{text}"))
+                            .monospace()
+                            .color(color),
+                    );
+                }
+            });
+    }
+}
+
+fn tr(language: EngineLanguage, key: &'static str) -> &'static str {
+    i18n::text(language, "git_panel", key)
+}
@@ -0,0 +1,198 @@
+use super::*;
+use engine_render::AntiAliasing;
+
+// Orçamentos de memória de um projeto sem configuração
+const DEFAULT_CPU_BUDGET_MB: u32 = 512;
+const DEFAULT_GPU_BUDGET_MB: u32 = 1024;
+
+/// Configurações gráficas do projeto, salvas em `ProjectSettings/graphics.cfg`
+pub(crate) struct GraphicsSettings {
+    pub(crate) open: bool,
+    anti_aliasing: AntiAliasing,
+    // Superfícies de água (pedem o alvo offscreen para refração e reflexo)
+    water: bool,
+    // Orçamentos de memória em MB (0 = sem limite), editados na janela de Memória
+    cpu_budget_mb: u32,
+    gpu_budget_mb: u32,
+    // Projeto cujas configurações estão carregadas
+    loaded_for: Option<PathBuf>,
+    status: Option<String>,
+}
+
+impl Default for GraphicsSettings {
+    fn default() -> Self {
+        Self {
+            open: false,
+            anti_aliasing: AntiAliasing::default(),
+            water: true,
+            cpu_budget_mb: DEFAULT_CPU_BUDGET_MB,
+            gpu_budget_mb: DEFAULT_GPU_BUDGET_MB,
+            loaded_for: None,
+            status: None,
+        }
+    }
+}
+
+impl GraphicsSettings {
+    fn config_path(project: &Path) -> Option<PathBuf> {
+        project
+            .parent()
+            .map(|root| root.join("ProjectSettings").join("graphics.cfg"))
+    }
+
+    fn load(&mut self, project: Option<&Path>) {
+        self.anti_aliasing = AntiAliasing::default();
+        self.water = true;
+        self.cpu_budget_mb = DEFAULT_CPU_BUDGET_MB;
+        self.gpu_budget_mb = DEFAULT_GPU_BUDGET_MB;
+        self.loaded_for = project.map(Path::to_path_buf);
+        self.status = None;
+        let Some(raw) = project
+            .and_then(Self::config_path)
+            .and_then(|path| fs::read_to_string(path).ok())
+        else {
+            return;
+        };
+        for (key, value) in raw.lines().filter_map(|line| line.split_once('=')) {
+            match key.trim() {
+                "anti_aliasing" => {
+                    if let Some(mode) = AntiAliasing::from_config_str(value) {
+                        self.anti_aliasing = mode;
+                    }
+                }
+                "water" => self.water = value.trim() != "false",
+                "cpu_memory_budget_mb" => {
+                    if let Ok(mb) = value.trim().parse() {
+                        self.cpu_budget_mb = mb;
+                    }
+                }
+                "gpu_memory_budget_mb" => {
+                    if let Ok(mb) = value.trim().parse() {
+                        self.gpu_budget_mb = mb;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Orçamentos de memória (CPU, GPU) em bytes; 0 = sem limite
+    pub(crate) fn memory_budgets(&self) -> (usize, usize) {
+        (
+            self.cpu_budget_mb as usize * 1024 * 1024,
+            self.gpu_budget_mb as usize * 1024 * 1024,
+        )
+    }
+
+    pub(crate) fn memory_budgets_mb(&self) -> (u32, u32) {
+        (self.cpu_budget_mb, self.gpu_budget_mb)
+    }
+
+    pub(crate) fn set_memory_budgets_mb(&mut self, cpu: u32, gpu: u32) -> Result<(), String> {
+        self.cpu_budget_mb = cpu;
+        self.gpu_budget_mb = gpu;
+        self.save()
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let Some(path) = self.loaded_for.as_deref().and_then(Self::config_path) else {
+            return Err("Abra um projeto para salvar as configurações gráficas".to_string());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let out = format!(
+            "anti_aliasing={}\nwater={}\ncpu_memory_budget_mb={}\ngpu_memory_budget_mb={}\n",
+            self.anti_aliasing.as_config_str(),
+            self.water,
+            self.cpu_budget_mb,
+            self.gpu_budget_mb
+        );
+        fs::write(&path, out).map_err(|e| e.to_string())
+    }
+}
+
+impl EditorApp {
+    /// Carrega as configurações ao trocar de projeto e aplica na viewport
+    pub(crate) fn sync_graphics_settings(&mut self) {
+        if self.graphics.loaded_for != self.current_project {
+            let project = self.current_project.clone();
+            self.graphics.load(project.as_deref());
+        }
+        self.viewport.set_anti_aliasing(self.graphics.anti_aliasing);
+        self.viewport.set_water_enabled(self.graphics.water);
+        if let Some(gpu) = &self.viewport_gpu {
+            gpu.set_memory_budget(self.graphics.memory_budgets().1);
+        }
+    }
+
+    pub(crate) fn draw_graphics_window(&mut self, ctx: &egui::Context) {
+        engine_core::profile_function!();
+        if !self.graphics.open {
+            return;
+        }
+        let effective = self
+            .viewport_gpu
+            .as_ref()
+            .map(|gpu| gpu.resolve_anti_aliasing(self.graphics.anti_aliasing));
+
+        let mut open = self.graphics.open;
+        let mut changed = false;
+        egui::Window::new("🖥 Gráficos")
+            .open(&mut open)
+            .default_size([280.0, 140.0])
+            .resizable(false)
+            .show(ctx, |ui| {
+                let graphics = &mut self.graphics;
+                egui::Grid::new("graphics_grid")
+                    .num_columns(2)
+                    .spacing([8.0, 6.0])
+                    .show(ui, |ui| {
+                        ui.label("Anti-aliasing");
+                        egui::ComboBox::from_id_salt("graphics_anti_aliasing")
+                            .selected_text(graphics.anti_aliasing.label())
+                            .show_ui(ui, |ui| {
+                                for mode in AntiAliasing::all() {
+                                    changed |= ui
+                                        .selectable_value(
+                                            &mut graphics.anti_aliasing,
+                                            mode,
+                                            mode.label(),
+                                        )
+                                        .changed();
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label("Água");
+                        changed |= ui
+                            .checkbox(&mut graphics.water, "Reflexo e refração")
+                            .changed();
+                        ui.end_row();
+                    });
+                ui.label(
+                    egui::RichText::new("Aplicado à cena e ao modo Play")
+                        .small()
+                        .weak(),
+                );
+                if let Some(mode) = effective.filter(|mode| *mode != graphics.anti_aliasing) {
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "Sem suporte neste dispositivo, usando {}",
+                            mode.label()
+                        ))
+                        .small()
+                        .color(egui::Color32::from_rgb(230, 180, 80)),
+                    );
+                }
+                if let Some(status) = &graphics.status {
+                    ui.label(egui::RichText::new(status).small().weak());
+                }
+            });
+        self.graphics.open = open;
+
+        if changed {
+            self.graphics.status = self.graphics.save().err();
+        }
+    }
+}
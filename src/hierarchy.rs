@@ -1,9 +1,13 @@
 use crate::EngineLanguage;
+use crate::i18n;
 use crate::inspector;
+use crate::selection::Selection;
+use crate::theme;
 use eframe::egui::{
     self, Align2, Color32, FontFamily, FontId, Id, Key, Modifiers, Order, Pos2, Rect, Stroke,
     TextureHandle, Vec2,
 };
+use engine_core::Guid;
 use epaint::ColorImage;
 use std::collections::{HashMap, HashSet};
 
@@ -28,6 +32,77 @@ pub struct LightSpawnRequest {
     pub object_name: String,
 }
 
+/// Copiar, colar ou duplicar pedido pelo menu de contexto; quem executa é o
+/// editor, que junta os dados de todos os painéis
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ClipboardRequest {
+    Copy,
+    Paste,
+    Duplicate,
+}
+
+/// Pedido do menu de cenas; quem executa é o editor, dono do mundo ECS onde as
+/// cenas aditivas são carregadas
+#[derive(Clone, PartialEq, Eq)]
+pub enum SceneRequest {
+    Create,
+    LoadAdditive,
+    // None = cena principal
+    SaveAs(Option<String>),
+    Unload(String),
+}
+
+/// Objetos da hierarquia (ordem, pais, cenas, cores, deletados) num momento,
+/// guardados durante a edição isolada de um prefab
+#[derive(Clone, Default)]
+pub struct HierarchySnapshot {
+    object_colors: HashMap<String, Color32>,
+    object_visibility: HashMap<String, bool>,
+    deleted_objects: HashSet<String>,
+    top_level_order: Vec<String>,
+    player_order: Vec<String>,
+    armature_order: Vec<String>,
+    environment_order: Vec<String>,
+    additive_scenes: Vec<String>,
+    object_scene: HashMap<String, String>,
+    active_scene: Option<String>,
+    imported_children: HashMap<String, Vec<String>>,
+    object_parents: HashMap<String, String>,
+}
+
+/// Objeto tirado da cena pela hierarquia, com a cor e a visibilidade que
+/// foram descartadas junto
+#[derive(Clone)]
+pub struct RemovedObject {
+    pub name: String,
+    color: Option<Color32>,
+    visible: Option<bool>,
+}
+
+/// Onde um objeto está: lista, posição nela, pai e a cena dele e dos descendentes
+#[derive(Clone, PartialEq)]
+pub struct ObjectPlacement {
+    container: Option<HierarchyContainer>,
+    index: usize,
+    parent: Option<String>,
+    scenes: Vec<(String, Option<String>)>,
+}
+
+/// Ação feita na hierarquia, entregue ao histórico do editor por `take_edits`
+#[derive(Clone)]
+pub enum HierarchyEdit {
+    /// Objetos criados (primitiva, luz, asset solto, colagem)
+    Spawn(Vec<String>),
+    /// Objetos deletados, com os descendentes
+    Delete(Vec<RemovedObject>),
+    /// Objeto arrastado para outro pai, lista ou posição
+    Reparent {
+        object: String,
+        before: ObjectPlacement,
+        after: ObjectPlacement,
+    },
+}
+
 pub struct HierarchyWindow {
     pub open: bool,
     selector_icon_texture: Option<TextureHandle>,
@@ -36,12 +111,10 @@ pub struct HierarchyWindow {
     no_view_icon_texture: Option<TextureHandle>,
     camera_icon_texture: Option<TextureHandle>,
     sun_icon_texture: Option<TextureHandle>,
-    dock_side: Option<HierarchyDockSide>,
-    window_pos: Option<Pos2>,
-    window_width: f32,
-    dragging_from_header: bool,
-    resizing_width: bool,
-    selected_object: String,
+    selection: Selection,
+    // Linhas desenhadas no último frame, em ordem, para o Shift+clique
+    row_order: Vec<String>,
+    drawn_rows: Vec<String>,
     player_open: bool,
     armature_open: bool,
     environment_open: bool,
@@ -57,18 +130,34 @@ pub struct HierarchyWindow {
     drag_hover_parent: Option<(String, f64)>,
     color_picker_open: bool,
     picker_color: Color32,
-    pending_delete_object: Option<String>,
+    pending_delete_objects: Vec<String>,
     hovered_object: Option<String>,
     pending_spawn_primitive: Option<Primitive3DSpawnRequest>,
     pending_spawn_light: Option<LightSpawnRequest>,
+    pending_clipboard_request: Option<ClipboardRequest>,
+    pending_scene_request: Option<SceneRequest>,
+    // Ações deste frame, para o histórico
+    pending_edits: Vec<HierarchyEdit>,
     language: EngineLanguage,
     last_panel_rect: Option<Rect>,
-}
-
-#[derive(Clone, Copy)]
-enum HierarchyDockSide {
-    Left,
-    Right,
+    additive_scenes: Vec<String>,
+    object_scene: HashMap<String, String>,
+    active_scene: Option<String>,
+    scene_open: HashMap<String, bool>,
+    object_guids: HashMap<String, Guid>,
+    imported_children: HashMap<String, Vec<String>>,
+    imported_open: HashMap<String, bool>,
+    // Pai de objetos da lista de topo, definido arrastando um sobre o outro
+    object_parents: HashMap<String, String>,
+    search_query: String,
+    // Busca para a qual os pais dos resultados já foram abertos
+    search_expanded_for: String,
+    // Com a busca ativa: os objetos que batem com ela e os pais deles
+    search_shown: Option<HashSet<String>>,
+    // Tipos de componente de cada objeto, vindos do inspetor durante a busca
+    search_components: HashMap<String, Vec<&'static str>>,
+    // Linha que a lista rola até mostrar, pedida pela busca rápida
+    scroll_to: Option<String>,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -83,10 +172,12 @@ enum HierarchyContainer {
 enum HierarchyDropTarget {
     Row { target: String, after: bool },
     Container(HierarchyContainer),
+    // Vira filho de um objeto da lista de topo
+    Parent(String),
 }
 
 fn load_png_as_texture(ctx: &egui::Context, png_path: &str) -> Option<TextureHandle> {
-    let bytes = std::fs::read(png_path).ok()?;
+    let bytes = engine_render::vfs_read(png_path).ok()?;
     let rgba = image::load_from_memory(&bytes).ok()?.to_rgba8();
     let size = [rgba.width() as usize, rgba.height() as usize];
     let color_image = ColorImage::from_rgba_unmultiplied(size, rgba.as_raw());
@@ -140,12 +231,9 @@ impl HierarchyWindow {
             no_view_icon_texture: None,
             camera_icon_texture: None,
             sun_icon_texture: None,
-            dock_side: Some(HierarchyDockSide::Right),
-            window_pos: None,
-            window_width: 220.0,
-            dragging_from_header: false,
-            resizing_width: false,
-            selected_object: "Main Camera".to_string(),
+            selection: Selection::single("Main Camera"),
+            row_order: Vec::new(),
+            drawn_rows: Vec::new(),
             player_open: true,
             armature_open: true,
             environment_open: true,
@@ -160,13 +248,29 @@ impl HierarchyWindow {
             drop_target: None,
             drag_hover_parent: None,
             color_picker_open: false,
-            picker_color: Color32::from_rgb(15, 232, 121),
-            pending_delete_object: None,
+            picker_color: theme::palette().accent,
+            pending_delete_objects: Vec::new(),
             hovered_object: None,
             pending_spawn_primitive: None,
             pending_spawn_light: None,
+            pending_clipboard_request: None,
+            pending_scene_request: None,
+            pending_edits: Vec::new(),
             language: EngineLanguage::Pt,
             last_panel_rect: None,
+            additive_scenes: Vec::new(),
+            object_scene: HashMap::new(),
+            object_guids: HashMap::new(),
+            active_scene: None,
+            scene_open: HashMap::new(),
+            imported_children: HashMap::new(),
+            imported_open: HashMap::new(),
+            object_parents: HashMap::new(),
+            search_query: String::new(),
+            search_expanded_for: String::new(),
+            search_shown: None,
+            search_components: HashMap::new(),
+            scroll_to: None,
         }
     }
 
@@ -178,6 +282,11 @@ impl HierarchyWindow {
         self.last_panel_rect
     }
 
+    /// O painel não está visível neste frame (aba escondida ou fechado)
+    pub fn clear_panel_rect(&mut self) {
+        self.last_panel_rect = None;
+    }
+
     pub fn on_asset_dropped(&mut self, asset_name: &str) -> String {
         let stem = std::path::Path::new(asset_name)
             .file_stem()
@@ -190,11 +299,192 @@ impl HierarchyWindow {
             object_name = format!("{stem} (Mesh {idx})");
         }
         self.top_level_order.push(object_name.clone());
-        self.selected_object = object_name.clone();
+        self.assign_to_active_scene(&object_name);
+        self.selection.select(&object_name);
         self.deleted_objects.remove(&object_name);
+        self.pending_edits
+            .push(HierarchyEdit::Spawn(vec![object_name.clone()]));
         object_name
     }
 
+    /// Cria o objeto de topo de uma colagem ou duplicação: `label` (sem o
+    /// número de cópia) vira um nome único e os filhos ficam sob ele
+    pub fn add_pasted_object(
+        &mut self,
+        label: &str,
+        child_labels: &[String],
+    ) -> (String, Vec<String>) {
+        let base = match label.rsplit_once(' ') {
+            Some((base, number)) if number.parse::<u32>().is_ok() => base,
+            _ => label,
+        };
+        let object_name = self.create_top_object_unique(base);
+        let children: Vec<String> = child_labels
+            .iter()
+            .map(|child| format!("{object_name}/{child}"))
+            .collect();
+        if !children.is_empty() {
+            self.set_imported_children(&object_name, children.clone());
+        }
+        self.pending_edits
+            .push(HierarchyEdit::Spawn(vec![object_name.clone()]));
+        (object_name, children)
+    }
+
+    /// Filhos não deletados de um objeto (nós de cena importada e objetos
+    /// soltos sobre ele)
+    pub fn object_children(&self, object_name: &str) -> Vec<String> {
+        self.imported_children
+            .get(object_name)
+            .into_iter()
+            .flatten()
+            .cloned()
+            .chain(self.parented_children(object_name))
+            .filter(|child| !self.deleted_objects.contains(child))
+            .collect()
+    }
+
+    /// Pai de um nó de cena importada ou de um objeto solto sobre outro
+    pub fn object_parent(&self, object_name: &str) -> Option<&str> {
+        self.object_parents
+            .get(object_name)
+            .map(String::as_str)
+            .or_else(|| self.imported_parent_of(object_name))
+    }
+
+    /// Filhos definidos arrastando, na ordem da lista de topo
+    fn parented_children(&self, object_name: &str) -> Vec<String> {
+        self.top_level_order
+            .iter()
+            .filter(|name| {
+                self.object_parents
+                    .get(*name)
+                    .is_some_and(|p| p == object_name)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Cor própria e visibilidade do objeto
+    pub fn object_style(&self, object_name: &str) -> (Option<Color32>, bool) {
+        (
+            self.object_colors.get(object_name).copied(),
+            *self.object_visibility.get(object_name).unwrap_or(&true),
+        )
+    }
+
+    pub fn set_object_style(&mut self, object_name: &str, color: Option<Color32>, visible: bool) {
+        if let Some(color) = color {
+            self.object_colors.insert(object_name.to_string(), color);
+        }
+        if !visible {
+            self.object_visibility
+                .insert(object_name.to_string(), false);
+        }
+    }
+
+    pub fn take_clipboard_request(&mut self) -> Option<ClipboardRequest> {
+        self.pending_clipboard_request.take()
+    }
+
+    pub fn take_scene_request(&mut self) -> Option<SceneRequest> {
+        self.pending_scene_request.take()
+    }
+
+    /// Registra os nós criados pela importação de uma cena como filhos de `root`
+    pub fn set_imported_children(&mut self, root: &str, children: Vec<String>) {
+        for child in &children {
+            self.deleted_objects.remove(child);
+        }
+        self.imported_open.insert(root.to_string(), true);
+        self.imported_children.insert(root.to_string(), children);
+    }
+
+    fn imported_parent_of(&self, name: &str) -> Option<&str> {
+        self.imported_children
+            .iter()
+            .find(|(_, children)| children.iter().any(|c| c == name))
+            .map(|(root, _)| root.as_str())
+    }
+
+    fn assign_to_active_scene(&mut self, object_name: &str) {
+        match &self.active_scene {
+            Some(scene) => {
+                self.object_scene
+                    .insert(object_name.to_string(), scene.clone());
+            }
+            None => {
+                self.object_scene.remove(object_name);
+            }
+        }
+    }
+
+    /// Cria uma cena vazia carregada de forma aditiva e a torna ativa
+    pub fn create_additive_scene(&mut self) -> String {
+        let mut idx = self.additive_scenes.len() + 2;
+        let mut scene_name = format!("Scene {idx}");
+        while self.additive_scenes.iter().any(|n| n == &scene_name) {
+            idx += 1;
+            scene_name = format!("Scene {idx}");
+        }
+        self.additive_scenes.push(scene_name.clone());
+        self.active_scene = Some(scene_name.clone());
+        scene_name
+    }
+
+    /// Grupo de uma cena carregada de arquivo; o nome ganha número se já existir
+    pub fn add_additive_scene(&mut self, base_name: &str) -> String {
+        let mut scene_name = base_name.to_string();
+        let mut idx = 1;
+        while self.additive_scenes.iter().any(|n| n == &scene_name) {
+            idx += 1;
+            scene_name = format!("{base_name} {idx}");
+        }
+        self.additive_scenes.push(scene_name.clone());
+        scene_name
+    }
+
+    /// Cenas aditivas, na ordem em que foram carregadas
+    pub fn additive_scene_names(&self) -> &[String] {
+        &self.additive_scenes
+    }
+
+    /// Objeto de topo novo dentro de `scene` (None = cena principal)
+    pub fn add_scene_object(&mut self, base_name: &str, scene: Option<&str>) -> String {
+        let object_name = self.create_top_object_unique(base_name);
+        self.set_scene_recursive(&object_name, scene.map(str::to_string));
+        object_name
+    }
+
+    /// Descarrega uma cena aditiva, removendo todos os objetos que pertencem a ela
+    pub fn unload_additive_scene(&mut self, scene_name: &str) {
+        let owned: Vec<String> = self
+            .object_scene
+            .iter()
+            .filter(|(_, scene)| scene.as_str() == scene_name)
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in owned {
+            self.delete_object_recursive(&name, &mut Vec::new());
+            self.object_scene.remove(&name);
+        }
+        self.additive_scenes.retain(|n| n != scene_name);
+        self.scene_open.remove(scene_name);
+        if self.active_scene.as_deref() == Some(scene_name) {
+            self.active_scene = None;
+        }
+    }
+
+    /// Move o objeto e seus descendentes para `scene` (None = cena principal)
+    pub fn set_object_scene(&mut self, object_name: &str, scene: Option<&str>) {
+        self.set_scene_recursive(object_name, scene.map(str::to_string));
+    }
+
+    /// Cena aditiva dona do objeto (None = cena principal)
+    pub fn scene_of(&self, object_name: &str) -> Option<&str> {
+        self.object_scene.get(object_name).map(String::as_str)
+    }
+
     pub fn take_spawn_primitive_request(&mut self) -> Option<Primitive3DSpawnRequest> {
         self.pending_spawn_primitive.take()
     }
@@ -211,8 +501,9 @@ impl HierarchyWindow {
             object_name = format!("{base_name} {idx}");
         }
         self.top_level_order.push(object_name.clone());
+        self.assign_to_active_scene(&object_name);
         self.deleted_objects.remove(&object_name);
-        self.selected_object = object_name.clone();
+        self.selection.select(&object_name);
         object_name
     }
 
@@ -225,6 +516,8 @@ impl HierarchyWindow {
             Primitive3DKind::Plane => "Plane",
         };
         let object_name = self.create_top_object_unique(base_name);
+        self.pending_edits
+            .push(HierarchyEdit::Spawn(vec![object_name.clone()]));
         self.pending_spawn_primitive = Some(Primitive3DSpawnRequest { kind, object_name });
     }
 
@@ -235,101 +528,155 @@ impl HierarchyWindow {
             inspector::LightType::Spot => "Spot Light",
         };
         let object_name = self.create_top_object_unique(base_name);
+        self.pending_edits
+            .push(HierarchyEdit::Spawn(vec![object_name.clone()]));
         self.pending_spawn_light = Some(LightSpawnRequest {
             light_type,
             object_name,
         });
     }
 
+    /// Cena ativa (None = cena principal)
+    pub fn active_scene_name(&self) -> Option<&str> {
+        self.active_scene.as_deref()
+    }
+
+    /// Objeto ativo da seleção (o último selecionado)
     pub fn selected_object_name(&self) -> &str {
-        &self.selected_object
+        self.selection.active().unwrap_or_default()
     }
 
-    pub fn set_selected_object(&mut self, object_name: &str) {
-        if self.top_level_order.iter().any(|n| n == object_name) && !self.is_deleted(object_name) {
-            self.selected_object = object_name.to_string();
+    pub fn selection(&self) -> &Selection {
+        &self.selection
+    }
+
+    /// Adota a seleção da viewport, sem objetos desconhecidos ou deletados;
+    /// uma seleção vazia é ignorada (a hierarquia sempre tem um ativo)
+    pub fn set_selection(&mut self, selection: &Selection) {
+        let mut selection = selection.clone();
+        selection.retain(|name| {
+            let known = self.top_level_order.iter().any(|n| n == name)
+                || self.imported_parent_of(name).is_some();
+            known && !self.is_deleted(name)
+        });
+        if !selection.is_empty() {
+            self.selection = selection;
         }
     }
 
+    /// Seleciona o objeto, abre os pais e a cena dele e rola a lista até a
+    /// linha; uma busca que o esconde é limpa
+    pub fn reveal_object(&mut self, object_name: &str) {
+        if self.is_deleted(object_name) {
+            return;
+        }
+        let mut cursor = self
+            .imported_parent_of(object_name)
+            .or_else(|| self.parent_of_current(object_name))
+            .map(str::to_string);
+        while let Some(parent) = cursor {
+            self.set_parent_open(&parent, true);
+            cursor = self
+                .imported_parent_of(&parent)
+                .or_else(|| self.parent_of_current(&parent))
+                .map(str::to_string);
+        }
+        let scene = self.scene_of(object_name).unwrap_or_default().to_string();
+        self.scene_open.insert(scene, true);
+        if self
+            .search_shown
+            .as_ref()
+            .is_some_and(|shown| !shown.contains(object_name))
+        {
+            self.search_query.clear();
+        }
+        self.selection.select(object_name);
+        self.scroll_to = Some(object_name.to_string());
+    }
+
     pub fn object_is_deleted(&self, object_name: &str) -> bool {
         self.is_deleted(object_name)
     }
 
-    fn tr(&self, key: &'static str) -> &'static str {
-        match (self.language, key) {
-            (EngineLanguage::Pt, "title") => "Hierarquia",
-            (EngineLanguage::En, "title") => "Hierarchy",
-            (EngineLanguage::Es, "title") => "Jerarquía",
-            (EngineLanguage::Pt, "copy") => "Copiar",
-            (EngineLanguage::En, "copy") => "Copy",
-            (EngineLanguage::Es, "copy") => "Copiar",
-            (EngineLanguage::Pt, "delete") => "Deletar",
-            (EngineLanguage::En, "delete") => "Delete",
-            (EngineLanguage::Es, "delete") => "Eliminar",
-            (EngineLanguage::Pt, "create_empty") => "Criar objeto vazio",
-            (EngineLanguage::En, "create_empty") => "Create Empty Object",
-            (EngineLanguage::Es, "create_empty") => "Crear objeto vacío",
-            (EngineLanguage::Pt, "lights") => "Luzes",
-            (EngineLanguage::En, "lights") => "Lights",
-            (EngineLanguage::Es, "lights") => "Luces",
-            (EngineLanguage::Pt, "pick_color") => "Selecionar cor",
-            (EngineLanguage::En, "pick_color") => "Pick color",
-            (EngineLanguage::Es, "pick_color") => "Seleccionar color",
-            _ => key,
+    /// Estado da cena na hierarquia, guardado pelo histórico do editor
+    pub fn snapshot(&self) -> HierarchySnapshot {
+        HierarchySnapshot {
+            object_colors: self.object_colors.clone(),
+            object_visibility: self.object_visibility.clone(),
+            deleted_objects: self.deleted_objects.clone(),
+            top_level_order: self.top_level_order.clone(),
+            player_order: self.player_order.clone(),
+            armature_order: self.armature_order.clone(),
+            environment_order: self.environment_order.clone(),
+            additive_scenes: self.additive_scenes.clone(),
+            object_scene: self.object_scene.clone(),
+            active_scene: self.active_scene.clone(),
+            imported_children: self.imported_children.clone(),
+            object_parents: self.object_parents.clone(),
         }
     }
 
-    fn object_label(&self, object_id: &str) -> String {
-        match (self.language, object_id) {
-            (EngineLanguage::Pt, "Directional Light") => "Luz Direcional".to_string(),
-            (EngineLanguage::En, "Directional Light") => "Directional Light".to_string(),
-            (EngineLanguage::Es, "Directional Light") => "Luz Direccional".to_string(),
-
-            (EngineLanguage::Pt, "Main Camera") => "Câmera Principal".to_string(),
-            (EngineLanguage::En, "Main Camera") => "Main Camera".to_string(),
-            (EngineLanguage::Es, "Main Camera") => "Cámara Principal".to_string(),
-
-            (EngineLanguage::Pt, "Player") => "Jogador".to_string(),
-            (EngineLanguage::En, "Player") => "Player".to_string(),
-            (EngineLanguage::Es, "Player") => "Jugador".to_string(),
-
-            (EngineLanguage::Pt, "Mesh") => "Malha".to_string(),
-            (EngineLanguage::En, "Mesh") => "Mesh".to_string(),
-            (EngineLanguage::Es, "Mesh") => "Malla".to_string(),
-
-            (EngineLanguage::Pt, "Weapon Socket") => "Encaixe de Arma".to_string(),
-            (EngineLanguage::En, "Weapon Socket") => "Weapon Socket".to_string(),
-            (EngineLanguage::Es, "Weapon Socket") => "Anclaje de Arma".to_string(),
+    /// Volta a um estado do histórico; selecionados que deixaram de existir
+    /// saem da seleção
+    pub fn restore(&mut self, snapshot: &HierarchySnapshot) {
+        let snapshot = snapshot.clone();
+        self.object_colors = snapshot.object_colors;
+        self.object_visibility = snapshot.object_visibility;
+        self.deleted_objects = snapshot.deleted_objects;
+        self.top_level_order = snapshot.top_level_order;
+        self.player_order = snapshot.player_order;
+        self.armature_order = snapshot.armature_order;
+        self.environment_order = snapshot.environment_order;
+        self.additive_scenes = snapshot.additive_scenes;
+        self.object_scene = snapshot.object_scene;
+        self.active_scene = snapshot.active_scene;
+        self.imported_children = snapshot.imported_children;
+        self.object_parents = snapshot.object_parents;
+        let selection = self.selection.clone();
+        self.selection = Selection::single("Main Camera");
+        self.set_selection(&selection);
+    }
 
-            (EngineLanguage::Pt, "Armature") => "Armadura".to_string(),
-            (EngineLanguage::En, "Armature") => "Armature".to_string(),
-            (EngineLanguage::Es, "Armature") => "Armadura".to_string(),
+    /// Hierarquia vazia para a edição isolada de um prefab; a cena de antes
+    /// volta com `restore`
+    pub fn clear_for_isolation(&mut self) {
+        self.restore(&HierarchySnapshot::default());
+    }
 
-            (EngineLanguage::Pt, "Spine") => "Espinha".to_string(),
-            (EngineLanguage::En, "Spine") => "Spine".to_string(),
-            (EngineLanguage::Es, "Spine") => "Columna".to_string(),
+    /// GUID estável do objeto, gerado no primeiro acesso
+    pub fn object_guid(&mut self, object_name: &str) -> Guid {
+        *self
+            .object_guids
+            .entry(object_name.to_string())
+            .or_insert_with(Guid::generate)
+    }
 
-            (EngineLanguage::Pt, "Head") => "Cabeça".to_string(),
-            (EngineLanguage::En, "Head") => "Head".to_string(),
-            (EngineLanguage::Es, "Head") => "Cabeza".to_string(),
+    /// Objeto vindo de um arquivo de cena mantém o GUID gravado nele
+    pub fn set_object_guid(&mut self, object_name: &str, guid: Guid) {
+        self.object_guids.insert(object_name.to_string(), guid);
+    }
 
-            (EngineLanguage::Pt, "Environment") => "Ambiente".to_string(),
-            (EngineLanguage::En, "Environment") => "Environment".to_string(),
-            (EngineLanguage::Es, "Environment") => "Entorno".to_string(),
+    /// Resolve uma referência por GUID para o nome do objeto (se ainda existir)
+    pub fn object_by_guid(&self, guid: Guid) -> Option<&str> {
+        self.object_guids
+            .iter()
+            .find(|(name, g)| **g == guid && !self.is_deleted(name))
+            .map(|(name, _)| name.as_str())
+    }
 
-            (EngineLanguage::Pt, "Terrain") => "Terreno".to_string(),
-            (EngineLanguage::En, "Terrain") => "Terrain".to_string(),
-            (EngineLanguage::Es, "Terrain") => "Terreno".to_string(),
+    /// Objeto sendo arrastado na Hierarquia (para campos de referência do Inspetor)
+    pub fn dragging_object_name(&self) -> Option<&str> {
+        self.dragging_object.as_deref()
+    }
 
-            (EngineLanguage::Pt, "Trees") => "Árvores".to_string(),
-            (EngineLanguage::En, "Trees") => "Trees".to_string(),
-            (EngineLanguage::Es, "Trees") => "Árboles".to_string(),
+    fn tr(&self, key: &'static str) -> &'static str {
+        i18n::text(self.language, "hierarchy", key)
+    }
 
-            (EngineLanguage::Pt, "Fog Volume") => "Volume de Névoa".to_string(),
-            (EngineLanguage::En, "Fog Volume") => "Fog Volume".to_string(),
-            (EngineLanguage::Es, "Fog Volume") => "Volumen de Niebla".to_string(),
-            _ => object_id.to_string(),
-        }
+    fn object_label(&self, object_id: &str) -> String {
+        i18n::lookup(self.language, "objects", object_id)
+            .unwrap_or(object_id)
+            .to_string()
     }
 
     fn is_parent_open(&self, object_id: &str) -> Option<bool> {
@@ -337,7 +684,7 @@ impl HierarchyWindow {
             "Player" => Some(self.player_open),
             "Armature" => Some(self.armature_open),
             "Environment" => Some(self.environment_open),
-            _ => None,
+            _ => self.imported_open.get(object_id).copied(),
         }
     }
 
@@ -346,7 +693,9 @@ impl HierarchyWindow {
             "Player" => self.player_open = open,
             "Armature" => self.armature_open = open,
             "Environment" => self.environment_open = open,
-            _ => {}
+            _ => {
+                self.imported_open.insert(object_id.to_string(), open);
+            }
         }
     }
 
@@ -364,6 +713,15 @@ impl HierarchyWindow {
         }
     }
 
+    fn order(&self, container: HierarchyContainer) -> &[String] {
+        match container {
+            HierarchyContainer::Top => &self.top_level_order,
+            HierarchyContainer::Player => &self.player_order,
+            HierarchyContainer::Armature => &self.armature_order,
+            HierarchyContainer::Environment => &self.environment_order,
+        }
+    }
+
     fn order_mut(&mut self, container: HierarchyContainer) -> &mut Vec<String> {
         match container {
             HierarchyContainer::Top => &mut self.top_level_order,
@@ -382,7 +740,10 @@ impl HierarchyWindow {
         }
     }
 
-    fn parent_of_current(&self, name: &str) -> Option<&'static str> {
+    fn parent_of_current(&self, name: &str) -> Option<&str> {
+        if let Some(parent) = self.object_parents.get(name) {
+            return Some(parent);
+        }
         match self.container_of(name) {
             Some(container) => Self::container_parent_object(container),
             None => None,
@@ -400,6 +761,42 @@ impl HierarchyWindow {
         false
     }
 
+    /// `dragged` pode virar filho de `parent` sem criar um ciclo
+    fn can_parent_to(&self, dragged: &str, parent: &str) -> bool {
+        dragged != parent
+            && self.container_of(parent) == Some(HierarchyContainer::Top)
+            && !self.is_descendant_of(parent, dragged)
+    }
+
+    /// Solta `object` do pai definido arrastando; ele volta ao topo
+    pub fn clear_object_parent(&mut self, object: &str) {
+        self.object_parents.remove(object);
+    }
+
+    /// Torna `object` filho de `parent`; ele e seus descendentes vão para a
+    /// cena do novo pai
+    pub fn set_object_parent(&mut self, object: &str, parent: &str) {
+        self.object_parents
+            .insert(object.to_string(), parent.to_string());
+        self.imported_open.insert(parent.to_string(), true);
+        let scene = self.scene_of(parent).map(str::to_string);
+        self.set_scene_recursive(object, scene);
+    }
+
+    fn set_scene_recursive(&mut self, object: &str, scene: Option<String>) {
+        match &scene {
+            Some(scene) => {
+                self.object_scene.insert(object.to_string(), scene.clone());
+            }
+            None => {
+                self.object_scene.remove(object);
+            }
+        }
+        for child in self.parented_children(object) {
+            self.set_scene_recursive(&child, scene.clone());
+        }
+    }
+
     fn can_move_to_container(&self, dragged: &str, to_container: HierarchyContainer) -> bool {
         if let Some(parent_obj) = Self::container_parent_object(to_container) {
             if parent_obj == dragged {
@@ -429,9 +826,17 @@ impl HierarchyWindow {
                 if !self.can_move_to_container(dragged, to_container) {
                     return;
                 }
+                // Soltar no espaço vazio tira o objeto do pai
+                if to_container == HierarchyContainer::Top {
+                    self.object_parents.remove(dragged);
+                }
                 if from_container == to_container {
                     return;
                 }
+                if to_container != HierarchyContainer::Top {
+                    self.object_parents.remove(dragged);
+                    self.set_scene_recursive(dragged, None);
+                }
                 self.remove_from_container(dragged, from_container);
                 let to_order = self.order_mut(to_container);
                 if !to_order.iter().any(|x| x == dragged) {
@@ -445,6 +850,14 @@ impl HierarchyWindow {
                 if !self.can_move_to_container(dragged, to_container) {
                     return;
                 }
+                // Antes ou depois de um filho, o arrastado vira irmão dele
+                let target_parent = self.object_parents.get(&target).cloned();
+                if target_parent
+                    .as_deref()
+                    .is_some_and(|parent| !self.can_parent_to(dragged, parent))
+                {
+                    return;
+                }
 
                 self.remove_from_container(dragged, from_container);
                 let to_order = self.order_mut(to_container);
@@ -456,6 +869,27 @@ impl HierarchyWindow {
                 if !to_order.iter().any(|x| x == dragged) {
                     to_order.insert(idx, dragged.to_string());
                 }
+                match target_parent {
+                    Some(parent) => {
+                        self.object_parents.insert(dragged.to_string(), parent);
+                    }
+                    None => {
+                        self.object_parents.remove(dragged);
+                    }
+                }
+                // Soltar sobre um objeto move o arrastado para a cena dele
+                let scene = self.scene_of(&target).map(str::to_string);
+                self.set_scene_recursive(dragged, scene);
+            }
+            HierarchyDropTarget::Parent(parent) => {
+                if !self.can_parent_to(dragged, &parent) {
+                    return;
+                }
+                if from_container != HierarchyContainer::Top {
+                    self.remove_from_container(dragged, from_container);
+                    self.top_level_order.push(dragged.to_string());
+                }
+                self.set_object_parent(dragged, &parent);
             }
         }
     }
@@ -472,6 +906,12 @@ impl HierarchyWindow {
     }
 
     fn is_deleted(&self, name: &str) -> bool {
+        if self
+            .imported_parent_of(name)
+            .is_some_and(|parent| self.is_deleted(parent))
+        {
+            return true;
+        }
         let mut cursor = Some(name);
         while let Some(current) = cursor {
             if self.deleted_objects.contains(current) {
@@ -491,28 +931,146 @@ impl HierarchyWindow {
         }
     }
 
-    fn delete_object_recursive(&mut self, name: &str) {
-        self.deleted_objects.insert(name.to_string());
-        self.object_colors.remove(name);
-        self.object_visibility.remove(name);
+    /// Marca `name` e descendentes como deletados; os que ainda não estavam
+    /// vão para `removed`
+    fn delete_object_recursive(&mut self, name: &str, removed: &mut Vec<RemovedObject>) {
+        let newly = self.deleted_objects.insert(name.to_string());
+        let color = self.object_colors.remove(name);
+        let visible = self.object_visibility.remove(name);
+        if newly {
+            removed.push(RemovedObject {
+                name: name.to_string(),
+                color,
+                visible,
+            });
+        }
         for &child in Self::children_of(name) {
-            self.delete_object_recursive(child);
+            self.delete_object_recursive(child, removed);
         }
-        if self.selected_object == name {
-            self.selected_object = "Main Camera".to_string();
+        for child in self
+            .imported_children
+            .get(name)
+            .cloned()
+            .unwrap_or_default()
+        {
+            self.delete_object_recursive(&child, removed);
         }
+        for child in self.parented_children(name) {
+            self.delete_object_recursive(&child, removed);
+        }
+        self.selection.retain(|selected| selected != name);
+        if self.selection.is_empty() {
+            self.selection.select("Main Camera");
+        }
+    }
+
+    /// Remove o objeto (e descendentes) sem pedir confirmação
+    pub fn remove_object(&mut self, object_name: &str) {
+        self.delete_object_recursive(object_name, &mut Vec::new());
     }
 
+    /// Remove os objetos sem gerar ação; devolve o que saiu, para voltar
+    /// com `restore_objects`
+    pub fn remove_objects(&mut self, object_names: &[String]) -> Vec<RemovedObject> {
+        let mut removed = Vec::new();
+        for name in object_names {
+            self.delete_object_recursive(name, &mut removed);
+        }
+        removed
+    }
+
+    /// Traz de volta objetos removidos, com cor e visibilidade
+    pub fn restore_objects(&mut self, removed: &[RemovedObject]) {
+        for object in removed {
+            self.deleted_objects.remove(&object.name);
+            if let Some(color) = object.color {
+                self.object_colors.insert(object.name.clone(), color);
+            }
+            if let Some(visible) = object.visible {
+                self.object_visibility.insert(object.name.clone(), visible);
+            }
+        }
+    }
+
+    /// Lugar atual do objeto, para desfazer um arrasto
+    fn placement(&self, object: &str) -> ObjectPlacement {
+        let container = self.container_of(object);
+        let index = container
+            .and_then(|c| self.order(c).iter().position(|n| n == object))
+            .unwrap_or(0);
+        let mut scenes = Vec::new();
+        let mut stack = vec![object.to_string()];
+        while let Some(name) = stack.pop() {
+            stack.extend(self.parented_children(&name));
+            let scene = self.object_scene.get(&name).cloned();
+            scenes.push((name, scene));
+        }
+        ObjectPlacement {
+            container,
+            index,
+            parent: self.object_parents.get(object).cloned(),
+            scenes,
+        }
+    }
+
+    /// Põe o objeto de volta num lugar guardado por `placement`
+    pub fn set_placement(&mut self, object: &str, placement: &ObjectPlacement) {
+        if let Some(container) = self.container_of(object) {
+            self.remove_from_container(object, container);
+        }
+        if let Some(container) = placement.container {
+            let order = self.order_mut(container);
+            let index = placement.index.min(order.len());
+            order.insert(index, object.to_string());
+        }
+        match &placement.parent {
+            Some(parent) => {
+                self.object_parents
+                    .insert(object.to_string(), parent.clone());
+            }
+            None => {
+                self.object_parents.remove(object);
+            }
+        }
+        for (name, scene) in &placement.scenes {
+            match scene {
+                Some(scene) => {
+                    self.object_scene.insert(name.clone(), scene.clone());
+                }
+                None => {
+                    self.object_scene.remove(name);
+                }
+            }
+        }
+    }
+
+    /// Ações feitas desde a última chamada, na ordem
+    pub fn take_edits(&mut self) -> Vec<HierarchyEdit> {
+        std::mem::take(&mut self.pending_edits)
+    }
+
+    /// Traz de volta um objeto removido
+    pub fn restore_object(&mut self, object_name: &str) {
+        self.deleted_objects.remove(object_name);
+    }
+
+    /// Pede confirmação para deletar `object_id`; se ele faz parte da
+    /// seleção, a seleção inteira é deletada
     fn request_delete_object(&mut self, object_id: &str) {
         if self.is_deleted(object_id) {
             return;
         }
-        self.pending_delete_object = Some(object_id.to_string());
+        let targets = if self.selection.contains(object_id) {
+            self.selection.names().to_vec()
+        } else {
+            vec![object_id.to_string()]
+        };
+        self.request_delete_objects(&targets);
     }
 
     pub fn request_delete_selected(&mut self) {
-        let selected = self.selected_object.clone();
-        self.request_delete_object(&selected);
+        let selected = self.selection.names().to_vec();
+        self.request_delete_objects(&selected);
     }
 
     pub fn handle_delete_shortcut(&mut self) {
@@ -523,8 +1081,15 @@ impl HierarchyWindow {
         }
     }
 
-    pub fn request_delete_by_name(&mut self, object_id: &str) {
-        self.request_delete_object(object_id);
+    pub fn request_delete_objects(&mut self, object_ids: &[String]) {
+        if !self.pending_delete_objects.is_empty() {
+            return;
+        }
+        self.pending_delete_objects = object_ids
+            .iter()
+            .filter(|name| !self.is_deleted(name))
+            .cloned()
+            .collect();
     }
 
     pub fn take_pending_material_drop(ctx: &egui::Context) -> Option<(String, String)> {
@@ -536,6 +1101,132 @@ impl HierarchyWindow {
         data
     }
 
+    /// Todos os objetos não deletados da cena, de qualquer nível
+    pub fn scene_objects(&self) -> Vec<String> {
+        self.top_level_order
+            .iter()
+            .chain(&self.player_order)
+            .chain(&self.armature_order)
+            .chain(&self.environment_order)
+            .chain(self.imported_children.values().flatten())
+            .filter(|name| !self.is_deleted(name))
+            .cloned()
+            .collect()
+    }
+
+    pub fn search_active(&self) -> bool {
+        !self.search_query.trim().is_empty()
+    }
+
+    pub fn set_search_components(&mut self, components: HashMap<String, Vec<&'static str>>) {
+        self.search_components = components;
+    }
+
+    /// Cada palavra da busca precisa bater com o nome do objeto ou com um
+    /// tipo de componente dele; `t:` procura só nos componentes
+    fn matches_search(&self, name: &str, terms: &[&str]) -> bool {
+        let label = self.object_label(name).to_lowercase();
+        let name_lower = name.to_lowercase();
+        let components = self
+            .search_components
+            .get(name)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        terms.iter().all(|term| {
+            let (term, names) = match term.strip_prefix("t:") {
+                Some(component) => (component, false),
+                None => (*term, true),
+            };
+            (names && (name_lower.contains(term) || label.contains(term)))
+                || components
+                    .iter()
+                    .any(|component| component.to_lowercase().contains(term))
+        })
+    }
+
+    /// Recalcula o que a busca mostra; quando ela muda, os pais dos
+    /// resultados (e as cenas deles) são abertos
+    fn update_search(&mut self) {
+        let query = self.search_query.trim().to_lowercase();
+        if query.is_empty() {
+            self.search_shown = None;
+            self.search_expanded_for.clear();
+            self.search_components.clear();
+            return;
+        }
+        let terms: Vec<&str> = query.split_whitespace().collect();
+        let mut shown = HashSet::new();
+        let mut parents = HashSet::new();
+        for name in self.scene_objects() {
+            if !self.matches_search(&name, &terms) {
+                continue;
+            }
+            let mut cursor = self
+                .imported_parent_of(&name)
+                .or_else(|| self.parent_of_current(&name));
+            while let Some(parent) = cursor {
+                parents.insert(parent.to_string());
+                cursor = self
+                    .imported_parent_of(parent)
+                    .or_else(|| self.parent_of_current(parent));
+            }
+            shown.insert(name);
+        }
+        if self.search_expanded_for != query {
+            for name in shown.iter().chain(&parents) {
+                let scene = self.scene_of(name).unwrap_or_default().to_string();
+                self.scene_open.insert(scene, true);
+            }
+            for parent in &parents {
+                self.set_parent_open(parent, true);
+            }
+            self.search_expanded_for = query;
+        }
+        shown.extend(parents);
+        self.search_shown = Some(shown);
+    }
+
+    /// Linha fora da lista: deletada ou escondida pela busca
+    fn row_hidden(&self, object_id: &str) -> bool {
+        self.is_deleted(object_id)
+            || self
+                .search_shown
+                .as_ref()
+                .is_some_and(|shown| !shown.contains(object_id))
+    }
+
+    fn draw_search_box(&mut self, ui: &mut egui::Ui, rect: Rect) {
+        let hint = self.tr("search");
+        let help = self.tr("search_help");
+        let clear_w = if self.search_query.is_empty() {
+            0.0
+        } else {
+            20.0
+        };
+        let edit_rect = Rect::from_min_max(rect.min, egui::pos2(rect.max.x - clear_w, rect.max.y));
+        let resp = ui
+            .put(
+                edit_rect,
+                egui::TextEdit::singleline(&mut self.search_query)
+                    .hint_text(hint)
+                    .desired_width(edit_rect.width()),
+            )
+            .on_hover_text(help);
+        if resp.lost_focus() && ui.input(|i| i.key_pressed(Key::Escape)) {
+            self.search_query.clear();
+        }
+        if clear_w > 0.0 {
+            let clear_rect =
+                Rect::from_min_max(egui::pos2(edit_rect.max.x + 2.0, rect.min.y), rect.max);
+            if ui
+                .put(clear_rect, egui::Button::new("✕").frame(false))
+                .clicked()
+            {
+                self.search_query.clear();
+            }
+        }
+    }
+
     fn draw_object_row(
         &mut self,
         ui: &mut egui::Ui,
@@ -544,9 +1235,10 @@ impl HierarchyWindow {
         label: &str,
         selected: bool,
     ) -> egui::Response {
-        if self.is_deleted(object_id) {
+        if self.row_hidden(object_id) {
             return ui.allocate_response(egui::vec2(0.0, 0.0), egui::Sense::hover());
         }
+        self.drawn_rows.push(object_id.to_string());
 
         let color_dot = self.effective_color(object_id);
         let is_visible = *self.object_visibility.get(object_id).unwrap_or(&true);
@@ -651,7 +1343,7 @@ impl HierarchyWindow {
             indent,
             object_id,
             &label,
-            self.selected_object == object_id,
+            self.selection.contains(object_id),
         );
         self.apply_row_interactions(ui, &resp, object_id, &label);
     }
@@ -663,6 +1355,10 @@ impl HierarchyWindow {
         object_id: &str,
         label: &str,
     ) {
+        if self.scroll_to.as_deref() == Some(object_id) {
+            self.scroll_to = None;
+            resp.scroll_to_me(Some(egui::Align::Center));
+        }
         let full_row_rect = Rect::from_min_max(
             resp.rect.min,
             egui::pos2(ui.max_rect().right(), resp.rect.max.y),
@@ -692,7 +1388,7 @@ impl HierarchyWindow {
                             ui.painter().rect_stroke(
                                 full_row_rect.shrink(1.0),
                                 3.0,
-                                Stroke::new(2.0, Color32::from_rgb(255, 165, 0)),
+                                Stroke::new(2.0, theme::palette().warning),
                                 egui::StrokeKind::Outside,
                             );
 
@@ -713,11 +1409,19 @@ impl HierarchyWindow {
         }
 
         if !self.is_deleted(object_id) {
-            let mut copy_clicked = false;
+            let mut clipboard_clicked = None;
             let mut delete_clicked = false;
             drag_resp.context_menu(|ui| {
                 if ui.button(self.tr("copy")).clicked() {
-                    copy_clicked = true;
+                    clipboard_clicked = Some(ClipboardRequest::Copy);
+                    ui.close();
+                }
+                if ui.button(self.tr("paste")).clicked() {
+                    clipboard_clicked = Some(ClipboardRequest::Paste);
+                    ui.close();
+                }
+                if ui.button(self.tr("duplicate")).clicked() {
+                    clipboard_clicked = Some(ClipboardRequest::Duplicate);
                     ui.close();
                 }
                 if ui.button(self.tr("delete")).clicked() {
@@ -725,15 +1429,25 @@ impl HierarchyWindow {
                     ui.close();
                 }
             });
-            if copy_clicked {
+            if let Some(request) = clipboard_clicked {
+                // O menu age sobre a linha clicada, que entra na seleção
+                if !self.selection.contains(object_id) {
+                    self.selection.select(object_id);
+                }
                 ui.ctx().copy_text(label.to_owned());
+                self.pending_clipboard_request = Some(request);
             }
             if delete_clicked {
                 self.request_delete_object(object_id);
             }
         }
         if resp.clicked() || drag_resp.clicked() {
-            self.selected_object = object_id.to_string();
+            let modifiers = ui.input(|i| i.modifiers);
+            self.selection.click(object_id, modifiers, &self.row_order);
+            // A hierarquia sempre tem um objeto ativo
+            if self.selection.is_empty() {
+                self.selection.select(object_id);
+            }
         }
         if drag_resp.drag_started() {
             self.dragging_object = Some(object_id.to_string());
@@ -752,20 +1466,28 @@ impl HierarchyWindow {
                     .unwrap_or(full_row_rect.center().y);
                 let top_band = full_row_rect.top() + full_row_rect.height() * 0.28;
                 let bottom_band = full_row_rect.bottom() - full_row_rect.height() * 0.28;
-                let as_container = match object_id {
-                    "Player" => Some(HierarchyContainer::Player),
-                    "Armature" => Some(HierarchyContainer::Armature),
-                    "Environment" => Some(HierarchyContainer::Environment),
+                // No meio da linha, o arrastado entra no objeto
+                let drop_inside = match object_id {
+                    "Player" => Some(HierarchyDropTarget::Container(HierarchyContainer::Player)),
+                    "Armature" => {
+                        Some(HierarchyDropTarget::Container(HierarchyContainer::Armature))
+                    }
+                    "Environment" => Some(HierarchyDropTarget::Container(
+                        HierarchyContainer::Environment,
+                    )),
+                    _ if self.can_parent_to(dragging, object_id) => {
+                        Some(HierarchyDropTarget::Parent(object_id.to_string()))
+                    }
                     _ => None,
                 };
 
-                if let Some(container) = as_container {
-                    if hover_y > top_band && hover_y < bottom_band {
-                        self.drop_target = Some(HierarchyDropTarget::Container(container));
+                match drop_inside {
+                    Some(target) if hover_y > top_band && hover_y < bottom_band => {
+                        self.drop_target = Some(target);
                         ui.painter().rect_stroke(
                             full_row_rect.shrink(1.0),
                             3.0,
-                            Stroke::new(1.5, Color32::from_rgb(15, 232, 121)),
+                            Stroke::new(1.5, theme::palette().accent),
                             egui::StrokeKind::Outside,
                         );
                         if self.is_parent_open(object_id) == Some(false) {
@@ -783,7 +1505,8 @@ impl HierarchyWindow {
                         } else {
                             self.drag_hover_parent = None;
                         }
-                    } else {
+                    }
+                    _ => {
                         let after = hover_y > full_row_rect.center().y;
                         self.drop_target = Some(HierarchyDropTarget::Row {
                             target: object_id.to_string(),
@@ -794,34 +1517,20 @@ impl HierarchyWindow {
                         } else {
                             full_row_rect.top()
                         };
+                        // A linha começa no recuo da linha alvo: o arrastado
+                        // fica no mesmo nível dela
+                        let start = egui::pos2(resp.rect.left(), y);
+                        let stroke = Stroke::new(2.0, theme::palette().accent);
+                        ui.painter().circle_stroke(start, 3.0, stroke);
                         ui.painter().line_segment(
                             [
-                                egui::pos2(full_row_rect.left(), y),
+                                start + egui::vec2(3.0, 0.0),
                                 egui::pos2(full_row_rect.right(), y),
                             ],
-                            Stroke::new(2.0, Color32::from_rgb(15, 232, 121)),
+                            stroke,
                         );
                         self.drag_hover_parent = None;
                     }
-                } else {
-                    let after = hover_y > full_row_rect.center().y;
-                    self.drop_target = Some(HierarchyDropTarget::Row {
-                        target: object_id.to_string(),
-                        after,
-                    });
-                    let y = if after {
-                        full_row_rect.bottom()
-                    } else {
-                        full_row_rect.top()
-                    };
-                    ui.painter().line_segment(
-                        [
-                            egui::pos2(full_row_rect.left(), y),
-                            egui::pos2(full_row_rect.right(), y),
-                        ],
-                        Stroke::new(2.0, Color32::from_rgb(15, 232, 121)),
-                    );
-                    self.drag_hover_parent = None;
                 }
             }
         }
@@ -835,12 +1544,15 @@ impl HierarchyWindow {
         label: String,
         is_open: &mut bool,
     ) {
+        if self.row_hidden(object_id) {
+            return;
+        }
         let resp = self.draw_object_row(
             ui,
             indent + 16.0,
             object_id,
             &label,
-            self.selected_object == object_id,
+            self.selection.contains(object_id),
         );
         self.apply_row_interactions(ui, &resp, object_id, &label);
 
@@ -880,83 +1592,231 @@ impl HierarchyWindow {
         }
     }
 
-    pub fn show(
+    /// Objeto da lista de topo com o que estiver embaixo dele; Player e
+    /// Environment têm filhos fixos
+    fn draw_scene_object(&mut self, ui: &mut egui::Ui, indent: f32, object: &str) {
+        match object {
+            "Player" => {
+                let mut player_open = self.player_open;
+                self.draw_parent_row_with_context(
+                    ui,
+                    indent,
+                    "Player",
+                    self.object_label("Player"),
+                    &mut player_open,
+                );
+                self.player_open = player_open;
+                if !self.player_open {
+                    return;
+                }
+                for child in self.player_order.clone() {
+                    match child.as_str() {
+                        "Mesh" | "Weapon Socket" => {
+                            self.draw_object_row_with_context(
+                                ui,
+                                indent + 18.0,
+                                &child,
+                                self.object_label(&child),
+                            );
+                        }
+                        "Armature" => {
+                            let mut armature_open = self.armature_open;
+                            self.draw_parent_row_with_context(
+                                ui,
+                                indent + 18.0,
+                                "Armature",
+                                self.object_label("Armature"),
+                                &mut armature_open,
+                            );
+                            self.armature_open = armature_open;
+                            if !self.armature_open {
+                                continue;
+                            }
+                            for arm_child in self.armature_order.clone() {
+                                if matches!(arm_child.as_str(), "Spine" | "Head") {
+                                    self.draw_object_row_with_context(
+                                        ui,
+                                        indent + 36.0,
+                                        &arm_child,
+                                        self.object_label(&arm_child),
+                                    );
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            "Environment" => {
+                let mut environment_open = self.environment_open;
+                self.draw_parent_row_with_context(
+                    ui,
+                    indent,
+                    "Environment",
+                    self.object_label("Environment"),
+                    &mut environment_open,
+                );
+                self.environment_open = environment_open;
+                if !self.environment_open {
+                    return;
+                }
+                for env_child in self.environment_order.clone() {
+                    if matches!(env_child.as_str(), "Terrain" | "Trees" | "Fog Volume") {
+                        self.draw_object_row_with_context(
+                            ui,
+                            indent + 18.0,
+                            &env_child,
+                            self.object_label(&env_child),
+                        );
+                    }
+                }
+            }
+            _ => self.draw_top_level_object(ui, indent, object, self.object_label(object)),
+        }
+    }
+
+    /// Objeto de topo; cenas importadas aparecem como pai dos seus nós e
+    /// objetos soltos sobre ele aparecem como filhos
+    fn draw_top_level_object(
         &mut self,
-        ctx: &egui::Context,
-        left_reserved: f32,
-        right_reserved: f32,
-        bottom_reserved: f32,
-        language: EngineLanguage,
+        ui: &mut egui::Ui,
+        indent: f32,
+        object_id: &str,
+        label: String,
     ) {
+        let imported = self
+            .imported_children
+            .get(object_id)
+            .cloned()
+            .unwrap_or_default();
+        let parented = self.parented_children(object_id);
+        if imported.is_empty() && parented.is_empty() {
+            self.draw_object_row_with_context(ui, indent, object_id, label);
+            return;
+        }
+        let mut open = self.imported_open.get(object_id).copied().unwrap_or(true);
+        self.draw_parent_row_with_context(ui, indent, object_id, label, &mut open);
+        self.imported_open.insert(object_id.to_string(), open);
+        if !open {
+            return;
+        }
+        let prefix = format!("{}/", object_id);
+        for child in imported {
+            let label = child.strip_prefix(&prefix).unwrap_or(&child).to_string();
+            self.draw_object_row_with_context(ui, indent + 18.0, &child, label);
+        }
+        for child in parented {
+            self.draw_scene_object(ui, indent + 18.0, &child);
+        }
+    }
+
+    fn draw_scene_header(&mut self, ui: &mut egui::Ui, scene: Option<&str>) -> bool {
+        let key = scene.unwrap_or_default().to_string();
+        let is_open = *self.scene_open.get(&key).unwrap_or(&true);
+        let is_active = self.active_scene.as_deref() == scene;
+        let label = scene.unwrap_or(self.tr("main_scene")).to_string();
+
+        let (row_rect, resp) =
+            ui.allocate_exact_size(egui::vec2(ui.available_width(), 20.0), egui::Sense::click());
+        if resp.hovered() {
+            ui.painter().rect_filled(
+                row_rect,
+                3.0,
+                Color32::from_rgba_unmultiplied(255, 255, 255, 12),
+            );
+        }
+        ui.painter().text(
+            egui::pos2(row_rect.left() + 6.0, row_rect.center().y),
+            Align2::CENTER_CENTER,
+            if is_open { "▾" } else { "▸" },
+            FontId::new(11.0, FontFamily::Proportional),
+            Color32::from_gray(140),
+        );
+        ui.painter().text(
+            egui::pos2(row_rect.left() + 16.0, row_rect.center().y),
+            Align2::LEFT_CENTER,
+            label,
+            FontId::new(12.0, FontFamily::Proportional),
+            if is_active {
+                theme::palette().accent
+            } else {
+                theme::palette().text
+            },
+        );
+        ui.painter().line_segment(
+            [
+                egui::pos2(row_rect.left(), row_rect.bottom()),
+                egui::pos2(row_rect.right(), row_rect.bottom()),
+            ],
+            Stroke::new(1.0, Color32::from_gray(50)),
+        );
+
+        if resp.clicked() {
+            if ui
+                .ctx()
+                .input(|i| i.pointer.interact_pos())
+                .is_some_and(|p| p.x < row_rect.left() + 14.0)
+            {
+                self.scene_open.insert(key.clone(), !is_open);
+            } else {
+                self.active_scene = scene.map(str::to_string);
+            }
+        }
+        if resp.double_clicked() {
+            self.scene_open.insert(key, !is_open);
+        }
+
+        resp.context_menu(|ui| {
+            if ui.button(self.tr("set_active_scene")).clicked() {
+                self.active_scene = scene.map(str::to_string);
+                ui.close();
+            }
+            if ui.button(self.tr("save_scene_as")).clicked() {
+                self.pending_scene_request = Some(SceneRequest::SaveAs(scene.map(str::to_string)));
+                ui.close();
+            }
+            if scene.is_some() && ui.button(self.tr("unload_scene")).clicked() {
+                self.pending_scene_request = scene.map(|s| SceneRequest::Unload(s.to_string()));
+                ui.close();
+            }
+        });
+        is_open
+    }
+
+    /// Desenha a hierarquia em `panel`, o espaço que o layout de painéis deu a ela
+    pub fn show(&mut self, ctx: &egui::Context, panel: Rect, language: EngineLanguage) {
+        engine_core::profile_function!();
         if !self.open {
             return;
         }
         self.language = language;
         self.hovered_object = None;
+        self.row_order = std::mem::take(&mut self.drawn_rows);
+        self.update_search();
 
         if self.selector_icon_texture.is_none() {
-            self.selector_icon_texture =
-                load_png_as_texture(ctx, "src/assets/icons/seletorcor.png");
+            self.selector_icon_texture = load_png_as_texture(ctx, "Editor/icons/seletorcor.png");
         }
         if self.arrow_icon_texture.is_none() {
-            self.arrow_icon_texture = load_png_as_texture(ctx, "src/assets/icons/seta.png");
+            self.arrow_icon_texture = load_png_as_texture(ctx, "Editor/icons/seta.png");
         }
         if self.view_icon_texture.is_none() {
-            self.view_icon_texture = load_png_as_texture(ctx, "src/assets/icons/view.png");
+            self.view_icon_texture = load_png_as_texture(ctx, "Editor/icons/view.png");
         }
         if self.no_view_icon_texture.is_none() {
-            self.no_view_icon_texture = load_png_as_texture(ctx, "src/assets/icons/noview.png");
+            self.no_view_icon_texture = load_png_as_texture(ctx, "Editor/icons/noview.png");
         }
         if self.camera_icon_texture.is_none() {
-            self.camera_icon_texture = load_png_as_texture(ctx, "src/assets/icons/camera.png");
+            self.camera_icon_texture = load_png_as_texture(ctx, "Editor/icons/camera.png");
         }
         if self.sun_icon_texture.is_none() {
-            self.sun_icon_texture = load_png_as_texture(ctx, "src/assets/icons/sol.png");
+            self.sun_icon_texture = load_png_as_texture(ctx, "Editor/icons/sol.png");
         }
 
-        let dock_rect = ctx.available_rect();
-        let usable_bottom = (dock_rect.bottom() - bottom_reserved).max(dock_rect.top() + 120.0);
-        let usable_height = (usable_bottom - dock_rect.top()).max(120.0);
         let pointer_down = ctx.input(|i| i.pointer.primary_down());
-
-        let height = if self.dock_side.is_some() {
-            usable_height
-        } else {
-            (usable_height * 0.85).max(520.0)
-        };
-        let max_width = (dock_rect.width() - left_reserved - right_reserved - 40.0).max(180.0);
-        self.window_width = self.window_width.clamp(180.0, max_width.min(520.0));
-        let window_size = egui::vec2(self.window_width, height);
-        let left_snap_x = dock_rect.left() + left_reserved;
-        let right_snap_right = dock_rect.right() - right_reserved;
-
-        if self.window_pos.is_none() {
-            self.window_pos = Some(egui::pos2(
-                right_snap_right - self.window_width,
-                dock_rect.top(),
-            ));
-        }
-
-        if let Some(side) = self.dock_side {
-            if !self.dragging_from_header && !self.resizing_width && !pointer_down {
-                let x = match side {
-                    HierarchyDockSide::Left => left_snap_x,
-                    HierarchyDockSide::Right => right_snap_right - self.window_width,
-                };
-                self.window_pos = Some(egui::pos2(x, dock_rect.top()));
-            }
-        }
-
-        let pos = self.window_pos.unwrap_or(egui::pos2(
-            right_snap_right - self.window_width,
-            dock_rect.top(),
-        ));
-
-        let mut header_drag_started = false;
-        let mut header_drag_stopped = false;
-        let mut resize_started = false;
-        let mut resize_stopped = false;
-        let mut panel_rect = Rect::from_min_size(pos, window_size);
+        let pos = panel.min;
+        let window_size = panel.size();
+        let mut panel_rect = panel;
         let mut selector_icon_rect: Option<Rect> = None;
 
         egui::Area::new(Id::new("hierarquia_window_id"))
@@ -986,23 +1846,13 @@ impl HierarchyWindow {
                     egui::pos2(header_rect.max.x - icon_side, header_rect.min.y + 1.0),
                     egui::vec2(icon_side, icon_side),
                 );
-                let drag_rect =
-                    Rect::from_min_max(header_rect.min, egui::pos2(icon_rect.min.x - 4.0, header_rect.max.y));
-
-                let drag_resp = ui.interact(
-                    drag_rect,
-                    ui.id().with("hierarchy_header_drag"),
-                    egui::Sense::click_and_drag(),
+                let title_rect = Rect::from_min_max(
+                    header_rect.min,
+                    egui::pos2(icon_rect.min.x - 4.0, header_rect.max.y),
                 );
-                if drag_resp.drag_started() {
-                    header_drag_started = true;
-                }
-                if drag_resp.drag_stopped() {
-                    header_drag_stopped = true;
-                }
 
                 ui.painter().text(
-                    drag_rect.center(),
+                    title_rect.center(),
                     Align2::CENTER_CENTER,
                     self.tr("title"),
                     FontId::new(13.0, FontFamily::Proportional),
@@ -1028,10 +1878,10 @@ impl HierarchyWindow {
                         self.color_picker_open = !self.color_picker_open;
                         self.picker_color = self
                             .object_colors
-                            .get(&self.selected_object)
+                            .get(self.selected_object_name())
                             .copied()
-                            .or_else(|| self.effective_color(&self.selected_object))
-                            .unwrap_or(Color32::from_rgb(15, 232, 121));
+                            .or_else(|| self.effective_color(self.selected_object_name()))
+                            .unwrap_or(theme::palette().accent);
                     }
                 }
 
@@ -1044,14 +1894,20 @@ impl HierarchyWindow {
                     Stroke::new(1.0, Color32::from_gray(60)),
                 );
 
+                let search_rect = Rect::from_min_max(
+                    egui::pos2(inner.min.x, sep_y + 6.0),
+                    egui::pos2(inner.max.x, sep_y + 26.0),
+                );
+                self.draw_search_box(ui, search_rect);
+
                 let content_rect = Rect::from_min_max(
-                    egui::pos2(inner.min.x, sep_y + 8.0),
+                    egui::pos2(inner.min.x, search_rect.bottom() + 6.0),
                     egui::pos2(inner.max.x, rect.bottom() - 6.0),
                 );
                 ui.scope_builder(
-                    egui::UiBuilder::new().max_rect(content_rect).layout(egui::Layout::top_down(
-                        egui::Align::Min,
-                    )),
+                    egui::UiBuilder::new()
+                        .max_rect(content_rect)
+                        .layout(egui::Layout::top_down(egui::Align::Min)),
                     |ui| {
                         egui::ScrollArea::vertical()
                             .id_salt("hierarchy_scroll")
@@ -1061,142 +1917,31 @@ impl HierarchyWindow {
                                 ui.style_mut().visuals.selection.bg_fill =
                                     Color32::from_rgb(47, 47, 47);
                                 ui.style_mut().visuals.selection.stroke =
-                                    Stroke::new(1.0, Color32::from_rgb(15, 232, 121));
+                                    Stroke::new(1.0, theme::palette().accent);
+
+                                let grouped = !self.additive_scenes.is_empty();
+                                let main_open = !grouped || self.draw_scene_header(ui, None);
 
                                 let top_order = self.top_level_order.clone();
                                 for object in top_order {
-                                    match object.as_str() {
-                                        "Directional Light" => {
-                                            self.draw_object_row_with_context(
-                                                ui,
-                                                0.0,
-                                                "Directional Light",
-                                                self.object_label("Directional Light"),
-                                            );
-                                        }
-                                        "Main Camera" => {
-                                            self.draw_object_row_with_context(
-                                                ui,
-                                                0.0,
-                                                "Main Camera",
-                                                self.object_label("Main Camera"),
-                                            );
-                                        }
-                                        "Player" => {
-                                            let mut player_open = self.player_open;
-                                            self.draw_parent_row_with_context(
-                                                ui,
-                                                0.0,
-                                                "Player",
-                                                self.object_label("Player"),
-                                                &mut player_open,
-                                            );
-                                            self.player_open = player_open;
-
-                                            if self.player_open {
-                                                let player_children = self.player_order.clone();
-                                                for child in player_children {
-                                                    match child.as_str() {
-                                                        "Mesh" => {
-                                                            self.draw_object_row_with_context(
-                                                                ui,
-                                                                18.0,
-                                                                "Mesh",
-                                                                self.object_label("Mesh"),
-                                                            );
-                                                        }
-                                                        "Weapon Socket" => {
-                                                            self.draw_object_row_with_context(
-                                                                ui,
-                                                                18.0,
-                                                                "Weapon Socket",
-                                                                self.object_label("Weapon Socket"),
-                                                            );
-                                                        }
-                                                        "Armature" => {
-                                                            let mut armature_open = self.armature_open;
-                                                            self.draw_parent_row_with_context(
-                                                                ui,
-                                                                18.0,
-                                                                "Armature",
-                                                                self.object_label("Armature"),
-                                                                &mut armature_open,
-                                                            );
-                                                            self.armature_open = armature_open;
-                                                            if self.armature_open {
-                                                                let arm_children =
-                                                                    self.armature_order.clone();
-                                                                for arm_child in arm_children {
-                                                                    match arm_child.as_str() {
-                                                                        "Spine" => self
-                                                                            .draw_object_row_with_context(
-                                                                                ui,
-                                                                                36.0,
-                                                                                "Spine",
-                                                                                self.object_label("Spine"),
-                                                                            ),
-                                                                        "Head" => self
-                                                                            .draw_object_row_with_context(
-                                                                                ui,
-                                                                                36.0,
-                                                                                "Head",
-                                                                                self.object_label("Head"),
-                                                                            ),
-                                                                        _ => {}
-                                                                    }
-                                                                }
-                                                            }
-                                                        }
-                                                        _ => {}
-                                                    }
-                                                }
-                                            }
-                                        }
-                                        "Environment" => {
-                                            let mut environment_open = self.environment_open;
-                                            self.draw_parent_row_with_context(
-                                                ui,
-                                                0.0,
-                                                "Environment",
-                                                self.object_label("Environment"),
-                                                &mut environment_open,
-                                            );
-                                            self.environment_open = environment_open;
-                                            if self.environment_open {
-                                                let env_children = self.environment_order.clone();
-                                                for env_child in env_children {
-                                                    match env_child.as_str() {
-                                                        "Terrain" => self.draw_object_row_with_context(
-                                                            ui,
-                                                            18.0,
-                                                            "Terrain",
-                                                            self.object_label("Terrain"),
-                                                        ),
-                                                        "Trees" => self.draw_object_row_with_context(
-                                                            ui,
-                                                            18.0,
-                                                            "Trees",
-                                                            self.object_label("Trees"),
-                                                        ),
-                                                        "Fog Volume" => self
-                                                            .draw_object_row_with_context(
-                                                                ui,
-                                                                18.0,
-                                                                "Fog Volume",
-                                                                self.object_label("Fog Volume"),
-                                                            ),
-                                                        _ => {}
-                                                    }
-                                                }
-                                            }
-                                        }
-                                        _ => {
-                                            self.draw_object_row_with_context(
-                                                ui,
-                                                0.0,
-                                                &object,
-                                                object.clone(),
-                                            );
+                                    if !main_open
+                                        || self.scene_of(&object).is_some()
+                                        || self.object_parents.contains_key(&object)
+                                    {
+                                        continue;
+                                    }
+                                    self.draw_scene_object(ui, 0.0, &object);
+                                }
+
+                                for scene in self.additive_scenes.clone() {
+                                    if !self.draw_scene_header(ui, Some(&scene)) {
+                                        continue;
+                                    }
+                                    for object in self.top_level_order.clone() {
+                                        if self.scene_of(&object) == Some(scene.as_str())
+                                            && !self.object_parents.contains_key(&object)
+                                        {
+                                            self.draw_scene_object(ui, 12.0, &object);
                                         }
                                     }
                                 }
@@ -1218,7 +1963,7 @@ impl HierarchyWindow {
                                     ui.painter().rect_stroke(
                                         empty_rect.shrink(2.0),
                                         3.0,
-                                        Stroke::new(1.5, Color32::from_rgb(15, 232, 121)),
+                                        Stroke::new(1.5, theme::palette().accent),
                                         egui::StrokeKind::Outside,
                                     );
                                 }
@@ -1226,6 +1971,20 @@ impl HierarchyWindow {
                                     if ui.button(self.tr("create_empty")).clicked() {
                                         ui.close();
                                     }
+                                    if ui.button(self.tr("paste")).clicked() {
+                                        self.pending_clipboard_request =
+                                            Some(ClipboardRequest::Paste);
+                                        ui.close();
+                                    }
+                                    if ui.button(self.tr("add_scene")).clicked() {
+                                        self.pending_scene_request = Some(SceneRequest::Create);
+                                        ui.close();
+                                    }
+                                    if ui.button(self.tr("load_scene_additive")).clicked() {
+                                        self.pending_scene_request =
+                                            Some(SceneRequest::LoadAdditive);
+                                        ui.close();
+                                    }
                                     ui.menu_button("3D", |ui| {
                                         if ui.button("Cube").clicked() {
                                             self.request_spawn_primitive(Primitive3DKind::Cube);
@@ -1250,7 +2009,14 @@ impl HierarchyWindow {
                                     });
                                     ui.menu_button(self.tr("lights"), |ui| {
                                         if ui.button("Directional Light").clicked() {
-                                            self.deleted_objects.remove("Directional Light");
+                                            // Restaura a luz padrão; se já existir, cria uma direcional extra
+                                            if self.is_deleted("Directional Light") {
+                                                self.deleted_objects.remove("Directional Light");
+                                            } else {
+                                                self.request_spawn_light(
+                                                    inspector::LightType::Directional,
+                                                );
+                                            }
                                             ui.close();
                                         }
                                         if ui.button("Point Light").clicked() {
@@ -1266,32 +2032,6 @@ impl HierarchyWindow {
                             });
                     },
                 );
-
-                let handle_w = 10.0;
-                let handle_rect = match self.dock_side {
-                    Some(HierarchyDockSide::Right) => Rect::from_min_max(
-                        egui::pos2(rect.left(), rect.top()),
-                        egui::pos2(rect.left() + handle_w, rect.bottom()),
-                    ),
-                    _ => Rect::from_min_max(
-                        egui::pos2(rect.right() - handle_w, rect.top()),
-                        egui::pos2(rect.right(), rect.bottom()),
-                    ),
-                };
-                let resize_resp = ui.interact(
-                    handle_rect,
-                    ui.id().with("hierarchy_width_resize_handle"),
-                    egui::Sense::click_and_drag(),
-                );
-                if resize_resp.hovered() || resize_resp.dragged() {
-                    ui.output_mut(|o| o.cursor_icon = egui::CursorIcon::ResizeHorizontal);
-                }
-                if resize_resp.drag_started() {
-                    resize_started = true;
-                }
-                if resize_resp.drag_stopped() {
-                    resize_stopped = true;
-                }
             });
 
         if self.color_picker_open {
@@ -1310,30 +2050,26 @@ impl HierarchyWindow {
                         let mut color = self.picker_color;
                         if ui.color_edit_button_srgba(&mut color).changed() {
                             self.picker_color = color;
-                            self.object_colors
-                                .insert(self.selected_object.clone(), color);
+                            for name in self.selection.names() {
+                                self.object_colors.insert(name.clone(), color);
+                            }
                             self.color_picker_open = false;
                         }
                     });
                 });
         }
 
-        if let Some(target) = self.pending_delete_object.clone() {
-            let title = match self.language {
-                EngineLanguage::Pt => "Confirmar exclusao",
-                EngineLanguage::En => "Confirm deletion",
-                EngineLanguage::Es => "Confirmar eliminacion",
-            };
-            let question = match self.language {
-                EngineLanguage::Pt => format!("Deseja deletar \"{target}\"?"),
-                EngineLanguage::En => format!("Do you want to delete \"{target}\"?"),
-                EngineLanguage::Es => format!("Desea eliminar \"{target}\"?"),
-            };
-            let cancel_label = match self.language {
-                EngineLanguage::Pt => "Cancelar",
-                EngineLanguage::En => "Cancel",
-                EngineLanguage::Es => "Cancelar",
+        if !self.pending_delete_objects.is_empty() {
+            let targets = self.pending_delete_objects.clone();
+            let target = match targets.as_slice() {
+                [single] => format!("\"{single}\""),
+                _ => self
+                    .tr("objects_count")
+                    .replace("{count}", &targets.len().to_string()),
             };
+            let title = self.tr("confirm_deletion");
+            let question = self.tr("delete_question").replace("{target}", &target);
+            let cancel_label = self.tr("cancel");
             let delete_label = self.tr("delete");
             let mut confirm_delete = false;
             let mut confirm_cancel = false;
@@ -1371,93 +2107,33 @@ impl HierarchyWindow {
             }
 
             if confirm_delete {
-                self.delete_object_recursive(&target);
-                self.pending_delete_object = None;
-            } else if confirm_cancel {
-                self.pending_delete_object = None;
-            }
-        }
-
-        if header_drag_started {
-            self.dragging_from_header = true;
-            self.resizing_width = false;
-            self.dock_side = None;
-        }
-        if resize_started {
-            self.resizing_width = true;
-            self.dragging_from_header = false;
-        }
-
-        let delta = ctx.input(|i| i.pointer.delta());
-        if pointer_down {
-            if self.dragging_from_header && delta != Vec2::ZERO {
-                if let Some(p) = self.window_pos {
-                    self.window_pos = Some(p + delta);
+                let mut removed = Vec::new();
+                for target in &targets {
+                    self.delete_object_recursive(target, &mut removed);
                 }
-            } else if self.resizing_width && delta.x != 0.0 {
-                match self.dock_side {
-                    Some(HierarchyDockSide::Right) => {
-                        let old_w = self.window_width;
-                        let new_w = (old_w - delta.x).clamp(180.0, 520.0);
-                        let applied = old_w - new_w;
-                        self.window_width = new_w;
-                        if let Some(p) = self.window_pos {
-                            self.window_pos = Some(egui::pos2(p.x + applied, p.y));
-                        }
-                    }
-                    _ => {
-                        self.window_width = (self.window_width + delta.x).clamp(180.0, 520.0);
-                    }
+                if !removed.is_empty() {
+                    self.pending_edits.push(HierarchyEdit::Delete(removed));
                 }
+                self.pending_delete_objects.clear();
+            } else if confirm_cancel {
+                self.pending_delete_objects.clear();
             }
         }
 
-        let near_left = (panel_rect.left() - left_snap_x).abs() <= 28.0;
-        let near_right = (right_snap_right - panel_rect.right()).abs() <= 28.0;
-        if self.dragging_from_header && pointer_down && (near_left || near_right) {
-            let hint_w = 14.0;
-            let hint_rect = if near_left {
-                Rect::from_min_max(
-                    egui::pos2(left_snap_x, dock_rect.top()),
-                    egui::pos2(left_snap_x + hint_w, usable_bottom),
-                )
-            } else {
-                Rect::from_min_max(
-                    egui::pos2(right_snap_right - hint_w, dock_rect.top()),
-                    egui::pos2(right_snap_right, usable_bottom),
-                )
-            };
-            ctx.layer_painter(egui::LayerId::new(
-                Order::Foreground,
-                Id::new("hierarchy_dock_hint"),
-            ))
-            .rect_filled(
-                hint_rect,
-                6.0,
-                Color32::from_rgba_unmultiplied(15, 232, 121, 110),
-            );
-        }
-
-        if header_drag_stopped || (self.dragging_from_header && !pointer_down) {
-            self.dragging_from_header = false;
-            if near_left {
-                self.dock_side = Some(HierarchyDockSide::Left);
-            } else if near_right {
-                self.dock_side = Some(HierarchyDockSide::Right);
-            } else {
-                self.dock_side = None;
-            }
-        }
-
-        if resize_stopped || (self.resizing_width && !pointer_down) {
-            self.resizing_width = false;
-        }
-
         if self.dragging_object.is_some() && !pointer_down {
             if let (Some(dragged), Some(target)) =
                 (self.dragging_object.clone(), self.drop_target.clone())
             {
+                let before = self.placement(&dragged);
                 self.move_to_target(&dragged, target);
+                let after = self.placement(&dragged);
+                if after != before {
+                    self.pending_edits.push(HierarchyEdit::Reparent {
+                        object: dragged,
+                        before,
+                        after,
+                    });
+                }
             }
             self.dragging_object = None;
             self.drop_target = None;
@@ -1488,25 +2164,9 @@ impl HierarchyWindow {
                     Align2::LEFT_CENTER,
                     self.object_label(dragging),
                     FontId::new(12.0, FontFamily::Proportional),
-                    Color32::from_gray(220),
+                    theme::palette().text_strong,
                 );
             }
         }
     }
-
-    pub fn docked_left_width(&self) -> f32 {
-        if self.open && matches!(self.dock_side, Some(HierarchyDockSide::Left)) {
-            self.window_width
-        } else {
-            0.0
-        }
-    }
-
-    pub fn docked_right_width(&self) -> f32 {
-        if self.open && matches!(self.dock_side, Some(HierarchyDockSide::Right)) {
-            self.window_width
-        } else {
-            0.0
-        }
-    }
 }
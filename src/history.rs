@@ -0,0 +1,353 @@
+use super::*;
+use crate::hierarchy::{HierarchyEdit, ObjectPlacement, RemovedObject};
+use crate::inspector::{ComponentEdit, ImportEdit, ObjectComponents};
+use crate::log_panel::LogLevel;
+use crate::project::AssetFileEdit;
+use crate::viewport::MAX_UNDO_STEPS;
+
+/// Objetos que entram ou saem da cena; enquanto estão fora, o que a
+/// hierarquia e o inspetor descartaram deles fica guardado aqui
+struct ObjectSet {
+    roots: Vec<String>,
+    removed: Vec<RemovedObject>,
+    components: Vec<(String, ObjectComponents)>,
+}
+
+/// Ação feita num painel, com o necessário para desfazê-la e refazê-la
+enum SceneEdit {
+    /// Objetos criados: desfazer tira da cena, refazer devolve
+    Spawn(ObjectSet),
+    /// Objetos deletados: desfazer devolve, refazer tira de novo
+    Delete(ObjectSet),
+    /// Objeto arrastado na hierarquia para outro pai, lista ou posição
+    Reparent {
+        object: String,
+        before: ObjectPlacement,
+        after: ObjectPlacement,
+    },
+    /// Campos ou componentes editados no inspetor
+    Components {
+        object: String,
+        before: ObjectComponents,
+        after: ObjectComponents,
+    },
+}
+
+/// Uma ação do editor, desfeita e refeita inteira por Ctrl+Z / Ctrl+Shift+Z
+enum EditorCommand {
+    /// Uma interação na cena: as ações dos painéis, na ordem, e os passos
+    /// que a viewport empilhou (malhas, transforms, texturas)
+    Scene {
+        edits: Vec<SceneEdit>,
+        viewport_steps: usize,
+    },
+    /// Opções de importação gravadas no .meta de um asset
+    Import(ImportEdit),
+    /// Renomear, mover, duplicar ou excluir no Projeto
+    AssetFile(AssetFileEdit),
+}
+
+impl EditorCommand {
+    fn viewport_steps(&self) -> usize {
+        match self {
+            EditorCommand::Scene { viewport_steps, .. } => *viewport_steps,
+            EditorCommand::Import(_) | EditorCommand::AssetFile(_) => 0,
+        }
+    }
+}
+
+/// Desfazer/refazer de todos os painéis. Cada painel entrega as ações que
+/// fez (`take_edits`, `take_component_edits`...) e as de uma mesma interação
+/// viram um comando
+#[derive(Default)]
+pub(crate) struct EditorHistory {
+    undo: Vec<EditorCommand>,
+    redo: Vec<EditorCommand>,
+    // Ações da interação em andamento
+    open: Vec<SceneEdit>,
+    viewport_revision: u64,
+    // A interação atual já empilhou a viewport antes de mudá-la
+    viewport_step_open: bool,
+    // Em Play no último frame; o que muda no Play é descartado no Stop
+    playing: bool,
+    // O histórico foi apagado neste frame; o que os painéis fizeram até o
+    // fim dele também não vira comando
+    cleared: bool,
+}
+
+impl EditorHistory {
+    pub(crate) fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub(crate) fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    fn push(&mut self, command: EditorCommand) {
+        self.undo.push(command);
+        self.redo.clear();
+        // A viewport só guarda MAX_UNDO_STEPS passos: comandos mais antigos
+        // que isso não teriam como voltar
+        let mut steps: usize = self.undo.iter().map(EditorCommand::viewport_steps).sum();
+        while self.undo.len() > 1 && (steps > MAX_UNDO_STEPS || self.undo.len() > MAX_UNDO_STEPS) {
+            steps -= self.undo.remove(0).viewport_steps();
+        }
+    }
+
+    /// Junta edições seguidas dos mesmos componentes (um arrasto, um texto
+    /// digitado) numa só
+    fn add(&mut self, edit: SceneEdit) {
+        match edit {
+            SceneEdit::Components {
+                object,
+                before,
+                after,
+            } => {
+                let earlier = self
+                    .open
+                    .iter_mut()
+                    .rev()
+                    .map_while(|edit| match edit {
+                        SceneEdit::Components { object, after, .. } => Some((object, after)),
+                        _ => None,
+                    })
+                    .find(|(earlier, _)| **earlier == object);
+                match earlier {
+                    Some((_, earlier_after)) => *earlier_after = after,
+                    None => self.open.push(SceneEdit::Components {
+                        object,
+                        before,
+                        after,
+                    }),
+                }
+            }
+            edit => self.open.push(edit),
+        }
+    }
+}
+
+impl EditorApp {
+    /// Empilha a viewport uma vez por interação, antes de mudanças que ela
+    /// não registra sozinha (transform ao vivo no inspetor, textura, material)
+    pub(crate) fn record_viewport_step(&mut self) {
+        if !self.history.viewport_step_open {
+            self.viewport.push_undo_snapshot();
+            self.history.viewport_step_open = true;
+        }
+    }
+
+    /// Recolhe as ações da hierarquia e do inspetor. Chamado logo depois da
+    /// hierarquia, antes de os objetos deletados saírem do inspetor, e de
+    /// novo ao fim do frame
+    pub(crate) fn collect_history_edits(&mut self) {
+        for edit in self.hierarchy.take_edits() {
+            let edit = match edit {
+                HierarchyEdit::Spawn(roots) => SceneEdit::Spawn(ObjectSet {
+                    roots,
+                    removed: Vec::new(),
+                    components: Vec::new(),
+                }),
+                HierarchyEdit::Delete(removed) => {
+                    let components = removed
+                        .iter()
+                        .map(|o| (o.name.clone(), self.inspector.object_components(&o.name)))
+                        .collect();
+                    SceneEdit::Delete(ObjectSet {
+                        roots: removed.iter().map(|o| o.name.clone()).collect(),
+                        removed,
+                        components,
+                    })
+                }
+                HierarchyEdit::Reparent {
+                    object,
+                    before,
+                    after,
+                } => SceneEdit::Reparent {
+                    object,
+                    before,
+                    after,
+                },
+            };
+            self.history.add(edit);
+        }
+        for ComponentEdit {
+            object,
+            before,
+            after,
+        } in self.inspector.take_component_edits()
+        {
+            self.history.add(SceneEdit::Components {
+                object,
+                before,
+                after,
+            });
+        }
+    }
+
+    /// Chamado ao fim de cada frame. A ação só é fechada quando nada está
+    /// sendo arrastado ou digitado, para um arrasto inteiro virar um comando
+    pub(crate) fn record_history(&mut self, ctx: &egui::Context) {
+        if let Some(edit) = self.inspector.take_import_edit() {
+            self.history.push(EditorCommand::Import(edit));
+        }
+        if let Some(edit) = self.project.take_asset_edit() {
+            self.history.push(EditorCommand::AssetFile(edit));
+        }
+        // A edição isolada de prefab tem histórico próprio, como o Play
+        let playing = self.viewport.in_play_mode() || self.editing_prefab();
+        let cleared = std::mem::take(&mut self.history.cleared);
+        if playing || self.history.playing || cleared {
+            self.history.playing = playing;
+            self.rebase_history();
+            return;
+        }
+        self.collect_history_edits();
+        if ctx.input(|i| i.pointer.any_down()) || ctx.wants_keyboard_input() {
+            return;
+        }
+        self.commit_history();
+    }
+
+    /// Fecha a interação atual como um comando
+    fn commit_history(&mut self) {
+        let revision = self.viewport.history_revision();
+        let viewport_steps = revision.saturating_sub(self.history.viewport_revision) as usize;
+        self.history.viewport_revision = revision;
+        self.history.viewport_step_open = false;
+        let edits = std::mem::take(&mut self.history.open);
+        if !edits.is_empty() || viewport_steps > 0 {
+            self.history.push(EditorCommand::Scene {
+                edits,
+                viewport_steps,
+            });
+        }
+    }
+
+    /// Descarta o que os painéis fizeram desde o último comando
+    fn rebase_history(&mut self) {
+        self.hierarchy.take_edits();
+        self.inspector.take_component_edits();
+        self.history.open.clear();
+        self.history.viewport_revision = self.viewport.history_revision();
+        self.history.viewport_step_open = false;
+    }
+
+    /// Apaga o histórico depois de ações que ele não acompanha (carregar ou
+    /// descarregar uma cena)
+    pub(crate) fn clear_history(&mut self) {
+        self.history.undo.clear();
+        self.history.redo.clear();
+        self.history.cleared = true;
+    }
+
+    pub(crate) fn undo(&mut self) {
+        self.collect_history_edits();
+        self.commit_history();
+        let Some(mut command) = self.history.undo.pop() else {
+            return;
+        };
+        self.apply_command(&mut command, true);
+        self.history.redo.push(command);
+        self.rebase_history();
+    }
+
+    pub(crate) fn redo(&mut self) {
+        self.collect_history_edits();
+        self.commit_history();
+        let Some(mut command) = self.history.redo.pop() else {
+            return;
+        };
+        self.apply_command(&mut command, false);
+        self.history.undo.push(command);
+        self.rebase_history();
+    }
+
+    /// Volta ao estado de antes (`undo`) ou de depois do comando
+    fn apply_command(&mut self, command: &mut EditorCommand, undo: bool) {
+        match command {
+            EditorCommand::Scene {
+                edits,
+                viewport_steps,
+            } => {
+                if undo {
+                    for edit in edits.iter_mut().rev() {
+                        self.apply_edit(edit, true);
+                    }
+                    for _ in 0..*viewport_steps {
+                        self.viewport.undo();
+                    }
+                } else {
+                    for _ in 0..*viewport_steps {
+                        self.viewport.redo();
+                    }
+                    for edit in edits.iter_mut() {
+                        self.apply_edit(edit, false);
+                    }
+                }
+            }
+            EditorCommand::Import(edit) => {
+                if let Err(e) = edit.restore(undo) {
+                    self.log.push(LogLevel::Error, "Import", e);
+                    return;
+                }
+                self.inspector.reload_import_asset(&edit.path);
+                self.reimport_with_settings(&edit.path);
+            }
+            EditorCommand::AssetFile(edit) => {
+                if let Err(e) = self.project.restore_asset_edit(edit, undo) {
+                    self.log.push(LogLevel::Error, "Assets", e);
+                    self.log_enabled = true;
+                }
+            }
+        }
+    }
+
+    fn apply_edit(&mut self, edit: &mut SceneEdit, undo: bool) {
+        match edit {
+            SceneEdit::Spawn(objects) if undo => self.take_objects_out(objects),
+            SceneEdit::Spawn(objects) => self.put_objects_back(objects),
+            SceneEdit::Delete(objects) if undo => self.put_objects_back(objects),
+            SceneEdit::Delete(objects) => self.take_objects_out(objects),
+            SceneEdit::Reparent {
+                object,
+                before,
+                after,
+            } => {
+                self.hierarchy
+                    .set_placement(object, if undo { before } else { after });
+            }
+            SceneEdit::Components {
+                object,
+                before,
+                after,
+            } => {
+                let components = if undo { before } else { after };
+                self.inspector
+                    .set_object_components(object, components.clone());
+            }
+        }
+    }
+
+    /// Tira os objetos da hierarquia e do inspetor, guardando o que sai; a
+    /// malha sai da viewport pelos passos dela
+    fn take_objects_out(&mut self, objects: &mut ObjectSet) {
+        objects.removed = self.hierarchy.remove_objects(&objects.roots);
+        objects.components = objects
+            .removed
+            .iter()
+            .map(|o| (o.name.clone(), self.inspector.object_components(&o.name)))
+            .collect();
+        for (name, _) in &objects.components {
+            self.inspector.remove_object_data(name);
+        }
+    }
+
+    fn put_objects_back(&mut self, objects: &ObjectSet) {
+        self.hierarchy.restore_objects(&objects.removed);
+        for (name, components) in &objects.components {
+            self.inspector
+                .set_object_components(name, components.clone());
+        }
+    }
+}
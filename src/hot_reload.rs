@@ -0,0 +1,153 @@
+use super::*;
+use crate::log_panel::LogLevel;
+use engine_render::{
+    AssetDatabase, AssetKind, AssetWatcher, MATERIAL_REFERENCE_KEYS, WatchedAsset,
+};
+
+/// Hot reload: vigia a pasta Assets do projeto e recarrega o que mudou em disco
+#[derive(Default)]
+pub(crate) struct HotReload {
+    watcher: Option<AssetWatcher>,
+}
+
+impl EditorApp {
+    /// Reindexa os GUIDs (.meta) da pasta e corrige os caminhos guardados nos materiais
+    fn refresh_asset_database(&mut self, dir: &Path) {
+        let database = AssetDatabase::scan(dir);
+        for material in database.materials() {
+            match database.fix_references(&material, &MATERIAL_REFERENCE_KEYS) {
+                Ok(true) => self.log.push(
+                    LogLevel::Info,
+                    "Assets",
+                    format!("Referências atualizadas: {}", material.display()),
+                ),
+                Ok(false) => {}
+                Err(e) => self.log.push(LogLevel::Error, "Assets", e),
+            }
+        }
+        // Consultas de referências/dependências e exclusão segura do Project
+        self.project.set_asset_database(database);
+    }
+
+    /// Pasta Assets vigiada: a do projeto aberto ou, sem projeto, a do diretório atual
+    pub(crate) fn hot_reload_dir(&self) -> PathBuf {
+        self.current_project
+            .as_ref()
+            .and_then(|p| p.parent())
+            .map(|p| p.join("Assets"))
+            .unwrap_or_else(|| PathBuf::from("Assets"))
+    }
+
+    /// Recarrega nos mesmos handles/caminhos o que a cena usa de `path`;
+    /// devolve a mensagem do log, ou `None` se nada foi recarregado
+    fn reimport_asset(&mut self, path: &Path, kind: AssetKind) -> Option<String> {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        self.inspector.refresh_environment_preview(path);
+        match kind {
+            AssetKind::Texture => {
+                if let Some(gpu) = &self.viewport_gpu {
+                    gpu.reload_texture(&path.to_string_lossy());
+                }
+                self.viewport.reload_texture_file(path);
+                // PNG também pode ser LUT de color grading ou panorama do céu
+                self.post_process.reload_lut(path);
+                self.environment.reload_file(path);
+                Some(format!("Textura recarregada: {name}"))
+            }
+            AssetKind::Mesh => self
+                .viewport
+                .reload_mesh_source(path)
+                .then(|| format!("Reimportando malha: {name}")),
+            AssetKind::Environment => self
+                .environment
+                .reload_file(path)
+                .then(|| format!("Recarregando céu: {name}")),
+            _ => None,
+        }
+    }
+
+    /// Opções de importação do asset selecionado no Projeto e o pedido de
+    /// reimportação vindo do inspetor
+    pub(crate) fn sync_import_settings(&mut self) {
+        self.inspector
+            .set_import_asset(self.project.selected_import_asset_path());
+        self.inspector
+            .set_environment_asset(self.project.selected_asset_path());
+        if let Some(path) = self.inspector.take_reimport_request() {
+            self.reimport_with_settings(&path);
+        }
+        if let Some(path) = self.project.take_reimport_request() {
+            self.reimport_with_settings(&path);
+        }
+    }
+
+    /// Reimporta `path` com as opções do .meta (aplicadas, desfeitas ou
+    /// refeitas) e registra no log
+    pub(crate) fn reimport_with_settings(&mut self, path: &Path) {
+        self.project.invalidate_thumbnail(path);
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let message = AssetKind::from_path(path)
+            .and_then(|kind| self.reimport_asset(path, kind))
+            .unwrap_or_else(|| {
+                format!("{name} não está em uso na cena; as opções valem na próxima importação")
+            });
+        self.log.push(LogLevel::Info, "Import", message);
+    }
+
+    /// Texturas, malhas, shaders e scripts alterados por ferramentas externas são
+    /// recarregados nos mesmos handles/caminhos, sem reabrir a cena
+    pub(crate) fn sync_hot_reload(&mut self, ctx: &egui::Context) {
+        let dir = self.hot_reload_dir();
+        if self.hot_reload.watcher.as_ref().map(|w| w.dir()) != Some(dir.as_path()) {
+            // Projeto trocado: os arquivos que já existem não contam como alterados
+            self.hot_reload.watcher = Some(AssetWatcher::watch(&dir));
+            self.refresh_asset_database(&dir);
+        }
+        let Some(watcher) = &mut self.hot_reload.watcher else {
+            return;
+        };
+        let changes = watcher.poll();
+        if !changes.is_empty() {
+            // Arquivo novo, renomeado ou movido: .meta criado e materiais apontados
+            // para o caminho atual de cada GUID
+            self.refresh_asset_database(&dir);
+        }
+
+        for change in changes {
+            let path = change.path;
+            self.project.invalidate_thumbnail(&path);
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let reloaded = match change.kind {
+                WatchedAsset::Asset(kind) => self.reimport_asset(&path, kind),
+                WatchedAsset::Shader => match self.inspector.reload_material_shader(&path) {
+                    Some(Ok(())) => Some(format!("Shader de material recarregado: {name}")),
+                    Some(Err(e)) => {
+                        self.log.push(LogLevel::Error, "Hot reload", e);
+                        self.log_enabled = true;
+                        None
+                    }
+                    None => None,
+                },
+                WatchedAsset::Script => {
+                    let count = self.scripts.reload_script(&path);
+                    (count > 0).then(|| format!("Script recarregado: {name} ({count} objetos)"))
+                }
+                // Lidos do disco a cada frame pela viewport
+                WatchedAsset::Material => None,
+            };
+            if let Some(message) = reloaded {
+                self.log.push(LogLevel::Info, "Hot reload", message);
+            }
+            ctx.request_repaint();
+        }
+    }
+}
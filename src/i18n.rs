@@ -0,0 +1,160 @@
+use super::*;
+use std::sync::LazyLock;
+
+/// Pacotes embutidos no executável, usados quando `Editor/lang/<código>.lang`
+/// não está montado; editar o arquivo vale na próxima abertura do editor
+const BUILTIN_PACKS: [(EngineLanguage, &str); 7] = [
+    (EngineLanguage::Pt, include_str!("assets/lang/pt.lang")),
+    (EngineLanguage::En, include_str!("assets/lang/en.lang")),
+    (EngineLanguage::Es, include_str!("assets/lang/es.lang")),
+    (EngineLanguage::Fr, include_str!("assets/lang/fr.lang")),
+    (EngineLanguage::De, include_str!("assets/lang/de.lang")),
+    (EngineLanguage::Ja, include_str!("assets/lang/ja.lang")),
+    (EngineLanguage::Zh, include_str!("assets/lang/zh.lang")),
+];
+
+/// Fontes do sistema com ideogramas, tentadas em ordem para japonês e chinês
+const CJK_FONT_PATHS: [&str; 9] = [
+    "C:/Windows/Fonts/msyh.ttc",
+    "C:/Windows/Fonts/YuGothM.ttc",
+    "C:/Windows/Fonts/meiryo.ttc",
+    "/System/Library/Fonts/PingFang.ttc",
+    "/System/Library/Fonts/Hiragino Sans GB.ttc",
+    "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/google-noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/truetype/wqy/wqy-microhei.ttc",
+];
+
+/// Seção (`[main]`, `[fios]`, ...) -> chave -> texto
+type Pack = HashMap<String, HashMap<String, String>>;
+
+static PACKS: LazyLock<HashMap<EngineLanguage, Pack>> = LazyLock::new(|| {
+    BUILTIN_PACKS
+        .into_iter()
+        .map(|(language, builtin)| {
+            let path = format!("Editor/lang/{}.lang", language.code());
+            let raw = engine_render::vfs()
+                .read_to_string(&path)
+                .unwrap_or_else(|_| builtin.to_string());
+            (language, parse_pack(&raw))
+        })
+        .collect()
+});
+
+/// Formato: `# comentário`, `[seção]` e `chave = texto`, com `\n` para
+/// quebra de linha
+fn parse_pack(raw: &str) -> Pack {
+    let mut pack = Pack::new();
+    let mut section = String::new();
+    for line in raw.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = name.trim().to_string();
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            pack.entry(section.clone())
+                .or_default()
+                .insert(key.trim().to_string(), value.trim().replace("\\n", "\n"));
+        }
+    }
+    pack
+}
+
+/// Lê os pacotes de idioma; chamado na abertura do editor para não pagar a
+/// leitura no primeiro frame
+pub(crate) fn load() {
+    LazyLock::force(&PACKS);
+}
+
+/// Texto da chave no idioma, caindo para o inglês quando falta tradução
+pub(crate) fn lookup(language: EngineLanguage, section: &str, key: &str) -> Option<&'static str> {
+    let find = |language: EngineLanguage| {
+        PACKS
+            .get(&language)?
+            .get(section)?
+            .get(key)
+            .map(String::as_str)
+    };
+    find(language).or_else(|| find(EngineLanguage::En))
+}
+
+/// Como `lookup`, mostrando a própria chave quando nenhum pacote a tem
+pub(crate) fn text(language: EngineLanguage, section: &str, key: &'static str) -> &'static str {
+    lookup(language, section, key).unwrap_or(key)
+}
+
+/// Idioma do sistema para abrir o editor: o locale do usuário no Windows,
+/// `LC_ALL`/`LC_MESSAGES`/`LANG` nos demais. Um locale sem pacote abre em
+/// inglês; sem locale nenhum, em português como antes
+pub(crate) fn system_language() -> EngineLanguage {
+    match system_locale() {
+        Some(locale) => EngineLanguage::from_locale(&locale).unwrap_or(EngineLanguage::En),
+        None => EngineLanguage::Pt,
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn system_locale() -> Option<String> {
+    use windows_sys::Win32::Globalization::GetUserDefaultLocaleName;
+    // LOCALE_NAME_MAX_LENGTH
+    let mut name = [0u16; 85];
+    let len = unsafe { GetUserDefaultLocaleName(name.as_mut_ptr(), name.len() as i32) };
+    // O tamanho conta o terminador nulo
+    (len > 1).then(|| String::from_utf16_lossy(&name[..len as usize - 1]))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn system_locale() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| {
+            !value.is_empty() && value != "C" && value != "POSIX" && !value.starts_with("C.")
+        })
+        .or_else(app_locale)
+}
+
+/// Apps abertos pelo Finder não herdam LANG
+#[cfg(target_os = "macos")]
+fn app_locale() -> Option<String> {
+    Command::new("defaults")
+        .args(["read", "-g", "AppleLocale"])
+        .output()
+        .ok()
+        .map(|out| String::from_utf8_lossy(&out.stdout).trim().to_string())
+        .filter(|locale| !locale.is_empty())
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn app_locale() -> Option<String> {
+    None
+}
+
+/// Roboto como fonte principal e, quando o sistema tem uma, uma fonte CJK de
+/// reserva: sem ela japonês e chinês aparecem como quadrados
+pub(crate) fn install_fonts(ctx: &egui::Context) {
+    let mut fonts = egui::FontDefinitions::default();
+    fonts.font_data.insert(
+        "Roboto".to_owned(),
+        Arc::new(egui::FontData::from_static(include_bytes!(
+            "assets/fonts/roboto.ttf"
+        ))),
+    );
+    if let Some(family) = fonts.families.get_mut(&egui::FontFamily::Proportional) {
+        family.insert(0, "Roboto".to_owned());
+    }
+    if let Some(bytes) = CJK_FONT_PATHS.iter().find_map(|path| fs::read(path).ok()) {
+        fonts.font_data.insert(
+            "CJK".to_owned(),
+            Arc::new(egui::FontData::from_owned(bytes)),
+        );
+        for family in fonts.families.values_mut() {
+            family.push("CJK".to_owned());
+        }
+    }
+    ctx.set_fonts(fonts);
+}
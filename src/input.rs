@@ -0,0 +1,102 @@
+use super::*;
+use engine_core::{GamepadAxis, GamepadButton, MouseButton};
+
+const GAMEPAD_BUTTONS: [(gilrs::Button, GamepadButton); 14] = [
+    (gilrs::Button::South, GamepadButton::South),
+    (gilrs::Button::East, GamepadButton::East),
+    (gilrs::Button::West, GamepadButton::West),
+    (gilrs::Button::North, GamepadButton::North),
+    (gilrs::Button::LeftTrigger, GamepadButton::LeftBumper),
+    (gilrs::Button::RightTrigger, GamepadButton::RightBumper),
+    (gilrs::Button::Select, GamepadButton::Select),
+    (gilrs::Button::Start, GamepadButton::Start),
+    (gilrs::Button::LeftThumb, GamepadButton::LeftStick),
+    (gilrs::Button::RightThumb, GamepadButton::RightStick),
+    (gilrs::Button::DPadUp, GamepadButton::DPadUp),
+    (gilrs::Button::DPadDown, GamepadButton::DPadDown),
+    (gilrs::Button::DPadLeft, GamepadButton::DPadLeft),
+    (gilrs::Button::DPadRight, GamepadButton::DPadRight),
+];
+
+const GAMEPAD_STICKS: [(gilrs::Axis, GamepadAxis); 4] = [
+    (gilrs::Axis::LeftStickX, GamepadAxis::LeftStickX),
+    (gilrs::Axis::LeftStickY, GamepadAxis::LeftStickY),
+    (gilrs::Axis::RightStickX, GamepadAxis::RightStickX),
+    (gilrs::Axis::RightStickY, GamepadAxis::RightStickY),
+];
+
+// Gatilhos analógicos, lidos como botões com valor em [0, 1]
+const GAMEPAD_TRIGGERS: [(gilrs::Button, GamepadAxis); 2] = [
+    (gilrs::Button::LeftTrigger2, GamepadAxis::LeftTrigger),
+    (gilrs::Button::RightTrigger2, GamepadAxis::RightTrigger),
+];
+
+impl EditorApp {
+    /// Alimenta o recurso Input do engine com os eventos do egui deste frame
+    /// e com o estado do controle de jogo
+    pub(crate) fn update_engine_input(&mut self, ctx: &egui::Context) {
+        let input = &mut self.input;
+        input.begin_frame();
+        ctx.input(|i| {
+            if !i.focused {
+                input.keys.reset();
+                input.mouse_buttons.reset();
+                return;
+            }
+            input.keys.set_down(i.keys_down.iter().map(|k| k.name()));
+            let buttons = [
+                (egui::PointerButton::Primary, MouseButton::Left),
+                (egui::PointerButton::Secondary, MouseButton::Right),
+                (egui::PointerButton::Middle, MouseButton::Middle),
+                (egui::PointerButton::Extra1, MouseButton::Back),
+                (egui::PointerButton::Extra2, MouseButton::Forward),
+            ];
+            input.mouse_buttons.set_down(
+                buttons
+                    .into_iter()
+                    .filter(|(b, _)| i.pointer.button_down(*b))
+                    .map(|(_, m)| m),
+            );
+            if let Some(pos) = i.pointer.latest_pos() {
+                input.mouse_position = glam::Vec2::new(pos.x, pos.y);
+            }
+            let delta = i.pointer.delta();
+            input.mouse_delta = glam::Vec2::new(delta.x, delta.y);
+            input.scroll_delta = glam::Vec2::new(i.raw_scroll_delta.x, i.raw_scroll_delta.y);
+        });
+        self.poll_gamepad();
+    }
+
+    /// Primeiro controle conectado; sem nenhum, botões soltos e eixos zerados
+    fn poll_gamepad(&mut self) {
+        let Some(gilrs) = self.gamepads.as_mut() else {
+            return;
+        };
+        // Os eventos atualizam o estado em cache que é lido abaixo
+        while gilrs.next_event().is_some() {}
+        let input = &mut self.input;
+        let Some((_, pad)) = gilrs.gamepads().find(|(_, pad)| pad.is_connected()) else {
+            input.gamepad_buttons.reset();
+            for (_, axis) in GAMEPAD_STICKS {
+                input.set_gamepad_axis(axis, 0.0);
+            }
+            for (_, axis) in GAMEPAD_TRIGGERS {
+                input.set_gamepad_axis(axis, 0.0);
+            }
+            return;
+        };
+        input.gamepad_buttons.set_down(
+            GAMEPAD_BUTTONS
+                .into_iter()
+                .filter(|(button, _)| pad.is_pressed(*button))
+                .map(|(_, button)| button),
+        );
+        for (stick, axis) in GAMEPAD_STICKS {
+            input.set_gamepad_axis(axis, pad.value(stick));
+        }
+        for (trigger, axis) in GAMEPAD_TRIGGERS {
+            let value = pad.button_data(trigger).map_or(0.0, |data| data.value());
+            input.set_gamepad_axis(axis, value);
+        }
+    }
+}
@@ -1,17 +1,116 @@
 use crate::EngineLanguage;
+use crate::audio::{AudioClipView, paint_waveform};
+use crate::curve_editor::CurveEditor;
+use crate::i18n;
+use crate::reflect::{self, Field, FieldKind, Reflect, ReflectContext, ReflectEnum, ReflectList};
+use crate::theme;
 use eframe::egui::{
-    self, Align2, Color32, FontFamily, FontId, Id, Order, Pos2, Rect, Stroke, TextureHandle,
+    self, Align2, Color32, FontFamily, FontId, Id, Order, Rect, Stroke, TextureHandle,
     TextureOptions,
 };
+use engine_core::{
+    Camera, Guid, MAX_LOD_LEVELS, PostProcessSettings, Sprite, Text3D, TextAlign, Tonemapping,
+    UiText, select_lod_level,
+};
+use engine_physics::BodyType;
+use engine_render::{
+    ANISOTROPY_LEVELS, AssetKind, AssetManager, AssetSearchIndex, AssetType, Curve,
+    FontImportSettings, FormatKind, ImportSettings, MATERIAL_REFERENCE_KEYS, MAX_EMITTER_PARTICLES,
+    MeshImportSettings, ParticleCurve, ParticleEmitter, ShaderParam, ShaderParamKind,
+    TERRAIN_LAYERS, TerrainLayer, TextureFilter, TextureImportSettings, TextureSampling,
+    TextureSheet, Thumbnail, WaterSurface, ensure_guid, environment_sphere_thumbnail,
+    is_cubemap_source, migrate_format, same_file,
+};
 use epaint::ColorImage;
+use std::any::TypeId;
 use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
 use std::time::{Duration, Instant};
 
-const INSPECTOR_MIN_WIDTH: f32 = 260.0;
-const INSPECTOR_MAX_WIDTH: f32 = 520.0;
+fn tr(language: EngineLanguage, key: &'static str) -> &'static str {
+    i18n::text(language, "inspector", key)
+}
+
+/// Intervalo entre varreduras de `Assets/Shaders`
+const MATERIAL_SHADER_RESCAN: Duration = Duration::from_secs(1);
+/// Lado da esfera de pré-visualização de céus e cubemaps
+const ENVIRONMENT_PREVIEW_SIZE: u32 = 192;
+
+/// Opções de importação em edição do asset selecionado no Projeto
+#[derive(Clone, PartialEq)]
+enum ImportDraft {
+    Texture(TextureImportSettings),
+    Mesh(MeshImportSettings),
+    Font(FontImportSettings),
+}
+
+impl ImportDraft {
+    /// Opções gravadas no .meta de `path`; `None` se o tipo não tem opções
+    fn load(path: &Path) -> Option<Self> {
+        match AssetKind::from_path(path)? {
+            AssetKind::Texture => Some(ImportDraft::Texture(
+                TextureImportSettings::default().with_meta(path),
+            )),
+            AssetKind::Mesh => Some(ImportDraft::Mesh(
+                MeshImportSettings::default().with_meta(path),
+            )),
+            AssetKind::Font => Some(ImportDraft::Font(
+                FontImportSettings::default().with_meta(path),
+            )),
+            _ => None,
+        }
+    }
+
+    fn save(&self, path: &Path) -> Result<(), String> {
+        match self {
+            ImportDraft::Texture(settings) => settings.save_meta(path),
+            ImportDraft::Mesh(settings) => settings.save_meta(path),
+            ImportDraft::Font(settings) => settings.save_meta(path),
+        }
+    }
+}
+
+/// Opções de importação aplicadas a um asset, desfeitas gravando as anteriores
+/// no .meta
+#[derive(Clone)]
+pub(crate) struct ImportEdit {
+    pub(crate) path: PathBuf,
+    before: ImportDraft,
+    after: ImportDraft,
+}
+
+impl ImportEdit {
+    /// Grava no .meta as opções de antes (`undo`) ou de depois
+    pub(crate) fn restore(&self, undo: bool) -> Result<(), String> {
+        let settings = if undo { &self.before } else { &self.after };
+        settings.save(&self.path)
+    }
+}
+
+/// Componentes de um objeto antes e depois de uma edição no inspetor
+pub(crate) struct ComponentEdit {
+    pub(crate) object: String,
+    pub(crate) before: ObjectComponents,
+    pub(crate) after: ObjectComponents,
+}
+
+/// Céu/cubemap selecionado no Projeto, refletido numa esfera espelhada; a
+/// imagem é gerada numa thread, pois panoramas HDR grandes demoram a decodificar
+struct EnvironmentPreview {
+    path: PathBuf,
+    pending: Option<Receiver<Result<Thumbnail, String>>>,
+    texture: Option<TextureHandle>,
+    error: Option<String>,
+}
+
+/// Pedido de pre-escuta de um asset de áudio selecionado no Projeto
+pub enum AudioPreviewRequest {
+    Play(PathBuf),
+    Stop,
+}
 
 #[derive(Clone, Copy)]
 struct TransformDraft {
@@ -20,7 +119,7 @@ struct TransformDraft {
     scale: [f32; 3],
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct FiosControllerDraft {
     pub enabled: bool,
     pub move_speed: f32,
@@ -30,15 +129,694 @@ pub struct FiosControllerDraft {
     pub primary_clip: String,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq)]
 pub struct RigidbodyDraft {
     pub enabled: bool,
+    pub body_type: BodyType,
     pub mass: f32,
     pub use_gravity: bool,
+    pub lock_rotation: bool,
     pub jump_impulse: f32,
     pub gravity: [f32; 3],
 }
 
+#[derive(Clone, Copy, PartialEq)]
+pub enum ColliderKind {
+    Box,
+    Sphere,
+    Capsule,
+}
+
+impl ColliderKind {
+    pub const ALL: [ColliderKind; 3] = [
+        ColliderKind::Box,
+        ColliderKind::Sphere,
+        ColliderKind::Capsule,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ColliderKind::Box => "Box",
+            ColliderKind::Sphere => "Sphere",
+            ColliderKind::Capsule => "Capsule",
+        }
+    }
+}
+
+/// Tamanho em espaco local do objeto: Box usa meia-extensao XYZ,
+/// Sphere usa X como raio e Capsule usa X como raio e Y como meia-altura
+#[derive(Clone, Copy, PartialEq)]
+pub struct ColliderDraft {
+    pub enabled: bool,
+    pub kind: ColliderKind,
+    pub size: [f32; 3],
+    pub center: [f32; 3],
+    pub friction: f32,
+    pub restitution: f32,
+    pub is_trigger: bool,
+}
+
+impl Default for ColliderDraft {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            kind: ColliderKind::Box,
+            size: [0.5, 0.5, 0.5],
+            center: [0.0, 0.0, 0.0],
+            friction: 0.5,
+            restitution: 0.0,
+            is_trigger: false,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct ScriptDraft {
+    pub enabled: bool,
+    pub path: String,
+}
+
+impl Default for ScriptDraft {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            path: String::new(),
+        }
+    }
+}
+
+/// Campo de referência a outro objeto; salvo como GUID, o rótulo é só cache de exibição
+#[derive(Clone, Default, PartialEq)]
+pub struct EntityRefDraft {
+    pub guid: Guid,
+    pub label: String,
+}
+
+#[derive(Clone, PartialEq)]
+pub struct CameraFollowDraft {
+    pub enabled: bool,
+    pub target: EntityRefDraft,
+    pub offset: [f32; 3],
+    pub smoothing: f32,
+}
+
+impl Default for CameraFollowDraft {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            target: EntityRefDraft::default(),
+            offset: [0.0, 3.0, 6.0],
+            smoothing: 0.0,
+        }
+    }
+}
+
+/// Câmera do objeto; com `target_texture` ela renderiza para a textura e não
+/// aparece no modo Game
+#[derive(Clone, PartialEq)]
+pub struct CameraDraft {
+    pub enabled: bool,
+    pub camera: Camera,
+    pub target_texture: String,
+}
+
+impl Default for CameraDraft {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            camera: Camera::default(),
+            target_texture: String::new(),
+        }
+    }
+}
+
+/// Pós-processamento da câmera; a LUT é carregada pelo editor a partir do caminho
+#[derive(Clone, Default, PartialEq)]
+pub struct PostProcessDraft {
+    pub settings: PostProcessSettings,
+    pub lut_path: String,
+}
+
+/// Nível de LOD: malha usada até `distance` da câmera (vazia = proxy simplificado)
+#[derive(Clone, PartialEq)]
+pub struct LodLevelDraft {
+    pub mesh_path: String,
+    pub distance: f32,
+}
+
+/// LOD Group; o nível 0 é sempre a malha do próprio objeto
+#[derive(Clone, PartialEq)]
+pub struct LodDraft {
+    pub enabled: bool,
+    pub levels: Vec<LodLevelDraft>,
+    pub bias: f32,
+    // Esconde o objeto depois da distância do último nível
+    pub cull: bool,
+}
+
+impl Default for LodDraft {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            levels: vec![
+                LodLevelDraft {
+                    mesh_path: String::new(),
+                    distance: 15.0,
+                },
+                LodLevelDraft {
+                    mesh_path: String::new(),
+                    distance: 40.0,
+                },
+            ],
+            bias: 1.0,
+            cull: false,
+        }
+    }
+}
+
+impl LodDraft {
+    /// Nível usado a `distance` da câmera, None quando o objeto é descartado
+    pub fn select_level(&self, distance: f32) -> Option<usize> {
+        select_lod_level(
+            self.levels.iter().map(|level| level.distance),
+            self.bias,
+            self.cull,
+            distance,
+        )
+    }
+}
+
+/// Emissor de partículas; as curvas vão do nascimento (0) ao fim da vida (1)
+#[derive(Clone, PartialEq)]
+pub struct ParticleDraft {
+    pub enabled: bool,
+    pub max_particles: u32,
+    pub rate: f32,
+    pub lifetime: [f32; 2],
+    pub start_speed: [f32; 2],
+    pub start_size: [f32; 2],
+    pub start_color: [f32; 4],
+    pub end_color: [f32; 4],
+    pub cone_angle: f32,
+    pub gravity: f32,
+    pub size_curve: Curve,
+    pub alpha_curve: Curve,
+    pub speed_curve: Curve,
+    pub texture_path: String,
+    pub sheet: TextureSheet,
+    pub additive: bool,
+}
+
+impl Default for ParticleDraft {
+    fn default() -> Self {
+        let emitter = ParticleEmitter::default();
+        Self {
+            enabled: emitter.enabled,
+            max_particles: emitter.max_particles,
+            rate: emitter.rate,
+            lifetime: emitter.lifetime,
+            start_speed: emitter.start_speed,
+            start_size: emitter.start_size,
+            start_color: emitter.start_color,
+            end_color: emitter.end_color,
+            cone_angle: emitter.cone_angle,
+            gravity: emitter.gravity,
+            size_curve: Curve::linear(1.0, 1.0),
+            alpha_curve: Curve::linear(1.0, 0.0),
+            speed_curve: Curve::linear(1.0, 1.0),
+            texture_path: String::new(),
+            sheet: emitter.sheet,
+            additive: emitter.additive,
+        }
+    }
+}
+
+impl ParticleDraft {
+    pub fn to_emitter(&self) -> ParticleEmitter {
+        let texture = self.texture_path.trim();
+        ParticleEmitter {
+            enabled: self.enabled,
+            max_particles: self.max_particles,
+            rate: self.rate,
+            lifetime: self.lifetime,
+            start_speed: self.start_speed,
+            start_size: self.start_size,
+            start_color: self.start_color,
+            end_color: self.end_color,
+            cone_angle: self.cone_angle,
+            gravity: self.gravity,
+            size_over_lifetime: ParticleCurve::from_curve(self.size_curve.clone()),
+            alpha_over_lifetime: ParticleCurve::from_curve(self.alpha_curve.clone()),
+            speed_over_lifetime: ParticleCurve::from_curve(self.speed_curve.clone()),
+            texture: (!texture.is_empty()).then(|| texture.to_string()),
+            sheet: self.sheet,
+            additive: self.additive,
+        }
+    }
+}
+
+/// Camada de textura do terreno (pintada pelo splat map)
+#[derive(Clone, PartialEq)]
+pub struct TerrainLayerDraft {
+    pub texture_path: String,
+    pub tiling: f32,
+    pub color: [f32; 3],
+}
+
+/// Terreno por heightmap; as alturas e o splat map são editados na viewport
+#[derive(Clone, PartialEq)]
+pub struct TerrainDraft {
+    pub enabled: bool,
+    /// Amostras por lado (32·n + 1)
+    pub resolution: u32,
+    pub size: f32,
+    pub height_scale: f32,
+    /// Distância até a qual os chunks usam o LOD 0
+    pub lod_distance: f32,
+    /// PNG em tons de cinza (vazio = terreno plano); também é onde a viewport salva
+    pub heightmap_path: String,
+    pub splatmap_path: String,
+    pub layers: [TerrainLayerDraft; TERRAIN_LAYERS],
+}
+
+impl Default for TerrainDraft {
+    fn default() -> Self {
+        let colors = [
+            [0.36, 0.52, 0.26],
+            [0.52, 0.42, 0.30],
+            [0.55, 0.55, 0.55],
+            [0.92, 0.92, 0.95],
+        ];
+        Self {
+            enabled: true,
+            resolution: 129,
+            size: 40.0,
+            height_scale: 8.0,
+            lod_distance: 10.0,
+            heightmap_path: String::new(),
+            splatmap_path: String::new(),
+            layers: colors.map(|color| TerrainLayerDraft {
+                texture_path: String::new(),
+                tiling: 16.0,
+                color,
+            }),
+        }
+    }
+}
+
+impl TerrainDraft {
+    pub fn to_layers(&self) -> [TerrainLayer; TERRAIN_LAYERS] {
+        self.layers.clone().map(|layer| {
+            let texture = layer.texture_path.trim();
+            TerrainLayer {
+                texture: (!texture.is_empty()).then(|| texture.to_string()),
+                tiling: layer.tiling,
+                color: layer.color,
+            }
+        })
+    }
+}
+
+/// Superfície de água plana (reflexo, refração e neblina de profundidade)
+#[derive(Clone, PartialEq)]
+pub struct WaterDraft {
+    pub enabled: bool,
+    pub size: [f32; 2],
+    pub shallow_color: [f32; 3],
+    pub deep_color: [f32; 3],
+    pub fog_density: f32,
+    pub wave_scale: f32,
+    pub wave_speed: f32,
+    pub wave_strength: f32,
+    pub reflectivity: f32,
+    pub refraction_strength: f32,
+    /// Normal map opcional (vazio = ondas senoidais)
+    pub normal_map_path: String,
+}
+
+impl Default for WaterDraft {
+    fn default() -> Self {
+        let water = WaterSurface::default();
+        Self {
+            enabled: true,
+            size: water.size,
+            shallow_color: water.shallow_color,
+            deep_color: water.deep_color,
+            fog_density: water.fog_density,
+            wave_scale: water.wave_scale,
+            wave_speed: water.wave_speed,
+            wave_strength: water.wave_strength,
+            reflectivity: water.reflectivity,
+            refraction_strength: water.refraction_strength,
+            normal_map_path: String::new(),
+        }
+    }
+}
+
+impl WaterDraft {
+    pub fn to_surface(&self) -> WaterSurface {
+        let normal_map = self.normal_map_path.trim();
+        WaterSurface {
+            size: self.size,
+            shallow_color: self.shallow_color,
+            deep_color: self.deep_color,
+            fog_density: self.fog_density,
+            wave_scale: self.wave_scale,
+            wave_speed: self.wave_speed,
+            wave_strength: self.wave_strength,
+            reflectivity: self.reflectivity,
+            refraction_strength: self.refraction_strength,
+            normal_map: (!normal_map.is_empty()).then(|| normal_map.to_string()),
+        }
+    }
+}
+
+impl Reflect for WaterDraft {
+    fn fields(&mut self) -> Vec<Field<'_>> {
+        vec![
+            Field::new("Ativo", FieldKind::Bool(&mut self.enabled)),
+            Field::vector("Tamanho", &mut self.size, &["X", "Z"], 0.25, 0.1..=10_000.0),
+            Field::new("Cor rasa", FieldKind::Color(&mut self.shallow_color)),
+            Field::new("Cor profunda", FieldKind::Color(&mut self.deep_color)),
+            Field::float("Neblina", &mut self.fog_density, 0.01, 0.0..=10.0)
+                .with_tooltip("Densidade por unidade de profundidade"),
+            Field::float("Onda (m)", &mut self.wave_scale, 0.05, 0.05..=1_000.0)
+                .with_tooltip("Comprimento da onda"),
+            Field::float("Onda (m/s)", &mut self.wave_speed, 0.01, -20.0..=20.0)
+                .with_tooltip("Velocidade"),
+            Field::slider("Intensidade", &mut self.wave_strength, 0.0..=2.0),
+            Field::slider("Reflexo", &mut self.reflectivity, 0.0..=1.0),
+            Field::slider("Refração", &mut self.refraction_strength, 0.0..=0.2),
+            Field::new(
+                "Normal map",
+                FieldKind::Path {
+                    value: &mut self.normal_map_path,
+                    hint: "Ondas senoidais",
+                    extensions: &["png", "jpg", "jpeg", "webp"],
+                },
+            ),
+        ]
+    }
+}
+
+/// Quad texturizado no plano XY local do objeto
+#[derive(Clone, PartialEq)]
+pub struct SpriteDraft {
+    pub enabled: bool,
+    pub texture_path: String,
+    pub size: [f32; 2],
+    /// (0.5, 0.5) = centro do quad
+    pub pivot: [f32; 2],
+    pub color: [f32; 3],
+    pub opacity: f32,
+    pub flip_x: bool,
+    pub flip_y: bool,
+    /// Índice em `SortingLayers`
+    pub sorting_layer: i32,
+    pub order_in_layer: i32,
+}
+
+impl Default for SpriteDraft {
+    fn default() -> Self {
+        let sprite = Sprite::default();
+        Self {
+            enabled: true,
+            texture_path: String::new(),
+            size: sprite.size.to_array(),
+            pivot: sprite.pivot.to_array(),
+            color: [sprite.color[0], sprite.color[1], sprite.color[2]],
+            opacity: sprite.color[3],
+            flip_x: sprite.flip_x,
+            flip_y: sprite.flip_y,
+            sorting_layer: sprite.sorting_layer as i32,
+            order_in_layer: sprite.order_in_layer,
+        }
+    }
+}
+
+impl SpriteDraft {
+    /// Componente sem textura: quem desenha resolve `texture_path`
+    pub fn to_sprite(&self) -> Sprite {
+        let [r, g, b] = self.color;
+        Sprite {
+            size: self.size.into(),
+            pivot: self.pivot.into(),
+            color: [r, g, b, self.opacity],
+            flip_x: self.flip_x,
+            flip_y: self.flip_y,
+            sorting_layer: self.sorting_layer.clamp(0, u16::MAX as i32) as u16,
+            order_in_layer: self.order_in_layer,
+            ..Default::default()
+        }
+    }
+}
+
+impl Reflect for SpriteDraft {
+    fn fields(&mut self) -> Vec<Field<'_>> {
+        vec![
+            Field::new("Ativo", FieldKind::Bool(&mut self.enabled)),
+            Field::new(
+                "Textura",
+                FieldKind::Asset {
+                    value: &mut self.texture_path,
+                    hint: "Quad branco",
+                    extensions: &["png", "jpg", "jpeg", "webp"],
+                    asset_type: AssetType::Texture,
+                },
+            ),
+            Field::vector(
+                "Tamanho",
+                &mut self.size,
+                &["X", "Y"],
+                0.05,
+                0.001..=10_000.0,
+            ),
+            Field::vector("Pivô", &mut self.pivot, &["X", "Y"], 0.01, 0.0..=1.0),
+            Field::new("Cor", FieldKind::Color(&mut self.color)),
+            Field::slider("Opacidade", &mut self.opacity, 0.0..=1.0),
+            Field::new("Inverter X", FieldKind::Bool(&mut self.flip_x)),
+            Field::new("Inverter Y", FieldKind::Bool(&mut self.flip_y)),
+            Field::new(
+                "Camada",
+                FieldKind::Int {
+                    value: &mut self.sorting_layer,
+                    speed: 0.1,
+                },
+            )
+            .with_tooltip("Sorting layer: camadas maiores ficam na frente"),
+            Field::new(
+                "Ordem",
+                FieldKind::Int {
+                    value: &mut self.order_in_layer,
+                    speed: 0.1,
+                },
+            )
+            .with_tooltip("Ordem dentro da camada"),
+        ]
+    }
+}
+
+const TEXT_ALIGNS: [(TextAlign, &str); 3] = [
+    (TextAlign::Left, "Esquerda"),
+    (TextAlign::Center, "Centro"),
+    (TextAlign::Right, "Direita"),
+];
+
+impl ReflectEnum for TextAlign {
+    fn variants(&self) -> Vec<&'static str> {
+        TEXT_ALIGNS.iter().map(|(_, label)| *label).collect()
+    }
+
+    fn index(&self) -> usize {
+        TEXT_ALIGNS
+            .iter()
+            .position(|(align, _)| align == self)
+            .unwrap_or(0)
+    }
+
+    fn set_index(&mut self, index: usize) {
+        if let Some((align, _)) = TEXT_ALIGNS.get(index) {
+            *self = *align;
+        }
+    }
+}
+
+/// Texto no mundo, desenhado com o atlas SDF da fonte
+#[derive(Clone, PartialEq)]
+pub struct Text3DDraft {
+    pub enabled: bool,
+    pub text: String,
+    pub font_path: String,
+    /// Altura da linha em unidades do mundo
+    pub size: f32,
+    pub color: [f32; 3],
+    pub opacity: f32,
+    pub align: TextAlign,
+    /// Sempre virado para a câmera
+    pub billboard: bool,
+    pub offset: [f32; 3],
+}
+
+impl Default for Text3DDraft {
+    fn default() -> Self {
+        let text = Text3D::default();
+        Self {
+            enabled: true,
+            text: "Texto".to_string(),
+            font_path: String::new(),
+            size: text.size,
+            color: [text.color[0], text.color[1], text.color[2]],
+            opacity: text.color[3],
+            align: text.align,
+            billboard: text.billboard,
+            offset: text.offset.to_array(),
+        }
+    }
+}
+
+impl Text3DDraft {
+    /// Componente sem fonte: quem desenha resolve `font_path`
+    pub fn to_text3d(&self) -> Text3D {
+        let [r, g, b] = self.color;
+        Text3D {
+            text: self.text.clone(),
+            size: self.size,
+            color: [r, g, b, self.opacity],
+            align: self.align,
+            billboard: self.billboard,
+            offset: self.offset.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl Reflect for Text3DDraft {
+    fn fields(&mut self) -> Vec<Field<'_>> {
+        vec![
+            Field::new("Ativo", FieldKind::Bool(&mut self.enabled)),
+            Field::new(
+                "Texto",
+                FieldKind::Text {
+                    value: &mut self.text,
+                    hint: "Texto",
+                },
+            ),
+            Field::new(
+                "Fonte",
+                FieldKind::Asset {
+                    value: &mut self.font_path,
+                    hint: "Nenhuma",
+                    extensions: &["ttf", "otf"],
+                    asset_type: AssetType::Font,
+                },
+            )
+            .with_tooltip("Sem fonte o texto não é desenhado"),
+            Field::float("Tamanho", &mut self.size, 0.01, 0.01..=1_000.0),
+            Field::new("Cor", FieldKind::Color(&mut self.color)),
+            Field::slider("Opacidade", &mut self.opacity, 0.0..=1.0),
+            Field::new("Alinhamento", FieldKind::Enum(&mut self.align)),
+            Field::new("Billboard", FieldKind::Bool(&mut self.billboard))
+                .with_tooltip("Sempre virado para a câmera"),
+            Field::vector(
+                "Deslocamento",
+                &mut self.offset,
+                &["X", "Y", "Z"],
+                0.05,
+                -10_000.0..=10_000.0,
+            ),
+        ]
+    }
+}
+
+/// Texto da UI do jogo, em pixels sobre a tela (não usa o Transform)
+#[derive(Clone, PartialEq)]
+pub struct UiTextDraft {
+    pub enabled: bool,
+    pub text: String,
+    pub font_path: String,
+    /// Altura da linha em pixels
+    pub size: f32,
+    pub color: [f32; 3],
+    pub opacity: f32,
+    pub align: TextAlign,
+    /// Ponto da tela, de (0, 0) no topo esquerdo a (1, 1) embaixo à direita
+    pub anchor: [f32; 2],
+    /// Pixels a partir da âncora (Y para baixo)
+    pub offset: [f32; 2],
+}
+
+impl Default for UiTextDraft {
+    fn default() -> Self {
+        let text = UiText::default();
+        Self {
+            enabled: true,
+            text: "Texto".to_string(),
+            font_path: String::new(),
+            size: text.size,
+            color: [text.color[0], text.color[1], text.color[2]],
+            opacity: text.color[3],
+            align: text.align,
+            anchor: text.anchor.to_array(),
+            offset: text.offset.to_array(),
+        }
+    }
+}
+
+impl UiTextDraft {
+    /// Componente sem fonte: quem desenha resolve `font_path`
+    pub fn to_ui_text(&self) -> UiText {
+        let [r, g, b] = self.color;
+        UiText {
+            text: self.text.clone(),
+            size: self.size,
+            color: [r, g, b, self.opacity],
+            align: self.align,
+            anchor: self.anchor.into(),
+            offset: self.offset.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl Reflect for UiTextDraft {
+    fn fields(&mut self) -> Vec<Field<'_>> {
+        vec![
+            Field::new("Ativo", FieldKind::Bool(&mut self.enabled)),
+            Field::new(
+                "Texto",
+                FieldKind::Text {
+                    value: &mut self.text,
+                    hint: "Texto",
+                },
+            ),
+            Field::new(
+                "Fonte",
+                FieldKind::Asset {
+                    value: &mut self.font_path,
+                    hint: "Nenhuma",
+                    extensions: &["ttf", "otf"],
+                    asset_type: AssetType::Font,
+                },
+            )
+            .with_tooltip("Sem fonte o texto não é desenhado"),
+            Field::float("Tamanho (px)", &mut self.size, 0.5, 1.0..=1_000.0),
+            Field::new("Cor", FieldKind::Color(&mut self.color)),
+            Field::slider("Opacidade", &mut self.opacity, 0.0..=1.0),
+            Field::new("Alinhamento", FieldKind::Enum(&mut self.align)),
+            Field::vector("Âncora", &mut self.anchor, &["X", "Y"], 0.01, 0.0..=1.0)
+                .with_tooltip("(0, 0) = topo esquerdo, (1, 1) = canto inferior direito"),
+            Field::vector(
+                "Deslocamento",
+                &mut self.offset,
+                &["X", "Y"],
+                1.0,
+                -10_000.0..=10_000.0,
+            )
+            .with_tooltip("Pixels a partir da âncora"),
+        ]
+    }
+}
+
 #[derive(Clone, Copy, PartialEq)]
 pub enum LightType {
     Directional,
@@ -56,7 +834,7 @@ impl LightType {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq)]
 pub struct LightDraft {
     pub light_type: LightType,
     pub enabled: bool,
@@ -85,7 +863,7 @@ impl Default for LightDraft {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct AnimatorDraft {
     pub enabled: bool,
     pub controller_ref: String,
@@ -129,14 +907,613 @@ impl Default for RigidbodyDraft {
     fn default() -> Self {
         Self {
             enabled: true,
+            body_type: BodyType::Dynamic,
             mass: 1.0,
             use_gravity: true,
+            lock_rotation: true,
             jump_impulse: 5.0,
             gravity: [0.0, -9.81, 0.0],
         }
     }
 }
 
+impl ReflectEnum for BodyType {
+    fn variants(&self) -> Vec<&'static str> {
+        BodyType::ALL.iter().map(BodyType::as_str).collect()
+    }
+
+    fn index(&self) -> usize {
+        BodyType::ALL.iter().position(|t| t == self).unwrap_or(0)
+    }
+
+    fn set_index(&mut self, index: usize) {
+        if let Some(t) = BodyType::ALL.get(index) {
+            *self = *t;
+        }
+    }
+}
+
+impl ReflectEnum for ColliderKind {
+    fn variants(&self) -> Vec<&'static str> {
+        ColliderKind::ALL.iter().map(ColliderKind::as_str).collect()
+    }
+
+    fn index(&self) -> usize {
+        ColliderKind::ALL
+            .iter()
+            .position(|k| k == self)
+            .unwrap_or(0)
+    }
+
+    fn set_index(&mut self, index: usize) {
+        if let Some(k) = ColliderKind::ALL.get(index) {
+            *self = *k;
+        }
+    }
+}
+
+impl Reflect for RigidbodyDraft {
+    fn fields(&mut self) -> Vec<Field<'_>> {
+        vec![
+            Field::new("Tipo", FieldKind::Enum(&mut self.body_type)),
+            Field::float("Massa", &mut self.mass, 0.1, 0.001..=10000.0),
+            Field::new("Gravidade", FieldKind::Bool(&mut self.use_gravity)),
+            Field::new("Travar rotação", FieldKind::Bool(&mut self.lock_rotation)),
+            Field::float("Pulo", &mut self.jump_impulse, 0.1, 0.0..=100.0),
+        ]
+    }
+}
+
+impl Reflect for ColliderDraft {
+    fn fields(&mut self) -> Vec<Field<'_>> {
+        let kind = self.kind;
+        let mut fields = vec![Field::new("Forma", FieldKind::Enum(&mut self.kind))];
+        match kind {
+            ColliderKind::Box => fields.push(Field::vector(
+                "Meia-extensão",
+                &mut self.size,
+                &["X", "Y", "Z"],
+                0.01,
+                0.001..=1000.0,
+            )),
+            ColliderKind::Sphere => {
+                fields.push(Field::float(
+                    "Raio",
+                    &mut self.size[0],
+                    0.01,
+                    0.001..=1000.0,
+                ));
+            }
+            ColliderKind::Capsule => {
+                let [radius, half_height, _] = &mut self.size;
+                fields.push(Field::float("Raio", radius, 0.01, 0.001..=1000.0));
+                fields.push(Field::float("Meia-altura", half_height, 0.01, 0.0..=1000.0));
+            }
+        }
+        fields.extend([
+            Field::vector(
+                "Centro",
+                &mut self.center,
+                &["X", "Y", "Z"],
+                0.01,
+                f32::NEG_INFINITY..=f32::INFINITY,
+            ),
+            Field::float("Atrito", &mut self.friction, 0.01, 0.0..=2.0),
+            Field::float("Elasticidade", &mut self.restitution, 0.01, 0.0..=1.0),
+            Field::new("Trigger", FieldKind::Bool(&mut self.is_trigger)),
+        ]);
+        fields
+    }
+}
+
+impl Reflect for TransformDraft {
+    fn fields(&mut self) -> Vec<Field<'_>> {
+        let axes = &["X", "Y", "Z"];
+        let any = f32::NEG_INFINITY..=f32::INFINITY;
+        vec![
+            Field::vector("position", &mut self.position, axes, 0.1, any.clone()),
+            Field::vector("rotation", &mut self.rotation, axes, 0.1, any.clone()),
+            Field::vector("scale", &mut self.scale, axes, 0.05, any),
+        ]
+    }
+
+    fn label_section(&self) -> Option<&'static str> {
+        Some("inspector")
+    }
+}
+
+impl Reflect for LightDraft {
+    fn fields(&mut self) -> Vec<Field<'_>> {
+        let light_type = self.light_type;
+        let mut fields = vec![
+            Field::new("Tipo", FieldKind::Label(light_type.as_str())),
+            Field::new("Ativa", FieldKind::Bool(&mut self.enabled)),
+            Field::new("Cor", FieldKind::Color(&mut self.color)),
+            Field::float("Intensidade", &mut self.intensity, 0.05, 0.0..=10.0),
+        ];
+        match light_type {
+            LightType::Point => {
+                fields.push(Field::float("Alcance", &mut self.range, 0.1, 0.1..=100.0));
+            }
+            LightType::Spot => {
+                fields.push(Field::float("Alcance", &mut self.range, 0.1, 0.1..=100.0));
+                fields.push(Field::float(
+                    "Ângulo do Cone",
+                    &mut self.cone_angle,
+                    1.0,
+                    1.0..=180.0,
+                ));
+            }
+            LightType::Directional => {
+                fields.push(Field::float(
+                    "Yaw",
+                    &mut self.yaw,
+                    0.01,
+                    0.0..=std::f32::consts::TAU,
+                ));
+                fields.push(Field::float(
+                    "Pitch",
+                    &mut self.pitch,
+                    0.01,
+                    -std::f32::consts::FRAC_PI_2..=std::f32::consts::FRAC_PI_2,
+                ));
+            }
+        }
+        fields
+    }
+}
+
+impl Reflect for CameraDraft {
+    fn fields(&mut self) -> Vec<Field<'_>> {
+        let camera = &mut self.camera;
+        let projection = if camera.orthographic {
+            Field::float(
+                "Tamanho",
+                &mut camera.orthographic_size,
+                0.1,
+                0.01..=10000.0,
+            )
+            .with_tooltip("Altura visível em unidades do mundo")
+        } else {
+            Field::slider("FOV", &mut camera.fov, 10.0..=120.0).with_tooltip("Em graus")
+        };
+        vec![
+            Field::new("Ativo", FieldKind::Bool(&mut self.enabled)),
+            Field::new(
+                "Projeção",
+                FieldKind::Toggle {
+                    value: &mut camera.orthographic,
+                    off: "Perspectiva",
+                    on: "Ortográfica",
+                },
+            ),
+            projection,
+            Field::float("Near", &mut camera.near, 0.01, 0.01..=10.0),
+            Field::float("Far", &mut camera.far, 1.0, 1.0..=10000.0),
+            Field::vector(
+                "Viewport",
+                &mut camera.viewport,
+                &["x", "y", "w", "h"],
+                0.01,
+                0.0..=1.0,
+            )
+            .with_tooltip("x, y, largura, altura (0–1, a partir do canto superior esquerdo)"),
+            Field::new(
+                "Ordem",
+                FieldKind::Int {
+                    value: &mut camera.order,
+                    speed: 1.0,
+                },
+            )
+            .with_tooltip("A maior ordem é desenhada por último e aparece no modo Game"),
+            Field::new(
+                "Textura alvo",
+                FieldKind::Text {
+                    value: &mut self.target_texture,
+                    hint: "vazio = tela",
+                },
+            ),
+            Field::new(
+                "Névoa",
+                FieldKind::Optional {
+                    value: &mut camera.fog,
+                    text: "Névoa própria",
+                },
+            )
+            .with_tooltip("Substitui a névoa do ambiente enquanto esta câmera renderiza"),
+        ]
+    }
+}
+
+impl ReflectEnum for Tonemapping {
+    fn variants(&self) -> Vec<&'static str> {
+        vec!["Nenhum", "Reinhard", "ACES"]
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            Tonemapping::None => 0,
+            Tonemapping::Reinhard => 1,
+            Tonemapping::Aces => 2,
+        }
+    }
+
+    fn set_index(&mut self, index: usize) {
+        *self = match index {
+            0 => Tonemapping::None,
+            1 => Tonemapping::Reinhard,
+            _ => Tonemapping::Aces,
+        };
+    }
+}
+
+impl Reflect for PostProcessDraft {
+    fn fields(&mut self) -> Vec<Field<'_>> {
+        let settings = &mut self.settings;
+        let auto_exposure = settings.auto_exposure;
+        let bloom = settings.bloom_enabled;
+        let has_lut = !self.lut_path.trim().is_empty();
+        vec![
+            Field::new("Ativo", FieldKind::Bool(&mut settings.enabled)),
+            Field::log_slider("Exposição", &mut settings.exposure, 0.05..=8.0),
+            Field::new(
+                "Exposição auto",
+                FieldKind::Bool(&mut settings.auto_exposure),
+            ),
+            Field::float(
+                "Faixa mín.",
+                &mut settings.auto_exposure_min,
+                0.01,
+                0.01..=1.0,
+            )
+            .enabled_if(auto_exposure),
+            Field::float(
+                "Faixa máx.",
+                &mut settings.auto_exposure_max,
+                0.1,
+                1.0..=64.0,
+            )
+            .enabled_if(auto_exposure),
+            Field::log_slider("Adaptação", &mut settings.auto_exposure_speed, 0.1..=10.0)
+                .enabled_if(auto_exposure),
+            Field::new("Tonemapping", FieldKind::Enum(&mut settings.tonemapping)),
+            Field::new("Bloom", FieldKind::Bool(&mut settings.bloom_enabled)),
+            Field::slider("Limiar", &mut settings.bloom_threshold, 0.0..=4.0).enabled_if(bloom),
+            Field::slider("Intensidade", &mut settings.bloom_intensity, 0.0..=2.0)
+                .enabled_if(bloom),
+            Field::slider("Vinheta", &mut settings.vignette_intensity, 0.0..=1.0),
+            Field::slider("Suavidade", &mut settings.vignette_smoothness, 0.01..=1.0),
+            Field::new(
+                "LUT",
+                FieldKind::Path {
+                    value: &mut self.lut_path,
+                    hint: "Assets/LUTs/grade.png",
+                    extensions: &["png", "jpg", "jpeg"],
+                },
+            ),
+            Field::slider("Contribuição", &mut settings.lut_contribution, 0.0..=1.0)
+                .enabled_if(has_lut),
+        ]
+    }
+}
+
+impl Reflect for LodDraft {
+    fn fields(&mut self) -> Vec<Field<'_>> {
+        vec![
+            Field::new("Ativo", FieldKind::Bool(&mut self.enabled)),
+            Field::log_slider("Bias", &mut self.bias, 0.25..=4.0),
+            Field::new(
+                "Descartar",
+                FieldKind::Toggle {
+                    value: &mut self.cull,
+                    off: "Nunca",
+                    on: "Após o último nível",
+                },
+            ),
+        ]
+    }
+
+    fn list(&mut self) -> Option<&mut dyn ReflectList> {
+        Some(&mut self.levels)
+    }
+}
+
+impl ReflectList for Vec<LodLevelDraft> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn item_label(&self, index: usize) -> String {
+        format!("LOD {index}")
+    }
+
+    fn item_fields(&mut self, index: usize) -> Vec<Field<'_>> {
+        // Distâncias crescentes: cada nível começa onde o anterior termina
+        let min_distance = index
+            .checked_sub(1)
+            .and_then(|prev| self.get(prev))
+            .map_or(0.0, |prev| prev.distance);
+        let Some(level) = self.get_mut(index) else {
+            return Vec::new();
+        };
+        let mesh = if index == 0 {
+            Field::new("", FieldKind::Label("Malha do objeto"))
+        } else {
+            Field::new(
+                "",
+                FieldKind::Asset {
+                    value: &mut level.mesh_path,
+                    hint: "Proxy automático",
+                    extensions: &["fbx", "obj", "glb", "gltf"],
+                    asset_type: AssetType::Mesh,
+                },
+            )
+        };
+        vec![
+            mesh,
+            Field::float("", &mut level.distance, 0.5, min_distance..=10_000.0)
+                .with_tooltip("Distância da câmera, em metros"),
+        ]
+    }
+
+    fn can_remove(&self, index: usize) -> bool {
+        index > 0
+    }
+
+    fn remove(&mut self, index: usize) {
+        Vec::remove(self, index);
+    }
+
+    fn add_label(&self) -> Option<&'static str> {
+        (Vec::len(self) < MAX_LOD_LEVELS).then_some("+ Nível")
+    }
+
+    fn add(&mut self) {
+        let distance = self.last().map_or(15.0, |level| level.distance * 2.0);
+        self.push(LodLevelDraft {
+            mesh_path: String::new(),
+            distance,
+        });
+    }
+}
+
+/// Componente com editor gerado a partir dos campos (`Reflect`): o inspetor
+/// só precisa saber onde ficam os drafts e como intitular o quadro. Para um
+/// novo componente, basta implementar os dois traits e registrá-lo em
+/// `ComponentRegistry::with_builtin`
+trait InspectorComponent: Reflect + Sized + 'static {
+    const TITLE: &'static str;
+    /// Aviso mostrado abaixo dos campos
+    const NOTE: Option<&'static str> = None;
+
+    fn drafts(inspector: &mut InspectorWindow) -> &mut HashMap<String, Self>;
+}
+
+impl InspectorComponent for TransformDraft {
+    const TITLE: &'static str = "Transform";
+
+    fn drafts(inspector: &mut InspectorWindow) -> &mut HashMap<String, Self> {
+        &mut inspector.object_transforms
+    }
+}
+
+impl InspectorComponent for LightDraft {
+    const TITLE: &'static str = "💡 Luz";
+
+    fn drafts(inspector: &mut InspectorWindow) -> &mut HashMap<String, Self> {
+        &mut inspector.object_light
+    }
+}
+
+impl InspectorComponent for RigidbodyDraft {
+    const TITLE: &'static str = "Rigidbody";
+
+    fn drafts(inspector: &mut InspectorWindow) -> &mut HashMap<String, Self> {
+        &mut inspector.object_rigidbody
+    }
+}
+
+impl InspectorComponent for ColliderDraft {
+    const TITLE: &'static str = "Collider";
+
+    fn drafts(inspector: &mut InspectorWindow) -> &mut HashMap<String, Self> {
+        &mut inspector.object_collider
+    }
+}
+
+impl InspectorComponent for WaterDraft {
+    const TITLE: &'static str = "🌊 Water";
+    const NOTE: Option<&'static str> = Some("Requer \"Água\" ativa em Gráficos");
+
+    fn drafts(inspector: &mut InspectorWindow) -> &mut HashMap<String, Self> {
+        &mut inspector.object_water
+    }
+}
+
+impl InspectorComponent for SpriteDraft {
+    const TITLE: &'static str = "🖼 Sprite";
+
+    fn drafts(inspector: &mut InspectorWindow) -> &mut HashMap<String, Self> {
+        &mut inspector.object_sprite
+    }
+}
+
+impl InspectorComponent for UiTextDraft {
+    const TITLE: &'static str = "🔤 UI Text";
+    const NOTE: Option<&'static str> = Some("Aparece sobre a viewport no Play");
+
+    fn drafts(inspector: &mut InspectorWindow) -> &mut HashMap<String, Self> {
+        &mut inspector.object_ui_text
+    }
+}
+
+impl InspectorComponent for Text3DDraft {
+    const TITLE: &'static str = "🔤 Text 3D";
+
+    fn drafts(inspector: &mut InspectorWindow) -> &mut HashMap<String, Self> {
+        &mut inspector.object_text3d
+    }
+}
+
+impl InspectorComponent for CameraDraft {
+    const TITLE: &'static str = "🎥 Camera";
+
+    fn drafts(inspector: &mut InspectorWindow) -> &mut HashMap<String, Self> {
+        &mut inspector.object_camera
+    }
+}
+
+impl InspectorComponent for PostProcessDraft {
+    const TITLE: &'static str = "🎞 Post-processing";
+
+    fn drafts(inspector: &mut InspectorWindow) -> &mut HashMap<String, Self> {
+        &mut inspector.object_post_process
+    }
+}
+
+impl InspectorComponent for LodDraft {
+    const TITLE: &'static str = "🧊 LOD Group";
+
+    fn drafts(inspector: &mut InspectorWindow) -> &mut HashMap<String, Self> {
+        &mut inspector.object_lod
+    }
+}
+
+fn registered_draft<'a, T: InspectorComponent>(
+    inspector: &'a mut InspectorWindow,
+    object_name: &str,
+) -> Option<&'a mut dyn Reflect> {
+    T::drafts(inspector)
+        .get_mut(object_name)
+        .map(|draft| draft as &mut dyn Reflect)
+}
+
+fn remove_registered<T: InspectorComponent>(inspector: &mut InspectorWindow, object_name: &str) {
+    T::drafts(inspector).remove(object_name);
+}
+
+/// Entrada do registro: acesso ao draft de um objeto sem conhecer o tipo
+#[derive(Clone, Copy)]
+struct RegisteredComponent {
+    title: &'static str,
+    note: Option<&'static str>,
+    get: for<'a> fn(&'a mut InspectorWindow, &str) -> Option<&'a mut dyn Reflect>,
+    remove: fn(&mut InspectorWindow, &str),
+}
+
+/// Componentes com editor gerado, indexados pelo tipo do draft
+#[derive(Default)]
+struct ComponentRegistry {
+    types: HashMap<TypeId, RegisteredComponent>,
+}
+
+impl ComponentRegistry {
+    fn with_builtin() -> Self {
+        let mut registry = Self::default();
+        registry.register::<TransformDraft>();
+        registry.register::<LightDraft>();
+        registry.register::<RigidbodyDraft>();
+        registry.register::<ColliderDraft>();
+        registry.register::<WaterDraft>();
+        registry.register::<SpriteDraft>();
+        registry.register::<Text3DDraft>();
+        registry.register::<UiTextDraft>();
+        registry.register::<CameraDraft>();
+        registry.register::<PostProcessDraft>();
+        registry.register::<LodDraft>();
+        registry
+    }
+
+    fn register<T: InspectorComponent>(&mut self) {
+        self.types.insert(
+            TypeId::of::<T>(),
+            RegisteredComponent {
+                title: T::TITLE,
+                note: T::NOTE,
+                get: registered_draft::<T>,
+                remove: remove_registered::<T>,
+            },
+        );
+    }
+
+    fn get(&self, id: TypeId) -> Option<RegisteredComponent> {
+        self.types.get(&id).copied()
+    }
+}
+
+/// Componentes de um objeto: guardados no início do frame, para repetir nos
+/// demais selecionados o que foi editado no ativo, e pela área de transferência
+#[derive(Clone, PartialEq)]
+pub(crate) struct ObjectComponents {
+    fios_controller: Option<FiosControllerDraft>,
+    rigidbody: Option<RigidbodyDraft>,
+    collider: Option<ColliderDraft>,
+    animator: Option<AnimatorDraft>,
+    light: Option<LightDraft>,
+    script: Option<ScriptDraft>,
+    camera: Option<CameraDraft>,
+    camera_follow: Option<CameraFollowDraft>,
+    post_process: Option<PostProcessDraft>,
+    lod: Option<LodDraft>,
+    particles: Option<ParticleDraft>,
+    terrain: Option<TerrainDraft>,
+    water: Option<WaterDraft>,
+    sprite: Option<SpriteDraft>,
+    text3d: Option<Text3DDraft>,
+    ui_text: Option<UiTextDraft>,
+    texture: Option<String>,
+    shader: Option<String>,
+}
+
+/// Componentes de todos os objetos num momento, guardados durante a edição
+/// isolada de um prefab
+#[derive(Clone)]
+pub(crate) struct InspectorSnapshot {
+    object_transform_enabled: HashMap<String, bool>,
+    object_fios_controller: HashMap<String, FiosControllerDraft>,
+    object_rigidbody: HashMap<String, RigidbodyDraft>,
+    object_collider: HashMap<String, ColliderDraft>,
+    object_animator: HashMap<String, AnimatorDraft>,
+    object_light: HashMap<String, LightDraft>,
+    object_script: HashMap<String, ScriptDraft>,
+    object_camera: HashMap<String, CameraDraft>,
+    object_camera_follow: HashMap<String, CameraFollowDraft>,
+    object_post_process: HashMap<String, PostProcessDraft>,
+    object_lod: HashMap<String, LodDraft>,
+    object_particles: HashMap<String, ParticleDraft>,
+    object_terrain: HashMap<String, TerrainDraft>,
+    object_water: HashMap<String, WaterDraft>,
+    object_sprite: HashMap<String, SpriteDraft>,
+    object_text3d: HashMap<String, Text3DDraft>,
+    object_ui_text: HashMap<String, UiTextDraft>,
+    object_texture: HashMap<String, String>,
+    object_shader: HashMap<String, String>,
+}
+
+/// Repete em `targets` a mudança do componente de `source` desde `before`
+/// (inclusive adicionar ou remover o componente)
+fn copy_component_edit<T: Clone + PartialEq>(
+    map: &mut HashMap<String, T>,
+    source: &str,
+    before: Option<T>,
+    targets: &[String],
+) {
+    let after = map.get(source).cloned();
+    if after == before {
+        return;
+    }
+    for name in targets.iter().filter(|n| n.as_str() != source) {
+        match &after {
+            Some(value) => {
+                map.insert(name.clone(), value.clone());
+            }
+            None => {
+                map.remove(name);
+            }
+        }
+    }
+}
+
 pub struct InspectorWindow {
     pub open: bool,
     menu_icon_texture: Option<TextureHandle>,
@@ -144,51 +1521,79 @@ pub struct InspectorWindow {
     unlock_icon_texture: Option<TextureHandle>,
     add_icon_texture: Option<TextureHandle>,
     is_locked: bool,
-    dock_side: Option<InspectorDockSide>,
-    window_pos: Option<Pos2>,
-    window_width: f32,
-    dragging_from_header: bool,
-    resizing_width: bool,
-    fonts_initialized: bool,
     object_transforms: HashMap<String, TransformDraft>,
     object_transform_enabled: HashMap<String, bool>,
     last_selected_object: String,
+    // Seleção inteira da hierarquia; edições de componentes valem para todos
+    selected_objects: Vec<String>,
     pending_live_request: Option<(String, TransformDraft)>,
     pending_apply_request: Option<(String, TransformDraft)>,
     _pending_animator_request: Option<String>,
     object_fios_controller: HashMap<String, FiosControllerDraft>,
     object_rigidbody: HashMap<String, RigidbodyDraft>,
+    object_collider: HashMap<String, ColliderDraft>,
     object_animator: HashMap<String, AnimatorDraft>,
     object_light: HashMap<String, LightDraft>,
+    object_script: HashMap<String, ScriptDraft>,
+    object_camera: HashMap<String, CameraDraft>,
+    object_camera_follow: HashMap<String, CameraFollowDraft>,
+    object_post_process: HashMap<String, PostProcessDraft>,
+    object_lod: HashMap<String, LodDraft>,
+    object_particles: HashMap<String, ParticleDraft>,
+    object_terrain: HashMap<String, TerrainDraft>,
+    object_water: HashMap<String, WaterDraft>,
+    object_sprite: HashMap<String, SpriteDraft>,
+    object_text3d: HashMap<String, Text3DDraft>,
+    object_ui_text: HashMap<String, UiTextDraft>,
+    hierarchy_drag: Option<(String, Guid)>,
     object_texture: HashMap<String, String>,
     object_shader: HashMap<String, String>,
     pending_texture_request: Option<(String, Option<String>)>,
     pending_shader_request: Option<(String, Option<String>)>,
+    // Campo de material do objeto selecionado no último frame, alvo de
+    // materiais soltos do Projeto
+    material_slot: Option<(String, Rect)>,
+    // Textura com opções de amostragem salvas, a recriar na GPU
+    pending_texture_reload: Option<String>,
     shader_texture_cache: HashMap<String, TextureHandle>,
     apply_loading_until: Option<Instant>,
-}
-
-#[derive(Clone, Copy)]
-enum InspectorDockSide {
-    Left,
-    Right,
+    audio_asset: Option<AudioClipView>,
+    pending_audio_preview: Option<AudioPreviewRequest>,
+    /// Textura/malha selecionada no Projeto: (caminho, opções salvas, em edição)
+    import_asset: Option<(PathBuf, ImportDraft, ImportDraft)>,
+    pending_reimport: Option<PathBuf>,
+    pending_import_edit: Option<ImportEdit>,
+    // Componentes editados neste frame, para o histórico
+    pending_component_edits: Vec<ComponentEdit>,
+    /// Último asset checado para a pré-visualização de céu e a prévia, se for céu
+    environment_asset: Option<PathBuf>,
+    environment_preview: Option<EnvironmentPreview>,
+    /// Shaders WGSL do projeto (`Assets/Shaders`), com parâmetros refletidos
+    material_shaders: AssetManager,
+    material_shader_errors: Vec<(PathBuf, String)>,
+    material_shader_scan: Option<(PathBuf, Instant)>,
+    /// Índice de busca do Projeto, consultado pelos seletores de malha e material
+    search_index: Arc<AssetSearchIndex>,
+    asset_picker_query: String,
+    /// Componentes desenhados a partir dos campos expostos
+    components: ComponentRegistry,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum ShaderType {
+pub(crate) enum ShaderType {
     Standard,
     Unlit,
     Transparent,
 }
 
 impl ShaderType {
-    pub const ALL: [ShaderType; 3] = [
+    pub(crate) const ALL: [ShaderType; 3] = [
         ShaderType::Standard,
         ShaderType::Unlit,
         ShaderType::Transparent,
     ];
 
-    fn as_str(&self) -> &'static str {
+    pub(crate) fn as_str(&self) -> &'static str {
         match self {
             ShaderType::Standard => "Standard",
             ShaderType::Unlit => "Unlit",
@@ -196,7 +1601,7 @@ impl ShaderType {
         }
     }
 
-    fn display_name(&self) -> &'static str {
+    pub(crate) fn display_name(&self) -> &'static str {
         match self {
             ShaderType::Standard => "Standard",
             ShaderType::Unlit => "Unlit",
@@ -204,25 +1609,31 @@ impl ShaderType {
         }
     }
 
-    fn from_str(value: &str) -> Self {
+    /// `None` para nomes que não são embutidos (shaders do projeto)
+    fn parse(value: &str) -> Option<Self> {
         match value.trim().to_lowercase().as_str() {
-            "unlit" => ShaderType::Unlit,
-            "transparent" => ShaderType::Transparent,
-            _ => ShaderType::Standard,
+            "standard" | "" => Some(ShaderType::Standard),
+            "unlit" => Some(ShaderType::Unlit),
+            "transparent" => Some(ShaderType::Transparent),
+            _ => None,
         }
     }
 }
 
 #[derive(Clone, Debug)]
-struct ShaderProperties {
-    shader_type: ShaderType,
-    albedo: [f32; 3],
-    metallic: f32,
-    roughness: f32,
-    texture_path: Option<String>,
-    normal_map: Option<String>,
-    emission_color: [f32; 3],
-    emission_strength: f32,
+pub(crate) struct ShaderProperties {
+    pub(crate) shader_type: ShaderType,
+    pub(crate) albedo: [f32; 3],
+    pub(crate) metallic: f32,
+    pub(crate) roughness: f32,
+    pub(crate) texture_path: Option<String>,
+    pub(crate) normal_map: Option<String>,
+    pub(crate) emission_color: [f32; 3],
+    pub(crate) emission_strength: f32,
+    /// Shader WGSL do projeto (`shader=<nome>`), no lugar do tipo embutido
+    pub(crate) custom_shader: Option<String>,
+    /// Valores `param.<nome>=` dos parâmetros do shader do projeto
+    pub(crate) params: HashMap<String, [f32; 4]>,
 }
 
 impl Default for ShaderProperties {
@@ -236,21 +1647,51 @@ impl Default for ShaderProperties {
             normal_map: None,
             emission_color: [0.0, 0.0, 0.0],
             emission_strength: 0.0,
+            custom_shader: None,
+            params: HashMap::new(),
+        }
+    }
+}
+
+/// Conteúdo do material já migrado para a versão atual do formato
+fn read_material(mat_path: &str) -> Option<String> {
+    let raw = std::fs::read_to_string(mat_path).ok()?;
+    match migrate_format(FormatKind::Material, &raw) {
+        Ok(migrated) => Some(migrated.text),
+        Err(e) => {
+            log::error!("[SHADER] {}: {}", mat_path, e);
+            None
         }
     }
 }
 
-fn parse_shader_properties(mat_path: &str) -> Option<ShaderProperties> {
-    let content = std::fs::read_to_string(mat_path).ok()?;
+pub(crate) fn parse_shader_properties(mat_path: &str) -> Option<ShaderProperties> {
+    let content = read_material(mat_path)?;
     let mut props = ShaderProperties::default();
 
     for line in content.lines() {
         let line = line.trim();
         if let Some(val) = line.strip_prefix("shader=") {
-            props.shader_type = ShaderType::from_str(val);
+            match ShaderType::parse(val) {
+                Some(shader_type) => {
+                    props.shader_type = shader_type;
+                    props.custom_shader = None;
+                }
+                None => props.custom_shader = Some(val.trim().to_string()),
+            }
         }
         if let Some(val) = line.strip_prefix("shader_type=") {
-            props.shader_type = ShaderType::from_str(val);
+            props.shader_type = ShaderType::parse(val).unwrap_or(ShaderType::Standard);
+        }
+        if let Some((name, val)) = line
+            .strip_prefix("param.")
+            .and_then(|rest| rest.split_once('='))
+        {
+            let mut value = [0.0; 4];
+            for (slot, part) in value.iter_mut().zip(val.split(',')) {
+                *slot = part.trim().parse().unwrap_or(0.0);
+            }
+            props.params.insert(name.trim().to_string(), value);
         }
         if let Some(val) = line.strip_prefix("albedo=") {
             let parts: Vec<&str> = val.split(',').collect();
@@ -266,15 +1707,9 @@ fn parse_shader_properties(mat_path: &str) -> Option<ShaderProperties> {
         if let Some(val) = line.strip_prefix("roughness=") {
             props.roughness = val.trim().parse().unwrap_or(0.5);
         }
-        if let Some(val) = line.strip_prefix("smoothness=") {
-            props.roughness = 1.0 - val.trim().parse().unwrap_or(0.5);
-        }
         if let Some(val) = line.strip_prefix("albedo_texture=") {
             props.texture_path = Some(val.trim().to_string());
         }
-        if let Some(val) = line.strip_prefix("texture=") {
-            props.texture_path = Some(val.trim().to_string());
-        }
         if let Some(val) = line.strip_prefix("normal_map=") {
             props.normal_map = Some(val.trim().to_string());
         }
@@ -294,15 +1729,25 @@ fn parse_shader_properties(mat_path: &str) -> Option<ShaderProperties> {
     Some(props)
 }
 
-fn update_shader_property(mat_path: &str, key: &str, value: &str) {
-    if let Ok(content) = std::fs::read_to_string(mat_path) {
+pub(crate) fn update_shader_property(mat_path: &str, key: &str, value: &str) {
+    // Salvar grava o material na versão atual, mesmo que ele fosse antigo
+    if let Some(content) = read_material(mat_path) {
         let mut new_content = String::new();
         let mut found = false;
+        // Texturas levam o GUID junto: o material sobrevive a renomear/mover o arquivo
+        let guid_key = format!("{}_guid=", key);
+        let guid = if MATERIAL_REFERENCE_KEYS.contains(&key) {
+            ensure_guid(Path::new(value.trim())).ok()
+        } else {
+            None
+        };
 
         for line in content.lines() {
             if line.trim().starts_with(&format!("{}=", key)) {
                 new_content.push_str(&format!("{}={}\n", key, value));
                 found = true;
+            } else if line.trim().starts_with(&guid_key) {
+                continue;
             } else {
                 new_content.push_str(line);
                 new_content.push('\n');
@@ -312,9 +1757,71 @@ fn update_shader_property(mat_path: &str, key: &str, value: &str) {
         if !found {
             new_content.push_str(&format!("{}={}\n", key, value));
         }
+        if let Some(guid) = guid {
+            new_content.push_str(&format!("{}{}\n", guid_key, guid));
+        }
 
         let _ = std::fs::write(mat_path, new_content);
-        eprintln!("[SHADER] Atualizado {}: {} = {}", mat_path, key, value);
+        log::debug!("[SHADER] Atualizado {}: {} = {}", mat_path, key, value);
+    }
+}
+
+/// Pasta `Assets/Shaders` do projeto que contém o material
+fn project_shader_dir(mat_path: &str) -> Option<PathBuf> {
+    Path::new(mat_path)
+        .ancestors()
+        .find(|dir| dir.file_name().is_some_and(|name| name == "Assets"))
+        .map(|assets| assets.join("Shaders"))
+}
+
+fn format_shader_param(value: &[f32]) -> String {
+    value
+        .iter()
+        .map(|v| format!("{v:.3}"))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Um campo por parâmetro refletido do shader; alterações vão para `param.<nome>=`
+pub(crate) fn draw_shader_param(
+    ui: &mut egui::Ui,
+    mat_path: &str,
+    param: &ShaderParam,
+    stored: Option<&[f32; 4]>,
+) {
+    let mut value = stored.copied().unwrap_or(param.default);
+    let components = param.kind.components();
+    let changed = ui
+        .horizontal(|ui| {
+            ui.label(&param.name);
+            if param.color && param.kind == ShaderParamKind::Vec3 {
+                let mut rgb = [value[0], value[1], value[2]];
+                let changed = ui.color_edit_button_rgb(&mut rgb).changed();
+                value[..3].copy_from_slice(&rgb);
+                changed
+            } else if param.color {
+                ui.color_edit_button_rgba_unmultiplied(&mut value).changed()
+            } else if let Some((min, max)) = param.range {
+                let mut changed = false;
+                for v in &mut value[..components] {
+                    changed |= ui.add(egui::Slider::new(v, min..=max)).changed();
+                }
+                changed
+            } else {
+                let mut changed = false;
+                for v in &mut value[..components] {
+                    changed |= ui.add(egui::DragValue::new(v).speed(0.01)).changed();
+                }
+                changed
+            }
+        })
+        .inner;
+    if changed {
+        update_shader_property(
+            mat_path,
+            &format!("param.{}", param.name),
+            &format_shader_param(&value[..components]),
+        );
     }
 }
 
@@ -323,7 +1830,7 @@ fn load_png_as_texture(
     png_path: &str,
     tint: Option<Color32>,
 ) -> Option<TextureHandle> {
-    let bytes = std::fs::read(png_path).ok()?;
+    let bytes = engine_render::vfs_read(png_path).ok()?;
     let rgba_img = image::load_from_memory(&bytes).ok()?.to_rgba8();
     let size = [rgba_img.width() as usize, rgba_img.height() as usize];
     let mut rgba = rgba_img.into_raw();
@@ -363,28 +1870,125 @@ impl InspectorWindow {
             unlock_icon_texture: None,
             add_icon_texture: None,
             is_locked: true,
-            dock_side: Some(InspectorDockSide::Left),
-            window_pos: None,
-            window_width: INSPECTOR_MIN_WIDTH,
-            dragging_from_header: false,
-            resizing_width: false,
-            fonts_initialized: false,
             object_transforms: HashMap::new(),
             object_transform_enabled: HashMap::new(),
             last_selected_object: String::new(),
+            selected_objects: Vec::new(),
             pending_live_request: None,
             pending_apply_request: None,
             _pending_animator_request: None,
             object_fios_controller: HashMap::new(),
             object_rigidbody: HashMap::new(),
+            object_collider: HashMap::new(),
             object_animator: HashMap::new(),
             object_light: HashMap::new(),
+            object_script: HashMap::new(),
+            object_camera: HashMap::new(),
+            object_camera_follow: HashMap::new(),
+            object_post_process: HashMap::new(),
+            object_lod: HashMap::new(),
+            object_particles: HashMap::new(),
+            object_terrain: HashMap::new(),
+            object_water: HashMap::new(),
+            object_sprite: HashMap::new(),
+            object_text3d: HashMap::new(),
+            object_ui_text: HashMap::new(),
+            hierarchy_drag: None,
             object_texture: HashMap::new(),
             object_shader: HashMap::new(),
             pending_texture_request: None,
             pending_shader_request: None,
+            material_slot: None,
+            pending_texture_reload: None,
             shader_texture_cache: HashMap::new(),
             apply_loading_until: None,
+            audio_asset: None,
+            pending_audio_preview: None,
+            import_asset: None,
+            pending_reimport: None,
+            pending_import_edit: None,
+            pending_component_edits: Vec::new(),
+            environment_asset: None,
+            environment_preview: None,
+            material_shaders: AssetManager::new(),
+            material_shader_errors: Vec::new(),
+            material_shader_scan: None,
+            search_index: Arc::new(AssetSearchIndex::default()),
+            asset_picker_query: String::new(),
+            components: ComponentRegistry::with_builtin(),
+        }
+    }
+
+    /// Registra os shaders de `Assets/Shaders` do projeto do material,
+    /// varrendo de novo a cada `MATERIAL_SHADER_RESCAN`
+    pub(crate) fn refresh_material_shaders(&mut self, mat_path: &str) {
+        let Some(dir) = project_shader_dir(mat_path) else {
+            return;
+        };
+        let fresh = self
+            .material_shader_scan
+            .as_ref()
+            .is_some_and(|(scanned, at)| *scanned == dir && at.elapsed() < MATERIAL_SHADER_RESCAN);
+        if fresh {
+            return;
+        }
+        self.material_shader_errors = self.material_shaders.register_material_shaders(&dir);
+        self.material_shader_scan = Some((dir, Instant::now()));
+    }
+
+    /// Filtro, anisotropia e mipmaps da textura (arquivo `.sampling` ao lado dela)
+    fn draw_texture_sampling(
+        &mut self,
+        ui: &mut egui::Ui,
+        language: EngineLanguage,
+        texture_path: &str,
+    ) {
+        let path = Path::new(texture_path);
+        if !path.is_file() {
+            return;
+        }
+        let current = TextureSampling::load(path);
+        let mut sampling = current;
+        egui::CollapsingHeader::new(tr(language, "sampling"))
+            .id_salt(("texture_sampling", texture_path))
+            .default_open(false)
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr(language, "filter"));
+                    egui::ComboBox::from_id_salt(ui.id().with("texture_filter"))
+                        .selected_text(sampling.filter.label())
+                        .show_ui(ui, |ui| {
+                            for filter in TextureFilter::ALL {
+                                ui.selectable_value(&mut sampling.filter, filter, filter.label());
+                            }
+                        });
+                });
+                ui.add_enabled_ui(
+                    sampling.filter == TextureFilter::Trilinear && sampling.mipmaps,
+                    |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label(tr(language, "anisotropy"));
+                            egui::ComboBox::from_id_salt(ui.id().with("texture_anisotropy"))
+                                .selected_text(format!("{}x", sampling.anisotropy))
+                                .show_ui(ui, |ui| {
+                                    for level in ANISOTROPY_LEVELS {
+                                        ui.selectable_value(
+                                            &mut sampling.anisotropy,
+                                            level,
+                                            format!("{level}x"),
+                                        );
+                                    }
+                                });
+                        });
+                    },
+                );
+                ui.checkbox(&mut sampling.mipmaps, tr(language, "generate_mipmaps"));
+            });
+        if sampling != current {
+            match sampling.save(path) {
+                Ok(()) => self.pending_texture_reload = Some(texture_path.to_string()),
+                Err(e) => log::error!("[TEXTURE] {e}"),
+            }
         }
     }
 
@@ -415,37 +2019,188 @@ impl InspectorWindow {
         None
     }
 
-    pub fn fios_controller_targets(&self) -> Vec<(String, FiosControllerDraft)> {
-        self.object_fios_controller
+    pub fn fios_controller_targets(&self) -> Vec<(String, FiosControllerDraft)> {
+        self.object_fios_controller
+            .iter()
+            .filter_map(|(name, cfg)| {
+                if cfg.enabled {
+                    Some((name.clone(), cfg.clone()))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    pub fn rigidbody_targets(&self) -> Vec<(String, RigidbodyDraft)> {
+        self.object_rigidbody
+            .iter()
+            .filter_map(|(name, cfg)| {
+                if cfg.enabled {
+                    Some((name.clone(), *cfg))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    pub fn collider_targets(&self) -> Vec<(String, ColliderDraft)> {
+        self.object_collider
+            .iter()
+            .filter_map(|(name, cfg)| {
+                if cfg.enabled {
+                    Some((name.clone(), *cfg))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    pub fn animator_targets(&self) -> Vec<(String, AnimatorDraft)> {
+        self.object_animator
+            .iter()
+            .filter_map(|(name, cfg)| {
+                if cfg.enabled {
+                    Some((name.clone(), cfg.clone()))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    pub fn camera_targets(&self) -> Vec<(String, CameraDraft)> {
+        self.object_camera
+            .iter()
+            .filter(|(_, cfg)| cfg.enabled)
+            .map(|(name, cfg)| (name.clone(), cfg.clone()))
+            .collect()
+    }
+
+    pub fn camera_follow_targets(&self) -> Vec<(String, CameraFollowDraft)> {
+        self.object_camera_follow
+            .iter()
+            .filter(|(_, cfg)| cfg.enabled && !cfg.target.guid.is_nil())
+            .map(|(name, cfg)| (name.clone(), cfg.clone()))
+            .collect()
+    }
+
+    /// Shaders de material carregados (relatório de memória)
+    /// Shader de material alterado em disco, relido no mesmo handle; None se o
+    /// arquivo ainda não foi registrado (a próxima varredura o encontra)
+    pub fn reload_material_shader(&mut self, path: &Path) -> Option<Result<(), String>> {
+        let source = self
+            .material_shaders
+            .material_shaders
+            .values()
+            .find(|shader| same_file(&shader.source_path, path))?
+            .source_path
+            .clone();
+        let result = self
+            .material_shaders
+            .load_material_shader(&source)
+            .map(|_| ());
+        self.material_shader_errors
+            .retain(|(failed, _)| *failed != source);
+        if let Err(e) = &result {
+            self.material_shader_errors.push((source, e.clone()));
+        }
+        Some(result)
+    }
+
+    pub fn material_shader_assets(&self) -> &AssetManager {
+        &self.material_shaders
+    }
+
+    /// Erros de compilação dos shaders de `Assets/Shaders`, por arquivo
+    pub(crate) fn material_shader_errors(&self) -> &[(PathBuf, String)] {
+        &self.material_shader_errors
+    }
+
+    /// Pós-processamento ativo: o da "Main Camera" ou, sem ele, o primeiro por nome
+    pub fn post_process_target(&self) -> Option<PostProcessDraft> {
+        if let Some(cfg) = self
+            .object_post_process
+            .get("Main Camera")
+            .filter(|cfg| cfg.settings.enabled)
+        {
+            return Some(cfg.clone());
+        }
+        self.object_post_process
+            .iter()
+            .filter(|(_, cfg)| cfg.settings.enabled)
+            .min_by(|a, b| a.0.cmp(b.0))
+            .map(|(_, cfg)| cfg.clone())
+    }
+
+    pub fn lod_targets(&self) -> Vec<(String, LodDraft)> {
+        self.object_lod
+            .iter()
+            .filter(|(_, cfg)| cfg.enabled && !cfg.levels.is_empty())
+            .map(|(name, cfg)| (name.clone(), cfg.clone()))
+            .collect()
+    }
+
+    pub fn particle_targets(&self) -> Vec<(String, ParticleDraft)> {
+        self.object_particles
+            .iter()
+            .filter(|(_, cfg)| cfg.enabled)
+            .map(|(name, cfg)| (name.clone(), cfg.clone()))
+            .collect()
+    }
+
+    pub fn terrain_targets(&self) -> Vec<(String, TerrainDraft)> {
+        self.object_terrain
+            .iter()
+            .filter(|(_, cfg)| cfg.enabled)
+            .map(|(name, cfg)| (name.clone(), cfg.clone()))
+            .collect()
+    }
+
+    pub fn water_targets(&self) -> Vec<(String, WaterDraft)> {
+        self.object_water
             .iter()
-            .filter_map(|(name, cfg)| {
-                if cfg.enabled {
-                    Some((name.clone(), cfg.clone()))
-                } else {
-                    None
-                }
-            })
+            .filter(|(_, cfg)| cfg.enabled)
+            .map(|(name, cfg)| (name.clone(), cfg.clone()))
             .collect()
     }
 
-    pub fn rigidbody_targets(&self) -> Vec<(String, RigidbodyDraft)> {
-        self.object_rigidbody
+    pub fn sprite_targets(&self) -> Vec<(String, SpriteDraft)> {
+        self.object_sprite
             .iter()
-            .filter_map(|(name, cfg)| {
-                if cfg.enabled {
-                    Some((name.clone(), *cfg))
-                } else {
-                    None
-                }
-            })
+            .filter(|(_, cfg)| cfg.enabled)
+            .map(|(name, cfg)| (name.clone(), cfg.clone()))
             .collect()
     }
 
-    pub fn animator_targets(&self) -> Vec<(String, AnimatorDraft)> {
-        self.object_animator
+    pub fn text3d_targets(&self) -> Vec<(String, Text3DDraft)> {
+        self.object_text3d
+            .iter()
+            .filter(|(_, cfg)| cfg.enabled)
+            .map(|(name, cfg)| (name.clone(), cfg.clone()))
+            .collect()
+    }
+
+    pub fn ui_text_targets(&self) -> Vec<(String, UiTextDraft)> {
+        self.object_ui_text
+            .iter()
+            .filter(|(_, cfg)| cfg.enabled)
+            .map(|(name, cfg)| (name.clone(), cfg.clone()))
+            .collect()
+    }
+
+    /// Objeto arrastado na Hierarquia neste frame, com o seu GUID
+    pub fn set_hierarchy_drag(&mut self, drag: Option<(String, Guid)>) {
+        self.hierarchy_drag = drag;
+    }
+
+    pub fn script_targets(&self) -> Vec<(String, ScriptDraft)> {
+        self.object_script
             .iter()
             .filter_map(|(name, cfg)| {
-                if cfg.enabled {
+                if cfg.enabled && !cfg.path.trim().is_empty() {
                     Some((name.clone(), cfg.clone()))
                 } else {
                     None
@@ -454,13 +2209,29 @@ impl InspectorWindow {
             .collect()
     }
 
+    pub fn set_object_script(&mut self, object_name: &str, script: ScriptDraft) {
+        self.object_script.insert(object_name.to_string(), script);
+    }
+
     pub fn remove_object_data(&mut self, object_name: &str) {
         self.object_transforms.remove(object_name);
         self.object_transform_enabled.remove(object_name);
         self.object_fios_controller.remove(object_name);
         self.object_rigidbody.remove(object_name);
+        self.object_collider.remove(object_name);
         self.object_animator.remove(object_name);
         self.object_light.remove(object_name);
+        self.object_script.remove(object_name);
+        self.object_camera.remove(object_name);
+        self.object_camera_follow.remove(object_name);
+        self.object_post_process.remove(object_name);
+        self.object_lod.remove(object_name);
+        self.object_particles.remove(object_name);
+        self.object_terrain.remove(object_name);
+        self.object_water.remove(object_name);
+        self.object_sprite.remove(object_name);
+        self.object_text3d.remove(object_name);
+        self.object_ui_text.remove(object_name);
         self.object_texture.remove(object_name);
     }
 
@@ -468,10 +2239,355 @@ impl InspectorWindow {
         self.pending_texture_request.take()
     }
 
+    pub fn take_texture_reload_request(&mut self) -> Option<String> {
+        self.pending_texture_reload.take()
+    }
+
     pub fn take_shader_request(&mut self) -> Option<(String, Option<String>)> {
         self.pending_shader_request.take()
     }
 
+    /// Material do Projeto solto sobre o campo de material: vira o material
+    /// do objeto, aplicado na viewport pelo pedido de shader
+    pub(crate) fn on_material_dropped(&mut self, pos: egui::Pos2, path: &Path) {
+        let Some((object_name, _)) = self
+            .material_slot
+            .as_ref()
+            .filter(|(_, rect)| rect.contains(pos))
+        else {
+            return;
+        };
+        let object_name = object_name.clone();
+        let path = path.to_string_lossy().to_string();
+        self.object_shader.insert(object_name.clone(), path.clone());
+        self.pending_shader_request = Some((object_name, Some(path)));
+    }
+
+    pub(crate) fn material_slot_contains(&self, pos: egui::Pos2) -> bool {
+        self.material_slot
+            .as_ref()
+            .is_some_and(|(_, rect)| rect.contains(pos))
+    }
+
+    /// Material aplicado fora do inspetor (soltura na viewport)
+    pub(crate) fn set_object_shader(&mut self, object_name: &str, path: &str) {
+        self.object_shader
+            .insert(object_name.to_string(), path.to_string());
+    }
+
+    /// Asset de áudio selecionado no Projeto e o estado da pre-escuta
+    pub(crate) fn set_audio_asset(&mut self, asset: Option<AudioClipView>) {
+        self.audio_asset = asset;
+    }
+
+    pub fn take_audio_preview_request(&mut self) -> Option<AudioPreviewRequest> {
+        self.pending_audio_preview.take()
+    }
+
+    /// Asset selecionado no Projeto cujas opções de importação aparecem no inspetor
+    pub fn set_import_asset(&mut self, path: Option<PathBuf>) {
+        let current = self.import_asset.as_ref().map(|(p, _, _)| p);
+        if current == path.as_ref() {
+            return;
+        }
+        self.import_asset = path.and_then(|path| {
+            let saved = ImportDraft::load(&path)?;
+            Some((path, saved.clone(), saved))
+        });
+    }
+
+    /// Asset selecionado no Projeto; panoramas e cubemaps ganham a esfera de
+    /// pré-visualização
+    pub fn set_environment_asset(&mut self, path: Option<PathBuf>) {
+        if self.environment_asset == path {
+            return;
+        }
+        self.environment_asset = path.clone();
+        self.environment_preview = path.filter(|p| is_cubemap_source(p)).map(|path| {
+            let (tx, rx) = mpsc::channel();
+            let source = path.clone();
+            std::thread::spawn(move || {
+                let _ = tx.send(environment_sphere_thumbnail(
+                    &source,
+                    ENVIRONMENT_PREVIEW_SIZE,
+                ));
+            });
+            EnvironmentPreview {
+                path,
+                pending: Some(rx),
+                texture: None,
+                error: None,
+            }
+        });
+    }
+
+    /// O céu selecionado mudou em disco: gera a esfera de novo
+    pub fn refresh_environment_preview(&mut self, path: &Path) {
+        if self.environment_asset.as_deref() == Some(path) {
+            let selected = self.environment_asset.take();
+            self.set_environment_asset(selected);
+        }
+    }
+
+    fn draw_environment_preview(&mut self, ui: &mut egui::Ui, language: EngineLanguage) {
+        let Some(preview) = self.environment_preview.as_mut() else {
+            return;
+        };
+        if let Some(rx) = &preview.pending {
+            match rx.try_recv() {
+                Ok(Ok(thumbnail)) => {
+                    let image = ColorImage::from_rgba_unmultiplied(
+                        [thumbnail.width as usize, thumbnail.height as usize],
+                        &thumbnail.rgba,
+                    );
+                    preview.texture = Some(ui.ctx().load_texture(
+                        "inspector_environment_preview",
+                        image,
+                        TextureOptions::LINEAR,
+                    ));
+                    preview.pending = None;
+                }
+                Ok(Err(e)) => {
+                    preview.error = Some(e);
+                    preview.pending = None;
+                }
+                Err(TryRecvError::Empty) => {
+                    ui.ctx().request_repaint_after(Duration::from_millis(100));
+                }
+                Err(TryRecvError::Disconnected) => preview.pending = None,
+            }
+        }
+        egui::Frame::new()
+            .fill(theme::palette().panel_header)
+            .stroke(Stroke::new(1.0, theme::palette().widget_hover))
+            .corner_radius(6)
+            .inner_margin(egui::Margin::same(8))
+            .show(ui, |ui| {
+                ui.label(
+                    egui::RichText::new(tr(language, "sky_cubemap"))
+                        .strong()
+                        .color(theme::palette().text_strong),
+                );
+                ui.label(
+                    egui::RichText::new(
+                        preview
+                            .path
+                            .file_name()
+                            .map(|n| n.to_string_lossy().to_string())
+                            .unwrap_or_default(),
+                    )
+                    .color(theme::palette().text),
+                );
+                ui.add_space(4.0);
+                ui.vertical_centered(|ui| {
+                    let side = ENVIRONMENT_PREVIEW_SIZE as f32;
+                    if let Some(texture) = &preview.texture {
+                        ui.image((texture.id(), egui::vec2(side, side)));
+                    } else if let Some(error) = &preview.error {
+                        ui.label(
+                            egui::RichText::new(error)
+                                .small()
+                                .color(theme::palette().error),
+                        );
+                    } else {
+                        ui.add_sized([side, side], egui::Spinner::new());
+                    }
+                });
+            });
+        ui.add_space(8.0);
+    }
+
+    /// Arquivo a reimportar com as opções do .meta
+    pub fn take_reimport_request(&mut self) -> Option<PathBuf> {
+        self.pending_reimport.take()
+    }
+
+    /// Opções de importação aplicadas neste frame, para o histórico
+    pub(crate) fn take_import_edit(&mut self) -> Option<ImportEdit> {
+        self.pending_import_edit.take()
+    }
+
+    /// Componentes editados desde a última chamada, na ordem
+    pub(crate) fn take_component_edits(&mut self) -> Vec<ComponentEdit> {
+        std::mem::take(&mut self.pending_component_edits)
+    }
+
+    /// Registra a edição de `object_name` feita desde `before`, se algo mudou
+    pub(crate) fn record_component_edit(
+        &mut self,
+        object_name: &str,
+        mut before: ObjectComponents,
+    ) {
+        let after = self.object_components(object_name);
+        // A luz direcional segue os campos globais da viewport
+        if object_name == "Directional Light" {
+            before.light = after.light;
+        }
+        if after != before {
+            self.pending_component_edits.push(ComponentEdit {
+                object: object_name.to_string(),
+                before,
+                after,
+            });
+        }
+    }
+
+    /// Relê do .meta as opções de `path` se ele está aberto no inspetor
+    /// (depois de desfazer ou refazer)
+    pub fn reload_import_asset(&mut self, path: &Path) {
+        if self
+            .import_asset
+            .as_ref()
+            .is_some_and(|(p, _, _)| p == path)
+        {
+            self.import_asset = None;
+            self.set_import_asset(Some(path.to_path_buf()));
+        }
+    }
+
+    /// Collider gerado na importação; não substitui um que o objeto já tenha
+    pub fn add_generated_collider(&mut self, object_name: &str, collider: ColliderDraft) {
+        self.object_collider
+            .entry(object_name.to_string())
+            .or_insert(collider);
+    }
+
+    /// Seção "Import Settings" do asset selecionado: opções gravadas no .meta
+    /// ao aplicar, seguidas de uma reimportação
+    fn draw_import_settings(&mut self, ui: &mut egui::Ui, language: EngineLanguage) {
+        let Some((path, saved, draft)) = self.import_asset.as_mut() else {
+            return;
+        };
+        let mut apply = false;
+        let mut revert = false;
+        let mut reimport = false;
+        egui::Frame::new()
+            .fill(theme::palette().panel_header)
+            .stroke(Stroke::new(1.0, theme::palette().widget_hover))
+            .corner_radius(6)
+            .inner_margin(egui::Margin::same(8))
+            .show(ui, |ui| {
+                ui.label(
+                    egui::RichText::new(tr(language, "import_settings"))
+                        .strong()
+                        .color(theme::palette().text_strong),
+                );
+                ui.label(
+                    egui::RichText::new(
+                        path.file_name()
+                            .map(|n| n.to_string_lossy().to_string())
+                            .unwrap_or_default(),
+                    )
+                    .color(theme::palette().text),
+                );
+                ui.add_space(4.0);
+                match draft {
+                    ImportDraft::Texture(settings) => {
+                        ui.add_enabled(
+                            !settings.normal_map,
+                            egui::Checkbox::new(&mut settings.srgb, "sRGB"),
+                        );
+                        ui.checkbox(&mut settings.normal_map, tr(language, "normal_map"));
+                        ui.horizontal(|ui| {
+                            ui.label(tr(language, "max_size"));
+                            let original = tr(language, "original");
+                            let label = |size: u32| {
+                                if size == 0 {
+                                    original.to_string()
+                                } else {
+                                    size.to_string()
+                                }
+                            };
+                            egui::ComboBox::from_id_salt(ui.id().with("import_max_size"))
+                                .selected_text(label(settings.max_size))
+                                .show_ui(ui, |ui| {
+                                    for size in [0, 256, 512, 1024, 2048, 4096] {
+                                        ui.selectable_value(
+                                            &mut settings.max_size,
+                                            size,
+                                            label(size),
+                                        );
+                                    }
+                                });
+                        });
+                    }
+                    ImportDraft::Mesh(settings) => {
+                        ui.horizontal(|ui| {
+                            ui.label(tr(language, "scale"));
+                            ui.add(
+                                egui::DragValue::new(&mut settings.scale)
+                                    .speed(0.01)
+                                    .range(0.001..=1000.0),
+                            );
+                        });
+                        ui.checkbox(
+                            &mut settings.generate_colliders,
+                            tr(language, "generate_colliders"),
+                        );
+                    }
+                    ImportDraft::Font(settings) => {
+                        ui.horizontal(|ui| {
+                            ui.label(tr(language, "atlas_size_px"));
+                            egui::ComboBox::from_id_salt(ui.id().with("import_font_px"))
+                                .selected_text(format!("{}", settings.pixel_size))
+                                .show_ui(ui, |ui| {
+                                    for size in [16.0, 24.0, 32.0, 48.0, 64.0, 96.0, 128.0] {
+                                        ui.selectable_value(
+                                            &mut settings.pixel_size,
+                                            size,
+                                            format!("{size}"),
+                                        );
+                                    }
+                                });
+                        });
+                        ui.label(tr(language, "extra_characters"));
+                        ui.add(
+                            egui::TextEdit::singleline(&mut settings.extra_chars)
+                                .hint_text("€ŒœĀā")
+                                .desired_width(f32::INFINITY),
+                        );
+                    }
+                }
+                ui.add_space(4.0);
+                let changed = draft != saved;
+                ui.horizontal(|ui| {
+                    apply = ui
+                        .add_enabled(changed, egui::Button::new(tr(language, "apply")))
+                        .clicked();
+                    revert = ui
+                        .add_enabled(changed, egui::Button::new(tr(language, "revert")))
+                        .clicked();
+                    reimport = ui.button(tr(language, "reimport")).clicked();
+                });
+            });
+        ui.add_space(8.0);
+
+        if revert {
+            *draft = saved.clone();
+        }
+        if apply {
+            match draft.save(path) {
+                Ok(()) => {
+                    self.pending_import_edit = Some(ImportEdit {
+                        path: path.clone(),
+                        before: saved.clone(),
+                        after: draft.clone(),
+                    });
+                    *saved = draft.clone();
+                    reimport = true;
+                }
+                Err(e) => log::error!("[IMPORT] {e}"),
+            }
+        }
+        if reimport {
+            self.pending_reimport = Some(path.clone());
+        }
+    }
+
+    pub fn set_search_index(&mut self, index: Arc<AssetSearchIndex>) {
+        self.search_index = index;
+    }
+
     pub fn get_object_light(&self, object_name: &str) -> Option<LightDraft> {
         self.object_light.get(object_name).cloned()
     }
@@ -480,6 +2596,328 @@ impl InspectorWindow {
         self.object_light.insert(object_name.to_string(), light);
     }
 
+    pub fn set_selected_objects(&mut self, names: &[String]) {
+        self.selected_objects = names.to_vec();
+    }
+
+    pub(crate) fn snapshot(&self) -> InspectorSnapshot {
+        InspectorSnapshot {
+            // Só os desligados: selecionar um objeto já cria a entrada ligada
+            object_transform_enabled: self
+                .object_transform_enabled
+                .iter()
+                .filter(|(_, enabled)| !**enabled)
+                .map(|(name, enabled)| (name.clone(), *enabled))
+                .collect(),
+            object_fios_controller: self.object_fios_controller.clone(),
+            object_rigidbody: self.object_rigidbody.clone(),
+            object_collider: self.object_collider.clone(),
+            object_animator: self.object_animator.clone(),
+            // A luz direcional segue os campos globais da viewport
+            object_light: self
+                .object_light
+                .iter()
+                .filter(|(name, _)| name.as_str() != "Directional Light")
+                .map(|(name, light)| (name.clone(), *light))
+                .collect(),
+            object_script: self.object_script.clone(),
+            object_camera: self.object_camera.clone(),
+            object_camera_follow: self.object_camera_follow.clone(),
+            object_post_process: self.object_post_process.clone(),
+            object_lod: self.object_lod.clone(),
+            object_particles: self.object_particles.clone(),
+            object_terrain: self.object_terrain.clone(),
+            object_water: self.object_water.clone(),
+            object_sprite: self.object_sprite.clone(),
+            object_text3d: self.object_text3d.clone(),
+            object_ui_text: self.object_ui_text.clone(),
+            object_texture: self.object_texture.clone(),
+            object_shader: self.object_shader.clone(),
+        }
+    }
+
+    pub(crate) fn restore(&mut self, snapshot: &InspectorSnapshot) {
+        let snapshot = snapshot.clone();
+        self.object_transform_enabled.retain(|_, enabled| *enabled);
+        self.object_transform_enabled
+            .extend(snapshot.object_transform_enabled);
+        self.object_fios_controller = snapshot.object_fios_controller;
+        self.object_rigidbody = snapshot.object_rigidbody;
+        self.object_collider = snapshot.object_collider;
+        self.object_animator = snapshot.object_animator;
+        self.object_light
+            .retain(|name, _| name == "Directional Light");
+        self.object_light.extend(snapshot.object_light);
+        self.object_script = snapshot.object_script;
+        self.object_camera = snapshot.object_camera;
+        self.object_camera_follow = snapshot.object_camera_follow;
+        self.object_post_process = snapshot.object_post_process;
+        self.object_lod = snapshot.object_lod;
+        self.object_particles = snapshot.object_particles;
+        self.object_terrain = snapshot.object_terrain;
+        self.object_water = snapshot.object_water;
+        self.object_sprite = snapshot.object_sprite;
+        self.object_text3d = snapshot.object_text3d;
+        self.object_ui_text = snapshot.object_ui_text;
+        self.object_texture = snapshot.object_texture;
+        self.object_shader = snapshot.object_shader;
+    }
+
+    /// Quadro de um componente registrado, com título, botão de remover e
+    /// os campos gerados
+    fn draw_component(
+        &mut self,
+        ui: &mut egui::Ui,
+        component: TypeId,
+        object_name: &str,
+        language: EngineLanguage,
+    ) {
+        let Some(entry) = self.components.get(component) else {
+            return;
+        };
+        // Os campos `Asset` usam a busca do inspetor enquanto o draft está emprestado
+        let search_index = Arc::clone(&self.search_index);
+        let mut query = std::mem::take(&mut self.asset_picker_query);
+        let mut remove = false;
+        if let Some(draft) = (entry.get)(self, object_name) {
+            let mut ctx = ReflectContext::new(language).with_assets(&search_index, &mut query);
+            egui::Frame::new()
+                .fill(theme::palette().panel_header)
+                .stroke(Stroke::new(1.0, theme::palette().widget_hover))
+                .corner_radius(6)
+                .inner_margin(egui::Margin::same(8))
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            egui::RichText::new(entry.title)
+                                .strong()
+                                .color(theme::palette().text_strong),
+                        );
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui.button("×").clicked() {
+                                remove = true;
+                            }
+                        });
+                    });
+                    ui.add_space(4.0);
+                    reflect::draw_fields(ui, entry.title, draft, &mut ctx);
+                    if let Some(note) = entry.note {
+                        ui.label(
+                            egui::RichText::new(note)
+                                .small()
+                                .color(theme::palette().text_weak),
+                        );
+                    }
+                });
+            ui.add_space(8.0);
+        }
+        self.asset_picker_query = query;
+        if remove {
+            (entry.remove)(self, object_name);
+        }
+    }
+
+    /// Tipos de componente do objeto, como aparecem em Adicionar Componente
+    pub(crate) fn component_names(&self, object_name: &str) -> Vec<&'static str> {
+        let present = [
+            (
+                "Fios Controller",
+                self.object_fios_controller.contains_key(object_name),
+            ),
+            ("Rigidbody", self.object_rigidbody.contains_key(object_name)),
+            ("Collider", self.object_collider.contains_key(object_name)),
+            ("Animator", self.object_animator.contains_key(object_name)),
+            (
+                "Light",
+                object_name == "Directional Light" || self.object_light.contains_key(object_name),
+            ),
+            ("Lua Script", self.object_script.contains_key(object_name)),
+            ("Camera", self.object_camera.contains_key(object_name)),
+            (
+                "Camera Follow",
+                self.object_camera_follow.contains_key(object_name),
+            ),
+            (
+                "Post-processing",
+                self.object_post_process.contains_key(object_name),
+            ),
+            ("LOD Group", self.object_lod.contains_key(object_name)),
+            (
+                "Particle System",
+                self.object_particles.contains_key(object_name),
+            ),
+            ("Terrain", self.object_terrain.contains_key(object_name)),
+            ("Water", self.object_water.contains_key(object_name)),
+            ("Sprite", self.object_sprite.contains_key(object_name)),
+            ("Text 3D", self.object_text3d.contains_key(object_name)),
+            ("UI Text", self.object_ui_text.contains_key(object_name)),
+        ];
+        std::iter::once("Transform")
+            .chain(
+                present
+                    .into_iter()
+                    .filter(|(_, present)| *present)
+                    .map(|(name, _)| name),
+            )
+            .collect()
+    }
+
+    pub(crate) fn object_components(&self, object_name: &str) -> ObjectComponents {
+        ObjectComponents {
+            fios_controller: self.object_fios_controller.get(object_name).cloned(),
+            rigidbody: self.object_rigidbody.get(object_name).copied(),
+            collider: self.object_collider.get(object_name).copied(),
+            animator: self.object_animator.get(object_name).cloned(),
+            light: self.object_light.get(object_name).copied(),
+            script: self.object_script.get(object_name).cloned(),
+            camera: self.object_camera.get(object_name).cloned(),
+            camera_follow: self.object_camera_follow.get(object_name).cloned(),
+            post_process: self.object_post_process.get(object_name).cloned(),
+            lod: self.object_lod.get(object_name).cloned(),
+            particles: self.object_particles.get(object_name).cloned(),
+            terrain: self.object_terrain.get(object_name).cloned(),
+            water: self.object_water.get(object_name).cloned(),
+            sprite: self.object_sprite.get(object_name).cloned(),
+            text3d: self.object_text3d.get(object_name).cloned(),
+            ui_text: self.object_ui_text.get(object_name).cloned(),
+            texture: self.object_texture.get(object_name).cloned(),
+            shader: self.object_shader.get(object_name).cloned(),
+        }
+    }
+
+    /// Dá a `object_name` os componentes de outro objeto (colar, duplicar)
+    pub(crate) fn set_object_components(
+        &mut self,
+        object_name: &str,
+        components: ObjectComponents,
+    ) {
+        fn put<T>(map: &mut HashMap<String, T>, name: &str, value: Option<T>) {
+            match value {
+                Some(value) => {
+                    map.insert(name.to_string(), value);
+                }
+                None => {
+                    map.remove(name);
+                }
+            }
+        }
+        put(
+            &mut self.object_fios_controller,
+            object_name,
+            components.fios_controller,
+        );
+        put(
+            &mut self.object_rigidbody,
+            object_name,
+            components.rigidbody,
+        );
+        put(&mut self.object_collider, object_name, components.collider);
+        put(&mut self.object_animator, object_name, components.animator);
+        put(&mut self.object_light, object_name, components.light);
+        put(&mut self.object_script, object_name, components.script);
+        put(&mut self.object_camera, object_name, components.camera);
+        put(
+            &mut self.object_camera_follow,
+            object_name,
+            components.camera_follow,
+        );
+        put(
+            &mut self.object_post_process,
+            object_name,
+            components.post_process,
+        );
+        put(&mut self.object_lod, object_name, components.lod);
+        put(
+            &mut self.object_particles,
+            object_name,
+            components.particles,
+        );
+        put(&mut self.object_terrain, object_name, components.terrain);
+        put(&mut self.object_water, object_name, components.water);
+        put(&mut self.object_sprite, object_name, components.sprite);
+        put(&mut self.object_text3d, object_name, components.text3d);
+        put(&mut self.object_ui_text, object_name, components.ui_text);
+        put(&mut self.object_texture, object_name, components.texture);
+        put(&mut self.object_shader, object_name, components.shader);
+    }
+
+    /// Repete nos demais selecionados os componentes de `object_name` que
+    /// mudaram desde `before`
+    fn copy_edits_to_selection(&mut self, object_name: &str, before: ObjectComponents) {
+        let targets = &self.selected_objects;
+        if targets.len() < 2 || !targets.iter().any(|n| n == object_name) {
+            return;
+        }
+        copy_component_edit(
+            &mut self.object_fios_controller,
+            object_name,
+            before.fios_controller,
+            targets,
+        );
+        copy_component_edit(
+            &mut self.object_rigidbody,
+            object_name,
+            before.rigidbody,
+            targets,
+        );
+        copy_component_edit(
+            &mut self.object_collider,
+            object_name,
+            before.collider,
+            targets,
+        );
+        copy_component_edit(
+            &mut self.object_animator,
+            object_name,
+            before.animator,
+            targets,
+        );
+        copy_component_edit(&mut self.object_light, object_name, before.light, targets);
+        copy_component_edit(&mut self.object_script, object_name, before.script, targets);
+        copy_component_edit(&mut self.object_camera, object_name, before.camera, targets);
+        copy_component_edit(
+            &mut self.object_camera_follow,
+            object_name,
+            before.camera_follow,
+            targets,
+        );
+        copy_component_edit(
+            &mut self.object_post_process,
+            object_name,
+            before.post_process,
+            targets,
+        );
+        copy_component_edit(&mut self.object_lod, object_name, before.lod, targets);
+        copy_component_edit(
+            &mut self.object_particles,
+            object_name,
+            before.particles,
+            targets,
+        );
+        copy_component_edit(
+            &mut self.object_terrain,
+            object_name,
+            before.terrain,
+            targets,
+        );
+        copy_component_edit(&mut self.object_water, object_name, before.water, targets);
+        copy_component_edit(&mut self.object_sprite, object_name, before.sprite, targets);
+        copy_component_edit(&mut self.object_text3d, object_name, before.text3d, targets);
+        copy_component_edit(
+            &mut self.object_ui_text,
+            object_name,
+            before.ui_text,
+            targets,
+        );
+        copy_component_edit(
+            &mut self.object_texture,
+            object_name,
+            before.texture,
+            targets,
+        );
+        copy_component_edit(&mut self.object_shader, object_name, before.shader, targets);
+    }
+
     pub fn take_transform_apply_request(
         &mut self,
     ) -> Option<(String, [f32; 3], [f32; 3], [f32; 3])> {
@@ -494,12 +2932,11 @@ impl InspectorWindow {
         Some((name, draft.position, draft.rotation, draft.scale))
     }
 
+    /// Desenha o inspetor em `panel`, o espaço que o layout de painéis deu a ele
     pub fn show(
         &mut self,
         ctx: &egui::Context,
-        left_reserved: f32,
-        right_reserved: f32,
-        bottom_reserved: f32,
+        panel: Rect,
         language: EngineLanguage,
         selected_object: &str,
         selected_transform: Option<([f32; 3], [f32; 3], [f32; 3])>,
@@ -513,24 +2950,25 @@ impl InspectorWindow {
         light_enabled: &mut bool,
         texture_path: Option<String>,
     ) {
+        engine_core::profile_function!();
+        self.material_slot = None;
         if !self.open {
             return;
         }
 
         if self.menu_icon_texture.is_none() {
-            self.menu_icon_texture = load_png_as_texture(ctx, "src/assets/icons/more.png", None);
+            self.menu_icon_texture = load_png_as_texture(ctx, "Editor/icons/more.png", None);
         }
         if self.lock_icon_texture.is_none() {
-            self.lock_icon_texture = load_png_as_texture(ctx, "src/assets/icons/lock.png", None);
+            self.lock_icon_texture = load_png_as_texture(ctx, "Editor/icons/lock.png", None);
         }
         if self.unlock_icon_texture.is_none() {
-            self.unlock_icon_texture =
-                load_png_as_texture(ctx, "src/assets/icons/unlock.png", None);
+            self.unlock_icon_texture = load_png_as_texture(ctx, "Editor/icons/unlock.png", None);
         }
         if self.add_icon_texture.is_none() {
             self.add_icon_texture = load_png_as_texture(
                 ctx,
-                "src/assets/icons/add.png",
+                "Editor/icons/add.png",
                 Some(Color32::from_rgb(55, 55, 55)),
             );
         }
@@ -543,7 +2981,7 @@ impl InspectorWindow {
                 .join("Animations")
                 .join("Modules")
                 .join(module_name);
-            let raw = fs::read_to_string(path).ok()?;
+            let raw = engine_render::vfs().read_to_string(path).ok()?;
             let mut walk: Option<String> = None;
             let mut idle: Option<String> = None;
             let mut any_clip: Option<String> = None;
@@ -569,63 +3007,8 @@ impl InspectorWindow {
             walk.or(idle).or(any_clip)
         };
 
-        if !self.fonts_initialized {
-            let mut fonts = egui::FontDefinitions::default();
-            fonts.font_data.insert(
-                "Roboto".to_owned(),
-                Arc::new(egui::FontData::from_static(include_bytes!(
-                    "assets/fonts/roboto.ttf"
-                ))),
-            );
-            if let Some(family) = fonts.families.get_mut(&FontFamily::Proportional) {
-                family.insert(0, "Roboto".to_owned());
-            }
-            ctx.set_fonts(fonts);
-            self.fonts_initialized = true;
-        }
-
-        let dock_rect = ctx.available_rect();
-        let usable_bottom = (dock_rect.bottom() - bottom_reserved).max(dock_rect.top() + 120.0);
-        let usable_height = (usable_bottom - dock_rect.top()).max(120.0);
-        let pointer_down = ctx.input(|i| i.pointer.primary_down());
-
-        let height = if self.dock_side.is_some() {
-            usable_height
-        } else {
-            (usable_height * 0.85).max(520.0)
-        };
-        let max_width =
-            (dock_rect.width() - left_reserved - right_reserved - 40.0).max(INSPECTOR_MIN_WIDTH);
-        self.window_width = self
-            .window_width
-            .clamp(INSPECTOR_MIN_WIDTH, max_width.min(INSPECTOR_MAX_WIDTH));
-        let window_size = egui::vec2(self.window_width, height);
-        let left_snap_x = dock_rect.left() + left_reserved;
-        let right_snap_right = dock_rect.right() - right_reserved;
-
-        if self.window_pos.is_none() {
-            self.window_pos = Some(egui::pos2(left_snap_x, dock_rect.top()));
-        }
-
-        if let Some(side) = self.dock_side {
-            if !self.dragging_from_header && !self.resizing_width && !pointer_down {
-                let x = match side {
-                    InspectorDockSide::Left => left_snap_x,
-                    InspectorDockSide::Right => right_snap_right - self.window_width,
-                };
-                self.window_pos = Some(egui::pos2(x, dock_rect.top()));
-            }
-        }
-
-        let pos = self
-            .window_pos
-            .unwrap_or(egui::pos2(left_snap_x, dock_rect.top()));
-
-        let mut header_drag_started = false;
-        let mut header_drag_stopped = false;
-        let mut resize_started = false;
-        let mut resize_stopped = false;
-        let mut panel_rect = Rect::from_min_size(pos, window_size);
+        let pos = panel.min;
+        let window_size = panel.size();
         let selected_changed = self.last_selected_object != selected_object;
         if selected_changed {
             self.last_selected_object = selected_object.to_string();
@@ -662,60 +3045,22 @@ impl InspectorWindow {
             }
         }
 
+        // Na troca de seleção o ativo só é carregado, não editado
+        let components_before =
+            (!selected_changed).then(|| self.object_components(selected_object));
+
         egui::Area::new(Id::new("inspetor_window_id"))
             .order(Order::Foreground)
             .fixed_pos(pos)
             .show(ctx, |ui| {
                 let (rect, _) = ui.allocate_exact_size(window_size, egui::Sense::hover());
-                panel_rect = rect;
-
-                // Detect resizing area (5px on the left if docked right, or on the right if docked left)
-                let resize_edge_w = 6.0;
-                let is_docked_right = matches!(self.dock_side, Some(InspectorDockSide::Right));
-                let resize_rect = if is_docked_right {
-                    Rect::from_min_max(
-                        egui::pos2(rect.left(), rect.top()),
-                        egui::pos2(rect.left() + resize_edge_w, rect.bottom()),
-                    )
-                } else {
-                    Rect::from_min_max(
-                        egui::pos2(rect.right() - resize_edge_w, rect.top()),
-                        egui::pos2(rect.right(), rect.bottom()),
-                    )
-                };
-
-                let resize_resp = ui.interact(
-                    resize_rect,
-                    ui.id().with("resize_width"),
-                    egui::Sense::drag(),
-                );
-                if resize_resp.drag_started() {
-                    resize_started = true;
-                    self.resizing_width = true;
-                }
-                if resize_resp.drag_stopped() {
-                    resize_stopped = true;
-                }
-                if self.resizing_width {
-                    ui.ctx().set_cursor_icon(egui::CursorIcon::ResizeHorizontal);
-                    let delta = ctx.input(|i| i.pointer.delta().x);
-                    if is_docked_right {
-                        self.window_width = (self.window_width - delta)
-                            .clamp(INSPECTOR_MIN_WIDTH, max_width.min(INSPECTOR_MAX_WIDTH));
-                    } else {
-                        self.window_width = (self.window_width + delta)
-                            .clamp(INSPECTOR_MIN_WIDTH, max_width.min(INSPECTOR_MAX_WIDTH));
-                    }
-                } else if resize_resp.hovered() {
-                    ui.ctx().set_cursor_icon(egui::CursorIcon::ResizeHorizontal);
-                }
 
                 ui.painter()
-                    .rect_filled(rect, 6.0, Color32::from_rgb(28, 28, 28));
+                    .rect_filled(rect, 6.0, theme::palette().panel_bg);
                 ui.painter().rect_stroke(
                     rect,
                     6.0,
-                    Stroke::new(1.0, Color32::from_gray(60)),
+                    Stroke::new(1.0, theme::palette().border),
                     egui::StrokeKind::Outside,
                 );
 
@@ -726,33 +3071,17 @@ impl InspectorWindow {
                 let icon_side = 16.0;
                 let icon_gap = 5.0;
                 let icon_block = (icon_side * 2.0) + icon_gap;
-                let drag_rect = Rect::from_min_max(
+                let title_rect = Rect::from_min_max(
                     header_rect.min,
                     egui::pos2(header_rect.max.x - icon_block - 4.0, header_rect.max.y),
                 );
 
-                let drag_resp = ui.interact(
-                    drag_rect,
-                    ui.id().with("header_drag"),
-                    egui::Sense::click_and_drag(),
-                );
-                if drag_resp.drag_started() {
-                    header_drag_started = true;
-                }
-                if drag_resp.drag_stopped() {
-                    header_drag_stopped = true;
-                }
-
                 ui.painter().text(
-                    drag_rect.center(),
+                    title_rect.center(),
                     Align2::CENTER_CENTER,
-                    match language {
-                        EngineLanguage::Pt => "Inspetor",
-                        EngineLanguage::En => "Inspector",
-                        EngineLanguage::Es => "Inspector",
-                    },
+                    tr(language, "inspector"),
                     FontId::new(13.0, FontFamily::Proportional),
-                    Color32::WHITE,
+                    theme::palette().text_strong,
                 );
 
                 let lock_tex = if self.is_locked {
@@ -776,7 +3105,7 @@ impl InspectorWindow {
                         ui.painter().rect_filled(
                             lock_rect.expand2(egui::vec2(2.0, 2.0)),
                             4.0,
-                            Color32::from_rgba_unmultiplied(255, 255, 255, 28),
+                            theme::palette().widget_hover,
                         );
                     }
                     if lock_resp.clicked() {
@@ -799,7 +3128,7 @@ impl InspectorWindow {
                         ui.painter().rect_filled(
                             menu_rect.expand2(egui::vec2(2.0, 2.0)),
                             4.0,
-                            Color32::from_rgba_unmultiplied(255, 255, 255, 28),
+                            theme::palette().widget_hover,
                         );
                     }
 
@@ -808,23 +3137,19 @@ impl InspectorWindow {
                         .width(220.0)
                         .frame(
                             egui::Frame::new()
-                                .fill(Color32::from_rgb(45, 45, 45))
+                                .fill(theme::palette().widget_bg)
                                 .corner_radius(8)
-                                .stroke(Stroke::new(1.0, Color32::from_gray(78))),
+                                .stroke(Stroke::new(1.0, theme::palette().border)),
                         )
                         .show(|ui| {
                             let copy_clicked = ui
                                 .add_sized(
                                     [208.0, 26.0],
                                     egui::Button::new(
-                                        egui::RichText::new(match language {
-                                            EngineLanguage::Pt => "Copiar cadeia de componentes",
-                                            EngineLanguage::En => "Copy component chain",
-                                            EngineLanguage::Es => "Copiar cadena de componentes",
-                                        })
-                                        .color(Color32::WHITE),
+                                        egui::RichText::new(tr(language, "copy_component_chain"))
+                                        .color(theme::palette().text_strong),
                                     )
-                                    .fill(Color32::from_rgb(62, 62, 62))
+                                    .fill(theme::palette().widget_hover)
                                     .corner_radius(6),
                                 )
                                 .clicked();
@@ -837,14 +3162,10 @@ impl InspectorWindow {
                                 .add_sized(
                                     [208.0, 26.0],
                                     egui::Button::new(
-                                        egui::RichText::new(match language {
-                                            EngineLanguage::Pt => "Enviar cadeia para...",
-                                            EngineLanguage::En => "Send chain to...",
-                                            EngineLanguage::Es => "Enviar cadena a...",
-                                        })
-                                        .color(Color32::WHITE),
+                                        egui::RichText::new(tr(language, "send_chain_to"))
+                                        .color(theme::palette().text_strong),
                                     )
-                                    .fill(Color32::from_rgb(62, 62, 62))
+                                    .fill(theme::palette().widget_hover)
                                     .corner_radius(6),
                                 )
                                 .clicked();
@@ -860,7 +3181,7 @@ impl InspectorWindow {
                         egui::pos2(inner.min.x, sep_y),
                         egui::pos2(inner.max.x, sep_y),
                     ],
-                    Stroke::new(1.0, Color32::from_gray(60)),
+                    Stroke::new(1.0, theme::palette().border),
                 );
 
                 let button_h = 32.0;
@@ -881,6 +3202,82 @@ impl InspectorWindow {
                         egui::ScrollArea::vertical()
                             .id_salt("inspector_scroll")
                             .show(ui, |ui| {
+                                if let Some(audio) = self.audio_asset.clone() {
+                                    let audio_path = &audio.path;
+                                    let playing = audio.playing;
+                                    egui::Frame::new()
+                                        .fill(theme::palette().panel_header)
+                                        .stroke(Stroke::new(1.0, theme::palette().widget_hover))
+                                        .corner_radius(6)
+                                        .inner_margin(egui::Margin::same(8))
+                                        .show(ui, |ui| {
+                                            ui.label(
+                                                egui::RichText::new("🔊 Audio Clip")
+                                                    .strong()
+                                                    .color(theme::palette().text_strong),
+                                            );
+                                            ui.add_space(4.0);
+                                            let file_name = audio_path
+                                                .file_name()
+                                                .map(|n| n.to_string_lossy().to_string())
+                                                .unwrap_or_default();
+                                            ui.label(
+                                                egui::RichText::new(file_name)
+                                                    .color(theme::palette().text),
+                                            );
+                                            if let Some(info) = audio.info.as_ref() {
+                                                let channels = match info.channels {
+                                                    1 => "mono".to_string(),
+                                                    2 => tr(language, "stereo").to_string(),
+                                                    n => tr(language, "channels_count")
+                                                        .replace("{count}", &n.to_string()),
+                                                };
+                                                ui.label(
+                                                    egui::RichText::new(format!(
+                                                        "{} · {} Hz · {}",
+                                                        info.duration_label(),
+                                                        info.sample_rate,
+                                                        channels
+                                                    ))
+                                                    .small()
+                                                    .color(theme::palette().text_weak),
+                                                );
+                                            }
+                                            ui.add_space(4.0);
+                                            let (wave_rect, _) = ui.allocate_exact_size(
+                                                egui::vec2(ui.available_width(), 48.0),
+                                                egui::Sense::hover(),
+                                            );
+                                            paint_waveform(ui.painter(), wave_rect, &audio);
+                                            if playing {
+                                                ui.ctx().request_repaint();
+                                            }
+                                            ui.add_space(4.0);
+                                            let label = tr(language, if playing { "stop_preview" } else { "preview" });
+                                            if ui.button(label).clicked() {
+                                                self.pending_audio_preview = Some(if playing {
+                                                    AudioPreviewRequest::Stop
+                                                } else {
+                                                    AudioPreviewRequest::Play(audio_path.clone())
+                                                });
+                                            }
+                                        });
+                                    ui.add_space(8.0);
+                                }
+                                self.draw_environment_preview(ui, language);
+                                self.draw_import_settings(ui, language);
+
+                                let selected_count = self.selected_objects.len();
+                                if selected_count > 1 {
+                                    let text = tr(language, "multi_selection").replace("{count}", &selected_count.to_string());
+                                    ui.label(
+                                        egui::RichText::new(text)
+                                            .size(12.0)
+                                            .color(theme::palette().text_weak),
+                                    );
+                                    ui.add_space(6.0);
+                                }
+
                                 if selected_object == "Directional Light" {
                                     let light_draft = self
                                         .object_light
@@ -896,21 +3293,17 @@ impl InspectorWindow {
                                 {
                                     // Interface de Iluminação
                                     egui::Frame::new()
-                                        .fill(Color32::from_rgb(33, 33, 33))
-                                        .stroke(Stroke::new(1.0, Color32::from_gray(60)))
+                                        .fill(theme::palette().panel_header)
+                                        .stroke(Stroke::new(1.0, theme::palette().border))
                                         .corner_radius(6)
                                         .inner_margin(egui::Margin::same(10))
                                         .show(ui, |ui| {
                                             ui.horizontal(|ui| {
                                                 ui.label(
-                                                    egui::RichText::new(match language {
-                                                        EngineLanguage::Pt => "Iluminação Global",
-                                                        EngineLanguage::En => "Global Lighting",
-                                                        EngineLanguage::Es => "Iluminación Global",
-                                                    })
+                                                    egui::RichText::new(tr(language, "global_lighting"))
                                                     .strong()
                                                     .size(14.0)
-                                                    .color(Color32::WHITE),
+                                                    .color(theme::palette().text_strong),
                                                 );
                                                 ui.with_layout(
                                                     egui::Layout::right_to_left(
@@ -927,33 +3320,21 @@ impl InspectorWindow {
                                                 .num_columns(2)
                                                 .spacing([10.0, 10.0])
                                                 .show(ui, |ui| {
-                                                    ui.label(match language {
-                                                        EngineLanguage::Pt => "Yaw:",
-                                                        EngineLanguage::En => "Yaw:",
-                                                        EngineLanguage::Es => "Yaw:",
-                                                    });
+                                                    ui.label(tr(language, "yaw"));
                                                     ui.add(
                                                         egui::Slider::new(light_yaw, 0.0..=6.28)
                                                             .show_value(false),
                                                     );
                                                     ui.end_row();
 
-                                                    ui.label(match language {
-                                                        EngineLanguage::Pt => "Pitch:",
-                                                        EngineLanguage::En => "Pitch:",
-                                                        EngineLanguage::Es => "Pitch:",
-                                                    });
+                                                    ui.label(tr(language, "pitch"));
                                                     ui.add(
                                                         egui::Slider::new(light_pitch, 0.0..=1.57)
                                                             .show_value(false),
                                                     );
                                                     ui.end_row();
 
-                                                    ui.label(match language {
-                                                        EngineLanguage::Pt => "Intensidade:",
-                                                        EngineLanguage::En => "Intensity:",
-                                                        EngineLanguage::Es => "Intensidad:",
-                                                    });
+                                                    ui.label(tr(language, "intensity"));
                                                     ui.add(
                                                         egui::Slider::new(
                                                             light_intensity,
@@ -963,11 +3344,7 @@ impl InspectorWindow {
                                                     );
                                                     ui.end_row();
 
-                                                    ui.label(match language {
-                                                        EngineLanguage::Pt => "Cor:",
-                                                        EngineLanguage::En => "Color:",
-                                                        EngineLanguage::Es => "Color:",
-                                                    });
+                                                    ui.label(tr(language, "color"));
                                                     ui.color_edit_button_rgb(light_color);
                                                     if selected_object == "Directional Light" {
                                                         if let Some(light_draft) = self
@@ -994,25 +3371,13 @@ impl InspectorWindow {
                                         .entry(selected_object.to_string())
                                         .or_insert(true);
 
-                                    let title = match language {
-                                        EngineLanguage::Pt => "Transformação",
-                                        EngineLanguage::En => "Transform",
-                                        EngineLanguage::Es => "Transformación",
-                                    };
-                                    let apply_text = match language {
-                                        EngineLanguage::Pt => "Aplicar Transformações",
-                                        EngineLanguage::En => "Apply Transformations",
-                                        EngineLanguage::Es => "Aplicar Transformaciones",
-                                    };
-                                    let loading_text = match language {
-                                        EngineLanguage::Pt => "Aplicando...",
-                                        EngineLanguage::En => "Applying...",
-                                        EngineLanguage::Es => "Aplicando...",
-                                    };
+                                    let title = tr(language, "transform");
+                                    let apply_text = tr(language, "apply_transformations");
+                                    let loading_text = tr(language, "applying");
 
                                     egui::Frame::new()
-                                        .fill(Color32::from_rgb(36, 36, 36))
-                                        .stroke(Stroke::new(1.0, Color32::from_gray(62)))
+                                        .fill(theme::palette().panel_header)
+                                        .stroke(Stroke::new(1.0, theme::palette().widget_hover))
                                         .corner_radius(6)
                                         .inner_margin(egui::Margin::same(8))
                                         .show(ui, |ui| {
@@ -1023,7 +3388,7 @@ impl InspectorWindow {
                                                 ui.label(
                                                     egui::RichText::new(title)
                                                         .strong()
-                                                        .color(Color32::WHITE),
+                                                        .color(theme::palette().text_strong),
                                                 );
                                                 ui.with_layout(
                                                     egui::Layout::right_to_left(
@@ -1049,105 +3414,16 @@ impl InspectorWindow {
                                                         ui.cursor().min.y,
                                                     ),
                                                 ],
-                                                Stroke::new(1.0, Color32::from_gray(50)),
+                                                Stroke::new(1.0, theme::palette().border),
                                             );
-                                            ui.add_space(8.0);
-
-                                            let mut transform_changed = false;
-                                            let mut numeric_dragging = false;
-
-                                            let axis_labels = ["X", "Y", "Z"];
-                                            egui::Grid::new("transform_grid")
-                                                .num_columns(2)
-                                                .spacing([12.0, 8.0])
-                                                .show(ui, |ui| {
-                                                    // Posição
-                                                    ui.label(match language {
-                                                        EngineLanguage::Pt => "Posição",
-                                                        EngineLanguage::En => "Position",
-                                                        EngineLanguage::Es => "Posición",
-                                                    });
-                                                    ui.horizontal(|ui| {
-                                                        for i in 0..3 {
-                                                            ui.label(
-                                                                egui::RichText::new(axis_labels[i])
-                                                                    .size(9.0)
-                                                                    .color(Color32::GRAY),
-                                                            );
-                                                            let resp = ui.add(
-                                                                egui::DragValue::new(
-                                                                    &mut draft.position[i],
-                                                                )
-                                                                .speed(0.1),
-                                                            );
-                                                            if resp.changed() {
-                                                                transform_changed = true;
-                                                            }
-                                                            if resp.dragged() {
-                                                                numeric_dragging = true;
-                                                            }
-                                                        }
-                                                    });
-                                                    ui.end_row();
-
-                                                    // Rotação
-                                                    ui.label(match language {
-                                                        EngineLanguage::Pt => "Rotação",
-                                                        EngineLanguage::En => "Rotation",
-                                                        EngineLanguage::Es => "Rotación",
-                                                    });
-                                                    ui.horizontal(|ui| {
-                                                        for i in 0..3 {
-                                                            ui.label(
-                                                                egui::RichText::new(axis_labels[i])
-                                                                    .size(9.0)
-                                                                    .color(Color32::GRAY),
-                                                            );
-                                                            let resp = ui.add(
-                                                                egui::DragValue::new(
-                                                                    &mut draft.rotation[i],
-                                                                )
-                                                                .speed(0.1),
-                                                            );
-                                                            if resp.changed() {
-                                                                transform_changed = true;
-                                                            }
-                                                            if resp.dragged() {
-                                                                numeric_dragging = true;
-                                                            }
-                                                        }
-                                                    });
-                                                    ui.end_row();
+                                            ui.add_space(8.0);
 
-                                                    // Escala
-                                                    ui.label(match language {
-                                                        EngineLanguage::Pt => "Escala",
-                                                        EngineLanguage::En => "Scale",
-                                                        EngineLanguage::Es => "Escala",
-                                                    });
-                                                    ui.horizontal(|ui| {
-                                                        for i in 0..3 {
-                                                            ui.label(
-                                                                egui::RichText::new(axis_labels[i])
-                                                                    .size(9.0)
-                                                                    .color(Color32::GRAY),
-                                                            );
-                                                            let resp = ui.add(
-                                                                egui::DragValue::new(
-                                                                    &mut draft.scale[i],
-                                                                )
-                                                                .speed(0.05),
-                                                            );
-                                                            if resp.changed() {
-                                                                transform_changed = true;
-                                                            }
-                                                            if resp.dragged() {
-                                                                numeric_dragging = true;
-                                                            }
-                                                        }
-                                                    });
-                                                    ui.end_row();
-                                                });
+                                            let transform_changed = reflect::draw_fields(
+                                                ui,
+                                                "transform_grid",
+                                                draft,
+                                                &mut ReflectContext::new(language),
+                                            );
 
                                             ui.add_space(10.0);
                                             let is_loading = self
@@ -1203,21 +3479,17 @@ impl InspectorWindow {
 
                                         let shader_frame_width = ui.available_width();
                                         egui::Frame::new()
-                                            .fill(Color32::from_rgb(33, 33, 33))
-                                            .stroke(Stroke::new(1.0, Color32::from_gray(60)))
+                                            .fill(theme::palette().panel_header)
+                                            .stroke(Stroke::new(1.0, theme::palette().border))
                                             .corner_radius(6)
                                             .inner_margin(egui::Margin::same(10))
                                             .show(ui, |ui| {
                                                 ui.set_width(shader_frame_width);
                                                 ui.label(
-                                                    egui::RichText::new(match language {
-                                                        EngineLanguage::Pt => "Shader",
-                                                        EngineLanguage::En => "Shader",
-                                                        EngineLanguage::Es => "Shader",
-                                                    })
+                                                    egui::RichText::new(tr(language, "shader"))
                                                     .strong()
                                                     .size(13.0)
-                                                    .color(Color32::WHITE),
+                                                    .color(theme::palette().text_strong),
                                                 );
                                                 ui.add_space(6.0);
                                                 let mut current_shader = self.object_shader.get(selected_object).cloned().unwrap_or_default();
@@ -1237,7 +3509,7 @@ impl InspectorWindow {
                                                         ui.painter().rect_filled(
                                                             preview_rect,
                                                             8.0,
-                                                            Color32::from_rgb(22, 22, 22),
+                                                            theme::palette().panel_bg,
                                                         );
                                                         ui.painter().image(
                                                             texture.id(),
@@ -1252,53 +3524,35 @@ impl InspectorWindow {
                                                         ui.painter().rect_stroke(
                                                             preview_rect,
                                                             8.0,
-                                                            Stroke::new(1.0, Color32::from_gray(90)),
+                                                            Stroke::new(1.0, theme::palette().border),
                                                             egui::StrokeKind::Outside,
                                                         );
                                                         ui.painter().text(
                                                             preview_rect.center(),
                                                             Align2::CENTER_CENTER,
-                                                            match language {
-                                                                EngineLanguage::Pt => "Sem textura\narraste um shader",
-                                                                EngineLanguage::En => "No texture\nDrag a shader",
-                                                                EngineLanguage::Es => "Sin textura\nArrastra un shader",
-                                                            },
+                                                            tr(language, "no_texture_drag_shader"),
                                                             FontId::new(10.0, FontFamily::Proportional),
-                                                            Color32::from_gray(170),
+                                                            theme::palette().text_weak,
                                                         );
                                                     }
                                                     ui.add_space(8.0);
                                                     ui.vertical(|ui| {
                                                         ui.label(
-                                                            egui::RichText::new(match language {
-                                                                EngineLanguage::Pt => "Painel Shader",
-                                                                EngineLanguage::En => "Shader Panel",
-                                                                EngineLanguage::Es => "Panel de Shader",
-                                                            })
+                                                            egui::RichText::new(tr(language, "shader_panel"))
                                                             .strong()
                                                             .size(12.0),
                                                         );
                                                         ui.label(
-                                                            egui::RichText::new(match language {
-                                                                EngineLanguage::Pt => {
-                                                                    "Arraste um .mat ou selecione da árvore para manter texturas."
-                                                                }
-                                                                EngineLanguage::En => {
-                                                                    "Drop a .mat or pick from the tree to keep textures."
-                                                                }
-                                                                EngineLanguage::Es => {
-                                                                    "Suelta un .mat o elige en el árbol para conservar texturas."
-                                                                }
-                                                            })
+                                                            egui::RichText::new(tr(language, "drop_mat_hint"))
                                                             .size(10.0)
-                                                            .color(Color32::from_gray(200)),
+                                                            .color(theme::palette().text),
                                                         );
                                                         ui.add_space(2.0);
                                                         ui.horizontal(|ui| {
                                                             let inline_color = if !current_shader.is_empty() {
-                                                                Color32::from_rgb(255, 165, 0)
+                                                                theme::palette().warning
                                                             } else {
-                                                                Color32::from_rgb(120, 120, 120)
+                                                                theme::palette().text_weak
                                                             };
                                                             let (_, circle_resp) = ui.allocate_exact_size(
                                                                 egui::vec2(12.0, 12.0),
@@ -1313,17 +3567,13 @@ impl InspectorWindow {
                                                                 ui.painter().circle_stroke(
                                                                     circle_resp.rect.center(),
                                                                     6.0,
-                                                                    Stroke::new(1.0, Color32::from_gray(160)),
+                                                                    Stroke::new(1.0, theme::palette().border),
                                                                 );
                                                             }
                                                             ui.label(
-                                                                egui::RichText::new(match language {
-                                                                    EngineLanguage::Pt => "Shader ativo",
-                                                                    EngineLanguage::En => "Active Shader",
-                                                                    EngineLanguage::Es => "Shader activo",
-                                                                })
+                                                                egui::RichText::new(tr(language, "active_shader"))
                                                                 .size(10.0)
-                                                                .color(Color32::WHITE),
+                                                                .color(theme::palette().text_strong),
                                                             );
                                                         });
                                                     });
@@ -1334,11 +3584,7 @@ impl InspectorWindow {
                                                 ui.vertical(|ui| {
                                                     // Shader Field
                                                     ui.label(
-                                                        egui::RichText::new(match language {
-                                                            EngineLanguage::Pt => "Shader",
-                                                            EngineLanguage::En => "Shader",
-                                                            EngineLanguage::Es => "Shader",
-                                                        })
+                                                        egui::RichText::new(tr(language, "shader"))
                                                         .size(11.0)
                                                     );
 
@@ -1356,8 +3602,8 @@ impl InspectorWindow {
                                                     }
 
                                                     let shader_frame = egui::Frame::new()
-                                                        .fill(Color32::from_rgb(35, 35, 35))
-                                                        .stroke(Stroke::new(1.0, Color32::from_rgb(58, 58, 58)))
+                                                        .fill(theme::palette().widget_bg)
+                                                        .stroke(Stroke::new(1.0, theme::palette().border))
                                                         .corner_radius(6)
                                                         .inner_margin(egui::Margin::symmetric(8, 6));
 
@@ -1365,9 +3611,9 @@ impl InspectorWindow {
                                                         ui.horizontal(|ui| {
                                                             // Material indicator circle
                                                             let circle_color = if !current_shader.is_empty() {
-                                                                Color32::from_rgb(255, 165, 0) // Laranja
+                                                                theme::palette().warning // Laranja
                                                             } else {
-                                                                Color32::from_rgb(100, 100, 100)
+                                                                theme::palette().text_weak
                                                             };
                                                             let (rect, _resp) = ui.allocate_exact_size(
                                                                 egui::vec2(14.0, 14.0),
@@ -1377,6 +3623,16 @@ impl InspectorWindow {
 
                                                             ui.text_edit_singleline(&mut current_shader);
 
+                                                            if let Some(path) = asset_picker_button(
+                                                                ui,
+                                                                "material",
+                                                                &self.search_index,
+                                                                &mut self.asset_picker_query,
+                                                                AssetType::Material,
+                                                            ) {
+                                                                current_shader = path;
+                                                            }
+
                                                             // Clear button
                                                             if ui.small_button("✕").clicked() {
                                                                 current_shader.clear();
@@ -1384,24 +3640,11 @@ impl InspectorWindow {
                                                         });
                                                     });
 
-                                                    // Check for internal drag from Project window
-                                                    let pointer_pos = ui.ctx().input(|i| i.pointer.hover_pos());
-                                                    let is_dragging = ui.ctx().input(|i| i.pointer.primary_down() && i.pointer.delta().length_sq() > 0.0);
-
-                                                    if is_dragging && pointer_pos.is_some() && shader_resp.response.rect.contains(pointer_pos.unwrap()) {
-                                                        // Check if dragging from project window
-                                                        if let Some(dragging_asset) = ui.data(|d| d.get_temp::<String>(egui::Id::new("project_dragging_asset"))) {
-                                                            let ext = std::path::Path::new(&dragging_asset)
-                                                                .extension()
-                                                                .and_then(|e| e.to_str())
-                                                                .unwrap_or("")
-                                                                .to_lowercase();
-                                                            if matches!(ext.as_str(), "mat" | "material") {
-                                                                current_shader = dragging_asset.clone();
-                                                                eprintln!("[SHADER] Drop interno do projeto: {}", dragging_asset);
-                                                            }
-                                                        }
-                                                    }
+                                                    // Drop interno do Projeto: aplicado ao soltar, por on_material_dropped
+                                                    self.material_slot = Some((
+                                                        selected_object.to_string(),
+                                                        shader_resp.response.rect,
+                                                    ));
 
                                                     // Drag-drop highlight for material (external files)
                                                     let is_hovering_file = ui.ctx().input(|i| !i.raw.hovered_files.is_empty());
@@ -1411,7 +3654,7 @@ impl InspectorWindow {
                                                                 ui.painter().rect_stroke(
                                                                     shader_resp.response.rect.expand(2.0),
                                                                     4.0,
-                                                                    Stroke::new(2.0, Color32::from_rgb(255, 165, 0)),
+                                                                    Stroke::new(2.0, theme::palette().warning),
                                                                     egui::StrokeKind::Outside,
                                                                 );
                                                             }
@@ -1432,7 +3675,7 @@ impl InspectorWindow {
                                                                 ui.painter().rect_stroke(
                                                                     shader_resp.response.rect.expand(2.0),
                                                                     4.0,
-                                                                    Stroke::new(2.0, Color32::from_rgb(255, 165, 0)),
+                                                                    Stroke::new(2.0, theme::palette().warning),
                                                                     egui::StrokeKind::Outside,
                                                                 );
                                                             }
@@ -1443,7 +3686,7 @@ impl InspectorWindow {
                                                     self.object_shader.insert(selected_object.to_string(), current_shader.clone());
 
                                                     if prev_shader != current_shader {
-                                                        eprintln!("[SHADER] Inspector: objeto={}, shader={:?}", selected_object, current_shader);
+                                                        log::debug!("[SHADER] Inspector: objeto={}, shader={:?}", selected_object, current_shader);
                                                         self.pending_shader_request = Some((
                                                             selected_object.to_string(),
                                                             if current_shader.trim().is_empty() { None } else { Some(current_shader.clone()) },
@@ -1453,7 +3696,7 @@ impl InspectorWindow {
                                                         if let Some(shader_props) = Self::parse_shader_properties(&current_shader) {
                                                             if let Some(auto_tex) = shader_props.texture_path {
                                                                 if !auto_tex.trim().is_empty() && auto_tex != current_tex {
-                                                                    eprintln!("[SHADER] Aplicando textura do shader: {}", auto_tex);
+                                                                    log::debug!("[SHADER] Aplicando textura do shader: {}", auto_tex);
                                                                     current_tex = auto_tex;
                                                                     resp_changed = true;
                                                                 }
@@ -1465,41 +3708,92 @@ impl InspectorWindow {
 
                                                     // Shader Properties (when shader is assigned)
                                                     if !current_shader.is_empty() {
+                                                        self.refresh_material_shaders(&current_shader);
                                                         if let Some(shader_props) = Self::parse_shader_properties(&current_shader) {
-                                                            egui::CollapsingHeader::new(match language {
-                                                                EngineLanguage::Pt => "Propriedades do Shader",
-                                                                EngineLanguage::En => "Shader Properties",
-                                                                EngineLanguage::Es => "Propiedades del Shader",
-                                                            })
+                                                            egui::CollapsingHeader::new(tr(language, "shader_properties"))
                                                             .default_open(false)
                                                             .show(ui, |ui| {
-                                                                ui.label(match language {
-                                                                    EngineLanguage::Pt => "Tipo de Shader:",
-                                                                    EngineLanguage::En => "Shader Type:",
-                                                                    EngineLanguage::Es => "Tipo de Shader:",
-                                                                });
-                                                                let mut shader_type = shader_props.shader_type;
+                                                                ui.label(tr(language, "shader_type"));
+                                                                let custom_shader = shader_props.custom_shader.as_deref();
+                                                                let mut selected: Option<String> = None;
                                                                 egui::ComboBox::from_id_salt(ui.id().with("shader_type"))
-                                                                    .selected_text(shader_type.display_name())
+                                                                    .selected_text(
+                                                                        custom_shader.unwrap_or(shader_props.shader_type.display_name()),
+                                                                    )
                                                                     .show_ui(ui, |ui| {
                                                                         for variant in ShaderType::ALL {
                                                                             if ui
                                                                                 .selectable_label(
-                                                                                    shader_type == variant,
+                                                                                    custom_shader.is_none()
+                                                                                        && shader_props.shader_type == variant,
                                                                                     variant.display_name(),
                                                                                 )
                                                                                 .clicked()
                                                                             {
-                                                                                shader_type = variant;
+                                                                                selected = Some(variant.as_str().to_string());
+                                                                            }
+                                                                        }
+                                                                        let handles = self.material_shaders.material_shader_handles();
+                                                                        if !handles.is_empty() {
+                                                                            ui.separator();
+                                                                        }
+                                                                        for handle in handles {
+                                                                            let Some(shader) = self.material_shaders.get_material_shader(handle) else {
+                                                                                continue;
+                                                                            };
+                                                                            if ui
+                                                                                .selectable_label(
+                                                                                    custom_shader.is_some_and(|name| {
+                                                                                        name.eq_ignore_ascii_case(&shader.name)
+                                                                                    }),
+                                                                                    &shader.name,
+                                                                                )
+                                                                                .clicked()
+                                                                            {
+                                                                                selected = Some(shader.name.clone());
                                                                             }
                                                                         }
                                                                     });
-                                                                if shader_type != shader_props.shader_type {
-                                                                    Self::update_shader_property(
-                                                                        &current_shader,
-                                                                        "shader",
-                                                                        shader_type.as_str(),
-                                                                    );
+                                                                if let Some(selected) = selected {
+                                                                    Self::update_shader_property(&current_shader, "shader", &selected);
+                                                                }
+
+                                                                // Shader do projeto: só os parâmetros refletidos do WGSL
+                                                                if let Some(name) = custom_shader {
+                                                                    let shader = self
+                                                                        .material_shaders
+                                                                        .find_material_shader(name)
+                                                                        .and_then(|handle| self.material_shaders.get_material_shader(handle));
+                                                                    match shader {
+                                                                        Some(shader) if shader.params.is_empty() => {
+                                                                            ui.weak(tr(language, "shader_has_no_parameters"));
+                                                                        }
+                                                                        Some(shader) => {
+                                                                            ui.add_space(4.0);
+                                                                            for param in &shader.params {
+                                                                                draw_shader_param(
+                                                                                    ui,
+                                                                                    &current_shader,
+                                                                                    param,
+                                                                                    shader_props.params.get(&param.name),
+                                                                                );
+                                                                            }
+                                                                        }
+                                                                        None => {
+                                                                            ui.colored_label(
+                                                                                theme::palette().warning,
+                                                                                tr(language, "shader_not_found_in_assets"),
+                                                                            );
+                                                                        }
+                                                                    }
+                                                                    for (path, error) in &self.material_shader_errors {
+                                                                        let file = path.file_name().unwrap_or_default().to_string_lossy();
+                                                                        ui.colored_label(
+                                                                            theme::palette().error,
+                                                                            format!("{file}: {error}"),
+                                                                        );
+                                                                    }
+                                                                    return;
                                                                 }
 
                                                                 ui.add_space(4.0);
@@ -1605,17 +3899,13 @@ impl InspectorWindow {
 
                                                     // Texture Field
                                                     ui.label(
-                                                        egui::RichText::new(match language {
-                                                            EngineLanguage::Pt => "Textura",
-                                                            EngineLanguage::En => "Texture",
-                                                            EngineLanguage::Es => "Textura",
-                                                        })
+                                                        egui::RichText::new(tr(language, "texture"))
                                                         .size(11.0)
                                                     );
 
                                                     let tex_frame = egui::Frame::new()
-                                                        .fill(Color32::from_rgb(35, 35, 35))
-                                                        .stroke(Stroke::new(1.0, Color32::from_rgb(58, 58, 58)))
+                                                        .fill(theme::palette().widget_bg)
+                                                        .stroke(Stroke::new(1.0, theme::palette().border))
                                                         .corner_radius(6)
                                                         .inner_margin(egui::Margin::symmetric(8, 6));
 
@@ -1629,9 +3919,9 @@ impl InspectorWindow {
                                                         ui.horizontal(|ui| {
                                                             // Texture indicator circle
                                                             let circle_color = if !current_tex.is_empty() {
-                                                                Color32::from_rgb(15, 232, 121) // Verde
+                                                                theme::palette().accent // Verde
                                                             } else {
-                                                                Color32::from_rgb(100, 100, 100)
+                                                                theme::palette().text_weak
                                                             };
                                                             let (rect, _resp) = ui.allocate_exact_size(
                                                                 egui::vec2(14.0, 14.0),
@@ -1640,7 +3930,7 @@ impl InspectorWindow {
 
                                                             // Draw circle with highlight on hover
                                                             if is_hovering_file {
-                                                                ui.painter().circle_filled(rect.center(), 7.5, Color32::from_rgb(180, 180, 180));
+                                                                ui.painter().circle_filled(rect.center(), 7.5, theme::palette().text);
                                                                 ui.painter().circle_stroke(rect.center(), 8.0, Stroke::new(2.0, circle_color));
                                                             } else {
                                                                 ui.painter().circle_filled(rect.center(), 6.0, circle_color);
@@ -1655,7 +3945,7 @@ impl InspectorWindow {
                                                                         ui.painter().rect_stroke(
                                                                             resp.rect.expand(2.0),
                                                                             4.0,
-                                                                            Stroke::new(2.0, Color32::from_rgb(15, 232, 121)),
+                                                                            Stroke::new(2.0, theme::palette().accent),
                                                                             egui::StrokeKind::Outside,
                                                                         );
                                                                     }
@@ -1698,7 +3988,7 @@ impl InspectorWindow {
                                                                     if matches!(ext.as_str(), "png" | "jpg" | "jpeg" | "tga" | "bmp") {
                                                                         current_tex = dragging_asset.clone();
                                                                         resp_changed = true;
-                                                                        eprintln!("[TEXTURA] Drop interno do projeto: {}", dragging_asset);
+                                                                        log::debug!("[TEXTURA] Drop interno do projeto: {}", dragging_asset);
                                                                     }
                                                                 }
                                                             }
@@ -1726,13 +4016,17 @@ impl InspectorWindow {
                                                                 ui.painter().rect_stroke(
                                                                     tex_resp.response.rect.expand(2.0),
                                                                     4.0,
-                                                                    Stroke::new(2.0, Color32::from_rgb(15, 232, 121)),
+                                                                    Stroke::new(2.0, theme::palette().accent),
                                                                     egui::StrokeKind::Outside,
                                                                 );
                                                             }
                                                         }
                                                     }
 
+                                                    if !current_tex.trim().is_empty() {
+                                                        self.draw_texture_sampling(ui, language, &current_tex);
+                                                    }
+
                                                     if resp_changed {
                                                         let val = if current_tex.trim().is_empty() {
                                                             None
@@ -1761,16 +4055,12 @@ impl InspectorWindow {
                                                 self.add_icon_texture.as_ref().unwrap(),
                                             )
                                             .fit_to_exact_size(egui::vec2(10.0, 10.0)),
-                                            egui::RichText::new(match language {
-                                                EngineLanguage::Pt => "Add Comp",
-                                                EngineLanguage::En => "Add Comp",
-                                                EngineLanguage::Es => "Add Comp",
-                                            })
+                                            egui::RichText::new(tr(language, "add_comp"))
                                             .strong()
                                             .size(11.0)
-                                            .color(Color32::from_rgb(55, 55, 55)),
+                                            .color(theme::palette().panel_bg),
                                         )
-                                        .fill(Color32::from_rgb(0x0F, 0xE8, 0x79))
+                                        .fill(theme::palette().accent)
                                         .corner_radius(6);
 
                                         let add_resp = ui.add_sized([140.0, 26.0], add_btn);
@@ -1851,6 +4141,12 @@ impl InspectorWindow {
                                                     .or_default();
                                                 ui.close();
                                             }
+                                            if ui.button("Collider").clicked() {
+                                                self.object_collider
+                                                    .entry(selected_object.to_string())
+                                                    .or_default();
+                                                ui.close();
+                                            }
                                         });
 
                                         ui.menu_button("🎬 Animação", |ui: &mut egui::Ui| {
@@ -1870,6 +4166,81 @@ impl InspectorWindow {
                                                 ui.close();
                                             }
                                         });
+
+                                        ui.menu_button("📜 Script", |ui: &mut egui::Ui| {
+                                            if ui.button("Lua Script").clicked() {
+                                                self.object_script
+                                                    .entry(selected_object.to_string())
+                                                    .or_default();
+                                                ui.close();
+                                            }
+                                        });
+
+                                        ui.menu_button("🧊 Renderização", |ui: &mut egui::Ui| {
+                                            if ui.button("LOD Group").clicked() {
+                                                self.object_lod
+                                                    .entry(selected_object.to_string())
+                                                    .or_default();
+                                                ui.close();
+                                            }
+                                            if ui.button("Particle System").clicked() {
+                                                self.object_particles
+                                                    .entry(selected_object.to_string())
+                                                    .or_default();
+                                                ui.close();
+                                            }
+                                            if ui.button("Terrain").clicked() {
+                                                self.object_terrain
+                                                    .entry(selected_object.to_string())
+                                                    .or_default();
+                                                ui.close();
+                                            }
+                                            if ui.button("Water").clicked() {
+                                                self.object_water
+                                                    .entry(selected_object.to_string())
+                                                    .or_default();
+                                                ui.close();
+                                            }
+                                            if ui.button("Sprite").clicked() {
+                                                self.object_sprite
+                                                    .entry(selected_object.to_string())
+                                                    .or_default();
+                                                ui.close();
+                                            }
+                                            if ui.button("Text 3D").clicked() {
+                                                self.object_text3d
+                                                    .entry(selected_object.to_string())
+                                                    .or_default();
+                                                ui.close();
+                                            }
+                                            if ui.button("UI Text").clicked() {
+                                                self.object_ui_text
+                                                    .entry(selected_object.to_string())
+                                                    .or_default();
+                                                ui.close();
+                                            }
+                                        });
+
+                                        ui.menu_button("🎥 Câmera", |ui: &mut egui::Ui| {
+                                            if ui.button("Camera").clicked() {
+                                                self.object_camera
+                                                    .entry(selected_object.to_string())
+                                                    .or_default();
+                                                ui.close();
+                                            }
+                                            if ui.button("Camera Follow").clicked() {
+                                                self.object_camera_follow
+                                                    .entry(selected_object.to_string())
+                                                    .or_default();
+                                                ui.close();
+                                            }
+                                            if ui.button("Post-processing").clicked() {
+                                                self.object_post_process
+                                                    .entry(selected_object.to_string())
+                                                    .or_default();
+                                                ui.close();
+                                            }
+                                        });
                                         });
                                     });
 
@@ -1881,16 +4252,189 @@ impl InspectorWindow {
                                         self.object_fios_controller.get_mut(selected_object)
                                     {
                                         egui::Frame::new()
-                                            .fill(Color32::from_rgb(36, 36, 36))
-                                            .stroke(Stroke::new(1.0, Color32::from_gray(62)))
+                                            .fill(theme::palette().panel_header)
+                                            .stroke(Stroke::new(1.0, theme::palette().widget_hover))
+                                            .corner_radius(6)
+                                            .inner_margin(egui::Margin::same(8))
+                                            .show(ui, |ui| {
+                                                ui.horizontal(|ui| {
+                                                    ui.label(
+                                                        egui::RichText::new("Fios Controller")
+                                                            .strong()
+                                                            .color(theme::palette().text_strong),
+                                                    );
+                                                    ui.with_layout(
+                                                        egui::Layout::right_to_left(
+                                                            egui::Align::Center,
+                                                        ),
+                                                        |ui| {
+                                                            if ui.button("×").clicked() {
+                                                                remove_fios = true;
+                                                            }
+                                                        },
+                                                    );
+                                                });
+                                                ui.add_space(4.0);
+                                                egui::Grid::new("fios_grid")
+                                                    .num_columns(2)
+                                                    .spacing([10.0, 8.0])
+                                                    .show(ui, |ui| {
+                                                        ui.label("Modulo:");
+                                                        egui::ComboBox::from_id_salt(
+                                                            "fios_module_combo",
+                                                        )
+                                                        .selected_text(&ctrl.module_ref)
+                                                        .show_ui(ui, |ui| {
+                                                            for m in animation_modules {
+                                                                ui.selectable_value(
+                                                                    &mut ctrl.module_ref,
+                                                                    m.clone(),
+                                                                    m,
+                                                                );
+                                                            }
+                                                        });
+                                                        ui.end_row();
+
+                                                        ui.label("Clip:");
+                                                        egui::ComboBox::from_id_salt(
+                                                            "fios_clip_combo",
+                                                        )
+                                                        .selected_text(&ctrl.primary_clip)
+                                                        .show_ui(ui, |ui| {
+                                                            for c in fbx_animation_clips {
+                                                                ui.selectable_value(
+                                                                    &mut ctrl.primary_clip,
+                                                                    c.clone(),
+                                                                    c,
+                                                                );
+                                                            }
+                                                        });
+                                                        ui.end_row();
+
+                                                        ui.label("Velocidade:");
+                                                        ui.add(
+                                                            egui::DragValue::new(
+                                                                &mut ctrl.move_speed,
+                                                            )
+                                                            .speed(0.1),
+                                                        );
+                                                        ui.end_row();
+                                                    });
+                                            });
+                                        ui.add_space(8.0);
+                                    }
+                                    if remove_fios {
+                                        self.object_fios_controller.remove(selected_object);
+                                    }
+
+                                    for component in [
+                                        TypeId::of::<RigidbodyDraft>(),
+                                        TypeId::of::<ColliderDraft>(),
+                                        TypeId::of::<WaterDraft>(),
+                                        TypeId::of::<SpriteDraft>(),
+                                        TypeId::of::<Text3DDraft>(),
+                                        TypeId::of::<UiTextDraft>(),
+                                    ] {
+                                        self.draw_component(
+                                            ui,
+                                            component,
+                                            selected_object,
+                                            language,
+                                        );
+                                    }
+
+                                    let mut remove_anim = false;
+                                    if let Some(anim) =
+                                        self.object_animator.get_mut(selected_object)
+                                    {
+                                        egui::Frame::new()
+                                            .fill(theme::palette().panel_header)
+                                            .stroke(Stroke::new(1.0, theme::palette().widget_hover))
+                                            .corner_radius(6)
+                                            .inner_margin(egui::Margin::same(8))
+                                            .show(ui, |ui| {
+                                                ui.horizontal(|ui| {
+                                                    ui.label(
+                                                        egui::RichText::new("Animator")
+                                                            .strong()
+                                                            .color(theme::palette().text_strong),
+                                                    );
+                                                    ui.with_layout(
+                                                        egui::Layout::right_to_left(
+                                                            egui::Align::Center,
+                                                        ),
+                                                        |ui| {
+                                                            if ui.button("×").clicked() {
+                                                                remove_anim = true;
+                                                            }
+                                                        },
+                                                    );
+                                                });
+                                                ui.add_space(4.0);
+                                                egui::Grid::new("anim_grid")
+                                                    .num_columns(2)
+                                                    .spacing([10.0, 8.0])
+                                                    .show(ui, |ui| {
+                                                        ui.label("Controller:");
+                                                        egui::ComboBox::from_id_salt(
+                                                            "anim_ctrl_combo",
+                                                        )
+                                                        .selected_text(&anim.controller_ref)
+                                                        .show_ui(ui, |ui| {
+                                                            for c in animation_controllers {
+                                                                ui.selectable_value(
+                                                                    &mut anim.controller_ref,
+                                                                    c.clone(),
+                                                                    c,
+                                                                );
+                                                            }
+                                                        });
+                                                        ui.end_row();
+
+                                                        ui.label("Clip:");
+                                                        egui::ComboBox::from_id_salt(
+                                                            "anim_clip_combo",
+                                                        )
+                                                        .selected_text(&anim.clip_ref)
+                                                        .show_ui(ui, |ui| {
+                                                            for c in fbx_animation_clips {
+                                                                ui.selectable_value(
+                                                                    &mut anim.clip_ref,
+                                                                    c.clone(),
+                                                                    c,
+                                                                );
+                                                            }
+                                                        });
+                                                        ui.end_row();
+                                                    });
+                                            });
+                                        ui.add_space(8.0);
+                                    }
+                                    if remove_anim {
+                                        self.object_animator.remove(selected_object);
+                                    }
+
+                                    self.draw_component(
+                                        ui,
+                                        TypeId::of::<LightDraft>(),
+                                        selected_object,
+                                        language,
+                                    );
+
+                                    let mut remove_script = false;
+                                    if let Some(script) = self.object_script.get_mut(selected_object)
+                                    {
+                                        egui::Frame::new()
+                                            .fill(theme::palette().panel_header)
+                                            .stroke(Stroke::new(1.0, theme::palette().widget_hover))
                                             .corner_radius(6)
                                             .inner_margin(egui::Margin::same(8))
                                             .show(ui, |ui| {
                                                 ui.horizontal(|ui| {
                                                     ui.label(
-                                                        egui::RichText::new("Fios Controller")
+                                                        egui::RichText::new("📜 Lua Script")
                                                             .strong()
-                                                            .color(Color32::WHITE),
+                                                            .color(theme::palette().text_strong),
                                                     );
                                                     ui.with_layout(
                                                         egui::Layout::right_to_left(
@@ -1898,78 +4442,73 @@ impl InspectorWindow {
                                                         ),
                                                         |ui| {
                                                             if ui.button("×").clicked() {
-                                                                remove_fios = true;
+                                                                remove_script = true;
                                                             }
                                                         },
                                                     );
                                                 });
                                                 ui.add_space(4.0);
-                                                egui::Grid::new("fios_grid")
+                                                egui::Grid::new("script_grid")
                                                     .num_columns(2)
                                                     .spacing([10.0, 8.0])
                                                     .show(ui, |ui| {
-                                                        ui.label("Modulo:");
-                                                        egui::ComboBox::from_id_salt(
-                                                            "fios_module_combo",
-                                                        )
-                                                        .selected_text(&ctrl.module_ref)
-                                                        .show_ui(ui, |ui| {
-                                                            for m in animation_modules {
-                                                                ui.selectable_value(
-                                                                    &mut ctrl.module_ref,
-                                                                    m.clone(),
-                                                                    m,
-                                                                );
-                                                            }
-                                                        });
+                                                        ui.label("Ativo:");
+                                                        ui.checkbox(&mut script.enabled, "");
                                                         ui.end_row();
 
-                                                        ui.label("Clip:");
-                                                        egui::ComboBox::from_id_salt(
-                                                            "fios_clip_combo",
-                                                        )
-                                                        .selected_text(&ctrl.primary_clip)
-                                                        .show_ui(ui, |ui| {
-                                                            for c in fbx_animation_clips {
-                                                                ui.selectable_value(
-                                                                    &mut ctrl.primary_clip,
-                                                                    c.clone(),
-                                                                    c,
-                                                                );
+                                                        ui.label("Arquivo:");
+                                                        ui.horizontal(|ui| {
+                                                            ui.add(
+                                                                egui::TextEdit::singleline(
+                                                                    &mut script.path,
+                                                                )
+                                                                .hint_text("Assets/Scripts/player.lua")
+                                                                .desired_width(140.0),
+                                                            );
+                                                            if ui.button("...").clicked() {
+                                                                if let Some(path) =
+                                                                    rfd::FileDialog::new()
+                                                                        .add_filter("Lua", &["lua"])
+                                                                        .pick_file()
+                                                                {
+                                                                    script.path = path
+                                                                        .to_string_lossy()
+                                                                        .to_string();
+                                                                }
                                                             }
                                                         });
                                                         ui.end_row();
-
-                                                        ui.label("Velocidade:");
-                                                        ui.add(
-                                                            egui::DragValue::new(
-                                                                &mut ctrl.move_speed,
-                                                            )
-                                                            .speed(0.1),
-                                                        );
-                                                        ui.end_row();
                                                     });
                                             });
                                         ui.add_space(8.0);
                                     }
-                                    if remove_fios {
-                                        self.object_fios_controller.remove(selected_object);
+                                    if remove_script {
+                                        self.object_script.remove(selected_object);
                                     }
 
-                                    let mut remove_rb = false;
-                                    if let Some(rb) = self.object_rigidbody.get_mut(selected_object)
+                                    self.draw_component(
+                                        ui,
+                                        TypeId::of::<CameraDraft>(),
+                                        selected_object,
+                                        language,
+                                    );
+
+                                    let mut remove_follow = false;
+                                    let hierarchy_drag = self.hierarchy_drag.clone();
+                                    if let Some(follow) =
+                                        self.object_camera_follow.get_mut(selected_object)
                                     {
                                         egui::Frame::new()
-                                            .fill(Color32::from_rgb(36, 36, 36))
-                                            .stroke(Stroke::new(1.0, Color32::from_gray(62)))
+                                            .fill(theme::palette().panel_header)
+                                            .stroke(Stroke::new(1.0, theme::palette().widget_hover))
                                             .corner_radius(6)
                                             .inner_margin(egui::Margin::same(8))
                                             .show(ui, |ui| {
                                                 ui.horizontal(|ui| {
                                                     ui.label(
-                                                        egui::RichText::new("Rigidbody")
+                                                        egui::RichText::new("🎥 Camera Follow")
                                                             .strong()
-                                                            .color(Color32::WHITE),
+                                                            .color(theme::palette().text_strong),
                                                     );
                                                     ui.with_layout(
                                                         egui::Layout::right_to_left(
@@ -1977,49 +4516,85 @@ impl InspectorWindow {
                                                         ),
                                                         |ui| {
                                                             if ui.button("×").clicked() {
-                                                                remove_rb = true;
+                                                                remove_follow = true;
                                                             }
                                                         },
                                                     );
                                                 });
                                                 ui.add_space(4.0);
-                                                egui::Grid::new("rb_grid")
+                                                egui::Grid::new("camera_follow_grid")
                                                     .num_columns(2)
                                                     .spacing([10.0, 8.0])
                                                     .show(ui, |ui| {
-                                                        ui.label("Massa:");
-                                                        ui.add(
-                                                            egui::DragValue::new(&mut rb.mass)
-                                                                .speed(0.1),
+                                                        ui.label("Ativo:");
+                                                        ui.checkbox(&mut follow.enabled, "");
+                                                        ui.end_row();
+
+                                                        ui.label("Alvo:");
+                                                        entity_ref_field(
+                                                            ui,
+                                                            &mut follow.target,
+                                                            hierarchy_drag.as_ref(),
+                                                            language,
                                                         );
                                                         ui.end_row();
 
-                                                        ui.label("Gravidade:");
-                                                        ui.checkbox(&mut rb.use_gravity, "");
+                                                        ui.label("Offset:");
+                                                        ui.horizontal(|ui| {
+                                                            for v in follow.offset.iter_mut() {
+                                                                ui.add(
+                                                                    egui::DragValue::new(v)
+                                                                        .speed(0.05),
+                                                                );
+                                                            }
+                                                        });
+                                                        ui.end_row();
+
+                                                        ui.label("Suavização:");
+                                                        ui.add(
+                                                            egui::Slider::new(
+                                                                &mut follow.smoothing,
+                                                                0.0..=0.99,
+                                                            ),
+                                                        );
                                                         ui.end_row();
                                                     });
                                             });
                                         ui.add_space(8.0);
                                     }
-                                    if remove_rb {
-                                        self.object_rigidbody.remove(selected_object);
+                                    if remove_follow {
+                                        self.object_camera_follow.remove(selected_object);
                                     }
 
-                                    let mut remove_anim = false;
-                                    if let Some(anim) =
-                                        self.object_animator.get_mut(selected_object)
+                                    self.draw_component(
+                                        ui,
+                                        TypeId::of::<PostProcessDraft>(),
+                                        selected_object,
+                                        language,
+                                    );
+
+                                    self.draw_component(
+                                        ui,
+                                        TypeId::of::<LodDraft>(),
+                                        selected_object,
+                                        language,
+                                    );
+
+                                    let mut remove_particles = false;
+                                    if let Some(particles) =
+                                        self.object_particles.get_mut(selected_object)
                                     {
                                         egui::Frame::new()
-                                            .fill(Color32::from_rgb(36, 36, 36))
-                                            .stroke(Stroke::new(1.0, Color32::from_gray(62)))
+                                            .fill(theme::palette().panel_header)
+                                            .stroke(Stroke::new(1.0, theme::palette().widget_hover))
                                             .corner_radius(6)
                                             .inner_margin(egui::Margin::same(8))
                                             .show(ui, |ui| {
                                                 ui.horizontal(|ui| {
                                                     ui.label(
-                                                        egui::RichText::new("Animator")
+                                                        egui::RichText::new("✨ Particle System")
                                                             .strong()
-                                                            .color(Color32::WHITE),
+                                                            .color(theme::palette().text_strong),
                                                     );
                                                     ui.with_layout(
                                                         egui::Layout::right_to_left(
@@ -2027,69 +4602,217 @@ impl InspectorWindow {
                                                         ),
                                                         |ui| {
                                                             if ui.button("×").clicked() {
-                                                                remove_anim = true;
+                                                                remove_particles = true;
                                                             }
                                                         },
                                                     );
                                                 });
                                                 ui.add_space(4.0);
-                                                egui::Grid::new("anim_grid")
+                                                let range = |ui: &mut egui::Ui,
+                                                             value: &mut [f32; 2],
+                                                             speed: f64,
+                                                             suffix: &str| {
+                                                    ui.horizontal(|ui| {
+                                                        ui.add(
+                                                            egui::DragValue::new(&mut value[0])
+                                                                .speed(speed)
+                                                                .range(0.0..=1_000.0)
+                                                                .suffix(suffix),
+                                                        );
+                                                        ui.label("–");
+                                                        let min = value[0];
+                                                        ui.add(
+                                                            egui::DragValue::new(&mut value[1])
+                                                                .speed(speed)
+                                                                .range(min..=1_000.0)
+                                                                .suffix(suffix),
+                                                        );
+                                                    });
+                                                };
+                                                let curve = |ui: &mut egui::Ui,
+                                                             curve: &mut Curve,
+                                                             id: &str,
+                                                             max: Option<f32>| {
+                                                    ui.scope(|ui| {
+                                                        ui.set_width(190.0);
+                                                        let editor = CurveEditor::new(
+                                                            ("particle_curve", selected_object, id),
+                                                            curve,
+                                                            language,
+                                                        )
+                                                        .height(64.0);
+                                                        // Sem máximo a faixa acompanha a curva
+                                                        match max {
+                                                            Some(max) => {
+                                                                editor.value_range(0.0..=max).show(ui)
+                                                            }
+                                                            None => editor.show(ui),
+                                                        };
+                                                    });
+                                                };
+                                                egui::Grid::new("particle_system_grid")
                                                     .num_columns(2)
                                                     .spacing([10.0, 8.0])
                                                     .show(ui, |ui| {
-                                                        ui.label("Controller:");
-                                                        egui::ComboBox::from_id_salt(
-                                                            "anim_ctrl_combo",
-                                                        )
-                                                        .selected_text(&anim.controller_ref)
-                                                        .show_ui(ui, |ui| {
-                                                            for c in animation_controllers {
-                                                                ui.selectable_value(
-                                                                    &mut anim.controller_ref,
-                                                                    c.clone(),
-                                                                    c,
-                                                                );
-                                                            }
+                                                        ui.label("Ativo:");
+                                                        ui.checkbox(&mut particles.enabled, "");
+                                                        ui.end_row();
+
+                                                        ui.label("Máx. partículas:");
+                                                        ui.add(
+                                                            egui::DragValue::new(
+                                                                &mut particles.max_particles,
+                                                            )
+                                                            .speed(10.0)
+                                                            .range(1..=MAX_EMITTER_PARTICLES),
+                                                        );
+                                                        ui.end_row();
+
+                                                        ui.label("Taxa:");
+                                                        ui.add(
+                                                            egui::DragValue::new(
+                                                                &mut particles.rate,
+                                                            )
+                                                            .speed(1.0)
+                                                            .range(0.0..=10_000.0)
+                                                            .suffix(" /s"),
+                                                        );
+                                                        ui.end_row();
+
+                                                        ui.label("Vida:");
+                                                        range(ui, &mut particles.lifetime, 0.05, " s");
+                                                        ui.end_row();
+
+                                                        ui.label("Velocidade:");
+                                                        range(ui, &mut particles.start_speed, 0.05, "");
+                                                        ui.end_row();
+
+                                                        ui.label("Tamanho:");
+                                                        range(ui, &mut particles.start_size, 0.01, "");
+                                                        ui.end_row();
+
+                                                        ui.label("Cor:");
+                                                        ui.horizontal(|ui| {
+                                                            ui.color_edit_button_rgba_unmultiplied(
+                                                                &mut particles.start_color,
+                                                            );
+                                                            ui.label("→");
+                                                            ui.color_edit_button_rgba_unmultiplied(
+                                                                &mut particles.end_color,
+                                                            );
                                                         });
                                                         ui.end_row();
 
-                                                        ui.label("Clip:");
-                                                        egui::ComboBox::from_id_salt(
-                                                            "anim_clip_combo",
-                                                        )
-                                                        .selected_text(&anim.clip_ref)
-                                                        .show_ui(ui, |ui| {
-                                                            for c in fbx_animation_clips {
-                                                                ui.selectable_value(
-                                                                    &mut anim.clip_ref,
-                                                                    c.clone(),
-                                                                    c,
-                                                                );
+                                                        ui.label("Cone:");
+                                                        ui.add(
+                                                            egui::Slider::new(
+                                                                &mut particles.cone_angle,
+                                                                0.0..=180.0,
+                                                            )
+                                                            .suffix("°"),
+                                                        );
+                                                        ui.end_row();
+
+                                                        ui.label("Gravidade:");
+                                                        ui.add(
+                                                            egui::DragValue::new(
+                                                                &mut particles.gravity,
+                                                            )
+                                                            .speed(0.1)
+                                                            .range(-100.0..=100.0),
+                                                        );
+                                                        ui.end_row();
+
+                                                        ui.label("Tamanho (vida):");
+                                                        curve(ui, &mut particles.size_curve, "size", None);
+                                                        ui.end_row();
+
+                                                        ui.label("Alpha (vida):");
+                                                        curve(ui, &mut particles.alpha_curve, "alpha", Some(1.0));
+                                                        ui.end_row();
+
+                                                        ui.label("Velocidade (vida):");
+                                                        curve(ui, &mut particles.speed_curve, "speed", None);
+                                                        ui.end_row();
+
+                                                        ui.label("Textura:");
+                                                        ui.horizontal(|ui| {
+                                                            ui.add(
+                                                                egui::TextEdit::singleline(
+                                                                    &mut particles.texture_path,
+                                                                )
+                                                                .hint_text("Círculo suave")
+                                                                .desired_width(110.0),
+                                                            );
+                                                            if ui.button("...").clicked() {
+                                                                if let Some(path) =
+                                                                    rfd::FileDialog::new()
+                                                                        .add_filter(
+                                                                            "Imagem",
+                                                                            &["png", "jpg", "jpeg", "webp"],
+                                                                        )
+                                                                        .pick_file()
+                                                                {
+                                                                    particles.texture_path = path
+                                                                        .to_string_lossy()
+                                                                        .to_string();
+                                                                }
                                                             }
                                                         });
                                                         ui.end_row();
+
+                                                        ui.label("Sheet:");
+                                                        ui.horizontal(|ui| {
+                                                            ui.add(
+                                                                egui::DragValue::new(
+                                                                    &mut particles.sheet.columns,
+                                                                )
+                                                                .range(1..=16)
+                                                                .prefix("col "),
+                                                            );
+                                                            ui.add(
+                                                                egui::DragValue::new(
+                                                                    &mut particles.sheet.rows,
+                                                                )
+                                                                .range(1..=16)
+                                                                .prefix("lin "),
+                                                            );
+                                                            ui.add(
+                                                                egui::DragValue::new(
+                                                                    &mut particles.sheet.cycles,
+                                                                )
+                                                                .speed(0.05)
+                                                                .range(0.0..=32.0)
+                                                                .suffix("×"),
+                                                            );
+                                                        });
+                                                        ui.end_row();
+
+                                                        ui.label("Aditivo:");
+                                                        ui.checkbox(&mut particles.additive, "");
+                                                        ui.end_row();
                                                     });
                                             });
                                         ui.add_space(8.0);
                                     }
-                                    if remove_anim {
-                                        self.object_animator.remove(selected_object);
+                                    if remove_particles {
+                                        self.object_particles.remove(selected_object);
                                     }
 
-                                    let mut remove_light = false;
-                                    if let Some(light) = self.object_light.get_mut(selected_object)
+                                    let mut remove_terrain = false;
+                                    if let Some(terrain) = self.object_terrain.get_mut(selected_object)
                                     {
                                         egui::Frame::new()
-                                            .fill(Color32::from_rgb(36, 36, 36))
-                                            .stroke(Stroke::new(1.0, Color32::from_gray(62)))
+                                            .fill(theme::palette().panel_header)
+                                            .stroke(Stroke::new(1.0, theme::palette().widget_hover))
                                             .corner_radius(6)
                                             .inner_margin(egui::Margin::same(8))
                                             .show(ui, |ui| {
                                                 ui.horizontal(|ui| {
                                                     ui.label(
-                                                        egui::RichText::new(format!("💡 Luz - {}", light.light_type.as_str()))
+                                                        egui::RichText::new("⛰ Terrain")
                                                             .strong()
-                                                            .color(Color32::WHITE),
+                                                            .color(theme::palette().text_strong),
                                                     );
                                                     ui.with_layout(
                                                         egui::Layout::right_to_left(
@@ -2097,164 +4820,269 @@ impl InspectorWindow {
                                                         ),
                                                         |ui| {
                                                             if ui.button("×").clicked() {
-                                                                remove_light = true;
+                                                                remove_terrain = true;
                                                             }
                                                         },
                                                     );
                                                 });
                                                 ui.add_space(4.0);
-
-                                                // Enabled checkbox
-                                                ui.horizontal(|ui| {
-                                                    ui.label("Ativa:");
-                                                    ui.checkbox(&mut light.enabled, "");
-                                                });
-                                                ui.add_space(6.0);
-
-                                                egui::Grid::new("light_grid")
+                                                let image_path = |ui: &mut egui::Ui,
+                                                                  path: &mut String,
+                                                                  hint: &str| {
+                                                    ui.horizontal(|ui| {
+                                                        ui.add(
+                                                            egui::TextEdit::singleline(path)
+                                                                .hint_text(hint)
+                                                                .desired_width(110.0),
+                                                        );
+                                                        if ui.button("...").clicked() {
+                                                            if let Some(picked) =
+                                                                rfd::FileDialog::new()
+                                                                    .add_filter(
+                                                                        "Imagem",
+                                                                        &["png", "jpg", "jpeg", "webp"],
+                                                                    )
+                                                                    .pick_file()
+                                                            {
+                                                                *path = picked
+                                                                    .to_string_lossy()
+                                                                    .to_string();
+                                                            }
+                                                        }
+                                                    });
+                                                };
+                                                egui::Grid::new("terrain_grid")
                                                     .num_columns(2)
                                                     .spacing([10.0, 8.0])
                                                     .show(ui, |ui| {
-                                                        ui.label("Cor:");
-                                                        ui.color_edit_button_rgb(&mut light.color);
+                                                        ui.label("Ativo:");
+                                                        ui.checkbox(&mut terrain.enabled, "");
+                                                        ui.end_row();
+
+                                                        ui.label("Resolução:");
+                                                        egui::ComboBox::from_id_salt(
+                                                            "terrain_resolution",
+                                                        )
+                                                        .selected_text(format!(
+                                                            "{0}×{0}",
+                                                            terrain.resolution
+                                                        ))
+                                                        .show_ui(ui, |ui| {
+                                                            for resolution in [65, 129, 257, 513] {
+                                                                ui.selectable_value(
+                                                                    &mut terrain.resolution,
+                                                                    resolution,
+                                                                    format!("{0}×{0}", resolution),
+                                                                );
+                                                            }
+                                                        });
+                                                        ui.end_row();
+
+                                                        ui.label("Tamanho:");
+                                                        ui.add(
+                                                            egui::DragValue::new(&mut terrain.size)
+                                                                .speed(0.5)
+                                                                .range(1.0..=10_000.0),
+                                                        );
                                                         ui.end_row();
 
-                                                        ui.label("Intensidade:");
+                                                        ui.label("Altura:");
                                                         ui.add(
                                                             egui::DragValue::new(
-                                                                &mut light.intensity,
+                                                                &mut terrain.height_scale,
                                                             )
-                                                            .speed(0.05)
-                                                            .range(0.0..=10.0),
+                                                            .speed(0.1)
+                                                            .range(0.0..=1_000.0),
                                                         );
                                                         ui.end_row();
 
-                                                        // Type-specific properties
-                                                        match light.light_type {
-                                                            LightType::Point => {
-                                                                ui.label("Alcance:");
-                                                                ui.add(
-                                                                    egui::DragValue::new(&mut light.range)
-                                                                        .speed(0.1)
-                                                                        .range(0.1..=100.0),
-                                                                );
-                                                                ui.end_row();
-                                                            }
-                                                            LightType::Spot => {
-                                                                ui.label("Alcance:");
-                                                                ui.add(
-                                                                    egui::DragValue::new(&mut light.range)
-                                                                        .speed(0.1)
-                                                                        .range(0.1..=100.0),
-                                                                );
-                                                                ui.end_row();
+                                                        ui.label("Distância LOD:");
+                                                        ui.add(
+                                                            egui::DragValue::new(
+                                                                &mut terrain.lod_distance,
+                                                            )
+                                                            .speed(0.5)
+                                                            .range(1.0..=1_000.0),
+                                                        );
+                                                        ui.end_row();
 
-                                                                ui.label("Ângulo do Cone:");
-                                                                ui.add(
-                                                                    egui::DragValue::new(&mut light.cone_angle)
-                                                                        .speed(1.0)
-                                                                        .range(1.0..=180.0),
-                                                                );
-                                                                ui.end_row();
-                                                            }
-                                                            LightType::Directional => {
-                                                                ui.label("Yaw:");
-                                                                ui.add(
-                                                                    egui::DragValue::new(&mut light.yaw)
-                                                                        .speed(0.01)
-                                                                        .range(0.0..=std::f32::consts::TAU),
-                                                                );
-                                                                ui.end_row();
+                                                        ui.label("Heightmap:");
+                                                        image_path(
+                                                            ui,
+                                                            &mut terrain.heightmap_path,
+                                                            "Plano",
+                                                        );
+                                                        ui.end_row();
 
-                                                                ui.label("Pitch:");
-                                                                ui.add(
-                                                                    egui::DragValue::new(&mut light.pitch)
-                                                                        .speed(0.01)
-                                                                        .range(-std::f32::consts::FRAC_PI_2..=std::f32::consts::FRAC_PI_2),
+                                                        ui.label("Splat map:");
+                                                        image_path(
+                                                            ui,
+                                                            &mut terrain.splatmap_path,
+                                                            "Camada 1",
+                                                        );
+                                                        ui.end_row();
+
+                                                        for (i, layer) in
+                                                            terrain.layers.iter_mut().enumerate()
+                                                        {
+                                                            ui.label(format!("Camada {}:", i + 1));
+                                                            ui.vertical(|ui| {
+                                                                image_path(
+                                                                    ui,
+                                                                    &mut layer.texture_path,
+                                                                    "Só cor",
                                                                 );
-                                                                ui.end_row();
-                                                            }
+                                                                ui.horizontal(|ui| {
+                                                                    ui.color_edit_button_rgb(
+                                                                        &mut layer.color,
+                                                                    );
+                                                                    ui.add(
+                                                                        egui::DragValue::new(
+                                                                            &mut layer.tiling,
+                                                                        )
+                                                                        .speed(0.25)
+                                                                        .range(0.1..=512.0)
+                                                                        .suffix("×"),
+                                                                    )
+                                                                    .on_hover_text(
+                                                                        "Repetições da textura no terreno",
+                                                                    );
+                                                                });
+                                                            });
+                                                            ui.end_row();
                                                         }
                                                     });
+                                                ui.label(
+                                                    egui::RichText::new(
+                                                        "Esculpa e pinte com a ferramenta ⛰ da viewport",
+                                                    )
+                                                    .small()
+                                                    .color(theme::palette().text_weak),
+                                                );
                                             });
                                         ui.add_space(8.0);
                                     }
-                                    if remove_light {
-                                        self.object_light.remove(selected_object);
+                                    if remove_terrain {
+                                        self.object_terrain.remove(selected_object);
                                     }
+
                                 }
                             });
                     },
                 );
             });
 
-        let near_left = (panel_rect.left() - left_snap_x).abs() <= 28.0;
-        let near_right = (right_snap_right - panel_rect.right()).abs() <= 28.0;
-
-        if self.dragging_from_header && pointer_down && (near_left || near_right) {
-            let hint_w = 14.0;
-            let hint_rect = if near_left {
-                Rect::from_min_max(
-                    egui::pos2(left_snap_x, dock_rect.top()),
-                    egui::pos2(left_snap_x + hint_w, usable_bottom),
-                )
-            } else {
-                Rect::from_min_max(
-                    egui::pos2(right_snap_right - hint_w, dock_rect.top()),
-                    egui::pos2(right_snap_right, usable_bottom),
-                )
-            };
-            ctx.layer_painter(egui::LayerId::new(Order::Foreground, Id::new("dock_hint")))
-                .rect_filled(
-                    hint_rect,
-                    6.0,
-                    Color32::from_rgba_unmultiplied(15, 232, 121, 110),
-                );
-        }
-
-        if header_drag_stopped || (self.dragging_from_header && !pointer_down) {
-            self.dragging_from_header = false;
-            if near_left {
-                self.dock_side = Some(InspectorDockSide::Left);
-            } else if near_right {
-                self.dock_side = Some(InspectorDockSide::Right);
-            } else {
-                self.dock_side = None;
+        if let Some(before) = components_before {
+            if self.object_components(selected_object) != before {
+                let others: Vec<(String, ObjectComponents)> = self
+                    .selected_objects
+                    .iter()
+                    .filter(|name| name.as_str() != selected_object)
+                    .map(|name| (name.clone(), self.object_components(name)))
+                    .collect();
+                self.copy_edits_to_selection(selected_object, before.clone());
+                self.record_component_edit(selected_object, before);
+                for (name, before) in others {
+                    self.record_component_edit(&name, before);
+                }
             }
         }
-
-        if resize_stopped || (self.resizing_width && !pointer_down) {
-            self.resizing_width = false;
-        }
-
-        if self.dragging_from_header {
-            let delta = ctx.input(|i| i.pointer.delta());
-            self.window_pos = Some(pos + delta);
-            self.dock_side = None;
-        }
-
-        if header_drag_started {
-            self.dragging_from_header = true;
-        }
-
-        if resize_started {
-            self.resizing_width = true;
-        }
     }
+}
 
-    pub fn docked_left_width(&self) -> f32 {
-        if self.open && matches!(self.dock_side, Some(InspectorDockSide::Left)) {
-            self.window_width
-        } else {
-            0.0
-        }
+/// Botão 🔍 que busca assets de um tipo no índice do Projeto; devolve o caminho escolhido
+pub(crate) fn asset_picker_button(
+    ui: &mut egui::Ui,
+    id: impl std::hash::Hash,
+    index: &AssetSearchIndex,
+    query: &mut String,
+    asset_type: AssetType,
+) -> Option<String> {
+    let resp = ui.small_button("🔍");
+    if resp.clicked() {
+        query.clear();
     }
+    let mut picked = None;
+    egui::Popup::menu(&resp)
+        .id(Id::new(("asset_picker", id)))
+        .close_behavior(egui::PopupCloseBehavior::CloseOnClickOutside)
+        .show(|ui| {
+            ui.set_width(240.0);
+            let edit = ui.add(egui::TextEdit::singleline(query).hint_text(asset_type.name()));
+            if resp.clicked() {
+                edit.request_focus();
+            }
+            let hits = index.search_type(query, Some(asset_type), 12);
+            if hits.is_empty() {
+                ui.label(egui::RichText::new("—").weak());
+            }
+            for hit in hits {
+                let folder = hit
+                    .entry
+                    .path
+                    .parent()
+                    .and_then(|p| p.file_name())
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                if ui
+                    .button(&hit.entry.name)
+                    .on_hover_text(format!("{folder}/{}", hit.entry.name))
+                    .clicked()
+                {
+                    picked = Some(hit.entry.path.to_string_lossy().to_string());
+                    ui.close();
+                }
+            }
+        });
+    picked
+}
 
-    pub fn docked_right_width(&self) -> f32 {
-        if self.open && matches!(self.dock_side, Some(InspectorDockSide::Right)) {
-            self.window_width
+/// Campo de referência: aceita um objeto arrastado da Hierarquia e guarda o seu GUID
+fn entity_ref_field(
+    ui: &mut egui::Ui,
+    target: &mut EntityRefDraft,
+    drag: Option<&(String, Guid)>,
+    language: EngineLanguage,
+) {
+    ui.horizontal(|ui| {
+        let empty = tr(language, "none_drag_hint");
+        let text = if target.guid.is_nil() {
+            empty.to_string()
+        } else {
+            target.label.clone()
+        };
+        let (rect, _) = ui.allocate_exact_size(egui::vec2(150.0, 22.0), egui::Sense::hover());
+        let hovered_drop = drag.is_some()
+            && ui
+                .input(|i| i.pointer.hover_pos())
+                .is_some_and(|p| rect.contains(p));
+        let stroke = if hovered_drop {
+            Stroke::new(1.5, theme::palette().accent)
         } else {
-            0.0
+            Stroke::new(1.0, theme::palette().border)
+        };
+        ui.painter()
+            .rect_filled(rect, 4.0, theme::palette().panel_bg);
+        ui.painter()
+            .rect_stroke(rect, 4.0, stroke, egui::StrokeKind::Inside);
+        ui.painter().text(
+            rect.left_center() + egui::vec2(6.0, 0.0),
+            Align2::LEFT_CENTER,
+            text,
+            FontId::new(11.0, FontFamily::Proportional),
+            if target.guid.is_nil() {
+                theme::palette().text_weak
+            } else {
+                theme::palette().text_strong
+            },
+        );
+        let released = ui.input(|i| i.pointer.any_released());
+        if let Some((name, guid)) = drag.filter(|_| hovered_drop && released) {
+            target.guid = *guid;
+            target.label = name.clone();
         }
-    }
+        if !target.guid.is_nil() && ui.small_button("×").clicked() {
+            *target = EntityRefDraft::default();
+        }
+    });
 }
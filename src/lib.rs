@@ -5,6 +5,9 @@
 //! - Handle-based asset management
 //! - Separation between engine core, renderer, and editor
 
+pub use engine_audio;
 pub use engine_core;
 pub use engine_editor;
+pub use engine_net;
+pub use engine_physics;
 pub use engine_render;
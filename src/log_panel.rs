@@ -0,0 +1,434 @@
+use super::*;
+use std::sync::{Mutex, PoisonError};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum LogLevel {
+    Debug,
+    Info,
+    Warning,
+    Error,
+}
+
+impl LogLevel {
+    // Ordem dos filtros na barra do painel
+    const ALL: [LogLevel; 4] = [
+        LogLevel::Error,
+        LogLevel::Warning,
+        LogLevel::Info,
+        LogLevel::Debug,
+    ];
+
+    fn from_log(level: log::Level) -> Self {
+        match level {
+            log::Level::Error => LogLevel::Error,
+            log::Level::Warn => LogLevel::Warning,
+            log::Level::Info => LogLevel::Info,
+            log::Level::Debug | log::Level::Trace => LogLevel::Debug,
+        }
+    }
+
+    fn index(self) -> usize {
+        match self {
+            LogLevel::Debug => 0,
+            LogLevel::Info => 1,
+            LogLevel::Warning => 2,
+            LogLevel::Error => 3,
+        }
+    }
+
+    fn icon(self) -> &'static str {
+        match self {
+            LogLevel::Debug => "•",
+            LogLevel::Info => "ℹ",
+            LogLevel::Warning => "⚠",
+            LogLevel::Error => "⛔",
+        }
+    }
+
+    fn color(self) -> egui::Color32 {
+        match self {
+            LogLevel::Debug => egui::Color32::from_gray(140),
+            LogLevel::Info => egui::Color32::from_gray(200),
+            LogLevel::Warning => egui::Color32::from_rgb(230, 190, 90),
+            LogLevel::Error => egui::Color32::from_rgb(235, 110, 100),
+        }
+    }
+
+    fn label(self, language: EngineLanguage) -> &'static str {
+        match self {
+            LogLevel::Debug => tr(language, "debug"),
+            LogLevel::Info => tr(language, "info"),
+            LogLevel::Warning => tr(language, "warning"),
+            LogLevel::Error => tr(language, "error"),
+        }
+    }
+}
+
+struct LogEntry {
+    level: LogLevel,
+    // Subsistema que gerou a mensagem ("Shader", "Renderer", "Script", ...)
+    source: String,
+    message: String,
+    // Arquivo e linha de onde a mensagem veio, aberto com um clique
+    location: Option<(PathBuf, u32)>,
+}
+
+/// Mensagens recebidas pelo logger, de qualquer thread, ainda não levadas ao painel
+static PENDING: Mutex<Vec<LogEntry>> = Mutex::new(Vec::new());
+
+/// Recebe o `log::info!`/`log::warn!`/... da engine, do renderer e dos scripts
+struct PanelLogger;
+
+impl log::Log for PanelLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        // Bibliotecas de terceiros (wgpu, naga, eframe...) só com avisos e erros
+        let max = if is_engine_target(metadata.target()) {
+            log::Level::Debug
+        } else {
+            log::Level::Warn
+        };
+        metadata.level() <= max
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let message = record.args().to_string();
+        eprintln!("[{}] {}: {}", record.level(), record.target(), message);
+        let location = if record.target() == "script" {
+            script_location(&message)
+        } else if is_engine_target(record.target()) {
+            record
+                .file()
+                .zip(record.line())
+                .map(|(file, line)| (Path::new(env!("CARGO_MANIFEST_DIR")).join(file), line))
+        } else {
+            None
+        };
+        let entry = LogEntry {
+            level: LogLevel::from_log(record.level()),
+            source: source_for_target(record.target()).to_string(),
+            message,
+            location,
+        };
+        PENDING
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(entry);
+    }
+
+    fn flush(&self) {}
+}
+
+/// Liga o `log` ao painel Log; chamado uma vez antes de abrir a janela
+pub(crate) fn install_logger() {
+    static LOGGER: PanelLogger = PanelLogger;
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(log::LevelFilter::Debug);
+    }
+}
+
+fn is_engine_target(target: &str) -> bool {
+    target == "script" || target.starts_with("dengine") || target.starts_with("engine_")
+}
+
+fn source_for_target(target: &str) -> &'static str {
+    if target == "script" {
+        "Script"
+    } else if target.starts_with("engine_render")
+        || target.starts_with("dengine::viewport_gpu")
+        || target.starts_with("wgpu")
+        || target.starts_with("naga")
+    {
+        "Renderer"
+    } else {
+        "Engine"
+    }
+}
+
+/// `arquivo.lua:linha` dentro da mensagem de um script, também no formato
+/// `[string "arquivo.lua"]:linha` dos erros do Lua
+fn script_location(message: &str) -> Option<(PathBuf, u32)> {
+    for (index, _) in message.match_indices(".lua") {
+        let end = index + ".lua".len();
+        let start = message[..end]
+            .rfind(|c: char| c == '"' || c.is_whitespace())
+            .map_or(0, |i| i + 1);
+        let rest = message[end..].trim_start_matches("\"]");
+        let Some(rest) = rest.strip_prefix(':') else {
+            continue;
+        };
+        let digits: String = rest.chars().take_while(char::is_ascii_digit).collect();
+        if let Ok(line) = digits.parse() {
+            return Some((PathBuf::from(&message[start..end]), line));
+        }
+    }
+    None
+}
+
+/// Abre o arquivo na linha pelo VS Code; sem ele, no programa padrão do sistema
+fn open_source(path: &Path, line: u32) -> Result<(), String> {
+    let goto = format!("{}:{line}", path.display());
+    if Command::new("code")
+        .arg("--goto")
+        .arg(&goto)
+        .spawn()
+        .is_ok()
+    {
+        return Ok(());
+    }
+    #[cfg(target_os = "windows")]
+    let result = Command::new("cmd")
+        .args(["/C", "start", ""])
+        .arg(path)
+        .spawn();
+    #[cfg(target_os = "macos")]
+    let result = Command::new("open").arg(path).spawn();
+    #[cfg(all(unix, not(target_os = "macos")))]
+    let result = Command::new("xdg-open").arg(path).spawn();
+    result
+        .map(|_| ())
+        .map_err(|err| format!("Falha ao abrir {goto}: {err}"))
+}
+
+/// Mensagens do editor exibidas no painel Log da barra inferior
+pub(crate) struct LogPanel {
+    entries: Vec<LogEntry>,
+    // Níveis visíveis, indexados por `LogLevel::index`
+    shown: [bool; 4],
+    search: String,
+    // Mensagens iguais em uma linha só, com a contagem
+    collapse: bool,
+}
+
+impl Default for LogPanel {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+            shown: [false, true, true, true],
+            search: String::new(),
+            collapse: false,
+        }
+    }
+}
+
+impl LogPanel {
+    const MAX_ENTRIES: usize = 2000;
+
+    pub(crate) fn push(&mut self, level: LogLevel, source: &'static str, message: String) {
+        self.push_entry(LogEntry {
+            level,
+            source: source.to_string(),
+            message,
+            location: None,
+        });
+    }
+
+    fn push_entry(&mut self, entry: LogEntry) {
+        if self.entries.len() >= Self::MAX_ENTRIES {
+            self.entries.remove(0);
+        }
+        self.entries.push(entry);
+    }
+
+    /// Traz as mensagens do logger; retorna se chegou algum erro
+    pub(crate) fn collect_logged(&mut self) -> bool {
+        let pending = std::mem::take(&mut *PENDING.lock().unwrap_or_else(PoisonError::into_inner));
+        let mut error = false;
+        for entry in pending {
+            error |= entry.level == LogLevel::Error;
+            self.push_entry(entry);
+        }
+        error
+    }
+
+    fn count(&self, level: LogLevel) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| entry.level == level)
+            .count()
+    }
+
+    /// Índices das entradas visíveis com filtros e busca, e quantas vezes cada
+    /// uma se repete quando as duplicadas são agrupadas
+    fn visible_rows(&self) -> Vec<(usize, usize)> {
+        let search = self.search.trim().to_lowercase();
+        let mut rows: Vec<(usize, usize)> = Vec::new();
+        let mut groups: HashMap<(LogLevel, &str, &str), usize> = HashMap::new();
+        for (index, entry) in self.entries.iter().enumerate() {
+            if !self.shown[entry.level.index()] {
+                continue;
+            }
+            if !search.is_empty()
+                && !entry.message.to_lowercase().contains(&search)
+                && !entry.source.to_lowercase().contains(&search)
+            {
+                continue;
+            }
+            if self.collapse {
+                let key = (entry.level, entry.source.as_str(), entry.message.as_str());
+                if let Some(&row) = groups.get(&key) {
+                    rows[row].1 += 1;
+                    continue;
+                }
+                groups.insert(key, rows.len());
+            }
+            rows.push((index, 1));
+        }
+        rows
+    }
+}
+
+impl EditorApp {
+    /// Leva ao painel o que foi registrado pelo `log`; erros abrem o Log
+    pub(crate) fn sync_log(&mut self) {
+        if self.log.collect_logged() {
+            self.log_enabled = true;
+        }
+    }
+
+    /// Recarrega os shaders alterados em disco; erros de compilação abrem o Log
+    pub(crate) fn sync_shader_reload(&mut self, ctx: &egui::Context) {
+        let Some(gpu) = &self.viewport_gpu else {
+            return;
+        };
+        for message in gpu.reload_shaders() {
+            match message {
+                Ok(text) => self.log.push(LogLevel::Info, "Shader", text),
+                Err(text) => {
+                    self.log.push(LogLevel::Error, "Shader", text);
+                    self.log_enabled = true;
+                }
+            }
+            ctx.request_repaint();
+        }
+        // Sem input o egui não repinta; os arquivos continuam sendo verificados
+        // e as mensagens de outras threads chegam ao painel
+        ctx.request_repaint_after(std::time::Duration::from_secs(1));
+    }
+
+    /// Painel Log no espaço que o layout de painéis deu a ele
+    pub(crate) fn draw_log_panel(&mut self, ctx: &egui::Context, panel: egui::Rect) {
+        engine_core::profile_function!();
+        egui::Area::new(egui::Id::new("log_panel"))
+            .order(egui::Order::Foreground)
+            .fixed_pos(panel.min)
+            .show(ctx, |ui| {
+                let (rect, _) = ui.allocate_exact_size(panel.size(), egui::Sense::hover());
+                ui.painter()
+                    .rect_filled(rect, 0.0, theme::palette().panel_bg);
+                ui.painter().rect_stroke(
+                    rect,
+                    0.0,
+                    egui::Stroke::new(1.0, theme::palette().border),
+                    egui::StrokeKind::Outside,
+                );
+                let inner = rect.shrink2(egui::vec2(8.0, 6.0));
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(inner)
+                        .layout(egui::Layout::top_down(egui::Align::Min)),
+                    |ui| self.draw_log_entries(ui),
+                );
+            });
+    }
+
+    fn draw_log_entries(&mut self, ui: &mut egui::Ui) {
+        let language = self.language;
+        ui.horizontal(|ui| {
+            for level in LogLevel::ALL {
+                let text =
+                    egui::RichText::new(format!("{} {}", level.icon(), self.log.count(level)))
+                        .small()
+                        .color(level.color());
+                let shown = &mut self.log.shown[level.index()];
+                if ui
+                    .selectable_label(*shown, text)
+                    .on_hover_text(level.label(language))
+                    .clicked()
+                {
+                    *shown = !*shown;
+                }
+            }
+            ui.separator();
+            ui.add(
+                egui::TextEdit::singleline(&mut self.log.search)
+                    .hint_text(tr(language, "search"))
+                    .desired_width(180.0),
+            );
+            ui.checkbox(&mut self.log.collapse, tr(language, "collapse"));
+            if ui.small_button(tr(language, "clear")).clicked() {
+                self.log.entries.clear();
+            }
+        });
+        ui.separator();
+
+        let rows = self.log.visible_rows();
+        let row_height = ui.spacing().interact_size.y;
+        let mut open = None;
+        egui::ScrollArea::vertical()
+            .auto_shrink([false, false])
+            .stick_to_bottom(true)
+            .show_rows(ui, row_height, rows.len(), |ui, range| {
+                for &(index, repeats) in &rows[range] {
+                    let entry = &self.log.entries[index];
+                    ui.horizontal(|ui| {
+                        ui.set_height(row_height);
+                        ui.label(
+                            egui::RichText::new(entry.level.icon()).color(entry.level.color()),
+                        );
+                        ui.label(egui::RichText::new(&entry.source).small().weak());
+                        if repeats > 1 {
+                            ui.label(egui::RichText::new(format!("×{repeats}")).small().strong());
+                        }
+                        let first_line = entry.message.lines().next().unwrap_or_default();
+                        let response = ui
+                            .add(
+                                egui::Label::new(
+                                    egui::RichText::new(first_line)
+                                        .monospace()
+                                        .color(entry.level.color()),
+                                )
+                                .truncate()
+                                .sense(egui::Sense::click()),
+                            )
+                            .on_hover_text(match &entry.location {
+                                Some((path, line)) => {
+                                    format!("{}\n\n{}:{line}", entry.message, path.display())
+                                }
+                                None => entry.message.clone(),
+                            });
+                        if entry.location.is_some() && response.hovered() {
+                            ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+                        }
+                        if response.clicked() {
+                            open = entry.location.clone();
+                        }
+                        response.context_menu(|ui| {
+                            if ui.button(tr(language, "copy")).clicked() {
+                                ui.ctx().copy_text(entry.message.clone());
+                                ui.close();
+                            }
+                            if let Some(location) = &entry.location {
+                                if ui.button(tr(language, "open_source")).clicked() {
+                                    open = Some(location.clone());
+                                    ui.close();
+                                }
+                            }
+                        });
+                    });
+                }
+            });
+
+        if let Some((path, line)) = open {
+            if let Err(err) = open_source(&path, line) {
+                self.log.push(LogLevel::Error, "Log", err);
+            }
+        }
+    }
+}
+
+fn tr(language: EngineLanguage, key: &'static str) -> &'static str {
+    i18n::text(language, "log_panel", key)
+}
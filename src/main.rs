@@ -1,17 +1,55 @@
 // src/main.rs
+mod asset_memory;
+mod audio;
+mod build;
+mod bundles;
+mod camera_bookmarks;
+mod capture;
+mod clipboard;
+mod curve_editor;
+mod dock;
+mod environment;
+mod finder;
 mod fios;
+mod follow;
+mod git_panel;
+mod graphics;
 mod hierarchy;
+mod history;
+mod hot_reload;
+mod i18n;
+mod input;
 mod inspector;
+mod log_panel;
+mod material_editor;
+mod net;
+mod physics;
+mod play_mode;
+mod post_process;
+mod prefab;
+mod preferences;
+mod profiler_panel;
 mod project;
+mod recent;
+mod reflect;
+mod rig_editor;
+mod scenes;
+mod scripts;
+mod selection;
+mod sprite_atlas;
 mod terminai;
+mod theme;
+mod timeline;
 mod viewport;
 mod viewport_gpu;
 
+use dock::DockPanel;
 use eframe::egui::{self, Key, Modifiers, TextureHandle, TextureOptions, text::LayoutJob};
 use eframe::{App, Frame, NativeOptions};
 use epaint::ColorImage;
 use hierarchy::HierarchyWindow;
 use inspector::InspectorWindow;
+use log_panel::LogLevel;
 use portable_pty::{CommandBuilder, PtySize, native_pty_system};
 use project::ProjectWindow;
 use raw_window_handle::{HasWindowHandle, RawWindowHandle};
@@ -28,14 +66,47 @@ use vt100::Parser;
 #[cfg(target_os = "windows")]
 use windows_sys::Win32::Graphics::Dwm::DwmSetWindowAttribute;
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub enum EngineLanguage {
     Pt,
     En,
     Es,
+    Fr,
+    De,
+    Ja,
+    Zh,
 }
 
-impl EngineLanguage {}
+impl EngineLanguage {
+    pub(crate) const ALL: [Self; 7] = [
+        Self::Pt,
+        Self::En,
+        Self::Es,
+        Self::Fr,
+        Self::De,
+        Self::Ja,
+        Self::Zh,
+    ];
+
+    /// Nome do pacote de idioma em `Editor/lang/`
+    pub(crate) fn code(self) -> &'static str {
+        match self {
+            EngineLanguage::Pt => "pt",
+            EngineLanguage::En => "en",
+            EngineLanguage::Es => "es",
+            EngineLanguage::Fr => "fr",
+            EngineLanguage::De => "de",
+            EngineLanguage::Ja => "ja",
+            EngineLanguage::Zh => "zh",
+        }
+    }
+
+    /// Idioma de um locale como `pt_BR.UTF-8`, `fr-FR` ou `zh-Hans-CN`
+    pub(crate) fn from_locale(locale: &str) -> Option<Self> {
+        let code = locale.get(..2)?.to_ascii_lowercase();
+        Self::ALL.into_iter().find(|lang| lang.code() == code)
+    }
+}
 
 #[derive(Clone)]
 struct InstalledEngine {
@@ -94,7 +165,38 @@ struct EditorApp {
     current_project: Option<PathBuf>,
     terminai: terminai::TerminAiState,
     fios: fios::FiosState,
-    rigidbody_vertical_vel: HashMap<String, f32>,
+    finder: finder::QuickFinder,
+    input: engine_core::Input,
+    // Controles de jogo; None quando a plataforma não tem backend
+    gamepads: Option<gilrs::Gilrs>,
+    time: engine_core::Time,
+    audio_preview: audio::AudioPreview,
+    network: net::NetworkPanel,
+    environment: environment::EnvironmentPanel,
+    post_process: post_process::PostProcessStack,
+    graphics: graphics::GraphicsSettings,
+    build: build::BuildSettings,
+    asset_memory: asset_memory::AssetMemoryWindow,
+    capture: capture::CaptureSettings,
+    log: log_panel::LogPanel,
+    git: git_panel::GitPanel,
+    material_editor: material_editor::MaterialEditor,
+    timeline: timeline::Timeline,
+    rig_editor: rig_editor::RigEditor,
+    profiler: profiler_panel::ProfilerPanel,
+    preferences: preferences::EditorPreferences,
+    recent: recent::RecentScenes,
+    theme: theme::ThemeSettings,
+    camera_bookmarks: camera_bookmarks::CameraBookmarks,
+    play_session: Option<play_mode::PlaySession>,
+    prefabs: prefab::Prefabs,
+    entity_clipboard: clipboard::EntityClipboard,
+    history: history::EditorHistory,
+    dock: dock::DockLayout,
+    hot_reload: hot_reload::HotReload,
+    scripts: scripts::GameplayScripts,
+    physics: physics::GameplayPhysics,
+    scene_world: scenes::SceneWorld,
     animator_runtime: HashMap<String, AnimatorRuntimeState>,
 }
 
@@ -106,7 +208,7 @@ enum ToolbarMode {
 }
 
 fn load_png_as_texture(ctx: &egui::Context, png_path: &str) -> Option<TextureHandle> {
-    let bytes = std::fs::read(png_path).ok()?;
+    let bytes = engine_render::vfs_read(png_path).ok()?;
     let rgba = image::load_from_memory(&bytes).ok()?.to_rgba8();
     let size = [rgba.width() as usize, rgba.height() as usize];
     let color_image = ColorImage::from_rgba_unmultiplied(size, rgba.as_raw());
@@ -114,7 +216,7 @@ fn load_png_as_texture(ctx: &egui::Context, png_path: &str) -> Option<TextureHan
 }
 
 fn load_icon_data_from_png(png_path: &str) -> Option<Arc<egui::IconData>> {
-    let bytes = std::fs::read(png_path).ok()?;
+    let bytes = engine_render::vfs_read(png_path).ok()?;
     let rgba = image::load_from_memory(&bytes).ok()?.to_rgba8();
     let width = rgba.width();
     let height = rgba.height();
@@ -172,6 +274,37 @@ impl EditorApp {
         fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
     }
 
+    /// Regrava um .deng antigo na versão atual do formato; o de uma engine mais nova fica
+    /// intacto e o erro vai para o console
+    fn upgrade_project_file(&mut self, project_file: &Path) {
+        let Ok(raw) = fs::read_to_string(project_file) else {
+            return;
+        };
+        match engine_render::migrate_format(engine_render::FormatKind::Project, &raw) {
+            Ok(migrated) if migrated.upgraded() => match fs::write(project_file, &migrated.text) {
+                Ok(()) => self.log.push(
+                    LogLevel::Info,
+                    "Projeto",
+                    format!(
+                        "{} atualizado da versão {} para {}",
+                        project_file.display(),
+                        migrated.from_version,
+                        migrated.to_version
+                    ),
+                ),
+                Err(e) => {
+                    self.log.push(LogLevel::Error, "Projeto", e.to_string());
+                    self.log_enabled = true;
+                }
+            },
+            Ok(_) => {}
+            Err(e) => {
+                self.log.push(LogLevel::Error, "Projeto", e);
+                self.log_enabled = true;
+            }
+        }
+    }
+
     fn resolve_project_file_path(path: &Path, create_layout: bool) -> PathBuf {
         let mut in_path = path.to_path_buf();
         if in_path
@@ -342,83 +475,93 @@ impl EditorApp {
     }
 
     fn refresh_hub_projects(&mut self) {
-        eprintln!("[HUB] refresh_hub_projects iniciado");
+        log::debug!("[HUB] refresh_hub_projects iniciado");
         let mut out = Vec::<PathBuf>::new();
         let root = Path::new(".");
-        eprintln!("[HUB] Coletando arquivos .deng...");
+        log::debug!("[HUB] Coletando arquivos .deng...");
         collect_deng_files(root, root, 0, &mut out);
-        eprintln!("[HUB] {} projetos coletados", out.len());
-        eprintln!("[HUB] Carregando registry...");
+        log::debug!("[HUB] {} projetos coletados", out.len());
+        log::debug!("[HUB] Carregando registry...");
         for p in Self::load_hub_registry() {
             out.push(p);
         }
-        eprintln!("[HUB] Total apos registry: {} projetos", out.len());
+        log::debug!("[HUB] Total apos registry: {} projetos", out.len());
         Self::sort_and_dedupe_paths(&mut out);
         self.hub_projects = out;
-        eprintln!("[HUB] Salvando registry...");
+        log::debug!("[HUB] Salvando registry...");
         self.save_hub_registry();
         if let Some(sel) = self.hub_selected {
             if sel >= self.hub_projects.len() {
                 self.hub_selected = None;
             }
         }
-        eprintln!("[HUB] refresh_hub_projects concluido");
+        log::debug!("[HUB] refresh_hub_projects concluido");
     }
 
     fn create_project_dialog(&mut self) {
-        eprintln!("[HUB] create_project_dialog iniciado");
+        log::debug!("[HUB] create_project_dialog iniciado");
         let picked = rfd::FileDialog::new()
             .add_filter("Dengine Project", &["deng"])
             .set_file_name("NovoProjeto.deng")
             .save_file();
         let Some(path) = picked else {
-            eprintln!("[HUB] Nenhum projeto selecionado");
+            log::debug!("[HUB] Nenhum projeto selecionado");
             return;
         };
-        eprintln!("[HUB] Projeto selecionado: {:?}", path);
+        log::debug!("[HUB] Projeto selecionado: {:?}", path);
         let project_file = Self::resolve_project_file_path(&path, true);
-        eprintln!("[HUB] Project file resolvido: {:?}", project_file);
+        log::debug!("[HUB] Project file resolvido: {:?}", project_file);
 
         if let Ok(mut f) = File::create(&project_file) {
-            let _ = f.write_all(b"DENG1\n");
-            eprintln!("[HUB] Arquivo .deng criado");
+            let _ = f.write_all(engine_render::FormatKind::Project.header().as_bytes());
+            log::debug!("[HUB] Arquivo .deng criado");
         }
         let normalized = Self::resolve_project_file_path(&project_file, true);
-        eprintln!("[HUB] Normalizado: {:?}", normalized);
+        log::debug!("[HUB] Normalizado: {:?}", normalized);
         self.current_project = Some(normalized.clone());
         self.register_hub_project(&normalized);
+        self.remember_recent_scene(&normalized);
         self.show_hub = false;
-        eprintln!("[HUB] show_hub = false, refresh_hub_projects");
+        log::debug!("[HUB] show_hub = false, refresh_hub_projects");
         self.refresh_hub_projects();
-        eprintln!("[HUB] create_project_dialog concluido");
+        log::debug!("[HUB] create_project_dialog concluido");
     }
 
     fn open_project_dialog(&mut self) {
-        eprintln!("[HUB] open_project_dialog iniciado");
+        log::debug!("[HUB] open_project_dialog iniciado");
         let picked = rfd::FileDialog::new()
             .add_filter("Dengine Project", &["deng"])
             .pick_file();
         let Some(path) = picked else {
-            eprintln!("[HUB] Nenhum projeto selecionado");
+            log::debug!("[HUB] Nenhum projeto selecionado");
             return;
         };
-        eprintln!("[HUB] Projeto selecionado: {:?}", path);
-        let normalized = Self::resolve_project_file_path(&path, false);
-        eprintln!("[HUB] Normalizado: {:?}", normalized);
+        log::debug!("[HUB] Projeto selecionado: {:?}", path);
+        self.open_project_path(&path);
+        log::debug!("[HUB] show_hub = false");
+        self.refresh_hub_projects();
+        log::debug!("[HUB] open_project_dialog concluido");
+    }
+
+    /// Abre o .deng (pelo diálogo, pela lista do Hub ou pelos recentes) e sai do Hub
+    fn open_project_path(&mut self, path: &Path) {
+        let normalized = Self::resolve_project_file_path(path, false);
+        log::debug!("[HUB] Normalizado: {:?}", normalized);
+        self.upgrade_project_file(&normalized);
         self.current_project = Some(normalized.clone());
         self.register_hub_project(&normalized);
+        self.remember_recent_scene(&normalized);
+        self.reload_sprite_atlases();
         self.show_hub = false;
-        eprintln!("[HUB] show_hub = false");
-        self.refresh_hub_projects();
-        eprintln!("[HUB] open_project_dialog concluido");
     }
 
     fn draw_hub(&mut self, ctx: &egui::Context) {
-        let bg = egui::Color32::from_rgb(20, 23, 24);
-        let panel_fill = egui::Color32::from_rgb(28, 33, 34);
-        let panel_stroke = egui::Color32::from_rgba_unmultiplied(210, 228, 222, 42);
-        let accent = egui::Color32::from_rgb(15, 232, 121);
-        let muted = egui::Color32::from_gray(170);
+        let palette = theme::palette();
+        let bg = palette.panel_bg;
+        let panel_fill = palette.panel_header;
+        let panel_stroke = palette.border;
+        let accent = palette.accent;
+        let muted = palette.text_weak;
 
         egui::CentralPanel::default()
             .frame(egui::Frame::new().fill(bg))
@@ -443,7 +586,7 @@ impl EditorApp {
                                     egui::RichText::new("Dengine Hub")
                                         .strong()
                                         .size(18.0)
-                                        .color(egui::Color32::from_gray(230)),
+                                        .color(palette.text_strong),
                                 );
                                 ui.label(
                                     egui::RichText::new("Projetos locais")
@@ -462,13 +605,13 @@ impl EditorApp {
                                         ui.painter().circle_filled(
                                             close_rect.center(),
                                             9.0,
-                                            egui::Color32::from_rgba_unmultiplied(255, 255, 255, 28),
+                                            palette.widget_hover,
                                         );
                                     }
                                     ui.painter().circle_filled(
                                         close_rect.center(),
                                         5.0,
-                                        egui::Color32::from_rgb(0xD0, 0x24, 0x24),
+                                        palette.error,
                                     );
                                     if close_resp.clicked() {
                                         ui.ctx().send_viewport_cmd(egui::ViewportCommand::Close);
@@ -480,13 +623,13 @@ impl EditorApp {
                                         ui.painter().circle_filled(
                                             max_rect.center(),
                                             9.0,
-                                            egui::Color32::from_rgba_unmultiplied(255, 255, 255, 28),
+                                            palette.widget_hover,
                                         );
                                     }
                                     ui.painter().circle_filled(
                                         max_rect.center(),
                                         5.0,
-                                        egui::Color32::from_rgb(0x04, 0xBA, 0x6C),
+                                        accent,
                                     );
                                     if max_resp.clicked() {
                                         self.is_window_maximized = !self.is_window_maximized;
@@ -501,13 +644,13 @@ impl EditorApp {
                                         ui.painter().circle_filled(
                                             min_rect.center(),
                                             9.0,
-                                            egui::Color32::from_rgba_unmultiplied(255, 255, 255, 28),
+                                            palette.widget_hover,
                                         );
                                     }
                                     ui.painter().circle_filled(
                                         min_rect.center(),
                                         5.0,
-                                        egui::Color32::from_rgb(0xD5, 0x3C, 0x0D),
+                                        palette.warning,
                                     );
                                     if min_resp.clicked() {
                                         ui.ctx()
@@ -538,7 +681,7 @@ impl EditorApp {
                                     ui.label(
                                         egui::RichText::new("Acoes")
                                             .size(13.0)
-                                            .color(egui::Color32::from_gray(220)),
+                                            .color(palette.text_strong),
                                     );
                                     ui.add_space(8.0);
                                     if ui
@@ -546,7 +689,7 @@ impl EditorApp {
                                             [ui.available_width(), 30.0],
                                             egui::Button::new("Novo Projeto")
                                                 .corner_radius(6)
-                                                .fill(egui::Color32::from_rgb(44, 44, 44))
+                                                .fill(palette.widget_bg)
                                                 .stroke(egui::Stroke::new(1.0, accent)),
                                         )
                                         .clicked()
@@ -558,10 +701,10 @@ impl EditorApp {
                                             [ui.available_width(), 30.0],
                                             egui::Button::new("Abrir .deng")
                                                 .corner_radius(6)
-                                                .fill(egui::Color32::from_rgb(44, 44, 44))
+                                                .fill(palette.widget_bg)
                                                 .stroke(egui::Stroke::new(
                                                     1.0,
-                                                    egui::Color32::from_gray(70),
+                                                    palette.border,
                                                 )),
                                         )
                                         .clicked()
@@ -573,10 +716,10 @@ impl EditorApp {
                                             [ui.available_width(), 28.0],
                                             egui::Button::new("Atualizar Lista")
                                                 .corner_radius(6)
-                                                .fill(egui::Color32::from_rgb(40, 45, 46))
+                                                .fill(palette.widget_bg)
                                                 .stroke(egui::Stroke::new(
                                                     1.0,
-                                                    egui::Color32::from_gray(70),
+                                                    palette.border,
                                                 )),
                                         )
                                         .clicked()
@@ -598,22 +741,26 @@ impl EditorApp {
                                     ui.label(
                                         egui::RichText::new(self.current_project_label())
                                             .size(11.0)
-                                            .color(egui::Color32::from_gray(140)),
+                                            .color(palette.text_weak),
                                     );
                                     ui.add_space(10.0);
                                     ui.separator();
                                     ui.add_space(10.0);
+                                    self.draw_hub_recent(ui);
+                                    ui.add_space(10.0);
+                                    ui.separator();
+                                    ui.add_space(10.0);
                                     ui.vertical_centered(|ui| {
                                         ui.label(
                                             egui::RichText::new("Engines instaladas")
                                                 .size(13.0)
-                                                .color(egui::Color32::from_gray(220)),
+                                                .color(palette.text_strong),
                                         );
                                         if let Some(status) = &self.hub_engine_status {
                                             ui.label(
                                                 egui::RichText::new(status)
                                                     .size(10.0)
-                                                    .color(egui::Color32::from_gray(155)),
+                                                    .color(palette.text_weak),
                                             );
                                         }
                                     });
@@ -624,10 +771,10 @@ impl EditorApp {
                                         let engine = &self.hub_engines[idx];
                                         ui.vertical_centered(|ui| {
                                             egui::Frame::new()
-                                                .fill(egui::Color32::from_rgb(35, 39, 40))
+                                                .fill(palette.widget_bg)
                                                 .stroke(egui::Stroke::new(
                                                     1.0,
-                                                    egui::Color32::from_gray(70),
+                                                    palette.border,
                                                 ))
                                                 .corner_radius(6)
                                                 .inner_margin(egui::Margin::same(8))
@@ -639,7 +786,7 @@ impl EditorApp {
                                                                 engine.name, engine.version
                                                             ))
                                                             .size(11.0)
-                                                            .color(egui::Color32::from_gray(235)),
+                                                            .color(palette.text_strong),
                                                         );
                                                         ui.label(
                                                             egui::RichText::new(
@@ -654,7 +801,7 @@ impl EditorApp {
                                                                 },
                                                             )
                                                             .size(9.5)
-                                                            .color(egui::Color32::from_gray(168)),
+                                                            .color(palette.text_weak),
                                                         );
                                                         ui.label(
                                                             egui::RichText::new(
@@ -663,7 +810,7 @@ impl EditorApp {
                                                                     .to_string(),
                                                             )
                                                             .size(9.0)
-                                                            .color(egui::Color32::from_gray(150)),
+                                                            .color(palette.text_weak),
                                                         );
                                                         ui.add_space(4.0);
                                                         ui.horizontal(|ui| {
@@ -682,9 +829,7 @@ impl EditorApp {
                                                                         },
                                                                     )
                                                                     .corner_radius(6)
-                                                                    .fill(egui::Color32::from_rgb(
-                                                                        44, 44, 44,
-                                                                    ))
+                                                                    .fill(palette.widget_bg)
                                                                     .stroke(egui::Stroke::new(
                                                                         1.0, accent,
                                                                     )),
@@ -705,10 +850,10 @@ impl EditorApp {
                                                                 "Remover",
                                                             )
                                                             .corner_radius(6)
-                                                            .fill(egui::Color32::from_rgb(58, 41, 41))
+                                                            .fill(palette.widget_bg)
                                                             .stroke(egui::Stroke::new(
                                                                 1.0,
-                                                                egui::Color32::from_rgb(171, 84, 84),
+                                                                palette.error,
                                                             ));
                                                             if ui
                                                                 .add_enabled(
@@ -746,12 +891,12 @@ impl EditorApp {
                                 ui.label(
                                     egui::RichText::new("Projetos")
                                         .size(13.0)
-                                        .color(egui::Color32::from_gray(220)),
+                                        .color(palette.text_strong),
                                 );
                                 ui.label(
                                     egui::RichText::new(".deng")
                                         .size(11.0)
-                                        .color(egui::Color32::from_gray(150)),
+                                        .color(palette.text_weak),
                                 );
                             });
                             ui.add_space(8.0);
@@ -760,6 +905,7 @@ impl EditorApp {
                                 .max_height((ui.available_height() - 46.0).max(80.0))
                                 .show(ui, |ui| {
                                     let mut open_project_now: Option<PathBuf> = None;
+                                    let mut pin_toggle: Option<PathBuf> = None;
                                     if self.hub_projects.is_empty() {
                                         ui.label(
                                             egui::RichText::new(
@@ -781,14 +927,14 @@ impl EditorApp {
                                             .map(|p| p.to_string_lossy().to_string())
                                             .unwrap_or_else(|| ".".to_string());
                                         let stroke = if selected {
-                                            egui::Stroke::new(1.0, egui::Color32::from_rgb(123, 168, 255))
+                                            egui::Stroke::new(1.0, accent)
                                         } else {
-                                            egui::Stroke::new(1.0, egui::Color32::from_gray(66))
+                                            egui::Stroke::new(1.0, palette.border)
                                         };
                                         let fill = if selected {
-                                            egui::Color32::from_rgb(38, 48, 66)
+                                            palette.selection
                                         } else {
-                                            egui::Color32::from_rgb(34, 38, 39)
+                                            palette.widget_bg
                                         };
                                         egui::Frame::new()
                                             .fill(fill)
@@ -802,7 +948,7 @@ impl EditorApp {
                                                             selected,
                                                             egui::RichText::new(name)
                                                                 .size(13.0)
-                                                                .color(egui::Color32::from_gray(242)),
+                                                                .color(palette.text_strong),
                                                         );
                                                         if title_resp.clicked() {
                                                             self.hub_selected = Some(idx);
@@ -813,7 +959,7 @@ impl EditorApp {
                                                         ui.label(
                                                             egui::RichText::new(parent)
                                                                 .size(10.0)
-                                                                .color(egui::Color32::from_gray(150)),
+                                                                .color(palette.text_weak),
                                                         );
                                                     });
                                                     ui.with_layout(
@@ -827,13 +973,13 @@ impl EditorApp {
                                                                             .size(11.0),
                                                                     )
                                                                     .fill(if selected {
-                                                                        egui::Color32::from_rgb(32, 126, 84)
+                                                                        palette.selection
                                                                     } else {
-                                                                        egui::Color32::from_rgb(36, 96, 72)
+                                                                        palette.widget_bg
                                                                     })
                                                                     .stroke(egui::Stroke::new(
                                                                         1.0,
-                                                                        egui::Color32::from_rgb(82, 162, 126),
+                                                                        accent,
                                                                     ))
                                                                     .corner_radius(6),
                                                                 )
@@ -842,6 +988,25 @@ impl EditorApp {
                                                                 self.hub_selected = Some(idx);
                                                                 open_project_now = Some(path.clone());
                                                             }
+                                                            let pinned = self.recent.is_pinned(path);
+                                                            let star = if pinned {
+                                                                egui::RichText::new("★")
+                                                                    .color(palette.warning)
+                                                            } else {
+                                                                egui::RichText::new("☆")
+                                                                    .color(palette.text_weak)
+                                                            };
+                                                            if ui
+                                                                .add(egui::Button::new(star).frame(false))
+                                                                .on_hover_text(i18n::text(
+                                                                    self.language,
+                                                                    "recent",
+                                                                    if pinned { "unpin" } else { "pin" },
+                                                                ))
+                                                                .clicked()
+                                                            {
+                                                                pin_toggle = Some(path.clone());
+                                                            }
                                                         },
                                                     );
                                                 });
@@ -850,11 +1015,11 @@ impl EditorApp {
                                             .on_hover_text(full.as_ref());
                                         ui.add_space(6.0);
                                     }
+                                    if let Some(path) = pin_toggle {
+                                        self.toggle_recent_pin(&path);
+                                    }
                                     if let Some(path) = open_project_now {
-                                        let normalized = Self::resolve_project_file_path(&path, false);
-                                        self.current_project = Some(normalized.clone());
-                                        self.register_hub_project(&normalized);
-                                        self.show_hub = false;
+                                        self.open_project_path(&path);
                                     }
                                 });
 
@@ -868,6 +1033,10 @@ impl EditorApp {
             EngineLanguage::Pt => "Português",
             EngineLanguage::En => "English",
             EngineLanguage::Es => "Español",
+            EngineLanguage::Fr => "Français",
+            EngineLanguage::De => "Deutsch",
+            EngineLanguage::Ja => "日本語",
+            EngineLanguage::Zh => "中文",
         }
     }
 
@@ -876,106 +1045,62 @@ impl EditorApp {
             EngineLanguage::Pt => self.lang_pt_icon.as_ref(),
             EngineLanguage::En => self.lang_en_icon.as_ref(),
             EngineLanguage::Es => self.lang_es_icon.as_ref(),
+            _ => None,
         }
     }
 
     fn tr(&self, key: &'static str) -> &'static str {
-        match (self.language, key) {
-            (EngineLanguage::Pt, "menu_file") => "Arquivo",
-            (EngineLanguage::En, "menu_file") => "File",
-            (EngineLanguage::Es, "menu_file") => "Archivo",
-
-            (EngineLanguage::Pt, "menu_edit") => "Editar",
-            (EngineLanguage::En, "menu_edit") => "Edit",
-            (EngineLanguage::Es, "menu_edit") => "Editar",
-
-            (EngineLanguage::Pt, "menu_help") => "Ajuda",
-            (EngineLanguage::En, "menu_help") => "Help",
-            (EngineLanguage::Es, "menu_help") => "Ayuda",
-
-            (EngineLanguage::Pt, "animator_panel") => "Animador",
-            (EngineLanguage::En, "animator_panel") => "Animator",
-            (EngineLanguage::Es, "animator_panel") => "Animador",
-
-            (EngineLanguage::Pt, "new") => "Novo",
-            (EngineLanguage::En, "new") => "New",
-            (EngineLanguage::Es, "new") => "Nuevo",
-
-            (EngineLanguage::Pt, "save") => "Salvar",
-            (EngineLanguage::En, "save") => "Save",
-            (EngineLanguage::Es, "save") => "Guardar",
-
-            (EngineLanguage::Pt, "import") => "Importar",
-            (EngineLanguage::En, "import") => "Import",
-            (EngineLanguage::Es, "import") => "Importar",
-
-            (EngineLanguage::Pt, "exit") => "Sair",
-            (EngineLanguage::En, "exit") => "Exit",
-            (EngineLanguage::Es, "exit") => "Salir",
-
-            (EngineLanguage::Pt, "about") => "Sobre",
-            (EngineLanguage::En, "about") => "About",
-            (EngineLanguage::Es, "about") => "Acerca de",
-
-            (EngineLanguage::Pt, "scene") => "Cena",
-            (EngineLanguage::En, "scene") => "Scene",
-            (EngineLanguage::Es, "scene") => "Escena",
-
-            (EngineLanguage::Pt, "game") => "Game",
-            (EngineLanguage::En, "game") => "Game",
-            (EngineLanguage::Es, "game") => "Juego",
-            _ => key,
-        }
+        i18n::text(self.language, "main", key)
     }
 
     fn ensure_toolbar_icons_loaded(&mut self, ctx: &egui::Context) {
         if self.app_icon_texture.is_none() {
-            self.app_icon_texture = load_png_as_texture(ctx, "src/assets/icons/icon.png");
+            self.app_icon_texture = load_png_as_texture(ctx, "Editor/icons/icon.png");
         }
         if self.cena_icon.is_none() {
-            self.cena_icon = load_png_as_texture(ctx, "src/assets/icons/cena.png");
+            self.cena_icon = load_png_as_texture(ctx, "Editor/icons/cena.png");
         }
         if self.game_icon.is_none() {
-            self.game_icon = load_png_as_texture(ctx, "src/assets/icons/game.png");
+            self.game_icon = load_png_as_texture(ctx, "Editor/icons/game.png");
         }
         if self.play_icon.is_none() {
-            self.play_icon = load_png_as_texture(ctx, "src/assets/icons/play.png");
+            self.play_icon = load_png_as_texture(ctx, "Editor/icons/play.png");
         }
         if self.pause_icon.is_none() {
-            self.pause_icon = load_png_as_texture(ctx, "src/assets/icons/pause.png");
+            self.pause_icon = load_png_as_texture(ctx, "Editor/icons/pause.png");
         }
         if self.stop_icon.is_none() {
-            self.stop_icon = load_png_as_texture(ctx, "src/assets/icons/stop.png");
+            self.stop_icon = load_png_as_texture(ctx, "Editor/icons/stop.png");
         }
         if self.files_icon.is_none() {
-            self.files_icon = load_png_as_texture(ctx, "src/assets/icons/files.png");
+            self.files_icon = load_png_as_texture(ctx, "Editor/icons/files.png");
         }
         if self.rig_icon.is_none() {
-            self.rig_icon = load_png_as_texture(ctx, "src/assets/icons/rig.png");
+            self.rig_icon = load_png_as_texture(ctx, "Editor/icons/rig.png");
         }
         if self.animador_icon.is_none() {
-            self.animador_icon = load_png_as_texture(ctx, "src/assets/icons/animador.png");
+            self.animador_icon = load_png_as_texture(ctx, "Editor/icons/animador.png");
         }
         if self.fios_icon.is_none() {
-            self.fios_icon = load_png_as_texture(ctx, "src/assets/icons/fios.png");
+            self.fios_icon = load_png_as_texture(ctx, "Editor/icons/fios.png");
         }
         if self.log_icon.is_none() {
-            self.log_icon = load_png_as_texture(ctx, "src/assets/icons/log.png");
+            self.log_icon = load_png_as_texture(ctx, "Editor/icons/log.png");
         }
         if self.git_icon.is_none() {
-            self.git_icon = load_png_as_texture(ctx, "src/assets/icons/git.png");
+            self.git_icon = load_png_as_texture(ctx, "Editor/icons/git.png");
         }
         if self.terminal_icon.is_none() {
-            self.terminal_icon = load_png_as_texture(ctx, "src/assets/icons/terminal.png");
+            self.terminal_icon = load_png_as_texture(ctx, "Editor/icons/terminal.png");
         }
         if self.lang_pt_icon.is_none() {
-            self.lang_pt_icon = load_png_as_texture(ctx, "src/assets/icons/portugues.png");
+            self.lang_pt_icon = load_png_as_texture(ctx, "Editor/icons/portugues.png");
         }
         if self.lang_en_icon.is_none() {
-            self.lang_en_icon = load_png_as_texture(ctx, "src/assets/icons/ingles.png");
+            self.lang_en_icon = load_png_as_texture(ctx, "Editor/icons/ingles.png");
         }
         if self.lang_es_icon.is_none() {
-            self.lang_es_icon = load_png_as_texture(ctx, "src/assets/icons/espanhol.png");
+            self.lang_es_icon = load_png_as_texture(ctx, "Editor/icons/espanhol.png");
         }
     }
 }
@@ -986,11 +1111,18 @@ impl App for EditorApp {
     }
 
     fn update(&mut self, ctx: &egui::Context, frame: &mut Frame) {
-        // Dark theme
-        ctx.set_visuals(egui::Visuals::dark());
+        // Fecha o frame do profiler: inclui o render do frame anterior
+        self.profiler.begin_frame();
+        engine_core::profile_scope!("EditorApp::update");
+        // Tema do editor (Janela > Tema)
+        theme::apply(ctx);
         ctx.output_mut(|o| o.cursor_icon = egui::CursorIcon::Default);
         self.ensure_toolbar_icons_loaded(ctx);
-        self.fios.update_input(ctx);
+        self.update_engine_input(ctx);
+        if self.is_playing {
+            self.time.advance(ctx.input(|i| i.stable_dt));
+        }
+        self.fios.update_input(ctx, &self.input);
         self.poll_terminal_job();
         if self.show_hub {
             self.draw_hub(ctx);
@@ -1005,11 +1137,13 @@ impl App for EditorApp {
         let undo_pressed = ctx.input_mut(|i| i.consume_shortcut(&undo_shortcut));
         let redo_pressed = ctx.input_mut(|i| i.consume_shortcut(&redo_shortcut))
             || ctx.input_mut(|i| i.consume_shortcut(&redo_shortcut_alt));
-        if undo_pressed {
-            self.viewport.undo();
+        // Historico de edicao fica bloqueado durante o Play (o Stop restaura a cena)
+        let history_locked = self.viewport.in_play_mode();
+        if undo_pressed && !history_locked {
+            self.undo();
         }
-        if redo_pressed {
-            self.viewport.redo();
+        if redo_pressed && !history_locked {
+            self.redo();
         }
         if !self.windows_blur_initialized {
             self.windows_blur_initialized = true;
@@ -1080,7 +1214,7 @@ impl App for EditorApp {
                         ui.label(
                             egui::RichText::new("Dengine")
                                 .strong()
-                                .color(egui::Color32::from_gray(220)),
+                                .color(theme::palette().text_strong),
                         );
                         ui.add_space(8.0);
                         ui.label(
@@ -1108,12 +1242,12 @@ impl App for EditorApp {
                                 )
                                 .corner_radius(6)
                                 .fill(if self.fios_enabled {
-                                    egui::Color32::from_rgb(58, 84, 64)
+                                    theme::palette().selection
                                 } else {
-                                    egui::Color32::from_rgb(44, 44, 44)
+                                    theme::palette().widget_bg
                                 })
                                 .stroke(if self.fios_enabled {
-                                    egui::Stroke::new(1.0, egui::Color32::from_rgb(15, 232, 121))
+                                    egui::Stroke::new(1.0, theme::palette().accent)
                                 } else {
                                     egui::Stroke::new(1.0, egui::Color32::from_gray(70))
                                 }),
@@ -1125,15 +1259,12 @@ impl App for EditorApp {
                                 egui::Button::new("Fios")
                                     .corner_radius(6)
                                     .fill(if self.fios_enabled {
-                                        egui::Color32::from_rgb(58, 84, 64)
+                                        theme::palette().selection
                                     } else {
-                                        egui::Color32::from_rgb(44, 44, 44)
+                                        theme::palette().widget_bg
                                     })
                                     .stroke(if self.fios_enabled {
-                                        egui::Stroke::new(
-                                            1.0,
-                                            egui::Color32::from_rgb(15, 232, 121),
-                                        )
+                                        egui::Stroke::new(1.0, theme::palette().accent)
                                     } else {
                                         egui::Stroke::new(1.0, egui::Color32::from_gray(70))
                                     }),
@@ -1150,6 +1281,7 @@ impl App for EditorApp {
                                 if ui.button(self.tr("new")).clicked() {
                                     ui.close();
                                 }
+                                self.draw_open_recent_menu(ui);
                                 if ui.button(self.tr("save")).clicked() {
                                     if let Some(path) = self.current_project.clone() {
                                         let target = Self::resolve_project_file_path(&path, true);
@@ -1158,12 +1290,14 @@ impl App for EditorApp {
                                             .save_project_to_path(&target, self.language);
                                         self.current_project = Some(target.clone());
                                         self.register_hub_project(&target);
+                                        self.remember_recent_scene(&target);
                                     } else if let Some(path) =
                                         self.project.save_project_dialog(self.language)
                                     {
                                         let target = Self::resolve_project_file_path(&path, true);
                                         self.current_project = Some(target.clone());
                                         self.register_hub_project(&target);
+                                        self.remember_recent_scene(&target);
                                     }
                                     ui.close();
                                 }
@@ -1171,6 +1305,22 @@ impl App for EditorApp {
                                     self.project.import_asset_dialog(self.language);
                                     ui.close();
                                 }
+                                if ui.button(self.tr("build")).clicked() {
+                                    self.build.open = true;
+                                    ui.close();
+                                }
+                                if ui.button(self.tr("pack_bundles")).clicked() {
+                                    self.pack_asset_bundles_dialog();
+                                    ui.close();
+                                }
+                                if ui.button(self.tr("mount_archive")).clicked() {
+                                    self.mount_archive_dialog();
+                                    ui.close();
+                                }
+                                if ui.button(self.tr("pack_sprite_atlas")).clicked() {
+                                    self.pack_sprite_atlas_dialog();
+                                    ui.close();
+                                }
                                 if ui.button(self.tr("exit")).clicked() {
                                     ui.ctx().send_viewport_cmd(egui::ViewportCommand::Close);
                                     ui.close();
@@ -1180,26 +1330,106 @@ impl App for EditorApp {
                             ui.menu_button(self.tr("menu_edit"), |ui| {
                                 if ui
                                     .add_enabled(
-                                        self.viewport.can_undo(),
+                                        self.history.can_undo() && !self.viewport.in_play_mode(),
                                         egui::Button::new("Undo (Ctrl+Z)"),
                                     )
                                     .clicked()
                                 {
-                                    self.viewport.undo();
+                                    self.undo();
                                     ui.close();
                                 }
                                 if ui
                                     .add_enabled(
-                                        self.viewport.can_redo(),
+                                        self.history.can_redo() && !self.viewport.in_play_mode(),
                                         egui::Button::new("Redo (Ctrl+Shift+Z)"),
                                     )
                                     .clicked()
                                 {
-                                    self.viewport.redo();
+                                    self.redo();
+                                    ui.close();
+                                }
+                                ui.separator();
+                                if ui.button(self.tr("copy_objects")).clicked() {
+                                    self.copy_selected_objects(ui.ctx());
+                                    ui.close();
+                                }
+                                if ui
+                                    .add_enabled(
+                                        !self.entity_clipboard.is_empty(),
+                                        egui::Button::new(self.tr("paste_objects")),
+                                    )
+                                    .clicked()
+                                {
+                                    self.paste_objects();
+                                    ui.close();
+                                }
+                                if ui.button(self.tr("duplicate_objects")).clicked() {
+                                    self.duplicate_selected_objects();
+                                    ui.close();
+                                }
+                                ui.separator();
+                                if ui.button(self.tr("quick_find")).clicked() {
+                                    self.finder.show();
+                                    ui.close();
+                                }
+                                if ui.button(self.tr("preferences")).clicked() {
+                                    self.preferences.open = true;
+                                    ui.close();
+                                }
+                            });
+
+                            ui.menu_button(self.tr("menu_window"), |ui| {
+                                if ui
+                                    .checkbox(&mut self.network.open, self.tr("network"))
+                                    .clicked()
+                                {
+                                    ui.close();
+                                }
+                                if ui
+                                    .checkbox(&mut self.environment.open, self.tr("environment"))
+                                    .clicked()
+                                {
+                                    ui.close();
+                                }
+                                if ui
+                                    .checkbox(&mut self.graphics.open, self.tr("graphics"))
+                                    .clicked()
+                                {
+                                    ui.close();
+                                }
+                                if ui
+                                    .checkbox(&mut self.theme.open, self.tr("theme"))
+                                    .clicked()
+                                {
+                                    ui.close();
+                                }
+                                if ui
+                                    .checkbox(&mut self.asset_memory.open, self.tr("asset_memory"))
+                                    .clicked()
+                                {
+                                    ui.close();
+                                }
+                                if ui
+                                    .checkbox(
+                                        &mut self.material_editor.open,
+                                        self.tr("material_editor"),
+                                    )
+                                    .clicked()
+                                {
+                                    ui.close();
+                                }
+                                if ui
+                                    .checkbox(&mut self.profiler.open, self.tr("profiler"))
+                                    .clicked()
+                                {
                                     ui.close();
                                 }
                             });
 
+                            ui.menu_button(self.tr("menu_capture"), |ui| {
+                                self.capture_menu(ui);
+                            });
+
                             ui.menu_button(self.tr("menu_help"), |ui| {
                                 if ui.button(self.tr("about")).clicked() {}
                             });
@@ -1225,7 +1455,7 @@ impl App for EditorApp {
                                     egui::RichText::new(current_lang_name).size(12.0),
                                 )
                                 .corner_radius(6)
-                                .fill(egui::Color32::from_rgb(44, 44, 44))
+                                .fill(theme::palette().widget_bg)
                                 .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(70))),
                             )
                         } else {
@@ -1233,7 +1463,7 @@ impl App for EditorApp {
                                 [116.0, 24.0],
                                 egui::Button::new(current_lang_name)
                                     .corner_radius(6)
-                                    .fill(egui::Color32::from_rgb(44, 44, 44))
+                                    .fill(theme::palette().widget_bg)
                                     .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(70))),
                             )
                         };
@@ -1246,9 +1476,7 @@ impl App for EditorApp {
                         .id(egui::Id::new("language_menu_popup"))
                         .width(150.0)
                         .show(|ui| {
-                            let languages =
-                                [EngineLanguage::Pt, EngineLanguage::En, EngineLanguage::Es];
-                            for lang in languages {
+                            for lang in EngineLanguage::ALL {
                                 let name = self.language_name(lang);
                                 let selected = self.language == lang;
                                 let clicked = if let Some(icon) = self.language_icon(lang) {
@@ -1260,15 +1488,12 @@ impl App for EditorApp {
                                             egui::RichText::new(name),
                                         )
                                         .fill(if selected {
-                                            egui::Color32::from_rgb(62, 62, 62)
+                                            theme::palette().widget_hover
                                         } else {
-                                            egui::Color32::from_rgb(44, 44, 44)
+                                            theme::palette().widget_bg
                                         })
                                         .stroke(if selected {
-                                            egui::Stroke::new(
-                                                1.0,
-                                                egui::Color32::from_rgb(15, 232, 121),
-                                            )
+                                            egui::Stroke::new(1.0, theme::palette().accent)
                                         } else {
                                             egui::Stroke::new(1.0, egui::Color32::from_gray(70))
                                         })
@@ -1280,15 +1505,12 @@ impl App for EditorApp {
                                         [138.0, 24.0],
                                         egui::Button::new(name)
                                             .fill(if selected {
-                                                egui::Color32::from_rgb(62, 62, 62)
+                                                theme::palette().widget_hover
                                             } else {
-                                                egui::Color32::from_rgb(44, 44, 44)
+                                                theme::palette().widget_bg
                                             })
                                             .stroke(if selected {
-                                                egui::Stroke::new(
-                                                    1.0,
-                                                    egui::Color32::from_rgb(15, 232, 121),
-                                                )
+                                                egui::Stroke::new(1.0, theme::palette().accent)
                                             } else {
                                                 egui::Stroke::new(1.0, egui::Color32::from_gray(70))
                                             })
@@ -1401,13 +1623,13 @@ impl App for EditorApp {
                             )
                             .corner_radius(8)
                             .fill(if self.selected_mode == ToolbarMode::Cena {
-                                egui::Color32::from_rgb(62, 62, 62)
+                                theme::palette().widget_hover
                             } else {
-                                egui::Color32::from_rgb(44, 44, 44)
+                                theme::palette().widget_bg
                             })
                             .stroke(
                                 if self.selected_mode == ToolbarMode::Cena {
-                                    egui::Stroke::new(1.0, egui::Color32::from_rgb(15, 232, 121))
+                                    egui::Stroke::new(1.0, theme::palette().accent)
                                 } else {
                                     egui::Stroke::new(1.0, egui::Color32::from_gray(70))
                                 },
@@ -1423,15 +1645,12 @@ impl App for EditorApp {
                                     egui::Button::new(self.tr("scene"))
                                         .corner_radius(8)
                                         .fill(if self.selected_mode == ToolbarMode::Cena {
-                                            egui::Color32::from_rgb(62, 62, 62)
+                                            theme::palette().widget_hover
                                         } else {
-                                            egui::Color32::from_rgb(44, 44, 44)
+                                            theme::palette().widget_bg
                                         })
                                         .stroke(if self.selected_mode == ToolbarMode::Cena {
-                                            egui::Stroke::new(
-                                                1.0,
-                                                egui::Color32::from_rgb(15, 232, 121),
-                                            )
+                                            egui::Stroke::new(1.0, theme::palette().accent)
                                         } else {
                                             egui::Stroke::new(1.0, egui::Color32::from_gray(70))
                                         }),
@@ -1450,13 +1669,13 @@ impl App for EditorApp {
                             )
                             .corner_radius(8)
                             .fill(if self.selected_mode == ToolbarMode::Game {
-                                egui::Color32::from_rgb(62, 62, 62)
+                                theme::palette().widget_hover
                             } else {
-                                egui::Color32::from_rgb(44, 44, 44)
+                                theme::palette().widget_bg
                             })
                             .stroke(
                                 if self.selected_mode == ToolbarMode::Game {
-                                    egui::Stroke::new(1.0, egui::Color32::from_rgb(15, 232, 121))
+                                    egui::Stroke::new(1.0, theme::palette().accent)
                                 } else {
                                     egui::Stroke::new(1.0, egui::Color32::from_gray(70))
                                 },
@@ -1472,15 +1691,12 @@ impl App for EditorApp {
                                     egui::Button::new(self.tr("game"))
                                         .corner_radius(8)
                                         .fill(if self.selected_mode == ToolbarMode::Game {
-                                            egui::Color32::from_rgb(62, 62, 62)
+                                            theme::palette().widget_hover
                                         } else {
-                                            egui::Color32::from_rgb(44, 44, 44)
+                                            theme::palette().widget_bg
                                         })
                                         .stroke(if self.selected_mode == ToolbarMode::Game {
-                                            egui::Stroke::new(
-                                                1.0,
-                                                egui::Color32::from_rgb(15, 232, 121),
-                                            )
+                                            egui::Stroke::new(1.0, theme::palette().accent)
                                         } else {
                                             egui::Stroke::new(1.0, egui::Color32::from_gray(70))
                                         }),
@@ -1498,15 +1714,12 @@ impl App for EditorApp {
                                 egui::Button::new(animator_txt)
                                     .corner_radius(8)
                                     .fill(if self.selected_mode == ToolbarMode::Animator {
-                                        egui::Color32::from_rgb(62, 62, 62)
+                                        theme::palette().widget_hover
                                     } else {
-                                        egui::Color32::from_rgb(44, 44, 44)
+                                        theme::palette().widget_bg
                                     })
                                     .stroke(if self.selected_mode == ToolbarMode::Animator {
-                                        egui::Stroke::new(
-                                            1.0,
-                                            egui::Color32::from_rgb(15, 232, 121),
-                                        )
+                                        egui::Stroke::new(1.0, theme::palette().accent)
                                     } else {
                                         egui::Stroke::new(1.0, egui::Color32::from_gray(70))
                                     }),
@@ -1518,7 +1731,7 @@ impl App for EditorApp {
                     },
                 );
 
-                let controls_width = control_size.x * 2.0 + ui.spacing().item_spacing.x;
+                let controls_width = control_size.x * 4.0 + ui.spacing().item_spacing.x * 3.0;
                 let controls_rect = egui::Rect::from_center_size(
                     row_rect.center(),
                     egui::Vec2::new(controls_width, row_height),
@@ -1529,29 +1742,52 @@ impl App for EditorApp {
                             .with_main_align(egui::Align::Center),
                     ),
                     |ui| {
-                        let play_pause_texture = if self.is_playing {
+                        let play_pause_texture = if self.is_playing && !self.time.paused {
                             self.pause_icon.as_ref()
                         } else {
                             self.play_icon.as_ref()
                         };
 
                         if let Some(play_pause_texture) = play_pause_texture {
-                            let play_pause_clicked = ui
-                                .add_sized(
-                                    control_size,
-                                    egui::Button::image(
-                                        egui::Image::new(play_pause_texture)
-                                            .fit_to_exact_size(egui::Vec2::new(14.0, 14.0)),
-                                    )
-                                    .corner_radius(8),
+                            let play_pause_resp = ui.add_sized(
+                                control_size,
+                                egui::Button::image(
+                                    egui::Image::new(play_pause_texture)
+                                        .fit_to_exact_size(egui::Vec2::new(14.0, 14.0)),
                                 )
-                                .clicked();
-                            if play_pause_clicked {
-                                self.is_playing = !self.is_playing;
+                                .corner_radius(8),
+                            );
+                            if play_pause_resp.clicked() {
                                 if self.is_playing {
-                                    self.selected_mode = ToolbarMode::Game;
+                                    self.time.toggle_pause();
+                                } else {
+                                    self.enter_play_mode();
                                 }
                             }
+                            play_pause_resp.context_menu(|ui| {
+                                ui.label(i18n::text(self.language, "main", "time_scale"));
+                                for scale in [0.1, 0.25, 0.5, 1.0, 2.0] {
+                                    let label = format!("{scale}x");
+                                    if ui.radio(self.time.scale == scale, label).clicked() {
+                                        self.time.set_scale(scale);
+                                        ui.close();
+                                    }
+                                }
+                            });
+                        }
+
+                        // Um update fixo por clique (pausa se estiver rodando)
+                        let step_resp = ui
+                            .add_enabled(
+                                self.is_playing,
+                                egui::Button::new(egui::RichText::new("⏭").size(14.0))
+                                    .corner_radius(8)
+                                    .min_size(control_size),
+                            )
+                            .on_hover_text(i18n::text(self.language, "main", "step_one_frame"));
+                        if step_resp.clicked() {
+                            self.time.step();
+                            ctx.request_repaint();
                         }
 
                         if let Some(stop_icon) = &self.stop_icon {
@@ -1566,31 +1802,53 @@ impl App for EditorApp {
                                 )
                                 .clicked();
                             if stop_clicked {
-                                self.is_playing = false;
-                                self.selected_mode = ToolbarMode::Cena;
+                                self.exit_play_mode();
                             }
                         }
+
+                        let recording = self
+                            .viewport_gpu
+                            .as_ref()
+                            .is_some_and(|gpu| gpu.is_recording());
+                        let record_resp = ui
+                            .add_sized(
+                                control_size,
+                                egui::Button::new(egui::RichText::new("⏺").size(14.0).color(
+                                    if recording {
+                                        egui::Color32::from_rgb(230, 60, 60)
+                                    } else {
+                                        egui::Color32::from_gray(170)
+                                    },
+                                ))
+                                .corner_radius(8),
+                            )
+                            .on_hover_text(i18n::text(
+                                self.language,
+                                "main",
+                                if recording {
+                                    "stop_recording"
+                                } else {
+                                    "record_video"
+                                },
+                            ));
+                        if record_resp.clicked() {
+                            self.toggle_recording();
+                        }
+                        record_resp.context_menu(|ui| self.recording_menu(ui));
                     },
                 );
             });
 
         let dock_bar_h = 48.0;
-        let project_panel_h = if self.project_collapsed {
-            0.0
-        } else {
-            self.project.docked_bottom_height()
-        };
-        let project_bottom = project_panel_h + dock_bar_h;
-        let left_reserved = self.inspector.docked_left_width() + self.hierarchy.docked_left_width();
-        let right_reserved =
-            self.inspector.docked_right_width() + self.hierarchy.docked_right_width();
+        self.update_dock_layout(ctx, dock_bar_h);
+        let (left_reserved, right_reserved, project_bottom) = self.dock.margins();
         let mode_label = match self.selected_mode {
             ToolbarMode::Cena => "Cena",
             ToolbarMode::Game => "Game",
             ToolbarMode::Animator => "Animator",
         };
         let hierarchy_selected = self.hierarchy.selected_object_name().to_string();
-        self.viewport.set_selected_object(&hierarchy_selected);
+        self.viewport.set_selection(self.hierarchy.selection());
         let inspector_transform = self
             .viewport
             .object_transform_components(&hierarchy_selected);
@@ -1607,27 +1865,28 @@ impl App for EditorApp {
 
         let _selected_hierarchy = self.hierarchy.selected_object_name().to_string();
 
-        if self.fios_enabled {
-            self.fios.draw_embedded(
-                ctx,
-                left_reserved,
-                right_reserved,
-                project_bottom,
-                self.language,
-            );
-        } else {
-            self.fios.clear_embedded_rect();
-            self.viewport.show(
-                ctx,
-                mode_label,
-                left_reserved,
-                right_reserved,
-                project_bottom,
-                self.viewport_gpu.as_ref(),
-            );
-            if let Some(selected_in_viewport) = self.viewport.selected_object_name() {
-                self.hierarchy.set_selected_object(selected_in_viewport);
-            }
+        match self.dock.panel_rect(DockPanel::Animator) {
+            Some(rect) => self.fios.draw_embedded(ctx, rect, self.language),
+            None => self.fios.clear_embedded_rect(),
+        }
+        self.update_timeline(ctx);
+        let collider_gizmos = self.collider_gizmos();
+        self.viewport.set_collider_gizmos(collider_gizmos);
+        self.viewport.set_debug_draw(self.scripts.debug_commands());
+        self.update_rig_editor();
+        let selection_before = self.viewport.selection().clone();
+        self.viewport.show(
+            ctx,
+            mode_label,
+            left_reserved,
+            right_reserved,
+            project_bottom,
+            self.viewport_gpu.as_ref(),
+        );
+        // Só o que mudou na viewport volta: objetos sem malha (câmera,
+        // grupos) ficam fora da seleção dela
+        if self.viewport.selection() != &selection_before {
+            self.hierarchy.set_selection(self.viewport.selection());
         }
 
         let current_texture = self
@@ -1635,52 +1894,107 @@ impl App for EditorApp {
             .object_texture_path(self.hierarchy.selected_object_name());
 
         // Janela Inspetor
-        self.inspector.show(
-            ctx,
-            0.0,
-            0.0,
-            project_bottom,
-            self.language,
-            self.hierarchy.selected_object_name(),
-            inspector_transform,
-            &animation_controllers,
-            &animation_modules,
-            &fbx_animation_clips,
-            &mut self.viewport.light_yaw,
-            &mut self.viewport.light_pitch,
-            &mut self.viewport.light_color,
-            &mut self.viewport.light_intensity,
-            &mut self.viewport.light_enabled,
-            current_texture,
-        );
+        self.update_audio_preview();
+        self.sync_import_settings();
+        let hierarchy_drag = self
+            .hierarchy
+            .dragging_object_name()
+            .map(str::to_string)
+            .map(|name| {
+                let guid = self.hierarchy.object_guid(&name);
+                (name, guid)
+            });
+        self.inspector.set_hierarchy_drag(hierarchy_drag);
+        self.inspector.set_search_index(self.project.search_index());
+        self.inspector
+            .set_selected_objects(self.hierarchy.selection().names());
+        if let Some(rect) = self.dock.panel_rect(DockPanel::Inspector) {
+            self.inspector.show(
+                ctx,
+                rect,
+                self.language,
+                self.hierarchy.selected_object_name(),
+                inspector_transform,
+                &animation_controllers,
+                &animation_modules,
+                &fbx_animation_clips,
+                &mut self.viewport.light_yaw,
+                &mut self.viewport.light_pitch,
+                &mut self.viewport.light_color,
+                &mut self.viewport.light_intensity,
+                &mut self.viewport.light_enabled,
+                current_texture,
+            );
+        }
         if let Some((object_name, pos, rot, scale)) = self.inspector.take_transform_live_request() {
+            self.record_viewport_step();
             let _ = self
                 .viewport
-                .set_object_transform_components(&object_name, pos, rot, scale);
+                .set_selection_transform_components(&object_name, pos, rot, scale);
         }
         if let Some((object_name, pos, rot, scale)) = self.inspector.take_transform_apply_request()
         {
             let _ = self
                 .viewport
                 .apply_object_transform_components(&object_name, pos, rot, scale);
+            for other in self.selection_targets(&object_name) {
+                if other == object_name {
+                    continue;
+                }
+                if let Some((pos, rot, scale)) = self.viewport.object_transform_components(&other) {
+                    let _ = self
+                        .viewport
+                        .apply_object_transform_components(&other, pos, rot, scale);
+                }
+            }
         }
         if let Some((object_name, texture_path)) = self.inspector.take_texture_request() {
-            self.viewport
-                .set_object_texture_path(&object_name, texture_path);
+            self.record_viewport_step();
+            for name in self.selection_targets(&object_name) {
+                self.viewport
+                    .set_object_texture_path(&name, texture_path.clone());
+            }
+        }
+        if let Some(texture_path) = self.inspector.take_texture_reload_request() {
+            if let Some(gpu) = &self.viewport_gpu {
+                gpu.reload_texture(&texture_path);
+            }
         }
         if let Some((object_name, shader_path)) = self.inspector.take_shader_request() {
+            self.record_viewport_step();
+            for name in self.selection_targets(&object_name) {
+                self.viewport
+                    .set_object_material_path(&name, shader_path.clone());
+            }
+        }
+        // Material solto sobre uma malha da viewport, resolvida pelo picking
+        if let Some((object_name, material_path)) = self.viewport.take_material_drop() {
+            self.record_viewport_step();
             self.viewport
-                .set_object_material_path(&object_name, shader_path);
+                .set_object_material_path(&object_name, Some(material_path.clone()));
+            let before = self.inspector.object_components(&object_name);
+            self.inspector
+                .set_object_shader(&object_name, &material_path);
+            self.inspector.record_component_edit(&object_name, before);
+            self.log.push(
+                LogLevel::Info,
+                "Material",
+                format!("{material_path} -> {object_name}"),
+            );
         }
         // Handle material drop from hierarchy
         if let Some((object_name, material_path)) = HierarchyWindow::take_pending_material_drop(ctx)
         {
-            eprintln!(
+            log::debug!(
                 "[MATERIAL] Drop da hierarchy: objeto={}, material={}",
-                object_name, material_path
+                object_name,
+                material_path
             );
-            self.viewport
-                .set_object_material_path(&object_name, Some(material_path));
+            self.record_viewport_step();
+            for name in self.selection_targets(&object_name) {
+                self.viewport
+                    .set_object_material_path(&name, Some(material_path.clone()));
+            }
         }
 
         let animator_targets = self.inspector.animator_targets();
@@ -1729,13 +2043,14 @@ impl App for EditorApp {
         let look = self.fios.look_axis();
         let action = self.fios.action_signal();
         if self.is_playing
+            && self.time.is_running()
             && (axis[0].abs() > 1e-4
                 || axis[1].abs() > 1e-4
                 || look[0].abs() > 1e-4
                 || look[1].abs() > 1e-4
                 || action.abs() > 1e-4)
         {
-            let dt = ctx.input(|i| i.stable_dt).max(1.0 / 240.0);
+            let dt = self.time.delta;
             let len = (axis[0] * axis[0] + axis[1] * axis[1]).sqrt().max(1.0);
             let dir_x = axis[0] / len;
             let dir_z = axis[1] / len;
@@ -1752,7 +2067,7 @@ impl App for EditorApp {
                         .viewport
                         .rotate_object_by(&name, [look[1] * r_step, -look[0] * r_step, 0.0]);
                 }
-                if action.abs() > 0.01 && !self.rigidbody_vertical_vel.contains_key(&name) {
+                if action.abs() > 0.01 && !self.physics.has_body(&name) {
                     let a_step = ctrl.action_speed * dt;
                     let _ = self
                         .viewport
@@ -1760,51 +2075,49 @@ impl App for EditorApp {
                 }
             }
         }
-        if self.is_playing {
-            let dt = ctx.input(|i| i.stable_dt).max(1.0 / 240.0);
-            let rb_targets = self.inspector.rigidbody_targets();
-            let live_names: HashSet<String> = rb_targets.iter().map(|(n, _)| n.clone()).collect();
-            self.rigidbody_vertical_vel
-                .retain(|name, _| live_names.contains(name));
-
-            for (name, rb) in rb_targets {
-                let mut vy = *self.rigidbody_vertical_vel.get(&name).unwrap_or(&0.0);
-                if let Some((pos, _, _)) = self.viewport.object_transform_components(&name) {
-                    let on_ground = pos[1] <= 0.001;
-                    if action > 0.5 && on_ground {
-                        vy = rb.jump_impulse;
-                    }
-                }
-                vy += rb.gravity[1] * dt;
-                let dy = vy * dt;
-                if dy.abs() > 1e-6 {
-                    let _ = self.viewport.move_object_by(&name, [0.0, dy, 0.0]);
-                }
-                if let Some((mut pos, rot, scale)) =
-                    self.viewport.object_transform_components(&name)
-                {
-                    if pos[1] < 0.0 {
-                        pos[1] = 0.0;
-                        if vy < 0.0 {
-                            vy = 0.0;
-                        }
-                        let _ = self
-                            .viewport
-                            .set_object_transform_components(&name, pos, rot, scale);
-                    }
-                }
-                self.rigidbody_vertical_vel.insert(name, vy);
+        self.run_gameplay_physics(action);
+        self.run_gameplay_scripts();
+        self.run_camera_follow();
+        self.run_network(ctx);
+        let delete_requests = self.viewport.take_pending_delete_objects();
+        if !delete_requests.is_empty() {
+            self.hierarchy.request_delete_objects(&delete_requests);
+            // A confirmação aparece na hierarquia, mesmo se ela era uma aba escondida
+            self.dock.activate(DockPanel::Hierarchy);
+        }
+        if self.hierarchy.search_active() {
+            let components = self
+                .hierarchy
+                .scene_objects()
+                .into_iter()
+                .map(|name| {
+                    let names = self.inspector.component_names(&name);
+                    (name, names)
+                })
+                .collect();
+            self.hierarchy.set_search_components(components);
+        }
+        let hierarchy_dragged = self.hierarchy.dragging_object_name().map(str::to_string);
+        match self.dock.panel_rect(DockPanel::Hierarchy) {
+            Some(rect) => self.hierarchy.show(ctx, rect, self.language),
+            None => self.hierarchy.clear_panel_rect(),
+        }
+        self.collect_history_edits();
+        // Objeto da hierarquia solto no Projeto vira prefab
+        if let Some(object_name) = hierarchy_dragged {
+            let over_project = ctx
+                .input(|i| i.pointer.hover_pos())
+                .is_some_and(|pos| self.project.contains_point(pos));
+            if self.hierarchy.dragging_object_name().is_none() && over_project {
+                self.create_prefab(&object_name);
             }
-        } else {
-            self.rigidbody_vertical_vel.clear();
         }
-        let i_left = self.inspector.docked_left_width();
-        let i_right = self.inspector.docked_right_width();
-        if let Some(delete_request) = self.viewport.take_pending_delete_object() {
-            self.hierarchy.request_delete_by_name(&delete_request);
+        if let Some(request) = self.hierarchy.take_clipboard_request() {
+            self.handle_clipboard_request(ctx, request);
+        }
+        if let Some(request) = self.hierarchy.take_scene_request() {
+            self.handle_scene_request(request);
         }
-        self.hierarchy
-            .show(ctx, i_left, i_right, project_bottom, self.language);
         while let Some(req) = self.hierarchy.take_spawn_primitive_request() {
             let _ = self.viewport.spawn_primitive(req.kind, &req.object_name);
         }
@@ -1835,41 +2148,88 @@ impl App for EditorApp {
             self.inspector
                 .set_object_light(&light_req.object_name, light);
         }
+        if let Some((root, children)) = self.viewport.take_imported_tree() {
+            self.hierarchy.set_imported_children(&root, children);
+        }
+        for (name, collider) in self.viewport.take_generated_colliders() {
+            self.inspector.add_generated_collider(&name, collider);
+        }
         for name in self.viewport.scene_object_names() {
             if self.hierarchy.object_is_deleted(&name) {
                 let _ = self.viewport.remove_scene_object(&name);
                 self.inspector.remove_object_data(&name);
             }
         }
-
-        // Sincronizar TODAS as luzes do inspetor com a viewport
-        for name in self.viewport.scene_object_names() {
-            if let Some(light) = self.inspector.get_object_light(&name) {
-                match light.light_type {
-                    inspector::LightType::Directional => {
-                        // Sincronizar luz direcional global
-                        self.viewport.light_enabled = light.enabled;
-                        self.viewport.light_color = light.color;
-                        self.viewport.light_intensity = light.intensity;
-                        self.viewport.light_yaw = light.yaw;
-                        self.viewport.light_pitch = light.pitch;
-                    }
-                    inspector::LightType::Point => {
-                        // TODO: Implementar point lights na viewport GPU
-                        // Seria necessário passar múltiplas luzes para o shader
-                    }
-                    inspector::LightType::Spot => {
-                        // TODO: Implementar spot lights na viewport GPU
-                        // Seria necessário passar múltiplas luzes com parâmetros de cone para o shader
-                    }
-                }
-            }
+        // Pais definidos na Hierarquia; o filho fica onde estava no mundo
+        let scene_objects = self.viewport.scene_object_names();
+        for name in &scene_objects {
+            let parent = self
+                .hierarchy
+                .object_parent(name)
+                .filter(|p| scene_objects.iter().any(|o| o == p));
+            self.viewport.set_object_parent(name, parent, true);
         }
 
+        // Sincronizar TODAS as luzes do inspetor com a viewport.
+        // A "Directional Light" padrão é a luz global; as demais vão para o forward+.
+        let scene_lights = self
+            .viewport
+            .scene_object_names()
+            .into_iter()
+            .filter_map(|name| {
+                let light = self.inspector.get_object_light(&name)?;
+                Some((name, light))
+            })
+            .collect();
+        self.viewport.set_scene_lights(scene_lights);
+        self.viewport.set_lod_groups(self.inspector.lod_targets());
+        self.viewport
+            .set_particle_emitters(self.inspector.particle_targets());
+        self.viewport.set_terrains(self.inspector.terrain_targets());
+        self.viewport
+            .set_water_surfaces(self.inspector.water_targets());
+        self.viewport.set_sprites(self.inspector.sprite_targets());
+        self.viewport.set_texts(self.inspector.text3d_targets());
+        self.viewport.set_ui_texts(self.inspector.ui_text_targets());
+        // Câmeras que renderizam para textura não aparecem no modo Game
+        let game_cameras = self
+            .inspector
+            .camera_targets()
+            .into_iter()
+            .filter(|(_, cfg)| cfg.target_texture.trim().is_empty())
+            .map(|(name, cfg)| (name, cfg.camera))
+            .collect();
+        self.viewport.set_game_cameras(game_cameras);
+        self.viewport
+            .set_game_view(self.selected_mode == ToolbarMode::Game);
+        self.sync_environment(ctx);
+        self.sync_post_process();
+        self.sync_graphics_settings();
+        self.sync_editor_preferences(ctx);
+        self.sync_autosave();
+        self.sync_camera_bookmarks();
+        self.enforce_memory_budget();
+        self.sync_shader_reload(ctx);
+        self.sync_log();
+        self.sync_hot_reload(ctx);
+        self.sync_captures();
+        self.sync_prefabs();
+
         let engine_busy = self.is_playing;
 
-        if !self.project_collapsed && self.project.show(ctx, self.language, dock_bar_h) {
-            self.project_collapsed = true;
+        match self.dock.panel_rect(DockPanel::Project) {
+            Some(rect) => {
+                if self.project.show(ctx, self.language, rect) {
+                    self.project_collapsed = true;
+                }
+            }
+            None => self.project.clear_panel_rect(),
+        }
+        if let Some(path) = self.project.take_material_open_request() {
+            self.material_editor.open_material(path);
+        }
+        if let Some(path) = self.project.take_prefab_open_request() {
+            self.open_prefab(&path);
         }
 
         let full_rect = ctx.available_rect();
@@ -1888,7 +2248,7 @@ impl App for EditorApp {
                 ui.painter().rect_stroke(
                     rect,
                     0.0,
-                    egui::Stroke::new(1.0, egui::Color32::from_rgb(58, 58, 58)),
+                    egui::Stroke::new(1.0, theme::palette().border),
                     egui::StrokeKind::Outside,
                 );
 
@@ -1906,11 +2266,8 @@ impl App for EditorApp {
                 );
                 if files_resp.hovered() {
                     ui.output_mut(|o| o.cursor_icon = egui::CursorIcon::PointingHand);
-                    ui.painter().rect_filled(
-                        files_rect.expand(2.0),
-                        3.0,
-                        egui::Color32::from_rgb(58, 58, 58),
-                    );
+                    ui.painter()
+                        .rect_filled(files_rect.expand(2.0), 3.0, theme::palette().border);
                 }
                 if files_resp.clicked() {
                     self.project_collapsed = !self.project_collapsed;
@@ -1940,9 +2297,9 @@ impl App for EditorApp {
                         rig_rect.expand(2.0),
                         3.0,
                         if self.rig_enabled {
-                            egui::Color32::from_rgb(58, 84, 64)
+                            theme::palette().selection
                         } else {
-                            egui::Color32::from_rgb(58, 58, 58)
+                            theme::palette().border
                         },
                     );
                 }
@@ -1976,9 +2333,9 @@ impl App for EditorApp {
                         animator_rect.expand(2.0),
                         3.0,
                         if self.animator_enabled {
-                            egui::Color32::from_rgb(58, 84, 64)
+                            theme::palette().selection
                         } else {
-                            egui::Color32::from_rgb(58, 58, 58)
+                            theme::palette().border
                         },
                     );
                 }
@@ -2013,9 +2370,9 @@ impl App for EditorApp {
                         fios_rect.expand(2.0),
                         3.0,
                         if self.fios_enabled {
-                            egui::Color32::from_rgb(58, 84, 64)
+                            theme::palette().selection
                         } else {
-                            egui::Color32::from_rgb(58, 58, 58)
+                            theme::palette().border
                         },
                     );
                 }
@@ -2050,9 +2407,9 @@ impl App for EditorApp {
                         log_rect.expand(2.0),
                         3.0,
                         if self.log_enabled {
-                            egui::Color32::from_rgb(58, 84, 64)
+                            theme::palette().selection
                         } else {
-                            egui::Color32::from_rgb(58, 58, 58)
+                            theme::palette().border
                         },
                     );
                 }
@@ -2083,9 +2440,9 @@ impl App for EditorApp {
                         git_rect.expand(2.0),
                         3.0,
                         if self.git_enabled {
-                            egui::Color32::from_rgb(58, 84, 64)
+                            theme::palette().selection
                         } else {
-                            egui::Color32::from_rgb(58, 58, 58)
+                            theme::palette().border
                         },
                     );
                 }
@@ -2116,9 +2473,9 @@ impl App for EditorApp {
                         terminal_rect.expand(2.0),
                         3.0,
                         if self.terminai.terminal_enabled {
-                            egui::Color32::from_rgb(58, 84, 64)
+                            theme::palette().selection
                         } else {
-                            egui::Color32::from_rgb(58, 58, 58)
+                            theme::palette().border
                         },
                     );
                 }
@@ -2135,7 +2492,7 @@ impl App for EditorApp {
 
                 let label_y = rect.bottom() - 10.0;
                 let label_font = egui::FontId::proportional(12.0);
-                let label_color = egui::Color32::from_gray(190);
+                let label_color = theme::palette().text;
                 let letter_spacing = 0.8_f32;
                 let draw_spaced_label = |center: egui::Pos2, text: &str| {
                     let mut widths = Vec::new();
@@ -2187,7 +2544,7 @@ impl App for EditorApp {
                             ui.add(
                                 egui::Spinner::new()
                                     .size(16.0)
-                                    .color(egui::Color32::from_rgb(15, 232, 121)),
+                                    .color(theme::palette().accent),
                             );
                         },
                     );
@@ -2209,6 +2566,20 @@ impl App for EditorApp {
                 let drag_path = self.project.dragging_asset_path();
                 if self.fios_enabled && self.fios.contains_point(pos) {
                     let _ = self.fios.on_asset_dropped(asset_name, drag_path.as_deref());
+                } else if (self.viewport.contains_point(pos) || self.hierarchy.contains_point(pos))
+                    && drag_path.as_deref().is_some_and(prefab::is_prefab)
+                {
+                    if let Some(path) = drag_path {
+                        self.instantiate_prefab(&path);
+                    }
+                } else if self.viewport.contains_point(pos)
+                    && drag_path
+                        .as_deref()
+                        .is_some_and(material_editor::is_material)
+                {
+                    if let Some(path) = drag_path {
+                        self.viewport.on_material_dropped(pos, &path);
+                    }
                 } else if self.viewport.contains_point(pos) {
                     let object_name = self.hierarchy.on_asset_dropped(asset_name);
                     if let Some(path) = drag_path {
@@ -2219,11 +2590,29 @@ impl App for EditorApp {
                     }
                 } else if self.hierarchy.contains_point(pos) {
                     self.hierarchy.on_asset_dropped(asset_name);
+                } else if self.inspector.material_slot_contains(pos) {
+                    if let Some(path) = drag_path.filter(|path| material_editor::is_material(path))
+                    {
+                        self.inspector.on_material_dropped(pos, &path);
+                    }
+                } else if self.material_editor.contains_point(pos) {
+                    if let Some(path) = drag_path {
+                        self.material_editor.on_asset_dropped(pos, &path);
+                    }
+                } else if self.timeline.contains_point(pos) {
+                    if let Some(path) = drag_path {
+                        self.open_timeline_clip(&path);
+                    }
+                } else if self.rig_editor.contains_point(pos) {
+                    if let Some(path) = drag_path {
+                        self.open_rig(&path);
+                    }
                 }
             }
             self.project.clear_dragging_asset();
         }
 
+        self.handle_clipboard_shortcuts(ctx);
         if delete_pressed && !ctx.wants_keyboard_input() {
             if let Some(pos) = drop_pos {
                 if self.viewport.contains_point(pos) {
@@ -2319,7 +2708,7 @@ impl App for EditorApp {
                     painter.rect_stroke(
                         rect.shrink(2.0),
                         6.0,
-                        egui::Stroke::new(2.0, egui::Color32::from_rgb(15, 232, 121)),
+                        egui::Stroke::new(2.0, theme::palette().accent),
                         egui::StrokeKind::Outside,
                     );
                     if dragging_anim_clip {
@@ -2346,7 +2735,7 @@ impl App for EditorApp {
                     painter.rect_stroke(
                         rect.shrink(2.0),
                         4.0,
-                        egui::Stroke::new(2.0, egui::Color32::from_rgb(15, 232, 121)),
+                        egui::Stroke::new(2.0, theme::palette().accent),
                         egui::StrokeKind::Outside,
                     );
                 }
@@ -2355,14 +2744,46 @@ impl App for EditorApp {
                     painter.rect_stroke(
                         rect.shrink(2.0),
                         6.0,
-                        egui::Stroke::new(2.0, egui::Color32::from_rgb(15, 232, 121)),
+                        egui::Stroke::new(2.0, theme::palette().accent),
                         egui::StrokeKind::Outside,
                     );
                 }
             }
         }
 
+        self.draw_network_window(ctx);
+        self.draw_environment_window(ctx);
+        self.draw_graphics_window(ctx);
+        self.draw_theme_window(ctx);
+        self.draw_preferences_window(ctx);
+        self.draw_quick_finder(ctx);
+        self.draw_build_window(ctx);
+        self.draw_asset_memory_window(ctx);
+        if let Some(rect) = self.dock.panel_rect(DockPanel::Log) {
+            self.draw_log_panel(ctx, rect);
+        }
+        if let Some(rect) = self.dock.panel_rect(DockPanel::Git) {
+            self.draw_git_panel(ctx, rect);
+        }
+        match self.dock.panel_rect(DockPanel::Material) {
+            Some(rect) => self.draw_material_editor(ctx, rect),
+            None => self.material_editor.clear_panel_rect(),
+        }
+        match self.dock.panel_rect(DockPanel::Timeline) {
+            Some(rect) => self.draw_timeline_panel(ctx, rect),
+            None => self.timeline.clear_panel_rect(),
+        }
+        match self.dock.panel_rect(DockPanel::Rig) {
+            Some(rect) => self.draw_rig_panel(ctx, rect),
+            None => self.rig_editor.clear_panel_rect(),
+        }
+        if let Some(rect) = self.dock.panel_rect(DockPanel::Profiler) {
+            self.draw_profiler_panel(ctx, rect);
+        }
         self.draw_terminal_window(ctx);
+        self.draw_prefab_breadcrumb(ctx);
+        self.draw_play_tint(ctx);
+        self.record_history(ctx);
     }
 }
 fn enable_windows_backdrop_blur(frame: &Frame) -> bool {
@@ -2426,14 +2847,31 @@ fn collect_deng_files(root: &Path, current: &Path, depth: usize, out: &mut Vec<P
                 .strip_prefix(root)
                 .map(PathBuf::from)
                 .unwrap_or(path.clone());
-            eprintln!("[HUB] Encontrado .deng: {:?}", rel);
+            log::debug!("[HUB] Encontrado .deng: {:?}", rel);
             out.push(rel);
         }
     }
 }
 
+/// Arquivos embutidos do editor (ícones, fontes): `src/assets` do diretório atual
+/// ou, rodando de outra pasta, o do crate
+fn mount_editor_files() {
+    let local = Path::new("src").join("assets");
+    let dir = if local.is_dir() {
+        local
+    } else {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("assets")
+    };
+    engine_render::vfs_mut().mount("Editor", engine_render::DirSource::new(dir));
+}
+
 fn main() -> eframe::Result<()> {
-    let app_icon = load_icon_data_from_png("src/assets/icons/icon.png");
+    log_panel::install_logger();
+    mount_editor_files();
+    i18n::load();
+    let app_icon = load_icon_data_from_png("Editor/icons/icon.png");
     let options = NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_title("Dengine Editor")
@@ -2457,6 +2895,7 @@ fn main() -> eframe::Result<()> {
         "Dengine Editor",
         options,
         Box::new(|cc| {
+            i18n::install_fonts(&cc.egui_ctx);
             let mut app = EditorApp {
                 inspector: InspectorWindow::new(),
                 hierarchy: HierarchyWindow::new(),
@@ -2487,7 +2926,7 @@ fn main() -> eframe::Result<()> {
                 fios_enabled: false,
                 log_enabled: false,
                 git_enabled: false,
-                language: EngineLanguage::Pt,
+                language: i18n::system_language(),
                 project_collapsed: false,
                 windows_blur_initialized: false,
                 last_pointer_pos: None,
@@ -2499,9 +2938,47 @@ fn main() -> eframe::Result<()> {
                 current_project: None,
                 terminai: terminai::TerminAiState::new(),
                 fios: fios::FiosState::new(),
-                rigidbody_vertical_vel: HashMap::new(),
+                finder: finder::QuickFinder::default(),
+                input: engine_core::Input::default(),
+                gamepads: gilrs::Gilrs::new().ok(),
+                time: engine_core::Time::default(),
+                audio_preview: audio::AudioPreview::default(),
+                network: net::NetworkPanel::default(),
+                environment: environment::EnvironmentPanel::default(),
+                post_process: post_process::PostProcessStack::default(),
+                graphics: graphics::GraphicsSettings::default(),
+                build: build::BuildSettings::default(),
+                asset_memory: asset_memory::AssetMemoryWindow::default(),
+                capture: capture::CaptureSettings::default(),
+                log: log_panel::LogPanel::default(),
+                git: git_panel::GitPanel::default(),
+                material_editor: material_editor::MaterialEditor::default(),
+                timeline: timeline::Timeline::default(),
+                rig_editor: rig_editor::RigEditor::default(),
+                profiler: profiler_panel::ProfilerPanel::default(),
+                preferences: preferences::EditorPreferences::load(),
+                recent: recent::RecentScenes::load(),
+                theme: theme::ThemeSettings::default(),
+                camera_bookmarks: camera_bookmarks::CameraBookmarks::default(),
+                play_session: None,
+                prefabs: prefab::Prefabs::default(),
+                entity_clipboard: clipboard::EntityClipboard::default(),
+                history: history::EditorHistory::default(),
+                dock: dock::DockLayout::default(),
+                hot_reload: hot_reload::HotReload::default(),
+                scripts: scripts::GameplayScripts::default(),
+                physics: physics::GameplayPhysics::default(),
+                scene_world: scenes::SceneWorld::default(),
                 animator_runtime: HashMap::new(),
             };
+            app.viewport.set_camera_nav(app.preferences.camera);
+            app.viewport.set_snap_settings(app.preferences.snap);
+            app.viewport.set_game_aspect(app.preferences.game_aspect);
+            app.theme.select(&app.preferences.theme);
+            if let Some(language) = app.preferences.language {
+                app.language = language;
+            }
+            app.dock.load(None, &app.preferences.layout);
             app.refresh_hub_projects();
             app.refresh_hub_engines();
             Ok(Box::new(app))
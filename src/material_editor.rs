@@ -0,0 +1,407 @@
+use super::*;
+use crate::inspector::{
+    ShaderProperties, ShaderType, draw_shader_param, parse_shader_properties,
+    update_shader_property,
+};
+use crate::viewport_gpu::MaterialPreview;
+use engine_render::AssetKind;
+
+/// Lado máximo da esfera de preview
+const PREVIEW_SIZE: f32 = 180.0;
+const TEXTURE_EXTENSIONS: [&str; 5] = ["png", "jpg", "jpeg", "tga", "bmp"];
+
+fn tr(language: EngineLanguage, key: &'static str) -> &'static str {
+    i18n::text(language, "material_editor", key)
+}
+
+fn is_texture(path: &Path) -> bool {
+    AssetKind::from_path(path) == Some(AssetKind::Texture)
+}
+
+pub(crate) fn is_material(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("mat"))
+}
+
+/// Editor de materiais (.mat): shader, texturas, parâmetros e uma esfera de
+/// preview. As mudanças vão direto para o arquivo, como no inspetor
+#[derive(Default)]
+pub(crate) struct MaterialEditor {
+    pub(crate) open: bool,
+    path: Option<PathBuf>,
+    // Giro da esfera do preview, arrastando com o mouse
+    preview_yaw: f32,
+    // Espaço do painel e dos campos de textura no último frame, para soltar
+    // assets arrastados do Projeto
+    panel_rect: Option<egui::Rect>,
+    slot_rects: Vec<(&'static str, egui::Rect)>,
+}
+
+impl MaterialEditor {
+    pub(crate) fn open_material(&mut self, path: PathBuf) {
+        self.path = Some(path);
+        self.open = true;
+    }
+
+    pub(crate) fn contains_point(&self, pos: egui::Pos2) -> bool {
+        self.panel_rect.is_some_and(|rect| rect.contains(pos))
+    }
+
+    pub(crate) fn clear_panel_rect(&mut self) {
+        self.panel_rect = None;
+        self.slot_rects.clear();
+    }
+
+    /// Asset do Projeto solto sobre o painel: outro material passa a ser
+    /// editado; uma imagem vai para o campo de textura sob o cursor
+    pub(crate) fn on_asset_dropped(&mut self, pos: egui::Pos2, path: &Path) {
+        if is_material(path) {
+            self.path = Some(path.to_path_buf());
+            return;
+        }
+        let Some(mat_path) = &self.path else {
+            return;
+        };
+        if !is_texture(path) {
+            return;
+        }
+        if let Some((key, _)) = self.slot_rects.iter().find(|(_, rect)| rect.contains(pos)) {
+            update_shader_property(&mat_path.to_string_lossy(), key, &path.to_string_lossy());
+        }
+    }
+
+    /// Campo de textura com o nome do arquivo, seletor e botão de limpar
+    fn texture_slot(
+        &mut self,
+        ui: &mut egui::Ui,
+        language: EngineLanguage,
+        mat_path: &str,
+        key: &'static str,
+        current: Option<&str>,
+        dragging: Option<&Path>,
+    ) {
+        let current = current.filter(|path| !path.trim().is_empty());
+        let resp = egui::Frame::new()
+            .fill(egui::Color32::from_rgb(35, 35, 35))
+            .stroke(egui::Stroke::new(1.0, theme::palette().border))
+            .corner_radius(4)
+            .inner_margin(egui::Margin::symmetric(6, 3))
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    let name = current
+                        .and_then(|path| Path::new(path).file_name())
+                        .map(|name| name.to_string_lossy().to_string())
+                        .unwrap_or_else(|| tr(language, "no_texture").to_string());
+                    let label = ui.label(egui::RichText::new(name).small());
+                    if let Some(path) = current {
+                        label.on_hover_text(path);
+                    }
+                    if ui.small_button("...").clicked() {
+                        if let Some(picked) = rfd::FileDialog::new()
+                            .add_filter(tr(language, key), &TEXTURE_EXTENSIONS)
+                            .pick_file()
+                        {
+                            update_shader_property(mat_path, key, &picked.to_string_lossy());
+                        }
+                    }
+                    if current.is_some() && ui.small_button("✕").clicked() {
+                        update_shader_property(mat_path, key, "");
+                    }
+                });
+            })
+            .response;
+        let hovered = ui
+            .ctx()
+            .pointer_hover_pos()
+            .is_some_and(|pos| resp.rect.contains(pos));
+        if hovered && dragging.is_some_and(is_texture) {
+            ui.painter().rect_stroke(
+                resp.rect.expand(2.0),
+                4.0,
+                egui::Stroke::new(2.0, theme::palette().warning),
+                egui::StrokeKind::Outside,
+            );
+        }
+        self.slot_rects.push((key, resp.rect));
+    }
+}
+
+impl EditorApp {
+    /// Painel do editor de materiais no espaço que o layout de painéis deu a ele
+    pub(crate) fn draw_material_editor(&mut self, ctx: &egui::Context, panel: egui::Rect) {
+        engine_core::profile_function!();
+        self.material_editor.panel_rect = Some(panel);
+        self.material_editor.slot_rects.clear();
+        let dragging = self.project.dragging_asset_path();
+        egui::Area::new(egui::Id::new("material_editor_panel"))
+            .order(egui::Order::Foreground)
+            .fixed_pos(panel.min)
+            .show(ctx, |ui| {
+                let (rect, _) = ui.allocate_exact_size(panel.size(), egui::Sense::hover());
+                ui.painter()
+                    .rect_filled(rect, 0.0, theme::palette().panel_bg);
+                ui.painter().rect_stroke(
+                    rect,
+                    0.0,
+                    egui::Stroke::new(1.0, theme::palette().border),
+                    egui::StrokeKind::Outside,
+                );
+                if dragging.as_deref().is_some_and(is_material)
+                    && ctx
+                        .pointer_hover_pos()
+                        .is_some_and(|pos| rect.contains(pos))
+                {
+                    ui.painter().rect_stroke(
+                        rect.shrink(2.0),
+                        6.0,
+                        egui::Stroke::new(2.0, theme::palette().accent),
+                        egui::StrokeKind::Outside,
+                    );
+                }
+                let inner = rect.shrink2(egui::vec2(8.0, 6.0));
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(inner)
+                        .layout(egui::Layout::top_down(egui::Align::Min)),
+                    |ui| self.draw_material_editor_contents(ui, dragging.as_deref()),
+                );
+            });
+    }
+
+    fn draw_material_editor_contents(&mut self, ui: &mut egui::Ui, dragging: Option<&Path>) {
+        let language = self.language;
+        ui.horizontal(|ui| {
+            if ui.button(tr(language, "new")).clicked() {
+                if let Some(path) = self.project.create_material(language) {
+                    self.material_editor.path = Some(path);
+                }
+            }
+            if ui.button(tr(language, "open")).clicked() {
+                if let Some(picked) = rfd::FileDialog::new()
+                    .add_filter("Material", &["mat"])
+                    .set_directory(Path::new("Assets").join("Materials"))
+                    .pick_file()
+                {
+                    self.material_editor.path = Some(picked);
+                }
+            }
+            if let Some(path) = &self.material_editor.path {
+                ui.label(
+                    egui::RichText::new(path.file_name().unwrap_or_default().to_string_lossy())
+                        .strong(),
+                )
+                .on_hover_text(path.to_string_lossy());
+            }
+        });
+        ui.separator();
+
+        let Some(path) = self.material_editor.path.clone() else {
+            ui.weak(tr(language, "empty"));
+            return;
+        };
+        let mat_path = path.to_string_lossy().to_string();
+        let Some(props) = parse_shader_properties(&mat_path) else {
+            ui.colored_label(theme::palette().error, tr(language, "unreadable"));
+            return;
+        };
+        self.inspector.refresh_material_shaders(&mat_path);
+
+        egui::ScrollArea::vertical()
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                self.draw_material_preview(ui, &props);
+                ui.add_space(8.0);
+                self.draw_material_fields(ui, &mat_path, &props, dragging);
+            });
+    }
+
+    /// Esfera com o material, desenhada pela mesma GPU da viewport
+    fn draw_material_preview(&mut self, ui: &mut egui::Ui, props: &ShaderProperties) {
+        let language = self.language;
+        let size = ui.available_width().min(PREVIEW_SIZE);
+        let (row, resp) =
+            ui.allocate_exact_size(egui::vec2(ui.available_width(), size), egui::Sense::drag());
+        let rect = egui::Rect::from_center_size(row.center(), egui::vec2(size, size));
+        ui.painter()
+            .rect_filled(rect, 6.0, egui::Color32::from_rgb(22, 22, 22));
+        if resp.dragged() {
+            self.material_editor.preview_yaw += resp.drag_delta().x * 0.01;
+        }
+        let hint = if props.custom_shader.is_some() || props.shader_type != ShaderType::Standard {
+            tr(language, "preview_lit")
+        } else {
+            tr(language, "rotate")
+        };
+        resp.on_hover_text(hint);
+
+        let albedo = props.albedo;
+        match &self.viewport_gpu {
+            Some(gpu) => {
+                let preview = MaterialPreview {
+                    albedo,
+                    metallic: props.metallic,
+                    roughness: props.roughness,
+                    texture_path: props
+                        .texture_path
+                        .clone()
+                        .filter(|path| !path.trim().is_empty()),
+                    yaw: self.material_editor.preview_yaw,
+                };
+                ui.painter()
+                    .add(gpu.material_preview_callback(rect.shrink(4.0), preview));
+            }
+            // Sem wgpu: só a cor do material
+            None => {
+                ui.painter().circle_filled(
+                    rect.center(),
+                    size * 0.4,
+                    egui::Color32::from_rgb(
+                        (albedo[0].clamp(0.0, 1.0) * 255.0) as u8,
+                        (albedo[1].clamp(0.0, 1.0) * 255.0) as u8,
+                        (albedo[2].clamp(0.0, 1.0) * 255.0) as u8,
+                    ),
+                );
+            }
+        }
+    }
+
+    fn draw_material_fields(
+        &mut self,
+        ui: &mut egui::Ui,
+        mat_path: &str,
+        props: &ShaderProperties,
+        dragging: Option<&Path>,
+    ) {
+        let language = self.language;
+        let assets = self.inspector.material_shader_assets();
+        let custom_shader = props.custom_shader.as_deref();
+        egui::Grid::new("material_editor_grid")
+            .num_columns(2)
+            .spacing([10.0, 8.0])
+            .show(ui, |ui| {
+                ui.label(format!("{}:", tr(language, "shader")));
+                let mut selected: Option<String> = None;
+                egui::ComboBox::from_id_salt("material_editor_shader")
+                    .selected_text(custom_shader.unwrap_or(props.shader_type.display_name()))
+                    .show_ui(ui, |ui| {
+                        for variant in ShaderType::ALL {
+                            let current = custom_shader.is_none() && props.shader_type == variant;
+                            if ui
+                                .selectable_label(current, variant.display_name())
+                                .clicked()
+                            {
+                                selected = Some(variant.as_str().to_string());
+                            }
+                        }
+                        let handles = assets.material_shader_handles();
+                        if !handles.is_empty() {
+                            ui.separator();
+                        }
+                        for handle in handles {
+                            let Some(shader) = assets.get_material_shader(handle) else {
+                                continue;
+                            };
+                            let current = custom_shader
+                                .is_some_and(|name| name.eq_ignore_ascii_case(&shader.name));
+                            if ui.selectable_label(current, &shader.name).clicked() {
+                                selected = Some(shader.name.clone());
+                            }
+                        }
+                    });
+                if let Some(selected) = selected {
+                    update_shader_property(mat_path, "shader", &selected);
+                }
+                ui.end_row();
+                // Shader do projeto: só os parâmetros dele, abaixo da grade
+                if custom_shader.is_some() {
+                    return;
+                }
+
+                ui.label(format!("{}:", tr(language, "albedo")));
+                let mut albedo = props.albedo;
+                if ui.color_edit_button_rgb(&mut albedo).changed() {
+                    update_shader_property(
+                        mat_path,
+                        "albedo",
+                        &format!("{:.2},{:.2},{:.2}", albedo[0], albedo[1], albedo[2]),
+                    );
+                }
+                ui.end_row();
+
+                for (key, current) in [
+                    ("albedo_texture", props.texture_path.as_deref()),
+                    ("normal_map", props.normal_map.as_deref()),
+                ] {
+                    ui.label(format!("{}:", tr(language, key)));
+                    self.material_editor
+                        .texture_slot(ui, language, mat_path, key, current, dragging);
+                    ui.end_row();
+                }
+
+                for (key, value, max) in [
+                    ("metallic", props.metallic, 1.0),
+                    ("roughness", props.roughness, 1.0),
+                ] {
+                    ui.label(format!("{}:", tr(language, key)));
+                    let mut value = value;
+                    if ui.add(egui::Slider::new(&mut value, 0.0..=max)).changed() {
+                        update_shader_property(mat_path, key, &format!("{:.2}", value));
+                    }
+                    ui.end_row();
+                }
+
+                ui.label(format!("{}:", tr(language, "emission")));
+                let mut emission = props.emission_color;
+                if ui.color_edit_button_rgb(&mut emission).changed() {
+                    update_shader_property(
+                        mat_path,
+                        "emission",
+                        &format!("{:.2},{:.2},{:.2}", emission[0], emission[1], emission[2]),
+                    );
+                }
+                ui.end_row();
+
+                ui.label(format!("{}:", tr(language, "emission_strength")));
+                let mut strength = props.emission_strength;
+                if ui
+                    .add(egui::Slider::new(&mut strength, 0.0..=10.0))
+                    .changed()
+                {
+                    update_shader_property(
+                        mat_path,
+                        "emission_strength",
+                        &format!("{:.2}", strength),
+                    );
+                }
+                ui.end_row();
+            });
+
+        let Some(name) = custom_shader else {
+            return;
+        };
+        ui.add_space(6.0);
+        let shader = assets
+            .find_material_shader(name)
+            .and_then(|handle| assets.get_material_shader(handle));
+        match shader {
+            Some(shader) if shader.params.is_empty() => {
+                ui.weak(tr(language, "no_params"));
+            }
+            Some(shader) => {
+                for param in &shader.params {
+                    draw_shader_param(ui, mat_path, param, props.params.get(&param.name));
+                }
+            }
+            None => {
+                ui.colored_label(
+                    egui::Color32::from_rgb(230, 170, 60),
+                    tr(language, "shader_missing"),
+                );
+            }
+        }
+        for (path, error) in self.inspector.material_shader_errors() {
+            let file = path.file_name().unwrap_or_default().to_string_lossy();
+            ui.colored_label(theme::palette().error, format!("{file}: {error}"));
+        }
+    }
+}
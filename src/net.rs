@@ -0,0 +1,320 @@
+use super::*;
+use engine_core::{EngineWorld, Guid, Transform, WorldSnapshot};
+use engine_net::{
+    ConnectionState, DEFAULT_PORT, NetEvent, NetOwner, NetSession, Replicated, Snapshot,
+};
+use glam::{EulerRot, Quat, Vec3};
+
+const NET_LOG_LINES: usize = 40;
+
+/// Janela de Rede: sessão servidor/cliente e espelho ECS dos objetos replicados
+pub(crate) struct NetworkPanel {
+    pub(crate) open: bool,
+    port: String,
+    address: String,
+    player_name: String,
+    session: NetSession,
+    world: EngineWorld,
+    log: Vec<String>,
+}
+
+impl Default for NetworkPanel {
+    fn default() -> Self {
+        Self {
+            open: false,
+            port: DEFAULT_PORT.to_string(),
+            address: format!("127.0.0.1:{}", DEFAULT_PORT),
+            player_name: "Player".to_string(),
+            session: NetSession::default(),
+            world: EngineWorld::new(),
+            log: Vec::new(),
+        }
+    }
+}
+
+impl NetworkPanel {
+    fn push_log(&mut self, line: String) {
+        self.log.push(line);
+        if self.log.len() > NET_LOG_LINES {
+            let extra = self.log.len() - NET_LOG_LINES;
+            self.log.drain(..extra);
+        }
+    }
+
+    fn host(&mut self) {
+        let port = self.port.trim().parse::<u16>().unwrap_or(DEFAULT_PORT);
+        self.world = EngineWorld::new();
+        // Erros chegam ao log por `take_error`
+        if self.session.host(port).is_ok() {
+            self.push_log(format!("Servidor ouvindo na porta {}", port));
+        }
+    }
+
+    fn connect(&mut self) {
+        self.world = EngineWorld::new();
+        let address = self.address.trim().to_string();
+        if self
+            .session
+            .connect(&address, self.player_name.trim())
+            .is_ok()
+        {
+            self.push_log(format!("Conectando a {}...", address));
+        }
+    }
+
+    fn disconnect(&mut self) {
+        self.session.disconnect();
+        self.world = EngineWorld::new();
+    }
+
+    pub(crate) fn snapshot(&self) -> WorldSnapshot {
+        self.world.snapshot()
+    }
+
+    pub(crate) fn restore(&mut self, snapshot: &WorldSnapshot) {
+        self.world.restore(snapshot);
+    }
+}
+
+/// GUID de rede derivado do nome do objeto (FNV-1a 128), igual em todas as instâncias do editor
+fn network_guid(object_name: &str) -> Guid {
+    const OFFSET: u128 = 0x6c62272e07bb014262b821756295c58d;
+    const PRIME: u128 = 0x0000000001000000000000000000013B;
+    let value = object_name
+        .bytes()
+        .fold(OFFSET, |hash, b| (hash ^ b as u128).wrapping_mul(PRIME));
+    Guid { value }
+}
+
+impl EditorApp {
+    /// Replicação durante o Play: o servidor envia todos os objetos da cena,
+    /// o cliente envia os objetos controlados pelos Fios e recebe o resto
+    pub(crate) fn run_network(&mut self, ctx: &egui::Context) {
+        let state = self.network.session.state();
+        if state == ConnectionState::Disconnected {
+            self.drain_network_events();
+            return;
+        }
+        ctx.request_repaint();
+        let dt = ctx.input(|i| i.stable_dt);
+        let names = self.viewport.scene_object_names();
+        let local_owner = self.network.session.local_owner();
+
+        if let Some(owner) = local_owner.filter(|_| self.is_playing) {
+            let owned: Vec<String> = match owner {
+                NetOwner::Server => names.clone(),
+                NetOwner::Client(_) => self
+                    .inspector
+                    .fios_controller_targets()
+                    .into_iter()
+                    .map(|(name, _)| name)
+                    .collect(),
+            };
+            let world = &mut self.network.world;
+            for name in owned {
+                let Some((pos, rot, scale)) = self.viewport.object_transform_components(&name)
+                else {
+                    continue;
+                };
+                let transform = Transform::new(
+                    Vec3::from_array(pos),
+                    Quat::from_euler(
+                        EulerRot::XYZ,
+                        rot[0].to_radians(),
+                        rot[1].to_radians(),
+                        rot[2].to_radians(),
+                    ),
+                    Vec3::from_array(scale),
+                );
+                let guid = network_guid(&name);
+                match world.find_by_guid(guid) {
+                    Some(entity) => {
+                        // Objetos assumidos por um cliente são escritos por ele, não pelo servidor
+                        let foreign = world
+                            .world()
+                            .get::<&Replicated>(entity)
+                            .is_ok_and(|r| r.owner != owner && owner == NetOwner::Server);
+                        if !foreign {
+                            let _ = world
+                                .world_mut()
+                                .insert(entity, (transform, Replicated::new(owner)));
+                        }
+                    }
+                    None => {
+                        world.spawn((transform, guid, Replicated::new(owner)));
+                    }
+                }
+            }
+        }
+
+        self.network.session.update(&mut self.network.world, dt);
+        self.drain_network_events();
+
+        if !self.is_playing {
+            return;
+        }
+        let local_owner = self.network.session.local_owner();
+        let remote = Snapshot::capture_filtered(&self.network.world, 0, 0.0, |r| {
+            Some(r.owner) != local_owner
+        });
+        for name in names {
+            let guid = network_guid(&name);
+            let Some(state) = remote.entities.iter().find(|s| s.guid == guid) else {
+                continue;
+            };
+            let (rx, ry, rz) = state.rotation.to_euler(EulerRot::XYZ);
+            let _ = self.viewport.set_object_transform_components(
+                &name,
+                state.position.to_array(),
+                [rx.to_degrees(), ry.to_degrees(), rz.to_degrees()],
+                state.scale.to_array(),
+            );
+        }
+    }
+
+    fn drain_network_events(&mut self) {
+        for event in self.network.session.drain_events() {
+            let line = match event {
+                NetEvent::ClientConnected { id, name } => {
+                    format!("Cliente #{} conectado ({})", id, name)
+                }
+                NetEvent::ClientDisconnected { id } => format!("Cliente #{} desconectado", id),
+                NetEvent::Connected { client_id } => {
+                    format!("Conectado ao servidor como cliente #{}", client_id)
+                }
+                NetEvent::Disconnected { reason } => format!("Desconectado: {}", reason),
+            };
+            self.network.push_log(line);
+        }
+        if let Some(error) = self.network.session.take_error() {
+            self.network.push_log(error);
+        }
+    }
+
+    pub(crate) fn draw_network_window(&mut self, ctx: &egui::Context) {
+        engine_core::profile_function!();
+        if !self.network.open {
+            return;
+        }
+        let mut open = self.network.open;
+        egui::Window::new("🌐 Rede")
+            .open(&mut open)
+            .default_size([320.0, 360.0])
+            .resizable(true)
+            .show(ctx, |ui| {
+                let net = &mut self.network;
+                let state = net.session.state();
+                let idle = state == ConnectionState::Disconnected;
+
+                egui::Grid::new("net_connection_grid")
+                    .num_columns(2)
+                    .spacing([8.0, 6.0])
+                    .show(ui, |ui| {
+                        ui.label("Nome");
+                        ui.add_enabled(
+                            idle,
+                            egui::TextEdit::singleline(&mut net.player_name).desired_width(160.0),
+                        );
+                        ui.end_row();
+                        ui.label("Porta");
+                        ui.add_enabled(
+                            idle,
+                            egui::TextEdit::singleline(&mut net.port).desired_width(160.0),
+                        );
+                        ui.end_row();
+                        ui.label("Servidor");
+                        ui.add_enabled(
+                            idle,
+                            egui::TextEdit::singleline(&mut net.address).desired_width(160.0),
+                        );
+                        ui.end_row();
+                    });
+                ui.add_space(6.0);
+
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(idle, egui::Button::new("Hospedar"))
+                        .clicked()
+                    {
+                        net.host();
+                    }
+                    if ui
+                        .add_enabled(idle, egui::Button::new("Conectar"))
+                        .clicked()
+                    {
+                        net.connect();
+                    }
+                    if ui
+                        .add_enabled(!idle, egui::Button::new("Desconectar"))
+                        .clicked()
+                    {
+                        net.disconnect();
+                    }
+                });
+                ui.separator();
+
+                let (status, color) = match state {
+                    ConnectionState::Disconnected => ("Desconectado", theme::palette().text_weak),
+                    ConnectionState::Connecting => {
+                        ("Conectando...", egui::Color32::from_rgb(230, 190, 90))
+                    }
+                    ConnectionState::Connected => ("Conectado", theme::palette().accent),
+                    ConnectionState::Hosting => ("Servidor", theme::palette().accent),
+                };
+                ui.horizontal(|ui| {
+                    ui.label("Estado:");
+                    ui.colored_label(color, status);
+                    if let Some(addr) = net.session.local_addr() {
+                        ui.label(egui::RichText::new(addr.to_string()).weak());
+                    }
+                });
+                match state {
+                    ConnectionState::Hosting => {
+                        ui.label(format!("Clientes: {}", net.session.peers().len()));
+                        for peer in net.session.peers() {
+                            ui.label(format!("  #{} {} ({})", peer.id, peer.name, peer.addr));
+                        }
+                    }
+                    ConnectionState::Connected => {
+                        if let Some(id) = net.session.client_id() {
+                            ui.label(format!("Cliente #{}", id));
+                        }
+                        if let Some(rtt) = net.session.rtt() {
+                            ui.label(format!("Ping: {:.0} ms", rtt * 1000.0));
+                        }
+                    }
+                    _ => {}
+                }
+                let stats = net.session.stats();
+                ui.label(
+                    egui::RichText::new(format!(
+                        "Enviado: {:.1} KB ({} pacotes) · Recebido: {:.1} KB ({} pacotes)",
+                        stats.bytes_sent as f32 / 1024.0,
+                        stats.packets_sent,
+                        stats.bytes_received as f32 / 1024.0,
+                        stats.packets_received
+                    ))
+                    .small()
+                    .weak(),
+                );
+                ui.label(
+                    egui::RichText::new(
+                        "Objetos são replicados pelo nome durante o Play; no cliente, os objetos com Fios são enviados ao servidor.",
+                    )
+                    .small()
+                    .weak(),
+                );
+                ui.separator();
+
+                egui::ScrollArea::vertical()
+                    .max_height(140.0)
+                    .stick_to_bottom(true)
+                    .show(ui, |ui| {
+                        for line in &net.log {
+                            ui.label(egui::RichText::new(line).monospace().small());
+                        }
+                    });
+            });
+        self.network.open = open;
+    }
+}
@@ -0,0 +1,204 @@
+use super::*;
+use engine_core::{EngineWorld, Transform, WorldSnapshot};
+use engine_physics::{Collider, ColliderShape, PhysicsWorld, RigidBody};
+use glam::{EulerRot, Quat, Vec3};
+use inspector::{ColliderDraft, ColliderKind, RigidbodyDraft};
+
+/// Espelho ECS dos objetos com Rigidbody/Collider, simulado pelo engine_physics durante o Play
+pub(crate) struct GameplayPhysics {
+    world: EngineWorld,
+    physics: PhysicsWorld,
+    entities: HashMap<String, hecs::Entity>,
+}
+
+impl Default for GameplayPhysics {
+    fn default() -> Self {
+        let mut physics = PhysicsWorld::new();
+        physics.add_ground_plane(0.0);
+        Self {
+            world: EngineWorld::new(),
+            physics,
+            entities: HashMap::new(),
+        }
+    }
+}
+
+impl GameplayPhysics {
+    pub(crate) fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub(crate) fn snapshot(&self) -> WorldSnapshot {
+        self.world.snapshot()
+    }
+
+    /// Volta o espelho ao snapshot; os corpos do rapier são recriados a partir
+    /// dos componentes na próxima simulação
+    pub(crate) fn restore(&mut self, snapshot: &WorldSnapshot) {
+        self.world.restore(snapshot);
+        let mut physics = PhysicsWorld::new();
+        physics.add_ground_plane(0.0);
+        self.physics = physics;
+        let world = &self.world;
+        self.entities.retain(|_, e| world.world().contains(*e));
+    }
+
+    pub(crate) fn has_body(&self, object_name: &str) -> bool {
+        self.entities.get(object_name).is_some_and(|e| {
+            self.world
+                .world()
+                .entity(*e)
+                .is_ok_and(|r| r.has::<RigidBody>())
+        })
+    }
+}
+
+/// Converte o collider do inspetor (espaco local) para unidades de mundo usando a escala do objeto
+pub(crate) fn baked_collider(draft: &ColliderDraft, scale: [f32; 3]) -> Collider {
+    let s = Vec3::from_array(scale).abs();
+    let shape = match draft.kind {
+        ColliderKind::Box => ColliderShape::Cuboid {
+            half_extents: Vec3::from_array(draft.size) * s,
+        },
+        ColliderKind::Sphere => ColliderShape::Sphere {
+            radius: draft.size[0] * s.max_element(),
+        },
+        ColliderKind::Capsule => ColliderShape::Capsule {
+            half_height: draft.size[1] * s.y,
+            radius: draft.size[0] * s.x.max(s.z),
+        },
+    };
+    Collider {
+        shape,
+        offset: Vec3::from_array(draft.center) * s,
+        friction: draft.friction,
+        restitution: draft.restitution,
+        is_sensor: draft.is_trigger,
+    }
+}
+
+pub(crate) fn to_rigid_body(draft: &RigidbodyDraft) -> RigidBody {
+    RigidBody {
+        body_type: draft.body_type,
+        mass: draft.mass,
+        gravity_scale: if draft.use_gravity {
+            draft.gravity[1] / -9.81
+        } else {
+            0.0
+        },
+        lock_rotation: draft.lock_rotation,
+        ..Default::default()
+    }
+}
+
+impl EditorApp {
+    /// Colliders de todos os objetos (em unidades de mundo) para o gizmo da viewport
+    pub(crate) fn collider_gizmos(&self) -> Vec<(String, Collider)> {
+        self.inspector
+            .collider_targets()
+            .into_iter()
+            .filter_map(|(name, draft)| {
+                let (_, _, scale) = self.viewport.object_transform_components(&name)?;
+                Some((name, baked_collider(&draft, scale)))
+            })
+            .collect()
+    }
+
+    pub(crate) fn run_gameplay_physics(&mut self, action: f32) {
+        if !self.is_playing || !self.time.is_running() || !self.scripts.game_playing() {
+            return;
+        }
+        let bodies: HashMap<String, RigidbodyDraft> =
+            self.inspector.rigidbody_targets().into_iter().collect();
+        let colliders: HashMap<String, ColliderDraft> =
+            self.inspector.collider_targets().into_iter().collect();
+        let sim = &mut self.physics;
+
+        let stale: Vec<String> = sim
+            .entities
+            .keys()
+            .filter(|n| !bodies.contains_key(*n) && !colliders.contains_key(*n))
+            .cloned()
+            .collect();
+        for name in stale {
+            if let Some(entity) = sim.entities.remove(&name) {
+                let _ = sim.world.world_mut().despawn(entity);
+            }
+        }
+
+        let mut names: Vec<&String> = bodies.keys().chain(colliders.keys()).collect();
+        names.sort();
+        names.dedup();
+        for name in names {
+            let Some((pos, rot, scale)) = self.viewport.object_transform_components(name) else {
+                continue;
+            };
+            let transform = Transform::new(
+                Vec3::from_array(pos),
+                Quat::from_euler(
+                    EulerRot::XYZ,
+                    rot[0].to_radians(),
+                    rot[1].to_radians(),
+                    rot[2].to_radians(),
+                ),
+                Vec3::from_array(scale),
+            );
+            let entity = *sim
+                .entities
+                .entry(name.clone())
+                .or_insert_with(|| sim.world.world_mut().spawn((transform,)));
+            let world = sim.world.world_mut();
+            let _ = world.insert_one(entity, transform);
+            match bodies.get(name) {
+                Some(rb) => {
+                    let _ = world.insert_one(entity, to_rigid_body(rb));
+                }
+                None => {
+                    let _ = world.remove_one::<RigidBody>(entity);
+                }
+            }
+            match colliders.get(name) {
+                Some(col) => {
+                    let _ = world.insert_one(entity, baked_collider(col, scale));
+                }
+                None => {
+                    let _ = world.remove_one::<Collider>(entity);
+                }
+            }
+        }
+
+        // Pulo: aplica a velocidade vertical quando o corpo esta apoiado
+        if action > 0.5 {
+            for (name, rb) in &bodies {
+                let Some(&entity) = sim.entities.get(name) else {
+                    continue;
+                };
+                if !sim.physics.is_touching(entity) {
+                    continue;
+                }
+                if let Some(v) = sim.physics.linear_velocity(entity).filter(|v| v.y <= 0.1) {
+                    sim.physics
+                        .set_linear_velocity(entity, Vec3::new(v.x, rb.jump_impulse, v.z));
+                }
+            }
+        }
+
+        sim.physics.step(&mut sim.world, self.time.delta);
+
+        for (name, entity) in &sim.entities {
+            if !bodies.contains_key(name) {
+                continue;
+            }
+            let Ok(t) = sim.world.world().get::<&Transform>(*entity) else {
+                continue;
+            };
+            let (rx, ry, rz) = t.rotation.to_euler(EulerRot::XYZ);
+            let _ = self.viewport.set_object_transform_components(
+                name,
+                t.position.to_array(),
+                [rx.to_degrees(), ry.to_degrees(), rz.to_degrees()],
+                t.scale.to_array(),
+            );
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use super::*;
+use engine_core::WorldSnapshot;
+
+/// Cor sobre o editor (fora da viewport) enquanto o Play roda
+const PLAY_TINT: egui::Color32 = egui::Color32::from_rgba_premultiplied(22, 34, 58, 44);
+
+/// Mundos ECS no momento do Play: a cena e os espelhos de física, scripts e
+/// rede. O Stop restaura cada um e devolve a cena aos painéis; a viewport
+/// guarda as malhas dela
+pub(crate) struct PlaySession {
+    scene: WorldSnapshot,
+    physics: WorldSnapshot,
+    scripts: WorldSnapshot,
+    network: WorldSnapshot,
+}
+
+impl EditorApp {
+    /// Play: guarda o snapshot de todos os mundos
+    pub(crate) fn enter_play_mode(&mut self) {
+        if self.is_playing {
+            return;
+        }
+        self.sync_scene_world();
+        self.play_session = Some(PlaySession {
+            scene: self.scene_world.world.snapshot(),
+            physics: self.physics.snapshot(),
+            scripts: self.scripts.snapshot(),
+            network: self.network.snapshot(),
+        });
+        self.is_playing = true;
+        self.time.reset();
+        self.viewport.enter_play_mode();
+        self.selected_mode = ToolbarMode::Game;
+    }
+
+    /// Stop: descarta o estado do jogo e restaura os mundos de antes do Play
+    pub(crate) fn exit_play_mode(&mut self) {
+        self.is_playing = false;
+        self.time.reset();
+        let played = self.viewport.scene_object_names();
+        self.viewport.exit_play_mode();
+        self.animator_runtime.clear();
+        match self.play_session.take() {
+            Some(session) => {
+                self.scene_world.restore(&session.scene);
+                self.physics.restore(&session.physics);
+                self.scripts.restore(&session.scripts);
+                self.network.restore(&session.network);
+                self.apply_scene_world(&played);
+            }
+            None => {
+                self.scripts.reset();
+                self.physics.reset();
+            }
+        }
+        self.selected_mode = ToolbarMode::Cena;
+    }
+
+    /// Tinge painéis e barras durante o Play para lembrar que edições não
+    /// ficam; a viewport fica sem tinta para o jogo aparecer com as cores reais
+    pub(crate) fn draw_play_tint(&self, ctx: &egui::Context) {
+        if !self.is_playing {
+            return;
+        }
+        // Acima das áreas dos painéis; não recebe input
+        let painter = ctx.layer_painter(egui::LayerId::new(
+            egui::Order::Tooltip,
+            egui::Id::new("play_mode_tint"),
+        ));
+        let screen = ctx.content_rect();
+        let Some(view) = self.viewport.panel_rect() else {
+            painter.rect_filled(screen, 0.0, PLAY_TINT);
+            return;
+        };
+        let bands = [
+            egui::Rect::from_min_max(screen.min, egui::pos2(screen.max.x, view.min.y)),
+            egui::Rect::from_min_max(egui::pos2(screen.min.x, view.max.y), screen.max),
+            egui::Rect::from_min_max(
+                egui::pos2(screen.min.x, view.min.y),
+                egui::pos2(view.min.x, view.max.y),
+            ),
+            egui::Rect::from_min_max(
+                egui::pos2(view.max.x, view.min.y),
+                egui::pos2(screen.max.x, view.max.y),
+            ),
+        ];
+        for band in bands.into_iter().filter(|band| band.is_positive()) {
+            painter.rect_filled(band, 0.0, PLAY_TINT);
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use super::*;
+use engine_core::TextureHandle as LutHandle;
+use engine_render::{AssetHandle, AssetManager, TextureData, same_file};
+
+/// LUTs de color grading usadas pelo pós-processamento da câmera
+#[derive(Default)]
+pub(crate) struct PostProcessStack {
+    assets: AssetManager,
+    luts: HashMap<u64, Arc<TextureData>>,
+    // Caminhos que falharam, para não tentar abrir o arquivo a cada frame
+    failed: HashSet<String>,
+}
+
+impl PostProcessStack {
+    pub(crate) fn assets(&self) -> &AssetManager {
+        &self.assets
+    }
+
+    /// Descarrega as LUTs que a câmera não usa mais
+    fn unload_unused_luts(&mut self, used: Option<LutHandle>) {
+        for handle in self.assets.unload_unused(used.map(AssetHandle::Texture)) {
+            if let AssetHandle::Texture(texture) = handle {
+                self.luts.remove(&texture.id);
+            }
+        }
+    }
+
+    /// LUT alterada em disco: a cópia atual sai e o arquivo é lido de novo na
+    /// próxima frame (também quando a leitura anterior falhou)
+    pub(crate) fn reload_lut(&mut self, path: &Path) {
+        let stale: Vec<u64> = self
+            .assets
+            .textures
+            .iter()
+            .filter(|(_, texture)| {
+                texture
+                    .source_path
+                    .as_deref()
+                    .is_some_and(|source| same_file(source, path))
+            })
+            .map(|(id, _)| *id)
+            .collect();
+        self.failed
+            .retain(|failed| !same_file(Path::new(failed), path));
+        for id in stale {
+            self.assets.unload_texture(LutHandle { id });
+            self.luts.remove(&id);
+        }
+    }
+
+    fn load_lut(&mut self, path: &str) -> Option<(LutHandle, Arc<TextureData>)> {
+        if self.failed.contains(path) {
+            return None;
+        }
+        let handle = match self.assets.load_color_lut(Path::new(path)) {
+            Ok(handle) => handle,
+            Err(e) => {
+                log::error!("[POST] {}", e);
+                self.failed.insert(path.to_string());
+                return None;
+            }
+        };
+        let texture = self.assets.get_texture(handle)?;
+        let lut = self
+            .luts
+            .entry(handle.id)
+            .or_insert_with(|| Arc::new(texture.clone()))
+            .clone();
+        Some((handle, lut))
+    }
+}
+
+impl EditorApp {
+    /// Envia o pós-processamento da câmera (componente do Inspector) para a viewport
+    pub(crate) fn sync_post_process(&mut self) {
+        let Some(draft) = self.inspector.post_process_target() else {
+            self.post_process.unload_unused_luts(None);
+            self.viewport.set_post_process(None, None);
+            return;
+        };
+        let mut settings = draft.settings;
+        let lut_path = draft.lut_path.trim();
+        let lut = if lut_path.is_empty() {
+            None
+        } else {
+            self.post_process.load_lut(lut_path)
+        };
+        settings.color_lut = lut.as_ref().map(|(handle, _)| *handle);
+        self.post_process.unload_unused_luts(settings.color_lut);
+        self.viewport
+            .set_post_process(Some(settings), lut.map(|(_, texture)| texture));
+    }
+}
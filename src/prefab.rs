@@ -0,0 +1,357 @@
+use super::*;
+use crate::hierarchy::HierarchySnapshot;
+use crate::inspector::InspectorSnapshot;
+
+fn tr(language: EngineLanguage, key: &'static str) -> &'static str {
+    i18n::text(language, "prefab", key)
+}
+
+/// Pasta onde objetos soltos da hierarquia no Projeto viram prefab
+const PREFAB_DIR: &str = "Assets/Mold";
+
+pub(crate) fn is_prefab(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("mold"))
+}
+
+/// Prefab (.mold): malha, material e rotação/escala, em linhas `chave=valor`.
+/// A posição é de cada instância
+#[derive(Clone, PartialEq)]
+pub(crate) struct PrefabAsset {
+    mesh: String,
+    material: Option<String>,
+    rotation: [f32; 3],
+    scale: [f32; 3],
+}
+
+impl PrefabAsset {
+    fn load(path: &Path) -> Result<Self, String> {
+        let raw = fs::read_to_string(path)
+            .map_err(|e| format!("Falha ao ler {}: {e}", path.display()))?;
+        let mut mesh = None;
+        let mut material = None;
+        let mut rotation = [0.0; 3];
+        let mut scale = [1.0; 3];
+        for (key, value) in raw.lines().filter_map(|line| line.split_once('=')) {
+            let value = value.trim();
+            match key.trim() {
+                "mesh" if !value.is_empty() => mesh = Some(value.to_string()),
+                "material" if !value.is_empty() => material = Some(value.to_string()),
+                "rotation" => rotation = parse_vec3(value).unwrap_or(rotation),
+                "scale" => scale = parse_vec3(value).unwrap_or(scale),
+                _ => {}
+            }
+        }
+        let mesh = mesh.ok_or_else(|| format!("{} sem malha (mesh=)", path.display()))?;
+        Ok(Self {
+            mesh,
+            material,
+            rotation,
+            scale,
+        })
+    }
+
+    fn save(&self, path: &Path) -> Result<(), String> {
+        let vec3 = |v: [f32; 3]| format!("{},{},{}", v[0], v[1], v[2]);
+        let text = format!(
+            "mesh={}\nmaterial={}\nrotation={}\nscale={}\n",
+            self.mesh,
+            self.material.as_deref().unwrap_or(""),
+            vec3(self.rotation),
+            vec3(self.scale),
+        );
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        fs::write(path, text).map_err(|e| format!("Falha ao salvar {}: {e}", path.display()))
+    }
+}
+
+fn parse_vec3(text: &str) -> Option<[f32; 3]> {
+    let mut parts = text.split(',').map(|p| p.trim().parse::<f32>().ok());
+    Some([parts.next()??, parts.next()??, parts.next()??])
+}
+
+/// Cena guardada enquanto um prefab é editado isolado na viewport
+struct PrefabSession {
+    path: PathBuf,
+    // Objeto que representa o prefab na cena isolada
+    object: String,
+    hierarchy: HierarchySnapshot,
+    inspector: InspectorSnapshot,
+}
+
+/// Instâncias de prefabs na cena e a edição isolada de um deles
+#[derive(Default)]
+pub(crate) struct Prefabs {
+    // Objeto da cena -> prefab de onde veio
+    instances: HashMap<String, PathBuf>,
+    // Objetos esperando a malha carregar para receber material e transform
+    pending: Vec<(String, PrefabAsset)>,
+    editing: Option<PrefabSession>,
+}
+
+impl EditorApp {
+    pub(crate) fn editing_prefab(&self) -> bool {
+        self.prefabs.editing.is_some()
+    }
+
+    /// Objeto novo na cena com a malha do prefab; material, rotação e escala
+    /// entram quando a malha termina de carregar
+    pub(crate) fn instantiate_prefab(&mut self, path: &Path) {
+        let prefab = match PrefabAsset::load(path) {
+            Ok(prefab) => prefab,
+            Err(e) => {
+                self.log.push(LogLevel::Error, "Prefab", e);
+                self.log_enabled = true;
+                return;
+            }
+        };
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let object_name = self.hierarchy.on_asset_dropped(&file_name);
+        self.viewport
+            .on_asset_file_dropped_named(Path::new(&prefab.mesh), &object_name);
+        self.prefabs
+            .instances
+            .insert(object_name.clone(), path.to_path_buf());
+        self.prefabs.pending.push((object_name, prefab));
+    }
+
+    /// Objeto da hierarquia solto no Projeto: grava um .mold com a malha, o
+    /// material e a rotação/escala dele e passa a tratá-lo como instância
+    pub(crate) fn create_prefab(&mut self, object_name: &str) {
+        let Some(mesh) = self.viewport.object_source(object_name) else {
+            self.log.push(
+                LogLevel::Warning,
+                "Prefab",
+                format!("{object_name} não tem malha importada para virar prefab"),
+            );
+            return;
+        };
+        let (_, rotation, scale) = self
+            .viewport
+            .object_transform_components(object_name)
+            .unwrap_or(([0.0; 3], [0.0; 3], [1.0; 3]));
+        let prefab = PrefabAsset {
+            mesh: mesh.to_string_lossy().replace('\\', "/"),
+            material: self.viewport.object_material_path(object_name),
+            rotation,
+            scale,
+        };
+        let stem = mesh
+            .file_stem()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "Prefab".to_string());
+        let dir = Path::new(PREFAB_DIR);
+        let mut path = dir.join(format!("{stem}.mold"));
+        let mut idx = 1;
+        while path.exists() {
+            path = dir.join(format!("{stem}_{idx}.mold"));
+            idx += 1;
+        }
+        match prefab.save(&path) {
+            Ok(()) => {
+                self.prefabs
+                    .instances
+                    .insert(object_name.to_string(), path.clone());
+                self.log.push(
+                    LogLevel::Info,
+                    "Prefab",
+                    format!("Prefab criado: {}", path.display()),
+                );
+            }
+            Err(e) => {
+                self.log.push(LogLevel::Error, "Prefab", e);
+                self.log_enabled = true;
+            }
+        }
+    }
+
+    /// Duplo clique num .mold: a cena é guardada e a viewport mostra só o
+    /// prefab, até voltar pela barra de navegação
+    pub(crate) fn open_prefab(&mut self, path: &Path) {
+        if self.is_playing {
+            self.log.push(
+                LogLevel::Warning,
+                "Prefab",
+                "Pare o Play antes de editar um prefab".to_string(),
+            );
+            return;
+        }
+        if self.editing_prefab() {
+            self.close_prefab();
+        }
+        let prefab = match PrefabAsset::load(path) {
+            Ok(prefab) => prefab,
+            Err(e) => {
+                self.log.push(LogLevel::Error, "Prefab", e);
+                self.log_enabled = true;
+                return;
+            }
+        };
+        let hierarchy = self.hierarchy.snapshot();
+        let inspector = self.inspector.snapshot();
+        self.viewport.enter_prefab_mode();
+        self.hierarchy.clear_for_isolation();
+        self.selected_mode = ToolbarMode::Cena;
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let object = self.hierarchy.on_asset_dropped(&file_name);
+        self.viewport
+            .on_asset_file_dropped_named(Path::new(&prefab.mesh), &object);
+        self.prefabs.pending.push((object.clone(), prefab));
+        self.prefabs.editing = Some(PrefabSession {
+            path: path.to_path_buf(),
+            object,
+            hierarchy,
+            inspector,
+        });
+    }
+
+    /// Grava no .mold o material e a rotação/escala do objeto editado
+    fn apply_prefab(&mut self) {
+        let Some(session) = &self.prefabs.editing else {
+            return;
+        };
+        let path = session.path.clone();
+        let object = session.object.clone();
+        let mut prefab = match PrefabAsset::load(&path) {
+            Ok(prefab) => prefab,
+            Err(e) => {
+                self.log.push(LogLevel::Error, "Prefab", e);
+                self.log_enabled = true;
+                return;
+            }
+        };
+        if let Some((_, rotation, scale)) = self.viewport.object_transform_components(&object) {
+            prefab.rotation = rotation;
+            prefab.scale = scale;
+        }
+        prefab.material = self.viewport.object_material_path(&object);
+        match prefab.save(&path) {
+            Ok(()) => self.log.push(
+                LogLevel::Info,
+                "Prefab",
+                format!("Prefab aplicado: {}", path.display()),
+            ),
+            Err(e) => {
+                self.log.push(LogLevel::Error, "Prefab", e);
+                self.log_enabled = true;
+            }
+        }
+    }
+
+    /// Volta para a cena guardada e atualiza as instâncias com o .mold
+    fn close_prefab(&mut self) {
+        let Some(session) = self.prefabs.editing.take() else {
+            return;
+        };
+        self.prefabs
+            .pending
+            .retain(|(name, _)| name != &session.object);
+        self.viewport.exit_prefab_mode();
+        self.hierarchy.restore(&session.hierarchy);
+        self.inspector.restore(&session.inspector);
+        let Ok(prefab) = PrefabAsset::load(&session.path) else {
+            return;
+        };
+        let instances: Vec<String> = self
+            .prefabs
+            .instances
+            .iter()
+            .filter(|(_, path)| **path == session.path)
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in &instances {
+            self.apply_prefab_to(name, &prefab);
+        }
+        if !instances.is_empty() {
+            self.log.push(
+                LogLevel::Info,
+                "Prefab",
+                format!(
+                    "{} instância(s) de {} atualizadas",
+                    instances.len(),
+                    session.path.display()
+                ),
+            );
+        }
+    }
+
+    /// Material, rotação e escala do prefab num objeto; a posição fica
+    fn apply_prefab_to(&mut self, object_name: &str, prefab: &PrefabAsset) -> bool {
+        let Some((position, _, _)) = self.viewport.object_transform_components(object_name) else {
+            return false;
+        };
+        self.viewport.set_object_transform_components(
+            object_name,
+            position,
+            prefab.rotation,
+            prefab.scale,
+        );
+        self.viewport
+            .set_object_material_path(object_name, prefab.material.clone());
+        self.inspector
+            .set_object_shader(object_name, prefab.material.as_deref().unwrap_or(""));
+        true
+    }
+
+    /// Aplica o prefab nas instâncias cuja malha acabou de carregar
+    pub(crate) fn sync_prefabs(&mut self) {
+        if self.prefabs.pending.is_empty() {
+            return;
+        }
+        let pending = std::mem::take(&mut self.prefabs.pending);
+        for (name, prefab) in pending {
+            if !self.apply_prefab_to(&name, &prefab) {
+                self.prefabs.pending.push((name, prefab));
+            }
+        }
+    }
+
+    /// Barra sobre a viewport na edição de prefab: "Cena > Nome.mold",
+    /// aplicar e voltar
+    pub(crate) fn draw_prefab_breadcrumb(&mut self, ctx: &egui::Context) {
+        let (Some(session), Some(view)) = (&self.prefabs.editing, self.viewport.panel_rect())
+        else {
+            return;
+        };
+        let name = session
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let mut back = false;
+        let mut apply = false;
+        egui::Area::new(egui::Id::new("prefab_breadcrumb"))
+            .order(egui::Order::Foreground)
+            .fixed_pos(view.left_bottom() + egui::vec2(8.0, -38.0))
+            .show(ctx, |ui| {
+                egui::Frame::new()
+                    .fill(egui::Color32::from_rgba_unmultiplied(24, 36, 60, 230))
+                    .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(56, 95, 166)))
+                    .corner_radius(4)
+                    .inner_margin(egui::Margin::symmetric(8, 4))
+                    .show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            back = ui.link(tr(self.language, "scene")).clicked();
+                            ui.label(">");
+                            ui.label(egui::RichText::new(&name).strong());
+                            ui.separator();
+                            apply = ui.button(tr(self.language, "apply")).clicked();
+                        });
+                    });
+            });
+        if apply {
+            self.apply_prefab();
+        }
+        if back {
+            self.close_prefab();
+        }
+    }
+}
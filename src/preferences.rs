@@ -0,0 +1,332 @@
+use super::*;
+use crate::viewport::{CameraNavMode, CameraNavSettings, GameAspect, SnapSettings, SurfaceSnap};
+use std::time::{Duration, Instant};
+
+/// Intervalos do salvamento automático oferecidos na janela, em minutos (0 desliga)
+const AUTOSAVE_CHOICES: [u32; 7] = [0, 1, 2, 5, 10, 15, 30];
+
+/// Pasta de configuração do editor: `%APPDATA%\Dengine` no Windows,
+/// `~/Library/Application Support/Dengine` no macOS e `$XDG_CONFIG_HOME/dengine`
+/// (ou `~/.config/dengine`) nos demais. Sem nenhuma delas, a pasta atual
+pub(crate) fn config_dir() -> PathBuf {
+    platform_config_dir().unwrap_or_else(|| PathBuf::from("."))
+}
+
+#[cfg(target_os = "windows")]
+fn platform_config_dir() -> Option<PathBuf> {
+    std::env::var_os("APPDATA").map(|dir| PathBuf::from(dir).join("Dengine"))
+}
+
+#[cfg(target_os = "macos")]
+fn platform_config_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(|home| {
+        PathBuf::from(home)
+            .join("Library")
+            .join("Application Support")
+            .join("Dengine")
+    })
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn platform_config_dir() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .map(|dir| dir.join("dengine"))
+}
+
+/// Preferências do editor (valem para qualquer projeto), salvas em
+/// `editor_prefs.cfg` na pasta de configuração do sistema
+#[derive(Default)]
+pub(crate) struct EditorPreferences {
+    pub(crate) camera: CameraNavSettings,
+    pub(crate) snap: SnapSettings,
+    pub(crate) game_aspect: GameAspect,
+    // Id do tema (`dark`, `light`, `high_contrast` ou um tema do usuário)
+    pub(crate) theme: String,
+    // Sem valor salvo o editor abre no idioma do sistema
+    pub(crate) language: Option<EngineLanguage>,
+    // Último layout dos painéis, no formato de `ProjectSettings/layout.cfg`;
+    // vale para projetos sem layout próprio e para o editor sem projeto
+    pub(crate) layout: String,
+    // Minutos entre salvamentos automáticos do projeto; 0 desliga
+    pub(crate) autosave_minutes: u32,
+    pub(crate) open: bool,
+    last_autosave: Option<Instant>,
+}
+
+impl EditorPreferences {
+    fn config_path() -> PathBuf {
+        config_dir().join("editor_prefs.cfg")
+    }
+
+    /// Onde as versões anteriores salvavam, ao lado do executável
+    fn legacy_config_path() -> PathBuf {
+        PathBuf::from(".dengine_editor_prefs.cfg")
+    }
+
+    pub(crate) fn load() -> Self {
+        let mut prefs = Self {
+            autosave_minutes: 5,
+            ..Self::default()
+        };
+        let Ok(raw) = fs::read_to_string(Self::config_path())
+            .or_else(|_| fs::read_to_string(Self::legacy_config_path()))
+        else {
+            return prefs;
+        };
+        for (key, value) in raw.lines().filter_map(|line| line.split_once('=')) {
+            let value = value.trim();
+            match key.trim() {
+                "camera_mode" => {
+                    if let Some(mode) = CameraNavMode::from_config_str(value) {
+                        prefs.camera.mode = mode;
+                    }
+                }
+                "camera_speed" => {
+                    if let Ok(speed) = value.parse::<f32>() {
+                        prefs.camera.speed = speed.clamp(
+                            *CameraNavSettings::SPEED_RANGE.start(),
+                            *CameraNavSettings::SPEED_RANGE.end(),
+                        );
+                    }
+                }
+                "camera_acceleration" => {
+                    if let Ok(acceleration) = value.parse::<f32>() {
+                        prefs.camera.acceleration = acceleration.max(0.0);
+                    }
+                }
+                "camera_invert_y" => prefs.camera.invert_y = value == "true",
+                "grid_visible" => prefs.snap.grid_visible = value != "false",
+                "grid_spacing" => {
+                    if let Ok(spacing) = value.parse::<f32>() {
+                        prefs.snap.grid_spacing = spacing.max(0.01);
+                    }
+                }
+                "grid_extent" => {
+                    if let Ok(extent) = value.parse::<u32>() {
+                        prefs.snap.grid_extent = extent.clamp(1, 500);
+                    }
+                }
+                "snap_enabled" => prefs.snap.enabled = value == "true",
+                "snap_translate" => {
+                    if let Ok(step) = value.parse::<f32>() {
+                        prefs.snap.translate = step.max(0.0);
+                    }
+                }
+                "snap_rotate_deg" => {
+                    if let Ok(step) = value.parse::<f32>() {
+                        prefs.snap.rotate_deg = step.max(0.0);
+                    }
+                }
+                "snap_scale" => {
+                    if let Ok(step) = value.parse::<f32>() {
+                        prefs.snap.scale = step.max(0.0);
+                    }
+                }
+                "snap_surface" => {
+                    if let Some(surface) = SurfaceSnap::from_config_str(value) {
+                        prefs.snap.surface = surface;
+                    }
+                }
+                "game_aspect" => {
+                    if let Some(aspect) = GameAspect::from_config_str(value) {
+                        prefs.game_aspect = aspect;
+                    }
+                }
+                "theme" => prefs.theme = value.to_string(),
+                "language" => prefs.language = EngineLanguage::from_locale(value),
+                "autosave_minutes" => {
+                    if let Ok(minutes) = value.parse::<u32>() {
+                        prefs.autosave_minutes = minutes.min(120);
+                    }
+                }
+                // Uma linha `layout=` por linha do layout
+                "layout" => {
+                    prefs.layout.push_str(value);
+                    prefs.layout.push('\n');
+                }
+                _ => {}
+            }
+        }
+        prefs
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let mut out = format!(
+            "camera_mode={}\ncamera_speed={}\ncamera_acceleration={}\ncamera_invert_y={}\n",
+            self.camera.mode.as_config_str(),
+            self.camera.speed,
+            self.camera.acceleration,
+            self.camera.invert_y
+        );
+        out.push_str(&format!(
+            "grid_visible={}\ngrid_spacing={}\ngrid_extent={}\n",
+            self.snap.grid_visible, self.snap.grid_spacing, self.snap.grid_extent
+        ));
+        out.push_str(&format!(
+            "snap_enabled={}\nsnap_translate={}\nsnap_rotate_deg={}\nsnap_scale={}\nsnap_surface={}\n",
+            self.snap.enabled,
+            self.snap.translate,
+            self.snap.rotate_deg,
+            self.snap.scale,
+            self.snap.surface.as_config_str()
+        ));
+        out.push_str(&format!(
+            "game_aspect={}\n",
+            self.game_aspect.as_config_str()
+        ));
+        out.push_str(&format!("theme={}\n", self.theme));
+        if let Some(language) = self.language {
+            out.push_str(&format!("language={}\n", language.code()));
+        }
+        out.push_str(&format!("autosave_minutes={}\n", self.autosave_minutes));
+        for line in self.layout.lines() {
+            out.push_str(&format!("layout={line}\n"));
+        }
+        let path = Self::config_path();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        fs::write(path, out).map_err(|e| e.to_string())
+    }
+}
+
+fn tr(language: EngineLanguage, key: &'static str) -> &'static str {
+    i18n::text(language, "preferences", key)
+}
+
+impl EditorApp {
+    pub(crate) fn save_editor_preferences(&mut self) {
+        if let Err(err) = self.preferences.save() {
+            self.log.push(
+                LogLevel::Warning,
+                "Preferências",
+                format!("Falha ao salvar preferências: {err}"),
+            );
+        }
+    }
+
+    /// Grava as preferências quando a navegação da câmera, o snap, a proporção
+    /// do Game, o tema ou o idioma mudam. Arrastar um slider muda o valor a cada frame: só salva ao soltar.
+    pub(crate) fn sync_editor_preferences(&mut self, ctx: &egui::Context) {
+        let camera = self.viewport.camera_nav();
+        let snap = self.viewport.snap_settings();
+        let game_aspect = self.viewport.game_aspect();
+        let changed = camera != self.preferences.camera
+            || snap != self.preferences.snap
+            || game_aspect != self.preferences.game_aspect
+            || self.theme.selected_id() != self.preferences.theme
+            || self.preferences.language != Some(self.language);
+        if !changed || ctx.input(|i| i.pointer.any_down()) {
+            return;
+        }
+        self.preferences.camera = camera;
+        self.preferences.snap = snap;
+        self.preferences.game_aspect = game_aspect;
+        self.preferences.theme = self.theme.selected_id().to_string();
+        self.preferences.language = Some(self.language);
+        self.save_editor_preferences();
+    }
+
+    /// Salva o projeto aberto a cada `autosave_minutes`, como Arquivo → Salvar;
+    /// fica parado durante o Play para não gravar o estado da simulação
+    pub(crate) fn sync_autosave(&mut self) {
+        let minutes = self.preferences.autosave_minutes;
+        let Some(project) = self.current_project.clone() else {
+            return;
+        };
+        if minutes == 0 || self.viewport.in_play_mode() {
+            return;
+        }
+        let now = Instant::now();
+        let last = *self.preferences.last_autosave.get_or_insert(now);
+        if now.duration_since(last) < Duration::from_secs(u64::from(minutes) * 60) {
+            return;
+        }
+        self.preferences.last_autosave = Some(now);
+        let target = Self::resolve_project_file_path(&project, true);
+        if self.project.save_project_to_path(&target, self.language) {
+            self.log.push(
+                LogLevel::Info,
+                "Autosave",
+                format!("{} salvo", target.display()),
+            );
+        } else {
+            self.log.push(
+                LogLevel::Warning,
+                "Autosave",
+                format!("Falha ao salvar {}", target.display()),
+            );
+        }
+    }
+
+    pub(crate) fn draw_preferences_window(&mut self, ctx: &egui::Context) {
+        if !self.preferences.open {
+            return;
+        }
+        let lang = self.language;
+        let mut open = self.preferences.open;
+        let mut changed = false;
+        egui::Window::new(tr(lang, "title"))
+            .open(&mut open)
+            .resizable(false)
+            .default_width(300.0)
+            .show(ctx, |ui| {
+                egui::Grid::new("editor_preferences_grid")
+                    .num_columns(2)
+                    .spacing([12.0, 6.0])
+                    .show(ui, |ui| {
+                        ui.label(tr(lang, "language"));
+                        egui::ComboBox::from_id_salt("preferences_language")
+                            .selected_text(self.language_name(self.language))
+                            .show_ui(ui, |ui| {
+                                for language in EngineLanguage::ALL {
+                                    let name = self.language_name(language);
+                                    ui.selectable_value(&mut self.language, language, name);
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label(tr(lang, "autosave"));
+                        let label = |minutes: u32| {
+                            if minutes == 0 {
+                                tr(lang, "autosave_off").to_string()
+                            } else {
+                                format!("{minutes} min")
+                            }
+                        };
+                        egui::ComboBox::from_id_salt("preferences_autosave")
+                            .selected_text(label(self.preferences.autosave_minutes))
+                            .show_ui(ui, |ui| {
+                                for minutes in AUTOSAVE_CHOICES {
+                                    changed |= ui
+                                        .selectable_value(
+                                            &mut self.preferences.autosave_minutes,
+                                            minutes,
+                                            label(minutes),
+                                        )
+                                        .changed();
+                                }
+                            });
+                        ui.end_row();
+                    });
+                ui.add_space(6.0);
+                ui.label(
+                    egui::RichText::new(format!(
+                        "{}: {}",
+                        tr(lang, "config_file"),
+                        EditorPreferences::config_path().display()
+                    ))
+                    .small()
+                    .color(theme::palette().text_weak),
+                );
+            });
+        self.preferences.open = open;
+        if changed {
+            self.preferences.last_autosave = None;
+            self.save_editor_preferences();
+        }
+    }
+}
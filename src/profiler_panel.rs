@@ -0,0 +1,557 @@
+use super::*;
+use engine_core::profiler::{self, ProfileCategory, ProfileFrame};
+use std::time::Duration;
+
+const HEADER_HEIGHT: f32 = 26.0;
+const HISTORY_HEIGHT: f32 = 58.0;
+const SUMMARY_WIDTH: f32 = 300.0;
+const SUMMARY_ROWS: usize = 14;
+const THREAD_LABEL_HEIGHT: f32 = 16.0;
+const ROW_HEIGHT: f32 = 18.0;
+// Meta de 60 fps, linha de referência do histórico
+const TARGET_FRAME_MS: f32 = 1000.0 / 60.0;
+
+fn tr(language: EngineLanguage, key: &'static str) -> &'static str {
+    i18n::text(language, "profiler_panel", key)
+}
+
+fn category_key(category: ProfileCategory) -> &'static str {
+    match category {
+        ProfileCategory::Editor => "Editor",
+        ProfileCategory::Systems => "Systems",
+        ProfileCategory::Render => "Render",
+        ProfileCategory::Other => "Other",
+    }
+}
+
+fn category_color(category: ProfileCategory) -> egui::Color32 {
+    match category {
+        ProfileCategory::Editor => egui::Color32::from_rgb(86, 146, 214),
+        ProfileCategory::Systems => egui::Color32::from_rgb(104, 178, 104),
+        ProfileCategory::Render => egui::Color32::from_rgb(218, 142, 72),
+        ProfileCategory::Other => egui::Color32::from_rgb(150, 150, 150),
+    }
+}
+
+/// Cor do escopo: a da categoria, mais clara ou escura conforme o nome,
+/// para escopos vizinhos não se confundirem
+fn span_color(name: &str, category: ProfileCategory) -> egui::Color32 {
+    let hash = name
+        .bytes()
+        .fold(0u32, |h, b| h.wrapping_mul(31).wrapping_add(b as u32));
+    let shade = 0.75 + (hash % 8) as f32 * 0.05;
+    let base = category_color(category);
+    let channel = |c: u8| (c as f32 * shade).min(255.0) as u8;
+    egui::Color32::from_rgb(channel(base.r()), channel(base.g()), channel(base.b()))
+}
+
+fn ms(duration: Duration) -> f32 {
+    duration.as_secs_f32() * 1000.0
+}
+
+/// Painel Profiler: histórico dos últimos frames e flame graph do frame escolhido
+pub(crate) struct ProfilerPanel {
+    pub(crate) open: bool,
+    recording: bool,
+    // Cópia do histórico do profiler, congelada enquanto pausado
+    frames: Vec<Arc<ProfileFrame>>,
+    // Quantos frames o histórico e o resumo consideram
+    history: usize,
+    // Frame inspecionado (`ProfileFrame::index`); None segue o mais recente
+    selected: Option<u64>,
+    // Categorias visíveis, indexadas por `ProfileCategory::index`
+    shown: [bool; 4],
+    // Janela do flame graph em ms desde o início do frame; None = frame inteiro
+    view: Option<(f32, f32)>,
+}
+
+impl Default for ProfilerPanel {
+    fn default() -> Self {
+        Self {
+            open: false,
+            recording: true,
+            frames: Vec::new(),
+            history: 120,
+            selected: None,
+            shown: [true; 4],
+            view: None,
+        }
+    }
+}
+
+impl ProfilerPanel {
+    /// Liga a gravação só com o painel aberto e fecha o frame anterior;
+    /// chamado no começo de cada frame do editor
+    pub(crate) fn begin_frame(&mut self) {
+        profiler::set_enabled(self.open && self.recording);
+        profiler::finish_frame();
+        if profiler::is_enabled() {
+            self.frames = profiler::frames();
+        }
+    }
+
+    fn recent(&self) -> &[Arc<ProfileFrame>] {
+        let skip = self.frames.len().saturating_sub(self.history);
+        &self.frames[skip..]
+    }
+
+    fn selected_frame(&self) -> Option<Arc<ProfileFrame>> {
+        let recent = self.recent();
+        self.selected
+            .and_then(|index| recent.iter().find(|frame| frame.index == index))
+            .or_else(|| recent.last())
+            .cloned()
+    }
+
+    fn set_recording(&mut self, recording: bool) {
+        self.recording = recording;
+        if recording {
+            self.selected = None;
+            self.view = None;
+        }
+    }
+}
+
+impl EditorApp {
+    /// Painel Profiler no espaço que o layout de painéis deu a ele
+    pub(crate) fn draw_profiler_panel(&mut self, ctx: &egui::Context, panel: egui::Rect) {
+        engine_core::profile_function!();
+        if self.profiler.recording {
+            ctx.request_repaint();
+        }
+        egui::Area::new(egui::Id::new("profiler_panel"))
+            .order(egui::Order::Foreground)
+            .fixed_pos(panel.min)
+            .show(ctx, |ui| {
+                let (rect, _) = ui.allocate_exact_size(panel.size(), egui::Sense::hover());
+                ui.painter()
+                    .rect_filled(rect, 0.0, theme::palette().panel_bg);
+                ui.painter().rect_stroke(
+                    rect,
+                    0.0,
+                    egui::Stroke::new(1.0, theme::palette().border),
+                    egui::StrokeKind::Outside,
+                );
+                let inner = rect.shrink2(egui::vec2(8.0, 4.0));
+                let header =
+                    egui::Rect::from_min_size(inner.min, egui::vec2(inner.width(), HEADER_HEIGHT));
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(header)
+                        .layout(egui::Layout::left_to_right(egui::Align::Center)),
+                    |ui| self.draw_profiler_header(ui),
+                );
+
+                let body = egui::Rect::from_min_max(
+                    egui::pos2(inner.left(), header.bottom() + 4.0),
+                    inner.max,
+                );
+                if self.profiler.frames.is_empty() {
+                    ui.scope_builder(egui::UiBuilder::new().max_rect(body), |ui| {
+                        ui.label(egui::RichText::new(tr(self.language, "empty")).weak());
+                    });
+                    return;
+                }
+                let history =
+                    egui::Rect::from_min_size(body.min, egui::vec2(body.width(), HISTORY_HEIGHT));
+                self.draw_profiler_history(ui, history);
+
+                let lower_top = history.bottom() + 6.0;
+                let summary_width = SUMMARY_WIDTH.min(body.width() * 0.4);
+                let graph = egui::Rect::from_min_max(
+                    egui::pos2(body.left(), lower_top),
+                    egui::pos2(body.right() - summary_width - 8.0, body.bottom()),
+                );
+                let summary =
+                    egui::Rect::from_min_max(egui::pos2(graph.right() + 8.0, lower_top), body.max);
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(graph)
+                        .layout(egui::Layout::top_down(egui::Align::Min)),
+                    |ui| self.draw_profiler_flame_graph(ui),
+                );
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(summary)
+                        .layout(egui::Layout::top_down(egui::Align::Min)),
+                    |ui| self.draw_profiler_summary(ui),
+                );
+            });
+    }
+
+    fn draw_profiler_header(&mut self, ui: &mut egui::Ui) {
+        let language = self.language;
+        let (label, color) = if self.profiler.recording {
+            (tr(language, "pause"), egui::Color32::from_rgb(220, 90, 80))
+        } else {
+            (tr(language, "record"), ui.visuals().text_color())
+        };
+        if ui.button(egui::RichText::new(label).color(color)).clicked() {
+            let recording = !self.profiler.recording;
+            self.profiler.set_recording(recording);
+        }
+        if ui.button(tr(language, "clear")).clicked() {
+            profiler::clear_frames();
+            self.profiler.frames.clear();
+            self.profiler.selected = None;
+            self.profiler.view = None;
+        }
+        ui.separator();
+        for category in ProfileCategory::ALL {
+            let shown = &mut self.profiler.shown[category.index()];
+            let text = egui::RichText::new(tr(language, category_key(category))).color(if *shown {
+                category_color(category)
+            } else {
+                egui::Color32::from_gray(110)
+            });
+            if ui.selectable_label(*shown, text).clicked() {
+                *shown = !*shown;
+            }
+        }
+        ui.separator();
+        ui.label(tr(language, "frames"));
+        ui.add(
+            egui::DragValue::new(&mut self.profiler.history)
+                .range(10..=profiler::MAX_FRAMES)
+                .speed(1.0),
+        );
+
+        let recent = self.profiler.recent();
+        if !recent.is_empty() {
+            let total: f32 = recent.iter().map(|frame| ms(frame.duration)).sum();
+            let max = recent
+                .iter()
+                .map(|frame| ms(frame.duration))
+                .fold(0.0, f32::max);
+            ui.separator();
+            ui.label(
+                egui::RichText::new(format!(
+                    "{} {:.2} ms · {} {:.2} ms",
+                    tr(language, "average"),
+                    total / recent.len() as f32,
+                    tr(language, "max"),
+                    max
+                ))
+                .small()
+                .monospace(),
+            );
+        }
+        if let Some(frame) = self.profiler.selected_frame() {
+            ui.separator();
+            ui.label(
+                egui::RichText::new(format!(
+                    "{} #{}: {:.2} ms",
+                    tr(language, "frame"),
+                    frame.index,
+                    ms(frame.duration)
+                ))
+                .small()
+                .strong(),
+            );
+            for category in ProfileCategory::ALL {
+                let time = frame.category_time(category);
+                if !time.is_zero() {
+                    ui.label(
+                        egui::RichText::new(format!("{:.2}", ms(time)))
+                            .small()
+                            .monospace()
+                            .color(category_color(category)),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Barras dos últimos frames, empilhadas por categoria; clicar pausa e
+    /// abre o frame no flame graph
+    fn draw_profiler_history(&mut self, ui: &mut egui::Ui, rect: egui::Rect) {
+        let response = ui.interact(rect, ui.id().with("profiler_history"), egui::Sense::click());
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 2.0, egui::Color32::from_rgb(24, 24, 24));
+
+        let recent = self.profiler.recent().to_vec();
+        let scale_ms = recent
+            .iter()
+            .map(|frame| ms(frame.duration))
+            .fold(TARGET_FRAME_MS * 2.0, f32::max);
+        let to_y = |value_ms: f32| rect.bottom() - value_ms / scale_ms * rect.height();
+        for guide in [TARGET_FRAME_MS, TARGET_FRAME_MS * 2.0] {
+            let y = to_y(guide);
+            painter.hline(
+                rect.x_range(),
+                y,
+                egui::Stroke::new(1.0, theme::palette().widget_hover),
+            );
+            painter.text(
+                egui::pos2(rect.left() + 3.0, y - 1.0),
+                egui::Align2::LEFT_BOTTOM,
+                format!("{guide:.1} ms"),
+                egui::FontId::monospace(9.0),
+                egui::Color32::from_gray(110),
+            );
+        }
+
+        let slot = rect.width() / self.profiler.history.max(1) as f32;
+        let selected = self.profiler.selected_frame().map(|frame| frame.index);
+        let first_x = rect.right() - slot * recent.len() as f32;
+        for (i, frame) in recent.iter().enumerate() {
+            let left = first_x + slot * i as f32;
+            let bar = |from: f32, to: f32| {
+                egui::Rect::from_min_max(
+                    egui::pos2(left + 0.5, to_y(to)),
+                    egui::pos2(left + (slot - 1.0).max(1.0), to_y(from)),
+                )
+            };
+            painter.rect_filled(
+                bar(0.0, ms(frame.duration)),
+                0.0,
+                egui::Color32::from_rgb(52, 52, 52),
+            );
+            let mut stacked = 0.0;
+            for category in ProfileCategory::ALL {
+                if !self.profiler.shown[category.index()] {
+                    continue;
+                }
+                let time = ms(frame.category_time(category));
+                if time > 0.0 {
+                    painter.rect_filled(
+                        bar(stacked, stacked + time),
+                        0.0,
+                        category_color(category),
+                    );
+                    stacked += time;
+                }
+            }
+            if selected == Some(frame.index) {
+                painter.rect_stroke(
+                    bar(0.0, ms(frame.duration)).expand(1.0),
+                    0.0,
+                    egui::Stroke::new(1.0, egui::Color32::WHITE),
+                    egui::StrokeKind::Outside,
+                );
+            }
+        }
+
+        if let Some(pos) = response.interact_pointer_pos().or(response.hover_pos()) {
+            let i = ((pos.x - first_x) / slot).floor();
+            if let Some(frame) = (i >= 0.0).then(|| recent.get(i as usize)).flatten() {
+                if response.clicked() {
+                    self.profiler.set_recording(false);
+                    self.profiler.selected = Some(frame.index);
+                    self.profiler.view = None;
+                }
+                response.on_hover_text(format!(
+                    "{} #{}: {:.2} ms",
+                    tr(self.language, "frame"),
+                    frame.index,
+                    ms(frame.duration)
+                ));
+            }
+        }
+    }
+
+    /// Escopos do frame escolhido: uma faixa por thread, uma linha por nível
+    fn draw_profiler_flame_graph(&mut self, ui: &mut egui::Ui) {
+        let language = self.language;
+        let Some(frame) = self.profiler.selected_frame() else {
+            return;
+        };
+        let frame_ms = ms(frame.duration).max(0.001);
+        let (view_start, view_len) = self.profiler.view.unwrap_or((0.0, frame_ms));
+        ui.label(
+            egui::RichText::new(tr(language, "graph_help"))
+                .small()
+                .weak(),
+        );
+
+        let lanes: Vec<(u32, &str, u16)> = frame
+            .threads
+            .iter()
+            .map(|(thread, name)| {
+                let depth = frame
+                    .spans
+                    .iter()
+                    .filter(|span| span.thread == *thread)
+                    .map(|span| span.depth + 1)
+                    .max()
+                    .unwrap_or(1);
+                (*thread, name.as_str(), depth)
+            })
+            .collect();
+        let height: f32 = lanes
+            .iter()
+            .map(|(_, _, depth)| THREAD_LABEL_HEIGHT + *depth as f32 * ROW_HEIGHT + 4.0)
+            .sum();
+
+        let mut new_view = None;
+        egui::ScrollArea::vertical()
+            .id_salt("profiler_flame_graph")
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                let width = ui.available_width();
+                let (rect, response) = ui.allocate_exact_size(
+                    egui::vec2(width, height.max(ui.available_height())),
+                    egui::Sense::click_and_drag(),
+                );
+                let painter = ui.painter_at(rect);
+                let to_x = |t: f32| rect.left() + (t - view_start) / view_len * rect.width();
+
+                // Roda do mouse aproxima em volta do cursor, arrastar desloca
+                let mut start = view_start;
+                let mut len = view_len;
+                if let Some(pos) = response.hover_pos() {
+                    let scroll = ui.input(|i| i.smooth_scroll_delta.y);
+                    if scroll != 0.0 {
+                        let anchor = start + (pos.x - rect.left()) / rect.width() * len;
+                        let zoomed = (len * (-scroll * 0.003).exp()).clamp(0.01, frame_ms);
+                        start = anchor - (anchor - start) * zoomed / len;
+                        len = zoomed;
+                    }
+                }
+                if response.dragged() {
+                    start -= response.drag_delta().x / rect.width() * len;
+                }
+                if response.double_clicked() {
+                    start = 0.0;
+                    len = frame_ms;
+                }
+                start = start.clamp(0.0, (frame_ms - len).max(0.0));
+                if (start, len) != (view_start, view_len) {
+                    new_view = Some((start, len));
+                }
+
+                let mut hovered = None;
+                let mut top = rect.top();
+                for (thread, name, depth) in &lanes {
+                    painter.text(
+                        egui::pos2(rect.left() + 2.0, top + THREAD_LABEL_HEIGHT * 0.5),
+                        egui::Align2::LEFT_CENTER,
+                        *name,
+                        egui::FontId::proportional(11.0),
+                        theme::palette().text_weak,
+                    );
+                    let lane_top = top + THREAD_LABEL_HEIGHT;
+                    for span in frame.spans.iter().filter(|span| span.thread == *thread) {
+                        if !self.profiler.shown[span.category.index()] {
+                            continue;
+                        }
+                        let x0 = to_x(ms(span.start)).max(rect.left());
+                        let x1 = to_x(ms(span.end())).min(rect.right());
+                        if x1 < rect.left() || x0 > rect.right() {
+                            continue;
+                        }
+                        let y0 = lane_top + span.depth as f32 * ROW_HEIGHT;
+                        let bar = egui::Rect::from_min_max(
+                            egui::pos2(x0, y0),
+                            egui::pos2(x1.max(x0 + 1.0), y0 + ROW_HEIGHT - 1.0),
+                        );
+                        painter.rect_filled(bar, 2.0, span_color(span.name, span.category));
+                        if bar.width() > 30.0 {
+                            painter.with_clip_rect(bar.intersect(rect)).text(
+                                egui::pos2(bar.left() + 4.0, bar.center().y),
+                                egui::Align2::LEFT_CENTER,
+                                span.name,
+                                egui::FontId::proportional(11.0),
+                                egui::Color32::from_rgb(20, 20, 20),
+                            );
+                        }
+                        if response.hover_pos().is_some_and(|pos| bar.contains(pos)) {
+                            hovered = Some(*span);
+                        }
+                    }
+                    top = lane_top + *depth as f32 * ROW_HEIGHT + 4.0;
+                    painter.hline(
+                        rect.x_range(),
+                        top - 2.0,
+                        egui::Stroke::new(1.0, egui::Color32::from_rgb(45, 45, 45)),
+                    );
+                }
+
+                if let Some(span) = hovered {
+                    response.on_hover_text(format!(
+                        "{}\n{}\n{:.3} ms ({:.1}% {})",
+                        span.name,
+                        tr(language, category_key(span.category)),
+                        ms(span.duration),
+                        ms(span.duration) / frame_ms * 100.0,
+                        tr(language, "of_frame"),
+                    ));
+                }
+            });
+        if let Some((start, len)) = new_view {
+            self.profiler.view = if len >= frame_ms && start <= 0.0 {
+                None
+            } else {
+                Some((start, len))
+            };
+        }
+    }
+
+    /// Escopos que mais custaram nos últimos frames, em média por frame
+    fn draw_profiler_summary(&mut self, ui: &mut egui::Ui) {
+        let language = self.language;
+        let recent = self.profiler.recent();
+        let mut totals: HashMap<(&'static str, ProfileCategory), (usize, Duration, Duration)> =
+            HashMap::new();
+        for frame in recent {
+            for span in &frame.spans {
+                let entry = totals.entry((span.name, span.category)).or_default();
+                entry.0 += 1;
+                entry.1 += span.duration;
+                entry.2 = entry.2.max(span.duration);
+            }
+        }
+        let mut rows: Vec<_> = totals
+            .into_iter()
+            .filter(|((_, category), _)| self.profiler.shown[category.index()])
+            .collect();
+        rows.sort_by(|a, b| b.1.1.cmp(&a.1.1));
+        let frame_count = recent.len().max(1) as f32;
+
+        egui::ScrollArea::vertical()
+            .id_salt("profiler_summary")
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                egui::Grid::new("profiler_summary_grid")
+                    .num_columns(4)
+                    .spacing([8.0, 2.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label(egui::RichText::new(tr(language, "scope")).small().strong());
+                        ui.label(
+                            egui::RichText::new(tr(language, "per_frame"))
+                                .small()
+                                .strong(),
+                        );
+                        ui.label(egui::RichText::new(tr(language, "max")).small().strong());
+                        ui.label(egui::RichText::new(tr(language, "calls")).small().strong());
+                        ui.end_row();
+                        for ((name, category), (calls, total, max)) in
+                            rows.into_iter().take(SUMMARY_ROWS)
+                        {
+                            ui.label(
+                                egui::RichText::new(name)
+                                    .small()
+                                    .color(category_color(category)),
+                            );
+                            ui.label(
+                                egui::RichText::new(format!("{:.3}", ms(total) / frame_count))
+                                    .small()
+                                    .monospace(),
+                            );
+                            ui.label(
+                                egui::RichText::new(format!("{:.3}", ms(max)))
+                                    .small()
+                                    .monospace(),
+                            );
+                            ui.label(
+                                egui::RichText::new(format!("{:.1}", calls as f32 / frame_count))
+                                    .small()
+                                    .monospace(),
+                            );
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+}
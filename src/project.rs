@@ -3,27 +3,43 @@ use std::fs::{self, File};
 use std::hash::{Hash, Hasher};
 use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
+use std::process::Command;
 use std::sync::Arc;
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::mpsc::{self, Receiver, Sender};
 
 use eframe::egui::{
     self, Align2, Color32, FontFamily, FontId, Id, Key, Order, Pos2, Rect, Sense, Stroke,
     TextureHandle, Vec2,
 };
+use engine_core::MAX_LOD_LEVELS;
+use engine_render::{
+    AssetDatabase, AssetGuid, AssetKind, AssetSearchIndex, AssetType, FormatKind, MeshData,
+    SearchQuery, ThumbnailGenerator, ThumbnailKind, ensure_guid, generate_lods, is_sidecar,
+    meta_path, parse_labels, read_labels, same_file, write_labels, write_meta_value, write_obj,
+};
 use epaint::ColorImage;
 
 use crate::EngineLanguage;
+use crate::audio::{AudioClipView, paint_waveform};
+use crate::i18n;
+use crate::inspector::AudioPreviewRequest;
+use crate::theme;
 
 pub struct ProjectWindow {
     pub open: bool,
-    panel_height: f32,
-    resizing_height: bool,
     selected_folder: &'static str,
+    // Pasta criada pelo usuário aberta na grade; `selected_folder` fica com a
+    // pasta conhecida mais próxima acima dela
+    sub_folder: Option<PathBuf>,
+    // Pastas do usuário abertas na árvore
+    open_tree_folders: HashSet<PathBuf>,
+    // Asset ou pasta sendo renomeado e o nome digitado
+    rename_editor: Option<(PathBuf, String)>,
     selected_asset: Option<String>,
     selected_sub_asset: Option<String>,
     search_query: String,
     icon_scale: f32,
+    // Grade de miniaturas ou lista com uma linha por asset
+    list_view: bool,
     deleted_assets: HashSet<String>,
     status_text: String,
     arrow_icon_texture: Option<TextureHandle>,
@@ -37,24 +53,83 @@ pub struct ProjectWindow {
     preview_cache: BTreeMap<String, TextureHandle>,
     preview_lru: VecDeque<String>,
     dragging_asset: Option<String>,
-    image_preview_tx: Sender<ImagePreviewDecoded>,
-    image_preview_rx: Receiver<ImagePreviewDecoded>,
-    mesh_preview_tx: Sender<MeshPreviewDecoded>,
-    mesh_preview_rx: Receiver<MeshPreviewDecoded>,
-    image_preview_pending: HashSet<String>,
-    mesh_preview_pending: HashSet<String>,
-    image_preview_workers: Arc<AtomicUsize>,
-    mesh_preview_workers: Arc<AtomicUsize>,
+    // Miniaturas de texturas, malhas e materiais, geradas e cacheadas em disco
+    thumbnails: ThumbnailGenerator,
+    // Quadros do giro de cada miniatura de malha (1 para as demais)
+    preview_frames: BTreeMap<String, u32>,
     fbx_meta_cache: BTreeMap<String, FbxMetaCacheEntry>,
     fbx_expanded_assets: HashSet<String>,
     last_panel_rect: Option<Rect>,
     hovered_asset: Option<String>,
+    model_import: ModelImportSettings,
+    // Modelo escolhido no diálogo, aguardando as opções de importação
+    pending_model_import: Option<PathBuf>,
+    // GUIDs e quem referencia quem, refeito pelo hot reload a cada mudança em Assets
+    asset_database: AssetDatabase,
+    asset_links: Option<AssetLinks>,
+    // Asset usado por outros aguardando confirmação para excluir
+    pending_delete: Option<(String, Vec<PathBuf>)>,
+    // Clipe de áudio selecionado: forma de onda e botão de pre-escuta no bloco
+    audio_clip_view: Option<AudioClipView>,
+    pending_audio_preview: Option<AudioPreviewRequest>,
+    // Material aberto com duplo clique ou "Abrir", para o editor de materiais
+    pending_material_open: Option<PathBuf>,
+    // Prefab (.mold) aberto com duplo clique, para a edição isolada
+    pending_prefab_open: Option<PathBuf>,
+    // Nomes, tipos e rótulos de todos os assets, para a busca e os seletores de asset
+    search_index: Arc<AssetSearchIndex>,
+    // Lista de resultados do projeto inteiro aberta sob a caixa de busca
+    search_popup_open: bool,
+    search_box_rect: Option<Rect>,
+    // Asset com os rótulos em edição e o texto digitado
+    labels_editor: Option<(PathBuf, String)>,
+    // Operação de arquivo feita neste frame, para o desfazer do editor
+    pending_asset_edit: Option<AssetFileEdit>,
+    // "Reimportar" do menu, aplicado pelo editor com as opções do .meta
+    pending_reimport: Option<PathBuf>,
+}
+
+/// Operação de arquivo do Projeto, desfeita e refeita por Ctrl+Z / Ctrl+Shift+Z
+#[derive(Clone)]
+pub(crate) enum AssetFileEdit {
+    /// Renomeado ou movido, com o .meta
+    Move { from: PathBuf, to: PathBuf },
+    /// Cópia ao lado do original, com GUID próprio
+    Duplicate { source: PathBuf, copy: PathBuf },
+    /// Escondido do Projeto pela exclusão
+    Delete { asset: String },
+}
+
+/// Resultado de "Encontrar referências/dependências" de um asset
+struct AssetLinks {
+    asset: String,
+    // true = quem usa o asset; false = o que o asset usa
+    references: bool,
+    paths: Vec<PathBuf>,
+}
+
+/// Opções da janela de importação de modelos
+#[derive(Clone, Copy)]
+struct ModelImportSettings {
+    generate_lods: bool,
+    // Proporção de triângulos de cada nível em relação ao anterior
+    lod_ratio: f32,
+    lod_levels: usize,
 }
 
-struct MeshPreview {
-    lines: Vec<([f32; 2], [f32; 2])>,
+impl Default for ModelImportSettings {
+    fn default() -> Self {
+        Self {
+            generate_lods: false,
+            lod_ratio: 0.5,
+            lod_levels: 2,
+        }
+    }
 }
 
+/// Resultados mostrados na lista da busca
+const SEARCH_POPUP_RESULTS: usize = 20;
+
 const KNOWN_FOLDER_PATHS: &[(&str, &str)] = &[
     ("Assets", "Assets"),
     ("Assets/Animations", "Animations"),
@@ -81,18 +156,8 @@ struct FbxMetaCacheEntry {
     meta: FbxAssetMeta,
 }
 
-struct ImagePreviewDecoded {
-    key: String,
-    image: Option<([usize; 2], Vec<u8>)>,
-}
-
-struct MeshPreviewDecoded {
-    key: String,
-    image: Option<([usize; 2], Vec<u8>)>,
-}
-
 fn load_png_as_texture(ctx: &egui::Context, png_path: &str) -> Option<TextureHandle> {
-    let bytes = std::fs::read(png_path).ok()?;
+    let bytes = engine_render::vfs_read(png_path).ok()?;
     let rgba = image::load_from_memory(&bytes).ok()?.to_rgba8();
     let size = [rgba.width() as usize, rgba.height() as usize];
     let color_image = ColorImage::from_rgba_unmultiplied(size, rgba.as_raw());
@@ -105,25 +170,35 @@ fn load_png_as_texture(ctx: &egui::Context, png_path: &str) -> Option<TextureHan
 
 impl ProjectWindow {
     const MAX_IMAGE_PREVIEWS: usize = 128;
-    const MAX_IMAGE_PREVIEW_WORKERS: usize = 4;
-    const MAX_MESH_PREVIEW_WORKERS: usize = 2;
-    const MESH_THUMB_SIZE: [usize; 2] = [176, 124];
+    const THUMBNAIL_WORKERS: usize = 3;
+    // Quadros por segundo do giro da malha sob o mouse
+    const TURNTABLE_FPS: f64 = 8.0;
+    // Faixa do tamanho das miniaturas na grade
+    const ICON_SCALE_MIN: f32 = 48.0;
+    const ICON_SCALE_MAX: f32 = 160.0;
+    // Altura de cada linha no modo lista
+    const LIST_ROW_H: f32 = 22.0;
+    // Largura do selo de tipo no fim da linha no modo lista
+    const LIST_BADGE_W: f32 = 38.0;
 
     pub fn new() -> Self {
         let _ = fs::create_dir_all("Assets/Textures");
-        let (img_tx, img_rx) = mpsc::channel();
-        let (mesh_tx, mesh_rx) = mpsc::channel();
-        let image_preview_workers = Arc::new(AtomicUsize::new(0));
-        let mesh_preview_workers = Arc::new(AtomicUsize::new(0));
+        let thumbnails = ThumbnailGenerator::new(
+            Path::new("Assets").join(".cache").join("thumbnails"),
+            Self::THUMBNAIL_WORKERS,
+            load_preview_mesh_cached,
+        );
         Self {
             open: true,
-            panel_height: 260.0,
-            resizing_height: false,
             selected_folder: "Assets",
+            sub_folder: None,
+            open_tree_folders: HashSet::new(),
+            rename_editor: None,
             selected_asset: None,
             selected_sub_asset: None,
             search_query: String::new(),
             icon_scale: 72.0,
+            list_view: false,
             deleted_assets: HashSet::new(),
             status_text: String::new(),
             arrow_icon_texture: None,
@@ -137,18 +212,27 @@ impl ProjectWindow {
             preview_cache: BTreeMap::new(),
             preview_lru: VecDeque::new(),
             dragging_asset: None,
-            image_preview_tx: img_tx,
-            image_preview_rx: img_rx,
-            mesh_preview_tx: mesh_tx,
-            mesh_preview_rx: mesh_rx,
-            image_preview_pending: HashSet::new(),
-            mesh_preview_pending: HashSet::new(),
-            image_preview_workers,
-            mesh_preview_workers,
+            thumbnails,
+            preview_frames: BTreeMap::new(),
             fbx_meta_cache: BTreeMap::new(),
             fbx_expanded_assets: HashSet::new(),
             last_panel_rect: None,
             hovered_asset: None,
+            model_import: ModelImportSettings::default(),
+            pending_model_import: None,
+            asset_database: AssetDatabase::default(),
+            asset_links: None,
+            pending_delete: None,
+            audio_clip_view: None,
+            pending_audio_preview: None,
+            pending_material_open: None,
+            pending_prefab_open: None,
+            search_index: Arc::new(AssetSearchIndex::default()),
+            search_popup_open: false,
+            search_box_rect: None,
+            labels_editor: None,
+            pending_asset_edit: None,
+            pending_reimport: None,
         }
     }
 
@@ -165,132 +249,104 @@ impl ProjectWindow {
                 break;
             };
             self.preview_cache.remove(&old_key);
+            self.preview_frames.remove(&old_key);
         }
     }
 
     fn poll_preview_jobs(&mut self, ctx: &egui::Context) {
-        while let Ok(decoded) = self.image_preview_rx.try_recv() {
-            if let Some((size, rgba)) = decoded.image {
-                let color_image = ColorImage::from_rgba_unmultiplied(size, &rgba);
-                let tex = ctx.load_texture(
-                    decoded.key.clone(),
-                    color_image,
-                    egui::TextureOptions::LINEAR,
-                );
-                self.preview_cache.insert(decoded.key.clone(), tex);
-                Self::lru_touch(&mut self.preview_lru, &decoded.key);
-                self.evict_preview_cache_if_needed();
-            }
-            self.image_preview_pending.remove(&decoded.key);
-        }
-        while let Ok(decoded) = self.mesh_preview_rx.try_recv() {
-            if let Some((size, rgba)) = decoded.image {
-                let color_image = ColorImage::from_rgba_unmultiplied(size, &rgba);
-                let tex = ctx.load_texture(
-                    decoded.key.clone(),
-                    color_image,
-                    egui::TextureOptions::LINEAR,
-                );
-                self.preview_cache.insert(decoded.key.clone(), tex);
-                Self::lru_touch(&mut self.preview_lru, &decoded.key);
-                self.evict_preview_cache_if_needed();
-            }
-            self.mesh_preview_pending.remove(&decoded.key);
+        for result in self.thumbnails.poll() {
+            let Ok(thumbnail) = result.thumbnail else {
+                continue;
+            };
+            let key = result.path.to_string_lossy().to_string();
+            let size = [
+                (thumbnail.width * thumbnail.frames) as usize,
+                thumbnail.height as usize,
+            ];
+            let color_image = ColorImage::from_rgba_unmultiplied(size, &thumbnail.rgba);
+            let tex = ctx.load_texture(key.clone(), color_image, egui::TextureOptions::LINEAR);
+            self.preview_cache.insert(key.clone(), tex);
+            self.preview_frames.insert(key.clone(), thumbnail.frames);
+            Self::lru_touch(&mut self.preview_lru, &key);
+            self.evict_preview_cache_if_needed();
         }
     }
 
-    fn tr(&self, lang: EngineLanguage, key: &'static str) -> &'static str {
-        match (lang, key) {
-            (EngineLanguage::Pt, "title") => "Projeto",
-            (EngineLanguage::En, "title") => "Project",
-            (EngineLanguage::Es, "title") => "Proyecto",
-            (EngineLanguage::Pt, "assets") => "Assets",
-            (EngineLanguage::En, "assets") => "Assets",
-            (EngineLanguage::Es, "assets") => "Assets",
-            (EngineLanguage::Pt, "packages") => "Pacotes",
-            (EngineLanguage::En, "packages") => "Packages",
-            (EngineLanguage::Es, "packages") => "Paquetes",
-            (EngineLanguage::Pt, "search") => "Buscar em Assets",
-            (EngineLanguage::En, "search") => "Search in Assets",
-            (EngineLanguage::Es, "search") => "Buscar en Assets",
-            (EngineLanguage::Pt, "count") => "itens",
-            (EngineLanguage::En, "count") => "items",
-            (EngineLanguage::Es, "count") => "elementos",
-            (EngineLanguage::Pt, "open") => "Abrir",
-            (EngineLanguage::En, "open") => "Open",
-            (EngineLanguage::Es, "open") => "Abrir",
-            (EngineLanguage::Pt, "reveal") => "Mostrar no Explorer",
-            (EngineLanguage::En, "reveal") => "Show in Explorer",
-            (EngineLanguage::Es, "reveal") => "Mostrar en Explorer",
-            (EngineLanguage::Pt, "delete") => "Excluir",
-            (EngineLanguage::En, "delete") => "Delete",
-            (EngineLanguage::Es, "delete") => "Eliminar",
-            (EngineLanguage::Pt, "import") => "Importar",
-            (EngineLanguage::En, "import") => "Import",
-            (EngineLanguage::Es, "import") => "Importar",
-            (EngineLanguage::Pt, "save") => "Salvar",
-            (EngineLanguage::En, "save") => "Save",
-            (EngineLanguage::Es, "save") => "Guardar",
-            (EngineLanguage::Pt, "create") => "Criar",
-            (EngineLanguage::En, "create") => "Create",
-            (EngineLanguage::Es, "create") => "Crear",
-            (EngineLanguage::Pt, "create_script") => "Script C#",
-            (EngineLanguage::En, "create_script") => "C# Script",
-            (EngineLanguage::Es, "create_script") => "Script C#",
-            (EngineLanguage::Pt, "create_material") => "Material",
-            (EngineLanguage::En, "create_material") => "Material",
-            (EngineLanguage::Es, "create_material") => "Material",
-            (EngineLanguage::Pt, "create_folder") => "Pasta",
-            (EngineLanguage::En, "create_folder") => "Folder",
-            (EngineLanguage::Es, "create_folder") => "Carpeta",
-            (EngineLanguage::Pt, "created") => "Criado",
-            (EngineLanguage::En, "created") => "Created",
-            (EngineLanguage::Es, "created") => "Creado",
-            _ => key,
+    /// Descarta a miniatura de um arquivo alterado em disco; a próxima é refeita
+    pub fn invalidate_thumbnail(&mut self, path: &Path) {
+        let stale: Vec<String> = self
+            .preview_cache
+            .keys()
+            .filter(|key| same_file(Path::new(key.as_str()), path))
+            .cloned()
+            .collect();
+        for key in stale {
+            self.preview_cache.remove(&key);
+            self.preview_frames.remove(&key);
         }
     }
 
+    fn tr(&self, lang: EngineLanguage, key: &'static str) -> &'static str {
+        i18n::text(lang, "project", key)
+    }
+
     fn is_package_folder(folder: &str) -> bool {
         matches!(folder, "Packages" | "TextMeshPro" | "InputSystem")
     }
 
-    fn breadcrumb_segments(&self, language: EngineLanguage) -> Vec<(&'static str, String)> {
-        if self.selected_folder == "Packages" {
-            vec![("Packages", self.tr(language, "packages").to_string())]
-        } else if self.selected_folder == "Assets" {
-            vec![("Assets", self.tr(language, "assets").to_string())]
-        } else if Self::is_package_folder(self.selected_folder) {
-            vec![
-                ("Packages", self.tr(language, "packages").to_string()),
-                (self.selected_folder, self.selected_folder.to_string()),
-            ]
-        } else {
-            vec![
-                ("Assets", self.tr(language, "assets").to_string()),
-                (self.selected_folder, self.selected_folder.to_string()),
-            ]
+    /// Um segmento por pasta do caminho aberto (Assets > Meshes > Props)
+    fn breadcrumb_segments(&self, language: EngineLanguage) -> Vec<(PathBuf, String)> {
+        let Some(current) = self.selected_folder_path() else {
+            return Vec::new();
+        };
+        let mut out = Vec::new();
+        let mut acc = PathBuf::new();
+        for component in current.components() {
+            acc.push(component);
+            let label = if acc == Path::new("Assets") {
+                self.tr(language, "assets").to_string()
+            } else if acc == Path::new("Packages") {
+                self.tr(language, "packages").to_string()
+            } else {
+                component.as_os_str().to_string_lossy().to_string()
+            };
+            out.push((acc.clone(), label));
         }
+        out
     }
 
     fn assets_for_folder(&self) -> Vec<String> {
-        self.assets_for_folder_id(self.selected_folder)
+        match &self.sub_folder {
+            Some(dir) => {
+                let mut out = Self::list_dir_assets(dir);
+                out.sort_by_key(|s| s.to_ascii_lowercase());
+                out
+            }
+            None => self.assets_for_folder_id(self.selected_folder),
+        }
     }
 
-    fn assets_for_folder_id(&self, folder: &'static str) -> Vec<String> {
-        let mut out: Vec<String> = Vec::new();
-        if let Some(folder_path) = Self::folder_path_from_id(folder) {
-            if let Ok(entries) = fs::read_dir(folder_path) {
-                for entry in entries.flatten() {
-                    let Some(name) = entry.file_name().to_str().map(|s| s.to_string()) else {
-                        continue;
-                    };
-                    if name.starts_with('.') {
-                        continue;
-                    }
-                    out.push(name);
+    /// Arquivos e pastas visíveis de `dir` (sem ocultos nem .meta)
+    fn list_dir_assets(dir: &Path) -> Vec<String> {
+        let mut out = Vec::new();
+        if let Ok(entries) = fs::read_dir(dir) {
+            for entry in entries.flatten() {
+                let Some(name) = entry.file_name().to_str().map(|s| s.to_string()) else {
+                    continue;
+                };
+                if name.starts_with('.') || is_sidecar(Path::new(&name)) {
+                    continue;
                 }
+                out.push(name);
             }
         }
+        out
+    }
+
+    fn assets_for_folder_id(&self, folder: &'static str) -> Vec<String> {
+        let mut out: Vec<String> = Self::folder_path_from_id(folder)
+            .map(|folder_path| Self::list_dir_assets(&folder_path))
+            .unwrap_or_default();
 
         if let Some(extra) = self.imported_assets.get(folder) {
             for name in extra {
@@ -354,11 +410,51 @@ impl ProjectWindow {
     }
 
     /// Extrai o caminho da textura de um arquivo FBX (se existir textura embutida ou referenciada)
+    /// Textura albedo dos materiais do FBX/OBJ; texturas embutidas são extraídas para Assets/Textures
+    fn extract_imported_material_texture(mesh_path: &Path) -> Option<String> {
+        let mut assets = engine_render::AssetManager::new();
+        let scene = match mesh_path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("obj") => assets.import_obj(mesh_path),
+            _ => assets.import_fbx(mesh_path),
+        }
+        .ok()?;
+        let material = scene.materials.iter().find_map(|m| {
+            assets
+                .get_material(*m)
+                .filter(|m| m.albedo_texture.is_some() || m.albedo_texture_handle.is_some())
+        })?;
+        if let Some(path) = &material.albedo_texture {
+            return Some(path.clone());
+        }
+        let texture = assets.get_texture(material.albedo_texture_handle?)?;
+        let texture_name: String = texture
+            .name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        let dest_tex = Path::new("Assets").join("Textures").join(format!(
+            "{}_{}.png",
+            mesh_path.file_stem()?.to_string_lossy(),
+            texture_name
+        ));
+        std::fs::create_dir_all("Assets/Textures").ok()?;
+        image::save_buffer(
+            &dest_tex,
+            &texture.rgba,
+            texture.width,
+            texture.height,
+            image::ColorType::Rgba8,
+        )
+        .ok()?;
+        log::debug!("[Import] Textura embutida extraída: {:?}", dest_tex);
+        Some(dest_tex.to_string_lossy().to_string())
+    }
+
     fn extract_fbx_texture_path(fbx_path: &Path) -> Option<String> {
-        eprintln!("[FBX] extract_fbx_texture_path: {:?}", fbx_path);
+        log::debug!("[FBX] extract_fbx_texture_path: {:?}", fbx_path);
         let fbx_dir = fbx_path.parent()?;
         let fbx_name = fbx_path.file_stem()?.to_string_lossy();
-        eprintln!("[FBX] Procurando por: {}*", fbx_name);
+        log::debug!("[FBX] Procurando por: {}*", fbx_name);
 
         // Tenta extrair nome base (remove _1, _2, etc do final)
         let base_name = if let Some(idx) = fbx_name.rfind("_texture_") {
@@ -370,7 +466,7 @@ impl ProjectWindow {
         } else {
             &fbx_name
         };
-        eprintln!("[FBX] Nome base: {}", base_name);
+        log::debug!("[FBX] Nome base: {}", base_name);
 
         // Procura por texturas com nome similar
         let texture_extensions = [
@@ -388,14 +484,14 @@ impl ProjectWindow {
         for tex_name in &texture_extensions {
             let tex_path = fbx_dir.join(tex_name);
             if tex_path.exists() {
-                eprintln!("[FBX] Textura encontrada (Meshes): {:?}", tex_path);
+                log::debug!("[FBX] Textura encontrada (Meshes): {:?}", tex_path);
                 return Some(tex_path.to_string_lossy().to_string());
             }
         }
 
         // Procura em Assets/Textures/ (pasta correta para texturas)
         let textures_dir = Path::new("Assets").join("Textures");
-        eprintln!(
+        log::debug!(
             "[FBX] Procurando em Assets/Textures/: exists={}",
             textures_dir.exists()
         );
@@ -403,7 +499,7 @@ impl ProjectWindow {
             for tex_name in &texture_extensions {
                 let tex_path = textures_dir.join(tex_name);
                 if tex_path.exists() {
-                    eprintln!("[FBX] Textura encontrada (Textures): {:?}", tex_path);
+                    log::debug!("[FBX] Textura encontrada (Textures): {:?}", tex_path);
                     return Some(tex_path.to_string_lossy().to_string());
                 }
             }
@@ -413,7 +509,7 @@ impl ProjectWindow {
                 let tex_name = format!("{}.{}", base_name, ext);
                 let tex_path = textures_dir.join(&tex_name);
                 if tex_path.exists() {
-                    eprintln!(
+                    log::debug!(
                         "[FBX] Textura encontrada (Textures simples): {:?}",
                         tex_path
                     );
@@ -425,11 +521,11 @@ impl ProjectWindow {
         // Fallback: procura em Assets/Assets/ (legado)
         let assets_dir = Path::new("Assets").join("Assets");
         if assets_dir.exists() {
-            eprintln!("[FBX] Fallback: procurando em Assets/Assets/");
+            log::debug!("[FBX] Fallback: procurando em Assets/Assets/");
             for tex_name in &texture_extensions {
                 let tex_path = assets_dir.join(tex_name);
                 if tex_path.exists() {
-                    eprintln!(
+                    log::debug!(
                         "[FBX] Textura encontrada (Assets/Assets legado): {:?}",
                         tex_path
                     );
@@ -438,7 +534,7 @@ impl ProjectWindow {
             }
         }
 
-        eprintln!("[FBX] Textura nao encontrada para {:?}", fbx_path);
+        log::debug!("[FBX] Textura nao encontrada para {:?}", fbx_path);
         None
     }
 
@@ -481,6 +577,11 @@ impl ProjectWindow {
             return;
         }
 
+        // GUID estável em `<arquivo>.meta`: referências sobrevivem a renomear/mover
+        if let Err(err) = ensure_guid(&dest_path) {
+            log::error!("[IMPORT] {err}");
+        }
+
         let imported_name = dest_path
             .file_name()
             .and_then(|n| n.to_str())
@@ -498,7 +599,7 @@ impl ProjectWindow {
         self.deleted_assets.remove(&imported_name);
 
         // Create automatic material for FBX and GLB imports
-        if ext == "fbx" || ext == "glb" || ext == "gltf" {
+        if ext == "fbx" || ext == "obj" || ext == "glb" || ext == "gltf" {
             let mat_name = format!(
                 "{}_Mat",
                 dest_path
@@ -508,8 +609,11 @@ impl ProjectWindow {
             );
 
             // Try to extract texture path
-            let texture_path = if ext == "fbx" {
-                Self::extract_fbx_texture_path(&dest_path)
+            let texture_path = if ext == "obj" {
+                Self::extract_imported_material_texture(&dest_path)
+            } else if ext == "fbx" {
+                Self::extract_imported_material_texture(&dest_path)
+                    .or_else(|| Self::extract_fbx_texture_path(&dest_path))
             } else {
                 // For GLB/GLTF, try to find extracted texture in cache
                 let cache_dir = Path::new("Assets").join(".cache").join("textures");
@@ -530,7 +634,7 @@ impl ProjectWindow {
                                         Path::new("Assets").join("Textures").join(&tex_name);
                                     if let Ok(_) = std::fs::create_dir_all("Assets/Textures") {
                                         if let Ok(_) = std::fs::copy(&path, &dest_tex) {
-                                            eprintln!("[GLB] Textura extraída: {:?}", dest_tex);
+                                            log::debug!("[GLB] Textura extraída: {:?}", dest_tex);
                                             found_tex =
                                                 Some(dest_tex.to_string_lossy().to_string());
                                             break;
@@ -546,15 +650,22 @@ impl ProjectWindow {
 
             let mat_content = if let Some(tex) = &texture_path {
                 // Quando tem textura, usa só albedo_texture (albedo branco é default)
-                format!(
-                    "# Dengine Material\n# Auto-generated for {}\nshader=Standard\nalbedo_texture={}\n",
-                    imported_name, tex
-                )
+                let mut content = format!(
+                    "# Dengine Material\n# Auto-generated for {}\n{}shader=Standard\nalbedo_texture={}\n",
+                    imported_name,
+                    FormatKind::Material.header(),
+                    tex
+                );
+                if let Ok(guid) = ensure_guid(Path::new(tex)) {
+                    content.push_str(&format!("albedo_texture_guid={guid}\n"));
+                }
+                content
             } else {
                 // Sem textura, usa cor sólida
                 format!(
-                    "# Dengine Material\n# Auto-generated for {}\nshader=Standard\nalbedo=1,1,1,1\nmetallic=0.0\nsmoothness=0.5\n",
-                    imported_name
+                    "# Dengine Material\n# Auto-generated for {}\n{}shader=Standard\nalbedo=1,1,1,1\nmetallic=0.0\nroughness=0.5\n",
+                    imported_name,
+                    FormatKind::Material.header()
                 )
             };
             self.create_text_asset_with_name(language, "Materials", &mat_name, "mat", &mat_content);
@@ -586,6 +697,122 @@ impl ProjectWindow {
         } else {
             self.status_text = format!("{}: {}", self.tr(language, "import"), imported_name);
         }
+
+        if Self::is_model_file(&dest_path) && self.model_import.generate_lods {
+            self.generate_import_lods(&dest_path, target_folder);
+        }
+    }
+
+    fn is_model_file(path: &Path) -> bool {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase())
+            .is_some_and(|e| matches!(e.as_str(), "fbx" | "obj" | "glb" | "gltf"))
+    }
+
+    /// Grava `<modelo>_LOD<n>.obj` ao lado do modelo importado, cada nível com
+    /// `lod_ratio` dos triângulos do anterior
+    fn generate_import_lods(&mut self, model_path: &Path, target_folder: &'static str) {
+        let mesh = match MeshData::load_from_file(model_path) {
+            Ok(mesh) => mesh,
+            Err(err) => {
+                self.status_text = format!("{} | LOD: {err}", self.status_text);
+                return;
+            }
+        };
+        let dir = model_path.parent().unwrap_or_else(|| Path::new("."));
+        let stem = model_path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("Model")
+            .to_string();
+        let settings = self.model_import;
+        let mut written = Vec::new();
+        for (level, lod) in generate_lods(&mesh, settings.lod_ratio, settings.lod_levels)
+            .iter()
+            .enumerate()
+        {
+            let path =
+                Self::unique_named_file_path(dir, &format!("{stem}_LOD{}", level + 1), "obj");
+            if let Err(err) = write_obj(lod, &path) {
+                self.status_text = format!("{} | LOD: {err}", self.status_text);
+                break;
+            }
+            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                let imported = self.imported_assets.entry(target_folder).or_default();
+                if !imported.iter().any(|n| n == name) {
+                    imported.push(name.to_string());
+                }
+                written.push(format!("{name} ({} tris)", lod.indices.len() / 3));
+            }
+        }
+        if !written.is_empty() {
+            self.status_text = format!("{} | LODs: {}", self.status_text, written.join(", "));
+        }
+    }
+
+    /// Janela de opções mostrada antes de importar um modelo pelo diálogo
+    fn show_model_import_window(&mut self, ctx: &egui::Context, language: EngineLanguage) {
+        let Some(path) = self.pending_model_import.clone() else {
+            return;
+        };
+        let title = self.tr(language, "import_model");
+        let labels = [
+            self.tr(language, "generate_lods"),
+            self.tr(language, "lod_ratio"),
+            self.tr(language, "lod_levels"),
+            self.tr(language, "import"),
+            self.tr(language, "cancel"),
+        ];
+        let settings = &mut self.model_import;
+        let mut open = true;
+        let mut confirm = false;
+        let mut cancel = false;
+        egui::Window::new(title)
+            .id(Id::new("model_import_window"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.label(
+                    path.file_name()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                        .to_string(),
+                );
+                ui.separator();
+                ui.checkbox(&mut settings.generate_lods, labels[0]);
+                ui.add_enabled_ui(settings.generate_lods, |ui| {
+                    egui::Grid::new("model_import_lod_grid")
+                        .num_columns(2)
+                        .show(ui, |ui| {
+                            ui.label(labels[1]);
+                            ui.add(
+                                egui::Slider::new(&mut settings.lod_ratio, 0.1..=0.9)
+                                    .fixed_decimals(2),
+                            );
+                            ui.end_row();
+                            ui.label(labels[2]);
+                            ui.add(
+                                egui::DragValue::new(&mut settings.lod_levels)
+                                    .range(1..=MAX_LOD_LEVELS - 1),
+                            );
+                            ui.end_row();
+                        });
+                });
+                ui.separator();
+                ui.horizontal(|ui| {
+                    confirm = ui.button(labels[3]).clicked();
+                    cancel = ui.button(labels[4]).clicked();
+                });
+            });
+        if confirm {
+            self.pending_model_import = None;
+            self.import_model_path(&path, language);
+        } else if cancel || !open {
+            self.pending_model_import = None;
+        }
     }
 
     fn unique_named_file_path(dir: &Path, base_stem: &str, ext: &str) -> PathBuf {
@@ -640,14 +867,14 @@ impl ProjectWindow {
         base_stem: &str,
         ext: &str,
         content: &str,
-    ) {
+    ) -> Option<PathBuf> {
         let dir = Path::new("Assets").join(target_folder);
         if let Err(err) = fs::create_dir_all(&dir) {
             self.status_text = format!(
                 "{}: erro ao criar pasta ({err})",
                 self.tr(language, "create")
             );
-            return;
+            return None;
         }
         let target = Self::unique_named_file_path(&dir, base_stem, ext);
         if let Err(err) = fs::write(&target, content.as_bytes()) {
@@ -655,7 +882,7 @@ impl ProjectWindow {
                 "{}: erro ao criar arquivo ({err})",
                 self.tr(language, "create")
             );
-            return;
+            return None;
         }
         let Some(name) = target
             .file_name()
@@ -663,16 +890,26 @@ impl ProjectWindow {
             .map(|s| s.to_string())
         else {
             self.status_text = format!("{}: erro ao resolver nome", self.tr(language, "create"));
-            return;
+            return None;
         };
         let imported = self.imported_assets.entry(target_folder).or_default();
         if !imported.iter().any(|n| n == &name) {
             imported.push(name.clone());
         }
         self.deleted_assets.remove(&name);
-        self.selected_folder = target_folder;
+        self.select_folder(target_folder);
         self.selected_asset = Some(name.clone());
         self.status_text = format!("{}: {}", self.tr(language, "created"), name);
+        Some(target)
+    }
+
+    /// Material novo com os valores padrão em `Assets/Materials`
+    pub(crate) fn create_material(&mut self, language: EngineLanguage) -> Option<PathBuf> {
+        let content = format!(
+            "# Dengine Material\n{}shader=Standard\nalbedo=1,1,1,1\nmetallic=0.0\nroughness=0.5\n",
+            FormatKind::Material.header()
+        );
+        self.create_text_asset(language, "Materials", "NovoMaterial", "mat", &content)
     }
 
     fn unique_named_folder_path(dir: &Path, base_name: &str) -> PathBuf {
@@ -725,7 +962,11 @@ impl ProjectWindow {
         let file = rfd::FileDialog::new().pick_file();
 
         if let Some(path) = file {
-            self.import_model_path(&path, language);
+            if Self::is_model_file(&path) {
+                self.pending_model_import = Some(path);
+            } else {
+                self.import_model_path(&path, language);
+            }
         }
     }
 
@@ -811,7 +1052,8 @@ impl ProjectWindow {
         files.sort_by_key(|s| s.to_ascii_lowercase());
 
         let mut f = File::create(path).map_err(|e| e.to_string())?;
-        f.write_all(b"DENG1\n").map_err(|e| e.to_string())?;
+        f.write_all(FormatKind::Project.header().as_bytes())
+            .map_err(|e| e.to_string())?;
         for rel in files {
             let line = format!("asset={rel}\n");
             f.write_all(line.as_bytes()).map_err(|e| e.to_string())?;
@@ -820,6 +1062,7 @@ impl ProjectWindow {
     }
 
     fn icon_style(asset: &str) -> (Color32, &'static str) {
+        let asset = asset.to_ascii_lowercase();
         if asset.ends_with(".mold") {
             (Color32::from_rgb(56, 95, 166), "PF")
         } else if asset.ends_with(".cs") {
@@ -830,6 +1073,8 @@ impl ProjectWindow {
             || asset.ends_with(".webp")
         {
             (Color32::from_rgb(64, 146, 112), "IMG")
+        } else if asset.ends_with(".hdr") || asset.ends_with(".exr") {
+            (Color32::from_rgb(64, 120, 146), "HDR")
         } else if asset.ends_with(".wav")
             || asset.ends_with(".mp3")
             || asset.ends_with(".ogg")
@@ -846,6 +1091,10 @@ impl ProjectWindow {
             || asset.ends_with(".gltf")
         {
             (Color32::from_rgb(86, 132, 176), "MESH")
+        } else if asset.ends_with(".lua") {
+            (Color32::from_rgb(52, 84, 168), "LUA")
+        } else if asset.ends_with(".wgsl") {
+            (Color32::from_rgb(168, 70, 92), "SH")
         } else if asset.ends_with(".json") {
             (Color32::from_rgb(127, 127, 127), "{}")
         } else {
@@ -854,7 +1103,269 @@ impl ProjectWindow {
     }
 
     fn selected_folder_path(&self) -> Option<PathBuf> {
-        Self::folder_path_from_id(self.selected_folder)
+        self.sub_folder
+            .clone()
+            .or_else(|| Self::folder_path_from_id(self.selected_folder))
+    }
+
+    fn select_folder(&mut self, folder: &'static str) {
+        self.selected_folder = folder;
+        self.sub_folder = None;
+    }
+
+    /// Abre qualquer pasta de Assets/Packages na grade; as que não são
+    /// conhecidas ficam em `sub_folder`
+    fn open_dir(&mut self, path: &Path) {
+        self.selected_asset = None;
+        self.selected_sub_asset = None;
+        let known = |dir: &Path| {
+            KNOWN_FOLDER_PATHS
+                .iter()
+                .find(|(known, _)| Path::new(known) == dir)
+                .map(|(_, id)| *id)
+        };
+        if let Some(id) = known(path) {
+            self.select_folder(id);
+        } else {
+            self.selected_folder = path.ancestors().find_map(known).unwrap_or("Assets");
+            self.sub_folder = Some(path.to_path_buf());
+        }
+        for dir in path.ancestors().skip(1) {
+            self.open_tree_folders.insert(dir.to_path_buf());
+        }
+        if path.starts_with("Packages") {
+            self.packages_open = true;
+        } else {
+            self.assets_open = true;
+        }
+    }
+
+    /// Subpastas visíveis de `dir`, em ordem alfabética
+    fn subdirs(dir: &Path) -> Vec<PathBuf> {
+        let mut dirs: Vec<PathBuf> = fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.is_dir()
+                    && !path
+                        .file_name()
+                        .is_some_and(|n| n.to_string_lossy().starts_with('.'))
+            })
+            .collect();
+        dirs.sort_by_key(|p| p.to_string_lossy().to_ascii_lowercase());
+        dirs
+    }
+
+    /// Move ou renomeia no disco levando o .meta junto, para o GUID e as
+    /// referências continuarem valendo
+    fn move_asset_path(from: &Path, to: &Path) -> Result<(), String> {
+        if to.exists() {
+            return Err(format!("{} já existe", to.display()));
+        }
+        if from.is_dir() && to.starts_with(from) {
+            return Err("Uma pasta não pode ir para dentro dela mesma".to_string());
+        }
+        fs::rename(from, to).map_err(|e| e.to_string())?;
+        let meta = meta_path(from);
+        if meta.is_file() {
+            fs::rename(&meta, meta_path(to)).map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+
+    /// Solta o asset arrastado da grade dentro de `dest`
+    fn move_dragged_into(&mut self, dest: &Path, language: EngineLanguage) {
+        let Some(name) = self
+            .dragging_asset
+            .take()
+            .filter(|name| !name.contains("::"))
+        else {
+            return;
+        };
+        let Some(from) = self.asset_path_in_selected_folder(&name) else {
+            return;
+        };
+        if from.parent() == Some(dest) || from == dest {
+            return;
+        }
+        let to = dest.join(&name);
+        match Self::move_asset_path(&from, &to) {
+            Ok(()) => {
+                self.pending_asset_edit = Some(AssetFileEdit::Move { from, to });
+                if self.selected_asset.as_deref() == Some(name.as_str()) {
+                    self.selected_asset = None;
+                }
+                self.status_text = format!(
+                    "{}: {name} -> {}",
+                    self.tr(language, "moved"),
+                    dest.display()
+                );
+            }
+            Err(err) => self.status_text = err,
+        }
+    }
+
+    /// Linha da árvore ou bloco de pasta que aceita assets arrastados da grade
+    fn folder_drop_target(
+        &mut self,
+        ui: &egui::Ui,
+        rect: Rect,
+        dest: &Path,
+        language: EngineLanguage,
+    ) {
+        let dragging = self
+            .dragging_asset
+            .as_ref()
+            .is_some_and(|name| !name.contains("::"));
+        if !dragging || !ui.rect_contains_pointer(rect) {
+            return;
+        }
+        ui.painter().rect_stroke(
+            rect,
+            3.0,
+            Stroke::new(1.0, theme::palette().accent),
+            egui::StrokeKind::Inside,
+        );
+        if ui.input(|i| i.pointer.primary_released()) {
+            self.move_dragged_into(dest, language);
+        }
+    }
+
+    /// Pastas do usuário sob `dir` na árvore, com menu (nova pasta, renomear)
+    /// e soltura de assets para mover
+    fn draw_folder_subtree(
+        &mut self,
+        ui: &mut egui::Ui,
+        dir: &Path,
+        indent: f32,
+        language: EngineLanguage,
+    ) {
+        for sub in Self::subdirs(dir) {
+            let name = sub
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let id = sub.to_string_lossy().to_string();
+            let selected = self.sub_folder.as_deref() == Some(sub.as_path());
+            let has_children = !Self::subdirs(&sub).is_empty();
+            let mut opened = self.open_tree_folders.contains(&sub);
+            let resp = if has_children {
+                let resp = self.draw_tree_parent_row(ui, &id, &name, indent, &mut opened, selected);
+                if opened {
+                    self.open_tree_folders.insert(sub.clone());
+                } else {
+                    self.open_tree_folders.remove(&sub);
+                }
+                resp
+            } else {
+                Self::draw_tree_leaf_row(ui, &id, &name, indent + 16.0, selected)
+            };
+            if resp.clicked() {
+                self.open_dir(&sub);
+            }
+            self.folder_context_menu(&resp, &sub, language);
+            self.folder_drop_target(ui, resp.rect, &sub, language);
+            if has_children && opened {
+                self.draw_folder_subtree(ui, &sub, indent + 16.0, language);
+            }
+        }
+    }
+
+    /// Menu de uma pasta da árvore
+    fn folder_context_menu(&mut self, resp: &egui::Response, dir: &Path, language: EngineLanguage) {
+        let mut new_folder = false;
+        let mut rename = false;
+        resp.context_menu(|ui| {
+            if ui.button(self.tr(language, "new_folder")).clicked() {
+                new_folder = true;
+                ui.close();
+            }
+            if ui.button(self.tr(language, "rename")).clicked() {
+                rename = true;
+                ui.close();
+            }
+        });
+        if new_folder {
+            self.open_dir(dir);
+            self.create_folder_in_selected(language);
+            self.open_tree_folders.insert(dir.to_path_buf());
+        }
+        if rename {
+            self.start_rename(dir);
+        }
+    }
+
+    fn start_rename(&mut self, path: &Path) {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        self.rename_editor = Some((path.to_path_buf(), name));
+    }
+
+    /// Novo nome de um asset ou pasta, aplicado no disco
+    fn show_rename_window(&mut self, ctx: &egui::Context, language: EngineLanguage) {
+        let labels = [
+            self.tr(language, "rename"),
+            self.tr(language, "save"),
+            self.tr(language, "cancel"),
+        ];
+        let Some((_, text)) = self.rename_editor.as_mut() else {
+            return;
+        };
+        let mut save = false;
+        let mut cancel = false;
+        egui::Window::new(labels[0])
+            .id(Id::new("project_asset_rename"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                let resp = ui.add(egui::TextEdit::singleline(text).desired_width(240.0));
+                save = resp.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+                ui.horizontal(|ui| {
+                    save |= ui.button(labels[1]).clicked();
+                    cancel = ui.button(labels[2]).clicked();
+                });
+            });
+        if cancel {
+            self.rename_editor = None;
+            return;
+        }
+        if !save {
+            return;
+        }
+        let Some((from, name)) = self.rename_editor.take() else {
+            return;
+        };
+        let name = name.trim();
+        if name.is_empty() || name.contains(['/', '\\']) {
+            self.status_text = format!("{}: nome inválido", labels[0]);
+            return;
+        }
+        let to = from.with_file_name(name);
+        if to == from {
+            return;
+        }
+        match Self::move_asset_path(&from, &to) {
+            Ok(()) => {
+                // Pasta aberta (ou uma acima dela) renomeada: segue para o novo caminho
+                if let Some(open) = self.sub_folder.clone() {
+                    if let Ok(rest) = open.strip_prefix(&from) {
+                        self.open_dir(&to.join(rest));
+                    }
+                }
+                let old_name = from.file_name().map(|n| n.to_string_lossy().to_string());
+                if self.selected_asset == old_name {
+                    self.selected_asset = Some(name.to_string());
+                }
+                self.pending_asset_edit = Some(AssetFileEdit::Move { from, to });
+                self.status_text = format!("{}: {name}", labels[0]);
+            }
+            Err(err) => self.status_text = err,
+        }
     }
 
     fn folder_path_from_id(folder: &'static str) -> Option<PathBuf> {
@@ -899,7 +1410,7 @@ impl ProjectWindow {
     }
 
     fn parse_fbx_animation_names(raw: &str) -> Vec<String> {
-        eprintln!("[FBX] parse_fbx_animation_names: {} chars", raw.len());
+        log::debug!("[FBX] parse_fbx_animation_names: {} chars", raw.len());
         let mut out = Vec::<String>::new();
         let mut push_unique = |name: &str| {
             let clean = name.trim();
@@ -995,22 +1506,22 @@ impl ProjectWindow {
     fn parse_fbx_binary_animation_names(bytes: &[u8]) -> Vec<String> {
         let mut out = Vec::<String>::new();
 
-        eprintln!(
+        log::debug!(
             "[FBX] parse_fbx_binary_animation_names: {} bytes",
             bytes.len()
         );
 
         // FBX binary header is 27 bytes, then nodes start
         if bytes.len() < 27 {
-            eprintln!("[FBX] Arquivo muito pequeno (< 27 bytes)");
+            log::debug!("[FBX] Arquivo muito pequeno (< 27 bytes)");
             return out;
         }
 
         // Check for FBX binary magic
         let header = String::from_utf8_lossy(&bytes[0..20]);
-        eprintln!("[FBX] Header: {:?}", header);
+        log::debug!("[FBX] Header: {:?}", header);
         if !header.contains("Kaydara FBX Binary") {
-            eprintln!("[FBX] Não é um arquivo FBX binário válido");
+            log::debug!("[FBX] Não é um arquivo FBX binário válido");
             return out;
         }
 
@@ -1024,14 +1535,14 @@ impl ProjectWindow {
         // - padding to 4-byte align
         // - property data
 
-        eprintln!("[FBX] Iniciando scan em pos=27");
+        log::debug!("[FBX] Iniciando scan em pos=27");
         let mut pos = 27; // Skip header
         let mut iterations = 0;
 
         while pos + 4 < bytes.len() {
             iterations += 1;
             if iterations > 10000 {
-                eprintln!("[FBX] Limite de iteracoes atingido (10000), saindo");
+                log::debug!("[FBX] Limite de iteracoes atingido (10000), saindo");
                 break;
             }
 
@@ -1040,9 +1551,10 @@ impl ProjectWindow {
                     as usize;
 
             if record_len == 0 || record_len > bytes.len().saturating_sub(pos) {
-                eprintln!(
+                log::debug!(
                     "[FBX] record_len invalid em pos={}: record_len={}",
-                    pos, record_len
+                    pos,
+                    record_len
                 );
                 break;
             }
@@ -1081,7 +1593,7 @@ impl ProjectWindow {
             }
 
             if pos + name_len > bytes.len() {
-                eprintln!("[FBX] name_len excede buffer em pos={}", pos);
+                log::debug!("[FBX] name_len excede buffer em pos={}", pos);
                 break;
             }
 
@@ -1096,7 +1608,7 @@ impl ProjectWindow {
 
             // Check if this is an AnimationStack node
             if node_name.contains("AnimationStack") || node_name.contains("AnimStack") {
-                eprintln!("[FBX] Encontrado AnimationStack: {:?}", node_name);
+                log::debug!("[FBX] Encontrado AnimationStack: {:?}", node_name);
                 // Try to find the name property (usually first string property)
                 // Skip the properties and look for the name
                 let mut search_pos = pos;
@@ -1141,7 +1653,7 @@ impl ProjectWindow {
         // Only do this if we haven't found animations yet and the file isn't too large
         if out.is_empty() && bytes.len() < 10_000_000 {
             let bytes_str = String::from_utf8_lossy(bytes);
-            eprintln!("[FBX] Buscando Take em {} chars", bytes_str.len());
+            log::debug!("[FBX] Buscando Take em {} chars", bytes_str.len());
 
             // Convert to chars for safe iteration
             let chars: Vec<char> = bytes_str.chars().collect();
@@ -1153,7 +1665,7 @@ impl ProjectWindow {
                     if chars[i + 1] == 'a' && chars[i + 2] == 'k' && chars[i + 3] == 'e' {
                         takes_found += 1;
                         if takes_found <= 10 {
-                            eprintln!("[FBX] Take encontrado na posicao {}", i);
+                            log::debug!("[FBX] Take encontrado na posicao {}", i);
                         }
                         // Found "Take", extract alphanumeric name after it
                         let name_start = i + 4;
@@ -1166,7 +1678,7 @@ impl ProjectWindow {
 
                         if name_chars.len() > 1 {
                             if takes_found <= 10 {
-                                eprintln!("[FBX] Nome extraido: {:?}", name_chars);
+                                log::debug!("[FBX] Nome extraido: {:?}", name_chars);
                             }
                             if !out.iter().any(|x| x.eq_ignore_ascii_case(&name_chars)) {
                                 out.push(name_chars);
@@ -1178,12 +1690,12 @@ impl ProjectWindow {
                 }
                 i += 1;
             }
-            eprintln!("[FBX] Total de Takes encontrados: {}", takes_found);
-            eprintln!("[FBX] Animacoes encontradas: {:?}", out);
+            log::debug!("[FBX] Total de Takes encontrados: {}", takes_found);
+            log::debug!("[FBX] Animacoes encontradas: {:?}", out);
         } else if !out.is_empty() {
-            eprintln!("[FBX] Animacoes ja encontradas, pulando busca por Take");
+            log::debug!("[FBX] Animacoes ja encontradas, pulando busca por Take");
         } else {
-            eprintln!(
+            log::debug!(
                 "[FBX] Arquivo muito grande para busca por Take ({} bytes > 10MB)",
                 bytes.len()
             );
@@ -1208,17 +1720,17 @@ impl ProjectWindow {
         imported_fbx_name: &str,
         fbx_path: &Path,
     ) -> Result<Option<String>, String> {
-        eprintln!(
+        log::debug!(
             "[FBX] upsert_default_animation_module_for_fbx: {:?}",
             fbx_path
         );
         let bytes = fs::read(fbx_path).map_err(|e| e.to_string())?;
-        eprintln!("[FBX] Lido {} bytes", bytes.len());
+        log::debug!("[FBX] Lido {} bytes", bytes.len());
         let raw = String::from_utf8_lossy(&bytes);
         let clips = Self::parse_fbx_animation_names(&raw);
-        eprintln!("[FBX] Clips encontrados: {:?}", clips);
+        log::debug!("[FBX] Clips encontrados: {:?}", clips);
         if clips.is_empty() {
-            eprintln!("[FBX] Sem clips de animacao, retornando None");
+            log::debug!("[FBX] Sem clips de animacao, retornando None");
             return Ok(None);
         }
 
@@ -1280,13 +1792,13 @@ impl ProjectWindow {
         let is_binary = bytes.len() > 20
             && String::from_utf8_lossy(&bytes[0..20]).contains("Kaydara FBX Binary");
 
-        eprintln!("[FBX] parse_fbx_meta: {:?}, is_binary={}", path, is_binary);
+        log::debug!("[FBX] parse_fbx_meta: {:?}, is_binary={}", path, is_binary);
 
         let animations = if is_binary {
-            eprintln!("[FBX] Usando parser binario");
+            log::debug!("[FBX] Usando parser binario");
             Self::parse_fbx_binary_animation_names(&bytes)
         } else {
-            eprintln!("[FBX] Usando parser ASCII");
+            log::debug!("[FBX] Usando parser ASCII");
             Self::parse_fbx_animation_names(&raw)
         };
 
@@ -1434,88 +1946,23 @@ impl ProjectWindow {
         self.selected_folder_path().map(|p| p.join(asset_name))
     }
 
-    fn asset_preview_texture<'a>(
-        &'a mut self,
-        _ctx: &egui::Context,
-        asset_name: &str,
-    ) -> Option<&'a TextureHandle> {
+    /// Miniatura do asset e quantos quadros de giro ela tem, pedindo-a ao gerador
+    /// na primeira vez
+    fn asset_preview_texture(&mut self, asset_name: &str) -> Option<(&TextureHandle, u32)> {
         let asset_path = self.asset_path_in_selected_folder(asset_name)?;
-        let ext = asset_path
-            .extension()
-            .and_then(|e| e.to_str())
-            .map(|e| e.to_ascii_lowercase())
-            .unwrap_or_default();
-        let is_image = ext == "png" || ext == "jpg" || ext == "jpeg" || ext == "webp";
-        let is_mesh = ext == "obj" || ext == "glb" || ext == "gltf" || ext == "fbx";
-        if !is_image && !is_mesh {
-            return None;
-        }
-        if !asset_path.exists() {
+        ThumbnailKind::from_path(&asset_path)?;
+        if !asset_path.is_file() {
             return None;
         }
 
         let key = asset_path.to_string_lossy().to_string();
         if !self.preview_cache.contains_key(&key) {
-            if is_image && !self.image_preview_pending.contains(&key) {
-                let active = self.image_preview_workers.load(Ordering::Relaxed);
-                if active >= Self::MAX_IMAGE_PREVIEW_WORKERS {
-                    return None;
-                }
-                self.image_preview_pending.insert(key.clone());
-                let tx = self.image_preview_tx.clone();
-                let key_clone = key.clone();
-                let workers = Arc::clone(&self.image_preview_workers);
-                workers.fetch_add(1, Ordering::Relaxed);
-                std::thread::spawn(move || {
-                    let decoded = match std::fs::read(&asset_path)
-                        .ok()
-                        .and_then(|bytes| image::load_from_memory(&bytes).ok())
-                        .map(|img| img.to_rgba8())
-                    {
-                        Some(rgba) => ImagePreviewDecoded {
-                            key: key_clone,
-                            image: Some((
-                                [rgba.width() as usize, rgba.height() as usize],
-                                rgba.into_raw(),
-                            )),
-                        },
-                        None => ImagePreviewDecoded {
-                            key: key_clone,
-                            image: None,
-                        },
-                    };
-                    let _ = tx.send(decoded);
-                    workers.fetch_sub(1, Ordering::Relaxed);
-                });
-            } else if is_mesh && !self.mesh_preview_pending.contains(&key) {
-                let active = self.mesh_preview_workers.load(Ordering::Relaxed);
-                if active >= Self::MAX_MESH_PREVIEW_WORKERS {
-                    return None;
-                }
-                self.mesh_preview_pending.insert(key.clone());
-                let tx = self.mesh_preview_tx.clone();
-                let key_clone = key.clone();
-                let workers = Arc::clone(&self.mesh_preview_workers);
-                workers.fetch_add(1, Ordering::Relaxed);
-                std::thread::spawn(move || {
-                    let image = build_mesh_preview(&asset_path).ok().map(|preview| {
-                        let size = Self::MESH_THUMB_SIZE;
-                        let rgba = rasterize_mesh_preview(&preview, size);
-                        (size, rgba)
-                    });
-                    let _ = tx.send(MeshPreviewDecoded {
-                        key: key_clone,
-                        image,
-                    });
-                    workers.fetch_sub(1, Ordering::Relaxed);
-                });
-            }
-        }
-        if self.preview_cache.contains_key(&key) {
-            Self::lru_touch(&mut self.preview_lru, &key);
-            self.evict_preview_cache_if_needed();
+            self.thumbnails.request(&asset_path);
+            return None;
         }
-        self.preview_cache.get(&key)
+        Self::lru_touch(&mut self.preview_lru, &key);
+        let frames = self.preview_frames.get(&key).copied().unwrap_or(1);
+        self.preview_cache.get(&key).map(|tex| (tex, frames))
     }
 
     pub fn dragging_asset_name(&self) -> Option<&str> {
@@ -1527,6 +1974,50 @@ impl ProjectWindow {
         self.asset_path_in_selected_folder(name)
     }
 
+    /// Caminho do asset de áudio selecionado (wav/ogg/mp3), para a pre-escuta
+    pub fn selected_audio_asset_path(&self) -> Option<PathBuf> {
+        let asset = self.selected_asset.as_deref()?;
+        let lower = asset.to_ascii_lowercase();
+        if !(lower.ends_with(".wav") || lower.ends_with(".ogg") || lower.ends_with(".mp3")) {
+            return None;
+        }
+        self.asset_path_in_selected_folder(asset)
+    }
+
+    /// Caminho do asset selecionado, qualquer que seja o tipo
+    pub fn selected_asset_path(&self) -> Option<PathBuf> {
+        let asset = self.selected_asset.as_deref()?;
+        self.asset_path_in_selected_folder(asset)
+    }
+
+    /// Caminho da textura ou malha selecionada, cujas opções de importação o inspetor mostra
+    pub fn selected_import_asset_path(&self) -> Option<PathBuf> {
+        let asset = self.selected_asset.as_deref()?;
+        let path = self.asset_path_in_selected_folder(asset)?;
+        matches!(
+            AssetKind::from_path(&path),
+            Some(AssetKind::Texture | AssetKind::Mesh | AssetKind::Font)
+        )
+        .then_some(path)
+    }
+
+    /// Estado da pre-escuta do clipe selecionado, desenhado no bloco do asset
+    pub(crate) fn set_audio_clip_view(&mut self, view: Option<AudioClipView>) {
+        self.audio_clip_view = view;
+    }
+
+    pub(crate) fn take_audio_preview_request(&mut self) -> Option<AudioPreviewRequest> {
+        self.pending_audio_preview.take()
+    }
+
+    pub(crate) fn take_material_open_request(&mut self) -> Option<PathBuf> {
+        self.pending_material_open.take()
+    }
+
+    pub(crate) fn take_prefab_open_request(&mut self) -> Option<PathBuf> {
+        self.pending_prefab_open.take()
+    }
+
     fn get_asset_full_path(&self, asset_name: &str) -> String {
         self.asset_path_in_selected_folder(asset_name)
             .map(|p| p.to_string_lossy().to_string())
@@ -1573,9 +2064,21 @@ impl ProjectWindow {
         ellipsis.to_owned()
     }
 
-    fn draw_icon_size_slider(&mut self, ui: &mut egui::Ui, rect: Rect) {
-        let min = 56.0;
-        let max = 98.0;
+    /// Selo com o tipo do asset (cor e sigla de `icon_style`)
+    fn draw_type_badge(painter: &egui::Painter, rect: Rect, color: Color32, tag: &str) {
+        painter.rect_filled(rect, 3.0, color);
+        painter.text(
+            rect.center(),
+            Align2::CENTER_CENTER,
+            tag,
+            FontId::proportional(8.5),
+            Color32::from_gray(245),
+        );
+    }
+
+    fn draw_icon_size_slider(&mut self, ui: &mut egui::Ui, rect: Rect) {
+        let min = Self::ICON_SCALE_MIN;
+        let max = Self::ICON_SCALE_MAX;
         let t = ((self.icon_scale - min) / (max - min)).clamp(0.0, 1.0);
 
         let resp = ui.interact(
@@ -1592,7 +2095,7 @@ impl ProjectWindow {
 
         let track_rect = Rect::from_center_size(rect.center(), egui::vec2(rect.width(), 4.0));
         ui.painter()
-            .rect_filled(track_rect, 6.0, Color32::from_rgb(74, 74, 74));
+            .rect_filled(track_rect, 6.0, theme::palette().widget_hover);
 
         let fill_rect = Rect::from_min_max(
             track_rect.min,
@@ -1602,19 +2105,294 @@ impl ProjectWindow {
             ),
         );
         ui.painter()
-            .rect_filled(fill_rect, 6.0, Color32::from_rgb(15, 232, 121));
+            .rect_filled(fill_rect, 6.0, theme::palette().accent);
 
         let knob_center = egui::pos2(
             track_rect.left() + track_rect.width() * t,
             track_rect.center().y,
         );
         ui.painter()
-            .circle_filled(knob_center, 5.0, Color32::from_rgb(34, 34, 34));
-        ui.painter().circle_stroke(
-            knob_center,
-            5.0,
-            Stroke::new(1.4, Color32::from_rgb(15, 232, 121)),
-        );
+            .circle_filled(knob_center, 5.0, theme::palette().panel_bg);
+        ui.painter()
+            .circle_stroke(knob_center, 5.0, Stroke::new(1.4, theme::palette().accent));
+    }
+
+    pub fn set_asset_database(&mut self, database: AssetDatabase) {
+        self.search_index = Arc::new(AssetSearchIndex::build(&database));
+        self.asset_database = database;
+    }
+
+    /// Índice de busca de assets, compartilhado com os seletores do Inspetor
+    pub fn search_index(&self) -> Arc<AssetSearchIndex> {
+        self.search_index.clone()
+    }
+
+    /// Filtro da grade: usa os rótulos do índice quando o asset já foi indexado
+    fn asset_matches(&self, query: &SearchQuery, asset: &str) -> bool {
+        let entry = self
+            .asset_path_in_selected_folder(asset)
+            .and_then(|path| self.search_index.entry_at(&path));
+        match entry {
+            Some(entry) => query.score(entry).is_some(),
+            None => query
+                .score_parts(asset, AssetType::from_path(Path::new(asset)), &[])
+                .is_some(),
+        }
+    }
+
+    /// Abre a pasta do asset na grade e o seleciona
+    pub(crate) fn reveal_asset(&mut self, path: &Path, language: EngineLanguage) {
+        let parent = path.parent().unwrap_or(Path::new(""));
+        if let Some(folder_id) = Self::folder_id_from_path(parent) {
+            self.select_folder(folder_id);
+            if Self::is_packages_folder_id(folder_id) {
+                self.packages_open = true;
+            } else if Self::is_assets_folder_id(folder_id) {
+                self.assets_open = true;
+            }
+        } else if parent.starts_with("Assets") || parent.starts_with("Packages") {
+            self.open_dir(parent);
+        } else {
+            self.status_text = path.to_string_lossy().to_string();
+            return;
+        }
+        self.selected_asset = path.file_name().map(|n| n.to_string_lossy().to_string());
+        self.selected_sub_asset = None;
+        self.status_text = format!("{}: {}", self.tr(language, "reveal"), path.display());
+    }
+
+    /// Resultados da busca no projeto inteiro, sob a caixa de busca
+    fn show_search_popup(&mut self, ctx: &egui::Context, language: EngineLanguage) {
+        let Some(anchor) = self.search_box_rect else {
+            return;
+        };
+        if !self.search_popup_open || self.search_query.trim().is_empty() {
+            return;
+        }
+        if ctx.input(|i| i.key_pressed(Key::Escape)) {
+            self.search_popup_open = false;
+            return;
+        }
+        let index = self.search_index.clone();
+        let hits = index.search(&self.search_query, SEARCH_POPUP_RESULTS);
+        let none = self.tr(language, "none");
+        let mut picked = None;
+        let area = egui::Area::new(Id::new("project_search_results"))
+            .order(Order::Foreground)
+            .fixed_pos(anchor.left_bottom() + egui::vec2(0.0, 4.0))
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.set_min_width(anchor.width().max(260.0));
+                    if hits.is_empty() {
+                        ui.label(egui::RichText::new(none).weak());
+                    }
+                    for hit in &hits {
+                        let entry = hit.entry;
+                        let folder = entry
+                            .path
+                            .parent()
+                            .and_then(|p| p.file_name())
+                            .map(|n| n.to_string_lossy().to_string())
+                            .unwrap_or_default();
+                        let mut text = egui::text::LayoutJob::default();
+                        text.append(
+                            &entry.name,
+                            0.0,
+                            egui::TextFormat::simple(
+                                FontId::proportional(12.0),
+                                theme::palette().text_strong,
+                            ),
+                        );
+                        text.append(
+                            &format!("  {} · {folder}", entry.asset_type.name()),
+                            0.0,
+                            egui::TextFormat::simple(
+                                FontId::proportional(10.0),
+                                theme::palette().text_weak,
+                            ),
+                        );
+                        let resp = ui.add(egui::Button::new(text).frame(false));
+                        let resp = if entry.labels.is_empty() {
+                            resp
+                        } else {
+                            resp.on_hover_text(entry.labels.join(", "))
+                        };
+                        if resp.clicked() {
+                            picked = Some(entry.path.clone());
+                        }
+                    }
+                });
+            });
+        // Fecha ao clicar fora da caixa e da lista
+        let pressed_outside = ctx.input(|i| {
+            i.pointer.any_pressed()
+                && i.pointer
+                    .interact_pos()
+                    .is_some_and(|p| !area.response.rect.contains(p) && !anchor.contains(p))
+        });
+        if pressed_outside {
+            self.search_popup_open = false;
+        }
+        if let Some(path) = picked {
+            self.search_popup_open = false;
+            self.reveal_asset(&path, language);
+        }
+    }
+
+    /// Edição dos rótulos de busca de um asset (gravados no .meta)
+    fn show_labels_window(&mut self, ctx: &egui::Context, language: EngineLanguage) {
+        let labels = [
+            self.tr(language, "labels").trim_end_matches('.'),
+            self.tr(language, "labels_hint"),
+            self.tr(language, "save"),
+            self.tr(language, "cancel"),
+        ];
+        let Some((path, text)) = self.labels_editor.as_mut() else {
+            return;
+        };
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let mut save = false;
+        let mut cancel = false;
+        egui::Window::new(format!("{}: {name}", labels[0]))
+            .id(Id::new("project_asset_labels"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                let resp = ui.add(
+                    egui::TextEdit::singleline(text)
+                        .hint_text(labels[1])
+                        .desired_width(240.0),
+                );
+                save = resp.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+                ui.horizontal(|ui| {
+                    save |= ui.button(labels[2]).clicked();
+                    cancel = ui.button(labels[3]).clicked();
+                });
+            });
+        if cancel {
+            self.labels_editor = None;
+        } else if save {
+            let Some((path, text)) = self.labels_editor.take() else {
+                return;
+            };
+            let labels = parse_labels(&text);
+            match write_labels(&path, &labels) {
+                Ok(()) => {
+                    let guid = self.search_index.entry_at(&path).map(|e| e.guid);
+                    if let Some(guid) = guid {
+                        Arc::make_mut(&mut self.search_index).set_labels(guid, labels);
+                    }
+                    self.status_text = format!("{}: {name}", self.tr(language, "save"));
+                }
+                Err(e) => self.status_text = e,
+            }
+        }
+    }
+
+    /// Arquivos que usam (`references`) ou são usados pelo asset da pasta atual
+    fn asset_link_paths(&self, asset: &str, references: bool) -> Vec<PathBuf> {
+        let Some(guid) = self
+            .asset_path_in_selected_folder(asset)
+            .and_then(|path| self.asset_database.guid_of(&path))
+        else {
+            return Vec::new();
+        };
+        let guids = if references {
+            self.asset_database.references(guid)
+        } else {
+            self.asset_database.dependencies(guid).to_vec()
+        };
+        guids
+            .into_iter()
+            .filter_map(|guid| self.asset_database.path_of(guid).map(Path::to_path_buf))
+            .collect()
+    }
+
+    /// Exclusão segura: um asset usado por outros pede confirmação antes
+    fn request_delete_asset(&mut self, language: EngineLanguage, asset: &str) {
+        let users = self.asset_link_paths(asset, true);
+        if users.is_empty() {
+            self.delete_asset(language, asset);
+        } else {
+            self.pending_delete = Some((asset.to_string(), users));
+        }
+    }
+
+    /// Janelas de referências/dependências e de confirmação de exclusão
+    fn show_asset_links_windows(&mut self, ctx: &egui::Context, language: EngineLanguage) {
+        let none = self.tr(language, "none");
+        if let Some(links) = &self.asset_links {
+            let title = if links.references {
+                self.tr(language, "find_references")
+            } else {
+                self.tr(language, "find_dependencies")
+            };
+            let mut open = true;
+            egui::Window::new(format!("{title}: {}", links.asset))
+                .id(Id::new("project_asset_links"))
+                .collapsible(false)
+                .default_size([320.0, 200.0])
+                .open(&mut open)
+                .show(ctx, |ui| {
+                    if links.paths.is_empty() {
+                        ui.label(egui::RichText::new(none).weak());
+                    }
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        for path in &links.paths {
+                            ui.label(path.to_string_lossy());
+                        }
+                    });
+                });
+            if !open {
+                self.asset_links = None;
+            }
+        }
+
+        let Some((asset, users)) = &self.pending_delete else {
+            return;
+        };
+        let labels = [
+            self.tr(language, "delete"),
+            self.tr(language, "used_by"),
+            self.tr(language, "cancel"),
+        ];
+        let mut confirm = false;
+        let mut cancel = false;
+        egui::Window::new(format!("{}: {asset}", labels[0]))
+            .id(Id::new("project_safe_delete"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(format!("{} {}:", labels[1], users.len()));
+                for path in users.iter().take(12) {
+                    ui.label(egui::RichText::new(path.to_string_lossy()).weak());
+                }
+                if users.len() > 12 {
+                    ui.label("...");
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    confirm = ui
+                        .add(
+                            egui::Button::new(labels[0])
+                                .fill(theme::palette().error.gamma_multiply(0.35)),
+                        )
+                        .clicked();
+                    cancel = ui.button(labels[2]).clicked();
+                });
+            });
+        if confirm {
+            let asset = asset.clone();
+            self.pending_delete = None;
+            self.delete_asset(language, &asset);
+        } else if cancel {
+            self.pending_delete = None;
+        }
     }
 
     fn delete_asset(&mut self, language: EngineLanguage, asset: &str) {
@@ -1622,6 +2400,9 @@ impl ProjectWindow {
             return;
         }
         self.deleted_assets.insert(asset.to_string());
+        self.pending_asset_edit = Some(AssetFileEdit::Delete {
+            asset: asset.to_string(),
+        });
         if self.selected_asset.as_deref() == Some(asset) {
             self.selected_asset = None;
         }
@@ -1636,6 +2417,101 @@ impl ProjectWindow {
         self.hovered_asset = None;
     }
 
+    /// Copia o arquivo (e o .meta, com GUID novo) para `copy`
+    fn copy_asset_file(source: &Path, copy: &Path) -> Result<(), String> {
+        fs::copy(source, copy).map_err(|e| format!("Falha ao copiar {}: {e}", source.display()))?;
+        let meta = meta_path(source);
+        if meta.is_file() {
+            fs::copy(&meta, meta_path(copy)).map_err(|e| e.to_string())?;
+            write_meta_value(copy, "guid", Some(&AssetGuid::generate().to_string()))?;
+        }
+        Ok(())
+    }
+
+    fn remove_asset_file(path: &Path) -> Result<(), String> {
+        fs::remove_file(path).map_err(|e| format!("Falha ao remover {}: {e}", path.display()))?;
+        let meta = meta_path(path);
+        if meta.is_file() {
+            fs::remove_file(&meta).map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+
+    /// "Nome_1.ext" ao lado do original
+    fn duplicate_asset(&mut self, language: EngineLanguage, source: &Path) {
+        let Some(dir) = source.parent() else {
+            return;
+        };
+        let stem = source
+            .file_stem()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let copy = match source.extension().and_then(|e| e.to_str()) {
+            Some(ext) => Self::unique_named_file_path(dir, &stem, ext),
+            None => Self::unique_named_folder_path(dir, &stem),
+        };
+        match Self::copy_asset_file(source, &copy) {
+            Ok(()) => {
+                let name = copy
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                self.status_text = format!("{}: {name}", self.tr(language, "duplicate"));
+                self.selected_asset = Some(name);
+                self.pending_asset_edit = Some(AssetFileEdit::Duplicate {
+                    source: source.to_path_buf(),
+                    copy,
+                });
+            }
+            Err(err) => self.status_text = err,
+        }
+    }
+
+    /// Operação de arquivo feita pelo Projeto neste frame, para o histórico
+    pub(crate) fn take_asset_edit(&mut self) -> Option<AssetFileEdit> {
+        self.pending_asset_edit.take()
+    }
+
+    pub(crate) fn take_reimport_request(&mut self) -> Option<PathBuf> {
+        self.pending_reimport.take()
+    }
+
+    /// Volta ao estado de antes (`undo`) ou de depois da operação
+    pub(crate) fn restore_asset_edit(
+        &mut self,
+        edit: &AssetFileEdit,
+        undo: bool,
+    ) -> Result<(), String> {
+        match edit {
+            AssetFileEdit::Move { from, to } => {
+                let (from, to) = if undo { (to, from) } else { (from, to) };
+                Self::move_asset_path(from, to)?;
+                if let Some(open) = self.sub_folder.clone() {
+                    if let Ok(rest) = open.strip_prefix(from) {
+                        self.open_dir(&to.join(rest));
+                    }
+                }
+                self.selected_asset = None;
+            }
+            AssetFileEdit::Duplicate { source, copy } => {
+                if undo {
+                    Self::remove_asset_file(copy)?;
+                    self.selected_asset = None;
+                } else {
+                    Self::copy_asset_file(source, copy)?;
+                }
+            }
+            AssetFileEdit::Delete { asset } => {
+                if undo {
+                    self.deleted_assets.remove(asset);
+                } else {
+                    self.deleted_assets.insert(asset.clone());
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn try_open_asset_folder(
         &mut self,
         asset: &str,
@@ -1645,18 +2521,8 @@ impl ProjectWindow {
         if !path.is_dir() {
             return false;
         }
-        let Some(folder_id) = Self::folder_id_from_path(path) else {
-            return false;
-        };
-        self.selected_folder = folder_id;
-        self.selected_asset = None;
-        self.selected_sub_asset = None;
+        self.open_dir(path);
         self.status_text = format!("{}: {}", self.tr(language, "open"), asset);
-        if Self::is_packages_folder_id(folder_id) {
-            self.packages_open = true;
-        } else if Self::is_assets_folder_id(folder_id) {
-            self.assets_open = true;
-        }
         true
     }
 
@@ -1716,11 +2582,11 @@ impl ProjectWindow {
             label,
             FontId::new(12.0, FontFamily::Proportional),
             if selected {
-                Color32::from_rgb(15, 232, 121)
+                theme::palette().accent
             } else if resp.hovered() {
-                Color32::from_gray(225)
+                theme::palette().text_strong
             } else {
-                Color32::from_gray(195)
+                theme::palette().text
             },
         );
 
@@ -1775,7 +2641,7 @@ impl ProjectWindow {
                 Align2::CENTER_CENTER,
                 if *is_open { "▾" } else { "▸" },
                 FontId::new(11.0, FontFamily::Proportional),
-                Color32::from_gray(140),
+                theme::palette().text_weak,
             );
         }
 
@@ -1785,64 +2651,46 @@ impl ProjectWindow {
             label,
             FontId::new(12.0, FontFamily::Proportional),
             if selected {
-                Color32::from_rgb(15, 232, 121)
+                theme::palette().accent
             } else if row_resp.hovered() {
-                Color32::from_gray(225)
+                theme::palette().text_strong
             } else {
-                Color32::from_gray(195)
+                theme::palette().text
             },
         );
 
         row_resp
     }
 
+    /// Desenha o Projeto em `panel_rect`, o espaço que o layout de painéis deu
+    /// a ele; devolve se o painel pediu para ser recolhido
     pub fn show(
         &mut self,
         ctx: &egui::Context,
         language: EngineLanguage,
-        bottom_offset: f32,
+        panel_rect: Rect,
     ) -> bool {
+        engine_core::profile_function!();
         if !self.open {
             return false;
         }
 
         if self.arrow_icon_texture.is_none() {
-            self.arrow_icon_texture = load_png_as_texture(ctx, "src/assets/icons/seta.png");
+            self.arrow_icon_texture = load_png_as_texture(ctx, "Editor/icons/seta.png");
         }
         if self.rig_icon_texture.is_none() {
-            self.rig_icon_texture = load_png_as_texture(ctx, "src/assets/icons/rig.png");
+            self.rig_icon_texture = load_png_as_texture(ctx, "Editor/icons/rig.png");
         }
         if self.animador_icon_texture.is_none() {
-            self.animador_icon_texture = load_png_as_texture(ctx, "src/assets/icons/animador.png");
+            self.animador_icon_texture = load_png_as_texture(ctx, "Editor/icons/animador.png");
         }
         self.poll_preview_jobs(ctx);
 
-        let dock_rect_full = ctx.available_rect();
-        let dock_rect = Rect::from_min_max(
-            dock_rect_full.min,
-            egui::pos2(
-                dock_rect_full.max.x,
-                dock_rect_full.max.y - bottom_offset.max(0.0),
-            ),
-        );
-        let pointer_down = ctx.input(|i| i.pointer.primary_down());
-
-        let min_h = 185.0;
-        let max_h = (dock_rect.height() - 120.0).max(min_h);
-        self.panel_height = self.panel_height.clamp(min_h, max_h);
-
-        let panel_rect = Rect::from_min_max(
-            egui::pos2(dock_rect.left(), dock_rect.bottom() - self.panel_height),
-            egui::pos2(dock_rect.right(), dock_rect.bottom()),
-        );
-
         let mut request_collapse = false;
         let mut request_import = false;
         let mut request_create_script = false;
         let mut request_create_material = false;
         let mut request_create_folder = false;
-        let mut resize_started = false;
-        let mut resize_stopped = false;
 
         egui::Area::new(Id::new("project_window"))
             .order(Order::Foreground)
@@ -1852,40 +2700,14 @@ impl ProjectWindow {
                 self.last_panel_rect = Some(rect);
 
                 ui.painter()
-                    .rect_filled(rect, 0.0, Color32::from_rgb(35, 35, 35));
+                    .rect_filled(rect, 0.0, theme::palette().panel_bg);
                 ui.painter().rect_stroke(
                     rect,
                     0.0,
-                    Stroke::new(1.0, Color32::from_rgb(58, 58, 58)),
+                    Stroke::new(1.0, theme::palette().border),
                     egui::StrokeKind::Outside,
                 );
 
-                let resize_rect = Rect::from_min_max(
-                    egui::pos2(rect.left(), rect.top() - 4.0),
-                    egui::pos2(rect.right(), rect.top() + 5.0),
-                );
-                let resize_resp = ui.interact(
-                    resize_rect,
-                    ui.id().with("project_resize"),
-                    Sense::click_and_drag(),
-                );
-                if resize_resp.hovered() || resize_resp.dragged() {
-                    ui.output_mut(|o| o.cursor_icon = egui::CursorIcon::ResizeVertical);
-                    ui.painter().line_segment(
-                        [
-                            egui::pos2(rect.left(), rect.top()),
-                            egui::pos2(rect.right(), rect.top()),
-                        ],
-                        Stroke::new(2.0, Color32::from_rgb(15, 232, 121)),
-                    );
-                }
-                if resize_resp.drag_started() {
-                    resize_started = true;
-                }
-                if resize_resp.drag_stopped() {
-                    resize_stopped = true;
-                }
-
                 let inner = rect.shrink2(egui::vec2(8.0, 6.0));
                 let header_rect =
                     Rect::from_min_max(inner.min, egui::pos2(inner.max.x, inner.min.y + 24.0));
@@ -1904,7 +2726,7 @@ impl ProjectWindow {
                     ui.painter().rect_filled(
                         collapse_btn_rect.expand(2.0),
                         3.0,
-                        Color32::from_rgb(58, 58, 58),
+                        theme::palette().border,
                     );
                 }
                 if collapse_resp.clicked() {
@@ -1923,7 +2745,7 @@ impl ProjectWindow {
                         Align2::CENTER_CENTER,
                         "▾",
                         FontId::new(11.0, FontFamily::Proportional),
-                        Color32::from_gray(190),
+                        theme::palette().text,
                     );
                 }
 
@@ -1941,22 +2763,26 @@ impl ProjectWindow {
                         ui.label(
                             egui::RichText::new(self.tr(language, "title"))
                                 .size(12.0)
-                                .color(Color32::from_gray(175)),
+                                .color(theme::palette().text),
                         );
                         ui.add_space(6.0);
                         ui.label(
                             egui::RichText::new("|")
                                 .size(12.0)
-                                .color(Color32::from_gray(110)),
+                                .color(theme::palette().text_weak),
                         );
                         ui.add_space(8.0);
 
-                        for (idx, (folder_id, folder_label)) in breadcrumb.iter().enumerate() {
-                            let is_current = *folder_id == self.selected_folder;
+                        for (idx, (folder_path, folder_label)) in breadcrumb.iter().enumerate() {
+                            let is_current = idx + 1 == breadcrumb.len();
                             let crumb = ui.add(
                                 egui::Label::new(
                                     egui::RichText::new(folder_label).size(12.0).color(
-                                        Color32::from_gray(if is_current { 220 } else { 190 }),
+                                        if is_current {
+                                            theme::palette().text_strong
+                                        } else {
+                                            theme::palette().text
+                                        },
                                     ),
                                 )
                                 .sense(Sense::click()),
@@ -1968,24 +2794,19 @@ impl ProjectWindow {
                                         egui::pos2(crumb.rect.left(), crumb.rect.bottom() + 1.0),
                                         egui::pos2(crumb.rect.right(), crumb.rect.bottom() + 1.0),
                                     ],
-                                    Stroke::new(1.0, Color32::from_rgb(15, 232, 121)),
+                                    Stroke::new(1.0, theme::palette().accent),
                                 );
                             }
                             if crumb.clicked() {
-                                self.selected_folder = *folder_id;
-                                self.selected_asset = None;
-                                if self.selected_folder == "Assets" {
-                                    self.assets_open = true;
-                                } else if self.selected_folder == "Packages" {
-                                    self.packages_open = true;
-                                }
+                                self.open_dir(folder_path);
                             }
+                            self.folder_drop_target(ui, crumb.rect, folder_path, language);
 
                             if idx + 1 < breadcrumb.len() {
                                 ui.label(
                                     egui::RichText::new(">")
                                         .size(12.0)
-                                        .color(Color32::from_gray(150)),
+                                        .color(theme::palette().text_weak),
                                 );
                             }
                         }
@@ -2029,11 +2850,17 @@ impl ProjectWindow {
                                 .with_main_align(egui::Align::Center),
                         ),
                         |ui| {
-                            ui.add(
-                                egui::TextEdit::singleline(&mut self.search_query)
-                                    .desired_width(search_w)
-                                    .hint_text(search_hint),
-                            );
+                            let search_resp = ui
+                                .add(
+                                    egui::TextEdit::singleline(&mut self.search_query)
+                                        .desired_width(search_w)
+                                        .hint_text(search_hint),
+                                )
+                                .on_hover_text(self.tr(language, "search_help"));
+                            if search_resp.changed() || search_resp.gained_focus() {
+                                self.search_popup_open = true;
+                            }
+                            self.search_box_rect = Some(search_resp.rect);
                         },
                     );
                 }
@@ -2048,7 +2875,7 @@ impl ProjectWindow {
                                 [import_w, 22.0],
                                 egui::Button::new(self.tr(language, "import"))
                                     .corner_radius(6)
-                                    .stroke(Stroke::new(1.0, Color32::from_rgb(15, 232, 121))),
+                                    .stroke(Stroke::new(1.0, theme::palette().accent)),
                             )
                             .clicked()
                         {
@@ -2062,7 +2889,7 @@ impl ProjectWindow {
                         egui::pos2(inner.left(), splitter_y),
                         egui::pos2(inner.right(), splitter_y),
                     ],
-                    Stroke::new(1.0, Color32::from_rgb(62, 62, 62)),
+                    Stroke::new(1.0, theme::palette().widget_hover),
                 );
 
                 let content_rect = Rect::from_min_max(
@@ -2084,7 +2911,7 @@ impl ProjectWindow {
                         egui::pos2(sidebar_rect.right() + 4.0, content_rect.top()),
                         egui::pos2(sidebar_rect.right() + 4.0, content_rect.bottom()),
                     ],
-                    Stroke::new(1.0, Color32::from_rgb(60, 60, 60)),
+                    Stroke::new(1.0, theme::palette().border),
                 );
 
                 ui.scope_builder(
@@ -2096,7 +2923,8 @@ impl ProjectWindow {
                             .id_salt("project_sidebar")
                             .auto_shrink([false, false])
                             .show(ui, |ui| {
-                                let assets_selected = self.selected_folder == "Assets";
+                                let assets_selected =
+                                    self.selected_folder == "Assets" && self.sub_folder.is_none();
                                 let mut assets_open = self.assets_open;
                                 let assets_resp = self.draw_tree_parent_row(
                                     ui,
@@ -2108,9 +2936,15 @@ impl ProjectWindow {
                                 );
                                 self.assets_open = assets_open;
                                 if assets_resp.clicked() {
-                                    self.selected_folder = "Assets";
+                                    self.select_folder("Assets");
                                     self.selected_asset = None;
                                 }
+                                self.folder_drop_target(
+                                    ui,
+                                    assets_resp.rect,
+                                    Path::new("Assets"),
+                                    language,
+                                );
 
                                 if self.assets_open {
                                     for folder in [
@@ -2129,12 +2963,18 @@ impl ProjectWindow {
                                             folder,
                                             folder,
                                             18.0,
-                                            self.selected_folder == folder,
+                                            self.selected_folder == folder
+                                                && self.sub_folder.is_none(),
                                         );
                                         if leaf.clicked() {
-                                            self.selected_folder = folder;
+                                            self.select_folder(folder);
                                             self.selected_asset = None;
                                         }
+                                        if let Some(dir) = Self::folder_path_from_id(folder) {
+                                            self.folder_context_menu(&leaf, &dir, language);
+                                            self.folder_drop_target(ui, leaf.rect, &dir, language);
+                                            self.draw_folder_subtree(ui, &dir, 18.0, language);
+                                        }
                                         if folder == "Meshes" && self.selected_folder == "Meshes" {
                                             let Some(meshes_dir) =
                                                 Self::folder_path_from_id("Meshes")
@@ -2160,7 +3000,7 @@ impl ProjectWindow {
                                                     self.fbx_expanded_assets.remove(&fbx_asset);
                                                 }
                                                 if row.clicked() {
-                                                    self.selected_folder = "Meshes";
+                                                    self.select_folder("Meshes");
                                                     self.selected_asset = Some(fbx_asset.clone());
                                                     self.status_text = fbx_asset.clone();
                                                 }
@@ -2233,7 +3073,7 @@ impl ProjectWindow {
                                                                 self.animador_icon_texture.as_ref(),
                                                             );
                                                         if resp.clicked() {
-                                                            self.selected_folder = "Meshes";
+                                                            self.select_folder("Meshes");
                                                             self.selected_asset =
                                                                 Some(fbx_asset.clone());
                                                             self.status_text =
@@ -2246,9 +3086,40 @@ impl ProjectWindow {
                                     }
                                 }
 
+                                // Pastas criadas pelo usuário direto em Assets
+                                if self.assets_open {
+                                    let known: Vec<PathBuf> = KNOWN_FOLDER_PATHS
+                                        .iter()
+                                        .map(|(path, _)| PathBuf::from(path))
+                                        .collect();
+                                    for dir in Self::subdirs(Path::new("Assets")) {
+                                        if known.contains(&dir) {
+                                            continue;
+                                        }
+                                        let name = dir
+                                            .file_name()
+                                            .map(|n| n.to_string_lossy().to_string())
+                                            .unwrap_or_default();
+                                        let leaf = Self::draw_tree_leaf_row(
+                                            ui,
+                                            &dir.to_string_lossy(),
+                                            &name,
+                                            18.0,
+                                            self.sub_folder.as_deref() == Some(dir.as_path()),
+                                        );
+                                        if leaf.clicked() {
+                                            self.open_dir(&dir);
+                                        }
+                                        self.folder_context_menu(&leaf, &dir, language);
+                                        self.folder_drop_target(ui, leaf.rect, &dir, language);
+                                        self.draw_folder_subtree(ui, &dir, 18.0, language);
+                                    }
+                                }
+
                                 ui.add_space(2.0);
 
-                                let packages_selected = self.selected_folder == "Packages";
+                                let packages_selected =
+                                    self.selected_folder == "Packages" && self.sub_folder.is_none();
                                 let mut packages_open = self.packages_open;
                                 let pkg_resp = self.draw_tree_parent_row(
                                     ui,
@@ -2260,7 +3131,7 @@ impl ProjectWindow {
                                 );
                                 self.packages_open = packages_open;
                                 if pkg_resp.clicked() {
-                                    self.selected_folder = "Packages";
+                                    self.select_folder("Packages");
                                     self.selected_asset = None;
                                 }
 
@@ -2274,10 +3145,11 @@ impl ProjectWindow {
                                             folder,
                                             folder,
                                             18.0,
-                                            self.selected_folder == folder,
+                                            self.selected_folder == folder
+                                                && self.sub_folder.is_none(),
                                         );
                                         if leaf.clicked() {
-                                            self.selected_folder = folder;
+                                            self.select_folder(folder);
                                             self.selected_asset = None;
                                         }
                                     }
@@ -2287,12 +3159,12 @@ impl ProjectWindow {
                 );
 
                 let assets = self.assets_for_folder();
-                let filter = self.search_query.to_lowercase();
+                let query = SearchQuery::parse(&self.search_query);
                 let filtered_assets: Vec<&String> = assets
                     .iter()
                     .filter(|asset| {
                         !self.deleted_assets.contains(*asset)
-                            && (filter.is_empty() || asset.to_lowercase().contains(&filter))
+                            && (query.is_empty() || self.asset_matches(&query, asset))
                     })
                     .collect();
                 self.hovered_asset = None;
@@ -2332,17 +3204,34 @@ impl ProjectWindow {
                             .id_salt("project_grid")
                             .auto_shrink([false, false])
                             .show(ui, |ui| {
-                                let spacing = egui::vec2(8.0, 8.0);
+                                let mut spacing = egui::vec2(8.0, 8.0);
                                 ui.spacing_mut().item_spacing = spacing;
-                                let tile_w = self.icon_scale.clamp(56.0, 98.0);
+                                let list_view = self.list_view;
+                                if list_view {
+                                    spacing.y = 2.0;
+                                    ui.spacing_mut().item_spacing = spacing;
+                                }
+                                let tile_w = if list_view {
+                                    ui.available_width()
+                                } else {
+                                    self.icon_scale
+                                        .clamp(Self::ICON_SCALE_MIN, Self::ICON_SCALE_MAX)
+                                };
                                 let tile_name_h = 18.0;
                                 let tile_pad = 6.0;
-                                let tile_size =
-                                    Vec2::new(tile_w, tile_w + tile_name_h + tile_pad * 2.0);
-                                let cols = (((ui.available_width() + spacing.x)
-                                    / (tile_size.x + spacing.x))
-                                    .floor() as usize)
-                                    .max(1);
+                                let tile_size = if list_view {
+                                    Vec2::new(tile_w, Self::LIST_ROW_H)
+                                } else {
+                                    Vec2::new(tile_w, tile_w + tile_name_h + tile_pad * 2.0)
+                                };
+                                let cols = if list_view {
+                                    1
+                                } else {
+                                    (((ui.available_width() + spacing.x)
+                                        / (tile_size.x + spacing.x))
+                                        .floor() as usize)
+                                        .max(1)
+                                };
                                 let now = ui.ctx().input(|i| i.time);
                                 let mut hovered_any = false;
 
@@ -2359,10 +3248,6 @@ impl ProjectWindow {
                                                 .as_ref()
                                                 .map(|p| p.is_dir())
                                                 .unwrap_or(false);
-                                            let tile_size = Vec2::new(
-                                                tile_w,
-                                                tile_w + tile_name_h + tile_pad * 2.0,
-                                            );
                                             let selected =
                                                 self.selected_asset.as_ref() == Some(asset);
                                             let (tile_rect, tile_resp) = ui.allocate_exact_size(
@@ -2374,82 +3259,211 @@ impl ProjectWindow {
                                                 tile_rect,
                                                 4.0,
                                                 if selected {
-                                                    Color32::from_rgb(64, 64, 64)
+                                                    theme::palette().selection
                                                 } else {
-                                                    Color32::from_rgb(44, 44, 44)
+                                                    theme::palette().widget_bg
                                                 },
                                             );
                                             ui.painter().rect_stroke(
                                                 tile_rect,
                                                 4.0,
                                                 if selected {
-                                                    Stroke::new(
-                                                        1.0,
-                                                        Color32::from_rgb(15, 232, 121),
-                                                    )
+                                                    Stroke::new(1.0, theme::palette().accent)
                                                 } else {
-                                                    Stroke::new(1.0, Color32::from_rgb(58, 58, 58))
+                                                    Stroke::new(1.0, theme::palette().border)
                                                 },
                                                 egui::StrokeKind::Outside,
                                             );
 
-                                            let preview_rect = Rect::from_min_max(
-                                                tile_rect.min + egui::vec2(tile_pad, tile_pad),
-                                                egui::pos2(
-                                                    tile_rect.max.x - tile_pad,
-                                                    tile_rect.max.y - tile_name_h - tile_pad,
-                                                ),
-                                            );
+                                            let (badge_color, badge_tag) = if asset_is_dir {
+                                                (Color32::from_rgb(150, 126, 64), "DIR")
+                                            } else {
+                                                Self::icon_style(asset)
+                                            };
+                                            // Lista: miniatura pequena à esquerda e o selo
+                                            // de tipo no fim da linha
+                                            let preview_rect = if list_view {
+                                                Rect::from_min_size(
+                                                    tile_rect.min + egui::vec2(3.0, 3.0),
+                                                    Vec2::splat(Self::LIST_ROW_H - 6.0),
+                                                )
+                                            } else {
+                                                Rect::from_min_max(
+                                                    tile_rect.min + egui::vec2(tile_pad, tile_pad),
+                                                    egui::pos2(
+                                                        tile_rect.max.x - tile_pad,
+                                                        tile_rect.max.y - tile_name_h - tile_pad,
+                                                    ),
+                                                )
+                                            };
                                             ui.painter().rect_filled(
                                                 preview_rect,
                                                 3.0,
-                                                Color32::from_rgb(38, 40, 42),
+                                                theme::palette().panel_header,
                                             );
-                                            if let Some(tex) =
-                                                self.asset_preview_texture(ui.ctx(), asset)
+                                            let mut has_thumbnail = false;
+                                            if let Some((tex, frames)) =
+                                                self.asset_preview_texture(asset)
                                             {
+                                                has_thumbnail = true;
+                                                // Malha sob o mouse gira pelos quadros do
+                                                // turntable
+                                                let frame = if frames > 1 && tile_resp.hovered() {
+                                                    ui.ctx().request_repaint();
+                                                    (now * Self::TURNTABLE_FPS) as u32 % frames
+                                                } else {
+                                                    0
+                                                };
+                                                let u0 = frame as f32 / frames as f32;
+                                                let u1 = (frame + 1) as f32 / frames as f32;
                                                 let image_rect = preview_rect.shrink(1.0);
                                                 let _ = ui.put(
                                                     image_rect,
                                                     egui::Image::new(tex)
+                                                        .uv(Rect::from_min_max(
+                                                            egui::pos2(u0, 0.0),
+                                                            egui::pos2(u1, 1.0),
+                                                        ))
                                                         .fit_to_exact_size(image_rect.size()),
                                                 );
                                                 ui.painter().rect_stroke(
                                                     preview_rect,
                                                     2.0,
-                                                    Stroke::new(
-                                                        1.0,
-                                                        Color32::from_rgba_unmultiplied(
-                                                            255, 255, 255, 26,
-                                                        ),
-                                                    ),
+                                                    Stroke::new(1.0, theme::palette().border),
                                                     egui::StrokeKind::Outside,
                                                 );
+                                            } else if let Some(audio) = self
+                                                .audio_clip_view
+                                                .as_ref()
+                                                .filter(|view| {
+                                                    !list_view
+                                                        && selected
+                                                        && asset_path.as_deref()
+                                                            == Some(view.path.as_path())
+                                                })
+                                                .cloned()
+                                            {
+                                                // Clipe selecionado: forma de onda e ▶/■
+                                                let wave_rect = preview_rect.shrink(1.0);
+                                                paint_waveform(ui.painter(), wave_rect, &audio);
+                                                let play_rect = Rect::from_center_size(
+                                                    wave_rect.left_bottom()
+                                                        + egui::vec2(10.0, -10.0),
+                                                    egui::vec2(16.0, 16.0),
+                                                );
+                                                let play_resp = ui.interact(
+                                                    play_rect,
+                                                    ui.id().with(("audio_tile_play", asset)),
+                                                    Sense::click(),
+                                                );
+                                                ui.painter().circle_filled(
+                                                    play_rect.center(),
+                                                    8.0,
+                                                    if play_resp.hovered() {
+                                                        theme::palette().accent
+                                                    } else {
+                                                        Color32::from_rgba_unmultiplied(
+                                                            0, 0, 0, 160,
+                                                        )
+                                                    },
+                                                );
+                                                ui.painter().text(
+                                                    play_rect.center(),
+                                                    Align2::CENTER_CENTER,
+                                                    if audio.playing { "■" } else { "▶" },
+                                                    FontId::proportional(9.0),
+                                                    Color32::WHITE,
+                                                );
+                                                if let Some(info) = audio.info.as_ref() {
+                                                    ui.painter().text(
+                                                        wave_rect.right_top()
+                                                            + egui::vec2(-3.0, 2.0),
+                                                        Align2::RIGHT_TOP,
+                                                        info.duration_label(),
+                                                        FontId::proportional(9.0),
+                                                        Color32::from_gray(200),
+                                                    );
+                                                }
+                                                if audio.playing {
+                                                    ui.ctx().request_repaint();
+                                                }
+                                                if play_resp.clicked() {
+                                                    self.pending_audio_preview =
+                                                        Some(if audio.playing {
+                                                            AudioPreviewRequest::Stop
+                                                        } else {
+                                                            AudioPreviewRequest::Play(
+                                                                audio.path.clone(),
+                                                            )
+                                                        });
+                                                }
                                             } else {
-                                                let (icon_color, icon_tag) =
-                                                    Self::icon_style(asset);
                                                 ui.painter().rect_filled(
                                                     preview_rect.shrink(1.0),
                                                     2.0,
-                                                    icon_color,
+                                                    badge_color,
                                                 );
-                                                ui.painter().text(
-                                                    preview_rect.center(),
-                                                    egui::Align2::CENTER_CENTER,
-                                                    icon_tag,
-                                                    FontId::proportional(10.0),
-                                                    Color32::from_gray(245),
+                                                if !list_view {
+                                                    ui.painter().text(
+                                                        preview_rect.center(),
+                                                        egui::Align2::CENTER_CENTER,
+                                                        badge_tag,
+                                                        FontId::proportional(10.0),
+                                                        Color32::from_gray(245),
+                                                    );
+                                                }
+                                            }
+                                            if list_view {
+                                                let badge_rect = Rect::from_min_size(
+                                                    egui::pos2(
+                                                        tile_rect.right() - Self::LIST_BADGE_W,
+                                                        tile_rect.center().y - 7.0,
+                                                    ),
+                                                    egui::vec2(Self::LIST_BADGE_W - 6.0, 14.0),
+                                                );
+                                                Self::draw_type_badge(
+                                                    ui.painter(),
+                                                    badge_rect,
+                                                    badge_color,
+                                                    badge_tag,
+                                                );
+                                            } else if has_thumbnail {
+                                                // Miniatura cobre a sigla: selo no canto
+                                                let badge_rect = Rect::from_min_size(
+                                                    preview_rect.min + egui::vec2(3.0, 3.0),
+                                                    egui::vec2(
+                                                        (badge_tag.len() as f32 * 6.0 + 6.0)
+                                                            .min(preview_rect.width() - 6.0),
+                                                        12.0,
+                                                    ),
+                                                );
+                                                Self::draw_type_badge(
+                                                    ui.painter(),
+                                                    badge_rect,
+                                                    badge_color.gamma_multiply(0.9),
+                                                    badge_tag,
                                                 );
                                             }
                                             let mut expanded_fbx = false;
                                             if self.selected_folder == "Meshes"
+                                                && self.sub_folder.is_none()
                                                 && asset.to_ascii_lowercase().ends_with(".fbx")
                                             {
-                                                let expand_rect = Rect::from_center_size(
+                                                let expand_center = if list_view {
+                                                    egui::pos2(
+                                                        tile_rect.right()
+                                                            - Self::LIST_BADGE_W
+                                                            - 10.0,
+                                                        tile_rect.center().y,
+                                                    )
+                                                } else {
                                                     egui::pos2(
                                                         preview_rect.right() - 8.0,
                                                         preview_rect.center().y,
-                                                    ),
+                                                    )
+                                                };
+                                                let expand_rect = Rect::from_center_size(
+                                                    expand_center,
                                                     egui::vec2(14.0, 14.0),
                                                 );
                                                 let expand_resp = ui.interact(
@@ -2464,9 +3478,7 @@ impl ProjectWindow {
                                                     ui.painter().circle_filled(
                                                         expand_rect.center(),
                                                         8.0,
-                                                        Color32::from_rgba_unmultiplied(
-                                                            255, 255, 255, 26,
-                                                        ),
+                                                        theme::palette().widget_hover,
                                                     );
                                                 }
                                                 if let Some(arrow_tex) = &self.arrow_icon_texture {
@@ -2489,7 +3501,7 @@ impl ProjectWindow {
                                                         Align2::CENTER_CENTER,
                                                         if expanded { "▾" } else { "▸" },
                                                         FontId::new(11.0, FontFamily::Proportional),
-                                                        Color32::from_gray(230),
+                                                        theme::palette().text_strong,
                                                     );
                                                 }
                                                 if expand_resp.clicked() {
@@ -2505,17 +3517,37 @@ impl ProjectWindow {
                                                 }
                                             }
                                             let name_font = FontId::proportional(11.0);
-                                            let name_color = Color32::from_gray(210);
-                                            let name_rect = Rect::from_min_max(
-                                                egui::pos2(
-                                                    tile_rect.left() + tile_pad,
-                                                    tile_rect.bottom() - tile_name_h - 2.0,
-                                                ),
-                                                egui::pos2(
-                                                    tile_rect.right() - tile_pad,
-                                                    tile_rect.bottom() - 2.0,
-                                                ),
-                                            );
+                                            let name_color = theme::palette().text;
+                                            let name_rect = if list_view {
+                                                Rect::from_min_max(
+                                                    egui::pos2(
+                                                        preview_rect.right() + 6.0,
+                                                        tile_rect.top(),
+                                                    ),
+                                                    egui::pos2(
+                                                        tile_rect.right()
+                                                            - Self::LIST_BADGE_W
+                                                            - 20.0,
+                                                        tile_rect.bottom(),
+                                                    ),
+                                                )
+                                            } else {
+                                                Rect::from_min_max(
+                                                    egui::pos2(
+                                                        tile_rect.left() + tile_pad,
+                                                        tile_rect.bottom() - tile_name_h - 2.0,
+                                                    ),
+                                                    egui::pos2(
+                                                        tile_rect.right() - tile_pad,
+                                                        tile_rect.bottom() - 2.0,
+                                                    ),
+                                                )
+                                            };
+                                            let (name_pos, name_align) = if list_view {
+                                                (name_rect.left_center(), Align2::LEFT_CENTER)
+                                            } else {
+                                                (name_rect.center(), Align2::CENTER_CENTER)
+                                            };
                                             let clipped_painter =
                                                 ui.painter().with_clip_rect(name_rect);
                                             let full_w = ui
@@ -2530,8 +3562,8 @@ impl ProjectWindow {
 
                                             if full_w <= name_rect.width() {
                                                 clipped_painter.text(
-                                                    name_rect.center(),
-                                                    egui::Align2::CENTER_CENTER,
+                                                    name_pos,
+                                                    name_align,
                                                     asset,
                                                     name_font.clone(),
                                                     name_color,
@@ -2552,8 +3584,8 @@ impl ProjectWindow {
                                                         name_rect.width(),
                                                     );
                                                     clipped_painter.text(
-                                                        name_rect.center(),
-                                                        egui::Align2::CENTER_CENTER,
+                                                        name_pos,
+                                                        name_align,
                                                         short,
                                                         name_font.clone(),
                                                         name_color,
@@ -2578,8 +3610,11 @@ impl ProjectWindow {
                                                     } else {
                                                         overflow
                                                     };
-                                                    let base_x =
-                                                        name_rect.center().x - full_w * 0.5;
+                                                    let base_x = if list_view {
+                                                        name_rect.left()
+                                                    } else {
+                                                        name_rect.center().x - full_w * 0.5
+                                                    };
 
                                                     clipped_painter.text(
                                                         egui::pos2(
@@ -2600,8 +3635,8 @@ impl ProjectWindow {
                                                     name_rect.width(),
                                                 );
                                                 clipped_painter.text(
-                                                    name_rect.center(),
-                                                    egui::Align2::CENTER_CENTER,
+                                                    name_pos,
+                                                    name_align,
                                                     short,
                                                     name_font.clone(),
                                                     name_color,
@@ -2628,6 +3663,13 @@ impl ProjectWindow {
                                             let mut open_clicked = false;
                                             let mut reveal_clicked = false;
                                             let mut delete_clicked = false;
+                                            let mut links_clicked = None;
+                                            let mut labels_clicked = false;
+                                            let mut rename_clicked = false;
+                                            let mut duplicate_clicked = false;
+                                            let mut reimport_clicked = false;
+                                            let mut copy_path_clicked = false;
+                                            let mut copy_guid_clicked = false;
                                             tile_resp.context_menu(|ui| {
                                                 if ui.button(self.tr(language, "open")).clicked() {
                                                     open_clicked = true;
@@ -2639,12 +3681,73 @@ impl ProjectWindow {
                                                     ui.close();
                                                 }
                                                 ui.separator();
+                                                if ui
+                                                    .button(self.tr(language, "find_references"))
+                                                    .clicked()
+                                                {
+                                                    links_clicked = Some(true);
+                                                    ui.close();
+                                                }
+                                                if ui
+                                                    .button(self.tr(language, "find_dependencies"))
+                                                    .clicked()
+                                                {
+                                                    links_clicked = Some(false);
+                                                    ui.close();
+                                                }
+                                                if ui.button(self.tr(language, "labels")).clicked()
+                                                {
+                                                    labels_clicked = true;
+                                                    ui.close();
+                                                }
+                                                if ui.button(self.tr(language, "rename")).clicked()
+                                                {
+                                                    rename_clicked = true;
+                                                    ui.close();
+                                                }
+                                                if !asset_is_dir {
+                                                    if ui
+                                                        .button(self.tr(language, "duplicate"))
+                                                        .clicked()
+                                                    {
+                                                        duplicate_clicked = true;
+                                                        ui.close();
+                                                    }
+                                                    if ui
+                                                        .button(self.tr(language, "reimport"))
+                                                        .clicked()
+                                                    {
+                                                        reimport_clicked = true;
+                                                        ui.close();
+                                                    }
+                                                }
+                                                ui.separator();
+                                                if ui
+                                                    .button(self.tr(language, "copy_path"))
+                                                    .clicked()
+                                                {
+                                                    copy_path_clicked = true;
+                                                    ui.close();
+                                                }
+                                                if !asset_is_dir
+                                                    && ui
+                                                        .button(self.tr(language, "copy_guid"))
+                                                        .clicked()
+                                                {
+                                                    copy_guid_clicked = true;
+                                                    ui.close();
+                                                }
+                                                ui.separator();
                                                 if ui
                                                     .add(
                                                         egui::Button::new(
                                                             self.tr(language, "delete"),
                                                         )
-                                                        .fill(Color32::from_rgb(74, 38, 38)),
+                                                        .fill(
+                                                            theme::palette()
+                                                                .error
+                                                                .gamma_multiply(0.35),
+                                                        ),
                                                     )
                                                     .clicked()
                                                 {
@@ -2653,6 +3756,25 @@ impl ProjectWindow {
                                                 }
                                             });
 
+                                            let is_material =
+                                                asset_path.as_deref().is_some_and(|path| {
+                                                    path.extension().is_some_and(|ext| {
+                                                        ext.eq_ignore_ascii_case("mat")
+                                                    })
+                                                });
+                                            if is_material
+                                                && (open_clicked || tile_resp.double_clicked())
+                                            {
+                                                self.pending_material_open = asset_path.clone();
+                                            }
+                                            let is_prefab = asset_path
+                                                .as_deref()
+                                                .is_some_and(crate::prefab::is_prefab);
+                                            if is_prefab
+                                                && (open_clicked || tile_resp.double_clicked())
+                                            {
+                                                self.pending_prefab_open = asset_path.clone();
+                                            }
                                             if open_clicked {
                                                 self.selected_asset = Some(asset.clone());
                                                 self.status_text = format!(
@@ -2662,14 +3784,83 @@ impl ProjectWindow {
                                                 );
                                             }
                                             if reveal_clicked {
+                                                if let Some(path) = asset_path.as_deref() {
+                                                    self.status_text =
+                                                        match show_in_file_manager(path) {
+                                                            Ok(()) => format!(
+                                                                "{}: {}",
+                                                                self.tr(language, "reveal"),
+                                                                asset
+                                                            ),
+                                                            Err(err) => err,
+                                                        };
+                                                }
+                                            }
+                                            if duplicate_clicked {
+                                                if let Some(path) = asset_path.as_deref() {
+                                                    self.duplicate_asset(language, path);
+                                                }
+                                            }
+                                            if reimport_clicked {
+                                                self.pending_reimport = asset_path.clone();
                                                 self.status_text = format!(
                                                     "{}: {}",
-                                                    self.tr(language, "reveal"),
+                                                    self.tr(language, "reimport"),
                                                     asset
                                                 );
                                             }
+                                            if copy_path_clicked {
+                                                if let Some(path) = asset_path.as_deref() {
+                                                    let text =
+                                                        path.to_string_lossy().replace('\\', "/");
+                                                    self.status_text = format!(
+                                                        "{}: {text}",
+                                                        self.tr(language, "copied")
+                                                    );
+                                                    ui.ctx().copy_text(text);
+                                                }
+                                            }
+                                            if copy_guid_clicked {
+                                                if let Some(path) = asset_path.as_deref() {
+                                                    match ensure_guid(path) {
+                                                        Ok(guid) => {
+                                                            self.status_text = format!(
+                                                                "{}: {guid}",
+                                                                self.tr(language, "copied")
+                                                            );
+                                                            ui.ctx().copy_text(guid.to_string());
+                                                        }
+                                                        Err(err) => self.status_text = err,
+                                                    }
+                                                }
+                                            }
+                                            if let Some(references) = links_clicked {
+                                                self.asset_links = Some(AssetLinks {
+                                                    asset: asset.clone(),
+                                                    references,
+                                                    paths: self.asset_link_paths(asset, references),
+                                                });
+                                            }
+                                            if rename_clicked {
+                                                if let Some(path) = asset_path.as_deref() {
+                                                    self.start_rename(path);
+                                                }
+                                            }
+                                            if asset_is_dir {
+                                                if let Some(path) = asset_path.as_deref() {
+                                                    self.folder_drop_target(
+                                                        ui, tile_rect, path, language,
+                                                    );
+                                                }
+                                            }
+                                            if labels_clicked {
+                                                if let Some(path) = asset_path.clone() {
+                                                    let text = read_labels(&path).join(", ");
+                                                    self.labels_editor = Some((path, text));
+                                                }
+                                            }
                                             if delete_clicked {
-                                                self.delete_asset(language, asset);
+                                                self.request_delete_asset(language, asset);
                                             }
 
                                             if tile_resp.clicked() {
@@ -2737,12 +3928,16 @@ impl ProjectWindow {
                                                 let child_tile_w = (tile_w * 0.82).max(48.0);
                                                 let child_tile_name_h = 16.0;
                                                 let child_tile_pad = 5.0;
-                                                let child_tile_size = Vec2::new(
-                                                    child_tile_w,
-                                                    child_tile_w
-                                                        + child_tile_name_h
-                                                        + child_tile_pad * 2.0,
-                                                );
+                                                let child_tile_size = if list_view {
+                                                    Vec2::new(tile_w, Self::LIST_ROW_H)
+                                                } else {
+                                                    Vec2::new(
+                                                        child_tile_w,
+                                                        child_tile_w
+                                                            + child_tile_name_h
+                                                            + child_tile_pad * 2.0,
+                                                    )
+                                                };
                                                 let mut children: Vec<(
                                                     String,
                                                     Option<&TextureHandle>,
@@ -2803,9 +3998,9 @@ impl ProjectWindow {
                                                         ui.painter().rect_filled(
                                                             c_rect.expand(1.0),
                                                             5.0,
-                                                            Color32::from_rgba_unmultiplied(
-                                                                15, 232, 121, 24,
-                                                            ),
+                                                            theme::palette()
+                                                                .accent
+                                                                .gamma_multiply(0.1),
                                                         );
                                                     }
                                                     ui.painter().rect_stroke(
@@ -2814,36 +4009,41 @@ impl ProjectWindow {
                                                         if selected_sub {
                                                             Stroke::new(
                                                                 1.2,
-                                                                Color32::from_rgb(15, 232, 121),
+                                                                theme::palette().accent,
                                                             )
                                                         } else {
                                                             Stroke::new(
                                                                 1.0,
-                                                                Color32::from_rgb(70, 70, 78),
+                                                                theme::palette().border,
                                                             )
                                                         },
                                                         egui::StrokeKind::Outside,
                                                     );
-                                                    let c_preview = Rect::from_min_max(
-                                                        c_rect.min
-                                                            + egui::vec2(
-                                                                child_tile_pad,
-                                                                child_tile_pad,
+                                                    // Lista: filhos recuados sob o FBX
+                                                    let c_preview = if list_view {
+                                                        Rect::from_min_size(
+                                                            c_rect.min + egui::vec2(22.0, 3.0),
+                                                            Vec2::splat(Self::LIST_ROW_H - 6.0),
+                                                        )
+                                                    } else {
+                                                        Rect::from_min_max(
+                                                            c_rect.min
+                                                                + egui::vec2(
+                                                                    child_tile_pad,
+                                                                    child_tile_pad,
+                                                                ),
+                                                            egui::pos2(
+                                                                c_rect.max.x - child_tile_pad,
+                                                                c_rect.max.y
+                                                                    - child_tile_name_h
+                                                                    - child_tile_pad,
                                                             ),
-                                                        egui::pos2(
-                                                            c_rect.max.x - child_tile_pad,
-                                                            c_rect.max.y
-                                                                - child_tile_name_h
-                                                                - child_tile_pad,
-                                                        ),
-                                                    );
+                                                        )
+                                                    };
                                                     ui.painter().rect_stroke(
                                                         c_preview,
                                                         3.0,
-                                                        Stroke::new(
-                                                            1.0,
-                                                            Color32::from_rgb(88, 96, 108),
-                                                        ),
+                                                        Stroke::new(1.0, theme::palette().border),
                                                         egui::StrokeKind::Outside,
                                                     );
                                                     if let Some(icon) = icon_opt
@@ -2864,30 +4064,54 @@ impl ProjectWindow {
                                                                 ),
                                                         );
                                                     }
-                                                    let c_name_rect = Rect::from_min_max(
-                                                        egui::pos2(
-                                                            c_rect.left() + child_tile_pad,
-                                                            c_rect.bottom()
-                                                                - child_tile_name_h
-                                                                - 2.0,
-                                                        ),
-                                                        egui::pos2(
-                                                            c_rect.right() - child_tile_pad,
-                                                            c_rect.bottom() - 2.0,
-                                                        ),
-                                                    );
+                                                    let c_name_rect = if list_view {
+                                                        Rect::from_min_max(
+                                                            egui::pos2(
+                                                                c_preview.right() + 6.0,
+                                                                c_rect.top(),
+                                                            ),
+                                                            egui::pos2(
+                                                                c_rect.right() - child_tile_pad,
+                                                                c_rect.bottom(),
+                                                            ),
+                                                        )
+                                                    } else {
+                                                        Rect::from_min_max(
+                                                            egui::pos2(
+                                                                c_rect.left() + child_tile_pad,
+                                                                c_rect.bottom()
+                                                                    - child_tile_name_h
+                                                                    - 2.0,
+                                                            ),
+                                                            egui::pos2(
+                                                                c_rect.right() - child_tile_pad,
+                                                                c_rect.bottom() - 2.0,
+                                                            ),
+                                                        )
+                                                    };
                                                     let short = Self::truncate_with_ellipsis(
                                                         ui.painter(),
                                                         &label,
                                                         &FontId::proportional(11.0),
                                                         c_name_rect.width(),
                                                     );
+                                                    let (c_name_pos, c_name_align) = if list_view {
+                                                        (
+                                                            c_name_rect.left_center(),
+                                                            Align2::LEFT_CENTER,
+                                                        )
+                                                    } else {
+                                                        (
+                                                            c_name_rect.center(),
+                                                            Align2::CENTER_CENTER,
+                                                        )
+                                                    };
                                                     ui.painter().text(
-                                                        c_name_rect.center(),
-                                                        Align2::CENTER_CENTER,
+                                                        c_name_pos,
+                                                        c_name_align,
                                                         short,
                                                         FontId::proportional(11.0),
-                                                        Color32::from_gray(210),
+                                                        theme::palette().text,
                                                     );
                                                     if c_resp.clicked() {
                                                         self.selected_asset = Some(asset.clone());
@@ -2950,31 +4174,33 @@ impl ProjectWindow {
                         ui.label(
                             egui::RichText::new(status)
                                 .size(11.0)
-                                .color(Color32::from_gray(165)),
+                                .color(theme::palette().text_weak),
                         );
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                            let (slider_rect, _) =
-                                ui.allocate_exact_size(egui::vec2(140.0, 14.0), Sense::hover());
-                            self.draw_icon_size_slider(ui, slider_rect);
+                            if !self.list_view {
+                                let (slider_rect, _) =
+                                    ui.allocate_exact_size(egui::vec2(140.0, 14.0), Sense::hover());
+                                self.draw_icon_size_slider(ui, slider_rect);
+                            }
+                            if ui
+                                .selectable_label(self.list_view, "☰")
+                                .on_hover_text(self.tr(language, "list_view"))
+                                .clicked()
+                            {
+                                self.list_view = true;
+                            }
+                            if ui
+                                .selectable_label(!self.list_view, "▦")
+                                .on_hover_text(self.tr(language, "grid_view"))
+                                .clicked()
+                            {
+                                self.list_view = false;
+                            }
                         });
                     },
                 );
             });
 
-        if resize_started {
-            self.resizing_height = true;
-        }
-
-        if self.resizing_height && pointer_down {
-            let delta = ctx.input(|i| i.pointer.delta());
-            if delta.y != 0.0 {
-                self.panel_height = (self.panel_height - delta.y).clamp(min_h, max_h);
-            }
-        }
-
-        if resize_stopped || (self.resizing_height && !pointer_down) {
-            self.resizing_height = false;
-        }
         if request_import {
             self.import_asset_dialog(language);
         }
@@ -2988,148 +4214,23 @@ impl ProjectWindow {
             );
         }
         if request_create_material {
-            self.create_text_asset(
-                language,
-                "Materials",
-                "NovoMaterial",
-                "mat",
-                "# Dengine Material\nshader=Standard\nalbedo=1,1,1,1\nmetallic=0.0\nsmoothness=0.5\n",
-            );
+            self.pending_material_open = self.create_material(language);
         }
         if request_create_folder {
             self.create_folder_in_selected(language);
         }
+        self.show_model_import_window(ctx, language);
+        self.show_asset_links_windows(ctx, language);
+        self.show_search_popup(ctx, language);
+        self.show_labels_window(ctx, language);
+        self.show_rename_window(ctx, language);
 
         request_collapse
     }
 
-    pub fn docked_bottom_height(&self) -> f32 {
-        if self.open { self.panel_height } else { 0.0 }
-    }
-}
-
-fn build_mesh_preview(path: &Path) -> Result<MeshPreview, String> {
-    let (mut vertices, triangles) = load_preview_mesh_cached(path)?;
-    normalize_preview_vertices(&mut vertices);
-
-    // Isometric-ish thumbnail projection.
-    let yaw = 0.65_f32;
-    let pitch = 0.52_f32;
-    let (sy, cy) = yaw.sin_cos();
-    let (sp, cp) = pitch.sin_cos();
-    let mut p2: Vec<[f32; 2]> = Vec::with_capacity(vertices.len());
-    for v in &vertices {
-        let x1 = v.x * cy - v.z * sy;
-        let z1 = v.x * sy + v.z * cy;
-        let y2 = v.y * cp - z1 * sp;
-        p2.push([x1, y2]);
-    }
-
-    let mut edges = HashSet::<(u32, u32)>::new();
-    let mut lines: Vec<([f32; 2], [f32; 2])> = Vec::new();
-    for tri in &triangles {
-        for (a, b) in [(tri[0], tri[1]), (tri[1], tri[2]), (tri[2], tri[0])] {
-            let key = if a < b { (a, b) } else { (b, a) };
-            if !edges.insert(key) {
-                continue;
-            }
-            let ai = a as usize;
-            let bi = b as usize;
-            if ai < p2.len() && bi < p2.len() {
-                lines.push((p2[ai], p2[bi]));
-            }
-        }
-    }
-    if lines.is_empty() {
-        return Err("sem arestas para preview".to_string());
-    }
-    Ok(MeshPreview { lines })
-}
-
-fn rasterize_mesh_preview(preview: &MeshPreview, size: [usize; 2]) -> Vec<u8> {
-    let w = size[0].max(1);
-    let h = size[1].max(1);
-    let mut rgba = vec![0_u8; w * h * 4];
-
-    for px in rgba.chunks_exact_mut(4) {
-        px[0] = 33;
-        px[1] = 39;
-        px[2] = 46;
-        px[3] = 255;
-    }
-
-    let cx = w as f32 * 0.5;
-    let cy = h as f32 * 0.5;
-    let sx = w as f32 * 0.42;
-    let sy = h as f32 * 0.42;
-
-    for (a, b) in &preview.lines {
-        let x0 = (cx + a[0] * sx).round() as i32;
-        let y0 = (cy - a[1] * sy).round() as i32;
-        let x1 = (cx + b[0] * sx).round() as i32;
-        let y1 = (cy - b[1] * sy).round() as i32;
-        draw_line_rgba(&mut rgba, w, h, x0, y0, x1, y1, [145, 198, 236, 255]);
-    }
-
-    // Outer border to improve readability on dark tiles.
-    for x in 0..w {
-        put_pixel_rgba(&mut rgba, w, h, x as i32, 0, [82, 112, 136, 255]);
-        put_pixel_rgba(&mut rgba, w, h, x as i32, h as i32 - 1, [82, 112, 136, 255]);
-    }
-    for y in 0..h {
-        put_pixel_rgba(&mut rgba, w, h, 0, y as i32, [82, 112, 136, 255]);
-        put_pixel_rgba(&mut rgba, w, h, w as i32 - 1, y as i32, [82, 112, 136, 255]);
-    }
-
-    rgba
-}
-
-fn put_pixel_rgba(rgba: &mut [u8], w: usize, h: usize, x: i32, y: i32, color: [u8; 4]) {
-    if x < 0 || y < 0 {
-        return;
-    }
-    let xu = x as usize;
-    let yu = y as usize;
-    if xu >= w || yu >= h {
-        return;
-    }
-    let idx = (yu * w + xu) * 4;
-    rgba[idx] = color[0];
-    rgba[idx + 1] = color[1];
-    rgba[idx + 2] = color[2];
-    rgba[idx + 3] = color[3];
-}
-
-fn draw_line_rgba(
-    rgba: &mut [u8],
-    w: usize,
-    h: usize,
-    mut x0: i32,
-    mut y0: i32,
-    x1: i32,
-    y1: i32,
-    color: [u8; 4],
-) {
-    let dx = (x1 - x0).abs();
-    let sx = if x0 < x1 { 1 } else { -1 };
-    let dy = -(y1 - y0).abs();
-    let sy = if y0 < y1 { 1 } else { -1 };
-    let mut err = dx + dy;
-
-    loop {
-        put_pixel_rgba(rgba, w, h, x0, y0, color);
-        if x0 == x1 && y0 == y1 {
-            break;
-        }
-        let e2 = err * 2;
-        if e2 >= dy {
-            err += dy;
-            x0 += sx;
-        }
-        if e2 <= dx {
-            err += dx;
-            y0 += sy;
-        }
+    /// O painel não está visível neste frame (aba escondida ou recolhido)
+    pub fn clear_panel_rect(&mut self) {
+        self.last_panel_rect = None;
     }
 }
 
@@ -3152,7 +4253,7 @@ fn collect_project_files_recursive(
         }
         if path.is_dir() {
             collect_project_files_recursive(root, &path, out)?;
-        } else if path.is_file() {
+        } else if path.is_file() && !is_sidecar(&path) {
             let rel = path
                 .strip_prefix(root)
                 .ok()
@@ -3180,7 +4281,7 @@ fn load_preview_mesh_cached(path: &Path) -> Result<(Vec<glam::Vec3>, Vec<[u32; 3
     let mesh = match ext.as_str() {
         "obj" => load_obj_preview_mesh(path)?,
         "glb" | "gltf" => load_gltf_preview_mesh(path)?,
-        "fbx" => load_fbx_ascii_preview_mesh(path)?,
+        "fbx" => load_fbx_preview_mesh(path)?,
         _ => return Err("formato não suportado".to_string()),
     };
     let _ = write_dmesh_cache_preview(path, &mesh, stamp);
@@ -3432,77 +4533,40 @@ fn load_gltf_buffers_mesh_only_preview(
     Ok(out)
 }
 
-fn load_fbx_ascii_preview_mesh(path: &Path) -> Result<(Vec<glam::Vec3>, Vec<[u32; 3]>), String> {
-    use fbxcel_dom::any::AnyDocument;
-    use fbxcel_dom::v7400::object::{TypedObjectHandle, geometry::TypedGeometryHandle};
-    use std::io::BufReader;
-
-    let file = std::fs::File::open(path).map_err(|e| e.to_string())?;
-    let reader = BufReader::new(file);
-    let doc = match AnyDocument::from_seekable_reader(reader).map_err(|e| e.to_string())? {
-        AnyDocument::V7400(_, doc) => doc,
-        _ => return Err("versão FBX não suportada".to_string()),
-    };
-
-    let mut vertices = Vec::<glam::Vec3>::new();
-    let mut triangles = Vec::<[u32; 3]>::new();
-    for obj in doc.objects() {
-        let TypedObjectHandle::Geometry(TypedGeometryHandle::Mesh(mesh)) = obj.get_typed() else {
-            continue;
-        };
-        let poly_verts = mesh.polygon_vertices().map_err(|e| e.to_string())?;
-        let cps: Vec<_> = poly_verts
-            .raw_control_points()
-            .map_err(|e| e.to_string())?
-            .collect();
-        if cps.is_empty() {
-            continue;
-        }
-        let base = vertices.len() as u32;
-        // FBX forward correction kept consistent with runtime mesh import.
-        vertices.extend(
-            cps.iter()
-                .map(|p| glam::Vec3::new(-(p.x as f32), p.y as f32, -(p.z as f32))),
-        );
-
-        let mut poly: Vec<u32> = Vec::new();
-        for raw in poly_verts.raw_polygon_vertices() {
-            let is_end = *raw < 0;
-            let local_idx = if is_end {
-                (-raw - 1) as u32
-            } else {
-                *raw as u32
-            };
-            if (local_idx as usize) < cps.len() {
-                poly.push(base + local_idx);
-            }
-            if is_end {
-                if poly.len() >= 3 {
-                    for i in 1..(poly.len() - 1) {
-                        triangles.push([poly[0], poly[i], poly[i + 1]]);
-                    }
-                }
-                poly.clear();
-            }
-        }
-    }
-    if vertices.is_empty() || triangles.is_empty() {
-        return Err("FBX sem malha suportada".to_string());
-    }
+fn load_fbx_preview_mesh(path: &Path) -> Result<(Vec<glam::Vec3>, Vec<[u32; 3]>), String> {
+    let mesh = engine_render::MeshData::load_from_file(path)?;
+    // FBX forward correction kept consistent with runtime mesh import.
+    let vertices = mesh
+        .vertices
+        .iter()
+        .map(|v| glam::Vec3::new(-v.position.x, v.position.y, -v.position.z))
+        .collect();
+    let triangles = mesh
+        .indices
+        .chunks_exact(3)
+        .map(|t| [t[0], t[1], t[2]])
+        .collect();
     Ok((vertices, triangles))
 }
 
-fn normalize_preview_vertices(vertices: &mut [glam::Vec3]) {
-    let mut min = glam::Vec3::splat(f32::INFINITY);
-    let mut max = glam::Vec3::splat(f32::NEG_INFINITY);
-    for v in vertices.iter().copied() {
-        min = min.min(v);
-        max = max.max(v);
-    }
-    let center = (min + max) * 0.5;
-    let ext = (max - min).max(glam::Vec3::splat(1e-5));
-    let s = 1.7 / ext.x.max(ext.y).max(ext.z);
-    for v in vertices {
-        *v = (*v - center) * s;
-    }
+/// Abre o gerenciador de arquivos do sistema com `path` selecionado
+fn show_in_file_manager(path: &Path) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    let result = Command::new("explorer")
+        .arg(format!("/select,{}", path.display()))
+        .spawn();
+    #[cfg(target_os = "macos")]
+    let result = Command::new("open").arg("-R").arg(path).spawn();
+    // xdg-open não seleciona arquivos: abre a pasta que contém o asset
+    #[cfg(all(unix, not(target_os = "macos")))]
+    let result = Command::new("xdg-open")
+        .arg(if path.is_dir() {
+            path
+        } else {
+            path.parent().unwrap_or(Path::new("."))
+        })
+        .spawn();
+    result
+        .map(|_| ())
+        .map_err(|err| format!("Falha ao abrir {}: {err}", path.display()))
 }
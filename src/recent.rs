@@ -0,0 +1,281 @@
+use super::*;
+
+/// Quantos recentes sem fixar a lista guarda; os fixados não contam
+const MAX_RECENT: usize = 10;
+
+#[derive(Clone)]
+pub(crate) struct RecentEntry {
+    pub(crate) path: PathBuf,
+    pub(crate) pinned: bool,
+}
+
+/// Cenas (.deng) abertas recentemente, salvas em `recent.txt` na pasta de
+/// configuração do editor (uma linha `pin=<caminho>` ou `recent=<caminho>`, da mais nova para a mais antiga)
+#[derive(Default)]
+pub(crate) struct RecentScenes {
+    entries: Vec<RecentEntry>,
+}
+
+impl RecentScenes {
+    fn config_path() -> PathBuf {
+        preferences::config_dir().join("recent.txt")
+    }
+
+    pub(crate) fn load() -> Self {
+        let mut recent = Self::default();
+        let Ok(raw) = fs::read_to_string(Self::config_path()) else {
+            return recent;
+        };
+        for (key, value) in raw.lines().filter_map(|line| line.split_once('=')) {
+            let path = PathBuf::from(value.trim());
+            // Cena apagada ou movida desde a última abertura
+            if !path.is_file() || recent.position(&path).is_some() {
+                continue;
+            }
+            recent.entries.push(RecentEntry {
+                path,
+                pinned: key.trim() == "pin",
+            });
+        }
+        recent
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let mut out = String::new();
+        for entry in &self.entries {
+            out.push_str(if entry.pinned { "pin=" } else { "recent=" });
+            out.push_str(&entry.path.to_string_lossy());
+            out.push('\n');
+        }
+        let path = Self::config_path();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        fs::write(path, out).map_err(|e| e.to_string())
+    }
+
+    fn position(&self, path: &Path) -> Option<usize> {
+        let key = scene_key(path);
+        self.entries
+            .iter()
+            .position(|entry| scene_key(&entry.path) == key)
+    }
+
+    /// Fixados primeiro; dentro de cada grupo, do mais recente ao mais antigo
+    pub(crate) fn entries(&self) -> Vec<RecentEntry> {
+        let (mut pinned, recent): (Vec<_>, Vec<_>) =
+            self.entries.iter().cloned().partition(|entry| entry.pinned);
+        pinned.extend(recent);
+        pinned
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub(crate) fn is_pinned(&self, path: &Path) -> bool {
+        self.position(path)
+            .is_some_and(|idx| self.entries[idx].pinned)
+    }
+
+    /// Leva a cena para o topo, mantendo o estado de fixada
+    fn record(&mut self, path: &Path) {
+        let pinned = self
+            .position(path)
+            .map(|idx| self.entries.remove(idx).pinned)
+            .unwrap_or(false);
+        self.entries.insert(
+            0,
+            RecentEntry {
+                path: path.to_path_buf(),
+                pinned,
+            },
+        );
+        let mut unpinned = 0;
+        self.entries.retain(|entry| {
+            if !entry.pinned {
+                unpinned += 1;
+            }
+            entry.pinned || unpinned <= MAX_RECENT
+        });
+    }
+
+    /// Fixar uma cena que nunca foi aberta também a coloca na lista
+    fn toggle_pin(&mut self, path: &Path) {
+        match self.position(path) {
+            Some(idx) => self.entries[idx].pinned = !self.entries[idx].pinned,
+            None => self.entries.push(RecentEntry {
+                path: path.to_path_buf(),
+                pinned: true,
+            }),
+        }
+    }
+
+    fn remove(&mut self, path: &Path) {
+        if let Some(idx) = self.position(path) {
+            self.entries.remove(idx);
+        }
+    }
+
+    /// Esquece os recentes, mantendo os favoritos
+    fn clear(&mut self) {
+        self.entries.retain(|entry| entry.pinned);
+    }
+}
+
+fn tr(language: EngineLanguage, key: &'static str) -> &'static str {
+    i18n::text(language, "recent", key)
+}
+
+/// Caminho comparável da cena: resolvido no disco (sem `.`, `..` nem links) e,
+/// só no Windows, sem diferença de maiúsculas
+fn scene_key(path: &Path) -> PathBuf {
+    let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    if cfg!(windows) {
+        PathBuf::from(path.to_string_lossy().to_lowercase())
+    } else {
+        path
+    }
+}
+
+fn scene_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string_lossy().to_string())
+}
+
+impl EditorApp {
+    fn save_recent_scenes(&mut self) {
+        if let Err(err) = self.recent.save() {
+            self.log.push(
+                LogLevel::Warning,
+                "Recentes",
+                format!("Falha ao salvar cenas recentes: {err}"),
+            );
+        }
+    }
+
+    /// Chamado sempre que uma cena é criada, aberta ou salva
+    pub(crate) fn remember_recent_scene(&mut self, path: &Path) {
+        self.recent.record(path);
+        self.save_recent_scenes();
+    }
+
+    pub(crate) fn toggle_recent_pin(&mut self, path: &Path) {
+        self.recent.toggle_pin(path);
+        self.save_recent_scenes();
+    }
+
+    /// Abre uma cena da lista; se o arquivo sumiu, ela sai da lista
+    fn open_recent_scene(&mut self, path: &Path) {
+        if !path.is_file() {
+            self.log.push(
+                LogLevel::Warning,
+                "Recentes",
+                format!("Cena não encontrada: {}", path.display()),
+            );
+            self.log_enabled = true;
+            self.recent.remove(path);
+            self.save_recent_scenes();
+            return;
+        }
+        self.open_project_path(path);
+    }
+
+    /// Botão de estrela para fixar/desafixar a cena
+    pub(crate) fn draw_pin_button(&mut self, ui: &mut egui::Ui, path: &Path) {
+        let pinned = self.recent.is_pinned(path);
+        let (icon, color, hint) = if pinned {
+            ("★", theme::palette().warning, tr(self.language, "unpin"))
+        } else {
+            ("☆", theme::palette().text_weak, tr(self.language, "pin"))
+        };
+        if ui
+            .add(egui::Button::new(egui::RichText::new(icon).color(color)).frame(false))
+            .on_hover_text(hint)
+            .clicked()
+        {
+            self.toggle_recent_pin(path);
+        }
+    }
+
+    /// Submenu Arquivo → Abrir recente
+    pub(crate) fn draw_open_recent_menu(&mut self, ui: &mut egui::Ui) {
+        let lang = self.language;
+        ui.add_enabled_ui(!self.recent.is_empty(), |ui| {
+            ui.menu_button(tr(lang, "open_recent"), |ui| {
+                let mut open: Option<PathBuf> = None;
+                let entries = self.recent.entries();
+                for (idx, entry) in entries.iter().enumerate() {
+                    // Separa os fixados dos demais
+                    if idx > 0 && entries[idx - 1].pinned && !entry.pinned {
+                        ui.separator();
+                    }
+                    ui.horizontal(|ui| {
+                        self.draw_pin_button(ui, &entry.path);
+                        let current = self.current_project.as_deref() == Some(entry.path.as_path());
+                        if ui
+                            .selectable_label(current, scene_name(&entry.path))
+                            .on_hover_text(entry.path.to_string_lossy())
+                            .clicked()
+                        {
+                            open = Some(entry.path.clone());
+                        }
+                    });
+                }
+                ui.separator();
+                if ui.button(tr(lang, "clear")).clicked() {
+                    self.recent.clear();
+                    self.save_recent_scenes();
+                    ui.close();
+                }
+                if let Some(path) = open {
+                    self.open_recent_scene(&path);
+                    ui.close();
+                }
+            });
+        });
+    }
+
+    /// Lista de recentes do Hub; clique abre a cena
+    pub(crate) fn draw_hub_recent(&mut self, ui: &mut egui::Ui) {
+        ui.label(
+            egui::RichText::new("Recentes")
+                .size(13.0)
+                .color(theme::palette().text_strong),
+        );
+        ui.add_space(6.0);
+        if self.recent.is_empty() {
+            ui.label(
+                egui::RichText::new("Nenhuma cena aberta recentemente.")
+                    .size(11.0)
+                    .color(theme::palette().text_weak),
+            );
+            return;
+        }
+        let mut open: Option<PathBuf> = None;
+        for entry in self.recent.entries() {
+            ui.horizontal(|ui| {
+                self.draw_pin_button(ui, &entry.path);
+                if ui
+                    .add(
+                        egui::Label::new(
+                            egui::RichText::new(scene_name(&entry.path))
+                                .size(12.0)
+                                .color(theme::palette().text),
+                        )
+                        .sense(egui::Sense::click())
+                        .truncate(),
+                    )
+                    .on_hover_text(entry.path.to_string_lossy())
+                    .clicked()
+                {
+                    open = Some(entry.path.clone());
+                }
+            });
+        }
+        if let Some(path) = open {
+            self.open_recent_scene(&path);
+        }
+    }
+}
@@ -0,0 +1,488 @@
+use crate::EngineLanguage;
+use crate::i18n;
+use eframe::egui;
+use engine_render::{AssetSearchIndex, AssetType};
+use std::ops::RangeInclusive;
+
+/// Enum editável por índice, mostrado como combo no inspetor
+pub(crate) trait ReflectEnum {
+    fn variants(&self) -> Vec<&'static str>;
+    fn index(&self) -> usize;
+    fn set_index(&mut self, index: usize);
+}
+
+/// Valor opcional ligado/desligado por uma caixa (ex.: névoa própria da
+/// câmera); ligado, os campos dele aparecem logo abaixo
+pub(crate) trait ReflectOption {
+    fn is_set(&self) -> bool;
+    fn set(&mut self, on: bool);
+    fn value(&mut self) -> Option<&mut dyn Reflect>;
+}
+
+impl<T: Default + Reflect> ReflectOption for Option<T> {
+    fn is_set(&self) -> bool {
+        self.is_some()
+    }
+
+    fn set(&mut self, on: bool) {
+        if on != self.is_some() {
+            *self = on.then(T::default);
+        }
+    }
+
+    fn value(&mut self) -> Option<&mut dyn Reflect> {
+        self.as_mut().map(|v| v as &mut dyn Reflect)
+    }
+}
+
+/// Lista de itens com os mesmos campos (ex.: níveis do LOD), com botões de
+/// adicionar e remover
+pub(crate) trait ReflectList {
+    fn len(&self) -> usize;
+    fn item_label(&self, index: usize) -> String;
+    fn item_fields(&mut self, index: usize) -> Vec<Field<'_>>;
+    fn can_remove(&self, index: usize) -> bool;
+    fn remove(&mut self, index: usize);
+    /// Texto do botão de adicionar; None quando a lista está cheia
+    fn add_label(&self) -> Option<&'static str>;
+    fn add(&mut self);
+}
+
+/// Editor gerado para um campo
+pub(crate) enum FieldKind<'a> {
+    Float {
+        value: &'a mut f32,
+        speed: f64,
+        range: RangeInclusive<f32>,
+    },
+    Slider {
+        value: &'a mut f32,
+        range: RangeInclusive<f32>,
+        logarithmic: bool,
+    },
+    Int {
+        value: &'a mut i32,
+        speed: f64,
+    },
+    /// Um valor por eixo, lado a lado
+    Vector {
+        values: &'a mut [f32],
+        axes: &'static [&'static str],
+        speed: f64,
+        range: RangeInclusive<f32>,
+    },
+    Bool(&'a mut bool),
+    /// Bool mostrado como duas opções lado a lado
+    Toggle {
+        value: &'a mut bool,
+        off: &'static str,
+        on: &'static str,
+    },
+    Color(&'a mut [f32; 3]),
+    Enum(&'a mut dyn ReflectEnum),
+    Optional {
+        value: &'a mut dyn ReflectOption,
+        text: &'static str,
+    },
+    Text {
+        value: &'a mut String,
+        hint: &'static str,
+    },
+    /// Texto fixo, só para leitura
+    Label(&'a str),
+    /// Caminho de arquivo, com seletor filtrado pelas extensões
+    Path {
+        value: &'a mut String,
+        hint: &'static str,
+        extensions: &'static [&'static str],
+    },
+    /// Caminho de asset do projeto: como `Path`, mais a busca no índice
+    Asset {
+        value: &'a mut String,
+        hint: &'static str,
+        extensions: &'static [&'static str],
+        asset_type: AssetType,
+    },
+}
+
+pub(crate) struct Field<'a> {
+    pub(crate) label: &'static str,
+    pub(crate) kind: FieldKind<'a>,
+    pub(crate) tooltip: Option<&'static str>,
+    pub(crate) enabled: bool,
+}
+
+impl<'a> Field<'a> {
+    pub(crate) fn new(label: &'static str, kind: FieldKind<'a>) -> Self {
+        Self {
+            label,
+            kind,
+            tooltip: None,
+            enabled: true,
+        }
+    }
+
+    pub(crate) fn float(
+        label: &'static str,
+        value: &'a mut f32,
+        speed: f64,
+        range: RangeInclusive<f32>,
+    ) -> Self {
+        Self::new(
+            label,
+            FieldKind::Float {
+                value,
+                speed,
+                range,
+            },
+        )
+    }
+
+    pub(crate) fn slider(
+        label: &'static str,
+        value: &'a mut f32,
+        range: RangeInclusive<f32>,
+    ) -> Self {
+        Self::new(
+            label,
+            FieldKind::Slider {
+                value,
+                range,
+                logarithmic: false,
+            },
+        )
+    }
+
+    pub(crate) fn log_slider(
+        label: &'static str,
+        value: &'a mut f32,
+        range: RangeInclusive<f32>,
+    ) -> Self {
+        Self::new(
+            label,
+            FieldKind::Slider {
+                value,
+                range,
+                logarithmic: true,
+            },
+        )
+    }
+
+    pub(crate) fn vector(
+        label: &'static str,
+        values: &'a mut [f32],
+        axes: &'static [&'static str],
+        speed: f64,
+        range: RangeInclusive<f32>,
+    ) -> Self {
+        Self::new(
+            label,
+            FieldKind::Vector {
+                values,
+                axes,
+                speed,
+                range,
+            },
+        )
+    }
+
+    pub(crate) fn with_tooltip(mut self, tooltip: &'static str) -> Self {
+        self.tooltip = Some(tooltip);
+        self
+    }
+
+    /// Campo desabilitado (mas visível) enquanto `enabled` for falso
+    pub(crate) fn enabled_if(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+}
+
+/// Componente que expõe seus campos; o inspetor monta a interface a partir
+/// deles. Os campos podem depender do estado (ex.: a forma do collider)
+pub(crate) trait Reflect {
+    fn fields(&mut self) -> Vec<Field<'_>>;
+
+    /// Itens repetidos mostrados depois dos campos
+    fn list(&mut self) -> Option<&mut dyn ReflectList> {
+        None
+    }
+
+    /// Seção do i18n onde os rótulos são chaves; None = rótulos já prontos
+    fn label_section(&self) -> Option<&'static str> {
+        None
+    }
+}
+
+/// O que os editores gerados precisam além do próprio componente
+pub(crate) struct ReflectContext<'a> {
+    language: EngineLanguage,
+    // Índice e texto da busca dos campos `Asset`; sem eles só há o seletor de arquivo
+    assets: Option<(&'a AssetSearchIndex, &'a mut String)>,
+}
+
+impl<'a> ReflectContext<'a> {
+    pub(crate) fn new(language: EngineLanguage) -> Self {
+        Self {
+            language,
+            assets: None,
+        }
+    }
+
+    pub(crate) fn with_assets(
+        mut self,
+        index: &'a AssetSearchIndex,
+        query: &'a mut String,
+    ) -> Self {
+        self.assets = Some((index, query));
+        self
+    }
+}
+
+/// Desenha os campos de `component` em duas colunas (nome, editor); true se
+/// algum valor mudou
+pub(crate) fn draw_fields(
+    ui: &mut egui::Ui,
+    id_salt: &str,
+    component: &mut dyn Reflect,
+    ctx: &mut ReflectContext<'_>,
+) -> bool {
+    let section = component.label_section();
+    let language = ctx.language;
+    let label = |key: &'static str| match section {
+        Some(section) => i18n::text(language, section, key),
+        None => key,
+    };
+    let mut changed = false;
+    egui::Grid::new((id_salt, "reflect_grid"))
+        .num_columns(2)
+        .spacing([10.0, 8.0])
+        .show(ui, |ui| {
+            for field in component.fields() {
+                changed |= draw_row(ui, id_salt, label(field.label), field, ctx);
+            }
+        });
+    if let Some(list) = component.list() {
+        changed |= draw_list(ui, id_salt, list, ctx);
+    }
+    changed
+}
+
+fn draw_row(
+    ui: &mut egui::Ui,
+    id_salt: &str,
+    label: &str,
+    field: Field<'_>,
+    ctx: &mut ReflectContext<'_>,
+) -> bool {
+    if label.is_empty() {
+        ui.label("");
+    } else {
+        ui.label(format!("{label}:"));
+    }
+    let mut nested = None;
+    let resp = ui
+        .add_enabled_ui(field.enabled, |ui| {
+            draw_field(ui, id_salt, field.label, field.kind, &mut nested, ctx)
+        })
+        .inner;
+    let mut changed = resp.changed();
+    if let Some(tooltip) = field.tooltip {
+        resp.on_hover_text(tooltip);
+    }
+    ui.end_row();
+    if let Some(inner) = nested {
+        for field in inner.fields() {
+            changed |= draw_row(ui, id_salt, field.label, field, ctx);
+        }
+    }
+    changed
+}
+
+fn draw_list(
+    ui: &mut egui::Ui,
+    id_salt: &str,
+    list: &mut dyn ReflectList,
+    ctx: &mut ReflectContext<'_>,
+) -> bool {
+    let mut changed = false;
+    let mut remove = None;
+    egui::Grid::new((id_salt, "reflect_list"))
+        .num_columns(2)
+        .spacing([10.0, 8.0])
+        .show(ui, |ui| {
+            for index in 0..list.len() {
+                ui.label(format!("{}:", list.item_label(index)));
+                if list.can_remove(index) && ui.small_button("−").clicked() {
+                    remove = Some(index);
+                }
+                ui.end_row();
+                for field in list.item_fields(index) {
+                    let item_id = format!("{id_salt}_{index}");
+                    changed |= draw_row(ui, &item_id, field.label, field, ctx);
+                }
+            }
+        });
+    if let Some(index) = remove {
+        list.remove(index);
+        changed = true;
+    }
+    let add = list
+        .add_label()
+        .is_some_and(|text| ui.button(text).clicked());
+    if add {
+        list.add();
+        changed = true;
+    }
+    changed
+}
+
+/// `nested` recebe os campos de um `Optional` ligado, desenhados pelo chamador
+fn draw_field<'a>(
+    ui: &mut egui::Ui,
+    id_salt: &str,
+    label: &'static str,
+    kind: FieldKind<'a>,
+    nested: &mut Option<&'a mut dyn Reflect>,
+    ctx: &mut ReflectContext<'_>,
+) -> egui::Response {
+    match kind {
+        FieldKind::Float {
+            value,
+            speed,
+            range,
+        } => ui.add(egui::DragValue::new(value).speed(speed).range(range)),
+        FieldKind::Slider {
+            value,
+            range,
+            logarithmic,
+        } => ui.add(egui::Slider::new(value, range).logarithmic(logarithmic)),
+        FieldKind::Int { value, speed } => ui.add(egui::DragValue::new(value).speed(speed)),
+        FieldKind::Vector {
+            values,
+            axes,
+            speed,
+            range,
+        } => {
+            let inner = ui.horizontal(|ui| {
+                let mut changed = false;
+                for (axis, value) in axes.iter().zip(values.iter_mut()) {
+                    changed |= ui
+                        .add(
+                            egui::DragValue::new(value)
+                                .speed(speed)
+                                .range(range.clone())
+                                .prefix(format!("{axis} ")),
+                        )
+                        .changed();
+                }
+                changed
+            });
+            let mut resp = inner.response;
+            if inner.inner {
+                resp.mark_changed();
+            }
+            resp
+        }
+        FieldKind::Bool(value) => ui.checkbox(value, ""),
+        FieldKind::Toggle { value, off, on } => {
+            let inner = ui.horizontal(|ui| {
+                let before = *value;
+                ui.selectable_value(value, false, off);
+                ui.selectable_value(value, true, on);
+                before != *value
+            });
+            let mut resp = inner.response;
+            if inner.inner {
+                resp.mark_changed();
+            }
+            resp
+        }
+        FieldKind::Color(value) => ui.color_edit_button_rgb(value),
+        FieldKind::Enum(value) => {
+            let variants = value.variants();
+            let selected = value.index();
+            let inner = egui::ComboBox::from_id_salt((id_salt, label))
+                .selected_text(variants.get(selected).copied().unwrap_or_default())
+                .show_ui(ui, |ui| {
+                    for (index, variant) in variants.iter().enumerate() {
+                        if ui.selectable_label(index == selected, *variant).clicked() {
+                            value.set_index(index);
+                        }
+                    }
+                });
+            let mut resp = inner.response;
+            if value.index() != selected {
+                resp.mark_changed();
+            }
+            resp
+        }
+        FieldKind::Optional { value, text } => {
+            let mut on = value.is_set();
+            let resp = ui.checkbox(&mut on, text);
+            if resp.changed() {
+                value.set(on);
+            }
+            *nested = value.value();
+            resp
+        }
+        FieldKind::Text { value, hint } => {
+            ui.add(egui::TextEdit::singleline(value).hint_text(hint))
+        }
+        FieldKind::Label(text) => ui.label(egui::RichText::new(text).weak()),
+        FieldKind::Path {
+            value,
+            hint,
+            extensions,
+        } => path_field(ui, label, value, hint, extensions, |_| None),
+        FieldKind::Asset {
+            value,
+            hint,
+            extensions,
+            asset_type,
+        } => path_field(ui, label, value, hint, extensions, |ui| {
+            let (index, query) = ctx.assets.as_mut()?;
+            crate::inspector::asset_picker_button(ui, (id_salt, label), index, query, asset_type)
+        }),
+    }
+}
+
+/// Texto do caminho, seletor de arquivo e, opcionalmente, outro seletor
+/// (`extra`) que devolve o caminho escolhido
+fn path_field(
+    ui: &mut egui::Ui,
+    label: &'static str,
+    value: &mut String,
+    hint: &'static str,
+    extensions: &'static [&'static str],
+    mut extra: impl FnMut(&mut egui::Ui) -> Option<String>,
+) -> egui::Response {
+    let inner = ui.horizontal(|ui| {
+        let mut changed = ui
+            .add(
+                egui::TextEdit::singleline(value)
+                    .hint_text(hint)
+                    .desired_width(110.0),
+            )
+            .changed();
+        if ui.button("...").clicked() {
+            if let Some(picked) = rfd::FileDialog::new()
+                .add_filter(label, extensions)
+                .pick_file()
+            {
+                *value = picked.to_string_lossy().to_string();
+                changed = true;
+            }
+        }
+        if let Some(picked) = extra(ui) {
+            *value = picked;
+            changed = true;
+        }
+        changed
+    });
+    let mut resp = inner.response;
+    if inner.inner {
+        resp.mark_changed();
+    }
+    resp
+}
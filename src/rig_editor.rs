@@ -0,0 +1,693 @@
+use super::*;
+use engine_render::{AssetManager, BoneConstraint, Rig};
+use glam::{Mat4, Vec3};
+
+const HEADER_HEIGHT: f32 = 26.0;
+const BONE_LIST_WIDTH: f32 = 230.0;
+const INDENT: f32 = 12.0;
+
+fn tr(language: EngineLanguage, key: &'static str) -> &'static str {
+    i18n::text(language, "rig_editor", key)
+}
+
+/// Editor de esqueleto do painel Rig: hierarquia de ossos do modelo
+/// importado, nomes, pais e restrições, salvo em `.rig`
+pub(crate) struct RigEditor {
+    rig: Option<Rig>,
+    path: Option<PathBuf>,
+    // Alterado desde o último Salvar
+    modified: bool,
+    // Objeto da viewport sobre o qual os ossos são desenhados
+    object: Option<String>,
+    selected: Option<usize>,
+    // Nome em edição do osso selecionado, aplicado ao sair do campo
+    name_edit: String,
+    show_bones: bool,
+    // Esqueleto sendo lido do modelo em segundo plano, para o objeto
+    loading: Option<(String, Receiver<Result<Rig, String>>)>,
+    panel_rect: Option<egui::Rect>,
+}
+
+impl Default for RigEditor {
+    fn default() -> Self {
+        Self {
+            rig: None,
+            path: None,
+            modified: false,
+            object: None,
+            selected: None,
+            name_edit: String::new(),
+            show_bones: true,
+            loading: None,
+            panel_rect: None,
+        }
+    }
+}
+
+impl RigEditor {
+    pub(crate) fn contains_point(&self, pos: egui::Pos2) -> bool {
+        self.panel_rect.is_some_and(|rect| rect.contains(pos))
+    }
+
+    pub(crate) fn clear_panel_rect(&mut self) {
+        self.panel_rect = None;
+    }
+
+    fn select(&mut self, index: Option<usize>) {
+        self.selected = index;
+        self.name_edit = index
+            .and_then(|i| self.rig.as_ref()?.bone(i))
+            .map(|bone| bone.name.clone())
+            .unwrap_or_default();
+    }
+
+    fn set_rig(&mut self, rig: Rig, path: Option<PathBuf>) {
+        self.rig = Some(rig);
+        self.path = path;
+        self.modified = false;
+        self.select(None);
+    }
+
+    /// Grava no arquivo aberto ou pergunta onde, em `Assets/Rigs`
+    fn save(&mut self) -> Result<Option<PathBuf>, String> {
+        let Some(rig) = &self.rig else {
+            return Ok(None);
+        };
+        let path = match &self.path {
+            Some(path) => path.clone(),
+            None => {
+                let dir = Path::new("Assets").join("Rigs");
+                let _ = fs::create_dir_all(&dir);
+                let name = rig
+                    .source
+                    .as_deref()
+                    .and_then(Path::file_stem)
+                    .map(|stem| format!("{}.rig", stem.to_string_lossy()))
+                    .unwrap_or_else(|| "NovoRig.rig".to_string());
+                let Some(path) = rfd::FileDialog::new()
+                    .add_filter("Rig", &["rig"])
+                    .set_directory(&dir)
+                    .set_file_name(name)
+                    .save_file()
+                else {
+                    return Ok(None);
+                };
+                path
+            }
+        };
+        rig.save(&path)?;
+        self.path = Some(path.clone());
+        self.modified = false;
+        Ok(Some(path))
+    }
+
+    /// Posição em mundo de cada osso no bind, sobre o transform de `object_matrix`:
+    /// o objeto é a malha com skin, então o bind da malha vira o transform dele
+    fn bone_heads(&self, object_matrix: Mat4) -> Vec<Vec3> {
+        let Some(rig) = &self.rig else {
+            return Vec::new();
+        };
+        let to_world = object_matrix * rig.mesh_bind.inverse();
+        rig.bones()
+            .iter()
+            .map(|bone| to_world.transform_point3(bone.bind.w_axis.truncate()))
+            .collect()
+    }
+}
+
+/// Escreve `rig=<caminho>` nos módulos de animação gerados para o modelo de
+/// origem, que passam a usar o rig; retorna quantos foram atualizados
+fn link_animation_modules(rig_path: &Path, source: &Path) -> usize {
+    let names: Vec<String> = [source.file_name(), source.file_stem()]
+        .into_iter()
+        .flatten()
+        .map(|name| name.to_string_lossy().to_ascii_lowercase())
+        .collect();
+    let rig_line = format!("rig={}", rig_path.to_string_lossy().replace('\\', "/"));
+    let dir = Path::new("Assets").join("Animations").join("Modules");
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    let mut linked = 0;
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if !path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("animodule"))
+        {
+            continue;
+        }
+        let Ok(text) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let from_source = text.lines().any(|line| {
+            line.trim()
+                .strip_prefix("source_fbx=")
+                .is_some_and(|name| names.contains(&name.trim().to_ascii_lowercase()))
+        });
+        if !from_source {
+            continue;
+        }
+        let mut out: String = text
+            .lines()
+            .filter(|line| !line.trim().starts_with("rig="))
+            .map(|line| format!("{line}\n"))
+            .collect();
+        out.push_str(&rig_line);
+        out.push('\n');
+        if out != text && fs::write(&path, out).is_ok() {
+            linked += 1;
+        }
+    }
+    linked
+}
+
+impl EditorApp {
+    /// Recebe o esqueleto lido em segundo plano e manda os ossos para a viewport
+    pub(crate) fn update_rig_editor(&mut self) {
+        if let Some((object, rx)) = &self.rig_editor.loading {
+            match rx.try_recv() {
+                Ok(result) => {
+                    let object = object.clone();
+                    self.rig_editor.loading = None;
+                    match result {
+                        Ok(rig) => {
+                            self.rig_editor.set_rig(rig, None);
+                            self.rig_editor.modified = true;
+                            self.rig_editor.object = Some(object);
+                        }
+                        Err(e) => {
+                            self.log
+                                .push(LogLevel::Error, "Rig", format!("{object}: {e}"));
+                            self.log_enabled = true;
+                        }
+                    }
+                }
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Disconnected) => self.rig_editor.loading = None,
+            }
+        }
+
+        let object_matrix = self
+            .rig_editor
+            .object
+            .as_deref()
+            .and_then(|object| self.viewport.object_matrix(object));
+        let (Some(rig), Some(object_matrix), true, true) = (
+            &self.rig_editor.rig,
+            object_matrix,
+            self.rig_enabled,
+            self.rig_editor.show_bones,
+        ) else {
+            self.viewport.set_rig_overlay(Vec::new());
+            return;
+        };
+        let heads = self.rig_editor.bone_heads(object_matrix);
+        let bones = rig
+            .bones()
+            .iter()
+            .enumerate()
+            .map(|(index, bone)| {
+                let head = heads[index];
+                let children = rig.children(index);
+                let tail = match (children.first(), bone.parent) {
+                    (Some(&child), _) => heads[child],
+                    // Ponta: continua a direção do pai com um terço do comprimento dele
+                    (None, Some(parent)) => head + (head - heads[parent]) * 0.35,
+                    (None, None) => head,
+                };
+                (head, tail, self.rig_editor.selected == Some(index))
+            })
+            .collect();
+        self.viewport.set_rig_overlay(bones);
+    }
+
+    /// `.rig` arrastado do Projeto para o painel ou escolhido em Abrir
+    pub(crate) fn open_rig(&mut self, path: &Path) {
+        if !path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("rig"))
+        {
+            return;
+        }
+        match Rig::load(path) {
+            Ok(rig) => {
+                self.rig_editor.set_rig(rig, Some(path.to_path_buf()));
+                if self.rig_editor.object.is_none() {
+                    let selected = self.hierarchy.selected_object_name().to_string();
+                    if self.viewport.object_matrix(&selected).is_some() {
+                        self.rig_editor.object = Some(selected);
+                    }
+                }
+            }
+            Err(e) => {
+                self.log
+                    .push(LogLevel::Error, "Rig", format!("{}: {e}", path.display()));
+                self.log_enabled = true;
+            }
+        }
+    }
+
+    /// Lê em segundo plano o esqueleto do modelo de onde veio o objeto selecionado
+    fn build_rig_from_selection(&mut self) {
+        let object = self.hierarchy.selected_object_name().to_string();
+        let Some(source) = self.viewport.object_source(&object) else {
+            self.log.push(
+                LogLevel::Error,
+                "Rig",
+                format!("{object}: objeto sem modelo importado"),
+            );
+            self.log_enabled = true;
+            return;
+        };
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let is_fbx = source
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("fbx"));
+            // Só o importador FBX lê skins; glTF e OBJ chegam sem esqueleto
+            if !is_fbx {
+                let _ = tx.send(Err("Modelo sem esqueleto".to_string()));
+                return;
+            }
+            let mut assets = AssetManager::new();
+            let scene = assets.import_fbx(&source);
+            let _ = tx.send(scene.and_then(|scene| Rig::from_scene(&scene, &assets)));
+        });
+        self.rig_editor.loading = Some((object, rx));
+    }
+
+    /// Painel Rig no espaço que o layout de painéis deu a ele
+    pub(crate) fn draw_rig_panel(&mut self, ctx: &egui::Context, panel: egui::Rect) {
+        engine_core::profile_function!();
+        self.rig_editor.panel_rect = Some(panel);
+        if self.rig_editor.loading.is_some() {
+            ctx.request_repaint();
+        }
+        egui::Area::new(egui::Id::new("rig_panel"))
+            .order(egui::Order::Foreground)
+            .fixed_pos(panel.min)
+            .show(ctx, |ui| {
+                let (rect, _) = ui.allocate_exact_size(panel.size(), egui::Sense::hover());
+                ui.painter()
+                    .rect_filled(rect, 0.0, theme::palette().panel_bg);
+                ui.painter().rect_stroke(
+                    rect,
+                    0.0,
+                    egui::Stroke::new(1.0, theme::palette().border),
+                    egui::StrokeKind::Outside,
+                );
+                let inner = rect.shrink2(egui::vec2(8.0, 4.0));
+                let header =
+                    egui::Rect::from_min_size(inner.min, egui::vec2(inner.width(), HEADER_HEIGHT));
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(header)
+                        .layout(egui::Layout::left_to_right(egui::Align::Center)),
+                    |ui| self.draw_rig_header(ui),
+                );
+                let body = egui::Rect::from_min_max(
+                    egui::pos2(inner.left(), header.bottom() + 4.0),
+                    inner.max,
+                );
+                if self.rig_editor.rig.is_none() {
+                    ui.scope_builder(egui::UiBuilder::new().max_rect(body), |ui| {
+                        ui.label(egui::RichText::new(tr(self.language, "empty")).weak());
+                    });
+                    return;
+                }
+                let list = egui::Rect::from_min_size(
+                    body.min,
+                    egui::vec2(BONE_LIST_WIDTH.min(body.width() * 0.5), body.height()),
+                );
+                let details =
+                    egui::Rect::from_min_max(egui::pos2(list.right() + 8.0, body.top()), body.max);
+                ui.painter().vline(
+                    list.right() + 4.0,
+                    body.y_range(),
+                    egui::Stroke::new(1.0, theme::palette().border),
+                );
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(list)
+                        .layout(egui::Layout::top_down(egui::Align::Min)),
+                    |ui| self.draw_rig_bone_list(ui),
+                );
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(details)
+                        .layout(egui::Layout::top_down(egui::Align::Min)),
+                    |ui| self.draw_rig_bone_details(ui),
+                );
+            });
+    }
+
+    fn draw_rig_header(&mut self, ui: &mut egui::Ui) {
+        let language = self.language;
+        let selected = self.hierarchy.selected_object_name().to_string();
+        let has_source = self.viewport.object_source(&selected).is_some();
+        let loading = self.rig_editor.loading.is_some();
+        if ui
+            .add_enabled(
+                has_source && !loading,
+                egui::Button::new(tr(language, "from_object")),
+            )
+            .on_hover_text(tr(language, "from_object_help"))
+            .clicked()
+        {
+            self.build_rig_from_selection();
+        }
+        if ui.button(tr(language, "open")).clicked() {
+            if let Some(path) = rfd::FileDialog::new()
+                .add_filter("Rig", &["rig"])
+                .set_directory(Path::new("Assets").join("Rigs"))
+                .pick_file()
+            {
+                self.open_rig(&path);
+            }
+        }
+        if ui
+            .add_enabled(
+                self.rig_editor.rig.is_some(),
+                egui::Button::new(tr(language, "save")),
+            )
+            .clicked()
+        {
+            match self.rig_editor.save() {
+                Ok(Some(path)) => {
+                    let linked = self
+                        .rig_editor
+                        .rig
+                        .as_ref()
+                        .and_then(|rig| rig.source.as_deref())
+                        .map_or(0, |source| link_animation_modules(&path, source));
+                    self.log.push(
+                        LogLevel::Info,
+                        "Rig",
+                        format!(
+                            "Rig salvo em {} ({linked} módulos de animação vinculados)",
+                            path.display()
+                        ),
+                    );
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    self.log
+                        .push(LogLevel::Error, "Rig", format!("Falha ao salvar: {e}"));
+                    self.log_enabled = true;
+                }
+            }
+        }
+        ui.separator();
+        ui.checkbox(&mut self.rig_editor.show_bones, tr(language, "show_bones"));
+        let object = self
+            .rig_editor
+            .object
+            .clone()
+            .unwrap_or_else(|| tr(language, "no_object").to_string());
+        ui.label(egui::RichText::new(format!("⛓ {object}")).small());
+        if ui
+            .add_enabled(
+                self.viewport.object_matrix(&selected).is_some()
+                    && self.rig_editor.object.as_deref() != Some(selected.as_str()),
+                egui::Button::new(tr(language, "attach")).small(),
+            )
+            .on_hover_text(tr(language, "attach_help"))
+            .clicked()
+        {
+            self.rig_editor.object = Some(selected);
+        }
+        ui.separator();
+        if loading {
+            ui.spinner();
+            ui.label(egui::RichText::new(tr(language, "loading")).small().weak());
+        } else if self.rig_editor.rig.is_some() {
+            let name = self
+                .rig_editor
+                .path
+                .as_deref()
+                .and_then(Path::file_name)
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| tr(language, "untitled").to_string());
+            let modified = if self.rig_editor.modified { " *" } else { "" };
+            ui.label(
+                egui::RichText::new(format!("{name}{modified}"))
+                    .small()
+                    .weak(),
+            );
+        }
+    }
+
+    /// Árvore de ossos, pais antes dos filhos
+    fn draw_rig_bone_list(&mut self, ui: &mut egui::Ui) {
+        let Some(rig) = &self.rig_editor.rig else {
+            return;
+        };
+        let mut rows = Vec::new();
+        let mut stack: Vec<(usize, usize)> =
+            rig.roots().into_iter().rev().map(|i| (i, 0)).collect();
+        while let Some((index, depth)) = stack.pop() {
+            rows.push((index, depth));
+            stack.extend(
+                rig.children(index)
+                    .into_iter()
+                    .rev()
+                    .map(|c| (c, depth + 1)),
+            );
+        }
+        let mut clicked = None;
+        egui::ScrollArea::vertical()
+            .id_salt("rig_bones")
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                for (index, depth) in rows {
+                    let bone = &rig.bones()[index];
+                    ui.horizontal(|ui| {
+                        ui.add_space(depth as f32 * INDENT);
+                        let mut text = egui::RichText::new(&bone.name);
+                        if !bone.constraints.is_empty() {
+                            text = text.color(egui::Color32::from_rgb(230, 190, 90));
+                        }
+                        if ui
+                            .selectable_label(self.rig_editor.selected == Some(index), text)
+                            .clicked()
+                        {
+                            clicked = Some(index);
+                        }
+                    });
+                }
+            });
+        if let Some(index) = clicked {
+            self.rig_editor.select(Some(index));
+        }
+    }
+
+    fn draw_rig_bone_details(&mut self, ui: &mut egui::Ui) {
+        let language = self.language;
+        let Some(index) = self.rig_editor.selected else {
+            ui.label(egui::RichText::new(tr(language, "select_bone")).weak());
+            return;
+        };
+        let Some(rig) = self.rig_editor.rig.as_mut() else {
+            return;
+        };
+        let Some(bone) = rig.bone(index).cloned() else {
+            return;
+        };
+        let names: Vec<String> = rig.bones().iter().map(|b| b.name.clone()).collect();
+        let mut changed = false;
+        let mut error = None;
+
+        egui::ScrollArea::vertical()
+            .id_salt("rig_bone_details")
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                egui::Grid::new("rig_bone_fields")
+                    .num_columns(2)
+                    .spacing([8.0, 4.0])
+                    .show(ui, |ui| {
+                        ui.label(tr(language, "name"));
+                        let response = ui.add(
+                            egui::TextEdit::singleline(&mut self.rig_editor.name_edit)
+                                .desired_width(180.0),
+                        );
+                        if response.lost_focus() && self.rig_editor.name_edit != bone.name {
+                            match rig.rename(index, &self.rig_editor.name_edit) {
+                                Ok(()) => changed = true,
+                                Err(e) => {
+                                    error = Some(e);
+                                    self.rig_editor.name_edit = bone.name.clone();
+                                }
+                            }
+                        }
+                        ui.end_row();
+
+                        ui.label(tr(language, "source_name"));
+                        ui.label(egui::RichText::new(&bone.source_name).weak());
+                        ui.end_row();
+
+                        ui.label(tr(language, "parent"));
+                        let current = bone
+                            .parent
+                            .and_then(|p| names.get(p).cloned())
+                            .unwrap_or_else(|| tr(language, "no_parent").to_string());
+                        egui::ComboBox::from_id_salt("rig_bone_parent")
+                            .selected_text(current)
+                            .width(180.0)
+                            .show_ui(ui, |ui| {
+                                if ui
+                                    .selectable_label(
+                                        bone.parent.is_none(),
+                                        tr(language, "no_parent"),
+                                    )
+                                    .clicked()
+                                    && bone.parent.is_some()
+                                {
+                                    match rig.set_parent(index, None) {
+                                        Ok(()) => changed = true,
+                                        Err(e) => error = Some(e),
+                                    }
+                                }
+                                for (candidate, name) in names.iter().enumerate() {
+                                    // Nem o próprio osso nem os descendentes dele
+                                    if rig.is_descendant(candidate, index) {
+                                        continue;
+                                    }
+                                    if ui
+                                        .selectable_label(bone.parent == Some(candidate), name)
+                                        .clicked()
+                                        && bone.parent != Some(candidate)
+                                    {
+                                        match rig.set_parent(index, Some(candidate)) {
+                                            Ok(()) => changed = true,
+                                            Err(e) => error = Some(e),
+                                        }
+                                    }
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label(tr(language, "bind_position"));
+                        let position = bone.bind.w_axis.truncate();
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "{:.3}  {:.3}  {:.3}",
+                                position.x, position.y, position.z
+                            ))
+                            .monospace()
+                            .weak(),
+                        );
+                        ui.end_row();
+                    });
+
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new(tr(language, "constraints")).strong());
+                    ui.menu_button(tr(language, "add_constraint"), |ui| {
+                        let new = if ui.button(tr(language, "rotation_limit")).clicked() {
+                            Some(BoneConstraint::RotationLimit {
+                                min: Vec3::splat(-45.0),
+                                max: Vec3::splat(45.0),
+                            })
+                        } else if ui.button(tr(language, "copy_rotation")).clicked() {
+                            Some(BoneConstraint::CopyRotation {
+                                source: bone.parent.unwrap_or(0),
+                                weight: 1.0,
+                            })
+                        } else {
+                            None
+                        };
+                        if let Some(constraint) = new {
+                            if let Some(constraints) = rig.constraints_mut(index) {
+                                constraints.push(constraint);
+                                changed = true;
+                            }
+                            ui.close();
+                        }
+                    });
+                });
+
+                let Some(constraints) = rig.constraints_mut(index) else {
+                    return;
+                };
+                let mut remove = None;
+                for (slot, constraint) in constraints.iter_mut().enumerate() {
+                    ui.push_id(("rig_constraint", slot), |ui| {
+                        egui::Frame::group(ui.style()).show(ui, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.label(
+                                    egui::RichText::new(tr(language, constraint.id())).strong(),
+                                );
+                                ui.with_layout(
+                                    egui::Layout::right_to_left(egui::Align::Center),
+                                    |ui| {
+                                        if ui.small_button("✖").clicked() {
+                                            remove = Some(slot);
+                                        }
+                                    },
+                                );
+                            });
+                            match constraint {
+                                BoneConstraint::RotationLimit { min, max } => {
+                                    for (label, value) in
+                                        [(tr(language, "min"), min), (tr(language, "max"), max)]
+                                    {
+                                        ui.horizontal(|ui| {
+                                            ui.label(label);
+                                            for axis in 0..3 {
+                                                changed |= ui
+                                                    .add(
+                                                        egui::DragValue::new(&mut value[axis])
+                                                            .speed(1.0)
+                                                            .range(-180.0..=180.0)
+                                                            .suffix("°"),
+                                                    )
+                                                    .changed();
+                                            }
+                                        });
+                                    }
+                                }
+                                BoneConstraint::CopyRotation { source, weight } => {
+                                    ui.horizontal(|ui| {
+                                        ui.label(tr(language, "source"));
+                                        egui::ComboBox::from_id_salt("rig_copy_source")
+                                            .selected_text(
+                                                names.get(*source).cloned().unwrap_or_default(),
+                                            )
+                                            .width(160.0)
+                                            .show_ui(ui, |ui| {
+                                                for (candidate, name) in names.iter().enumerate() {
+                                                    if candidate != index {
+                                                        changed |= ui
+                                                            .selectable_value(
+                                                                source, candidate, name,
+                                                            )
+                                                            .changed();
+                                                    }
+                                                }
+                                            });
+                                    });
+                                    ui.horizontal(|ui| {
+                                        ui.label(tr(language, "weight"));
+                                        changed |=
+                                            ui.add(egui::Slider::new(weight, 0.0..=1.0)).changed();
+                                    });
+                                }
+                            }
+                        });
+                    });
+                }
+                if let Some(slot) = remove {
+                    constraints.remove(slot);
+                    changed = true;
+                }
+            });
+
+        if changed {
+            self.rig_editor.modified = true;
+        }
+        if let Some(e) = error {
+            self.log.push(LogLevel::Error, "Rig", e);
+            self.log_enabled = true;
+        }
+    }
+}
@@ -0,0 +1,744 @@
+use super::*;
+use crate::hierarchy::SceneRequest;
+use engine_core::{
+    Camera, DirectionalLight, EngineWorld, EntityRef, MeshAsset, Name, Parent, PointLight,
+    SceneHandle, ScriptComponent, SpotLight, Sprite, SpriteTexture, Text3D, TextFont, Transform,
+    UiText, WorldSnapshot, read_scene_with, write_scene_with,
+};
+use engine_physics::{Collider, PhysicsSceneComponents, RigidBody};
+use glam::{Mat4, Vec3};
+use inspector::{LightDraft, LightType, ObjectComponents};
+use physics::{baked_collider, to_rigid_body};
+
+/// Extensão dos arquivos de cena
+pub(crate) const SCENE_EXTENSION: &str = "dscene";
+
+/// Componentes do Inspetor que ainda não têm um equivalente no engine; vão
+/// junto nos snapshots do mundo para o Stop devolvê-los como estavam
+#[derive(Clone)]
+pub(crate) struct EditorComponents(pub(crate) ObjectComponents);
+
+/// Espelho ECS da cena editada: um entity por objeto da hierarquia e as cenas
+/// aditivas como `SceneHandle`. É sincronizado com os painéis quando alguém
+/// precisa dele (salvar, carregar aditiva, Play, build), não a cada frame
+pub(crate) struct SceneWorld {
+    pub(crate) world: EngineWorld,
+    entities: HashMap<String, hecs::Entity>,
+}
+
+impl Default for SceneWorld {
+    fn default() -> Self {
+        let mut world = EngineWorld::new();
+        world.register_component::<EditorComponents>();
+        world.register_component::<RigidBody>();
+        world.register_component::<Collider>();
+        Self {
+            world,
+            entities: HashMap::new(),
+        }
+    }
+}
+
+impl SceneWorld {
+    pub(crate) fn entity(&self, object_name: &str) -> Option<hecs::Entity> {
+        self.entities.get(object_name).copied()
+    }
+
+    /// Objeto da hierarquia de um entity
+    pub(crate) fn object_name(&self, entity: hecs::Entity) -> Option<&str> {
+        self.entities
+            .iter()
+            .find(|(_, e)| **e == entity)
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Volta ao snapshot; objetos criados depois dele saem do mapa
+    pub(crate) fn restore(&mut self, snapshot: &WorldSnapshot) {
+        self.world.restore(snapshot);
+        let world = &self.world;
+        self.entities.retain(|_, e| world.world().contains(*e));
+    }
+
+    fn scene_handle(&self, scene_name: &str) -> Option<SceneHandle> {
+        self.world
+            .scenes()
+            .iter()
+            .find(|s| s.name == scene_name)
+            .map(|s| s.handle)
+    }
+
+    /// Entities da cena principal (sem `SceneHandle`)
+    fn main_scene_entities(&self) -> Vec<hecs::Entity> {
+        self.world
+            .world()
+            .query::<hecs::Entity>()
+            .without::<&SceneHandle>()
+            .iter()
+            .collect()
+    }
+}
+
+fn transform_from_matrix(matrix: Mat4) -> Transform {
+    let (scale, rotation, position) = matrix.to_scale_rotation_translation();
+    Transform::new(position, rotation, scale)
+}
+
+fn light_components(
+    light: &LightDraft,
+) -> (
+    Option<DirectionalLight>,
+    Option<PointLight>,
+    Option<SpotLight>,
+) {
+    let color = Vec3::from_array(light.color);
+    match light.light_type {
+        LightType::Directional => (
+            Some(DirectionalLight::new(color, light.intensity)),
+            None,
+            None,
+        ),
+        LightType::Point => (
+            None,
+            Some(PointLight::new(color, light.intensity, light.range)),
+            None,
+        ),
+        LightType::Spot => (
+            None,
+            None,
+            Some(SpotLight::new(
+                color,
+                light.intensity,
+                light.range,
+                (light.cone_angle * 0.5).to_radians(),
+            )),
+        ),
+    }
+}
+
+/// Luz do inspetor para um entity lido de arquivo
+fn light_draft(entity: hecs::EntityRef<'_>) -> Option<LightDraft> {
+    let mut draft = LightDraft::default();
+    if let Some(l) = entity.get::<&DirectionalLight>() {
+        draft.light_type = LightType::Directional;
+        draft.color = l.color.to_array();
+        draft.intensity = l.intensity;
+    } else if let Some(l) = entity.get::<&PointLight>() {
+        draft.light_type = LightType::Point;
+        draft.color = l.color.to_array();
+        draft.intensity = l.intensity;
+        draft.range = l.range;
+    } else if let Some(l) = entity.get::<&SpotLight>() {
+        draft.light_type = LightType::Spot;
+        draft.color = l.color.to_array();
+        draft.intensity = l.intensity;
+        draft.range = l.range;
+        draft.cone_angle = (l.outer_angle * 2.0).to_degrees();
+    } else {
+        return None;
+    }
+    Some(draft)
+}
+
+/// Objeto lido de uma cena, antes de entrar nos painéis
+struct LoadedObject {
+    entity: hecs::Entity,
+    label: String,
+    guid: Option<engine_core::Guid>,
+    mesh: Option<String>,
+    transform: Mat4,
+    script: Option<String>,
+    light: Option<LightDraft>,
+}
+
+impl EditorApp {
+    /// Caminho gravado na cena: relativo a Assets, igual à chave nos bundles
+    fn scene_asset_key(&self, path: &Path) -> String {
+        let assets = self.hot_reload_dir();
+        let rel = path
+            .strip_prefix(&assets)
+            .or_else(|_| path.strip_prefix("Assets"))
+            .unwrap_or(path);
+        rel.to_string_lossy().replace('\\', "/")
+    }
+
+    fn scene_asset_path(&self, key: &str) -> PathBuf {
+        let path = Path::new(key);
+        if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            self.hot_reload_dir().join(path)
+        }
+    }
+
+    /// Traz o mundo da cena para o estado atual dos painéis: cenas aditivas,
+    /// objetos (nome, transform local, malha, script, luz, câmera, física,
+    /// componentes do Inspetor), pais e cena dona
+    pub(crate) fn sync_scene_world(&mut self) {
+        let scene_names: Vec<String> = self.hierarchy.additive_scene_names().to_vec();
+        let stale_scenes: Vec<SceneHandle> = self
+            .scene_world
+            .world
+            .scenes()
+            .iter()
+            .filter(|s| !scene_names.contains(&s.name))
+            .map(|s| s.handle)
+            .collect();
+        for handle in stale_scenes {
+            self.scene_world.world.unload_scene(handle);
+        }
+        for name in &scene_names {
+            if self.scene_world.scene_handle(name).is_none() {
+                self.scene_world.world.create_scene(name);
+            }
+        }
+
+        let names: Vec<String> = self
+            .viewport
+            .scene_object_names()
+            .into_iter()
+            .filter(|name| !self.hierarchy.object_is_deleted(name))
+            .collect();
+        let scripts: HashMap<String, inspector::ScriptDraft> =
+            self.inspector.script_targets().into_iter().collect();
+        let bodies: HashMap<String, inspector::RigidbodyDraft> =
+            self.inspector.rigidbody_targets().into_iter().collect();
+        let colliders: HashMap<String, inspector::ColliderDraft> =
+            self.inspector.collider_targets().into_iter().collect();
+        let sprites: HashMap<String, inspector::SpriteDraft> =
+            self.inspector.sprite_targets().into_iter().collect();
+        let texts: HashMap<String, inspector::Text3DDraft> =
+            self.inspector.text3d_targets().into_iter().collect();
+        let ui_texts: HashMap<String, inspector::UiTextDraft> =
+            self.inspector.ui_text_targets().into_iter().collect();
+        // Câmeras que renderizam para textura não viram a câmera do jogo
+        let cameras: HashMap<String, Camera> = self
+            .inspector
+            .camera_targets()
+            .into_iter()
+            .filter(|(_, cfg)| cfg.target_texture.trim().is_empty())
+            .map(|(name, cfg)| (name, cfg.camera))
+            .collect();
+        let scene = &mut self.scene_world;
+        let stale: Vec<String> = scene
+            .entities
+            .iter()
+            .filter(|(name, e)| !names.contains(*name) || !scene.world.world().contains(**e))
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in stale {
+            if let Some(entity) = scene.entities.remove(&name) {
+                let _ = scene.world.world_mut().despawn(entity);
+            }
+        }
+
+        for name in &names {
+            let guid = self.hierarchy.object_guid(name);
+            let scene = &mut self.scene_world;
+            let entity = match scene.entities.get(name) {
+                Some(&entity) => entity,
+                None => {
+                    let entity = match scene.world.find_by_guid(guid) {
+                        Some(entity) => entity,
+                        None => {
+                            scene.world.spawn((guid,));
+                            scene.world.find_by_guid(guid).expect("entity recém-criado")
+                        }
+                    };
+                    scene.entities.insert(name.clone(), entity);
+                    entity
+                }
+            };
+            let transform = self
+                .viewport
+                .object_local_matrix(name)
+                .map(transform_from_matrix)
+                .unwrap_or_default();
+            let mesh = self
+                .viewport
+                .object_source(name)
+                .map(|path| MeshAsset::new(self.scene_asset_key(&path)));
+            let owner = self
+                .hierarchy
+                .scene_of(name)
+                .and_then(|s| self.scene_world.scene_handle(s));
+            let light = self
+                .inspector
+                .get_object_light(name)
+                .map(|l| light_components(&l))
+                .unwrap_or_default();
+            let script = scripts
+                .get(name)
+                .map(|s| ScriptComponent::new(self.scene_asset_key(Path::new(s.path.trim()))));
+            // Collider em unidades de mundo, como no Play
+            let collider = colliders.get(name).map(|c| {
+                let scale = self
+                    .viewport
+                    .object_transform_components(name)
+                    .map_or([1.0; 3], |(_, _, scale)| scale);
+                baked_collider(c, scale)
+            });
+            let body = bodies.get(name).map(to_rigid_body);
+            let sprite = sprites.get(name).map(|s| {
+                let path = s.texture_path.trim();
+                let texture = (!path.is_empty())
+                    .then(|| SpriteTexture::new(self.scene_asset_key(Path::new(path))));
+                (s.to_sprite(), texture)
+            });
+            let text = texts.get(name);
+            let ui_text = ui_texts.get(name);
+            // Uma fonte por entidade: a do Text 3D, senão a do UI Text
+            let font = text
+                .map(|t| t.font_path.trim())
+                .into_iter()
+                .chain(ui_text.map(|t| t.font_path.trim()))
+                .find(|path| !path.is_empty())
+                .map(|path| TextFont::new(self.scene_asset_key(Path::new(path))));
+            let text = text.map(inspector::Text3DDraft::to_text3d);
+            let ui_text = ui_text.map(inspector::UiTextDraft::to_ui_text);
+            let components = EditorComponents(self.inspector.object_components(name));
+            let world = self.scene_world.world.world_mut();
+            let _ = world.insert(
+                entity,
+                (guid, Name::new(name.as_str()), transform, components),
+            );
+            match mesh {
+                Some(mesh) => {
+                    let _ = world.insert_one(entity, mesh);
+                }
+                None => {
+                    let _ = world.remove_one::<MeshAsset>(entity);
+                }
+            }
+            match script {
+                Some(script) => {
+                    let _ = world.insert_one(entity, script);
+                }
+                None => {
+                    let _ = world.remove_one::<ScriptComponent>(entity);
+                }
+            }
+            match cameras.get(name) {
+                Some(camera) => {
+                    let _ = world.insert_one(entity, *camera);
+                }
+                None => {
+                    let _ = world.remove_one::<Camera>(entity);
+                }
+            }
+            match body {
+                Some(body) => {
+                    let _ = world.insert_one(entity, body);
+                }
+                None => {
+                    let _ = world.remove_one::<RigidBody>(entity);
+                }
+            }
+            match collider {
+                Some(collider) => {
+                    let _ = world.insert_one(entity, collider);
+                }
+                None => {
+                    let _ = world.remove_one::<Collider>(entity);
+                }
+            }
+            let _ = world.remove_one::<SpriteTexture>(entity);
+            match sprite {
+                Some((sprite, texture)) => {
+                    let _ = world.insert_one(entity, sprite);
+                    if let Some(texture) = texture {
+                        let _ = world.insert_one(entity, texture);
+                    }
+                }
+                None => {
+                    let _ = world.remove_one::<Sprite>(entity);
+                }
+            }
+            match text {
+                Some(text) => {
+                    let _ = world.insert_one(entity, text);
+                }
+                None => {
+                    let _ = world.remove_one::<Text3D>(entity);
+                }
+            }
+            match ui_text {
+                Some(text) => {
+                    let _ = world.insert_one(entity, text);
+                }
+                None => {
+                    let _ = world.remove_one::<UiText>(entity);
+                }
+            }
+            match font {
+                Some(font) => {
+                    let _ = world.insert_one(entity, font);
+                }
+                None => {
+                    let _ = world.remove_one::<TextFont>(entity);
+                }
+            }
+            match owner {
+                Some(owner) => {
+                    let _ = world.insert_one(entity, owner);
+                }
+                None => {
+                    let _ = world.remove_one::<SceneHandle>(entity);
+                }
+            }
+            let _ = world.remove_one::<DirectionalLight>(entity);
+            let _ = world.remove_one::<PointLight>(entity);
+            let _ = world.remove_one::<SpotLight>(entity);
+            match light {
+                (Some(l), _, _) => {
+                    let _ = world.insert_one(entity, l);
+                }
+                (_, Some(l), _) => {
+                    let _ = world.insert_one(entity, l);
+                }
+                (_, _, Some(l)) => {
+                    let _ = world.insert_one(entity, l);
+                }
+                _ => {}
+            }
+        }
+
+        // Pais depois de todos os objetos existirem
+        for name in &names {
+            let parent = self
+                .hierarchy
+                .object_parent(name)
+                .map(str::to_string)
+                .filter(|p| names.contains(p));
+            let parent = parent.map(|p| Parent(EntityRef::new(self.hierarchy.object_guid(&p))));
+            let Some(entity) = self.scene_world.entity(name) else {
+                continue;
+            };
+            let world = self.scene_world.world.world_mut();
+            match parent {
+                Some(parent) => {
+                    let _ = world.insert_one(entity, parent);
+                }
+                None => {
+                    let _ = world.remove_one::<Parent>(entity);
+                }
+            }
+        }
+    }
+
+    /// O caminho inverso de `sync_scene_world`, usado pelo Stop depois de
+    /// restaurar o mundo: `played` são os objetos que existiam durante o jogo
+    pub(crate) fn apply_scene_world(&mut self, played: &[String]) {
+        let scene_names: Vec<String> = self
+            .scene_world
+            .world
+            .scenes()
+            .iter()
+            .map(|s| s.name.clone())
+            .collect();
+        let extra: Vec<String> = self
+            .hierarchy
+            .additive_scene_names()
+            .iter()
+            .filter(|name| !scene_names.contains(name))
+            .cloned()
+            .collect();
+        for name in extra {
+            self.hierarchy.unload_additive_scene(&name);
+        }
+        for name in &scene_names {
+            self.hierarchy.add_additive_scene(name);
+        }
+
+        for name in played {
+            if self.scene_world.entity(name).is_none() {
+                self.hierarchy.remove_object(name);
+                self.inspector.remove_object_data(name);
+            }
+        }
+
+        let objects: Vec<(String, hecs::Entity)> = self
+            .scene_world
+            .entities
+            .iter()
+            .map(|(name, e)| (name.clone(), *e))
+            .collect();
+        for (name, entity) in &objects {
+            let world = &self.scene_world.world;
+            let Ok(e) = world.world().entity(*entity) else {
+                continue;
+            };
+            let transform = e.get::<&Transform>().map(|t| t.to_mat4());
+            let components = e.get::<&EditorComponents>().map(|c| c.0.clone());
+            let scene = e
+                .get::<&SceneHandle>()
+                .and_then(|h| world.scenes().iter().find(|s| s.handle == *h))
+                .map(|s| s.name.clone());
+            let parent = e
+                .get::<&Parent>()
+                .and_then(|p| p.0.get(world))
+                .and_then(|p| self.scene_world.object_name(p))
+                .map(str::to_string);
+
+            self.hierarchy.restore_object(name);
+            self.hierarchy.set_object_scene(name, scene.as_deref());
+            match &parent {
+                Some(parent) => {
+                    if self.hierarchy.object_parent(name) != Some(parent.as_str()) {
+                        self.hierarchy.set_object_parent(name, parent);
+                    }
+                }
+                None => self.hierarchy.clear_object_parent(name),
+            }
+            self.viewport
+                .set_object_parent(name, parent.as_deref(), false);
+            if let Some(transform) = transform {
+                self.viewport.place_object(name, transform);
+            }
+            if let Some(components) = components {
+                self.inspector.set_object_components(name, components);
+            }
+        }
+    }
+
+    /// Executa o pedido do menu de cenas da Hierarquia
+    pub(crate) fn handle_scene_request(&mut self, request: SceneRequest) {
+        match request {
+            SceneRequest::Create => {
+                let name = self.hierarchy.create_additive_scene();
+                self.scene_world.world.create_scene(&name);
+            }
+            SceneRequest::LoadAdditive => self.load_scene_additive_dialog(),
+            SceneRequest::SaveAs(scene) => self.save_scene_dialog(scene.as_deref()),
+            SceneRequest::Unload(name) => self.unload_additive_scene(&name),
+        }
+    }
+
+    fn scenes_dir(&self) -> PathBuf {
+        self.current_project
+            .as_ref()
+            .and_then(|p| p.parent())
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| PathBuf::from("."))
+    }
+
+    /// Grava os objetos de uma cena (None = principal) num .dscene
+    fn save_scene_dialog(&mut self, scene: Option<&str>) {
+        let default_name = format!(
+            "{}.{SCENE_EXTENSION}",
+            scene.unwrap_or(self.hierarchy_main_scene_name())
+        );
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Dengine Scene", &[SCENE_EXTENSION])
+            .set_directory(self.scenes_dir())
+            .set_file_name(default_name)
+            .save_file()
+        else {
+            return;
+        };
+        match self.save_scene_to(scene, &path) {
+            Ok(count) => self.log.push(
+                LogLevel::Info,
+                "Cena",
+                format!("{count} objeto(s) salvos em {}", path.display()),
+            ),
+            Err(e) => {
+                self.log.push(LogLevel::Error, "Cena", e);
+                self.log_enabled = true;
+            }
+        }
+    }
+
+    fn hierarchy_main_scene_name(&self) -> &str {
+        self.current_project
+            .as_ref()
+            .and_then(|p| p.file_stem())
+            .and_then(|s| s.to_str())
+            .unwrap_or("Scene")
+    }
+
+    /// Grava a cena no arquivo; devolve quantos objetos foram escritos
+    pub(crate) fn save_scene_to(
+        &mut self,
+        scene: Option<&str>,
+        path: &Path,
+    ) -> Result<usize, String> {
+        let (text, count) = self.scene_text(scene)?;
+        fs::write(path, text).map_err(|e| format!("Falha ao salvar {}: {e}", path.display()))?;
+        Ok(count)
+    }
+
+    /// Texto do .dscene de uma cena (None = principal) e quantos objetos ele tem
+    pub(crate) fn scene_text(&mut self, scene: Option<&str>) -> Result<(String, usize), String> {
+        self.sync_scene_world();
+        let entities = match scene {
+            Some(name) => {
+                let handle = self
+                    .scene_world
+                    .scene_handle(name)
+                    .ok_or_else(|| format!("Cena {name} não está carregada"))?;
+                self.scene_world.world.scene_entities(handle)
+            }
+            None => self.scene_world.main_scene_entities(),
+        };
+        let text = write_scene_with(
+            &self.scene_world.world,
+            &entities,
+            &[&PhysicsSceneComponents],
+        );
+        Ok((text, entities.len()))
+    }
+
+    fn load_scene_additive_dialog(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Dengine Scene", &[SCENE_EXTENSION])
+            .set_directory(self.scenes_dir())
+            .pick_file()
+        else {
+            return;
+        };
+        match self.load_scene_additive(&path) {
+            Ok(name) => self.log.push(
+                LogLevel::Info,
+                "Cena",
+                format!("Cena {name} carregada de {}", path.display()),
+            ),
+            Err(e) => {
+                self.log.push(LogLevel::Error, "Cena", e);
+                self.log_enabled = true;
+            }
+        }
+    }
+
+    /// Carrega o arquivo como cena aditiva: cada entity vira um objeto na
+    /// Hierarquia (no grupo da cena) e na viewport; carregar o mesmo arquivo de
+    /// novo cria outra cópia com GUIDs novos
+    pub(crate) fn load_scene_additive(&mut self, path: &Path) -> Result<String, String> {
+        let raw = fs::read_to_string(path)
+            .map_err(|e| format!("Falha ao ler {}: {e}", path.display()))?;
+        let snapshot = read_scene_with(&raw, &[&PhysicsSceneComponents])
+            .map_err(|e| format!("{}: {e}", path.display()))?;
+        self.sync_scene_world();
+        let base = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "Scene".to_string());
+        let scene_name = self.hierarchy.add_additive_scene(&base);
+        let handle = self
+            .scene_world
+            .world
+            .load_scene_additive(&scene_name, &snapshot);
+
+        let loaded: Vec<LoadedObject> = self
+            .scene_world
+            .world
+            .scene_entities(handle)
+            .into_iter()
+            .filter_map(|entity| {
+                let e = self.scene_world.world.world().entity(entity).ok()?;
+                Some(LoadedObject {
+                    entity,
+                    label: e
+                        .get::<&Name>()
+                        .map(|n| n.0.clone())
+                        .filter(|n| !n.trim().is_empty())
+                        .unwrap_or_else(|| "Object".to_string()),
+                    guid: e.get::<&engine_core::Guid>().map(|g| *g),
+                    mesh: e.get::<&MeshAsset>().map(|m| m.path.clone()),
+                    transform: e
+                        .get::<&Transform>()
+                        .map(|t| t.to_mat4())
+                        .unwrap_or_default(),
+                    script: e
+                        .get::<&ScriptComponent>()
+                        .map(|s| s.path.display().to_string()),
+                    light: light_draft(e),
+                })
+            })
+            .collect();
+
+        self.viewport.push_undo_snapshot();
+        let mut errors = Vec::new();
+        for object in &loaded {
+            let name = self
+                .hierarchy
+                .add_scene_object(&object.label, Some(&scene_name));
+            if let Some(guid) = object.guid {
+                self.hierarchy.set_object_guid(&name, guid);
+            }
+            let _ = self
+                .scene_world
+                .world
+                .world_mut()
+                .insert_one(object.entity, Name::new(name.as_str()));
+            self.scene_world
+                .entities
+                .insert(name.clone(), object.entity);
+            if let Some(light) = &object.light {
+                self.viewport.spawn_light(&name, light.light_type);
+                self.viewport.place_object(&name, object.transform);
+                self.inspector.set_object_light(&name, *light);
+            } else if let Some(mesh) = &object.mesh {
+                let mesh_path = self.scene_asset_path(mesh);
+                if let Err(e) = self
+                    .viewport
+                    .spawn_scene_mesh(&name, &mesh_path, object.transform)
+                {
+                    errors.push(format!("{name}: {e}"));
+                }
+            }
+            if let Some(script) = &object.script {
+                self.inspector.set_object_script(
+                    &name,
+                    inspector::ScriptDraft {
+                        enabled: true,
+                        path: self.scene_asset_path(script).display().to_string(),
+                    },
+                );
+            }
+        }
+
+        // Pais só depois de todos os objetos da cena existirem
+        for object in &loaded {
+            let parent = self
+                .scene_world
+                .world
+                .world()
+                .get::<&Parent>(object.entity)
+                .ok()
+                .and_then(|p| p.0.get(&self.scene_world.world));
+            let (Some(child), Some(parent)) = (
+                self.scene_world
+                    .object_name(object.entity)
+                    .map(str::to_string),
+                parent
+                    .and_then(|p| self.scene_world.object_name(p))
+                    .map(str::to_string),
+            ) else {
+                continue;
+            };
+            self.hierarchy.set_object_parent(&child, &parent);
+            // O transform do arquivo é relativo ao pai
+            self.viewport
+                .set_object_parent(&child, Some(&parent), false);
+        }
+
+        if !errors.is_empty() {
+            self.log.push(LogLevel::Warning, "Cena", errors.join("\n"));
+        }
+        self.clear_history();
+        Ok(scene_name)
+    }
+
+    /// Descarrega a cena do mundo e os objetos dela dos painéis
+    fn unload_additive_scene(&mut self, scene_name: &str) {
+        if let Some(handle) = self.scene_world.scene_handle(scene_name) {
+            self.scene_world.world.unload_scene(handle);
+        }
+        let world = &self.scene_world.world;
+        self.scene_world
+            .entities
+            .retain(|_, entity| world.world().contains(*entity));
+        self.hierarchy.unload_additive_scene(scene_name);
+        self.clear_history();
+    }
+}
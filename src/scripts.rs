@@ -0,0 +1,159 @@
+use super::*;
+use engine_core::{
+    DebugCommand, EngineWorld, GameSystems, ScreenshotRequest, ScriptComponent, ScriptInput,
+    ScriptSystem, Transform, WorldSnapshot,
+};
+use glam::{EulerRot, Quat, Vec3};
+
+/// Espelho ECS dos objetos com Lua Script, usado apenas durante o Play
+#[derive(Default)]
+pub(crate) struct GameplayScripts {
+    world: EngineWorld,
+    system: ScriptSystem,
+    // Estado do jogo, timers e tarefas, no mesmo mundo dos scripts
+    game: GameSystems,
+    entities: HashMap<String, hecs::Entity>,
+    last_error: Option<String>,
+}
+
+impl GameplayScripts {
+    /// Descarta o estado dos scripts; o proximo Play chama on_start de novo
+    pub(crate) fn reset(&mut self) {
+        self.world = EngineWorld::new();
+        self.system.reset();
+        self.game = GameSystems::new();
+        self.entities.clear();
+        self.last_error = None;
+    }
+
+    pub(crate) fn snapshot(&self) -> WorldSnapshot {
+        self.world.snapshot()
+    }
+
+    /// Volta o espelho ao snapshot; o estado Lua é descartado e o próximo Play
+    /// chama on_start de novo
+    pub(crate) fn restore(&mut self, snapshot: &WorldSnapshot) {
+        self.world.restore(snapshot);
+        self.system.reset();
+        self.game = GameSystems::new();
+        let world = &self.world;
+        self.entities.retain(|_, e| world.world().contains(*e));
+        self.last_error = None;
+    }
+
+    /// Script alterado em disco: os objetos que o usam recomeçam com o código
+    /// novo (on_start de novo) na próxima frame do Play
+    pub(crate) fn reload_script(&mut self, path: &Path) -> usize {
+        self.system.reload_path(path)
+    }
+
+    /// Estado do jogo em `Playing`; nos outros (pausa, menu...) a física para
+    pub(crate) fn game_playing(&self) -> bool {
+        self.game.is_playing()
+    }
+
+    /// Formas de debug enfileiradas pelos scripts, desenhadas sobre o viewport
+    pub(crate) fn debug_commands(&self) -> Vec<DebugCommand> {
+        self.system.debug_draw().commands().to_vec()
+    }
+
+    /// Capturas pedidas por `screenshot(...)` nos scripts
+    pub(crate) fn take_screenshot_requests(&mut self) -> Vec<ScreenshotRequest> {
+        self.system.take_screenshot_requests()
+    }
+}
+
+fn to_engine_transform(pos: [f32; 3], rot_deg: [f32; 3], scale: [f32; 3]) -> Transform {
+    Transform::new(
+        Vec3::from_array(pos),
+        Quat::from_euler(
+            EulerRot::XYZ,
+            rot_deg[0].to_radians(),
+            rot_deg[1].to_radians(),
+            rot_deg[2].to_radians(),
+        ),
+        Vec3::from_array(scale),
+    )
+}
+
+impl EditorApp {
+    pub(crate) fn run_gameplay_scripts(&mut self) {
+        if !self.is_playing || !self.time.is_running() {
+            return;
+        }
+        let targets = self.inspector.script_targets();
+        let scripts = &mut self.scripts;
+
+        let live: HashSet<&str> = targets.iter().map(|(n, _)| n.as_str()).collect();
+        let stale: Vec<String> = scripts
+            .entities
+            .keys()
+            .filter(|n| !live.contains(n.as_str()))
+            .cloned()
+            .collect();
+        for name in stale {
+            if let Some(entity) = scripts.entities.remove(&name) {
+                let _ = scripts.world.world_mut().despawn(entity);
+            }
+        }
+
+        for (name, cfg) in &targets {
+            let Some((pos, rot, scale)) = self.viewport.object_transform_components(name) else {
+                continue;
+            };
+            let transform = to_engine_transform(pos, rot, scale);
+            let component = ScriptComponent::new(cfg.path.trim());
+            match scripts.entities.get(name) {
+                Some(&entity) => {
+                    let _ = scripts
+                        .world
+                        .world_mut()
+                        .insert(entity, (transform, component));
+                }
+                None => {
+                    let entity = scripts.world.world_mut().spawn((transform, component));
+                    scripts.entities.insert(name.clone(), entity);
+                }
+            }
+        }
+
+        let input = ScriptInput {
+            axis: self.fios.movement_axis(),
+            look: self.fios.look_axis(),
+            action: self.fios.action_signal(),
+            keys_down: self
+                .input
+                .keys
+                .get_pressed()
+                .map(|key| key.to_string())
+                .collect(),
+            game_state: scripts.game.state(),
+        };
+        scripts
+            .system
+            .update(&mut scripts.world, &input, self.time.delta);
+        if let Some(state) = scripts.system.take_state_request() {
+            scripts.game.states.set(state);
+        }
+        scripts.game.update(&mut scripts.world, self.time.delta);
+
+        let error = scripts.system.errors().first().cloned();
+        if error.is_some() && error != scripts.last_error {
+            log::error!(target: "script", "{}", error.as_deref().unwrap_or_default());
+        }
+        scripts.last_error = error;
+
+        for (name, entity) in &scripts.entities {
+            let Ok(t) = scripts.world.world().get::<&Transform>(*entity) else {
+                continue;
+            };
+            let (rx, ry, rz) = t.rotation.to_euler(EulerRot::XYZ);
+            let _ = self.viewport.set_object_transform_components(
+                name,
+                t.position.to_array(),
+                [rx.to_degrees(), ry.to_degrees(), rz.to_degrees()],
+                t.scale.to_array(),
+            );
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use crate::EditorApp;
+use eframe::egui::Modifiers;
+
+/// Objetos selecionados, compartilhados por hierarquia, viewport e inspetor.
+/// Ficam na ordem em que foram selecionados; o último é o ativo, mostrado
+/// no inspetor e onde o gizmo fica.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Selection {
+    names: Vec<String>,
+    // Onde começa o intervalo do Shift+clique
+    anchor: Option<String>,
+}
+
+impl Selection {
+    pub(crate) fn single(name: &str) -> Self {
+        Self {
+            names: vec![name.to_string()],
+            anchor: Some(name.to_string()),
+        }
+    }
+
+    pub(crate) fn active(&self) -> Option<&str> {
+        self.names.last().map(String::as_str)
+    }
+
+    pub(crate) fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub(crate) fn contains(&self, name: &str) -> bool {
+        self.names.iter().any(|n| n == name)
+    }
+
+    /// Só `name` selecionado
+    pub(crate) fn select(&mut self, name: &str) {
+        *self = Self::single(name);
+    }
+
+    /// Acrescenta `name` (ou o move para o fim) e o torna ativo
+    pub(crate) fn add(&mut self, name: &str) {
+        self.names.retain(|n| n != name);
+        self.names.push(name.to_string());
+        self.anchor = Some(name.to_string());
+    }
+
+    /// Tira `name` da seleção se estiver nela, senão o acrescenta
+    pub(crate) fn toggle(&mut self, name: &str) {
+        if self.contains(name) {
+            self.names.retain(|n| n != name);
+            self.anchor = self.names.last().cloned();
+        } else {
+            self.add(name);
+        }
+    }
+
+    /// Seleciona do âncora até `name` na ordem de `order` (linhas da
+    /// hierarquia); sem âncora ou fora da ordem, só acrescenta `name`
+    pub(crate) fn select_range(&mut self, name: &str, order: &[String]) {
+        let from = self
+            .anchor
+            .as_deref()
+            .and_then(|anchor| order.iter().position(|n| n == anchor));
+        let to = order.iter().position(|n| n == name);
+        let (Some(from), Some(to)) = (from, to) else {
+            self.add(name);
+            return;
+        };
+        let anchor = self.anchor.take();
+        self.names.clear();
+        let range = if from <= to {
+            &order[from..=to]
+        } else {
+            &order[to..=from]
+        };
+        self.names
+            .extend(range.iter().filter(|n| n.as_str() != name).cloned());
+        self.names.push(name.to_string());
+        self.anchor = anchor;
+    }
+
+    /// Clique em `name`: Ctrl alterna, Shift estende o intervalo e sem
+    /// modificador seleciona só ele
+    pub(crate) fn click(&mut self, name: &str, modifiers: Modifiers, order: &[String]) {
+        if modifiers.command {
+            self.toggle(name);
+        } else if modifiers.shift {
+            self.select_range(name, order);
+        } else {
+            self.select(name);
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.names.clear();
+        self.anchor = None;
+    }
+
+    pub(crate) fn retain(&mut self, mut keep: impl FnMut(&str) -> bool) {
+        self.names.retain(|n| keep(n));
+        if self.anchor.as_deref().is_some_and(|a| !keep(a)) {
+            self.anchor = self.names.last().cloned();
+        }
+    }
+}
+
+impl EditorApp {
+    /// Objetos afetados por uma edição em `object_name`: a seleção inteira
+    /// quando ele faz parte dela, senão só ele
+    pub(crate) fn selection_targets(&self, object_name: &str) -> Vec<String> {
+        let selection = self.hierarchy.selection();
+        if selection.contains(object_name) {
+            selection.names().to_vec()
+        } else {
+            vec![object_name.to_string()]
+        }
+    }
+}
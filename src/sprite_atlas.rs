@@ -0,0 +1,93 @@
+use super::*;
+use crate::log_panel::LogLevel;
+use engine_render::{ATLAS_EXTENSION, SpriteAtlas, pack_sprite_atlas};
+
+/// Maior página de atlas gerada pelo editor
+const MAX_ATLAS_SIZE: u32 = 4096;
+/// Pixels entre sprites vizinhos, para a filtragem não misturar as bordas
+const ATLAS_PADDING: u32 = 2;
+
+/// Arquivos `.atlas` de `dir` e das subpastas
+fn collect_atlas_files(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_atlas_files(&path, out);
+        } else if path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.eq_ignore_ascii_case(ATLAS_EXTENSION))
+        {
+            out.push(path);
+        }
+    }
+}
+
+impl EditorApp {
+    /// Lê os atlas da pasta Assets: a viewport desenha os sprites empacotados
+    /// da página do atlas, como o jogo
+    pub(crate) fn reload_sprite_atlases(&mut self) {
+        let assets = self.hot_reload_dir();
+        let mut files = Vec::new();
+        collect_atlas_files(&assets, &mut files);
+        let mut atlases = Vec::with_capacity(files.len());
+        for path in files {
+            match SpriteAtlas::load(&path) {
+                Ok(atlas) => atlases.push((path, atlas)),
+                Err(e) => self.log.push(LogLevel::Warning, "Atlas", e),
+            }
+        }
+        self.viewport.set_sprite_atlases(assets, &atlases);
+    }
+
+    /// Empacota sprites escolhidos da pasta Assets num atlas (.atlas + .png);
+    /// o renderizador de sprites passa a desenhá-los da página do atlas
+    pub(crate) fn pack_sprite_atlas_dialog(&mut self) {
+        let assets = self.hot_reload_dir();
+        let Some(sprites) = rfd::FileDialog::new()
+            .set_directory(&assets)
+            .add_filter("Sprites", &["png", "jpg", "jpeg", "webp"])
+            .pick_files()
+        else {
+            return;
+        };
+        let Some(out) = rfd::FileDialog::new()
+            .set_directory(&assets)
+            .add_filter("Atlas", &[ATLAS_EXTENSION])
+            .set_file_name(format!("sprites.{ATLAS_EXTENSION}"))
+            .save_file()
+        else {
+            return;
+        };
+        let out = out.with_extension(ATLAS_EXTENSION);
+        // Caminhos do diálogo são absolutos; a pasta Assets pode ser relativa
+        let assets = fs::canonicalize(&assets).unwrap_or(assets);
+        let sprites: Vec<PathBuf> = sprites
+            .into_iter()
+            .map(|path| fs::canonicalize(&path).unwrap_or(path))
+            .collect();
+        match pack_sprite_atlas(&sprites, &assets, &out, MAX_ATLAS_SIZE, ATLAS_PADDING) {
+            Ok(atlas) => {
+                self.log.push(
+                    LogLevel::Info,
+                    "Atlas",
+                    format!(
+                        "{} sprites em {}x{}: {}",
+                        atlas.regions.len(),
+                        atlas.width,
+                        atlas.height,
+                        out.display()
+                    ),
+                );
+                self.reload_sprite_atlases();
+            }
+            Err(e) => {
+                self.log.push(LogLevel::Error, "Atlas", e);
+                self.log_enabled = true;
+            }
+        }
+    }
+}
@@ -691,6 +691,7 @@ impl EditorApp {
     }
 
     pub(crate) fn draw_terminal_window(&mut self, ctx: &egui::Context) {
+        engine_core::profile_function!();
         self.poll_terminal_job();
         if !self.terminai.terminal_enabled {
             return;
@@ -729,14 +730,14 @@ impl EditorApp {
                             let selected = self.terminai.terminal_selected_model == Some(model);
                             let button = egui::Button::new(model.label())
                                 .fill(if selected {
-                                    egui::Color32::from_rgb(58, 84, 64)
+                                    theme::palette().selection
                                 } else {
                                     egui::Color32::from_rgb(52, 52, 52)
                                 })
                                 .stroke(egui::Stroke::new(
                                     1.0,
                                     if selected {
-                                        egui::Color32::from_rgb(15, 232, 121)
+                                        theme::palette().accent
                                     } else {
                                         egui::Color32::from_gray(80)
                                     },
@@ -760,7 +761,7 @@ impl EditorApp {
                             ui.add(
                                 egui::Spinner::new()
                                     .size(14.0)
-                                    .color(egui::Color32::from_rgb(15, 232, 121)),
+                                    .color(theme::palette().accent),
                             );
                             ui.label("Preparando terminal...");
                         });
@@ -813,7 +814,7 @@ impl EditorApp {
                         ui.painter().rect_stroke(
                             frame_resp.rect,
                             3.0,
-                            egui::Stroke::new(1.0, egui::Color32::from_rgb(15, 232, 121)),
+                            egui::Stroke::new(1.0, theme::palette().accent),
                             egui::StrokeKind::Outside,
                         );
                     }
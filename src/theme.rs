@@ -0,0 +1,394 @@
+use super::*;
+use eframe::egui::{Color32, Stroke};
+use std::sync::{LazyLock, RwLock};
+
+/// Pasta dos temas criados pelo usuário (`<nome>.theme`)
+const USER_THEME_DIR: &str = "EditorThemes";
+
+/// Cores do editor por papel. Os painéis leem `theme::palette()` em vez de
+/// cores fixas, então trocar o tema vale para o editor inteiro
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) struct Palette {
+    // Base dos widgets do egui (Visuals::dark ou ::light)
+    pub(crate) dark: bool,
+    pub(crate) panel_bg: Color32,
+    pub(crate) panel_header: Color32,
+    // Barras de abas do dock e réguas
+    pub(crate) tab_bar: Color32,
+    pub(crate) widget_bg: Color32,
+    pub(crate) widget_hover: Color32,
+    pub(crate) border: Color32,
+    pub(crate) selection: Color32,
+    pub(crate) text_strong: Color32,
+    pub(crate) text: Color32,
+    pub(crate) text_weak: Color32,
+    pub(crate) accent: Color32,
+    pub(crate) warning: Color32,
+    pub(crate) error: Color32,
+    // Canvas do grafo do Fios
+    pub(crate) graph_bg: Color32,
+    pub(crate) graph_grid: Color32,
+    pub(crate) graph_border: Color32,
+}
+
+impl Palette {
+    pub(crate) const DARK: Self = Self {
+        dark: true,
+        panel_bg: Color32::from_rgb(30, 30, 30),
+        panel_header: Color32::from_rgb(36, 36, 36),
+        tab_bar: Color32::from_rgb(24, 24, 24),
+        widget_bg: Color32::from_rgb(44, 44, 44),
+        widget_hover: Color32::from_rgb(62, 62, 62),
+        border: Color32::from_rgb(58, 58, 58),
+        selection: Color32::from_rgb(58, 84, 64),
+        text_strong: Color32::from_gray(220),
+        text: Color32::from_gray(190),
+        text_weak: Color32::from_gray(150),
+        accent: Color32::from_rgb(15, 232, 121),
+        warning: Color32::from_rgb(255, 165, 0),
+        error: Color32::from_rgb(230, 90, 90),
+        graph_bg: Color32::from_rgb(21, 22, 24),
+        graph_grid: Color32::from_rgb(30, 31, 36),
+        graph_border: Color32::from_rgb(64, 66, 72),
+    };
+
+    pub(crate) const LIGHT: Self = Self {
+        dark: false,
+        panel_bg: Color32::from_rgb(236, 236, 236),
+        panel_header: Color32::from_rgb(222, 222, 222),
+        tab_bar: Color32::from_rgb(212, 212, 212),
+        widget_bg: Color32::from_rgb(210, 210, 210),
+        widget_hover: Color32::from_rgb(196, 196, 196),
+        border: Color32::from_rgb(170, 170, 170),
+        selection: Color32::from_rgb(170, 214, 186),
+        text_strong: Color32::from_gray(20),
+        text: Color32::from_gray(50),
+        text_weak: Color32::from_gray(100),
+        accent: Color32::from_rgb(0, 150, 80),
+        warning: Color32::from_rgb(200, 120, 0),
+        error: Color32::from_rgb(190, 40, 40),
+        graph_bg: Color32::from_rgb(244, 244, 246),
+        graph_grid: Color32::from_rgb(220, 222, 228),
+        graph_border: Color32::from_rgb(160, 162, 170),
+    };
+
+    pub(crate) const HIGH_CONTRAST: Self = Self {
+        dark: true,
+        panel_bg: Color32::BLACK,
+        panel_header: Color32::from_gray(16),
+        tab_bar: Color32::from_gray(8),
+        widget_bg: Color32::from_gray(24),
+        widget_hover: Color32::from_gray(60),
+        border: Color32::WHITE,
+        selection: Color32::from_rgb(0, 90, 160),
+        text_strong: Color32::WHITE,
+        text: Color32::from_gray(240),
+        text_weak: Color32::from_gray(200),
+        accent: Color32::from_rgb(255, 230, 0),
+        warning: Color32::from_rgb(255, 160, 0),
+        error: Color32::from_rgb(255, 80, 80),
+        graph_bg: Color32::BLACK,
+        graph_grid: Color32::from_gray(50),
+        graph_border: Color32::WHITE,
+    };
+
+    /// Papéis com a chave usada nos arquivos `.theme` e na janela de tema
+    fn roles_mut(&mut self) -> [(&'static str, &mut Color32); 16] {
+        [
+            ("panel_bg", &mut self.panel_bg),
+            ("panel_header", &mut self.panel_header),
+            ("tab_bar", &mut self.tab_bar),
+            ("widget_bg", &mut self.widget_bg),
+            ("widget_hover", &mut self.widget_hover),
+            ("border", &mut self.border),
+            ("selection", &mut self.selection),
+            ("text_strong", &mut self.text_strong),
+            ("text", &mut self.text),
+            ("text_weak", &mut self.text_weak),
+            ("accent", &mut self.accent),
+            ("warning", &mut self.warning),
+            ("error", &mut self.error),
+            ("graph_bg", &mut self.graph_bg),
+            ("graph_grid", &mut self.graph_grid),
+            ("graph_border", &mut self.graph_border),
+        ]
+    }
+
+    /// Visuals do egui com as cores do tema, para os widgets padrão
+    fn visuals(&self) -> egui::Visuals {
+        let mut visuals = if self.dark {
+            egui::Visuals::dark()
+        } else {
+            egui::Visuals::light()
+        };
+        visuals.panel_fill = self.panel_bg;
+        visuals.window_fill = self.panel_bg;
+        visuals.faint_bg_color = self.panel_header;
+        visuals.window_stroke = Stroke::new(1.0, self.border);
+        visuals.hyperlink_color = self.accent;
+        visuals.warn_fg_color = self.warning;
+        visuals.error_fg_color = self.error;
+        visuals.selection.bg_fill = self.selection;
+        visuals.selection.stroke.color = self.text_strong;
+        let widgets = &mut visuals.widgets;
+        widgets.noninteractive.bg_fill = self.panel_bg;
+        widgets.noninteractive.bg_stroke.color = self.border;
+        widgets.noninteractive.fg_stroke.color = self.text;
+        widgets.inactive.bg_fill = self.widget_bg;
+        widgets.inactive.weak_bg_fill = self.widget_bg;
+        widgets.inactive.fg_stroke.color = self.text;
+        for state in [&mut widgets.hovered, &mut widgets.active, &mut widgets.open] {
+            state.bg_fill = self.widget_hover;
+            state.weak_bg_fill = self.widget_hover;
+            state.fg_stroke.color = self.text_strong;
+        }
+        visuals
+    }
+}
+
+static CURRENT: LazyLock<RwLock<Palette>> = LazyLock::new(|| RwLock::new(Palette::DARK));
+
+/// Cores do tema ativo
+pub(crate) fn palette() -> Palette {
+    *CURRENT.read().expect("theme lock")
+}
+
+fn set_palette(palette: Palette) {
+    *CURRENT.write().expect("theme lock") = palette;
+}
+
+/// Aplica o tema ativo aos widgets do egui; chamado no começo de cada frame
+pub(crate) fn apply(ctx: &egui::Context) {
+    ctx.set_visuals(palette().visuals());
+}
+
+fn color_to_hex(color: Color32) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r(), color.g(), color.b())
+}
+
+fn color_from_hex(text: &str) -> Option<Color32> {
+    let hex = text.trim().trim_start_matches('#');
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some(Color32::from_rgb(channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// Um tema da lista: os de fábrica ou um arquivo de `EditorThemes`
+#[derive(Clone)]
+struct Theme {
+    // Nome salvo nas preferências; o nome do arquivo nos temas do usuário
+    id: String,
+    palette: Palette,
+    builtin: bool,
+}
+
+impl Theme {
+    fn builtin() -> Vec<Self> {
+        [
+            ("dark", Palette::DARK),
+            ("light", Palette::LIGHT),
+            ("high_contrast", Palette::HIGH_CONTRAST),
+        ]
+        .into_iter()
+        .map(|(id, palette)| Self {
+            id: id.to_string(),
+            palette,
+            builtin: true,
+        })
+        .collect()
+    }
+
+    /// Temas do usuário; começam do escuro, então chaves ausentes ficam com ele
+    fn load_user() -> Vec<Self> {
+        let mut themes: Vec<Self> = fs::read_dir(USER_THEME_DIR)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "theme"))
+            .filter_map(|path| {
+                let raw = fs::read_to_string(&path).ok()?;
+                let mut palette = Palette::DARK;
+                for (key, value) in raw.lines().filter_map(|line| line.split_once('=')) {
+                    let (key, value) = (key.trim(), value.trim());
+                    if key == "dark" {
+                        palette.dark = value != "false";
+                        continue;
+                    }
+                    if let (Some((_, slot)), Some(color)) = (
+                        palette.roles_mut().into_iter().find(|(k, _)| *k == key),
+                        color_from_hex(value),
+                    ) {
+                        *slot = color;
+                    }
+                }
+                Some(Self {
+                    id: path.file_stem()?.to_string_lossy().to_string(),
+                    palette,
+                    builtin: false,
+                })
+            })
+            .collect();
+        themes.sort_by(|a, b| a.id.cmp(&b.id));
+        themes
+    }
+
+    fn save(&self) -> Result<PathBuf, String> {
+        fs::create_dir_all(USER_THEME_DIR).map_err(|e| e.to_string())?;
+        let path = Path::new(USER_THEME_DIR).join(format!("{}.theme", self.id));
+        let mut palette = self.palette;
+        let mut out = format!("dark={}\n", palette.dark);
+        for (key, color) in palette.roles_mut() {
+            out.push_str(&format!("{key}={}\n", color_to_hex(*color)));
+        }
+        fs::write(&path, out).map_err(|e| format!("Falha ao salvar {}: {e}", path.display()))?;
+        Ok(path)
+    }
+
+    fn label(&self, language: EngineLanguage) -> String {
+        if !self.builtin {
+            return self.id.clone();
+        }
+        tr(language, &self.id).to_string()
+    }
+}
+
+fn tr(language: EngineLanguage, key: &str) -> &'static str {
+    i18n::lookup(language, "theme", key).unwrap_or("")
+}
+
+/// Tema escolhido, os disponíveis e a janela para editar e salvar temas
+pub(crate) struct ThemeSettings {
+    pub(crate) open: bool,
+    themes: Vec<Theme>,
+    selected: usize,
+    save_name: String,
+}
+
+impl Default for ThemeSettings {
+    fn default() -> Self {
+        let mut themes = Theme::builtin();
+        themes.extend(Theme::load_user());
+        Self {
+            open: false,
+            themes,
+            selected: 0,
+            save_name: String::new(),
+        }
+    }
+}
+
+impl ThemeSettings {
+    /// Id do tema ativo, salvo nas preferências
+    pub(crate) fn selected_id(&self) -> &str {
+        &self.themes[self.selected].id
+    }
+
+    /// Ativa o tema `id`; um id desconhecido fica no escuro
+    pub(crate) fn select(&mut self, id: &str) {
+        self.selected = self.themes.iter().position(|t| t.id == id).unwrap_or(0);
+        set_palette(self.themes[self.selected].palette);
+    }
+}
+
+impl EditorApp {
+    /// Janela de tema: escolhe um tema, ajusta as cores ao vivo e salva como
+    /// tema do usuário
+    pub(crate) fn draw_theme_window(&mut self, ctx: &egui::Context) {
+        if !self.theme.open {
+            return;
+        }
+        let language = self.language;
+        let mut open = true;
+        let mut status = None;
+        egui::Window::new(tr(language, "title"))
+            .id(egui::Id::new("theme_window"))
+            .open(&mut open)
+            .resizable(false)
+            .default_width(300.0)
+            .show(ctx, |ui| {
+                let settings = &mut self.theme;
+                let current = settings.themes[settings.selected].label(language);
+                let mut selected = settings.selected;
+                egui::ComboBox::from_id_salt("theme_select")
+                    .selected_text(current)
+                    .width(200.0)
+                    .show_ui(ui, |ui| {
+                        for (idx, theme) in settings.themes.iter().enumerate() {
+                            ui.selectable_value(&mut selected, idx, theme.label(language));
+                        }
+                    });
+                if selected != settings.selected {
+                    let id = settings.themes[selected].id.clone();
+                    settings.select(&id);
+                }
+                ui.separator();
+
+                let theme = &mut settings.themes[settings.selected];
+                let mut changed = ui
+                    .checkbox(&mut theme.palette.dark, tr(language, "dark_base"))
+                    .changed();
+                egui::Grid::new("theme_colors")
+                    .num_columns(2)
+                    .spacing([12.0, 4.0])
+                    .show(ui, |ui| {
+                        for (key, color) in theme.palette.roles_mut() {
+                            ui.label(key);
+                            changed |= ui.color_edit_button_srgba(color).changed();
+                            ui.end_row();
+                        }
+                    });
+                if theme.builtin && ui.button(tr(language, "reset")).clicked() {
+                    theme.palette = Theme::builtin()
+                        .into_iter()
+                        .find(|t| t.id == theme.id)
+                        .map(|t| t.palette)
+                        .unwrap_or(Palette::DARK);
+                    changed = true;
+                }
+                if changed {
+                    set_palette(theme.palette);
+                }
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut settings.save_name).desired_width(160.0),
+                    );
+                    let name = settings.save_name.trim().to_string();
+                    let valid = !name.is_empty()
+                        && !name.contains(['/', '\\', '.'])
+                        && !Theme::builtin().iter().any(|t| t.id == name);
+                    if ui
+                        .add_enabled(valid, egui::Button::new(tr(language, "save_as")))
+                        .clicked()
+                    {
+                        let theme = Theme {
+                            id: name.clone(),
+                            palette: settings.themes[settings.selected].palette,
+                            builtin: false,
+                        };
+                        status = Some(theme.save());
+                        settings.themes.retain(|t| t.id != name);
+                        settings.themes.push(theme);
+                        settings.select(&name);
+                    }
+                });
+            });
+        self.theme.open = open;
+        match status {
+            Some(Ok(path)) => self.log.push(
+                LogLevel::Info,
+                "Tema",
+                format!("Tema salvo: {}", path.display()),
+            ),
+            Some(Err(e)) => {
+                self.log.push(LogLevel::Error, "Tema", e);
+                self.log_enabled = true;
+            }
+            None => {}
+        }
+    }
+}
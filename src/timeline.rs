@@ -0,0 +1,751 @@
+use super::*;
+use crate::curve_editor::CurveEditor;
+use crate::log_panel::LogLevel;
+use engine_render::{Curve, CurveKey, FormatKind, migrate_format};
+
+const HEADER_HEIGHT: f32 = 26.0;
+const RULER_HEIGHT: f32 = 20.0;
+const ROW_HEIGHT: f32 = 20.0;
+const TRACK_LIST_WIDTH: f32 = 210.0;
+/// Quadros por segundo da grade; chaves arrastadas encaixam nela
+const FRAME_RATE: f32 = 30.0;
+const DEFAULT_LENGTH: f32 = 2.0;
+
+fn tr(language: EngineLanguage, key: &'static str) -> &'static str {
+    i18n::text(language, "timeline", key)
+}
+
+/// Parte do transform animada por uma trilha
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TrackProperty {
+    Position,
+    Rotation,
+    Scale,
+}
+
+impl TrackProperty {
+    const ALL: [TrackProperty; 3] = [
+        TrackProperty::Position,
+        TrackProperty::Rotation,
+        TrackProperty::Scale,
+    ];
+    const AXES: [&'static str; 3] = ["x", "y", "z"];
+
+    fn key(self) -> &'static str {
+        match self {
+            TrackProperty::Position => "position",
+            TrackProperty::Rotation => "rotation",
+            TrackProperty::Scale => "scale",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|property| property.key() == key)
+    }
+
+    fn index(self) -> usize {
+        match self {
+            TrackProperty::Position => 0,
+            TrackProperty::Rotation => 1,
+            TrackProperty::Scale => 2,
+        }
+    }
+}
+
+/// Uma propriedade de um objeto ao longo do tempo
+#[derive(Clone, Debug, PartialEq)]
+struct Track {
+    object: String,
+    property: TrackProperty,
+    axis: usize,
+    curve: Curve,
+}
+
+impl Track {
+    /// Grava `value` em `time`, substituindo a chave do mesmo quadro
+    fn set_key(&mut self, time: f32, value: f32) {
+        self.curve.set_key(time, value, 0.5 / FRAME_RATE);
+    }
+}
+
+/// Clipe de animação salvo em `.anim`:
+///
+/// ```text
+/// version=1
+/// length=2.000
+/// track=Cube|position.x
+/// key=0.0000,1.0000,0.0000,0.0000,auto
+/// ```
+///
+/// Cada chave é `tempo,valor,tangente de entrada,tangente de saída,modo`;
+/// só `tempo,valor` vale como chave linear
+#[derive(Clone, Debug, PartialEq)]
+struct AnimationClip {
+    length: f32,
+    tracks: Vec<Track>,
+}
+
+impl Default for AnimationClip {
+    fn default() -> Self {
+        Self {
+            length: DEFAULT_LENGTH,
+            tracks: Vec::new(),
+        }
+    }
+}
+
+impl AnimationClip {
+    fn parse(raw: &str) -> Result<Self, String> {
+        let text = migrate_format(FormatKind::Animation, raw)?.text;
+        let mut clip = AnimationClip::default();
+        for line in text.lines().map(str::trim) {
+            if let Some(value) = line.strip_prefix("length=") {
+                clip.length = value.trim().parse().unwrap_or(DEFAULT_LENGTH).max(0.1);
+            } else if let Some(value) = line.strip_prefix("track=") {
+                let (object, channel) = value
+                    .rsplit_once('|')
+                    .ok_or_else(|| format!("Trilha inválida: {value}"))?;
+                let (property, axis) = channel
+                    .split_once('.')
+                    .ok_or_else(|| format!("Trilha inválida: {value}"))?;
+                let property = TrackProperty::from_key(property)
+                    .ok_or_else(|| format!("Propriedade desconhecida: {property}"))?;
+                let axis = TrackProperty::AXES
+                    .iter()
+                    .position(|a| *a == axis)
+                    .ok_or_else(|| format!("Eixo desconhecido: {axis}"))?;
+                clip.tracks.push(Track {
+                    object: object.to_string(),
+                    property,
+                    axis,
+                    curve: Curve::default(),
+                });
+            } else if let Some(value) = line.strip_prefix("key=") {
+                let track = clip
+                    .tracks
+                    .last_mut()
+                    .ok_or_else(|| "Chave antes da primeira trilha".to_string())?;
+                track.curve.insert_key(CurveKey::parse(value)?);
+            }
+        }
+        Ok(clip)
+    }
+
+    fn serialize(&self) -> String {
+        let mut out = format!(
+            "# Dengine Animation\n{}length={:.3}\n",
+            FormatKind::Animation.header(),
+            self.length
+        );
+        for track in &self.tracks {
+            out.push_str(&format!(
+                "track={}|{}.{}\n",
+                track.object,
+                track.property.key(),
+                TrackProperty::AXES[track.axis]
+            ));
+            for key in track.curve.keys() {
+                out.push_str(&format!("key={}\n", key.to_text()));
+            }
+        }
+        out
+    }
+
+    fn track_mut(&mut self, object: &str, property: TrackProperty, axis: usize) -> &mut Track {
+        let index = match self
+            .tracks
+            .iter()
+            .position(|t| t.object == object && t.property == property && t.axis == axis)
+        {
+            Some(index) => index,
+            None => {
+                self.tracks.push(Track {
+                    object: object.to_string(),
+                    property,
+                    axis,
+                    curve: Curve::default(),
+                });
+                self.tracks.len() - 1
+            }
+        };
+        &mut self.tracks[index]
+    }
+
+    fn objects(&self) -> Vec<String> {
+        let mut objects: Vec<String> = Vec::new();
+        for track in &self.tracks {
+            if !objects.contains(&track.object) {
+                objects.push(track.object.clone());
+            }
+        }
+        objects
+    }
+}
+
+/// Posição, rotação (graus) e escala, na ordem de `TrackProperty::index`
+type TransformParts = [[f32; 3]; 3];
+
+/// Painel Linha do tempo (botão Animador da barra inferior): trilhas por
+/// propriedade do transform, chaves gravadas do inspetor e o clipe salvo em
+/// `.anim`. Arrastar o cursor de tempo posa os objetos na viewport; ao fechar o
+/// painel eles voltam ao transform de antes
+#[derive(Default)]
+pub(crate) struct Timeline {
+    clip: AnimationClip,
+    path: Option<PathBuf>,
+    // Alterado desde o último Salvar
+    modified: bool,
+    time: f32,
+    playing: bool,
+    // O tempo ou as chaves mudaram: a viewport precisa ser posada de novo
+    pose_dirty: bool,
+    // Transform de cada objeto antes da primeira pose, restaurado ao fechar
+    rest_pose: HashMap<String, TransformParts>,
+    // Trilha aberta no editor de curva no lugar das chaves
+    curve_track: Option<usize>,
+    panel_rect: Option<egui::Rect>,
+}
+
+impl Timeline {
+    pub(crate) fn contains_point(&self, pos: egui::Pos2) -> bool {
+        self.panel_rect.is_some_and(|rect| rect.contains(pos))
+    }
+
+    pub(crate) fn clear_panel_rect(&mut self) {
+        self.panel_rect = None;
+    }
+
+    fn set_time(&mut self, time: f32) {
+        self.time = time.clamp(0.0, self.clip.length);
+        self.pose_dirty = true;
+    }
+
+    fn remove_track(&mut self, index: usize) {
+        self.clip.tracks.remove(index);
+        self.curve_track = match self.curve_track {
+            Some(open) if open == index => None,
+            Some(open) if open > index => Some(open - 1),
+            open => open,
+        };
+        self.modified = true;
+        self.pose_dirty = true;
+    }
+
+    fn load(&mut self, path: &Path) -> Result<(), String> {
+        let raw = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        self.clip = AnimationClip::parse(&raw)?;
+        self.curve_track = None;
+        self.path = Some(path.to_path_buf());
+        self.modified = false;
+        self.playing = false;
+        self.set_time(0.0);
+        Ok(())
+    }
+
+    /// Grava no arquivo aberto ou pergunta onde, em `Assets/Animations`
+    fn save(&mut self) -> Result<Option<PathBuf>, String> {
+        let path = match &self.path {
+            Some(path) => path.clone(),
+            None => {
+                let dir = Path::new("Assets").join("Animations");
+                let _ = fs::create_dir_all(&dir);
+                let Some(path) = rfd::FileDialog::new()
+                    .add_filter("Animation", &["anim"])
+                    .set_directory(&dir)
+                    .set_file_name("NovaAnimacao.anim")
+                    .save_file()
+                else {
+                    return Ok(None);
+                };
+                path
+            }
+        };
+        fs::write(&path, self.clip.serialize()).map_err(|e| e.to_string())?;
+        self.path = Some(path.clone());
+        self.modified = false;
+        Ok(Some(path))
+    }
+}
+
+impl EditorApp {
+    /// Avança a reprodução e posa a viewport; fechar o painel desfaz a pose
+    pub(crate) fn update_timeline(&mut self, ctx: &egui::Context) {
+        if !self.animator_enabled {
+            self.timeline.playing = false;
+            self.timeline.pose_dirty = true;
+            for (name, [position, rotation, scale]) in self.timeline.rest_pose.drain() {
+                self.viewport
+                    .pose_object_transform(&name, position, rotation, scale);
+            }
+            return;
+        }
+        if self.timeline.playing {
+            let dt = ctx.input(|i| i.stable_dt);
+            let length = self.timeline.clip.length;
+            self.timeline.time = (self.timeline.time + dt) % length;
+            self.timeline.pose_dirty = true;
+            ctx.request_repaint();
+        }
+        if !std::mem::take(&mut self.timeline.pose_dirty) {
+            return;
+        }
+        let time = self.timeline.time;
+        for object in self.timeline.clip.objects() {
+            let Some((position, rotation, scale)) =
+                self.viewport.object_transform_components(&object)
+            else {
+                continue;
+            };
+            let mut parts = [position, rotation, scale];
+            self.timeline
+                .rest_pose
+                .entry(object.clone())
+                .or_insert(parts);
+            for track in self
+                .timeline
+                .clip
+                .tracks
+                .iter()
+                .filter(|t| t.object == object)
+            {
+                if let Some(value) = track.curve.evaluate(time) {
+                    parts[track.property.index()][track.axis] = value;
+                }
+            }
+            let [position, rotation, scale] = parts;
+            self.viewport
+                .pose_object_transform(&object, position, rotation, scale);
+        }
+    }
+
+    /// `.anim` arrastado do Projeto para o painel
+    pub(crate) fn open_timeline_clip(&mut self, path: &Path) {
+        if !path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("anim"))
+        {
+            return;
+        }
+        if let Err(e) = self.timeline.load(path) {
+            self.log.push(
+                LogLevel::Error,
+                "Animação",
+                format!("{}: {e}", path.display()),
+            );
+            self.log_enabled = true;
+        }
+    }
+
+    /// Grava o transform atual (o mesmo do inspetor) de `object` no tempo atual
+    fn key_object(&mut self, object: &str, only: Option<(TrackProperty, usize)>) {
+        let Some((position, rotation, scale)) = self.viewport.object_transform_components(object)
+        else {
+            return;
+        };
+        let parts = [position, rotation, scale];
+        let time = self.timeline.time;
+        self.timeline
+            .rest_pose
+            .entry(object.to_string())
+            .or_insert(parts);
+        for property in TrackProperty::ALL {
+            for axis in 0..3 {
+                if only.is_some_and(|only| only != (property, axis)) {
+                    continue;
+                }
+                self.timeline
+                    .clip
+                    .track_mut(object, property, axis)
+                    .set_key(time, parts[property.index()][axis]);
+            }
+        }
+        self.timeline.modified = true;
+    }
+
+    /// Painel Linha do tempo no espaço que o layout de painéis deu a ele
+    pub(crate) fn draw_timeline_panel(&mut self, ctx: &egui::Context, panel: egui::Rect) {
+        engine_core::profile_function!();
+        self.timeline.panel_rect = Some(panel);
+        egui::Area::new(egui::Id::new("timeline_panel"))
+            .order(egui::Order::Foreground)
+            .fixed_pos(panel.min)
+            .show(ctx, |ui| {
+                let (rect, _) = ui.allocate_exact_size(panel.size(), egui::Sense::hover());
+                ui.painter()
+                    .rect_filled(rect, 0.0, theme::palette().panel_bg);
+                ui.painter().rect_stroke(
+                    rect,
+                    0.0,
+                    egui::Stroke::new(1.0, theme::palette().border),
+                    egui::StrokeKind::Outside,
+                );
+                let inner = rect.shrink2(egui::vec2(8.0, 4.0));
+                let header =
+                    egui::Rect::from_min_size(inner.min, egui::vec2(inner.width(), HEADER_HEIGHT));
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(header)
+                        .layout(egui::Layout::left_to_right(egui::Align::Center)),
+                    |ui| self.draw_timeline_header(ui),
+                );
+                let body = egui::Rect::from_min_max(
+                    egui::pos2(inner.left(), header.bottom() + 4.0),
+                    inner.max,
+                );
+                self.draw_timeline_tracks(ui, body);
+            });
+    }
+
+    fn draw_timeline_header(&mut self, ui: &mut egui::Ui) {
+        let language = self.language;
+        if ui.button(tr(language, "new")).clicked() {
+            self.timeline.clip = AnimationClip::default();
+            self.timeline.curve_track = None;
+            self.timeline.path = None;
+            self.timeline.modified = false;
+            self.timeline.set_time(0.0);
+        }
+        if ui.button(tr(language, "open")).clicked() {
+            if let Some(path) = rfd::FileDialog::new()
+                .add_filter("Animation", &["anim"])
+                .set_directory(Path::new("Assets").join("Animations"))
+                .pick_file()
+            {
+                self.open_timeline_clip(&path);
+            }
+        }
+        if ui.button(tr(language, "save")).clicked() {
+            match self.timeline.save() {
+                Ok(Some(path)) => self.log.push(
+                    LogLevel::Info,
+                    "Animação",
+                    format!("Clipe salvo em {}", path.display()),
+                ),
+                Ok(None) => {}
+                Err(e) => {
+                    self.log
+                        .push(LogLevel::Error, "Animação", format!("Falha ao salvar: {e}"));
+                    self.log_enabled = true;
+                }
+            }
+        }
+        ui.separator();
+
+        let play_label = if self.timeline.playing { "⏸" } else { "▶" };
+        if ui.button(play_label).clicked() {
+            self.timeline.playing = !self.timeline.playing;
+        }
+        let mut time = self.timeline.time;
+        if ui
+            .add(
+                egui::DragValue::new(&mut time)
+                    .speed(1.0 / FRAME_RATE as f64)
+                    .range(0.0..=self.timeline.clip.length)
+                    .suffix(" s")
+                    .max_decimals(2),
+            )
+            .changed()
+        {
+            self.timeline.set_time(time);
+        }
+        ui.label(format!("{}:", tr(language, "length")));
+        let mut length = self.timeline.clip.length;
+        if ui
+            .add(
+                egui::DragValue::new(&mut length)
+                    .speed(0.05)
+                    .range(0.1..=600.0)
+                    .suffix(" s")
+                    .max_decimals(2),
+            )
+            .changed()
+        {
+            self.timeline.clip.length = length;
+            self.timeline.modified = true;
+            self.timeline.set_time(self.timeline.time);
+        }
+        ui.separator();
+
+        let selected = self.hierarchy.selected_object_name().to_string();
+        let can_key = self
+            .viewport
+            .object_transform_components(&selected)
+            .is_some();
+        if ui
+            .add_enabled(can_key, egui::Button::new(tr(language, "key")))
+            .on_hover_text(tr(language, "key_help"))
+            .clicked()
+        {
+            self.key_object(&selected, None);
+        }
+
+        let name = self
+            .timeline
+            .path
+            .as_deref()
+            .and_then(Path::file_name)
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| tr(language, "untitled").to_string());
+        let modified = if self.timeline.modified { " *" } else { "" };
+        ui.label(
+            egui::RichText::new(format!("{name}{modified}"))
+                .small()
+                .weak(),
+        );
+    }
+
+    /// Lista de trilhas à esquerda, régua e chaves (ou a curva aberta) à direita
+    fn draw_timeline_tracks(&mut self, ui: &mut egui::Ui, body: egui::Rect) {
+        let language = self.language;
+        let accent = theme::palette().accent;
+        let painter = ui.painter_at(body);
+        if self.timeline.clip.tracks.is_empty() {
+            painter.text(
+                body.center(),
+                egui::Align2::CENTER_CENTER,
+                tr(language, "empty"),
+                egui::FontId::proportional(12.0),
+                egui::Color32::from_gray(140),
+            );
+        }
+
+        let keys_area = egui::Rect::from_min_max(
+            egui::pos2(body.left() + TRACK_LIST_WIDTH, body.top()),
+            body.max,
+        );
+        let length = self.timeline.clip.length;
+        let to_x = |time: f32| keys_area.left() + time / length * keys_area.width();
+        let to_time =
+            |x: f32| ((x - keys_area.left()) / keys_area.width() * length).clamp(0.0, length);
+        let snap = |time: f32| (time * FRAME_RATE).round() / FRAME_RATE;
+
+        // Régua: um traço por quadro quando cabe, rótulo a cada segundo
+        let ruler =
+            egui::Rect::from_min_size(keys_area.min, egui::vec2(keys_area.width(), RULER_HEIGHT));
+        painter.rect_filled(ruler, 0.0, theme::palette().tab_bar);
+        let frames = (length * FRAME_RATE).ceil() as usize;
+        let frame_px = keys_area.width() / (length * FRAME_RATE);
+        let step = if frame_px >= 6.0 {
+            1
+        } else {
+            (6.0 / frame_px).ceil() as usize
+        };
+        for frame in (0..=frames).step_by(step.max(1)) {
+            let time = frame as f32 / FRAME_RATE;
+            let x = to_x(time.min(length));
+            let whole_second = frame % FRAME_RATE as usize == 0;
+            let top = if whole_second {
+                ruler.top() + 4.0
+            } else {
+                ruler.bottom() - 5.0
+            };
+            painter.line_segment(
+                [egui::pos2(x, top), egui::pos2(x, ruler.bottom())],
+                egui::Stroke::new(1.0, egui::Color32::from_gray(90)),
+            );
+            if whole_second {
+                painter.text(
+                    egui::pos2(x + 3.0, ruler.top() + 2.0),
+                    egui::Align2::LEFT_TOP,
+                    format!("{}s", frame / FRAME_RATE as usize),
+                    egui::FontId::proportional(10.0),
+                    egui::Color32::from_gray(170),
+                );
+            }
+        }
+        let ruler_resp = ui.interact(
+            ruler,
+            ui.id().with("timeline_ruler"),
+            egui::Sense::click_and_drag(),
+        );
+        if ruler_resp.clicked() || ruler_resp.dragged() {
+            if let Some(pos) = ruler_resp.interact_pointer_pos() {
+                self.timeline.playing = false;
+                self.timeline.set_time(snap(to_time(pos.x)));
+            }
+        }
+
+        let mut remove_track = None;
+        let mut key_track = None;
+        let mut move_key = None;
+        let mut delete_key = None;
+        let mut jump_to = None;
+        let mut toggle_curve = None;
+        let curve_track = self
+            .timeline
+            .curve_track
+            .filter(|&index| index < self.timeline.clip.tracks.len());
+        let rows_top = ruler.bottom() + 2.0;
+        for (index, track) in self.timeline.clip.tracks.iter().enumerate() {
+            let row = egui::Rect::from_min_size(
+                egui::pos2(body.left(), rows_top + index as f32 * ROW_HEIGHT),
+                egui::vec2(body.width(), ROW_HEIGHT),
+            );
+            if row.top() > body.bottom() {
+                break;
+            }
+            if curve_track == Some(index) {
+                painter.rect_filled(row, 0.0, accent.gamma_multiply(0.15));
+            } else if index % 2 == 1 {
+                painter.rect_filled(row, 0.0, egui::Color32::from_rgb(34, 34, 34));
+            }
+            let list =
+                egui::Rect::from_min_max(row.min, egui::pos2(keys_area.left() - 6.0, row.bottom()));
+            ui.scope_builder(
+                egui::UiBuilder::new()
+                    .max_rect(list)
+                    .layout(egui::Layout::left_to_right(egui::Align::Center)),
+                |ui| {
+                    if ui.small_button("✕").clicked() {
+                        remove_track = Some(index);
+                    }
+                    if ui
+                        .small_button("◆")
+                        .on_hover_text(tr(language, "key_track"))
+                        .clicked()
+                    {
+                        key_track = Some(index);
+                    }
+                    if ui
+                        .selectable_label(curve_track == Some(index), "📈")
+                        .on_hover_text(tr(language, "curve"))
+                        .clicked()
+                    {
+                        toggle_curve = Some(index);
+                    }
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "{} · {} {}",
+                            track.object,
+                            tr(language, track.property.key()),
+                            TrackProperty::AXES[track.axis].to_uppercase()
+                        ))
+                        .small(),
+                    );
+                },
+            );
+            if curve_track.is_some() {
+                continue;
+            }
+            for (key_index, key) in track.curve.keys().iter().enumerate() {
+                let center = egui::pos2(to_x(key.time), row.center().y);
+                let hit = egui::Rect::from_center_size(center, egui::vec2(10.0, 12.0));
+                let resp = ui
+                    .interact(
+                        hit,
+                        ui.id().with(("timeline_key", index, key_index)),
+                        egui::Sense::click_and_drag(),
+                    )
+                    .on_hover_text(format!(
+                        "{:.2}s = {:.3}\n{}",
+                        key.time,
+                        key.value,
+                        tr(language, "key_menu")
+                    ));
+                let current = (key.time - self.timeline.time).abs() < 0.5 / FRAME_RATE;
+                let color = if resp.hovered() || resp.dragged() || current {
+                    accent
+                } else {
+                    egui::Color32::from_gray(200)
+                };
+                let r = 4.5;
+                painter.add(egui::Shape::convex_polygon(
+                    vec![
+                        center + egui::vec2(0.0, -r),
+                        center + egui::vec2(r, 0.0),
+                        center + egui::vec2(0.0, r),
+                        center + egui::vec2(-r, 0.0),
+                    ],
+                    color,
+                    egui::Stroke::new(1.0, egui::Color32::from_gray(20)),
+                ));
+                if resp.dragged() {
+                    if let Some(pos) = resp.interact_pointer_pos() {
+                        move_key = Some((index, key_index, snap(to_time(pos.x))));
+                    }
+                }
+                if resp.clicked() {
+                    jump_to = Some(key.time);
+                }
+                if resp.secondary_clicked() {
+                    delete_key = Some((index, key_index));
+                }
+            }
+        }
+
+        // A curva ocupa a área das chaves, alinhada com a régua (o editor
+        // deixa 6 px de margem de cada lado)
+        if let Some(index) = curve_track {
+            let area = egui::Rect::from_min_max(
+                egui::pos2(keys_area.left() - 6.0, rows_top),
+                egui::pos2(keys_area.right() + 6.0, body.bottom()),
+            );
+            let time = self.timeline.time;
+            let track = &mut self.timeline.clip.tracks[index];
+            let id = (track.object.clone(), track.property.key(), track.axis);
+            let changed = ui
+                .scope_builder(egui::UiBuilder::new().max_rect(area), |ui| {
+                    CurveEditor::new(("timeline_curve", id), &mut track.curve, language)
+                        .height(area.height())
+                        .time_range(0.0..=length)
+                        .time_step(1.0 / FRAME_RATE)
+                        .playhead(time)
+                        .show(ui)
+                        .changed()
+                })
+                .inner;
+            if changed {
+                self.timeline.modified = true;
+                self.timeline.pose_dirty = true;
+            }
+        }
+
+        // Cursor de tempo por cima das trilhas
+        let x = to_x(self.timeline.time);
+        let bottom = if curve_track.is_some() {
+            ruler.bottom()
+        } else {
+            body.bottom()
+        };
+        painter.line_segment(
+            [egui::pos2(x, ruler.top()), egui::pos2(x, bottom)],
+            egui::Stroke::new(1.5, accent),
+        );
+
+        if let Some((track, key, time)) = move_key {
+            let curve = &mut self.timeline.clip.tracks[track].curve;
+            let value = curve.keys()[key].value;
+            curve.move_key(key, time, value);
+            self.timeline.modified = true;
+            self.timeline.pose_dirty = true;
+        }
+        if let Some((track, key)) = delete_key {
+            let curve = &mut self.timeline.clip.tracks[track].curve;
+            curve.remove_key(key);
+            if curve.is_empty() {
+                self.timeline.remove_track(track);
+            }
+            self.timeline.modified = true;
+            self.timeline.pose_dirty = true;
+        }
+        if let Some(track) = remove_track {
+            self.timeline.remove_track(track);
+        }
+        if let Some(index) = toggle_curve {
+            self.timeline.curve_track = if curve_track == Some(index) {
+                None
+            } else {
+                Some(index)
+            };
+        }
+        if let Some(time) = jump_to {
+            self.timeline.playing = false;
+            self.timeline.set_time(time);
+        }
+        if let Some(index) = key_track {
+            let track = &self.timeline.clip.tracks[index];
+            let (object, property, axis) = (track.object.clone(), track.property, track.axis);
+            self.key_object(&object, Some((property, axis)));
+        }
+    }
+}
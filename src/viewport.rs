@@ -4,28 +4,77 @@ use std::fs::{self, File};
 use std::hash::{Hash, Hasher};
 use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::sync::mpsc::{self, Receiver, Sender};
 
+use crate::asset_memory::format_bytes;
 use crate::hierarchy::Primitive3DKind;
 use crate::inspector;
-use crate::viewport_gpu::ViewportGpuRenderer;
+use crate::selection::Selection;
+use crate::theme;
+use crate::viewport_gpu::{QuadUpload, TerrainUpload, ViewportGpuRenderer, WaterUpload};
 use eframe::egui::{
-    self, Align2, Color32, FontId, PointerButton, Pos2, Rect, Sense, Stroke, TextureHandle,
-    TextureOptions, Vec2,
+    self, Align2, Color32, FontId, Modifiers, PointerButton, Pos2, Rect, Sense, Stroke,
+    TextureHandle, TextureOptions, Vec2,
 };
 use egui_gizmo::{Gizmo, GizmoMode, GizmoOrientation};
+use engine_core::{
+    Aabb, Camera, DebugCommand, DebugShape, LightInstance, LightKind, MaterialHandle,
+    PostProcessSettings, Ray, SpatialIndex, Sprite, Text3D, Transform, UiText,
+};
+use engine_physics::{Collider, ColliderShape};
+use engine_render::{
+    AntiAliasing, AssetManager, BrushMode, ClusteredLights, EnvironmentMap, EnvironmentSettings,
+    FontAsset, FormatKind, ImportSettings, MeshImportSettings, ParticleEmitter, ParticleView,
+    PostProcessPass, RenderStats, ShadingMode, SpriteAtlas, TERRAIN_LAYERS, Terrain, TerrainBrush,
+    TerrainMesh, TextureData, WaterSurface, WaterView, bundle_key, migrate_format,
+    post_process_chain, push_quad, push_text3d_quads, push_ui_text_quads, same_file, sprite_quad,
+    terrain_uniform_bytes, water_uniform_bytes,
+};
 use epaint::ColorImage;
-use glam::{EulerRot, Mat4, Quat, Vec3};
+use glam::{EulerRot, Mat4, Quat, Vec3, Vec4};
 
 const MAX_RUNTIME_TRIANGLES: usize = 90_000;
 const MAX_RUNTIME_VERTICES: usize = 120_000;
 const MAX_IMPORT_FILE_BYTES: u64 = 350 * 1024 * 1024;
 const MAX_PARSED_TRIANGLES: usize = 6_000_000;
 const MAX_PARSED_VERTICES: usize = 3_000_000;
+/// Passos guardados na pilha de desfazer; os mais antigos são descartados
+pub const MAX_UNDO_STEPS: usize = 64;
+const CAMERA_BOOKMARK_KEYS: [egui::Key; CAMERA_BOOKMARK_SLOTS] = [
+    egui::Key::Num1,
+    egui::Key::Num2,
+    egui::Key::Num3,
+    egui::Key::Num4,
+    egui::Key::Num5,
+    egui::Key::Num6,
+    egui::Key::Num7,
+    egui::Key::Num8,
+    egui::Key::Num9,
+];
+// Modo Voar: unidades por segundo com velocidade 1 e teto da aceleração
+const FLY_BASE_SPEED: f32 = 4.0;
+const FLY_MAX_BOOST: f32 = 8.0;
 const VIEWPORT_PROXY_TRIANGLES: usize = 12_000;
 const VIEWPORT_PROXY_VERTICES: usize = 24_000;
 const VIEWPORT_NAV_TRIANGLES: usize = 18_000;
 const VIEWPORT_NAV_VERTICES: usize = 36_000;
+const VIEWPORT_NEAR: f32 = 0.1;
+const VIEWPORT_FAR: f32 = 50.0;
+// FOV vertical da câmera do editor; em ortho a altura visível acompanha a distância
+const VIEWPORT_FOV_DEG: f32 = 45.0;
+// Painéis extras da viewport dividida: câmera a meio caminho do plano distante
+// e a faixa da barra de controles acima dos painéis
+const SPLIT_PANE_DISTANCE: f32 = VIEWPORT_FAR * 0.5;
+const SPLIT_TOOLBAR_HEIGHT: f32 = 38.0;
+// Contorno dos objetos selecionados (largura em pontos)
+const SELECTION_OUTLINE_COLOR: Color32 = Color32::from_rgb(255, 160, 40);
+const SELECTION_OUTLINE_WIDTH: f32 = 2.0;
+// Nome das malhas geradas pelo terreno (as do objeto ficam guardadas à parte)
+const TERRAIN_MESH_NAME: &str = "Terrain";
+// Régua: a medida e, mais apagados, os catetos horizontal e vertical
+const MEASURE_COLOR: [f32; 4] = [1.0, 0.85, 0.2, 1.0];
+const MEASURE_LEG_COLOR: [f32; 4] = [1.0, 0.85, 0.2, 0.45];
 
 /// Normaliza um path removendo o prefixo verbatim do Windows (\\?\)
 fn normalize_path_string(path: &str) -> String {
@@ -39,6 +88,10 @@ fn normalize_path_string(path: &str) -> String {
 pub struct ViewportPanel {
     is_3d: bool,
     is_ortho: bool,
+    shading_mode: ShadingMode,
+    split: ViewportSplit,
+    // Topo, frente e lado; com 2 painéis só o primeiro aparece
+    split_panes: [SplitPane; 3],
     gizmo_mode: GizmoMode,
     gizmo_orientation: GizmoOrientation,
     model_matrix: Mat4,
@@ -48,8 +101,13 @@ pub struct ViewportPanel {
     camera_target: Vec3,
     object_selected: bool,
     scene_entries: Vec<SceneEntry>,
+    // Objeto ativo da seleção (gizmo, foco, terreno)
     selected_scene_object: Option<String>,
+    selection: Selection,
     pending_mesh_name: Option<String>,
+    pending_imported_tree: Option<(String, Vec<String>)>,
+    // Colliders de caixa das malhas importadas com `generate_colliders`
+    pending_generated_colliders: Vec<(String, inspector::ColliderDraft)>,
     rotation_icon: Option<TextureHandle>,
     scale_icon: Option<TextureHandle>,
     transform_icon: Option<TextureHandle>,
@@ -59,12 +117,86 @@ pub struct ViewportPanel {
     dropped_asset_label: Option<String>,
     mesh_status: Option<String>,
     mesh_loading: bool,
-    pending_delete_object: Option<String>,
+    pending_delete_objects: Vec<String>,
     import_pipeline: AssetImportPipeline,
     pending_mesh_job: Option<u64>,
+    pending_mesh_source: Option<PathBuf>,
+    // Reimportações de arquivos alterados em disco, por job
+    reload_jobs: HashMap<u64, PathBuf>,
+    // Incrementada a cada malha recarregada: força o reenvio do lote para a GPU
+    mesh_revision: u64,
+    scene_spatial: SceneSpatial,
     next_import_job_id: u64,
     undo_stack: Vec<ViewportSnapshot>,
     redo_stack: Vec<ViewportSnapshot>,
+    // Passos já empilhados; o histórico do editor conta quantos cada ação usou
+    history_revision: u64,
+    play_snapshot: Option<PlayModeSnapshot>,
+    // Cena guardada enquanto um prefab é editado isolado
+    prefab_snapshot: Option<PlayModeSnapshot>,
+    collider_gizmos: Vec<(String, Collider)>,
+    debug_draw: Vec<DebugCommand>,
+    // Ossos do editor de rig em mundo: cabeça, ponta e se está selecionado
+    rig_overlay: Vec<(Vec3, Vec3, bool)>,
+    scene_lights: Vec<(String, inspector::LightDraft)>,
+    environment: EnvironmentSettings,
+    environment_map: Option<EnvironmentMap>,
+    post_process: Option<PostProcessSettings>,
+    post_lut: Option<Arc<TextureData>>,
+    anti_aliasing: AntiAliasing,
+    lod_groups: HashMap<String, inspector::LodDraft>,
+    // Malhas dos níveis de LOD por caminho (None = falhou ao carregar)
+    lod_meshes: HashMap<String, Option<MeshData>>,
+    particle_emitters: Vec<(String, ParticleEmitter)>,
+    terrains: HashMap<String, TerrainInstance>,
+    terrain_brush: TerrainBrush,
+    // Ferramenta de esculpir/pintar ativa e pincelada em andamento
+    terrain_tool: bool,
+    terrain_stroke: bool,
+    measure: MeasureTool,
+    water_surfaces: Vec<(String, WaterSurface)>,
+    // Recurso "Água" das configurações gráficas
+    water_enabled: bool,
+    // Sprites dos objetos: componente e caminho da textura (vazio = quad branco)
+    sprites: Vec<(String, Sprite, String)>,
+    // Sprites empacotados nos atlas do projeto: chave relativa a Assets ->
+    // (página do atlas, UV na página), e a pasta Assets de onde saem as chaves
+    sprite_atlases: HashMap<String, (String, [f32; 4])>,
+    sprite_assets_dir: PathBuf,
+    // Textos 3D dos objetos: componente e arquivo da fonte
+    texts: Vec<(String, Text3D, String)>,
+    // Texto da UI do jogo (desenhado só no Play): componente e arquivo da fonte
+    ui_texts: Vec<(String, UiText, String)>,
+    // Fontes carregadas, com o atlas SDF pronto para a GPU (None = falhou)
+    text_fonts: HashMap<String, Option<(FontAsset, Arc<TextureData>)>>,
+    // Câmeras dos objetos e se a viewport está no modo Game (mostra a câmera do jogo)
+    game_cameras: Vec<(String, Camera)>,
+    camera_nav: CameraNavSettings,
+    snap: SnapSettings,
+    camera_bookmarks: [Option<CameraPose>; CAMERA_BOOKMARK_SLOTS],
+    // Marcadores mudaram desde a última gravação
+    camera_bookmarks_dirty: bool,
+    // Segundos andando no modo Voar (base da aceleração)
+    fly_held: f32,
+    // Overlay de FPS, draws, triângulos, entidades e VRAM
+    show_stats: bool,
+    stats: ViewportStats,
+    game_view: bool,
+    game_aspect: GameAspect,
+    // Tamanho da área da cena em pixels no último frame (base das capturas)
+    pixel_size: [u32; 2],
+    // Terreno / água sob o clique pendente no picking da GPU (não entram no ID buffer)
+    pending_surface_pick: Option<String>,
+    // Ctrl/Shift no clique cujo pick na GPU ainda não voltou
+    pending_pick_modifiers: Modifiers,
+    // Material solto do Projeto: posição e arquivo, resolvidos no próximo frame
+    material_drop_request: Option<(Pos2, String)>,
+    // Material cujo pick na GPU ainda não voltou (o pick não seleciona)
+    pending_material_pick: Option<String>,
+    // Objeto sob a soltura e o material, para o editor aplicar
+    material_drop: Option<(String, String)>,
+    // Objetos do lote da GPU na ordem dos IDs do picking (vazio = picking na CPU)
+    gpu_pick_names: Vec<String>,
     pub light_yaw: f32,
     pub light_pitch: f32,
     pub light_color: [f32; 3],
@@ -75,12 +207,571 @@ pub struct ViewportPanel {
     texture_cache: HashMap<String, TextureHandle>,
 }
 
+/// Vistas alinhadas aos eixos (teclado numérico, como no Blender; Alt = lado oposto,
+/// Ctrl fica com os marcadores de câmera)
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum AxisView {
+    Front,
+    Back,
+    Right,
+    Left,
+    Top,
+    Bottom,
+}
+
+impl AxisView {
+    const ALL: [AxisView; 6] = [
+        AxisView::Front,
+        AxisView::Back,
+        AxisView::Right,
+        AxisView::Left,
+        AxisView::Top,
+        AxisView::Bottom,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            AxisView::Front => "Frente (1)",
+            AxisView::Back => "Trás (Alt+1)",
+            AxisView::Right => "Direita (3)",
+            AxisView::Left => "Esquerda (Alt+3)",
+            AxisView::Top => "Topo (7)",
+            AxisView::Bottom => "Base (Alt+7)",
+        }
+    }
+
+    /// Nome sem o atalho, para os painéis da viewport dividida
+    fn name(self) -> &'static str {
+        match self {
+            AxisView::Front => "Frente",
+            AxisView::Back => "Trás",
+            AxisView::Right => "Direita",
+            AxisView::Left => "Esquerda",
+            AxisView::Top => "Topo",
+            AxisView::Bottom => "Base",
+        }
+    }
+
+    /// (yaw, pitch) da órbita; topo/base ficam a um passo do polo para o
+    /// look_at não degenerar
+    fn yaw_pitch(self) -> (f32, f32) {
+        use std::f32::consts::{FRAC_PI_2, PI};
+        let pole = FRAC_PI_2 - 1e-3;
+        match self {
+            AxisView::Front => (-FRAC_PI_2, 0.0),
+            AxisView::Back => (FRAC_PI_2, 0.0),
+            AxisView::Right => (0.0, 0.0),
+            AxisView::Left => (PI, 0.0),
+            AxisView::Top => (-FRAC_PI_2, pole),
+            AxisView::Bottom => (-FRAC_PI_2, -pole),
+        }
+    }
+}
+
+/// Quantos painéis a vista de cena 3D mostra: o primeiro é sempre a câmera do
+/// editor, os demais são vistas ortográficas com câmera e shading próprios
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+enum ViewportSplit {
+    #[default]
+    Single,
+    Two,
+    Four,
+}
+
+impl ViewportSplit {
+    const ALL: [ViewportSplit; 3] = [
+        ViewportSplit::Single,
+        ViewportSplit::Two,
+        ViewportSplit::Four,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            ViewportSplit::Single => "1 painel",
+            ViewportSplit::Two => "2 painéis (lado a lado)",
+            ViewportSplit::Four => "4 painéis",
+        }
+    }
+
+    /// Retângulos dos painéis em `area`, começando pela vista principal
+    fn rects(self, area: Rect) -> Vec<Rect> {
+        const GAP: f32 = 2.0;
+        let mid = area.center();
+        let left = Rect::from_min_max(area.min, egui::pos2(mid.x - GAP, area.max.y));
+        let right = Rect::from_min_max(egui::pos2(mid.x + GAP, area.min.y), area.max);
+        match self {
+            ViewportSplit::Single => vec![area],
+            ViewportSplit::Two => vec![left, right],
+            ViewportSplit::Four => {
+                let top = |column: Rect| {
+                    Rect::from_min_max(column.min, egui::pos2(column.max.x, mid.y - GAP))
+                };
+                let bottom = |column: Rect| {
+                    Rect::from_min_max(egui::pos2(column.min.x, mid.y + GAP), column.max)
+                };
+                // Principal e topo em cima, frente e lado embaixo
+                vec![top(left), top(right), bottom(left), bottom(right)]
+            }
+        }
+    }
+}
+
+/// Painel extra da viewport dividida: câmera ortográfica alinhada a um eixo
+struct SplitPane {
+    view: AxisView,
+    shading_mode: ShadingMode,
+    target: Vec3,
+    // Meia altura visível, em unidades do mundo
+    half_height: f32,
+}
+
+impl SplitPane {
+    fn new(view: AxisView) -> Self {
+        Self {
+            view,
+            shading_mode: ShadingMode::Wireframe,
+            target: Vec3::ZERO,
+            half_height: 5.0,
+        }
+    }
+}
+
+/// Régua da viewport: dois cliques medem distância, desnível e inclinação
+#[derive(Default)]
+struct MeasureTool {
+    active: bool,
+    // Pontos marcados (até dois); o terceiro clique começa outra medida
+    points: Vec<MeasurePoint>,
+}
+
+#[derive(Clone)]
+struct MeasurePoint {
+    position: Vec3,
+    // Medido no pivô do objeto: a régua acompanha o objeto se ele mover
+    entity: Option<String>,
+}
+
+/// O que o botão direito faz na viewport 3D
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub enum CameraNavMode {
+    /// Órbita em torno do objeto selecionado (Local) ou olhar livre (Global)
+    #[default]
+    Orbit,
+    /// Olhar livre; WASD anda, Q/E desce/sobe
+    Fly,
+    /// Arrasta a vista
+    Pan,
+}
+
+impl CameraNavMode {
+    pub const ALL: [CameraNavMode; 3] =
+        [CameraNavMode::Orbit, CameraNavMode::Fly, CameraNavMode::Pan];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CameraNavMode::Orbit => "Orbitar",
+            CameraNavMode::Fly => "Voar",
+            CameraNavMode::Pan => "Pan",
+        }
+    }
+
+    pub fn as_config_str(self) -> &'static str {
+        match self {
+            CameraNavMode::Orbit => "orbit",
+            CameraNavMode::Fly => "fly",
+            CameraNavMode::Pan => "pan",
+        }
+    }
+
+    pub fn from_config_str(value: &str) -> Option<Self> {
+        match value.trim() {
+            "orbit" => Some(CameraNavMode::Orbit),
+            "fly" => Some(CameraNavMode::Fly),
+            "pan" => Some(CameraNavMode::Pan),
+            _ => None,
+        }
+    }
+}
+
+/// Navegação da câmera da cena, guardada nas preferências do editor
+#[derive(Clone, Copy, PartialEq)]
+pub struct CameraNavSettings {
+    pub mode: CameraNavMode,
+    /// Multiplica pan, zoom e o deslocamento do modo Voar
+    pub speed: f32,
+    /// Ganho de velocidade por segundo com WASD pressionado (0 = constante)
+    pub acceleration: f32,
+    pub invert_y: bool,
+}
+
+impl Default for CameraNavSettings {
+    fn default() -> Self {
+        Self {
+            mode: CameraNavMode::Orbit,
+            speed: 1.0,
+            acceleration: 1.0,
+            invert_y: false,
+        }
+    }
+}
+
+impl CameraNavSettings {
+    pub const SPEED_RANGE: std::ops::RangeInclusive<f32> = 0.1..=10.0;
+
+    /// Velocidade do modo Voar (unidades/s) depois de `held` segundos andando
+    fn fly_speed(&self, held: f32) -> f32 {
+        let boost = (1.0 + self.acceleration * held).min(FLY_MAX_BOOST);
+        FLY_BASE_SPEED * self.speed * boost
+    }
+}
+
+/// Snap do gizmo de mover ao que está sob o cursor
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub enum SurfaceSnap {
+    #[default]
+    Off,
+    /// Pivô na superfície de outro objeto (ou no chão, sem nada sob o cursor)
+    Surface,
+    /// Pivô no vértice mais próximo do objeto sob o cursor
+    Vertex,
+}
+
+impl SurfaceSnap {
+    pub const ALL: [SurfaceSnap; 3] = [SurfaceSnap::Off, SurfaceSnap::Surface, SurfaceSnap::Vertex];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SurfaceSnap::Off => "Desligado",
+            SurfaceSnap::Surface => "Superfície",
+            SurfaceSnap::Vertex => "Vértice",
+        }
+    }
+
+    pub fn as_config_str(self) -> &'static str {
+        match self {
+            SurfaceSnap::Off => "off",
+            SurfaceSnap::Surface => "surface",
+            SurfaceSnap::Vertex => "vertex",
+        }
+    }
+
+    pub fn from_config_str(value: &str) -> Option<Self> {
+        match value.trim() {
+            "off" => Some(SurfaceSnap::Off),
+            "surface" => Some(SurfaceSnap::Surface),
+            "vertex" => Some(SurfaceSnap::Vertex),
+            _ => None,
+        }
+    }
+}
+
+/// Proporção ou resolução da vista Game; a área do jogo fica com faixas
+/// pretas em volta quando o painel tem outra proporção
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub enum GameAspect {
+    /// Usa o painel inteiro
+    #[default]
+    Free,
+    Ratio16x9,
+    Ratio16x10,
+    Ratio4x3,
+    Ratio21x9,
+    Full1080p,
+    Hd720p,
+    Qhd1440p,
+    /// Celular em retrato
+    Phone,
+    Tablet,
+}
+
+impl GameAspect {
+    pub const ALL: [GameAspect; 10] = [
+        GameAspect::Free,
+        GameAspect::Ratio16x9,
+        GameAspect::Ratio16x10,
+        GameAspect::Ratio4x3,
+        GameAspect::Ratio21x9,
+        GameAspect::Full1080p,
+        GameAspect::Hd720p,
+        GameAspect::Qhd1440p,
+        GameAspect::Phone,
+        GameAspect::Tablet,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            GameAspect::Free => "Livre",
+            GameAspect::Ratio16x9 => "16:9",
+            GameAspect::Ratio16x10 => "16:10",
+            GameAspect::Ratio4x3 => "4:3",
+            GameAspect::Ratio21x9 => "21:9",
+            GameAspect::Full1080p => "1080p (1920x1080)",
+            GameAspect::Hd720p => "720p (1280x720)",
+            GameAspect::Qhd1440p => "1440p (2560x1440)",
+            GameAspect::Phone => "Celular (1080x2340)",
+            GameAspect::Tablet => "Tablet (2048x1536)",
+        }
+    }
+
+    pub fn as_config_str(self) -> &'static str {
+        match self {
+            GameAspect::Free => "free",
+            GameAspect::Ratio16x9 => "16x9",
+            GameAspect::Ratio16x10 => "16x10",
+            GameAspect::Ratio4x3 => "4x3",
+            GameAspect::Ratio21x9 => "21x9",
+            GameAspect::Full1080p => "1080p",
+            GameAspect::Hd720p => "720p",
+            GameAspect::Qhd1440p => "1440p",
+            GameAspect::Phone => "phone",
+            GameAspect::Tablet => "tablet",
+        }
+    }
+
+    pub fn from_config_str(value: &str) -> Option<Self> {
+        let value = value.trim();
+        Self::ALL
+            .into_iter()
+            .find(|aspect| aspect.as_config_str() == value)
+    }
+
+    /// Resolução fixa do preset, em pixels
+    fn resolution(self) -> Option<[u32; 2]> {
+        match self {
+            GameAspect::Full1080p => Some([1920, 1080]),
+            GameAspect::Hd720p => Some([1280, 720]),
+            GameAspect::Qhd1440p => Some([2560, 1440]),
+            GameAspect::Phone => Some([1080, 2340]),
+            GameAspect::Tablet => Some([2048, 1536]),
+            _ => None,
+        }
+    }
+
+    /// Largura / altura (None = a do painel)
+    fn ratio(self) -> Option<f32> {
+        match self {
+            GameAspect::Free => None,
+            GameAspect::Ratio16x9 => Some(16.0 / 9.0),
+            GameAspect::Ratio16x10 => Some(16.0 / 10.0),
+            GameAspect::Ratio4x3 => Some(4.0 / 3.0),
+            GameAspect::Ratio21x9 => Some(21.0 / 9.0),
+            _ => self.resolution().map(|[w, h]| w as f32 / h as f32),
+        }
+    }
+
+    /// Área do jogo dentro do painel: a maior com a proporção do preset, ou a
+    /// resolução exata (pixel a pixel) quando ela cabe
+    fn fit(self, area: Rect, pixels_per_point: f32) -> Rect {
+        let Some(ratio) = self.ratio() else {
+            return area;
+        };
+        let mut size = if area.width() / area.height().max(1.0) > ratio {
+            egui::vec2(area.height() * ratio, area.height())
+        } else {
+            egui::vec2(area.width(), area.width() / ratio)
+        };
+        if let Some([w, h]) = self.resolution() {
+            let native = egui::vec2(w as f32, h as f32) / pixels_per_point;
+            if native.x <= size.x && native.y <= size.y {
+                size = native;
+            }
+        }
+        Rect::from_center_size(area.center(), size)
+    }
+}
+
+/// Grade do chão e incrementos de snap, guardados nas preferências do editor
+#[derive(Clone, Copy, PartialEq)]
+pub struct SnapSettings {
+    pub grid_visible: bool,
+    /// Distância entre as linhas da grade do chão
+    pub grid_spacing: f32,
+    /// Linhas para cada lado da origem
+    pub grid_extent: u32,
+    /// Snap ligado no gizmo e ao posicionar objetos (Ctrl inverte no gizmo)
+    pub enabled: bool,
+    pub translate: f32,
+    pub rotate_deg: f32,
+    pub scale: f32,
+    /// Independe de `enabled`: vale ao arrastar o gizmo de mover
+    pub surface: SurfaceSnap,
+}
+
+impl Default for SnapSettings {
+    fn default() -> Self {
+        Self {
+            grid_visible: true,
+            grid_spacing: 1.0,
+            grid_extent: 20,
+            enabled: false,
+            translate: 0.25,
+            rotate_deg: 15.0,
+            scale: 0.1,
+            surface: SurfaceSnap::Off,
+        }
+    }
+}
+
+impl SnapSettings {
+    fn snap_position(&self, position: Vec3) -> Vec3 {
+        if !self.enabled || self.translate <= 0.0 {
+            return position;
+        }
+        (position / self.translate).round() * self.translate
+    }
+
+    fn snap_angle(&self, radians: f32) -> f32 {
+        if !self.enabled || self.rotate_deg <= 0.0 {
+            return radians;
+        }
+        let step = self.rotate_deg.to_radians();
+        (radians / step).round() * step
+    }
+}
+
+/// Marcadores de câmera por cena (Ctrl+1..9)
+pub const CAMERA_BOOKMARK_SLOTS: usize = 9;
+
+/// Posição da câmera da cena, guardada nos marcadores
+#[derive(Clone, Copy, PartialEq)]
+pub struct CameraPose {
+    pub yaw: f32,
+    pub pitch: f32,
+    pub distance: f32,
+    pub target: Vec3,
+    pub ortho: bool,
+}
+
+/// Números do overlay de estatísticas da viewport
+#[derive(Default)]
+struct ViewportStats {
+    // Média móvel do intervalo entre frames, em segundos
+    frame_time: f32,
+    render: RenderStats,
+    visible: usize,
+    culled: usize,
+}
+
 #[derive(Clone, PartialEq)]
 struct SceneEntry {
     name: String,
+    // Transform de mundo: `transform` do pai * `local`
+    transform: Mat4,
+    // Transform relativo ao pai (igual ao de mundo sem pai)
+    local: Mat4,
+    // Objeto da cena ao qual este está preso
+    parent: Option<String>,
+    full: MeshData,
+    proxy: MeshData,
+    // Arquivo importado de onde a malha veio (recarregado quando muda em disco)
+    source: Option<PathBuf>,
+}
+
+/// Caixas de mundo dos objetos da cena num `SpatialIndex`, usado pelo
+/// picking, pela régua, pelo snap e pelo culling. A cada frame só refaz a
+/// caixa de quem mudou de transform; a caixa local (vértices do proxy) só é
+/// recalculada quando a malha do objeto muda
+#[derive(Default)]
+struct SceneSpatial {
+    // Chave = posição em `scene_entries`
+    index: SpatialIndex<usize>,
+    cached: Vec<SpatialEntry>,
+}
+
+/// O que a caixa guardada de uma entrada levou em conta
+struct SpatialEntry {
+    name: String,
+    mesh_name: String,
+    mesh_vertices: usize,
+    mesh_revision: u64,
+    transform: Mat4,
+    local: Aabb,
+}
+
+impl SpatialEntry {
+    fn new(entry: &SceneEntry, mesh_revision: u64) -> Self {
+        Self {
+            name: entry.name.clone(),
+            mesh_name: entry.proxy.name.clone(),
+            mesh_vertices: entry.proxy.vertices.len(),
+            mesh_revision,
+            transform: entry.transform,
+            local: Aabb::from_points(entry.proxy.vertices.iter().copied()).unwrap_or_default(),
+        }
+    }
+
+    fn same_mesh(&self, entry: &SceneEntry, mesh_revision: u64) -> bool {
+        self.mesh_revision == mesh_revision
+            && self.name == entry.name
+            && self.mesh_name == entry.proxy.name
+            && self.mesh_vertices == entry.proxy.vertices.len()
+    }
+}
+
+impl SceneSpatial {
+    /// Acompanha `scene_entries`: entradas novas ou com outra malha refazem a
+    /// caixa local, as que só se moveram reaproveitam a dela
+    fn refresh(&mut self, entries: &[SceneEntry], mesh_revision: u64) {
+        engine_core::profile_function!();
+        if self.cached.len() > entries.len() {
+            let len = entries.len();
+            self.cached.truncate(len);
+            self.index.retain(|i| *i < len);
+        }
+        for (i, entry) in entries.iter().enumerate() {
+            let same_mesh = self
+                .cached
+                .get(i)
+                .is_some_and(|cached| cached.same_mesh(entry, mesh_revision));
+            if same_mesh {
+                let cached = &mut self.cached[i];
+                if cached.transform != entry.transform {
+                    cached.transform = entry.transform;
+                    self.index
+                        .set(i, cached.local.transformed(&entry.transform));
+                }
+                continue;
+            }
+            let cached = SpatialEntry::new(entry, mesh_revision);
+            self.index
+                .set(i, cached.local.transformed(&entry.transform));
+            if i < self.cached.len() {
+                self.cached[i] = cached;
+            } else {
+                self.cached.push(cached);
+            }
+        }
+        self.index.refresh();
+    }
+
+    /// A malha do objeto mudou sem trocar de nome nem de tamanho (terreno
+    /// esculpido): a caixa local é refeita no próximo `refresh`
+    fn invalidate(&mut self, name: &str) {
+        for cached in self.cached.iter_mut().filter(|cached| cached.name == name) {
+            cached.name.clear();
+        }
+    }
+}
+
+/// Malha e transform de um objeto copiado, independentes do original
+#[derive(Clone)]
+pub struct SceneObjectCopy {
     transform: Mat4,
     full: MeshData,
     proxy: MeshData,
+    source: Option<PathBuf>,
+}
+
+/// Terreno de um objeto: alturas e splat map editáveis na viewport
+struct TerrainInstance {
+    draft: inspector::TerrainDraft,
+    terrain: Terrain,
+    // Malhas do objeto antes do terreno, devolvidas quando o componente sai
+    original: Option<(MeshData, MeshData)>,
+    synced_height_version: u64,
+    // Último envio para a GPU: (chave, vértices, índices) e (versão, RGBA)
+    gpu_mesh: Option<(u64, Arc<[u8]>, Arc<[u8]>)>,
+    gpu_splat: Option<(u64, Arc<[u8]>)>,
 }
 
 #[derive(Clone, PartialEq)]
@@ -96,7 +787,8 @@ struct MeshData {
 
 /// Parse um arquivo .mat e extrai o caminho da textura (albedo/diffuse)
 fn parse_material_texture_path(mat_path: &str) -> Option<String> {
-    let content = std::fs::read_to_string(mat_path).ok()?;
+    let raw = std::fs::read_to_string(mat_path).ok()?;
+    let content = migrate_format(FormatKind::Material, &raw).ok()?.text;
     let mat_dir = std::path::Path::new(mat_path).parent()?;
 
     for line in content.lines() {
@@ -160,7 +852,7 @@ fn parse_material_texture_path(mat_path: &str) -> Option<String> {
                         if let Some(ext) = entry_path.extension().and_then(|e| e.to_str()) {
                             if matches!(ext.to_lowercase().as_str(), "png" | "jpg" | "jpeg") {
                                 if let Ok(abs) = std::fs::canonicalize(&entry_path) {
-                                    eprintln!("[MATERIAL] Fallback textura cache: {:?}", abs);
+                                    log::debug!("[MATERIAL] Fallback textura cache: {:?}", abs);
                                     return Some(normalize_path_string(&abs.to_string_lossy()));
                                 }
                             }
@@ -187,9 +879,6 @@ fn parse_material_texture_path(mat_path: &str) -> Option<String> {
             }
             return Some(path);
         }
-        if let Some(val) = line.strip_prefix("texture=") {
-            return Some(val.trim().trim_matches('"').to_string());
-        }
         if let Some(val) = line.strip_prefix("texture_path=") {
             return Some(val.trim().trim_matches('"').to_string());
         }
@@ -205,7 +894,7 @@ fn parse_material_texture_path(mat_path: &str) -> Option<String> {
     for ext in &["png", "jpg", "jpeg"] {
         let tex_path = format!("Assets/Textures/{}_{}.{}", base_name, base_name, ext);
         if std::path::Path::new(&tex_path).exists() {
-            eprintln!(
+            log::debug!(
                 "[MATERIAL] Textura encontrada por nome similar: {}",
                 tex_path
             );
@@ -269,10 +958,21 @@ where
 struct ViewportSnapshot {
     scene_entries: Vec<SceneEntry>,
     selected_scene_object: Option<String>,
+    selection: Selection,
     object_selected: bool,
     dropped_asset_label: Option<String>,
 }
 
+/// Estado da cena no momento do Play (restaurado no Stop) ou ao abrir a
+/// edição de um prefab (restaurado ao voltar para a cena)
+struct PlayModeSnapshot {
+    scene: ViewportSnapshot,
+    undo_stack: Vec<ViewportSnapshot>,
+    redo_stack: Vec<ViewportSnapshot>,
+    // Luz direcional: fica fora do histórico, mas scripts podem mexer nela
+    light: (f32, f32, [f32; 3], f32, bool),
+}
+
 enum MeshLoadEvent {
     Proxy(MeshData),
     Full(Result<MeshData, String>),
@@ -280,10 +980,25 @@ enum MeshLoadEvent {
 
 enum ImportRequest {
     LoadMesh { job_id: u64, path: PathBuf },
+    LoadScene { job_id: u64, path: PathBuf },
 }
 
 enum ImportEvent {
-    Mesh { job_id: u64, event: MeshLoadEvent },
+    Mesh {
+        job_id: u64,
+        event: MeshLoadEvent,
+    },
+    Scene {
+        job_id: u64,
+        result: Result<Vec<ImportedSceneNode>, String>,
+    },
+}
+
+/// Nó com malha de uma cena glTF/FBX, já no espaço normalizado da viewport
+struct ImportedSceneNode {
+    name: String,
+    transform: Mat4,
+    mesh: MeshData,
 }
 
 struct ViewportMeshAsset {
@@ -305,7 +1020,11 @@ impl AssetImportPipeline {
                 match req {
                     ImportRequest::LoadMesh { job_id, path } => {
                         match load_viewport_mesh_asset_cached(&path) {
-                            Ok(asset) => {
+                            Ok(mut asset) => {
+                                // Depois do cache, que não depende do .meta
+                                let scale = mesh_import_scale(&path);
+                                apply_import_scale(&mut asset.full, scale);
+                                apply_import_scale(&mut asset.proxy, scale);
                                 let _ = tx_evt.send(ImportEvent::Mesh {
                                     job_id,
                                     event: MeshLoadEvent::Proxy(asset.proxy),
@@ -323,6 +1042,10 @@ impl AssetImportPipeline {
                             }
                         }
                     }
+                    ImportRequest::LoadScene { job_id, path } => {
+                        let result = load_scene_nodes(&path);
+                        let _ = tx_evt.send(ImportEvent::Scene { job_id, result });
+                    }
                 }
             }
         });
@@ -335,6 +1058,10 @@ impl AssetImportPipeline {
     fn enqueue_mesh(&self, job_id: u64, path: PathBuf) {
         let _ = self.tx.send(ImportRequest::LoadMesh { job_id, path });
     }
+
+    fn enqueue_scene(&self, job_id: u64, path: PathBuf) {
+        let _ = self.tx.send(ImportRequest::LoadScene { job_id, path });
+    }
 }
 
 impl ViewportPanel {
@@ -399,108 +1126,549 @@ impl ViewportPanel {
         Some((screen_center, screen_radius))
     }
 
-    fn focus_selected_or_origin(&mut self) {
-        if let Some(name) = self.selected_scene_object.clone() {
-            if let Some(entry) = self.scene_entries.iter().find(|o| o.name == name) {
-                let center = Self::scene_entry_world_center(entry);
-                let radius = Self::scene_entry_world_radius(entry, center);
-                self.camera_target = center;
-                self.camera_distance = (radius * 3.0).clamp(0.8, 80.0);
-                return;
+    /// Atualiza e desenha o overlay de estatísticas no canto inferior esquerdo.
+    /// Visíveis/cortadas: objetos dentro do frustum com malha no LOD atual.
+    fn draw_stats_overlay(
+        &mut self,
+        ui: &mut egui::Ui,
+        viewport_rect: Rect,
+        gpu_renderer: Option<&ViewportGpuRenderer>,
+        view_proj: Mat4,
+        eye: Vec3,
+        use_proxy: bool,
+    ) {
+        let dt = ui.ctx().input(|i| i.unstable_dt);
+        self.stats.frame_time = if self.stats.frame_time <= 0.0 {
+            dt
+        } else {
+            self.stats.frame_time + (dt - self.stats.frame_time) * 0.1
+        };
+        // Os contadores somam os passes do frame anterior
+        self.stats.render = gpu_renderer
+            .map(|gpu| gpu.take_render_stats())
+            .unwrap_or_default();
+        let visible = self
+            .scene_spatial
+            .index
+            .query(|bounds| aabb_in_frustum(view_proj, bounds))
+            .into_iter()
+            .filter_map(|i| self.scene_entries.get(i))
+            .filter(|entry| self.entry_mesh(entry, use_proxy, eye).is_some())
+            .count();
+        self.stats.visible = visible;
+        self.stats.culled = self.scene_entries.len() - visible;
+        // Sem repintura contínua o FPS só mediria os eventos de entrada
+        ui.ctx().request_repaint();
+
+        let stats = &self.stats;
+        let fps = if stats.frame_time > 0.0 {
+            1.0 / stats.frame_time
+        } else {
+            0.0
+        };
+        let mut lines = vec![
+            format!("FPS: {fps:.0}"),
+            format!("Frame: {:.2} ms", stats.frame_time * 1000.0),
+        ];
+        match gpu_renderer {
+            Some(gpu) => {
+                let memory = gpu.memory_usage();
+                lines.push(format!("Draw calls: {}", stats.render.draw_calls));
+                lines.push(format!("Triângulos: {}", stats.render.triangles));
+                lines.push(format!(
+                    "Visíveis: {}  Cortadas: {}",
+                    stats.visible, stats.culled
+                ));
+                lines.push(if memory.limit() == 0 {
+                    format!("VRAM: {}", format_bytes(memory.total()))
+                } else {
+                    format!(
+                        "VRAM: {} / {}",
+                        format_bytes(memory.total()),
+                        format_bytes(memory.limit())
+                    )
+                });
+            }
+            None => {
+                lines.push(format!(
+                    "Visíveis: {}  Cortadas: {}",
+                    stats.visible, stats.culled
+                ));
+                lines.push("Sem GPU: sem contadores".to_string());
             }
         }
-        self.camera_target = Vec3::ZERO;
-        self.camera_distance = self.camera_distance.clamp(0.8, 80.0);
-    }
 
-    pub fn new() -> Self {
-        let import_pipeline = AssetImportPipeline::new();
-        let mut s = Self {
-            is_3d: true,
-            is_ortho: false,
-            gizmo_mode: GizmoMode::Translate,
-            gizmo_orientation: GizmoOrientation::Local,
-            model_matrix: Mat4::IDENTITY,
-            camera_yaw: 0.78,
-            camera_pitch: 0.42,
-            camera_distance: 4.8,
-            camera_target: Vec3::ZERO,
-            object_selected: false,
-            scene_entries: Vec::new(),
-            selected_scene_object: None,
-            pending_mesh_name: None,
-            rotation_icon: None,
-            scale_icon: None,
-            transform_icon: None,
-            move_icon: None,
-            move_view_mode: false,
-            last_viewport_rect: None,
-            dropped_asset_label: None,
-            mesh_status: None,
-            mesh_loading: false,
-            pending_delete_object: None,
-            import_pipeline,
-            pending_mesh_job: None,
-            next_import_job_id: 1,
-            undo_stack: Vec::new(),
-            redo_stack: Vec::new(),
-            light_yaw: 0.78,
-            light_pitch: 0.42,
-            light_color: [1.0, 1.0, 1.0],
-            light_intensity: 1.0,
-            light_enabled: true,
-            pending_gizmo_undo: false,
-            gizmo_interacting: false,
-            texture_cache: HashMap::new(),
-        };
-        s.push_undo_snapshot();
-        s
+        let line_height = 14.0;
+        // Acima da linha de ajuda dos controles do mouse
+        let overlay_rect = Rect::from_min_size(
+            egui::pos2(
+                viewport_rect.left() + 8.0,
+                viewport_rect.bottom() - 30.0 - 10.0 - line_height * lines.len() as f32,
+            ),
+            egui::vec2(176.0, 10.0 + line_height * lines.len() as f32),
+        );
+        let painter = ui.painter();
+        painter.rect_filled(
+            overlay_rect,
+            4.0,
+            theme::palette().panel_bg.gamma_multiply(0.8),
+        );
+        painter.rect_stroke(
+            overlay_rect,
+            4.0,
+            Stroke::new(1.0, theme::palette().border),
+            egui::StrokeKind::Inside,
+        );
+        for (i, line) in lines.iter().enumerate() {
+            painter.text(
+                egui::pos2(
+                    overlay_rect.left() + 8.0,
+                    overlay_rect.top() + 5.0 + line_height * i as f32,
+                ),
+                Align2::LEFT_TOP,
+                line,
+                FontId::monospace(11.0),
+                theme::palette().text,
+            );
+        }
     }
 
-    fn alloc_import_job_id(&mut self) -> u64 {
-        let id = self.next_import_job_id;
-        self.next_import_job_id = self.next_import_job_id.wrapping_add(1).max(1);
-        id
+    fn scene_entry_world_aabb(entry: &SceneEntry) -> Aabb {
+        Aabb::from_points(entry.proxy.vertices.iter().copied())
+            .unwrap_or_default()
+            .transformed(&entry.transform)
     }
 
-    pub fn contains_point(&self, p: Pos2) -> bool {
-        self.last_viewport_rect.is_some_and(|r| r.contains(p))
+    /// Picking por raio: índice espacial da cena, refinado pelos triângulos da malha.
+    fn pick_scene_entry_by_ray(&self, ray: &Ray) -> Option<String> {
+        self.raycast_scene_entries(ray, |_| true)
+            .map(|(_, i)| self.scene_entries[i].name.clone())
     }
 
-    pub fn panel_rect(&self) -> Option<Rect> {
-        self.last_viewport_rect
+    /// Entidade mais próxima atingida pelo raio entre as aceitas por `filter`:
+    /// (distância, índice em `scene_entries`). Usa o índice espacial do
+    /// começo do frame
+    fn raycast_scene_entries(
+        &self,
+        ray: &Ray,
+        filter: impl Fn(&SceneEntry) -> bool,
+    ) -> Option<(f32, usize)> {
+        let mut best: Option<(f32, usize)> = None;
+        for hit in self
+            .scene_spatial
+            .index
+            .raycast_all(ray.origin, ray.direction)
+        {
+            if best.is_some_and(|(d, _)| hit.distance >= d) {
+                break;
+            }
+            let Some(entry) = self.scene_entries.get(hit.item).filter(|e| filter(e)) else {
+                continue;
+            };
+            let mesh = if entry.full.triangles.is_empty() {
+                &entry.proxy
+            } else {
+                &entry.full
+            };
+            let distance = if mesh.triangles.is_empty() {
+                Some(hit.distance)
+            } else {
+                mesh.triangles
+                    .iter()
+                    .filter_map(|tri| {
+                        let [a, b, c] = tri.map(|i| {
+                            mesh.vertices
+                                .get(i as usize)
+                                .map(|v| entry.transform.transform_point3(*v))
+                        });
+                        ray.triangle_distance(a?, b?, c?)
+                    })
+                    .min_by(|a, b| a.total_cmp(b))
+            };
+            if let Some(d) = distance.filter(|d| best.is_none_or(|(best_d, _)| *d < best_d)) {
+                best = Some((d, hit.item));
+            }
+        }
+        best
     }
 
-    pub fn request_delete_selected_object(&mut self) {
-        if self.pending_delete_object.is_some() {
-            return;
+    /// Alvo do snap de superfície/vértice sob o cursor, ignorando os objetos
+    /// arrastados; None deixa o gizmo mover normalmente
+    fn surface_snap_point(&self, ray: &Ray) -> Option<Vec3> {
+        // Sem snap não paga os triângulos a cada frame do arraste
+        if self.snap.surface == SurfaceSnap::Off {
+            return None;
         }
-        if self.object_selected {
-            if let Some(name) = self.selected_scene_object.clone() {
-                self.pending_delete_object = Some(name);
+        // O raio vai no índice espacial do começo do frame; os filhos dos
+        // arrastados se movem junto e também ficam de fora
+        let dragged = |entry: &SceneEntry| {
+            self.selection.contains(&entry.name)
+                || self
+                    .selection
+                    .names()
+                    .iter()
+                    .any(|selected| self.is_ancestor(selected, &entry.name))
+        };
+        let hit = self.raycast_scene_entries(ray, |entry| !dragged(entry));
+        match (self.snap.surface, hit) {
+            (SurfaceSnap::Off, _) => None,
+            (SurfaceSnap::Surface, Some((distance, _))) => Some(ray.at(distance)),
+            (SurfaceSnap::Surface, None) => {
+                // Sem objeto sob o cursor, assenta no plano do chão (y = 0)
+                let t = -ray.origin.y / ray.direction.y;
+                (ray.direction.y.abs() > 1e-6 && t > 0.0).then(|| ray.at(t))
+            }
+            (SurfaceSnap::Vertex, Some((distance, i))) => {
+                let point = ray.at(distance);
+                let entry = &self.scene_entries[i];
+                let mesh = if entry.full.vertices.is_empty() {
+                    &entry.proxy
+                } else {
+                    &entry.full
+                };
+                mesh.vertices
+                    .iter()
+                    .map(|v| entry.transform.transform_point3(*v))
+                    .min_by(|a, b| {
+                        a.distance_squared(point)
+                            .total_cmp(&b.distance_squared(point))
+                    })
             }
+            (SurfaceSnap::Vertex, None) => None,
+        }
+    }
+
+    /// Seleciona o objeto clicado na viewport: Ctrl alterna e Shift acrescenta
+    /// à seleção; clicar no vazio sem modificador limpa a seleção
+    fn select_picked_object(&mut self, name: Option<String>, modifiers: Modifiers) {
+        if let Some(name) = name {
+            self.selection.click(&name, modifiers, &[]);
+            self.sync_active_selection();
+            self.dropped_asset_label = Some(name);
+        } else if !modifiers.command && !modifiers.shift {
+            self.selection.clear();
+            self.sync_active_selection();
+        }
+    }
+
+    /// Seleciona só `name`
+    fn select_only(&mut self, name: &str) {
+        self.selection.select(name);
+        self.sync_active_selection();
+    }
+
+    /// Ativo = último da seleção
+    fn sync_active_selection(&mut self) {
+        self.selected_scene_object = self.selection.active().map(str::to_string);
+        self.object_selected = self.selected_scene_object.is_some();
+    }
+
+    /// Enquadra a seleção inteira (F); sem seleção volta para a origem
+    fn focus_selected_or_origin(&mut self) {
+        let bounds = self
+            .scene_entries
+            .iter()
+            .filter(|entry| self.object_selected && self.selection.contains(&entry.name))
+            .map(Self::scene_entry_world_aabb)
+            .reduce(|a, b| Aabb {
+                min: a.min.min(b.min),
+                max: a.max.max(b.max),
+            });
+        if let Some(bounds) = bounds {
+            let center = (bounds.min + bounds.max) * 0.5;
+            let radius = ((bounds.max - bounds.min).length() * 0.5).max(0.1);
+            // Distância em que a esfera da seleção cabe no campo de visão
+            let fit = radius / (VIEWPORT_FOV_DEG.to_radians() * 0.5).sin();
+            self.camera_target = center;
+            self.camera_distance = (fit * 1.1).clamp(0.8, 80.0);
+            return;
+        }
+        self.camera_target = Vec3::ZERO;
+        self.camera_distance = self.camera_distance.clamp(0.8, 80.0);
+    }
+
+    pub fn camera_nav(&self) -> CameraNavSettings {
+        self.camera_nav
+    }
+
+    pub fn set_camera_nav(&mut self, nav: CameraNavSettings) {
+        self.camera_nav = nav;
+    }
+
+    pub fn snap_settings(&self) -> SnapSettings {
+        self.snap
+    }
+
+    pub fn set_snap_settings(&mut self, snap: SnapSettings) {
+        self.snap = snap;
+    }
+
+    /// Onde objetos novos entram: no alvo da câmera, de frente para ela,
+    /// com posição e giro no snap quando ligado
+    fn placement_transform(&self) -> Mat4 {
+        Mat4::from_translation(self.snap.snap_position(self.camera_target))
+            * Mat4::from_rotation_y(self.snap.snap_angle(self.camera_yaw + std::f32::consts::PI))
+    }
+
+    fn camera_pose(&self) -> CameraPose {
+        CameraPose {
+            yaw: self.camera_yaw,
+            pitch: self.camera_pitch,
+            distance: self.camera_distance,
+            target: self.camera_target,
+            ortho: self.is_ortho,
+        }
+    }
+
+    /// Marcadores da cena aberta (trocar de cena descarta os anteriores)
+    pub fn set_camera_bookmarks(&mut self, bookmarks: [Option<CameraPose>; CAMERA_BOOKMARK_SLOTS]) {
+        self.camera_bookmarks = bookmarks;
+        self.camera_bookmarks_dirty = false;
+    }
+
+    /// Marcadores a gravar, se algum foi salvo desde a última chamada
+    pub fn take_changed_camera_bookmarks(
+        &mut self,
+    ) -> Option<[Option<CameraPose>; CAMERA_BOOKMARK_SLOTS]> {
+        std::mem::take(&mut self.camera_bookmarks_dirty).then_some(self.camera_bookmarks)
+    }
+
+    fn save_camera_bookmark(&mut self, slot: usize) {
+        self.camera_bookmarks[slot] = Some(self.camera_pose());
+        self.camera_bookmarks_dirty = true;
+        self.mesh_status = Some(format!("Marcador de câmera {} salvo", slot + 1));
+    }
+
+    fn recall_camera_bookmark(&mut self, slot: usize) {
+        let Some(pose) = self.camera_bookmarks[slot] else {
+            self.mesh_status = Some(format!(
+                "Marcador {} vazio (Ctrl+Shift+{} salva)",
+                slot + 1,
+                slot + 1
+            ));
+            return;
+        };
+        self.camera_yaw = pose.yaw;
+        self.camera_pitch = pose.pitch;
+        self.camera_distance = pose.distance;
+        self.camera_target = pose.target;
+        self.is_ortho = pose.ortho && self.is_3d;
+    }
+
+    /// Gira a câmera mantendo o olho parado (olhar livre)
+    fn look_around(&mut self, yaw_delta: f32, pitch_delta: f32) {
+        let old_orbit = Vec3::new(
+            self.camera_yaw.cos() * self.camera_pitch.cos(),
+            self.camera_pitch.sin(),
+            self.camera_yaw.sin() * self.camera_pitch.cos(),
+        );
+        let eye = self.camera_target + old_orbit * self.camera_distance;
+        self.camera_yaw -= yaw_delta;
+        self.camera_pitch = (self.camera_pitch - pitch_delta).clamp(-1.45, 1.45);
+        let new_orbit = Vec3::new(
+            self.camera_yaw.cos() * self.camera_pitch.cos(),
+            self.camera_pitch.sin(),
+            self.camera_yaw.sin() * self.camera_pitch.cos(),
+        );
+        self.camera_target = eye - new_orbit * self.camera_distance;
+    }
+
+    /// Modo Voar: WASD anda no plano da vista, Q/E desce/sobe, Shift corre
+    fn fly_step(&mut self, ctx: &egui::Context) {
+        let (direction, run, dt) = ctx.input(|i| {
+            let axis = |positive: egui::Key, negative: egui::Key| {
+                i.key_down(positive) as i32 as f32 - i.key_down(negative) as i32 as f32
+            };
+            (
+                Vec3::new(
+                    axis(egui::Key::D, egui::Key::A),
+                    axis(egui::Key::E, egui::Key::Q),
+                    axis(egui::Key::W, egui::Key::S),
+                ),
+                i.modifiers.shift,
+                i.stable_dt,
+            )
+        });
+        if direction == Vec3::ZERO {
+            self.fly_held = 0.0;
+            return;
+        }
+        self.fly_held += dt;
+        let forward = -Vec3::new(
+            self.camera_yaw.cos() * self.camera_pitch.cos(),
+            self.camera_pitch.sin(),
+            self.camera_yaw.sin() * self.camera_pitch.cos(),
+        );
+        let right = forward.cross(Vec3::Y).normalize_or_zero();
+        let step = (right * direction.x + Vec3::Y * direction.y + forward * direction.z)
+            .normalize_or_zero();
+        let speed = self.camera_nav.fly_speed(self.fly_held) * if run { 3.0 } else { 1.0 };
+        self.camera_target += step * speed * dt;
+    }
+
+    pub fn new() -> Self {
+        let import_pipeline = AssetImportPipeline::new();
+        let mut s = Self {
+            is_3d: true,
+            is_ortho: false,
+            shading_mode: ShadingMode::Lit,
+            split: ViewportSplit::Single,
+            split_panes: [
+                SplitPane::new(AxisView::Top),
+                SplitPane::new(AxisView::Front),
+                SplitPane::new(AxisView::Right),
+            ],
+            gizmo_mode: GizmoMode::Translate,
+            gizmo_orientation: GizmoOrientation::Local,
+            model_matrix: Mat4::IDENTITY,
+            camera_yaw: 0.78,
+            camera_pitch: 0.42,
+            camera_distance: 4.8,
+            camera_target: Vec3::ZERO,
+            object_selected: false,
+            scene_entries: Vec::new(),
+            selected_scene_object: None,
+            selection: Selection::default(),
+            pending_mesh_name: None,
+            pending_imported_tree: None,
+            pending_generated_colliders: Vec::new(),
+            rotation_icon: None,
+            scale_icon: None,
+            transform_icon: None,
+            move_icon: None,
+            move_view_mode: false,
+            last_viewport_rect: None,
+            dropped_asset_label: None,
+            mesh_status: None,
+            mesh_loading: false,
+            pending_delete_objects: Vec::new(),
+            import_pipeline,
+            pending_mesh_job: None,
+            pending_mesh_source: None,
+            reload_jobs: HashMap::new(),
+            mesh_revision: 0,
+            scene_spatial: SceneSpatial::default(),
+            next_import_job_id: 1,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            history_revision: 0,
+            play_snapshot: None,
+            prefab_snapshot: None,
+            collider_gizmos: Vec::new(),
+            debug_draw: Vec::new(),
+            rig_overlay: Vec::new(),
+            scene_lights: Vec::new(),
+            environment: EnvironmentSettings::default(),
+            environment_map: None,
+            post_process: None,
+            post_lut: None,
+            anti_aliasing: AntiAliasing::default(),
+            lod_groups: HashMap::new(),
+            lod_meshes: HashMap::new(),
+            particle_emitters: Vec::new(),
+            terrains: HashMap::new(),
+            terrain_brush: TerrainBrush::default(),
+            terrain_tool: false,
+            terrain_stroke: false,
+            measure: MeasureTool::default(),
+            water_surfaces: Vec::new(),
+            sprites: Vec::new(),
+            sprite_atlases: HashMap::new(),
+            sprite_assets_dir: PathBuf::from("Assets"),
+            texts: Vec::new(),
+            ui_texts: Vec::new(),
+            text_fonts: HashMap::new(),
+            water_enabled: true,
+            game_cameras: Vec::new(),
+            camera_nav: CameraNavSettings::default(),
+            snap: SnapSettings::default(),
+            camera_bookmarks: [None; CAMERA_BOOKMARK_SLOTS],
+            camera_bookmarks_dirty: false,
+            fly_held: 0.0,
+            show_stats: false,
+            stats: ViewportStats::default(),
+            game_view: false,
+            game_aspect: GameAspect::default(),
+            pixel_size: [0, 0],
+            pending_surface_pick: None,
+            pending_pick_modifiers: Modifiers::NONE,
+            material_drop_request: None,
+            pending_material_pick: None,
+            material_drop: None,
+            gpu_pick_names: Vec::new(),
+            light_yaw: 0.78,
+            light_pitch: 0.42,
+            light_color: [1.0, 1.0, 1.0],
+            light_intensity: 1.0,
+            light_enabled: true,
+            pending_gizmo_undo: false,
+            gizmo_interacting: false,
+            texture_cache: HashMap::new(),
+        };
+        s.push_undo_snapshot();
+        s
+    }
+
+    fn alloc_import_job_id(&mut self) -> u64 {
+        let id = self.next_import_job_id;
+        self.next_import_job_id = self.next_import_job_id.wrapping_add(1).max(1);
+        id
+    }
+
+    pub fn contains_point(&self, p: Pos2) -> bool {
+        self.last_viewport_rect.is_some_and(|r| r.contains(p))
+    }
+
+    pub fn panel_rect(&self) -> Option<Rect> {
+        self.last_viewport_rect
+    }
+
+    pub fn request_delete_selected_object(&mut self) {
+        if !self.pending_delete_objects.is_empty() {
+            return;
+        }
+        if self.object_selected {
+            self.pending_delete_objects = self.selection.names().to_vec();
         }
     }
 
-    pub fn take_pending_delete_object(&mut self) -> Option<String> {
-        self.pending_delete_object.take()
+    pub fn take_pending_delete_objects(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.pending_delete_objects)
     }
 
-    pub fn selected_object_name(&self) -> Option<&str> {
-        self.selected_scene_object.as_deref()
+    pub fn selection(&self) -> &Selection {
+        &self.selection
     }
 
     pub fn scene_object_names(&self) -> Vec<String> {
         self.scene_entries.iter().map(|o| o.name.clone()).collect()
     }
 
-    fn gpu_scene_mesh_id(&self, use_proxy: bool) -> u64 {
+    /// Objetos do lote da GPU; terrenos e água têm pipelines próprios
+    fn batched_entries(&self) -> impl Iterator<Item = &SceneEntry> {
+        let water_active = self.water_active();
+        self.scene_entries.iter().filter(move |entry| {
+            !self.terrains.contains_key(&entry.name)
+                && !(water_active && self.water_surfaces.iter().any(|(n, _)| n == &entry.name))
+        })
+    }
+
+    /// Há superfícies de água e o recurso está ligado (força a cena offscreen)
+    fn water_active(&self) -> bool {
+        self.water_enabled && !self.water_surfaces.is_empty()
+    }
+
+    fn gpu_scene_mesh_id(&self, use_proxy: bool, eye: Vec3) -> u64 {
         let mut hasher = DefaultHasher::new();
         use_proxy.hash(&mut hasher);
+        self.mesh_revision.hash(&mut hasher);
         self.scene_entries.len().hash(&mut hasher);
-        for entry in &self.scene_entries {
+        for entry in self.batched_entries() {
             entry.name.hash(&mut hasher);
-            let mesh = if use_proxy { &entry.proxy } else { &entry.full };
+            let Some(mesh) = self.entry_mesh(entry, use_proxy, eye) else {
+                continue;
+            };
+            mesh.name.hash(&mut hasher);
             mesh.vertices.len().hash(&mut hasher);
             mesh.triangles.len().hash(&mut hasher);
             for col in entry.transform.to_cols_array_2d() {
@@ -512,7 +1680,13 @@ impl ViewportPanel {
         hasher.finish().max(1)
     }
 
-    fn build_gpu_scene_mesh(&self, use_proxy: bool) -> (MeshData, bool) {
+    /// Junta os objetos num único lote; também devolve a faixa de triângulos de cada
+    /// objeto (usada pelo picking na GPU)
+    fn build_gpu_scene_mesh(
+        &self,
+        use_proxy: bool,
+        eye: Vec3,
+    ) -> (MeshData, bool, Vec<(String, std::ops::Range<u32>)>) {
         let mut vertices: Vec<Vec3> = Vec::new();
         let mut normals: Vec<Vec3> = Vec::new();
         let mut uvs: Vec<[f32; 2]> = Vec::new();
@@ -520,10 +1694,14 @@ impl ViewportPanel {
         let mut texture_path: Option<String> = None;
         let mut texture_conflict = false;
         let mut unique_texture: Option<String> = None;
+        let mut object_ranges = Vec::with_capacity(self.scene_entries.len());
 
-        for entry in &self.scene_entries {
-            let mesh = if use_proxy { &entry.proxy } else { &entry.full };
+        for entry in self.batched_entries() {
+            let Some(mesh) = self.entry_mesh(entry, use_proxy, eye) else {
+                continue;
+            };
             let base = vertices.len() as u32;
+            let first_triangle = triangles.len() as u32;
             vertices.extend(
                 mesh.vertices
                     .iter()
@@ -552,6 +1730,7 @@ impl ViewportPanel {
                     triangles.push([base + tri[0], base + tri[1], base + tri[2]]);
                 }
             }
+            object_ranges.push((entry.name.clone(), first_triangle..triangles.len() as u32));
             let entry_texture = mesh.texture_path.clone().or_else(|| {
                 mesh.material_path
                     .as_ref()
@@ -582,17 +1761,15 @@ impl ViewportPanel {
             texture_path,
             material_path: None,
         };
-        (mesh_summary, texture_conflict)
+        (mesh_summary, texture_conflict, object_ranges)
     }
 
-    pub fn set_selected_object(&mut self, object_name: &str) {
-        if self.scene_entries.iter().any(|o| o.name == object_name) {
-            self.selected_scene_object = Some(object_name.to_string());
-            self.object_selected = true;
-        } else {
-            self.selected_scene_object = None;
-            self.object_selected = false;
-        }
+    /// Seleção vinda da hierarquia; objetos sem malha na cena ficam de fora
+    pub fn set_selection(&mut self, selection: &Selection) {
+        let mut selection = selection.clone();
+        selection.retain(|name| self.scene_entries.iter().any(|o| o.name == name));
+        self.selection = selection;
+        self.sync_active_selection();
     }
 
     pub fn remove_scene_object(&mut self, object_name: &str) -> bool {
@@ -605,17 +1782,189 @@ impl ViewportPanel {
         };
         self.push_undo_snapshot();
         self.scene_entries.remove(idx);
-        if self
-            .selected_scene_object
-            .as_ref()
-            .is_some_and(|n| n == object_name)
-        {
-            self.selected_scene_object = None;
-            self.object_selected = false;
+        if self.selection.contains(object_name) {
+            self.selection.retain(|name| name != object_name);
+            self.sync_active_selection();
+        }
+        true
+    }
+
+    pub fn object_matrix(&self, object_name: &str) -> Option<Mat4> {
+        self.scene_entries
+            .iter()
+            .find(|o| o.name == object_name)
+            .map(|o| o.transform)
+    }
+
+    /// Arquivo importado de onde veio a malha do objeto
+    pub fn object_source(&self, object_name: &str) -> Option<PathBuf> {
+        self.scene_entries
+            .iter()
+            .find(|o| o.name == object_name)
+            .and_then(|o| o.source.clone())
+    }
+
+    /// Transform do objeto relativo ao pai
+    pub fn object_local_matrix(&self, object_name: &str) -> Option<Mat4> {
+        self.scene_entries
+            .iter()
+            .find(|o| o.name == object_name)
+            .map(|o| o.local)
+    }
+
+    pub fn object_parent(&self, object_name: &str) -> Option<&str> {
+        self.scene_entries
+            .iter()
+            .find(|o| o.name == object_name)
+            .and_then(|o| o.parent.as_deref())
+    }
+
+    /// Coloca o objeto em `local` (relativo ao pai) sem selecionar nem
+    /// empilhar desfazer (cena carregada de arquivo, Stop do Play)
+    pub fn place_object(&mut self, object_name: &str, local: Mat4) -> bool {
+        let Some(idx) = self.entry_index(object_name) else {
+            return false;
+        };
+        self.scene_entries[idx].local = local;
+        self.scene_entries[idx].transform = self.parent_world(idx) * local;
+        self.refresh_children(object_name, 0);
+        if self.selected_scene_object.as_deref() == Some(object_name) {
+            self.model_matrix = self.scene_entries[idx].transform;
+        }
+        true
+    }
+
+    /// Prende o objeto a `parent` (None = solta). Com `keep_world` ele fica onde
+    /// está e o local é recalculado; sem, o local é mantido e ele segue o pai
+    pub fn set_object_parent(
+        &mut self,
+        object_name: &str,
+        parent: Option<&str>,
+        keep_world: bool,
+    ) -> bool {
+        let Some(idx) = self.entry_index(object_name) else {
+            return false;
+        };
+        let parent = parent.filter(|p| *p != object_name && !self.is_ancestor(object_name, p));
+        if self.scene_entries[idx].parent.as_deref() == parent {
+            return false;
+        }
+        self.scene_entries[idx].parent = parent.map(str::to_string);
+        if keep_world {
+            self.commit_world_transform(idx);
+        } else {
+            let local = self.scene_entries[idx].local;
+            self.scene_entries[idx].transform = self.parent_world(idx) * local;
+            self.refresh_children(object_name, 0);
+        }
+        if self.selected_scene_object.as_deref() == Some(object_name) {
+            self.model_matrix = self.scene_entries[idx].transform;
         }
         true
     }
 
+    fn entry_index(&self, object_name: &str) -> Option<usize> {
+        self.scene_entries
+            .iter()
+            .position(|o| o.name == object_name)
+    }
+
+    /// `ancestor` está acima de `object_name` na cadeia de pais
+    fn is_ancestor(&self, ancestor: &str, object_name: &str) -> bool {
+        let mut current = object_name;
+        // O limite evita laço infinito se a cadeia vier corrompida
+        for _ in 0..self.scene_entries.len() {
+            let Some(parent) = self.object_parent(current) else {
+                return false;
+            };
+            if parent == ancestor {
+                return true;
+            }
+            current = parent;
+        }
+        false
+    }
+
+    /// Transform de mundo do pai (identidade sem pai ou com o pai removido)
+    fn parent_world(&self, idx: usize) -> Mat4 {
+        self.scene_entries[idx]
+            .parent
+            .as_deref()
+            .and_then(|p| self.object_matrix(p))
+            .unwrap_or(Mat4::IDENTITY)
+    }
+
+    /// Depois de mudar o transform de mundo de `idx`: recalcula o local dele
+    /// e recompõe os descendentes, que acompanham o movimento
+    fn commit_world_transform(&mut self, idx: usize) {
+        let parent = self.parent_world(idx);
+        let entry = &mut self.scene_entries[idx];
+        if parent.determinant().abs() > f32::EPSILON {
+            entry.local = parent.inverse() * entry.transform;
+        }
+        let name = entry.name.clone();
+        self.refresh_children(&name, 0);
+    }
+
+    /// Mundo dos filhos de `object_name` = mundo dele * local de cada filho
+    fn refresh_children(&mut self, object_name: &str, depth: usize) {
+        if depth > self.scene_entries.len() {
+            return;
+        }
+        let Some(world) = self.object_matrix(object_name) else {
+            return;
+        };
+        let mut children = Vec::new();
+        for entry in &mut self.scene_entries {
+            if entry.parent.as_deref() == Some(object_name) {
+                entry.transform = world * entry.local;
+                children.push(entry.name.clone());
+            }
+        }
+        for child in children {
+            self.refresh_children(&child, depth + 1);
+        }
+    }
+
+    /// Filhos ficam onde estão quando o pai muda de referencial (transform
+    /// aplicado nos vértices)
+    fn rebase_children(&mut self, object_name: &str) {
+        let world = self.object_matrix(object_name).unwrap_or(Mat4::IDENTITY);
+        if world.determinant().abs() <= f32::EPSILON {
+            return;
+        }
+        let inverse = world.inverse();
+        for entry in &mut self.scene_entries {
+            if entry.parent.as_deref() == Some(object_name) {
+                entry.local = inverse * entry.transform;
+            }
+        }
+    }
+
+    /// Objeto de uma cena carregada de arquivo: a malha é lida na hora (do
+    /// cache .vmesh quando existe) para os objetos chegarem todos juntos
+    pub fn spawn_scene_mesh(
+        &mut self,
+        object_name: &str,
+        path: &Path,
+        transform: Mat4,
+    ) -> Result<(), String> {
+        let asset = load_viewport_mesh_asset_cached(path)?;
+        let proxy = make_proxy_mesh(&asset.full, VIEWPORT_NAV_TRIANGLES, VIEWPORT_NAV_VERTICES);
+        self.scene_entries.retain(|e| e.name != object_name);
+        self.scene_entries.push(SceneEntry {
+            name: object_name.to_string(),
+            transform,
+            local: transform,
+            parent: None,
+            full: asset.full,
+            proxy,
+            source: Some(path.to_path_buf()),
+        });
+        self.queue_generated_collider(object_name);
+        Ok(())
+    }
+
     pub fn object_transform_components(
         &self,
         object_name: &str,
@@ -672,9 +2021,15 @@ impl ViewportPanel {
             }
             entry.transform = Mat4::IDENTITY;
         }
+        let parent = self.parent_world(idx);
+        if parent.determinant().abs() > f32::EPSILON {
+            self.scene_entries[idx].local = parent.inverse();
+        }
+        self.rebase_children(object_name);
         self.model_matrix = Mat4::IDENTITY;
-        self.object_selected = true;
-        self.selected_scene_object = Some(object_name.to_string());
+        if !self.selection.contains(object_name) {
+            self.select_only(object_name);
+        }
         self.mesh_status = Some("Transformacoes aplicadas e zeradas".to_string());
         true
     }
@@ -707,12 +2062,104 @@ impl ViewportPanel {
             return false;
         }
         self.scene_entries[idx].transform = new_transform;
+        self.commit_world_transform(idx);
         self.model_matrix = new_transform;
-        self.object_selected = true;
-        self.selected_scene_object = Some(object_name.to_string());
+        if !self.selection.contains(object_name) {
+            self.select_only(object_name);
+        }
+        true
+    }
+
+    /// Pose de uma animação sendo editada: só o transform, sem selecionar o
+    /// objeto nem empilhar desfazer
+    pub fn pose_object_transform(
+        &mut self,
+        object_name: &str,
+        position: [f32; 3],
+        rotation_deg: [f32; 3],
+        scale: [f32; 3],
+    ) -> bool {
+        let Some(idx) = self.entry_index(object_name) else {
+            return false;
+        };
+        let new_transform = Mat4::from_scale_rotation_translation(
+            Vec3::from_array(scale),
+            Quat::from_euler(
+                EulerRot::XYZ,
+                rotation_deg[0].to_radians(),
+                rotation_deg[1].to_radians(),
+                rotation_deg[2].to_radians(),
+            ),
+            Vec3::from_array(position),
+        );
+        if self.scene_entries[idx].transform == new_transform {
+            return false;
+        }
+        self.scene_entries[idx].transform = new_transform;
+        self.commit_world_transform(idx);
+        if self.selected_scene_object.as_deref() == Some(object_name) {
+            self.model_matrix = new_transform;
+        }
+        true
+    }
+
+    /// Como `set_object_transform_components`, levando junto os demais
+    /// objetos selecionados com o mesmo movimento
+    pub fn set_selection_transform_components(
+        &mut self,
+        object_name: &str,
+        position: [f32; 3],
+        rotation_deg: [f32; 3],
+        scale: [f32; 3],
+    ) -> bool {
+        let Some(old) = self.object_transform(object_name) else {
+            return false;
+        };
+        if !self.set_object_transform_components(object_name, position, rotation_deg, scale) {
+            return false;
+        }
+        if let Some(new) = self.object_transform(object_name) {
+            self.move_selection_with(object_name, old, new);
+        }
         true
     }
 
+    fn object_transform(&self, object_name: &str) -> Option<Mat4> {
+        self.scene_entries
+            .iter()
+            .find(|o| o.name == object_name)
+            .map(|o| o.transform)
+    }
+
+    /// Aplica aos selecionados (menos `leader`) a mudança `old` -> `new` do
+    /// transform de `leader`, no espaço do mundo
+    fn move_selection_with(&mut self, leader: &str, old: Mat4, new: Mat4) {
+        // Escala zero não tem inversa: não há movimento a repetir
+        if old.determinant().abs() <= f32::EPSILON {
+            return;
+        }
+        let delta = new * old.inverse();
+        // Quem tem um ancestral selecionado já anda junto com ele
+        let moved: Vec<usize> = (0..self.scene_entries.len())
+            .filter(|&idx| {
+                let name = &self.scene_entries[idx].name;
+                name != leader
+                    && self.selection.contains(name)
+                    && !self.is_ancestor(leader, name)
+                    && !self
+                        .selection
+                        .names()
+                        .iter()
+                        .any(|other| self.is_ancestor(other, name))
+            })
+            .collect();
+        for idx in moved {
+            let entry = &mut self.scene_entries[idx];
+            entry.transform = delta * entry.transform;
+            self.commit_world_transform(idx);
+        }
+    }
+
     pub fn move_object_by(&mut self, object_name: &str, delta: [f32; 3]) -> bool {
         let Some(idx) = self
             .scene_entries
@@ -731,6 +2178,7 @@ impl ViewportPanel {
         let next_t = translation + d;
         self.scene_entries[idx].transform =
             Mat4::from_scale_rotation_translation(scale, rotation, next_t);
+        self.commit_world_transform(idx);
         true
     }
 
@@ -757,15 +2205,13 @@ impl ViewportPanel {
         );
         self.scene_entries[idx].transform =
             Mat4::from_scale_rotation_translation(scale, rotation * dq, translation);
+        self.commit_world_transform(idx);
         true
     }
 
-    pub fn can_undo(&self) -> bool {
-        !self.undo_stack.is_empty()
-    }
-
-    pub fn can_redo(&self) -> bool {
-        !self.redo_stack.is_empty()
+    /// Cresce a cada passo empilhado por `push_undo_snapshot`
+    pub fn history_revision(&self) -> u64 {
+        self.history_revision
     }
 
     pub fn undo(&mut self) {
@@ -788,6 +2234,7 @@ impl ViewportPanel {
         ViewportSnapshot {
             scene_entries: self.scene_entries.clone(),
             selected_scene_object: self.selected_scene_object.clone(),
+            selection: self.selection.clone(),
             object_selected: self.object_selected,
             dropped_asset_label: self.dropped_asset_label.clone(),
         }
@@ -795,22 +2242,1004 @@ impl ViewportPanel {
 
     fn apply_snapshot(&mut self, snap: ViewportSnapshot) {
         self.scene_entries = snap.scene_entries;
+        // As malhas podem ter voltado com o mesmo nome
+        self.scene_spatial = SceneSpatial::default();
         self.selected_scene_object = snap.selected_scene_object;
+        self.selection = snap.selection;
         self.object_selected = snap.object_selected;
         self.dropped_asset_label = snap.dropped_asset_label;
         self.mesh_status = Some("Historico aplicado".to_string());
     }
 
-    fn push_undo_snapshot(&mut self) {
-        let snap = self.snapshot();
-        if self.undo_stack.last().is_some_and(|s| s == &snap) {
+    fn stash_scene(&self) -> PlayModeSnapshot {
+        PlayModeSnapshot {
+            scene: self.snapshot(),
+            undo_stack: self.undo_stack.clone(),
+            redo_stack: self.redo_stack.clone(),
+            light: (
+                self.light_yaw,
+                self.light_pitch,
+                self.light_color,
+                self.light_intensity,
+                self.light_enabled,
+            ),
+        }
+    }
+
+    fn restore_stash(&mut self, snap: PlayModeSnapshot) {
+        self.apply_snapshot(snap.scene);
+        self.undo_stack = snap.undo_stack;
+        self.redo_stack = snap.redo_stack;
+        (
+            self.light_yaw,
+            self.light_pitch,
+            self.light_color,
+            self.light_intensity,
+            self.light_enabled,
+        ) = snap.light;
+        self.mesh_status = Some("Cena restaurada".to_string());
+    }
+
+    /// Guarda o estado de edicao ao entrar no modo Play (ignora se ja estiver em Play/Pause)
+    pub fn enter_play_mode(&mut self) {
+        if self.play_snapshot.is_some() {
             return;
         }
-        self.undo_stack.push(snap);
-        if self.undo_stack.len() > 64 {
+        self.play_snapshot = Some(self.stash_scene());
+    }
+
+    /// Descarta as alteracoes feitas durante o Play e volta ao estado de edicao
+    pub fn exit_play_mode(&mut self) {
+        if let Some(snap) = self.play_snapshot.take() {
+            self.restore_stash(snap);
+        }
+    }
+
+    pub fn in_play_mode(&self) -> bool {
+        self.play_snapshot.is_some()
+    }
+
+    /// Edição de prefab: guarda a cena e deixa a viewport vazia, com histórico
+    /// próprio, para só o prefab aparecer
+    pub fn enter_prefab_mode(&mut self) {
+        if self.prefab_snapshot.is_some() {
+            return;
+        }
+        self.prefab_snapshot = Some(self.stash_scene());
+        self.scene_entries.clear();
+        self.selection = Selection::default();
+        self.selected_scene_object = None;
+        self.object_selected = false;
+        self.dropped_asset_label = None;
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+    }
+
+    /// Volta para a cena guardada; o que foi feito no prefab é descartado
+    pub fn exit_prefab_mode(&mut self) {
+        if let Some(snap) = self.prefab_snapshot.take() {
+            self.restore_stash(snap);
+        }
+    }
+
+    pub fn in_prefab_mode(&self) -> bool {
+        self.prefab_snapshot.is_some()
+    }
+
+    /// Colliders (em unidades de mundo) desenhados como wireframe sobre os objetos
+    pub fn set_collider_gizmos(&mut self, gizmos: Vec<(String, Collider)>) {
+        self.collider_gizmos = gizmos;
+    }
+
+    /// Formas de debug (linhas, caixas, esferas, setas, textos) do frame atual
+    pub fn set_debug_draw(&mut self, commands: Vec<DebugCommand>) {
+        self.debug_draw = commands;
+    }
+
+    /// Ossos do editor de rig (cabeça e ponta em mundo), desenhados por cima da cena
+    pub fn set_rig_overlay(&mut self, bones: Vec<(Vec3, Vec3, bool)>) {
+        self.rig_overlay = bones;
+    }
+
+    /// Luzes dos objetos da cena (pontuais, spot e direcionais extras)
+    pub fn set_scene_lights(&mut self, lights: Vec<(String, inspector::LightDraft)>) {
+        self.scene_lights = lights;
+    }
+
+    /// Céu da cena ativa; `map` é o panorama já carregado de `settings.environment`
+    pub fn set_environment(&mut self, settings: EnvironmentSettings, map: Option<EnvironmentMap>) {
+        self.environment = settings;
+        self.environment_map = map;
+    }
+
+    /// Pós-processamento da câmera; `lut` é a textura de `settings.color_lut`
+    pub fn set_post_process(
+        &mut self,
+        settings: Option<PostProcessSettings>,
+        lut: Option<Arc<TextureData>>,
+    ) {
+        self.post_process = settings;
+        self.post_lut = lut;
+    }
+
+    /// Anti-aliasing da viewport (edição e Play usam o mesmo modo)
+    pub fn set_anti_aliasing(&mut self, mode: AntiAliasing) {
+        self.anti_aliasing = mode;
+    }
+
+    /// Emissores de partículas dos objetos, simulados na GPU como preview na viewport
+    pub fn set_particle_emitters(&mut self, emitters: Vec<(String, inspector::ParticleDraft)>) {
+        self.particle_emitters = emitters
+            .into_iter()
+            .map(|(name, draft)| (name, draft.to_emitter()))
+            .collect();
+    }
+
+    pub fn set_water_surfaces(&mut self, waters: Vec<(String, inspector::WaterDraft)>) {
+        self.water_surfaces = waters
+            .into_iter()
+            .map(|(name, draft)| (name, draft.to_surface()))
+            .collect();
+    }
+
+    /// Sprites dos objetos; os de textura empacotada num atlas são desenhados da
+    /// página, com o UV remapeado (como `SpriteAtlases::remap` no jogo)
+    pub fn set_sprites(&mut self, sprites: Vec<(String, inspector::SpriteDraft)>) {
+        let sprites = sprites
+            .into_iter()
+            .map(|(name, draft)| {
+                let mut sprite = draft.to_sprite();
+                let mut texture = draft.texture_path.trim().to_string();
+                if let Some((page, [a0, b0, a1, b1])) = self.atlas_region(&texture) {
+                    let [u0, v0, u1, v1] = sprite.uv_rect;
+                    sprite.uv_rect = [
+                        a0 + u0 * (a1 - a0),
+                        b0 + v0 * (b1 - b0),
+                        a0 + u1 * (a1 - a0),
+                        b0 + v1 * (b1 - b0),
+                    ];
+                    texture = page.clone();
+                }
+                (name, sprite, texture)
+            })
+            .collect();
+        self.sprites = sprites;
+    }
+
+    /// Textos 3D dos objetos
+    pub fn set_texts(&mut self, texts: Vec<(String, inspector::Text3DDraft)>) {
+        self.texts = texts
+            .into_iter()
+            .map(|(name, draft)| {
+                let font = draft.font_path.trim().to_string();
+                (name, draft.to_text3d(), font)
+            })
+            .collect();
+        self.load_text_fonts();
+    }
+
+    /// Texto da UI do jogo dos objetos, mostrado sobre a viewport no Play
+    pub fn set_ui_texts(&mut self, texts: Vec<(String, inspector::UiTextDraft)>) {
+        self.ui_texts = texts
+            .into_iter()
+            .map(|(name, draft)| {
+                let font = draft.font_path.trim().to_string();
+                (name, draft.to_ui_text(), font)
+            })
+            .collect();
+        self.load_text_fonts();
+    }
+
+    /// Carrega (e gera o atlas SDF) uma vez cada fonte usada pelos textos e
+    /// descarta as que nenhum texto usa mais
+    fn load_text_fonts(&mut self) {
+        let used: Vec<&String> = self
+            .texts
+            .iter()
+            .map(|(_, _, font)| font)
+            .chain(self.ui_texts.iter().map(|(_, _, font)| font))
+            .filter(|font| !font.is_empty())
+            .collect();
+        self.text_fonts.retain(|path, _| used.contains(&path));
+        for path in used {
+            if self.text_fonts.contains_key(path) {
+                continue;
+            }
+            let font = match FontAsset::load(Path::new(path)) {
+                Ok(font) => {
+                    let atlas = Arc::new(font.to_texture_data());
+                    Some((font, atlas))
+                }
+                Err(e) => {
+                    self.mesh_status = Some(format!("Fonte {path}: {e}"));
+                    None
+                }
+            };
+            self.text_fonts.insert(path.clone(), font);
+        }
+    }
+
+    /// Atlas do projeto (arquivo `.atlas` e conteúdo), lidos da pasta `assets`
+    pub fn set_sprite_atlases(&mut self, assets: PathBuf, atlases: &[(PathBuf, SpriteAtlas)]) {
+        self.sprite_atlases = atlases
+            .iter()
+            .flat_map(|(path, atlas)| {
+                let page = atlas.image_path(path).to_string_lossy().to_string();
+                atlas.regions.iter().map(move |region| {
+                    (region.sprite.clone(), (page.clone(), atlas.uv_rect(region)))
+                })
+            })
+            .collect();
+        self.sprite_assets_dir = assets;
+    }
+
+    /// Página e UV do atlas em que a textura foi empacotada
+    fn atlas_region(&self, texture: &str) -> Option<&(String, [f32; 4])> {
+        let path = Path::new(texture);
+        let relative = path.strip_prefix(&self.sprite_assets_dir).unwrap_or(path);
+        self.sprite_atlases
+            .get(&bundle_key(&relative.to_string_lossy()))
+    }
+
+    pub fn set_water_enabled(&mut self, enabled: bool) {
+        self.water_enabled = enabled;
+    }
+
+    pub fn set_game_cameras(&mut self, cameras: Vec<(String, Camera)>) {
+        self.game_cameras = cameras;
+    }
+
+    pub fn set_game_view(&mut self, game_view: bool) {
+        self.game_view = game_view;
+    }
+
+    pub fn game_aspect(&self) -> GameAspect {
+        self.game_aspect
+    }
+
+    pub fn set_game_aspect(&mut self, aspect: GameAspect) {
+        self.game_aspect = aspect;
+    }
+
+    /// Olha ao longo de um eixo em projeção ortográfica (blocking de nível e 2D)
+    fn set_axis_view(&mut self, view: AxisView) {
+        (self.camera_yaw, self.camera_pitch) = view.yaw_pitch();
+        if self.is_3d {
+            self.is_ortho = true;
+        }
+    }
+
+    /// Meia altura visível em ortho: a mesma que a perspectiva mostra no alvo,
+    /// então o zoom (distância) continua funcionando
+    fn ortho_half_height(&self) -> f32 {
+        self.camera_distance * (VIEWPORT_FOV_DEG * 0.5).to_radians().tan()
+    }
+
+    /// Direção da luz global da cena (yaw/pitch do Inspector)
+    fn light_direction(&self) -> Vec3 {
+        Vec3::new(
+            self.light_yaw.cos() * self.light_pitch.cos(),
+            self.light_pitch.sin(),
+            self.light_yaw.sin() * self.light_pitch.cos(),
+        )
+    }
+
+    /// Tamanho da cena em pixels no último frame ([0, 0] antes do primeiro)
+    pub fn pixel_size(&self) -> [u32; 2] {
+        self.pixel_size
+    }
+
+    /// No modo Game: a câmera de tela com maior ordem (empate decide pelo nome)
+    /// e a sua transformação, sem escala
+    fn active_game_camera(&self) -> Option<(Camera, Transform)> {
+        if !self.game_view {
+            return None;
+        }
+        self.game_cameras
+            .iter()
+            .filter(|(_, camera)| camera.is_screen_camera())
+            .filter_map(|(name, camera)| {
+                let entry = self.scene_entries.iter().find(|e| &e.name == name)?;
+                let (_, rotation, translation) = entry.transform.to_scale_rotation_translation();
+                Some((
+                    name,
+                    *camera,
+                    Transform::new(translation, rotation, Vec3::ONE),
+                ))
+            })
+            .max_by(|a, b| a.1.order.cmp(&b.1.order).then_with(|| b.0.cmp(a.0)))
+            .map(|(_, camera, transform)| (camera, transform))
+    }
+
+    /// Terrenos dos objetos; heightmap e splat map só são relidos quando a
+    /// resolução ou os caminhos mudam, para não perder o que foi esculpido
+    pub fn set_terrains(&mut self, terrains: Vec<(String, inspector::TerrainDraft)>) {
+        let removed: Vec<String> = self
+            .terrains
+            .keys()
+            .filter(|name| !terrains.iter().any(|(n, _)| n == *name))
+            .cloned()
+            .collect();
+        for name in removed {
+            let Some(instance) = self.terrains.remove(&name) else {
+                continue;
+            };
+            let entry = self.scene_entries.iter_mut().find(|e| e.name == name);
+            if let (Some((full, proxy)), Some(entry)) = (instance.original, entry) {
+                entry.full = full;
+                entry.proxy = proxy;
+            }
+        }
+        for (name, draft) in terrains {
+            let reload = self.terrains.get(&name).is_none_or(|t| {
+                t.draft.resolution != draft.resolution
+                    || t.draft.heightmap_path != draft.heightmap_path
+                    || t.draft.splatmap_path != draft.splatmap_path
+            });
+            if reload {
+                let original = self.terrains.remove(&name).and_then(|t| t.original);
+                self.terrains.insert(
+                    name.clone(),
+                    TerrainInstance {
+                        draft: draft.clone(),
+                        terrain: load_terrain(&draft),
+                        original,
+                        synced_height_version: 0,
+                        gpu_mesh: None,
+                        gpu_splat: None,
+                    },
+                );
+            }
+            let Some(instance) = self.terrains.get_mut(&name) else {
+                continue;
+            };
+            instance
+                .terrain
+                .set_dimensions(draft.size, draft.height_scale);
+            instance.terrain.lod_distance = draft.lod_distance;
+            instance.terrain.layers = draft.to_layers();
+            instance.draft = draft;
+        }
+        self.sync_terrain_meshes();
+    }
+
+    /// Troca as malhas dos objetos com terreno pela malha do terreno (picking,
+    /// foco e desenho sem GPU usam as malhas do objeto)
+    fn sync_terrain_meshes(&mut self) {
+        for (name, instance) in &mut self.terrains {
+            let Some(entry) = self.scene_entries.iter_mut().find(|e| &e.name == name) else {
+                continue;
+            };
+            let is_terrain_mesh = entry.full.name == TERRAIN_MESH_NAME;
+            if is_terrain_mesh
+                && instance.synced_height_version == instance.terrain.height_version()
+            {
+                continue;
+            }
+            if !is_terrain_mesh && instance.original.is_none() {
+                instance.original = Some((entry.full.clone(), entry.proxy.clone()));
+            }
+            entry.full = terrain_mesh_data(instance.terrain.build_uniform_mesh(1));
+            entry.proxy = terrain_mesh_data(instance.terrain.build_uniform_mesh(3));
+            instance.synced_height_version = instance.terrain.height_version();
+            self.scene_spatial.invalidate(name);
+        }
+    }
+
+    /// Ponto da régua sob o raio: a superfície atingida ou o chão (y = 0); com
+    /// `pivot`, o pivô do objeto atingido
+    fn measure_pick(&self, ray: &Ray, pivot: bool) -> Option<MeasurePoint> {
+        match self.raycast_scene_entries(ray, |_| true) {
+            Some((_, i)) if pivot => {
+                let entry = &self.scene_entries[i];
+                Some(MeasurePoint {
+                    position: entry.transform.w_axis.truncate(),
+                    entity: Some(entry.name.clone()),
+                })
+            }
+            Some((distance, _)) => Some(MeasurePoint {
+                position: ray.at(distance),
+                entity: None,
+            }),
+            None => {
+                let t = -ray.origin.y / ray.direction.y;
+                (ray.direction.y.abs() > 1e-6 && t > 0.0).then(|| MeasurePoint {
+                    position: ray.at(t),
+                    entity: None,
+                })
+            }
+        }
+    }
+
+    /// Posição atual do ponto (o pivô, se o objeto ainda existe)
+    fn measure_position(&self, point: &MeasurePoint) -> Vec3 {
+        point
+            .entity
+            .as_ref()
+            .and_then(|name| self.scene_entries.iter().find(|e| &e.name == name))
+            .map(|entry| entry.transform.w_axis.truncate())
+            .unwrap_or(point.position)
+    }
+
+    /// Régua como formas de debug, desenhadas na vista principal e nos painéis.
+    /// Com só o primeiro ponto marcado, `hover` estende a régua até o cursor
+    fn measure_overlay(&self, hover: Option<&MeasurePoint>) -> Vec<DebugCommand> {
+        if !self.measure.active {
+            return Vec::new();
+        }
+        let command = |shape, color| DebugCommand {
+            shape,
+            color,
+            remaining: 0.0,
+        };
+        let mut points: Vec<Vec3> = self
+            .measure
+            .points
+            .iter()
+            .map(|point| self.measure_position(point))
+            .collect();
+        if points.len() == 1 {
+            points.extend(hover.map(|point| self.measure_position(point)));
+        }
+        let mut commands: Vec<DebugCommand> = points
+            .iter()
+            .map(|&center| {
+                command(
+                    DebugShape::Sphere {
+                        center,
+                        radius: 0.05,
+                    },
+                    MEASURE_COLOR,
+                )
+            })
+            .collect();
+        let &[a, b] = points.as_slice() else {
+            return commands;
+        };
+        let delta = b - a;
+        let horizontal = delta.x.hypot(delta.z);
+        let slope = delta.y.atan2(horizontal).to_degrees();
+        let corner = Vec3::new(b.x, a.y, b.z);
+        commands.push(command(
+            DebugShape::Line { start: a, end: b },
+            MEASURE_COLOR,
+        ));
+        commands.push(command(
+            DebugShape::Line {
+                start: a,
+                end: corner,
+            },
+            MEASURE_LEG_COLOR,
+        ));
+        commands.push(command(
+            DebugShape::Line {
+                start: corner,
+                end: b,
+            },
+            MEASURE_LEG_COLOR,
+        ));
+        commands.push(command(
+            DebugShape::Text {
+                position: (a + b) * 0.5,
+                text: format!(
+                    "{:.2} m\nhoriz. {horizontal:.2} m · Δy {:+.2} m · {slope:.1}°",
+                    delta.length(),
+                    delta.y
+                ),
+            },
+            MEASURE_COLOR,
+        ));
+        commands
+    }
+
+    /// Objeto selecionado quando a ferramenta de terreno pode agir sobre ele
+    fn terrain_tool_target(&self) -> Option<String> {
+        if !self.terrain_tool {
+            return None;
+        }
+        self.selected_scene_object
+            .clone()
+            .filter(|name| self.terrains.contains_key(name))
+    }
+
+    /// Ponto (no espaço local do objeto) onde o raio toca o terreno
+    fn terrain_hit_local(&self, name: &str, ray: &Ray) -> Option<Vec3> {
+        let instance = self.terrains.get(name)?;
+        let entry = self.scene_entries.iter().find(|e| e.name == name)?;
+        let inverse = entry.transform.inverse();
+        instance.terrain.raycast(
+            inverse.transform_point3(ray.origin),
+            inverse.transform_vector3(ray.direction),
+        )
+    }
+
+    /// Ponto (no espaço local do objeto) onde o raio toca o retângulo da água
+    fn water_hit_local(&self, name: &str, surface: &WaterSurface, ray: &Ray) -> Option<Vec3> {
+        let entry = self.scene_entries.iter().find(|e| e.name == name)?;
+        let inverse = entry.transform.inverse();
+        let origin = inverse.transform_point3(ray.origin);
+        let direction = inverse.transform_vector3(ray.direction);
+        if direction.y.abs() < 1e-6 {
+            return None;
+        }
+        let t = -origin.y / direction.y;
+        let hit = origin + direction * t;
+        let inside = hit.x.abs() <= surface.size[0] * 0.5 && hit.z.abs() <= surface.size[1] * 0.5;
+        (t >= 0.0 && inside).then_some(hit)
+    }
+
+    /// Ponto (no espaço local do objeto) onde o raio toca o quad do sprite
+    fn sprite_hit_local(&self, name: &str, sprite: &Sprite, ray: &Ray) -> Option<Vec3> {
+        let entry = self.scene_entries.iter().find(|e| e.name == name)?;
+        let inverse = entry.transform.inverse();
+        let origin = inverse.transform_point3(ray.origin);
+        let direction = inverse.transform_vector3(ray.direction);
+        if direction.z.abs() < 1e-6 {
+            return None;
+        }
+        let t = -origin.z / direction.z;
+        let hit = origin + direction * t;
+        let u = hit.x / sprite.size.x + sprite.pivot.x;
+        let v = hit.y / sprite.size.y + sprite.pivot.y;
+        let inside = (0.0..=1.0).contains(&u) && (0.0..=1.0).contains(&v);
+        (t >= 0.0 && inside).then_some(hit)
+    }
+
+    /// Terreno, superfície de água ou sprite mais próximo atingido pelo raio
+    fn pick_surface_by_ray(&self, ray: &Ray) -> Option<String> {
+        let terrain_hits = self
+            .terrains
+            .keys()
+            .filter_map(|name| Some((name, self.terrain_hit_local(name, ray)?)));
+        let water_hits = self
+            .water_surfaces
+            .iter()
+            .filter(|_| self.water_active())
+            .filter_map(|(name, surface)| Some((name, self.water_hit_local(name, surface, ray)?)));
+        let sprite_hits = self.sprites.iter().filter_map(|(name, sprite, _)| {
+            Some((name, self.sprite_hit_local(name, sprite, ray)?))
+        });
+        terrain_hits
+            .chain(water_hits)
+            .chain(sprite_hits)
+            .filter_map(|(name, local)| {
+                let entry = self.scene_entries.iter().find(|e| &e.name == name)?;
+                let hit = entry.transform.transform_point3(local);
+                Some((ray.origin.distance(hit), name))
+            })
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, name)| name.clone())
+    }
+
+    /// Uniforms das superfícies de água (vazio com o recurso desligado)
+    fn water_uploads(
+        &self,
+        view_proj: Mat4,
+        eye: Vec3,
+        light_dir: Vec3,
+        time: f32,
+    ) -> Vec<WaterUpload> {
+        if !self.water_active() {
+            return Vec::new();
+        }
+        let view = WaterView {
+            view_proj,
+            camera_pos: eye,
+            light_dir,
+            light_color: Vec3::from(self.light_color),
+            light_intensity: if self.light_enabled {
+                self.light_intensity
+            } else {
+                0.0
+            },
+            time,
+        };
+        self.water_surfaces
+            .iter()
+            .filter_map(|(name, surface)| {
+                let entry = self.scene_entries.iter().find(|e| &e.name == name)?;
+                Some(WaterUpload {
+                    name: name.clone(),
+                    normal_map: surface.normal_map.clone(),
+                    uniforms: water_uniform_bytes(surface, entry.transform, &view),
+                })
+            })
+            .collect()
+    }
+
+    /// Quads dos sprites em ordem de desenho, como no jogo (camada, ordem na
+    /// camada, depois do mais distante ao mais próximo), agrupados por textura
+    fn sprite_uploads(&self, eye: Vec3, forward: Vec3) -> Vec<QuadUpload> {
+        let mut sprites: Vec<(&str, &Sprite, Transform, f32)> = self
+            .sprites
+            .iter()
+            .filter_map(|(name, sprite, texture)| {
+                let entry = self.scene_entries.iter().find(|e| &e.name == name)?;
+                let (scale, rotation, position) = entry.transform.to_scale_rotation_translation();
+                let depth = (position - eye).dot(forward);
+                Some((
+                    texture.as_str(),
+                    sprite,
+                    Transform::new(position, rotation, scale),
+                    depth,
+                ))
+            })
+            .collect();
+        sprites.sort_by(|a, b| {
+            a.1.sorting_layer
+                .cmp(&b.1.sorting_layer)
+                .then(a.1.order_in_layer.cmp(&b.1.order_in_layer))
+                .then(b.3.total_cmp(&a.3))
+        });
+        let mut uploads: Vec<QuadUpload> = Vec::new();
+        for (texture, sprite, transform, _) in sprites {
+            if uploads
+                .last()
+                .is_none_or(|upload| upload.texture != texture)
+            {
+                uploads.push(QuadUpload {
+                    texture: texture.to_string(),
+                    image: None,
+                    vertices: Vec::new(),
+                    indices: Vec::new(),
+                });
+            }
+            if let Some(upload) = uploads.last_mut() {
+                push_quad(
+                    &mut upload.vertices,
+                    &mut upload.indices,
+                    sprite_quad(&transform, sprite),
+                );
+            }
+        }
+        uploads
+    }
+
+    /// Quads dos glifos dos textos 3D, do mais distante ao mais próximo como no
+    /// jogo, agrupados por fonte; os billboards seguem os eixos da câmera (`view`)
+    fn text_uploads(&self, view: Mat4, eye: Vec3, forward: Vec3) -> Vec<QuadUpload> {
+        let mut texts: Vec<(&str, &Text3D, Transform, f32)> = self
+            .texts
+            .iter()
+            .filter(|(_, text, font)| !text.text.is_empty() && !font.is_empty())
+            .filter_map(|(name, text, font)| {
+                let entry = self.scene_entries.iter().find(|e| &e.name == name)?;
+                let (scale, rotation, position) = entry.transform.to_scale_rotation_translation();
+                let depth = (position + text.offset - eye).dot(forward);
+                Some((
+                    font.as_str(),
+                    text,
+                    Transform::new(position, rotation, scale),
+                    depth,
+                ))
+            })
+            .collect();
+        texts.sort_by(|a, b| b.3.total_cmp(&a.3));
+        let right = view.row(0).truncate();
+        let up = view.row(1).truncate();
+        let mut uploads: Vec<QuadUpload> = Vec::new();
+        for (path, text, transform, _) in texts {
+            let Some(Some((font, atlas))) = self.text_fonts.get(path) else {
+                continue;
+            };
+            if uploads.last().is_none_or(|upload| upload.texture != path) {
+                uploads.push(QuadUpload {
+                    texture: path.to_string(),
+                    image: Some(atlas.clone()),
+                    vertices: Vec::new(),
+                    indices: Vec::new(),
+                });
+            }
+            if let Some(upload) = uploads.last_mut() {
+                push_text3d_quads(
+                    font,
+                    text,
+                    &transform,
+                    right,
+                    up,
+                    &mut upload.vertices,
+                    &mut upload.indices,
+                );
+            }
+        }
+        uploads
+    }
+
+    /// Quads dos glifos do texto da UI em clip space de uma tela de `viewport`
+    /// pixels, na ordem dos objetos e agrupados por fonte (vazio fora do Play)
+    fn ui_text_uploads(&self, viewport: glam::Vec2) -> Vec<QuadUpload> {
+        if !self.in_play_mode() {
+            return Vec::new();
+        }
+        let mut uploads: Vec<QuadUpload> = Vec::new();
+        for (_, text, path) in &self.ui_texts {
+            if text.text.is_empty() {
+                continue;
+            }
+            let Some(Some((font, atlas))) = self.text_fonts.get(path) else {
+                continue;
+            };
+            if uploads.last().is_none_or(|upload| &upload.texture != path) {
+                uploads.push(QuadUpload {
+                    texture: path.clone(),
+                    image: Some(atlas.clone()),
+                    vertices: Vec::new(),
+                    indices: Vec::new(),
+                });
+            }
+            if let Some(upload) = uploads.last_mut() {
+                push_ui_text_quads(
+                    font,
+                    text,
+                    viewport,
+                    &mut upload.vertices,
+                    &mut upload.indices,
+                );
+            }
+        }
+        uploads
+    }
+
+    /// Salva alturas e splat map do terreno nos caminhos do componente
+    fn save_terrain(&mut self, name: &str) {
+        let Some(instance) = self.terrains.get(name) else {
+            return;
+        };
+        let heightmap = instance.draft.heightmap_path.trim();
+        let splatmap = instance.draft.splatmap_path.trim();
+        if heightmap.is_empty() && splatmap.is_empty() {
+            self.mesh_status =
+                Some("Defina o heightmap / splat map do Terrain no Inspector".to_string());
+            return;
+        }
+        let mut result = Ok(());
+        if !heightmap.is_empty() {
+            result = instance.terrain.save_heightmap(Path::new(heightmap));
+        }
+        if result.is_ok() && !splatmap.is_empty() {
+            result = instance.terrain.save_splatmap(Path::new(splatmap));
+        }
+        self.mesh_status = Some(match result {
+            Ok(()) => format!("Terreno salvo: {}", name),
+            Err(e) => e,
+        });
+    }
+
+    /// Malhas (LOD de cada chunk visto de `eye`) e uniforms dos terrenos para a GPU;
+    /// os bytes só são refeitos quando as alturas, os LODs ou o splat map mudam
+    fn terrain_uploads(
+        &mut self,
+        view_proj: Mat4,
+        eye: Vec3,
+        light_dir: Vec3,
+    ) -> Vec<TerrainUpload> {
+        let light_intensity = if self.light_enabled {
+            self.light_intensity
+        } else {
+            0.0
+        };
+        let mut uploads = Vec::with_capacity(self.terrains.len());
+        for (name, instance) in &mut self.terrains {
+            let Some(entry) = self.scene_entries.iter().find(|e| &e.name == name) else {
+                continue;
+            };
+            let eye_local = entry.transform.inverse().transform_point3(eye);
+            let lods = instance.terrain.select_lods(eye_local);
+            let mut hasher = DefaultHasher::new();
+            instance.terrain.height_version().hash(&mut hasher);
+            lods.hash(&mut hasher);
+            let mesh_key = hasher.finish();
+            if instance
+                .gpu_mesh
+                .as_ref()
+                .is_none_or(|(key, _, _)| *key != mesh_key)
+            {
+                let mesh = instance.terrain.build_mesh(&lods);
+                instance.gpu_mesh = Some((
+                    mesh_key,
+                    mesh.vertex_bytes().into(),
+                    mesh.index_bytes().into(),
+                ));
+            }
+            let splat_key = instance.terrain.splat_version();
+            if instance
+                .gpu_splat
+                .as_ref()
+                .is_none_or(|(key, _)| *key != splat_key)
+            {
+                instance.gpu_splat = Some((splat_key, instance.terrain.splat_rgba().into()));
+            }
+            let (Some((mesh_key, vertices, indices)), Some((splat_key, splat))) =
+                (&instance.gpu_mesh, &instance.gpu_splat)
+            else {
+                continue;
+            };
+            uploads.push(TerrainUpload {
+                name: name.clone(),
+                mesh_key: *mesh_key,
+                vertices: vertices.clone(),
+                indices: indices.clone(),
+                splat_key: *splat_key,
+                splat_size: instance.terrain.resolution(),
+                splat: splat.clone(),
+                layer_textures: instance.terrain.layers.clone().map(|layer| layer.texture),
+                uniforms: terrain_uniform_bytes(
+                    view_proj,
+                    entry.transform,
+                    light_dir,
+                    Vec3::from(self.light_color),
+                    light_intensity,
+                    &instance.terrain.layers,
+                ),
+            });
+        }
+        uploads
+    }
+
+    /// Contorno do pincel sobre o terreno, em torno do ponto local `center`
+    fn draw_terrain_brush(
+        &self,
+        ui: &mut egui::Ui,
+        viewport: Rect,
+        view_proj: Mat4,
+        name: &str,
+        center: Vec3,
+    ) {
+        let (Some(instance), Some(entry)) = (
+            self.terrains.get(name),
+            self.scene_entries.iter().find(|e| e.name == name),
+        ) else {
+            return;
+        };
+        let radius = self.terrain_brush.radius;
+        let lift = instance.terrain.height_scale().abs() * 0.002 + 0.02;
+        let points: Vec<Pos2> = (0..=48)
+            .filter_map(|i| {
+                let angle = i as f32 / 48.0 * std::f32::consts::TAU;
+                let local = glam::Vec2::new(
+                    center.x + angle.cos() * radius,
+                    center.z + angle.sin() * radius,
+                );
+                let height = instance.terrain.height_at(local) + lift;
+                let world = entry
+                    .transform
+                    .transform_point3(Vec3::new(local.x, height, local.y));
+                project_point(viewport, view_proj, world)
+            })
+            .collect();
+        let stroke = Stroke::new(1.5, theme::palette().accent);
+        for pair in points.windows(2) {
+            ui.painter().line_segment([pair[0], pair[1]], stroke);
+        }
+    }
+
+    /// LOD Groups dos objetos; as malhas dos níveis são carregadas uma única vez
+    pub fn set_lod_groups(&mut self, groups: Vec<(String, inspector::LodDraft)>) {
+        for (_, lod) in &groups {
+            for level in lod.levels.iter().skip(1) {
+                let path = level.mesh_path.trim();
+                if path.is_empty() || self.lod_meshes.contains_key(path) {
+                    continue;
+                }
+                let mesh = match load_viewport_mesh_asset_cached(Path::new(path)) {
+                    Ok(asset) => Some(asset.full),
+                    Err(e) => {
+                        log::warn!("[LOD] Falha ao carregar {}: {}", path, e);
+                        None
+                    }
+                };
+                self.lod_meshes.insert(path.to_string(), mesh);
+            }
+        }
+        self.lod_groups = groups.into_iter().collect();
+    }
+
+    /// Malha do objeto vista de `eye`: o nível de LOD (se houver) ou a malha completa.
+    /// Navegando, o nível 0 usa o proxy. None quando o LOD descarta o objeto.
+    fn entry_mesh<'a>(
+        &'a self,
+        entry: &'a SceneEntry,
+        use_proxy: bool,
+        eye: Vec3,
+    ) -> Option<&'a MeshData> {
+        let base = if use_proxy { &entry.proxy } else { &entry.full };
+        let Some(lod) = self.lod_groups.get(&entry.name) else {
+            return Some(base);
+        };
+        let distance = eye.distance(entry.transform.w_axis.truncate());
+        let level = lod.select_level(distance)?;
+        if level == 0 {
+            return Some(base);
+        }
+        let mesh = lod
+            .levels
+            .get(level)
+            .map(|level| level.mesh_path.trim())
+            .filter(|path| !path.is_empty())
+            .and_then(|path| self.lod_meshes.get(path)?.as_ref());
+        // Sem malha própria o nível usa o proxy simplificado do objeto
+        Some(mesh.unwrap_or(&entry.proxy))
+    }
+
+    /// Converte as luzes da cena para o formato do renderer, usando o transform atual de cada objeto
+    fn collect_light_instances(&self) -> Vec<LightInstance> {
+        let mut out = Vec::with_capacity(self.scene_lights.len());
+        for (name, light) in self.scene_lights.iter().filter(|(_, l)| l.enabled) {
+            let Some(entry) = self.scene_entries.iter().find(|e| &e.name == name) else {
+                continue;
+            };
+            let color = Vec3::from(light.color);
+            let position = entry.transform.w_axis.truncate();
+            // O cone do gizmo abre para -Y local
+            let down = entry
+                .transform
+                .transform_vector3(-Vec3::Y)
+                .normalize_or(-Vec3::Y);
+            let instance = match light.light_type {
+                inspector::LightType::Directional => LightInstance {
+                    kind: LightKind::Directional,
+                    position,
+                    direction: -Vec3::new(
+                        light.yaw.cos() * light.pitch.cos(),
+                        light.pitch.sin(),
+                        light.yaw.sin() * light.pitch.cos(),
+                    ),
+                    color,
+                    intensity: light.intensity,
+                    range: f32::INFINITY,
+                    inner_angle: 0.0,
+                    outer_angle: 0.0,
+                },
+                inspector::LightType::Point => LightInstance {
+                    kind: LightKind::Point,
+                    position,
+                    direction: down,
+                    color,
+                    intensity: light.intensity,
+                    range: light.range,
+                    inner_angle: 0.0,
+                    outer_angle: 0.0,
+                },
+                inspector::LightType::Spot => {
+                    // cone_angle é a abertura total do cone, em graus
+                    let outer = (light.cone_angle * 0.5).to_radians();
+                    LightInstance {
+                        kind: LightKind::Spot,
+                        position,
+                        direction: down,
+                        color,
+                        intensity: light.intensity,
+                        range: light.range,
+                        inner_angle: outer * 0.8,
+                        outer_angle: outer,
+                    }
+                }
+            };
+            out.push(instance);
+        }
+        out
+    }
+
+    /// Empilha o estado atual antes de uma mudança. Sempre empilha, mesmo igual
+    /// ao anterior: o histórico do editor desfaz cada ação com o número de
+    /// passos que ela empilhou
+    pub fn push_undo_snapshot(&mut self) {
+        self.undo_stack.push(self.snapshot());
+        if self.undo_stack.len() > MAX_UNDO_STEPS {
             self.undo_stack.remove(0);
         }
         self.redo_stack.clear();
+        self.history_revision += 1;
+    }
+
+    /// Material solto em `pos`: a malha sob o cursor é resolvida pelo picking
+    /// (ID buffer da GPU ou raio na CPU) e entregue por `take_material_drop`
+    pub fn on_material_dropped(&mut self, pos: Pos2, path: &Path) {
+        self.material_drop_request = Some((pos, path.to_string_lossy().to_string()));
+    }
+
+    pub fn take_material_drop(&mut self) -> Option<(String, String)> {
+        self.material_drop.take()
     }
 
     pub fn on_asset_dropped(&mut self, asset_name: &str) {
@@ -831,23 +3260,61 @@ impl ViewportPanel {
         }
         self.push_undo_snapshot();
         let nav_proxy = make_proxy_mesh(&full, VIEWPORT_NAV_TRIANGLES, VIEWPORT_NAV_VERTICES);
-        let target_pos = self.camera_target;
-        let rotation = Mat4::from_rotation_y(self.camera_yaw + std::f32::consts::PI);
-        let transform = Mat4::from_translation(target_pos) * rotation;
+        let transform = self.placement_transform();
         let name = object_name.to_string();
         self.scene_entries.push(SceneEntry {
             name: name.clone(),
             transform,
+            local: transform,
+            parent: None,
             full,
             proxy: nav_proxy,
+            source: None,
         });
-        self.selected_scene_object = Some(name.clone());
+        self.select_only(&name);
         self.dropped_asset_label = Some(name);
-        self.object_selected = true;
         self.mesh_status = Some("Primitiva 3D criada".to_string());
         true
     }
 
+    pub fn copy_scene_object(&self, object_name: &str) -> Option<SceneObjectCopy> {
+        let entry = self.scene_entries.iter().find(|e| e.name == object_name)?;
+        Some(SceneObjectCopy {
+            transform: entry.transform,
+            full: entry.full.clone(),
+            proxy: entry.proxy.clone(),
+            source: entry.source.clone(),
+        })
+    }
+
+    /// Coloca cópias na cena com novos nomes, deslocadas `offset` no mundo;
+    /// um passo de histórico para a colagem inteira
+    pub fn paste_scene_objects(
+        &mut self,
+        objects: Vec<(String, SceneObjectCopy)>,
+        offset: [f32; 3],
+    ) {
+        if objects.is_empty() {
+            return;
+        }
+        self.push_undo_snapshot();
+        let offset = Mat4::from_translation(Vec3::from(offset));
+        for (name, copy) in objects {
+            self.scene_entries.retain(|e| e.name != name);
+            let transform = offset * copy.transform;
+            self.scene_entries.push(SceneEntry {
+                name,
+                transform,
+                local: transform,
+                parent: None,
+                full: copy.full,
+                proxy: copy.proxy,
+                source: copy.source,
+            });
+        }
+        self.mesh_status = Some("Objetos colados".to_string());
+    }
+
     pub fn spawn_light(&mut self, object_name: &str, light_type: inspector::LightType) -> bool {
         let full = make_light_mesh(light_type);
         if full.vertices.is_empty() || full.triangles.is_empty() {
@@ -855,19 +3322,19 @@ impl ViewportPanel {
         }
         self.push_undo_snapshot();
         let nav_proxy = make_proxy_mesh(&full, VIEWPORT_NAV_TRIANGLES, VIEWPORT_NAV_VERTICES);
-        let target_pos = self.camera_target;
-        let rotation = Mat4::from_rotation_y(self.camera_yaw + std::f32::consts::PI);
-        let transform = Mat4::from_translation(target_pos) * rotation;
+        let transform = self.placement_transform();
         let name = object_name.to_string();
         self.scene_entries.push(SceneEntry {
             name: name.clone(),
             transform,
+            local: transform,
+            parent: None,
             full,
             proxy: nav_proxy,
+            source: None,
         });
-        self.selected_scene_object = Some(name.clone());
+        self.select_only(&name);
         self.dropped_asset_label = Some(name);
-        self.object_selected = true;
         self.mesh_status = Some("Luz adicionada".to_string());
         true
     }
@@ -906,6 +3373,7 @@ impl ViewportPanel {
                 }
                 let job_id = self.alloc_import_job_id();
                 self.pending_mesh_job = Some(job_id);
+                self.pending_mesh_source = Some(path.to_path_buf());
                 self.mesh_loading = true;
                 self.mesh_status = Some("Carregando proxy...".to_string());
                 if self.pending_mesh_name.is_none() {
@@ -916,8 +3384,13 @@ impl ViewportPanel {
                             .to_string(),
                     );
                 }
-                self.import_pipeline
-                    .enqueue_mesh(job_id, path.to_path_buf());
+                if matches!(ext.as_str(), "glb" | "gltf" | "fbx") {
+                    self.import_pipeline
+                        .enqueue_scene(job_id, path.to_path_buf());
+                } else {
+                    self.import_pipeline
+                        .enqueue_mesh(job_id, path.to_path_buf());
+                }
             }
             "png" | "jpg" | "jpeg" | "webp" => {
                 self.mesh_status = Some("Viewport em modo sólido: textura desativada".to_string());
@@ -926,23 +3399,155 @@ impl ViewportPanel {
         }
     }
 
+    /// Reimporta em segundo plano os objetos vindos de `path` e descarta os níveis
+    /// de LOD lidos dele; false se nada na cena usa o arquivo
+    pub fn reload_mesh_source(&mut self, path: &Path) -> bool {
+        let lod_paths: Vec<String> = self
+            .lod_meshes
+            .keys()
+            .filter(|lod_path| same_file(Path::new(lod_path), path))
+            .cloned()
+            .collect();
+        for lod_path in &lod_paths {
+            // `set_lod_groups` lê o arquivo de novo na próxima frame
+            self.lod_meshes.remove(lod_path);
+        }
+        if !lod_paths.is_empty() {
+            self.mesh_revision += 1;
+        }
+
+        let used = self.scene_entries.iter().any(|entry| {
+            entry
+                .source
+                .as_deref()
+                .is_some_and(|source| same_file(source, path))
+        });
+        if used {
+            let job_id = self.alloc_import_job_id();
+            self.reload_jobs.insert(job_id, path.to_path_buf());
+            let ext = path
+                .extension()
+                .and_then(|e| e.to_str())
+                .map(|e| e.to_ascii_lowercase())
+                .unwrap_or_default();
+            if matches!(ext.as_str(), "glb" | "gltf" | "fbx") {
+                self.import_pipeline
+                    .enqueue_scene(job_id, path.to_path_buf());
+            } else {
+                self.import_pipeline
+                    .enqueue_mesh(job_id, path.to_path_buf());
+            }
+        }
+        used || !lod_paths.is_empty()
+    }
+
+    /// Descarta a cópia da textura usada no modo sólido sem GPU
+    pub fn reload_texture_file(&mut self, path: &Path) {
+        self.texture_cache
+            .retain(|cached, _| !same_file(Path::new(cached), path));
+    }
+
+    /// Troca a malha dos objetos vindos do arquivo reimportado, mantendo nome,
+    /// transform, textura e material de cada um
+    fn apply_reload_event(&mut self, event: ImportEvent) {
+        let (job_id, result) = match event {
+            ImportEvent::Mesh {
+                event: MeshLoadEvent::Proxy(_),
+                ..
+            } => return,
+            ImportEvent::Mesh {
+                job_id,
+                event: MeshLoadEvent::Full(result),
+            } => (job_id, result.map(|mesh| vec![(String::new(), mesh)])),
+            ImportEvent::Scene { job_id, result } => (
+                job_id,
+                result.map(|nodes| {
+                    nodes
+                        .into_iter()
+                        .map(|node| (node.name, node.mesh))
+                        .collect()
+                }),
+            ),
+        };
+        let Some(path) = self.reload_jobs.remove(&job_id) else {
+            return;
+        };
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let meshes: Vec<(String, MeshData)> = match result {
+            Ok(meshes) => meshes,
+            Err(err) => {
+                self.mesh_status = Some(format!("Falha ao recarregar {file_name}: {err}"));
+                return;
+            }
+        };
+
+        for entry in self.scene_entries.iter_mut().filter(|entry| {
+            entry
+                .source
+                .as_deref()
+                .is_some_and(|source| same_file(source, &path))
+        }) {
+            // Arquivo com uma malha: todas as instâncias dele; cena: o nó de mesmo nome
+            let leaf = entry.name.rsplit('/').next().unwrap_or_default();
+            let mesh = if meshes.len() == 1 {
+                meshes.first()
+            } else {
+                meshes.iter().find(|(node, _)| {
+                    leaf == node
+                        || leaf
+                            .strip_prefix(node.as_str())
+                            .is_some_and(|rest| rest.starts_with(' '))
+                })
+            };
+            let Some((_, mesh)) = mesh else {
+                continue;
+            };
+            let mut full = mesh.clone();
+            if full.triangles.len() > MAX_RUNTIME_TRIANGLES
+                || full.vertices.len() > MAX_RUNTIME_VERTICES
+            {
+                full = make_proxy_mesh(&full, MAX_RUNTIME_TRIANGLES, MAX_RUNTIME_VERTICES);
+            }
+            if entry.full.texture_path.is_some() {
+                full.texture_path = entry.full.texture_path.clone();
+            }
+            if entry.full.material_path.is_some() {
+                full.material_path = entry.full.material_path.clone();
+            }
+            entry.proxy = make_proxy_mesh(&full, VIEWPORT_NAV_TRIANGLES, VIEWPORT_NAV_VERTICES);
+            entry.full = full;
+        }
+        self.mesh_revision += 1;
+        self.mesh_status = Some(format!("Malha recarregada: {file_name}"));
+    }
+
     fn ensure_icons_loaded(&mut self, ctx: &egui::Context) {
         if self.rotation_icon.is_none() {
-            self.rotation_icon = load_png_as_texture(ctx, "src/assets/icons/rotation.png");
+            self.rotation_icon = load_png_as_texture(ctx, "Editor/icons/rotation.png");
         }
         if self.scale_icon.is_none() {
-            self.scale_icon = load_png_as_texture(ctx, "src/assets/icons/scale.png");
+            self.scale_icon = load_png_as_texture(ctx, "Editor/icons/scale.png");
         }
         if self.transform_icon.is_none() {
-            self.transform_icon = load_png_as_texture(ctx, "src/assets/icons/transform.png");
+            self.transform_icon = load_png_as_texture(ctx, "Editor/icons/transform.png");
         }
         if self.move_icon.is_none() {
-            self.move_icon = load_png_as_texture(ctx, "src/assets/icons/move.png");
+            self.move_icon = load_png_as_texture(ctx, "Editor/icons/move.png");
         }
     }
 
     fn poll_import_pipeline(&mut self) {
         while let Ok(event) = self.import_pipeline.rx.try_recv() {
+            let job_id = match &event {
+                ImportEvent::Mesh { job_id, .. } | ImportEvent::Scene { job_id, .. } => *job_id,
+            };
+            if self.reload_jobs.contains_key(&job_id) {
+                self.apply_reload_event(event);
+                continue;
+            }
             match event {
                 ImportEvent::Mesh { job_id, event } => {
                     if self.pending_mesh_job != Some(job_id) {
@@ -975,16 +3580,17 @@ impl ViewportPanel {
                                 .pending_mesh_name
                                 .take()
                                 .unwrap_or_else(|| full.name.clone());
-                            let target_pos = self.camera_target;
-                            let rotation =
-                                Mat4::from_rotation_y(self.camera_yaw + std::f32::consts::PI);
-                            let transform = Mat4::from_translation(target_pos) * rotation;
+                            let transform = self.placement_transform();
                             self.scene_entries.push(SceneEntry {
                                 name: name.clone(),
                                 transform,
+                                local: transform,
+                                parent: None,
                                 full,
                                 proxy: nav_proxy,
+                                source: self.pending_mesh_source.clone(),
                             });
+                            self.queue_generated_collider(&name);
                             if let Some(entry) = self.scene_entries.last_mut() {
                                 if entry.full.material_path.is_none() {
                                     let mut name_candidates = vec![name.clone()];
@@ -1002,9 +3608,8 @@ impl ViewportPanel {
                                     }
                                 }
                             }
-                            self.selected_scene_object = Some(name.clone());
+                            self.select_only(&name);
                             self.dropped_asset_label = Some(name);
-                            self.object_selected = true;
                             self.mesh_status = Some(if is_heavy {
                                 "Mesh carregada com otimização automática".to_string()
                             } else {
@@ -1021,10 +3626,122 @@ impl ViewportPanel {
                         }
                     }
                 }
+                ImportEvent::Scene { job_id, result } => {
+                    if self.pending_mesh_job != Some(job_id) {
+                        continue;
+                    }
+                    match result {
+                        Ok(nodes) => self.spawn_imported_scene(nodes),
+                        Err(err) => {
+                            self.pending_mesh_name = None;
+                            self.mesh_status = Some(format!("Falha ao carregar cena: {err}"));
+                        }
+                    }
+                    self.mesh_loading = false;
+                    self.pending_mesh_job = None;
+                }
             }
         }
     }
 
+    /// Instancia a árvore importada: um objeto por nó com malha, filhos de `pending_mesh_name`
+    fn spawn_imported_scene(&mut self, nodes: Vec<ImportedSceneNode>) {
+        self.push_undo_snapshot();
+        let root = self
+            .pending_mesh_name
+            .take()
+            .unwrap_or_else(|| "Imported Scene".to_string());
+        let placement = self.placement_transform();
+        let single = nodes.len() == 1;
+        let mut children = Vec::new();
+        let mut optimized = false;
+        for node in nodes {
+            let name = if single {
+                root.clone()
+            } else {
+                let base = format!("{}/{}", root, node.name);
+                let mut name = base.clone();
+                let mut idx = 1;
+                while self.scene_entries.iter().any(|e| e.name == name) {
+                    idx += 1;
+                    name = format!("{base} {idx}");
+                }
+                name
+            };
+            let mut full = node.mesh;
+            if full.triangles.len() > MAX_RUNTIME_TRIANGLES
+                || full.vertices.len() > MAX_RUNTIME_VERTICES
+            {
+                full = make_proxy_mesh(&full, MAX_RUNTIME_TRIANGLES, MAX_RUNTIME_VERTICES);
+                optimized = true;
+            }
+            if full.material_path.is_none() {
+                full.material_path = find_material_path_for_names([root.clone(), node.name].iter());
+            }
+            let proxy = make_proxy_mesh(&full, VIEWPORT_NAV_TRIANGLES, VIEWPORT_NAV_VERTICES);
+            let transform = placement * node.transform;
+            self.scene_entries.push(SceneEntry {
+                name: name.clone(),
+                transform,
+                local: transform,
+                parent: None,
+                full,
+                proxy,
+                source: self.pending_mesh_source.clone(),
+            });
+            self.queue_generated_collider(&name);
+            if !single {
+                children.push(name);
+            }
+        }
+
+        let selected = children.first().cloned().unwrap_or_else(|| root.clone());
+        self.mesh_status = Some(if single {
+            "Mesh carregada".to_string()
+        } else {
+            format!("Cena carregada ({} objetos)", children.len())
+        });
+        if optimized {
+            self.mesh_status = Some("Mesh carregada com otimização automática".to_string());
+        }
+        if !children.is_empty() {
+            self.pending_imported_tree = Some((root.clone(), children));
+        }
+        self.select_only(&selected);
+        self.dropped_asset_label = Some(root);
+    }
+
+    /// Árvore criada pela última importação de cena (raiz, filhos), consumida pela hierarquia
+    pub fn take_imported_tree(&mut self) -> Option<(String, Vec<String>)> {
+        self.pending_imported_tree.take()
+    }
+
+    /// Collider de caixa ajustado à malha do objeto, quando o .meta do arquivo
+    /// de origem pede `generate_colliders`
+    fn queue_generated_collider(&mut self, name: &str) {
+        let Some(entry) = self.scene_entries.iter().find(|e| e.name == name) else {
+            return;
+        };
+        let Some(source) = entry.source.as_deref() else {
+            return;
+        };
+        if !MeshImportSettings::default()
+            .with_meta(source)
+            .generate_colliders
+        {
+            return;
+        }
+        if let Some(collider) = fitted_box_collider(&entry.full) {
+            self.pending_generated_colliders
+                .push((name.to_string(), collider));
+        }
+    }
+
+    /// Colliders gerados na importação, consumidos pelo inspetor
+    pub fn take_generated_colliders(&mut self) -> Vec<(String, inspector::ColliderDraft)> {
+        std::mem::take(&mut self.pending_generated_colliders)
+    }
+
     fn gizmo_icon_button(
         ui: &mut egui::Ui,
         texture: Option<&TextureHandle>,
@@ -1039,14 +3756,14 @@ impl ViewportPanel {
         }
         .corner_radius(6)
         .fill(if selected {
-            Color32::from_rgb(64, 64, 68)
+            theme::palette().widget_hover
         } else {
-            Color32::from_rgb(42, 42, 46)
+            theme::palette().widget_bg
         })
         .stroke(if selected {
-            Stroke::new(1.0, Color32::from_rgb(15, 232, 121))
+            Stroke::new(1.0, theme::palette().accent)
         } else {
-            Stroke::new(1.0, Color32::from_rgb(72, 72, 78))
+            Stroke::new(1.0, theme::palette().border)
         });
 
         ui.add_sized([28.0, 24.0], button)
@@ -1054,6 +3771,207 @@ impl ViewportPanel {
             .clicked()
     }
 
+    /// Painel extra da viewport dividida, com renderizador próprio. Mostra malhas,
+    /// terreno, luzes, contorno da seleção e debug draw; partículas, água e
+    /// pós-processamento ficam só na vista principal. Botão direito ou do meio
+    /// arrasta, a roda aproxima e o duplo clique centraliza no alvo da câmera
+    fn draw_split_pane(
+        &mut self,
+        ui: &mut egui::Ui,
+        index: usize,
+        rect: Rect,
+        gpu_renderer: Option<&ViewportGpuRenderer>,
+    ) {
+        if rect.width() < 80.0 || rect.height() < 80.0 {
+            return;
+        }
+        let ctx = ui.ctx().clone();
+        ui.painter()
+            .rect_filled(rect, 0.0, theme::palette().panel_bg);
+        let sky_shape = ui.painter().add(egui::Shape::Noop);
+        ui.painter().rect_stroke(
+            rect,
+            0.0,
+            Stroke::new(1.0, theme::palette().border),
+            egui::StrokeKind::Outside,
+        );
+        let resp = ui.interact(
+            rect,
+            ui.id().with(("split_pane_input", index)),
+            Sense::click_and_drag(),
+        );
+
+        let pane = &mut self.split_panes[index];
+        let (yaw, pitch) = pane.view.yaw_pitch();
+        let orbit = Vec3::new(
+            yaw.cos() * pitch.cos(),
+            pitch.sin(),
+            yaw.sin() * pitch.cos(),
+        );
+        let right = (-orbit).cross(Vec3::Y).normalize_or_zero();
+        let up = right.cross(-orbit);
+        if resp.dragged_by(PointerButton::Secondary) || resp.dragged_by(PointerButton::Middle) {
+            let units_per_point = 2.0 * pane.half_height / rect.height();
+            let delta = resp.drag_delta();
+            pane.target += (up * delta.y - right * delta.x) * units_per_point;
+        }
+        if resp.hovered() {
+            let scroll = ctx.input(|i| i.smooth_scroll_delta.y);
+            if scroll != 0.0 {
+                pane.half_height = (pane.half_height * (-scroll * 0.002).exp()).clamp(0.05, 500.0);
+            }
+        }
+        if resp.double_clicked() {
+            pane.target = self.camera_target;
+        }
+
+        let aspect = rect.width() / rect.height();
+        let eye = pane.target + orbit * SPLIT_PANE_DISTANCE;
+        let view = Mat4::look_at_rh(eye, pane.target, Vec3::Y);
+        let half = pane.half_height;
+        let proj = Mat4::orthographic_rh_gl(
+            -half * aspect,
+            half * aspect,
+            -half,
+            half,
+            VIEWPORT_NEAR,
+            VIEWPORT_FAR,
+        );
+        let shading_mode = pane.shading_mode;
+        let pixels = rect.size() * ctx.pixels_per_point();
+        let pixel_size = [pixels.x.round() as u32, pixels.y.round() as u32];
+
+        if let Some(gpu) = gpu_renderer {
+            gpu.set_shading_mode(shading_mode);
+            gpu.update_sky(
+                proj * view,
+                eye,
+                &self.environment,
+                self.environment_map.as_ref(),
+            );
+            gpu.update_fog(&self.environment.fog);
+            ui.painter().set(
+                sky_shape,
+                egui::Shape::Callback(gpu.sky_paint_callback(rect)),
+            );
+        }
+        if self.snap.grid_visible {
+            ui.painter()
+                .with_clip_rect(rect)
+                .add(ground_grid(rect, proj * view, &self.snap));
+        }
+
+        let mut gpu_drawn = false;
+        if let Some(gpu) = gpu_renderer.filter(|_| !self.scene_entries.is_empty()) {
+            let (scene_batch, texture_conflict, object_ranges) =
+                self.build_gpu_scene_mesh(false, eye);
+            if !texture_conflict {
+                let light_dir = self.light_direction();
+                gpu.update_scene(
+                    self.gpu_scene_mesh_id(false, eye),
+                    &scene_batch.vertices,
+                    &scene_batch.normals,
+                    &scene_batch.uvs,
+                    &scene_batch.triangles,
+                    &object_ranges
+                        .iter()
+                        .map(|(_, range)| range.clone())
+                        .collect::<Vec<_>>(),
+                    proj * view,
+                    Mat4::IDENTITY,
+                    eye,
+                    light_dir,
+                    Vec3::from(self.light_color),
+                    self.light_intensity,
+                    self.light_enabled,
+                    scene_batch.texture_path,
+                );
+                let clustered = ClusteredLights::build(
+                    &self.collect_light_instances(),
+                    view,
+                    proj,
+                    VIEWPORT_NEAR,
+                    VIEWPORT_FAR,
+                );
+                gpu.update_lights(&clustered, -orbit);
+                gpu.update_terrains(self.terrain_uploads(proj * view, eye, light_dir));
+                gpu.update_sprites(proj * view, self.sprite_uploads(eye, -orbit));
+                gpu.update_texts(proj * view, self.text_uploads(view, eye, -orbit));
+                let selected = object_ranges
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (name, _))| self.object_selected && self.selection.contains(name))
+                    .map(|(i, _)| i)
+                    .collect();
+                gpu.update_selection(
+                    selected,
+                    pixel_size,
+                    SELECTION_OUTLINE_COLOR,
+                    SELECTION_OUTLINE_WIDTH * ctx.pixels_per_point(),
+                );
+                ui.painter()
+                    .add(egui::Shape::Callback(gpu.paint_callback(rect)));
+                if gpu.textures_loading() {
+                    ctx.request_repaint_after(std::time::Duration::from_millis(50));
+                }
+                gpu_drawn = true;
+            }
+        }
+        if !gpu_drawn {
+            let mut texture_cache = std::mem::take(&mut self.texture_cache);
+            for entry in &self.scene_entries {
+                let mvp_obj = proj * view * entry.transform;
+                let Some(mesh) = self.entry_mesh(entry, false, eye) else {
+                    continue;
+                };
+                if shading_mode == ShadingMode::Wireframe {
+                    let selected = self.selection.contains(&entry.name);
+                    draw_wire_mesh(ui, rect, mvp_obj, mesh, selected);
+                    continue;
+                }
+                draw_solid_mesh(ui, rect, mvp_obj, mesh, &mut texture_cache);
+            }
+            self.texture_cache = texture_cache;
+        }
+        for command in &self.debug_draw {
+            draw_debug_command(ui, rect, proj * view, command);
+        }
+        for command in self.measure_overlay(None) {
+            draw_debug_command(ui, rect, proj * view, &command);
+        }
+
+        // Vista e shading do painel
+        let pane = &mut self.split_panes[index];
+        ui.scope_builder(
+            egui::UiBuilder::new()
+                .max_rect(Rect::from_min_size(
+                    rect.min + egui::vec2(8.0, 6.0),
+                    egui::vec2(rect.width() - 16.0, 22.0),
+                ))
+                .layout(egui::Layout::left_to_right(egui::Align::Center)),
+            |ui| {
+                egui::ComboBox::from_id_salt(("split_pane_view", index))
+                    .width(84.0)
+                    .selected_text(pane.view.name())
+                    .show_ui(ui, |ui| {
+                        for view in AxisView::ALL {
+                            ui.selectable_value(&mut pane.view, view, view.name());
+                        }
+                    });
+                egui::ComboBox::from_id_salt(("split_pane_shading", index))
+                    .width(92.0)
+                    .selected_text(pane.shading_mode.label())
+                    .show_ui(ui, |ui| {
+                        for mode in ShadingMode::all() {
+                            ui.selectable_value(&mut pane.shading_mode, mode, mode.label());
+                        }
+                    })
+                    .response
+                    .on_hover_text("Modo de shading do painel");
+            },
+        );
+    }
+
     pub fn show(
         &mut self,
         ctx: &egui::Context,
@@ -1063,16 +3981,19 @@ impl ViewportPanel {
         bottom_reserved: f32,
         gpu_renderer: Option<&ViewportGpuRenderer>,
     ) {
+        engine_core::profile_function!();
         self.ensure_icons_loaded(ctx);
 
         egui::CentralPanel::default()
             .frame(
                 egui::Frame::new()
-                    .fill(Color32::from_rgb(28, 28, 30))
-                    .stroke(Stroke::new(1.0, Color32::from_rgb(48, 48, 52))),
+                    .fill(theme::palette().panel_bg)
+                    .stroke(Stroke::new(1.0, theme::palette().border)),
             )
             .show(ctx, |ui| {
                 self.poll_import_pipeline();
+                self.scene_spatial
+                    .refresh(&self.scene_entries, self.mesh_revision);
 
                 if self.mesh_loading {
                     ui.ctx().request_repaint();
@@ -1083,37 +4004,94 @@ impl ViewportPanel {
                     egui::pos2(content.left() + left_reserved, content.top()),
                     egui::pos2(content.right() - right_reserved, content.bottom() - bottom_reserved),
                 );
+                // Barra de controles fica presa ao painel, mesmo com faixas pretas no Game
+                let panel_rect = viewport_rect;
+                // Viewport dividida (só na cena 3D): a barra ganha uma faixa própria
+                // e a câmera do editor fica com o primeiro painel
+                let split_rects = if self.is_3d && !self.game_view && self.split != ViewportSplit::Single {
+                    self.split.rects(Rect::from_min_max(
+                        egui::pos2(panel_rect.left(), panel_rect.top() + SPLIT_TOOLBAR_HEIGHT),
+                        panel_rect.max,
+                    ))
+                } else {
+                    Vec::new()
+                };
+                let viewport_rect = split_rects.first().copied().unwrap_or(viewport_rect);
+                // Game: a área do preset de proporção e, dentro dela, a da câmera
+                // do jogo (viewport rect normalizado)
+                let game_camera = self.active_game_camera();
+                let game_area = self.game_aspect.fit(viewport_rect, ctx.pixels_per_point());
+                // Escala da resolução fixa na tela (1.0 = pixel a pixel)
+                let game_scale = self
+                    .game_aspect
+                    .resolution()
+                    .map(|[w, _]| game_area.width() * ctx.pixels_per_point() / w as f32);
+                if self.game_view && self.is_3d && game_camera.is_none() {
+                    // Sem câmera não há o que o jogo mostraria: nada da câmera do editor
+                    ui.painter().rect_filled(viewport_rect, 0.0, Color32::BLACK);
+                    ui.painter().text(
+                        game_area.center(),
+                        Align2::CENTER_CENTER,
+                        "Nenhuma câmera ativa na cena\nAdicione um componente Câmera a um objeto",
+                        FontId::proportional(13.0),
+                        theme::palette().text_weak,
+                    );
+                    self.last_viewport_rect = Some(viewport_rect);
+                    return;
+                }
+                let viewport_rect = match &game_camera {
+                    Some((camera, _)) => {
+                        ui.painter().rect_filled(viewport_rect, 0.0, Color32::BLACK);
+                        let [x, y, w, h] = camera.viewport_rect();
+                        Rect::from_min_size(
+                            game_area.min + game_area.size() * egui::vec2(x, y),
+                            game_area.size() * egui::vec2(w, h),
+                        )
+                    }
+                    None => viewport_rect,
+                };
                 if viewport_rect.width() < 80.0 || viewport_rect.height() < 80.0 {
                     self.last_viewport_rect = None;
                     return;
                 }
                 self.last_viewport_rect = Some(viewport_rect);
+                for (index, rect) in split_rects.iter().skip(1).enumerate() {
+                    let split_gpu = gpu_renderer.and_then(|gpu| gpu.split_views().get(index));
+                    self.draw_split_pane(ui, index, *rect, split_gpu);
+                }
 
                 ui.painter()
-                    .rect_filled(viewport_rect, 0.0, Color32::from_rgb(22, 22, 24));
+                    .rect_filled(viewport_rect, 0.0, theme::palette().panel_bg);
+                // Reservado para o céu (GPU), desenhado atrás da grade e dos textos
+                let sky_shape = ui.painter().add(egui::Shape::Noop);
+                // Grade do chão (3D), definida depois que a câmera é calculada
+                let ground_grid_shape = ui.painter().add(egui::Shape::Noop);
                 ui.painter().rect_stroke(
                     viewport_rect,
                     0.0,
-                    Stroke::new(1.0, Color32::from_rgb(58, 58, 62)),
+                    Stroke::new(1.0, theme::palette().border),
                     egui::StrokeKind::Outside,
                 );
 
-                let grid_step = 24.0;
-                let mut x = viewport_rect.left();
-                while x <= viewport_rect.right() {
-                    ui.painter().line_segment(
-                        [egui::pos2(x, viewport_rect.top()), egui::pos2(x, viewport_rect.bottom())],
-                        Stroke::new(1.0, Color32::from_rgba_unmultiplied(86, 86, 92, 24)),
-                    );
-                    x += grid_step;
-                }
-                let mut y = viewport_rect.top();
-                while y <= viewport_rect.bottom() {
-                    ui.painter().line_segment(
-                        [egui::pos2(viewport_rect.left(), y), egui::pos2(viewport_rect.right(), y)],
-                        Stroke::new(1.0, Color32::from_rgba_unmultiplied(86, 86, 92, 24)),
-                    );
-                    y += grid_step;
+                // Grade de fundo em tela, trocada pela grade do chão no 3D (nenhuma no Game)
+                if !(self.is_3d && (self.snap.grid_visible || game_camera.is_some())) {
+                    let grid_step = 24.0;
+                    let mut x = viewport_rect.left();
+                    while x <= viewport_rect.right() {
+                        ui.painter().line_segment(
+                            [egui::pos2(x, viewport_rect.top()), egui::pos2(x, viewport_rect.bottom())],
+                            Stroke::new(1.0, theme::palette().graph_grid),
+                        );
+                        x += grid_step;
+                    }
+                    let mut y = viewport_rect.top();
+                    while y <= viewport_rect.bottom() {
+                        ui.painter().line_segment(
+                            [egui::pos2(viewport_rect.left(), y), egui::pos2(viewport_rect.right(), y)],
+                            Stroke::new(1.0, theme::palette().graph_grid),
+                        );
+                        y += grid_step;
+                    }
                 }
 
                 ui.painter().text(
@@ -1121,7 +4099,7 @@ impl ViewportPanel {
                     Align2::LEFT_TOP,
                     format!("Viewport - {}", mode_label),
                     FontId::proportional(13.0),
-                    Color32::from_gray(210),
+                    theme::palette().text,
                 );
                 if let Some(asset) = &self.dropped_asset_label {
                     ui.painter().text(
@@ -1129,7 +4107,7 @@ impl ViewportPanel {
                         Align2::LEFT_TOP,
                         format!("Asset: {asset}"),
                         FontId::proportional(11.0),
-                        Color32::from_rgb(144, 206, 168),
+                        theme::palette().accent,
                     );
                 }
                 if let Some(status) = &self.mesh_status {
@@ -1138,7 +4116,7 @@ impl ViewportPanel {
                         Align2::LEFT_TOP,
                         status,
                         FontId::proportional(10.0),
-                        Color32::from_gray(190),
+                        theme::palette().text,
                     );
                 }
                 if self.mesh_loading {
@@ -1146,12 +4124,12 @@ impl ViewportPanel {
                     ui.painter().rect_filled(
                         loading_rect,
                         6.0,
-                        Color32::from_rgba_unmultiplied(25, 30, 33, 220),
+                        theme::palette().panel_header,
                     );
                     ui.painter().rect_stroke(
                         loading_rect,
                         6.0,
-                        Stroke::new(1.0, Color32::from_rgb(72, 92, 96)),
+                        Stroke::new(1.0, theme::palette().border),
                         egui::StrokeKind::Outside,
                     );
                     ui.scope_builder(
@@ -1172,18 +4150,163 @@ impl ViewportPanel {
                 let viewport_resp =
                     ui.interact(viewport_rect, ui.id().with("scene_viewport_input"), Sense::click_and_drag());
 
+                let controls_width = if game_camera.is_some() { 901.0 } else { 751.0 };
                 let controls_rect = Rect::from_min_max(
-                    egui::pos2(viewport_rect.right() - 395.0, viewport_rect.top() + 6.0),
-                    egui::pos2(viewport_rect.right() - 8.0, viewport_rect.top() + 32.0),
+                    egui::pos2(panel_rect.right() - controls_width, panel_rect.top() + 6.0),
+                    egui::pos2(panel_rect.right() - 8.0, panel_rect.top() + 32.0),
                 );
                 ui.scope_builder(
                     egui::UiBuilder::new()
                         .max_rect(controls_rect)
                         .layout(egui::Layout::right_to_left(egui::Align::Center)),
                     |ui| {
+                        if game_camera.is_some() {
+                            let selected = match game_scale {
+                                Some(scale) => format!(
+                                    "{} · {:.0}%",
+                                    self.game_aspect.label(),
+                                    scale * 100.0
+                                ),
+                                None => self.game_aspect.label().to_string(),
+                            };
+                            egui::ComboBox::from_id_salt("viewport_game_aspect")
+                                .selected_text(selected)
+                                .width(140.0)
+                                .show_ui(ui, |ui| {
+                                    for aspect in GameAspect::ALL {
+                                        ui.selectable_value(
+                                            &mut self.game_aspect,
+                                            aspect,
+                                            aspect.label(),
+                                        );
+                                    }
+                                })
+                                .response
+                                .on_hover_text("Proporção / resolução da vista Game");
+                        }
+                        ui.menu_button("Grade", |ui| {
+                            ui.checkbox(&mut self.snap.grid_visible, "Grade do chão");
+                            ui.add(
+                                egui::Slider::new(&mut self.snap.grid_spacing, 0.1..=10.0)
+                                    .logarithmic(true)
+                                    .text("Espaçamento"),
+                            );
+                            ui.add(
+                                egui::Slider::new(&mut self.snap.grid_extent, 2..=200)
+                                    .text("Linhas por lado"),
+                            );
+                            ui.separator();
+                            ui.label("Incrementos de snap");
+                            ui.add(
+                                egui::DragValue::new(&mut self.snap.translate)
+                                    .speed(0.01)
+                                    .range(0.001..=100.0)
+                                    .prefix("Mover: "),
+                            );
+                            ui.add(
+                                egui::DragValue::new(&mut self.snap.rotate_deg)
+                                    .speed(0.5)
+                                    .range(0.1..=180.0)
+                                    .prefix("Girar: ")
+                                    .suffix("°"),
+                            );
+                            ui.add(
+                                egui::DragValue::new(&mut self.snap.scale)
+                                    .speed(0.01)
+                                    .range(0.001..=10.0)
+                                    .prefix("Escala: "),
+                            );
+                            if ui.button("Usar espaçamento da grade").clicked() {
+                                self.snap.translate = self.snap.grid_spacing;
+                            }
+                            ui.separator();
+                            ui.label("Ao mover com o gizmo, grudar em");
+                            for mode in SurfaceSnap::ALL {
+                                ui.radio_value(&mut self.snap.surface, mode, mode.label());
+                            }
+                        });
+                        let snap_on = self.snap.enabled;
+                        if ui
+                            .add_sized(
+                                [52.0, 22.0],
+                                egui::Button::new("Snap")
+                                    .corner_radius(6)
+                                    .fill(if snap_on {
+                                        theme::palette().widget_hover
+                                    } else {
+                                        theme::palette().widget_bg
+                                    })
+                                    .stroke(if snap_on {
+                                        Stroke::new(1.0, theme::palette().accent)
+                                    } else {
+                                        Stroke::new(1.0, theme::palette().border)
+                                    }),
+                            )
+                            .on_hover_text("Snap do gizmo e ao posicionar objetos (segure Ctrl para inverter)")
+                            .clicked()
+                        {
+                            self.snap.enabled = !self.snap.enabled;
+                        }
+                        ui.add_space(6.0);
+                        ui.menu_button("Vista", |ui| {
+                            for view in AxisView::ALL {
+                                if ui.button(view.label()).clicked() {
+                                    self.set_axis_view(view);
+                                    ui.close();
+                                }
+                            }
+                            ui.separator();
+                            for split in ViewportSplit::ALL {
+                                ui.radio_value(&mut self.split, split, split.label());
+                            }
+                            ui.menu_button("Marcadores", |ui| {
+                                for slot in 0..CAMERA_BOOKMARK_SLOTS {
+                                    let set = self.camera_bookmarks[slot].is_some();
+                                    ui.horizontal(|ui| {
+                                        if ui
+                                            .add_enabled(
+                                                set,
+                                                egui::Button::new(format!("Ir {} (Ctrl+{})", slot + 1, slot + 1)),
+                                            )
+                                            .clicked()
+                                        {
+                                            self.recall_camera_bookmark(slot);
+                                            ui.close();
+                                        }
+                                        if ui.button("Salvar").clicked() {
+                                            self.save_camera_bookmark(slot);
+                                            ui.close();
+                                        }
+                                    });
+                                }
+                            });
+                            ui.separator();
+                            ui.checkbox(&mut self.show_stats, "Estatísticas");
+                            ui.separator();
+                            ui.label("Navegação (botão direito)");
+                            for mode in CameraNavMode::ALL {
+                                ui.radio_value(&mut self.camera_nav.mode, mode, mode.label());
+                            }
+                            ui.add(
+                                egui::Slider::new(
+                                    &mut self.camera_nav.speed,
+                                    CameraNavSettings::SPEED_RANGE,
+                                )
+                                .logarithmic(true)
+                                .text("Velocidade"),
+                            );
+                            ui.add(
+                                egui::Slider::new(&mut self.camera_nav.acceleration, 0.0..=4.0)
+                                    .text("Aceleração"),
+                            );
+                            ui.checkbox(&mut self.camera_nav.invert_y, "Inverter Y");
+                        });
+                        ui.add_space(6.0);
+
                         let proj_label = if self.is_ortho { "Ortho" } else { "Persp" };
                         if ui
                             .add_sized([74.0, 22.0], egui::Button::new(proj_label).corner_radius(6))
+                            .on_hover_text("Alterna perspectiva / ortográfica (5)")
                             .clicked()
                             && self.is_3d
                         {
@@ -1197,7 +4320,7 @@ impl ViewportPanel {
                                 [52.0, 22.0],
                                 egui::Button::new(dim_label)
                                     .corner_radius(6)
-                                    .stroke(Stroke::new(1.0, Color32::from_rgb(15, 232, 121))),
+                                    .stroke(Stroke::new(1.0, theme::palette().accent)),
                             )
                             .clicked()
                         {
@@ -1212,14 +4335,14 @@ impl ViewportPanel {
                                 egui::Button::new("Local")
                                     .corner_radius(6)
                                     .fill(if local_selected {
-                                        Color32::from_rgb(62, 62, 62)
+                                        theme::palette().widget_hover
                                     } else {
-                                        Color32::from_rgb(44, 44, 44)
+                                        theme::palette().widget_bg
                                     })
                                     .stroke(if local_selected {
-                                        Stroke::new(1.0, Color32::from_rgb(15, 232, 121))
+                                        Stroke::new(1.0, theme::palette().accent)
                                     } else {
-                                        Stroke::new(1.0, Color32::from_gray(70))
+                                        Stroke::new(1.0, theme::palette().border)
                                     }),
                             )
                             .clicked()
@@ -1232,14 +4355,14 @@ impl ViewportPanel {
                                 egui::Button::new("Global")
                                     .corner_radius(6)
                                     .fill(if !local_selected {
-                                        Color32::from_rgb(62, 62, 62)
+                                        theme::palette().widget_hover
                                     } else {
-                                        Color32::from_rgb(44, 44, 44)
+                                        theme::palette().widget_bg
                                     })
                                     .stroke(if !local_selected {
-                                        Stroke::new(1.0, Color32::from_rgb(15, 232, 121))
+                                        Stroke::new(1.0, theme::palette().accent)
                                     } else {
-                                        Stroke::new(1.0, Color32::from_gray(70))
+                                        Stroke::new(1.0, theme::palette().border)
                                     }),
                             )
                             .clicked()
@@ -1290,15 +4413,166 @@ impl ViewportPanel {
                             self.gizmo_mode = GizmoMode::Rotate;
                             self.object_selected = true;
                         }
+                        ui.add_space(10.0);
+
+                        egui::ComboBox::from_id_salt("viewport_shading_mode")
+                            .width(92.0)
+                            .selected_text(self.shading_mode.label())
+                            .show_ui(ui, |ui| {
+                                for mode in ShadingMode::all() {
+                                    ui.selectable_value(&mut self.shading_mode, mode, mode.label());
+                                }
+                            })
+                            .response
+                            .on_hover_text("Modo de shading da viewport");
+                        ui.add_space(6.0);
+
+                        let terrain_button = egui::Button::new("⛰")
+                            .corner_radius(6)
+                            .fill(if self.terrain_tool {
+                                theme::palette().widget_hover
+                            } else {
+                                theme::palette().widget_bg
+                            })
+                            .stroke(if self.terrain_tool {
+                                Stroke::new(1.0, theme::palette().accent)
+                            } else {
+                                Stroke::new(1.0, theme::palette().border)
+                            });
+                        if ui
+                            .add_sized([30.0, 22.0], terrain_button)
+                            .on_hover_text("Esculpir / pintar terreno (objeto selecionado)")
+                            .clicked()
+                        {
+                            self.terrain_tool = !self.terrain_tool;
+                            self.terrain_stroke = false;
+                            self.measure.active = false;
+                        }
+                        ui.add_space(6.0);
+
+                        let measure_button = egui::Button::new("📏")
+                            .corner_radius(6)
+                            .fill(if self.measure.active {
+                                theme::palette().widget_hover
+                            } else {
+                                theme::palette().widget_bg
+                            })
+                            .stroke(if self.measure.active {
+                                Stroke::new(1.0, theme::palette().accent)
+                            } else {
+                                Stroke::new(1.0, theme::palette().border)
+                            });
+                        if ui
+                            .add_sized([30.0, 22.0], measure_button)
+                            .on_hover_text(
+                                "Régua: clique em dois pontos (Shift = pivô do objeto), Esc limpa",
+                            )
+                            .clicked()
+                        {
+                            self.measure.active = !self.measure.active;
+                            self.measure.points.clear();
+                            self.terrain_tool = false;
+                        }
                     },
                 );
 
+                let terrain_target = self.terrain_tool_target();
+                let terrain_panel_rect = terrain_target.as_ref().map(|_| {
+                    Rect::from_min_size(
+                        egui::pos2(viewport_rect.right() - 238.0, viewport_rect.top() + 38.0),
+                        egui::vec2(230.0, 150.0),
+                    )
+                });
+                if let (Some(name), Some(panel_rect)) = (&terrain_target, terrain_panel_rect) {
+                    let mut save = false;
+                    ui.scope_builder(egui::UiBuilder::new().max_rect(panel_rect), |ui| {
+                        egui::Frame::new()
+                            .fill(theme::palette().panel_header)
+                            .stroke(Stroke::new(1.0, theme::palette().widget_hover))
+                            .corner_radius(6)
+                            .inner_margin(egui::Margin::same(8))
+                            .show(ui, |ui| {
+                                ui.set_width(panel_rect.width() - 16.0);
+                                ui.label(
+                                    egui::RichText::new(format!("⛰ {}", name))
+                                        .strong()
+                                        .color(theme::palette().text_strong),
+                                );
+                                let brush = &mut self.terrain_brush;
+                                egui::Grid::new("viewport_terrain_brush")
+                                    .num_columns(2)
+                                    .spacing([10.0, 6.0])
+                                    .show(ui, |ui| {
+                                        ui.label("Pincel");
+                                        egui::ComboBox::from_id_salt("viewport_terrain_mode")
+                                            .width(110.0)
+                                            .selected_text(brush.mode.label())
+                                            .show_ui(ui, |ui| {
+                                                for mode in BrushMode::all() {
+                                                    ui.selectable_value(
+                                                        &mut brush.mode,
+                                                        mode,
+                                                        mode.label(),
+                                                    );
+                                                }
+                                            });
+                                        ui.end_row();
+
+                                        ui.label("Raio");
+                                        ui.add(
+                                            egui::DragValue::new(&mut brush.radius)
+                                                .speed(0.05)
+                                                .range(0.1..=50.0),
+                                        );
+                                        ui.end_row();
+
+                                        ui.label("Força");
+                                        ui.add(
+                                            egui::DragValue::new(&mut brush.strength)
+                                                .speed(0.05)
+                                                .range(0.0..=50.0),
+                                        );
+                                        ui.end_row();
+
+                                        if brush.mode == BrushMode::Paint {
+                                            ui.label("Camada");
+                                            egui::ComboBox::from_id_salt("viewport_terrain_layer")
+                                                .width(110.0)
+                                                .selected_text(format!("Camada {}", brush.layer + 1))
+                                                .show_ui(ui, |ui| {
+                                                    for layer in 0..TERRAIN_LAYERS {
+                                                        ui.selectable_value(
+                                                            &mut brush.layer,
+                                                            layer,
+                                                            format!("Camada {}", layer + 1),
+                                                        );
+                                                    }
+                                                });
+                                            ui.end_row();
+                                        }
+                                    });
+                                save = ui
+                                    .button("Salvar")
+                                    .on_hover_text("Grava o heightmap e o splat map do componente")
+                                    .clicked();
+                            });
+                    });
+                    if save {
+                        self.save_terrain(name);
+                    }
+                }
+
+                let rmb_hint = match self.camera_nav.mode {
+                    CameraNavMode::Orbit => "RMB arrastar olhar (camera fixa)",
+                    CameraNavMode::Fly => "RMB olhar + WASD/QE voar (Shift corre, Scroll velocidade)",
+                    CameraNavMode::Pan => "RMB pan",
+                };
                 ui.painter().text(
                     egui::pos2(viewport_rect.left() + 12.0, viewport_rect.bottom() - 10.0),
                     Align2::LEFT_BOTTOM,
-                    "Mouse (Unity): Alt+LMB orbitar | RMB arrastar olhar (camera fixa) | MMB pan | Alt+RMB zoom | Scroll zoom | LMB selecionar | Touchpad: clique selecionar | 2 dedos pan | Pinch zoom | Ctrl+2 dedos orbitar",
+                    format!("Mouse (Unity): Alt+LMB orbitar | {rmb_hint} | MMB pan | Alt+RMB zoom | Scroll zoom | F enquadrar | LMB selecionar | Touchpad: clique selecionar | 2 dedos pan | Pinch zoom | Ctrl+2 dedos orbitar"),
                     FontId::proportional(11.0),
-                    Color32::from_gray(170),
+                    theme::palette().text_weak,
                 );
 
                 if self.is_3d {
@@ -1311,28 +4585,122 @@ impl ViewportPanel {
                     let middle_down = ctx.input(|i| i.pointer.middle_down());
                     let secondary_down = ctx.input(|i| i.pointer.secondary_down());
                     let pointer_over_controls = ctx.input(|i| {
-                        i.pointer
-                            .hover_pos()
-                            .is_some_and(|p| controls_rect.contains(p))
+                        i.pointer.hover_pos().is_some_and(|p| {
+                            controls_rect.contains(p)
+                                || terrain_panel_rect.is_some_and(|r| r.contains(p))
+                        })
                     });
                     let view_gizmo_rect = Rect::from_min_size(
                         egui::pos2(viewport_rect.right() - 66.0, viewport_rect.bottom() - 66.0),
                         egui::vec2(56.0, 56.0),
                     );
 
-                    let aspect = (viewport_rect.width() / viewport_rect.height()).max(0.1);
-                    let orbit = Vec3::new(
-                        self.camera_yaw.cos() * self.camera_pitch.cos(),
-                        self.camera_pitch.sin(),
-                        self.camera_yaw.sin() * self.camera_pitch.cos(),
-                    );
-                    let eye = self.camera_target + orbit * self.camera_distance;
-                    let view = Mat4::look_at_rh(eye, self.camera_target, Vec3::Y);
-                    let proj = if self.is_ortho {
-                        Mat4::orthographic_rh_gl(-2.0 * aspect, 2.0 * aspect, -2.0, 2.0, 0.1, 50.0)
-                    } else {
-                        Mat4::perspective_rh_gl(45.0_f32.to_radians(), aspect, 0.1, 50.0)
+                    let pixels = viewport_rect.size() * ctx.pixels_per_point();
+                    self.pixel_size = [pixels.x.round() as u32, pixels.y.round() as u32];
+                    // Capturas e gravações projetam a cena com a proporção delas
+                    let capture_aspect = gpu_renderer.and_then(|gpu| gpu.capture_aspect());
+                    if gpu_renderer.is_some_and(|gpu| gpu.capture_busy()) {
+                        ctx.request_repaint();
+                    }
+                    let aspect = capture_aspect
+                        .unwrap_or(viewport_rect.width() / viewport_rect.height())
+                        .max(0.1);
+                    let (eye, view, proj, camera_forward, near, far) = match &game_camera {
+                        Some((camera, transform)) => (
+                            transform.position,
+                            Camera::view_matrix(transform),
+                            camera.projection_matrix(aspect),
+                            transform.forward(),
+                            camera.near,
+                            camera.far,
+                        ),
+                        None => {
+                            let orbit = Vec3::new(
+                                self.camera_yaw.cos() * self.camera_pitch.cos(),
+                                self.camera_pitch.sin(),
+                                self.camera_yaw.sin() * self.camera_pitch.cos(),
+                            );
+                            let eye = self.camera_target + orbit * self.camera_distance;
+                            let view = Mat4::look_at_rh(eye, self.camera_target, Vec3::Y);
+                            let proj = if self.is_ortho {
+                                let half = self.ortho_half_height();
+                                Mat4::orthographic_rh_gl(
+                                    -half * aspect,
+                                    half * aspect,
+                                    -half,
+                                    half,
+                                    VIEWPORT_NEAR,
+                                    VIEWPORT_FAR,
+                                )
+                            } else {
+                                Mat4::perspective_rh_gl(
+                                    VIEWPORT_FOV_DEG.to_radians(),
+                                    aspect,
+                                    VIEWPORT_NEAR,
+                                    VIEWPORT_FAR,
+                                )
+                            };
+                            let forward = self.camera_target - eye;
+                            (eye, view, proj, forward, VIEWPORT_NEAR, VIEWPORT_FAR)
+                        }
                     };
+                    // Com pós-processamento ou AA, céu e cena vão juntos para o slot do
+                    // céu (definido depois que a malha da cena é enviada)
+                    let post_chain = self
+                        .post_process
+                        .as_ref()
+                        .map(post_process_chain)
+                        .unwrap_or_default();
+                    let anti_aliasing = gpu_renderer.map_or(AntiAliasing::Off, |gpu| {
+                        gpu.resolve_anti_aliasing(self.anti_aliasing)
+                    });
+                    // A água lê a cor e a profundidade da cena: só existe offscreen
+                    let offscreen = !post_chain.is_empty()
+                        || anti_aliasing != AntiAliasing::Off
+                        || (gpu_renderer.is_some() && self.water_active())
+                        || capture_aspect.is_some();
+                    if let Some(gpu) = gpu_renderer {
+                        gpu.set_shading_mode(self.shading_mode);
+                        gpu.update_sky(
+                            proj * view,
+                            eye,
+                            &self.environment,
+                            self.environment_map.as_ref(),
+                        );
+                        // A câmera do Game pode trocar a névoa do ambiente
+                        let fog = game_camera
+                            .as_ref()
+                            .and_then(|(camera, _)| camera.fog)
+                            .unwrap_or(self.environment.fog);
+                        gpu.update_fog(&fog);
+                        if offscreen {
+                            // A exposição automática se adapta ao longo dos frames
+                            if post_chain
+                                .iter()
+                                .any(|pass| matches!(pass, PostProcessPass::AutoExposure { .. }))
+                            {
+                                ctx.request_repaint();
+                            }
+                            gpu.update_post_process(
+                                &post_chain,
+                                self.post_lut.as_ref(),
+                                anti_aliasing,
+                                self.pixel_size,
+                            );
+                        } else {
+                            ui.painter().set(
+                                sky_shape,
+                                egui::Shape::Callback(gpu.sky_paint_callback(viewport_rect)),
+                            );
+                        }
+                    }
+                    if self.snap.grid_visible && game_camera.is_none() {
+                        ui.painter().with_clip_rect(viewport_rect).set(
+                            ground_grid_shape,
+                            ground_grid(viewport_rect, proj * view, &self.snap),
+                        );
+                    }
+                    let mut scene_on_gpu = false;
                     if let Some((next_yaw, next_pitch)) = draw_view_orientation_gizmo(ui, view_gizmo_rect, view) {
                         self.camera_yaw = next_yaw;
                         self.camera_pitch = next_pitch;
@@ -1365,39 +4733,51 @@ impl ViewportPanel {
                         });
                     }
 
-                    let key_front =
-                        ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Num1));
-                    let key_side =
-                        ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Num3));
-                    let key_top =
-                        ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Num7));
-                    let key_focus =
-                        ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::F));
-                    if key_front {
-                        self.camera_yaw = -std::f32::consts::FRAC_PI_2;
-                        self.camera_pitch = 0.0;
-                        ui.ctx().request_repaint();
+                    let axis_keys = [
+                        (egui::Modifiers::NONE, egui::Key::Num1, AxisView::Front),
+                        (egui::Modifiers::ALT, egui::Key::Num1, AxisView::Back),
+                        (egui::Modifiers::NONE, egui::Key::Num3, AxisView::Right),
+                        (egui::Modifiers::ALT, egui::Key::Num3, AxisView::Left),
+                        (egui::Modifiers::NONE, egui::Key::Num7, AxisView::Top),
+                        (egui::Modifiers::ALT, egui::Key::Num7, AxisView::Bottom),
+                    ];
+                    for (modifiers, key, view) in axis_keys {
+                        if ctx.input_mut(|i| i.consume_key(modifiers, key)) {
+                            self.set_axis_view(view);
+                            ui.ctx().request_repaint();
+                        }
                     }
-                    if key_side {
-                        self.camera_yaw = 0.0;
-                        self.camera_pitch = 0.0;
-                        ui.ctx().request_repaint();
+                    // Marcadores: Ctrl+Shift+N salva, Ctrl+N volta (o Shift é testado antes)
+                    for (slot, key) in CAMERA_BOOKMARK_KEYS.into_iter().enumerate() {
+                        let save_shortcut = egui::Modifiers::COMMAND | egui::Modifiers::SHIFT;
+                        if ctx.input_mut(|i| i.consume_key(save_shortcut, key)) {
+                            self.save_camera_bookmark(slot);
+                        } else if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, key)) {
+                            self.recall_camera_bookmark(slot);
+                            ui.ctx().request_repaint();
+                        }
                     }
-                    if key_top {
-                        self.camera_yaw = -std::f32::consts::FRAC_PI_2;
-                        self.camera_pitch = 1.45;
+                    let key_ortho =
+                        ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Num5));
+                    if key_ortho && self.is_3d {
+                        self.is_ortho = !self.is_ortho;
                         ui.ctx().request_repaint();
                     }
+                    let key_focus =
+                        ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::F));
                     if key_focus && viewport_resp.hovered() && !pointer_over_controls {
                         self.focus_selected_or_origin();
                         ui.ctx().request_repaint();
                     }
 
+                    let nav = self.camera_nav;
+                    let look_y = if nav.invert_y { -1.0 } else { 1.0 };
+                    let flying = nav.mode == CameraNavMode::Fly && secondary_down && !alt_down;
                     if can_navigate_camera {
                         if self.move_view_mode && primary_down {
                             let right = Vec3::new(self.camera_yaw.sin(), 0.0, -self.camera_yaw.cos());
                             let up = Vec3::Y;
-                            let pan_scale = self.camera_distance * 0.0022;
+                            let pan_scale = self.camera_distance * 0.0022 * nav.speed;
                             self.camera_target += (-pointer_delta.x * pan_scale) * right;
                             self.camera_target += (pointer_delta.y * pan_scale) * up;
                             ui.ctx().request_repaint();
@@ -1433,26 +4813,26 @@ impl ViewportPanel {
                                     let base_pitch = dir.y.clamp(-1.0, 1.0).asin();
                                     self.camera_yaw = base_yaw + pointer_delta.x * 0.012;
                                     self.camera_pitch =
-                                        (base_pitch + pointer_delta.y * 0.009).clamp(-1.45, 1.45);
+                                        (base_pitch + look_y * pointer_delta.y * 0.009).clamp(-1.45, 1.45);
                                     self.camera_target = pivot;
                                     self.camera_distance = len.clamp(0.8, 80.0);
                                 } else {
                                     self.camera_yaw -= pointer_delta.x * 0.012;
-                                    self.camera_pitch = (self.camera_pitch + pointer_delta.y * 0.009)
+                                    self.camera_pitch = (self.camera_pitch + look_y * pointer_delta.y * 0.009)
                                         .clamp(-1.45, 1.45);
                                 }
                             } else {
                                 self.camera_yaw -= pointer_delta.x * 0.012;
-                                self.camera_pitch = (self.camera_pitch - pointer_delta.y * 0.009)
+                                self.camera_pitch = (self.camera_pitch - look_y * pointer_delta.y * 0.009)
                                     .clamp(-1.45, 1.45);
                             }
                             ui.ctx().request_repaint();
                         }
 
-                        // RMB drag:
+                        // RMB drag (modo Orbitar):
                         // - Local: orbit around selected object center.
                         // - Global: keep previous free-look behavior (fixed eye).
-                        if secondary_down && !alt_down {
+                        if secondary_down && !alt_down && nav.mode == CameraNavMode::Orbit {
                             let pivot_local = if self.gizmo_orientation == GizmoOrientation::Local {
                                 self.selected_scene_object.as_ref().and_then(|name| {
                                     self.scene_entries
@@ -1479,12 +4859,12 @@ impl ViewportPanel {
                                     let base_pitch = dir.y.clamp(-1.0, 1.0).asin();
                                     self.camera_yaw = base_yaw + pointer_delta.x * 0.012;
                                     self.camera_pitch =
-                                        (base_pitch + pointer_delta.y * 0.009).clamp(-1.45, 1.45);
+                                        (base_pitch + look_y * pointer_delta.y * 0.009).clamp(-1.45, 1.45);
                                     self.camera_target = pivot;
                                     self.camera_distance = len.clamp(0.8, 80.0);
                                 } else {
                                     self.camera_yaw -= pointer_delta.x * 0.012;
-                                    self.camera_pitch = (self.camera_pitch + pointer_delta.y * 0.009)
+                                    self.camera_pitch = (self.camera_pitch + look_y * pointer_delta.y * 0.009)
                                         .clamp(-1.45, 1.45);
                                 }
                             } else {
@@ -1495,7 +4875,7 @@ impl ViewportPanel {
                                 );
                                 let eye = self.camera_target + old_orbit * self.camera_distance;
                                 self.camera_yaw -= pointer_delta.x * 0.012;
-                                self.camera_pitch = (self.camera_pitch - pointer_delta.y * 0.009)
+                                self.camera_pitch = (self.camera_pitch - look_y * pointer_delta.y * 0.009)
                                     .clamp(-1.45, 1.45);
                                 let new_orbit = Vec3::new(
                                     self.camera_yaw.cos() * self.camera_pitch.cos(),
@@ -1507,11 +4887,26 @@ impl ViewportPanel {
                             ui.ctx().request_repaint();
                         }
 
-                        // Unity-like pan: MMB drag.
-                        if middle_down {
+                        // Modo Voar: RMB olha em volta com o olho parado e libera WASD/QE
+                        if flying {
+                            self.look_around(
+                                pointer_delta.x * 0.012,
+                                look_y * pointer_delta.y * 0.009,
+                            );
+                            self.fly_step(ctx);
+                            // Teclas seguradas não geram eventos: repinta enquanto voa
+                            ui.ctx().request_repaint();
+                        } else {
+                            self.fly_held = 0.0;
+                        }
+
+                        // Unity-like pan: MMB drag (ou RMB no modo Pan).
+                        if middle_down
+                            || (secondary_down && !alt_down && nav.mode == CameraNavMode::Pan)
+                        {
                             let right = Vec3::new(self.camera_yaw.sin(), 0.0, -self.camera_yaw.cos());
                             let up = Vec3::Y;
-                            let pan_scale = self.camera_distance * 0.002;
+                            let pan_scale = self.camera_distance * 0.002 * nav.speed;
                             self.camera_target += (-pointer_delta.x * pan_scale) * right;
                             self.camera_target += (pointer_delta.y * pan_scale) * up;
                             ui.ctx().request_repaint();
@@ -1520,20 +4915,28 @@ impl ViewportPanel {
                         // Unity-like dolly: Alt + RMB drag.
                         if alt_down && secondary_down && pointer_delta.y.abs() > 0.0 {
                             self.camera_distance =
-                                (self.camera_distance + pointer_delta.y * 0.02).clamp(0.8, 80.0);
+                                (self.camera_distance + pointer_delta.y * 0.02 * nav.speed).clamp(0.8, 80.0);
                             ui.ctx().request_repaint();
                         }
 
                         // Scroll zoom (mouse wheel / touchpad scroll).
                         // Quando Ctrl estiver pressionado, o gesto vira orbita (não zoom).
-                        if scroll_delta.y.abs() > 0.0 && !ctrl_down {
+                        // No modo Voar com RMB, a roda ajusta a velocidade (como no Unity).
+                        if flying && scroll_delta.y.abs() > 0.0 {
+                            self.camera_nav.speed = (self.camera_nav.speed
+                                * (1.0 + scroll_delta.y * 0.002).clamp(0.5, 2.0))
+                            .clamp(
+                                *CameraNavSettings::SPEED_RANGE.start(),
+                                *CameraNavSettings::SPEED_RANGE.end(),
+                            );
+                        } else if scroll_delta.y.abs() > 0.0 && !ctrl_down {
                             self.camera_distance =
-                                (self.camera_distance - scroll_delta.y * 0.01).clamp(0.8, 80.0);
+                                (self.camera_distance - scroll_delta.y * 0.01 * nav.speed).clamp(0.8, 80.0);
                             ui.ctx().request_repaint();
                         }
 
                         // Touchpad: dois dedos = pan; Ctrl + dois dedos = orbita.
-                        if scroll_delta.length_sq() > 0.0 {
+                        if scroll_delta.length_sq() > 0.0 && !flying {
                             if ctrl_down {
                                 // Local: orbita em torno do objeto selecionado.
                                 // Global: orbita livre mantendo o ponto de vista atual.
@@ -1562,13 +4965,13 @@ impl ViewportPanel {
                                         let base_pitch = dir.y.clamp(-1.0, 1.0).asin();
                                         self.camera_yaw = base_yaw + scroll_delta.x * 0.008;
                                         self.camera_pitch =
-                                            (base_pitch - scroll_delta.y * 0.006).clamp(-1.45, 1.45);
+                                            (base_pitch - look_y * scroll_delta.y * 0.006).clamp(-1.45, 1.45);
                                         self.camera_target = pivot;
                                         self.camera_distance = len.clamp(0.8, 80.0);
                                     } else {
                                         self.camera_yaw -= scroll_delta.x * 0.008;
                                         self.camera_pitch =
-                                            (self.camera_pitch - scroll_delta.y * 0.006).clamp(-1.45, 1.45);
+                                            (self.camera_pitch - look_y * scroll_delta.y * 0.006).clamp(-1.45, 1.45);
                                     }
                                 } else {
                                     let old_orbit = Vec3::new(
@@ -1579,7 +4982,7 @@ impl ViewportPanel {
                                     let eye = self.camera_target + old_orbit * self.camera_distance;
                                     self.camera_yaw -= scroll_delta.x * 0.008;
                                     self.camera_pitch =
-                                        (self.camera_pitch - scroll_delta.y * 0.006).clamp(-1.45, 1.45);
+                                        (self.camera_pitch - look_y * scroll_delta.y * 0.006).clamp(-1.45, 1.45);
                                     let new_orbit = Vec3::new(
                                         self.camera_yaw.cos() * self.camera_pitch.cos(),
                                         self.camera_pitch.sin(),
@@ -1590,7 +4993,7 @@ impl ViewportPanel {
                             } else {
                                 let right = Vec3::new(self.camera_yaw.sin(), 0.0, -self.camera_yaw.cos());
                                 let up = Vec3::Y;
-                                let pan_scale = self.camera_distance * 0.0016;
+                                let pan_scale = self.camera_distance * 0.0016 * nav.speed;
                                 self.camera_target += (-scroll_delta.x * pan_scale) * right;
                                 self.camera_target += (scroll_delta.y * pan_scale) * up;
                             }
@@ -1604,17 +5007,100 @@ impl ViewportPanel {
                         }
                     }
 
+                    // Ferramenta de terreno: LMB esculpe/pinta onde o cursor toca o terreno
+                    let brush_hit = terrain_target.as_ref().and_then(|name| {
+                        let cursor = ctx.input(|i| i.pointer.hover_pos())?;
+                        if !viewport_rect.contains(cursor) {
+                            return None;
+                        }
+                        let ray = cursor_ray(viewport_rect, proj * view, cursor)?;
+                        self.terrain_hit_local(name, &ray)
+                    });
+                    let sculpting = terrain_target.is_some()
+                        && viewport_resp.hovered()
+                        && primary_down
+                        && !alt_down
+                        && !self.move_view_mode
+                        && !pointer_over_controls
+                        && !pointer_over_view_gizmo;
+                    if let (true, Some(name), Some(hit)) = (sculpting, &terrain_target, brush_hit) {
+                        if !self.terrain_stroke {
+                            // Nivelar usa a altura do início da pincelada
+                            self.terrain_stroke = true;
+                            self.terrain_brush.target_height = hit.y;
+                        }
+                        let dt = ctx.input(|i| i.stable_dt);
+                        let brush = self.terrain_brush;
+                        if let Some(instance) = self.terrains.get_mut(name) {
+                            instance.terrain.apply_brush(hit, &brush, dt);
+                        }
+                        self.sync_terrain_meshes();
+                        ui.ctx().request_repaint();
+                    } else if !primary_down {
+                        self.terrain_stroke = false;
+                    }
+
+                    // Régua: cada clique marca um ponto; o terceiro recomeça
+                    let measure_cursor = ctx
+                        .input(|i| i.pointer.hover_pos())
+                        .filter(|_| self.measure.active && viewport_resp.hovered())
+                        .and_then(|cursor| cursor_ray(viewport_rect, proj * view, cursor))
+                        .and_then(|ray| self.measure_pick(&ray, ctx.input(|i| i.modifiers.shift)));
+                    if self.measure.active
+                        && viewport_resp.clicked_by(PointerButton::Primary)
+                        && !pointer_over_controls
+                        && !pointer_over_view_gizmo
+                        && !alt_down
+                        && !self.move_view_mode
+                    {
+                        if let Some(point) = measure_cursor.clone() {
+                            if self.measure.points.len() >= 2 {
+                                self.measure.points.clear();
+                            }
+                            self.measure.points.push(point);
+                        }
+                    }
+                    if self.measure.active
+                        && !self.measure.points.is_empty()
+                        && ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Escape))
+                    {
+                        self.measure.points.clear();
+                    }
+
                     if viewport_resp.clicked_by(PointerButton::Primary)
                         && !pointer_over_controls
                         && !pointer_over_view_gizmo
                         && !alt_down
                         && !self.move_view_mode
+                        && terrain_target.is_none()
+                        && !self.measure.active
                     {
                         let hover_pos = ctx.input(|i| i.pointer.hover_pos());
-                        if let Some(cursor) = hover_pos {
+                        let modifiers = ctx.input(|i| i.modifiers);
+                        self.pending_pick_modifiers = modifiers;
+                        // Com a cena na GPU, o ID buffer resolve o clique (por pixel)
+                        let gpu_pick = gpu_renderer.filter(|_| !self.gpu_pick_names.is_empty());
+                        if let (Some(cursor), Some(gpu)) = (hover_pos, gpu_pick) {
+                            self.pending_surface_pick = cursor_ray(viewport_rect, proj * view, cursor)
+                                .and_then(|ray| self.pick_surface_by_ray(&ray));
+                            let ppp = ctx.pixels_per_point();
+                            let local = (cursor - viewport_rect.min) * ppp;
+                            let size = viewport_rect.size() * ppp;
+                            gpu.request_pick(
+                                [local.x.max(0.0) as u32, local.y.max(0.0) as u32],
+                                [size.x.round() as u32, size.y.round() as u32],
+                            );
+                            ui.ctx().request_repaint();
+                        } else if let Some(cursor) = hover_pos {
                             let mut best: Option<(f32, String)> = None;
                             let view_proj = proj * view;
-                            for entry in &self.scene_entries {
+                            let ray_pick = cursor_ray(viewport_rect, view_proj, cursor)
+                                .and_then(|ray| self.pick_scene_entry_by_ray(&ray));
+                            if let Some(name) = ray_pick {
+                                best = Some((0.0, name));
+                            }
+                            // Fallback em espaco de tela quando o raio nao acerta nenhuma malha
+                            for entry in self.scene_entries.iter().filter(|_| best.is_none()) {
                                 if let Some((screen, radius_px)) =
                                     Self::scene_entry_screen_hit_info(entry, viewport_rect, view_proj)
                                 {
@@ -1627,36 +5113,71 @@ impl ViewportPanel {
                                     }
                                 }
                             }
-                            if let Some((_, name)) = best {
-                                self.selected_scene_object = Some(name.clone());
-                                self.dropped_asset_label = Some(name);
-                                self.object_selected = true;
+                            self.select_picked_object(best.map(|(_, name)| name), modifiers);
+                        }
+                    }
+                    if let Some((cursor, material)) = self.material_drop_request.take() {
+                        let gpu_pick = gpu_renderer.filter(|_| !self.gpu_pick_names.is_empty());
+                        if let Some(gpu) = gpu_pick {
+                            let ppp = ctx.pixels_per_point();
+                            let local = (cursor - viewport_rect.min) * ppp;
+                            let size = viewport_rect.size() * ppp;
+                            gpu.request_pick(
+                                [local.x.max(0.0) as u32, local.y.max(0.0) as u32],
+                                [size.x.round() as u32, size.y.round() as u32],
+                            );
+                            self.pending_material_pick = Some(material);
+                            ui.ctx().request_repaint();
+                        } else if let Some(name) = cursor_ray(viewport_rect, proj * view, cursor)
+                            .and_then(|ray| self.pick_scene_entry_by_ray(&ray))
+                        {
+                            self.material_drop = Some((name, material));
+                        }
+                    }
+                    if let Some(gpu) = gpu_renderer {
+                        if let Some(hit) = gpu.take_pick_result() {
+                            if let Some(material) = self.pending_material_pick.take() {
+                                // Só malhas: terreno e água não estão no ID buffer
+                                if let Some(name) = hit.and_then(|i| self.gpu_pick_names.get(i).cloned()) {
+                                    self.material_drop = Some((name, material));
+                                }
                             } else {
-                                self.selected_scene_object = None;
-                                self.object_selected = false;
+                                let surface_pick = self.pending_surface_pick.take();
+                                let name = hit
+                                    .and_then(|i| self.gpu_pick_names.get(i).cloned())
+                                    .or(surface_pick);
+                                self.select_picked_object(name, self.pending_pick_modifiers);
                             }
                         }
+                        if gpu.pick_pending() {
+                            ui.ctx().request_repaint();
+                        }
+                        if gpu.textures_loading() {
+                            ui.ctx()
+                                .request_repaint_after(std::time::Duration::from_millis(50));
+                        }
                     }
 
+                    let mut pick_names = Vec::new();
                     if !self.scene_entries.is_empty() {
                         let use_proxy = is_navigating;
                         let mut gpu_drawn = false;
                         if let Some(gpu) = gpu_renderer {
-                            let (scene_batch, texture_conflict) =
-                                self.build_gpu_scene_mesh(use_proxy);
+                            let (scene_batch, texture_conflict, object_ranges) =
+                                self.build_gpu_scene_mesh(use_proxy, eye);
                             if !texture_conflict {
-                                let mesh_id = self.gpu_scene_mesh_id(use_proxy);
-                                let light_dir = Vec3::new(
-                                    self.light_yaw.cos() * self.light_pitch.cos(),
-                                    self.light_pitch.sin(),
-                                    self.light_yaw.sin() * self.light_pitch.cos(),
-                                );
+                                let mesh_id = self.gpu_scene_mesh_id(use_proxy, eye);
+                                let light_dir = self.light_direction();
                                 gpu.update_scene(
                                     mesh_id,
                                     &scene_batch.vertices,
                                     &scene_batch.normals,
                                     &scene_batch.uvs,
                                     &scene_batch.triangles,
+                                    &object_ranges
+                                        .iter()
+                                        .map(|(_, range)| range.clone())
+                                        .collect::<Vec<_>>(),
                                     proj * view,
                                     Mat4::IDENTITY,
                                     eye,
@@ -1666,38 +5187,117 @@ impl ViewportPanel {
                                     self.light_enabled,
                                     scene_batch.texture_path,
                                 );
-                                let cb = gpu.paint_callback(viewport_rect);
-                                ui.painter().add(egui::Shape::Callback(cb));
+                                let clustered = ClusteredLights::build(
+                                    &self.collect_light_instances(),
+                                    view,
+                                    proj,
+                                    near,
+                                    far,
+                                );
+                                gpu.update_lights(&clustered, camera_forward);
+                                let emitters: Vec<(String, ParticleEmitter, Mat4)> = self
+                                    .particle_emitters
+                                    .iter()
+                                    .filter_map(|(name, emitter)| {
+                                        let entry =
+                                            self.scene_entries.iter().find(|e| &e.name == name)?;
+                                        let (_, rotation, translation) =
+                                            entry.transform.to_scale_rotation_translation();
+                                        Some((
+                                            name.clone(),
+                                            emitter.clone(),
+                                            Mat4::from_rotation_translation(rotation, translation),
+                                        ))
+                                    })
+                                    .collect();
+                                gpu.update_particles(
+                                    &emitters,
+                                    &ParticleView {
+                                        view_proj: proj * view,
+                                        camera_right: view.row(0).truncate(),
+                                        camera_up: view.row(1).truncate(),
+                                    },
+                                    ctx.input(|i| i.stable_dt),
+                                );
+                                gpu.update_terrains(self.terrain_uploads(proj * view, eye, light_dir));
+                                gpu.update_sprites(
+                                    proj * view,
+                                    self.sprite_uploads(eye, camera_forward),
+                                );
+                                gpu.update_texts(
+                                    proj * view,
+                                    self.text_uploads(view, eye, camera_forward),
+                                );
+                                let pixels = viewport_rect.size() * ctx.pixels_per_point();
+                                gpu.update_ui_texts(
+                                    self.ui_text_uploads(glam::Vec2::new(pixels.x, pixels.y)),
+                                );
+                                gpu.update_water(self.water_uploads(
+                                    proj * view,
+                                    eye,
+                                    light_dir,
+                                    ctx.input(|i| i.time) as f32,
+                                ));
+                                // Partículas e ondas animam a cada frame
+                                if !emitters.is_empty() || self.water_active() {
+                                    ctx.request_repaint();
+                                }
+                                let selected = object_ranges
+                                    .iter()
+                                    .enumerate()
+                                    .filter(|(_, (name, _))| {
+                                        self.object_selected && self.selection.contains(name)
+                                    })
+                                    .map(|(i, _)| i)
+                                    .collect();
+                                gpu.update_selection(
+                                    selected,
+                                    self.pixel_size,
+                                    SELECTION_OUTLINE_COLOR,
+                                    SELECTION_OUTLINE_WIDTH * ctx.pixels_per_point(),
+                                );
+                                pick_names =
+                                    object_ranges.into_iter().map(|(name, _)| name).collect();
+                                if !offscreen {
+                                    let cb = gpu.paint_callback(viewport_rect);
+                                    ui.painter().add(egui::Shape::Callback(cb));
+                                }
                                 gpu_drawn = true;
                             }
                         }
+                        scene_on_gpu = gpu_drawn;
                         if !gpu_drawn {
+                            // O cache de texturas sai do self enquanto as malhas
+                            // de LOD são emprestadas dele
+                            let mut texture_cache = std::mem::take(&mut self.texture_cache);
                             for entry in &self.scene_entries {
                                 let model = entry.transform;
                                 let mvp_obj = proj * view * model;
-                                let mesh = if is_navigating {
-                                    &entry.proxy
-                                } else {
-                                    &entry.full
+                                let Some(mesh) = self.entry_mesh(entry, is_navigating, eye)
+                                else {
+                                    continue;
                                 };
-                                eprintln!("[VIEWPORT] Renderizando: {} (proxy={}), material_path={:?}", entry.name, is_navigating, mesh.material_path);
+                                log::debug!("[VIEWPORT] Renderizando: {} (proxy={}), material_path={:?}", entry.name, is_navigating, mesh.material_path);
+                                // Sem GPU só o wireframe tem variante; os demais modos usam o sólido
+                                if self.shading_mode == ShadingMode::Wireframe {
+                                    let selected = self.selection.contains(&entry.name);
+                                    draw_wire_mesh(ui, viewport_rect, mvp_obj, mesh, selected);
+                                    continue;
+                                }
                                 draw_solid_mesh(
                                     ui,
                                     viewport_rect,
                                     mvp_obj,
                                     mesh,
-                                    &mut self.texture_cache,
+                                    &mut texture_cache,
                                 );
                             }
+                            self.texture_cache = texture_cache;
                         }
                         for entry in &self.scene_entries {
                             let model = entry.transform;
                             let mvp_obj = proj * view * model;
-                            let selected = self
-                                .selected_scene_object
-                                .as_ref()
-                                .is_some_and(|name| name == &entry.name);
-                            if selected {
+                            if self.selection.contains(&entry.name) {
                                 draw_mesh_silhouette(
                                     ui,
                                     viewport_rect,
@@ -1707,9 +5307,66 @@ impl ViewportPanel {
                                 );
                             }
                         }
+                        for (name, collider) in &self.collider_gizmos {
+                            let Some(entry) = self.scene_entries.iter().find(|o| &o.name == name)
+                            else {
+                                continue;
+                            };
+                            let (_, rotation, translation) =
+                                entry.transform.to_scale_rotation_translation();
+                            let model = Mat4::from_rotation_translation(rotation, translation);
+                            let selected = self.selection.contains(name);
+                            draw_collider_gizmo(
+                                ui,
+                                viewport_rect,
+                                proj * view * model,
+                                collider,
+                                selected,
+                            );
+                        }
+                    }
+                    if let (Some(name), Some(hit)) = (&terrain_target, brush_hit) {
+                        self.draw_terrain_brush(ui, viewport_rect, proj * view, name, hit);
+                    }
+                    for command in &self.debug_draw {
+                        draw_debug_command(ui, viewport_rect, proj * view, command);
+                    }
+                    for command in self.measure_overlay(measure_cursor.as_ref()) {
+                        draw_debug_command(ui, viewport_rect, proj * view, &command);
+                    }
+                    if self.measure.active {
+                        ui.painter().text(
+                            egui::pos2(viewport_rect.center().x, viewport_rect.bottom() - 10.0),
+                            Align2::CENTER_BOTTOM,
+                            "Régua: clique em dois pontos · Shift mede o pivô do objeto · Esc limpa",
+                            FontId::proportional(11.0),
+                            theme::palette().text,
+                        );
+                    }
+                    for &(head, tail, selected) in &self.rig_overlay {
+                        draw_rig_bone(ui, viewport_rect, proj * view, head, tail, selected);
+                    }
+                    self.gpu_pick_names = pick_names;
+                    if let Some(gpu) = gpu_renderer.filter(|_| offscreen) {
+                        ui.painter().set(
+                            sky_shape,
+                            egui::Shape::Callback(
+                                gpu.post_process_callback(viewport_rect, scene_on_gpu),
+                            ),
+                        );
+                    }
+                    if self.show_stats {
+                        self.draw_stats_overlay(
+                            ui,
+                            viewport_rect,
+                            gpu_renderer,
+                            proj * view,
+                            eye,
+                            is_navigating,
+                        );
                     }
 
-                    if self.object_selected {
+                    if self.object_selected && terrain_target.is_none() && !self.measure.active {
                         let selected_name = self.selected_scene_object.clone();
                         let selected_transform = selected_name
                             .as_ref()
@@ -1726,6 +5383,10 @@ impl ViewportPanel {
                             .model_matrix(selected_transform.to_cols_array_2d().into())
                             .mode(self.gizmo_mode)
                             .orientation(self.gizmo_orientation)
+                            .snapping(self.snap.enabled != ctrl_down)
+                            .snap_distance(self.snap.translate)
+                            .snap_angle(self.snap.rotate_deg.to_radians())
+                            .snap_scale(self.snap.scale)
                             .viewport(viewport_rect);
 
                         let gizmo_result = gizmo.interact(ui);
@@ -1739,7 +5400,15 @@ impl ViewportPanel {
                         }
 
                         if let Some(result) = gizmo_result {
-                            let new_transform = Mat4::from(result.transform());
+                            let mut new_transform = Mat4::from(result.transform());
+                            let surface_target = (self.gizmo_mode == GizmoMode::Translate)
+                                .then(|| ctx.input(|i| i.pointer.hover_pos()))
+                                .flatten()
+                                .and_then(|cursor| cursor_ray(viewport_rect, proj * view, cursor))
+                                .and_then(|ray| self.surface_snap_point(&ray));
+                            if let Some(point) = surface_target {
+                                new_transform.w_axis = point.extend(1.0);
+                            }
                             if let Some(name) = selected_name {
                                 if let Some(idx) = self.scene_entries.iter().position(|o| o.name == name) {
                                     let old = self.scene_entries[idx].transform;
@@ -1749,6 +5418,8 @@ impl ViewportPanel {
                                             self.pending_gizmo_undo = false;
                                         }
                                         self.scene_entries[idx].transform = new_transform;
+                                        self.commit_world_transform(idx);
+                                        self.move_selection_with(&name, old, new_transform);
                                     }
                                 }
                             } else {
@@ -1770,6 +5441,13 @@ impl ViewportPanel {
             .and_then(|e| e.full.texture_path.clone())
     }
 
+    pub fn object_material_path(&self, object_name: &str) -> Option<String> {
+        self.scene_entries
+            .iter()
+            .find(|e| e.name == object_name)
+            .and_then(|e| e.full.material_path.clone())
+    }
+
     pub fn set_object_texture_path(&mut self, object_name: &str, path: Option<String>) -> bool {
         if let Some(entry) = self
             .scene_entries
@@ -1784,32 +5462,74 @@ impl ViewportPanel {
     }
 
     pub fn set_object_material_path(&mut self, object_name: &str, path: Option<String>) -> bool {
-        eprintln!(
+        log::debug!(
             "[VIEWPORT] set_object_material_path: objeto={}, path={:?}",
-            object_name, path
+            object_name,
+            path
         );
         if let Some(entry) = self
             .scene_entries
             .iter_mut()
             .find(|e| e.name == object_name)
         {
-            eprintln!(
+            log::debug!(
                 "[VIEWPORT] Material definido: {:?} -> {:?}",
-                entry.full.material_path, path
+                entry.full.material_path,
+                path
             );
             entry.full.material_path = path.clone();
             // Also update proxy mesh
             entry.proxy.material_path = path;
             true
         } else {
-            eprintln!("[VIEWPORT] Objeto nao encontrado: {}", object_name);
+            log::warn!("[VIEWPORT] Objeto nao encontrado: {}", object_name);
             false
         }
     }
 }
 
+/// Terreno do componente, com heightmap e splat map do disco quando configurados
+fn load_terrain(draft: &inspector::TerrainDraft) -> Terrain {
+    let heightmap = draft.heightmap_path.trim();
+    let flat = || Terrain::new(draft.resolution, draft.size, draft.height_scale);
+    let mut terrain = if heightmap.is_empty() {
+        flat()
+    } else {
+        Terrain::from_heightmap(
+            Path::new(heightmap),
+            draft.resolution,
+            draft.size,
+            draft.height_scale,
+        )
+        .unwrap_or_else(|e| {
+            log::error!("[TERRAIN] {}", e);
+            flat()
+        })
+    };
+    let splatmap = draft.splatmap_path.trim();
+    if !splatmap.is_empty() {
+        if let Err(e) = terrain.load_splatmap(Path::new(splatmap)) {
+            log::error!("[TERRAIN] {}", e);
+        }
+    }
+    terrain
+}
+
+fn terrain_mesh_data(mesh: TerrainMesh) -> MeshData {
+    let triangles = mesh.triangles();
+    MeshData {
+        name: TERRAIN_MESH_NAME.to_string(),
+        vertices: mesh.positions,
+        normals: mesh.normals,
+        uvs: mesh.uvs,
+        triangles,
+        texture_path: None,
+        material_path: None,
+    }
+}
+
 fn load_png_as_texture(ctx: &egui::Context, png_path: &str) -> Option<TextureHandle> {
-    let bytes = std::fs::read(png_path).ok()?;
+    let bytes = engine_render::vfs_read(png_path).ok()?;
     let rgba = image::load_from_memory(&bytes).ok()?.to_rgba8();
     let size = [rgba.width() as usize, rgba.height() as usize];
     let color_image = ColorImage::from_rgba_unmultiplied(size, rgba.as_raw());
@@ -1824,7 +5544,7 @@ fn load_mesh_from_path(path: &Path) -> Result<MeshData, String> {
         .ok_or_else(|| "extensão inválida".to_string())?;
 
     let mut mesh = match ext.as_str() {
-        "fbx" => load_fbx_ascii_mesh(path)?,
+        "fbx" => load_fbx_mesh(path)?,
         "obj" => load_obj_mesh(path)?,
         "glb" | "gltf" => load_gltf_mesh(path)?,
         _ => return Err("formato não suportado".to_string()),
@@ -2402,97 +6122,30 @@ fn read_vmesh_cache(source: &Path, stamp: (u64, u64)) -> Result<Option<ViewportM
     Ok(Some(ViewportMeshAsset { full, proxy }))
 }
 
-fn load_fbx_ascii_mesh(path: &Path) -> Result<MeshData, String> {
-    use fbxcel_dom::any::AnyDocument;
-    use fbxcel_dom::v7400::object::{TypedObjectHandle, geometry::TypedGeometryHandle};
-    use std::io::BufReader;
-
-    let file = std::fs::File::open(path).map_err(|e| e.to_string())?;
-    let reader = BufReader::new(file);
-    let doc = match AnyDocument::from_seekable_reader(reader).map_err(|e| e.to_string())? {
-        AnyDocument::V7400(_, doc) => doc,
-        _ => return Err("versão FBX não suportada".to_string()),
-    };
-
-    let mut vertices: Vec<Vec3> = Vec::new();
-    let mut uvs: Vec<[f32; 2]> = Vec::new();
-    let mut triangles: Vec<[u32; 3]> = Vec::new();
-    let mut texture_path: Option<String> = None;
-
-    // Try to find a texture path in the FBX document
-    for obj in doc.objects() {
-        if let TypedObjectHandle::Texture(tex) = obj.get_typed() {
-            if let Some(filename) = tex.name() {
-                // Resolve path relative to the FBX file
-                let fbx_dir = path.parent().unwrap_or(Path::new(""));
-                let full_path = fbx_dir.join(filename);
-                texture_path = Some(normalize_path_string(&full_path.to_string_lossy()));
-                break; // Take the first texture found for simplicity
-            }
-        }
-    }
-
-    for obj in doc.objects() {
-        let TypedObjectHandle::Geometry(TypedGeometryHandle::Mesh(mesh)) = obj.get_typed() else {
-            continue;
-        };
-        let poly_verts = mesh.polygon_vertices().map_err(|e| e.to_string())?;
-        let cps: Vec<_> = poly_verts
-            .raw_control_points()
-            .map_err(|e| e.to_string())?
-            .collect();
-        if cps.is_empty() {
-            continue;
-        }
-        let base = vertices.len() as u32;
-        // FBX forward correction: align imported meshes to editor forward (+Z).
-        vertices.extend(
-            cps.iter()
-                .map(|p| Vec3::new(-(p.x as f32), p.y as f32, -(p.z as f32))),
-        );
-
-        // FBX UVs: fbxcel_dom has limited UV extraction support
-        // For now, use placeholder UVs - FBX files with textures should use GLTF/GLB instead
-        // TODO: Implement proper UV extraction using fbxcel crate's layer element API
-        uvs.resize(vertices.len(), [0.0, 0.0]);
-
-        let mut poly: Vec<u32> = Vec::new();
-        for raw in poly_verts.raw_polygon_vertices() {
-            let is_end = *raw < 0;
-            let local_idx = if is_end {
-                (-raw - 1) as u32
-            } else {
-                *raw as u32
-            };
-            if (local_idx as usize) < cps.len() {
-                poly.push(base + local_idx);
-            }
-            if is_end {
-                if poly.len() >= 3 {
-                    for i in 1..(poly.len() - 1) {
-                        triangles.push([poly[0], poly[i], poly[i + 1]]);
-                    }
-                }
-                poly.clear();
-            }
-        }
-    }
-    if vertices.is_empty() || triangles.is_empty() {
-        return Err("FBX sem malha suportada".to_string());
+fn load_fbx_mesh(path: &Path) -> Result<MeshData, String> {
+    let mesh = engine_render::MeshData::load_from_file(path)?;
+    let mut vertices: Vec<Vec3> = mesh.vertices.iter().map(|v| v.position).collect();
+    // FBX forward correction: align imported meshes to editor forward (+Z).
+    for v in &mut vertices {
+        *v = Vec3::new(-v.x, v.y, -v.z);
     }
-
-    let name = path
-        .file_name()
-        .and_then(|s| s.to_str())
-        .unwrap_or("FBX")
-        .to_string();
     Ok(MeshData {
-        name,
+        name: mesh.name,
         vertices,
         normals: vec![], // Será computado
-        uvs,
-        triangles,
-        texture_path,
+        uvs: mesh
+            .vertices
+            .iter()
+            .map(|v| v.texcoord.to_array())
+            .collect(),
+        triangles: mesh
+            .indices
+            .chunks_exact(3)
+            .map(|t| [t[0], t[1], t[2]])
+            .collect(),
+        texture_path: mesh
+            .albedo_texture_path
+            .map(|p| normalize_path_string(&p.to_string_lossy())),
         material_path: None,
     })
 }
@@ -2755,6 +6408,133 @@ fn append_gltf_node_meshes(
     }
 }
 
+/// Importa a árvore de nós de um .gltf/.glb/.fbx: uma malha por nó, normalizada em conjunto
+fn load_scene_nodes(path: &Path) -> Result<Vec<ImportedSceneNode>, String> {
+    let is_fbx = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("fbx"));
+    let mut assets = AssetManager::new();
+    let scene = if is_fbx {
+        assets.import_fbx(path)?
+    } else {
+        assets.import_gltf(path)?
+    };
+    // FBX forward correction: align imported meshes to editor forward (+Z).
+    let correction = if is_fbx {
+        Mat4::from_rotation_y(std::f32::consts::PI)
+    } else {
+        Mat4::IDENTITY
+    };
+
+    let mut parts = Vec::new();
+    let mut total_triangles = 0;
+    let mut total_vertices = 0;
+    for index in scene.mesh_nodes() {
+        let node = &scene.nodes[index];
+        let mut mesh = MeshData {
+            name: node.name.clone(),
+            vertices: Vec::new(),
+            normals: Vec::new(),
+            uvs: Vec::new(),
+            triangles: Vec::new(),
+            texture_path: None,
+            material_path: None,
+        };
+        for primitive in &node.primitives {
+            let Some(data) = assets.get_mesh(primitive.mesh) else {
+                continue;
+            };
+            let base = mesh.vertices.len() as u32;
+            mesh.vertices
+                .extend(data.vertices.iter().map(|v| v.position));
+            mesh.uvs
+                .extend(data.vertices.iter().map(|v| v.texcoord.to_array()));
+            mesh.triangles.extend(
+                data.indices
+                    .chunks_exact(3)
+                    .map(|t| [base + t[0], base + t[1], base + t[2]]),
+            );
+            if mesh.texture_path.is_none() {
+                mesh.texture_path = primitive
+                    .material
+                    .and_then(|m| gltf_material_texture_path(&assets, m));
+            }
+        }
+        if mesh.triangles.is_empty() {
+            continue;
+        }
+        total_triangles += mesh.triangles.len();
+        total_vertices += mesh.vertices.len();
+        parts.push((mesh, correction * scene.world_matrix(index)));
+    }
+    if parts.is_empty() {
+        return Err("cena sem triângulos suportados".to_string());
+    }
+    if total_triangles > MAX_PARSED_TRIANGLES || total_vertices > MAX_PARSED_VERTICES {
+        return Err("malha excede limite de complexidade para importacao".to_string());
+    }
+
+    // Mesma normalização de `normalize_mesh`, aplicada à cena inteira
+    let mut min = Vec3::splat(f32::INFINITY);
+    let mut max = Vec3::splat(f32::NEG_INFINITY);
+    for (mesh, world) in &parts {
+        for v in &mesh.vertices {
+            let p = world.transform_point3(*v);
+            min = min.min(p);
+            max = max.max(p);
+        }
+    }
+    let center = (min + max) * 0.5;
+    let extents = (max - min).max(Vec3::splat(1e-5));
+    let longest = extents.x.max(extents.y).max(extents.z);
+    let scale = if longest > 0.0 { 1.1 / longest } else { 1.0 };
+    let scale = scale * mesh_import_scale(path);
+    let normalization = Mat4::from_scale(Vec3::splat(scale)) * Mat4::from_translation(-center);
+
+    Ok(parts
+        .into_iter()
+        .map(|(mut mesh, world)| {
+            compute_smooth_normals(&mut mesh);
+            ImportedSceneNode {
+                name: mesh.name.clone(),
+                transform: normalization * world,
+                mesh,
+            }
+        })
+        .collect())
+}
+
+/// Caminho da textura albedo de um material importado; texturas embutidas vão para o cache
+fn gltf_material_texture_path(assets: &AssetManager, material: MaterialHandle) -> Option<String> {
+    let material = assets.get_material(material)?;
+    if let Some(path) = &material.albedo_texture {
+        return Some(normalize_path_string(path));
+    }
+    let texture = assets.get_texture(material.albedo_texture_handle?)?;
+    cache_texture_png(texture)
+}
+
+fn cache_texture_png(texture: &TextureData) -> Option<String> {
+    let mut hasher = DefaultHasher::new();
+    texture.rgba.hash(&mut hasher);
+    let cache_dir = Path::new("Assets").join(".cache").join("textures");
+    fs::create_dir_all(&cache_dir).ok()?;
+    let file_path = cache_dir.join(format!("{:016x}.png", hasher.finish()));
+    if !file_path.exists() {
+        image::save_buffer(
+            &file_path,
+            &texture.rgba,
+            texture.width,
+            texture.height,
+            image::ColorType::Rgba8,
+        )
+        .ok()?;
+    }
+    let path = fs::canonicalize(&file_path).unwrap_or(file_path);
+    Some(normalize_path_string(&path.to_string_lossy()))
+}
+
 fn load_gltf_buffers_mesh_only(path: &Path, gltf: &gltf::Gltf) -> Result<Vec<Vec<u8>>, String> {
     let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
     let mut out = Vec::new();
@@ -2781,6 +6561,34 @@ fn load_gltf_buffers_mesh_only(path: &Path, gltf: &gltf::Gltf) -> Result<Vec<Vec
     Ok(out)
 }
 
+/// Escala de importação do .meta do arquivo, aplicada depois da normalização
+fn mesh_import_scale(path: &Path) -> f32 {
+    MeshImportSettings::default().with_meta(path).scale
+}
+
+fn apply_import_scale(mesh: &mut MeshData, scale: f32) {
+    if scale != 1.0 {
+        for v in &mut mesh.vertices {
+            *v *= scale;
+        }
+    }
+}
+
+/// Caixa (meia-extensão e centro, em espaço local) que envolve a malha
+fn fitted_box_collider(mesh: &MeshData) -> Option<inspector::ColliderDraft> {
+    let first = *mesh.vertices.first()?;
+    let (min, max) = mesh
+        .vertices
+        .iter()
+        .fold((first, first), |(min, max), v| (min.min(*v), max.max(*v)));
+    Some(inspector::ColliderDraft {
+        kind: inspector::ColliderKind::Box,
+        size: ((max - min) * 0.5).max(Vec3::splat(0.01)).to_array(),
+        center: ((min + max) * 0.5).to_array(),
+        ..Default::default()
+    })
+}
+
 fn normalize_mesh(mesh: &mut MeshData) {
     if mesh.vertices.is_empty() {
         return;
@@ -2799,8 +6607,11 @@ fn normalize_mesh(mesh: &mut MeshData) {
     for v in &mut mesh.vertices {
         *v = (*v - center) * scale;
     }
+    compute_smooth_normals(mesh);
+}
 
-    // Calcular normais suaves
+/// Calcula normais suaves (média das normais das faces adjacentes)
+fn compute_smooth_normals(mesh: &mut MeshData) {
     mesh.normals = vec![Vec3::ZERO; mesh.vertices.len()];
     for tri in &mesh.triangles {
         let i0 = tri[0] as usize;
@@ -2834,13 +6645,11 @@ fn draw_view_orientation_gizmo(ui: &mut egui::Ui, rect: Rect, view: Mat4) -> Opt
     painter.circle_filled(
         center,
         radius,
-        Color32::from_rgba_unmultiplied(28, 31, 36, if resp.hovered() { 230 } else { 205 }),
-    );
-    painter.circle_stroke(
-        center,
-        radius,
-        Stroke::new(1.0, Color32::from_rgb(74, 82, 95)),
+        theme::palette()
+            .panel_header
+            .gamma_multiply(if resp.hovered() { 0.9 } else { 0.8 }),
     );
+    painter.circle_stroke(center, radius, Stroke::new(1.0, theme::palette().border));
 
     let axes = [
         (
@@ -2937,6 +6746,84 @@ fn draw_view_orientation_gizmo(ui: &mut egui::Ui, rect: Rect, view: Mat4) -> Opt
     None
 }
 
+/// Raio em espaco de mundo sob o cursor (NDC z de -1 a 1, projecao GL)
+fn cursor_ray(viewport: Rect, view_proj: Mat4, cursor: Pos2) -> Option<Ray> {
+    let inv = view_proj.inverse();
+    if !inv.is_finite() {
+        return None;
+    }
+    let x = (cursor.x - viewport.left()) / viewport.width() * 2.0 - 1.0;
+    let y = 1.0 - (cursor.y - viewport.top()) / viewport.height() * 2.0;
+    let near = inv.project_point3(Vec3::new(x, y, -1.0));
+    let far = inv.project_point3(Vec3::new(x, y, 1.0));
+    let dir = far - near;
+    (dir.length_squared() > 1e-12).then(|| Ray::new(near, dir))
+}
+
+/// Linhas da grade no plano XZ; a cada 10 a linha é mais forte e os eixos
+/// X (vermelho) e Z (azul) ficam coloridos
+fn ground_grid(viewport: Rect, view_proj: Mat4, snap: &SnapSettings) -> egui::Shape {
+    let extent = snap.grid_extent as i32;
+    let half = extent as f32 * snap.grid_spacing;
+    let mut lines = Vec::with_capacity((extent as usize * 2 + 1) * 2);
+    for i in -extent..=extent {
+        let offset = i as f32 * snap.grid_spacing;
+        let (x_color, z_color) = if i == 0 {
+            (
+                Color32::from_rgba_unmultiplied(80, 140, 235, 170),
+                Color32::from_rgba_unmultiplied(220, 80, 80, 170),
+            )
+        } else if i % 10 == 0 {
+            (Color32::from_white_alpha(48), Color32::from_white_alpha(48))
+        } else {
+            (Color32::from_white_alpha(22), Color32::from_white_alpha(22))
+        };
+        // Linha de X constante (paralela a Z) e de Z constante (paralela a X)
+        let segments = [
+            (
+                Vec3::new(offset, 0.0, -half),
+                Vec3::new(offset, 0.0, half),
+                x_color,
+            ),
+            (
+                Vec3::new(-half, 0.0, offset),
+                Vec3::new(half, 0.0, offset),
+                z_color,
+            ),
+        ];
+        for (a, b, color) in segments {
+            if let Some(points) = project_segment(viewport, view_proj, a, b) {
+                lines.push(egui::Shape::line_segment(points, Stroke::new(1.0, color)));
+            }
+        }
+    }
+    egui::Shape::Vec(lines)
+}
+
+/// Projeta um segmento cortando a parte atrás da câmera (project_point
+/// descartaria a linha inteira)
+fn project_segment(viewport: Rect, view_proj: Mat4, a: Vec3, b: Vec3) -> Option<[Pos2; 2]> {
+    const NEAR_W: f32 = 1e-3;
+    let mut clip_a = view_proj * a.extend(1.0);
+    let mut clip_b = view_proj * b.extend(1.0);
+    if clip_a.w < NEAR_W && clip_b.w < NEAR_W {
+        return None;
+    }
+    if clip_a.w < NEAR_W {
+        clip_a = clip_b.lerp(clip_a, (clip_b.w - NEAR_W) / (clip_b.w - clip_a.w));
+    } else if clip_b.w < NEAR_W {
+        clip_b = clip_a.lerp(clip_b, (clip_a.w - NEAR_W) / (clip_a.w - clip_b.w));
+    }
+    let to_screen = |clip: Vec4| {
+        let ndc = clip.truncate() / clip.w;
+        egui::pos2(
+            viewport.left() + (ndc.x * 0.5 + 0.5) * viewport.width(),
+            viewport.top() + (1.0 - (ndc.y * 0.5 + 0.5)) * viewport.height(),
+        )
+    };
+    Some([to_screen(clip_a), to_screen(clip_b)])
+}
+
 fn project_point(viewport: Rect, mvp: Mat4, point: Vec3) -> Option<Pos2> {
     let clip = mvp * point.extend(1.0);
     if clip.w.abs() <= 1e-6 {
@@ -2951,7 +6838,249 @@ fn project_point(viewport: Rect, mvp: Mat4, point: Vec3) -> Option<Pos2> {
     Some(egui::pos2(x, y))
 }
 
-#[allow(dead_code)]
+fn draw_collider_gizmo(
+    ui: &mut egui::Ui,
+    viewport: Rect,
+    mvp: Mat4,
+    collider: &Collider,
+    selected: bool,
+) {
+    let color = match (collider.is_sensor, selected) {
+        (true, _) => Color32::from_rgb(240, 200, 60),
+        (false, true) => Color32::from_rgb(120, 255, 140),
+        (false, false) => Color32::from_rgba_unmultiplied(90, 200, 110, 150),
+    };
+    let stroke = Stroke::new(if selected { 1.4 } else { 1.0 }, color);
+    let painter = ui.painter().with_clip_rect(viewport);
+    let line = |a: Vec3, b: Vec3| {
+        let a = project_point(viewport, mvp, collider.offset + a);
+        let b = project_point(viewport, mvp, collider.offset + b);
+        if let (Some(a), Some(b)) = (a, b) {
+            painter.line_segment([a, b], stroke);
+        }
+    };
+    const SEGMENTS: usize = 32;
+    let circle_point = |axis: usize, radius: f32, t: f32| {
+        let (s, c) = (t.sin() * radius, t.cos() * radius);
+        match axis {
+            0 => Vec3::new(0.0, c, s),
+            1 => Vec3::new(c, 0.0, s),
+            _ => Vec3::new(c, s, 0.0),
+        }
+    };
+
+    match collider.shape {
+        ColliderShape::Cuboid { half_extents: h } => {
+            let corner = |i: usize| {
+                Vec3::new(
+                    if i & 1 == 0 { -h.x } else { h.x },
+                    if i & 2 == 0 { -h.y } else { h.y },
+                    if i & 4 == 0 { -h.z } else { h.z },
+                )
+            };
+            for i in 0..8 {
+                for bit in [1, 2, 4] {
+                    if i & bit == 0 {
+                        line(corner(i), corner(i | bit));
+                    }
+                }
+            }
+        }
+        ColliderShape::Sphere { radius } => {
+            for axis in 0..3 {
+                for k in 0..SEGMENTS {
+                    let t0 = k as f32 / SEGMENTS as f32 * std::f32::consts::TAU;
+                    let t1 = (k + 1) as f32 / SEGMENTS as f32 * std::f32::consts::TAU;
+                    line(
+                        circle_point(axis, radius, t0),
+                        circle_point(axis, radius, t1),
+                    );
+                }
+            }
+        }
+        ColliderShape::Capsule {
+            half_height,
+            radius,
+        } => {
+            let up = Vec3::Y * half_height;
+            for k in 0..SEGMENTS {
+                let t0 = k as f32 / SEGMENTS as f32 * std::f32::consts::TAU;
+                let t1 = (k + 1) as f32 / SEGMENTS as f32 * std::f32::consts::TAU;
+                let (a, b) = (circle_point(1, radius, t0), circle_point(1, radius, t1));
+                line(a + up, b + up);
+                line(a - up, b - up);
+                // Semicirculos das tampas nos planos XY e ZY
+                for axis in [0, 2] {
+                    let (a, b) = (
+                        circle_point(axis, radius, t0),
+                        circle_point(axis, radius, t1),
+                    );
+                    let cap = if a.y + b.y >= 0.0 { up } else { -up };
+                    line(a + cap, b + cap);
+                }
+            }
+            for side in [Vec3::X, -Vec3::X, Vec3::Z, -Vec3::Z] {
+                line(side * radius + up, side * radius - up);
+            }
+        }
+    }
+}
+
+/// Teste conservador de frustum: a caixa só fica de fora se os 8 cantos
+/// estão além do mesmo plano de recorte
+fn aabb_in_frustum(view_proj: Mat4, aabb: &Aabb) -> bool {
+    let corners: Vec<Vec4> = (0..8)
+        .map(|i| {
+            let corner = Vec3::new(
+                if i & 1 == 0 { aabb.min.x } else { aabb.max.x },
+                if i & 2 == 0 { aabb.min.y } else { aabb.max.y },
+                if i & 4 == 0 { aabb.min.z } else { aabb.max.z },
+            );
+            view_proj * corner.extend(1.0)
+        })
+        .collect();
+    let outside = |test: fn(&Vec4) -> bool| corners.iter().all(test);
+    !(outside(|c| c.x < -c.w)
+        || outside(|c| c.x > c.w)
+        || outside(|c| c.y < -c.w)
+        || outside(|c| c.y > c.w)
+        || outside(|c| c.z < -c.w)
+        || outside(|c| c.z > c.w))
+}
+
+/// Osso do editor de rig: losango da cabeça até a ponta, como no Blender
+fn draw_rig_bone(
+    ui: &mut egui::Ui,
+    viewport: Rect,
+    view_proj: Mat4,
+    head: Vec3,
+    tail: Vec3,
+    selected: bool,
+) {
+    let color = if selected {
+        Color32::from_rgb(120, 255, 140)
+    } else {
+        Color32::from_rgba_unmultiplied(220, 220, 235, 200)
+    };
+    let stroke = Stroke::new(if selected { 1.6 } else { 1.1 }, color);
+    let painter = ui.painter().with_clip_rect(viewport);
+    let line = |a: Vec3, b: Vec3| {
+        let a = project_point(viewport, view_proj, a);
+        let b = project_point(viewport, view_proj, b);
+        if let (Some(a), Some(b)) = (a, b) {
+            painter.line_segment([a, b], stroke);
+        }
+    };
+    let dir = tail - head;
+    let length = dir.length();
+    if length <= 1e-6 {
+        if let Some(p) = project_point(viewport, view_proj, head) {
+            painter.circle_stroke(p, 3.0, stroke);
+        }
+        return;
+    }
+    let side = dir.any_orthonormal_vector() * length * 0.1;
+    let up = dir.normalize().cross(side);
+    let waist = head + dir * 0.2;
+    let ring = [waist + side, waist + up, waist - side, waist - up];
+    for i in 0..4 {
+        line(head, ring[i]);
+        line(ring[i], tail);
+        line(ring[i], ring[(i + 1) % 4]);
+    }
+    if let Some(p) = project_point(viewport, view_proj, head) {
+        painter.circle_filled(p, if selected { 3.5 } else { 2.5 }, color);
+    }
+}
+
+/// Overlay de debug: sem teste de profundidade, sempre por cima da cena
+fn draw_debug_command(ui: &mut egui::Ui, viewport: Rect, view_proj: Mat4, command: &DebugCommand) {
+    let [r, g, b, a] = command
+        .color
+        .map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+    let color = Color32::from_rgba_unmultiplied(r, g, b, a);
+    let stroke = Stroke::new(1.5, color);
+    let painter = ui.painter().with_clip_rect(viewport);
+    let line = |a: Vec3, b: Vec3| {
+        let a = project_point(viewport, view_proj, a);
+        let b = project_point(viewport, view_proj, b);
+        if let (Some(a), Some(b)) = (a, b) {
+            painter.line_segment([a, b], stroke);
+        }
+    };
+
+    match &command.shape {
+        DebugShape::Line { start, end } => line(*start, *end),
+        DebugShape::WireBox {
+            center,
+            half_extents: h,
+            rotation,
+        } => {
+            let corner = |i: usize| {
+                *center
+                    + *rotation
+                        * Vec3::new(
+                            if i & 1 == 0 { -h.x } else { h.x },
+                            if i & 2 == 0 { -h.y } else { h.y },
+                            if i & 4 == 0 { -h.z } else { h.z },
+                        )
+            };
+            for i in 0..8 {
+                for bit in [1, 2, 4] {
+                    if i & bit == 0 {
+                        line(corner(i), corner(i | bit));
+                    }
+                }
+            }
+        }
+        DebugShape::Sphere { center, radius } => {
+            const SEGMENTS: usize = 24;
+            for axis in 0..3 {
+                let point = |k: usize| {
+                    let t = k as f32 / SEGMENTS as f32 * std::f32::consts::TAU;
+                    let (s, c) = (t.sin() * radius, t.cos() * radius);
+                    *center
+                        + match axis {
+                            0 => Vec3::new(0.0, c, s),
+                            1 => Vec3::new(c, 0.0, s),
+                            _ => Vec3::new(c, s, 0.0),
+                        }
+                };
+                for k in 0..SEGMENTS {
+                    line(point(k), point(k + 1));
+                }
+            }
+        }
+        DebugShape::Arrow { start, end } => {
+            line(*start, *end);
+            let dir = *end - *start;
+            let length = dir.length();
+            if length <= 1e-5 {
+                return;
+            }
+            let dir = dir / length;
+            let side = dir.any_orthonormal_vector();
+            let up = dir.cross(side);
+            let head = length.min(1.0) * 0.2;
+            let base = *end - dir * head;
+            for offset in [side, -side, up, -up] {
+                line(*end, base + offset * head * 0.5);
+            }
+        }
+        DebugShape::Text { position, text } => {
+            if let Some(pos) = project_point(viewport, view_proj, *position) {
+                painter.text(
+                    pos,
+                    Align2::CENTER_BOTTOM,
+                    text,
+                    FontId::monospace(12.0),
+                    color,
+                );
+            }
+        }
+    }
+}
+
 fn draw_wire_mesh(ui: &mut egui::Ui, viewport: Rect, mvp: Mat4, mesh: &MeshData, selected: bool) {
     let projected: Vec<Option<Pos2>> = mesh
         .vertices
@@ -2959,7 +7088,7 @@ fn draw_wire_mesh(ui: &mut egui::Ui, viewport: Rect, mvp: Mat4, mesh: &MeshData,
         .map(|p| project_point(viewport, mvp, *p))
         .collect();
     let stroke = if selected {
-        Stroke::new(1.5, Color32::from_rgb(15, 232, 121))
+        Stroke::new(1.5, theme::palette().accent)
     } else {
         Stroke::new(1.1, Color32::from_rgb(150, 150, 165))
     };
@@ -2994,7 +7123,7 @@ fn draw_wire_mesh(ui: &mut egui::Ui, viewport: Rect, mvp: Mat4, mesh: &MeshData,
         Align2::LEFT_TOP,
         format!("Mesh: {}", mesh.name),
         FontId::proportional(10.0),
-        Color32::from_gray(180),
+        theme::palette().text_weak,
     );
 }
 
@@ -3040,7 +7169,7 @@ fn draw_solid_mesh(
     });
 
     let has_texture = texture.is_some() && !mesh.uvs.is_empty();
-    eprintln!(
+    log::debug!(
         "[VIEWPORT] has_texture={}, texture.is_some()={}, uvs.len()={}",
         has_texture,
         texture.is_some(),
@@ -3246,8 +7375,8 @@ fn draw_mesh_silhouette(
         return;
     }
 
-    let glow = Stroke::new(3.0, Color32::from_rgba_unmultiplied(15, 232, 121, 70));
-    let line = Stroke::new(1.7, Color32::from_rgb(15, 232, 121));
+    let glow = Stroke::new(3.0, theme::palette().accent.gamma_multiply(0.27));
+    let line = Stroke::new(1.7, theme::palette().accent);
     for i in 0..hull.len() {
         let a = hull[i];
         let b = hull[(i + 1) % hull.len()];
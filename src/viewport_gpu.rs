@@ -1,4 +1,5 @@
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use eframe::egui;
 use eframe::egui_wgpu;
@@ -6,11 +7,85 @@ use eframe::wgpu;
 use eframe::wgpu::util::DeviceExt;
 use eframe::wgpu::{TexelCopyBufferLayout, TexelCopyTextureInfo};
 use glam::{Mat4, Vec3};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
-use engine_render::shader::{LIT_SHADER, LIT_UNIFORM_SIZE, LIT_VERTEX_STRIDE};
+use engine_core::FogSettings;
+use engine_render::asset_importer::{ImportSettings, TextureImportSettings, import_texture};
+use engine_render::asset_manager::TextureData;
+use engine_render::asset_watcher::same_file;
+use engine_render::capture::{
+    CaptureFormat, CapturePixels, CaptureRequest, f16_to_f32, save_capture,
+};
+use engine_render::compute::ComputeContext;
+use engine_render::environment::{EnvironmentMap, EnvironmentSettings, fog_uniform};
+use engine_render::hdr::rgba16f_mip_chain;
+use engine_render::lighting::ClusteredLights;
+use engine_render::memory_budget::{MemoryBudget, texture_gpu_bytes};
+use engine_render::mesh::Mesh;
+use engine_render::particles::{
+    ParticleEmitter, ParticleSpawner, ParticleView, particle_uniform_bytes,
+};
+use engine_render::post_process::{
+    BLOOM_DOWNSCALE, PostProcessPass, lut_strip_size, post_uniform_bytes,
+};
+use engine_render::render_stats::{RenderCounters, RenderStats};
+use engine_render::renderer::AntiAliasing;
+use engine_render::shader::{
+    IBL_SPECULAR_MIPS, LIT_UNIFORM_SIZE, LIT_VERTEX_STRIDE, OUTLINE_UNIFORM_SIZE, PARTICLE_STRIDE,
+    PARTICLE_UNIFORM_SIZE, PARTICLE_WORKGROUP_SIZE, POST_UNIFORM_SIZE, SKY_UNIFORM_SIZE,
+    SPRITE_UNIFORM_SIZE, SPRITE_VERTEX_STRIDE, ShadingMode, TERRAIN_UNIFORM_SIZE,
+    WATER_UNIFORM_SIZE, ibl_prefilter_exponent, water_shader_source,
+};
+use engine_render::shader_library::{ShaderFile, ShaderLibrary};
+use engine_render::sprite::{SpriteVertex, vertex_bytes};
+use engine_render::terrain::TERRAIN_LAYERS;
+use engine_render::texture_sampling::{TextureFilter, TextureSampling};
+use engine_render::vfs::open_image;
+use engine_render::video::{VIDEO_FPS, VideoRecorder};
 
 const MAX_GPU_TRIANGLES: usize = 120_000;
 
+/// Formato do alvo offscreen da cena (HDR, lido pelo pós-processamento)
+const HDR_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+// Frames de vídeo lidos da GPU ao mesmo tempo (além disso o frame é pulado)
+const MAX_VIDEO_READBACKS: usize = 3;
+// Painéis extras da viewport dividida (topo, frente e lado)
+const SPLIT_VIEWS: u32 = 3;
+
+/// Recursos de GPU por vista: o mapa do egui guarda um valor por tipo, e as
+/// vistas da viewport dividida não podem dividir uniforms nem alvos
+struct PerView<T>(HashMap<u32, T>);
+
+fn view_resources<T: Send + Sync + 'static>(
+    resources: &egui_wgpu::CallbackResources,
+    view: u32,
+) -> Option<&T> {
+    resources
+        .get::<PerView<T>>()
+        .and_then(|per_view| per_view.0.get(&view))
+}
+
+fn view_resources_mut<T: Send + Sync + 'static>(
+    resources: &mut egui_wgpu::CallbackResources,
+    view: u32,
+) -> Option<&mut T> {
+    resources
+        .get_mut::<PerView<T>>()
+        .and_then(|per_view| per_view.0.get_mut(&view))
+}
+
+fn view_entry<T: Send + Sync + 'static>(
+    resources: &mut egui_wgpu::CallbackResources,
+    view: u32,
+) -> std::collections::hash_map::Entry<'_, u32, T> {
+    resources
+        .entry::<PerView<T>>()
+        .or_insert_with(|| PerView(HashMap::new()))
+        .0
+        .entry(view)
+}
+
 /// Normaliza um path removendo o prefixo verbatim do Windows (\\?\)
 fn normalize_path(path: &str) -> String {
     if path.starts_with("\\\\?\\") {
@@ -19,8 +94,30 @@ fn normalize_path(path: &str) -> String {
         path.to_string()
     }
 }
+
+/// Lê e decodifica a textura numa thread própria, com as opções de importação do .meta
+fn spawn_texture_load(path: &str) -> TextureLoad {
+    let load: TextureLoad = Arc::new(Mutex::new(None));
+    let slot = load.clone();
+    let path = PathBuf::from(normalize_path(path));
+    std::thread::spawn(move || {
+        let settings = TextureImportSettings::default().with_meta(&path);
+        let texture = import_texture(&path, &settings);
+        *slot.lock().expect("texture load lock") = Some(texture);
+    });
+    load
+}
 const MAX_GPU_VERTICES: usize = 160_000;
+// Nomes da malha do lote e dos terrenos no orçamento de memória da GPU
+const SCENE_MESH_MEMORY_KEY: &str = "Malha da cena";
+const TERRAIN_MEMORY_PREFIX: &str = "Terreno ";
 const GPU_UPLOAD_BUDGET_BYTES: usize = 8 * 1024 * 1024;
+// Tamanhos dos mapas de IBL (equirretangulares 2:1)
+const IBL_BASE_SIZE: (u32, u32) = (128, 64);
+const IBL_IRRADIANCE_SIZE: (u32, u32) = (32, 16);
+// A viewport desenha a cena num único lote, sem material PBR por objeto
+const IBL_DEFAULT_ROUGHNESS: f32 = 0.5;
+const IBL_DEFAULT_METALLIC: f32 = 0.0;
 
 #[derive(Default)]
 struct SceneState {
@@ -37,36 +134,405 @@ struct SceneState {
     light_intensity: f32,
     light_enabled: f32,
     texture_path: Option<String>,
+    // Texturas com opções de amostragem alteradas, recriadas no próximo prepare
+    reload_textures: Vec<String>,
+    // Luzes dinâmicas (forward+): lista, grade de clusters e índices
+    light_bytes: Vec<u8>,
+    cluster_bytes: Vec<u8>,
+    light_index_bytes: Vec<u8>,
+    cluster_params: [f32; 4],
+    camera_forward: [f32; 3],
+    // Névoa do frame já empacotada (`fog_uniform`)
+    fog: [f32; 12],
+    // Faixa de índices (primeiro, quantidade) de cada objeto do lote, para o picking
+    pick_ranges: Vec<[u32; 2]>,
+    pick_request: Option<PickRequest>,
+    // Pedido já enviado para a GPU, aguardando a leitura
+    pick_in_flight: bool,
+    pick_result: Option<Option<usize>>,
+    // Alguma textura ainda sendo decodificada (a viewport deve continuar repintando)
+    textures_loading: bool,
+    // Contorno da seleção: objetos do lote (índices do picking), tamanho da
+    // máscara em pixels e os uniforms (cor, largura)
+    selection: Vec<usize>,
+    outline_size: [u32; 2],
+    outline_uniforms: [u8; OUTLINE_UNIFORM_SIZE],
+    shading: ShadingMode,
+    // Emissores do frame e o estado de emissão de cada um (por nome do objeto)
+    particles: Vec<ParticleUpload>,
+    spawners: HashMap<String, ParticleSpawner>,
+    terrains: Vec<TerrainUpload>,
+    waters: Vec<WaterUpload>,
+    // Sprites do frame, já ordenados, e a view-projection com que foram montados
+    sprites: Vec<QuadUpload>,
+    sprite_view_proj: Mat4,
+    // Textos 3D do frame (quads do atlas de cada fonte), idem
+    texts: Vec<QuadUpload>,
+    text_view_proj: Mat4,
+    // Texto da UI do jogo em clip space (só no Play), desenhado sobre tudo
+    ui_texts: Vec<QuadUpload>,
+    // Memória da GPU por asset; texturas do lote acima do limite são liberadas
+    memory: MemoryBudget,
+}
+
+/// Emissor de partículas com os uniforms do frame já montados
+struct ParticleUpload {
+    name: String,
+    capacity: u32,
+    texture: Option<String>,
+    additive: bool,
+    uniforms: [u8; PARTICLE_UNIFORM_SIZE],
+}
+
+/// Terreno do frame: malha com os LODs já escolhidos, splat map e uniforms.
+/// As chaves dizem quando a malha / o splat map mudaram desde o último envio.
+pub struct TerrainUpload {
+    pub name: String,
+    pub mesh_key: u64,
+    pub vertices: Arc<[u8]>,
+    pub indices: Arc<[u8]>,
+    pub splat_key: u64,
+    pub splat_size: u32,
+    pub splat: Arc<[u8]>,
+    pub layer_textures: [Option<String>; TERRAIN_LAYERS],
+    pub uniforms: [u8; TERRAIN_UNIFORM_SIZE],
+}
+
+/// Superfície de água do frame, desenhada no alvo offscreen depois da cena
+#[derive(Clone)]
+pub struct WaterUpload {
+    pub name: String,
+    pub normal_map: Option<String>,
+    pub uniforms: [u8; WATER_UNIFORM_SIZE],
+}
+
+/// Quads do frame que usam a mesma textura, no vertex layout do `SPRITE_SHADER`
+pub struct QuadUpload {
+    /// Arquivo da textura (vazio = quad branco)
+    pub texture: String,
+    /// Pixels já prontos (atlas SDF de uma fonte); sem eles `texture` é lida do disco
+    pub image: Option<Arc<TextureData>>,
+    pub vertices: Vec<SpriteVertex>,
+    pub indices: Vec<u32>,
+}
+
+/// Pixel (em pixels físicos) consultado no ID buffer de tamanho `size`
+#[derive(Clone, Copy)]
+struct PickRequest {
+    pixel: [u32; 2],
+    size: [u32; 2],
+}
+
+#[derive(Default)]
+struct SkyState {
+    view_proj_inv: [[f32; 4]; 4],
+    camera_pos: [f32; 3],
+    settings: EnvironmentSettings,
+    map: Option<EnvironmentMap>,
+    // Incrementada quando o arquivo do panorama é recarregado do disco
+    map_revision: u64,
+}
+
+#[derive(Default)]
+struct PostState {
+    chain: Vec<PostProcessPass>,
+    // Tamanho da viewport em pixels (alvo offscreen)
+    size: [u32; 2],
+    lut: Option<Arc<TextureData>>,
+    // Já resolvido para o que o dispositivo suporta
+    anti_aliasing: AntiAliasing,
+    // Captura pedida, renderizada no próximo frame no tamanho do pedido
+    capture_request: Option<CaptureRequest>,
+    // Do pedido até o arquivo ser salvo
+    capture_in_flight: bool,
+    // Ok = arquivo salvo, Err = falha
+    capture_results: Vec<Result<PathBuf, String>>,
+    recording: Option<Recording>,
+}
+
+/// Gravação de vídeo: um frame a cada 1/VIDEO_FPS s no tamanho do vídeo
+struct Recording {
+    recorder: VideoRecorder,
+    started: Instant,
+    last_frame: Option<Instant>,
+}
+
+impl Recording {
+    /// Tempo do frame a gravar agora (None = cedo demais para o próximo)
+    fn frame_due(&mut self, now: Instant) -> Option<Duration> {
+        let interval = Duration::from_secs_f64(1.0 / VIDEO_FPS as f64);
+        if self
+            .last_frame
+            .is_some_and(|last| now.duration_since(last) < interval)
+        {
+            return None;
+        }
+        self.last_frame = Some(now);
+        Some(now.duration_since(self.started))
+    }
 }
 
 pub struct ViewportGpuRenderer {
     target_format: wgpu::TextureFormat,
+    // Nºs de amostras MSAA suportados pelo formato da viewport e pela profundidade
+    msaa_samples: Vec<u32>,
     scene: Arc<Mutex<SceneState>>,
+    sky: Arc<Mutex<SkyState>>,
+    post: Arc<Mutex<PostState>>,
+    // Fontes WGSL atuais (recarregadas do disco) e o dispositivo que as valida
+    shaders: Arc<Mutex<ShaderLibrary>>,
+    // Draws e triângulos dos passes da viewport, lidos uma vez por frame
+    counters: Arc<RenderCounters>,
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    // Chave dos recursos de GPU desta vista (0 = viewport principal)
+    view: u32,
+    // Vistas extras da viewport dividida
+    split_views: Vec<ViewportGpuRenderer>,
+}
+
+/// Céu + cena num alvo HDR offscreen (com MSAA opcional), depois a exposição,
+/// a cadeia de pós-processamento e o FXAA na viewport
+struct ScenePostCallback {
+    target_format: wgpu::TextureFormat,
+    view: u32,
+    sky: SkyCallback,
+    scene: Option<Draw3dCallback>,
+    post: Arc<Mutex<PostState>>,
+    shaders: Arc<Mutex<ShaderLibrary>>,
+    counters: Arc<RenderCounters>,
+}
+
+struct PostResources {
+    shader_generation: u64,
+    bright_pipeline: wgpu::RenderPipeline,
+    blur_pipeline: wgpu::RenderPipeline,
+    composite_pipeline: wgpu::RenderPipeline,
+    // Composição num alvo intermediário, lido pelo FXAA
+    composite_ldr_pipeline: wgpu::RenderPipeline,
+    fxaa_pipeline: wgpu::RenderPipeline,
+    // FXAA fora do pass do egui (sem profundidade), para as capturas
+    fxaa_capture_pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    // Exposição automática: média da luminância da cena HDR
+    exposure_pipeline: wgpu::ComputePipeline,
+    exposure_bind_group_layout: wgpu::BindGroupLayout,
+    // Exposição atual e luminância média, lidos pela composição
+    exposure_buffer: wgpu::Buffer,
+    exposure_bind_group: Option<wgpu::BindGroup>,
+    last_frame: Option<Instant>,
+    sampler: wgpu::Sampler,
+    // Parâmetros da cadeia, blur horizontal e blur vertical
+    uniform_buffers: [wgpu::Buffer; 3],
+    black_view: wgpu::TextureView,
+    targets: Option<PostTargets>,
+    lut: Option<(Arc<TextureData>, wgpu::Texture, wgpu::TextureView, u32)>,
+    // Bright pass, blur H, blur V, composição e FXAA
+    bind_groups: Option<[wgpu::BindGroup; 5]>,
+    // Com FXAA a composição é feita offscreen no prepare
+    fxaa: bool,
+    water: Option<WaterResources>,
+    capture: Option<CaptureReadback>,
+    // Frames do vídeo ainda na GPU, em ordem
+    video_frames: Vec<CaptureReadback>,
+}
+
+/// O que fazer com os pixels lidos
+enum CaptureKind {
+    Still(CaptureRequest),
+    // Tempo desde o início da gravação
+    Video(Duration),
+}
+
+/// Captura ou frame de vídeo em leitura: copiado no frame N, mapeado no N+1
+struct CaptureReadback {
+    kind: CaptureKind,
+    size: [u32; 2],
+    buffer: wgpu::Buffer,
+    // Linha no buffer (alinhada a 256 bytes) e bytes por pixel
+    padded_row: u32,
+    pixel_size: u32,
+    bgra: bool,
+    state: PickReadback,
+}
+
+/// Pipeline da água e a cópia da cor da cena lida pela refração
+struct WaterResources {
+    // Amostras da profundidade da cena (muda o tipo da textura no shader)
+    depth_samples: u32,
+    bind_group_layout: wgpu::BindGroupLayout,
+    pipeline: wgpu::RenderPipeline,
+    repeat_sampler: wgpu::Sampler,
+    clamp_sampler: wgpu::Sampler,
+    // Normal map neutro, ligado quando a superfície não tem um
+    flat_normal: (wgpu::Texture, wgpu::TextureView),
+    scene_copy: Option<([u32; 2], wgpu::Texture, wgpu::TextureView)>,
+    surfaces: HashMap<String, WaterBuffers>,
+}
+
+struct WaterBuffers {
+    normal_map_path: Option<String>,
+    normal_map: Option<(wgpu::Texture, wgpu::TextureView)>,
+    uniform_buffer: wgpu::Buffer,
+}
+
+struct PostTargets {
+    size: [u32; 2],
+    sample_count: u32,
+    bloom_size: [u32; 2],
+    // Cor HDR da cena
+    color: (wgpu::Texture, wgpu::TextureView),
+    // Cor multiamostrada, resolvida em `color` no fim do pass da cena
+    msaa_color: Option<(wgpu::Texture, wgpu::TextureView)>,
+    depth: (wgpu::Texture, wgpu::TextureView),
+    bloom: [(wgpu::Texture, wgpu::TextureView); 2],
+    ldr: (wgpu::Texture, wgpu::TextureView),
+}
+
+struct SkyCallback {
+    target_format: wgpu::TextureFormat,
+    view: u32,
+    sky: Arc<Mutex<SkyState>>,
+    shaders: Arc<Mutex<ShaderLibrary>>,
+    counters: Arc<RenderCounters>,
+}
+
+struct SkyResources {
+    shader_generation: u64,
+    pipeline: wgpu::RenderPipeline,
+    shader: wgpu::ShaderModule,
+    pipeline_layout: wgpu::PipelineLayout,
+    // Variante para o alvo HDR offscreen (nº de amostras, pipeline)
+    hdr_pipeline: Option<(u32, wgpu::RenderPipeline)>,
+    uniform_buffer: wgpu::Buffer,
+    bind_group_layout: wgpu::BindGroupLayout,
+    bind_group: Option<wgpu::BindGroup>,
+    sampler: wgpu::Sampler,
+    fallback_view: wgpu::TextureView,
+    // Panorama enviado para a GPU, identificado pelo arquivo de origem e revisão
+    map: Option<((std::path::PathBuf, u64), wgpu::Texture, wgpu::TextureView)>,
+    ibl_pipelines: Option<IblPipelines>,
+    ibl: Option<IblMaps>,
+    next_ibl_id: u64,
+}
+
+struct IblPipelines {
+    bind_group_layout: wgpu::BindGroupLayout,
+    downsample: wgpu::ComputePipeline,
+    irradiance: wgpu::ComputePipeline,
+    prefilter: wgpu::ComputePipeline,
+}
+
+/// Mapas de IBL gerados a partir do panorama do céu
+#[derive(Clone)]
+struct IblMaps {
+    id: u64,
+    irradiance: (wgpu::Texture, wgpu::TextureView),
+    specular: (wgpu::Texture, wgpu::TextureView),
 }
 
 struct Draw3dCallback {
     target_format: wgpu::TextureFormat,
+    view: u32,
     scene: Arc<Mutex<SceneState>>,
+    sky: Arc<Mutex<SkyState>>,
+    shaders: Arc<Mutex<ShaderLibrary>>,
+    counters: Arc<RenderCounters>,
+}
+
+/// Esfera com um material, desenhada no editor de materiais
+struct MaterialPreviewCallback {
+    target_format: wgpu::TextureFormat,
+    // Vista cujo céu fornece o IBL
+    view: u32,
+    preview: MaterialPreview,
+    // Largura / altura do retângulo do preview
+    aspect: f32,
+    sky: Arc<Mutex<SkyState>>,
+    shaders: Arc<Mutex<ShaderLibrary>>,
+}
+
+struct MaterialPreviewResources {
+    shader_generation: u64,
+    pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    bind_group: Option<wgpu::BindGroup>,
+    // Textura e mapas de IBL com que o `bind_group` foi montado
+    bound: Option<(Option<String>, Option<u64>)>,
+    uniform_buffer: wgpu::Buffer,
+    // Sem luzes dinâmicas: buffers vazios só para o layout do shader lit
+    light_buffer: wgpu::Buffer,
+    cluster_buffer: wgpu::Buffer,
+    light_index_buffer: wgpu::Buffer,
+    vertex_buffer: wgpu::Buffer,
+    index_buffer: wgpu::Buffer,
+    index_count: u32,
+    sampler: wgpu::Sampler,
+    white_pixel_view: wgpu::TextureView,
+    // Textura albedo carregada, por path (`None` = arquivo ilegível)
+    texture: Option<(String, Option<wgpu::TextureView>)>,
+    ibl_fallback_view: wgpu::TextureView,
+    env_sampler: wgpu::Sampler,
 }
 
 struct GpuResources {
+    // Geração da `ShaderLibrary` com que os pipelines foram criados
+    shader_generation: u64,
     solid_pipeline: wgpu::RenderPipeline,
+    // Formato da viewport, alvo do `solid_pipeline`
+    target_format: wgpu::TextureFormat,
+    shader: wgpu::ShaderModule,
+    pipeline_layout: wgpu::PipelineLayout,
+    // Variante para o alvo HDR offscreen (nº de amostras, pipeline)
+    hdr_pipeline: Option<(u32, wgpu::RenderPipeline)>,
+    // Modo de shading dos dois pipelines acima
+    shading: ShadingMode,
     uniform_buffer: wgpu::Buffer,
     uniform_data: [u8; LIT_UNIFORM_SIZE],
     bind_group_layout: wgpu::BindGroupLayout,
     current_bind_group: Option<wgpu::BindGroup>,
+    light_buffer: wgpu::Buffer,
+    cluster_buffer: wgpu::Buffer,
+    light_index_buffer: wgpu::Buffer,
     vertex_buffer: Option<wgpu::Buffer>,
     index_buffer: Option<wgpu::Buffer>,
     index_count: u32,
+    // Arestas (line list) da malha enviada, para o modo wireframe: (mesh, buffer, índices)
+    wire_index: Option<(u64, wgpu::Buffer, u32)>,
     uploaded_mesh_id: u64,
     pending_mesh_upload: Option<PendingMeshUpload>,
     staged_vertices: Vec<[f32; 3]>,
     staged_normals: Vec<[f32; 3]>,
     staged_uvs: Vec<[f32; 2]>,
     staged_triangles: Vec<[u32; 3]>,
+    // Faixas de picking da malha já enviada
+    pick_ranges: Vec<[u32; 2]>,
+    pick: Option<PickResources>,
+    outline: Option<OutlineResources>,
+    particles: Option<ParticleResources>,
+    terrain: Option<TerrainResources>,
+    sprites: Option<QuadResources>,
+    texts: Option<QuadResources>,
+    ui_texts: Option<QuadResources>,
     textures: std::collections::HashMap<String, (wgpu::Texture, wgpu::TextureView, wgpu::Sampler)>,
     current_texture_path: Option<String>,
+    // Texturas sendo decodificadas em segundo plano, por path
+    texture_loads: HashMap<String, TextureLoad>,
+    // Geração de mips das texturas importadas (criado no primeiro uso)
+    mipmap: Option<MipmapPipeline>,
     white_pixel_texture: (wgpu::Texture, wgpu::TextureView, wgpu::Sampler),
+    // IBL: mapas pretos quando não há panorama, sampler com U repetido
+    ibl_fallback_view: wgpu::TextureView,
+    env_sampler: wgpu::Sampler,
+    bound_ibl_id: Option<u64>,
+}
+
+/// Resultado da decodificação de uma textura, preenchido pela thread de leitura
+type TextureLoad = Arc<Mutex<Option<Result<TextureData, String>>>>;
+
+struct MipmapPipeline {
+    pipeline: wgpu::ComputePipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
 }
 
 struct PendingMeshUpload {
@@ -78,16 +544,456 @@ struct PendingMeshUpload {
     vertex_buffer: wgpu::Buffer,
     index_buffer: wgpu::Buffer,
     index_count: u32,
+    pick_ranges: Vec<[u32; 2]>,
+}
+
+/// Pipelines das partículas, criados com o primeiro emissor
+struct ParticleResources {
+    shader: wgpu::ShaderModule,
+    sim_pipeline: wgpu::ComputePipeline,
+    sim_bind_group_layout: wgpu::BindGroupLayout,
+    render_bind_group_layout: wgpu::BindGroupLayout,
+    render_pipeline_layout: wgpu::PipelineLayout,
+    // Alpha blend e aditivo, no formato da viewport
+    direct: [wgpu::RenderPipeline; 2],
+    // Variantes para o alvo HDR offscreen (nº de amostras, pipelines)
+    hdr: Option<(u32, [wgpu::RenderPipeline; 2])>,
+    sampler: wgpu::Sampler,
+    emitters: HashMap<String, ParticleBuffers>,
+}
+
+/// Buffer de partículas (anel) de um emissor e os seus bind groups
+struct ParticleBuffers {
+    capacity: u32,
+    texture: Option<String>,
+    additive: bool,
+    uniform_buffer: wgpu::Buffer,
+    _particle_buffer: wgpu::Buffer,
+    _sheet: Option<(wgpu::Texture, wgpu::TextureView)>,
+    sim_bind_group: wgpu::BindGroup,
+    render_bind_group: wgpu::BindGroup,
+}
+
+/// Pipeline do terreno (splatting), criado com o primeiro terreno
+struct TerrainResources {
+    shader: wgpu::ShaderModule,
+    bind_group_layout: wgpu::BindGroupLayout,
+    pipeline_layout: wgpu::PipelineLayout,
+    direct: wgpu::RenderPipeline,
+    // Variante para o alvo HDR offscreen (nº de amostras, pipeline)
+    hdr: Option<(u32, wgpu::RenderPipeline)>,
+    // Camadas repetem; o splat map fica preso na borda
+    layer_sampler: wgpu::Sampler,
+    splat_sampler: wgpu::Sampler,
+    terrains: HashMap<String, TerrainBuffers>,
+}
+
+/// Malha, splat map e texturas das camadas de um terreno
+struct TerrainBuffers {
+    mesh_key: u64,
+    vertex_buffer: wgpu::Buffer,
+    index_buffer: wgpu::Buffer,
+    index_count: u32,
+    splat_key: u64,
+    splat_size: u32,
+    splat: wgpu::Texture,
+    layer_textures: [Option<String>; TERRAIN_LAYERS],
+    layers: Vec<(wgpu::Texture, wgpu::TextureView)>,
+    uniform_buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+}
+
+/// Como os quads de um `QuadResources` amostram a textura e testam a profundidade
+#[derive(Clone, Copy)]
+struct QuadStyle {
+    // sRGB para as cores dos sprites, linear para a distância dos atlas SDF
+    texture_format: wgpu::TextureFormat,
+    // Nearest mantém a pixel art nítida; linear suaviza as bordas do SDF
+    filter: wgpu::FilterMode,
+    // Always para o texto da UI, que fica sobre a cena
+    depth_compare: wgpu::CompareFunction,
+}
+
+const SPRITE_QUADS: QuadStyle = QuadStyle {
+    texture_format: wgpu::TextureFormat::Rgba8UnormSrgb,
+    filter: wgpu::FilterMode::Nearest,
+    depth_compare: wgpu::CompareFunction::LessEqual,
+};
+
+const TEXT_QUADS: QuadStyle = QuadStyle {
+    texture_format: wgpu::TextureFormat::Rgba8Unorm,
+    filter: wgpu::FilterMode::Linear,
+    depth_compare: wgpu::CompareFunction::LessEqual,
+};
+
+const UI_TEXT_QUADS: QuadStyle = QuadStyle {
+    depth_compare: wgpu::CompareFunction::Always,
+    ..TEXT_QUADS
+};
+
+/// Quads com alpha blend no vertex layout do `SPRITE_SHADER`, criados com o
+/// primeiro sprite (ou texto)
+struct QuadResources {
+    shader: wgpu::ShaderModule,
+    pipeline_layout: wgpu::PipelineLayout,
+    bind_group_layout: wgpu::BindGroupLayout,
+    // Entradas de vértice e de fragmento do shader
+    entry_points: [&'static str; 2],
+    direct: wgpu::RenderPipeline,
+    // Variante para o alvo HDR offscreen (nº de amostras, pipeline)
+    hdr: Option<(u32, wgpu::RenderPipeline)>,
+    // View-projection do frame, a mesma para todos os quads
+    uniform_buffer: wgpu::Buffer,
+    sampler: wgpu::Sampler,
+    style: QuadStyle,
+    // Textura por caminho e o bind group que a amostra (None = falhou ao carregar)
+    textures: HashMap<String, Option<(wgpu::Texture, wgpu::BindGroup)>>,
+    // Quads sem textura (ou com a textura ilegível) saem brancos
+    white_bind_group: wgpu::BindGroup,
+    batches: Vec<QuadBuffers>,
+}
+
+/// Vértices e índices de um `QuadUpload`, refeitos a cada frame
+struct QuadBuffers {
+    texture: String,
+    vertex_buffer: wgpu::Buffer,
+    index_buffer: wgpu::Buffer,
+    index_count: u32,
+}
+
+/// ID buffer do picking, criado no primeiro clique
+struct PickResources {
+    pipeline: wgpu::RenderPipeline,
+    // Tamanho, IDs (R32Uint) e profundidade
+    targets: Option<(
+        [u32; 2],
+        wgpu::Texture,
+        wgpu::TextureView,
+        wgpu::TextureView,
+    )>,
+    readback: wgpu::Buffer,
+    state: PickReadback,
+}
+
+/// Contorno da seleção, criado com a primeira seleção: os objetos selecionados
+/// entram numa máscara (pipeline do picking) e a borda é desenhada no pass do egui
+struct OutlineResources {
+    pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    uniform_buffer: wgpu::Buffer,
+    // Tamanho, máscara (R32Uint), profundidade e o bind group que lê a máscara
+    targets: Option<(
+        [u32; 2],
+        wgpu::TextureView,
+        wgpu::TextureView,
+        wgpu::BindGroup,
+    )>,
+    // A máscara deste frame tem algum objeto
+    active: bool,
+}
+
+/// Leitura da GPU (pixel do pick ou captura): copiada no frame N, mapeada no N+1
+/// e lida quando o map termina
+enum PickReadback {
+    Idle,
+    Copied,
+    Mapping(Arc<Mutex<Option<bool>>>),
+}
+
+/// Material desenhado na esfera de preview. Metálico e rugosidade só aparecem
+/// com o IBL do céu ligado, como na cena
+#[derive(Clone, Debug, PartialEq)]
+pub struct MaterialPreview {
+    pub albedo: [f32; 3],
+    pub metallic: f32,
+    pub roughness: f32,
+    pub texture_path: Option<String>,
+    /// Giro da esfera em torno de Y, em radianos
+    pub yaw: f32,
 }
 
 impl ViewportGpuRenderer {
     pub fn new(render_state: egui_wgpu::RenderState) -> Self {
-        Self {
+        let color_flags = render_state
+            .adapter
+            .get_texture_format_features(HDR_FORMAT)
+            .flags;
+        let depth_flags = render_state
+            .adapter
+            .get_texture_format_features(wgpu::TextureFormat::Depth24Plus)
+            .flags;
+        // Sem esta feature o dispositivo só garante 4 amostras
+        let adapter_specific = render_state
+            .device
+            .features()
+            .contains(wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES);
+        let msaa_samples = AntiAliasing::MSAA_SAMPLE_COUNTS
+            .into_iter()
+            .filter(|&samples| {
+                (adapter_specific || samples == 4)
+                    && color_flags.sample_count_supported(samples)
+                    && color_flags.contains(wgpu::TextureFormatFeatureFlags::MULTISAMPLE_RESOLVE)
+                    && depth_flags.sample_count_supported(samples)
+            })
+            .collect();
+        let mut renderer = Self {
             target_format: render_state.target_format,
+            msaa_samples,
+            scene: Arc::new(Mutex::new(SceneState::default())),
+            sky: Arc::new(Mutex::new(SkyState::default())),
+            post: Arc::new(Mutex::new(PostState::default())),
+            shaders: Arc::new(Mutex::new(ShaderLibrary::default())),
+            counters: Arc::new(RenderCounters::default()),
+            device: render_state.device.clone(),
+            queue: render_state.queue.clone(),
+            view: 0,
+            split_views: Vec::new(),
+        };
+        renderer.split_views = (1..=SPLIT_VIEWS)
+            .map(|view| renderer.split_view(view))
+            .collect();
+        renderer
+    }
+
+    /// Renderizador de uma vista extra: divide dispositivo, shaders e contadores
+    /// com a viewport principal, com cena, céu e recursos de GPU próprios
+    fn split_view(&self, view: u32) -> Self {
+        Self {
+            target_format: self.target_format,
+            msaa_samples: self.msaa_samples.clone(),
             scene: Arc::new(Mutex::new(SceneState::default())),
+            sky: Arc::new(Mutex::new(SkyState::default())),
+            post: Arc::new(Mutex::new(PostState::default())),
+            shaders: self.shaders.clone(),
+            counters: self.counters.clone(),
+            device: self.device.clone(),
+            queue: self.queue.clone(),
+            view,
+            split_views: Vec::new(),
+        }
+    }
+
+    /// Renderizadores dos painéis extras da viewport dividida, em ordem
+    pub fn split_views(&self) -> &[ViewportGpuRenderer] {
+        &self.split_views
+    }
+
+    /// Relê os shaders alterados em disco. Cada um é compilado antes de entrar em
+    /// uso; com erro, os pipelines continuam com a versão anterior. Uma mensagem
+    /// por arquivo: Ok = recarregado, Err = erro de compilação
+    pub fn reload_shaders(&self) -> Vec<Result<String, String>> {
+        let mut shaders = self.shaders.lock().expect("shaders lock");
+        let mut messages = Vec::new();
+        for change in shaders.poll() {
+            let name = change.file.file_name();
+            match validate_wgsl(&self.device, name, &change.source) {
+                Ok(()) => {
+                    shaders.apply(change);
+                    messages.push(Ok(format!("Shader recarregado: {}", name)));
+                }
+                Err(e) => messages.push(Err(format!("Erro ao compilar {}: {}", name, e))),
+            }
+        }
+        messages
+    }
+
+    /// Recria a textura na próxima frame (opções de amostragem ou arquivo alterados);
+    /// aceita caminhos relativos ou absolutos
+    pub fn reload_texture(&self, path: &str) {
+        self.scene
+            .lock()
+            .expect("scene lock")
+            .reload_textures
+            .push(normalize_path(path));
+        for split in &self.split_views {
+            split.reload_texture(path);
+        }
+    }
+
+    /// API de compute no mesmo dispositivo da viewport (simulações do usuário)
+    pub fn compute(&self) -> ComputeContext {
+        ComputeContext::new(self.device.clone(), self.queue.clone())
+    }
+
+    /// Modo de AA efetivo neste dispositivo (MSAA sem suporte cai para FXAA)
+    pub fn resolve_anti_aliasing(&self, mode: AntiAliasing) -> AntiAliasing {
+        mode.resolve(&self.msaa_samples)
+    }
+
+    /// Cadeia de pós-processamento da câmera e AA; `size` é a viewport em pixels
+    pub fn update_post_process(
+        &self,
+        chain: &[PostProcessPass],
+        lut: Option<&Arc<TextureData>>,
+        anti_aliasing: AntiAliasing,
+        size: [u32; 2],
+    ) {
+        let mut p = self.post.lock().expect("post lock");
+        p.chain.clear();
+        p.chain.extend_from_slice(chain);
+        p.size = [size[0].max(1), size[1].max(1)];
+        p.lut = lut.cloned();
+        p.anti_aliasing = self.resolve_anti_aliasing(anti_aliasing);
+    }
+
+    /// Pede uma captura da cena na resolução de `request`: é renderizada offscreen
+    /// no próximo frame da viewport e salva em segundo plano
+    pub fn request_capture(&self, request: CaptureRequest) -> Result<(), String> {
+        let max = self.device.limits().max_texture_dimension_2d;
+        if request.width > max || request.height > max {
+            return Err(format!(
+                "Resolução {}x{} excede o limite da GPU ({max})",
+                request.width, request.height
+            ));
+        }
+        let mut p = self.post.lock().expect("post lock");
+        if p.capture_in_flight {
+            return Err("Já existe uma captura em andamento".to_string());
+        }
+        if p.recording.is_some() {
+            return Err("Pare a gravação de vídeo antes de capturar".to_string());
+        }
+        p.capture_request = Some(request);
+        p.capture_in_flight = true;
+        Ok(())
+    }
+
+    /// Começa a gravar a viewport em `path` (.mp4 ou .webm) no tamanho `size`
+    pub fn start_recording(&self, path: PathBuf, size: [u32; 2]) -> Result<(), String> {
+        let max = self.device.limits().max_texture_dimension_2d;
+        if size[0] > max || size[1] > max {
+            return Err(format!(
+                "Resolução {}x{} excede o limite da GPU ({max})",
+                size[0], size[1]
+            ));
+        }
+        let mut p = self.post.lock().expect("post lock");
+        if p.capture_in_flight || p.recording.is_some() {
+            return Err("Já existe uma captura em andamento".to_string());
+        }
+        p.recording = Some(Recording {
+            recorder: VideoRecorder::start(path, size[0], size[1])?,
+            started: Instant::now(),
+            last_frame: None,
+        });
+        Ok(())
+    }
+
+    /// Para a gravação; o arquivo é fechado em segundo plano e o resultado sai
+    /// em `take_capture_results`
+    pub fn stop_recording(&self) {
+        let recording = self.post.lock().expect("post lock").recording.take();
+        if let Some(recording) = recording {
+            finish_recording(&self.post, recording);
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.post.lock().expect("post lock").recording.is_some()
+    }
+
+    /// Proporção da captura pendente ou da gravação; a viewport projeta a cena
+    /// com ela para a imagem não sair esticada
+    pub fn capture_aspect(&self) -> Option<f32> {
+        let p = self.post.lock().expect("post lock");
+        p.capture_request
+            .as_ref()
+            .map(CaptureRequest::aspect)
+            .or_else(|| {
+                p.recording.as_ref().map(|recording| {
+                    let [width, height] = recording.recorder.size();
+                    width as f32 / height as f32
+                })
+            })
+    }
+
+    /// Há captura ou gravação em andamento (a viewport continua repintando)
+    pub fn capture_busy(&self) -> bool {
+        let p = self.post.lock().expect("post lock");
+        p.capture_in_flight || p.recording.is_some()
+    }
+
+    /// Capturas concluídas desde a última chamada: Ok = arquivo salvo
+    pub fn take_capture_results(&self) -> Vec<Result<PathBuf, String>> {
+        std::mem::take(&mut self.post.lock().expect("post lock").capture_results)
+    }
+
+    /// Substitui os callbacks do céu e da cena: os dois são renderizados offscreen
+    /// e o resultado passa pela cadeia de pós-processamento
+    pub fn post_process_callback(&self, rect: egui::Rect, draw_scene: bool) -> egui::PaintCallback {
+        egui_wgpu::Callback::new_paint_callback(
+            rect,
+            ScenePostCallback {
+                target_format: self.target_format,
+                view: self.view,
+                sky: SkyCallback {
+                    target_format: self.target_format,
+                    view: self.view,
+                    sky: self.sky.clone(),
+                    shaders: self.shaders.clone(),
+                    counters: self.counters.clone(),
+                },
+                scene: draw_scene.then(|| Draw3dCallback {
+                    target_format: self.target_format,
+                    view: self.view,
+                    scene: self.scene.clone(),
+                    sky: self.sky.clone(),
+                    shaders: self.shaders.clone(),
+                    counters: self.counters.clone(),
+                }),
+                post: self.post.clone(),
+                shaders: self.shaders.clone(),
+                counters: self.counters.clone(),
+            },
+        )
+    }
+
+    /// Atualiza o céu da cena (panorama ou gradiente) para a câmera atual
+    pub fn update_sky(
+        &self,
+        view_proj: Mat4,
+        camera_pos: Vec3,
+        settings: &EnvironmentSettings,
+        map: Option<&EnvironmentMap>,
+    ) {
+        let mut s = self.sky.lock().expect("sky lock");
+        s.view_proj_inv = view_proj.inverse().to_cols_array_2d();
+        s.camera_pos = [camera_pos.x, camera_pos.y, camera_pos.z];
+        s.settings = *settings;
+        let same_map = match (&s.map, map) {
+            (Some(a), Some(b)) => a.source_path == b.source_path,
+            (None, None) => true,
+            _ => false,
+        };
+        if !same_map {
+            s.map = map.cloned();
+        }
+    }
+
+    /// O panorama atual mudou em disco: reenvia para a GPU e refaz o IBL
+    pub fn reload_environment(&self) {
+        let mut s = self.sky.lock().expect("sky lock");
+        s.map = None;
+        s.map_revision += 1;
+        drop(s);
+        for split in &self.split_views {
+            split.reload_environment();
         }
     }
 
+    pub fn sky_paint_callback(&self, rect: egui::Rect) -> egui::PaintCallback {
+        egui_wgpu::Callback::new_paint_callback(
+            rect,
+            SkyCallback {
+                target_format: self.target_format,
+                view: self.view,
+                sky: self.sky.clone(),
+                shaders: self.shaders.clone(),
+                counters: self.counters.clone(),
+            },
+        )
+    }
+
     pub fn update_scene(
         &self,
         mesh_id: u64,
@@ -95,6 +1001,7 @@ impl ViewportGpuRenderer {
         normals: &[Vec3],
         uvs: &[[f32; 2]],
         triangles: &[[u32; 3]],
+        object_ranges: &[std::ops::Range<u32>],
         mvp: Mat4,
         model: Mat4,
         camera_pos: Vec3,
@@ -135,7 +1042,7 @@ impl ViewportGpuRenderer {
             while s.uvs.len() < s.vertices.len() {
                 s.uvs.push([0.0, 0.0]);
             }
-            eprintln!(
+            log::debug!(
                 "[GPU] Mesh upload: vertices={}, normals={}, uvs={}, triangles={}",
                 s.vertices.len(),
                 s.normals.len(),
@@ -144,6 +1051,8 @@ impl ViewportGpuRenderer {
             );
             let tri_target = triangles.len().min(MAX_GPU_TRIANGLES).max(1);
             let tri_step = ((triangles.len() as f32 / tri_target as f32).ceil() as usize).max(1);
+            // Triângulo de origem de cada triângulo mantido (crescente)
+            let mut kept_source = Vec::with_capacity(tri_target);
             for (i, tri) in triangles.iter().enumerate() {
                 if i % tri_step != 0 || s.triangles.len() >= tri_target {
                     continue;
@@ -155,7 +1064,16 @@ impl ViewportGpuRenderer {
                     continue;
                 }
                 s.triangles.push(*tri);
+                kept_source.push(i as u32);
             }
+            s.pick_ranges = object_ranges
+                .iter()
+                .map(|range| {
+                    let first = kept_source.partition_point(|&t| t < range.start) as u32;
+                    let end = kept_source.partition_point(|&t| t < range.end) as u32;
+                    [first * 3, (end - first) * 3]
+                })
+                .collect();
         }
 
         s.mvp = mvp.to_cols_array_2d();
@@ -167,55 +1085,213 @@ impl ViewportGpuRenderer {
         s.light_enabled = if light_enabled { 1.0 } else { 0.0 };
     }
 
+    /// Atualiza as luzes pontuais/spot (e direcionais extras) já distribuídas nos clusters
+    pub fn update_lights(&self, lights: &ClusteredLights, camera_forward: Vec3) {
+        let mut s = self.scene.lock().expect("scene lock");
+        s.light_bytes = lights.light_bytes();
+        s.cluster_bytes = lights.cluster_bytes();
+        s.light_index_bytes = lights.index_bytes();
+        s.cluster_params = [
+            lights.near,
+            lights.far,
+            lights.directional_count as f32,
+            lights.local_count() as f32,
+        ];
+        let forward = camera_forward.normalize_or(Vec3::NEG_Z);
+        s.camera_forward = [forward.x, forward.y, forward.z];
+    }
+
+    /// Névoa aplicada no forward pass (a do ambiente ou a da câmera do Game)
+    pub fn update_fog(&self, fog: &FogSettings) {
+        self.scene.lock().expect("scene lock").fog = fog_uniform(fog);
+    }
+
+    /// Objetos do lote com contorno (índices como no picking); `size` é a viewport
+    /// em pixels e `width` a largura da borda em pixels
+    pub fn update_selection(
+        &self,
+        selected: Vec<usize>,
+        size: [u32; 2],
+        color: egui::Color32,
+        width: f32,
+    ) {
+        let mut s = self.scene.lock().expect("scene lock");
+        s.selection = selected;
+        s.outline_size = [size[0].max(1), size[1].max(1)];
+        let mut uniforms = [0_u8; OUTLINE_UNIFORM_SIZE];
+        for (i, channel) in color.to_normalized_gamma_f32().into_iter().enumerate() {
+            push_f32(&mut uniforms, i * 4, channel);
+        }
+        push_f32(&mut uniforms, 16, width.max(0.0));
+        s.outline_uniforms = uniforms;
+    }
+
+    /// Emissores de partículas (nome, emissor, transform de mundo) para o próximo frame;
+    /// a emissão avança `dt` segundos e a simulação roda na GPU
+    pub fn update_particles(
+        &self,
+        emitters: &[(String, ParticleEmitter, Mat4)],
+        view: &ParticleView,
+        dt: f32,
+    ) {
+        let mut s = self.scene.lock().expect("scene lock");
+        s.spawners
+            .retain(|name, _| emitters.iter().any(|(n, _, _)| n == name));
+        let mut uploads = Vec::with_capacity(emitters.len());
+        for (name, emitter, transform) in emitters {
+            let step = s
+                .spawners
+                .entry(name.clone())
+                .or_default()
+                .advance(emitter, dt);
+            uploads.push(ParticleUpload {
+                name: name.clone(),
+                capacity: emitter.capacity(),
+                texture: emitter.texture.clone(),
+                additive: emitter.additive,
+                uniforms: particle_uniform_bytes(
+                    emitter,
+                    *transform,
+                    view,
+                    &step,
+                    emitter.texture.is_some(),
+                ),
+            });
+        }
+        s.particles = uploads;
+    }
+
+    /// Terrenos do próximo frame (desenhados com o `TERRAIN_SHADER`, fora do lote da cena)
+    pub fn update_terrains(&self, terrains: Vec<TerrainUpload>) {
+        self.scene.lock().expect("scene lock").terrains = terrains;
+    }
+
+    /// Superfícies de água do próximo frame (só no caminho offscreen)
+    pub fn update_water(&self, waters: Vec<WaterUpload>) {
+        self.scene.lock().expect("scene lock").waters = waters;
+    }
+
+    /// Sprites do próximo frame, em ordem de desenho (desenhados com o `SPRITE_SHADER`
+    /// depois do lote e dos terrenos)
+    pub fn update_sprites(&self, view_proj: Mat4, sprites: Vec<QuadUpload>) {
+        let mut s = self.scene.lock().expect("scene lock");
+        s.sprites = sprites;
+        s.sprite_view_proj = view_proj;
+    }
+
+    /// Textos 3D do próximo frame, do mais distante ao mais próximo (desenhados
+    /// com o `TEXT_SHADER` depois dos sprites)
+    pub fn update_texts(&self, view_proj: Mat4, texts: Vec<QuadUpload>) {
+        let mut s = self.scene.lock().expect("scene lock");
+        s.texts = texts;
+        s.text_view_proj = view_proj;
+    }
+
+    /// Texto da UI do próximo frame, já em clip space (desenhado por último,
+    /// depois do contorno, sem teste de profundidade)
+    pub fn update_ui_texts(&self, texts: Vec<QuadUpload>) {
+        self.scene.lock().expect("scene lock").ui_texts = texts;
+    }
+
+    /// Modo de shading da viewport (variantes de fragment shader do `LIT_SHADER`)
+    pub fn set_shading_mode(&self, mode: ShadingMode) {
+        self.scene.lock().expect("scene lock").shading = mode;
+    }
+
+    /// Pede o objeto sob `pixel` (pixels físicos relativos à viewport de tamanho `size`);
+    /// o ID buffer é renderizado no próximo frame e lido de volta alguns frames depois
+    pub fn request_pick(&self, pixel: [u32; 2], size: [u32; 2]) {
+        let mut s = self.scene.lock().expect("scene lock");
+        let size = [size[0].max(1), size[1].max(1)];
+        s.pick_request = Some(PickRequest {
+            pixel: [pixel[0].min(size[0] - 1), pixel[1].min(size[1] - 1)],
+            size,
+        });
+    }
+
+    /// Resultado do picking: Some(Some(i)) = i-ésimo objeto do lote, Some(None) = fundo
+    pub fn take_pick_result(&self) -> Option<Option<usize>> {
+        self.scene.lock().expect("scene lock").pick_result.take()
+    }
+
+    /// Há um pedido de picking ainda sem resposta (a viewport deve continuar repintando)
+    pub fn pick_pending(&self) -> bool {
+        let s = self.scene.lock().expect("scene lock");
+        s.pick_request.is_some() || s.pick_in_flight
+    }
+
+    /// Há texturas sendo lidas em segundo plano
+    pub fn textures_loading(&self) -> bool {
+        self.scene.lock().expect("scene lock").textures_loading
+    }
+
+    /// Limite de memória da GPU em bytes (0 = sem limite); acima dele as texturas
+    /// menos usadas recentemente são liberadas no próximo frame
+    pub fn set_memory_budget(&self, bytes: usize) {
+        self.scene
+            .lock()
+            .expect("scene lock")
+            .memory
+            .set_limit(bytes);
+    }
+
+    /// Memória da GPU por asset, para a janela de Memória
+    pub fn memory_usage(&self) -> MemoryBudget {
+        self.scene.lock().expect("scene lock").memory.clone()
+    }
+
+    /// Draws e triângulos enviados desde a última chamada (o frame anterior)
+    pub fn take_render_stats(&self) -> RenderStats {
+        self.counters.take()
+    }
+
     pub fn paint_callback(&self, rect: egui::Rect) -> egui::PaintCallback {
         egui_wgpu::Callback::new_paint_callback(
             rect,
             Draw3dCallback {
                 target_format: self.target_format,
+                view: self.view,
                 scene: self.scene.clone(),
+                sky: self.sky.clone(),
+                shaders: self.shaders.clone(),
+                counters: self.counters.clone(),
+            },
+        )
+    }
+
+    /// Esfera com `preview` em `rect`, iluminada pela luz padrão e pelo IBL do céu
+    pub fn material_preview_callback(
+        &self,
+        rect: egui::Rect,
+        preview: MaterialPreview,
+    ) -> egui::PaintCallback {
+        egui_wgpu::Callback::new_paint_callback(
+            rect,
+            MaterialPreviewCallback {
+                target_format: self.target_format,
+                view: self.view,
+                preview,
+                aspect: rect.width() / rect.height().max(1.0),
+                sky: self.sky.clone(),
+                shaders: self.shaders.clone(),
             },
         )
     }
 }
 
 impl Draw3dCallback {
-    fn create_resources(&self, device: &wgpu::Device, queue: &wgpu::Queue) -> GpuResources {
+    fn create_resources(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        shaders: &ShaderLibrary,
+    ) -> GpuResources {
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("viewport_gpu_shader"),
-            source: wgpu::ShaderSource::Wgsl(LIT_SHADER.into()),
+            source: wgpu::ShaderSource::Wgsl(shaders.source(ShaderFile::Lit).into()),
         });
 
-        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-            label: Some("viewport_gpu_bind_layout"),
-            entries: &[
-                wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Uniform,
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
-                    },
-                    count: None,
-                },
-                wgpu::BindGroupLayoutEntry {
-                    binding: 1,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
-                    count: None,
-                },
-                wgpu::BindGroupLayoutEntry {
-                    binding: 2,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Texture {
-                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
-                        view_dimension: wgpu::TextureViewDimension::D2,
-                        multisampled: false,
-                    },
-                    count: None,
-                },
-            ],
-        });
+        let bind_group_layout = create_lit_bind_group_layout(device, "viewport_gpu_bind_layout");
 
         // Sampler padrão
         let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
@@ -230,39 +1306,7 @@ impl Draw3dCallback {
         });
 
         // Textura branca 1x1 como fallback
-        let white_pixel_data = [255, 255, 255, 255];
-        let white_pixel_size = wgpu::Extent3d {
-            width: 1,
-            height: 1,
-            depth_or_array_layers: 1,
-        };
-        let white_pixel_texture = device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("viewport_gpu_white_pixel_texture"),
-            size: white_pixel_size,
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Rgba8UnormSrgb,
-            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
-            view_formats: &[],
-        });
-        queue.write_texture(
-            TexelCopyTextureInfo {
-                texture: &white_pixel_texture,
-                mip_level: 0,
-                origin: wgpu::Origin3d::ZERO,
-                aspect: wgpu::TextureAspect::All,
-            },
-            &white_pixel_data,
-            TexelCopyBufferLayout {
-                offset: 0,
-                bytes_per_row: Some(4),
-                rows_per_image: Some(1),
-            },
-            white_pixel_size,
-        );
-        let white_pixel_view =
-            white_pixel_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let (white_pixel_texture, white_pixel_view) = create_white_pixel_texture(device, queue);
 
         let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("viewport_gpu_ubo"),
@@ -270,460 +1314,5128 @@ impl Draw3dCallback {
             usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
         });
 
-        // Vertex layout: pos(vec3) + normal(vec3) + uv(vec2) = 32 bytes
-        let vertex_layout = wgpu::VertexBufferLayout {
-            array_stride: LIT_VERTEX_STRIDE as u64,
-            step_mode: wgpu::VertexStepMode::Vertex,
-            attributes: &[
-                wgpu::VertexAttribute {
-                    format: wgpu::VertexFormat::Float32x3,
-                    offset: 0,
-                    shader_location: 0, // pos
-                },
-                wgpu::VertexAttribute {
-                    format: wgpu::VertexFormat::Float32x3,
-                    offset: 12,
-                    shader_location: 1, // normal
-                },
-                wgpu::VertexAttribute {
-                    format: wgpu::VertexFormat::Float32x2,
-                    offset: 24,
-                    shader_location: 2, // uv
-                },
-            ],
-        };
+        let empty_lights = ClusteredLights::default();
+        let light_buffer =
+            create_storage_buffer(device, "viewport_gpu_lights", &empty_lights.light_bytes());
+        let cluster_buffer = create_storage_buffer(
+            device,
+            "viewport_gpu_clusters",
+            &empty_lights.cluster_bytes(),
+        );
+        let light_index_buffer = create_storage_buffer(
+            device,
+            "viewport_gpu_light_indices",
+            &empty_lights.index_bytes(),
+        );
 
-        let solid_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("viewport_gpu_solid_pipeline"),
-            layout: Some(
-                &device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-                    label: Some("viewport_gpu_pipeline_layout"),
-                    bind_group_layouts: &[&bind_group_layout],
-                    push_constant_ranges: &[],
-                }),
-            ),
-            vertex: wgpu::VertexState {
-                module: &shader,
-                entry_point: Some("vs_main"),
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-                buffers: &[vertex_layout],
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &shader,
-                entry_point: Some("fs_main"),
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: self.target_format,
-                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                cull_mode: Some(wgpu::Face::Back),
-                front_face: wgpu::FrontFace::Ccw,
-                ..Default::default()
-            },
-            depth_stencil: Some(wgpu::DepthStencilState {
-                format: wgpu::TextureFormat::Depth24Plus,
-                depth_write_enabled: true,
-                depth_compare: wgpu::CompareFunction::LessEqual,
-                stencil: wgpu::StencilState::default(),
-                bias: wgpu::DepthBiasState::default(),
-            }),
-            multisample: wgpu::MultisampleState::default(),
-            multiview: None,
-            cache: None,
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("viewport_gpu_pipeline_layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let solid_pipeline = create_solid_pipeline(
+            device,
+            &shader,
+            &pipeline_layout,
+            self.target_format,
+            1,
+            ShadingMode::Lit,
+        );
+
+        let (_ibl_fallback, ibl_fallback_view) =
+            create_sky_texture(device, queue, 1, 1, &[0_u8; 8]);
+        let env_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("viewport_gpu_env_sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::FilterMode::Linear,
+            address_mode_u: wgpu::AddressMode::Repeat,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            ..Default::default()
         });
 
         GpuResources {
+            shader_generation: shaders.generation(),
             solid_pipeline,
+            target_format: self.target_format,
+            shader,
+            pipeline_layout,
+            hdr_pipeline: None,
+            shading: ShadingMode::Lit,
             uniform_buffer,
             uniform_data: [0_u8; LIT_UNIFORM_SIZE],
             bind_group_layout,
             current_bind_group: None,
+            light_buffer,
+            cluster_buffer,
+            light_index_buffer,
             vertex_buffer: None,
             index_buffer: None,
             index_count: 0,
+            wire_index: None,
             uploaded_mesh_id: 0,
             pending_mesh_upload: None,
             staged_vertices: Vec::new(),
             staged_normals: Vec::new(),
             staged_uvs: Vec::new(),
             staged_triangles: Vec::new(),
+            pick_ranges: Vec::new(),
+            pick: None,
+            outline: None,
+            particles: None,
+            terrain: None,
+            sprites: None,
+            texts: None,
+            ui_texts: None,
             textures: std::collections::HashMap::new(),
             current_texture_path: None,
+            texture_loads: HashMap::new(),
+            mipmap: None,
             white_pixel_texture: (white_pixel_texture, white_pixel_view, sampler),
+            ibl_fallback_view,
+            env_sampler,
+            bound_ibl_id: None,
         }
     }
 }
 
-fn push_f32(buf: &mut [u8], offset: usize, value: f32) {
-    buf[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+/// Pipeline da malha da cena; `sample_count` > 1 para o alvo com MSAA. Wireframe
+/// desenha line list e Overdraw soma as camadas sem teste de profundidade
+fn create_solid_pipeline(
+    device: &wgpu::Device,
+    shader: &wgpu::ShaderModule,
+    layout: &wgpu::PipelineLayout,
+    format: wgpu::TextureFormat,
+    sample_count: u32,
+    shading: ShadingMode,
+) -> wgpu::RenderPipeline {
+    let overdraw = shading == ShadingMode::Overdraw;
+    let additive = wgpu::BlendState {
+        color: wgpu::BlendComponent {
+            src_factor: wgpu::BlendFactor::One,
+            dst_factor: wgpu::BlendFactor::One,
+            operation: wgpu::BlendOperation::Add,
+        },
+        alpha: wgpu::BlendComponent::OVER,
+    };
+    // Vertex layout: pos(vec3) + normal(vec3) + uv(vec2) = 32 bytes
+    let vertex_layout = wgpu::VertexBufferLayout {
+        array_stride: LIT_VERTEX_STRIDE as u64,
+        step_mode: wgpu::VertexStepMode::Vertex,
+        attributes: &[
+            wgpu::VertexAttribute {
+                format: wgpu::VertexFormat::Float32x3,
+                offset: 0,
+                shader_location: 0, // pos
+            },
+            wgpu::VertexAttribute {
+                format: wgpu::VertexFormat::Float32x3,
+                offset: 12,
+                shader_location: 1, // normal
+            },
+            wgpu::VertexAttribute {
+                format: wgpu::VertexFormat::Float32x2,
+                offset: 24,
+                shader_location: 2, // uv
+            },
+        ],
+    };
+
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("viewport_gpu_solid_pipeline"),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: Some("vs_main"),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            buffers: &[vertex_layout],
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: Some(shading.fragment_entry()),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(if overdraw {
+                    additive
+                } else {
+                    wgpu::BlendState::ALPHA_BLENDING
+                }),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: if shading == ShadingMode::Wireframe {
+                wgpu::PrimitiveTopology::LineList
+            } else {
+                wgpu::PrimitiveTopology::TriangleList
+            },
+            cull_mode: matches!(
+                shading,
+                ShadingMode::Lit | ShadingMode::Unlit | ShadingMode::Normals
+            )
+            .then_some(wgpu::Face::Back),
+            front_face: wgpu::FrontFace::Ccw,
+            ..Default::default()
+        },
+        depth_stencil: Some(wgpu::DepthStencilState {
+            format: wgpu::TextureFormat::Depth24Plus,
+            depth_write_enabled: !overdraw,
+            depth_compare: if overdraw {
+                wgpu::CompareFunction::Always
+            } else {
+                wgpu::CompareFunction::LessEqual
+            },
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        }),
+        multisample: wgpu::MultisampleState {
+            count: sample_count,
+            ..Default::default()
+        },
+        multiview: None,
+        cache: None,
+    })
 }
 
-fn upload_pending_mesh_chunk(
-    queue: &wgpu::Queue,
-    pending: &mut PendingMeshUpload,
-    vertices: &[[f32; 3]],
-    normals: &[[f32; 3]],
-    uvs: &[[f32; 2]],
-    triangles: &[[u32; 3]],
-    budget_left: &mut usize,
-) {
-    if *budget_left == 0 {
-        return;
-    }
+/// Layout do shader lit: uniforms, textura albedo, luzes (forward+) e IBL
+fn create_lit_bind_group_layout(device: &wgpu::Device, label: &str) -> wgpu::BindGroupLayout {
+    device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some(label),
+        entries: &[
+            wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 1,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 2,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            },
+            storage_layout_entry(3),
+            storage_layout_entry(4),
+            storage_layout_entry(5),
+            wgpu::BindGroupLayoutEntry {
+                binding: 6,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 7,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 8,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                count: None,
+            },
+        ],
+    })
+}
 
-    let vertex_available = vertices.len().min(normals.len()).min(uvs.len());
-    if pending.vertex_cursor < pending.vertex_len && pending.vertex_cursor < vertex_available {
-        let stride = LIT_VERTEX_STRIDE; // 32 bytes: pos(12) + normal(12) + uv(8)
-        let max_count = (*budget_left / stride).max(1);
-        let end = (pending.vertex_cursor + max_count)
-            .min(pending.vertex_len)
-            .min(vertex_available);
-        let chunk_len = end - pending.vertex_cursor;
-        let mut bytes = Vec::with_capacity(chunk_len * stride);
-        for i in 0..chunk_len {
-            let vi = pending.vertex_cursor + i;
-            let v = &vertices[vi];
-            let n = &normals[vi];
-            let uv = &uvs[vi];
-            // pos
-            bytes.extend_from_slice(&v[0].to_le_bytes());
-            bytes.extend_from_slice(&v[1].to_le_bytes());
-            bytes.extend_from_slice(&v[2].to_le_bytes());
-            // normal
-            bytes.extend_from_slice(&n[0].to_le_bytes());
-            bytes.extend_from_slice(&n[1].to_le_bytes());
-            bytes.extend_from_slice(&n[2].to_le_bytes());
-            // uv
-            bytes.extend_from_slice(&uv[0].to_le_bytes());
-            bytes.extend_from_slice(&uv[1].to_le_bytes());
-        }
-        let offset = (pending.vertex_cursor * stride) as u64;
-        queue.write_buffer(&pending.vertex_buffer, offset, &bytes);
-        pending.vertex_cursor = end;
-        *budget_left = budget_left.saturating_sub(bytes.len());
-        if *budget_left == 0 {
-            return;
-        }
-    }
+fn create_white_pixel_texture(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+) -> (wgpu::Texture, wgpu::TextureView) {
+    let white_pixel_data = [255, 255, 255, 255];
+    let white_pixel_size = wgpu::Extent3d {
+        width: 1,
+        height: 1,
+        depth_or_array_layers: 1,
+    };
+    let white_pixel_texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("viewport_gpu_white_pixel_texture"),
+        size: white_pixel_size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8UnormSrgb,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    });
+    queue.write_texture(
+        TexelCopyTextureInfo {
+            texture: &white_pixel_texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        &white_pixel_data,
+        TexelCopyBufferLayout {
+            offset: 0,
+            bytes_per_row: Some(4),
+            rows_per_image: Some(1),
+        },
+        white_pixel_size,
+    );
+    let white_pixel_view = white_pixel_texture.create_view(&wgpu::TextureViewDescriptor::default());
+    (white_pixel_texture, white_pixel_view)
+}
 
-    if pending.tri_cursor < pending.tri_len && pending.tri_cursor < triangles.len() {
-        let stride = std::mem::size_of::<[u32; 3]>();
-        let max_count = (*budget_left / stride).max(1);
-        let end = (pending.tri_cursor + max_count)
-            .min(pending.tri_len)
-            .min(triangles.len());
-        let chunk_len = end - pending.tri_cursor;
-        let mut bytes = Vec::with_capacity(chunk_len * stride);
-        for tri in &triangles[pending.tri_cursor..end] {
-            bytes.extend_from_slice(&tri[0].to_le_bytes());
-            bytes.extend_from_slice(&tri[1].to_le_bytes());
-            bytes.extend_from_slice(&tri[2].to_le_bytes());
-        }
-        let offset = (pending.tri_cursor * stride) as u64;
-        queue.write_buffer(&pending.index_buffer, offset, &bytes);
-        pending.tri_cursor = end;
-        *budget_left = budget_left.saturating_sub(bytes.len());
+fn storage_layout_entry(binding: u32) -> wgpu::BindGroupLayoutEntry {
+    wgpu::BindGroupLayoutEntry {
+        binding,
+        visibility: wgpu::ShaderStages::FRAGMENT,
+        ty: wgpu::BindingType::Buffer {
+            ty: wgpu::BufferBindingType::Storage { read_only: true },
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+        count: None,
     }
 }
 
-impl egui_wgpu::CallbackTrait for Draw3dCallback {
-    fn prepare(
-        &self,
-        device: &wgpu::Device,
-        queue: &wgpu::Queue,
-        _screen_descriptor: &egui_wgpu::ScreenDescriptor,
-        _egui_encoder: &mut wgpu::CommandEncoder,
-        callback_resources: &mut egui_wgpu::CallbackResources,
-    ) -> Vec<wgpu::CommandBuffer> {
-        use std::path::PathBuf;
+fn create_storage_buffer(device: &wgpu::Device, label: &str, contents: &[u8]) -> wgpu::Buffer {
+    device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some(label),
+        contents,
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+    })
+}
 
-        let resources = callback_resources
-            .entry::<GpuResources>()
-            .or_insert_with(|| self.create_resources(device, queue));
+/// Escreve no storage buffer, recriando-o quando o conteúdo não cabe.
+/// Retorna true se o buffer foi recriado (o bind group precisa ser refeito).
+fn write_storage_buffer(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    buffer: &mut wgpu::Buffer,
+    label: &str,
+    contents: &[u8],
+) -> bool {
+    if contents.is_empty() {
+        return false;
+    }
+    if (contents.len() as u64) <= buffer.size() {
+        queue.write_buffer(buffer, 0, contents);
+        return false;
+    }
+    let size = (contents.len() as u64).next_power_of_two();
+    *buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some(label),
+        size,
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    queue.write_buffer(buffer, 0, contents);
+    true
+}
 
-        let scene = self.scene.lock().expect("scene lock");
-        let current_mesh_texture_path = scene.texture_path.clone().map(|p| normalize_path(&p));
+fn push_f32(buf: &mut [u8], offset: usize, value: f32) {
+    buf[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+}
 
-        if scene.mesh_id == 0 || scene.vertices.is_empty() || scene.triangles.is_empty() {
-            if resources.current_texture_path.as_ref() != current_mesh_texture_path.as_ref() {
-                resources.current_texture_path = current_mesh_texture_path;
-                resources.current_bind_group = None;
+/// Avança o picking: termina a leitura pendente ou renderiza o ID buffer de um
+/// novo pedido (um objeto por draw, com o ID em `first_instance`)
+fn pick_step(
+    device: &wgpu::Device,
+    resources: &mut GpuResources,
+    scene: &mut SceneState,
+    shaders: &ShaderLibrary,
+) -> Option<wgpu::CommandBuffer> {
+    engine_core::profile_function!(engine_core::ProfileCategory::Render);
+    if let Some(pick) = &mut resources.pick {
+        // O buffer só pode ser mapeado depois que a cópia foi submetida
+        match std::mem::replace(&mut pick.state, PickReadback::Idle) {
+            PickReadback::Idle => {}
+            PickReadback::Copied => {
+                let status = Arc::new(Mutex::new(None));
+                let callback_status = status.clone();
+                pick.readback
+                    .slice(..)
+                    .map_async(wgpu::MapMode::Read, move |result| {
+                        *callback_status.lock().expect("pick lock") = Some(result.is_ok());
+                    });
+                pick.state = PickReadback::Mapping(status);
+            }
+            PickReadback::Mapping(status) => {
+                let _ = device.poll(wgpu::PollType::Poll);
+                let mapped = *status.lock().expect("pick lock");
+                match mapped {
+                    Some(ok) => {
+                        let id = ok.then(|| {
+                            let data = pick.readback.slice(..).get_mapped_range();
+                            u32::from_le_bytes([data[0], data[1], data[2], data[3]])
+                        });
+                        if ok {
+                            pick.readback.unmap();
+                        }
+                        // ID 0 é o fundo
+                        scene.pick_result = Some(id.filter(|&id| id > 0).map(|id| id as usize - 1));
+                        scene.pick_in_flight = false;
+                    }
+                    None => pick.state = PickReadback::Mapping(status),
+                }
             }
-            return Vec::new();
         }
+    }
 
-        // Upload de mesh quando o ID muda
-        if resources.uploaded_mesh_id != scene.mesh_id {
-            let should_rebuild = resources
-                .pending_mesh_upload
-                .as_ref()
-                .map_or(true, |p| p.mesh_id != scene.mesh_id);
-            if should_rebuild {
-                let vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-                    label: Some("viewport_gpu_vb"),
-                    size: (scene.vertices.len() * LIT_VERTEX_STRIDE) as u64,
-                    usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-                    mapped_at_creation: false,
-                });
-                let index_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-                    label: Some("viewport_gpu_ib"),
-                    size: (scene.triangles.len() * std::mem::size_of::<[u32; 3]>()) as u64,
-                    usage: wgpu::BufferUsages::INDEX | wgpu::BufferUsages::COPY_DST,
-                    mapped_at_creation: false,
-                });
+    // Um pedido por vez; espera a malha atual terminar de subir
+    let busy = resources
+        .pick
+        .as_ref()
+        .is_some_and(|pick| !matches!(pick.state, PickReadback::Idle));
+    if busy || scene.pick_request.is_none() || resources.pending_mesh_upload.is_some() {
+        return None;
+    }
+    let request = scene.pick_request.take()?;
+    let (Some(vb), Some(ib), Some(bind_group)) = (
+        &resources.vertex_buffer,
+        &resources.index_buffer,
+        &resources.current_bind_group,
+    ) else {
+        scene.pick_result = Some(None);
+        return None;
+    };
 
-                resources.staged_vertices.clear();
-                resources.staged_vertices.extend_from_slice(&scene.vertices);
-                resources.staged_normals.clear();
-                resources.staged_normals.extend_from_slice(&scene.normals);
-                resources.staged_uvs.clear();
-                resources.staged_uvs.extend_from_slice(&scene.uvs);
-                resources.staged_triangles.clear();
-                resources
-                    .staged_triangles
-                    .extend_from_slice(&scene.triangles);
+    let pick = resources.pick.get_or_insert_with(|| {
+        create_pick_resources(
+            device,
+            &resources.pipeline_layout,
+            shaders.source(ShaderFile::Pick),
+        )
+    });
+    if pick
+        .targets
+        .as_ref()
+        .is_none_or(|targets| targets.0 != request.size)
+    {
+        let target = |label: &str, format: wgpu::TextureFormat| {
+            device.create_texture(&wgpu::TextureDescriptor {
+                label: Some(label),
+                size: wgpu::Extent3d {
+                    width: request.size[0],
+                    height: request.size[1],
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+                view_formats: &[],
+            })
+        };
+        let ids = target("viewport_pick_ids", wgpu::TextureFormat::R32Uint);
+        let ids_view = ids.create_view(&wgpu::TextureViewDescriptor::default());
+        let depth = target("viewport_pick_depth", wgpu::TextureFormat::Depth24Plus)
+            .create_view(&wgpu::TextureViewDescriptor::default());
+        pick.targets = Some((request.size, ids, ids_view, depth));
+    }
+    let Some((_, ids, ids_view, depth_view)) = &pick.targets else {
+        return None;
+    };
 
-                let vertex_len = resources
-                    .staged_vertices
-                    .len()
-                    .min(resources.staged_normals.len())
-                    .min(resources.staged_uvs.len());
-                resources.pending_mesh_upload = Some(PendingMeshUpload {
-                    mesh_id: scene.mesh_id,
-                    vertex_len,
-                    tri_len: resources.staged_triangles.len(),
-                    vertex_cursor: 0,
-                    tri_cursor: 0,
-                    vertex_buffer,
-                    index_buffer,
-                    index_count: (scene.triangles.len() * 3) as u32,
-                });
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("viewport_pick_encoder"),
+    });
+    {
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("viewport_pick_pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: ids_view,
+                depth_slice: None,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: depth_view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(1.0),
+                    store: wgpu::StoreOp::Discard,
+                }),
+                stencil_ops: None,
+            }),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_pipeline(&pick.pipeline);
+        pass.set_bind_group(0, bind_group, &[]);
+        pass.set_vertex_buffer(0, vb.slice(..));
+        pass.set_index_buffer(ib.slice(..), wgpu::IndexFormat::Uint32);
+        for (i, &[first, count]) in resources.pick_ranges.iter().enumerate() {
+            if count > 0 {
+                let id = i as u32 + 1;
+                pass.draw_indexed(first..first + count, 0, id..id + 1);
             }
         }
+    }
+    encoder.copy_texture_to_buffer(
+        TexelCopyTextureInfo {
+            texture: ids,
+            mip_level: 0,
+            origin: wgpu::Origin3d {
+                x: request.pixel[0],
+                y: request.pixel[1],
+                z: 0,
+            },
+            aspect: wgpu::TextureAspect::All,
+        },
+        wgpu::TexelCopyBufferInfo {
+            buffer: &pick.readback,
+            layout: TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT),
+                rows_per_image: Some(1),
+            },
+        },
+        wgpu::Extent3d {
+            width: 1,
+            height: 1,
+            depth_or_array_layers: 1,
+        },
+    );
+    pick.state = PickReadback::Copied;
+    scene.pick_in_flight = true;
+    Some(encoder.finish())
+}
 
-        // Carrega textura se necessário
-        let mut has_texture = 0.0_f32;
-
-        if let Some(texture_path_str) = &current_mesh_texture_path {
-            if resources.current_texture_path.as_ref() != Some(texture_path_str) {
-                resources.current_texture_path = Some(texture_path_str.clone());
-                resources.current_bind_group = None;
+/// Sincroniza os buffers dos emissores e simula as partículas (compute) antes do draw
+fn particle_step(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    resources: &mut GpuResources,
+    scene: &SceneState,
+    shaders: &ShaderLibrary,
+) -> Option<wgpu::CommandBuffer> {
+    engine_core::profile_function!(engine_core::ProfileCategory::Render);
+    if scene.particles.is_empty() {
+        if let Some(particles) = &mut resources.particles {
+            particles.emitters.clear();
+        }
+        return None;
+    }
+    let white_view = &resources.white_pixel_texture.1;
+    let particles = resources.particles.get_or_insert_with(|| {
+        create_particle_resources(
+            device,
+            resources.target_format,
+            shaders.source(ShaderFile::Particle),
+        )
+    });
+    particles
+        .emitters
+        .retain(|name, _| scene.particles.iter().any(|upload| &upload.name == name));
+    for upload in &scene.particles {
+        let stale = particles
+            .emitters
+            .get(&upload.name)
+            .is_none_or(|b| b.capacity != upload.capacity || b.texture != upload.texture);
+        if stale {
+            let buffers = create_particle_buffers(device, queue, particles, upload, white_view);
+            particles.emitters.insert(upload.name.clone(), buffers);
+        }
+        if let Some(buffers) = particles.emitters.get_mut(&upload.name) {
+            buffers.additive = upload.additive;
+            queue.write_buffer(&buffers.uniform_buffer, 0, &upload.uniforms);
+        }
+    }
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("viewport_particle_encoder"),
+    });
+    {
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("viewport_particle_sim_pass"),
+            timestamp_writes: None,
+        });
+        pass.set_pipeline(&particles.sim_pipeline);
+        for buffers in particles.emitters.values() {
+            pass.set_bind_group(0, &buffers.sim_bind_group, &[]);
+            pass.dispatch_workgroups(buffers.capacity.div_ceil(PARTICLE_WORKGROUP_SIZE), 1, 1);
+        }
+    }
+    Some(encoder.finish())
+}
+
+fn create_particle_resources(
+    device: &wgpu::Device,
+    target_format: wgpu::TextureFormat,
+    source: &str,
+) -> ParticleResources {
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("viewport_particle_shader"),
+        source: wgpu::ShaderSource::Wgsl(source.into()),
+    });
+    let uniform_entry = |visibility| wgpu::BindGroupLayoutEntry {
+        binding: 0,
+        visibility,
+        ty: wgpu::BindingType::Buffer {
+            ty: wgpu::BufferBindingType::Uniform,
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+        count: None,
+    };
+    let sim_bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("viewport_particle_sim_layout"),
+        entries: &[
+            uniform_entry(wgpu::ShaderStages::COMPUTE),
+            wgpu::BindGroupLayoutEntry {
+                binding: 1,
+                visibility: wgpu::ShaderStages::COMPUTE,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Storage { read_only: false },
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            },
+        ],
+    });
+    let render_bind_group_layout =
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("viewport_particle_render_layout"),
+            entries: &[
+                uniform_entry(wgpu::ShaderStages::VERTEX_FRAGMENT),
+                wgpu::BindGroupLayoutEntry {
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ..storage_layout_entry(2)
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 4,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+    let sim_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("viewport_particle_sim_pipeline_layout"),
+        bind_group_layouts: &[&sim_bind_group_layout],
+        push_constant_ranges: &[],
+    });
+    let sim_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+        label: Some("viewport_particle_sim_pipeline"),
+        layout: Some(&sim_layout),
+        module: &shader,
+        entry_point: Some("cs_simulate"),
+        compilation_options: wgpu::PipelineCompilationOptions::default(),
+        cache: None,
+    });
+    let render_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("viewport_particle_render_pipeline_layout"),
+        bind_group_layouts: &[&render_bind_group_layout],
+        push_constant_ranges: &[],
+    });
+    let direct = [false, true].map(|additive| {
+        create_particle_pipeline(
+            device,
+            &shader,
+            &render_pipeline_layout,
+            target_format,
+            1,
+            additive,
+        )
+    });
+    let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+        label: Some("viewport_particle_sampler"),
+        mag_filter: wgpu::FilterMode::Linear,
+        min_filter: wgpu::FilterMode::Linear,
+        ..Default::default()
+    });
+
+    ParticleResources {
+        shader,
+        sim_pipeline,
+        sim_bind_group_layout,
+        render_bind_group_layout,
+        render_pipeline_layout,
+        direct,
+        hdr: None,
+        sampler,
+        emitters: HashMap::new(),
+    }
+}
+
+/// Billboards das partículas: testam a profundidade da cena sem escrever nela
+fn create_particle_pipeline(
+    device: &wgpu::Device,
+    shader: &wgpu::ShaderModule,
+    layout: &wgpu::PipelineLayout,
+    format: wgpu::TextureFormat,
+    sample_count: u32,
+    additive: bool,
+) -> wgpu::RenderPipeline {
+    let blend = if additive {
+        wgpu::BlendState {
+            color: wgpu::BlendComponent {
+                src_factor: wgpu::BlendFactor::SrcAlpha,
+                dst_factor: wgpu::BlendFactor::One,
+                operation: wgpu::BlendOperation::Add,
+            },
+            alpha: wgpu::BlendComponent::OVER,
+        }
+    } else {
+        wgpu::BlendState::ALPHA_BLENDING
+    };
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("viewport_particle_pipeline"),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: Some("vs_particle"),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            buffers: &[],
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: Some("fs_particle"),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(blend),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            cull_mode: None,
+            ..Default::default()
+        },
+        depth_stencil: Some(wgpu::DepthStencilState {
+            format: wgpu::TextureFormat::Depth24Plus,
+            depth_write_enabled: false,
+            depth_compare: wgpu::CompareFunction::LessEqual,
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        }),
+        multisample: wgpu::MultisampleState {
+            count: sample_count,
+            ..Default::default()
+        },
+        multiview: None,
+        cache: None,
+    })
+}
+
+fn create_particle_buffers(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    particles: &ParticleResources,
+    upload: &ParticleUpload,
+    white_view: &wgpu::TextureView,
+) -> ParticleBuffers {
+    let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("viewport_particle_ubo"),
+        contents: &upload.uniforms,
+        usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+    });
+    // Zerado = todas as partículas mortas (vida 0)
+    let particle_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("viewport_particle_buffer"),
+        size: (upload.capacity as usize * PARTICLE_STRIDE) as u64,
+        usage: wgpu::BufferUsages::STORAGE,
+        mapped_at_creation: false,
+    });
+    let sheet = upload.texture.as_deref().and_then(|path| {
+        load_image_texture(device, queue, path, wgpu::TextureFormat::Rgba8UnormSrgb)
+    });
+    let sheet_view = sheet.as_ref().map_or(white_view, |(_, view)| view);
+
+    let sim_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("viewport_particle_sim_bind_group"),
+        layout: &particles.sim_bind_group_layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: particle_buffer.as_entire_binding(),
+            },
+        ],
+    });
+    let render_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("viewport_particle_render_bind_group"),
+        layout: &particles.render_bind_group_layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: particle_buffer.as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 3,
+                resource: wgpu::BindingResource::TextureView(sheet_view),
+            },
+            wgpu::BindGroupEntry {
+                binding: 4,
+                resource: wgpu::BindingResource::Sampler(&particles.sampler),
+            },
+        ],
+    });
+
+    ParticleBuffers {
+        capacity: upload.capacity,
+        texture: upload.texture.clone(),
+        additive: upload.additive,
+        uniform_buffer,
+        _particle_buffer: particle_buffer,
+        _sheet: sheet,
+        sim_bind_group,
+        render_bind_group,
+    }
+}
+
+/// Textura de um arquivo de imagem (sprite sheet, camada de terreno, normal
+/// map); None se o arquivo não abrir
+fn load_image_texture(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    path: &str,
+    format: wgpu::TextureFormat,
+) -> Option<(wgpu::Texture, wgpu::TextureView)> {
+    let rgba = open_image(Path::new(&normalize_path(path)))
+        .ok()?
+        .to_rgba8();
+    let (width, height) = rgba.dimensions();
+    Some(create_rgba_texture(
+        device,
+        queue,
+        [width, height],
+        &rgba,
+        format,
+    ))
+}
+
+/// Textura RGBA8 com os pixels já decodificados
+fn create_rgba_texture(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    [width, height]: [u32; 2],
+    rgba: &[u8],
+    format: wgpu::TextureFormat,
+) -> (wgpu::Texture, wgpu::TextureView) {
+    let size = wgpu::Extent3d {
+        width,
+        height,
+        depth_or_array_layers: 1,
+    };
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("viewport_image_texture"),
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    });
+    queue.write_texture(
+        TexelCopyTextureInfo {
+            texture: &texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        rgba,
+        TexelCopyBufferLayout {
+            offset: 0,
+            bytes_per_row: Some(width * 4),
+            rows_per_image: Some(height),
+        },
+        size,
+    );
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    (texture, view)
+}
+
+/// Sincroniza malha, splat map, camadas e uniforms de cada terreno antes do draw
+fn terrain_step(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    resources: &mut GpuResources,
+    scene: &SceneState,
+    shaders: &ShaderLibrary,
+) {
+    engine_core::profile_function!(engine_core::ProfileCategory::Render);
+    if scene.terrains.is_empty() {
+        if let Some(terrain) = &mut resources.terrain {
+            terrain.terrains.clear();
+        }
+        return;
+    }
+    let white_view = &resources.white_pixel_texture.1;
+    let terrain = resources.terrain.get_or_insert_with(|| {
+        create_terrain_resources(
+            device,
+            resources.target_format,
+            shaders.source(ShaderFile::Terrain),
+        )
+    });
+    terrain
+        .terrains
+        .retain(|name, _| scene.terrains.iter().any(|upload| &upload.name == name));
+    for upload in &scene.terrains {
+        let stale = terrain.terrains.get(&upload.name).is_none_or(|b| {
+            b.splat_size != upload.splat_size || b.layer_textures != upload.layer_textures
+        });
+        if stale {
+            let buffers = create_terrain_buffers(device, queue, terrain, upload, white_view);
+            terrain.terrains.insert(upload.name.clone(), buffers);
+        }
+        let Some(buffers) = terrain.terrains.get_mut(&upload.name) else {
+            continue;
+        };
+        if buffers.mesh_key != upload.mesh_key {
+            let (vertex_buffer, index_buffer) = create_terrain_mesh_buffers(device, upload);
+            buffers.vertex_buffer = vertex_buffer;
+            buffers.index_buffer = index_buffer;
+            buffers.index_count = (upload.indices.len() / 4) as u32;
+            buffers.mesh_key = upload.mesh_key;
+        }
+        if buffers.splat_key != upload.splat_key {
+            write_splat_texture(queue, &buffers.splat, upload);
+            buffers.splat_key = upload.splat_key;
+        }
+        queue.write_buffer(&buffers.uniform_buffer, 0, &upload.uniforms);
+    }
+}
+
+/// Bytes de uma textura na GPU, com todos os mips e camadas
+fn gpu_texture_bytes(texture: &wgpu::Texture) -> usize {
+    let size = texture.size();
+    let texel = texture.format().block_copy_size(None).unwrap_or(4);
+    texture_gpu_bytes(size.width, size.height, texel, texture.mip_level_count())
+        * size.depth_or_array_layers as usize
+}
+
+/// Atualiza a malha do lote e os terrenos no orçamento (residentes enquanto
+/// estão na cena) e libera as texturas menos usadas quando o total passa do limite;
+/// uma textura liberada é lida de novo quando o lote voltar a usá-la
+fn memory_step(resources: &mut GpuResources, memory: &mut MemoryBudget) {
+    engine_core::profile_function!(engine_core::ProfileCategory::Render);
+    let mesh_bytes = [&resources.vertex_buffer, &resources.index_buffer]
+        .into_iter()
+        .flatten()
+        .chain(resources.wire_index.as_ref().map(|(_, buffer, _)| buffer))
+        .map(|buffer| buffer.size() as usize)
+        .sum::<usize>();
+    if mesh_bytes > 0 {
+        memory.track(SCENE_MESH_MEMORY_KEY, "Mesh", mesh_bytes, false);
+    } else {
+        memory.forget(SCENE_MESH_MEMORY_KEY);
+    }
+
+    // Recursos recriados (shader recarregado) ou terrenos removidos saem da conta
+    let terrains = resources.terrain.as_ref().map(|terrain| &terrain.terrains);
+    for entry in memory.entries() {
+        let present = match entry.category {
+            "Texture" => resources.textures.contains_key(&entry.name),
+            "Terrain" => entry
+                .name
+                .strip_prefix(TERRAIN_MEMORY_PREFIX)
+                .is_some_and(|name| terrains.is_some_and(|t| t.contains_key(name))),
+            _ => true,
+        };
+        if !present {
+            memory.forget(&entry.name);
+        }
+    }
+    for (name, buffers) in terrains.into_iter().flatten() {
+        let bytes = buffers.vertex_buffer.size() as usize
+            + buffers.index_buffer.size() as usize
+            + gpu_texture_bytes(&buffers.splat)
+            + buffers
+                .layers
+                .iter()
+                .map(|(texture, _)| gpu_texture_bytes(texture))
+                .sum::<usize>();
+        let key = format!("{TERRAIN_MEMORY_PREFIX}{name}");
+        memory.track(&key, "Terrain", bytes, false);
+    }
+
+    for key in memory.evict() {
+        resources.textures.remove(&key);
+    }
+}
+
+fn create_terrain_resources(
+    device: &wgpu::Device,
+    target_format: wgpu::TextureFormat,
+    source: &str,
+) -> TerrainResources {
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("viewport_terrain_shader"),
+        source: wgpu::ShaderSource::Wgsl(source.into()),
+    });
+    let texture_entry = |binding| wgpu::BindGroupLayoutEntry {
+        binding,
+        visibility: wgpu::ShaderStages::FRAGMENT,
+        ty: wgpu::BindingType::Texture {
+            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+            view_dimension: wgpu::TextureViewDimension::D2,
+            multisampled: false,
+        },
+        count: None,
+    };
+    let sampler_entry = |binding| wgpu::BindGroupLayoutEntry {
+        binding,
+        visibility: wgpu::ShaderStages::FRAGMENT,
+        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+        count: None,
+    };
+    let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("viewport_terrain_layout"),
+        entries: &[
+            wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            },
+            sampler_entry(1),
+            sampler_entry(2),
+            texture_entry(3),
+            texture_entry(4),
+            texture_entry(5),
+            texture_entry(6),
+            texture_entry(7),
+        ],
+    });
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("viewport_terrain_pipeline_layout"),
+        bind_group_layouts: &[&bind_group_layout],
+        push_constant_ranges: &[],
+    });
+    let direct = create_terrain_pipeline(device, &shader, &pipeline_layout, target_format, 1);
+    let layer_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+        label: Some("viewport_terrain_layer_sampler"),
+        mag_filter: wgpu::FilterMode::Linear,
+        min_filter: wgpu::FilterMode::Linear,
+        address_mode_u: wgpu::AddressMode::Repeat,
+        address_mode_v: wgpu::AddressMode::Repeat,
+        ..Default::default()
+    });
+    let splat_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+        label: Some("viewport_terrain_splat_sampler"),
+        mag_filter: wgpu::FilterMode::Linear,
+        min_filter: wgpu::FilterMode::Linear,
+        ..Default::default()
+    });
+
+    TerrainResources {
+        shader,
+        bind_group_layout,
+        pipeline_layout,
+        direct,
+        hdr: None,
+        layer_sampler,
+        splat_sampler,
+        terrains: HashMap::new(),
+    }
+}
+
+/// Terreno opaco, mesmo vertex layout do `LIT_SHADER`
+fn create_terrain_pipeline(
+    device: &wgpu::Device,
+    shader: &wgpu::ShaderModule,
+    layout: &wgpu::PipelineLayout,
+    format: wgpu::TextureFormat,
+    sample_count: u32,
+) -> wgpu::RenderPipeline {
+    let vertex_layout = wgpu::VertexBufferLayout {
+        array_stride: LIT_VERTEX_STRIDE as u64,
+        step_mode: wgpu::VertexStepMode::Vertex,
+        attributes: &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3, 2 => Float32x2],
+    };
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("viewport_terrain_pipeline"),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: Some("vs_terrain"),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            buffers: &[vertex_layout],
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: Some("fs_terrain"),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: None,
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            cull_mode: Some(wgpu::Face::Back),
+            front_face: wgpu::FrontFace::Ccw,
+            ..Default::default()
+        },
+        depth_stencil: Some(wgpu::DepthStencilState {
+            format: wgpu::TextureFormat::Depth24Plus,
+            depth_write_enabled: true,
+            depth_compare: wgpu::CompareFunction::LessEqual,
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        }),
+        multisample: wgpu::MultisampleState {
+            count: sample_count,
+            ..Default::default()
+        },
+        multiview: None,
+        cache: None,
+    })
+}
+
+fn create_terrain_mesh_buffers(
+    device: &wgpu::Device,
+    upload: &TerrainUpload,
+) -> (wgpu::Buffer, wgpu::Buffer) {
+    let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("viewport_terrain_vb"),
+        contents: &upload.vertices,
+        usage: wgpu::BufferUsages::VERTEX,
+    });
+    let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("viewport_terrain_ib"),
+        contents: &upload.indices,
+        usage: wgpu::BufferUsages::INDEX,
+    });
+    (vertex_buffer, index_buffer)
+}
+
+fn write_splat_texture(queue: &wgpu::Queue, texture: &wgpu::Texture, upload: &TerrainUpload) {
+    let size = upload.splat_size;
+    queue.write_texture(
+        TexelCopyTextureInfo {
+            texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        &upload.splat,
+        TexelCopyBufferLayout {
+            offset: 0,
+            bytes_per_row: Some(size * 4),
+            rows_per_image: Some(size),
+        },
+        wgpu::Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: 1,
+        },
+    );
+}
+
+fn create_terrain_buffers(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    terrain: &TerrainResources,
+    upload: &TerrainUpload,
+    white_view: &wgpu::TextureView,
+) -> TerrainBuffers {
+    let (vertex_buffer, index_buffer) = create_terrain_mesh_buffers(device, upload);
+    let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("viewport_terrain_ubo"),
+        contents: &upload.uniforms,
+        usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+    });
+    // Pesos, não cor: sem sRGB
+    let splat = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("viewport_terrain_splat"),
+        size: wgpu::Extent3d {
+            width: upload.splat_size,
+            height: upload.splat_size,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    });
+    write_splat_texture(queue, &splat, upload);
+    let splat_view = splat.create_view(&wgpu::TextureViewDescriptor::default());
+
+    let layers: Vec<Option<(wgpu::Texture, wgpu::TextureView)>> = upload
+        .layer_textures
+        .iter()
+        .map(|path| {
+            path.as_deref().and_then(|path| {
+                load_image_texture(device, queue, path, wgpu::TextureFormat::Rgba8UnormSrgb)
+            })
+        })
+        .collect();
+    let layer_views: Vec<&wgpu::TextureView> = layers
+        .iter()
+        .map(|layer| layer.as_ref().map_or(white_view, |(_, view)| view))
+        .collect();
+    let mut entries = vec![
+        wgpu::BindGroupEntry {
+            binding: 0,
+            resource: uniform_buffer.as_entire_binding(),
+        },
+        wgpu::BindGroupEntry {
+            binding: 1,
+            resource: wgpu::BindingResource::Sampler(&terrain.layer_sampler),
+        },
+        wgpu::BindGroupEntry {
+            binding: 2,
+            resource: wgpu::BindingResource::Sampler(&terrain.splat_sampler),
+        },
+        wgpu::BindGroupEntry {
+            binding: 3,
+            resource: wgpu::BindingResource::TextureView(&splat_view),
+        },
+    ];
+    for (i, view) in layer_views.iter().enumerate() {
+        entries.push(wgpu::BindGroupEntry {
+            binding: 4 + i as u32,
+            resource: wgpu::BindingResource::TextureView(view),
+        });
+    }
+    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("viewport_terrain_bind_group"),
+        layout: &terrain.bind_group_layout,
+        entries: &entries,
+    });
+
+    TerrainBuffers {
+        mesh_key: upload.mesh_key,
+        vertex_buffer,
+        index_buffer,
+        index_count: (upload.indices.len() / 4) as u32,
+        splat_key: upload.splat_key,
+        splat_size: upload.splat_size,
+        splat,
+        layer_textures: upload.layer_textures.clone(),
+        layers: layers.into_iter().flatten().collect(),
+        uniform_buffer,
+        bind_group,
+    }
+}
+
+impl TerrainResources {
+    fn ensure_hdr_pipeline(&mut self, device: &wgpu::Device, sample_count: u32) {
+        if self.hdr.as_ref().map(|p| p.0) != Some(sample_count) {
+            let pipeline = create_terrain_pipeline(
+                device,
+                &self.shader,
+                &self.pipeline_layout,
+                HDR_FORMAT,
+                sample_count,
+            );
+            self.hdr = Some((sample_count, pipeline));
+        }
+    }
+
+    fn draw(
+        &self,
+        render_pass: &mut wgpu::RenderPass<'_>,
+        hdr_samples: Option<u32>,
+        counters: &RenderCounters,
+    ) {
+        let pipeline = match (hdr_samples, &self.hdr) {
+            (None, _) => &self.direct,
+            (Some(wanted), Some((samples, pipeline))) if *samples == wanted => pipeline,
+            _ => return,
+        };
+        render_pass.set_pipeline(pipeline);
+        for buffers in self.terrains.values().filter(|b| b.index_count > 0) {
+            render_pass.set_bind_group(0, &buffers.bind_group, &[]);
+            render_pass.set_vertex_buffer(0, buffers.vertex_buffer.slice(..));
+            render_pass.set_index_buffer(buffers.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+            render_pass.draw_indexed(0..buffers.index_count, 0, 0..1);
+            counters.record_draw(u64::from(buffers.index_count / 3));
+        }
+    }
+}
+
+/// Texturas, vértices e view-projection dos sprites antes do draw
+fn sprite_step(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    resources: &mut GpuResources,
+    scene: &SceneState,
+    shaders: &ShaderLibrary,
+) {
+    engine_core::profile_function!(engine_core::ProfileCategory::Render);
+    if scene.sprites.is_empty() {
+        if let Some(sprites) = &mut resources.sprites {
+            sprites.batches.clear();
+            sprites.textures.clear();
+        }
+        return;
+    }
+    let white_view = &resources.white_pixel_texture.1;
+    let sprites = resources.sprites.get_or_insert_with(|| {
+        create_quad_resources(
+            device,
+            resources.target_format,
+            shaders.source(ShaderFile::Sprite),
+            ["vs_sprite", "fs_sprite"],
+            SPRITE_QUADS,
+            white_view,
+        )
+    });
+    sprites.upload(device, queue, &scene.sprites, scene.sprite_view_proj);
+}
+
+/// Atlas das fontes, vértices e view-projection dos textos 3D antes do draw
+fn text_step(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    resources: &mut GpuResources,
+    scene: &SceneState,
+    shaders: &ShaderLibrary,
+) {
+    engine_core::profile_function!(engine_core::ProfileCategory::Render);
+    if scene.texts.is_empty() {
+        if let Some(texts) = &mut resources.texts {
+            texts.batches.clear();
+            texts.textures.clear();
+        }
+        return;
+    }
+    let white_view = &resources.white_pixel_texture.1;
+    let texts = resources.texts.get_or_insert_with(|| {
+        create_quad_resources(
+            device,
+            resources.target_format,
+            shaders.source(ShaderFile::Text),
+            ["vs_text", "fs_text"],
+            TEXT_QUADS,
+            white_view,
+        )
+    });
+    texts.upload(device, queue, &scene.texts, scene.text_view_proj);
+}
+
+/// Atlas das fontes e vértices do texto da UI (já em clip space) antes do draw
+fn ui_text_step(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    resources: &mut GpuResources,
+    scene: &SceneState,
+    shaders: &ShaderLibrary,
+) {
+    engine_core::profile_function!(engine_core::ProfileCategory::Render);
+    if scene.ui_texts.is_empty() {
+        if let Some(texts) = &mut resources.ui_texts {
+            texts.batches.clear();
+            texts.textures.clear();
+        }
+        return;
+    }
+    let white_view = &resources.white_pixel_texture.1;
+    let texts = resources.ui_texts.get_or_insert_with(|| {
+        create_quad_resources(
+            device,
+            resources.target_format,
+            shaders.source(ShaderFile::Text),
+            ["vs_text", "fs_text"],
+            UI_TEXT_QUADS,
+            white_view,
+        )
+    });
+    texts.upload(device, queue, &scene.ui_texts, Mat4::IDENTITY);
+}
+
+fn create_quad_resources(
+    device: &wgpu::Device,
+    target_format: wgpu::TextureFormat,
+    source: &str,
+    entry_points: [&'static str; 2],
+    style: QuadStyle,
+    white_view: &wgpu::TextureView,
+) -> QuadResources {
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("viewport_quad_shader"),
+        source: wgpu::ShaderSource::Wgsl(source.into()),
+    });
+    let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("viewport_quad_layout"),
+        entries: &[
+            wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 1,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 2,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                count: None,
+            },
+        ],
+    });
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("viewport_quad_pipeline_layout"),
+        bind_group_layouts: &[&bind_group_layout],
+        push_constant_ranges: &[],
+    });
+    let direct = create_quad_pipeline(
+        device,
+        &shader,
+        &pipeline_layout,
+        entry_points,
+        target_format,
+        1,
+        style.depth_compare,
+    );
+    let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("viewport_quad_uniforms"),
+        size: SPRITE_UNIFORM_SIZE as u64,
+        usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+        label: Some("viewport_quad_sampler"),
+        mag_filter: style.filter,
+        min_filter: style.filter,
+        ..Default::default()
+    });
+    let white_bind_group = create_quad_bind_group(
+        device,
+        &bind_group_layout,
+        &uniform_buffer,
+        white_view,
+        &sampler,
+    );
+
+    QuadResources {
+        shader,
+        pipeline_layout,
+        bind_group_layout,
+        entry_points,
+        direct,
+        hdr: None,
+        uniform_buffer,
+        sampler,
+        style,
+        textures: HashMap::new(),
+        white_bind_group,
+        batches: Vec::new(),
+    }
+}
+
+fn create_quad_bind_group(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    uniform_buffer: &wgpu::Buffer,
+    view: &wgpu::TextureView,
+    sampler: &wgpu::Sampler,
+) -> wgpu::BindGroup {
+    device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("viewport_quad_bind_group"),
+        layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::TextureView(view),
+            },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: wgpu::BindingResource::Sampler(sampler),
+            },
+        ],
+    })
+}
+
+/// Quads com alpha blend: testam a profundidade da cena (`depth_compare`) sem
+/// escrevê-la, para os sprites ordenados se misturarem uns sobre os outros
+fn create_quad_pipeline(
+    device: &wgpu::Device,
+    shader: &wgpu::ShaderModule,
+    layout: &wgpu::PipelineLayout,
+    entry_points: [&str; 2],
+    format: wgpu::TextureFormat,
+    sample_count: u32,
+    depth_compare: wgpu::CompareFunction,
+) -> wgpu::RenderPipeline {
+    let vertex_layout = wgpu::VertexBufferLayout {
+        array_stride: SPRITE_VERTEX_STRIDE as u64,
+        step_mode: wgpu::VertexStepMode::Vertex,
+        attributes: &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x2, 2 => Float32x4],
+    };
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("viewport_quad_pipeline"),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: Some(entry_points[0]),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            buffers: &[vertex_layout],
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: Some(entry_points[1]),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+        }),
+        // Sprites invertidos ficam de costas para a câmera
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            cull_mode: None,
+            ..Default::default()
+        },
+        depth_stencil: Some(wgpu::DepthStencilState {
+            format: wgpu::TextureFormat::Depth24Plus,
+            depth_write_enabled: false,
+            depth_compare,
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        }),
+        multisample: wgpu::MultisampleState {
+            count: sample_count,
+            ..Default::default()
+        },
+        multiview: None,
+        cache: None,
+    })
+}
+
+impl QuadResources {
+    /// Carrega as texturas novas, descarta as que saíram do frame e refaz os buffers
+    fn upload(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        uploads: &[QuadUpload],
+        view_proj: Mat4,
+    ) {
+        let view_proj: Vec<u8> = view_proj
+            .to_cols_array()
+            .iter()
+            .flat_map(|f| f.to_le_bytes())
+            .collect();
+        queue.write_buffer(&self.uniform_buffer, 0, &view_proj);
+        self.textures
+            .retain(|path, _| uploads.iter().any(|upload| &upload.texture == path));
+        for upload in uploads.iter().filter(|upload| !upload.texture.is_empty()) {
+            if self.textures.contains_key(&upload.texture) {
+                continue;
+            }
+            let loaded = match &upload.image {
+                Some(image) => Some(create_rgba_texture(
+                    device,
+                    queue,
+                    [image.width, image.height],
+                    &image.rgba,
+                    self.style.texture_format,
+                )),
+                None => {
+                    load_image_texture(device, queue, &upload.texture, self.style.texture_format)
+                }
+            }
+            .map(|(texture, view)| {
+                let bind_group = create_quad_bind_group(
+                    device,
+                    &self.bind_group_layout,
+                    &self.uniform_buffer,
+                    &view,
+                    &self.sampler,
+                );
+                (texture, bind_group)
+            });
+            self.textures.insert(upload.texture.clone(), loaded);
+        }
+        self.batches = uploads
+            .iter()
+            .filter(|upload| !upload.indices.is_empty())
+            .map(|upload| QuadBuffers {
+                texture: upload.texture.clone(),
+                vertex_buffer: device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some("viewport_quad_vb"),
+                    contents: &vertex_bytes(&upload.vertices),
+                    usage: wgpu::BufferUsages::VERTEX,
+                }),
+                index_buffer: device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some("viewport_quad_ib"),
+                    contents: &upload
+                        .indices
+                        .iter()
+                        .flat_map(|i| i.to_le_bytes())
+                        .collect::<Vec<u8>>(),
+                    usage: wgpu::BufferUsages::INDEX,
+                }),
+                index_count: upload.indices.len() as u32,
+            })
+            .collect();
+    }
+
+    fn ensure_hdr_pipeline(&mut self, device: &wgpu::Device, sample_count: u32) {
+        if self.hdr.as_ref().map(|p| p.0) != Some(sample_count) {
+            let pipeline = create_quad_pipeline(
+                device,
+                &self.shader,
+                &self.pipeline_layout,
+                self.entry_points,
+                HDR_FORMAT,
+                sample_count,
+                self.style.depth_compare,
+            );
+            self.hdr = Some((sample_count, pipeline));
+        }
+    }
+
+    fn draw(
+        &self,
+        render_pass: &mut wgpu::RenderPass<'_>,
+        hdr_samples: Option<u32>,
+        counters: &RenderCounters,
+    ) {
+        let pipeline = match (hdr_samples, &self.hdr) {
+            (None, _) => &self.direct,
+            (Some(wanted), Some((samples, pipeline))) if *samples == wanted => pipeline,
+            _ => return,
+        };
+        render_pass.set_pipeline(pipeline);
+        for batch in &self.batches {
+            let bind_group = match self.textures.get(&batch.texture) {
+                Some(Some((_, bind_group))) => bind_group,
+                _ => &self.white_bind_group,
+            };
+            render_pass.set_bind_group(0, bind_group, &[]);
+            render_pass.set_vertex_buffer(0, batch.vertex_buffer.slice(..));
+            render_pass.set_index_buffer(batch.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+            render_pass.draw_indexed(0..batch.index_count, 0, 0..1);
+            counters.record_draw(u64::from(batch.index_count / 3));
+        }
+    }
+}
+
+impl ParticleResources {
+    fn ensure_hdr_pipelines(&mut self, device: &wgpu::Device, sample_count: u32) {
+        if self.hdr.as_ref().map(|p| p.0) != Some(sample_count) {
+            let pipelines = [false, true].map(|additive| {
+                create_particle_pipeline(
+                    device,
+                    &self.shader,
+                    &self.render_pipeline_layout,
+                    HDR_FORMAT,
+                    sample_count,
+                    additive,
+                )
+            });
+            self.hdr = Some((sample_count, pipelines));
+        }
+    }
+
+    fn draw(
+        &self,
+        render_pass: &mut wgpu::RenderPass<'_>,
+        hdr_samples: Option<u32>,
+        counters: &RenderCounters,
+    ) {
+        let pipelines = match (hdr_samples, &self.hdr) {
+            (None, _) => &self.direct,
+            (Some(wanted), Some((samples, pipelines))) if *samples == wanted => pipelines,
+            _ => return,
+        };
+        for buffers in self.emitters.values() {
+            render_pass.set_pipeline(&pipelines[usize::from(buffers.additive)]);
+            render_pass.set_bind_group(0, &buffers.render_bind_group, &[]);
+            render_pass.draw(0..6, 0..buffers.capacity);
+            // Dois triângulos por partícula
+            counters.record_draw(u64::from(buffers.capacity) * 2);
+        }
+    }
+}
+
+fn create_pick_resources(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    source: &str,
+) -> PickResources {
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("viewport_pick_shader"),
+        source: wgpu::ShaderSource::Wgsl(source.into()),
+    });
+    let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("viewport_pick_pipeline"),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: &shader,
+            entry_point: Some("vs_pick"),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            // Mesmo vertex buffer da cena; só a posição é lida
+            buffers: &[wgpu::VertexBufferLayout {
+                array_stride: LIT_VERTEX_STRIDE as u64,
+                step_mode: wgpu::VertexStepMode::Vertex,
+                attributes: &[wgpu::VertexAttribute {
+                    offset: 0,
+                    shader_location: 0,
+                    format: wgpu::VertexFormat::Float32x3,
+                }],
+            }],
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: &shader,
+            entry_point: Some("fs_pick"),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            targets: &[Some(wgpu::ColorTargetState {
+                format: wgpu::TextureFormat::R32Uint,
+                blend: None,
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            cull_mode: None,
+            ..Default::default()
+        },
+        depth_stencil: Some(wgpu::DepthStencilState {
+            format: wgpu::TextureFormat::Depth24Plus,
+            depth_write_enabled: true,
+            depth_compare: wgpu::CompareFunction::Less,
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        }),
+        multisample: wgpu::MultisampleState::default(),
+        multiview: None,
+        cache: None,
+    });
+    let readback = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("viewport_pick_readback"),
+        size: wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as u64,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    PickResources {
+        pipeline,
+        targets: None,
+        readback,
+        state: PickReadback::Idle,
+    }
+}
+
+/// Desenha os objetos selecionados na máscara do contorno (mesmo pipeline do
+/// picking, ID 1) e atualiza os uniforms da borda
+fn outline_step(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    resources: &mut GpuResources,
+    scene: &SceneState,
+    shaders: &ShaderLibrary,
+) -> Option<wgpu::CommandBuffer> {
+    engine_core::profile_function!(engine_core::ProfileCategory::Render);
+    if let Some(outline) = &mut resources.outline {
+        outline.active = false;
+    }
+    let ranges: Vec<[u32; 2]> = scene
+        .selection
+        .iter()
+        .filter_map(|&i| resources.pick_ranges.get(i).copied())
+        .filter(|&[_, count]| count > 0)
+        .collect();
+    if ranges.is_empty() {
+        return None;
+    }
+    let (Some(vb), Some(ib), Some(bind_group)) = (
+        &resources.vertex_buffer,
+        &resources.index_buffer,
+        &resources.current_bind_group,
+    ) else {
+        return None;
+    };
+
+    let pick = resources.pick.get_or_insert_with(|| {
+        create_pick_resources(
+            device,
+            &resources.pipeline_layout,
+            shaders.source(ShaderFile::Pick),
+        )
+    });
+    let outline = resources.outline.get_or_insert_with(|| {
+        create_outline_resources(
+            device,
+            resources.target_format,
+            shaders.source(ShaderFile::Outline),
+        )
+    });
+    let size = scene.outline_size;
+    if outline
+        .targets
+        .as_ref()
+        .is_none_or(|targets| targets.0 != size)
+    {
+        let target = |label: &str, format: wgpu::TextureFormat, usage: wgpu::TextureUsages| {
+            device
+                .create_texture(&wgpu::TextureDescriptor {
+                    label: Some(label),
+                    size: wgpu::Extent3d {
+                        width: size[0],
+                        height: size[1],
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format,
+                    usage: wgpu::TextureUsages::RENDER_ATTACHMENT | usage,
+                    view_formats: &[],
+                })
+                .create_view(&wgpu::TextureViewDescriptor::default())
+        };
+        let mask = target(
+            "viewport_outline_mask",
+            wgpu::TextureFormat::R32Uint,
+            wgpu::TextureUsages::TEXTURE_BINDING,
+        );
+        let depth = target(
+            "viewport_outline_depth",
+            wgpu::TextureFormat::Depth24Plus,
+            wgpu::TextureUsages::empty(),
+        );
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("viewport_outline_bind_group"),
+            layout: &outline.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: outline.uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&mask),
+                },
+            ],
+        });
+        outline.targets = Some((size, mask, depth, bind_group));
+    }
+    let Some((_, mask_view, depth_view, _)) = &outline.targets else {
+        return None;
+    };
+    queue.write_buffer(&outline.uniform_buffer, 0, &scene.outline_uniforms);
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("viewport_outline_encoder"),
+    });
+    {
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("viewport_outline_mask_pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: mask_view,
+                depth_slice: None,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: depth_view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(1.0),
+                    store: wgpu::StoreOp::Discard,
+                }),
+                stencil_ops: None,
+            }),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_pipeline(&pick.pipeline);
+        pass.set_bind_group(0, bind_group, &[]);
+        pass.set_vertex_buffer(0, vb.slice(..));
+        pass.set_index_buffer(ib.slice(..), wgpu::IndexFormat::Uint32);
+        // Silhueta inteira, mesmo atrás de outros objetos (só os selecionados entram)
+        for [first, count] in ranges {
+            pass.draw_indexed(first..first + count, 0, 1..2);
+        }
+    }
+    outline.active = true;
+    Some(encoder.finish())
+}
+
+/// Pipeline da borda do contorno, desenhado no pass do egui
+fn create_outline_resources(
+    device: &wgpu::Device,
+    target_format: wgpu::TextureFormat,
+    source: &str,
+) -> OutlineResources {
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("viewport_outline_shader"),
+        source: wgpu::ShaderSource::Wgsl(source.into()),
+    });
+    let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("viewport_outline_bind_layout"),
+        entries: &[
+            wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 1,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Uint,
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            },
+        ],
+    });
+    let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("viewport_outline_pipeline_layout"),
+        bind_group_layouts: &[&bind_group_layout],
+        push_constant_ranges: &[],
+    });
+    let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("viewport_outline_pipeline"),
+        layout: Some(&layout),
+        vertex: wgpu::VertexState {
+            module: &shader,
+            entry_point: Some("vs_outline"),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            buffers: &[],
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: &shader,
+            entry_point: Some("fs_outline"),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            targets: &[Some(wgpu::ColorTargetState {
+                format: target_format,
+                blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            cull_mode: None,
+            ..Default::default()
+        },
+        // Por cima de tudo no pass do egui, sem escrever profundidade
+        depth_stencil: Some(wgpu::DepthStencilState {
+            format: wgpu::TextureFormat::Depth24Plus,
+            depth_write_enabled: false,
+            depth_compare: wgpu::CompareFunction::Always,
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        }),
+        multisample: wgpu::MultisampleState::default(),
+        multiview: None,
+        cache: None,
+    });
+    let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("viewport_outline_ubo"),
+        size: OUTLINE_UNIFORM_SIZE as u64,
+        usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    OutlineResources {
+        pipeline,
+        bind_group_layout,
+        uniform_buffer,
+        targets: None,
+        active: false,
+    }
+}
+
+fn upload_pending_mesh_chunk(
+    queue: &wgpu::Queue,
+    pending: &mut PendingMeshUpload,
+    vertices: &[[f32; 3]],
+    normals: &[[f32; 3]],
+    uvs: &[[f32; 2]],
+    triangles: &[[u32; 3]],
+    budget_left: &mut usize,
+) {
+    engine_core::profile_function!(engine_core::ProfileCategory::Render);
+    if *budget_left == 0 {
+        return;
+    }
+
+    let vertex_available = vertices.len().min(normals.len()).min(uvs.len());
+    if pending.vertex_cursor < pending.vertex_len && pending.vertex_cursor < vertex_available {
+        let stride = LIT_VERTEX_STRIDE; // 32 bytes: pos(12) + normal(12) + uv(8)
+        let max_count = (*budget_left / stride).max(1);
+        let end = (pending.vertex_cursor + max_count)
+            .min(pending.vertex_len)
+            .min(vertex_available);
+        let chunk_len = end - pending.vertex_cursor;
+        let mut bytes = Vec::with_capacity(chunk_len * stride);
+        for i in 0..chunk_len {
+            let vi = pending.vertex_cursor + i;
+            let v = &vertices[vi];
+            let n = &normals[vi];
+            let uv = &uvs[vi];
+            // pos
+            bytes.extend_from_slice(&v[0].to_le_bytes());
+            bytes.extend_from_slice(&v[1].to_le_bytes());
+            bytes.extend_from_slice(&v[2].to_le_bytes());
+            // normal
+            bytes.extend_from_slice(&n[0].to_le_bytes());
+            bytes.extend_from_slice(&n[1].to_le_bytes());
+            bytes.extend_from_slice(&n[2].to_le_bytes());
+            // uv
+            bytes.extend_from_slice(&uv[0].to_le_bytes());
+            bytes.extend_from_slice(&uv[1].to_le_bytes());
+        }
+        let offset = (pending.vertex_cursor * stride) as u64;
+        queue.write_buffer(&pending.vertex_buffer, offset, &bytes);
+        pending.vertex_cursor = end;
+        *budget_left = budget_left.saturating_sub(bytes.len());
+        if *budget_left == 0 {
+            return;
+        }
+    }
+
+    if pending.tri_cursor < pending.tri_len && pending.tri_cursor < triangles.len() {
+        let stride = std::mem::size_of::<[u32; 3]>();
+        let max_count = (*budget_left / stride).max(1);
+        let end = (pending.tri_cursor + max_count)
+            .min(pending.tri_len)
+            .min(triangles.len());
+        let chunk_len = end - pending.tri_cursor;
+        let mut bytes = Vec::with_capacity(chunk_len * stride);
+        for tri in &triangles[pending.tri_cursor..end] {
+            bytes.extend_from_slice(&tri[0].to_le_bytes());
+            bytes.extend_from_slice(&tri[1].to_le_bytes());
+            bytes.extend_from_slice(&tri[2].to_le_bytes());
+        }
+        let offset = (pending.tri_cursor * stride) as u64;
+        queue.write_buffer(&pending.index_buffer, offset, &bytes);
+        pending.tri_cursor = end;
+        *budget_left = budget_left.saturating_sub(bytes.len());
+    }
+}
+
+impl egui_wgpu::CallbackTrait for Draw3dCallback {
+    fn prepare(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        _screen_descriptor: &egui_wgpu::ScreenDescriptor,
+        _egui_encoder: &mut wgpu::CommandEncoder,
+        callback_resources: &mut egui_wgpu::CallbackResources,
+    ) -> Vec<wgpu::CommandBuffer> {
+        engine_core::profile_function!(engine_core::ProfileCategory::Render);
+        use std::path::PathBuf;
+
+        // Mapas de IBL vêm do céu, preparado antes neste mesmo frame
+        let ibl = view_resources::<SkyResources>(callback_resources, self.view)
+            .and_then(|sky| sky.ibl.clone());
+        let sky_settings = self.sky.lock().expect("sky lock").settings;
+
+        let shaders = self.shaders.lock().expect("shaders lock");
+        let resources = view_entry::<GpuResources>(callback_resources, self.view)
+            .or_insert_with(|| self.create_resources(device, queue, &shaders));
+        // Shader recarregado: recria os pipelines (a malha sobe de novo)
+        if resources.shader_generation != shaders.generation() {
+            *resources = self.create_resources(device, queue, &shaders);
+        }
+        if resources.bound_ibl_id != ibl.as_ref().map(|maps| maps.id) {
+            resources.bound_ibl_id = ibl.as_ref().map(|maps| maps.id);
+            resources.current_bind_group = None;
+        }
+
+        let mut scene = self.scene.lock().expect("scene lock");
+        scene.memory.begin_frame();
+        let current_mesh_texture_path = scene.texture_path.clone().map(|p| normalize_path(&p));
+        let reload_textures = std::mem::take(&mut scene.reload_textures);
+        for path in reload_textures {
+            let keys: Vec<String> = resources
+                .textures
+                .keys()
+                .filter(|key| {
+                    same_file(
+                        Path::new(&normalize_path(key)),
+                        Path::new(&normalize_path(&path)),
+                    )
+                })
+                .cloned()
+                .collect();
+            for key in keys {
+                if current_mesh_texture_path.as_ref() == Some(&key) {
+                    // A textura em uso continua desenhada até a nova ser decodificada
+                    resources
+                        .texture_loads
+                        .insert(key.clone(), spawn_texture_load(&key));
+                } else if resources.textures.remove(&key).is_some() {
+                    scene.memory.forget(&key);
+                    resources.current_bind_group = None;
+                }
+            }
+        }
+
+        if scene.mesh_id == 0 || scene.vertices.is_empty() || scene.triangles.is_empty() {
+            if resources.current_texture_path.as_ref() != current_mesh_texture_path.as_ref() {
+                resources.current_texture_path = current_mesh_texture_path;
+                resources.current_bind_group = None;
+            }
+            // Lote vazio (só terrenos, por exemplo): nada do lote anterior é desenhado
+            resources.index_count = 0;
+            resources.uploaded_mesh_id = 0;
+            // Cena vazia: o clique cai no fundo
+            if scene.pick_request.take().is_some() {
+                scene.pick_result = Some(None);
+            }
+            if let Some(outline) = &mut resources.outline {
+                outline.active = false;
+            }
+            terrain_step(device, queue, resources, &scene, &shaders);
+            sprite_step(device, queue, resources, &scene, &shaders);
+            text_step(device, queue, resources, &scene, &shaders);
+            ui_text_step(device, queue, resources, &scene, &shaders);
+            memory_step(resources, &mut scene.memory);
+            return Vec::new();
+        }
+
+        // Upload de mesh quando o ID muda
+        if resources.uploaded_mesh_id != scene.mesh_id {
+            let should_rebuild = resources
+                .pending_mesh_upload
+                .as_ref()
+                .map_or(true, |p| p.mesh_id != scene.mesh_id);
+            if should_rebuild {
+                let vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+                    label: Some("viewport_gpu_vb"),
+                    size: (scene.vertices.len() * LIT_VERTEX_STRIDE) as u64,
+                    usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                });
+                let index_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+                    label: Some("viewport_gpu_ib"),
+                    size: (scene.triangles.len() * std::mem::size_of::<[u32; 3]>()) as u64,
+                    usage: wgpu::BufferUsages::INDEX | wgpu::BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                });
+
+                resources.staged_vertices.clear();
+                resources.staged_vertices.extend_from_slice(&scene.vertices);
+                resources.staged_normals.clear();
+                resources.staged_normals.extend_from_slice(&scene.normals);
+                resources.staged_uvs.clear();
+                resources.staged_uvs.extend_from_slice(&scene.uvs);
+                resources.staged_triangles.clear();
+                resources
+                    .staged_triangles
+                    .extend_from_slice(&scene.triangles);
+
+                let vertex_len = resources
+                    .staged_vertices
+                    .len()
+                    .min(resources.staged_normals.len())
+                    .min(resources.staged_uvs.len());
+                resources.pending_mesh_upload = Some(PendingMeshUpload {
+                    mesh_id: scene.mesh_id,
+                    vertex_len,
+                    tri_len: resources.staged_triangles.len(),
+                    vertex_cursor: 0,
+                    tri_cursor: 0,
+                    vertex_buffer,
+                    index_buffer,
+                    index_count: (scene.triangles.len() * 3) as u32,
+                    pick_ranges: scene.pick_ranges.clone(),
+                });
+            }
+        }
+
+        // Carrega textura se necessário
+        let mut has_texture = 0.0_f32;
+        let mut mip_commands = Vec::new();
+
+        if let Some(texture_path_str) = &current_mesh_texture_path {
+            if resources.current_texture_path.as_ref() != Some(texture_path_str) {
+                resources.current_texture_path = Some(texture_path_str.clone());
+                resources.current_bind_group = None;
+            }
+
+            if resources.textures.get(texture_path_str).is_none()
+                || resources.texture_loads.contains_key(texture_path_str)
+            {
+                // Normaliza o path para abrir o arquivo (remove \\?\ se existir)
+                let path = PathBuf::from(normalize_path(texture_path_str));
+                // A leitura e a decodificação rodam fora da thread da UI; até
+                // terminarem o lote é desenhado sem textura (ou com a anterior,
+                // quando o arquivo foi alterado)
+                let load = resources
+                    .texture_loads
+                    .entry(texture_path_str.clone())
+                    .or_insert_with(|| spawn_texture_load(texture_path_str));
+                let decoded = load.lock().expect("texture load lock").take();
+                if decoded.is_some() {
+                    resources.texture_loads.remove(texture_path_str);
+                }
+                match decoded {
+                    None => {}
+                    Some(Ok(texture)) => {
+                        let sampling = TextureSampling::load(&path);
+                        let srgb = TextureImportSettings::default().with_meta(&path).is_srgb();
+                        let mipmap = resources.mipmap.get_or_insert_with(|| {
+                            create_mipmap_pipeline(device, shaders.source(ShaderFile::Mipmap))
+                        });
+                        let label = format!("viewport_gpu_texture_{}", texture_path_str);
+                        let texture = match &texture.hdr_rgba {
+                            // HDR/EXR (lightmaps): floats na GPU, mips montados na CPU
+                            Some(pixels) => create_hdr_texture(
+                                device,
+                                queue,
+                                &label,
+                                pixels,
+                                (texture.width, texture.height),
+                                sampling,
+                            ),
+                            None => {
+                                let (texture, commands) = create_sampled_texture(
+                                    device, queue, mipmap, &label, &texture, sampling, srgb,
+                                );
+                                mip_commands.extend(commands);
+                                texture
+                            }
+                        };
+                        scene.memory.track(
+                            texture_path_str,
+                            "Texture",
+                            gpu_texture_bytes(&texture.0),
+                            true,
+                        );
+                        resources.textures.insert(texture_path_str.clone(), texture);
+                        // Invalida bind group para recriar com a nova textura
+                        resources.current_bind_group = None;
+                    }
+                    Some(Err(_e)) => {
+                        resources.textures.insert(
+                            texture_path_str.clone(),
+                            resources.white_pixel_texture.clone(),
+                        );
+                        // Invalida bind group mesmo em caso de erro (usa white pixel)
+                        resources.current_bind_group = None;
+                    }
+                }
+            }
+            if resources.textures.get(texture_path_str).is_some() {
+                has_texture = 1.0;
+                scene.memory.touch(texture_path_str);
+            }
+        }
+        scene.textures_loading = !resources.texture_loads.is_empty();
+
+        // Luzes dinâmicas; buffers recriados invalidam o bind group
+        let lights_resized = [
+            write_storage_buffer(
+                device,
+                queue,
+                &mut resources.light_buffer,
+                "viewport_gpu_lights",
+                &scene.light_bytes,
+            ),
+            write_storage_buffer(
+                device,
+                queue,
+                &mut resources.cluster_buffer,
+                "viewport_gpu_clusters",
+                &scene.cluster_bytes,
+            ),
+            write_storage_buffer(
+                device,
+                queue,
+                &mut resources.light_index_buffer,
+                "viewport_gpu_light_indices",
+                &scene.light_index_bytes,
+            ),
+        ];
+        if lights_resized.contains(&true) {
+            resources.current_bind_group = None;
+        }
+
+        // Preenche uniform buffer (288 bytes)
+        // Layout do shader:
+        //   0..64   mvp (mat4)
+        //  64..128  model (mat4)
+        // 128..140  camera_pos (vec3)
+        // 140..144  light_intensity (f32)
+        // 144..156  light_dir (vec3)
+        // 156..160  light_enabled (f32)
+        // 160..172  light_color (vec3)
+        // 172..176  has_texture (f32)
+        // 176..192  tint (vec4)
+        // 192..208  cluster_params (near, far, nº direcionais, nº locais)
+        // 208..224  camera_forward (vec4)
+        // 224..240  ibl_params (intensidade, rotação do céu, rugosidade, metálico)
+        // 240..288  fog_color, fog_params, fog_height (ver `fog_uniform`)
+        let mut offs = 0usize;
+        for col in &scene.mvp {
+            for f in col {
+                push_f32(&mut resources.uniform_data, offs, *f);
+                offs += 4;
+            }
+        }
+        for col in &scene.model {
+            for f in col {
+                push_f32(&mut resources.uniform_data, offs, *f);
+                offs += 4;
+            }
+        }
+        // camera_pos (128..140)
+        push_f32(&mut resources.uniform_data, offs, scene.camera_pos[0]);
+        offs += 4;
+        push_f32(&mut resources.uniform_data, offs, scene.camera_pos[1]);
+        offs += 4;
+        push_f32(&mut resources.uniform_data, offs, scene.camera_pos[2]);
+        offs += 4;
+        // light_intensity (140..144)
+        push_f32(&mut resources.uniform_data, offs, scene.light_intensity);
+        offs += 4;
+        // light_dir (144..156)
+        push_f32(&mut resources.uniform_data, offs, scene.light_dir[0]);
+        offs += 4;
+        push_f32(&mut resources.uniform_data, offs, scene.light_dir[1]);
+        offs += 4;
+        push_f32(&mut resources.uniform_data, offs, scene.light_dir[2]);
+        offs += 4;
+        // light_enabled (156..160)
+        push_f32(&mut resources.uniform_data, offs, scene.light_enabled);
+        offs += 4;
+        // light_color (160..172)
+        push_f32(&mut resources.uniform_data, offs, scene.light_color[0]);
+        offs += 4;
+        push_f32(&mut resources.uniform_data, offs, scene.light_color[1]);
+        offs += 4;
+        push_f32(&mut resources.uniform_data, offs, scene.light_color[2]);
+        offs += 4;
+        // has_texture (172..176)
+        push_f32(&mut resources.uniform_data, offs, has_texture);
+        offs += 4;
+        // tint (176..192)
+        push_f32(&mut resources.uniform_data, offs, 1.0); // R
+        offs += 4;
+        push_f32(&mut resources.uniform_data, offs, 1.0); // G
+        offs += 4;
+        push_f32(&mut resources.uniform_data, offs, 1.0); // B
+        offs += 4;
+        push_f32(&mut resources.uniform_data, offs, 1.0); // A
+        offs += 4;
+        // cluster_params (192..208)
+        for f in scene.cluster_params {
+            push_f32(&mut resources.uniform_data, offs, f);
+            offs += 4;
+        }
+        // camera_forward (208..224)
+        for f in scene.camera_forward {
+            push_f32(&mut resources.uniform_data, offs, f);
+            offs += 4;
+        }
+        push_f32(&mut resources.uniform_data, offs, 0.0);
+        offs += 4;
+        // ibl_params (224..240)
+        let ibl_intensity = if sky_settings.ibl_enabled && ibl.is_some() {
+            sky_settings.ibl_intensity.max(0.0)
+        } else {
+            0.0
+        };
+        for f in [
+            ibl_intensity,
+            sky_settings.rotation,
+            IBL_DEFAULT_ROUGHNESS,
+            IBL_DEFAULT_METALLIC,
+        ] {
+            push_f32(&mut resources.uniform_data, offs, f);
+            offs += 4;
+        }
+        // fog (240..288)
+        for f in scene.fog {
+            push_f32(&mut resources.uniform_data, offs, f);
+            offs += 4;
+        }
+        let _ = offs;
+
+        queue.write_buffer(&resources.uniform_buffer, 0, &resources.uniform_data);
+
+        // Atualiza bind group se necessário
+        let bind_group_needed = resources.current_bind_group.is_none()
+            || resources.current_texture_path.as_ref().map(|s| s.as_str())
+                != current_mesh_texture_path.as_ref().map(|s| s.as_str());
+        if bind_group_needed {
+            let (irradiance_view, specular_view) = ibl.as_ref().map_or(
+                (&resources.ibl_fallback_view, &resources.ibl_fallback_view),
+                |maps| (&maps.irradiance.1, &maps.specular.1),
+            );
+            let (_tex, tex_view, tex_sampler) = if let Some(path) = &resources.current_texture_path
+            {
+                resources
+                    .textures
+                    .get(path)
+                    .unwrap_or(&resources.white_pixel_texture)
+            } else {
+                &resources.white_pixel_texture
+            };
+
+            resources.current_bind_group =
+                Some(device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: Some("viewport_gpu_bind_group"),
+                    layout: &resources.bind_group_layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                                buffer: &resources.uniform_buffer,
+                                offset: 0,
+                                size: None,
+                            }),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: wgpu::BindingResource::Sampler(tex_sampler),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 2,
+                            resource: wgpu::BindingResource::TextureView(tex_view),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 3,
+                            resource: resources.light_buffer.as_entire_binding(),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 4,
+                            resource: resources.cluster_buffer.as_entire_binding(),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 5,
+                            resource: resources.light_index_buffer.as_entire_binding(),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 6,
+                            resource: wgpu::BindingResource::TextureView(irradiance_view),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 7,
+                            resource: wgpu::BindingResource::TextureView(specular_view),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 8,
+                            resource: wgpu::BindingResource::Sampler(&resources.env_sampler),
+                        },
+                    ],
+                }));
+        }
+
+        // Upload de mesh (chunked)
+        let mut budget = GPU_UPLOAD_BUDGET_BYTES;
+        while let Some(mut pending) = resources.pending_mesh_upload.take() {
+            upload_pending_mesh_chunk(
+                queue,
+                &mut pending,
+                &resources.staged_vertices,
+                &resources.staged_normals,
+                &resources.staged_uvs,
+                &resources.staged_triangles,
+                &mut budget,
+            );
+            let done = pending.vertex_cursor >= pending.vertex_len
+                && pending.tri_cursor >= pending.tri_len;
+            if done {
+                resources.vertex_buffer = Some(pending.vertex_buffer);
+                resources.index_buffer = Some(pending.index_buffer);
+                resources.index_count = if pending.vertex_len > 0 {
+                    pending.index_count
+                } else {
+                    0
+                };
+                resources.uploaded_mesh_id = pending.mesh_id;
+                resources.pick_ranges = pending.pick_ranges;
+                resources.staged_vertices.clear();
+                resources.staged_normals.clear();
+                resources.staged_uvs.clear();
+                resources.staged_triangles.clear();
+                break;
+            }
+            resources.pending_mesh_upload = Some(pending);
+            if budget == 0 {
+                break;
+            }
+        }
+
+        resources.set_shading(device, scene.shading);
+        if scene.shading == ShadingMode::Wireframe
+            && resources.uploaded_mesh_id == scene.mesh_id
+            && resources.wire_index.as_ref().map(|w| w.0) != Some(scene.mesh_id)
+        {
+            let edges: Vec<u32> = scene
+                .triangles
+                .iter()
+                .flat_map(|&[a, b, c]| [a, b, b, c, c, a])
+                .collect();
+            let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("viewport_gpu_wire_ib"),
+                contents: &edges
+                    .iter()
+                    .flat_map(|i| i.to_le_bytes())
+                    .collect::<Vec<u8>>(),
+                usage: wgpu::BufferUsages::INDEX,
+            });
+            resources.wire_index = Some((scene.mesh_id, buffer, edges.len() as u32));
+        }
+
+        terrain_step(device, queue, resources, &scene, &shaders);
+        sprite_step(device, queue, resources, &scene, &shaders);
+        text_step(device, queue, resources, &scene, &shaders);
+        ui_text_step(device, queue, resources, &scene, &shaders);
+        memory_step(resources, &mut scene.memory);
+        mip_commands
+            .into_iter()
+            .chain(pick_step(device, resources, &mut scene, &shaders))
+            .chain(outline_step(device, queue, resources, &scene, &shaders))
+            .chain(particle_step(device, queue, resources, &scene, &shaders))
+            .collect()
+    }
+
+    fn paint(
+        &self,
+        info: egui::PaintCallbackInfo,
+        render_pass: &mut wgpu::RenderPass<'static>,
+        callback_resources: &egui_wgpu::CallbackResources,
+    ) {
+        engine_core::profile_function!(engine_core::ProfileCategory::Render);
+        let Some(resources) = view_resources::<GpuResources>(callback_resources, self.view) else {
+            return;
+        };
+        if !resources.is_drawable() {
+            return;
+        }
+
+        // Viewport e scissor na rect do callback
+        let ppp = info.pixels_per_point;
+        let v = &info.viewport;
+        let x = v.min.x * ppp;
+        let y = v.min.y * ppp;
+        let w = (v.width() * ppp).max(1.0) as u32;
+        let h = (v.height() * ppp).max(1.0) as u32;
+        if w > 0 && h > 0 {
+            render_pass.set_viewport(x, y, w as f32, h as f32, 0.0, 1.0);
+            render_pass.set_scissor_rect(x as u32, y as u32, w.max(1), h.max(1));
+        }
+
+        resources.draw(render_pass, None, &self.counters);
+        resources.draw_outline(render_pass, &self.counters);
+        resources.draw_ui(render_pass, &self.counters);
+    }
+}
+
+impl GpuResources {
+    fn is_drawable(&self) -> bool {
+        let batch = self.vertex_buffer.is_some()
+            && self.index_buffer.is_some()
+            && self.current_bind_group.is_some()
+            && self.index_count > 0;
+        batch
+            || self
+                .terrain
+                .as_ref()
+                .is_some_and(|terrain| !terrain.terrains.is_empty())
+            || self
+                .sprites
+                .as_ref()
+                .is_some_and(|sprites| !sprites.batches.is_empty())
+            || self
+                .texts
+                .as_ref()
+                .is_some_and(|texts| !texts.batches.is_empty())
+            || self
+                .ui_texts
+                .as_ref()
+                .is_some_and(|texts| !texts.batches.is_empty())
+    }
+
+    /// Troca a variante de shading; o pipeline HDR é recriado sob demanda
+    fn set_shading(&mut self, device: &wgpu::Device, shading: ShadingMode) {
+        if self.shading == shading {
+            return;
+        }
+        self.shading = shading;
+        self.solid_pipeline = create_solid_pipeline(
+            device,
+            &self.shader,
+            &self.pipeline_layout,
+            self.target_format,
+            1,
+            shading,
+        );
+        self.hdr_pipeline = None;
+    }
+
+    /// Cria (uma vez por nº de amostras) o pipeline usado no alvo HDR offscreen
+    fn ensure_hdr_pipeline(&mut self, device: &wgpu::Device, sample_count: u32) {
+        if self.hdr_pipeline.as_ref().map(|p| p.0) != Some(sample_count) {
+            let pipeline = create_solid_pipeline(
+                device,
+                &self.shader,
+                &self.pipeline_layout,
+                HDR_FORMAT,
+                sample_count,
+                self.shading,
+            );
+            self.hdr_pipeline = Some((sample_count, pipeline));
+        }
+        if let Some(particles) = &mut self.particles {
+            particles.ensure_hdr_pipelines(device, sample_count);
+        }
+        if let Some(terrain) = &mut self.terrain {
+            terrain.ensure_hdr_pipeline(device, sample_count);
+        }
+        if let Some(sprites) = &mut self.sprites {
+            sprites.ensure_hdr_pipeline(device, sample_count);
+        }
+        if let Some(texts) = &mut self.texts {
+            texts.ensure_hdr_pipeline(device, sample_count);
+        }
+    }
+
+    /// Desenha a cena no pass atual (viewport/scissor já definidos): o lote de
+    /// malhas, os terrenos, os sprites, os textos 3D e as partículas. `hdr_samples` seleciona
+    /// os pipelines do alvo HDR offscreen
+    fn draw(
+        &self,
+        render_pass: &mut wgpu::RenderPass<'_>,
+        hdr_samples: Option<u32>,
+        counters: &RenderCounters,
+    ) {
+        self.draw_batch(render_pass, hdr_samples, counters);
+        if let Some(terrain) = &self.terrain {
+            terrain.draw(render_pass, hdr_samples, counters);
+        }
+        if let Some(sprites) = &self.sprites {
+            sprites.draw(render_pass, hdr_samples, counters);
+        }
+        if let Some(texts) = &self.texts {
+            texts.draw(render_pass, hdr_samples, counters);
+        }
+        if let Some(particles) = &self.particles {
+            particles.draw(render_pass, hdr_samples, counters);
+        }
+    }
+
+    /// Texto da UI do jogo sobre a imagem final (viewport já definida)
+    fn draw_ui(&self, render_pass: &mut wgpu::RenderPass<'_>, counters: &RenderCounters) {
+        if let Some(texts) = &self.ui_texts {
+            texts.draw(render_pass, None, counters);
+        }
+    }
+
+    /// Borda dos objetos selecionados sobre a imagem final (viewport já definida)
+    fn draw_outline(&self, render_pass: &mut wgpu::RenderPass<'_>, counters: &RenderCounters) {
+        let Some(outline) = self.outline.as_ref().filter(|outline| outline.active) else {
+            return;
+        };
+        let Some((_, _, _, bind_group)) = &outline.targets else {
+            return;
+        };
+        render_pass.set_pipeline(&outline.pipeline);
+        render_pass.set_bind_group(0, bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+        counters.record_draw(1);
+    }
+
+    fn draw_batch(
+        &self,
+        render_pass: &mut wgpu::RenderPass<'_>,
+        hdr_samples: Option<u32>,
+        counters: &RenderCounters,
+    ) {
+        let (Some(vb), Some(ib), Some(bind_group)) = (
+            &self.vertex_buffer,
+            &self.index_buffer,
+            &self.current_bind_group,
+        ) else {
+            return;
+        };
+        if self.index_count == 0 {
+            return;
+        }
+        let pipeline = match (hdr_samples, &self.hdr_pipeline) {
+            (None, _) => &self.solid_pipeline,
+            (Some(wanted), Some((samples, pipeline))) if *samples == wanted => pipeline,
+            _ => return,
+        };
+        let (ib, index_count) = match (&self.wire_index, self.shading) {
+            (Some((mesh_id, wire_ib, count)), ShadingMode::Wireframe)
+                if *mesh_id == self.uploaded_mesh_id =>
+            {
+                (wire_ib, *count)
+            }
+            (_, ShadingMode::Wireframe) => return,
+            _ => (ib, self.index_count),
+        };
+        render_pass.set_bind_group(0, bind_group, &[]);
+        render_pass.set_pipeline(pipeline);
+        render_pass.set_vertex_buffer(0, vb.slice(..));
+        render_pass.set_index_buffer(ib.slice(..), wgpu::IndexFormat::Uint32);
+        render_pass.draw_indexed(0..index_count, 0, 0..1);
+        counters.record_draw(u64::from(index_count / 3));
+    }
+}
+
+impl MaterialPreviewCallback {
+    fn create_resources(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        shaders: &ShaderLibrary,
+    ) -> MaterialPreviewResources {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("material_preview_shader"),
+            source: wgpu::ShaderSource::Wgsl(shaders.source(ShaderFile::Lit).into()),
+        });
+        let bind_group_layout = create_lit_bind_group_layout(device, "material_preview_layout");
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("material_preview_pipeline_layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = create_solid_pipeline(
+            device,
+            &shader,
+            &pipeline_layout,
+            self.target_format,
+            1,
+            ShadingMode::Lit,
+        );
+
+        // `Mesh::sphere` gira no sentido horário visto de fora; o pipeline
+        // descarta as faces horárias
+        let sphere = Mesh::sphere(48);
+        let vertices: Vec<u8> = sphere
+            .vertices
+            .iter()
+            .flat_map(|v| {
+                [
+                    v.position.x,
+                    v.position.y,
+                    v.position.z,
+                    v.normal.x,
+                    v.normal.y,
+                    v.normal.z,
+                    v.texcoord.x,
+                    v.texcoord.y,
+                ]
+            })
+            .flat_map(f32::to_le_bytes)
+            .collect();
+        let indices: Vec<u8> = sphere
+            .indices
+            .chunks_exact(3)
+            .flat_map(|tri| [tri[0], tri[2], tri[1]])
+            .flat_map(u32::to_le_bytes)
+            .collect();
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("material_preview_vb"),
+            contents: &vertices,
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("material_preview_ib"),
+            contents: &indices,
+            usage: wgpu::BufferUsages::INDEX,
+        });
+
+        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("material_preview_ubo"),
+            contents: &[0_u8; LIT_UNIFORM_SIZE],
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+        let empty_lights = ClusteredLights::default();
+        let (_white_pixel, white_pixel_view) = create_white_pixel_texture(device, queue);
+        let (_ibl_fallback, ibl_fallback_view) =
+            create_sky_texture(device, queue, 1, 1, &[0_u8; 8]);
+
+        MaterialPreviewResources {
+            shader_generation: shaders.generation(),
+            pipeline,
+            bind_group_layout,
+            bind_group: None,
+            bound: None,
+            uniform_buffer,
+            light_buffer: create_storage_buffer(
+                device,
+                "material_preview_lights",
+                &empty_lights.light_bytes(),
+            ),
+            cluster_buffer: create_storage_buffer(
+                device,
+                "material_preview_clusters",
+                &empty_lights.cluster_bytes(),
+            ),
+            light_index_buffer: create_storage_buffer(
+                device,
+                "material_preview_light_indices",
+                &empty_lights.index_bytes(),
+            ),
+            vertex_buffer,
+            index_buffer,
+            index_count: (indices.len() / 4) as u32,
+            sampler: device.create_sampler(&wgpu::SamplerDescriptor {
+                label: Some("material_preview_sampler"),
+                mag_filter: wgpu::FilterMode::Linear,
+                min_filter: wgpu::FilterMode::Linear,
+                address_mode_u: wgpu::AddressMode::Repeat,
+                address_mode_v: wgpu::AddressMode::Repeat,
+                ..Default::default()
+            }),
+            white_pixel_view,
+            texture: None,
+            ibl_fallback_view,
+            env_sampler: device.create_sampler(&wgpu::SamplerDescriptor {
+                label: Some("material_preview_env_sampler"),
+                mag_filter: wgpu::FilterMode::Linear,
+                min_filter: wgpu::FilterMode::Linear,
+                mipmap_filter: wgpu::FilterMode::Linear,
+                address_mode_u: wgpu::AddressMode::Repeat,
+                address_mode_v: wgpu::AddressMode::ClampToEdge,
+                address_mode_w: wgpu::AddressMode::ClampToEdge,
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+impl egui_wgpu::CallbackTrait for MaterialPreviewCallback {
+    fn prepare(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        _screen_descriptor: &egui_wgpu::ScreenDescriptor,
+        _egui_encoder: &mut wgpu::CommandEncoder,
+        callback_resources: &mut egui_wgpu::CallbackResources,
+    ) -> Vec<wgpu::CommandBuffer> {
+        engine_core::profile_function!(engine_core::ProfileCategory::Render);
+        let ibl = view_resources::<SkyResources>(callback_resources, self.view)
+            .and_then(|sky| sky.ibl.clone());
+        let sky_settings = self.sky.lock().expect("sky lock").settings;
+        let shaders = self.shaders.lock().expect("shaders lock");
+        let resources = callback_resources
+            .entry::<MaterialPreviewResources>()
+            .or_insert_with(|| self.create_resources(device, queue, &shaders));
+        if resources.shader_generation != shaders.generation() {
+            *resources = self.create_resources(device, queue, &shaders);
+        }
+
+        let texture_path = self.preview.texture_path.as_deref().map(normalize_path);
+        if resources.texture.as_ref().map(|(path, _)| path) != texture_path.as_ref() {
+            resources.texture = texture_path.map(|path| {
+                let view =
+                    load_image_texture(device, queue, &path, wgpu::TextureFormat::Rgba8UnormSrgb)
+                        .map(|(_, view)| view);
+                (path, view)
+            });
+        }
+        let texture_view = resources
+            .texture
+            .as_ref()
+            .and_then(|(_, view)| view.as_ref());
+        let bound = (
+            resources
+                .texture
+                .as_ref()
+                .filter(|(_, view)| view.is_some())
+                .map(|(path, _)| path.clone()),
+            ibl.as_ref().map(|maps| maps.id),
+        );
+        if resources.bound.as_ref() != Some(&bound) {
+            let (irradiance_view, specular_view) = ibl.as_ref().map_or(
+                (&resources.ibl_fallback_view, &resources.ibl_fallback_view),
+                |maps| (&maps.irradiance.1, &maps.specular.1),
+            );
+            resources.bind_group = Some(device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("material_preview_bind_group"),
+                layout: &resources.bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: resources.uniform_buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(&resources.sampler),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::TextureView(
+                            texture_view.unwrap_or(&resources.white_pixel_view),
+                        ),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 3,
+                        resource: resources.light_buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 4,
+                        resource: resources.cluster_buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 5,
+                        resource: resources.light_index_buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 6,
+                        resource: wgpu::BindingResource::TextureView(irradiance_view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 7,
+                        resource: wgpu::BindingResource::TextureView(specular_view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 8,
+                        resource: wgpu::BindingResource::Sampler(&resources.env_sampler),
+                    },
+                ],
+            }));
+            resources.bound = Some(bound);
+        }
+
+        // Câmera fixa olhando a esfera (raio 0.5) de frente; a luz vem de
+        // cima, à direita
+        let eye = Vec3::new(0.0, 0.0, 2.2);
+        let view = Mat4::look_at_rh(eye, Vec3::ZERO, Vec3::Y);
+        let proj = Mat4::perspective_rh(30_f32.to_radians(), self.aspect.max(0.01), 0.1, 10.0);
+        let model = Mat4::from_rotation_y(self.preview.yaw);
+        let light_dir = Vec3::new(0.6, 0.7, 0.8).normalize();
+        let has_texture = if texture_view.is_some() { 1.0 } else { 0.0 };
+        let ibl_intensity = if sky_settings.ibl_enabled && ibl.is_some() {
+            sky_settings.ibl_intensity.max(0.0)
+        } else {
+            0.0
+        };
+        let albedo = self.preview.albedo;
+        // Mesmo layout de `Draw3dCallback::prepare`; a névoa fica zerada
+        let values = (proj * view * model)
+            .to_cols_array()
+            .into_iter()
+            .chain(model.to_cols_array())
+            .chain([eye.x, eye.y, eye.z, 1.0])
+            .chain([light_dir.x, light_dir.y, light_dir.z, 1.0])
+            .chain([1.0, 1.0, 1.0, has_texture])
+            .chain([albedo[0], albedo[1], albedo[2], 1.0])
+            .chain([0.1, 10.0, 0.0, 0.0])
+            .chain([0.0, 0.0, -1.0, 0.0])
+            .chain([
+                ibl_intensity,
+                sky_settings.rotation,
+                self.preview.roughness.clamp(0.0, 1.0),
+                self.preview.metallic.clamp(0.0, 1.0),
+            ]);
+        let mut uniform_data = [0_u8; LIT_UNIFORM_SIZE];
+        for (i, f) in values.enumerate() {
+            push_f32(&mut uniform_data, i * 4, f);
+        }
+        queue.write_buffer(&resources.uniform_buffer, 0, &uniform_data);
+        Vec::new()
+    }
+
+    fn paint(
+        &self,
+        info: egui::PaintCallbackInfo,
+        render_pass: &mut wgpu::RenderPass<'static>,
+        callback_resources: &egui_wgpu::CallbackResources,
+    ) {
+        engine_core::profile_function!(engine_core::ProfileCategory::Render);
+        let Some(resources) = callback_resources.get::<MaterialPreviewResources>() else {
+            return;
+        };
+        let Some(bind_group) = &resources.bind_group else {
+            return;
+        };
+        let ppp = info.pixels_per_point;
+        let v = &info.viewport;
+        let w = (v.width() * ppp).max(1.0);
+        let h = (v.height() * ppp).max(1.0);
+        render_pass.set_viewport(v.min.x * ppp, v.min.y * ppp, w, h, 0.0, 1.0);
+        render_pass.set_pipeline(&resources.pipeline);
+        render_pass.set_bind_group(0, bind_group, &[]);
+        render_pass.set_vertex_buffer(0, resources.vertex_buffer.slice(..));
+        render_pass.set_index_buffer(resources.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+        render_pass.draw_indexed(0..resources.index_count, 0, 0..1);
+    }
+}
+
+impl SkyCallback {
+    fn create_resources(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        shaders: &ShaderLibrary,
+    ) -> SkyResources {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("viewport_sky_shader"),
+            source: wgpu::ShaderSource::Wgsl(shaders.source(ShaderFile::Sky).into()),
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("viewport_sky_bind_layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        // U repete (costura do panorama), V fica preso nos polos
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("viewport_sky_sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::FilterMode::Linear,
+            address_mode_u: wgpu::AddressMode::Repeat,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            ..Default::default()
+        });
+
+        let (_fallback, fallback_view) = create_sky_texture(device, queue, 1, 1, &[0_u8; 8]);
+
+        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("viewport_sky_ubo"),
+            contents: &[0_u8; SKY_UNIFORM_SIZE],
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("viewport_sky_pipeline_layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline =
+            create_sky_pipeline(device, &shader, &pipeline_layout, self.target_format, 1);
+
+        SkyResources {
+            shader_generation: shaders.generation(),
+            pipeline,
+            shader,
+            pipeline_layout,
+            hdr_pipeline: None,
+            uniform_buffer,
+            bind_group_layout,
+            bind_group: None,
+            sampler,
+            fallback_view,
+            map: None,
+            ibl_pipelines: None,
+            ibl: None,
+            next_ibl_id: 1,
+        }
+    }
+}
+
+/// Pipeline do céu; `sample_count` > 1 para o alvo com MSAA
+fn create_sky_pipeline(
+    device: &wgpu::Device,
+    shader: &wgpu::ShaderModule,
+    layout: &wgpu::PipelineLayout,
+    format: wgpu::TextureFormat,
+    sample_count: u32,
+) -> wgpu::RenderPipeline {
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("viewport_sky_pipeline"),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: Some("vs_sky"),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            buffers: &[],
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: Some("fs_sky"),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: None,
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            cull_mode: None,
+            ..Default::default()
+        },
+        // O céu fica atrás de tudo: não escreve nem testa profundidade
+        depth_stencil: Some(wgpu::DepthStencilState {
+            format: wgpu::TextureFormat::Depth24Plus,
+            depth_write_enabled: false,
+            depth_compare: wgpu::CompareFunction::Always,
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        }),
+        multisample: wgpu::MultisampleState {
+            count: sample_count,
+            ..Default::default()
+        },
+        multiview: None,
+        cache: None,
+    })
+}
+
+/// Cria uma textura RGBA16F com os bytes já convertidos
+fn create_sky_texture(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    width: u32,
+    height: u32,
+    rgba16f: &[u8],
+) -> (wgpu::Texture, wgpu::TextureView) {
+    let size = wgpu::Extent3d {
+        width,
+        height,
+        depth_or_array_layers: 1,
+    };
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("viewport_sky_texture"),
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba16Float,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    });
+    queue.write_texture(
+        TexelCopyTextureInfo {
+            texture: &texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        rgba16f,
+        TexelCopyBufferLayout {
+            offset: 0,
+            bytes_per_row: Some(width * 8),
+            rows_per_image: Some(height),
+        },
+        size,
+    );
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    (texture, view)
+}
+
+impl egui_wgpu::CallbackTrait for SkyCallback {
+    fn prepare(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        _screen_descriptor: &egui_wgpu::ScreenDescriptor,
+        _egui_encoder: &mut wgpu::CommandEncoder,
+        callback_resources: &mut egui_wgpu::CallbackResources,
+    ) -> Vec<wgpu::CommandBuffer> {
+        engine_core::profile_function!(engine_core::ProfileCategory::Render);
+        let shaders = self.shaders.lock().expect("shaders lock");
+        let resources = view_entry::<SkyResources>(callback_resources, self.view)
+            .or_insert_with(|| self.create_resources(device, queue, &shaders));
+        // Recriado sem panorama: o mapa e o IBL são refeitos logo abaixo
+        if resources.shader_generation != shaders.generation() {
+            *resources = self.create_resources(device, queue, &shaders);
+        }
+        let sky = self.sky.lock().expect("sky lock");
+        let mut command_buffers = Vec::new();
+
+        // Envia o panorama quando ele muda
+        let uploaded = resources.map.as_ref().map(|(key, _, _)| key);
+        let wanted = sky
+            .map
+            .as_ref()
+            .map(|m| (m.source_path.clone(), sky.map_revision));
+        if uploaded != wanted.as_ref() {
+            resources.map = sky.map.as_ref().map(|map| {
+                let (texture, view) =
+                    create_sky_texture(device, queue, map.width, map.height, &map.rgba16f_bytes());
+                ((map.source_path.clone(), sky.map_revision), texture, view)
+            });
+            resources.bind_group = None;
+
+            // Regenera irradiância e especular pré-filtrado para o novo panorama
+            resources.ibl = None;
+            if let Some((_, _, source)) = &resources.map {
+                let pipelines = resources.ibl_pipelines.get_or_insert_with(|| {
+                    create_ibl_pipelines(device, shaders.source(ShaderFile::Ibl))
+                });
+                let (maps, commands) =
+                    generate_ibl(device, pipelines, source, resources.next_ibl_id);
+                resources.next_ibl_id += 1;
+                resources.ibl = Some(maps);
+                command_buffers.push(commands);
+            }
+        }
+
+        // Uniforms (144 bytes), mesmo layout de SkyUniforms
+        let settings = &sky.settings;
+        let mut data = Vec::with_capacity(SKY_UNIFORM_SIZE);
+        for col in &sky.view_proj_inv {
+            data.extend(col.iter().flat_map(|f| f.to_le_bytes()));
+        }
+        let has_map = if resources.map.is_some() { 1.0 } else { 0.0 };
+        for f in [
+            sky.camera_pos[0],
+            sky.camera_pos[1],
+            sky.camera_pos[2],
+            settings.exposure,
+            settings.rotation,
+            has_map,
+            0.0,
+            0.0,
+        ] {
+            data.extend_from_slice(&f.to_le_bytes());
+        }
+        for color in [
+            settings.zenith_color,
+            settings.horizon_color,
+            settings.ground_color,
+        ] {
+            for f in [color[0], color[1], color[2], 1.0] {
+                data.extend_from_slice(&f.to_le_bytes());
+            }
+        }
+        queue.write_buffer(&resources.uniform_buffer, 0, &data);
+
+        if resources.bind_group.is_none() {
+            let view = resources
+                .map
+                .as_ref()
+                .map_or(&resources.fallback_view, |(_, _, view)| view);
+            resources.bind_group = Some(device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("viewport_sky_bind_group"),
+                layout: &resources.bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: resources.uniform_buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(&resources.sampler),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::TextureView(view),
+                    },
+                ],
+            }));
+        }
+
+        command_buffers
+    }
+
+    fn paint(
+        &self,
+        info: egui::PaintCallbackInfo,
+        render_pass: &mut wgpu::RenderPass<'static>,
+        callback_resources: &egui_wgpu::CallbackResources,
+    ) {
+        engine_core::profile_function!(engine_core::ProfileCategory::Render);
+        let Some(resources) = view_resources::<SkyResources>(callback_resources, self.view) else {
+            return;
+        };
+        if resources.bind_group.is_none() {
+            return;
+        }
+
+        let ppp = info.pixels_per_point;
+        let v = &info.viewport;
+        let x = v.min.x * ppp;
+        let y = v.min.y * ppp;
+        let w = (v.width() * ppp).max(1.0) as u32;
+        let h = (v.height() * ppp).max(1.0) as u32;
+        render_pass.set_viewport(x, y, w as f32, h as f32, 0.0, 1.0);
+        render_pass.set_scissor_rect(x as u32, y as u32, w, h);
+
+        resources.draw(render_pass, None, &self.counters);
+    }
+}
+
+impl SkyResources {
+    /// Cria (uma vez por nº de amostras) o pipeline usado no alvo HDR offscreen
+    fn ensure_hdr_pipeline(&mut self, device: &wgpu::Device, sample_count: u32) {
+        if self.hdr_pipeline.as_ref().map(|p| p.0) != Some(sample_count) {
+            let pipeline = create_sky_pipeline(
+                device,
+                &self.shader,
+                &self.pipeline_layout,
+                HDR_FORMAT,
+                sample_count,
+            );
+            self.hdr_pipeline = Some((sample_count, pipeline));
+        }
+    }
+
+    /// Desenha o céu no pass atual (viewport/scissor já definidos);
+    /// `hdr_samples` seleciona o pipeline do alvo HDR offscreen
+    fn draw(
+        &self,
+        render_pass: &mut wgpu::RenderPass<'_>,
+        hdr_samples: Option<u32>,
+        counters: &RenderCounters,
+    ) {
+        let Some(bind_group) = &self.bind_group else {
+            return;
+        };
+        let pipeline = match (hdr_samples, &self.hdr_pipeline) {
+            (None, _) => &self.pipeline,
+            (Some(wanted), Some((samples, pipeline))) if *samples == wanted => pipeline,
+            _ => return,
+        };
+        render_pass.set_bind_group(0, bind_group, &[]);
+        render_pass.set_pipeline(pipeline);
+        render_pass.draw(0..3, 0..1);
+        counters.record_draw(1);
+    }
+}
+
+fn create_ibl_pipelines(device: &wgpu::Device, source: &str) -> IblPipelines {
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("viewport_ibl_shader"),
+        source: wgpu::ShaderSource::Wgsl(source.into()),
+    });
+    let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("viewport_ibl_bind_layout"),
+        entries: &[
+            wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::COMPUTE,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 1,
+                visibility: wgpu::ShaderStages::COMPUTE,
+                ty: wgpu::BindingType::StorageTexture {
+                    access: wgpu::StorageTextureAccess::WriteOnly,
+                    format: wgpu::TextureFormat::Rgba16Float,
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 2,
+                visibility: wgpu::ShaderStages::COMPUTE,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            },
+        ],
+    });
+    let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("viewport_ibl_pipeline_layout"),
+        bind_group_layouts: &[&bind_group_layout],
+        push_constant_ranges: &[],
+    });
+    let pipeline = |entry_point: &str| {
+        device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some(entry_point),
+            layout: Some(&layout),
+            module: &shader,
+            entry_point: Some(entry_point),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            cache: None,
+        })
+    };
+    IblPipelines {
+        downsample: pipeline("cs_downsample"),
+        irradiance: pipeline("cs_irradiance"),
+        prefilter: pipeline("cs_prefilter"),
+        bind_group_layout,
+    }
+}
+
+fn create_mipmap_pipeline(device: &wgpu::Device, source: &str) -> MipmapPipeline {
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("viewport_mipmap_shader"),
+        source: wgpu::ShaderSource::Wgsl(source.into()),
+    });
+    let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("viewport_mipmap_bind_layout"),
+        entries: &[
+            wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::COMPUTE,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 1,
+                visibility: wgpu::ShaderStages::COMPUTE,
+                ty: wgpu::BindingType::StorageTexture {
+                    access: wgpu::StorageTextureAccess::WriteOnly,
+                    format: wgpu::TextureFormat::Rgba8Unorm,
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                },
+                count: None,
+            },
+        ],
+    });
+    let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("viewport_mipmap_pipeline_layout"),
+        bind_group_layouts: &[&bind_group_layout],
+        push_constant_ranges: &[],
+    });
+    let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+        label: Some("viewport_mipmap_pipeline"),
+        layout: Some(&layout),
+        module: &shader,
+        entry_point: Some("cs_downsample"),
+        compilation_options: wgpu::PipelineCompilationOptions::default(),
+        cache: None,
+    });
+    MipmapPipeline {
+        pipeline,
+        bind_group_layout,
+    }
+}
+
+/// Sampler com o filtro / anisotropia escolhidos para a textura
+fn create_texture_sampler(device: &wgpu::Device, sampling: TextureSampling) -> wgpu::Sampler {
+    let (filter, mipmap_filter) = match sampling.filter {
+        TextureFilter::Point => (wgpu::FilterMode::Nearest, wgpu::FilterMode::Nearest),
+        TextureFilter::Bilinear => (wgpu::FilterMode::Linear, wgpu::FilterMode::Nearest),
+        TextureFilter::Trilinear => (wgpu::FilterMode::Linear, wgpu::FilterMode::Linear),
+    };
+    device.create_sampler(&wgpu::SamplerDescriptor {
+        label: Some("viewport_gpu_texture_sampler"),
+        mag_filter: filter,
+        min_filter: filter,
+        mipmap_filter,
+        address_mode_u: wgpu::AddressMode::ClampToEdge,
+        address_mode_v: wgpu::AddressMode::ClampToEdge,
+        address_mode_w: wgpu::AddressMode::ClampToEdge,
+        anisotropy_clamp: sampling.effective_anisotropy(),
+        ..Default::default()
+    })
+}
+
+/// Sobe a textura e, com mipmaps, gera a cadeia por compute (cada nível
+/// reduz o anterior). O armazenamento é RGBA8 linear porque formatos sRGB não
+/// podem ser storage; o shader amostra por uma view sRGB, ou linear quando a
+/// textura não é cor (`srgb` falso: máscaras, normal maps)
+fn create_sampled_texture(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    mipmap: &MipmapPipeline,
+    label: &str,
+    image: &TextureData,
+    sampling: TextureSampling,
+    srgb: bool,
+) -> (
+    (wgpu::Texture, wgpu::TextureView, wgpu::Sampler),
+    Option<wgpu::CommandBuffer>,
+) {
+    let (width, height) = (image.width, image.height);
+    let mip_level_count = sampling.mip_levels(width, height);
+    let size = wgpu::Extent3d {
+        width,
+        height,
+        depth_or_array_layers: 1,
+    };
+    let mut usage = wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST;
+    if mip_level_count > 1 {
+        usage |= wgpu::TextureUsages::STORAGE_BINDING;
+    }
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some(label),
+        size,
+        mip_level_count,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage,
+        view_formats: &[wgpu::TextureFormat::Rgba8UnormSrgb],
+    });
+    queue.write_texture(
+        TexelCopyTextureInfo {
+            texture: &texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        &image.rgba,
+        TexelCopyBufferLayout {
+            offset: 0,
+            bytes_per_row: Some(width * 4),
+            rows_per_image: Some(height),
+        },
+        size,
+    );
+    let view = texture.create_view(&wgpu::TextureViewDescriptor {
+        format: Some(if srgb {
+            wgpu::TextureFormat::Rgba8UnormSrgb
+        } else {
+            wgpu::TextureFormat::Rgba8Unorm
+        }),
+        ..Default::default()
+    });
+    let sampler = create_texture_sampler(device, sampling);
+    if mip_level_count == 1 {
+        return ((texture, view, sampler), None);
+    }
+
+    let mip_view = |level: u32, format: wgpu::TextureFormat| {
+        texture.create_view(&wgpu::TextureViewDescriptor {
+            format: Some(format),
+            base_mip_level: level,
+            mip_level_count: Some(1),
+            ..Default::default()
+        })
+    };
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("viewport_mipmap_encoder"),
+    });
+    {
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("viewport_mipmap_pass"),
+            timestamp_writes: None,
+        });
+        pass.set_pipeline(&mipmap.pipeline);
+        for level in 1..mip_level_count {
+            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("viewport_mipmap_bind_group"),
+                layout: &mipmap.bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(&mip_view(
+                            level - 1,
+                            wgpu::TextureFormat::Rgba8UnormSrgb,
+                        )),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(&mip_view(
+                            level,
+                            wgpu::TextureFormat::Rgba8Unorm,
+                        )),
+                    },
+                ],
+            });
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(
+                (width >> level).max(1).div_ceil(8),
+                (height >> level).max(1).div_ceil(8),
+                1,
+            );
+        }
+    }
+    ((texture, view, sampler), Some(encoder.finish()))
+}
+
+/// Sobe uma textura HDR/EXR como RGBA16F linear. O compute de mipmaps só
+/// escreve RGBA8, então a cadeia de mips vem pronta da CPU
+fn create_hdr_texture(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    label: &str,
+    pixels: &[f32],
+    (width, height): (u32, u32),
+    sampling: TextureSampling,
+) -> (wgpu::Texture, wgpu::TextureView, wgpu::Sampler) {
+    let mip_level_count = sampling.mip_levels(width, height);
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some(label),
+        size: wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba16Float,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    });
+    let levels = rgba16f_mip_chain(width, height, pixels, mip_level_count);
+    for (level, (w, h, bytes)) in levels.iter().enumerate() {
+        queue.write_texture(
+            TexelCopyTextureInfo {
+                texture: &texture,
+                mip_level: level as u32,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            bytes,
+            TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(w * 8),
+                rows_per_image: Some(*h),
+            },
+            wgpu::Extent3d {
+                width: *w,
+                height: *h,
+                depth_or_array_layers: 1,
+            },
+        );
+    }
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    let sampler = create_texture_sampler(device, sampling);
+    (texture, view, sampler)
+}
+
+/// Textura RGBA16F usada como saída (storage) e depois amostrada no shader de luz
+fn create_ibl_texture(
+    device: &wgpu::Device,
+    label: &str,
+    (width, height): (u32, u32),
+    mip_level_count: u32,
+) -> (wgpu::Texture, wgpu::TextureView) {
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some(label),
+        size: wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba16Float,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::STORAGE_BINDING,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    (texture, view)
+}
+
+/// Reduz o panorama, depois gera irradiância (difusa) e o especular pré-filtrado por mip
+fn generate_ibl(
+    device: &wgpu::Device,
+    pipelines: &IblPipelines,
+    source: &wgpu::TextureView,
+    id: u64,
+) -> (IblMaps, wgpu::CommandBuffer) {
+    let (_base, base_view) = create_ibl_texture(device, "viewport_ibl_base", IBL_BASE_SIZE, 1);
+    let irradiance = create_ibl_texture(device, "viewport_ibl_irradiance", IBL_IRRADIANCE_SIZE, 1);
+    let specular = create_ibl_texture(
+        device,
+        "viewport_ibl_specular",
+        IBL_BASE_SIZE,
+        IBL_SPECULAR_MIPS,
+    );
+
+    // (entrada, saída, expoente do lobo, tamanho, pipeline)
+    let mut passes: Vec<(
+        &wgpu::TextureView,
+        wgpu::TextureView,
+        f32,
+        (u32, u32),
+        &wgpu::ComputePipeline,
+    )> = vec![
+        (
+            source,
+            base_view.clone(),
+            0.0,
+            IBL_BASE_SIZE,
+            &pipelines.downsample,
+        ),
+        (
+            &base_view,
+            irradiance.1.clone(),
+            0.0,
+            IBL_IRRADIANCE_SIZE,
+            &pipelines.irradiance,
+        ),
+    ];
+    for level in 0..IBL_SPECULAR_MIPS {
+        let mip_view = specular.0.create_view(&wgpu::TextureViewDescriptor {
+            base_mip_level: level,
+            mip_level_count: Some(1),
+            ..Default::default()
+        });
+        passes.push((
+            &base_view,
+            mip_view,
+            ibl_prefilter_exponent(level),
+            (
+                (IBL_BASE_SIZE.0 >> level).max(1),
+                (IBL_BASE_SIZE.1 >> level).max(1),
+            ),
+            &pipelines.prefilter,
+        ));
+    }
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("viewport_ibl_encoder"),
+    });
+    {
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("viewport_ibl_pass"),
+            timestamp_writes: None,
+        });
+        for (input, output, exponent, (width, height), pipeline) in &passes {
+            let mut params = [0_u8; 16];
+            push_f32(&mut params, 0, *exponent);
+            let params_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("viewport_ibl_params"),
+                contents: &params,
+                usage: wgpu::BufferUsages::UNIFORM,
+            });
+            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("viewport_ibl_bind_group"),
+                layout: &pipelines.bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(input),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(output),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: params_buffer.as_entire_binding(),
+                    },
+                ],
+            });
+            pass.set_pipeline(pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(width.div_ceil(8), height.div_ceil(8), 1);
+        }
+    }
+
+    (
+        IblMaps {
+            id,
+            irradiance,
+            specular,
+        },
+        encoder.finish(),
+    )
+}
+
+impl ScenePostCallback {
+    fn create_resources(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        shaders: &ShaderLibrary,
+    ) -> PostResources {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("viewport_post_shader"),
+            source: wgpu::ShaderSource::Wgsl(shaders.source(ShaderFile::Post).into()),
+        });
+
+        let texture_entry = |binding: u32| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("viewport_post_bind_layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                texture_entry(2),
+                texture_entry(3),
+                texture_entry(4),
+                storage_layout_entry(5),
+            ],
+        });
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("viewport_post_pipeline_layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = |label: &str,
+                        entry_point: &str,
+                        format: wgpu::TextureFormat,
+                        depth_stencil: Option<wgpu::DepthStencilState>| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some("vs_post"),
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                    buffers: &[],
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some(entry_point),
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format,
+                        blend: None,
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                }),
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleList,
+                    cull_mode: None,
+                    ..Default::default()
+                },
+                depth_stencil,
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+                cache: None,
+            })
+        };
+        let bright_pipeline = pipeline(
+            "viewport_post_bright_pipeline",
+            "fs_bright",
+            HDR_FORMAT,
+            None,
+        );
+        let blur_pipeline = pipeline("viewport_post_blur_pipeline", "fs_blur", HDR_FORMAT, None);
+        // A composição (ou o FXAA) é desenhada no pass do egui, como o céu
+        let egui_depth = wgpu::DepthStencilState {
+            format: wgpu::TextureFormat::Depth24Plus,
+            depth_write_enabled: false,
+            depth_compare: wgpu::CompareFunction::Always,
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        };
+        let composite_pipeline = pipeline(
+            "viewport_post_composite_pipeline",
+            "fs_composite",
+            self.target_format,
+            Some(egui_depth.clone()),
+        );
+        let composite_ldr_pipeline = pipeline(
+            "viewport_post_composite_ldr_pipeline",
+            "fs_composite",
+            self.target_format,
+            None,
+        );
+        let fxaa_pipeline = pipeline(
+            "viewport_post_fxaa_pipeline",
+            "fs_fxaa",
+            self.target_format,
+            Some(egui_depth),
+        );
+        let fxaa_capture_pipeline = pipeline(
+            "viewport_capture_fxaa_pipeline",
+            "fs_fxaa",
+            self.target_format,
+            None,
+        );
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("viewport_post_sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            ..Default::default()
+        });
+        let uniform_buffers = std::array::from_fn(|_| {
+            device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("viewport_post_ubo"),
+                contents: &[0_u8; POST_UNIFORM_SIZE],
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            })
+        });
+        let (_black, black_view) = create_sky_texture(device, queue, 1, 1, &[0_u8; 8]);
+
+        let exposure_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("viewport_exposure_shader"),
+            source: wgpu::ShaderSource::Wgsl(shaders.source(ShaderFile::Exposure).into()),
+        });
+        let exposure_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("viewport_exposure_bind_layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Storage { read_only: false },
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                ],
+            });
+        let exposure_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("viewport_exposure_pipeline_layout"),
+            bind_group_layouts: &[&exposure_bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let exposure_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("viewport_exposure_pipeline"),
+            layout: Some(&exposure_layout),
+            module: &exposure_shader,
+            entry_point: Some("cs_exposure"),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            cache: None,
+        });
+        // Exposição 0 = ainda não medida (o primeiro frame usa o valor alvo direto)
+        let exposure_buffer = create_storage_buffer(device, "viewport_exposure_state", &[0_u8; 16]);
+
+        PostResources {
+            shader_generation: shaders.generation(),
+            bright_pipeline,
+            blur_pipeline,
+            composite_pipeline,
+            composite_ldr_pipeline,
+            fxaa_pipeline,
+            fxaa_capture_pipeline,
+            bind_group_layout,
+            exposure_pipeline,
+            exposure_bind_group_layout,
+            exposure_buffer,
+            exposure_bind_group: None,
+            last_frame: None,
+            sampler,
+            uniform_buffers,
+            black_view,
+            targets: None,
+            lut: None,
+            bind_groups: None,
+            fxaa: false,
+            water: None,
+            capture: None,
+            video_frames: Vec::new(),
+        }
+    }
+}
+
+/// Alvos offscreen da cena (cor HDR + profundidade, com MSAA se `sample_count` > 1),
+/// os dois alvos do bloom e o alvo da composição (`ldr_format`) lido pelo FXAA
+fn create_post_targets(
+    device: &wgpu::Device,
+    ldr_format: wgpu::TextureFormat,
+    [width, height]: [u32; 2],
+    sample_count: u32,
+) -> PostTargets {
+    let target = |label: &str, size: [u32; 2], format: wgpu::TextureFormat, samples: u32| {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(label),
+            size: wgpu::Extent3d {
+                width: size[0],
+                height: size[1],
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: samples,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            // A água lê a profundidade (multiamostrada com MSAA) e copia a cor
+            usage: if samples > 1 || format.is_depth_stencil_format() {
+                wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING
+            } else {
+                wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::TEXTURE_BINDING
+                    | wgpu::TextureUsages::COPY_SRC
+            },
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        (texture, view)
+    };
+    let bloom_size = [
+        (width / BLOOM_DOWNSCALE).max(1),
+        (height / BLOOM_DOWNSCALE).max(1),
+    ];
+    let size = [width, height];
+    PostTargets {
+        size,
+        sample_count,
+        bloom_size,
+        color: target("viewport_post_color", size, HDR_FORMAT, 1),
+        msaa_color: (sample_count > 1)
+            .then(|| target("viewport_post_color_msaa", size, HDR_FORMAT, sample_count)),
+        depth: target(
+            "viewport_post_depth",
+            size,
+            wgpu::TextureFormat::Depth24Plus,
+            sample_count,
+        ),
+        bloom: [
+            target("viewport_post_bloom_a", bloom_size, HDR_FORMAT, 1),
+            target("viewport_post_bloom_b", bloom_size, HDR_FORMAT, 1),
+        ],
+        ldr: target("viewport_post_ldr", size, ldr_format, 1),
+    }
+}
+
+/// Faixa de LUT (RGBA8 sem conversão sRGB: os valores já são de exibição)
+fn create_lut_texture(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    lut: &TextureData,
+) -> (wgpu::Texture, wgpu::TextureView) {
+    let size = wgpu::Extent3d {
+        width: lut.width,
+        height: lut.height,
+        depth_or_array_layers: 1,
+    };
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("viewport_post_lut"),
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    });
+    queue.write_texture(
+        TexelCopyTextureInfo {
+            texture: &texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        &lut.rgba,
+        TexelCopyBufferLayout {
+            offset: 0,
+            bytes_per_row: Some(lut.width * 4),
+            rows_per_image: Some(lut.height),
+        },
+        size,
+    );
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    (texture, view)
+}
+
+/// Pass de tela cheia de um dos estágios do pós-processamento
+fn post_fullscreen_pass(
+    encoder: &mut wgpu::CommandEncoder,
+    label: &str,
+    target: &wgpu::TextureView,
+    pipeline: &wgpu::RenderPipeline,
+    bind_group: &wgpu::BindGroup,
+) {
+    let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some(label),
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view: target,
+            depth_slice: None,
+            resolve_target: None,
+            ops: wgpu::Operations {
+                load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                store: wgpu::StoreOp::Store,
+            },
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    });
+    pass.set_pipeline(pipeline);
+    pass.set_bind_group(0, bind_group, &[]);
+    pass.draw(0..3, 0..1);
+}
+
+/// Avança uma leitura: Some(true) = pixels prontos, Some(false) = falhou,
+/// None = ainda na GPU
+fn poll_readback(device: &wgpu::Device, readback: &mut CaptureReadback) -> Option<bool> {
+    match std::mem::replace(&mut readback.state, PickReadback::Idle) {
+        PickReadback::Idle => None,
+        PickReadback::Copied => {
+            let status = Arc::new(Mutex::new(None));
+            let callback_status = status.clone();
+            readback
+                .buffer
+                .slice(..)
+                .map_async(wgpu::MapMode::Read, move |result| {
+                    *callback_status.lock().expect("capture lock") = Some(result.is_ok());
+                });
+            readback.state = PickReadback::Mapping(status);
+            None
+        }
+        PickReadback::Mapping(status) => {
+            let _ = device.poll(wgpu::PollType::Poll);
+            let mapped = *status.lock().expect("capture lock");
+            if mapped.is_none() {
+                readback.state = PickReadback::Mapping(status);
+            }
+            mapped
+        }
+    }
+}
+
+/// Avança as leituras em andamento. A captura, quando pronta, é salva numa
+/// thread (PNG/EXR grandes levam centenas de ms); os frames do vídeo vão para a
+/// fila do encoder
+fn capture_step(
+    device: &wgpu::Device,
+    resources: &mut PostResources,
+    post: &mut PostState,
+    shared: &Arc<Mutex<PostState>>,
+) {
+    if let Some(capture) = &mut resources.capture {
+        match poll_readback(device, capture) {
+            Some(true) => {
+                let pixels = capture_pixels(capture);
+                capture.buffer.unmap();
+                if let CaptureKind::Still(request) = &capture.kind {
+                    let request = request.clone();
+                    let shared = shared.clone();
+                    std::thread::spawn(move || {
+                        let result = save_capture(&request, pixels);
+                        finish_capture(&shared, &request, result);
+                    });
+                }
+                resources.capture = None;
+            }
+            Some(false) => {
+                resources.capture = None;
+                post.capture_in_flight = false;
+                post.capture_results
+                    .push(Err("Falha ao ler a captura da GPU".to_string()));
             }
+            None => {}
+        }
+    }
+
+    let mut index = 0;
+    while index < resources.video_frames.len() {
+        let Some(ok) = poll_readback(device, &mut resources.video_frames[index]) else {
+            index += 1;
+            continue;
+        };
+        let frame = resources.video_frames.remove(index);
+        if !ok {
+            continue;
+        }
+        let pixels = capture_pixels(&frame);
+        frame.buffer.unmap();
+        // Frames que chegam depois do fim da gravação são descartados
+        if let (Some(recording), CapturePixels::Rgba8(rgba), CaptureKind::Video(time)) =
+            (&mut post.recording, pixels, frame.kind)
+        {
+            recording.recorder.push_frame(rgba, time);
+        }
+    }
+}
+
+/// Pixels RGBA contíguos a partir do buffer mapeado (sem o padding das linhas)
+fn capture_pixels(capture: &CaptureReadback) -> CapturePixels {
+    let data = capture.buffer.slice(..).get_mapped_range();
+    let row = (capture.size[0] * capture.pixel_size) as usize;
+    let rows = data
+        .chunks_exact(capture.padded_row as usize)
+        .take(capture.size[1] as usize)
+        .map(|line| &line[..row]);
+    if capture.pixel_size == 8 {
+        // Rgba16Float: a EXR guarda a cor HDR linear
+        CapturePixels::RgbaF32(
+            rows.flat_map(|line| line.chunks_exact(2))
+                .map(|b| f16_to_f32(u16::from_le_bytes([b[0], b[1]])))
+                .collect(),
+        )
+    } else {
+        let mut pixels: Vec<u8> = rows.flatten().copied().collect();
+        if capture.bgra {
+            for pixel in pixels.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+        }
+        CapturePixels::Rgba8(pixels)
+    }
+}
+
+fn finish_capture(
+    shared: &Arc<Mutex<PostState>>,
+    request: &CaptureRequest,
+    result: Result<(), String>,
+) {
+    let mut post = shared.lock().expect("post lock");
+    post.capture_in_flight = false;
+    post.capture_results
+        .push(result.map(|()| request.path.clone()));
+}
+
+/// Finaliza o arquivo do vídeo numa thread (o ffmpeg ainda codifica a fila)
+fn finish_recording(shared: &Arc<Mutex<PostState>>, recording: Recording) {
+    let shared = shared.clone();
+    std::thread::spawn(move || {
+        let dropped = recording.recorder.dropped_frames();
+        let result = recording.recorder.finish();
+        if dropped > 0 {
+            log::warn!("[CAPTURA] {dropped} frames descartados na gravação");
+        }
+        shared
+            .lock()
+            .expect("post lock")
+            .capture_results
+            .push(result);
+    });
+}
+
+/// Desenha a imagem final (ou usa a cor HDR, para EXR) e copia para um buffer
+/// de leitura. A imagem final passa pela mesma composição/FXAA da viewport
+fn encode_capture(
+    device: &wgpu::Device,
+    encoder: &mut wgpu::CommandEncoder,
+    resources: &PostResources,
+    targets: &PostTargets,
+    bind_groups: &[wgpu::BindGroup; 5],
+    target_format: wgpu::TextureFormat,
+    kind: CaptureKind,
+) -> Result<CaptureReadback, (CaptureKind, String)> {
+    let size = targets.size;
+    let hdr = matches!(&kind, CaptureKind::Still(request) if request.format == CaptureFormat::Exr);
+    if matches!(&kind, CaptureKind::Still(request) if request.size() != size) {
+        return Err((kind, "Alvo da captura com tamanho inesperado".to_string()));
+    }
+    let (texture, pixel_size, bgra) = if hdr {
+        (targets.color.0.clone(), 8, false)
+    } else {
+        let bgra = match target_format {
+            wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb => false,
+            wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb => true,
+            other => {
+                return Err((
+                    kind,
+                    format!("Formato da viewport sem suporte a captura: {other:?}"),
+                ));
+            }
+        };
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("viewport_capture"),
+            size: wgpu::Extent3d {
+                width: size[0],
+                height: size[1],
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: target_format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        if resources.fxaa {
+            post_fullscreen_pass(
+                encoder,
+                "viewport_capture_fxaa_pass",
+                &view,
+                &resources.fxaa_capture_pipeline,
+                &bind_groups[4],
+            );
+        } else {
+            post_fullscreen_pass(
+                encoder,
+                "viewport_capture_composite_pass",
+                &view,
+                &resources.composite_ldr_pipeline,
+                &bind_groups[3],
+            );
+        }
+        (texture, 4, bgra)
+    };
+    let padded_row = (size[0] * pixel_size).next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("viewport_capture_readback"),
+        size: padded_row as u64 * size[1] as u64,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    encoder.copy_texture_to_buffer(
+        TexelCopyTextureInfo {
+            texture: &texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        wgpu::TexelCopyBufferInfo {
+            buffer: &buffer,
+            layout: TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_row),
+                rows_per_image: Some(size[1]),
+            },
+        },
+        wgpu::Extent3d {
+            width: size[0],
+            height: size[1],
+            depth_or_array_layers: 1,
+        },
+    );
+    Ok(CaptureReadback {
+        kind,
+        size,
+        buffer,
+        padded_row,
+        pixel_size,
+        bgra,
+        state: PickReadback::Copied,
+    })
+}
+
+/// Compila `source` num escopo de validação: o erro volta como texto em vez de
+/// ir para o handler de erros não capturados (que encerra o editor)
+fn validate_wgsl(device: &wgpu::Device, label: &str, source: &str) -> Result<(), String> {
+    device.push_error_scope(wgpu::ErrorFilter::Validation);
+    let _module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some(label),
+        source: wgpu::ShaderSource::Wgsl(source.into()),
+    });
+    // No wgpu nativo o escopo já está resolvido quando é removido
+    let error = std::pin::pin!(device.pop_error_scope());
+    match error.poll(&mut std::task::Context::from_waker(std::task::Waker::noop())) {
+        std::task::Poll::Ready(Some(error)) => Err(error.to_string()),
+        _ => Ok(()),
+    }
+}
+
+/// Sincroniza o pipeline da água (por nº de amostras da profundidade), a cópia
+/// da cor da cena e os uniforms / normal maps de cada superfície
+fn water_step(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    water: &mut Option<WaterResources>,
+    uploads: &[WaterUpload],
+    size: [u32; 2],
+    depth_samples: u32,
+    source: &str,
+) {
+    if uploads.is_empty() {
+        if let Some(water) = water {
+            water.surfaces.clear();
+        }
+        return;
+    }
+    if water
+        .as_ref()
+        .is_none_or(|water| water.depth_samples != depth_samples)
+    {
+        *water = Some(create_water_resources(device, queue, depth_samples, source));
+    }
+    let Some(water) = water.as_mut() else {
+        return;
+    };
+    if water
+        .scene_copy
+        .as_ref()
+        .is_none_or(|(copy_size, ..)| *copy_size != size)
+    {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("viewport_water_scene_copy"),
+            size: wgpu::Extent3d {
+                width: size[0],
+                height: size[1],
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: HDR_FORMAT,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        water.scene_copy = Some((size, texture, view));
+    }
+    water
+        .surfaces
+        .retain(|name, _| uploads.iter().any(|upload| &upload.name == name));
+    for upload in uploads {
+        let buffers = water
+            .surfaces
+            .entry(upload.name.clone())
+            .or_insert_with(|| WaterBuffers {
+                normal_map_path: None,
+                normal_map: None,
+                uniform_buffer: device.create_buffer(&wgpu::BufferDescriptor {
+                    label: Some("viewport_water_ubo"),
+                    size: WATER_UNIFORM_SIZE as u64,
+                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                }),
+            });
+        if buffers.normal_map_path != upload.normal_map {
+            // Normal map guarda vetores, não cor: sem sRGB
+            buffers.normal_map = upload.normal_map.as_deref().and_then(|path| {
+                load_image_texture(device, queue, path, wgpu::TextureFormat::Rgba8Unorm)
+            });
+            buffers.normal_map_path = upload.normal_map.clone();
+        }
+        queue.write_buffer(&buffers.uniform_buffer, 0, &upload.uniforms);
+    }
+}
 
-            if resources.textures.get(texture_path_str).is_none() {
-                // Normaliza o path para abrir o arquivo (remove \\?\ se existir)
-                let disk_path = normalize_path(texture_path_str);
-                let path = PathBuf::from(&disk_path);
-                match image::open(&path) {
-                    Ok(img) => {
-                        let rgba = img.to_rgba8();
-                        let (width, height) = rgba.dimensions();
-                        let size = wgpu::Extent3d {
-                            width,
-                            height,
-                            depth_or_array_layers: 1,
-                        };
-                        let new_texture = device.create_texture(&wgpu::TextureDescriptor {
-                            label: Some(&format!("viewport_gpu_texture_{}", texture_path_str)),
-                            size,
-                            mip_level_count: 1,
-                            sample_count: 1,
-                            dimension: wgpu::TextureDimension::D2,
-                            format: wgpu::TextureFormat::Rgba8UnormSrgb,
-                            usage: wgpu::TextureUsages::TEXTURE_BINDING
-                                | wgpu::TextureUsages::COPY_DST,
-                            view_formats: &[],
-                        });
-                        queue.write_texture(
-                            TexelCopyTextureInfo {
-                                texture: &new_texture,
-                                mip_level: 0,
-                                origin: wgpu::Origin3d::ZERO,
-                                aspect: wgpu::TextureAspect::All,
-                            },
-                            &rgba,
-                            TexelCopyBufferLayout {
-                                offset: 0,
-                                bytes_per_row: Some(width * 4),
-                                rows_per_image: Some(height),
-                            },
-                            size,
-                        );
-                        let new_texture_view =
-                            new_texture.create_view(&wgpu::TextureViewDescriptor::default());
-                        resources.textures.insert(
-                            texture_path_str.clone(),
-                            (
-                                new_texture,
-                                new_texture_view,
-                                resources.white_pixel_texture.2.clone(),
-                            ),
-                        );
-                        // Invalida bind group para recriar com a nova textura
-                        resources.current_bind_group = None;
-                    }
-                    Err(_e) => {
-                        resources.textures.insert(
-                            texture_path_str.clone(),
-                            resources.white_pixel_texture.clone(),
-                        );
-                        // Invalida bind group mesmo em caso de erro (usa white pixel)
-                        resources.current_bind_group = None;
-                    }
-                }
-            }
-            if resources.textures.get(texture_path_str).is_some() {
-                has_texture = 1.0;
-            }
+fn create_water_resources(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    depth_samples: u32,
+    source: &str,
+) -> WaterResources {
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("viewport_water_shader"),
+        source: wgpu::ShaderSource::Wgsl(water_shader_source(source, depth_samples).into()),
+    });
+    let uniform_entry = |binding, visibility| wgpu::BindGroupLayoutEntry {
+        binding,
+        visibility,
+        ty: wgpu::BindingType::Buffer {
+            ty: wgpu::BufferBindingType::Uniform,
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+        count: None,
+    };
+    let sampler_entry = |binding| wgpu::BindGroupLayoutEntry {
+        binding,
+        visibility: wgpu::ShaderStages::FRAGMENT,
+        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+        count: None,
+    };
+    let texture_entry = |binding| wgpu::BindGroupLayoutEntry {
+        binding,
+        visibility: wgpu::ShaderStages::FRAGMENT,
+        ty: wgpu::BindingType::Texture {
+            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+            view_dimension: wgpu::TextureViewDimension::D2,
+            multisampled: false,
+        },
+        count: None,
+    };
+    let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("viewport_water_layout"),
+        entries: &[
+            uniform_entry(0, wgpu::ShaderStages::VERTEX_FRAGMENT),
+            uniform_entry(1, wgpu::ShaderStages::FRAGMENT),
+            sampler_entry(2),
+            sampler_entry(3),
+            texture_entry(4),
+            texture_entry(5),
+            wgpu::BindGroupLayoutEntry {
+                binding: 6,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Depth,
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: depth_samples > 1,
+                },
+                count: None,
+            },
+            texture_entry(7),
+        ],
+    });
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("viewport_water_pipeline_layout"),
+        bind_group_layouts: &[&bind_group_layout],
+        push_constant_ranges: &[],
+    });
+    // Sem profundidade: o fragment shader testa contra a profundidade da cena
+    let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("viewport_water_pipeline"),
+        layout: Some(&pipeline_layout),
+        vertex: wgpu::VertexState {
+            module: &shader,
+            entry_point: Some("vs_water"),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            buffers: &[],
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: &shader,
+            entry_point: Some("fs_water"),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            targets: &[Some(wgpu::ColorTargetState {
+                format: HDR_FORMAT,
+                blend: None,
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            cull_mode: None,
+            ..Default::default()
+        },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        multiview: None,
+        cache: None,
+    });
+    let sampler = |label, address_mode| {
+        device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some(label),
+            address_mode_u: address_mode,
+            address_mode_v: address_mode,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        })
+    };
+
+    let flat_normal = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("viewport_water_flat_normal"),
+        size: wgpu::Extent3d {
+            width: 1,
+            height: 1,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    });
+    queue.write_texture(
+        flat_normal.as_image_copy(),
+        &[128, 128, 255, 255],
+        TexelCopyBufferLayout {
+            offset: 0,
+            bytes_per_row: Some(4),
+            rows_per_image: Some(1),
+        },
+        wgpu::Extent3d {
+            width: 1,
+            height: 1,
+            depth_or_array_layers: 1,
+        },
+    );
+    let flat_view = flat_normal.create_view(&wgpu::TextureViewDescriptor::default());
+
+    WaterResources {
+        depth_samples,
+        bind_group_layout,
+        pipeline,
+        repeat_sampler: sampler("viewport_water_repeat_sampler", wgpu::AddressMode::Repeat),
+        clamp_sampler: sampler(
+            "viewport_water_clamp_sampler",
+            wgpu::AddressMode::ClampToEdge,
+        ),
+        flat_normal: (flat_normal, flat_view),
+        scene_copy: None,
+        surfaces: HashMap::new(),
+    }
+}
+
+/// Copia a cor da cena (fonte da refração e do reflexo) e desenha a água por cima
+fn encode_water(
+    device: &wgpu::Device,
+    encoder: &mut wgpu::CommandEncoder,
+    water: &WaterResources,
+    targets: &PostTargets,
+    sky: &SkyResources,
+    counters: &RenderCounters,
+) {
+    let Some((size, copy, copy_view)) = &water.scene_copy else {
+        return;
+    };
+    if *size != targets.size {
+        return;
+    }
+    encoder.copy_texture_to_texture(
+        targets.color.0.as_image_copy(),
+        copy.as_image_copy(),
+        wgpu::Extent3d {
+            width: size[0],
+            height: size[1],
+            depth_or_array_layers: 1,
+        },
+    );
+    let sky_view = sky.map.as_ref().map_or(&sky.fallback_view, |map| &map.2);
+    let bind_groups: Vec<wgpu::BindGroup> = water
+        .surfaces
+        .values()
+        .map(|surface| {
+            let normal_view = surface
+                .normal_map
+                .as_ref()
+                .map_or(&water.flat_normal.1, |(_, view)| view);
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("viewport_water_bind_group"),
+                layout: &water.bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: surface.uniform_buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: sky.uniform_buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::Sampler(&water.repeat_sampler),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 3,
+                        resource: wgpu::BindingResource::Sampler(&water.clamp_sampler),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 4,
+                        resource: wgpu::BindingResource::TextureView(sky_view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 5,
+                        resource: wgpu::BindingResource::TextureView(copy_view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 6,
+                        resource: wgpu::BindingResource::TextureView(&targets.depth.1),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 7,
+                        resource: wgpu::BindingResource::TextureView(normal_view),
+                    },
+                ],
+            })
+        })
+        .collect();
+
+    let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some("viewport_water_pass"),
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view: &targets.color.1,
+            depth_slice: None,
+            resolve_target: None,
+            ops: wgpu::Operations {
+                load: wgpu::LoadOp::Load,
+                store: wgpu::StoreOp::Store,
+            },
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    });
+    pass.set_pipeline(&water.pipeline);
+    for bind_group in &bind_groups {
+        pass.set_bind_group(0, bind_group, &[]);
+        pass.draw(0..6, 0..1);
+        counters.record_draw(2);
+    }
+}
+
+impl egui_wgpu::CallbackTrait for ScenePostCallback {
+    fn prepare(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        screen_descriptor: &egui_wgpu::ScreenDescriptor,
+        egui_encoder: &mut wgpu::CommandEncoder,
+        callback_resources: &mut egui_wgpu::CallbackResources,
+    ) -> Vec<wgpu::CommandBuffer> {
+        engine_core::profile_function!(engine_core::ProfileCategory::Render);
+        // Céu e cena preparam os próprios recursos como nos callbacks diretos
+        let mut command_buffers = egui_wgpu::CallbackTrait::prepare(
+            &self.sky,
+            device,
+            queue,
+            screen_descriptor,
+            egui_encoder,
+            callback_resources,
+        );
+        if let Some(scene) = &self.scene {
+            command_buffers.extend(egui_wgpu::CallbackTrait::prepare(
+                scene,
+                device,
+                queue,
+                screen_descriptor,
+                egui_encoder,
+                callback_resources,
+            ));
         }
 
-        // Preenche uniform buffer (192 bytes)
-        // Layout do shader:
-        //   0..64   mvp (mat4)
-        //  64..128  model (mat4)
-        // 128..140  camera_pos (vec3)
-        // 140..144  light_intensity (f32)
-        // 144..156  light_dir (vec3)
-        // 156..160  light_enabled (f32)
-        // 160..172  light_color (vec3)
-        // 172..176  has_texture (f32)
-        // 176..192  tint (vec4)
-        let mut offs = 0usize;
-        for col in &scene.mvp {
-            for f in col {
-                push_f32(&mut resources.uniform_data, offs, *f);
-                offs += 4;
-            }
+        let mut post = self.post.lock().expect("post lock");
+        let sample_count = post.anti_aliasing.sample_count();
+        if let Some(sky) = view_resources_mut::<SkyResources>(callback_resources, self.view) {
+            sky.ensure_hdr_pipeline(device, sample_count);
         }
-        for col in &scene.model {
-            for f in col {
-                push_f32(&mut resources.uniform_data, offs, *f);
-                offs += 4;
-            }
+        if let Some(scene) = view_resources_mut::<GpuResources>(callback_resources, self.view)
+            .filter(|_| self.scene.is_some())
+        {
+            scene.ensure_hdr_pipeline(device, sample_count);
         }
-        // camera_pos (128..140)
-        push_f32(&mut resources.uniform_data, offs, scene.camera_pos[0]);
-        offs += 4;
-        push_f32(&mut resources.uniform_data, offs, scene.camera_pos[1]);
-        offs += 4;
-        push_f32(&mut resources.uniform_data, offs, scene.camera_pos[2]);
-        offs += 4;
-        // light_intensity (140..144)
-        push_f32(&mut resources.uniform_data, offs, scene.light_intensity);
-        offs += 4;
-        // light_dir (144..156)
-        push_f32(&mut resources.uniform_data, offs, scene.light_dir[0]);
-        offs += 4;
-        push_f32(&mut resources.uniform_data, offs, scene.light_dir[1]);
-        offs += 4;
-        push_f32(&mut resources.uniform_data, offs, scene.light_dir[2]);
-        offs += 4;
-        // light_enabled (156..160)
-        push_f32(&mut resources.uniform_data, offs, scene.light_enabled);
-        offs += 4;
-        // light_color (160..172)
-        push_f32(&mut resources.uniform_data, offs, scene.light_color[0]);
-        offs += 4;
-        push_f32(&mut resources.uniform_data, offs, scene.light_color[1]);
-        offs += 4;
-        push_f32(&mut resources.uniform_data, offs, scene.light_color[2]);
-        offs += 4;
-        // has_texture (172..176)
-        push_f32(&mut resources.uniform_data, offs, has_texture);
-        offs += 4;
-        // tint (176..192)
-        push_f32(&mut resources.uniform_data, offs, 1.0); // R
-        offs += 4;
-        push_f32(&mut resources.uniform_data, offs, 1.0); // G
-        offs += 4;
-        push_f32(&mut resources.uniform_data, offs, 1.0); // B
-        offs += 4;
-        push_f32(&mut resources.uniform_data, offs, 1.0); // A
-        offs += 4;
-        let _ = offs;
 
-        queue.write_buffer(&resources.uniform_buffer, 0, &resources.uniform_data);
+        let shaders = self.shaders.lock().expect("shaders lock");
+        let resources = view_entry::<PostResources>(callback_resources, self.view)
+            .or_insert_with(|| self.create_resources(device, queue, &shaders));
+        if resources.shader_generation != shaders.generation() {
+            *resources = self.create_resources(device, queue, &shaders);
+        }
 
-        // Atualiza bind group se necessário
-        let bind_group_needed = resources.current_bind_group.is_none()
-            || resources.current_texture_path.as_ref().map(|s| s.as_str())
-                != current_mesh_texture_path.as_ref().map(|s| s.as_str());
-        if bind_group_needed {
-            let (_tex, tex_view, tex_sampler) = if let Some(path) = &resources.current_texture_path
-            {
-                resources
-                    .textures
-                    .get(path)
-                    .unwrap_or(&resources.white_pixel_texture)
-            } else {
-                &resources.white_pixel_texture
-            };
+        capture_step(device, resources, &mut post, &self.post);
+        // Um pedido por vez; o frame da captura usa alvos no tamanho pedido
+        let capture = if resources.capture.is_none() {
+            post.capture_request.take()
+        } else {
+            None
+        };
+        // Frames do vídeo no ritmo de VIDEO_FPS, com poucas leituras em voo
+        let video_frame = if capture.is_none() && resources.video_frames.len() < MAX_VIDEO_READBACKS
+        {
+            post.recording
+                .as_mut()
+                .and_then(|recording| recording.frame_due(Instant::now()))
+        } else {
+            None
+        };
+        let size = capture
+            .as_ref()
+            .map(CaptureRequest::size)
+            .or_else(|| post.recording.as_ref().map(|r| r.recorder.size()))
+            .unwrap_or(post.size);
+        if resources
+            .targets
+            .as_ref()
+            .is_none_or(|targets| targets.size != size || targets.sample_count != sample_count)
+        {
+            resources.targets = Some(create_post_targets(
+                device,
+                self.target_format,
+                [size[0].max(1), size[1].max(1)],
+                sample_count,
+            ));
+            resources.bind_groups = None;
+            resources.exposure_bind_group = None;
+        }
 
-            resources.current_bind_group =
-                Some(device.create_bind_group(&wgpu::BindGroupDescriptor {
-                    label: Some("viewport_gpu_bind_group"),
+        let lut_changed = match (&resources.lut, &post.lut) {
+            (Some((uploaded, ..)), Some(wanted)) => !Arc::ptr_eq(uploaded, wanted),
+            (None, None) => false,
+            _ => true,
+        };
+        if lut_changed {
+            resources.lut = post.lut.as_ref().and_then(|data| {
+                let size = lut_strip_size(data.width, data.height)?;
+                let (texture, view) = create_lut_texture(device, queue, data);
+                Some((data.clone(), texture, view, size))
+            });
+            resources.bind_groups = None;
+        }
+
+        let waters = self
+            .scene
+            .as_ref()
+            .map(|scene| scene.scene.lock().expect("scene lock").waters.clone())
+            .unwrap_or_default();
+        let copy_size = resources.targets.as_ref().map_or([1, 1], |t| t.size);
+        water_step(
+            device,
+            queue,
+            &mut resources.water,
+            &waters,
+            copy_size,
+            sample_count,
+            shaders.source(ShaderFile::Water),
+        );
+        drop(shaders);
+
+        let Some(targets) = &resources.targets else {
+            if capture.is_some() {
+                post.capture_in_flight = false;
+                post.capture_results
+                    .push(Err("Viewport sem alvo offscreen".to_string()));
+            }
+            return command_buffers;
+        };
+        let lut_size = resources.lut.as_ref().map_or(0, |lut| lut.3);
+        // Intervalo entre frames para a adaptação da exposição (limitado: o egui
+        // só repinta quando necessário)
+        let now = Instant::now();
+        let delta_time = resources
+            .last_frame
+            .replace(now)
+            .map_or(0.0, |last| now.duration_since(last).as_secs_f32().min(0.1));
+        let texel = [
+            1.0 / targets.bloom_size[0] as f32,
+            1.0 / targets.bloom_size[1] as f32,
+        ];
+        for (buffer, blur_step) in
+            resources
+                .uniform_buffers
+                .iter()
+                .zip([[0.0, 0.0], [texel[0], 0.0], [0.0, texel[1]]])
+        {
+            queue.write_buffer(
+                buffer,
+                0,
+                &post_uniform_bytes(&post.chain, lut_size, blur_step, delta_time),
+            );
+        }
+
+        if resources.bind_groups.is_none() {
+            let lut_view = resources
+                .lut
+                .as_ref()
+                .map_or(&resources.black_view, |lut| &lut.2);
+            // (uniforms, fonte, bloom, LUT)
+            let sources = [
+                (
+                    0,
+                    &targets.color.1,
+                    &resources.black_view,
+                    &resources.black_view,
+                ),
+                (
+                    1,
+                    &targets.bloom[0].1,
+                    &resources.black_view,
+                    &resources.black_view,
+                ),
+                (
+                    2,
+                    &targets.bloom[1].1,
+                    &resources.black_view,
+                    &resources.black_view,
+                ),
+                (0, &targets.color.1, &targets.bloom[0].1, lut_view),
+                (
+                    0,
+                    &targets.ldr.1,
+                    &resources.black_view,
+                    &resources.black_view,
+                ),
+            ];
+            resources.bind_groups = Some(sources.map(|(ubo, source, bloom, lut)| {
+                device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: Some("viewport_post_bind_group"),
                     layout: &resources.bind_group_layout,
                     entries: &[
                         wgpu::BindGroupEntry {
                             binding: 0,
-                            resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
-                                buffer: &resources.uniform_buffer,
-                                offset: 0,
-                                size: None,
-                            }),
+                            resource: resources.uniform_buffers[ubo].as_entire_binding(),
                         },
                         wgpu::BindGroupEntry {
                             binding: 1,
-                            resource: wgpu::BindingResource::Sampler(tex_sampler),
+                            resource: wgpu::BindingResource::Sampler(&resources.sampler),
                         },
                         wgpu::BindGroupEntry {
                             binding: 2,
-                            resource: wgpu::BindingResource::TextureView(tex_view),
+                            resource: wgpu::BindingResource::TextureView(source),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 3,
+                            resource: wgpu::BindingResource::TextureView(bloom),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 4,
+                            resource: wgpu::BindingResource::TextureView(lut),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 5,
+                            resource: resources.exposure_buffer.as_entire_binding(),
+                        },
+                    ],
+                })
+            }));
+        }
+        if resources.exposure_bind_group.is_none() {
+            resources.exposure_bind_group =
+                Some(device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: Some("viewport_exposure_bind_group"),
+                    layout: &resources.exposure_bind_group_layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: resources.uniform_buffers[0].as_entire_binding(),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: wgpu::BindingResource::TextureView(&targets.color.1),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 2,
+                            resource: resources.exposure_buffer.as_entire_binding(),
                         },
                     ],
                 }));
         }
+        let bloom = post
+            .chain
+            .iter()
+            .any(|pass| matches!(pass, PostProcessPass::Bloom { .. }));
+        let auto_exposure = post
+            .chain
+            .iter()
+            .any(|pass| matches!(pass, PostProcessPass::AutoExposure { .. }));
+        let fxaa = post.anti_aliasing == AntiAliasing::Fxaa;
+        resources.fxaa = fxaa;
+        drop(post);
 
-        // Upload de mesh (chunked)
-        let mut budget = GPU_UPLOAD_BUDGET_BYTES;
-        while let Some(mut pending) = resources.pending_mesh_upload.take() {
-            upload_pending_mesh_chunk(
-                queue,
-                &mut pending,
-                &resources.staged_vertices,
-                &resources.staged_normals,
-                &resources.staged_uvs,
-                &resources.staged_triangles,
-                &mut budget,
+        let (Some(resources), Some(sky)) = (
+            view_resources::<PostResources>(callback_resources, self.view),
+            view_resources::<SkyResources>(callback_resources, self.view),
+        ) else {
+            return command_buffers;
+        };
+        let (Some(targets), Some(bind_groups), Some(exposure_bind_group)) = (
+            &resources.targets,
+            &resources.bind_groups,
+            &resources.exposure_bind_group,
+        ) else {
+            return command_buffers;
+        };
+        let scene = self
+            .scene
+            .as_ref()
+            .and_then(|_| view_resources::<GpuResources>(callback_resources, self.view));
+        let water = resources
+            .water
+            .as_ref()
+            .filter(|water| !water.surfaces.is_empty() && scene.is_some());
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("viewport_post_encoder"),
+        });
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("viewport_post_scene_pass"),
+                color_attachments: &[Some(match &targets.msaa_color {
+                    Some((_, msaa_view)) => wgpu::RenderPassColorAttachment {
+                        view: msaa_view,
+                        depth_slice: None,
+                        resolve_target: Some(&targets.color.1),
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                            store: wgpu::StoreOp::Discard,
+                        },
+                    },
+                    None => wgpu::RenderPassColorAttachment {
+                        view: &targets.color.1,
+                        depth_slice: None,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                            store: wgpu::StoreOp::Store,
+                        },
+                    },
+                })],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &targets.depth.1,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(1.0),
+                        // A água compara a própria profundidade com a da cena
+                        store: if water.is_some() {
+                            wgpu::StoreOp::Store
+                        } else {
+                            wgpu::StoreOp::Discard
+                        },
+                    }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            sky.draw(&mut pass, Some(targets.sample_count), &self.counters);
+            if let Some(scene) = scene {
+                scene.draw(&mut pass, Some(targets.sample_count), &self.counters);
+            }
+        }
+        if let Some(water) = water {
+            encode_water(device, &mut encoder, water, targets, sky, &self.counters);
+        }
+        if auto_exposure {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("viewport_exposure_pass"),
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(&resources.exposure_pipeline);
+            pass.set_bind_group(0, exposure_bind_group, &[]);
+            pass.dispatch_workgroups(1, 1, 1);
+        }
+        if bloom {
+            let passes = [
+                ("viewport_post_bright_pass", 0, &resources.bright_pipeline),
+                ("viewport_post_blur_h_pass", 1, &resources.blur_pipeline),
+                ("viewport_post_blur_v_pass", 0, &resources.blur_pipeline),
+            ];
+            for (stage, (label, target, pipeline)) in passes.into_iter().enumerate() {
+                post_fullscreen_pass(
+                    &mut encoder,
+                    label,
+                    &targets.bloom[target].1,
+                    pipeline,
+                    &bind_groups[stage],
+                );
+                self.counters.record_draw(1);
+            }
+        }
+        if fxaa {
+            post_fullscreen_pass(
+                &mut encoder,
+                "viewport_post_composite_pass",
+                &targets.ldr.1,
+                &resources.composite_ldr_pipeline,
+                &bind_groups[3],
             );
-            let done = pending.vertex_cursor >= pending.vertex_len
-                && pending.tri_cursor >= pending.tri_len;
-            if done {
-                resources.vertex_buffer = Some(pending.vertex_buffer);
-                resources.index_buffer = Some(pending.index_buffer);
-                resources.index_count = if pending.vertex_len > 0 {
-                    pending.index_count
-                } else {
-                    0
-                };
-                resources.uploaded_mesh_id = pending.mesh_id;
-                resources.staged_vertices.clear();
-                resources.staged_normals.clear();
-                resources.staged_uvs.clear();
-                resources.staged_triangles.clear();
-                break;
+            self.counters.record_draw(1);
+        }
+        let capture = capture
+            .map(CaptureKind::Still)
+            .or(video_frame.map(CaptureKind::Video))
+            .map(|kind| {
+                encode_capture(
+                    device,
+                    &mut encoder,
+                    resources,
+                    targets,
+                    bind_groups,
+                    self.target_format,
+                    kind,
+                )
+            });
+        command_buffers.push(encoder.finish());
+        match capture {
+            Some(Ok(readback)) => {
+                if let Some(resources) =
+                    view_resources_mut::<PostResources>(callback_resources, self.view)
+                {
+                    match readback.kind {
+                        CaptureKind::Still(_) => resources.capture = Some(readback),
+                        CaptureKind::Video(_) => resources.video_frames.push(readback),
+                    }
+                }
             }
-            resources.pending_mesh_upload = Some(pending);
-            if budget == 0 {
-                break;
+            Some(Err((CaptureKind::Still(request), error))) => {
+                finish_capture(&self.post, &request, Err(error));
             }
+            Some(Err((CaptureKind::Video(_), error))) => {
+                // Sem como ler os frames: encerra a gravação com o erro
+                let mut post = self.post.lock().expect("post lock");
+                let recording = post.recording.take();
+                post.capture_results.push(Err(error));
+                drop(post);
+                if let Some(recording) = recording {
+                    finish_recording(&self.post, recording);
+                }
+            }
+            None => {}
         }
-
-        Vec::new()
+        command_buffers
     }
 
     fn paint(
@@ -732,35 +6444,39 @@ impl egui_wgpu::CallbackTrait for Draw3dCallback {
         render_pass: &mut wgpu::RenderPass<'static>,
         callback_resources: &egui_wgpu::CallbackResources,
     ) {
-        let Some(resources) = callback_resources.get::<GpuResources>() else {
+        engine_core::profile_function!(engine_core::ProfileCategory::Render);
+        let Some(resources) = view_resources::<PostResources>(callback_resources, self.view) else {
             return;
         };
-        let vb = &resources.vertex_buffer;
-        let ib = &resources.index_buffer;
-        let bind_group = &resources.current_bind_group;
-        let (Some(vb), Some(ib), Some(bind_group)) = (vb, ib, bind_group) else {
+        let Some(bind_groups) = &resources.bind_groups else {
             return;
         };
-        if resources.index_count == 0 {
-            return;
-        }
 
-        // Viewport e scissor na rect do callback
         let ppp = info.pixels_per_point;
         let v = &info.viewport;
         let x = v.min.x * ppp;
         let y = v.min.y * ppp;
         let w = (v.width() * ppp).max(1.0) as u32;
         let h = (v.height() * ppp).max(1.0) as u32;
-        if w > 0 && h > 0 {
-            render_pass.set_viewport(x, y, w as f32, h as f32, 0.0, 1.0);
-            render_pass.set_scissor_rect(x as u32, y as u32, w.max(1), h.max(1));
+        render_pass.set_viewport(x, y, w as f32, h as f32, 0.0, 1.0);
+        render_pass.set_scissor_rect(x as u32, y as u32, w, h);
+
+        if resources.fxaa {
+            render_pass.set_bind_group(0, &bind_groups[4], &[]);
+            render_pass.set_pipeline(&resources.fxaa_pipeline);
+        } else {
+            render_pass.set_bind_group(0, &bind_groups[3], &[]);
+            render_pass.set_pipeline(&resources.composite_pipeline);
         }
+        render_pass.draw(0..3, 0..1);
+        self.counters.record_draw(1);
 
-        render_pass.set_bind_group(0, bind_group, &[]);
-        render_pass.set_pipeline(&resources.solid_pipeline);
-        render_pass.set_vertex_buffer(0, vb.slice(..));
-        render_pass.set_index_buffer(ib.slice(..), wgpu::IndexFormat::Uint32);
-        render_pass.draw_indexed(0..resources.index_count, 0, 0..1);
+        // O contorno e o texto da UI ficam fora do pós-processamento
+        if let Some(scene) = view_resources::<GpuResources>(callback_resources, self.view)
+            .filter(|_| self.scene.is_some())
+        {
+            scene.draw_outline(render_pass, &self.counters);
+            scene.draw_ui(render_pass, &self.counters);
+        }
     }
 }